@@ -0,0 +1,101 @@
+//! Chunked uploads for `lpaste new` payloads larger than one request body.
+
+use super::compression::with_bytes_body;
+use super::{api_url, error_message_for_response};
+use serde_json::Value;
+
+/// File payloads above this size are uploaded through the chunk endpoints.
+pub(crate) const CHUNKED_UPLOAD_THRESHOLD_BYTES: usize = 32 * 1024 * 1024;
+/// Raw bytes per append request, before gzip.
+const CHUNK_BYTES: usize = 8 * 1024 * 1024;
+/// Attempts per chunk before giving up; each retry resumes from server state.
+const CHUNK_ATTEMPTS: usize = 3;
+
+async fn send_json(request: reqwest::RequestBuilder) -> Result<Value, String> {
+    let res = super::retry::send_with_retry(request)
+        .await
+        .map_err(|err| err.to_string())?;
+    let status = res.status();
+    let body = res.text().await.map_err(|err| err.to_string())?;
+    if !status.is_success() {
+        return Err(format!(
+            "{} ({})",
+            error_message_for_response(status, &body),
+            status
+        ));
+    }
+    serde_json::from_str(&body).map_err(|err| format!("invalid response: {}", err))
+}
+
+fn received_bytes(status: &Value) -> Result<usize, String> {
+    status
+        .get("received_bytes")
+        .and_then(Value::as_u64)
+        .map(|value| value as usize)
+        .ok_or_else(|| "upload status missing 'received_bytes'".to_string())
+}
+
+/// Replace a paste's content through begin/append/commit chunk requests.
+///
+/// Failed appends re-read the session status and resume from the server's
+/// `received_bytes`, so transient errors do not resend the whole payload.
+///
+/// # Arguments
+/// - `client`: HTTP client used for all requests.
+/// - `server`: Normalized server base URL.
+/// - `paste_id`: Existing paste whose content is replaced on commit.
+/// - `content`: Full payload bytes.
+///
+/// # Returns
+/// The committed paste JSON.
+///
+/// # Errors
+/// Returns a message when any request fails after retries.
+pub(crate) async fn upload_in_chunks(
+    client: &reqwest::Client,
+    server: &str,
+    paste_id: &str,
+    content: &[u8],
+) -> Result<Value, String> {
+    let begin_url = api_url(server, &["api", "paste", paste_id, "chunks"])?;
+    let session = send_json(client.post(begin_url)).await?;
+    let upload_id = session
+        .get("upload_id")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "upload session missing 'upload_id'".to_string())?
+        .to_string();
+    let session_url = api_url(server, &["api", "paste", paste_id, "chunks", &upload_id])?;
+
+    let mut offset = 0;
+    let mut attempts = 0;
+    while offset < content.len() {
+        let end = (offset + CHUNK_BYTES).min(content.len());
+        // Offsets count decompressed bytes, so resuming is unaffected.
+        let request = with_bytes_body(
+            client
+                .post(session_url.clone())
+                .query(&[("offset", offset)]),
+            &content[offset..end],
+        );
+        match send_json(request).await {
+            Ok(status) => {
+                offset = received_bytes(&status)?;
+                attempts = 0;
+            }
+            Err(err) => {
+                attempts += 1;
+                if attempts >= CHUNK_ATTEMPTS {
+                    return Err(err);
+                }
+                let status = send_json(client.get(session_url.clone())).await?;
+                offset = received_bytes(&status)?;
+            }
+        }
+    }
+
+    let commit_url = api_url(
+        server,
+        &["api", "paste", paste_id, "chunks", &upload_id, "commit"],
+    )?;
+    send_json(client.post(commit_url)).await
+}
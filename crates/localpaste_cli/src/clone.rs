@@ -0,0 +1,82 @@
+//! `lpaste clone`: mirror every paste into a gist-style directory layout.
+
+use super::ServerResolutionSource;
+use super::{api_url_or_exit, ensure_success_or_exit, log_timing, send_or_exit};
+use clap::Args;
+use localpaste_core::gist_layout::{read_gist_layout, write_gist_paste};
+use localpaste_core::models::paste::Paste;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Arguments for `lpaste clone`.
+#[derive(Debug, Args)]
+pub(crate) struct CloneArgs {
+    /// Target directory; created when missing and updated in place when re-run.
+    pub(crate) dir: PathBuf,
+    /// Remove paste directories whose paste no longer exists on the server.
+    #[arg(long)]
+    pub(crate) prune: bool,
+}
+
+/// Removes layout directories for ids missing from `keep`.
+///
+/// # Returns
+/// Number of directories removed.
+///
+/// # Errors
+/// Returns an error when the layout cannot be read or a directory cannot be removed.
+pub(crate) fn prune_gist_layout(
+    dir: &Path,
+    keep: &HashSet<&str>,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut removed = 0;
+    for entry in read_gist_layout(dir)? {
+        if !keep.contains(entry.meta.id.as_str()) {
+            std::fs::remove_dir_all(dir.join(entry.meta.id.as_str()))?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Download every paste and write it into the gist layout under `args.dir`.
+///
+/// # Errors
+/// Returns an error if the response cannot be decoded or files cannot be written.
+pub(crate) async fn run_clone(
+    client: &reqwest::Client,
+    server: &str,
+    source: ServerResolutionSource,
+    args: CloneArgs,
+    timing: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = api_url_or_exit(server, "Clone", &["api", "pastes", "export"]);
+    let request_start = Instant::now();
+    let res = send_or_exit(client.get(endpoint), "Clone", source, server).await;
+    let res = ensure_success_or_exit(res, "Clone").await;
+    let pastes: Vec<Paste> = res.json().await?;
+    log_timing(timing, "clone", request_start.elapsed());
+
+    std::fs::create_dir_all(&args.dir)?;
+    for paste in &pastes {
+        write_gist_paste(&args.dir, paste)?;
+    }
+    let pruned = if args.prune {
+        let keep: HashSet<&str> = pastes.iter().map(|paste| paste.id.as_str()).collect();
+        prune_gist_layout(&args.dir, &keep)?
+    } else {
+        0
+    };
+    eprintln!(
+        "Cloned {} paste(s) into {}{}.",
+        pastes.len(),
+        args.dir.display(),
+        if pruned > 0 {
+            format!(", pruned {}", pruned)
+        } else {
+            String::new()
+        }
+    );
+    Ok(())
+}
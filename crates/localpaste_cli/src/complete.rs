@@ -0,0 +1,288 @@
+//! Shell completion scripts and the hidden `lpaste __complete` protocol.
+//!
+//! Static clap completions cannot know paste ids, so `completions --dynamic`
+//! emits a small script that calls back into `lpaste __complete -- <words>`
+//! on every TAB. The command prints one `value<TAB>description` line per
+//! candidate and stays silent on errors so a stopped server never breaks the
+//! shell.
+
+use super::api_url;
+use clap::{Args, Command, CommandFactory};
+use clap_complete::{generate, Shell};
+use serde_json::Value;
+use std::io;
+
+/// Rows requested from `/api/pastes/meta` (the server caps list limits at 100).
+const COMPLETION_FETCH_LIMIT: usize = 100;
+
+/// Positional argument ids that take a paste id on top-level commands.
+const PASTE_ID_ARGS: &[&str] = &["id", "left_id", "right_id"];
+
+/// Hidden alias of `__complete` that static completion scripts list instead.
+///
+/// clap's bash generator splits subcommand paths on `__` and panics on the
+/// primary name; the alias keeps the listed word a valid command.
+pub(crate) const STATIC_COMPLETE_NAME: &str = "complete-words";
+
+/// `lpaste` command tree as seen by static completion generators.
+pub(crate) fn static_completion_command() -> Command {
+    super::Cli::command().mut_subcommand("__complete", |sub| {
+        sub.name(STATIC_COMPLETE_NAME).alias(None)
+    })
+}
+
+/// Arguments for `lpaste completions`.
+#[derive(Debug, Args)]
+pub(crate) struct CompletionsArgs {
+    /// Shell to generate completions for
+    #[arg(value_enum)]
+    pub(crate) shell: Shell,
+    /// Emit a bash/zsh/fish script that also completes paste ids from the server.
+    #[arg(long)]
+    pub(crate) dynamic: bool,
+}
+
+/// Arguments for the hidden `lpaste __complete` command.
+#[derive(Debug, Args)]
+pub(crate) struct CompleteArgs {
+    /// Words after `lpaste`, ending with the (possibly empty) word being completed.
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    pub(crate) words: Vec<String>,
+}
+
+/// What the word under the cursor should complete to.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum CompletionTarget {
+    /// Fixed candidates (subcommands or flags) as `(value, description)` pairs.
+    Values(Vec<(String, String)>),
+    /// Paste ids or names starting with the given prefix.
+    PasteIds(String),
+}
+
+const BASH_SCRIPT: &str = r#"_lpaste_dynamic() {
+    local IFS=$'\n'
+    COMPREPLY=($(lpaste -t 2 __complete -- "${COMP_WORDS[@]:1:COMP_CWORD}" 2>/dev/null | cut -f1))
+}
+complete -o default -F _lpaste_dynamic lpaste
+"#;
+
+const ZSH_SCRIPT: &str = r#"#compdef lpaste
+_lpaste_dynamic() {
+    local -a candidates
+    candidates=("${(@f)$(lpaste -t 2 __complete -- "${(@)words[2,CURRENT]}" 2>/dev/null)}")
+    candidates=("${(@)candidates//:/\\:}")
+    candidates=("${(@)candidates/$'\t'/:}")
+    if (( ${#candidates} )) && [[ -n "${candidates[1]}" ]]; then
+        _describe 'lpaste' candidates
+    else
+        _files
+    fi
+}
+compdef _lpaste_dynamic lpaste
+"#;
+
+const FISH_SCRIPT: &str = r#"function __lpaste_complete
+    set -l tokens (commandline -opc)
+    lpaste -t 2 __complete -- $tokens[2..-1] (commandline -ct) 2>/dev/null
+end
+complete -c lpaste -f -a '(__lpaste_complete)'
+"#;
+
+/// Print a completion script for `args.shell` to stdout.
+///
+/// Static scripts come from `clap_complete`; dynamic scripts delegate to
+/// `lpaste __complete` and exist for bash, zsh, and fish only.
+pub(crate) fn print_completions(args: CompletionsArgs) {
+    if !args.dynamic {
+        let mut cmd = static_completion_command();
+        let name = cmd.get_name().to_string();
+        generate(args.shell, &mut cmd, name, &mut io::stdout());
+        return;
+    }
+    let script = match args.shell {
+        Shell::Bash => BASH_SCRIPT,
+        Shell::Zsh => ZSH_SCRIPT,
+        Shell::Fish => FISH_SCRIPT,
+        other => {
+            eprintln!(
+                "Dynamic completions support bash, zsh, and fish (not {}).",
+                other
+            );
+            std::process::exit(2);
+        }
+    };
+    print!("{}", script);
+}
+
+fn takes_value(cmd: &Command, root: &Command, flag: &str) -> bool {
+    if flag.contains('=') {
+        return false;
+    }
+    let matches = |arg: &clap::Arg| {
+        if let Some(long) = flag.strip_prefix("--") {
+            arg.get_long() == Some(long)
+        } else {
+            flag.len() == 2
+                && flag.strip_prefix('-').and_then(|s| s.chars().next()) == arg.get_short()
+        }
+    };
+    cmd.get_arguments()
+        .chain(root.get_arguments())
+        .find(|arg| matches(arg))
+        .is_some_and(|arg| arg.get_action().takes_values())
+}
+
+fn visible_subcommands(cmd: &Command, prefix: &str) -> Vec<(String, String)> {
+    cmd.get_subcommands()
+        .filter(|sub| !sub.is_hide_set() && sub.get_name().starts_with(prefix))
+        .map(|sub| {
+            let about = sub.get_about().map(|about| about.to_string());
+            (sub.get_name().to_string(), about.unwrap_or_default())
+        })
+        .collect()
+}
+
+fn visible_flags(cmd: &Command, root: &Command, prefix: &str) -> Vec<(String, String)> {
+    let mut flags: Vec<(String, String)> = cmd
+        .get_arguments()
+        .chain(root.get_arguments().filter(|arg| arg.is_global_set()))
+        .filter(|arg| !arg.is_hide_set())
+        .filter_map(|arg| {
+            let flag = format!("--{}", arg.get_long()?);
+            let help = arg.get_help().map(|help| help.to_string());
+            flag.starts_with(prefix)
+                .then(|| (flag, help.unwrap_or_default()))
+        })
+        .collect();
+    flags.sort();
+    flags.dedup_by(|a, b| a.0 == b.0);
+    flags
+}
+
+/// Work out what the last of `words` should complete to.
+///
+/// # Arguments
+/// - `root`: The `lpaste` clap command.
+/// - `words`: Words after `lpaste`; the last one is the word being completed.
+///
+/// # Returns
+/// Subcommand or flag candidates, or a paste-id lookup for id positionals.
+pub(crate) fn completion_target(root: &Command, words: &[String]) -> CompletionTarget {
+    let (current, done) = match words.split_last() {
+        Some((current, done)) => (current.as_str(), done),
+        None => ("", &[][..]),
+    };
+    let mut cmd = root;
+    let mut depth = 0;
+    let mut positionals = 0;
+    let mut skip_value = false;
+    for word in done {
+        if std::mem::take(&mut skip_value) {
+            continue;
+        }
+        if word.starts_with('-') {
+            skip_value = takes_value(cmd, root, word);
+        } else if cmd.has_subcommands() {
+            let Some(sub) = cmd.find_subcommand(word) else {
+                return CompletionTarget::Values(Vec::new());
+            };
+            cmd = sub;
+            depth += 1;
+        } else {
+            positionals += 1;
+        }
+    }
+    if skip_value {
+        return CompletionTarget::Values(Vec::new());
+    }
+    if current.starts_with('-') {
+        return CompletionTarget::Values(visible_flags(cmd, root, current));
+    }
+    if cmd.has_subcommands() {
+        return CompletionTarget::Values(visible_subcommands(cmd, current));
+    }
+    let is_paste_id = cmd
+        .get_positionals()
+        .nth(positionals)
+        .is_some_and(|arg| PASTE_ID_ARGS.contains(&arg.get_id().as_str()));
+    if depth == 1 && is_paste_id {
+        CompletionTarget::PasteIds(current.to_string())
+    } else {
+        CompletionTarget::Values(Vec::new())
+    }
+}
+
+/// Pick metadata rows whose id or name starts with `prefix`.
+///
+/// Name matches are case-insensitive; each candidate is `(id, name)`.
+pub(crate) fn paste_id_candidates(rows: &[Value], prefix: &str) -> Vec<(String, String)> {
+    let lowered = prefix.to_lowercase();
+    rows.iter()
+        .filter_map(|row| {
+            let id = row.get("id")?.as_str()?;
+            let name = row.get("name").and_then(Value::as_str).unwrap_or_default();
+            (id.starts_with(prefix) || name.to_lowercase().starts_with(lowered.as_str()))
+                .then(|| (id.to_string(), name.replace(['\t', '\n'], " ")))
+        })
+        .collect()
+}
+
+async fn fetch_rows(client: &reqwest::Client, server: &str, path: &[&str]) -> Option<Vec<Value>> {
+    let mut endpoint = api_url(server, path).ok()?;
+    endpoint
+        .query_pairs_mut()
+        .append_pair("limit", COMPLETION_FETCH_LIMIT.to_string().as_str());
+    let res = client.get(endpoint).send().await.ok()?;
+    if !res.status().is_success() {
+        return None;
+    }
+    res.json().await.ok()
+}
+
+/// Recently opened pastes first, when the server keeps recent history, then
+/// the most recently updated ones.
+async fn fetch_paste_rows(client: &reqwest::Client, server: &str) -> Option<Vec<Value>> {
+    let recent = fetch_rows(client, server, &["api", "recent"]).await;
+    let updated = fetch_rows(client, server, &["api", "pastes", "meta"]).await;
+    if recent.is_none() && updated.is_none() {
+        return None;
+    }
+    Some(recent_first(
+        recent.unwrap_or_default(),
+        updated.unwrap_or_default(),
+    ))
+}
+
+/// Append `updated` rows to `recent`, skipping ids already listed.
+pub(crate) fn recent_first(mut recent: Vec<Value>, updated: Vec<Value>) -> Vec<Value> {
+    let seen: std::collections::HashSet<String> = recent
+        .iter()
+        .filter_map(|row| row.get("id")?.as_str().map(str::to_string))
+        .collect();
+    recent.extend(updated.into_iter().filter(|row| {
+        row.get("id")
+            .and_then(Value::as_str)
+            .is_none_or(|id| !seen.contains(id))
+    }));
+    recent
+}
+
+/// Print completion candidates for `args.words`, one `value<TAB>description` per line.
+///
+/// Server failures produce no output rather than an error.
+pub(crate) async fn run_complete(client: &reqwest::Client, server: &str, args: CompleteArgs) {
+    let candidates = match completion_target(&super::Cli::command(), &args.words) {
+        CompletionTarget::Values(values) => values,
+        CompletionTarget::PasteIds(prefix) => fetch_paste_rows(client, server)
+            .await
+            .map(|rows| paste_id_candidates(&rows, prefix.as_str()))
+            .unwrap_or_default(),
+    };
+    for (value, description) in candidates {
+        if description.is_empty() {
+            println!("{}", value);
+        } else {
+            println!("{}\t{}", value, description);
+        }
+    }
+}
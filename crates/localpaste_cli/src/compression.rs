@@ -0,0 +1,59 @@
+//! Gzip request bodies for large paste uploads.
+
+use flate2::{write::GzEncoder, Compression};
+use serde_json::Value;
+use std::io::Write;
+
+/// Serialized JSON bodies above this size are sent gzip-compressed.
+pub(crate) const COMPRESS_BODY_THRESHOLD_BYTES: usize = 64 * 1024;
+
+/// Serialize `body`, gzip-compressing it when it exceeds the threshold.
+///
+/// # Returns
+/// The encoded bytes and whether they are gzip-compressed. Compression
+/// failures fall back to the plain JSON bytes.
+pub(crate) fn encode_json_body(body: &Value) -> (Vec<u8>, bool) {
+    let raw = body.to_string().into_bytes();
+    if raw.len() <= COMPRESS_BODY_THRESHOLD_BYTES {
+        return (raw, false);
+    }
+    match gzip(raw.as_slice()) {
+        Some(compressed) => (compressed, true),
+        None => (raw, false),
+    }
+}
+
+fn gzip(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+    encoder.write_all(bytes).and_then(|_| encoder.finish()).ok()
+}
+
+/// Attach raw `bytes`, gzip-compressed with `Content-Encoding: gzip` unless
+/// compression fails.
+pub(crate) fn with_bytes_body(
+    request: reqwest::RequestBuilder,
+    bytes: &[u8],
+) -> reqwest::RequestBuilder {
+    match gzip(bytes) {
+        Some(compressed) => request
+            .header(reqwest::header::CONTENT_ENCODING, "gzip")
+            .body(compressed),
+        None => request.body(bytes.to_vec()),
+    }
+}
+
+/// Attach `body` as JSON, gzip-compressed with `Content-Encoding: gzip` above
+/// [`COMPRESS_BODY_THRESHOLD_BYTES`].
+pub(crate) fn with_json_body(
+    request: reqwest::RequestBuilder,
+    body: &Value,
+) -> reqwest::RequestBuilder {
+    let (bytes, compressed) = encode_json_body(body);
+    let request = request.header(reqwest::header::CONTENT_TYPE, "application/json");
+    let request = if compressed {
+        request.header(reqwest::header::CONTENT_ENCODING, "gzip")
+    } else {
+        request
+    };
+    request.body(bytes)
+}
@@ -0,0 +1,67 @@
+//! `lpaste count`: count pastes without listing them.
+
+use super::ServerResolutionSource;
+use super::{api_url_or_exit, ensure_success_or_exit, log_timing, send_or_exit};
+use clap::Args;
+use serde_json::Value;
+use std::time::Instant;
+
+/// Arguments for `lpaste count`.
+#[derive(Debug, Args)]
+pub(crate) struct CountArgs {
+    /// Only count pastes filed directly in this folder id.
+    #[arg(long)]
+    pub(crate) folder: Option<String>,
+    /// Only count pastes carrying this tag (case-insensitive).
+    #[arg(long)]
+    pub(crate) tag: Option<String>,
+    /// Only count pastes with this language (aliases such as `sh` match).
+    #[arg(long)]
+    pub(crate) language: Option<String>,
+}
+
+impl CountArgs {
+    /// Query pairs sent to `GET /api/pastes/count`.
+    pub(crate) fn query(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("folder_id", self.folder.as_deref()),
+            ("tag", self.tag.as_deref()),
+            ("language", self.language.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|value| (key, value)))
+        .collect()
+    }
+}
+
+/// Print the number of pastes matching the filters.
+///
+/// # Errors
+/// Returns an error if the response cannot be decoded or printed.
+pub(crate) async fn run_count(
+    client: &reqwest::Client,
+    server: &str,
+    source: ServerResolutionSource,
+    args: CountArgs,
+    json: bool,
+    timing: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = api_url_or_exit(server, "Count", &["api", "pastes", "count"]);
+    let request_start = Instant::now();
+    let res = send_or_exit(
+        client.get(endpoint).query(&args.query()),
+        "Count",
+        source,
+        server,
+    )
+    .await;
+    let res = ensure_success_or_exit(res, "Count").await;
+    let body: Value = res.json().await?;
+    log_timing(timing, "count", request_start.elapsed());
+    if json {
+        println!("{}", serde_json::to_string_pretty(&body)?);
+    } else {
+        println!("{}", body.get("count").and_then(Value::as_u64).unwrap_or(0));
+    }
+    Ok(())
+}
@@ -0,0 +1,67 @@
+//! `lpaste digest`: store a markdown summary of recent activity.
+
+use super::ServerResolutionSource;
+use super::{api_url_or_exit, ensure_success_or_exit, log_timing, send_or_exit};
+use clap::Args;
+use localpaste_core::digest::DigestPeriod;
+use serde_json::{json, Value};
+use std::time::Instant;
+
+/// Arguments for `lpaste digest`.
+#[derive(Debug, Args)]
+pub(crate) struct DigestArgs {
+    /// Summarize the last 7 days instead of the last 24 hours.
+    #[arg(long)]
+    pub(crate) week: bool,
+}
+
+impl DigestArgs {
+    /// Window requested from `POST /api/digest`.
+    pub(crate) fn period(&self) -> DigestPeriod {
+        if self.week {
+            DigestPeriod::Week
+        } else {
+            DigestPeriod::Day
+        }
+    }
+}
+
+/// Generate a digest paste in the `Digests` folder and print its id.
+///
+/// Suitable for cron or a scheduled task, e.g. `lpaste digest --week`.
+///
+/// # Errors
+/// Returns an error if the response cannot be decoded or printed.
+pub(crate) async fn run_digest(
+    client: &reqwest::Client,
+    server: &str,
+    source: ServerResolutionSource,
+    args: DigestArgs,
+    json: bool,
+    timing: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = api_url_or_exit(server, "Digest", &["api", "digest"]);
+    let request_start = Instant::now();
+    let res = send_or_exit(
+        client
+            .post(endpoint)
+            .json(&json!({ "period": args.period() })),
+        "Digest",
+        source,
+        server,
+    )
+    .await;
+    let res = ensure_success_or_exit(res, "Digest").await;
+    let digest: Value = res.json().await?;
+    log_timing(timing, "digest", request_start.elapsed());
+    if json {
+        println!("{}", serde_json::to_string_pretty(&digest)?);
+    } else {
+        println!(
+            "Created: {} ({})",
+            digest.get("name").and_then(Value::as_str).unwrap_or(""),
+            digest.get("id").and_then(Value::as_str).unwrap_or("?")
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//! `lpaste export`: download a rendered, syntax-highlighted HTML or PDF document.
+
+use super::ServerResolutionSource;
+use super::{api_url_or_exit, ensure_success_or_exit, log_timing, send_or_exit};
+use clap::{Args, ValueEnum};
+use std::io::{self, IsTerminal, Write};
+use std::time::Instant;
+
+/// Rendered document formats accepted by `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum ExportFormatArg {
+    Html,
+    Pdf,
+}
+
+impl ExportFormatArg {
+    fn as_query(self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Pdf => "pdf",
+        }
+    }
+}
+
+/// Arguments for `lpaste export`.
+#[derive(Debug, Args)]
+pub(crate) struct ExportArgs {
+    /// Paste id or unique prefix to export.
+    pub(crate) id: String,
+    /// Document format to render.
+    #[arg(short, long, value_enum, default_value = "html")]
+    pub(crate) format: ExportFormatArg,
+    /// Write the document to this file instead of stdout.
+    #[arg(short, long)]
+    pub(crate) output: Option<String>,
+}
+
+/// Fetch a rendered export and write it to the requested destination.
+///
+/// PDF output is refused when stdout is a terminal and no `--output` is given.
+///
+/// # Errors
+/// Returns an error if the response body cannot be read or written.
+pub(crate) async fn run_export(
+    client: &reqwest::Client,
+    server: &str,
+    source: ServerResolutionSource,
+    args: ExportArgs,
+    timing: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if args.output.is_none() && args.format == ExportFormatArg::Pdf && io::stdout().is_terminal() {
+        eprintln!("Export failed: refusing to write PDF to a terminal; pass --output <path>.");
+        std::process::exit(1);
+    }
+    let endpoint = api_url_or_exit(
+        server,
+        "Export",
+        &["api", "paste", args.id.as_str(), "export"],
+    );
+    let request_start = Instant::now();
+    let res = send_or_exit(
+        client
+            .get(endpoint)
+            .query(&[("format", args.format.as_query())]),
+        "Export",
+        source,
+        server,
+    )
+    .await;
+    let res = ensure_success_or_exit(res, "Export").await;
+    let body = res.bytes().await?;
+    log_timing(timing, "export", request_start.elapsed());
+    match args.output {
+        Some(path) => {
+            std::fs::write(&path, &body)?;
+            eprintln!("Exported {} to {} ({} bytes).", args.id, path, body.len());
+        }
+        None => {
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(&body)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,193 @@
+//! `lpaste folder`: folder-level operations.
+
+use super::ServerResolutionSource;
+use super::{api_url_or_exit, ensure_success_or_exit, log_timing, send_or_exit};
+use clap::{Args, Subcommand, ValueEnum};
+use reqwest::header::CONTENT_DISPOSITION;
+use serde_json::Value;
+use std::path::Path;
+use std::time::Instant;
+
+/// Arguments for `lpaste folder`.
+#[derive(Debug, Args)]
+pub(crate) struct FolderArgs {
+    #[command(subcommand)]
+    pub(crate) command: FolderCommand,
+}
+
+/// Sort keys for `lpaste folder list`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum FolderSortArg {
+    Name,
+    CreatedAt,
+    PasteCount,
+}
+
+impl FolderSortArg {
+    fn as_query(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::CreatedAt => "created_at",
+            Self::PasteCount => "paste_count",
+        }
+    }
+}
+
+/// Folder subcommands.
+#[derive(Debug, Subcommand)]
+pub(crate) enum FolderCommand {
+    /// List folders with paste counts.
+    List {
+        /// Only list direct children of this folder id; `""` lists top-level folders.
+        #[arg(long)]
+        parent: Option<String>,
+        /// Sort key; folders are listed by name when omitted.
+        #[arg(long, value_enum)]
+        sort: Option<FolderSortArg>,
+        /// Sort descending.
+        #[arg(long, requires = "sort")]
+        desc: bool,
+        /// Show paste counts including all subfolders.
+        #[arg(long)]
+        recursive: bool,
+    },
+    /// Show a folder's direct and recursive paste counts.
+    Count {
+        /// Folder id to count.
+        id: String,
+    },
+    /// Download a folder and its subfolders as a ZIP archive with a manifest.
+    Export {
+        /// Folder id to export.
+        id: String,
+        /// Archive path; defaults to the server-suggested name in the current directory.
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+}
+
+/// Extracts a bare file name from an `attachment; filename="..."` header value.
+pub(crate) fn attachment_filename(disposition: &str) -> Option<String> {
+    let (_, rest) = disposition.split_once("filename=\"")?;
+    let (name, _) = rest.split_once('"')?;
+    let name = Path::new(name).file_name()?.to_str()?;
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Formats one `folder list` row as `id  name (count)`.
+pub(crate) fn format_folder_row(folder: &Value) -> String {
+    let count = folder
+        .get("recursive_paste_count")
+        .or_else(|| folder.get("paste_count"))
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    format!(
+        "{}  {} ({})",
+        folder.get("id").and_then(Value::as_str).unwrap_or("?"),
+        folder.get("name").and_then(Value::as_str).unwrap_or(""),
+        count
+    )
+}
+
+/// Formats the `folder count` line from a folder count response.
+pub(crate) fn format_folder_counts(counts: &Value) -> String {
+    let count = |key| counts.get(key).and_then(Value::as_u64).unwrap_or(0);
+    format!(
+        "{} direct, {} including subfolders",
+        count("paste_count"),
+        count("recursive_paste_count")
+    )
+}
+
+/// Run a `lpaste folder` subcommand.
+///
+/// # Errors
+/// Returns an error if the response body cannot be read or written.
+pub(crate) async fn run_folder(
+    client: &reqwest::Client,
+    server: &str,
+    source: ServerResolutionSource,
+    args: FolderArgs,
+    json: bool,
+    timing: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match args.command {
+        FolderCommand::List {
+            parent,
+            sort,
+            desc,
+            recursive,
+        } => {
+            let endpoint = api_url_or_exit(server, "Folder list", &["api", "folders"]);
+            let mut query: Vec<(&str, String)> = Vec::new();
+            if let Some(parent) = parent {
+                query.push(("parent_id", parent));
+            }
+            if let Some(sort) = sort {
+                query.push(("sort", sort.as_query().to_string()));
+                query.push(("order", if desc { "desc" } else { "asc" }.to_string()));
+            }
+            if recursive {
+                query.push(("recursive_counts", "true".to_string()));
+            }
+            let request_start = Instant::now();
+            let res = send_or_exit(
+                client.get(endpoint).query(&query),
+                "Folder list",
+                source,
+                server,
+            )
+            .await;
+            let res = ensure_success_or_exit(res, "Folder list").await;
+            let folders: Vec<Value> = res.json().await?;
+            log_timing(timing, "folder-list", request_start.elapsed());
+            if json {
+                println!("{}", serde_json::to_string_pretty(&folders)?);
+            } else {
+                for folder in &folders {
+                    println!("{}", format_folder_row(folder));
+                }
+            }
+        }
+        FolderCommand::Count { id } => {
+            let endpoint = api_url_or_exit(
+                server,
+                "Folder count",
+                &["api", "folder", id.as_str(), "count"],
+            );
+            let request_start = Instant::now();
+            let res = send_or_exit(client.get(endpoint), "Folder count", source, server).await;
+            let res = ensure_success_or_exit(res, "Folder count").await;
+            let counts: Value = res.json().await?;
+            log_timing(timing, "folder-count", request_start.elapsed());
+            if json {
+                println!("{}", serde_json::to_string_pretty(&counts)?);
+            } else {
+                println!("{}", format_folder_counts(&counts));
+            }
+        }
+        FolderCommand::Export { id, output } => {
+            let endpoint = api_url_or_exit(
+                server,
+                "Folder export",
+                &["api", "folder", id.as_str(), "export"],
+            );
+            let request_start = Instant::now();
+            let res = send_or_exit(client.get(endpoint), "Folder export", source, server).await;
+            let res = ensure_success_or_exit(res, "Folder export").await;
+            let suggested = res
+                .headers()
+                .get(CONTENT_DISPOSITION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(attachment_filename);
+            let body = res.bytes().await?;
+            log_timing(timing, "folder-export", request_start.elapsed());
+            let path = output
+                .or(suggested)
+                .unwrap_or_else(|| format!("folder-{}.zip", id));
+            std::fs::write(&path, &body)?;
+            eprintln!("Exported folder {} to {} ({} bytes).", id, path, body.len());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,152 @@
+//! `lpaste generate-docs`: man pages and completion scripts for packagers.
+//!
+//! Distro packages (AUR, Homebrew, ...) install these next to the binaries,
+//! so the output layout is fixed: `man/` holds roff pages for `lpaste`, each
+//! visible subcommand, and the `localpaste` server; `completions/` holds the
+//! static clap script for every supported shell.
+
+use clap::{Arg, ArgAction, Args, Command, CommandFactory, ValueEnum};
+use clap_complete::Shell;
+use localpaste_core::DEFAULT_PORT;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Arguments for `lpaste generate-docs`.
+#[derive(Debug, Args)]
+pub(crate) struct GenerateDocsArgs {
+    /// Directory to write `man/` and `completions/` into (created if missing).
+    pub(crate) out_dir: PathBuf,
+}
+
+/// Files written by one [`generate_docs`] run, relative to the output directory.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct GeneratedDocs {
+    pub(crate) man_pages: Vec<PathBuf>,
+    pub(crate) completions: Vec<PathBuf>,
+}
+
+/// Describes the `localpaste` server binary for its man page.
+///
+/// The server parses its flags by hand (see `print_help` in
+/// `localpaste_server`'s `main.rs`); keep the two in sync.
+pub(crate) fn server_command() -> Command {
+    let path_flag = |name: &'static str, help: &'static str| {
+        Arg::new(name)
+            .long(name)
+            .value_name("PATH")
+            .value_parser(clap::value_parser!(PathBuf))
+            .help(help)
+    };
+    Command::new("localpaste")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("LocalPaste server")
+        .long_about(
+            "Headless LocalPaste API server. Without maintenance flags it serves \
+             the HTTP API until interrupted; configuration comes from environment \
+             variables.",
+        )
+        .disable_help_flag(true)
+        .disable_version_flag(true)
+        .arg(
+            Arg::new("backup")
+                .long("backup")
+                .action(ArgAction::SetTrue)
+                .help("Create a backup of the database, with a BLAKE3 checksum manifest"),
+        )
+        .arg(
+            Arg::new("cleanup")
+                .long("cleanup")
+                .action(ArgAction::SetTrue)
+                .help("Remove orphaned rows and compact the database, then exit"),
+        )
+        .arg(path_flag(
+            "verify-backup",
+            "Check a backup against its checksum manifest, then exit",
+        ))
+        .arg(path_flag(
+            "restore-backup",
+            "Verify a backup and replace the database with it, then exit",
+        ))
+        .arg(
+            Arg::new("build-info")
+                .long("build-info")
+                .action(ArgAction::SetTrue)
+                .help("Print version, commit, build date, features, and default paths"),
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .action(ArgAction::Help)
+                .help("Show the help message, including environment variables"),
+        )
+        .after_help(format!(
+            "Common environment variables: DB_PATH (database directory), PORT \
+             (default: {port}), BIND (e.g. 0.0.0.0:{port}; non-loopback needs \
+             ALLOW_PUBLIC_ACCESS=1), MAX_PASTE_SIZE (bytes), AUTO_BACKUP, \
+             LOCALPASTE_BACKUP_PASSPHRASE. Run `localpaste --help` for the full \
+             list. --cleanup and --restore-backup rewrite data.redb, so stop the \
+             server first.",
+            port = DEFAULT_PORT
+        ))
+}
+
+/// Write man pages and completion scripts for `lpaste` and `localpaste`.
+///
+/// # Returns
+/// The generated file paths, relative to `out_dir`.
+///
+/// # Errors
+/// Returns an error when a directory or file cannot be written.
+pub(crate) fn generate_docs(out_dir: &Path) -> io::Result<GeneratedDocs> {
+    let man_dir = out_dir.join("man");
+    let completions_dir = out_dir.join("completions");
+    std::fs::create_dir_all(&man_dir)?;
+    std::fs::create_dir_all(&completions_dir)?;
+
+    let mut generated = GeneratedDocs::default();
+    for cmd in [super::Cli::command(), server_command()] {
+        // Building assigns `lpaste-<sub>` display names, which become the
+        // per-subcommand page names.
+        let mut cmd = cmd.disable_help_subcommand(true);
+        cmd.build();
+        write_man_pages(&cmd, &man_dir, &mut generated.man_pages)?;
+    }
+
+    for shell in Shell::value_variants() {
+        let mut cmd = super::complete::static_completion_command();
+        let path = clap_complete::generate_to(*shell, &mut cmd, "lpaste", &completions_dir)?;
+        generated
+            .completions
+            .push(Path::new("completions").join(path.file_name().unwrap_or_default()));
+    }
+    Ok(generated)
+}
+
+/// Render `cmd` and each visible subcommand as `<display-name>.1`.
+fn write_man_pages(cmd: &Command, man_dir: &Path, written: &mut Vec<PathBuf>) -> io::Result<()> {
+    let path = clap_mangen::Man::new(cmd.clone()).generate_to(man_dir)?;
+    written.push(Path::new("man").join(path.file_name().unwrap_or_default()));
+    for sub in cmd.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        write_man_pages(sub, man_dir, written)?;
+    }
+    Ok(())
+}
+
+/// Run `lpaste generate-docs`, listing what was written.
+pub(crate) fn run_generate_docs(args: GenerateDocsArgs) {
+    match generate_docs(&args.out_dir) {
+        Ok(generated) => {
+            for path in generated.man_pages.iter().chain(&generated.completions) {
+                println!("{}", args.out_dir.join(path).display());
+            }
+        }
+        Err(err) => {
+            eprintln!(
+                "Error: writing docs to {} failed: {}",
+                args.out_dir.display(),
+                err
+            );
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! `lpaste import`: create pastes from other snippet tools' exports.
+
+use super::compression::with_json_body;
+use super::ServerResolutionSource;
+use super::{
+    api_url_or_exit, error_message_for_response, log_timing, paste_id_and_name, send_or_exit,
+};
+use clap::{Args, ValueEnum};
+use localpaste_core::import::{parse_import, ImportFormat, ImportedSnippet};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Request header that applies the server's import size limit to each snippet.
+const IMPORT_SOURCE_HEADER: &str = "x-localpaste-source";
+
+/// Import sources accepted by `lpaste import`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum ImportFormatArg {
+    /// massCode `db.json` or its storage directory.
+    Masscode,
+    /// Boostnote.next note file or storage directory.
+    Boostnote,
+    /// Markdown file with one fenced code block per snippet.
+    Markdown,
+    /// LocalPaste gist directory written by `lpaste clone`.
+    Gist,
+}
+
+impl From<ImportFormatArg> for ImportFormat {
+    fn from(value: ImportFormatArg) -> Self {
+        match value {
+            ImportFormatArg::Masscode => ImportFormat::MassCode,
+            ImportFormatArg::Boostnote => ImportFormat::Boostnote,
+            ImportFormatArg::Markdown => ImportFormat::Markdown,
+            ImportFormatArg::Gist => ImportFormat::Gist,
+        }
+    }
+}
+
+/// Arguments for `lpaste import`.
+#[derive(Debug, Args)]
+pub(crate) struct ImportArgs {
+    /// Source format.
+    #[arg(value_enum)]
+    pub(crate) format: ImportFormatArg,
+    /// Source file or directory.
+    pub(crate) path: PathBuf,
+    /// List what would be imported without creating pastes.
+    #[arg(long)]
+    pub(crate) dry_run: bool,
+}
+
+fn create_body(snippet: &ImportedSnippet) -> Value {
+    let mut body = serde_json::json!({
+        "content": snippet.content,
+        "name": snippet.name,
+        "tags": snippet.tags,
+    });
+    if let Some(language) = snippet.language.as_deref() {
+        body["language"] = language.into();
+    }
+    body
+}
+
+fn format_preview_line(snippet: &ImportedSnippet) -> String {
+    let mut line = format!(
+        "{}\t{}\t{} bytes",
+        snippet.name,
+        snippet.language.as_deref().unwrap_or("auto"),
+        snippet.content.len()
+    );
+    if !snippet.tags.is_empty() {
+        line.push('\t');
+        line.push_str(snippet.tags.join(",").as_str());
+    }
+    line
+}
+
+/// Parse an import source and create one paste per snippet.
+///
+/// Snippets the server rejects are reported and skipped; the process exits
+/// non-zero when any snippet failed.
+///
+/// # Errors
+/// Returns an error if a response body cannot be read or printed.
+pub(crate) async fn run_import(
+    client: &reqwest::Client,
+    server: &str,
+    source: ServerResolutionSource,
+    args: ImportArgs,
+    json: bool,
+    timing: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let snippets = match parse_import(args.format.into(), &args.path) {
+        Ok(snippets) => snippets,
+        Err(err) => {
+            eprintln!("Import failed: {}", err);
+            std::process::exit(1);
+        }
+    };
+    if snippets.is_empty() {
+        eprintln!("Import: no snippets found in {}.", args.path.display());
+        return Ok(());
+    }
+    if args.dry_run {
+        for snippet in &snippets {
+            println!("{}", format_preview_line(snippet));
+        }
+        eprintln!("Dry run: {} snippet(s) would be imported.", snippets.len());
+        return Ok(());
+    }
+
+    let endpoint = api_url_or_exit(server, "Import", &["api", "paste"]);
+    let request_start = Instant::now();
+    let mut created = Vec::with_capacity(snippets.len());
+    let mut failed = 0usize;
+    for snippet in &snippets {
+        let mut res = None;
+        if let Some(id) = snippet.id.as_deref() {
+            // Layouts that carry ids update the original paste when it still exists.
+            let update = send_or_exit(
+                with_json_body(
+                    client
+                        .put(api_url_or_exit(server, "Import", &["api", "paste", id]))
+                        .header(IMPORT_SOURCE_HEADER, "import"),
+                    &create_body(snippet),
+                ),
+                "Import",
+                source,
+                server,
+            )
+            .await;
+            if update.status() != reqwest::StatusCode::NOT_FOUND {
+                res = Some(update);
+            }
+        }
+        let res = match res {
+            Some(res) => res,
+            None => {
+                send_or_exit(
+                    with_json_body(
+                        client
+                            .post(endpoint.clone())
+                            .header(IMPORT_SOURCE_HEADER, "import"),
+                        &create_body(snippet),
+                    ),
+                    "Import",
+                    source,
+                    server,
+                )
+                .await
+            }
+        };
+        let status = res.status();
+        if !status.is_success() {
+            let body = res.text().await.unwrap_or_default();
+            eprintln!(
+                "Import of '{}' failed ({}): {}",
+                snippet.name,
+                status,
+                error_message_for_response(status, body.as_str())
+            );
+            failed += 1;
+            continue;
+        }
+        let paste: Value = res.json().await?;
+        if !json {
+            if let Some((id, name)) = paste_id_and_name(&paste) {
+                let verb = if snippet.id.as_deref() == Some(id) {
+                    "Updated"
+                } else {
+                    "Created"
+                };
+                println!("{}: {} ({})", verb, name, id);
+            }
+        }
+        created.push(paste);
+    }
+    log_timing(timing, "import", request_start.elapsed());
+    if json {
+        println!("{}", serde_json::to_string_pretty(&created)?);
+    }
+    eprintln!(
+        "Imported {} of {} snippet(s).",
+        created.len(),
+        snippets.len()
+    );
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,52 @@
+//! `lpaste lint`: check a JSON, YAML, or TOML paste for syntax errors.
+
+use super::ServerResolutionSource;
+use super::{api_url_or_exit, ensure_success_or_exit, log_timing, send_or_exit};
+use clap::Args;
+use localpaste_core::lint::PasteLintResponse;
+use std::time::Instant;
+
+/// Arguments for `lpaste lint`.
+#[derive(Debug, Args)]
+pub(crate) struct LintArgs {
+    /// Paste id, slug, or unique id prefix.
+    pub(crate) id: String,
+}
+
+/// Formats the line printed by `lpaste lint`.
+pub(crate) fn format_lint_output(report: &PasteLintResponse) -> String {
+    match (&report.error, report.language.as_deref()) {
+        (Some(error), _) => format!("{}:{}: {}", error.line, error.column, error.message),
+        (None, Some(language)) if report.checked => format!("valid {}", language),
+        _ => "not checked (no linter for this language)".to_string(),
+    }
+}
+
+/// Print the paste's lint result; exits with status 1 when the paste is invalid.
+///
+/// # Errors
+/// Returns an error if the response cannot be decoded or printed.
+pub(crate) async fn run_lint(
+    client: &reqwest::Client,
+    server: &str,
+    source: ServerResolutionSource,
+    args: LintArgs,
+    json: bool,
+    timing: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = api_url_or_exit(server, "Lint", &["api", "paste", args.id.as_str(), "lint"]);
+    let request_start = Instant::now();
+    let res = send_or_exit(client.get(endpoint), "Lint", source, server).await;
+    let res = ensure_success_or_exit(res, "Lint").await;
+    let report: PasteLintResponse = res.json().await?;
+    log_timing(timing, "lint", request_start.elapsed());
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", format_lint_output(&report));
+    }
+    if !report.valid {
+        std::process::exit(1);
+    }
+    Ok(())
+}
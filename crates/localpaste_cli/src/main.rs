@@ -1,8 +1,8 @@
 //! Command-line client for the LocalPaste API.
 
-use clap::{CommandFactory, Parser, Subcommand};
-use clap_complete::{generate, Shell};
+use clap::{Parser, Subcommand, ValueEnum};
 use localpaste_core::diff::{DiffRef, DiffRequest, DiffResponse, EqualResponse};
+use localpaste_core::timestamps::DisplayTimezone;
 use localpaste_core::DEFAULT_CLI_SERVER_URL;
 use serde_json::Value;
 use std::io::{self, Read, Write};
@@ -10,6 +10,24 @@ use std::net::ToSocketAddrs;
 use std::num::NonZeroU64;
 use std::time::{Duration, Instant};
 
+mod chunked_upload;
+mod clone;
+mod complete;
+mod compression;
+mod count;
+mod digest;
+mod export;
+mod folder;
+mod generate_docs;
+mod import;
+mod lint;
+mod name_template;
+mod new_multi;
+mod recent;
+mod retry;
+mod slug;
+mod stats;
+
 #[derive(Parser)]
 #[command(name = "lpaste", about = "LocalPaste CLI", version)]
 struct Cli {
@@ -39,30 +57,73 @@ struct Cli {
     #[arg(short = 't', long, global = true, default_value = "30")]
     timeout: NonZeroU64,
 
+    /// Timezone for printed timestamps: `local` or `utc`.
+    ///
+    /// Defaults to the `display.timezone` entry in the DB directory's
+    /// `settings.json` (the GUI writes it), falling back to `local`.
+    #[arg(long, global = true, env = "LP_TIMEZONE")]
+    timezone: Option<DisplayTimezone>,
+
+    /// Bearer token for servers running in multi-user mode.
+    #[arg(long, global = true, env = "LP_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+
+    /// Print version, commit, build date, enabled features, and default
+    /// paths, then exit. Works without a subcommand.
+    #[arg(long, global = true)]
+    build_info: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+/// Output formats accepted by `lpaste search --format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SearchFormatArg {
+    Text,
+    Json,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Generate shell completions
-    Completions {
-        /// Shell to generate completions for
-        #[arg(value_enum)]
-        shell: Shell,
-    },
+    Completions(complete::CompletionsArgs),
+    /// Write man pages and completion scripts for packaging.
+    GenerateDocs(generate_docs::GenerateDocsArgs),
+    /// Dynamic completion protocol used by `completions --dynamic` scripts.
+    #[command(name = "__complete", alias = complete::STATIC_COMPLETE_NAME, hide = true)]
+    Complete(complete::CompleteArgs),
     /// Create a new paste from stdin or a file.
     New {
         /// Read paste content from a file instead of stdin.
         #[arg(short, long)]
         file: Option<String>,
         /// Optional paste name. When omitted, the server generates one.
+        ///
+        /// `{{var}}` placeholders expand locally: `date`, `time`, `datetime`,
+        /// `git_branch`, `git_repo`, `git_commit`, `hostname`, `user`, `cwd`,
+        /// `file`, and `env.NAME`; `{{var|fallback}}` covers missing values.
         #[arg(short, long)]
         name: Option<String>,
+        /// Reuse an existing paste with identical content instead of creating one.
+        /// Not applied to chunked uploads.
+        #[arg(long, conflicts_with = "stdin_multi")]
+        dedupe: bool,
+        /// File the paste under a folder path such as `work/project/logs`;
+        /// missing folders are created.
+        #[arg(long)]
+        folder: Option<String>,
+        /// Split stdin on `--split` and create one paste per snippet in a single
+        /// transaction. `--name` becomes a numbered prefix.
+        #[arg(long, conflicts_with = "file")]
+        stdin_multi: bool,
+        /// Delimiter regex for `--stdin-multi`; `^` and `$` match at line boundaries.
+        #[arg(long, requires = "stdin_multi", default_value = new_multi::DEFAULT_SPLIT_PATTERN)]
+        split: String,
     },
     /// Fetch a paste by id and print its content.
     Get {
-        /// Paste id to read.
+        /// Paste id, slug, or a unique id prefix of at least 4 characters, to read.
         id: String,
     },
     /// List recent paste metadata.
@@ -75,6 +136,13 @@ enum Commands {
     Search {
         /// Search query text.
         query: String,
+        /// Output format; `json` matches the global `--json` flag.
+        #[arg(long, value_enum, default_value = "text")]
+        format: SearchFormatArg,
+        /// Include each matching paste's full content and match ranges.
+        /// Implies `--format json`.
+        #[arg(long)]
+        with_content: bool,
     },
     /// Search persisted metadata only (name, tags, language, derived terms).
     SearchMeta {
@@ -83,12 +151,12 @@ enum Commands {
     },
     /// Delete a paste by id.
     Delete {
-        /// Paste id to delete.
+        /// Paste id or unique prefix to delete.
         id: String,
     },
     /// List stored historical versions for a paste.
     Versions {
-        /// Paste id whose version history should be listed.
+        /// Paste id or unique prefix whose version history should be listed.
         id: String,
         /// Maximum number of historical versions to return.
         #[arg(short, long, default_value = "50")]
@@ -96,16 +164,16 @@ enum Commands {
     },
     /// Fetch one stored historical version by paste id and version id.
     GetVersion {
-        /// Paste id whose version should be read.
+        /// Paste id or unique prefix whose version should be read.
         id: String,
         /// Historical version timestamp id in milliseconds.
         version_id_ms: u64,
     },
     /// Diff two paste refs, optionally pinning each side to a historical version.
     Diff {
-        /// Left-hand paste id.
+        /// Left-hand paste id or unique prefix.
         left_id: String,
-        /// Right-hand paste id.
+        /// Right-hand paste id or unique prefix.
         right_id: String,
         /// Optional historical version id for the left-hand paste.
         #[arg(long)]
@@ -116,9 +184,9 @@ enum Commands {
     },
     /// Compare two paste refs and return a success exit code only when equal.
     Equal {
-        /// Left-hand paste id.
+        /// Left-hand paste id or unique prefix.
         left_id: String,
-        /// Right-hand paste id.
+        /// Right-hand paste id or unique prefix.
         right_id: String,
         /// Optional historical version id for the left-hand paste.
         #[arg(long)]
@@ -129,7 +197,7 @@ enum Commands {
     },
     /// Reset a paste to a stored historical version and discard newer history.
     ResetHard {
-        /// Paste id to reset.
+        /// Paste id or unique prefix to reset.
         id: String,
         /// Historical version timestamp id in milliseconds.
         version_id_ms: u64,
@@ -139,7 +207,7 @@ enum Commands {
     },
     /// Create a new paste from a stored historical version.
     DuplicateVersion {
-        /// Paste id whose historical version should be duplicated.
+        /// Paste id or unique prefix whose historical version should be duplicated.
         id: String,
         /// Historical version timestamp id in milliseconds.
         version_id_ms: u64,
@@ -147,12 +215,40 @@ enum Commands {
         #[arg(short, long)]
         name: Option<String>,
     },
+    /// Render a paste as highlighted HTML or PDF.
+    Export(export::ExportArgs),
+    /// Folder operations such as ZIP export.
+    Folder(folder::FolderArgs),
+    /// Import snippets from massCode, Boostnote, markdown, or a gist directory.
+    Import(import::ImportArgs),
+    /// Mirror every paste into a gist-style directory (one folder per paste).
+    Clone(clone::CloneArgs),
+    /// Show, set, or clear a paste's slug (a stable name usable in place of its id).
+    Slug(slug::SlugArgs),
+    /// Count pastes, optionally by folder, tag, or language.
+    Count(count::CountArgs),
+    /// Show paste totals by language and folder, and the largest pastes.
+    Stats(stats::StatsArgs),
+    /// List pastes recently opened from the GUI, CLI, or web UI (needs
+    /// `LOCALPASTE_RECENT_HISTORY=true` on the server).
+    Recent(recent::RecentArgs),
+    /// Check a JSON, YAML, or TOML paste for syntax errors; exits 1 when invalid.
+    Lint(lint::LintArgs),
+    /// Store a markdown digest of the last day's (or `--week`'s) activity.
+    Digest(digest::DigestArgs),
 }
 
 enum ApiCommand {
     New {
         file: Option<String>,
         name: Option<String>,
+        dedupe: bool,
+        folder: Option<String>,
+    },
+    NewMulti {
+        name: Option<String>,
+        split: String,
+        folder: Option<String>,
     },
     Get {
         id: String,
@@ -162,6 +258,8 @@ enum ApiCommand {
     },
     Search {
         query: String,
+        format: SearchFormatArg,
+        with_content: bool,
     },
     SearchMeta {
         query: String,
@@ -198,15 +296,64 @@ enum ApiCommand {
         version_id_ms: u64,
         name: Option<String>,
     },
+    Export(export::ExportArgs),
+    Folder(folder::FolderArgs),
+    Import(import::ImportArgs),
+    Clone(clone::CloneArgs),
+    Slug(slug::SlugArgs),
+    Count(count::CountArgs),
+    Stats(stats::StatsArgs),
+    Recent(recent::RecentArgs),
+    Lint(lint::LintArgs),
+    Digest(digest::DigestArgs),
+    Complete(complete::CompleteArgs),
+}
+
+/// Commands that run without contacting a server.
+enum LocalCommand {
+    Completions(complete::CompletionsArgs),
+    GenerateDocs(generate_docs::GenerateDocsArgs),
 }
 
-fn classify_command(command: Commands) -> Result<ApiCommand, Shell> {
+fn classify_command(command: Commands) -> Result<ApiCommand, LocalCommand> {
     match command {
-        Commands::Completions { shell } => Err(shell),
-        Commands::New { file, name } => Ok(ApiCommand::New { file, name }),
+        Commands::Completions(args) => Err(LocalCommand::Completions(args)),
+        Commands::GenerateDocs(args) => Err(LocalCommand::GenerateDocs(args)),
+        Commands::Complete(args) => Ok(ApiCommand::Complete(args)),
+        Commands::New {
+            name,
+            stdin_multi: true,
+            split,
+            folder,
+            ..
+        } => Ok(ApiCommand::NewMulti {
+            name,
+            split,
+            folder,
+        }),
+        Commands::New {
+            file,
+            name,
+            dedupe,
+            folder,
+            ..
+        } => Ok(ApiCommand::New {
+            file,
+            name,
+            dedupe,
+            folder,
+        }),
         Commands::Get { id } => Ok(ApiCommand::Get { id }),
         Commands::List { limit } => Ok(ApiCommand::List { limit }),
-        Commands::Search { query } => Ok(ApiCommand::Search { query }),
+        Commands::Search {
+            query,
+            format,
+            with_content,
+        } => Ok(ApiCommand::Search {
+            query,
+            format,
+            with_content,
+        }),
         Commands::SearchMeta { query } => Ok(ApiCommand::SearchMeta { query }),
         Commands::Delete { id } => Ok(ApiCommand::Delete { id }),
         Commands::Versions { id, limit } => Ok(ApiCommand::Versions { id, limit }),
@@ -247,9 +394,26 @@ fn classify_command(command: Commands) -> Result<ApiCommand, Shell> {
             version_id_ms,
             name,
         }),
+        Commands::Export(args) => Ok(ApiCommand::Export(args)),
+        Commands::Folder(args) => Ok(ApiCommand::Folder(args)),
+        Commands::Import(args) => Ok(ApiCommand::Import(args)),
+        Commands::Clone(args) => Ok(ApiCommand::Clone(args)),
+        Commands::Slug(args) => Ok(ApiCommand::Slug(args)),
+        Commands::Count(args) => Ok(ApiCommand::Count(args)),
+        Commands::Stats(args) => Ok(ApiCommand::Stats(args)),
+        Commands::Recent(args) => Ok(ApiCommand::Recent(args)),
+        Commands::Lint(args) => Ok(ApiCommand::Lint(args)),
+        Commands::Digest(args) => Ok(ApiCommand::Digest(args)),
     }
 }
 
+fn expand_name_or_exit(name: Option<String>, file: Option<&str>) -> Option<String> {
+    name_template::expand_name(name, file).unwrap_or_else(|message| {
+        eprintln!("New failed: {}", message);
+        std::process::exit(1);
+    })
+}
+
 fn log_timing(timing: bool, label: &str, duration: Duration) {
     if timing {
         eprintln!(
@@ -360,7 +524,21 @@ fn format_delete_output(id: &str, response: &Value, json: bool) -> Result<String
     Ok(format!("Deleted paste: {}", id))
 }
 
-fn format_versions_output(items: &[Value], json: bool) -> Result<String, String> {
+/// Resolve the display timezone from the flag, then the settings file.
+fn resolve_display_timezone(flag: Option<DisplayTimezone>) -> DisplayTimezone {
+    flag.unwrap_or_else(|| {
+        let db_path = localpaste_core::config::db_path_from_env_or_default();
+        localpaste_core::settings::load_settings(db_path.as_str())
+            .map(|settings| settings.display.timezone)
+            .unwrap_or_default()
+    })
+}
+
+fn format_versions_output(
+    items: &[Value],
+    json: bool,
+    timezone: DisplayTimezone,
+) -> Result<String, String> {
     if json {
         return serde_json::to_string_pretty(items)
             .map_err(|err| format!("response encoding error: {}", err));
@@ -376,6 +554,9 @@ fn format_versions_output(items: &[Value], json: bool) -> Result<String, String>
             .get("created_at")
             .and_then(Value::as_str)
             .ok_or_else(|| format!("response item {} missing 'created_at' field", index))?;
+        let created_at = timezone
+            .format_rfc3339(created_at, "%Y-%m-%d %H:%M:%S")
+            .unwrap_or_else(|| created_at.to_string());
         let len = item
             .get("len")
             .and_then(Value::as_u64)
@@ -665,7 +846,7 @@ async fn send_or_exit(
     source: ServerResolutionSource,
     server: &str,
 ) -> reqwest::Response {
-    match request.send().await {
+    match retry::send_with_retry(request).await {
         Ok(response) => response,
         Err(err) => {
             eprintln!("{} failed: {}", action, err);
@@ -715,29 +896,73 @@ fn validate_server_base_or_exit(server: &str) {
     }
 }
 
+/// Whether `--build-info` appears before any `--` separator.
+///
+/// Checked ahead of clap so the flag works without the otherwise required
+/// subcommand.
+fn wants_build_info(args: impl IntoIterator<Item = std::ffi::OsString>) -> bool {
+    args.into_iter()
+        .skip(1)
+        .take_while(|arg| arg != "--")
+        .any(|arg| arg == "--build-info")
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    if wants_build_info(std::env::args_os()) {
+        print!(
+            "{}",
+            localpaste_core::build_info::render_build_info(
+                "lpaste",
+                env!("CARGO_PKG_VERSION"),
+                &[]
+            )
+        );
+        return Ok(());
+    }
+
     let Cli {
         server,
         no_discovery,
         json,
         timing,
         timeout,
+        timezone,
+        token,
+        build_info: _,
         command,
     } = Cli::parse();
 
     let command = match classify_command(command) {
-        Err(shell) => {
-            let mut cmd = Cli::command();
-            let name = cmd.get_name().to_string();
-            generate(shell, &mut cmd, name, &mut io::stdout());
+        Err(LocalCommand::Completions(args)) => {
+            complete::print_completions(args);
+            return Ok(());
+        }
+        Err(LocalCommand::GenerateDocs(args)) => {
+            generate_docs::run_generate_docs(args);
             return Ok(());
         }
         Ok(command) => command,
     };
 
+    // Names this frontend in the server's per-client recent history.
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(
+        "x-localpaste-client",
+        reqwest::header::HeaderValue::from_static(recent::CLI_CLIENT),
+    );
+    if let Some(token) = token
+        .as_deref()
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+    {
+        let mut value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))?;
+        value.set_sensitive(true);
+        headers.insert(reqwest::header::AUTHORIZATION, value);
+    }
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(timeout.get()))
+        .default_headers(headers)
         .build()?;
     let (resolved_server, source) = resolve_server_with_source(server, !no_discovery);
     let server = normalize_server(resolved_server);
@@ -747,7 +972,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     match command {
-        ApiCommand::New { file, name } => {
+        ApiCommand::New {
+            file,
+            name,
+            dedupe,
+            folder,
+        } => {
+            let name = expand_name_or_exit(name, file.as_deref());
             let endpoint = api_url_or_exit(&server, "New", &["api", "paste"]);
             let content = if let Some(path) = file {
                 std::fs::read_to_string(path)?
@@ -757,14 +988,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 buffer
             };
 
-            let mut body = serde_json::json!({ "content": content });
+            // Oversized files create an empty paste first, then stream content in chunks.
+            let chunked = content.len() > chunked_upload::CHUNKED_UPLOAD_THRESHOLD_BYTES;
+            let initial_content = if chunked { "" } else { content.as_str() };
+            let mut body = serde_json::json!({ "content": initial_content });
             if let Some(n) = name {
                 body["name"] = n.into();
             }
+            if dedupe && !chunked {
+                body["dedupe"] = true.into();
+            }
+            if let Some(folder) = folder {
+                body["folder_path"] = folder.into();
+            }
 
             let request_start = Instant::now();
             let res = send_or_exit(
-                client.post(endpoint).json(&body),
+                compression::with_json_body(client.post(endpoint), &body),
                 "New",
                 source,
                 server.as_str(),
@@ -772,12 +1012,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .await;
             let request_elapsed = request_start.elapsed();
             let res = ensure_success_or_exit(res, "New").await;
+            let deduplicated = res.headers().contains_key("x-localpaste-deduplicated");
 
             let parse_start = Instant::now();
-            let paste: Value = res.json().await?;
+            let mut paste: Value = res.json().await?;
             let parse_elapsed = parse_start.elapsed();
 
             log_timing_parts(timing, "new", request_elapsed, Some(parse_elapsed));
+            if chunked {
+                let id = paste.get("id").and_then(Value::as_str).unwrap_or_default();
+                let upload_start = Instant::now();
+                paste = chunked_upload::upload_in_chunks(&client, &server, id, content.as_bytes())
+                    .await
+                    .unwrap_or_else(|err| {
+                        eprintln!("New failed: chunked upload to '{}' failed: {}", id, err);
+                        std::process::exit(1);
+                    });
+                log_timing(timing, "new chunked upload", upload_start.elapsed());
+            }
             if json {
                 println!("{}", serde_json::to_string_pretty(&paste)?);
             } else {
@@ -785,9 +1037,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     eprintln!("New failed: response missing 'id' or 'name' field");
                     std::process::exit(1);
                 };
-                println!("Created: {} ({})", name, id);
+                let verb = if deduplicated { "Existing" } else { "Created" };
+                println!("{}: {} ({})", verb, name, id);
             }
         }
+        ApiCommand::NewMulti {
+            name,
+            split,
+            folder,
+        } => {
+            let name = expand_name_or_exit(name, None);
+            let mut content = String::new();
+            io::stdin().read_to_string(&mut content)?;
+            new_multi::run_new_multi(
+                &client,
+                &server,
+                source,
+                &content,
+                &split,
+                name.as_deref(),
+                folder.as_deref(),
+                json,
+                timing,
+            )
+            .await?;
+        }
         ApiCommand::Get { id } => {
             let endpoint = api_url_or_exit(&server, "Get", &["api", "paste", id.as_str()]);
             let request_start = Instant::now();
@@ -838,11 +1112,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!("{}", output);
             }
         }
-        ApiCommand::Search { query } => {
+        ApiCommand::Search {
+            query,
+            format,
+            with_content,
+        } => {
             let endpoint = api_url_or_exit(&server, "Search", &["api", "search"]);
+            let mut params = vec![("q", query.as_str())];
+            if with_content {
+                params.extend([("include_content", "true"), ("include_matches", "true")]);
+            }
             let request_start = Instant::now();
             let res = send_or_exit(
-                client.get(endpoint).query(&[("q", query.as_str())]),
+                client.get(endpoint).query(&params),
                 "Search",
                 source,
                 server.as_str(),
@@ -850,12 +1132,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .await;
             let request_elapsed = request_start.elapsed();
             let res = ensure_success_or_exit(res, "Search").await;
+            if let Some(omitted) = res
+                .headers()
+                .get("x-localpaste-content-omitted")
+                .and_then(|value| value.to_str().ok())
+            {
+                eprintln!(
+                    "Search: content omitted for {} result(s) (response size cap or burn-after-read).",
+                    omitted
+                );
+            }
 
             let parse_start = Instant::now();
             let pastes: Vec<Value> = res.json().await?;
             let parse_elapsed = parse_start.elapsed();
 
             log_timing_parts(timing, "search", request_elapsed, Some(parse_elapsed));
+            let json = json || with_content || format == SearchFormatArg::Json;
             let output = match format_summary_output(&pastes, json) {
                 Ok(output) => output,
                 Err(message) => {
@@ -939,13 +1232,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             let parse_elapsed = parse_start.elapsed();
             log_timing_parts(timing, "versions", request_elapsed, Some(parse_elapsed));
 
-            let output = match format_versions_output(&versions, json) {
-                Ok(output) => output,
-                Err(message) => {
-                    eprintln!("Versions failed: {}", message);
-                    std::process::exit(1);
-                }
-            };
+            let output =
+                match format_versions_output(&versions, json, resolve_display_timezone(timezone)) {
+                    Ok(output) => output,
+                    Err(message) => {
+                        eprintln!("Versions failed: {}", message);
+                        std::process::exit(1);
+                    }
+                };
             if !output.is_empty() {
                 println!("{}", output);
             }
@@ -1149,6 +1443,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!("Created: {} ({})", new_name, new_id);
             }
         }
+        ApiCommand::Export(args) => {
+            export::run_export(&client, &server, source, args, timing).await?;
+        }
+        ApiCommand::Folder(args) => {
+            folder::run_folder(&client, &server, source, args, json, timing).await?;
+        }
+        ApiCommand::Import(args) => {
+            import::run_import(&client, &server, source, args, json, timing).await?;
+        }
+        ApiCommand::Clone(args) => {
+            clone::run_clone(&client, &server, source, args, timing).await?;
+        }
+        ApiCommand::Slug(args) => {
+            slug::run_slug(&client, &server, source, args, json, timing).await?;
+        }
+        ApiCommand::Count(args) => {
+            count::run_count(&client, &server, source, args, json, timing).await?;
+        }
+        ApiCommand::Stats(args) => {
+            stats::run_stats(&client, &server, source, args, json, timing).await?;
+        }
+        ApiCommand::Recent(args) => {
+            recent::run_recent(&client, &server, source, args, json, timing).await?;
+        }
+        ApiCommand::Lint(args) => {
+            lint::run_lint(&client, &server, source, args, json, timing).await?;
+        }
+        ApiCommand::Digest(args) => {
+            digest::run_digest(&client, &server, source, args, json, timing).await?;
+        }
+        ApiCommand::Complete(args) => complete::run_complete(&client, &server, args).await,
     }
 
     Ok(())
@@ -0,0 +1,95 @@
+//! Client-side `{{variable}}` expansion for `lpaste new --name`.
+//!
+//! Variables describe where the command runs, so they are resolved here
+//! rather than on the server: clock values from
+//! [`TemplateVars::now`], plus `git_branch`, `git_repo`, `git_commit`,
+//! `hostname`, `user`, `cwd`, and `file` (the `--file` name) when known.
+
+use localpaste_core::templates::{has_placeholders, render_template, TemplateVars};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn env_value(keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+}
+
+fn file_name(path: &str) -> Option<String> {
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(str::to_string)
+}
+
+/// Collect template variables for the current directory and host.
+///
+/// Values that cannot be determined (no git checkout, no `--file`) are left
+/// unset so `{{name|fallback}}` can cover them.
+pub(crate) fn cli_template_vars(file: Option<&str>) -> TemplateVars {
+    let mut vars = TemplateVars::now();
+    let mut set = |name: &str, value: Option<String>| {
+        if let Some(value) = value {
+            vars.insert(name, value);
+        }
+    };
+    set(
+        "git_branch",
+        command_output("git", &["rev-parse", "--abbrev-ref", "HEAD"]),
+    );
+    set(
+        "git_repo",
+        command_output("git", &["rev-parse", "--show-toplevel"]).and_then(|top| file_name(&top)),
+    );
+    set(
+        "git_commit",
+        command_output("git", &["rev-parse", "--short", "HEAD"]),
+    );
+    set(
+        "hostname",
+        env_value(&["HOSTNAME", "COMPUTERNAME"]).or_else(|| command_output("hostname", &[])),
+    );
+    set("user", env_value(&["USER", "USERNAME"]));
+    set(
+        "cwd",
+        std::env::current_dir()
+            .ok()
+            .and_then(|dir| dir.to_str().and_then(file_name)),
+    );
+    set("file", file.and_then(file_name));
+    vars
+}
+
+/// Expand placeholders in a `--name` value.
+///
+/// Names without `{{` are returned untouched and no context is gathered.
+///
+/// # Errors
+/// Returns a message for malformed templates or unknown variables.
+pub(crate) fn expand_name(
+    name: Option<String>,
+    file: Option<&str>,
+) -> Result<Option<String>, String> {
+    match name {
+        Some(name) if has_placeholders(&name) => render_template(&name, &cli_template_vars(file))
+            .map(Some)
+            .map_err(|err| err.to_string()),
+        other => Ok(other),
+    }
+}
@@ -0,0 +1,129 @@
+//! `lpaste new --stdin-multi`: split stdin on a delimiter and batch-create pastes.
+
+use super::ServerResolutionSource;
+use super::{
+    api_url_or_exit, compression, ensure_success_or_exit, log_timing, paste_id_and_name,
+    send_or_exit,
+};
+use regex_automata::meta::Regex;
+use regex_automata::util::syntax;
+use serde_json::{json, Value};
+use std::time::Instant;
+
+/// Delimiter used by `--stdin-multi` when `--split` is omitted.
+pub(crate) const DEFAULT_SPLIT_PATTERN: &str = "^---$";
+
+/// Splits `content` into snippets at every match of `pattern`.
+///
+/// `^`/`$` match at line boundaries, and the line ending that follows a
+/// delimiter is dropped with it. Blank snippets (for example before a leading
+/// delimiter) are skipped.
+///
+/// # Errors
+/// Returns the pattern parse error as a message.
+pub(crate) fn split_snippets(content: &str, pattern: &str) -> Result<Vec<String>, String> {
+    let regex = Regex::builder()
+        .syntax(syntax::Config::new().multi_line(true).crlf(true))
+        .build(pattern)
+        .map_err(|err| err.to_string())?;
+    let mut snippets = Vec::new();
+    let mut start = 0;
+    for found in regex.find_iter(content) {
+        if found.is_empty() {
+            continue;
+        }
+        snippets.push(&content[start..found.start()]);
+        let rest = &content[found.end()..];
+        start = found.end()
+            + if rest.starts_with("\r\n") {
+                2
+            } else {
+                usize::from(rest.starts_with('\n'))
+            };
+    }
+    snippets.push(&content[start.min(content.len())..]);
+    Ok(snippets
+        .into_iter()
+        .filter(|snippet| !snippet.trim().is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Builds the batch create payload; `name` becomes a numbered prefix and
+/// every paste is filed under `folder_path` when given.
+pub(crate) fn batch_body(
+    snippets: Vec<String>,
+    name: Option<&str>,
+    folder_path: Option<&str>,
+) -> Value {
+    let items = snippets
+        .into_iter()
+        .enumerate()
+        .map(|(index, content)| {
+            let mut item = match name {
+                Some(name) => {
+                    json!({ "content": content, "name": format!("{}-{}", name, index + 1) })
+                }
+                None => json!({ "content": content }),
+            };
+            if let Some(folder_path) = folder_path {
+                item["folder_path"] = folder_path.into();
+            }
+            item
+        })
+        .collect();
+    Value::Array(items)
+}
+
+/// Create one paste per snippet through `POST /api/pastes/batch`.
+///
+/// # Errors
+/// Returns an error if the response cannot be decoded or printed.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run_new_multi(
+    client: &reqwest::Client,
+    server: &str,
+    source: ServerResolutionSource,
+    content: &str,
+    pattern: &str,
+    name: Option<&str>,
+    folder_path: Option<&str>,
+    json: bool,
+    timing: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let snippets = split_snippets(content, pattern).unwrap_or_else(|err| {
+        eprintln!("New failed: invalid --split pattern: {}", err);
+        std::process::exit(1);
+    });
+    if snippets.is_empty() {
+        eprintln!("New failed: no snippets found in stdin");
+        std::process::exit(1);
+    }
+
+    let endpoint = api_url_or_exit(server, "New", &["api", "pastes", "batch"]);
+    let body = batch_body(snippets, name, folder_path);
+    let request_start = Instant::now();
+    let res = send_or_exit(
+        compression::with_json_body(client.post(endpoint), &body),
+        "New",
+        source,
+        server,
+    )
+    .await;
+    let res = ensure_success_or_exit(res, "New").await;
+    let pastes: Vec<Value> = res.json().await?;
+    log_timing(timing, "new batch", request_start.elapsed());
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&pastes)?);
+        return Ok(());
+    }
+    for paste in &pastes {
+        let Some((id, name)) = paste_id_and_name(paste) else {
+            eprintln!("New failed: response missing 'id' or 'name' field");
+            std::process::exit(1);
+        };
+        println!("Created: {} ({})", name, id);
+    }
+    Ok(())
+}
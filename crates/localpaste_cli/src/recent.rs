@@ -0,0 +1,75 @@
+//! `lpaste recent`: pastes recently opened from any frontend, via `GET /api/recent`.
+
+use super::ServerResolutionSource;
+use super::{api_url_or_exit, ensure_success_or_exit, log_timing, send_or_exit};
+use clap::Args;
+use localpaste_core::models::recent::RecentPaste;
+use std::fmt::Write as _;
+use std::time::Instant;
+
+/// Client label the CLI sends in `x-localpaste-client`.
+pub(crate) const CLI_CLIENT: &str = "cli";
+
+/// Arguments for `lpaste recent`.
+#[derive(Debug, Args)]
+pub(crate) struct RecentArgs {
+    /// Maximum number of rows to return (server caps this at 100).
+    #[arg(short, long, default_value = "10")]
+    pub(crate) limit: usize,
+    /// Only list pastes opened by this client (`gui`, `cli`, `web`, ...).
+    #[arg(long)]
+    pub(crate) client: Option<String>,
+}
+
+/// Formats one line per paste: id, opening client, open time, and name.
+pub(crate) fn format_recent(rows: &[RecentPaste]) -> String {
+    let mut out = String::new();
+    for row in rows {
+        let _ = writeln!(
+            out,
+            "{}  {:<8} {}  {}",
+            row.meta.id,
+            row.client,
+            row.opened_at.format("%Y-%m-%d %H:%M"),
+            row.meta.name
+        );
+    }
+    out
+}
+
+/// Print recently opened pastes.
+///
+/// # Errors
+/// Returns an error if the response cannot be decoded or printed.
+pub(crate) async fn run_recent(
+    client: &reqwest::Client,
+    server: &str,
+    source: ServerResolutionSource,
+    args: RecentArgs,
+    json: bool,
+    timing: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = api_url_or_exit(server, "Recent", &["api", "recent"]);
+    let mut params = vec![("limit", args.limit.to_string())];
+    if let Some(client_label) = args.client {
+        params.push(("client", client_label));
+    }
+    let request_start = Instant::now();
+    let res = send_or_exit(
+        client.get(endpoint).query(&params),
+        "Recent",
+        source,
+        server,
+    )
+    .await;
+    let res = ensure_success_or_exit(res, "Recent").await;
+    let body: serde_json::Value = res.json().await?;
+    log_timing(timing, "recent", request_start.elapsed());
+    if json {
+        println!("{}", serde_json::to_string_pretty(&body)?);
+    } else {
+        let rows: Vec<RecentPaste> = serde_json::from_value(body)?;
+        print!("{}", format_recent(&rows));
+    }
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! Retry writes the server rate limited with `429 Too Many Requests`.
+//!
+//! The server's write limiter answers bursts (imports, chunked uploads) with
+//! `429` and a `Retry-After` in whole seconds. Every CLI request goes through
+//! [`send_with_retry`], so bulk commands slow down instead of failing.
+
+use std::time::Duration;
+
+/// Attempts per request, including the first, before a `429` is returned.
+const RATE_LIMIT_ATTEMPTS: usize = 6;
+/// Wait used when a `429` carries no usable `Retry-After`.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+/// Upper bound on a single wait, whatever the server asks for.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// Wait requested by a `429` response's `Retry-After` header.
+///
+/// Only the delay-seconds form is read; HTTP dates fall back to the default.
+pub(crate) fn retry_after(headers: &reqwest::header::HeaderMap) -> Duration {
+    headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_RETRY_AFTER)
+        .min(MAX_RETRY_AFTER)
+}
+
+/// Send `request`, retrying after `Retry-After` while the server answers `429`.
+///
+/// Requests whose body cannot be cloned (streams) are sent once.
+///
+/// # Returns
+/// The first non-`429` response, or the last `429` once attempts run out.
+///
+/// # Errors
+/// Returns the transport error of the failing attempt.
+pub(crate) async fn send_with_retry(
+    request: reqwest::RequestBuilder,
+) -> reqwest::Result<reqwest::Response> {
+    let mut request = request;
+    for _ in 1..RATE_LIMIT_ATTEMPTS {
+        let Some(retry) = request.try_clone() else {
+            break;
+        };
+        let response = request.send().await?;
+        if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Ok(response);
+        }
+        tokio::time::sleep(retry_after(response.headers())).await;
+        request = retry;
+    }
+    request.send().await
+}
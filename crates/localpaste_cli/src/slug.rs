@@ -0,0 +1,61 @@
+//! `lpaste slug`: show, set, rename, or clear a paste's slug.
+
+use super::ServerResolutionSource;
+use super::{api_url_or_exit, ensure_success_or_exit, log_timing, send_or_exit};
+use clap::Args;
+use serde_json::{json, Value};
+use std::time::Instant;
+
+/// Arguments for `lpaste slug`.
+#[derive(Debug, Args)]
+pub(crate) struct SlugArgs {
+    /// Paste id, slug, or unique id prefix.
+    pub(crate) id: String,
+    /// New slug (a-z, 0-9, '-', '_', '.'); prints the current slug when omitted.
+    #[arg(conflicts_with = "clear")]
+    pub(crate) slug: Option<String>,
+    /// Remove the paste's slug.
+    #[arg(long)]
+    pub(crate) clear: bool,
+}
+
+/// Formats the slug line printed by `lpaste slug`.
+pub(crate) fn format_slug_output(paste: &Value) -> String {
+    let id = paste.get("id").and_then(Value::as_str).unwrap_or_default();
+    match paste.get("slug").and_then(Value::as_str) {
+        Some(slug) => format!("{}  {}", id, slug),
+        None => format!("{}  (no slug)", id),
+    }
+}
+
+/// Show or change a paste slug.
+///
+/// # Errors
+/// Returns an error if the response cannot be decoded or printed.
+pub(crate) async fn run_slug(
+    client: &reqwest::Client,
+    server: &str,
+    source: ServerResolutionSource,
+    args: SlugArgs,
+    json: bool,
+    timing: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let request_start = Instant::now();
+    let res = if args.slug.is_none() && !args.clear {
+        let endpoint = api_url_or_exit(server, "Slug", &["api", "paste", args.id.as_str()]);
+        send_or_exit(client.get(endpoint), "Slug", source, server).await
+    } else {
+        let endpoint = api_url_or_exit(server, "Slug", &["api", "paste", args.id.as_str(), "slug"]);
+        let body = json!({ "slug": args.slug });
+        send_or_exit(client.put(endpoint).json(&body), "Slug", source, server).await
+    };
+    let res = ensure_success_or_exit(res, "Slug").await;
+    let paste: Value = res.json().await?;
+    log_timing(timing, "slug", request_start.elapsed());
+    if json {
+        println!("{}", serde_json::to_string_pretty(&paste)?);
+    } else {
+        println!("{}", format_slug_output(&paste));
+    }
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//! `lpaste stats`: instance totals and breakdowns from `GET /api/stats`.
+
+use super::ServerResolutionSource;
+use super::{api_url_or_exit, ensure_success_or_exit, log_timing, send_or_exit};
+use clap::Args;
+use localpaste_core::stats::PasteStats;
+use std::fmt::Write as _;
+use std::time::Instant;
+
+/// Arguments for `lpaste stats`.
+#[derive(Debug, Args)]
+pub(crate) struct StatsArgs {
+    /// How many of the largest pastes to list (server caps this at 100).
+    #[arg(long, default_value_t = localpaste_core::stats::DEFAULT_STATS_LARGEST)]
+    pub(crate) largest: usize,
+}
+
+/// Formats the human-readable `lpaste stats` report.
+pub(crate) fn format_stats(stats: &PasteStats) -> String {
+    let mut out = format!(
+        "{} pastes, {} bytes\n",
+        stats.total_pastes, stats.total_bytes
+    );
+    out.push_str("\nLanguages:\n");
+    for row in &stats.languages {
+        let _ = writeln!(
+            out,
+            "  {:<16} {:>6} pastes {:>10} bytes",
+            row.language.as_deref().unwrap_or("(none)"),
+            row.pastes,
+            row.bytes
+        );
+    }
+    out.push_str("\nFolders:\n");
+    for row in &stats.folders {
+        let label = match (&row.folder_id, &row.name) {
+            (None, _) => "(unfiled)".to_string(),
+            (Some(id), Some(name)) => format!("{} ({})", name, id),
+            (Some(id), None) => id.clone(),
+        };
+        let _ = writeln!(
+            out,
+            "  {:<16} {:>6} pastes {:>10} bytes",
+            label, row.pastes, row.bytes
+        );
+    }
+    if !stats.largest.is_empty() {
+        out.push_str("\nLargest:\n");
+        for row in &stats.largest {
+            let _ = writeln!(out, "  {}  {:>10} bytes  {}", row.id, row.bytes, row.name);
+        }
+    }
+    out
+}
+
+/// Print instance statistics.
+///
+/// # Errors
+/// Returns an error if the response cannot be decoded or printed.
+pub(crate) async fn run_stats(
+    client: &reqwest::Client,
+    server: &str,
+    source: ServerResolutionSource,
+    args: StatsArgs,
+    json: bool,
+    timing: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = api_url_or_exit(server, "Stats", &["api", "stats"]);
+    let request_start = Instant::now();
+    let res = send_or_exit(
+        client
+            .get(endpoint)
+            .query(&[("largest", args.largest.to_string())]),
+        "Stats",
+        source,
+        server,
+    )
+    .await;
+    let res = ensure_success_or_exit(res, "Stats").await;
+    let body: serde_json::Value = res.json().await?;
+    log_timing(timing, "stats", request_start.elapsed());
+    if json {
+        println!("{}", serde_json::to_string_pretty(&body)?);
+    } else {
+        let stats: PasteStats = serde_json::from_value(body)?;
+        print!("{}", format_stats(&stats));
+    }
+    Ok(())
+}
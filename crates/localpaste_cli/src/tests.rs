@@ -1,17 +1,31 @@
 //! Unit tests for the `lpaste` CLI entrypoint module.
 
+use super::clone::prune_gist_layout;
+use super::export::ExportFormatArg;
+use super::folder::{
+    attachment_filename, format_folder_counts, format_folder_row, FolderCommand, FolderSortArg,
+};
+use super::generate_docs::generate_docs;
+use super::import::ImportFormatArg;
+use super::lint::format_lint_output;
+use super::name_template::expand_name;
+use super::recent::format_recent;
+use super::slug::format_slug_output;
+use super::stats::format_stats;
 use super::{
     api_url, default_resolution_connect_hint, discovered_server_from_file_with_reachability,
     discovery_probe_response_looks_like_localpaste, error_message_for_response,
     format_delete_output, format_diff_output, format_equal_output, format_get_output,
-    format_summary_output, normalize_server, paste_id_and_name, resolve_server,
-    resolve_server_with_source, ServerResolutionSource,
+    format_summary_output, format_versions_output, normalize_server, paste_id_and_name,
+    resolve_server, resolve_server_with_source, wants_build_info, ServerResolutionSource,
 };
-use super::{Cli, Commands};
+use super::{Cli, Commands, SearchFormatArg};
 use clap::{CommandFactory, Parser};
 use localpaste_core::config::api_addr_file_path_from_env_or_default;
 use localpaste_core::diff::{unified_diff_lines, DiffResponse, EqualResponse};
+use localpaste_core::digest::DigestPeriod;
 use localpaste_core::env::{env_lock, EnvGuard};
+use localpaste_core::timestamps::DisplayTimezone;
 use localpaste_core::{DEFAULT_CLI_SERVER_URL, DEFAULT_PORT};
 use std::io::{Read, Write};
 use std::net::TcpListener;
@@ -336,6 +350,432 @@ fn cli_parses_search_meta_subcommand() {
     }
 }
 
+#[test]
+fn cli_parses_search_format_and_with_content_flags() {
+    let cli = Cli::try_parse_from(["lpaste", "search", "needle"]).expect("cli should parse search");
+    match cli.command {
+        Commands::Search {
+            format,
+            with_content,
+            ..
+        } => {
+            assert_eq!(format, SearchFormatArg::Text);
+            assert!(!with_content);
+        }
+        _ => panic!("expected search command"),
+    }
+
+    let cli = Cli::try_parse_from([
+        "lpaste",
+        "search",
+        "needle",
+        "--format",
+        "json",
+        "--with-content",
+    ])
+    .expect("cli should parse search flags");
+    match cli.command {
+        Commands::Search {
+            query,
+            format,
+            with_content,
+        } => {
+            assert_eq!(query, "needle");
+            assert_eq!(format, SearchFormatArg::Json);
+            assert!(with_content);
+        }
+        _ => panic!("expected search command"),
+    }
+    assert!(Cli::try_parse_from(["lpaste", "search", "x", "--format", "yaml"]).is_err());
+}
+
+#[test]
+fn versions_output_renders_created_at_in_display_timezone() {
+    let cli = Cli::try_parse_from(["lpaste", "versions", "abc", "--timezone", "UTC"])
+        .expect("cli should parse --timezone");
+    assert_eq!(cli.timezone, Some(DisplayTimezone::Utc));
+    assert!(Cli::try_parse_from(["lpaste", "list", "--timezone", "mars"]).is_err());
+
+    let items = vec![serde_json::json!({
+        "version_id_ms": 1_773_126_300_000u64,
+        "created_at": "2026-03-10T09:05:00+02:00",
+        "len": 12
+    })];
+    let rendered = format_versions_output(&items, false, DisplayTimezone::Utc)
+        .expect("versions output should render");
+    assert!(
+        rendered.contains("2026-03-10 07:05:00 UTC"),
+        "unexpected row: {rendered}"
+    );
+    let raw = format_versions_output(&items, true, DisplayTimezone::Utc)
+        .expect("versions json output should render");
+    assert!(raw.contains("2026-03-10T09:05:00+02:00"));
+}
+
+#[test]
+fn cli_parses_export_subcommand() {
+    let cli = Cli::try_parse_from(["lpaste", "export", "abc", "--format", "pdf", "-o", "a.pdf"])
+        .expect("cli should parse export");
+    match cli.command {
+        Commands::Export(args) => {
+            assert_eq!(args.id, "abc");
+            assert_eq!(args.format, ExportFormatArg::Pdf);
+            assert_eq!(args.output.as_deref(), Some("a.pdf"));
+        }
+        _ => panic!("expected export command"),
+    }
+    assert!(Cli::try_parse_from(["lpaste", "export", "abc", "--format", "docx"]).is_err());
+}
+
+#[test]
+fn cli_parses_folder_export_subcommand() {
+    let cli = Cli::try_parse_from(["lpaste", "folder", "export", "f1", "-o", "out.zip"])
+        .expect("cli should parse folder export");
+    match cli.command {
+        Commands::Folder(args) => match args.command {
+            FolderCommand::Export { id, output } => {
+                assert_eq!(id, "f1");
+                assert_eq!(output.as_deref(), Some("out.zip"));
+            }
+            _ => panic!("expected folder export"),
+        },
+        _ => panic!("expected folder command"),
+    }
+    assert!(Cli::try_parse_from(["lpaste", "folder", "export"]).is_err());
+}
+
+#[test]
+fn cli_parses_folder_list_and_formats_rows() {
+    let cli = Cli::try_parse_from([
+        "lpaste",
+        "folder",
+        "list",
+        "--parent",
+        "",
+        "--sort",
+        "paste-count",
+        "--desc",
+        "--recursive",
+    ])
+    .expect("cli should parse folder list");
+    match cli.command {
+        Commands::Folder(args) => match args.command {
+            FolderCommand::List {
+                parent,
+                sort,
+                desc,
+                recursive,
+            } => {
+                assert_eq!(parent.as_deref(), Some(""));
+                assert_eq!(sort, Some(FolderSortArg::PasteCount));
+                assert!(desc && recursive);
+            }
+            _ => panic!("expected folder list"),
+        },
+        _ => panic!("expected folder command"),
+    }
+    assert!(Cli::try_parse_from(["lpaste", "folder", "list", "--desc"]).is_err());
+
+    let row = serde_json::json!({
+        "id": "f1", "name": "work", "paste_count": 1, "recursive_paste_count": 3
+    });
+    assert_eq!(format_folder_row(&row), "f1  work (3)");
+}
+
+#[test]
+fn cli_parses_import_subcommand() {
+    let cli = Cli::try_parse_from(["lpaste", "import", "masscode", "db.json", "--dry-run"])
+        .expect("cli should parse import");
+    match cli.command {
+        Commands::Import(args) => {
+            assert_eq!(args.format, ImportFormatArg::Masscode);
+            assert_eq!(args.path, std::path::PathBuf::from("db.json"));
+            assert!(args.dry_run);
+        }
+        _ => panic!("expected import command"),
+    }
+    assert!(Cli::try_parse_from(["lpaste", "import", "evernote", "x"]).is_err());
+}
+
+#[test]
+fn cli_parses_clone_subcommand() {
+    let cli = Cli::try_parse_from(["lpaste", "clone", "backup", "--prune"])
+        .expect("cli should parse clone");
+    match cli.command {
+        Commands::Clone(args) => {
+            assert_eq!(args.dir, std::path::PathBuf::from("backup"));
+            assert!(args.prune);
+        }
+        _ => panic!("expected clone command"),
+    }
+}
+
+#[test]
+fn new_name_templates_expand_client_side_context() {
+    assert_eq!(expand_name(None, None), Ok(None));
+    assert_eq!(
+        expand_name(Some("plain {name}".to_string()), None),
+        Ok(Some("plain {name}".to_string()))
+    );
+    let expanded = expand_name(
+        Some("{{file}}-{{git_branch|nogit}}-{{date}}".to_string()),
+        Some("some/dir/notes.md"),
+    )
+    .expect("template should expand")
+    .expect("name should be kept");
+    assert!(expanded.starts_with("notes.md-"), "{expanded}");
+    assert!(!expanded.contains("{{"), "{expanded}");
+    let err = expand_name(Some("{{nope}}".to_string()), None).expect_err("unknown variable");
+    assert!(err.contains("'nope'"), "{err}");
+}
+
+#[test]
+fn cli_parses_digest_subcommand_period() {
+    for (argv, expected) in [
+        (vec!["lpaste", "digest"], DigestPeriod::Day),
+        (vec!["lpaste", "digest", "--week"], DigestPeriod::Week),
+    ] {
+        let cli = Cli::try_parse_from(argv).expect("cli should parse digest");
+        match cli.command {
+            Commands::Digest(args) => assert_eq!(args.period(), expected),
+            _ => panic!("expected digest command"),
+        }
+    }
+}
+
+#[test]
+fn cli_parses_slug_subcommand_and_formats_output() {
+    let cli = Cli::try_parse_from(["lpaste", "slug", "3f2a", "nginx-config"])
+        .expect("cli should parse slug");
+    match cli.command {
+        Commands::Slug(args) => {
+            assert_eq!(args.id, "3f2a");
+            assert_eq!(args.slug.as_deref(), Some("nginx-config"));
+            assert!(!args.clear);
+        }
+        _ => panic!("expected slug command"),
+    }
+    assert!(Cli::try_parse_from(["lpaste", "slug", "3f2a", "x", "--clear"]).is_err());
+
+    let paste = serde_json::json!({ "id": "abc", "slug": "nginx-config" });
+    assert_eq!(format_slug_output(&paste), "abc  nginx-config");
+    let bare = serde_json::json!({ "id": "abc", "slug": null });
+    assert_eq!(format_slug_output(&bare), "abc  (no slug)");
+}
+
+#[test]
+fn cli_parses_stats_and_formats_report() {
+    let cli =
+        Cli::try_parse_from(["lpaste", "stats", "--largest", "3"]).expect("cli should parse stats");
+    match cli.command {
+        Commands::Stats(args) => assert_eq!(args.largest, 3),
+        _ => panic!("expected stats command"),
+    }
+
+    let stats = serde_json::from_value(serde_json::json!({
+        "total_pastes": 2,
+        "total_bytes": 138,
+        "languages": [
+            { "language": "rust", "pastes": 1, "bytes": 130 },
+            { "language": null, "pastes": 1, "bytes": 8 },
+        ],
+        "folders": [
+            { "folder_id": null, "name": null, "pastes": 1, "bytes": 8 },
+            { "folder_id": "f1", "name": "work", "pastes": 1, "bytes": 130 },
+        ],
+        "largest": [
+            { "id": "abc", "name": "main", "language": "rust", "folder_id": "f1", "bytes": 130 },
+        ],
+    }))
+    .expect("stats");
+    let report = format_stats(&stats);
+    assert!(report.starts_with("2 pastes, 138 bytes\n"));
+    assert!(report.contains("  rust                  1 pastes        130 bytes\n"));
+    assert!(report.contains("  (none)                1 pastes          8 bytes\n"));
+    assert!(report.contains("  (unfiled)             1 pastes          8 bytes\n"));
+    assert!(report.contains("  work (f1)             1 pastes        130 bytes\n"));
+    assert!(report.ends_with("Largest:\n  abc         130 bytes  main\n"));
+}
+
+#[test]
+fn cli_parses_recent_and_formats_rows() {
+    let cli = Cli::try_parse_from(["lpaste", "recent", "--limit", "5", "--client", "gui"])
+        .expect("cli should parse recent");
+    match cli.command {
+        Commands::Recent(args) => {
+            assert_eq!(args.limit, 5);
+            assert_eq!(args.client.as_deref(), Some("gui"));
+        }
+        _ => panic!("expected recent command"),
+    }
+
+    let rows: Vec<_> = serde_json::from_value(serde_json::json!([{
+        "id": "abc",
+        "name": "main",
+        "language": "rust",
+        "folder_id": null,
+        "updated_at": "2026-01-02T03:04:05Z",
+        "updated_at_ms": 1767323045000u64,
+        "tags": [],
+        "content_len": 12,
+        "is_markdown": false,
+        "client": "gui",
+        "opened_at": "2026-01-02T10:30:00Z",
+        "opened_at_ms": 1767349800000u64,
+    }]))
+    .expect("recent rows");
+    assert_eq!(
+        format_recent(&rows),
+        "abc  gui      2026-01-02 10:30  main\n"
+    );
+}
+
+#[test]
+fn cli_parses_count_commands_and_formats_folder_counts() {
+    let cli = Cli::try_parse_from(["lpaste", "count", "--tag", "work", "--language", "sh"])
+        .expect("cli should parse count");
+    match cli.command {
+        Commands::Count(args) => {
+            assert_eq!(args.query(), vec![("tag", "work"), ("language", "sh")]);
+        }
+        _ => panic!("expected count command"),
+    }
+
+    let cli = Cli::try_parse_from(["lpaste", "folder", "count", "f1"])
+        .expect("cli should parse folder count");
+    match cli.command {
+        Commands::Folder(args) => match args.command {
+            FolderCommand::Count { id } => assert_eq!(id, "f1"),
+            _ => panic!("expected folder count"),
+        },
+        _ => panic!("expected folder command"),
+    }
+    let counts = serde_json::json!({ "paste_count": 2, "recursive_paste_count": 5 });
+    assert_eq!(
+        format_folder_counts(&counts),
+        "2 direct, 5 including subfolders"
+    );
+}
+
+#[test]
+fn cli_parses_lint_and_formats_error_position() {
+    let cli = Cli::try_parse_from(["lpaste", "lint", "cfg"]).expect("cli should parse lint");
+    match cli.command {
+        Commands::Lint(args) => assert_eq!(args.id, "cfg"),
+        _ => panic!("expected lint command"),
+    }
+
+    let report = |body: serde_json::Value| {
+        serde_json::from_value::<localpaste_core::lint::PasteLintResponse>(body).expect("report")
+    };
+    let invalid = report(serde_json::json!({
+        "id": "a", "checked": true, "valid": false, "language": "json",
+        "error": { "line": 3, "column": 7, "message": "expected `:`" }
+    }));
+    assert_eq!(format_lint_output(&invalid), "3:7: expected `:`");
+    let valid = report(serde_json::json!({
+        "id": "a", "checked": true, "valid": true, "language": "toml", "error": null
+    }));
+    assert_eq!(format_lint_output(&valid), "valid toml");
+    let unchecked = report(serde_json::json!({
+        "id": "a", "checked": false, "valid": true, "language": null, "error": null
+    }));
+    assert!(format_lint_output(&unchecked).starts_with("not checked"));
+}
+
+#[test]
+fn prune_gist_layout_removes_only_missing_pastes() {
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("lpaste-cli-clone-{}", nonce));
+    let kept = localpaste_core::models::paste::Paste::new("a".to_string(), "kept".to_string());
+    let gone = localpaste_core::models::paste::Paste::new("b".to_string(), "gone".to_string());
+    for paste in [&kept, &gone] {
+        localpaste_core::gist_layout::write_gist_paste(&dir, paste).expect("write");
+    }
+
+    let keep = std::collections::HashSet::from([kept.id.as_str()]);
+    assert_eq!(prune_gist_layout(&dir, &keep).expect("prune"), 1);
+    assert!(dir.join(kept.id.as_str()).exists());
+    assert!(!dir.join(gone.id.as_str()).exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn build_info_flag_is_detected_without_a_subcommand() {
+    let args = |list: &[&str]| -> Vec<std::ffi::OsString> { list.iter().map(Into::into).collect() };
+    assert!(wants_build_info(args(&["lpaste", "--build-info"])));
+    assert!(wants_build_info(args(&[
+        "lpaste",
+        "--json",
+        "--build-info",
+        "list"
+    ])));
+    assert!(!wants_build_info(args(&["lpaste", "list"])));
+    assert!(!wants_build_info(args(&[
+        "lpaste",
+        "__complete",
+        "--",
+        "--build-info"
+    ])));
+    let cli = Cli::try_parse_from(["lpaste", "--build-info", "list"]).expect("parse");
+    assert!(cli.build_info);
+}
+
+#[test]
+fn generate_docs_writes_man_pages_and_completions_for_both_binaries() {
+    let cli = Cli::try_parse_from(["lpaste", "generate-docs", "dist/docs"]).expect("parse");
+    assert!(matches!(cli.command, Commands::GenerateDocs(_)));
+
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("lpaste-cli-docs-{}", nonce));
+    let generated = generate_docs(&dir).expect("generate");
+    for page in [
+        "lpaste.1",
+        "lpaste-new.1",
+        "lpaste-folder.1",
+        "localpaste.1",
+    ] {
+        let path = std::path::Path::new("man").join(page);
+        assert!(generated.man_pages.contains(&path), "{page}");
+    }
+    assert!(!generated
+        .man_pages
+        .iter()
+        .any(|path| path.to_string_lossy().contains("__complete")));
+    let server_page = std::fs::read_to_string(dir.join("man/localpaste.1")).expect("server page");
+    assert!(server_page.contains("restore\\-backup"));
+    assert!(server_page.contains("DB_PATH"));
+    for script in ["lpaste.bash", "_lpaste", "lpaste.fish"] {
+        let path = std::path::Path::new("completions").join(script);
+        assert!(generated.completions.contains(&path), "{script}");
+        assert!(dir.join(&path).is_file());
+    }
+    let bash = std::fs::read_to_string(dir.join("completions/lpaste.bash")).expect("bash");
+    assert!(bash.contains("complete-words"));
+    let listed = Cli::try_parse_from(["lpaste", "complete-words", "--", "ne"]).expect("alias");
+    assert!(matches!(listed.command, Commands::Complete(_)));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn attachment_filename_strips_directories() {
+    assert_eq!(
+        attachment_filename("attachment; filename=\"notes.zip\""),
+        Some("notes.zip".to_string())
+    );
+    assert_eq!(
+        attachment_filename("attachment; filename=\"../evil.zip\""),
+        Some("evil.zip".to_string())
+    );
+    assert_eq!(attachment_filename("attachment"), None);
+}
+
 #[test]
 fn cli_parses_versions_and_get_version_subcommands() {
     let versions = Cli::try_parse_from(["lpaste", "versions", "abc", "--limit", "25"])
@@ -645,3 +1085,283 @@ fn default_resolution_connect_hint_only_applies_to_default_source() {
     assert!(default_resolution_connect_hint(ServerResolutionSource::Explicit).is_none());
     assert!(default_resolution_connect_hint(ServerResolutionSource::Discovery).is_none());
 }
+
+#[test]
+fn cli_parses_new_dedupe_flag() {
+    let cli = Cli::try_parse_from(["lpaste", "new", "--dedupe", "-n", "notes"])
+        .expect("cli should parse new --dedupe");
+    match cli.command {
+        Commands::New {
+            name,
+            dedupe,
+            folder,
+            ..
+        } => {
+            assert_eq!(name.as_deref(), Some("notes"));
+            assert!(dedupe);
+            assert_eq!(folder, None);
+        }
+        _ => panic!("expected new command"),
+    }
+
+    let cli = Cli::try_parse_from(["lpaste", "new", "--folder", "work/project/logs"])
+        .expect("cli should parse new --folder");
+    match cli.command {
+        Commands::New { folder, .. } => assert_eq!(folder.as_deref(), Some("work/project/logs")),
+        _ => panic!("expected new command"),
+    }
+}
+
+#[test]
+fn cli_parses_new_stdin_multi_and_splits_snippets() {
+    use super::new_multi::{batch_body, split_snippets, DEFAULT_SPLIT_PATTERN};
+
+    let cli = Cli::try_parse_from(["lpaste", "new", "--stdin-multi", "-n", "snip"])
+        .expect("cli should parse new --stdin-multi");
+    match cli.command {
+        Commands::New {
+            stdin_multi, split, ..
+        } => {
+            assert!(stdin_multi);
+            assert_eq!(split, DEFAULT_SPLIT_PATTERN);
+        }
+        _ => panic!("expected new command"),
+    }
+    assert!(Cli::try_parse_from(["lpaste", "new", "--split", "^==$"]).is_err());
+    assert!(Cli::try_parse_from(["lpaste", "new", "--stdin-multi", "--dedupe"]).is_err());
+
+    let snippets = split_snippets(
+        "---\none\n---\r\ntwo\n--- not a delimiter\n---\n\n",
+        "^---$",
+    )
+    .expect("valid pattern");
+    assert_eq!(snippets, vec!["one\n", "two\n--- not a delimiter\n"]);
+    assert!(split_snippets("text", "(").is_err());
+
+    let body = batch_body(snippets.clone(), Some("snip"), None);
+    assert_eq!(body[0]["name"], "snip-1");
+    assert_eq!(body[1]["content"], "two\n--- not a delimiter\n");
+    assert!(body[0].get("folder_path").is_none());
+
+    let filed = batch_body(snippets, None, Some("work/logs"));
+    assert_eq!(filed[1]["folder_path"], "work/logs");
+}
+
+#[test]
+fn large_json_bodies_are_gzip_encoded() {
+    use super::compression::{encode_json_body, COMPRESS_BODY_THRESHOLD_BYTES};
+
+    let small = serde_json::json!({ "content": "hello" });
+    let (bytes, compressed) = encode_json_body(&small);
+    assert!(!compressed);
+    assert_eq!(bytes, small.to_string().into_bytes());
+
+    let large = serde_json::json!({ "content": "x".repeat(COMPRESS_BODY_THRESHOLD_BYTES) });
+    let (bytes, compressed) = encode_json_body(&large);
+    assert!(compressed);
+    assert!(bytes.len() < COMPRESS_BODY_THRESHOLD_BYTES);
+    let mut decoded = String::new();
+    flate2::read::GzDecoder::new(bytes.as_slice())
+        .read_to_string(&mut decoded)
+        .expect("gzip body should decode");
+    assert_eq!(decoded, large.to_string());
+}
+
+#[test]
+fn complete_protocol_resolves_subcommands_flags_and_paste_id_slots() {
+    use super::complete::{completion_target, CompletionTarget};
+
+    let root = Cli::command();
+    let target = |words: &[&str]| {
+        let words: Vec<String> = words.iter().map(|word| word.to_string()).collect();
+        completion_target(&root, &words)
+    };
+    let values = |target: CompletionTarget| -> Vec<String> {
+        match target {
+            CompletionTarget::Values(values) => values.into_iter().map(|(v, _)| v).collect(),
+            CompletionTarget::PasteIds(prefix) => panic!("unexpected id slot for '{}'", prefix),
+        }
+    };
+
+    let top = values(target(&["ge"]));
+    assert!(top.contains(&"get".to_string()));
+    assert!(top.contains(&"get-version".to_string()));
+    assert!(!values(target(&["__"])).contains(&"__complete".to_string()));
+    assert!(values(target(&["get", "--ser"])).contains(&"--server".to_string()));
+    assert!(values(target(&["folder", ""])).contains(&"list".to_string()));
+
+    assert_eq!(
+        target(&["get", "3f"]),
+        CompletionTarget::PasteIds("3f".to_string())
+    );
+    assert_eq!(
+        target(&["-s", "http://x", "diff", "left", "--left-version", "5", ""]),
+        CompletionTarget::PasteIds(String::new())
+    );
+    assert!(values(target(&["get", "id", ""])).is_empty());
+    assert!(values(target(&["get-version", "id", ""])).is_empty());
+    assert!(values(target(&["new", "--file", ""])).is_empty());
+}
+
+#[test]
+fn paste_id_candidates_match_id_or_name_prefix() {
+    use super::complete::paste_id_candidates;
+
+    let rows = vec![
+        serde_json::json!({ "id": "3f2a-1", "name": "alpha" }),
+        serde_json::json!({ "id": "9c00-2", "name": "Notes\tdraft" }),
+    ];
+    assert_eq!(
+        paste_id_candidates(&rows, "3f"),
+        vec![("3f2a-1".to_string(), "alpha".to_string())]
+    );
+    assert_eq!(
+        paste_id_candidates(&rows, "no"),
+        vec![("9c00-2".to_string(), "Notes draft".to_string())]
+    );
+    assert_eq!(paste_id_candidates(&rows, "").len(), 2);
+}
+
+#[test]
+fn completion_rows_list_recent_opens_before_other_pastes() {
+    use super::complete::recent_first;
+
+    let recent = vec![serde_json::json!({ "id": "b", "client": "gui" })];
+    let updated = vec![
+        serde_json::json!({ "id": "a" }),
+        serde_json::json!({ "id": "b" }),
+    ];
+    let ids: Vec<_> = recent_first(recent, updated)
+        .iter()
+        .map(|row| row["id"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(ids, vec!["b", "a"]);
+}
+
+#[test]
+fn cli_parses_dynamic_completions_and_hidden_complete_command() {
+    let cli = Cli::try_parse_from(["lpaste", "completions", "zsh", "--dynamic"])
+        .expect("cli should parse completions --dynamic");
+    assert!(matches!(cli.command, Commands::Completions(args) if args.dynamic));
+
+    let cli = Cli::try_parse_from(["lpaste", "__complete", "--", "get", "--json", ""])
+        .expect("cli should parse __complete words");
+    match cli.command {
+        Commands::Complete(args) => assert_eq!(args.words, vec!["get", "--json", ""]),
+        _ => panic!("expected __complete command"),
+    }
+}
+
+/// Fake API that accepts `burst` writes, then answers every other request
+/// with `429` until the client retries, like the server's token bucket.
+fn spawn_rate_limited_api(burst: usize) -> (String, mpsc::Receiver<(usize, usize)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let url = format!("http://{}", listener.local_addr().expect("listener addr"));
+    let (done_tx, done_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let (mut created, mut limited) = (0usize, 0usize);
+        let mut limit_next = false;
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                break;
+            };
+            let mut request = Vec::new();
+            let mut buf = [0_u8; 4096];
+            while let Ok(read) = stream.read(&mut buf) {
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..read]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(split) = text.find("\r\n\r\n") {
+                    let length = text[..split]
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= split + 4 + length {
+                        break;
+                    }
+                }
+            }
+            let response = if created >= burst && limit_next {
+                limit_next = false;
+                limited += 1;
+                let body = r#"{"error":"Too many write requests; retry later"}"#;
+                format!(
+                    "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 1\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            } else {
+                limit_next = true;
+                created += 1;
+                let body = format!(
+                    r#"{{"id":"paste-{}","name":"Snippet {}"}}"#,
+                    created, created
+                );
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            };
+            let _ = stream.write_all(response.as_bytes());
+            let _ = done_tx.send((created, limited));
+        }
+    });
+    (url, done_rx)
+}
+
+#[tokio::test]
+async fn import_past_the_rate_limit_burst_waits_out_429s() {
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock")
+        .as_nanos();
+    let source = std::env::temp_dir().join(format!("lpaste-import-burst-{}.md", nonce));
+    let markdown: String = (0..4)
+        .map(|index| format!("```sh\necho {}\n```\n\n", index))
+        .collect();
+    std::fs::write(&source, markdown).expect("write markdown");
+    let (url, progress) = spawn_rate_limited_api(2);
+
+    super::import::run_import(
+        &reqwest::Client::new(),
+        url.as_str(),
+        ServerResolutionSource::Explicit,
+        super::import::ImportArgs {
+            format: ImportFormatArg::Markdown,
+            path: source.clone(),
+            dry_run: false,
+        },
+        true,
+        false,
+    )
+    .await
+    .expect("import succeeds after waiting out 429s");
+    let _ = std::fs::remove_file(&source);
+
+    let (created, limited) = progress.try_iter().last().expect("requests served");
+    assert_eq!(created, 4);
+    assert_eq!(limited, 2);
+}
+
+#[test]
+fn retry_after_reads_seconds_and_caps_the_wait() {
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+    let mut headers = HeaderMap::new();
+    assert_eq!(super::retry::retry_after(&headers), Duration::from_secs(1));
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+    assert_eq!(super::retry::retry_after(&headers), Duration::from_secs(3));
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("3600"));
+    assert_eq!(super::retry::retry_after(&headers), Duration::from_secs(30));
+    headers.insert(
+        RETRY_AFTER,
+        HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+    );
+    assert_eq!(super::retry::retry_after(&headers), Duration::from_secs(1));
+}
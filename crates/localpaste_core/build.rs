@@ -0,0 +1,76 @@
+//! Embeds the git commit and build date reported by `--build-info`.
+//!
+//! Packagers building from a source tarball (no `.git`) can pass
+//! `LOCALPASTE_GIT_HASH`; `SOURCE_DATE_EPOCH` pins the date for reproducible
+//! builds.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+fn git_hash(repo_root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .current_dir(repo_root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let hash = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!hash.is_empty()).then_some(hash)
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(
+        env::var("CARGO_MANIFEST_DIR").expect("missing CARGO_MANIFEST_DIR for build script"),
+    );
+    let repo_root = manifest_dir.join("../..");
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=LOCALPASTE_GIT_HASH");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    // Watch HEAD and the branch ref it names so new commits refresh the hash.
+    // Missing paths would rerun the script on every build, so only watch
+    // files that exist.
+    let git_dir = repo_root.join(".git");
+    let head = git_dir.join("HEAD");
+    let branch_ref = std::fs::read_to_string(&head).ok().and_then(|head| {
+        head.trim()
+            .strip_prefix("ref: ")
+            .map(|name| git_dir.join(name))
+    });
+    for path in std::iter::once(head).chain(branch_ref) {
+        if path.exists() {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+    }
+
+    let hash = env::var("LOCALPASTE_GIT_HASH")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .or_else(|| git_hash(&repo_root))
+        .unwrap_or_else(|| "unknown".to_string());
+    let built_at = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .unwrap_or_else(chrono::Utc::now);
+
+    println!("cargo:rustc-env=LOCALPASTE_GIT_HASH={}", hash);
+    println!(
+        "cargo:rustc-env=LOCALPASTE_BUILD_DATE={}",
+        built_at.format("%Y-%m-%d")
+    );
+    println!(
+        "cargo:rustc-env=LOCALPASTE_BUILD_TARGET={}",
+        env::var("TARGET").unwrap_or_default()
+    );
+    println!(
+        "cargo:rustc-env=LOCALPASTE_BUILD_PROFILE={}",
+        env::var("PROFILE").unwrap_or_default()
+    );
+}
@@ -0,0 +1,576 @@
+//! Minimal store-only ZIP writer and reader for folder exports.
+//!
+//! Entries are written uncompressed with UTF-8 names, which every common unzip
+//! tool reads and keeps the writer dependency-free. Archives stream into any
+//! [`Write`], and entries, offsets, or counts past the classic 4 GiB / 65535
+//! limits switch to ZIP64 records. [`read_stored_zip`] reads such archives
+//! back for tooling; it does not decompress.
+
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use std::io::Write;
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_SIGNATURE: u32 = 0x0605_4b50;
+const ZIP64_END_OF_CENTRAL_SIGNATURE: u32 = 0x0606_4b50;
+const ZIP64_LOCATOR_SIGNATURE: u32 = 0x0706_4b50;
+/// Extra field id of the ZIP64 extended information block.
+const ZIP64_EXTRA_ID: u16 = 0x0001;
+/// ZIP 2.0, the baseline every extractor supports.
+const VERSION: u16 = 20;
+/// ZIP 4.5, required to read ZIP64 records.
+const VERSION_ZIP64: u16 = 45;
+/// 32-bit field value meaning "see the ZIP64 record".
+const ZIP64_U32: u32 = u32::MAX;
+/// 16-bit field value meaning "see the ZIP64 record".
+const ZIP64_U16: u16 = u16::MAX;
+/// General purpose flag bit 11: names are UTF-8.
+const FLAG_UTF8: u16 = 0x0800;
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut idx = 0;
+    while idx < 256 {
+        let mut crc = idx as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[idx] = crc;
+        idx += 1;
+    }
+    table
+};
+
+/// CRC-32 (IEEE) checksum as used by ZIP entries.
+pub fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, byte| {
+        CRC32_TABLE[((crc ^ u32::from(*byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// Converts a timestamp into MS-DOS `(time, date)` fields, clamped to 1980.
+fn dos_datetime(at: DateTime<Utc>) -> (u16, u16) {
+    if at.year() < 1980 {
+        return (0, (1 << 5) | 1);
+    }
+    let time = (at.hour() << 11) | (at.minute() << 5) | (at.second() / 2);
+    let date = (((at.year() - 1980).min(127) as u32) << 9) | (at.month() << 5) | at.day();
+    (time as u16, date as u16)
+}
+
+struct CentralEntry {
+    name: String,
+    crc: u32,
+    size: u64,
+    time: u16,
+    date: u16,
+    offset: u64,
+}
+
+/// Streams a ZIP archive into `W`; the default builds it in memory.
+pub struct ZipWriter<W: Write = Vec<u8>> {
+    out: W,
+    written: u64,
+    entries: Vec<CentralEntry>,
+}
+
+impl Default for ZipWriter<Vec<u8>> {
+    fn default() -> Self {
+        Self::from_writer(Vec::new())
+    }
+}
+
+fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u64(out: &mut Vec<u8>, value: u64) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+/// `value` as a 32-bit field, or the ZIP64 marker when it does not fit.
+fn field_u32(value: u64) -> u32 {
+    u32::try_from(value)
+        .ok()
+        .filter(|v| *v != ZIP64_U32)
+        .unwrap_or(ZIP64_U32)
+}
+
+impl ZipWriter<Vec<u8>> {
+    /// Creates an empty in-memory archive.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<W: Write> ZipWriter<W> {
+    /// Creates an empty archive that writes into `out` as entries are added.
+    pub fn from_writer(out: W) -> Self {
+        Self {
+            out,
+            written: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    fn emit(&mut self, bytes: &[u8]) -> Result<(), String> {
+        self.out
+            .write_all(bytes)
+            .map_err(|err| format!("writing archive failed: {}", err))?;
+        self.written += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// Appends a stored file entry.
+    ///
+    /// # Arguments
+    /// - `name`: Entry path using `/` separators.
+    /// - `data`: File contents.
+    /// - `modified`: Modification time recorded for the entry.
+    ///
+    /// # Errors
+    /// Returns an error when the name is longer than 65535 bytes or the
+    /// underlying writer fails.
+    pub fn add_file(
+        &mut self,
+        name: &str,
+        data: &[u8],
+        modified: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let size = data.len() as u64;
+        let name_len =
+            u16::try_from(name.len()).map_err(|_| format!("entry name '{}' is too long", name))?;
+        let crc = crc32(data);
+        let (time, date) = dos_datetime(modified);
+        let zip64 = field_u32(size) == ZIP64_U32;
+
+        let mut header = Vec::with_capacity(30 + name.len() + 20);
+        let out = &mut header;
+        put_u32(out, LOCAL_HEADER_SIGNATURE);
+        put_u16(out, if zip64 { VERSION_ZIP64 } else { VERSION });
+        put_u16(out, FLAG_UTF8);
+        put_u16(out, 0); // stored
+        put_u16(out, time);
+        put_u16(out, date);
+        put_u32(out, crc);
+        put_u32(out, field_u32(size));
+        put_u32(out, field_u32(size));
+        put_u16(out, name_len);
+        put_u16(out, if zip64 { 20 } else { 0 });
+        out.extend_from_slice(name.as_bytes());
+        if zip64 {
+            // The local ZIP64 block carries both sizes whenever present.
+            put_u16(out, ZIP64_EXTRA_ID);
+            put_u16(out, 16);
+            put_u64(out, size);
+            put_u64(out, size);
+        }
+        let offset = self.written;
+        self.emit(&header)?;
+        self.emit(data)?;
+
+        self.entries.push(CentralEntry {
+            name: name.to_string(),
+            crc,
+            size,
+            time,
+            date,
+            offset,
+        });
+        Ok(())
+    }
+
+    /// Writes the central directory and returns the writer.
+    ///
+    /// # Errors
+    /// Returns an error when the underlying writer fails.
+    pub fn finish(mut self) -> Result<W, String> {
+        let central_start = self.written;
+        let entries = std::mem::take(&mut self.entries);
+        for entry in &entries {
+            // Only the fields that overflow appear in the ZIP64 block, in
+            // this fixed order.
+            let mut extra = Vec::new();
+            if field_u32(entry.size) == ZIP64_U32 {
+                put_u64(&mut extra, entry.size);
+                put_u64(&mut extra, entry.size);
+            }
+            if field_u32(entry.offset) == ZIP64_U32 {
+                put_u64(&mut extra, entry.offset);
+            }
+            let version = if extra.is_empty() {
+                VERSION
+            } else {
+                VERSION_ZIP64
+            };
+            let mut record = Vec::with_capacity(46 + entry.name.len() + 4 + extra.len());
+            let out = &mut record;
+            put_u32(out, CENTRAL_HEADER_SIGNATURE);
+            put_u16(out, version);
+            put_u16(out, version);
+            put_u16(out, FLAG_UTF8);
+            put_u16(out, 0);
+            put_u16(out, entry.time);
+            put_u16(out, entry.date);
+            put_u32(out, entry.crc);
+            put_u32(out, field_u32(entry.size));
+            put_u32(out, field_u32(entry.size));
+            put_u16(out, entry.name.len() as u16);
+            put_u16(
+                out,
+                if extra.is_empty() {
+                    0
+                } else {
+                    4 + extra.len() as u16
+                },
+            );
+            put_u16(out, 0); // comment length
+            put_u16(out, 0); // disk number
+            put_u16(out, 0); // internal attributes
+            put_u32(out, 0); // external attributes
+            put_u32(out, field_u32(entry.offset));
+            out.extend_from_slice(entry.name.as_bytes());
+            if !extra.is_empty() {
+                put_u16(out, ZIP64_EXTRA_ID);
+                put_u16(out, extra.len() as u16);
+                out.extend_from_slice(&extra);
+            }
+            self.emit(&record)?;
+        }
+        let central_size = self.written - central_start;
+        let count = entries.len() as u64;
+        let count_u16 = u16::try_from(count)
+            .ok()
+            .filter(|v| *v != ZIP64_U16)
+            .unwrap_or(ZIP64_U16);
+
+        let mut end = Vec::with_capacity(56 + 20 + 22);
+        let needs_zip64 = count_u16 == ZIP64_U16
+            || field_u32(central_start) == ZIP64_U32
+            || field_u32(central_size) == ZIP64_U32;
+        if needs_zip64 {
+            let zip64_end_offset = self.written;
+            let out = &mut end;
+            put_u32(out, ZIP64_END_OF_CENTRAL_SIGNATURE);
+            put_u64(out, 44); // record size after this field
+            put_u16(out, VERSION_ZIP64);
+            put_u16(out, VERSION_ZIP64);
+            put_u32(out, 0); // this disk
+            put_u32(out, 0); // disk with the central directory
+            put_u64(out, count);
+            put_u64(out, count);
+            put_u64(out, central_size);
+            put_u64(out, central_start);
+            put_u32(out, ZIP64_LOCATOR_SIGNATURE);
+            put_u32(out, 0);
+            put_u64(out, zip64_end_offset);
+            put_u32(out, 1); // total disks
+        }
+        let out = &mut end;
+        put_u32(out, END_OF_CENTRAL_SIGNATURE);
+        put_u16(out, 0);
+        put_u16(out, 0);
+        put_u16(out, count_u16);
+        put_u16(out, count_u16);
+        put_u32(out, field_u32(central_size));
+        put_u32(out, field_u32(central_start));
+        put_u16(out, 0);
+        self.emit(&end)?;
+        self.out
+            .flush()
+            .map_err(|err| format!("writing archive failed: {}", err))?;
+        Ok(self.out)
+    }
+}
+
+/// One file read back by [`read_stored_zip`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZipEntry {
+    /// Entry path using `/` separators.
+    pub name: String,
+    /// File contents.
+    pub data: Vec<u8>,
+}
+
+/// `len` bytes at `at`, or an error when the archive ends first.
+fn field_bytes(bytes: &[u8], at: usize, len: usize) -> Result<&[u8], String> {
+    at.checked_add(len)
+        .and_then(|end| bytes.get(at..end))
+        .ok_or_else(|| "archive is truncated".to_string())
+}
+
+fn get_u16(bytes: &[u8], at: usize) -> Result<u16, String> {
+    let raw = field_bytes(bytes, at, 2)?;
+    Ok(u16::from_le_bytes([raw[0], raw[1]]))
+}
+
+fn get_u32(bytes: &[u8], at: usize) -> Result<u32, String> {
+    let raw = field_bytes(bytes, at, 4)?;
+    Ok(u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]))
+}
+
+fn get_u64(bytes: &[u8], at: usize) -> Result<u64, String> {
+    let raw = field_bytes(bytes, at, 8)?;
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(raw);
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn to_offset(value: u64) -> Result<usize, String> {
+    usize::try_from(value).map_err(|_| "archive is truncated".to_string())
+}
+
+/// Reads every entry of a store-only archive such as [`ZipWriter`] produces.
+///
+/// ZIP64 counts, sizes, and offsets are honoured.
+///
+/// # Returns
+/// Entries in central directory order.
+///
+/// # Errors
+/// Returns an error when the archive is truncated or malformed, an entry is
+/// compressed, or an entry's CRC-32 does not match its contents.
+pub fn read_stored_zip(bytes: &[u8]) -> Result<Vec<ZipEntry>, String> {
+    let last = bytes
+        .len()
+        .checked_sub(22)
+        .ok_or_else(|| "archive is truncated".to_string())?;
+    // The end record sits before a comment of at most 65535 bytes.
+    let end = (last.saturating_sub(usize::from(u16::MAX))..=last)
+        .rev()
+        .find(|at| get_u32(bytes, *at) == Ok(END_OF_CENTRAL_SIGNATURE))
+        .ok_or_else(|| "missing end of central directory record".to_string())?;
+    let mut count = u64::from(get_u16(bytes, end + 10)?);
+    let mut central_start = u64::from(get_u32(bytes, end + 16)?);
+    if count == u64::from(ZIP64_U16) || central_start == u64::from(ZIP64_U32) {
+        let locator = end
+            .checked_sub(20)
+            .filter(|at| get_u32(bytes, *at) == Ok(ZIP64_LOCATOR_SIGNATURE))
+            .ok_or_else(|| "missing ZIP64 end of central directory locator".to_string())?;
+        let record = to_offset(get_u64(bytes, locator + 8)?)?;
+        if get_u32(bytes, record)? != ZIP64_END_OF_CENTRAL_SIGNATURE {
+            return Err("missing ZIP64 end of central directory record".to_string());
+        }
+        count = get_u64(bytes, record + 32)?;
+        central_start = get_u64(bytes, record + 48)?;
+    }
+
+    let mut at = to_offset(central_start)?;
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        if get_u32(bytes, at)? != CENTRAL_HEADER_SIGNATURE {
+            return Err("corrupt central directory".to_string());
+        }
+        let method = get_u16(bytes, at + 10)?;
+        let crc = get_u32(bytes, at + 16)?;
+        let compressed = get_u32(bytes, at + 20)?;
+        let mut size = u64::from(get_u32(bytes, at + 24)?);
+        let name_len = usize::from(get_u16(bytes, at + 28)?);
+        let extra_len = usize::from(get_u16(bytes, at + 30)?);
+        let comment_len = usize::from(get_u16(bytes, at + 32)?);
+        let mut offset = u64::from(get_u32(bytes, at + 42)?);
+        let name = String::from_utf8(field_bytes(bytes, at + 46, name_len)?.to_vec())
+            .map_err(|_| "entry name is not UTF-8".to_string())?;
+        if method != 0 {
+            return Err(format!(
+                "entry '{}' is compressed; only stored archives are supported",
+                name
+            ));
+        }
+
+        // Overflowing fields appear in the ZIP64 block in a fixed order.
+        let mut extra = field_bytes(bytes, at + 46 + name_len, extra_len)?;
+        while extra.len() >= 4 {
+            let id = get_u16(extra, 0)?;
+            let len = usize::from(get_u16(extra, 2)?);
+            let block = field_bytes(extra, 4, len)?;
+            if id == ZIP64_EXTRA_ID {
+                let mut field = 0;
+                if size == u64::from(ZIP64_U32) {
+                    size = get_u64(block, field)?;
+                    field += 8;
+                }
+                if compressed == ZIP64_U32 {
+                    field += 8;
+                }
+                if offset == u64::from(ZIP64_U32) {
+                    offset = get_u64(block, field)?;
+                }
+            }
+            extra = &extra[4 + len..];
+        }
+
+        let local = to_offset(offset)?;
+        if get_u32(bytes, local)? != LOCAL_HEADER_SIGNATURE {
+            return Err(format!("missing local header for '{}'", name));
+        }
+        let data_start = local
+            + 30
+            + usize::from(get_u16(bytes, local + 26)?)
+            + usize::from(get_u16(bytes, local + 28)?);
+        let data = field_bytes(bytes, data_start, to_offset(size)?)?;
+        if crc32(data) != crc {
+            return Err(format!("checksum mismatch for '{}'", name));
+        }
+        entries.push(ZipEntry {
+            name,
+            data: data.to_vec(),
+        });
+        at += 46 + name_len + extra_len + comment_len;
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn crc32_matches_reference_values() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"hello"), 0x3610_a686);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn zip_writer_emits_local_central_and_end_records() {
+        let at = Utc.with_ymd_and_hms(2024, 5, 6, 7, 8, 10).unwrap();
+        let mut zip = ZipWriter::new();
+        zip.add_file("a/b.txt", b"hello", at).expect("add");
+        zip.add_file("manifest.json", b"{}", at).expect("add");
+        let bytes = zip.finish().expect("finish");
+
+        assert_eq!(&bytes[..4], &LOCAL_HEADER_SIGNATURE.to_le_bytes());
+        let end = bytes.len() - 22;
+        assert_eq!(
+            &bytes[end..end + 4],
+            &END_OF_CENTRAL_SIGNATURE.to_le_bytes()
+        );
+        assert_eq!(u16::from_le_bytes([bytes[end + 10], bytes[end + 11]]), 2);
+        let central_start = u32::from_le_bytes(bytes[end + 16..end + 20].try_into().unwrap());
+        assert_eq!(
+            &bytes[central_start as usize..central_start as usize + 4],
+            &CENTRAL_HEADER_SIGNATURE.to_le_bytes()
+        );
+        assert_eq!(
+            dos_datetime(at),
+            ((7 << 11) | (8 << 5) | 5, (44 << 9) | (5 << 5) | 6)
+        );
+    }
+
+    fn end_record(bytes: &[u8]) -> &[u8] {
+        &bytes[bytes.len() - 22..]
+    }
+
+    #[test]
+    fn zip_writer_switches_to_zip64_past_65535_entries() {
+        let at = Utc.with_ymd_and_hms(2024, 5, 6, 7, 8, 10).unwrap();
+        let mut zip = ZipWriter::new();
+        for idx in 0..=u32::from(u16::MAX) {
+            zip.add_file(format!("{}", idx).as_str(), b"", at)
+                .expect("add");
+        }
+        let bytes = zip.finish().expect("finish");
+
+        let end = end_record(&bytes);
+        assert_eq!(&end[..4], &END_OF_CENTRAL_SIGNATURE.to_le_bytes());
+        assert_eq!(u16::from_le_bytes([end[10], end[11]]), ZIP64_U16);
+        let locator = &bytes[bytes.len() - 22 - 20..bytes.len() - 22];
+        assert_eq!(&locator[..4], &ZIP64_LOCATOR_SIGNATURE.to_le_bytes());
+        let zip64_end = u64::from_le_bytes(locator[8..16].try_into().unwrap()) as usize;
+        let record = &bytes[zip64_end..zip64_end + 56];
+        assert_eq!(&record[..4], &ZIP64_END_OF_CENTRAL_SIGNATURE.to_le_bytes());
+        assert_eq!(
+            u64::from_le_bytes(record[32..40].try_into().unwrap()),
+            65_536
+        );
+        let entries = read_stored_zip(&bytes).expect("read");
+        assert_eq!(entries.len(), 65_536);
+        assert_eq!(entries[65_535].name, "65535");
+    }
+
+    #[test]
+    fn read_stored_zip_round_trips_writer_output() {
+        let at = Utc.with_ymd_and_hms(2024, 5, 6, 7, 8, 10).unwrap();
+        let mut zip = ZipWriter::new();
+        zip.add_file("a/b.txt", b"hello", at).expect("add");
+        zip.add_file("empty.md", b"", at).expect("add");
+        let bytes = zip.finish().expect("finish");
+
+        let entries = read_stored_zip(&bytes).expect("read");
+        assert_eq!(
+            entries,
+            vec![
+                ZipEntry {
+                    name: "a/b.txt".to_string(),
+                    data: b"hello".to_vec(),
+                },
+                ZipEntry {
+                    name: "empty.md".to_string(),
+                    data: Vec::new(),
+                },
+            ]
+        );
+
+        let mut corrupt = bytes.clone();
+        corrupt[30 + "a/b.txt".len()] ^= 0xff;
+        assert!(read_stored_zip(&corrupt)
+            .unwrap_err()
+            .contains("checksum mismatch"));
+        assert!(read_stored_zip(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn zip_writer_moves_offsets_past_4_gib_into_zip64_extras() {
+        let at = Utc.with_ymd_and_hms(2024, 5, 6, 7, 8, 10).unwrap();
+        // Start as if 5 GiB were already written, without allocating them.
+        let offset = 5u64 << 30;
+        let mut zip = ZipWriter {
+            out: Vec::new(),
+            written: offset,
+            entries: Vec::new(),
+        };
+        zip.add_file("late.txt", b"hello", at).expect("add");
+        let bytes = zip.finish().expect("finish");
+
+        let central = 30 + "late.txt".len() + 5;
+        assert_eq!(
+            &bytes[central..central + 4],
+            &CENTRAL_HEADER_SIGNATURE.to_le_bytes()
+        );
+        assert_eq!(
+            u16::from_le_bytes([bytes[central + 6], bytes[central + 7]]),
+            VERSION_ZIP64
+        );
+        let stored_offset =
+            u32::from_le_bytes(bytes[central + 42..central + 46].try_into().unwrap());
+        assert_eq!(stored_offset, ZIP64_U32);
+        let extra = central + 46 + "late.txt".len();
+        assert_eq!(
+            u16::from_le_bytes([bytes[extra], bytes[extra + 1]]),
+            ZIP64_EXTRA_ID
+        );
+        assert_eq!(
+            u64::from_le_bytes(bytes[extra + 4..extra + 12].try_into().unwrap()),
+            offset
+        );
+        // The central directory itself starts past 4 GiB, so the end record
+        // defers to ZIP64 as well.
+        let end = end_record(&bytes);
+        assert_eq!(
+            u32::from_le_bytes(end[16..20].try_into().unwrap()),
+            ZIP64_U32
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! `--build-info` report shared by the LocalPaste binaries.
+//!
+//! The commit, date, target, and profile come from `build.rs`; the feature
+//! list reflects what this build of `localpaste_core` was compiled with,
+//! plus any binary-specific features the caller adds.
+
+use crate::config::{api_addr_file_path_for_db_path, db_path_from_env_or_default};
+use crate::settings::settings_file_path_for_db_path;
+
+/// Short commit hash the binary was built from, or `unknown`.
+pub const GIT_HASH: &str = env!("LOCALPASTE_GIT_HASH");
+/// Build date as `YYYY-MM-DD` (UTC, or `SOURCE_DATE_EPOCH` when set).
+pub const BUILD_DATE: &str = env!("LOCALPASTE_BUILD_DATE");
+/// Target triple the binary was compiled for.
+pub const BUILD_TARGET: &str = env!("LOCALPASTE_BUILD_TARGET");
+/// Cargo profile (`debug` or `release`).
+pub const BUILD_PROFILE: &str = env!("LOCALPASTE_BUILD_PROFILE");
+
+/// Optional `localpaste_core` features and whether this build enabled them.
+pub fn core_features() -> Vec<(&'static str, bool)> {
+    vec![
+        ("export", cfg!(feature = "export")),
+        ("magika", cfg!(feature = "magika")),
+        ("remote-backup", cfg!(feature = "remote-backup")),
+        ("update-check", cfg!(feature = "update-check")),
+        ("url-source", cfg!(feature = "url-source")),
+    ]
+}
+
+/// Render the multi-line `--build-info` report.
+///
+/// # Arguments
+/// - `binary`: Executable name, e.g. `lpaste`.
+/// - `version`: The binary's package version.
+/// - `extra_features`: Features owned by the calling crate, listed after
+///   the core ones.
+///
+/// # Returns
+/// Report text ending in a newline; features print as `+name`/`-name`.
+pub fn render_build_info(binary: &str, version: &str, extra_features: &[(&str, bool)]) -> String {
+    let features: Vec<String> = core_features()
+        .into_iter()
+        .chain(extra_features.iter().copied())
+        .map(|(name, enabled)| format!("{}{}", if enabled { '+' } else { '-' }, name))
+        .collect();
+    let db_path = db_path_from_env_or_default();
+    let db_source = if std::env::var_os("DB_PATH").is_some() {
+        "DB_PATH"
+    } else {
+        "default"
+    };
+    format!(
+        "{binary} {version}\n\
+commit:    {GIT_HASH}\n\
+built:     {BUILD_DATE} ({BUILD_PROFILE}, {BUILD_TARGET})\n\
+features:  {features}\n\
+db path:   {db_path} ({db_source})\n\
+discovery: {discovery}\n\
+settings:  {settings}\n",
+        features = features.join(" "),
+        discovery = api_addr_file_path_for_db_path(db_path.as_str()).display(),
+        settings = settings_file_path_for_db_path(db_path.as_str()).display(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_info_lists_commit_features_and_paths() {
+        let report = render_build_info("lpaste", "1.2.3", &[("otlp", false)]);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "lpaste 1.2.3");
+        assert_eq!(lines[1], format!("commit:    {}", GIT_HASH));
+        assert_eq!(BUILD_DATE.len(), "YYYY-MM-DD".len());
+        let features = lines
+            .iter()
+            .find(|line| line.starts_with("features:"))
+            .expect("features line");
+        assert!(features.contains(if cfg!(feature = "export") {
+            "+export"
+        } else {
+            "-export"
+        }));
+        assert!(features.ends_with("-otlp"));
+        assert!(report.contains(".api-addr"));
+        assert!(report.contains("settings.json"));
+    }
+}
@@ -0,0 +1,184 @@
+//! Patch-level merging for concurrent edits to one paste.
+//!
+//! Each writer sends one [`TextEdit`] made against the text it last saw.
+//! Edits committed since then are applied first: an incoming edit that does
+//! not overlap them is shifted past them, and one that overlaps replaces the
+//! union of both ranges, so the later writer wins the contested region.
+//!
+//! Offsets count Unicode scalar values (`char`s), not bytes.
+
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+
+/// Replace `start..end` with `text`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextEdit {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+fn char_to_byte(content: &str, offset: usize) -> Option<usize> {
+    if offset == 0 {
+        return Some(0);
+    }
+    content
+        .char_indices()
+        .map(|(index, _)| index)
+        .chain(std::iter::once(content.len()))
+        .nth(offset)
+}
+
+impl TextEdit {
+    /// The single edit turning `old` into `new`, found by trimming their
+    /// common prefix and suffix.
+    ///
+    /// # Returns
+    /// `None` when the texts are equal.
+    pub fn between(old: &str, new: &str) -> Option<Self> {
+        if old == new {
+            return None;
+        }
+        let on_boundary = |old_at: usize, new_at: usize| {
+            old.is_char_boundary(old_at) && new.is_char_boundary(new_at)
+        };
+        let mut prefix = old
+            .bytes()
+            .zip(new.bytes())
+            .take_while(|(a, b)| a == b)
+            .count();
+        while !on_boundary(prefix, prefix) {
+            prefix -= 1;
+        }
+        let mut suffix = old
+            .bytes()
+            .rev()
+            .zip(new.bytes().rev())
+            .take(old.len().min(new.len()) - prefix)
+            .take_while(|(a, b)| a == b)
+            .count();
+        while !on_boundary(old.len() - suffix, new.len() - suffix) {
+            suffix -= 1;
+        }
+        let start = old[..prefix].chars().count();
+        Some(Self {
+            start,
+            end: start + old[prefix..old.len() - suffix].chars().count(),
+            text: new[prefix..new.len() - suffix].to_string(),
+        })
+    }
+
+    /// Apply this edit to `content`.
+    ///
+    /// # Errors
+    /// Returns [`AppError::BadRequest`] when the range is reversed or runs
+    /// past the end of `content`.
+    pub fn apply(&self, content: &str) -> Result<String, AppError> {
+        let range = (self.start <= self.end)
+            .then(|| char_to_byte(content, self.start).zip(char_to_byte(content, self.end)))
+            .flatten();
+        let Some((start, end)) = range else {
+            return Err(AppError::BadRequest(format!(
+                "Edit range {}..{} is outside the paste ({} characters)",
+                self.start,
+                self.end,
+                content.chars().count()
+            )));
+        };
+        let mut merged = String::with_capacity(content.len() - (end - start) + self.text.len());
+        merged.push_str(&content[..start]);
+        merged.push_str(&self.text);
+        merged.push_str(&content[end..]);
+        Ok(merged)
+    }
+
+    /// Rewrite this edit, made against the text before `earlier`, so it
+    /// applies to the text after `earlier`.
+    ///
+    /// # Returns
+    /// The rebased edit, and `true` when the ranges overlapped and this edit
+    /// took over `earlier`'s region.
+    pub fn rebase(&self, earlier: &TextEdit) -> (TextEdit, bool) {
+        let inserted_end = earlier.start + earlier.text.chars().count();
+        let shift = |offset: usize| offset + inserted_end - earlier.end;
+        if self.end <= earlier.start {
+            return (self.clone(), false);
+        }
+        if self.start >= earlier.end {
+            let rebased = TextEdit {
+                start: shift(self.start),
+                end: shift(self.end),
+                text: self.text.clone(),
+            };
+            return (rebased, false);
+        }
+        let end = if self.end >= earlier.end {
+            shift(self.end)
+        } else {
+            inserted_end
+        };
+        let rebased = TextEdit {
+            start: self.start.min(earlier.start),
+            end,
+            text: self.text.clone(),
+        };
+        (rebased, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextEdit;
+
+    fn edit(start: usize, end: usize, text: &str) -> TextEdit {
+        TextEdit {
+            start,
+            end,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn between_and_apply_round_trip_on_multibyte_text() {
+        let old = "héllo wörld";
+        let new = "héllo, big wörld";
+        let diff = TextEdit::between(old, new).expect("edit");
+        assert_eq!(diff, edit(5, 5, ", big"));
+        assert_eq!(diff.apply(old).expect("apply"), new);
+        assert_eq!(TextEdit::between(old, old), None);
+        assert_eq!(
+            TextEdit::between("aaa", "aa")
+                .expect("edit")
+                .apply("aaa")
+                .expect("apply"),
+            "aa"
+        );
+        assert!(edit(3, 2, "").apply(old).is_err());
+        assert!(edit(0, 12, "").apply(old).is_err());
+    }
+
+    #[test]
+    fn rebase_shifts_disjoint_edits_and_lets_overlaps_win() {
+        let base = "alpha beta gamma";
+        // Earlier writer renamed "alpha"; the later one edits "gamma".
+        let earlier = edit(0, 5, "ALPHA!");
+        let later = edit(11, 16, "GAMMA");
+        let (rebased, conflicted) = later.rebase(&earlier);
+        assert!(!conflicted);
+        let current = earlier.apply(base).expect("apply");
+        assert_eq!(rebased.apply(&current).expect("apply"), "ALPHA! beta GAMMA");
+
+        // Edits before the earlier one are untouched.
+        let (rebased, conflicted) = edit(0, 0, ">").rebase(&later);
+        assert!(!conflicted);
+        assert_eq!(rebased, edit(0, 0, ">"));
+
+        // Overlapping edits: the later one replaces both ranges.
+        let earlier = edit(6, 10, "BETA");
+        let later = edit(8, 13, "x");
+        let (rebased, conflicted) = later.rebase(&earlier);
+        assert!(conflicted);
+        let current = earlier.apply(base).expect("apply");
+        assert_eq!(rebased.apply(&current).expect("apply"), "alpha xmma");
+    }
+}
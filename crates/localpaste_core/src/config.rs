@@ -7,9 +7,18 @@ use std::str::FromStr;
 use tracing::warn;
 
 use crate::constants::{
-    API_ADDR_FILE_NAME, DEFAULT_AUTO_SAVE_INTERVAL_MS, DEFAULT_MAX_PASTE_SIZE,
-    DEFAULT_PASTE_VERSION_INTERVAL_SECS, DEFAULT_PORT,
+    API_ADDR_FILE_NAME, DEFAULT_AUTO_SAVE_INTERVAL_MS, DEFAULT_BACKUP_SIZE_ALERT_MB,
+    DEFAULT_DB_SIZE_ALERT_MB, DEFAULT_FLUSH_INTERVAL_MS, DEFAULT_MAX_PASTE_SIZE,
+    DEFAULT_PASTE_VERSION_INTERVAL_SECS, DEFAULT_PORT, DEFAULT_SHUTDOWN_DRAIN_SECS,
+    DEFAULT_SLOW_QUERY_THRESHOLD_MS, DEFAULT_WRITE_RATE_LIMIT_BURST,
+    DEFAULT_WRITE_RATE_LIMIT_PER_SEC,
 };
+use crate::db::durability::FlushPolicy;
+use crate::db::users::MultiUserOptions;
+use crate::naming::title::TitleOptions;
+use crate::normalization::NormalizationOptions;
+use crate::size_limits::{PasteSizeLimits, PasteSource};
+use crate::text::CleanupOptions;
 
 /// Runtime configuration for LocalPaste.
 #[derive(Debug, Clone, Deserialize)]
@@ -19,6 +28,15 @@ pub struct Config {
     pub max_paste_size: usize,
     pub auto_save_interval: u64,
     pub auto_backup: bool,
+    /// Per-source and per-folder overrides of `max_paste_size`.
+    #[serde(default)]
+    pub size_limits: PasteSizeLimits,
+    /// When committed writes are fsynced.
+    #[serde(default)]
+    pub flush_policy: FlushPolicy,
+    /// Record which pastes each client opens for `GET /api/recent`.
+    #[serde(default)]
+    pub recent_history: bool,
 }
 
 /// Expand tilde (~) in paths to the user's home directory
@@ -290,6 +308,201 @@ pub fn paste_version_interval_secs_from_env() -> Result<u64, String> {
     resolve_paste_version_interval_secs(IntervalParseMode::Strict)
 }
 
+/// Resolve the slow database operation threshold.
+///
+/// # Returns
+/// `Some(threshold)` from `LOCALPASTE_SLOW_QUERY_MS` (default
+/// [`DEFAULT_SLOW_QUERY_THRESHOLD_MS`]), or `None` when it is set to `0` to
+/// disable slow-operation logging. Malformed values warn and use the default.
+pub fn slow_query_threshold_from_env_or_default() -> Option<std::time::Duration> {
+    const KEY: &str = "LOCALPASTE_SLOW_QUERY_MS";
+    let millis = match env::var(KEY) {
+        Ok(value) => match value.trim().parse::<u64>() {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                warn!(
+                    "Invalid value for {}='{}': {}. Using default {}",
+                    KEY, value, err, DEFAULT_SLOW_QUERY_THRESHOLD_MS
+                );
+                DEFAULT_SLOW_QUERY_THRESHOLD_MS
+            }
+        },
+        Err(_) => DEFAULT_SLOW_QUERY_THRESHOLD_MS,
+    };
+    (millis > 0).then(|| std::time::Duration::from_millis(millis))
+}
+
+const SHUTDOWN_DRAIN_ENV: &str = "LOCALPASTE_SHUTDOWN_DRAIN_SECS";
+
+/// Resolve how long shutdown waits for in-flight requests.
+///
+/// # Returns
+/// `LOCALPASTE_SHUTDOWN_DRAIN_SECS` (default [`DEFAULT_SHUTDOWN_DRAIN_SECS`]);
+/// `0` stops without waiting. Malformed values warn and use the default.
+pub fn shutdown_drain_timeout_from_env_or_default() -> std::time::Duration {
+    std::time::Duration::from_secs(parse_env_number(
+        SHUTDOWN_DRAIN_ENV,
+        DEFAULT_SHUTDOWN_DRAIN_SECS,
+    ))
+}
+
+/// Resolve the shutdown drain timeout in strict mode.
+///
+/// # Returns
+/// The configured drain timeout.
+///
+/// # Errors
+/// Returns an error when `LOCALPASTE_SHUTDOWN_DRAIN_SECS` is malformed.
+pub fn shutdown_drain_timeout_from_env() -> Result<std::time::Duration, String> {
+    parse_env_number_strict(SHUTDOWN_DRAIN_ENV, DEFAULT_SHUTDOWN_DRAIN_SECS)
+        .map(std::time::Duration::from_secs)
+}
+
+const WRITE_RATE_LIMIT_BURST_ENV: &str = "LOCALPASTE_RATE_LIMIT_BURST";
+const WRITE_RATE_LIMIT_PER_SEC_ENV: &str = "LOCALPASTE_RATE_LIMIT_PER_SEC";
+
+/// Per-client token bucket applied to API write requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteRateLimit {
+    /// Writes a client may send back to back before being throttled.
+    pub burst: u32,
+    /// Writes per second refilled into the bucket.
+    pub per_second: u32,
+}
+
+impl WriteRateLimit {
+    fn from_parts(burst: u32, per_second: u32) -> Option<Self> {
+        (burst > 0 && per_second > 0).then_some(Self { burst, per_second })
+    }
+}
+
+/// Resolve the API write rate limit.
+///
+/// # Returns
+/// The limit from `LOCALPASTE_RATE_LIMIT_BURST` and
+/// `LOCALPASTE_RATE_LIMIT_PER_SEC` (defaults [`DEFAULT_WRITE_RATE_LIMIT_BURST`]
+/// and [`DEFAULT_WRITE_RATE_LIMIT_PER_SEC`]), or `None` when either is `0` to
+/// disable limiting. Malformed values warn and use the default.
+pub fn write_rate_limit_from_env_or_default() -> Option<WriteRateLimit> {
+    WriteRateLimit::from_parts(
+        parse_env_number(WRITE_RATE_LIMIT_BURST_ENV, DEFAULT_WRITE_RATE_LIMIT_BURST),
+        parse_env_number(
+            WRITE_RATE_LIMIT_PER_SEC_ENV,
+            DEFAULT_WRITE_RATE_LIMIT_PER_SEC,
+        ),
+    )
+}
+
+/// Resolve the API write rate limit in strict mode.
+///
+/// # Returns
+/// The configured limit, or `None` when limiting is disabled.
+///
+/// # Errors
+/// Returns an error when either rate limit variable is malformed.
+pub fn write_rate_limit_from_env() -> Result<Option<WriteRateLimit>, String> {
+    Ok(WriteRateLimit::from_parts(
+        parse_env_number_strict(WRITE_RATE_LIMIT_BURST_ENV, DEFAULT_WRITE_RATE_LIMIT_BURST)?,
+        parse_env_number_strict(
+            WRITE_RATE_LIMIT_PER_SEC_ENV,
+            DEFAULT_WRITE_RATE_LIMIT_PER_SEC,
+        )?,
+    ))
+}
+
+const DB_SIZE_ALERT_ENV: &str = "LOCALPASTE_DB_SIZE_ALERT_MB";
+const BACKUP_SIZE_ALERT_ENV: &str = "LOCALPASTE_BACKUP_SIZE_ALERT_MB";
+
+/// Sizes above which storage alerts fire; `None` disables that check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageAlertLimits {
+    /// Database file size in bytes.
+    pub database_bytes: Option<u64>,
+    /// Combined size of every backup file in bytes.
+    pub backups_bytes: Option<u64>,
+}
+
+impl StorageAlertLimits {
+    fn from_mb(database_mb: u64, backups_mb: u64) -> Self {
+        let to_bytes = |mb: u64| (mb > 0).then(|| mb.saturating_mul(1024 * 1024));
+        Self {
+            database_bytes: to_bytes(database_mb),
+            backups_bytes: to_bytes(backups_mb),
+        }
+    }
+}
+
+/// Resolve the storage alert limits.
+///
+/// # Returns
+/// Limits from `LOCALPASTE_DB_SIZE_ALERT_MB` and
+/// `LOCALPASTE_BACKUP_SIZE_ALERT_MB` (defaults [`DEFAULT_DB_SIZE_ALERT_MB`]
+/// and [`DEFAULT_BACKUP_SIZE_ALERT_MB`]); `0` disables a check. Malformed
+/// values warn and use the default.
+pub fn storage_alert_limits_from_env_or_default() -> StorageAlertLimits {
+    StorageAlertLimits::from_mb(
+        parse_env_number(DB_SIZE_ALERT_ENV, DEFAULT_DB_SIZE_ALERT_MB),
+        parse_env_number(BACKUP_SIZE_ALERT_ENV, DEFAULT_BACKUP_SIZE_ALERT_MB),
+    )
+}
+
+/// Resolve the storage alert limits in strict mode.
+///
+/// # Returns
+/// The configured limits.
+///
+/// # Errors
+/// Returns an error when either alert variable is malformed.
+pub fn storage_alert_limits_from_env() -> Result<StorageAlertLimits, String> {
+    Ok(StorageAlertLimits::from_mb(
+        parse_env_number_strict(DB_SIZE_ALERT_ENV, DEFAULT_DB_SIZE_ALERT_MB)?,
+        parse_env_number_strict(BACKUP_SIZE_ALERT_ENV, DEFAULT_BACKUP_SIZE_ALERT_MB)?,
+    ))
+}
+
+/// Opt-in flag for per-client recently opened paste history.
+pub const RECENT_HISTORY_ENV: &str = "LOCALPASTE_RECENT_HISTORY";
+
+const FLUSH_POLICY_ENV: &str = "LOCALPASTE_FLUSH_POLICY";
+const FLUSH_INTERVAL_ENV: &str = "LOCALPASTE_FLUSH_INTERVAL_MS";
+
+fn flush_policy_from_parts(name: Option<String>, period_ms: u64) -> Result<FlushPolicy, String> {
+    match name {
+        Some(name) => FlushPolicy::from_name(&name, std::time::Duration::from_millis(period_ms))
+            .map_err(|err| format!("Invalid value for {}='{}': {}", FLUSH_POLICY_ENV, name, err)),
+        None => Ok(FlushPolicy::EveryCommit),
+    }
+}
+
+/// Resolve the database flush policy.
+///
+/// # Returns
+/// The policy named by `LOCALPASTE_FLUSH_POLICY` (`commit`, `interval`, or
+/// `idle`; default `commit`) with the period from
+/// `LOCALPASTE_FLUSH_INTERVAL_MS` (default [`DEFAULT_FLUSH_INTERVAL_MS`]).
+/// Malformed values warn and flush every commit.
+pub fn flush_policy_from_env_or_default() -> FlushPolicy {
+    let name = env::var(FLUSH_POLICY_ENV).ok();
+    let period_ms = parse_env_number(FLUSH_INTERVAL_ENV, DEFAULT_FLUSH_INTERVAL_MS);
+    flush_policy_from_parts(name, period_ms).unwrap_or_else(|err| {
+        warn!("{}. Flushing every commit", err);
+        FlushPolicy::EveryCommit
+    })
+}
+
+/// Resolve the database flush policy in strict mode.
+///
+/// # Returns
+/// The configured policy.
+///
+/// # Errors
+/// Returns an error when the policy name or period is malformed.
+pub fn flush_policy_from_env() -> Result<FlushPolicy, String> {
+    let name = env::var(FLUSH_POLICY_ENV).ok();
+    let period_ms = parse_env_number_strict(FLUSH_INTERVAL_ENV, DEFAULT_FLUSH_INTERVAL_MS)?;
+    flush_policy_from_parts(name, period_ms)
+}
+
 /// Parse a boolean-like environment flag value.
 ///
 /// # Supported Values
@@ -358,7 +571,7 @@ pub fn parse_bool_env_strict(name: &str, default: bool) -> Result<bool, String>
     })
 }
 
-fn parse_env_number<T>(name: &str, default: T) -> T
+pub(crate) fn parse_env_number<T>(name: &str, default: T) -> T
 where
     T: FromStr + Copy + std::fmt::Display,
     <T as FromStr>::Err: std::fmt::Display,
@@ -435,6 +648,9 @@ impl Config {
                 DEFAULT_AUTO_SAVE_INTERVAL_MS,
             ), // 2 seconds
             auto_backup: env_flag_enabled("AUTO_BACKUP"), // Default to false - backups should be explicit
+            size_limits: PasteSizeLimits::from_env(),
+            flush_policy: flush_policy_from_env_or_default(),
+            recent_history: env_flag_enabled(RECENT_HISTORY_ENV),
         }
     }
 
@@ -450,9 +666,17 @@ impl Config {
     pub fn from_env_strict() -> Result<Self, String> {
         let db_path = db_path_from_env_strict()?;
 
-        // Validate snapshot interval envs during strict startup so malformed values
-        // fail fast instead of surfacing later during write operations.
+        // Validate snapshot interval, normalization, title, and cleanup envs during
+        // strict startup so malformed values fail fast instead of surfacing later
+        // during write operations.
         let _ = paste_version_interval_secs_from_env()?;
+        let _ = shutdown_drain_timeout_from_env()?;
+        let _ = write_rate_limit_from_env()?;
+        let _ = storage_alert_limits_from_env()?;
+        let _ = NormalizationOptions::from_env_strict()?;
+        let _ = TitleOptions::from_env_strict()?;
+        let _ = CleanupOptions::clipboard_from_env_strict()?;
+        let _ = MultiUserOptions::from_env_strict()?;
 
         Ok(Self {
             db_path,
@@ -463,21 +687,50 @@ impl Config {
                 DEFAULT_AUTO_SAVE_INTERVAL_MS,
             )?,
             auto_backup: parse_bool_env_strict("AUTO_BACKUP", false)?,
+            size_limits: PasteSizeLimits::from_env_strict()?,
+            flush_policy: flush_policy_from_env()?,
+            recent_history: parse_bool_env_strict(RECENT_HISTORY_ENV, false)?,
         })
     }
+
+    /// Resolve the size limit for a write from `source` into `folder_id`.
+    ///
+    /// # Arguments
+    /// - `source`: Write path producing the content.
+    /// - `folder_id`: Destination folder, when the paste is filed.
+    ///
+    /// # Returns
+    /// Maximum allowed content size in bytes.
+    pub fn max_paste_size_for(&self, source: PasteSource, folder_id: Option<&str>) -> usize {
+        self.size_limits
+            .resolve(self.max_paste_size, source, folder_id)
+    }
+
+    /// Largest size limit any write path or folder can be granted.
+    ///
+    /// # Returns
+    /// The maximum of `max_paste_size` and every configured override.
+    pub fn largest_max_paste_size(&self) -> usize {
+        self.size_limits.largest(self.max_paste_size)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
         api_addr_file_path_for_db_path, db_path_from_env_or_default, db_path_from_env_strict,
-        env_flag_enabled, parse_bool_env, parse_bool_env_strict, parse_env_flag,
-        paste_version_interval_secs_from_env, paste_version_interval_secs_from_env_or_default,
-        resolve_db_path_with_explicit_or_env, Config,
+        env_flag_enabled, flush_policy_from_env, flush_policy_from_env_or_default, parse_bool_env,
+        parse_bool_env_strict, parse_env_flag, paste_version_interval_secs_from_env,
+        paste_version_interval_secs_from_env_or_default, resolve_db_path_with_explicit_or_env,
+        shutdown_drain_timeout_from_env, shutdown_drain_timeout_from_env_or_default,
+        slow_query_threshold_from_env_or_default, storage_alert_limits_from_env,
+        storage_alert_limits_from_env_or_default, write_rate_limit_from_env,
+        write_rate_limit_from_env_or_default, Config, FlushPolicy, StorageAlertLimits,
+        WriteRateLimit,
     };
     use crate::constants::{
         API_ADDR_FILE_NAME, DEFAULT_AUTO_SAVE_INTERVAL_MS, DEFAULT_MAX_PASTE_SIZE,
-        DEFAULT_PASTE_VERSION_INTERVAL_SECS, DEFAULT_PORT,
+        DEFAULT_PASTE_VERSION_INTERVAL_SECS, DEFAULT_PORT, DEFAULT_SLOW_QUERY_THRESHOLD_MS,
     };
     use crate::env::{env_lock, EnvGuard};
     use std::path::PathBuf;
@@ -645,6 +898,111 @@ mod tests {
         }
     }
 
+    #[test]
+    fn slow_query_threshold_defaults_disables_on_zero_and_ignores_garbage() {
+        let _lock = env_lock().lock().expect("env lock");
+        let default = Some(std::time::Duration::from_millis(
+            DEFAULT_SLOW_QUERY_THRESHOLD_MS,
+        ));
+        let _unset = EnvGuard::remove("LOCALPASTE_SLOW_QUERY_MS");
+        assert_eq!(slow_query_threshold_from_env_or_default(), default);
+        let _custom = EnvGuard::set("LOCALPASTE_SLOW_QUERY_MS", "40");
+        assert_eq!(
+            slow_query_threshold_from_env_or_default(),
+            Some(std::time::Duration::from_millis(40))
+        );
+        let _off = EnvGuard::set("LOCALPASTE_SLOW_QUERY_MS", "0");
+        assert_eq!(slow_query_threshold_from_env_or_default(), None);
+        let _bad = EnvGuard::set("LOCALPASTE_SLOW_QUERY_MS", "fast");
+        assert_eq!(slow_query_threshold_from_env_or_default(), default);
+    }
+
+    #[test]
+    fn shutdown_drain_timeout_defaults_and_strict_mode_rejects_garbage() {
+        let _lock = env_lock().lock().expect("env lock");
+        let _unset = EnvGuard::remove("LOCALPASTE_SHUTDOWN_DRAIN_SECS");
+        assert_eq!(
+            shutdown_drain_timeout_from_env_or_default(),
+            std::time::Duration::from_secs(30)
+        );
+        let _custom = EnvGuard::set("LOCALPASTE_SHUTDOWN_DRAIN_SECS", "0");
+        assert_eq!(
+            shutdown_drain_timeout_from_env(),
+            Ok(std::time::Duration::ZERO)
+        );
+        let _bad = EnvGuard::set("LOCALPASTE_SHUTDOWN_DRAIN_SECS", "soon");
+        assert_eq!(
+            shutdown_drain_timeout_from_env_or_default(),
+            std::time::Duration::from_secs(30)
+        );
+        assert!(shutdown_drain_timeout_from_env().is_err());
+    }
+
+    #[test]
+    fn write_rate_limit_defaults_disables_on_zero_and_strict_mode_rejects_garbage() {
+        let _lock = env_lock().lock().expect("env lock");
+        let _burst = EnvGuard::remove("LOCALPASTE_RATE_LIMIT_BURST");
+        let _rate = EnvGuard::remove("LOCALPASTE_RATE_LIMIT_PER_SEC");
+        assert_eq!(
+            write_rate_limit_from_env_or_default(),
+            Some(WriteRateLimit {
+                burst: 120,
+                per_second: 20
+            })
+        );
+        let _off = EnvGuard::set("LOCALPASTE_RATE_LIMIT_PER_SEC", "0");
+        assert_eq!(write_rate_limit_from_env(), Ok(None));
+        let _bad = EnvGuard::set("LOCALPASTE_RATE_LIMIT_BURST", "lots");
+        assert_eq!(write_rate_limit_from_env_or_default(), None);
+        assert!(write_rate_limit_from_env().is_err());
+    }
+
+    #[test]
+    fn storage_alert_limits_convert_mib_and_zero_disables_a_check() {
+        let _lock = env_lock().lock().expect("env lock");
+        let _db = EnvGuard::remove("LOCALPASTE_DB_SIZE_ALERT_MB");
+        let _backups = EnvGuard::set("LOCALPASTE_BACKUP_SIZE_ALERT_MB", "0");
+        assert_eq!(
+            storage_alert_limits_from_env(),
+            Ok(StorageAlertLimits {
+                database_bytes: Some(1024 * 1024 * 1024),
+                backups_bytes: None,
+            })
+        );
+        let _bad = EnvGuard::set("LOCALPASTE_DB_SIZE_ALERT_MB", "big");
+        assert_eq!(
+            storage_alert_limits_from_env_or_default().database_bytes,
+            Some(1024 * 1024 * 1024)
+        );
+        assert!(storage_alert_limits_from_env().is_err());
+    }
+
+    #[test]
+    fn flush_policy_reads_name_and_period() {
+        let _lock = env_lock().lock().expect("env lock");
+        let _policy = EnvGuard::remove("LOCALPASTE_FLUSH_POLICY");
+        let _period = EnvGuard::remove("LOCALPASTE_FLUSH_INTERVAL_MS");
+        assert_eq!(flush_policy_from_env(), Ok(FlushPolicy::EveryCommit));
+
+        let _policy = EnvGuard::set("LOCALPASTE_FLUSH_POLICY", "idle");
+        assert_eq!(
+            flush_policy_from_env(),
+            Ok(FlushPolicy::Idle(std::time::Duration::from_secs(1)))
+        );
+        let _period = EnvGuard::set("LOCALPASTE_FLUSH_INTERVAL_MS", "0");
+        assert!(flush_policy_from_env().is_err());
+        assert_eq!(flush_policy_from_env_or_default(), FlushPolicy::EveryCommit);
+
+        let _period = EnvGuard::set("LOCALPASTE_FLUSH_INTERVAL_MS", "250");
+        let _policy = EnvGuard::set("LOCALPASTE_FLUSH_POLICY", "interval");
+        assert_eq!(
+            flush_policy_from_env(),
+            Ok(FlushPolicy::Interval(std::time::Duration::from_millis(250)))
+        );
+        let _policy = EnvGuard::set("LOCALPASTE_FLUSH_POLICY", "often");
+        assert!(flush_policy_from_env().is_err());
+    }
+
     #[test]
     fn paste_version_interval_parsing_respects_strict_and_permissive_modes() {
         let _lock = env_lock().lock().expect("env lock");
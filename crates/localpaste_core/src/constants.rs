@@ -2,6 +2,10 @@
 
 /// Default API port for LocalPaste.
 pub const DEFAULT_PORT: u16 = 38411;
+/// Default port of the read-only gallery listener, one above the API.
+pub const DEFAULT_GALLERY_PORT: u16 = DEFAULT_PORT + 1;
+/// Successive ports the embedded server tries after the configured one is in use.
+pub const DEFAULT_PORT_FALLBACK_ATTEMPTS: u16 = 10;
 
 /// Default maximum paste size accepted by the API layer.
 pub const DEFAULT_MAX_PASTE_SIZE: usize = 10 * 1024 * 1024;
@@ -12,16 +16,54 @@ pub const MAX_DIFF_INPUT_BYTES: usize = 1024 * 1024;
 pub const DEFAULT_AUTO_SAVE_INTERVAL_MS: u64 = 2_000;
 /// Default minimum interval between persisted paste versions.
 pub const DEFAULT_PASTE_VERSION_INTERVAL_SECS: u64 = 300;
+/// Default duration above which database operations are logged as slow.
+pub const DEFAULT_SLOW_QUERY_THRESHOLD_MS: u64 = 250;
+/// Default time the server waits for in-flight requests after a shutdown signal.
+pub const DEFAULT_SHUTDOWN_DRAIN_SECS: u64 = 30;
+/// Default number of API writes one client may send back to back.
+pub const DEFAULT_WRITE_RATE_LIMIT_BURST: u32 = 120;
+/// Default sustained API writes per second allowed for one client.
+pub const DEFAULT_WRITE_RATE_LIMIT_PER_SEC: u32 = 20;
+/// Default database file size, in MiB, above which storage alerts fire.
+pub const DEFAULT_DB_SIZE_ALERT_MB: u64 = 1024;
+/// Default combined backup size, in MiB, above which storage alerts fire.
+pub const DEFAULT_BACKUP_SIZE_ALERT_MB: u64 = 4096;
+/// Default flush period, in milliseconds, for the `interval` and `idle` flush
+/// policies.
+pub const DEFAULT_FLUSH_INTERVAL_MS: u64 = 1000;
 
 /// Default list and search limits used by GUI list pagination.
 pub const DEFAULT_LIST_PASTES_LIMIT: usize = 512;
 /// Default upper bound for sidebar search result sets.
 pub const DEFAULT_SEARCH_PASTES_LIMIT: usize = 512;
 
+/// Default number of leading lines returned by paste preview snippets.
+pub const PASTE_PREVIEW_DEFAULT_LINES: usize = 10;
+/// Upper bound for caller-requested paste preview line counts.
+pub const PASTE_PREVIEW_MAX_LINES: usize = 50;
+/// Character cap applied to paste preview snippets after line truncation.
+pub const PASTE_PREVIEW_MAX_CHARS: usize = 2_000;
+/// Character cap for newline-collapsed list snippets (`?include_snippet=true`).
+pub const PASTE_LIST_SNIPPET_CHARS: usize = 160;
+/// Longest accepted `expires_in` for paste expiry, ten years in seconds.
+pub const MAX_PASTE_EXPIRES_IN_SECS: u64 = 10 * 365 * 24 * 60 * 60;
+/// Cap on content match ranges per search row (`?include_matches=true`).
+pub const SEARCH_MATCHES_MAX: usize = 100;
+/// Total content bytes one search response may inline (`?include_content=true`).
+pub const SEARCH_CONTENT_MAX_BYTES: usize = 16 * 1024 * 1024;
+
 /// Default base URL for CLI/API clients.
 pub const DEFAULT_CLI_SERVER_URL: &str = "http://localhost:38411";
 /// Discovery filename for the active embedded API address.
 pub const API_ADDR_FILE_NAME: &str = ".api-addr";
+/// Pin filename holding the API port the GUI should prefer on launch.
+pub const API_PORT_PIN_FILE_NAME: &str = ".api-port";
+/// Settings filename stored inside the DB directory.
+pub const SETTINGS_FILE_NAME: &str = "settings.json";
+/// Ledger of backup names pushed to each remote backup target.
+pub const REMOTE_BACKUP_LEDGER_FILE_NAME: &str = ".backup-remote.json";
+/// Marker naming a backup to restore before the database is next opened.
+pub const RESTORE_PENDING_FILE_NAME: &str = ".restore-pending";
 
 /// Process-lifetime owner lock file used to detect active writers safely.
 pub const DB_OWNER_LOCK_FILE_NAME: &str = "db.owner.lock";
@@ -0,0 +1,432 @@
+//! Line-anchored annotations stored beside pastes.
+//!
+//! Rows are keyed by `(paste_id, annotation_id)` like comments. Whenever paste
+//! content changes, the write transaction that stores the new content also
+//! re-anchors the paste's annotations by diffing old and new lines, so ranges
+//! keep pointing at the text they were written about.
+
+use crate::{
+    db::comments::{normalize_author, validated_body},
+    db::paste::deserialize_paste,
+    db::tables::{PASTES, PASTE_ANNOTATIONS},
+    error::AppError,
+    models::{annotation::Annotation, comment::DEFAULT_COMMENT_AUTHOR},
+};
+use chrono::Utc;
+use redb::{ReadableDatabase, ReadableTable};
+use similar::{DiffOp, TextDiff};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Where one old line ended up after an edit, as 0-based new line indexes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineFate {
+    Kept(usize),
+    /// Deleted or rewritten; `new_len` replacement lines start at `new_start`.
+    Replaced {
+        new_start: usize,
+        new_len: usize,
+    },
+}
+
+fn line_fates(old: &str, new: &str) -> Vec<LineFate> {
+    let mut fates = Vec::new();
+    for op in TextDiff::from_lines(old, new).ops() {
+        match *op {
+            DiffOp::Equal { new_index, len, .. } => {
+                fates.extend((new_index..new_index + len).map(LineFate::Kept));
+            }
+            DiffOp::Delete {
+                old_len, new_index, ..
+            } => fates.extend(std::iter::repeat_n(
+                LineFate::Replaced {
+                    new_start: new_index,
+                    new_len: 0,
+                },
+                old_len,
+            )),
+            DiffOp::Replace {
+                old_len,
+                new_index,
+                new_len,
+                ..
+            } => fates.extend(std::iter::repeat_n(
+                LineFate::Replaced {
+                    new_start: new_index,
+                    new_len,
+                },
+                old_len,
+            )),
+            DiffOp::Insert { .. } => {}
+        }
+    }
+    fates
+}
+
+/// Number of lines an annotation may point at; empty content still has one.
+fn line_count(content: &str) -> usize {
+    content.lines().count().max(1)
+}
+
+/// Map a 1-based inclusive line range from `old` content onto `new` content.
+///
+/// Lines inserted inside the range widen it, rewritten edge lines extend it to
+/// cover their replacement, and deleted edge lines shrink it. When every line
+/// in the range was deleted outright, the range collapses onto the line that
+/// now sits where they were.
+///
+/// # Returns
+/// `(start_line, end_line, detached)`, where `detached` reports that none of
+/// the annotated text survived.
+pub fn reanchor_range(
+    old: &str,
+    new: &str,
+    start_line: usize,
+    end_line: usize,
+) -> (usize, usize, bool) {
+    let new_last = line_count(new) - 1;
+    let fates = line_fates(old, new);
+    let Some(last_old) = fates.len().checked_sub(1) else {
+        let start = start_line.saturating_sub(1).min(new_last);
+        return (
+            start + 1,
+            end_line.saturating_sub(1).clamp(start, new_last) + 1,
+            false,
+        );
+    };
+    let start = start_line.saturating_sub(1).min(last_old);
+    let end = end_line.saturating_sub(1).clamp(start, last_old);
+    let range = &fates[start..=end];
+    let survived = range.iter().any(|fate| match fate {
+        LineFate::Kept(_) => true,
+        LineFate::Replaced { new_len, .. } => *new_len > 0,
+    });
+    let new_start = match fates[start] {
+        LineFate::Kept(line) => line,
+        LineFate::Replaced { new_start, .. } => new_start,
+    };
+    if !survived {
+        let line = new_start.min(new_last);
+        return (line + 1, line + 1, true);
+    }
+    let new_end = match fates[end] {
+        LineFate::Kept(line) => line,
+        LineFate::Replaced { new_start, new_len } => (new_start + new_len).saturating_sub(1),
+    };
+    let new_start = new_start.min(new_last);
+    (new_start + 1, new_end.clamp(new_start, new_last) + 1, false)
+}
+
+fn annotation_ids(
+    table: &redb::Table<'_, (&str, &str), &[u8]>,
+    paste_id: &str,
+) -> Result<Vec<String>, AppError> {
+    let mut ids = Vec::new();
+    for entry in table.range((paste_id, "")..)? {
+        let (key, _) = entry?;
+        let (owner, annotation_id) = key.value();
+        if owner != paste_id {
+            break;
+        }
+        ids.push(annotation_id.to_string());
+    }
+    Ok(ids)
+}
+
+/// Remove every annotation on `paste_id` inside an open write transaction.
+///
+/// # Errors
+/// Returns an error when the annotations table cannot be read or written.
+pub(crate) fn remove_paste_annotations(
+    write_txn: &redb::WriteTransaction,
+    paste_id: &str,
+) -> Result<(), AppError> {
+    let mut table = write_txn.open_table(PASTE_ANNOTATIONS)?;
+    for annotation_id in annotation_ids(&table, paste_id)? {
+        table.remove((paste_id, annotation_id.as_str()))?;
+    }
+    Ok(())
+}
+
+/// Move `paste_id`'s annotations from `old` content onto `new` content inside
+/// the write transaction that stores `new`.
+///
+/// Pastes without annotations skip the diff entirely.
+///
+/// # Errors
+/// Returns an error when the annotations table cannot be read, decoded, or
+/// written.
+pub(crate) fn reanchor_paste_annotations(
+    write_txn: &redb::WriteTransaction,
+    paste_id: &str,
+    old: &str,
+    new: &str,
+) -> Result<(), AppError> {
+    let mut table = write_txn.open_table(PASTE_ANNOTATIONS)?;
+    let ids = annotation_ids(&table, paste_id)?;
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let mut annotations = Vec::with_capacity(ids.len());
+    for annotation_id in &ids {
+        if let Some(row) = table.get((paste_id, annotation_id.as_str()))? {
+            annotations.push(bincode::deserialize::<Annotation>(row.value())?);
+        }
+    }
+    for mut annotation in annotations {
+        let (start, end, detached) =
+            reanchor_range(old, new, annotation.start_line, annotation.end_line);
+        if (start, end) == (annotation.start_line, annotation.end_line)
+            && (annotation.detached || !detached)
+        {
+            continue;
+        }
+        annotation.start_line = start;
+        annotation.end_line = end;
+        annotation.detached |= detached;
+        let encoded = bincode::serialize(&annotation)?;
+        table.insert((paste_id, annotation.id.as_str()), encoded.as_slice())?;
+    }
+    Ok(())
+}
+
+/// Accessor for the paste annotations table.
+pub struct AnnotationDb {
+    db: Arc<redb::Database>,
+}
+
+impl AnnotationDb {
+    /// Initialize the annotations table if it does not exist yet.
+    ///
+    /// # Returns
+    /// A new [`AnnotationDb`] accessor bound to `db`.
+    ///
+    /// # Errors
+    /// Returns an error when redb transaction/table initialization fails.
+    pub fn new(db: Arc<redb::Database>) -> Result<Self, AppError> {
+        let write_txn = db.begin_write()?;
+        write_txn.open_table(PASTE_ANNOTATIONS)?;
+        write_txn.commit()?;
+        Ok(Self { db })
+    }
+
+    /// List a paste's annotations in line order.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn list(&self, paste_id: &str) -> Result<Vec<Annotation>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(PASTE_ANNOTATIONS)?;
+        let mut annotations = Vec::new();
+        for entry in table.range((paste_id, "")..)? {
+            let (key, value) = entry?;
+            if key.value().0 != paste_id {
+                break;
+            }
+            annotations.push(bincode::deserialize::<Annotation>(value.value())?);
+        }
+        annotations.sort_by(|a, b| {
+            (a.start_line, a.end_line, a.created_at).cmp(&(b.start_line, b.end_line, b.created_at))
+        });
+        Ok(annotations)
+    }
+
+    /// Fetch one annotation.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn get(&self, paste_id: &str, annotation_id: &str) -> Result<Option<Annotation>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(PASTE_ANNOTATIONS)?;
+        table
+            .get((paste_id, annotation_id))?
+            .map(|row| bincode::deserialize::<Annotation>(row.value()))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Annotate a line range of an existing paste.
+    ///
+    /// # Arguments
+    /// - `paste_id`: Paste the annotation belongs to.
+    /// - `lines`: 1-based inclusive `(start, end)` range within the current content.
+    /// - `author`: Display label; blank falls back to [`DEFAULT_COMMENT_AUTHOR`].
+    /// - `author_id`: Account id in multi-user mode.
+    /// - `body`: Note text; surrounding whitespace is trimmed.
+    ///
+    /// # Returns
+    /// The stored annotation.
+    ///
+    /// # Errors
+    /// Returns `NotFound` when the paste is missing, `BadRequest` for a range
+    /// outside the content or an empty or oversized body, or an error when
+    /// storage fails.
+    pub fn create(
+        &self,
+        paste_id: &str,
+        lines: (usize, usize),
+        author: &str,
+        author_id: Option<&str>,
+        body: &str,
+    ) -> Result<Annotation, AppError> {
+        let body = validated_body("Annotation", body)?;
+        let author = match normalize_author(author) {
+            author if author.is_empty() => DEFAULT_COMMENT_AUTHOR.to_string(),
+            author => author,
+        };
+        let (start_line, end_line) = lines;
+        let write_txn = self.db.begin_write()?;
+        let annotation = {
+            // Read in the same transaction so the range is checked against the
+            // content it will be re-anchored from.
+            let pastes = write_txn.open_table(PASTES)?;
+            let Some(row) = pastes.get(paste_id)? else {
+                return Err(AppError::NotFound);
+            };
+            let available = line_count(deserialize_paste(row.value())?.content.as_str());
+            if start_line == 0 || end_line < start_line || end_line > available {
+                return Err(AppError::BadRequest(format!(
+                    "Line range {}-{} is outside the paste (1-{})",
+                    start_line, end_line, available
+                )));
+            }
+            let now = Utc::now();
+            let annotation = Annotation {
+                id: Uuid::new_v4().to_string(),
+                paste_id: paste_id.to_string(),
+                start_line,
+                end_line,
+                author,
+                author_id: author_id.map(str::to_string),
+                body,
+                detached: false,
+                created_at: now,
+                updated_at: now,
+            };
+            let encoded = bincode::serialize(&annotation)?;
+            let mut table = write_txn.open_table(PASTE_ANNOTATIONS)?;
+            table.insert((paste_id, annotation.id.as_str()), encoded.as_slice())?;
+            annotation
+        };
+        write_txn.commit()?;
+        Ok(annotation)
+    }
+
+    /// Replace an annotation's body; the anchored range is left alone.
+    ///
+    /// # Returns
+    /// The updated annotation, or `Ok(None)` when it does not exist.
+    ///
+    /// # Errors
+    /// Returns `BadRequest` for an empty or oversized body, or an error when
+    /// storage fails.
+    pub fn update(
+        &self,
+        paste_id: &str,
+        annotation_id: &str,
+        body: &str,
+    ) -> Result<Option<Annotation>, AppError> {
+        let body = validated_body("Annotation", body)?;
+        let write_txn = self.db.begin_write()?;
+        let updated = {
+            let mut table = write_txn.open_table(PASTE_ANNOTATIONS)?;
+            let existing = table
+                .get((paste_id, annotation_id))?
+                .map(|row| bincode::deserialize::<Annotation>(row.value()))
+                .transpose()?;
+            let Some(mut annotation) = existing else {
+                return Ok(None);
+            };
+            annotation.body = body;
+            annotation.updated_at = Utc::now();
+            let encoded = bincode::serialize(&annotation)?;
+            table.insert((paste_id, annotation_id), encoded.as_slice())?;
+            annotation
+        };
+        write_txn.commit()?;
+        Ok(Some(updated))
+    }
+
+    /// Delete one annotation.
+    ///
+    /// # Returns
+    /// `Ok(true)` when an annotation was removed, `Ok(false)` when none existed.
+    ///
+    /// # Errors
+    /// Returns an error when storage operations fail.
+    pub fn delete(&self, paste_id: &str, annotation_id: &str) -> Result<bool, AppError> {
+        let write_txn = self.db.begin_write()?;
+        let removed = write_txn
+            .open_table(PASTE_ANNOTATIONS)?
+            .remove((paste_id, annotation_id))?
+            .is_some();
+        write_txn.commit()?;
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::reanchor_range;
+    use crate::error::AppError;
+    use crate::models::paste::{Paste, UpdatePasteRequest};
+    use crate::test_support::setup_temp_db;
+
+    #[test]
+    fn reanchor_range_follows_inserted_rewritten_and_deleted_lines() {
+        let old = "a\nb\nc\nd\ne\n";
+        // Lines above shift the range down; lines inside widen it.
+        assert_eq!(
+            reanchor_range(old, "x\ny\na\nb\nc\nd\ne\n", 2, 3),
+            (4, 5, false)
+        );
+        assert_eq!(
+            reanchor_range(old, "a\nb\nnew\nc\nd\ne\n", 2, 3),
+            (2, 4, false)
+        );
+        // A rewritten edge line keeps the annotation on its replacement.
+        assert_eq!(reanchor_range(old, "a\nB\nc\nd\ne\n", 2, 3), (2, 3, false));
+        // Deleting one edge line shrinks the range; deleting all detaches it.
+        assert_eq!(reanchor_range(old, "a\nc\nd\ne\n", 2, 3), (2, 2, false));
+        assert_eq!(reanchor_range(old, "a\nd\ne\n", 2, 3), (2, 2, true));
+        assert_eq!(reanchor_range(old, "a\n", 4, 5), (1, 1, true));
+    }
+
+    #[test]
+    fn annotations_validate_ranges_and_move_with_content_updates() {
+        let (db, _temp) = setup_temp_db();
+        let paste = Paste::new("one\ntwo\nthree\n".to_string(), "notes".to_string());
+        db.pastes.create(&paste).expect("paste");
+
+        assert!(matches!(
+            db.annotations
+                .create(&paste.id, (2, 4), "ana", None, "too far"),
+            Err(AppError::BadRequest(_))
+        ));
+        let note = db
+            .annotations
+            .create(&paste.id, (2, 3), "ana", None, "explain these")
+            .expect("annotation");
+
+        let update = UpdatePasteRequest {
+            content: Some("zero\none\ntwo\nthree\n".to_string()),
+            name: None,
+            language: None,
+            language_is_manual: None,
+            folder_id: None,
+            tags: None,
+            expires_in: None,
+        };
+        db.pastes.update(&paste.id, update).expect("update");
+        let moved = db
+            .annotations
+            .get(&paste.id, &note.id)
+            .expect("get")
+            .expect("exists");
+        assert_eq!((moved.start_line, moved.end_line), (3, 4));
+        assert!(!moved.detached);
+
+        assert!(db.pastes.delete(&paste.id).expect("delete paste"));
+        assert!(db.annotations.list(&paste.id).expect("list").is_empty());
+    }
+}
@@ -0,0 +1,320 @@
+//! Rewrites a database so it can be shared to reproduce a bug.
+//!
+//! Paste and version content becomes lorem text with the same byte length and
+//! line layout, and paste, folder, and user names and tags become numbered
+//! placeholders. Ids, timestamps, languages, folder structure, and sizes are
+//! kept, so lists, search, and storage behave as they did. Tables holding
+//! other free text (comments, annotations, attachments, URL sources, drafts,
+//! folder rules, slugs, and API tokens) are emptied.
+//!
+//! Run it on a copy: the rewrite happens in place.
+
+use super::paste::{deserialize_paste, paste_content_hash};
+use super::tables::{
+    DRAFTS, FOLDER_RULES, PASTES, PASTE_ANNOTATIONS, PASTE_ATTACHMENTS, PASTE_ATTACHMENT_DATA,
+    PASTE_COMMENTS, PASTE_SLUGS, PASTE_URL_SOURCES, PASTE_VERSIONS_CONTENT, PASTE_VERSIONS_META,
+    USERS, USER_TOKENS,
+};
+use super::versioning::{decode_version_meta_list, encode_version_meta_list};
+use crate::error::AppError;
+use crate::models::user::User;
+use crate::Database;
+use redb::{ReadableTable, ReadableTableMetadata};
+use std::collections::HashMap;
+
+const LOREM_WORDS: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+];
+
+/// Counts of what [`anonymize_database`] rewrote or dropped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnonymizeReport {
+    /// Pastes whose content, name, and tags were replaced.
+    pub pastes: usize,
+    /// Historical versions whose content was replaced.
+    pub versions: usize,
+    /// Folders renamed.
+    pub folders: usize,
+    /// Distinct tags (case-insensitive) mapped to placeholders.
+    pub tags: usize,
+    /// Users renamed.
+    pub users: usize,
+    /// Rows removed from free-text side tables.
+    pub cleared_rows: usize,
+}
+
+/// Lorem text with the byte length and line layout of `content`.
+///
+/// Line endings and leading indentation are kept; every other byte of a
+/// line is filled with ASCII words.
+fn lorem_like(content: &str, word: &mut usize) -> String {
+    let mut out = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        let body = line.trim_end_matches(['\n', '\r']);
+        let text = body.trim_start_matches([' ', '\t']);
+        out.push_str(&body[..body.len() - text.len()]);
+        let start = out.len();
+        while out.len() - start < text.len() {
+            if out.len() > start {
+                out.push(' ');
+            }
+            out.push_str(LOREM_WORDS[*word % LOREM_WORDS.len()]);
+            *word += 1;
+        }
+        out.truncate(start + text.len());
+        out.push_str(&line[body.len()..]);
+    }
+    out
+}
+
+/// `paste-00042.rs`: a numbered name that keeps a short alphanumeric extension.
+fn placeholder_paste_name(index: usize, name: &str) -> String {
+    let extension = name.rsplit_once('.').map(|(_, ext)| ext).filter(|ext| {
+        !ext.is_empty() && ext.len() <= 8 && ext.chars().all(|ch| ch.is_ascii_alphanumeric())
+    });
+    match extension {
+        Some(ext) => format!("paste-{:05}.{}", index, ext),
+        None => format!("paste-{:05}", index),
+    }
+}
+
+/// Replaces user-authored text in every table of `db`.
+///
+/// Metadata and index tables are rebuilt afterwards, so search and filters
+/// see only the placeholders.
+///
+/// # Returns
+/// What was rewritten and how many side-table rows were dropped.
+///
+/// # Errors
+/// Returns an error when any read, decode, write, or commit fails.
+pub fn anonymize_database(db: &Database) -> Result<AnonymizeReport, AppError> {
+    let mut report = AnonymizeReport::default();
+    let mut word = 0usize;
+    let write_txn = db.db.begin_write()?;
+    {
+        let mut pastes = write_txn.open_table(PASTES)?;
+        let mut ids = pastes
+            .iter()?
+            .map(|item| item.map(|(key, _)| key.value().to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        ids.sort();
+        let mut tag_names: HashMap<String, String> = HashMap::new();
+        for (index, id) in ids.iter().enumerate() {
+            let Some(row) = pastes.get(id.as_str())? else {
+                continue;
+            };
+            let mut paste = deserialize_paste(row.value())?;
+            drop(row);
+            paste.content = lorem_like(paste.content.as_str(), &mut word);
+            paste.name = placeholder_paste_name(index + 1, paste.name.as_str());
+            paste.slug = None;
+            for tag in &mut paste.tags {
+                let next = tag_names.len() + 1;
+                *tag = tag_names
+                    .entry(tag.to_lowercase())
+                    .or_insert_with(|| format!("tag-{}", next))
+                    .clone();
+            }
+            paste.tags.dedup();
+            let encoded = bincode::serialize(&paste)?;
+            pastes.insert(id.as_str(), encoded.as_slice())?;
+            report.pastes += 1;
+        }
+        report.tags = tag_names.len();
+
+        let versions_meta = write_txn.open_table(PASTE_VERSIONS_META)?;
+        let mut versions_content = write_txn.open_table(PASTE_VERSIONS_CONTENT)?;
+        let mut rewritten_meta = Vec::new();
+        for item in versions_meta.iter()? {
+            let (key, value) = item?;
+            let paste_id = key.value().to_string();
+            let mut items = decode_version_meta_list(Some(value.value()))?;
+            for version in &mut items {
+                let key = (paste_id.as_str(), version.version_id_ms);
+                let Some(row) = versions_content.get(key)? else {
+                    continue;
+                };
+                let content: String = bincode::deserialize(row.value())?;
+                drop(row);
+                let replaced = lorem_like(content.as_str(), &mut word);
+                version.content_hash = paste_content_hash(replaced.as_str());
+                versions_content.insert(key, bincode::serialize(&replaced)?.as_slice())?;
+                report.versions += 1;
+            }
+            rewritten_meta.push((paste_id, encode_version_meta_list(&items)?));
+        }
+        drop(versions_meta);
+        let mut versions_meta = write_txn.open_table(PASTE_VERSIONS_META)?;
+        for (paste_id, encoded) in rewritten_meta {
+            versions_meta.insert(paste_id.as_str(), encoded.as_slice())?;
+        }
+
+        let mut users = write_txn.open_table(USERS)?;
+        let mut all_users = users
+            .iter()?
+            .map(|item| {
+                let (_, value) = item?;
+                Ok(bincode::deserialize::<User>(value.value())?)
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+        all_users.sort_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        for (index, mut user) in all_users.into_iter().enumerate() {
+            user.name = format!("user-{}", index + 1);
+            users.insert(user.id.as_str(), bincode::serialize(&user)?.as_slice())?;
+            report.users += 1;
+        }
+
+        report.cleared_rows += clear_table(&write_txn, PASTE_SLUGS)?;
+        report.cleared_rows += clear_table(&write_txn, USER_TOKENS)?;
+        report.cleared_rows += clear_table(&write_txn, DRAFTS)?;
+        report.cleared_rows += clear_table(&write_txn, FOLDER_RULES)?;
+        report.cleared_rows += clear_table(&write_txn, PASTE_URL_SOURCES)?;
+        report.cleared_rows += clear_table(&write_txn, PASTE_COMMENTS)?;
+        report.cleared_rows += clear_table(&write_txn, PASTE_ANNOTATIONS)?;
+        report.cleared_rows += clear_table(&write_txn, PASTE_ATTACHMENTS)?;
+        report.cleared_rows += clear_table(&write_txn, PASTE_ATTACHMENT_DATA)?;
+    }
+    write_txn.commit()?;
+
+    let mut folders = db.folders.list()?;
+    folders.sort_by(|a, b| {
+        a.created_at
+            .cmp(&b.created_at)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    for (index, folder) in folders.iter().enumerate() {
+        db.folders
+            .update(folder.id.as_str(), format!("folder-{}", index + 1), None)?;
+        report.folders += 1;
+    }
+
+    db.pastes.rebuild_meta_index()?;
+    Ok(report)
+}
+
+/// Empties `table` and returns how many rows it held.
+fn clear_table<K: redb::Key + 'static, V: redb::Value + 'static>(
+    write_txn: &redb::WriteTransaction,
+    table: redb::TableDefinition<K, V>,
+) -> Result<usize, AppError> {
+    let mut table = write_txn.open_table(table)?;
+    let rows = table.len()? as usize;
+    table.retain(|_, _| false)?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::TransactionOps;
+    use crate::models::{
+        folder::Folder,
+        paste::{Paste, SearchFilters, UpdatePasteRequest},
+    };
+    use crate::test_support::setup_temp_db;
+
+    #[test]
+    fn lorem_like_keeps_bytes_lines_and_indentation() {
+        let original = "fn secret() {\r\n    let key = \"hunter2\";\n\tdone\n\nlast";
+        let replaced = lorem_like(original, &mut 0);
+        assert_eq!(replaced.len(), original.len());
+        assert_eq!(
+            replaced.match_indices('\n').collect::<Vec<_>>(),
+            original.match_indices('\n').collect::<Vec<_>>()
+        );
+        assert!(replaced.contains("\r\n    "));
+        assert!(replaced.contains("\n\t"));
+        assert!(!replaced.contains("hunter2"));
+        assert_eq!(lorem_like("ünï", &mut 0).len(), "ünï".len());
+    }
+
+    #[test]
+    fn anonymize_replaces_text_and_keeps_structure() {
+        let (db, _temp) = setup_temp_db();
+        let folder = Folder::new("Client Acme".to_string());
+        db.folders.create(&folder).expect("create folder");
+        let mut filed = Paste::new(
+            "password = swordfish\n".to_string(),
+            "acme.toml".to_string(),
+        );
+        filed.tags = vec!["Acme".to_string(), "prod".to_string()];
+        TransactionOps::create_paste_with_folder(&db, &filed, &folder.id).expect("filed paste");
+        let mut other = Paste::new("plain".to_string(), "notes".to_string());
+        other.tags = vec!["acme".to_string()];
+        db.pastes.create(&other).expect("other paste");
+        let edit = UpdatePasteRequest {
+            content: Some("password = rotated\n".to_string()),
+            name: None,
+            language: None,
+            language_is_manual: None,
+            folder_id: None,
+            tags: None,
+            expires_in: None,
+        };
+        db.pastes.update(&filed.id, edit).expect("edit");
+
+        let report = anonymize_database(&db).expect("anonymize");
+        assert_eq!(report.pastes, 2);
+        assert_eq!(report.folders, 1);
+        assert_eq!(report.tags, 2, "tags are mapped case-insensitively");
+
+        let rewritten = db.pastes.get(&filed.id).expect("get").expect("paste");
+        assert_eq!(rewritten.content.len(), "password = rotated\n".len());
+        assert!(!rewritten.content.contains("password"));
+        assert!(rewritten.name.starts_with("paste-") && rewritten.name.ends_with(".toml"));
+        assert_eq!(rewritten.folder_id.as_deref(), Some(folder.id.as_str()));
+        let renamed = db.folders.get(&folder.id).expect("get").expect("folder");
+        assert_eq!(renamed.name, "folder-1");
+        let other = db.pastes.get(&other.id).expect("get").expect("paste");
+        assert!(rewritten.tags.contains(&other.tags[0]));
+
+        let versions = db
+            .pastes
+            .list_versions(&filed.id, None)
+            .expect("versions")
+            .unwrap_or_default();
+        assert_eq!(versions.len(), report.versions);
+        for version in versions {
+            let snapshot = db
+                .pastes
+                .get_version(&filed.id, version.version_id_ms)
+                .expect("version")
+                .expect("snapshot");
+            assert!(!snapshot.content.contains("swordfish"));
+        }
+        let none = SearchFilters::default();
+        assert!(db
+            .pastes
+            .search("swordfish", 10, &none)
+            .expect("search")
+            .is_empty());
+        assert!(db
+            .pastes
+            .search("acme", 10, &none)
+            .expect("search")
+            .is_empty());
+    }
+}
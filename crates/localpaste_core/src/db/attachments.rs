@@ -0,0 +1,293 @@
+//! Binary attachments stored beside pastes.
+//!
+//! Metadata and bytes live in separate tables keyed by `(paste_id,
+//! attachment_id)`, so listing a paste's attachments never reads file
+//! contents, and deleting a paste drops both in the same transaction.
+
+use crate::{
+    db::tables::{PASTES, PASTE_ATTACHMENTS, PASTE_ATTACHMENT_DATA},
+    error::AppError,
+    models::attachment::{
+        Attachment, DEFAULT_ATTACHMENT_CONTENT_TYPE, MAX_ATTACHMENTS_PER_PASTE,
+        MAX_ATTACHMENT_BYTES, MAX_ATTACHMENT_NAME_CHARS,
+    },
+};
+use chrono::Utc;
+use redb::{ReadableDatabase, ReadableTable};
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn paste_child_ids(
+    table: &impl ReadableTable<(&'static str, &'static str), &'static [u8]>,
+    paste_id: &str,
+) -> Result<Vec<String>, AppError> {
+    let mut ids = Vec::new();
+    for entry in table.range((paste_id, "")..)? {
+        let (key, _) = entry?;
+        let (owner, attachment_id) = key.value();
+        if owner != paste_id {
+            break;
+        }
+        ids.push(attachment_id.to_string());
+    }
+    Ok(ids)
+}
+
+/// Remove every attachment on `paste_id` inside an open write transaction.
+///
+/// # Errors
+/// Returns an error when the attachment tables cannot be read or written.
+pub(crate) fn remove_paste_attachments(
+    write_txn: &redb::WriteTransaction,
+    paste_id: &str,
+) -> Result<(), AppError> {
+    for definition in [PASTE_ATTACHMENTS, PASTE_ATTACHMENT_DATA] {
+        let mut table = write_txn.open_table(definition)?;
+        for attachment_id in paste_child_ids(&table, paste_id)? {
+            table.remove((paste_id, attachment_id.as_str()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Trim a file name to its stored form, capped at
+/// [`MAX_ATTACHMENT_NAME_CHARS`].
+fn validated_file_name(file_name: &str) -> Result<String, AppError> {
+    let file_name = file_name.trim();
+    if file_name.is_empty() {
+        return Err(AppError::BadRequest(
+            "Attachment file name cannot be empty".to_string(),
+        ));
+    }
+    Ok(file_name.chars().take(MAX_ATTACHMENT_NAME_CHARS).collect())
+}
+
+/// Accessor for the paste attachment tables.
+pub struct AttachmentDb {
+    db: Arc<redb::Database>,
+}
+
+impl AttachmentDb {
+    /// Initialize the attachment tables if they do not exist yet.
+    ///
+    /// # Returns
+    /// A new [`AttachmentDb`] accessor bound to `db`.
+    ///
+    /// # Errors
+    /// Returns an error when redb transaction/table initialization fails.
+    pub fn new(db: Arc<redb::Database>) -> Result<Self, AppError> {
+        let write_txn = db.begin_write()?;
+        write_txn.open_table(PASTE_ATTACHMENTS)?;
+        write_txn.open_table(PASTE_ATTACHMENT_DATA)?;
+        write_txn.commit()?;
+        Ok(Self { db })
+    }
+
+    /// List a paste's attachments, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn list(&self, paste_id: &str) -> Result<Vec<Attachment>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(PASTE_ATTACHMENTS)?;
+        let mut attachments = Vec::new();
+        for entry in table.range((paste_id, "")..)? {
+            let (key, value) = entry?;
+            if key.value().0 != paste_id {
+                break;
+            }
+            attachments.push(bincode::deserialize::<Attachment>(value.value())?);
+        }
+        attachments.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        Ok(attachments)
+    }
+
+    /// Fetch one attachment's metadata.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn get(&self, paste_id: &str, attachment_id: &str) -> Result<Option<Attachment>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(PASTE_ATTACHMENTS)?;
+        table
+            .get((paste_id, attachment_id))?
+            .map(|row| bincode::deserialize::<Attachment>(row.value()))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Fetch one attachment's metadata and bytes from a single snapshot.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn read(
+        &self,
+        paste_id: &str,
+        attachment_id: &str,
+    ) -> Result<Option<(Attachment, Vec<u8>)>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let meta = read_txn.open_table(PASTE_ATTACHMENTS)?;
+        let Some(row) = meta.get((paste_id, attachment_id))? else {
+            return Ok(None);
+        };
+        let attachment = bincode::deserialize::<Attachment>(row.value())?;
+        let data = read_txn.open_table(PASTE_ATTACHMENT_DATA)?;
+        let bytes = data
+            .get((paste_id, attachment_id))?
+            .map(|row| row.value().to_vec())
+            .ok_or_else(|| {
+                AppError::StorageMessage(format!(
+                    "Attachment '{}' has metadata but no data",
+                    attachment_id
+                ))
+            })?;
+        Ok(Some((attachment, bytes)))
+    }
+
+    /// Attach a file to an existing paste.
+    ///
+    /// # Arguments
+    /// - `paste_id`: Paste the file belongs to.
+    /// - `file_name`: Display name; surrounding whitespace is trimmed.
+    /// - `content_type`: MIME type; blank falls back to
+    ///   [`DEFAULT_ATTACHMENT_CONTENT_TYPE`].
+    /// - `bytes`: File contents.
+    ///
+    /// # Returns
+    /// The stored attachment metadata.
+    ///
+    /// # Errors
+    /// Returns `NotFound` when the paste is missing, `BadRequest` for a blank
+    /// name or when the paste already has [`MAX_ATTACHMENTS_PER_PASTE`]
+    /// attachments, `PayloadTooLarge` above [`MAX_ATTACHMENT_BYTES`], or an
+    /// error when storage fails.
+    pub fn create(
+        &self,
+        paste_id: &str,
+        file_name: &str,
+        content_type: Option<&str>,
+        bytes: &[u8],
+    ) -> Result<Attachment, AppError> {
+        if bytes.len() > MAX_ATTACHMENT_BYTES {
+            return Err(AppError::PayloadTooLarge(format!(
+                "Attachment exceeds {} bytes",
+                MAX_ATTACHMENT_BYTES
+            )));
+        }
+        let content_type = content_type
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or(DEFAULT_ATTACHMENT_CONTENT_TYPE);
+        let attachment = Attachment {
+            id: Uuid::new_v4().to_string(),
+            paste_id: paste_id.to_string(),
+            file_name: validated_file_name(file_name)?,
+            content_type: content_type.to_string(),
+            size: bytes.len() as u64,
+            created_at: Utc::now(),
+        };
+        let encoded = bincode::serialize(&attachment)?;
+        let write_txn = self.db.begin_write()?;
+        {
+            // Checked in the same transaction so a concurrent delete cannot
+            // leave an attachment behind on a missing paste.
+            if write_txn.open_table(PASTES)?.get(paste_id)?.is_none() {
+                return Err(AppError::NotFound);
+            }
+            let mut meta = write_txn.open_table(PASTE_ATTACHMENTS)?;
+            if paste_child_ids(&meta, paste_id)?.len() >= MAX_ATTACHMENTS_PER_PASTE {
+                return Err(AppError::BadRequest(format!(
+                    "A paste can have at most {} attachments",
+                    MAX_ATTACHMENTS_PER_PASTE
+                )));
+            }
+            meta.insert((paste_id, attachment.id.as_str()), encoded.as_slice())?;
+            write_txn
+                .open_table(PASTE_ATTACHMENT_DATA)?
+                .insert((paste_id, attachment.id.as_str()), bytes)?;
+        }
+        write_txn.commit()?;
+        Ok(attachment)
+    }
+
+    /// Delete one attachment and its bytes.
+    ///
+    /// # Returns
+    /// `Ok(true)` when an attachment was removed, `Ok(false)` when none existed.
+    ///
+    /// # Errors
+    /// Returns an error when storage operations fail.
+    pub fn delete(&self, paste_id: &str, attachment_id: &str) -> Result<bool, AppError> {
+        let write_txn = self.db.begin_write()?;
+        let removed = write_txn
+            .open_table(PASTE_ATTACHMENTS)?
+            .remove((paste_id, attachment_id))?
+            .is_some();
+        write_txn
+            .open_table(PASTE_ATTACHMENT_DATA)?
+            .remove((paste_id, attachment_id))?;
+        write_txn.commit()?;
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::paste::Paste;
+    use crate::test_support::setup_temp_db;
+
+    #[test]
+    fn attachments_round_trip_and_are_removed_with_the_paste() {
+        let (db, _temp) = setup_temp_db();
+        let paste = Paste::new("see attached".to_string(), "report".to_string());
+        db.pastes.create(&paste).expect("paste");
+
+        let image = db
+            .attachments
+            .create(&paste.id, " shot.png ", Some("image/png"), &[0x89, b'P', 0])
+            .expect("attachment");
+        assert_eq!(image.file_name, "shot.png");
+        assert_eq!(image.size, 3);
+        let blob = db
+            .attachments
+            .create(&paste.id, "dump.bin", None, &[1, 2])
+            .expect("attachment");
+        assert_eq!(blob.content_type, DEFAULT_ATTACHMENT_CONTENT_TYPE);
+        assert!(matches!(
+            db.attachments.create(&paste.id, "  ", None, &[1]),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(matches!(
+            db.attachments.create("missing", "a.txt", None, &[1]),
+            Err(AppError::NotFound)
+        ));
+
+        let listed: Vec<_> = db
+            .attachments
+            .list(&paste.id)
+            .expect("list")
+            .into_iter()
+            .map(|row| row.id)
+            .collect();
+        assert_eq!(listed, vec![image.id.clone(), blob.id.clone()]);
+        let (meta, bytes) = db
+            .attachments
+            .read(&paste.id, &image.id)
+            .expect("read")
+            .expect("present");
+        assert_eq!(meta, image);
+        assert_eq!(bytes, vec![0x89, b'P', 0]);
+
+        assert!(db.attachments.delete(&paste.id, &blob.id).expect("delete"));
+        assert!(!db.attachments.delete(&paste.id, &blob.id).expect("delete"));
+
+        assert!(db.pastes.delete(&paste.id).expect("delete paste"));
+        assert!(db.attachments.list(&paste.id).expect("list").is_empty());
+        assert!(db
+            .attachments
+            .read(&paste.id, &image.id)
+            .expect("read")
+            .is_none());
+    }
+}
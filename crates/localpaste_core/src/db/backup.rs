@@ -1,24 +1,254 @@
 //! Backup and restore helpers for redb databases.
+//!
+//! Every backup gets a `<backup>.manifest.json` sidecar listing each file with
+//! its size and BLAKE3 checksum. Restores refuse to proceed unless the manifest
+//! matches, so a truncated or corrupted copy is reported instead of restored.
+//!
+//! When `LOCALPASTE_BACKUP_PASSPHRASE` is set, the snapshot is encrypted with
+//! an age passphrase (scrypt) recipient into `<backup>.redb.age` and the
+//! plaintext copy is removed. The manifest checksums the encrypted file, so
+//! verification works without the passphrase; restoring needs it.
+//!
+//! A restore can also be scheduled while the database is open: the backup is
+//! verified and named in a `.restore-pending` marker, and the next launch
+//! restores it before opening the database.
 
+use super::lock::acquire_owner_lock_for_lifetime;
 use super::tables::{
-    FOLDERS, FOLDERS_DELETING, PASTES, PASTES_BY_UPDATED, PASTES_META, PASTES_META_STATE,
-    PASTE_VERSIONS_CONTENT, PASTE_VERSIONS_META, REDB_FILE_NAME,
+    FOLDERS, FOLDERS_DELETING, FOLDER_RULES, PASTES, PASTES_BY_CREATED, PASTES_BY_FOLDER,
+    PASTES_BY_LANGUAGE, PASTES_BY_TAG, PASTES_BY_TRIGRAM, PASTES_BY_UPDATED, PASTES_META,
+    PASTES_META_STATE, PASTE_ACCESS, PASTE_ANNOTATIONS, PASTE_ATTACHMENTS, PASTE_ATTACHMENT_DATA,
+    PASTE_COMMENTS, PASTE_SLUGS, PASTE_URL_SOURCES, PASTE_VERSIONS_CONTENT, PASTE_VERSIONS_META,
+    REDB_FILE_NAME, USERS, USER_TOKENS,
 };
 use super::time_util::unix_timestamp_seconds;
+use crate::constants::RESTORE_PENDING_FILE_NAME;
+use crate::discovery::write_file_atomic;
 use crate::error::AppError;
+use age::secrecy::SecretString;
 use redb::{ReadableDatabase, ReadableTable};
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
+/// Current backup manifest format version.
+pub const BACKUP_MANIFEST_VERSION: u32 = 1;
+/// Suffix appended to a backup file name to locate its manifest.
+pub const BACKUP_MANIFEST_SUFFIX: &str = ".manifest.json";
+
+/// Environment variable holding the passphrase used to encrypt backups.
+pub const BACKUP_PASSPHRASE_ENV: &str = "LOCALPASTE_BACKUP_PASSPHRASE";
+/// Suffix appended to the file name of an encrypted backup.
+pub const ENCRYPTED_BACKUP_SUFFIX: &str = ".age";
+
+/// Resolve the backup passphrase from `LOCALPASTE_BACKUP_PASSPHRASE`.
+///
+/// # Returns
+/// The passphrase, or `None` when unset or empty (backups stay plaintext).
+pub fn backup_passphrase_from_env() -> Option<String> {
+    std::env::var(BACKUP_PASSPHRASE_ENV)
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+/// Whether `backup_path` names an encrypted backup.
+///
+/// # Returns
+/// `true` when the file name ends with [`ENCRYPTED_BACKUP_SUFFIX`].
+pub fn is_encrypted_backup(backup_path: &Path) -> bool {
+    backup_path
+        .file_name()
+        .is_some_and(|name| name.to_string_lossy().ends_with(ENCRYPTED_BACKUP_SUFFIX))
+}
+
+fn with_file_name_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Checksummed description of the files making up one backup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub version: u32,
+    pub created_at_unix: u64,
+    pub files: Vec<BackupManifestFile>,
+}
+
+/// One file recorded in a [`BackupManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifestFile {
+    /// File name relative to the manifest's directory.
+    pub name: String,
+    pub size: u64,
+    /// Lowercase hex BLAKE3 digest of the file contents.
+    pub blake3: String,
+}
+
+fn parent_dir(path: &Path) -> PathBuf {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+/// One backup file found next to the database directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupEntry {
+    pub path: PathBuf,
+    /// Creation time encoded in the backup file name.
+    pub created_at_unix: u64,
+    pub size: u64,
+    pub encrypted: bool,
+    /// Whether the `<backup>.manifest.json` sidecar exists.
+    pub has_manifest: bool,
+}
+
+/// Path of the manifest that accompanies `backup_path`.
+///
+/// # Returns
+/// `backup_path` with [`BACKUP_MANIFEST_SUFFIX`] appended to its file name.
+pub fn backup_manifest_path(backup_path: &Path) -> PathBuf {
+    with_file_name_suffix(backup_path, BACKUP_MANIFEST_SUFFIX)
+}
+
+fn file_checksum(path: &Path) -> Result<(u64, String), AppError> {
+    let mut file = File::open(path).map_err(|err| {
+        AppError::StorageMessage(format!("Failed to open '{}': {}", path.display(), err))
+    })?;
+    let mut hasher = blake3::Hasher::new();
+    let size = std::io::copy(&mut file, &mut hasher).map_err(|err| {
+        AppError::StorageMessage(format!("Failed to read '{}': {}", path.display(), err))
+    })?;
+    Ok((size, hasher.finalize().to_hex().to_string()))
+}
+
+/// Encrypt `plain_path` into `<plain_path>.age` and remove the plaintext.
+///
+/// The plaintext copy is removed even when encryption fails so an unencrypted
+/// snapshot is never left behind.
+fn encrypt_backup_file(plain_path: &Path, passphrase: &SecretString) -> Result<PathBuf, AppError> {
+    let encrypted_path = with_file_name_suffix(plain_path, ENCRYPTED_BACKUP_SUFFIX);
+    let written = (|| -> std::io::Result<()> {
+        let mut input = File::open(plain_path)?;
+        let output = BufWriter::new(File::create(&encrypted_path)?);
+        let mut writer =
+            age::Encryptor::with_user_passphrase(passphrase.clone()).wrap_output(output)?;
+        std::io::copy(&mut input, &mut writer)?;
+        let mut output = writer.finish()?;
+        output.flush()?;
+        output.get_ref().sync_all()
+    })();
+    let _ = fs::remove_file(plain_path);
+    written.map_err(|err| {
+        let _ = fs::remove_file(&encrypted_path);
+        AppError::StorageMessage(format!(
+            "Failed to encrypt backup '{}': {}",
+            encrypted_path.display(),
+            err
+        ))
+    })?;
+    Ok(encrypted_path)
+}
+
+/// Decrypt an encrypted backup into `destination`.
+///
+/// # Errors
+/// Returns an error when no passphrase is available, the passphrase is wrong,
+/// or the ciphertext fails authentication.
+fn decrypt_backup_file(
+    encrypted_path: &Path,
+    destination: &Path,
+    passphrase: Option<&SecretString>,
+) -> Result<(), AppError> {
+    let passphrase = passphrase.ok_or_else(|| {
+        AppError::StorageMessage(format!(
+            "Backup '{}' is encrypted; set {} to restore it",
+            encrypted_path.display(),
+            BACKUP_PASSPHRASE_ENV
+        ))
+    })?;
+    let decrypt_error = |err: &dyn std::fmt::Display| {
+        AppError::StorageMessage(format!(
+            "Failed to decrypt backup '{}': {}",
+            encrypted_path.display(),
+            err
+        ))
+    };
+    let input = File::open(encrypted_path).map_err(|err| decrypt_error(&err))?;
+    let decryptor =
+        age::Decryptor::new(BufReader::new(input)).map_err(|err| decrypt_error(&err))?;
+    let identity = age::scrypt::Identity::new(passphrase.clone());
+    let mut reader = decryptor
+        .decrypt(std::iter::once(&identity as &dyn age::Identity))
+        .map_err(|err| decrypt_error(&err))?;
+    let mut output = File::create(destination).map_err(|err| decrypt_error(&err))?;
+    std::io::copy(&mut reader, &mut output)
+        .and_then(|_| output.sync_all())
+        .map_err(|err| decrypt_error(&err))
+}
+
+/// Check every file listed in the manifest for `backup_path`.
+///
+/// # Returns
+/// The verified manifest.
+///
+/// # Errors
+/// Returns an error when the manifest is missing or unreadable, or when any
+/// listed file is missing or its size or checksum differs.
+pub fn verify_backup(backup_path: &Path) -> Result<BackupManifest, AppError> {
+    let manifest_path = backup_manifest_path(backup_path);
+    let raw = fs::read(&manifest_path).map_err(|err| {
+        AppError::StorageMessage(format!(
+            "Backup manifest '{}' is unreadable: {}",
+            manifest_path.display(),
+            err
+        ))
+    })?;
+    let manifest: BackupManifest = serde_json::from_slice(&raw).map_err(|err| {
+        AppError::StorageMessage(format!(
+            "Backup manifest '{}' is malformed: {}",
+            manifest_path.display(),
+            err
+        ))
+    })?;
+    if manifest.version > BACKUP_MANIFEST_VERSION {
+        return Err(AppError::StorageMessage(format!(
+            "Backup manifest '{}' has unsupported version {}",
+            manifest_path.display(),
+            manifest.version
+        )));
+    }
+    let dir = manifest_path.parent().unwrap_or_else(|| Path::new("."));
+    for entry in &manifest.files {
+        let (size, checksum) = file_checksum(&dir.join(&entry.name))?;
+        if size != entry.size || checksum != entry.blake3 {
+            return Err(AppError::StorageMessage(format!(
+                "Backup file '{}' failed verification: expected {} bytes with BLAKE3 {}, found {} bytes with BLAKE3 {}",
+                entry.name, entry.size, entry.blake3, size, checksum
+            )));
+        }
+    }
+    Ok(manifest)
+}
+
 /// Backup manager for a database path.
 pub struct BackupManager {
     db_path: PathBuf,
     db_file_path: PathBuf,
+    passphrase: Option<SecretString>,
 }
 
 impl BackupManager {
     /// Create a backup manager for the database path.
     ///
+    /// The backup passphrase is read from `LOCALPASTE_BACKUP_PASSPHRASE`.
+    ///
     /// # Returns
     /// A new [`BackupManager`] bound to `db_path`.
     pub fn new(db_path: &str) -> Self {
@@ -27,9 +257,22 @@ impl BackupManager {
         Self {
             db_path,
             db_file_path,
+            passphrase: backup_passphrase_from_env().map(SecretString::from),
         }
     }
 
+    /// Override the passphrase used to encrypt and restore backups.
+    ///
+    /// # Arguments
+    /// - `passphrase`: Passphrase to use, or `None` for plaintext backups.
+    ///
+    /// # Returns
+    /// The manager with the passphrase replaced.
+    pub fn with_passphrase(mut self, passphrase: Option<String>) -> Self {
+        self.passphrase = passphrase.map(SecretString::from);
+        self
+    }
+
     /// Create a backup by snapshotting all known tables into a new redb file.
     ///
     /// This avoids direct file-copy behavior on open databases, which can fail
@@ -37,11 +280,14 @@ impl BackupManager {
     /// Snapshot consistency comes from a source read transaction; copied rows are
     /// committed into the destination backup file via a destination write transaction.
     ///
+    /// With a passphrase configured the snapshot is encrypted and the returned
+    /// path ends in [`ENCRYPTED_BACKUP_SUFFIX`].
+    ///
     /// # Returns
     /// The created backup file path, or an empty string when no database file exists.
     ///
     /// # Errors
-    /// Returns an error when transaction start or filesystem copy operations fail.
+    /// Returns an error when transaction start, filesystem copy, or encryption fails.
     pub fn create_backup(&self, db: &redb::Database) -> Result<String, AppError> {
         if !self.db_file_path.exists() {
             return Ok(String::new());
@@ -60,19 +306,346 @@ impl BackupManager {
         Self::copy_version_content_table(&source_read, &backup_write)?;
         Self::copy_bytes_table(&source_read, &backup_write, FOLDERS)?;
         Self::copy_unit_table(&source_read, &backup_write, FOLDERS_DELETING)?;
-        Self::copy_updated_index_table(&source_read, &backup_write)?;
+        Self::copy_bytes_table(&source_read, &backup_write, FOLDER_RULES)?;
+        Self::copy_str_table(&source_read, &backup_write, PASTE_SLUGS)?;
+        Self::copy_bytes_table(&source_read, &backup_write, USERS)?;
+        Self::copy_str_table(&source_read, &backup_write, USER_TOKENS)?;
+        Self::copy_bytes_table(&source_read, &backup_write, PASTE_ACCESS)?;
+        Self::copy_paste_child_table(&source_read, &backup_write, PASTE_COMMENTS)?;
+        Self::copy_paste_child_table(&source_read, &backup_write, PASTE_ANNOTATIONS)?;
+        Self::copy_paste_child_table(&source_read, &backup_write, PASTE_ATTACHMENTS)?;
+        Self::copy_paste_child_table(&source_read, &backup_write, PASTE_ATTACHMENT_DATA)?;
+        Self::copy_bytes_table(&source_read, &backup_write, PASTE_URL_SOURCES)?;
+        Self::copy_u64_index_table(&source_read, &backup_write, PASTES_BY_UPDATED)?;
+        Self::copy_u64_index_table(&source_read, &backup_write, PASTES_BY_CREATED)?;
+        Self::copy_filter_index_table(&source_read, &backup_write, PASTES_BY_FOLDER)?;
+        Self::copy_filter_index_table(&source_read, &backup_write, PASTES_BY_TAG)?;
+        Self::copy_filter_index_table(&source_read, &backup_write, PASTES_BY_LANGUAGE)?;
+        Self::copy_u64_index_table(&source_read, &backup_write, PASTES_BY_TRIGRAM)?;
         backup_write.commit()?;
+        drop(backup_db);
+        let backup_path = match self.passphrase.as_ref() {
+            Some(passphrase) => encrypt_backup_file(&backup_path, passphrase)?,
+            None => backup_path,
+        };
+        Self::write_manifest(&backup_path, timestamp)?;
 
         tracing::info!("Created database backup at: {:?}", backup_path);
         Ok(backup_path.to_string_lossy().to_string())
     }
 
+    fn write_manifest(backup_path: &Path, created_at_unix: u64) -> Result<(), AppError> {
+        let (size, blake3) = file_checksum(backup_path)?;
+        let name = backup_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let manifest = BackupManifest {
+            version: BACKUP_MANIFEST_VERSION,
+            created_at_unix,
+            files: vec![BackupManifestFile { name, size, blake3 }],
+        };
+        let bytes = serde_json::to_vec_pretty(&manifest).map_err(|err| {
+            AppError::StorageMessage(format!("Failed to encode manifest: {}", err))
+        })?;
+        let manifest_path = backup_manifest_path(backup_path);
+        write_file_atomic(&manifest_path, &bytes).map_err(|err| {
+            AppError::StorageMessage(format!(
+                "Failed to write backup manifest '{}': {}",
+                manifest_path.display(),
+                err
+            ))
+        })
+    }
+
+    /// Replace the database file with a verified backup.
+    ///
+    /// The backup is checked against its manifest first, and the database must
+    /// not be open anywhere: the owner lock is held for the whole restore. The
+    /// copy is written next to the live file, checked again, then renamed over it.
+    /// Encrypted backups are decrypted into that copy instead; the age format
+    /// authenticates the plaintext, so a wrong passphrase or tampering fails.
+    ///
+    /// # Arguments
+    /// - `backup_path`: Backup `.redb` or `.redb.age` file with a sibling manifest.
+    ///
+    /// # Errors
+    /// Returns an error when verification or decryption fails, another process
+    /// owns the database, or the copy cannot be written.
+    pub fn restore_backup(&self, backup_path: &Path) -> Result<(), AppError> {
+        let manifest = verify_backup(backup_path)?;
+        let expected = manifest.files.first().ok_or_else(|| {
+            AppError::StorageMessage("Backup manifest lists no files".to_string())
+        })?;
+        let db_path = self.db_path.to_string_lossy().to_string();
+        let _owner = acquire_owner_lock_for_lifetime(&db_path)?;
+
+        let staging = self.db_file_path.with_extension("redb.restoring");
+        let staged = if is_encrypted_backup(backup_path) {
+            decrypt_backup_file(backup_path, &staging, self.passphrase.as_ref())
+        } else {
+            Self::copy_verified(backup_path, &staging, expected)
+        };
+        if let Err(err) = staged {
+            let _ = fs::remove_file(&staging);
+            return Err(err);
+        }
+        fs::rename(&staging, &self.db_file_path).map_err(|err| {
+            let _ = fs::remove_file(&staging);
+            AppError::StorageMessage(format!(
+                "Failed to replace '{}': {}",
+                self.db_file_path.display(),
+                err
+            ))
+        })?;
+        tracing::info!("Restored database from backup: {:?}", backup_path);
+        Ok(())
+    }
+
+    /// List backups of this database, newest first.
+    ///
+    /// # Returns
+    /// Every `<db>.backup.<unix>[.<n>].redb[.age]` file in the directory that
+    /// holds the database directory; empty when that directory is missing.
+    ///
+    /// # Errors
+    /// Returns an error when the directory or a file's metadata cannot be read.
+    pub fn list_backups(&self) -> Result<Vec<BackupEntry>, AppError> {
+        let dir = self.backup_dir();
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => {
+                return Err(AppError::StorageMessage(format!(
+                    "Failed to list backups in '{}': {}",
+                    dir.display(),
+                    err
+                )))
+            }
+        };
+        let mut backups = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|err| {
+                AppError::StorageMessage(format!(
+                    "Failed to list backups in '{}': {}",
+                    dir.display(),
+                    err
+                ))
+            })?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(order) = self.backup_order_from_name(&name) else {
+                continue;
+            };
+            let metadata = entry.metadata().map_err(|err| {
+                AppError::StorageMessage(format!("Failed to stat backup '{}': {}", name, err))
+            })?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let path = entry.path();
+            backups.push((
+                order,
+                BackupEntry {
+                    created_at_unix: order.0,
+                    size: metadata.len(),
+                    encrypted: is_encrypted_backup(&path),
+                    has_manifest: backup_manifest_path(&path).is_file(),
+                    path,
+                },
+            ));
+        }
+        backups.sort_by(|a, b| b.0.cmp(&a.0));
+        Ok(backups.into_iter().map(|(_, entry)| entry).collect())
+    }
+
+    /// Delete a backup of this database and its manifest.
+    ///
+    /// # Errors
+    /// Returns an error when `backup_path` is not a backup of this database or
+    /// the file cannot be removed.
+    pub fn delete_backup(&self, backup_path: &Path) -> Result<(), AppError> {
+        self.ensure_own_backup(backup_path)?;
+        fs::remove_file(backup_path).map_err(|err| {
+            AppError::StorageMessage(format!(
+                "Failed to delete backup '{}': {}",
+                backup_path.display(),
+                err
+            ))
+        })?;
+        match fs::remove_file(backup_manifest_path(backup_path)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                Err(AppError::StorageMessage(format!(
+                    "Failed to delete manifest for '{}': {}",
+                    backup_path.display(),
+                    err
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Delete all but the newest `keep_last` backups of this database.
+    ///
+    /// The backup scheduled for restore is never deleted and does not count
+    /// toward `keep_last`.
+    ///
+    /// # Returns
+    /// Paths of the deleted backups, newest first.
+    ///
+    /// # Errors
+    /// Returns an error when listing fails or a backup cannot be removed;
+    /// backups deleted before the failure stay deleted.
+    pub fn prune_backups(&self, keep_last: usize) -> Result<Vec<PathBuf>, AppError> {
+        let pending = self.pending_restore();
+        let mut deleted = Vec::new();
+        for entry in self
+            .list_backups()?
+            .into_iter()
+            .filter(|entry| pending.as_ref() != Some(&entry.path))
+            .skip(keep_last)
+        {
+            self.delete_backup(&entry.path)?;
+            deleted.push(entry.path);
+        }
+        Ok(deleted)
+    }
+
+    /// Verify `backup_path` and restore it the next time the database is opened.
+    ///
+    /// Used while the database is open, when [`Self::restore_backup`] cannot
+    /// take the owner lock. A later call replaces the scheduled backup.
+    ///
+    /// # Errors
+    /// Returns an error when the backup fails verification or the marker
+    /// cannot be written.
+    pub fn schedule_restore(&self, backup_path: &Path) -> Result<(), AppError> {
+        self.ensure_own_backup(backup_path)?;
+        verify_backup(backup_path)?;
+        let marker = self.restore_marker_path();
+        write_file_atomic(&marker, backup_path.to_string_lossy().as_bytes()).map_err(|err| {
+            AppError::StorageMessage(format!(
+                "Failed to write restore marker '{}': {}",
+                marker.display(),
+                err
+            ))
+        })
+    }
+
+    /// Backup scheduled by [`Self::schedule_restore`], if any.
+    ///
+    /// # Returns
+    /// The scheduled backup path, or `None` when nothing is scheduled.
+    pub fn pending_restore(&self) -> Option<PathBuf> {
+        fs::read_to_string(self.restore_marker_path())
+            .ok()
+            .map(|raw| PathBuf::from(raw.trim()))
+            .filter(|path| !path.as_os_str().is_empty())
+    }
+
+    /// Drop a scheduled restore.
+    ///
+    /// # Errors
+    /// Returns an error when the marker exists but cannot be removed.
+    pub fn cancel_pending_restore(&self) -> Result<(), AppError> {
+        let marker = self.restore_marker_path();
+        match fs::remove_file(&marker) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                Err(AppError::StorageMessage(format!(
+                    "Failed to remove restore marker '{}': {}",
+                    marker.display(),
+                    err
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Restore the backup scheduled by [`Self::schedule_restore`].
+    ///
+    /// Call before opening the database. The marker is removed first, so a
+    /// restore that fails is reported once instead of on every launch.
+    ///
+    /// # Returns
+    /// The restored backup path, or `None` when nothing was scheduled.
+    ///
+    /// # Errors
+    /// Returns an error when the marker cannot be removed or the restore fails.
+    pub fn apply_pending_restore(&self) -> Result<Option<PathBuf>, AppError> {
+        let Some(backup_path) = self.pending_restore() else {
+            return Ok(None);
+        };
+        self.cancel_pending_restore()?;
+        self.restore_backup(&backup_path)?;
+        Ok(Some(backup_path))
+    }
+
+    fn restore_marker_path(&self) -> PathBuf {
+        self.db_path.join(RESTORE_PENDING_FILE_NAME)
+    }
+
+    fn backup_dir(&self) -> PathBuf {
+        parent_dir(&self.db_path)
+    }
+
+    /// Parse `(timestamp, collision suffix)` out of a backup file name
+    /// produced by [`Self::next_backup_path`].
+    fn backup_order_from_name(&self, name: &str) -> Option<(u64, u64)> {
+        let prefix = self.db_path.with_extension("backup");
+        let prefix = prefix.file_name()?.to_string_lossy();
+        let rest = name.strip_prefix(prefix.as_ref())?.strip_prefix('.')?;
+        let rest = rest.strip_suffix(ENCRYPTED_BACKUP_SUFFIX).unwrap_or(rest);
+        let stamp = rest.strip_suffix(".redb")?;
+        match stamp.split_once('.') {
+            Some((timestamp, suffix)) => Some((timestamp.parse().ok()?, suffix.parse().ok()?)),
+            None => Some((stamp.parse().ok()?, 0)),
+        }
+    }
+
+    fn ensure_own_backup(&self, backup_path: &Path) -> Result<(), AppError> {
+        let in_dir = parent_dir(backup_path) == self.backup_dir();
+        let named = backup_path
+            .file_name()
+            .and_then(|name| self.backup_order_from_name(&name.to_string_lossy()))
+            .is_some();
+        if in_dir && named {
+            return Ok(());
+        }
+        Err(AppError::StorageMessage(format!(
+            "'{}' is not a backup of '{}'",
+            backup_path.display(),
+            self.db_path.display()
+        )))
+    }
+
+    fn copy_verified(
+        backup_path: &Path,
+        staging: &Path,
+        expected: &BackupManifestFile,
+    ) -> Result<(), AppError> {
+        fs::copy(backup_path, staging).map_err(|err| {
+            AppError::StorageMessage(format!(
+                "Failed to copy backup to '{}': {}",
+                staging.display(),
+                err
+            ))
+        })?;
+        let (size, checksum) = file_checksum(staging)?;
+        if size != expected.size || checksum != expected.blake3 {
+            return Err(AppError::StorageMessage(
+                "Restored copy does not match the backup manifest".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     fn next_backup_path(&self, timestamp: u64) -> PathBuf {
         let mut candidate = self
             .db_path
             .with_extension(format!("backup.{}.redb", timestamp));
         let mut suffix = 1usize;
-        while candidate.exists() {
+        while candidate.exists()
+            || with_file_name_suffix(&candidate, ENCRYPTED_BACKUP_SUFFIX).exists()
+        {
             candidate = self
                 .db_path
                 .with_extension(format!("backup.{}.{}.redb", timestamp, suffix));
@@ -124,22 +697,102 @@ impl BackupManager {
         Ok(())
     }
 
-    fn copy_updated_index_table(
+    fn copy_str_table(
         source: &redb::ReadTransaction,
         destination: &redb::WriteTransaction,
+        table: redb::TableDefinition<&str, &str>,
     ) -> Result<(), AppError> {
-        let source_table = match source.open_table(PASTES_BY_UPDATED) {
+        let source_table = match source.open_table(table) {
             Ok(table) => table,
             Err(redb::TableError::TableDoesNotExist(_)) => return Ok(()),
             Err(err) => return Err(err.into()),
         };
-        let mut destination_table = destination.open_table(PASTES_BY_UPDATED)?;
+        let mut destination_table = destination.open_table(table)?;
+
+        for row in source_table.iter()? {
+            let (key, value) = row?;
+            let key_owned = key.value().to_string();
+            let value_owned = value.value().to_string();
+            destination_table.insert(key_owned.as_str(), value_owned.as_str())?;
+        }
+
+        Ok(())
+    }
+
+    fn copy_u64_index_table(
+        source: &redb::ReadTransaction,
+        destination: &redb::WriteTransaction,
+        table: redb::TableDefinition<(u64, &str), ()>,
+    ) -> Result<(), AppError> {
+        let source_table = match source.open_table(table) {
+            Ok(table) => table,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut destination_table = destination.open_table(table)?;
+
+        for row in source_table.iter()? {
+            let (key, _) = row?;
+            let (value, paste_id) = key.value();
+            let paste_id_owned = paste_id.to_string();
+            destination_table.insert((value, paste_id_owned.as_str()), ())?;
+        }
+
+        Ok(())
+    }
+
+    fn copy_filter_index_table(
+        source: &redb::ReadTransaction,
+        destination: &redb::WriteTransaction,
+        table: redb::TableDefinition<(&str, u64, &str), ()>,
+    ) -> Result<(), AppError> {
+        let source_table = match source.open_table(table) {
+            Ok(table) => table,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut destination_table = destination.open_table(table)?;
 
         for row in source_table.iter()? {
             let (key, _) = row?;
-            let (reverse_millis, paste_id) = key.value();
+            let (value, reverse_millis, paste_id) = key.value();
+            let value_owned = value.to_string();
+            let paste_id_owned = paste_id.to_string();
+            destination_table.insert(
+                (
+                    value_owned.as_str(),
+                    reverse_millis,
+                    paste_id_owned.as_str(),
+                ),
+                (),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn copy_paste_child_table(
+        source: &redb::ReadTransaction,
+        destination: &redb::WriteTransaction,
+        table: redb::TableDefinition<(&str, &str), &[u8]>,
+    ) -> Result<(), AppError> {
+        let source_table = match source.open_table(table) {
+            Ok(table) => table,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut destination_table = destination.open_table(table)?;
+
+        for row in source_table.iter()? {
+            let (key, value) = row?;
+            let (paste_id, child_id) = key.value();
             let paste_id_owned = paste_id.to_string();
-            destination_table.insert((reverse_millis, paste_id_owned.as_str()), ())?;
+            let child_id_owned = child_id.to_string();
+            let value_owned = value.value().to_vec();
+            destination_table.insert(
+                (paste_id_owned.as_str(), child_id_owned.as_str()),
+                value_owned.as_slice(),
+            )?;
         }
 
         Ok(())
@@ -173,7 +826,10 @@ impl BackupManager {
 
 #[cfg(test)]
 mod tests {
-    use super::{unix_timestamp_seconds, BackupManager};
+    use super::{
+        backup_manifest_path, is_encrypted_backup, unix_timestamp_seconds, verify_backup,
+        BackupManager,
+    };
     use crate::db::paste::{CURRENT_PASTES_META_SCHEMA_VERSION, META_SCHEMA_VERSION_KEY};
     use crate::db::tables::{
         PASTES, PASTES_META, PASTES_META_STATE, PASTE_VERSIONS_CONTENT, PASTE_VERSIONS_META,
@@ -220,6 +876,7 @@ mod tests {
                     language_is_manual: None,
                     folder_id: None,
                     tags: None,
+                    expires_in: None,
                 },
             )
             .expect("update paste");
@@ -288,4 +945,195 @@ mod tests {
             "backup must include historical version content"
         );
     }
+
+    #[test]
+    fn backup_manifest_detects_corruption() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let db_path = temp_dir.path().join("db");
+        let db_path_str = db_path.to_str().expect("db path");
+        let db = open_test_database(db_path_str);
+        db.pastes
+            .create(&Paste::new("checked".to_string(), "checked".to_string()))
+            .expect("create paste");
+
+        let backup_path = BackupManager::new(db_path_str)
+            .create_backup(db.db.as_ref())
+            .expect("create backup");
+        let backup_path = std::path::Path::new(&backup_path);
+        assert!(backup_manifest_path(backup_path).is_file());
+        let manifest = verify_backup(backup_path).expect("fresh backup verifies");
+        assert_eq!(manifest.files.len(), 1);
+
+        let mut bytes = std::fs::read(backup_path).expect("read backup");
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+        std::fs::write(backup_path, bytes).expect("corrupt backup");
+        let err = verify_backup(backup_path).expect_err("corruption must be reported");
+        assert!(err.to_string().contains("failed verification"), "{}", err);
+
+        std::fs::remove_file(backup_manifest_path(backup_path)).expect("remove manifest");
+        let err = verify_backup(backup_path).expect_err("missing manifest must fail");
+        assert!(err.to_string().contains("unreadable"), "{}", err);
+    }
+
+    #[test]
+    fn restore_backup_requires_exclusive_access_and_rolls_back_data() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let db_path = temp_dir.path().join("db");
+        let db_path_str = db_path.to_str().expect("db path");
+        let db = open_test_database(db_path_str);
+        let kept = Paste::new("kept".to_string(), "kept".to_string());
+        db.pastes.create(&kept).expect("create kept");
+        let manager = BackupManager::new(db_path_str);
+        let backup_path = manager.create_backup(db.db.as_ref()).expect("backup");
+        let backup_path = std::path::Path::new(&backup_path);
+        let later = Paste::new("later".to_string(), "later".to_string());
+        db.pastes.create(&later).expect("create later");
+
+        assert!(
+            manager.restore_backup(backup_path).is_err(),
+            "restore must refuse while the database is open"
+        );
+        drop(db);
+        manager.restore_backup(backup_path).expect("restore");
+
+        let restored = open_test_database(db_path_str);
+        assert!(restored.pastes.get(&kept.id).expect("get kept").is_some());
+        assert!(restored.pastes.get(&later.id).expect("get later").is_none());
+    }
+
+    #[test]
+    fn encrypted_backup_hides_content_and_needs_passphrase_to_restore() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let db_path = temp_dir.path().join("db");
+        let db_path_str = db_path.to_str().expect("db path");
+        let db = open_test_database(db_path_str);
+        let secret = Paste::new("sensitive-marker-body".to_string(), "secret".to_string());
+        db.pastes.create(&secret).expect("create paste");
+
+        let manager =
+            BackupManager::new(db_path_str).with_passphrase(Some("correct horse".to_string()));
+        let backup_path = manager.create_backup(db.db.as_ref()).expect("backup");
+        let backup_path = std::path::Path::new(&backup_path);
+        drop(db);
+        assert!(
+            is_encrypted_backup(backup_path),
+            "{}",
+            backup_path.display()
+        );
+        assert!(
+            !backup_path.with_extension("").exists(),
+            "plaintext snapshot must be removed"
+        );
+        let bytes = std::fs::read(backup_path).expect("read backup");
+        assert!(!bytes
+            .windows(b"sensitive-marker-body".len())
+            .any(|window| window == b"sensitive-marker-body"));
+        verify_backup(backup_path).expect("verify without passphrase");
+
+        let err = BackupManager::new(db_path_str)
+            .with_passphrase(None)
+            .restore_backup(backup_path)
+            .expect_err("restore without passphrase");
+        assert!(err.to_string().contains("is encrypted"), "{}", err);
+        let err = BackupManager::new(db_path_str)
+            .with_passphrase(Some("wrong".to_string()))
+            .restore_backup(backup_path)
+            .expect_err("restore with wrong passphrase");
+        assert!(err.to_string().contains("Failed to decrypt"), "{}", err);
+
+        manager.restore_backup(backup_path).expect("restore");
+        let restored = open_test_database(db_path_str);
+        assert!(restored.pastes.get(&secret.id).expect("get").is_some());
+    }
+
+    #[test]
+    fn listed_backups_can_be_deleted_and_scheduled_for_restore() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let db_path = temp_dir.path().join("db");
+        let db_path_str = db_path.to_str().expect("db path");
+        let db = open_test_database(db_path_str);
+        let kept = Paste::new("kept".to_string(), "kept".to_string());
+        db.pastes.create(&kept).expect("create kept");
+        let manager = BackupManager::new(db_path_str).with_passphrase(None);
+        let first = manager.create_backup(db.db.as_ref()).expect("first backup");
+        let second = manager
+            .create_backup(db.db.as_ref())
+            .expect("second backup");
+        std::fs::write(temp_dir.path().join("unrelated.redb"), b"x").expect("unrelated");
+
+        let listed = manager.list_backups().expect("list");
+        let paths: Vec<_> = listed.iter().map(|entry| entry.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                std::path::PathBuf::from(&second),
+                std::path::PathBuf::from(&first)
+            ]
+        );
+        assert!(listed
+            .iter()
+            .all(|entry| entry.has_manifest && entry.size > 0));
+
+        manager
+            .delete_backup(std::path::Path::new(&second))
+            .expect("delete");
+        assert!(!backup_manifest_path(std::path::Path::new(&second)).exists());
+        assert_eq!(manager.list_backups().expect("relist").len(), 1);
+        assert!(manager
+            .delete_backup(&temp_dir.path().join("unrelated.redb"))
+            .is_err());
+
+        let later = Paste::new("later".to_string(), "later".to_string());
+        db.pastes.create(&later).expect("create later");
+        manager
+            .schedule_restore(std::path::Path::new(&first))
+            .expect("schedule while open");
+        assert_eq!(
+            manager.pending_restore(),
+            Some(std::path::PathBuf::from(&first))
+        );
+        drop(db);
+        assert_eq!(
+            manager.apply_pending_restore().expect("apply"),
+            Some(std::path::PathBuf::from(&first))
+        );
+        assert_eq!(manager.pending_restore(), None);
+        assert_eq!(manager.apply_pending_restore().expect("noop"), None);
+        let restored = open_test_database(db_path_str);
+        assert!(restored.pastes.get(&kept.id).expect("get kept").is_some());
+        assert!(restored.pastes.get(&later.id).expect("get later").is_none());
+    }
+
+    #[test]
+    fn prune_backups_keeps_newest_and_scheduled_restore() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let db_path = temp_dir.path().join("db");
+        let db_path_str = db_path.to_str().expect("db path");
+        let db = open_test_database(db_path_str);
+        let manager = BackupManager::new(db_path_str).with_passphrase(None);
+        let backups: Vec<_> = (0..4)
+            .map(|_| {
+                std::path::PathBuf::from(manager.create_backup(db.db.as_ref()).expect("backup"))
+            })
+            .collect();
+        manager
+            .schedule_restore(&backups[0])
+            .expect("schedule oldest");
+
+        let deleted = manager.prune_backups(2).expect("prune");
+        assert_eq!(deleted, vec![backups[1].clone()]);
+        assert!(!backup_manifest_path(&backups[1]).exists());
+        let remaining: Vec<_> = manager
+            .list_backups()
+            .expect("list")
+            .into_iter()
+            .map(|entry| entry.path)
+            .collect();
+        assert_eq!(
+            remaining,
+            vec![backups[3].clone(), backups[2].clone(), backups[0].clone()]
+        );
+        assert!(manager.prune_backups(2).expect("prune again").is_empty());
+    }
 }
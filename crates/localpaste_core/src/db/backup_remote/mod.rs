@@ -0,0 +1,329 @@
+//! Remote destinations for backups.
+//!
+//! After a local backup is created it can be pushed to the targets listed in
+//! the settings file: a user command (for example `rclone copyto`), an
+//! S3-compatible bucket, or a WebDAV collection. The backup and its manifest
+//! are uploaded together. Names pushed to each target are recorded in
+//! `.backup-remote.json`, so `remote_keep_last` can delete the oldest copies
+//! remotely without listing the remote side.
+
+#[cfg(feature = "remote-backup")]
+mod http;
+
+use super::backup::{backup_manifest_path, BACKUP_MANIFEST_SUFFIX};
+use crate::constants::REMOTE_BACKUP_LEDGER_FILE_NAME;
+use crate::discovery::write_file_atomic;
+use crate::error::AppError;
+use crate::settings::{load_settings, settings_file_path_for_db_path, BackupSettings};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// One remote backup destination from the settings file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RemoteBackupTarget {
+    /// Run a local command per file. `{path}` and `{name}` are substituted in
+    /// each argument; without `delete`, remote retention is skipped.
+    Command {
+        name: String,
+        upload: Vec<String>,
+        #[serde(default)]
+        delete: Option<Vec<String>>,
+    },
+    /// PUT objects into an S3-compatible bucket (path-style URLs, SigV4).
+    S3 {
+        name: String,
+        endpoint: String,
+        bucket: String,
+        region: String,
+        #[serde(default)]
+        prefix: String,
+        /// Environment variable holding the access key id.
+        access_key_env: String,
+        /// Environment variable holding the secret access key.
+        secret_key_env: String,
+    },
+    /// PUT files into a WebDAV collection.
+    Webdav {
+        name: String,
+        url: String,
+        #[serde(default)]
+        username: Option<String>,
+        /// Environment variable holding the basic-auth password.
+        #[serde(default)]
+        password_env: Option<String>,
+    },
+}
+
+impl RemoteBackupTarget {
+    /// Name used in reports and as the retention ledger key.
+    ///
+    /// # Returns
+    /// The configured target name.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Command { name, .. } | Self::S3 { name, .. } | Self::Webdav { name, .. } => name,
+        }
+    }
+
+    fn upload(&self, path: &Path, file_name: &str) -> Result<(), String> {
+        match self {
+            Self::Command { upload, .. } => run_target_command(upload, path, file_name),
+            #[cfg(feature = "remote-backup")]
+            Self::S3 { .. } | Self::Webdav { .. } => http::upload(self, path, file_name),
+            #[cfg(not(feature = "remote-backup"))]
+            Self::S3 { .. } | Self::Webdav { .. } => Err(http_unavailable()),
+        }
+    }
+
+    /// Delete `file_name` remotely.
+    ///
+    /// # Returns
+    /// `Ok(false)` when this target has no way to delete files.
+    fn delete(&self, file_name: &str) -> Result<bool, String> {
+        match self {
+            Self::Command { delete: None, .. } => Ok(false),
+            Self::Command {
+                delete: Some(delete),
+                ..
+            } => run_target_command(delete, Path::new(""), file_name).map(|()| true),
+            #[cfg(feature = "remote-backup")]
+            Self::S3 { .. } | Self::Webdav { .. } => http::delete(self, file_name).map(|()| true),
+            #[cfg(not(feature = "remote-backup"))]
+            Self::S3 { .. } | Self::Webdav { .. } => Err(http_unavailable()),
+        }
+    }
+}
+
+#[cfg(not(feature = "remote-backup"))]
+fn http_unavailable() -> String {
+    "S3 and WebDAV targets need a build with the `remote-backup` feature".to_string()
+}
+
+fn run_target_command(argv: &[String], path: &Path, file_name: &str) -> Result<(), String> {
+    let path = path.to_string_lossy();
+    let substitute = |arg: &String| arg.replace("{path}", &path).replace("{name}", file_name);
+    let (program, args) = argv
+        .split_first()
+        .ok_or_else(|| "command is empty".to_string())?;
+    let program = substitute(program);
+    let status = Command::new(&program)
+        .args(args.iter().map(substitute))
+        .status()
+        .map_err(|err| format!("failed to run `{}`: {}", program, err))?;
+    if !status.success() {
+        return Err(format!("`{}` exited with {}", program, status));
+    }
+    Ok(())
+}
+
+/// Outcome of pushing one backup to one remote target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemotePushReport {
+    pub target: String,
+    /// Backup names pruned by remote retention, or the upload/prune error.
+    pub outcome: Result<Vec<String>, String>,
+}
+
+type RemoteLedger = BTreeMap<String, Vec<String>>;
+
+fn ledger_path(db_path: &str) -> PathBuf {
+    settings_file_path_for_db_path(db_path).with_file_name(REMOTE_BACKUP_LEDGER_FILE_NAME)
+}
+
+fn load_ledger(path: &Path) -> Result<RemoteLedger, AppError> {
+    match fs::read(path) {
+        Ok(raw) => serde_json::from_slice(&raw).map_err(|err| {
+            AppError::StorageMessage(format!(
+                "Remote backup ledger '{}' is malformed: {}",
+                path.display(),
+                err
+            ))
+        }),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(RemoteLedger::new()),
+        Err(err) => Err(AppError::StorageMessage(format!(
+            "Failed to read remote backup ledger '{}': {}",
+            path.display(),
+            err
+        ))),
+    }
+}
+
+/// Push a freshly created backup to every configured remote target.
+///
+/// Targets run one after another on a helper thread, so this is safe to call
+/// from inside an async runtime. A failing target does not stop the others;
+/// its error is returned in the report and its retention is skipped.
+///
+/// # Arguments
+/// - `db_path`: Database path whose settings file lists the targets.
+/// - `backup_path`: Local backup file; its manifest is uploaded alongside.
+///
+/// # Returns
+/// One report per configured target (empty when none are configured).
+///
+/// # Errors
+/// Returns an error when the settings file or retention ledger cannot be
+/// read, or the ledger cannot be written back.
+pub fn push_backup_to_remote_targets(
+    db_path: &str,
+    backup_path: &Path,
+) -> Result<Vec<RemotePushReport>, AppError> {
+    let settings = load_settings(db_path)?.backup;
+    if settings.remote_targets.is_empty() {
+        return Ok(Vec::new());
+    }
+    let ledger_path = ledger_path(db_path);
+    let mut ledger = load_ledger(&ledger_path)?;
+    let reports = std::thread::scope(|scope| {
+        scope
+            .spawn(|| push_to_targets(&settings, backup_path, &mut ledger))
+            .join()
+    })
+    .map_err(|_| AppError::StorageMessage("Remote backup push panicked".to_string()))?;
+
+    let bytes = serde_json::to_vec_pretty(&ledger)
+        .map_err(|err| AppError::StorageMessage(format!("Failed to encode ledger: {}", err)))?;
+    write_file_atomic(&ledger_path, &bytes).map_err(|err| {
+        AppError::StorageMessage(format!(
+            "Failed to write remote backup ledger '{}': {}",
+            ledger_path.display(),
+            err
+        ))
+    })?;
+    Ok(reports)
+}
+
+fn push_to_targets(
+    settings: &BackupSettings,
+    backup_path: &Path,
+    ledger: &mut RemoteLedger,
+) -> Vec<RemotePushReport> {
+    let manifest_path = backup_manifest_path(backup_path);
+    let backup_name = backup_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let manifest_name = format!("{}{}", backup_name, BACKUP_MANIFEST_SUFFIX);
+    settings
+        .remote_targets
+        .iter()
+        .map(|target| {
+            let pushed = ledger.entry(target.name().to_string()).or_default();
+            let outcome = target
+                .upload(backup_path, &backup_name)
+                .and_then(|()| target.upload(&manifest_path, &manifest_name))
+                .and_then(|()| {
+                    pushed.retain(|name| name != &backup_name);
+                    pushed.push(backup_name.clone());
+                    prune_remote(target, pushed, settings.remote_keep_last)
+                });
+            if let Err(err) = &outcome {
+                tracing::warn!("Remote backup target '{}' failed: {}", target.name(), err);
+            }
+            RemotePushReport {
+                target: target.name().to_string(),
+                outcome,
+            }
+        })
+        .collect()
+}
+
+/// Delete the oldest pushed backups until at most `keep_last` remain.
+///
+/// The newest backup is always kept. Entries stay in the ledger until both
+/// the backup and its manifest are deleted, so a failed prune is retried on
+/// the next push.
+fn prune_remote(
+    target: &RemoteBackupTarget,
+    pushed: &mut Vec<String>,
+    keep_last: Option<usize>,
+) -> Result<Vec<String>, String> {
+    let Some(keep_last) = keep_last else {
+        return Ok(Vec::new());
+    };
+    let mut pruned = Vec::new();
+    while pushed.len() > keep_last.max(1) {
+        let oldest = pushed[0].clone();
+        if !target.delete(&oldest)? {
+            break;
+        }
+        target.delete(&format!("{}{}", oldest, BACKUP_MANIFEST_SUFFIX))?;
+        pushed.remove(0);
+        pruned.push(oldest);
+    }
+    Ok(pruned)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::db::backup::BackupManager;
+    use crate::models::paste::Paste;
+    use crate::settings::{save_settings, Settings};
+    use crate::test_support::open_test_database;
+    use tempfile::TempDir;
+
+    #[test]
+    fn command_target_uploads_backups_and_prunes_remotely() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let db_path = temp_dir.path().join("db");
+        let db_path_str = db_path.to_str().expect("db path");
+        let remote = temp_dir.path().join("remote");
+        fs::create_dir_all(&remote).expect("remote dir");
+        let remote_file = format!("{}/{{name}}", remote.display());
+
+        let db = open_test_database(db_path_str);
+        db.pastes
+            .create(&Paste::new("remote".to_string(), "remote".to_string()))
+            .expect("create paste");
+        let mut settings = Settings::default();
+        settings.backup.remote_keep_last = Some(1);
+        settings.backup.remote_targets = vec![
+            RemoteBackupTarget::Command {
+                name: "copy".to_string(),
+                upload: vec!["cp".into(), "{path}".into(), remote_file.clone()],
+                delete: Some(vec!["rm".into(), remote_file]),
+            },
+            RemoteBackupTarget::Command {
+                name: "broken".to_string(),
+                upload: vec!["false".into()],
+                delete: None,
+            },
+        ];
+        save_settings(db_path_str, &settings).expect("save settings");
+
+        let manager = BackupManager::new(db_path_str).with_passphrase(None);
+        let first = manager.create_backup(db.db.as_ref()).expect("first backup");
+        let first = Path::new(&first);
+        let reports = push_backup_to_remote_targets(db_path_str, first).expect("push first");
+        assert_eq!(reports[0].outcome, Ok(Vec::new()));
+        assert!(reports[1].outcome.is_err(), "failing target is reported");
+        let first_name = first.file_name().expect("name").to_owned();
+        assert!(remote.join(&first_name).is_file());
+        assert!(remote
+            .join(backup_manifest_path(Path::new(&first_name)))
+            .is_file());
+
+        let second = manager
+            .create_backup(db.db.as_ref())
+            .expect("second backup");
+        let reports =
+            push_backup_to_remote_targets(db_path_str, Path::new(&second)).expect("push second");
+        assert_eq!(
+            reports[0].outcome,
+            Ok(vec![first_name.to_string_lossy().to_string()])
+        );
+        let remaining: Vec<_> = fs::read_dir(&remote)
+            .expect("read remote")
+            .filter_map(Result::ok)
+            .map(|entry| entry.file_name())
+            .collect();
+        assert_eq!(remaining.len(), 2, "{:?}", remaining);
+        assert!(!remote.join(&first_name).exists());
+    }
+}
@@ -0,0 +1,286 @@
+//! Comment threads stored beside pastes.
+//!
+//! Rows are keyed by `(paste_id, comment_id)` so one range scan reads a whole
+//! thread and deleting a paste can drop its comments in the same transaction.
+
+use crate::{
+    db::tables::{PASTES, PASTE_COMMENTS},
+    error::AppError,
+    models::comment::{
+        Comment, DEFAULT_COMMENT_AUTHOR, MAX_COMMENT_AUTHOR_CHARS, MAX_COMMENT_CHARS,
+    },
+};
+use chrono::Utc;
+use redb::{ReadableDatabase, ReadableTable};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Trim a note body and enforce the shared length cap.
+///
+/// `kind` names the note (`Comment`, `Annotation`) in error messages.
+pub(crate) fn validated_body(kind: &str, body: &str) -> Result<String, AppError> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(AppError::BadRequest(format!(
+            "{} body cannot be empty",
+            kind
+        )));
+    }
+    if body.chars().count() > MAX_COMMENT_CHARS {
+        return Err(AppError::BadRequest(format!(
+            "{} exceeds {} characters",
+            kind, MAX_COMMENT_CHARS
+        )));
+    }
+    Ok(body.to_string())
+}
+
+/// Trim an author label to its display form, capped at
+/// [`MAX_COMMENT_AUTHOR_CHARS`].
+pub fn normalize_author(author: &str) -> String {
+    author
+        .trim()
+        .chars()
+        .take(MAX_COMMENT_AUTHOR_CHARS)
+        .collect()
+}
+
+/// Remove every comment on `paste_id` inside an open write transaction.
+///
+/// # Errors
+/// Returns an error when the comments table cannot be read or written.
+pub(crate) fn remove_paste_comments(
+    write_txn: &redb::WriteTransaction,
+    paste_id: &str,
+) -> Result<(), AppError> {
+    let mut table = write_txn.open_table(PASTE_COMMENTS)?;
+    let comment_ids: Vec<String> = table
+        .range((paste_id, "")..)?
+        .map_while(|entry| {
+            let (key, _) = entry.ok()?;
+            let (owner, comment_id) = key.value();
+            (owner == paste_id).then(|| comment_id.to_string())
+        })
+        .collect();
+    for comment_id in comment_ids {
+        table.remove((paste_id, comment_id.as_str()))?;
+    }
+    Ok(())
+}
+
+/// Accessor for the paste comments table.
+pub struct CommentDb {
+    db: Arc<redb::Database>,
+}
+
+impl CommentDb {
+    /// Initialize the comments table if it does not exist yet.
+    ///
+    /// # Returns
+    /// A new [`CommentDb`] accessor bound to `db`.
+    ///
+    /// # Errors
+    /// Returns an error when redb transaction/table initialization fails.
+    pub fn new(db: Arc<redb::Database>) -> Result<Self, AppError> {
+        let write_txn = db.begin_write()?;
+        write_txn.open_table(PASTE_COMMENTS)?;
+        write_txn.commit()?;
+        Ok(Self { db })
+    }
+
+    /// List a paste's comments, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn list(&self, paste_id: &str) -> Result<Vec<Comment>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(PASTE_COMMENTS)?;
+        let mut comments = Vec::new();
+        for entry in table.range((paste_id, "")..)? {
+            let (key, value) = entry?;
+            if key.value().0 != paste_id {
+                break;
+            }
+            comments.push(bincode::deserialize::<Comment>(value.value())?);
+        }
+        comments.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        Ok(comments)
+    }
+
+    /// Fetch one comment.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn get(&self, paste_id: &str, comment_id: &str) -> Result<Option<Comment>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(PASTE_COMMENTS)?;
+        table
+            .get((paste_id, comment_id))?
+            .map(|row| bincode::deserialize::<Comment>(row.value()))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Add a comment to an existing paste.
+    ///
+    /// # Arguments
+    /// - `paste_id`: Paste the comment belongs to.
+    /// - `author`: Display label; blank falls back to [`DEFAULT_COMMENT_AUTHOR`].
+    /// - `author_id`: Account id in multi-user mode.
+    /// - `body`: Comment text; surrounding whitespace is trimmed.
+    ///
+    /// # Returns
+    /// The stored comment.
+    ///
+    /// # Errors
+    /// Returns `NotFound` when the paste is missing, `BadRequest` for an empty
+    /// or oversized body, or an error when storage fails.
+    pub fn create(
+        &self,
+        paste_id: &str,
+        author: &str,
+        author_id: Option<&str>,
+        body: &str,
+    ) -> Result<Comment, AppError> {
+        let body = validated_body("Comment", body)?;
+        let author = match normalize_author(author) {
+            author if author.is_empty() => DEFAULT_COMMENT_AUTHOR.to_string(),
+            author => author,
+        };
+        let now = Utc::now();
+        let comment = Comment {
+            id: Uuid::new_v4().to_string(),
+            paste_id: paste_id.to_string(),
+            author,
+            author_id: author_id.map(str::to_string),
+            body,
+            created_at: now,
+            updated_at: now,
+        };
+        let encoded = bincode::serialize(&comment)?;
+        let write_txn = self.db.begin_write()?;
+        {
+            // Checked in the same transaction so a concurrent delete cannot
+            // leave a comment behind on a missing paste.
+            if write_txn.open_table(PASTES)?.get(paste_id)?.is_none() {
+                return Err(AppError::NotFound);
+            }
+            let mut table = write_txn.open_table(PASTE_COMMENTS)?;
+            table.insert((paste_id, comment.id.as_str()), encoded.as_slice())?;
+        }
+        write_txn.commit()?;
+        Ok(comment)
+    }
+
+    /// Replace a comment's body.
+    ///
+    /// # Returns
+    /// The updated comment, or `Ok(None)` when it does not exist.
+    ///
+    /// # Errors
+    /// Returns `BadRequest` for an empty or oversized body, or an error when
+    /// storage fails.
+    pub fn update(
+        &self,
+        paste_id: &str,
+        comment_id: &str,
+        body: &str,
+    ) -> Result<Option<Comment>, AppError> {
+        let body = validated_body("Comment", body)?;
+        let write_txn = self.db.begin_write()?;
+        let updated = {
+            let mut table = write_txn.open_table(PASTE_COMMENTS)?;
+            let existing = table
+                .get((paste_id, comment_id))?
+                .map(|row| bincode::deserialize::<Comment>(row.value()))
+                .transpose()?;
+            let Some(mut comment) = existing else {
+                return Ok(None);
+            };
+            comment.body = body;
+            comment.updated_at = Utc::now();
+            let encoded = bincode::serialize(&comment)?;
+            table.insert((paste_id, comment_id), encoded.as_slice())?;
+            comment
+        };
+        write_txn.commit()?;
+        Ok(Some(updated))
+    }
+
+    /// Delete one comment.
+    ///
+    /// # Returns
+    /// `Ok(true)` when a comment was removed, `Ok(false)` when none existed.
+    ///
+    /// # Errors
+    /// Returns an error when storage operations fail.
+    pub fn delete(&self, paste_id: &str, comment_id: &str) -> Result<bool, AppError> {
+        let write_txn = self.db.begin_write()?;
+        let removed = write_txn
+            .open_table(PASTE_COMMENTS)?
+            .remove((paste_id, comment_id))?
+            .is_some();
+        write_txn.commit()?;
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AppError;
+    use crate::models::paste::Paste;
+    use crate::test_support::setup_temp_db;
+
+    #[test]
+    fn threads_are_ordered_per_paste_and_removed_with_the_paste() {
+        let (db, _temp) = setup_temp_db();
+        let paste = Paste::new("fn main() {}".to_string(), "main".to_string());
+        let other = Paste::new("other".to_string(), "other".to_string());
+        db.pastes.create(&paste).expect("paste");
+        db.pastes.create(&other).expect("paste");
+
+        let first = db
+            .comments
+            .create(&paste.id, "  ana ", None, " looks good ")
+            .expect("comment");
+        assert_eq!(first.author, "ana");
+        assert_eq!(first.body, "looks good");
+        let second = db
+            .comments
+            .create(&paste.id, "", None, "nit: rename")
+            .expect("comment");
+        assert_eq!(second.author, "anonymous");
+        db.comments
+            .create(&other.id, "ana", None, "unrelated")
+            .expect("comment");
+        assert!(matches!(
+            db.comments.create(&paste.id, "ana", None, "   "),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(matches!(
+            db.comments.create("missing", "ana", None, "hello"),
+            Err(AppError::NotFound)
+        ));
+
+        let edited = db
+            .comments
+            .update(&paste.id, &second.id, "nit: rename to run")
+            .expect("update")
+            .expect("exists");
+        assert_eq!(edited.body, "nit: rename to run");
+        let thread: Vec<String> = db
+            .comments
+            .list(&paste.id)
+            .expect("list")
+            .into_iter()
+            .map(|comment| comment.id)
+            .collect();
+        assert_eq!(thread, vec![first.id.clone(), second.id.clone()]);
+
+        assert!(db.comments.delete(&paste.id, &first.id).expect("delete"));
+        assert!(!db.comments.delete(&paste.id, &first.id).expect("delete"));
+        assert!(db.pastes.delete(&paste.id).expect("delete paste"));
+        assert!(db.comments.list(&paste.id).expect("list").is_empty());
+        assert_eq!(db.comments.list(&other.id).expect("list").len(), 1);
+    }
+}
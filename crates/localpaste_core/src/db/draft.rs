@@ -0,0 +1,89 @@
+//! Crash-recovery draft storage backed by redb.
+//!
+//! Drafts live in their own table so periodic writes never touch paste rows,
+//! versions, or the metadata index.
+
+use crate::{db::tables::DRAFTS, error::AppError, models::draft::Draft};
+use redb::{ReadableDatabase, ReadableTable};
+use std::sync::Arc;
+
+/// Accessor for the drafts table.
+pub struct DraftDb {
+    db: Arc<redb::Database>,
+}
+
+impl DraftDb {
+    /// Initialize the drafts table if it does not exist yet.
+    ///
+    /// # Returns
+    /// A new [`DraftDb`] accessor bound to `db`.
+    ///
+    /// # Errors
+    /// Returns an error when redb transaction/table initialization fails.
+    pub fn new(db: Arc<redb::Database>) -> Result<Self, AppError> {
+        let write_txn = db.begin_write()?;
+        write_txn.open_table(DRAFTS)?;
+        write_txn.commit()?;
+        Ok(Self { db })
+    }
+
+    /// Insert or replace the draft stored under `draft.key`.
+    ///
+    /// # Returns
+    /// `Ok(())` when the draft is persisted.
+    ///
+    /// # Errors
+    /// Returns an error when serialization or storage fails.
+    pub fn put(&self, draft: &Draft) -> Result<(), AppError> {
+        let encoded = bincode::serialize(draft)?;
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut drafts = write_txn.open_table(DRAFTS)?;
+            drafts.insert(draft.key.as_str(), encoded.as_slice())?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// List every stored draft, most recently updated first.
+    ///
+    /// Undecodable rows are skipped so one bad draft cannot block recovery.
+    ///
+    /// # Returns
+    /// Stored drafts.
+    ///
+    /// # Errors
+    /// Returns an error when storage access fails.
+    pub fn list(&self) -> Result<Vec<Draft>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let drafts = read_txn.open_table(DRAFTS)?;
+        let mut items = Vec::new();
+        for entry in drafts.iter()? {
+            let (key, value) = entry?;
+            match bincode::deserialize::<Draft>(value.value()) {
+                Ok(draft) => items.push(draft),
+                Err(err) => tracing::warn!("Skipping unreadable draft '{}': {}", key.value(), err),
+            }
+        }
+        items.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(items)
+    }
+
+    /// Remove the draft stored under `key`.
+    ///
+    /// # Returns
+    /// `Ok(true)` when a draft was removed, `Ok(false)` when none existed.
+    ///
+    /// # Errors
+    /// Returns an error when storage operations fail.
+    pub fn delete(&self, key: &str) -> Result<bool, AppError> {
+        let write_txn = self.db.begin_write()?;
+        let removed = {
+            let mut drafts = write_txn.open_table(DRAFTS)?;
+            let removed = drafts.remove(key)?.is_some();
+            removed
+        };
+        write_txn.commit()?;
+        Ok(removed)
+    }
+}
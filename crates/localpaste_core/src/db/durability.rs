@@ -0,0 +1,309 @@
+//! When committed writes are made durable on disk.
+//!
+//! redb fsyncs every commit by default. The relaxed policies commit journaled
+//! paste updates with [`Durability::None`] instead and rely on
+//! [`Database::flush`] to persist them, trading an fsync of the database for
+//! an append to the mutation journal. All other writes stay durable.
+//! Readers in the same process always see a committed write immediately,
+//! whatever the policy.
+//!
+//! [`Database::flush`]: super::Database::flush
+
+use crate::db::journal::Journal;
+use crate::error::AppError;
+use redb::{Database as RedbDatabase, Durability, WriteTransaction};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock, Weak};
+use std::time::{Duration, Instant};
+
+/// Shortest poll period for the idle policy's flush check.
+const MIN_IDLE_POLL: Duration = Duration::from_millis(10);
+
+/// How committed writes reach disk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlushPolicy {
+    /// Every commit is durable when it returns.
+    #[default]
+    EveryCommit,
+    /// Flush pending commits at most this long after the previous flush.
+    Interval(Duration),
+    /// Flush pending commits once no write has started for this long.
+    Idle(Duration),
+}
+
+impl FlushPolicy {
+    /// Build a policy from its name and period.
+    ///
+    /// # Arguments
+    /// - `name`: `commit`, `interval`, or `idle` (case-insensitive).
+    /// - `period`: Flush period for `interval` and `idle`; ignored for `commit`.
+    ///
+    /// # Returns
+    /// The matching policy.
+    ///
+    /// # Errors
+    /// Returns a message when `name` is unknown or a relaxed policy has a zero
+    /// period.
+    pub fn from_name(name: &str, period: Duration) -> Result<Self, String> {
+        let policy = name.parse::<Self>()?;
+        Ok(match policy {
+            Self::EveryCommit => Self::EveryCommit,
+            _ if period.is_zero() => {
+                return Err("flush period must be greater than zero".to_string());
+            }
+            Self::Interval(_) => Self::Interval(period),
+            Self::Idle(_) => Self::Idle(period),
+        })
+    }
+
+    /// How often a background task should call [`Database::flush_if_due`].
+    ///
+    /// # Returns
+    /// `None` for [`FlushPolicy::EveryCommit`], which needs no task.
+    ///
+    /// [`Database::flush_if_due`]: super::Database::flush_if_due
+    pub fn poll_interval(self) -> Option<Duration> {
+        match self {
+            Self::EveryCommit => None,
+            Self::Interval(period) => Some(period),
+            Self::Idle(period) => Some((period / 2).max(MIN_IDLE_POLL)),
+        }
+    }
+
+    pub(crate) fn is_relaxed(self) -> bool {
+        self != Self::EveryCommit
+    }
+}
+
+impl FromStr for FlushPolicy {
+    type Err = String;
+
+    /// Parse a policy name; relaxed policies get a zero period to fill in.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "commit" => Ok(Self::EveryCommit),
+            "interval" => Ok(Self::Interval(Duration::ZERO)),
+            "idle" => Ok(Self::Idle(Duration::ZERO)),
+            other => Err(format!(
+                "unknown flush policy '{}' (expected 'commit', 'interval', or 'idle')",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FlushTimes {
+    last_write: Instant,
+    last_flush: Instant,
+}
+
+/// Flush policy and pending-write tracking shared by every handle on one
+/// redb instance.
+#[derive(Debug)]
+pub(crate) struct DurabilityState {
+    policy: RwLock<FlushPolicy>,
+    dirty: AtomicBool,
+    times: Mutex<FlushTimes>,
+    // Held across a journaled write and across a flush, so a flush never
+    // empties the journal between a relaxed commit and its entry.
+    journal: Mutex<Option<Journal>>,
+}
+
+impl Default for DurabilityState {
+    fn default() -> Self {
+        let now = Instant::now();
+        Self {
+            policy: RwLock::new(FlushPolicy::default()),
+            dirty: AtomicBool::new(false),
+            times: Mutex::new(FlushTimes {
+                last_write: now,
+                last_flush: now,
+            }),
+            journal: Mutex::new(None),
+        }
+    }
+}
+
+fn registry() -> &'static RwLock<HashMap<usize, Weak<DurabilityState>>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<usize, Weak<DurabilityState>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn registry_key(db: &RedbDatabase) -> usize {
+    db as *const RedbDatabase as usize
+}
+
+impl DurabilityState {
+    /// Shared state for `db`, created on first use.
+    pub(crate) fn for_db(db: &Arc<RedbDatabase>) -> Result<Arc<Self>, AppError> {
+        let mut registry = registry().write().map_err(|_| {
+            AppError::StorageMessage("Durability state registry poisoned".to_string())
+        })?;
+        registry.retain(|_, state| state.upgrade().is_some());
+        let key = registry_key(db);
+        if let Some(existing) = registry.get(&key).and_then(Weak::upgrade) {
+            return Ok(existing);
+        }
+        let state = Arc::new(Self::default());
+        registry.insert(key, Arc::downgrade(&state));
+        Ok(state)
+    }
+
+    pub(crate) fn lookup(db: &RedbDatabase) -> Option<Arc<Self>> {
+        registry()
+            .read()
+            .ok()?
+            .get(&registry_key(db))
+            .and_then(Weak::upgrade)
+    }
+
+    fn lock_times(&self) -> std::sync::MutexGuard<'_, FlushTimes> {
+        self.times
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn lock_journal(&self) -> std::sync::MutexGuard<'_, Option<Journal>> {
+        self.journal
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn attach_journal(&self, journal: Journal) {
+        *self.lock_journal() = Some(journal);
+    }
+
+    pub(crate) fn policy(&self) -> FlushPolicy {
+        *self
+            .policy
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn set_policy(&self, policy: FlushPolicy) {
+        // Not changed mid-write, so a write is journaled exactly when its
+        // commit is relaxed.
+        let _journal = self.lock_journal();
+        *self
+            .policy
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = policy;
+    }
+
+    /// Whether pending writes are old enough to flush under the policy.
+    pub(crate) fn flush_due(&self) -> bool {
+        if !self.dirty.load(Ordering::SeqCst) {
+            return false;
+        }
+        let times = self.lock_times();
+        match self.policy() {
+            // Left over from a relaxed policy that was just switched off.
+            FlushPolicy::EveryCommit => true,
+            FlushPolicy::Interval(period) => times.last_flush.elapsed() >= period,
+            FlushPolicy::Idle(period) => times.last_write.elapsed() >= period,
+        }
+    }
+
+    /// Commit an empty durable transaction when non-durable commits are
+    /// pending, which persists them too, then empty the journal.
+    pub(crate) fn flush(&self, db: &RedbDatabase) -> Result<(), AppError> {
+        let mut journal = self.lock_journal();
+        // Cleared before committing: a writer that starts afterwards marks the
+        // state dirty again, and one already running commits before ours.
+        if !self.dirty.swap(false, Ordering::SeqCst) {
+            return Ok(());
+        }
+        let result = db
+            .begin_write()
+            .map_err(AppError::from)
+            .and_then(|write_txn| write_txn.commit().map_err(AppError::from));
+        match result {
+            Ok(()) => {
+                self.lock_times().last_flush = Instant::now();
+                match journal.as_mut() {
+                    Some(journal) => journal.clear(),
+                    None => Ok(()),
+                }
+            }
+            Err(err) => {
+                self.dirty.store(true, Ordering::SeqCst);
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Write transactions that may use the database's relaxed [`FlushPolicy`].
+///
+/// Only writes the mutation journal covers commit with
+/// [`Durability::None`]; every other write uses plain `begin_write` and is
+/// durable when it commits, whatever the policy.
+pub(crate) trait PolicyWrite {
+    /// Begin a write transaction, relaxed when `journaled` is set.
+    ///
+    /// # Arguments
+    /// - `journaled`: Whether the caller appends this write to the journal
+    ///   (see [`JournalSlot::is_active`]).
+    ///
+    /// # Errors
+    /// Returns an error when the transaction cannot be started.
+    ///
+    /// [`JournalSlot::is_active`]: crate::db::journal::JournalSlot::is_active
+    fn begin_journaled_write(&self, journaled: bool) -> Result<WriteTransaction, AppError>;
+}
+
+impl PolicyWrite for RedbDatabase {
+    fn begin_journaled_write(&self, journaled: bool) -> Result<WriteTransaction, AppError> {
+        let mut write_txn = self.begin_write()?;
+        if !journaled {
+            return Ok(write_txn);
+        }
+        let Some(state) = DurabilityState::lookup(self) else {
+            return Ok(write_txn);
+        };
+        if !state.policy().is_relaxed() {
+            return Ok(write_txn);
+        }
+        write_txn.set_durability(Durability::None).map_err(|err| {
+            AppError::StorageMessage(format!("Failed to relax durability: {}", err))
+        })?;
+        state.dirty.store(true, Ordering::SeqCst);
+        state.lock_times().last_write = Instant::now();
+        Ok(write_txn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FlushPolicy;
+    use std::time::Duration;
+
+    #[test]
+    fn from_name_parses_policies_and_rejects_zero_periods() {
+        let second = Duration::from_secs(1);
+        assert_eq!(
+            FlushPolicy::from_name("commit", Duration::ZERO),
+            Ok(FlushPolicy::EveryCommit)
+        );
+        assert_eq!(
+            FlushPolicy::from_name(" Interval ", second),
+            Ok(FlushPolicy::Interval(second))
+        );
+        assert_eq!(
+            FlushPolicy::from_name("idle", second),
+            Ok(FlushPolicy::Idle(second))
+        );
+        assert!(FlushPolicy::from_name("idle", Duration::ZERO).is_err());
+        assert!(FlushPolicy::from_name("sometimes", second).is_err());
+        assert_eq!(
+            FlushPolicy::Idle(second).poll_interval(),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(FlushPolicy::EveryCommit.poll_interval(), None);
+    }
+}
@@ -1,9 +1,49 @@
 //! Folder storage operations backed by redb.
 
-use crate::{db::tables::*, error::AppError, models::folder::*};
+use crate::{db::slow_query::SlowQueryTimer, db::tables::*, error::AppError, models::folder::*};
+use chrono::{DateTime, Utc};
 use redb::{ReadableDatabase, ReadableTable};
+use serde::Deserialize;
 use std::sync::Arc;
 
+/// Folder row layout written before per-folder byte/recency stats existed.
+#[derive(Deserialize)]
+struct PreStatsFolder {
+    id: String,
+    name: String,
+    created_at: DateTime<Utc>,
+    paste_count: usize,
+    parent_id: Option<String>,
+}
+
+impl From<PreStatsFolder> for Folder {
+    fn from(old: PreStatsFolder) -> Self {
+        Self {
+            id: old.id,
+            name: old.name,
+            created_at: old.created_at,
+            paste_count: old.paste_count,
+            parent_id: old.parent_id,
+            total_bytes: 0,
+            last_updated: None,
+        }
+    }
+}
+
+/// Deserializes a [`Folder`] row, upgrading rows written before folder stats.
+///
+/// Upgraded rows report zero bytes until startup reconciliation recomputes them.
+///
+/// # Errors
+/// Returns a bincode error when the row bytes match neither layout.
+pub(crate) fn deserialize_folder(bytes: &[u8]) -> Result<Folder, bincode::Error> {
+    bincode::deserialize::<Folder>(bytes).or_else(|err| {
+        bincode::deserialize::<PreStatsFolder>(bytes)
+            .map(Folder::from)
+            .map_err(|_| err)
+    })
+}
+
 /// Accessor for folder-related redb tables.
 pub struct FolderDb {
     db: Arc<redb::Database>,
@@ -37,6 +77,7 @@ impl FolderDb {
     /// Returns an error when serialization fails, the id already exists, or
     /// underlying storage operations fail.
     pub fn create(&self, folder: &Folder) -> Result<(), AppError> {
+        let _slow = SlowQueryTimer::start("folder_db.create").with_keys(1);
         let encoded = bincode::serialize(folder)?;
         let write_txn = self.db.begin_write()?;
         {
@@ -61,10 +102,11 @@ impl FolderDb {
     /// # Errors
     /// Returns an error when storage access or deserialization fails.
     pub fn get(&self, id: &str) -> Result<Option<Folder>, AppError> {
+        let _slow = SlowQueryTimer::start("folder_db.get").with_keys(1);
         let read_txn = self.db.begin_read()?;
         let folders = read_txn.open_table(FOLDERS)?;
         match folders.get(id)? {
-            Some(value) => Ok(Some(bincode::deserialize(value.value())?)),
+            Some(value) => Ok(Some(deserialize_folder(value.value())?)),
             None => Ok(None),
         }
     }
@@ -89,6 +131,7 @@ impl FolderDb {
         name: String,
         parent_id: Option<String>,
     ) -> Result<Option<Folder>, AppError> {
+        let _slow = SlowQueryTimer::start("folder_db.update").with_keys(1);
         self.update_folder_record(id, move |folder| {
             folder.name = name.clone();
             if let Some(ref pid) = parent_id {
@@ -110,6 +153,7 @@ impl FolderDb {
     /// # Errors
     /// Returns an error when storage operations fail.
     pub fn delete(&self, id: &str) -> Result<bool, AppError> {
+        let _slow = SlowQueryTimer::start("folder_db.delete").with_keys(1);
         let write_txn = self.db.begin_write()?;
         let removed = {
             let mut folders = write_txn.open_table(FOLDERS)?;
@@ -130,12 +174,14 @@ impl FolderDb {
     /// # Errors
     /// Returns an error when storage access or deserialization fails.
     pub fn list(&self) -> Result<Vec<Folder>, AppError> {
+        let mut slow = SlowQueryTimer::start("folder_db.list");
         let read_txn = self.db.begin_read()?;
         let folders_table = read_txn.open_table(FOLDERS)?;
         let mut folders = Vec::new();
         for item in folders_table.iter()? {
+            slow.keys += 1;
             let (_, value) = item?;
-            let folder: Folder = bincode::deserialize(value.value())?;
+            let folder = deserialize_folder(value.value())?;
             folders.push(folder);
         }
         folders.sort_by(|a, b| a.name.cmp(&b.name));
@@ -192,6 +238,39 @@ impl FolderDb {
         Ok(())
     }
 
+    /// Overwrite a folder's paste count, byte total, and last-updated time.
+    ///
+    /// # Arguments
+    /// - `id`: Folder id to update.
+    /// - `paste_count`: Exact direct paste count.
+    /// - `total_bytes`: Exact stored content bytes of direct pastes.
+    /// - `last_updated`: Latest direct paste update time, if any.
+    ///
+    /// # Returns
+    /// `Ok(())` when the update commits.
+    ///
+    /// # Errors
+    /// Returns [`AppError::NotFound`] when the folder is missing, or storage
+    /// / serialization errors when the update cannot be committed.
+    pub fn set_stats(
+        &self,
+        id: &str,
+        paste_count: usize,
+        total_bytes: u64,
+        last_updated: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError> {
+        let updated = self.update_folder_record(id, move |folder| {
+            folder.paste_count = paste_count;
+            folder.total_bytes = total_bytes;
+            folder.last_updated = last_updated;
+            Ok(())
+        })?;
+        if updated.is_none() {
+            return Err(AppError::NotFound);
+        }
+        Ok(())
+    }
+
     /// Mark folders as in-progress for delete workflows.
     ///
     /// # Returns
@@ -253,7 +332,7 @@ impl FolderDb {
                 return Ok(None);
             };
 
-            let mut folder: Folder = bincode::deserialize(value.value())?;
+            let mut folder = deserialize_folder(value.value())?;
             drop(value);
 
             mutator(&mut folder)?;
@@ -0,0 +1,338 @@
+//! Folder auto-organization rules.
+//!
+//! Rules are evaluated in `position` order when a paste is created without an
+//! explicit folder; the first match whose folder still exists wins. Rules
+//! pointing at a deleted folder are skipped rather than removed, so they stay
+//! visible for the user to fix.
+
+use crate::{
+    db::tables::{FOLDERS, FOLDERS_DELETING, FOLDER_RULES},
+    error::AppError,
+    models::folder_rule::{
+        FolderRule, RuleField, UpdateFolderRuleRequest, MAX_FOLDER_RULES, MAX_RULE_PATTERN_CHARS,
+    },
+    models::paste::Paste,
+    PasteSource,
+};
+use chrono::Utc;
+use redb::{ReadableDatabase, ReadableTable};
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn validated_pattern(pattern: &str) -> Result<String, AppError> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Err(AppError::BadRequest(
+            "Rule pattern cannot be empty".to_string(),
+        ));
+    }
+    if pattern.chars().count() > MAX_RULE_PATTERN_CHARS {
+        return Err(AppError::BadRequest(format!(
+            "Rule pattern exceeds {} characters",
+            MAX_RULE_PATTERN_CHARS
+        )));
+    }
+    Ok(pattern.to_string())
+}
+
+fn ensure_folder_exists(
+    write_txn: &redb::WriteTransaction,
+    folder_id: &str,
+) -> Result<(), AppError> {
+    let exists = write_txn.open_table(FOLDERS)?.get(folder_id)?.is_some()
+        && write_txn
+            .open_table(FOLDERS_DELETING)?
+            .get(folder_id)?
+            .is_none();
+    if exists {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Folder '{}' does not exist",
+            folder_id
+        )))
+    }
+}
+
+fn sorted_rules(
+    table: &impl ReadableTable<&'static str, &'static [u8]>,
+) -> Result<Vec<FolderRule>, AppError> {
+    let mut rules = Vec::new();
+    for entry in table.iter()? {
+        let (_, value) = entry?;
+        rules.push(bincode::deserialize::<FolderRule>(value.value())?);
+    }
+    rules.sort_by(|a, b| {
+        a.position
+            .cmp(&b.position)
+            .then(a.created_at.cmp(&b.created_at))
+            .then(a.id.cmp(&b.id))
+    });
+    Ok(rules)
+}
+
+/// Accessor for the folder rules table.
+pub struct FolderRuleDb {
+    db: Arc<redb::Database>,
+}
+
+impl FolderRuleDb {
+    /// Initialize the folder rules table if it does not exist yet.
+    ///
+    /// # Returns
+    /// A new [`FolderRuleDb`] accessor bound to `db`.
+    ///
+    /// # Errors
+    /// Returns an error when redb transaction/table initialization fails.
+    pub fn new(db: Arc<redb::Database>) -> Result<Self, AppError> {
+        let write_txn = db.begin_write()?;
+        write_txn.open_table(FOLDER_RULES)?;
+        write_txn.commit()?;
+        Ok(Self { db })
+    }
+
+    /// List every rule in evaluation order.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn list(&self) -> Result<Vec<FolderRule>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        sorted_rules(&read_txn.open_table(FOLDER_RULES)?)
+    }
+
+    /// Add a rule after the existing ones.
+    ///
+    /// # Returns
+    /// The stored rule.
+    ///
+    /// # Errors
+    /// Returns `BadRequest` for a blank or oversized pattern, a missing
+    /// folder, or when [`MAX_FOLDER_RULES`] rules already exist, or an error
+    /// when storage fails.
+    pub fn create(
+        &self,
+        field: RuleField,
+        pattern: &str,
+        folder_id: &str,
+    ) -> Result<FolderRule, AppError> {
+        let pattern = validated_pattern(pattern)?;
+        let write_txn = self.db.begin_write()?;
+        let rule = {
+            ensure_folder_exists(&write_txn, folder_id)?;
+            let mut table = write_txn.open_table(FOLDER_RULES)?;
+            let existing = sorted_rules(&table)?;
+            if existing.len() >= MAX_FOLDER_RULES {
+                return Err(AppError::BadRequest(format!(
+                    "At most {} folder rules are allowed",
+                    MAX_FOLDER_RULES
+                )));
+            }
+            let rule = FolderRule {
+                id: Uuid::new_v4().to_string(),
+                field,
+                pattern,
+                folder_id: folder_id.to_string(),
+                position: existing
+                    .last()
+                    .map_or(0, |last| last.position.saturating_add(1)),
+                created_at: Utc::now(),
+            };
+            let encoded = bincode::serialize(&rule)?;
+            table.insert(rule.id.as_str(), encoded.as_slice())?;
+            rule
+        };
+        write_txn.commit()?;
+        Ok(rule)
+    }
+
+    /// Change a rule's field, pattern, or folder.
+    ///
+    /// # Returns
+    /// The updated rule, or `Ok(None)` when it does not exist.
+    ///
+    /// # Errors
+    /// Returns `BadRequest` for a blank or oversized pattern or a missing
+    /// folder, or an error when storage fails.
+    pub fn update(
+        &self,
+        id: &str,
+        update: UpdateFolderRuleRequest,
+    ) -> Result<Option<FolderRule>, AppError> {
+        let pattern = update
+            .pattern
+            .as_deref()
+            .map(validated_pattern)
+            .transpose()?;
+        let write_txn = self.db.begin_write()?;
+        let updated = {
+            if let Some(folder_id) = update.folder_id.as_deref() {
+                ensure_folder_exists(&write_txn, folder_id)?;
+            }
+            let mut table = write_txn.open_table(FOLDER_RULES)?;
+            let existing = table
+                .get(id)?
+                .map(|row| bincode::deserialize::<FolderRule>(row.value()))
+                .transpose()?;
+            let Some(mut rule) = existing else {
+                return Ok(None);
+            };
+            if let Some(field) = update.field {
+                rule.field = field;
+            }
+            if let Some(pattern) = pattern {
+                rule.pattern = pattern;
+            }
+            if let Some(folder_id) = update.folder_id {
+                rule.folder_id = folder_id;
+            }
+            let encoded = bincode::serialize(&rule)?;
+            table.insert(id, encoded.as_slice())?;
+            rule
+        };
+        write_txn.commit()?;
+        Ok(Some(updated))
+    }
+
+    /// Rewrite evaluation order to follow `ids`.
+    ///
+    /// # Returns
+    /// Every rule in the new order.
+    ///
+    /// # Errors
+    /// Returns `BadRequest` unless `ids` names every rule exactly once, or an
+    /// error when storage fails.
+    pub fn reorder(&self, ids: &[String]) -> Result<Vec<FolderRule>, AppError> {
+        let write_txn = self.db.begin_write()?;
+        let reordered = {
+            let mut table = write_txn.open_table(FOLDER_RULES)?;
+            let mut rules = sorted_rules(&table)?;
+            let mut expected: Vec<&str> = rules.iter().map(|rule| rule.id.as_str()).collect();
+            let mut given: Vec<&str> = ids.iter().map(String::as_str).collect();
+            expected.sort_unstable();
+            given.sort_unstable();
+            if expected != given {
+                return Err(AppError::BadRequest(
+                    "Reorder must list every rule id exactly once".to_string(),
+                ));
+            }
+            rules.sort_by_key(|rule| ids.iter().position(|id| *id == rule.id));
+            for (position, rule) in rules.iter_mut().enumerate() {
+                rule.position = position as u32;
+                let encoded = bincode::serialize(&*rule)?;
+                table.insert(rule.id.as_str(), encoded.as_slice())?;
+            }
+            rules
+        };
+        write_txn.commit()?;
+        Ok(reordered)
+    }
+
+    /// Delete one rule.
+    ///
+    /// # Returns
+    /// `Ok(true)` when a rule was removed, `Ok(false)` when none existed.
+    ///
+    /// # Errors
+    /// Returns an error when storage operations fail.
+    pub fn delete(&self, id: &str) -> Result<bool, AppError> {
+        let write_txn = self.db.begin_write()?;
+        let removed = write_txn.open_table(FOLDER_RULES)?.remove(id)?.is_some();
+        write_txn.commit()?;
+        Ok(removed)
+    }
+
+    /// First rule matching a new paste whose folder still exists.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn folder_for(
+        &self,
+        paste: &Paste,
+        source: PasteSource,
+    ) -> Result<Option<FolderRule>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let rules = sorted_rules(&read_txn.open_table(FOLDER_RULES)?)?;
+        if rules.is_empty() {
+            return Ok(None);
+        }
+        let folders = read_txn.open_table(FOLDERS)?;
+        let deleting = read_txn.open_table(FOLDERS_DELETING)?;
+        for rule in rules {
+            if !rule.matches(paste, source) {
+                continue;
+            }
+            let folder_id = rule.folder_id.as_str();
+            if folders.get(folder_id)?.is_some() && deleting.get(folder_id)?.is_none() {
+                return Ok(Some(rule));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::folder::Folder;
+    use crate::test_support::setup_temp_db;
+
+    #[test]
+    fn first_matching_rule_with_a_live_folder_wins() {
+        let (db, _temp) = setup_temp_db();
+        let logs = Folder::new("logs".to_string());
+        let clips = Folder::new("clips".to_string());
+        let gone = Folder::new("gone".to_string());
+        for folder in [&logs, &clips, &gone] {
+            db.folders.create(folder).expect("folder");
+        }
+
+        let stale = db
+            .folder_rules
+            .create(RuleField::Name, "*.log", &gone.id)
+            .expect("rule");
+        let by_name = db
+            .folder_rules
+            .create(RuleField::Name, " *.LOG ", &logs.id)
+            .expect("rule");
+        let by_source = db
+            .folder_rules
+            .create(RuleField::Source, "clipboard", &clips.id)
+            .expect("rule");
+        assert_eq!(by_name.pattern, "*.LOG");
+        assert!(matches!(
+            db.folder_rules.create(RuleField::Tag, "x", "missing"),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(db.folders.delete(&gone.id).expect("delete folder"));
+
+        let paste = Paste::new("line".to_string(), "server.log".to_string());
+        let matched = db
+            .folder_rules
+            .folder_for(&paste, PasteSource::ClipboardCapture)
+            .expect("match")
+            .expect("rule");
+        assert_eq!(matched.id, by_name.id);
+
+        db.folder_rules
+            .reorder(&[by_source.id.clone(), by_name.id.clone(), stale.id.clone()])
+            .expect("reorder");
+        let matched = db
+            .folder_rules
+            .folder_for(&paste, PasteSource::ClipboardCapture)
+            .expect("match")
+            .expect("rule");
+        assert_eq!(matched.folder_id, clips.id);
+        assert!(db
+            .folder_rules
+            .folder_for(
+                &Paste::new("x".to_string(), "notes".to_string()),
+                PasteSource::Api
+            )
+            .expect("match")
+            .is_none());
+        assert!(matches!(
+            db.folder_rules.reorder(std::slice::from_ref(&by_name.id)),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+}
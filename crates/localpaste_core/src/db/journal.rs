@@ -0,0 +1,318 @@
+//! Write-ahead journal for paste updates committed under a relaxed flush
+//! policy.
+//!
+//! Relaxed commits skip fsync, so an OS crash can roll the database back to
+//! its last flush. Each paste update committed that way is also appended to
+//! [`JOURNAL_FILE_NAME`] in the DB directory and fsynced before the update
+//! returns. Entries carry a revision number that the same transaction stores
+//! in [`JOURNAL_STATE`]; when the database opens, entries newer than the
+//! stored revision are replayed. Each flush makes every journaled update
+//! durable and empties the file.
+//!
+//! The journal is a file next to the database rather than a table inside it,
+//! so it does not depend on how the storage engine orders its own writes.
+
+use crate::db::durability::DurabilityState;
+use crate::db::paste::{
+    deserialize_paste, reverse_timestamp_key, FilterIndexKeys, FilterIndexTables,
+};
+use crate::db::tables::{FOLDERS, JOURNAL_STATE, PASTES, PASTES_BY_UPDATED, PASTES_META};
+use crate::db::transactions::apply_folder_stats_transition;
+use crate::error::AppError;
+use crate::models::paste::{Paste, PasteMeta};
+use redb::{Database as RedbDatabase, ReadableTable, WriteTransaction};
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::MutexGuard;
+
+/// Journal file name within the configured DB directory.
+pub const JOURNAL_FILE_NAME: &str = "mutations.journal";
+
+const APPLIED_REVISION_KEY: &str = "applied_revision";
+/// Revision (`u64`) and payload length (`u32`), both little-endian.
+const FRAME_HEADER_LEN: usize = 12;
+
+/// Append handle for one database's journal file.
+#[derive(Debug)]
+pub(crate) struct Journal {
+    path: PathBuf,
+    file: Option<File>,
+}
+
+impl Journal {
+    fn new(path: PathBuf) -> Self {
+        Self { path, file: None }
+    }
+
+    fn io_error(&self, action: &str, err: std::io::Error) -> AppError {
+        AppError::StorageMessage(format!(
+            "Failed to {} journal '{}': {}",
+            action,
+            self.path.display(),
+            err
+        ))
+    }
+
+    /// Append one update and fsync it.
+    pub(crate) fn append(&mut self, rev: u64, paste: &Paste) -> Result<(), AppError> {
+        let payload = bincode::serialize(paste)?;
+        let len = u32::try_from(payload.len())
+            .map_err(|_| AppError::StorageMessage("Journal entry exceeds 4 GiB".to_string()))?;
+        let mut frame = Vec::with_capacity(FRAME_HEADER_LEN + payload.len());
+        frame.extend_from_slice(&rev.to_le_bytes());
+        frame.extend_from_slice(&len.to_le_bytes());
+        frame.extend_from_slice(&payload);
+
+        if self.file.is_none() {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .map_err(|err| self.io_error("open", err))?;
+            self.file = Some(file);
+        }
+        let result = match self.file.as_mut() {
+            Some(file) => file.write_all(&frame).and_then(|()| file.sync_data()),
+            None => Ok(()),
+        };
+        result.map_err(|err| {
+            // Reopen next time rather than appending after a partial frame.
+            self.file = None;
+            self.io_error("append to", err)
+        })
+    }
+
+    /// Drop every entry; called once they are all durable in the database.
+    pub(crate) fn clear(&mut self) -> Result<(), AppError> {
+        self.file = None;
+        match OpenOptions::new().write(true).open(&self.path) {
+            Ok(file) => file
+                .set_len(0)
+                .and_then(|()| file.sync_all())
+                .map_err(|err| self.io_error("truncate", err)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(self.io_error("open", err)),
+        }
+    }
+}
+
+/// The journal, locked for the duration of one paste update.
+pub(crate) struct JournalSlot<'a> {
+    journal: MutexGuard<'a, Option<Journal>>,
+    active: bool,
+}
+
+impl<'a> JournalSlot<'a> {
+    /// Lock the journal before the update's write transaction begins.
+    pub(crate) fn lock(state: &'a DurabilityState) -> Self {
+        let journal = state.lock_journal();
+        let active = journal.is_some() && state.policy().is_relaxed();
+        Self { journal, active }
+    }
+
+    /// Whether this update is journaled and may therefore commit relaxed.
+    pub(crate) fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Advance the revision inside `write_txn` when this update is journaled.
+    ///
+    /// # Returns
+    /// The revision to pass to [`JournalSlot::record`], or `None` when the
+    /// commit will be durable on its own.
+    ///
+    /// # Errors
+    /// Returns an error when the state table cannot be read or written.
+    pub(crate) fn begin(&self, write_txn: &WriteTransaction) -> Result<Option<u64>, AppError> {
+        if !self.active {
+            return Ok(None);
+        }
+        next_revision(write_txn).map(Some)
+    }
+
+    /// Append a committed update.
+    ///
+    /// The update is already visible, so a failed append is not reported to
+    /// the caller; the database is made durable instead, which leaves nothing
+    /// for the journal to recover.
+    pub(crate) fn record(&mut self, db: &RedbDatabase, rev: u64, paste: &Paste) {
+        let Some(journal) = self.journal.as_mut() else {
+            return;
+        };
+        let Err(err) = journal.append(rev, paste) else {
+            return;
+        };
+        tracing::warn!("{}; flushing the database instead", err);
+        let flushed = db
+            .begin_write()
+            .map_err(AppError::from)
+            .and_then(|write_txn| write_txn.commit().map_err(AppError::from))
+            .and_then(|()| journal.clear());
+        if let Err(err) = flushed {
+            tracing::error!(
+                "Paste '{}' may be lost on a crash: journal and flush both failed: {}",
+                paste.id,
+                err
+            );
+        }
+    }
+}
+
+fn next_revision(write_txn: &WriteTransaction) -> Result<u64, AppError> {
+    let mut state = write_txn.open_table(JOURNAL_STATE)?;
+    let rev = state
+        .get(APPLIED_REVISION_KEY)?
+        .map_or(0, |value| value.value())
+        + 1;
+    state.insert(APPLIED_REVISION_KEY, rev)?;
+    Ok(rev)
+}
+
+/// Read every complete entry, stopping at a torn or corrupt tail.
+fn read_entries(path: &Path) -> Result<Vec<(u64, Paste)>, AppError> {
+    let mut bytes = Vec::new();
+    match File::open(path) {
+        Ok(mut file) => file.read_to_end(&mut bytes).map_err(|err| {
+            AppError::StorageMessage(format!(
+                "Failed to read journal '{}': {}",
+                path.display(),
+                err
+            ))
+        })?,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(AppError::StorageMessage(format!(
+                "Failed to open journal '{}': {}",
+                path.display(),
+                err
+            )));
+        }
+    };
+
+    let mut entries = Vec::new();
+    let mut rest = bytes.as_slice();
+    while !rest.is_empty() {
+        let parsed = rest
+            .split_first_chunk::<FRAME_HEADER_LEN>()
+            .and_then(|(header, body)| {
+                let (rev, len) = header.split_at(8);
+                let rev = u64::from_le_bytes(rev.try_into().ok()?);
+                let len = u32::from_le_bytes(len.try_into().ok()?) as usize;
+                let payload = body.get(..len)?;
+                let paste = deserialize_paste(payload).ok()?;
+                Some((rev, paste, &body[len..]))
+            });
+        let Some((rev, paste, remaining)) = parsed else {
+            tracing::warn!(
+                "Ignoring {} trailing bytes of incomplete journal entry in '{}'",
+                rest.len(),
+                path.display()
+            );
+            break;
+        };
+        entries.push((rev, paste));
+        rest = remaining;
+    }
+    Ok(entries)
+}
+
+/// Write a journaled paste row and its derived indexes.
+///
+/// The stored folder wins over the journaled one: moves are not journaled,
+/// so the row on disk already reflects the latest durable folder.
+///
+/// # Returns
+/// `false` when the paste no longer exists and the entry was skipped.
+fn restore_paste(write_txn: &WriteTransaction, mut paste: Paste) -> Result<bool, AppError> {
+    let mut pastes = write_txn.open_table(PASTES)?;
+    let mut metas = write_txn.open_table(PASTES_META)?;
+    let mut updated = write_txn.open_table(PASTES_BY_UPDATED)?;
+    let Some(old_guard) = pastes.get(paste.id.as_str())? else {
+        return Ok(false);
+    };
+    let old = deserialize_paste(old_guard.value())?;
+    drop(old_guard);
+    paste.folder_id = old.folder_id.clone();
+
+    let id = paste.id.as_str();
+    let encoded_paste = bincode::serialize(&paste)?;
+    let encoded_meta = bincode::serialize(&PasteMeta::from(&paste))?;
+    pastes.insert(id, encoded_paste.as_slice())?;
+    metas.insert(id, encoded_meta.as_slice())?;
+    let old_recency_key = reverse_timestamp_key(old.updated_at);
+    let new_recency_key = reverse_timestamp_key(paste.updated_at);
+    if old_recency_key != new_recency_key {
+        let _ = updated.remove((old_recency_key, id))?;
+    }
+    updated.insert((new_recency_key, id), ())?;
+    FilterIndexTables::open(write_txn)?.replace(
+        id,
+        &FilterIndexKeys::from_paste(&old),
+        &FilterIndexKeys::from_paste(&paste),
+    )?;
+    if let Some(folder_id) = paste.folder_id.as_deref() {
+        let mut folders = write_txn.open_table(FOLDERS)?;
+        apply_folder_stats_transition(
+            &mut folders,
+            Some((folder_id, old.content.len())),
+            Some((folder_id, paste.content.len())),
+            paste.updated_at,
+        )?;
+    }
+    Ok(true)
+}
+
+/// Replay entries newer than the stored revision, then empty the journal and
+/// attach it to `state` for later appends.
+///
+/// # Returns
+/// How many entries were replayed.
+///
+/// # Errors
+/// Returns an error when the journal cannot be read or truncated, or the
+/// replay transaction fails.
+pub(crate) fn open(
+    db: &RedbDatabase,
+    state: &DurabilityState,
+    db_dir: &Path,
+) -> Result<usize, AppError> {
+    let path = db_dir.join(JOURNAL_FILE_NAME);
+    let entries = read_entries(&path)?;
+    let mut replayed = 0;
+    if !entries.is_empty() {
+        // A default write transaction is durable, so the replay itself
+        // cannot be lost before the journal is emptied below.
+        let write_txn = db.begin_write()?;
+        {
+            let mut journal_state = write_txn.open_table(JOURNAL_STATE)?;
+            let applied = journal_state
+                .get(APPLIED_REVISION_KEY)?
+                .map_or(0, |value| value.value());
+            let mut latest = applied;
+            for (rev, paste) in entries {
+                if rev <= applied {
+                    continue;
+                }
+                latest = latest.max(rev);
+                if restore_paste(&write_txn, paste)? {
+                    replayed += 1;
+                }
+            }
+            journal_state.insert(APPLIED_REVISION_KEY, latest)?;
+        }
+        write_txn.commit()?;
+        if replayed > 0 {
+            tracing::warn!(
+                "Recovered {} paste update(s) from journal '{}' that were not flushed before shutdown",
+                replayed,
+                path.display()
+            );
+        }
+    }
+
+    let mut journal = Journal::new(path);
+    journal.clear()?;
+    state.attach_journal(journal);
+    Ok(replayed)
+}
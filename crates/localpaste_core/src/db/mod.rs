@@ -1,19 +1,45 @@
 //! Database layer and transactional helpers for LocalPaste.
 
+/// Line-anchored paste annotations.
+pub mod annotations;
+/// Text scrubbing for shareable reproduction databases.
+pub mod anonymize;
+/// Binary files attached to pastes.
+pub mod attachments;
 /// Backup utilities.
 pub mod backup;
+/// Remote backup targets and remote retention.
+pub mod backup_remote;
+/// Paste comment threads.
+pub mod comments;
+/// Crash-recovery draft storage.
+pub mod draft;
+/// Flush policy for committed writes.
+pub mod durability;
 /// Folder storage helpers.
 pub mod folder;
+/// Folder auto-organization rule storage.
+pub mod folder_rules;
+/// Write-ahead journal replayed after a crash.
+pub mod journal;
 /// Lock handling helpers.
 pub mod lock;
 /// Paste storage helpers.
 pub mod paste;
+/// Per-client recently opened paste history.
+pub mod recent;
+mod slow_query;
 /// Typed redb table definitions.
 pub mod tables;
 mod time_util;
 mod transactions;
+/// Source URLs and refresh schedules for mirrored pastes.
+pub mod url_sources;
+/// Multi-user accounts and paste ownership.
+pub mod users;
 mod versioning;
 
+use crate::db::durability::{DurabilityState, FlushPolicy};
 use crate::db::tables::REDB_FILE_NAME;
 use crate::error::AppError;
 use crate::folder_ops::reconcile_folder_invariants;
@@ -221,8 +247,17 @@ pub struct Database {
     pub db: Arc<RedbDatabase>,
     pub pastes: paste::PasteDb,
     pub folders: folder::FolderDb,
+    pub folder_rules: folder_rules::FolderRuleDb,
+    pub drafts: draft::DraftDb,
+    pub users: users::UserDb,
+    pub comments: comments::CommentDb,
+    pub annotations: annotations::AnnotationDb,
+    pub attachments: attachments::AttachmentDb,
+    pub recent: recent::RecentDb,
+    pub url_sources: url_sources::UrlSourceDb,
     _owner_lock_guard: Option<Arc<lock::OwnerLockGuard>>,
     pub(crate) folder_txn_lock: Arc<Mutex<()>>,
+    durability: Arc<DurabilityState>,
 }
 
 #[cfg(test)]
@@ -305,6 +340,15 @@ impl Database {
         Ok(Self {
             pastes: paste::PasteDb::new(db.clone())?,
             folders: folder::FolderDb::new(db.clone())?,
+            folder_rules: folder_rules::FolderRuleDb::new(db.clone())?,
+            drafts: draft::DraftDb::new(db.clone())?,
+            users: users::UserDb::new(db.clone())?,
+            comments: comments::CommentDb::new(db.clone())?,
+            annotations: annotations::AnnotationDb::new(db.clone())?,
+            attachments: attachments::AttachmentDb::new(db.clone())?,
+            recent: recent::RecentDb::new(db.clone())?,
+            url_sources: url_sources::UrlSourceDb::new(db.clone())?,
+            durability: DurabilityState::for_db(&db)?,
             db,
             _owner_lock_guard: owner_lock_guard,
             folder_txn_lock,
@@ -433,18 +477,51 @@ impl Database {
         };
 
         let folder_txn_lock = Self::shared_folder_txn_lock_for_db(&db)?;
-        Self::from_shared_with_coordination(db, owner_lock_guard, folder_txn_lock)
+        let database = Self::from_shared_with_coordination(db, owner_lock_guard, folder_txn_lock)?;
+        journal::open(&database.db, &database.durability, db_dir)?;
+        Ok(database)
+    }
+
+    /// Current flush policy, shared by every handle on this database.
+    pub fn flush_policy(&self) -> FlushPolicy {
+        self.durability.policy()
     }
 
-    /// Compatibility no-op. redb durability is guaranteed on commit.
+    /// Change when writes from every handle on this database become durable.
+    ///
+    /// Relaxed policies need [`Database::flush_if_due`] called every
+    /// [`FlushPolicy::poll_interval`]; switching back to
+    /// [`FlushPolicy::EveryCommit`] does not persist earlier writes until
+    /// [`Database::flush`] runs.
+    pub fn set_flush_policy(&self, policy: FlushPolicy) {
+        self.durability.set_policy(policy);
+    }
+
+    /// Persist writes committed under a relaxed flush policy.
     ///
     /// # Returns
-    /// Always returns `Ok(())`.
+    /// `Ok(())` once every earlier commit is durable; a no-op when none are
+    /// pending.
     ///
     /// # Errors
-    /// This compatibility helper currently returns no errors.
+    /// Returns an error when the durable commit fails.
     pub fn flush(&self) -> Result<(), AppError> {
-        Ok(())
+        self.durability.flush(&self.db)
+    }
+
+    /// Flush when the policy's interval or idle period has elapsed.
+    ///
+    /// # Returns
+    /// `true` when a flush ran.
+    ///
+    /// # Errors
+    /// Returns an error when the durable commit fails.
+    pub fn flush_if_due(&self) -> Result<bool, AppError> {
+        if !self.durability.flush_due() {
+            return Ok(false);
+        }
+        self.flush()?;
+        Ok(true)
     }
 }
 
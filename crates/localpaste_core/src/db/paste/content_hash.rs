@@ -0,0 +1,133 @@
+//! Content-hash lookup for dedupe-on-create and `?content_hash=` filters.
+//!
+//! Hashes cover stored content, i.e. after save-time normalization, and are
+//! computed during a canonical-row scan rather than kept in an index.
+
+use super::{deserialize_paste, PasteDb};
+use crate::models::paste::Paste;
+use crate::{db::tables::PASTES, db::versioning::content_hash_hex, error::AppError};
+use redb::{ReadableDatabase, ReadableTable};
+
+/// Length of a hex-encoded BLAKE3 digest.
+const CONTENT_HASH_HEX_LEN: usize = 64;
+
+/// Lowercase BLAKE3 hex digest of stored paste content.
+pub fn paste_content_hash(content: &str) -> String {
+    content_hash_hex(content)
+}
+
+/// Validates a client-supplied content hash.
+///
+/// # Returns
+/// The digest in lowercase.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] unless the value is a 64-character hex digest.
+pub fn parse_content_hash(raw: &str) -> Result<String, AppError> {
+    let hash = raw.trim().to_ascii_lowercase();
+    if hash.len() != CONTENT_HASH_HEX_LEN || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(AppError::BadRequest(
+            "content_hash must be a 64-character BLAKE3 hex digest".to_string(),
+        ));
+    }
+    Ok(hash)
+}
+
+impl PasteDb {
+    /// Find pastes whose stored content hashes to `hash`.
+    ///
+    /// # Arguments
+    /// - `hash`: Lowercase BLAKE3 hex digest (see [`parse_content_hash`]).
+    /// - `limit`: Maximum rows to return.
+    /// - `folder_id`: Optional folder filter.
+    ///
+    /// # Returns
+    /// Matching pastes, most recently updated first.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or deserialization fails.
+    pub fn find_by_content_hash(
+        &self,
+        hash: &str,
+        limit: usize,
+        folder_id: Option<&str>,
+    ) -> Result<Vec<Paste>, AppError> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let read_txn = self.db.begin_read()?;
+        let pastes = read_txn.open_table(PASTES)?;
+        let mut matches = Vec::new();
+        for item in pastes.iter()? {
+            let (_, value) = item?;
+            let paste = deserialize_paste(value.value())?;
+            if folder_id.is_some_and(|fid| paste.folder_id.as_deref() != Some(fid)) {
+                continue;
+            }
+            if content_hash_hex(paste.content.as_str()) == hash {
+                matches.push(paste);
+            }
+        }
+        matches.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        matches.truncate(limit);
+        Ok(matches)
+    }
+
+    /// Return the most recently updated paste whose stored content equals `content`.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or deserialization fails.
+    pub fn find_duplicate(&self, content: &str) -> Result<Option<Paste>, AppError> {
+        Ok(self
+            .find_by_content_hash(content_hash_hex(content).as_str(), 1, None)?
+            .into_iter()
+            .next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::setup_temp_db;
+
+    #[test]
+    fn parse_content_hash_requires_full_hex_digest() {
+        let hash = paste_content_hash("hello");
+        assert_eq!(
+            parse_content_hash(hash.to_uppercase().as_str()).unwrap(),
+            hash
+        );
+        assert!(parse_content_hash("abc").is_err());
+        assert!(parse_content_hash(&"z".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn find_by_content_hash_matches_stored_content_newest_first() {
+        let (db, _temp) = setup_temp_db();
+        let older = Paste::new("same".to_string(), "older".to_string());
+        let mut newer = Paste::new("same".to_string(), "newer".to_string());
+        newer.updated_at = older.updated_at + chrono::Duration::seconds(5);
+        let other = Paste::new("different".to_string(), "other".to_string());
+        for paste in [&older, &newer, &other] {
+            db.pastes.create(paste).expect("create");
+        }
+
+        let hash = paste_content_hash("same");
+        let found = db
+            .pastes
+            .find_by_content_hash(&hash, 10, None)
+            .expect("find");
+        let names: Vec<&str> = found.iter().map(|paste| paste.name.as_str()).collect();
+        assert_eq!(names, vec!["newer", "older"]);
+        assert_eq!(
+            db.pastes.find_duplicate("same").expect("dup").map(|p| p.id),
+            Some(newer.id)
+        );
+        assert!(db.pastes.find_duplicate("missing").expect("dup").is_none());
+        assert!(db
+            .pastes
+            .find_by_content_hash(&hash, 10, Some("folder"))
+            .expect("find")
+            .is_empty());
+    }
+}
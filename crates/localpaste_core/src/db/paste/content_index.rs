@@ -0,0 +1,119 @@
+//! Trigram inverted index over paste content.
+//!
+//! Every distinct trigram of a paste's lowercased content is stored as a
+//! `(trigram, paste_id)` key in [`PASTES_BY_TRIGRAM`], so content search only
+//! loads pastes that contain all of the query's trigrams instead of every
+//! body. Candidates still go through the exact substring check: the index can
+//! only rule pastes out. Keys are maintained with the filter indexes in
+//! [`super::FilterIndexTables`].
+
+use crate::{db::tables::PASTES_BY_TRIGRAM, error::AppError};
+use redb::ReadTransaction;
+use std::collections::HashSet;
+
+/// Packs three characters into one key; each `char` fits in 21 bits.
+fn trigram_key(chars: [char; 3]) -> u64 {
+    (u64::from(chars[0]) << 42) | (u64::from(chars[1]) << 21) | u64::from(chars[2])
+}
+
+/// Distinct trigram keys of `text`, sorted.
+fn trigrams(text: &str) -> Vec<u64> {
+    let mut keys = Vec::new();
+    let mut window = ['\0'; 3];
+    for (idx, ch) in text.chars().enumerate() {
+        window = [window[1], window[2], ch];
+        if idx >= 2 {
+            keys.push(trigram_key(window));
+        }
+    }
+    keys.sort_unstable();
+    keys.dedup();
+    keys
+}
+
+/// Trigram keys indexed for `content`.
+///
+/// Content is lowercased the same way as case-insensitive search, so every
+/// match implies the query's trigrams are present.
+pub(super) fn content_trigrams(content: &str) -> Vec<u64> {
+    trigrams(content.to_lowercase().as_str())
+}
+
+/// Ids of pastes whose content may contain `query_lower`.
+///
+/// The first query trigram is scanned and later ones are checked by point
+/// lookups against the shrinking candidate set.
+///
+/// # Returns
+/// `None` when the query is shorter than three characters and the index
+/// cannot narrow the search.
+///
+/// # Errors
+/// Returns an error when storage access fails.
+pub(super) fn content_candidates(
+    read_txn: &ReadTransaction,
+    query_lower: &str,
+) -> Result<Option<HashSet<String>>, AppError> {
+    let keys = trigrams(query_lower);
+    let Some((first, rest)) = keys.split_first() else {
+        return Ok(None);
+    };
+    let index = read_txn.open_table(PASTES_BY_TRIGRAM)?;
+    let mut candidates = HashSet::new();
+    for item in index.range((*first, "")..)? {
+        let (key, _) = item?;
+        let (trigram, paste_id) = key.value();
+        if trigram != *first {
+            break;
+        }
+        candidates.insert(paste_id.to_string());
+    }
+    for key in rest {
+        if candidates.is_empty() {
+            break;
+        }
+        let mut kept = HashSet::with_capacity(candidates.len());
+        for id in candidates {
+            if index.get((*key, id.as_str()))?.is_some() {
+                kept.insert(id);
+            }
+        }
+        candidates = kept;
+    }
+    Ok(Some(candidates))
+}
+
+/// Keys in `left` that are not in `right`; both must be sorted.
+pub(super) fn sorted_difference(left: &[u64], right: &[u64]) -> Vec<u64> {
+    let mut out = Vec::new();
+    let mut other = right.iter().peekable();
+    for key in left {
+        while other.next_if(|candidate| *candidate < key).is_some() {}
+        if other.peek() != Some(&key) {
+            out.push(*key);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trigrams_are_distinct_lowercased_windows() {
+        assert!(content_trigrams("ab").is_empty());
+        let keys = content_trigrams("ABCabc");
+        // abc, bca, cab; the second "abc" is a duplicate.
+        assert_eq!(keys.len(), 3);
+        assert!(keys.contains(&trigram_key(['a', 'b', 'c'])));
+        assert_eq!(content_trigrams("ÄÖÜ"), vec![trigram_key(['ä', 'ö', 'ü'])]);
+    }
+
+    #[test]
+    fn sorted_difference_keeps_left_only_keys() {
+        assert_eq!(sorted_difference(&[1, 3, 5, 7], &[2, 3, 7, 9]), vec![1, 5]);
+        assert_eq!(sorted_difference(&[], &[1]), Vec::<u64>::new());
+        assert_eq!(sorted_difference(&[4], &[]), vec![4]);
+    }
+}
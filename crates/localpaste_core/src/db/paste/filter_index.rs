@@ -0,0 +1,579 @@
+//! Secondary list indexes for folder, tag, and language filters.
+//!
+//! Each table is keyed `(value, reverse_millis, paste_id)`, so one range scan
+//! yields the pastes for a single value newest-first without loading rows that
+//! do not match. The creation-order index `PASTES_BY_CREATED` is maintained
+//! with them and serves `created` list sorts. The tables are derived from
+//! `PASTES`: mutations write them in the same transaction as the canonical
+//! row, and [`PasteDb::rebuild_meta_index`] rewrites them alongside the
+//! metadata projection. The content trigram index
+//! of [`super::content_index`] rides along with the same keys.
+
+use super::content_index::{content_trigrams, sorted_difference};
+use super::helpers::{deserialize_meta, language_matches_filter};
+use super::{reverse_timestamp_key, PasteDb};
+use crate::{
+    db::{
+        slow_query::SlowQueryTimer,
+        tables::{
+            PASTES_BY_CREATED, PASTES_BY_FOLDER, PASTES_BY_LANGUAGE, PASTES_BY_TAG,
+            PASTES_BY_TRIGRAM, PASTES_BY_UPDATED, PASTES_META,
+        },
+    },
+    error::AppError,
+    models::{
+        folder::SortOrder,
+        paste::{normalize_language_filter, normalize_tag_filter, Paste, PasteMeta, PasteSortKey},
+    },
+};
+use redb::{
+    ReadTransaction, ReadableDatabase, ReadableTable, ReadableTableMetadata, TableDefinition,
+};
+use std::cmp::Ordering;
+
+type FilterIndexDefinition = TableDefinition<'static, (&'static str, u64, &'static str), ()>;
+type FilterIndexTable<'txn> = redb::Table<'txn, (&'static str, u64, &'static str), ()>;
+
+/// Index keys derived from one paste row.
+///
+/// Capture these before mutating a row so the stale entries can be removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FilterIndexKeys {
+    recency_key: u64,
+    created_key: u64,
+    folder_id: Option<String>,
+    tags: Vec<String>,
+    language: Option<String>,
+    /// Sorted content trigram keys.
+    trigrams: Vec<u64>,
+}
+
+impl FilterIndexKeys {
+    /// Derive index keys from a canonical paste row.
+    ///
+    /// Tags are normalized and deduplicated; the language is canonicalized so
+    /// aliases share one index range. Content trigrams are computed here too,
+    /// which costs one pass over the content.
+    pub(crate) fn from_paste(paste: &Paste) -> Self {
+        let mut tags: Vec<String> = paste
+            .tags
+            .iter()
+            .filter_map(|tag| normalize_tag_filter(Some(tag)))
+            .collect();
+        tags.sort();
+        tags.dedup();
+        Self {
+            recency_key: reverse_timestamp_key(paste.updated_at),
+            created_key: reverse_timestamp_key(paste.created_at),
+            folder_id: paste.folder_id.clone(),
+            tags,
+            language: normalize_language_filter(paste.language.as_deref()),
+            trigrams: content_trigrams(paste.content.as_str()),
+        }
+    }
+}
+
+/// Open write handles for the creation, folder, tag, language, and content index tables.
+pub(crate) struct FilterIndexTables<'txn> {
+    created: redb::Table<'txn, (u64, &'static str), ()>,
+    folder: FilterIndexTable<'txn>,
+    tag: FilterIndexTable<'txn>,
+    language: FilterIndexTable<'txn>,
+    content: redb::Table<'txn, (u64, &'static str), ()>,
+}
+
+impl<'txn> FilterIndexTables<'txn> {
+    /// Open all filter index tables in `write_txn`.
+    ///
+    /// # Errors
+    /// Returns an error when a table cannot be opened.
+    pub(crate) fn open(write_txn: &'txn redb::WriteTransaction) -> Result<Self, AppError> {
+        Ok(Self {
+            created: write_txn.open_table(PASTES_BY_CREATED)?,
+            folder: write_txn.open_table(PASTES_BY_FOLDER)?,
+            tag: write_txn.open_table(PASTES_BY_TAG)?,
+            language: write_txn.open_table(PASTES_BY_LANGUAGE)?,
+            content: write_txn.open_table(PASTES_BY_TRIGRAM)?,
+        })
+    }
+
+    /// Add index entries for `id`.
+    ///
+    /// # Errors
+    /// Returns an error when an insert fails.
+    pub(crate) fn insert(&mut self, id: &str, keys: &FilterIndexKeys) -> Result<(), AppError> {
+        self.insert_filters(id, keys)?;
+        for trigram in &keys.trigrams {
+            self.content.insert((*trigram, id), ())?;
+        }
+        Ok(())
+    }
+
+    fn insert_filters(&mut self, id: &str, keys: &FilterIndexKeys) -> Result<(), AppError> {
+        let recency = keys.recency_key;
+        self.created.insert((keys.created_key, id), ())?;
+        if let Some(folder_id) = keys.folder_id.as_deref() {
+            self.folder.insert((folder_id, recency, id), ())?;
+        }
+        for tag in &keys.tags {
+            self.tag.insert((tag.as_str(), recency, id), ())?;
+        }
+        if let Some(language) = keys.language.as_deref() {
+            self.language.insert((language, recency, id), ())?;
+        }
+        Ok(())
+    }
+
+    /// Remove index entries previously written for `id` with `keys`.
+    ///
+    /// # Errors
+    /// Returns an error when a removal fails.
+    pub(crate) fn remove(&mut self, id: &str, keys: &FilterIndexKeys) -> Result<(), AppError> {
+        self.remove_filters(id, keys)?;
+        for trigram in &keys.trigrams {
+            let _ = self.content.remove((*trigram, id))?;
+        }
+        Ok(())
+    }
+
+    fn remove_filters(&mut self, id: &str, keys: &FilterIndexKeys) -> Result<(), AppError> {
+        let recency = keys.recency_key;
+        let _ = self.created.remove((keys.created_key, id))?;
+        if let Some(folder_id) = keys.folder_id.as_deref() {
+            let _ = self.folder.remove((folder_id, recency, id))?;
+        }
+        for tag in &keys.tags {
+            let _ = self.tag.remove((tag.as_str(), recency, id))?;
+        }
+        if let Some(language) = keys.language.as_deref() {
+            let _ = self.language.remove((language, recency, id))?;
+        }
+        Ok(())
+    }
+
+    /// Swap `old` entries for `new` ones, skipping the writes when nothing changed.
+    ///
+    /// Only the trigrams that differ are touched, so metadata edits leave the
+    /// content index alone.
+    ///
+    /// # Errors
+    /// Returns an error when a removal or insert fails.
+    pub(crate) fn replace(
+        &mut self,
+        id: &str,
+        old: &FilterIndexKeys,
+        new: &FilterIndexKeys,
+    ) -> Result<(), AppError> {
+        if old == new {
+            return Ok(());
+        }
+        self.remove_filters(id, old)?;
+        self.insert_filters(id, new)?;
+        for trigram in sorted_difference(&old.trigrams, &new.trigrams) {
+            let _ = self.content.remove((trigram, id))?;
+        }
+        for trigram in sorted_difference(&new.trigrams, &old.trigrams) {
+            self.content.insert((trigram, id), ())?;
+        }
+        Ok(())
+    }
+
+    /// Drop every entry from all filter and content index tables.
+    ///
+    /// # Errors
+    /// Returns an error when a table cannot be cleared.
+    pub(crate) fn clear(&mut self) -> Result<(), AppError> {
+        self.created.retain(|_, _| false)?;
+        self.folder.retain(|_, _| false)?;
+        self.tag.retain(|_, _| false)?;
+        self.language.retain(|_, _| false)?;
+        self.content.retain(|_, _| false)?;
+        Ok(())
+    }
+}
+
+/// Visit paste ids for one indexed value, newest first.
+///
+/// # Arguments
+/// - `read_txn`: Read snapshot to scan.
+/// - `table`: Filter index table to scan.
+/// - `value`: Normalized folder id, tag, or language.
+/// - `visit`: Called per id; return `false` to stop.
+///
+/// # Errors
+/// Returns an error when storage access or `visit` fails.
+pub(super) fn scan_filter_index<F>(
+    read_txn: &ReadTransaction,
+    table: FilterIndexDefinition,
+    value: &str,
+    visit: F,
+) -> Result<(), AppError>
+where
+    F: FnMut(&str) -> Result<bool, AppError>,
+{
+    scan_filter_index_ordered(read_txn, table, value, SortOrder::Desc, visit)
+}
+
+/// [`scan_filter_index`] in either recency direction.
+///
+/// `value` followed by a NUL byte sorts after every key for `value` and before
+/// the next value, so it bounds the range from above.
+fn scan_filter_index_ordered<F>(
+    read_txn: &ReadTransaction,
+    table: FilterIndexDefinition,
+    value: &str,
+    order: SortOrder,
+    mut visit: F,
+) -> Result<(), AppError>
+where
+    F: FnMut(&str) -> Result<bool, AppError>,
+{
+    let index = read_txn.open_table(table)?;
+    let upper = format!("{}\0", value);
+    let range = index.range((value, 0u64, "")..(upper.as_str(), 0u64, ""))?;
+    let items: Box<dyn Iterator<Item = _>> = match order {
+        SortOrder::Desc => Box::new(range),
+        SortOrder::Asc => Box::new(range.rev()),
+    };
+    for item in items {
+        let (key, _) = item?;
+        let (_, _, paste_id) = key.value();
+        if !visit(paste_id)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Visit every id of a `(reverse_millis, paste_id)` index in `order`.
+fn scan_time_index<F>(
+    read_txn: &ReadTransaction,
+    table: TableDefinition<(u64, &str), ()>,
+    order: SortOrder,
+    mut visit: F,
+) -> Result<(), AppError>
+where
+    F: FnMut(&str) -> Result<bool, AppError>,
+{
+    let index = read_txn.open_table(table)?;
+    let items: Box<dyn Iterator<Item = _>> = match order {
+        SortOrder::Desc => Box::new(index.iter()?),
+        SortOrder::Asc => Box::new(index.iter()?.rev()),
+    };
+    for item in items {
+        let (key, _) = item?;
+        if !visit(key.value().1)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Case-insensitive name order, falling back to the exact name.
+fn compare_names(a: &str, b: &str) -> Ordering {
+    a.to_lowercase()
+        .cmp(&b.to_lowercase())
+        .then_with(|| a.cmp(b))
+}
+
+fn directed(ordering: Ordering, order: SortOrder) -> Ordering {
+    match order {
+        SortOrder::Asc => ordering,
+        SortOrder::Desc => ordering.reverse(),
+    }
+}
+
+/// Orders metadata rows for the field sorts that have no index.
+fn compare_meta(sort: PasteSortKey, a: &PasteMeta, b: &PasteMeta) -> Ordering {
+    let by_field = match sort {
+        PasteSortKey::Name => compare_names(&a.name, &b.name),
+        PasteSortKey::Size => a.content_len.cmp(&b.content_len),
+        // Served in index order; never sorted here.
+        PasteSortKey::Updated | PasteSortKey::Created => Ordering::Equal,
+    };
+    by_field.then_with(|| a.id.cmp(&b.id))
+}
+
+/// Sort canonical rows the way [`PasteDb::list_meta_sorted`] orders metadata.
+///
+/// For row sets gathered without an index, such as content-hash lookups.
+/// Ties break on paste id.
+pub fn sort_pastes(pastes: &mut [Paste], sort: PasteSortKey, order: SortOrder) {
+    pastes.sort_by(|a, b| {
+        let by_field = match sort {
+            PasteSortKey::Updated => a.updated_at.cmp(&b.updated_at),
+            PasteSortKey::Created => a.created_at.cmp(&b.created_at),
+            PasteSortKey::Name => compare_names(&a.name, &b.name),
+            PasteSortKey::Size => a.content.len().cmp(&b.content.len()),
+        };
+        directed(by_field.then_with(|| a.id.cmp(&b.id)), order)
+    });
+}
+
+fn meta_matches_list_filters(
+    meta: &PasteMeta,
+    folder_id: Option<&str>,
+    tag: Option<&str>,
+    language: Option<&str>,
+) -> bool {
+    folder_id.is_none_or(|fid| meta.folder_id.as_deref() == Some(fid))
+        && tag.is_none_or(|tag| {
+            meta.tags
+                .iter()
+                .any(|candidate| normalize_tag_filter(Some(candidate)).as_deref() == Some(tag))
+        })
+        && language_matches_filter(meta.language.as_deref(), language)
+}
+
+impl PasteDb {
+    /// List paste metadata newest-first with optional folder, tag, and language filters.
+    ///
+    /// # Arguments
+    /// - `limit`: Maximum rows to return.
+    /// - `folder_id`: Optional folder filter.
+    /// - `tag`: Optional tag filter (case-insensitive).
+    /// - `language`: Optional language filter (aliases canonicalized).
+    ///
+    /// # Returns
+    /// Up to `limit` metadata rows in descending recency order.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or deserialization fails.
+    pub fn list_meta_filtered(
+        &self,
+        limit: usize,
+        folder_id: Option<&str>,
+        tag: Option<&str>,
+        language: Option<&str>,
+    ) -> Result<Vec<PasteMeta>, AppError> {
+        self.list_meta_sorted(
+            limit,
+            folder_id,
+            tag,
+            language,
+            PasteSortKey::Updated,
+            SortOrder::Desc,
+        )
+    }
+
+    /// List paste metadata with optional filters in the requested order.
+    ///
+    /// `updated` walks one index range (folder, then tag, then language, or
+    /// the recency index without filters) from either end and checks any other
+    /// filters on the loaded rows. `created` walks the creation index and
+    /// checks every filter on the rows. `name` and `size` have no index: they
+    /// load every matching metadata row, sort, and keep the first `limit`, so
+    /// their time and memory grow with the number of matches rather than with
+    /// `limit`. Ties break on paste id.
+    ///
+    /// # Arguments
+    /// - `limit`: Maximum rows to return.
+    /// - `folder_id`: Optional folder filter.
+    /// - `tag`: Optional tag filter (case-insensitive).
+    /// - `language`: Optional language filter (aliases canonicalized).
+    /// - `sort`: Field to order by.
+    /// - `order`: Direction of `sort`.
+    ///
+    /// # Returns
+    /// Up to `limit` metadata rows.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or deserialization fails.
+    #[tracing::instrument(
+        level = "debug",
+        name = "paste_db.list_meta",
+        skip_all,
+        fields(limit = limit)
+    )]
+    pub fn list_meta_sorted(
+        &self,
+        limit: usize,
+        folder_id: Option<&str>,
+        tag: Option<&str>,
+        language: Option<&str>,
+        sort: PasteSortKey,
+        order: SortOrder,
+    ) -> Result<Vec<PasteMeta>, AppError> {
+        let mut slow = SlowQueryTimer::start("paste_db.list_meta");
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let tag = normalize_tag_filter(tag);
+        let language = normalize_language_filter(language);
+        // Unindexed sorts need every match before they can pick the first rows.
+        let sorted_in_memory = matches!(sort, PasteSortKey::Name | PasteSortKey::Size);
+        let scan_order = if sorted_in_memory {
+            SortOrder::Desc
+        } else {
+            order
+        };
+
+        let read_txn = self.db.begin_read()?;
+        let meta_table = read_txn.open_table(PASTES_META)?;
+        let mut metas = Vec::new();
+        let visit = |paste_id: &str| -> Result<bool, AppError> {
+            slow.keys += 1;
+            let Some(meta_guard) = meta_table.get(paste_id)? else {
+                return Ok(true);
+            };
+            let meta = deserialize_meta(meta_guard.value())?;
+            if meta_matches_list_filters(&meta, folder_id, tag.as_deref(), language.as_deref()) {
+                metas.push(meta);
+            }
+            Ok(sorted_in_memory || metas.len() < limit)
+        };
+
+        if sort == PasteSortKey::Created {
+            scan_time_index(&read_txn, PASTES_BY_CREATED, scan_order, visit)?;
+        } else if let Some(folder_id) = folder_id {
+            scan_filter_index_ordered(&read_txn, PASTES_BY_FOLDER, folder_id, scan_order, visit)?;
+        } else if let Some(tag) = tag.as_deref() {
+            scan_filter_index_ordered(&read_txn, PASTES_BY_TAG, tag, scan_order, visit)?;
+        } else if let Some(language) = language.as_deref() {
+            scan_filter_index_ordered(&read_txn, PASTES_BY_LANGUAGE, language, scan_order, visit)?;
+        } else {
+            scan_time_index(&read_txn, PASTES_BY_UPDATED, scan_order, visit)?;
+        }
+
+        if sorted_in_memory {
+            metas.sort_by(|a, b| directed(compare_meta(sort, a, b), order));
+            metas.truncate(limit);
+        }
+        Ok(metas)
+    }
+}
+
+impl PasteDb {
+    /// Count pastes matching optional folder, tag, and language filters.
+    ///
+    /// A single filter counts its index range without loading rows, and no
+    /// filter reads the recency index length. Combined filters walk the
+    /// driving index and check the rest against metadata rows.
+    ///
+    /// # Arguments
+    /// - `folder_id`: Optional folder filter (direct pastes only).
+    /// - `tag`: Optional tag filter (case-insensitive).
+    /// - `language`: Optional language filter (aliases canonicalized).
+    ///
+    /// # Returns
+    /// Number of matching pastes.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or deserialization fails.
+    #[tracing::instrument(level = "debug", name = "paste_db.count", skip_all)]
+    pub fn count_filtered(
+        &self,
+        folder_id: Option<&str>,
+        tag: Option<&str>,
+        language: Option<&str>,
+    ) -> Result<usize, AppError> {
+        let mut slow = SlowQueryTimer::start("paste_db.count");
+        let tag = normalize_tag_filter(tag);
+        let language = normalize_language_filter(language);
+        let read_txn = self.db.begin_read()?;
+
+        let filters = [
+            folder_id.map(|value| (PASTES_BY_FOLDER, value)),
+            tag.as_deref().map(|value| (PASTES_BY_TAG, value)),
+            language.as_deref().map(|value| (PASTES_BY_LANGUAGE, value)),
+        ];
+        let mut active = filters.iter().flatten();
+        let Some(&(driving_table, driving_value)) = active.next() else {
+            let updated_table = read_txn.open_table(PASTES_BY_UPDATED)?;
+            return Ok(updated_table.len()? as usize);
+        };
+        let needs_row_check = active.next().is_some();
+
+        let meta_table = read_txn.open_table(PASTES_META)?;
+        let mut count = 0usize;
+        scan_filter_index(&read_txn, driving_table, driving_value, |paste_id| {
+            slow.keys += 1;
+            if !needs_row_check {
+                count += 1;
+                return Ok(true);
+            }
+            let Some(meta_guard) = meta_table.get(paste_id)? else {
+                return Ok(true);
+            };
+            let meta = deserialize_meta(meta_guard.value())?;
+            if meta_matches_list_filters(&meta, folder_id, tag.as_deref(), language.as_deref()) {
+                count += 1;
+            }
+            Ok(true)
+        })?;
+        Ok(count)
+    }
+}
+
+impl PasteDb {
+    /// Count pastes per canonical language from the language index.
+    ///
+    /// Pastes without a language are not counted.
+    ///
+    /// # Returns
+    /// `(language, count)` pairs sorted by descending count, then language.
+    ///
+    /// # Errors
+    /// Returns an error when storage access fails.
+    #[tracing::instrument(level = "debug", name = "paste_db.language_counts", skip_all)]
+    pub fn language_counts(&self) -> Result<Vec<(String, usize)>, AppError> {
+        let mut slow = SlowQueryTimer::start("paste_db.language_counts");
+        let read_txn = self.db.begin_read()?;
+        let index = read_txn.open_table(PASTES_BY_LANGUAGE)?;
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for item in index.iter()? {
+            let (key, _) = item?;
+            slow.keys += 1;
+            let (language, _, _) = key.value();
+            match counts.last_mut() {
+                Some((last, count)) if last == language => *count += 1,
+                _ => counts.push((language.to_string(), 1)),
+            }
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(counts)
+    }
+
+    /// Count pastes per normalized tag from the tag index.
+    ///
+    /// # Returns
+    /// `(tag, count)` pairs sorted by descending count, then tag.
+    ///
+    /// # Errors
+    /// Returns an error when storage access fails.
+    #[tracing::instrument(level = "debug", name = "paste_db.tag_counts", skip_all)]
+    pub fn tag_counts(&self) -> Result<Vec<(String, usize)>, AppError> {
+        let mut slow = SlowQueryTimer::start("paste_db.tag_counts");
+        let read_txn = self.db.begin_read()?;
+        let index = read_txn.open_table(PASTES_BY_TAG)?;
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for item in index.iter()? {
+            let (key, _) = item?;
+            slow.keys += 1;
+            let (tag, _, _) = key.value();
+            match counts.last_mut() {
+                Some((last, count)) if last == tag => *count += 1,
+                _ => counts.push((tag.to_string(), 1)),
+            }
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(counts)
+    }
+
+    /// Ids of pastes carrying `tag` (case-insensitive), newest first.
+    ///
+    /// # Errors
+    /// Returns an error when storage access fails.
+    pub fn ids_with_tag(&self, tag: &str) -> Result<Vec<String>, AppError> {
+        let Some(tag) = normalize_tag_filter(Some(tag)) else {
+            return Ok(Vec::new());
+        };
+        let read_txn = self.db.begin_read()?;
+        let mut ids = Vec::new();
+        scan_filter_index(&read_txn, PASTES_BY_TAG, tag.as_str(), |id| {
+            ids.push(id.to_string());
+            Ok(true)
+        })?;
+        Ok(ids)
+    }
+}
@@ -1,6 +1,9 @@
 //! Helper functions shared by paste storage operations.
 
 use crate::models::paste::*;
+use crate::normalization::{
+    normalize_content, record_line_ending, LineEnding, NormalizationOptions,
+};
 use crate::semantic::{DerivedMeta, PasteKind};
 use chrono::{DateTime, Utc};
 use serde::de::DeserializeOwned;
@@ -26,12 +29,20 @@ pub(crate) fn reverse_timestamp_key(updated_at: DateTime<Utc>) -> u64 {
 /// # Arguments
 /// - `paste`: Mutable paste row to update.
 /// - `update`: Incoming patch payload.
-pub(crate) fn apply_update_request(paste: &mut Paste, update: &UpdatePasteRequest) {
+/// - `normalization`: Save-time content normalization options.
+pub(crate) fn apply_update_request(
+    paste: &mut Paste,
+    update: &UpdatePasteRequest,
+    normalization: &NormalizationOptions,
+) {
     let mut content_changed = false;
     let was_manual_before_update = paste.language_is_manual;
 
     if let Some(content) = &update.content {
-        paste.content = content.clone();
+        let (content, detected) = normalize_content(content.clone(), normalization);
+        paste.line_ending =
+            record_line_ending(paste.line_ending, detected, normalization.line_endings);
+        paste.content = content;
         paste.is_markdown = is_markdown_content(&paste.content);
         content_changed = true;
     }
@@ -82,6 +93,9 @@ pub(crate) fn apply_update_request(paste: &mut Paste, update: &UpdatePasteReques
     }
 
     paste.updated_at = Utc::now();
+    if let Some(expires_in) = update.expires_in {
+        paste.expires_at = expiry_after(paste.updated_at, expires_in);
+    }
 }
 
 /// Returns `true` when a paste language satisfies the provided filter.
@@ -106,26 +120,33 @@ pub(super) fn language_matches_filter(language: Option<&str>, filter: Option<&st
         .unwrap_or(false)
 }
 
-/// Returns `true` when metadata matches both folder and language filters.
+/// Returns `true` when a row passes every search filter.
 ///
 /// # Arguments
-/// - `meta`: Metadata row under evaluation.
-/// - `folder_filter`: Optional folder id filter.
-/// - `language_filter`: Optional language filter.
+/// - `filters`: Filters from [`SearchFilters::normalized`].
+/// - `folder_id`, `language`, `tags`, `updated_at`: Fields of the row under
+///   evaluation.
 ///
 /// # Returns
 /// `true` when all provided filters match.
-pub(super) fn meta_matches_filters(
-    meta: &PasteMeta,
-    folder_filter: Option<&str>,
-    language_filter: Option<&str>,
+pub(super) fn search_filters_match(
+    filters: &SearchFilters,
+    folder_id: Option<&str>,
+    language: Option<&str>,
+    tags: &[String],
+    updated_at: DateTime<Utc>,
 ) -> bool {
-    if let Some(folder_id) = folder_filter {
-        if meta.folder_id.as_deref() != Some(folder_id) {
-            return false;
-        }
-    }
-    language_matches_filter(meta.language.as_deref(), language_filter)
+    filters
+        .folder_id
+        .as_deref()
+        .is_none_or(|filter| folder_id == Some(filter))
+        && language_matches_filter(language, filters.language.as_deref())
+        && filters.tag.as_deref().is_none_or(|filter| {
+            tags.iter()
+                .any(|tag| normalize_tag_filter(Some(tag)).as_deref() == Some(filter))
+        })
+        && filters.before.is_none_or(|before| updated_at < before)
+        && filters.after.is_none_or(|after| updated_at >= after)
 }
 
 /// Scores a metadata row for search ranking.
@@ -277,34 +298,37 @@ fn kind_matches_query(kind: PasteKind, query_lower: &str) -> bool {
             .contains(query_lower.trim())
 }
 
-/// Scores a full paste row for search ranking.
+/// Scores a paste's name and tags for content search ranking.
 ///
-/// Name and tag matches are weighted above content matches.
+/// Name and tag matches are weighted above the content match, which the
+/// caller adds with [`content_matches_query`] once the body is loaded.
 ///
 /// # Arguments
-/// - `paste`: Paste row to score.
+/// - `meta`: Metadata row of the paste.
 /// - `query_lower`: Lowercased search query.
 ///
 /// # Returns
 /// A non-negative score used for top-k ordering.
-pub(super) fn score_paste_match(paste: &Paste, query_lower: &str) -> i32 {
+pub(super) fn score_paste_fields(meta: &PasteMeta, query_lower: &str) -> i32 {
     let mut score = 0;
-    if contains_case_insensitive(&paste.name, query_lower) {
+    if contains_case_insensitive(&meta.name, query_lower) {
         score += 10;
     }
-    if paste
+    if meta
         .tags
         .iter()
         .any(|tag| contains_case_insensitive(tag, query_lower))
     {
         score += 5;
     }
-    if contains_case_insensitive(&paste.content, query_lower) {
-        score += 1;
-    }
     score
 }
 
+/// Returns `true` when paste content contains the lowercased query, ignoring case.
+pub(super) fn content_matches_query(content: &str, query_lower: &str) -> bool {
+    contains_case_insensitive(content, query_lower)
+}
+
 /// Adds a metadata candidate into a bounded top-k ranking set.
 ///
 /// # Arguments
@@ -416,7 +440,27 @@ pub(super) fn folder_matches_expected(
 /// Returns the primary deserialization error when neither current nor legacy
 /// wire formats can be decoded.
 pub(crate) fn deserialize_paste(bytes: &[u8]) -> Result<Paste, bincode::Error> {
-    deserialize_current_or_legacy::<Paste, LegacyPaste>(bytes, Paste::from)
+    deserialize_current_or_legacy::<Paste, PreBurnPaste>(bytes, Paste::from)
+        .or_else(|err| {
+            bincode::deserialize::<PreExpiryPaste>(bytes)
+                .map(Paste::from)
+                .map_err(|_| err)
+        })
+        .or_else(|err| {
+            bincode::deserialize::<PreSlugPaste>(bytes)
+                .map(Paste::from)
+                .map_err(|_| err)
+        })
+        .or_else(|err| {
+            bincode::deserialize::<PreLineEndingPaste>(bytes)
+                .map(Paste::from)
+                .map_err(|_| err)
+        })
+        .or_else(|err| {
+            bincode::deserialize::<LegacyPaste>(bytes)
+                .map(Paste::from)
+                .map_err(|_| err)
+        })
 }
 
 /// Deserializes a [`PasteMeta`] row from storage bytes.
@@ -458,6 +502,72 @@ struct LegacyPaste {
     is_markdown: bool,
 }
 
+/// Row shape written before [`Paste::line_ending`] was persisted.
+#[derive(Serialize, Deserialize)]
+struct PreLineEndingPaste {
+    id: String,
+    name: String,
+    content: String,
+    language: Option<String>,
+    language_is_manual: bool,
+    folder_id: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    tags: Vec<String>,
+    is_markdown: bool,
+}
+
+/// Row shape written before [`Paste::slug`] was persisted.
+#[derive(Serialize, Deserialize)]
+struct PreSlugPaste {
+    id: String,
+    name: String,
+    content: String,
+    language: Option<String>,
+    language_is_manual: bool,
+    folder_id: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    tags: Vec<String>,
+    is_markdown: bool,
+    line_ending: LineEnding,
+}
+
+/// Row shape written before [`Paste::expires_at`] was persisted.
+#[derive(Serialize, Deserialize)]
+struct PreExpiryPaste {
+    id: String,
+    name: String,
+    content: String,
+    language: Option<String>,
+    language_is_manual: bool,
+    folder_id: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    tags: Vec<String>,
+    is_markdown: bool,
+    line_ending: LineEnding,
+    slug: Option<String>,
+}
+
+/// Row shape written before [`Paste::burn_after_read`] was persisted.
+#[derive(Serialize, Deserialize)]
+struct PreBurnPaste {
+    id: String,
+    name: String,
+    content: String,
+    language: Option<String>,
+    language_is_manual: bool,
+    folder_id: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    tags: Vec<String>,
+    is_markdown: bool,
+    line_ending: LineEnding,
+    slug: Option<String>,
+    expires_at: Option<DateTime<Utc>>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct LegacyPasteMeta {
     id: String,
@@ -497,6 +607,150 @@ impl From<LegacyPaste> for Paste {
             updated_at,
             tags,
             is_markdown,
+            line_ending: LineEnding::default(),
+            slug: None,
+            expires_at: None,
+            burn_after_read: false,
+        }
+    }
+}
+
+impl From<PreLineEndingPaste> for Paste {
+    fn from(old: PreLineEndingPaste) -> Self {
+        let PreLineEndingPaste {
+            id,
+            name,
+            content,
+            language,
+            language_is_manual,
+            folder_id,
+            created_at,
+            updated_at,
+            tags,
+            is_markdown,
+        } = old;
+        Self {
+            id,
+            name,
+            content,
+            language,
+            language_is_manual,
+            folder_id,
+            created_at,
+            updated_at,
+            tags,
+            is_markdown,
+            // Rows saved before line endings were recorded were stored verbatim,
+            // so LF is the only safe export default.
+            line_ending: LineEnding::default(),
+            slug: None,
+            expires_at: None,
+            burn_after_read: false,
+        }
+    }
+}
+
+impl From<PreSlugPaste> for Paste {
+    fn from(old: PreSlugPaste) -> Self {
+        let PreSlugPaste {
+            id,
+            name,
+            content,
+            language,
+            language_is_manual,
+            folder_id,
+            created_at,
+            updated_at,
+            tags,
+            is_markdown,
+            line_ending,
+        } = old;
+        Self {
+            id,
+            name,
+            content,
+            language,
+            language_is_manual,
+            folder_id,
+            created_at,
+            updated_at,
+            tags,
+            is_markdown,
+            line_ending,
+            slug: None,
+            expires_at: None,
+            burn_after_read: false,
+        }
+    }
+}
+
+impl From<PreExpiryPaste> for Paste {
+    fn from(old: PreExpiryPaste) -> Self {
+        let PreExpiryPaste {
+            id,
+            name,
+            content,
+            language,
+            language_is_manual,
+            folder_id,
+            created_at,
+            updated_at,
+            tags,
+            is_markdown,
+            line_ending,
+            slug,
+        } = old;
+        Self {
+            id,
+            name,
+            content,
+            language,
+            language_is_manual,
+            folder_id,
+            created_at,
+            updated_at,
+            tags,
+            is_markdown,
+            line_ending,
+            slug,
+            expires_at: None,
+            burn_after_read: false,
+        }
+    }
+}
+
+impl From<PreBurnPaste> for Paste {
+    fn from(old: PreBurnPaste) -> Self {
+        let PreBurnPaste {
+            id,
+            name,
+            content,
+            language,
+            language_is_manual,
+            folder_id,
+            created_at,
+            updated_at,
+            tags,
+            is_markdown,
+            line_ending,
+            slug,
+            expires_at,
+        } = old;
+        Self {
+            id,
+            name,
+            content,
+            language,
+            language_is_manual,
+            folder_id,
+            created_at,
+            updated_at,
+            tags,
+            is_markdown,
+            line_ending,
+            slug,
+            expires_at,
+            burn_after_read: false,
         }
     }
 }
@@ -531,7 +785,8 @@ impl From<LegacyPasteMeta> for PasteMeta {
 mod tests {
     use super::{
         apply_update_request, reverse_timestamp_key, score_meta_match, split_meta_query_terms,
-        DerivedMeta, LegacyPaste, LegacyPasteMeta, Paste, PasteKind,
+        DerivedMeta, LegacyPaste, LegacyPasteMeta, LineEnding, Paste, PasteKind, PreBurnPaste,
+        PreExpiryPaste, PreLineEndingPaste, PreSlugPaste,
     };
     use crate::models::paste::{PasteMeta, UpdatePasteRequest};
     use chrono::{TimeZone, Utc};
@@ -589,8 +844,9 @@ mod tests {
             language_is_manual: None,
             folder_id: None,
             tags: None,
+            expires_in: None,
         };
-        apply_update_request(&mut migrated, &update);
+        apply_update_request(&mut migrated, &update, &Default::default());
 
         assert_eq!(migrated.language.as_deref(), Some("rust"));
         assert!(migrated.language_is_manual);
@@ -667,6 +923,7 @@ mod tests {
                 kind: PasteKind::Code,
                 handle: Some("cargo test".to_string()),
                 terms: Vec::new(),
+                indent: None,
             },
             ..base.clone()
         };
@@ -675,6 +932,7 @@ mod tests {
                 kind: PasteKind::Code,
                 handle: None,
                 terms: vec!["cargo".to_string(), "test".to_string()],
+                indent: None,
             },
             ..base.clone()
         };
@@ -715,6 +973,119 @@ mod tests {
         assert_eq!(decoded.derived, DerivedMeta::default());
     }
 
+    #[test]
+    fn deserialize_paste_accepts_rows_without_line_ending() {
+        let old = PreLineEndingPaste {
+            id: "id".to_string(),
+            name: "pre-line-ending".to_string(),
+            content: "a\r\nb".to_string(),
+            language: None,
+            language_is_manual: true,
+            folder_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            tags: Vec::new(),
+            is_markdown: false,
+        };
+        let encoded = bincode::serialize(&old).expect("serialize");
+        let decoded = super::deserialize_paste(&encoded).expect("decode");
+        assert_eq!(decoded.content, "a\r\nb");
+        assert!(decoded.language_is_manual);
+        assert_eq!(decoded.line_ending, LineEnding::Lf);
+
+        let mut current = decoded;
+        current.line_ending = LineEnding::Crlf;
+        let encoded = bincode::serialize(&current).expect("serialize current");
+        let decoded = super::deserialize_paste(&encoded).expect("decode current");
+        assert_eq!(decoded.line_ending, LineEnding::Crlf);
+    }
+
+    #[test]
+    fn deserialize_paste_accepts_rows_without_slug() {
+        let old = PreSlugPaste {
+            id: "id".to_string(),
+            name: "pre-slug".to_string(),
+            content: "body".to_string(),
+            language: None,
+            language_is_manual: false,
+            folder_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            tags: Vec::new(),
+            is_markdown: false,
+            line_ending: LineEnding::Crlf,
+        };
+        let encoded = bincode::serialize(&old).expect("serialize");
+        let decoded = super::deserialize_paste(&encoded).expect("decode");
+        assert_eq!(decoded.line_ending, LineEnding::Crlf);
+        assert_eq!(decoded.slug, None);
+
+        let mut current = decoded;
+        current.slug = Some("nginx-config".to_string());
+        let encoded = bincode::serialize(&current).expect("serialize current");
+        let decoded = super::deserialize_paste(&encoded).expect("decode current");
+        assert_eq!(decoded.slug.as_deref(), Some("nginx-config"));
+    }
+
+    #[test]
+    fn deserialize_paste_accepts_rows_without_expiry() {
+        let old = PreExpiryPaste {
+            id: "id".to_string(),
+            name: "pre-expiry".to_string(),
+            content: "body".to_string(),
+            language: None,
+            language_is_manual: false,
+            folder_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            tags: Vec::new(),
+            is_markdown: false,
+            line_ending: LineEnding::Lf,
+            slug: Some("kept".to_string()),
+        };
+        let encoded = bincode::serialize(&old).expect("serialize");
+        let decoded = super::deserialize_paste(&encoded).expect("decode");
+        assert_eq!(decoded.slug.as_deref(), Some("kept"));
+        assert_eq!(decoded.expires_at, None);
+
+        let mut current = decoded;
+        let expires_at = Utc.with_ymd_and_hms(2030, 1, 2, 3, 4, 5).unwrap();
+        current.expires_at = Some(expires_at);
+        let encoded = bincode::serialize(&current).expect("serialize current");
+        let decoded = super::deserialize_paste(&encoded).expect("decode current");
+        assert_eq!(decoded.expires_at, Some(expires_at));
+    }
+
+    #[test]
+    fn deserialize_paste_accepts_rows_without_burn_after_read() {
+        let expires_at = Utc.with_ymd_and_hms(2030, 1, 2, 3, 4, 5).unwrap();
+        let old = PreBurnPaste {
+            id: "id".to_string(),
+            name: "pre-burn".to_string(),
+            content: "body".to_string(),
+            language: None,
+            language_is_manual: false,
+            folder_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            tags: Vec::new(),
+            is_markdown: false,
+            line_ending: LineEnding::Lf,
+            slug: None,
+            expires_at: Some(expires_at),
+        };
+        let encoded = bincode::serialize(&old).expect("serialize");
+        let decoded = super::deserialize_paste(&encoded).expect("decode");
+        assert_eq!(decoded.expires_at, Some(expires_at));
+        assert!(!decoded.burn_after_read);
+
+        let mut current = decoded;
+        current.burn_after_read = true;
+        let encoded = bincode::serialize(&current).expect("serialize current");
+        let decoded = super::deserialize_paste(&encoded).expect("decode current");
+        assert!(decoded.burn_after_read);
+    }
+
     #[test]
     fn split_meta_query_terms_dedupes_and_skips_short_tokens() {
         assert_eq!(
@@ -0,0 +1,99 @@
+//! Short-id resolution: slugs and unambiguous id prefixes stand in for full
+//! paste ids.
+
+use super::PasteDb;
+use crate::{db::tables::PASTES, error::AppError};
+use redb::ReadableDatabase;
+
+/// Shortest prefix accepted in place of a full paste id.
+pub const MIN_ID_PREFIX_LEN: usize = 4;
+/// Most candidate ids reported for an ambiguous prefix.
+pub const MAX_ID_PREFIX_CANDIDATES: usize = 10;
+
+impl PasteDb {
+    /// Resolve a full paste id, a slug, or a unique id prefix to the stored id.
+    ///
+    /// Exact ids always win, then slugs, so neither is reinterpreted as a
+    /// prefix. Prefixes are matched case-insensitively against the id key range.
+    ///
+    /// # Returns
+    /// The full paste id.
+    ///
+    /// # Errors
+    /// Returns [`AppError::NotFound`] when nothing matches or the prefix is
+    /// shorter than [`MIN_ID_PREFIX_LEN`], and [`AppError::AmbiguousId`] with
+    /// up to [`MAX_ID_PREFIX_CANDIDATES`] ids when several pastes match.
+    pub fn resolve_id(&self, id: &str) -> Result<String, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let pastes = read_txn.open_table(PASTES)?;
+        if pastes.get(id)?.is_some() {
+            return Ok(id.to_string());
+        }
+        if let Some(paste) = Self::slug_owner(&read_txn, id)? {
+            return Ok(paste.id);
+        }
+        let prefix = id.trim().to_ascii_lowercase();
+        if prefix.chars().count() < MIN_ID_PREFIX_LEN {
+            return Err(AppError::NotFound);
+        }
+        let mut candidates = Vec::new();
+        for item in pastes.range::<&str>(prefix.as_str()..)? {
+            let (key, _) = item?;
+            let key = key.value();
+            if !key.starts_with(prefix.as_str()) {
+                break;
+            }
+            candidates.push(key.to_string());
+            if candidates.len() > MAX_ID_PREFIX_CANDIDATES {
+                break;
+            }
+        }
+        match candidates.len() {
+            0 => Err(AppError::NotFound),
+            1 => Ok(candidates.remove(0)),
+            _ => {
+                candidates.truncate(MAX_ID_PREFIX_CANDIDATES);
+                Err(AppError::AmbiguousId { prefix, candidates })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::paste::Paste;
+    use crate::test_support::setup_temp_db;
+
+    fn paste_with_id(id: &str) -> Paste {
+        let mut paste = Paste::new("body".to_string(), id.to_string());
+        paste.id = id.to_string();
+        paste
+    }
+
+    #[test]
+    fn resolve_id_accepts_exact_ids_and_unique_prefixes() {
+        let (db, _temp) = setup_temp_db();
+        for id in ["3f2a0001", "3f2b0002", "3f2b0003", "abc"] {
+            db.pastes.create(&paste_with_id(id)).expect("create");
+        }
+
+        assert_eq!(db.pastes.resolve_id("abc").expect("exact"), "abc");
+        assert_eq!(db.pastes.resolve_id("3F2A").expect("prefix"), "3f2a0001");
+        assert!(matches!(
+            db.pastes.resolve_id("3f2"),
+            Err(AppError::NotFound)
+        ));
+        assert!(matches!(
+            db.pastes.resolve_id("ffff"),
+            Err(AppError::NotFound)
+        ));
+        match db.pastes.resolve_id("3f2b") {
+            Err(AppError::AmbiguousId { prefix, candidates }) => {
+                assert_eq!(prefix, "3f2b");
+                assert_eq!(candidates, vec!["3f2b0002", "3f2b0003"]);
+            }
+            other => panic!("expected ambiguous prefix, got {:?}", other),
+        }
+    }
+}
@@ -1,12 +1,21 @@
 //! Paste storage operations backed by redb.
 
 mod compare;
+mod content_hash;
+mod content_index;
+mod filter_index;
 mod helpers;
+mod id_prefix;
+mod slug;
 
+use crate::db::durability::{DurabilityState, PolicyWrite};
+use crate::db::journal::JournalSlot;
 use crate::{
     config::paste_version_interval_secs_from_env_or_default,
     db::{
+        slow_query::SlowQueryTimer,
         tables::*,
+        transactions::apply_folder_stats_transition,
         versioning::{
             decode_version_meta_list, encode_version_meta_list, next_version_meta_for_content,
             should_record_version,
@@ -14,26 +23,37 @@ use crate::{
     },
     error::AppError,
     models::paste::*,
-    naming,
+    naming::title::TitleOptions,
+    normalization::NormalizationOptions,
+    text::CleanupOptions,
 };
 use chrono::{DateTime, Utc};
 use redb::{ReadTransaction, ReadableDatabase, ReadableTable};
 use std::sync::Arc;
 
 use self::helpers::{
-    deserialize_meta, finalize_meta_search_results, folder_matches_expected,
-    language_matches_filter, meta_matches_filters, push_ranked_meta_top_k, score_meta_match,
-    score_paste_match,
+    content_matches_query, deserialize_meta, finalize_meta_search_results, folder_matches_expected,
+    push_ranked_meta_top_k, score_meta_match, score_paste_fields, search_filters_match,
 };
 
+use self::filter_index::scan_filter_index;
+pub(crate) use self::filter_index::{FilterIndexKeys, FilterIndexTables};
 pub(crate) use self::helpers::{apply_update_request, deserialize_paste, reverse_timestamp_key};
+pub use content_hash::{parse_content_hash, paste_content_hash};
+pub use filter_index::sort_pastes;
+pub use id_prefix::{MAX_ID_PREFIX_CANDIDATES, MIN_ID_PREFIX_LEN};
 
 /// Accessor for paste-related redb tables.
 pub struct PasteDb {
     db: Arc<redb::Database>,
     version_interval_secs: u64,
+    normalization: NormalizationOptions,
+    titles: TitleOptions,
+    clipboard_cleanup: CleanupOptions,
 }
 
+/// Rows scanned between cancellation checks in [`PasteDb::search_meta_until`].
+pub const SEARCH_CANCEL_CHECK_INTERVAL: usize = 256;
 const DEFAULT_VERSION_LIST_LIMIT: usize = 50;
 const MAX_VERSION_LIST_LIMIT: usize = 1_000;
 /// Singleton key storing the persisted metadata projection schema version.
@@ -41,8 +61,10 @@ pub(crate) const META_SCHEMA_VERSION_KEY: &str = "__schema_version";
 /// Current `PASTES_META` projection schema version for derived retrieval fields.
 ///
 /// Bump this whenever the persisted `PasteMeta` projection contract changes,
-/// including semantic-derived fields produced by [`PasteMeta::from`].
-pub(crate) const CURRENT_PASTES_META_SCHEMA_VERSION: u64 = 1;
+/// including semantic-derived fields produced by [`PasteMeta::from`] and the
+/// creation/folder/tag/language indexes and the content trigram index rebuilt
+/// alongside it.
+pub(crate) const CURRENT_PASTES_META_SCHEMA_VERSION: u64 = 5;
 
 impl PasteDb {
     fn ensure_content_within_size_limit(
@@ -80,17 +102,29 @@ impl PasteDb {
         // init. GUI/tool callers intentionally use permissive config loading, so
         // PasteDb follows the same fallback-to-default behavior here.
         let version_interval_secs = paste_version_interval_secs_from_env_or_default();
+        let normalization = NormalizationOptions::from_env();
+        let titles = TitleOptions::from_env();
+        let clipboard_cleanup = CleanupOptions::clipboard_from_env();
         let write_txn = db.begin_write()?;
         write_txn.open_table(PASTES)?;
         write_txn.open_table(PASTES_META)?;
         write_txn.open_table(PASTES_META_STATE)?;
         write_txn.open_table(PASTES_BY_UPDATED)?;
+        write_txn.open_table(PASTES_BY_CREATED)?;
+        write_txn.open_table(PASTES_BY_FOLDER)?;
+        write_txn.open_table(PASTES_BY_TAG)?;
+        write_txn.open_table(PASTES_BY_LANGUAGE)?;
+        write_txn.open_table(PASTES_BY_TRIGRAM)?;
         write_txn.open_table(PASTE_VERSIONS_META)?;
         write_txn.open_table(PASTE_VERSIONS_CONTENT)?;
+        write_txn.open_table(PASTE_SLUGS)?;
         write_txn.commit()?;
         Ok(Self {
             db,
             version_interval_secs,
+            normalization,
+            titles,
+            clipboard_cleanup,
         })
     }
 
@@ -102,10 +136,39 @@ impl PasteDb {
         self.version_interval_secs
     }
 
+    /// Save-time content normalization applied by update paths.
+    ///
+    /// Create paths build the [`Paste`] before storage sees it, so callers
+    /// normalize new content with these same options.
+    ///
+    /// # Returns
+    /// Options loaded from the environment at open time.
+    pub fn normalization(&self) -> &NormalizationOptions {
+        &self.normalization
+    }
+
+    /// Default-title strategies for pastes created without a name.
+    ///
+    /// # Returns
+    /// Options loaded from the environment at open time.
+    pub fn title_options(&self) -> &TitleOptions {
+        &self.titles
+    }
+
+    /// Cleanup transforms applied to clipboard captures before they are created.
+    ///
+    /// # Returns
+    /// Options loaded from `LOCALPASTE_CLIPBOARD_CLEANUP` at open time.
+    pub fn clipboard_cleanup(&self) -> &CleanupOptions {
+        &self.clipboard_cleanup
+    }
+
     /// Rebuild the persisted metadata projection from canonical paste rows.
     ///
-    /// `PASTES_META` is derived state, so schema evolution can safely rewrite it
-    /// from authoritative `PASTES` content during startup.
+    /// `PASTES_META`, the folder/tag/language filter indexes, and the content
+    /// trigram index are derived state, so schema evolution can safely rewrite
+    /// them from authoritative `PASTES` content during startup. Rows are
+    /// rewritten one at a time, so memory stays bounded by the largest paste.
     ///
     /// # Returns
     /// `Ok(())` when the derived metadata projection is fully rewritten.
@@ -113,22 +176,13 @@ impl PasteDb {
     /// # Errors
     /// Returns an error when any read, decode, write, or commit step fails.
     pub fn rebuild_meta_index(&self) -> Result<(), AppError> {
-        let rebuilt = {
-            let read_txn = self.db.begin_read()?;
-            let pastes = read_txn.open_table(PASTES)?;
-            let mut metas = Vec::new();
-            for item in pastes.iter()? {
-                let (_, value) = item?;
-                let paste = deserialize_paste(value.value())?;
-                metas.push(PasteMeta::from(&paste));
-            }
-            metas
-        };
-
         let write_txn = self.db.begin_write()?;
         {
+            let pastes = write_txn.open_table(PASTES)?;
             let mut metas = write_txn.open_table(PASTES_META)?;
             let mut meta_state = write_txn.open_table(PASTES_META_STATE)?;
+            let mut filters = FilterIndexTables::open(&write_txn)?;
+            filters.clear()?;
             let existing_ids = metas
                 .iter()?
                 .map(|item| item.map(|(key, _)| key.value().to_string()))
@@ -136,9 +190,13 @@ impl PasteDb {
             for id in existing_ids {
                 let _ = metas.remove(id.as_str())?;
             }
-            for meta in rebuilt {
+            for item in pastes.iter()? {
+                let (_, value) = item?;
+                let paste = deserialize_paste(value.value())?;
+                let meta = PasteMeta::from(&paste);
                 let encoded = bincode::serialize(&meta)?;
                 metas.insert(meta.id.as_str(), encoded.as_slice())?;
+                filters.insert(meta.id.as_str(), &FilterIndexKeys::from_paste(&paste))?;
             }
             let encoded_version = bincode::serialize(&CURRENT_PASTES_META_SCHEMA_VERSION)?;
             meta_state.insert(META_SCHEMA_VERSION_KEY, encoded_version.as_slice())?;
@@ -187,7 +245,9 @@ impl PasteDb {
     /// # Errors
     /// Returns an error when serialization fails, id already exists, or storage
     /// operations fail.
+    #[tracing::instrument(level = "debug", name = "paste_db.create", skip_all, fields(id = %paste.id))]
     pub fn create(&self, paste: &Paste) -> Result<(), AppError> {
+        let _slow = SlowQueryTimer::start("paste_db.create").with_keys(1);
         Self::reject_direct_folder_operation(
             paste.folder_id.is_some(),
             "Direct folder assignment via PasteDb::create is not allowed; use TransactionOps::create_paste_with_folder",
@@ -201,6 +261,7 @@ impl PasteDb {
             let mut pastes = write_txn.open_table(PASTES)?;
             let mut metas = write_txn.open_table(PASTES_META)?;
             let mut updated = write_txn.open_table(PASTES_BY_UPDATED)?;
+            let mut filters = FilterIndexTables::open(&write_txn)?;
 
             if pastes.get(paste.id.as_str())?.is_some() {
                 return Err(AppError::StorageMessage(format!(
@@ -212,6 +273,7 @@ impl PasteDb {
             pastes.insert(paste.id.as_str(), encoded_paste.as_slice())?;
             metas.insert(paste.id.as_str(), encoded_meta.as_slice())?;
             updated.insert((recency_key, paste.id.as_str()), ())?;
+            filters.insert(paste.id.as_str(), &FilterIndexKeys::from_paste(paste))?;
         }
         write_txn.commit()?;
         Ok(())
@@ -224,7 +286,9 @@ impl PasteDb {
     ///
     /// # Errors
     /// Returns an error when storage access or deserialization fails.
+    #[tracing::instrument(level = "debug", name = "paste_db.get", skip_all, fields(id = %id))]
     pub fn get(&self, id: &str) -> Result<Option<Paste>, AppError> {
+        let _slow = SlowQueryTimer::start("paste_db.get").with_keys(1);
         let read_txn = self.db.begin_read()?;
         let pastes = read_txn.open_table(PASTES)?;
         match pastes.get(id)? {
@@ -233,6 +297,22 @@ impl PasteDb {
         }
     }
 
+    /// Fetch a paste's metadata row without loading its content.
+    ///
+    /// # Returns
+    /// `Ok(Some(meta))` when found, `Ok(None)` when missing.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or deserialization fails.
+    pub fn get_meta(&self, id: &str) -> Result<Option<PasteMeta>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let metas = read_txn.open_table(PASTES_META)?;
+        match metas.get(id)? {
+            Some(value) => Ok(Some(deserialize_meta(value.value())?)),
+            None => Ok(None),
+        }
+    }
+
     /// Update a paste by id.
     ///
     /// This API only supports non-folder metadata/content updates. Use
@@ -272,18 +352,28 @@ impl PasteDb {
         self.update_inner(id, Some(expected_folder_id), update)
     }
 
+    #[tracing::instrument(level = "debug", name = "paste_db.update", skip_all, fields(id = %id))]
     fn update_inner(
         &self,
         id: &str,
         expected_folder: Option<Option<&str>>,
         update: UpdatePasteRequest,
     ) -> Result<Option<Paste>, AppError> {
+        let _slow = SlowQueryTimer::start("paste_db.update").with_keys(1);
         Self::reject_direct_folder_operation(
             update.folder_id.is_some(),
             "Direct folder updates via PasteDb::update are not allowed; use TransactionOps::move_paste_between_folders",
         )?;
         let version_interval_secs = self.version_interval_secs();
-        let write_txn = self.db.begin_write()?;
+        let durability = DurabilityState::lookup(&self.db);
+        let mut journal = durability.as_deref().map(JournalSlot::lock);
+        let write_txn = self
+            .db
+            .begin_journaled_write(journal.as_ref().is_some_and(JournalSlot::is_active))?;
+        let journal_rev = match journal.as_ref() {
+            Some(slot) => slot.begin(&write_txn)?,
+            None => None,
+        };
         let updated_paste = {
             let mut pastes = write_txn.open_table(PASTES)?;
             let mut metas = write_txn.open_table(PASTES_META)?;
@@ -297,6 +387,7 @@ impl PasteDb {
             let mut paste = deserialize_paste(old_guard.value())?;
             let old_folder = paste.folder_id.clone();
             let old_recency_key = reverse_timestamp_key(paste.updated_at);
+            let old_filter_keys = FilterIndexKeys::from_paste(&paste);
             drop(old_guard);
 
             if let Some(expected) = expected_folder {
@@ -311,7 +402,7 @@ impl PasteDb {
             let mut version_items = decode_version_meta_list(
                 versions_meta.get(id)?.as_ref().map(|value| value.value()),
             )?;
-            apply_update_request(&mut paste, &update);
+            apply_update_request(&mut paste, &update, &self.normalization);
             let content_changed = paste.content != old_content;
 
             if content_changed {
@@ -333,6 +424,12 @@ impl PasteDb {
                     let encoded_versions = encode_version_meta_list(&version_items)?;
                     versions_meta.insert(id, encoded_versions.as_slice())?;
                 }
+                crate::db::annotations::reanchor_paste_annotations(
+                    &write_txn,
+                    id,
+                    old_content.as_str(),
+                    paste.content.as_str(),
+                )?;
             }
 
             let encoded_paste = bincode::serialize(&paste)?;
@@ -346,11 +443,30 @@ impl PasteDb {
                 let _ = updated.remove((old_recency_key, id))?;
             }
             updated.insert((new_recency_key, id), ())?;
+            FilterIndexTables::open(&write_txn)?.replace(
+                id,
+                &old_filter_keys,
+                &FilterIndexKeys::from_paste(&paste),
+            )?;
+            if let Some(folder_id) = paste.folder_id.as_deref() {
+                let mut folders = write_txn.open_table(FOLDERS)?;
+                apply_folder_stats_transition(
+                    &mut folders,
+                    Some((folder_id, old_content.len())),
+                    Some((folder_id, paste.content.len())),
+                    paste.updated_at,
+                )?;
+            }
 
             Some(paste)
         };
 
         write_txn.commit()?;
+        if let (Some(slot), Some(rev), Some(paste)) =
+            (journal.as_mut(), journal_rev, updated_paste.as_ref())
+        {
+            slot.record(&self.db, rev, paste);
+        }
         Ok(updated_paste)
     }
 
@@ -364,7 +480,9 @@ impl PasteDb {
     ///
     /// # Errors
     /// Returns an error when storage access or deserialization fails.
+    #[tracing::instrument(level = "debug", name = "paste_db.delete", skip_all, fields(id = %id))]
     pub fn delete_and_return(&self, id: &str) -> Result<Option<Paste>, AppError> {
+        let _slow = SlowQueryTimer::start("paste_db.delete").with_keys(1);
         let write_txn = self.db.begin_write()?;
         let deleted = {
             let mut pastes = write_txn.open_table(PASTES)?;
@@ -372,6 +490,8 @@ impl PasteDb {
             let mut updated = write_txn.open_table(PASTES_BY_UPDATED)?;
             let mut versions_meta = write_txn.open_table(PASTE_VERSIONS_META)?;
             let mut versions_content = write_txn.open_table(PASTE_VERSIONS_CONTENT)?;
+            let mut slugs = write_txn.open_table(PASTE_SLUGS)?;
+            let mut access = write_txn.open_table(PASTE_ACCESS)?;
 
             let Some(old_guard) = pastes.get(id)? else {
                 return Ok(None);
@@ -385,8 +505,14 @@ impl PasteDb {
             drop(old_guard);
 
             let _ = updated.remove((recency_key, id))?;
+            FilterIndexTables::open(&write_txn)?
+                .remove(id, &FilterIndexKeys::from_paste(&paste))?;
             let _ = pastes.remove(id)?;
             let _ = metas.remove(id)?;
+            let _ = access.remove(id)?;
+            if let Some(slug) = paste.slug.as_deref() {
+                let _ = slugs.remove(slug)?;
+            }
             let version_items = decode_version_meta_list(
                 versions_meta.get(id)?.as_ref().map(|value| value.value()),
             )?;
@@ -394,6 +520,10 @@ impl PasteDb {
                 let _ = versions_content.remove((id, version.version_id_ms))?;
             }
             let _ = versions_meta.remove(id)?;
+            crate::db::comments::remove_paste_comments(&write_txn, id)?;
+            crate::db::annotations::remove_paste_annotations(&write_txn, id)?;
+            crate::db::attachments::remove_paste_attachments(&write_txn, id)?;
+            crate::db::url_sources::remove_paste_url_source(&write_txn, id)?;
             Some(paste)
         };
 
@@ -549,6 +679,8 @@ impl PasteDb {
             };
             let mut paste = deserialize_paste(paste_guard.value())?;
             let old_recency_key = reverse_timestamp_key(paste.updated_at);
+            let old_filter_keys = FilterIndexKeys::from_paste(&paste);
+            let old_content_len = paste.content.len();
             drop(paste_guard);
 
             let mut version_items = decode_version_meta_list(
@@ -576,6 +708,12 @@ impl PasteDb {
             // `apply_update_request` here is incorrect because it can re-run
             // auto-detection and silently mutate `language` / `language_is_manual`
             // instead of replaying the persisted historical state.
+            crate::db::annotations::reanchor_paste_annotations(
+                &write_txn,
+                paste_id,
+                paste.content.as_str(),
+                target_content.as_str(),
+            )?;
             paste.content = target_content;
             paste.is_markdown = is_markdown_content(&paste.content);
             paste.language = target_meta.language.clone();
@@ -589,6 +727,20 @@ impl PasteDb {
             metas.insert(paste_id, encoded_meta.as_slice())?;
             let _ = updated.remove((old_recency_key, paste_id))?;
             updated.insert((new_recency_key, paste_id), ())?;
+            FilterIndexTables::open(&write_txn)?.replace(
+                paste_id,
+                &old_filter_keys,
+                &FilterIndexKeys::from_paste(&paste),
+            )?;
+            if let Some(folder_id) = paste.folder_id.as_deref() {
+                let mut folders = write_txn.open_table(FOLDERS)?;
+                apply_folder_stats_transition(
+                    &mut folders,
+                    Some((folder_id, old_content_len)),
+                    Some((folder_id, paste.content.len())),
+                    paste.updated_at,
+                )?;
+            }
 
             let mut removed_versions = Vec::new();
             version_items.retain(|item| {
@@ -643,7 +795,7 @@ impl PasteDb {
             .map(str::trim)
             .filter(|value| !value.is_empty())
             .map(ToString::to_string)
-            .unwrap_or_else(naming::generate_name);
+            .unwrap_or_else(|| self.titles.default.name_for(snapshot.content.as_str()));
         let duplicate = Paste::new_with_language(
             snapshot.content,
             duplicate_name,
@@ -658,41 +810,79 @@ impl PasteDb {
     ///
     /// # Arguments
     /// - `limit`: Maximum rows to return.
-    /// - `folder_id`: Optional folder filter.
+    /// - `folder_id`: Optional folder filter, served from the folder index.
     ///
     /// # Returns
     /// Up to `limit` canonical rows in descending recency order.
     ///
     /// # Errors
     /// Returns an error when storage access or deserialization fails.
+    #[tracing::instrument(level = "debug", name = "paste_db.list", skip_all, fields(limit = limit))]
     pub fn list(&self, limit: usize, folder_id: Option<String>) -> Result<Vec<Paste>, AppError> {
+        let mut slow = SlowQueryTimer::start("paste_db.list");
         if limit == 0 {
             return Ok(Vec::new());
         }
 
         let read_txn = self.db.begin_read()?;
-        let updated_table = read_txn.open_table(PASTES_BY_UPDATED)?;
         let pastes_table = read_txn.open_table(PASTES)?;
         let mut pastes = Vec::new();
-
-        for item in updated_table.iter()? {
-            let (key, _) = item?;
-            let (_, paste_id) = key.value();
+        let mut visit = |paste_id: &str| -> Result<bool, AppError> {
+            slow.keys += 1;
             let Some(paste_guard) = pastes_table.get(paste_id)? else {
-                continue;
+                return Ok(true);
             };
             let paste = deserialize_paste(paste_guard.value())?;
-            if let Some(ref fid) = folder_id {
-                if paste.folder_id.as_ref() != Some(fid) {
-                    continue;
+            if folder_id
+                .as_deref()
+                .is_none_or(|fid| paste.folder_id.as_deref() == Some(fid))
+            {
+                pastes.push(paste);
+            }
+            Ok(pastes.len() < limit)
+        };
+
+        if let Some(folder_id) = folder_id.as_deref() {
+            scan_filter_index(&read_txn, PASTES_BY_FOLDER, folder_id, visit)?;
+        } else {
+            let updated_table = read_txn.open_table(PASTES_BY_UPDATED)?;
+            for item in updated_table.iter()? {
+                let (key, _) = item?;
+                if !visit(key.value().1)? {
+                    break;
                 }
             }
-            pastes.push(paste);
-            if pastes.len() >= limit {
+        }
+
+        Ok(pastes)
+    }
+
+    /// List canonical paste rows updated at or after `since`, newest first.
+    ///
+    /// Walks the recency index and stops at the first older key, so the cost
+    /// scales with the size of the window rather than the store.
+    ///
+    /// # Returns
+    /// Every paste whose `updated_at` is not earlier than `since`.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or deserialization fails.
+    pub fn list_updated_since(&self, since: DateTime<Utc>) -> Result<Vec<Paste>, AppError> {
+        let cutoff = reverse_timestamp_key(since);
+        let read_txn = self.db.begin_read()?;
+        let pastes_table = read_txn.open_table(PASTES)?;
+        let updated_table = read_txn.open_table(PASTES_BY_UPDATED)?;
+        let mut pastes = Vec::new();
+        for item in updated_table.iter()? {
+            let (key, _) = item?;
+            let (recency, paste_id) = key.value();
+            if recency > cutoff {
                 break;
             }
+            if let Some(paste_guard) = pastes_table.get(paste_id)? {
+                pastes.push(deserialize_paste(paste_guard.value())?);
+            }
         }
-
         Ok(pastes)
     }
 
@@ -762,11 +952,57 @@ impl PasteDb {
         Ok(())
     }
 
-    /// List paste metadata using the recency index.
+    /// Scan stored metadata rows without loading paste content.
+    ///
+    /// # Returns
+    /// `Ok(())` when every row was visited, in id order.
+    ///
+    /// # Errors
+    /// Returns an error when storage access, deserialization, or `on_meta`
+    /// fails.
+    pub fn scan_meta<F>(&self, mut on_meta: F) -> Result<(), AppError>
+    where
+        F: FnMut(PasteMeta) -> Result<(), AppError>,
+    {
+        let mut slow = SlowQueryTimer::start("paste_db.scan_meta");
+        let read_txn = self.db.begin_read()?;
+        let meta_table = read_txn.open_table(PASTES_META)?;
+        for item in meta_table.iter()? {
+            slow.keys += 1;
+            let (_, value) = item?;
+            on_meta(deserialize_meta(value.value())?)?;
+        }
+        Ok(())
+    }
+
+    /// Ids of pastes whose `expires_at` is at or before `now`.
+    ///
+    /// # Returns
+    /// Expired paste ids in key order.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or deserialization fails.
+    pub fn expired_ids(&self, now: DateTime<Utc>) -> Result<Vec<String>, AppError> {
+        let mut slow = SlowQueryTimer::start("paste_db.expired_ids");
+        let read_txn = self.db.begin_read()?;
+        let pastes_table = read_txn.open_table(PASTES)?;
+        let mut ids = Vec::new();
+        for item in pastes_table.iter()? {
+            slow.keys += 1;
+            let (key, value) = item?;
+            let paste = deserialize_paste(value.value())?;
+            if paste.expires_at.is_some_and(|expires_at| expires_at <= now) {
+                ids.push(key.value().to_string());
+            }
+        }
+        Ok(ids)
+    }
+
+    /// List paste metadata in descending recency order.
     ///
     /// # Arguments
     /// - `limit`: Maximum rows to return.
-    /// - `folder_id`: Optional folder filter.
+    /// - `folder_id`: Optional folder filter, served from the folder index.
     ///
     /// # Returns
     /// Up to `limit` metadata rows in index order.
@@ -778,83 +1014,74 @@ impl PasteDb {
         limit: usize,
         folder_id: Option<String>,
     ) -> Result<Vec<PasteMeta>, AppError> {
-        if limit == 0 {
-            return Ok(Vec::new());
-        }
-
-        let read_txn = self.db.begin_read()?;
-        let updated_table = read_txn.open_table(PASTES_BY_UPDATED)?;
-        let meta_table = read_txn.open_table(PASTES_META)?;
-
-        let mut metas = Vec::with_capacity(limit);
-        for item in updated_table.iter()? {
-            let (key, _) = item?;
-            let (_, paste_id) = key.value();
-            let Some(meta_guard) = meta_table.get(paste_id)? else {
-                continue;
-            };
-            let meta = deserialize_meta(meta_guard.value())?;
-            if let Some(ref fid) = folder_id {
-                if meta.folder_id.as_ref() != Some(fid) {
-                    continue;
-                }
-            }
-            metas.push(meta);
-            if metas.len() >= limit {
-                break;
-            }
-        }
-
-        Ok(metas)
+        self.list_meta_filtered(limit, folder_id.as_deref(), None, None)
     }
 
     /// Search canonical paste data and return ranked metadata rows.
     ///
+    /// Names and tags are scored from metadata rows. Content is only loaded
+    /// for pastes the trigram index cannot rule out; queries shorter than
+    /// three characters check every body.
+    ///
     /// # Arguments
     /// - `query`: Search query string.
     /// - `limit`: Maximum rows to return.
-    /// - `folder_id`: Optional folder filter.
-    /// - `language`: Optional language filter.
+    /// - `filters`: Folder, language, tag, and updated-time filters.
     ///
     /// # Returns
     /// Ranked metadata matches (name/tags/content scoring).
     ///
     /// # Errors
     /// Returns an error when storage access or deserialization fails.
+    #[tracing::instrument(level = "debug", name = "paste_db.search", skip_all, fields(limit = limit))]
     pub fn search(
         &self,
         query: &str,
         limit: usize,
-        folder_id: Option<String>,
-        language: Option<String>,
+        filters: &SearchFilters,
     ) -> Result<Vec<PasteMeta>, AppError> {
+        let mut slow = SlowQueryTimer::start("paste_db.search");
         let query = query.trim();
         if query.is_empty() || limit == 0 {
             return Ok(Vec::new());
         }
 
         let query_lower = query.to_lowercase();
-        let language_filter = normalize_language_filter(language.as_deref());
+        let filters = filters.normalized();
         let read_txn = self.db.begin_read()?;
+        let candidates = content_index::content_candidates(&read_txn, &query_lower)?;
+        let meta_table = read_txn.open_table(PASTES_META)?;
         let pastes_table = read_txn.open_table(PASTES)?;
         let mut results: Vec<(i32, DateTime<Utc>, PasteMeta)> = Vec::new();
 
-        for item in pastes_table.iter()? {
+        for item in meta_table.iter()? {
+            slow.keys += 1;
             let (_, value) = item?;
-            let paste = deserialize_paste(value.value())?;
+            let meta = deserialize_meta(value.value())?;
 
-            if let Some(ref fid) = folder_id {
-                if paste.folder_id.as_ref() != Some(fid) {
-                    continue;
-                }
-            }
-            if !language_matches_filter(paste.language.as_deref(), language_filter.as_deref()) {
+            if !search_filters_match(
+                &filters,
+                meta.folder_id.as_deref(),
+                meta.language.as_deref(),
+                &meta.tags,
+                meta.updated_at,
+            ) {
                 continue;
             }
 
-            let score = score_paste_match(&paste, &query_lower);
+            let mut score = score_paste_fields(&meta, &query_lower);
+            let content_may_match = candidates
+                .as_ref()
+                .is_none_or(|ids| ids.contains(meta.id.as_str()));
+            if content_may_match {
+                if let Some(row) = pastes_table.get(meta.id.as_str())? {
+                    let paste = deserialize_paste(row.value())?;
+                    if content_matches_query(&paste.content, &query_lower) {
+                        score += 1;
+                    }
+                }
+            }
             if score > 0 {
-                let meta = PasteMeta::from(&paste);
                 push_ranked_meta_top_k(&mut results, (score, meta.updated_at, meta), limit);
             }
         }
@@ -867,8 +1094,7 @@ impl PasteDb {
     /// # Arguments
     /// - `query`: Search query string.
     /// - `limit`: Maximum rows to return.
-    /// - `folder_id`: Optional folder filter.
-    /// - `language`: Optional language filter.
+    /// - `filters`: Folder, language, tag, and updated-time filters.
     ///
     /// # Returns
     /// Ranked metadata matches (name/tags/language scoring).
@@ -879,24 +1105,56 @@ impl PasteDb {
         &self,
         query: &str,
         limit: usize,
-        folder_id: Option<String>,
-        language: Option<String>,
+        filters: &SearchFilters,
     ) -> Result<Vec<PasteMeta>, AppError> {
+        self.search_meta_until(query, limit, filters, &|| false)
+            .map(Option::unwrap_or_default)
+    }
+
+    /// [`Self::search_meta`] that stops scanning once `cancelled` reports `true`.
+    ///
+    /// `cancelled` is polled every [`SEARCH_CANCEL_CHECK_INTERVAL`] rows so
+    /// superseded searches stop touching storage early.
+    ///
+    /// # Returns
+    /// `Ok(Some(rows))` when the scan finished, `Ok(None)` when it was abandoned.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or deserialization fails.
+    #[tracing::instrument(level = "debug", name = "paste_db.search_meta", skip_all, fields(limit = limit))]
+    pub fn search_meta_until(
+        &self,
+        query: &str,
+        limit: usize,
+        filters: &SearchFilters,
+        cancelled: &dyn Fn() -> bool,
+    ) -> Result<Option<Vec<PasteMeta>>, AppError> {
+        let mut slow = SlowQueryTimer::start("paste_db.search_meta");
         let query = query.trim();
         if query.is_empty() || limit == 0 {
-            return Ok(Vec::new());
+            return Ok(Some(Vec::new()));
         }
 
         let query_lower = query.to_lowercase();
-        let language_filter = normalize_language_filter(language.as_deref());
+        let filters = filters.normalized();
         let read_txn = self.db.begin_read()?;
         let meta_table = read_txn.open_table(PASTES_META)?;
         let mut results: Vec<(i32, DateTime<Utc>, PasteMeta)> = Vec::new();
 
-        for item in meta_table.iter()? {
+        for (scanned, item) in meta_table.iter()?.enumerate() {
+            if scanned % SEARCH_CANCEL_CHECK_INTERVAL == 0 && cancelled() {
+                return Ok(None);
+            }
+            slow.keys += 1;
             let (_, value) = item?;
             let meta = deserialize_meta(value.value())?;
-            if !meta_matches_filters(&meta, folder_id.as_deref(), language_filter.as_deref()) {
+            if !search_filters_match(
+                &filters,
+                meta.folder_id.as_deref(),
+                meta.language.as_deref(),
+                &meta.tags,
+                meta.updated_at,
+            ) {
                 continue;
             }
             let score = score_meta_match(&meta, &query_lower);
@@ -905,7 +1163,7 @@ impl PasteDb {
             }
         }
 
-        Ok(finalize_meta_search_results(results, limit))
+        Ok(Some(finalize_meta_search_results(results, limit)))
     }
 }
 
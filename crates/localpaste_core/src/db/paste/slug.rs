@@ -0,0 +1,166 @@
+//! User-chosen slugs: a unique, stable alias per paste kept in `paste_slugs`.
+//!
+//! The slug table maps slug -> paste id and is authoritative for lookups; the
+//! paste row carries the same slug so reads and exports can show it. Both are
+//! written in one transaction.
+
+use super::{deserialize_paste, PasteDb};
+use crate::models::paste::Paste;
+use crate::naming::slug::normalize_slug;
+use crate::{
+    db::tables::{PASTES, PASTE_SLUGS},
+    error::AppError,
+};
+use redb::{ReadTransaction, ReadableDatabase, ReadableTable};
+
+impl PasteDb {
+    /// Assign, rename, or clear a paste's slug.
+    ///
+    /// Renaming releases the previous slug. Content, metadata, and
+    /// `updated_at` are left untouched.
+    ///
+    /// # Arguments
+    /// - `id`: Paste id.
+    /// - `slug`: New slug (normalized via [`normalize_slug`]), or `None` to clear.
+    ///
+    /// # Returns
+    /// `Ok(Some(paste))` with the new slug applied, `Ok(None)` when the paste is missing.
+    ///
+    /// # Errors
+    /// Returns [`AppError::BadRequest`] for invalid slugs,
+    /// [`AppError::Conflict`] when another paste already owns the slug, and
+    /// storage errors otherwise.
+    pub fn set_slug(&self, id: &str, slug: Option<&str>) -> Result<Option<Paste>, AppError> {
+        let slug = slug.map(normalize_slug).transpose()?;
+        let write_txn = self.db.begin_write()?;
+        let paste = {
+            let mut pastes = write_txn.open_table(PASTES)?;
+            let mut slugs = write_txn.open_table(PASTE_SLUGS)?;
+            let Some(guard) = pastes.get(id)? else {
+                return Ok(None);
+            };
+            let mut paste = deserialize_paste(guard.value())?;
+            drop(guard);
+            if paste.slug == slug {
+                return Ok(Some(paste));
+            }
+
+            if let Some(new_slug) = slug.as_deref() {
+                let owner = slugs.get(new_slug)?.map(|guard| guard.value().to_string());
+                if let Some(owner) = owner.filter(|owner| owner != id) {
+                    if pastes.get(owner.as_str())?.is_some() {
+                        return Err(AppError::Conflict(format!(
+                            "Slug '{}' is already used by paste '{}'",
+                            new_slug, owner
+                        )));
+                    }
+                }
+            }
+            if let Some(old_slug) = paste.slug.as_deref() {
+                let _ = slugs.remove(old_slug)?;
+            }
+            if let Some(new_slug) = slug.as_deref() {
+                slugs.insert(new_slug, id)?;
+            }
+            paste.slug = slug;
+            let encoded = bincode::serialize(&paste)?;
+            pastes.insert(id, encoded.as_slice())?;
+            paste
+        };
+        write_txn.commit()?;
+        Ok(Some(paste))
+    }
+
+    /// Fetch a paste by slug.
+    ///
+    /// The slug is normalized first, so lookups are case-insensitive.
+    ///
+    /// # Returns
+    /// `Ok(Some(paste))` when a paste currently owns the slug, otherwise `Ok(None)`.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or deserialization fails.
+    pub fn get_by_slug(&self, slug: &str) -> Result<Option<Paste>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        Self::slug_owner(&read_txn, slug)
+    }
+
+    /// Look up the paste owning `slug` inside an existing read transaction.
+    ///
+    /// Rows whose stored slug no longer matches the index are ignored.
+    pub(super) fn slug_owner(
+        read_txn: &ReadTransaction,
+        slug: &str,
+    ) -> Result<Option<Paste>, AppError> {
+        let Ok(slug) = normalize_slug(slug) else {
+            return Ok(None);
+        };
+        let slugs = read_txn.open_table(PASTE_SLUGS)?;
+        let Some(id_guard) = slugs.get(slug.as_str())? else {
+            return Ok(None);
+        };
+        let pastes = read_txn.open_table(PASTES)?;
+        let Some(paste_guard) = pastes.get(id_guard.value())? else {
+            return Ok(None);
+        };
+        let paste = deserialize_paste(paste_guard.value())?;
+        Ok((paste.slug.as_deref() == Some(slug.as_str())).then_some(paste))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::setup_temp_db;
+
+    #[test]
+    fn slugs_are_unique_renamable_and_released_on_delete() {
+        let (db, _temp) = setup_temp_db();
+        let first = Paste::new("server {}".to_string(), "nginx".to_string());
+        let second = Paste::new("other".to_string(), "other".to_string());
+        db.pastes.create(&first).expect("create");
+        db.pastes.create(&second).expect("create");
+
+        let updated = db
+            .pastes
+            .set_slug(&first.id, Some("Nginx-Config"))
+            .expect("set")
+            .expect("paste");
+        assert_eq!(updated.slug.as_deref(), Some("nginx-config"));
+        assert_eq!(updated.updated_at, first.updated_at);
+        let found = db.pastes.get_by_slug("NGINX-config").expect("get");
+        assert_eq!(found.map(|paste| paste.id), Some(first.id.clone()));
+        assert_eq!(
+            db.pastes.resolve_id("nginx-config").expect("resolve"),
+            first.id
+        );
+        assert!(matches!(
+            db.pastes.set_slug(&second.id, Some("nginx-config")),
+            Err(AppError::Conflict(_))
+        ));
+        assert!(db
+            .pastes
+            .set_slug("missing", Some("x"))
+            .expect("missing")
+            .is_none());
+
+        db.pastes
+            .set_slug(&first.id, Some("nginx"))
+            .expect("rename");
+        assert!(db
+            .pastes
+            .get_by_slug("nginx-config")
+            .expect("old")
+            .is_none());
+        db.pastes
+            .set_slug(&second.id, Some("nginx-config"))
+            .expect("reuse released slug");
+
+        db.pastes.delete(&first.id).expect("delete");
+        db.pastes
+            .set_slug(&second.id, Some("nginx"))
+            .expect("reuse deleted paste slug");
+        db.pastes.set_slug(&second.id, None).expect("clear");
+        assert!(db.pastes.get_by_slug("nginx").expect("cleared").is_none());
+    }
+}
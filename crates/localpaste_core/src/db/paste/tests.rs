@@ -69,6 +69,7 @@ fn diff_resolution_uses_one_read_snapshot_for_both_refs() {
                 language_is_manual: None,
                 folder_id: None,
                 tags: None,
+                expires_in: None,
             },
         )
         .expect("update")
@@ -0,0 +1,230 @@
+//! Per-client history of recently opened pastes.
+//!
+//! Rows are keyed by `(client, paste_id)` so reopening a paste only moves its
+//! timestamp, and one range scan reads a client's whole history. Rows for
+//! deleted pastes are skipped on read rather than removed with the paste.
+
+use crate::{
+    db::tables::{PASTES, RECENT_OPENS},
+    error::AppError,
+    models::recent::{
+        RecentOpen, DEFAULT_RECENT_CLIENT, MAX_RECENT_CLIENT_CHARS, MAX_RECENT_PER_CLIENT,
+    },
+};
+use chrono::{DateTime, Utc};
+use redb::{ReadableDatabase, ReadableTable};
+use std::sync::Arc;
+
+/// Trim a client label to its stored form, capped at
+/// [`MAX_RECENT_CLIENT_CHARS`]; blank falls back to [`DEFAULT_RECENT_CLIENT`].
+pub fn normalize_client(client: &str) -> String {
+    let client: String = client
+        .trim()
+        .chars()
+        .take(MAX_RECENT_CLIENT_CHARS)
+        .collect();
+    if client.is_empty() {
+        DEFAULT_RECENT_CLIENT.to_string()
+    } else {
+        client
+    }
+}
+
+/// Accessor for the recent-opens table.
+pub struct RecentDb {
+    db: Arc<redb::Database>,
+}
+
+impl RecentDb {
+    /// Initialize the recent-opens table if it does not exist yet.
+    ///
+    /// # Returns
+    /// A new [`RecentDb`] accessor bound to `db`.
+    ///
+    /// # Errors
+    /// Returns an error when redb transaction/table initialization fails.
+    pub fn new(db: Arc<redb::Database>) -> Result<Self, AppError> {
+        let write_txn = db.begin_write()?;
+        write_txn.open_table(RECENT_OPENS)?;
+        write_txn.commit()?;
+        Ok(Self { db })
+    }
+
+    /// Record that `client` opened `paste_id` now.
+    ///
+    /// Keeps the newest [`MAX_RECENT_PER_CLIENT`] pastes for the client.
+    ///
+    /// # Errors
+    /// Returns an error when storage fails.
+    pub fn record(&self, client: &str, paste_id: &str) -> Result<(), AppError> {
+        self.record_at(client, paste_id, Utc::now())
+    }
+
+    fn record_at(
+        &self,
+        client: &str,
+        paste_id: &str,
+        opened_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let client = normalize_client(client);
+        let millis = u64::try_from(opened_at.timestamp_millis()).unwrap_or(0);
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(RECENT_OPENS)?;
+            table.insert((client.as_str(), paste_id), millis)?;
+            let mut rows = client_rows(&table, client.as_str())?;
+            if rows.len() > MAX_RECENT_PER_CLIENT {
+                rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                for (stale_id, _) in rows.split_off(MAX_RECENT_PER_CLIENT) {
+                    table.remove((client.as_str(), stale_id.as_str()))?;
+                }
+            }
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// List recently opened pastes that still exist, newest first.
+    ///
+    /// # Arguments
+    /// - `client`: Only this client's history; `None` merges every client and
+    ///   keeps each paste's latest open.
+    /// - `limit`: Maximum rows returned.
+    ///
+    /// # Errors
+    /// Returns an error when storage access fails.
+    pub fn list(&self, client: Option<&str>, limit: usize) -> Result<Vec<RecentOpen>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(RECENT_OPENS)?;
+        let pastes = read_txn.open_table(PASTES)?;
+        let mut items: Vec<RecentOpen> = Vec::new();
+        let rows: Box<dyn Iterator<Item = _>> = match client {
+            Some(client) => Box::new(table.range((client, "")..)?),
+            None => Box::new(table.iter()?),
+        };
+        for entry in rows {
+            let (key, value) = entry?;
+            let (row_client, paste_id) = key.value();
+            if client.is_some_and(|client| client != row_client) {
+                break;
+            }
+            if pastes.get(paste_id)?.is_none() {
+                continue;
+            }
+            let opened_at = DateTime::from_timestamp_millis(value.value() as i64)
+                .unwrap_or(DateTime::UNIX_EPOCH);
+            match items.iter_mut().find(|item| item.paste_id == paste_id) {
+                Some(existing) if existing.opened_at < opened_at => {
+                    existing.client = row_client.to_string();
+                    existing.opened_at = opened_at;
+                }
+                Some(_) => {}
+                None => items.push(RecentOpen {
+                    client: row_client.to_string(),
+                    paste_id: paste_id.to_string(),
+                    opened_at,
+                }),
+            }
+        }
+        items.sort_by(|a, b| {
+            b.opened_at
+                .cmp(&a.opened_at)
+                .then_with(|| a.paste_id.cmp(&b.paste_id))
+        });
+        items.truncate(limit);
+        Ok(items)
+    }
+}
+
+fn client_rows(
+    table: &redb::Table<(&str, &str), u64>,
+    client: &str,
+) -> Result<Vec<(String, u64)>, AppError> {
+    let mut rows = Vec::new();
+    for entry in table.range((client, "")..)? {
+        let (key, value) = entry?;
+        let (row_client, paste_id) = key.value();
+        if row_client != client {
+            break;
+        }
+        rows.push((paste_id.to_string(), value.value()));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::paste::Paste;
+    use crate::test_support::setup_temp_db;
+    use crate::Database;
+    use chrono::Duration;
+
+    fn create_paste(db: &Database, content: &str) -> String {
+        let paste = Paste::new(content.to_string(), "recent".to_string());
+        db.pastes.create(&paste).expect("paste");
+        paste.id
+    }
+
+    #[test]
+    fn list_merges_clients_keeps_latest_open_and_skips_deleted() {
+        let (db, _temp) = setup_temp_db();
+        let first = create_paste(&db, "first");
+        let second = create_paste(&db, "second");
+        let gone = create_paste(&db, "gone");
+        let start = Utc::now();
+
+        db.recent.record_at("gui", &first, start).expect("record");
+        db.recent
+            .record_at("cli", &second, start + Duration::seconds(1))
+            .expect("record");
+        db.recent
+            .record_at(" cli ", &first, start + Duration::seconds(2))
+            .expect("record");
+        db.recent
+            .record_at("gui", &gone, start + Duration::seconds(3))
+            .expect("record");
+        assert!(db.pastes.delete(&gone).expect("delete"));
+
+        let merged = db.recent.list(None, 10).expect("list");
+        let merged: Vec<_> = merged
+            .iter()
+            .map(|row| (row.client.as_str(), row.paste_id.as_str()))
+            .collect();
+        assert_eq!(
+            merged,
+            vec![("cli", first.as_str()), ("cli", second.as_str())]
+        );
+
+        let gui = db.recent.list(Some("gui"), 10).expect("list");
+        assert_eq!(gui.len(), 1);
+        assert_eq!(gui[0].paste_id, first);
+        assert_eq!(
+            gui[0].opened_at.timestamp_millis(),
+            start.timestamp_millis()
+        );
+        assert!(db.recent.list(Some("gu"), 10).expect("list").is_empty());
+    }
+
+    #[test]
+    fn record_keeps_the_newest_opens_per_client() {
+        let (db, _temp) = setup_temp_db();
+        let start = Utc::now();
+        for offset in 0..(MAX_RECENT_PER_CLIENT + 2) {
+            let id = create_paste(&db, &format!("paste {}", offset));
+            db.recent
+                .record_at("", &id, start + Duration::seconds(offset as i64))
+                .expect("record");
+        }
+
+        let rows = db
+            .recent
+            .list(Some(DEFAULT_RECENT_CLIENT), usize::MAX)
+            .expect("list");
+        assert_eq!(rows.len(), MAX_RECENT_PER_CLIENT);
+        assert_eq!(
+            rows.last().map(|row| row.opened_at.timestamp_millis()),
+            Some((start + Duration::seconds(2)).timestamp_millis())
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! Slow database operation logging.
+//!
+//! Storage methods start a [`SlowQueryTimer`] and bump its key count as they
+//! touch rows; when the timer drops after the configured threshold a warning
+//! names the operation, the keys visited, and the duration. Warnings are
+//! throttled per operation so a slow list polled every frame cannot flood the
+//! log; the next warning reports how many were suppressed.
+
+use crate::config::slow_query_threshold_from_env_or_default;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Minimum gap between two warnings for the same operation.
+const SLOW_QUERY_LOG_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy)]
+struct ThrottleState {
+    last_logged: Instant,
+    suppressed: u64,
+}
+
+/// Threshold plus per-operation throttle state.
+#[derive(Debug)]
+pub(crate) struct SlowQueryLog {
+    threshold: Option<Duration>,
+    throttle: Mutex<HashMap<&'static str, ThrottleState>>,
+}
+
+impl SlowQueryLog {
+    pub(crate) fn new(threshold: Option<Duration>) -> Self {
+        Self {
+            threshold,
+            throttle: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Decide whether a completed operation should be logged now.
+    ///
+    /// # Returns
+    /// `Some(suppressed)` with the number of slow calls skipped since the last
+    /// warning for `op`, or `None` when the call was fast or throttled.
+    fn should_log(&self, op: &'static str, elapsed: Duration, now: Instant) -> Option<u64> {
+        let threshold = self.threshold?;
+        if elapsed < threshold {
+            return None;
+        }
+        let mut throttle = self.throttle.lock().ok()?;
+        match throttle.get_mut(op) {
+            Some(state) if now.duration_since(state.last_logged) < SLOW_QUERY_LOG_INTERVAL => {
+                state.suppressed += 1;
+                None
+            }
+            Some(state) => {
+                let suppressed = std::mem::take(&mut state.suppressed);
+                state.last_logged = now;
+                Some(suppressed)
+            }
+            None => {
+                throttle.insert(
+                    op,
+                    ThrottleState {
+                        last_logged: now,
+                        suppressed: 0,
+                    },
+                );
+                Some(0)
+            }
+        }
+    }
+}
+
+fn slow_query_log() -> &'static SlowQueryLog {
+    static LOG: OnceLock<SlowQueryLog> = OnceLock::new();
+    LOG.get_or_init(|| SlowQueryLog::new(slow_query_threshold_from_env_or_default()))
+}
+
+/// Times one storage operation; logs on drop when it ran past the threshold.
+pub(crate) struct SlowQueryTimer {
+    op: &'static str,
+    started: Instant,
+    /// Rows or keys the operation visited.
+    pub(crate) keys: usize,
+}
+
+impl SlowQueryTimer {
+    pub(crate) fn start(op: &'static str) -> Self {
+        Self {
+            op,
+            started: Instant::now(),
+            keys: 0,
+        }
+    }
+
+    /// Sets the key count up front for single-row operations.
+    pub(crate) fn with_keys(mut self, keys: usize) -> Self {
+        self.keys = keys;
+        self
+    }
+}
+
+impl Drop for SlowQueryTimer {
+    fn drop(&mut self) {
+        let elapsed = self.started.elapsed();
+        let Some(suppressed) = slow_query_log().should_log(self.op, elapsed, Instant::now()) else {
+            return;
+        };
+        tracing::warn!(
+            op = self.op,
+            keys = self.keys,
+            duration_ms = elapsed.as_millis() as u64,
+            suppressed,
+            "slow database operation: {} took {} ms over {} keys{}",
+            self.op,
+            elapsed.as_millis(),
+            self.keys,
+            if suppressed > 0 {
+                format!(" ({} similar warnings suppressed)", suppressed)
+            } else {
+                String::new()
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_log_applies_threshold_and_per_operation_throttle() {
+        let log = SlowQueryLog::new(Some(Duration::from_millis(100)));
+        let start = Instant::now();
+        let slow = Duration::from_millis(150);
+        assert_eq!(
+            log.should_log("list", Duration::from_millis(50), start),
+            None
+        );
+        assert_eq!(log.should_log("list", slow, start), Some(0));
+        assert_eq!(log.should_log("list", slow, start), None);
+        assert_eq!(log.should_log("list", slow, start), None);
+        assert_eq!(log.should_log("get", slow, start), Some(0));
+        let later = start + SLOW_QUERY_LOG_INTERVAL;
+        assert_eq!(log.should_log("list", slow, later), Some(2));
+
+        let disabled = SlowQueryLog::new(None);
+        assert_eq!(
+            disabled.should_log("list", Duration::from_secs(60), start),
+            None
+        );
+    }
+}
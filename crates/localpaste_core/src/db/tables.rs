@@ -14,6 +14,8 @@ pub const PASTES_META_STATE: TableDefinition<&str, &[u8]> =
     TableDefinition::new("pastes_meta_state");
 /// Canonical folder rows (`Folder`, bincode-encoded).
 pub const FOLDERS: TableDefinition<&str, &[u8]> = TableDefinition::new("folders");
+/// Folder auto-organization rules keyed by rule id (`FolderRule`, bincode-encoded).
+pub const FOLDER_RULES: TableDefinition<&str, &[u8]> = TableDefinition::new("folder_rules");
 /// Per-paste version metadata (`Vec<VersionMeta>`, bincode-encoded).
 pub const PASTE_VERSIONS_META: TableDefinition<&str, &[u8]> =
     TableDefinition::new("paste_versions_meta");
@@ -21,8 +23,64 @@ pub const PASTE_VERSIONS_META: TableDefinition<&str, &[u8]> =
 pub const PASTE_VERSIONS_CONTENT: TableDefinition<(&str, u64), &[u8]> =
     TableDefinition::new("paste_versions_content");
 
+/// Unique paste slugs mapped to their paste id.
+pub const PASTE_SLUGS: TableDefinition<&str, &str> = TableDefinition::new("paste_slugs");
+
+/// Multi-user accounts keyed by user id (`User`, bincode-encoded).
+pub const USERS: TableDefinition<&str, &[u8]> = TableDefinition::new("users");
+/// BLAKE3 token hashes mapped to their user id.
+pub const USER_TOKENS: TableDefinition<&str, &str> = TableDefinition::new("user_tokens");
+/// Per-paste owner and visibility (`PasteAccess`, bincode-encoded).
+pub const PASTE_ACCESS: TableDefinition<&str, &[u8]> = TableDefinition::new("paste_access");
+
+/// Paste comments keyed by paste id then comment id (`Comment`, bincode-encoded).
+pub const PASTE_COMMENTS: TableDefinition<(&str, &str), &[u8]> =
+    TableDefinition::new("paste_comments");
+
+/// Line-anchored annotations keyed by paste id then annotation id
+/// (`Annotation`, bincode-encoded).
+pub const PASTE_ANNOTATIONS: TableDefinition<(&str, &str), &[u8]> =
+    TableDefinition::new("paste_annotations");
+
+/// Attachment metadata keyed by paste id then attachment id
+/// (`Attachment`, bincode-encoded).
+pub const PASTE_ATTACHMENTS: TableDefinition<(&str, &str), &[u8]> =
+    TableDefinition::new("paste_attachments");
+
+/// Raw attachment bytes keyed like [`PASTE_ATTACHMENTS`].
+pub const PASTE_ATTACHMENT_DATA: TableDefinition<(&str, &str), &[u8]> =
+    TableDefinition::new("paste_attachment_data");
+
+/// Source URL and refresh schedule keyed by paste id (`UrlSource`, bincode-encoded).
+pub const PASTE_URL_SOURCES: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("paste_url_sources");
+
+/// Crash-recovery drafts keyed by slot (`Draft`, bincode-encoded).
+pub const DRAFTS: TableDefinition<&str, &[u8]> = TableDefinition::new("drafts");
+
+/// Recently opened pastes keyed by client then paste id, valued by the
+/// open time in Unix millis.
+pub const RECENT_OPENS: TableDefinition<(&str, &str), u64> = TableDefinition::new("recent_opens");
+
 /// Recency index ordered by reverse-millis then id.
 pub const PASTES_BY_UPDATED: TableDefinition<(u64, &str), ()> =
     TableDefinition::new("pastes_by_updated");
+/// Creation-order index keyed by reverse created-millis then id.
+pub const PASTES_BY_CREATED: TableDefinition<(u64, &str), ()> =
+    TableDefinition::new("pastes_by_created");
+/// Folder list index ordered by folder id, reverse-millis, then paste id.
+pub const PASTES_BY_FOLDER: TableDefinition<(&str, u64, &str), ()> =
+    TableDefinition::new("pastes_by_folder");
+/// Tag list index ordered by normalized tag, reverse-millis, then paste id.
+pub const PASTES_BY_TAG: TableDefinition<(&str, u64, &str), ()> =
+    TableDefinition::new("pastes_by_tag");
+/// Language list index ordered by canonical language, reverse-millis, then paste id.
+pub const PASTES_BY_LANGUAGE: TableDefinition<(&str, u64, &str), ()> =
+    TableDefinition::new("pastes_by_language");
+/// Content trigram index keyed by packed trigram then paste id.
+pub const PASTES_BY_TRIGRAM: TableDefinition<(u64, &str), ()> =
+    TableDefinition::new("pastes_by_trigram");
 /// In-progress folder-delete markers.
 pub const FOLDERS_DELETING: TableDefinition<&str, ()> = TableDefinition::new("folders_deleting");
+/// Write-ahead journal state: the latest journaled revision (`u64`).
+pub const JOURNAL_STATE: TableDefinition<&str, u64> = TableDefinition::new("journal_state");
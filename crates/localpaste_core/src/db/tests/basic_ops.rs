@@ -1,8 +1,9 @@
 //! Basic database CRUD tests.
 
 use super::*;
-use crate::db::tables::{FOLDERS, PASTES, PASTE_VERSIONS_CONTENT, PASTE_VERSIONS_META};
+use crate::db::tables::{DRAFTS, FOLDERS, PASTES, PASTE_VERSIONS_CONTENT, PASTE_VERSIONS_META};
 use crate::env::{env_lock, EnvGuard};
+use crate::models::draft::{Draft, SCRATCHPAD_DRAFT_KEY};
 use redb::ReadableDatabase;
 use std::time::Duration;
 
@@ -19,6 +20,7 @@ fn update_request(
         language_is_manual,
         folder_id: None,
         tags: None,
+        expires_in: None,
     }
 }
 
@@ -847,3 +849,44 @@ fn corrupt_rows_surface_serialization_errors_without_removal() {
     assert!(folders.get("corrupt-folder").expect("folder get").is_some());
     assert!(pastes.get("corrupt-paste").expect("paste get").is_some());
 }
+
+#[test]
+fn test_drafts_upsert_list_newest_first_and_delete() {
+    let (db, _temp) = setup_test_db();
+    let older = Draft {
+        key: Draft::paste_key("p1"),
+        paste_id: Some("p1".to_string()),
+        content: "unsaved edit".to_string(),
+        updated_at: chrono::Utc::now() - chrono::Duration::seconds(30),
+    };
+    let newer = Draft {
+        key: SCRATCHPAD_DRAFT_KEY.to_string(),
+        paste_id: None,
+        content: "scratch".to_string(),
+        updated_at: chrono::Utc::now(),
+    };
+    db.drafts.put(&older).expect("put older");
+    db.drafts.put(&newer).expect("put newer");
+    let replaced = Draft {
+        content: "scratch v2".to_string(),
+        ..newer.clone()
+    };
+    db.drafts.put(&replaced).expect("replace");
+
+    let write_txn = db.db.begin_write().expect("begin write");
+    {
+        let mut drafts = write_txn.open_table(DRAFTS).expect("open drafts");
+        drafts
+            .insert("corrupt", b"not-a-draft".as_slice())
+            .expect("insert corrupt draft");
+    }
+    write_txn.commit().expect("commit");
+
+    assert_eq!(db.drafts.list().expect("list"), vec![replaced, older]);
+    assert!(db.drafts.delete(SCRATCHPAD_DRAFT_KEY).expect("delete"));
+    assert!(!db
+        .drafts
+        .delete(SCRATCHPAD_DRAFT_KEY)
+        .expect("delete again"));
+    assert_eq!(db.drafts.list().expect("list").len(), 1);
+}
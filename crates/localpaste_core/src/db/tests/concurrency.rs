@@ -39,6 +39,7 @@ fn concurrent_moves_keep_folder_counts_consistent() {
             language_is_manual: None,
             folder_id: Some(folder_for_a.clone()),
             tags: None,
+            expires_in: None,
         };
         TransactionOps::move_paste_between_folders(
             &worker_a,
@@ -61,6 +62,7 @@ fn concurrent_moves_keep_folder_counts_consistent() {
             language_is_manual: None,
             folder_id: Some(folder_for_b.clone()),
             tags: None,
+            expires_in: None,
         };
         TransactionOps::move_paste_between_folders(
             &worker_b,
@@ -131,6 +133,7 @@ fn concurrent_move_and_delete_keep_folder_counts_consistent() {
             language_is_manual: None,
             folder_id: Some(mover_folder_id.clone()),
             tags: None,
+            expires_in: None,
         };
         TransactionOps::move_paste_between_folders(
             &mover_db,
@@ -202,6 +205,7 @@ fn concurrent_reconcile_and_move_preserve_invariants() {
             language_is_manual: None,
             folder_id: Some(move_destination.clone()),
             tags: None,
+            expires_in: None,
         };
         TransactionOps::move_paste_between_folders(
             &move_db,
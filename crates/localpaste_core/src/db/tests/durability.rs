@@ -0,0 +1,179 @@
+//! Read-after-write consistency and flushing under each flush policy.
+
+use super::*;
+use std::time::Duration;
+
+const POLICIES: [FlushPolicy; 3] = [
+    FlushPolicy::EveryCommit,
+    FlushPolicy::Interval(Duration::from_secs(60)),
+    FlushPolicy::Idle(Duration::from_secs(60)),
+];
+
+#[test]
+fn writes_are_visible_to_the_next_read_under_every_policy() {
+    for policy in POLICIES {
+        let (db, _temp) = setup_test_db();
+        let reader = db.share().expect("share");
+        db.set_flush_policy(policy);
+        assert_eq!(reader.flush_policy(), policy);
+
+        let folder = Folder::new("notes".to_string());
+        db.folders.create(&folder).expect("create folder");
+        let paste = Paste::new("first".to_string(), "draft".to_string());
+        TransactionOps::create_paste_with_folder(&db, &paste, &folder.id).expect("create paste");
+        let stored = reader.pastes.get(&paste.id).expect("get").expect("paste");
+        assert_eq!(stored.content, "first", "{:?}", policy);
+        assert_eq!(stored.folder_id.as_deref(), Some(folder.id.as_str()));
+
+        db.pastes
+            .update(
+                &paste.id,
+                UpdatePasteRequest {
+                    content: Some("second".to_string()),
+                    name: None,
+                    language: None,
+                    language_is_manual: None,
+                    folder_id: None,
+                    tags: None,
+                    expires_in: None,
+                },
+            )
+            .expect("update")
+            .expect("paste exists");
+        let stored = reader.pastes.get(&paste.id).expect("get").expect("paste");
+        assert_eq!(stored.content, "second", "{:?}", policy);
+
+        assert!(TransactionOps::delete_paste_with_folder(&db, &paste.id).expect("delete"));
+        assert!(reader.pastes.get(&paste.id).expect("get").is_none());
+        assert!(reader.folders.get(&folder.id).expect("get").is_some());
+    }
+}
+
+#[test]
+fn flushed_relaxed_writes_survive_reopen() {
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("test.db");
+    let db_path = db_path.to_str().expect("db path");
+    let paste = Paste::new("created".to_string(), "durable".to_string());
+    {
+        let db = open_test_database(db_path);
+        db.set_flush_policy(FlushPolicy::Idle(Duration::from_millis(20)));
+        assert!(!db.flush_if_due().expect("clean"));
+        // Creates are not journaled, so they commit durably and leave
+        // nothing for the flush to do.
+        db.pastes.create(&paste).expect("create");
+        thread::sleep(Duration::from_millis(40));
+        assert!(!db.flush_if_due().expect("create was durable"));
+        db.pastes
+            .update(&paste.id, content_update("kept"))
+            .expect("update")
+            .expect("paste exists");
+        assert!(!db.flush_if_due().expect("not idle yet"));
+        thread::sleep(Duration::from_millis(40));
+        assert!(db.flush_if_due().expect("flush"));
+        assert!(!db.flush_if_due().expect("already flushed"));
+    }
+
+    let db = open_test_database(db_path);
+    let stored = db.pastes.get(&paste.id).expect("get").expect("paste");
+    assert_eq!(stored.content, "kept");
+    // Fresh state per open: the relaxed policy does not outlive the handle.
+    assert_eq!(db.flush_policy(), FlushPolicy::EveryCommit);
+}
+
+fn content_update(content: &str) -> UpdatePasteRequest {
+    UpdatePasteRequest {
+        content: Some(content.to_string()),
+        name: None,
+        language: None,
+        language_is_manual: None,
+        folder_id: None,
+        tags: None,
+        expires_in: None,
+    }
+}
+
+#[test]
+fn journal_replays_relaxed_updates_the_database_is_missing() {
+    use crate::db::journal::JOURNAL_FILE_NAME;
+
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let live_path = temp.path().join("live.db");
+    let stale_path = temp.path().join("stale.db");
+    let paste = Paste::new("before".to_string(), "autosave".to_string());
+
+    // The live database journals a relaxed update; the stale copy stands in
+    // for the same database rolled back to its last flush by an OS crash.
+    let journal = {
+        let db = open_test_database(live_path.to_str().expect("path"));
+        db.pastes.create(&paste).expect("create");
+        db.set_flush_policy(FlushPolicy::Idle(Duration::from_secs(60)));
+        db.pastes
+            .update(&paste.id, content_update("after"))
+            .expect("update")
+            .expect("paste exists");
+        std::fs::read(live_path.join(JOURNAL_FILE_NAME)).expect("read journal")
+    };
+    assert!(!journal.is_empty());
+    {
+        let db = open_test_database(stale_path.to_str().expect("path"));
+        db.pastes.create(&paste).expect("create");
+    }
+    let mut torn = journal.clone();
+    torn.extend_from_slice(&journal[..journal.len() / 2]);
+    std::fs::write(stale_path.join(JOURNAL_FILE_NAME), torn).expect("write journal");
+
+    let stale_path = stale_path.to_str().expect("path");
+    {
+        let db = open_test_database(stale_path);
+        let stored = db.pastes.get(&paste.id).expect("get").expect("paste");
+        assert_eq!(stored.content, "after");
+        let listed = db.pastes.list(10, None).expect("list");
+        assert_eq!(listed[0].content, "after");
+        // Journal entries are now durable, so a second open replays nothing.
+        db.pastes
+            .update(&paste.id, content_update("durable edit"))
+            .expect("update")
+            .expect("paste exists");
+    }
+    assert_eq!(
+        std::fs::metadata(Path::new(stale_path).join(JOURNAL_FILE_NAME))
+            .expect("journal")
+            .len(),
+        0
+    );
+    let db = open_test_database(stale_path);
+    let stored = db.pastes.get(&paste.id).expect("get").expect("paste");
+    assert_eq!(stored.content, "durable edit");
+}
+
+#[test]
+fn flush_empties_the_journal() {
+    use crate::db::journal::JOURNAL_FILE_NAME;
+
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("test.db");
+    let db = open_test_database(db_path.to_str().expect("path"));
+    let paste = Paste::new("one".to_string(), "flushed".to_string());
+    db.pastes.create(&paste).expect("create");
+    let journal_len = || {
+        std::fs::metadata(db_path.join(JOURNAL_FILE_NAME))
+            .map(|meta| meta.len())
+            .unwrap_or(0)
+    };
+
+    db.pastes
+        .update(&paste.id, content_update("durable"))
+        .expect("update")
+        .expect("paste exists");
+    assert_eq!(journal_len(), 0, "durable commits are not journaled");
+
+    db.set_flush_policy(FlushPolicy::Interval(Duration::from_secs(60)));
+    db.pastes
+        .update(&paste.id, content_update("relaxed"))
+        .expect("update")
+        .expect("paste exists");
+    assert!(journal_len() > 0);
+    db.flush().expect("flush");
+    assert_eq!(journal_len(), 0);
+}
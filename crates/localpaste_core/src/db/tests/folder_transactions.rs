@@ -125,6 +125,7 @@ fn move_between_folders_updates_counts_and_assignment() {
         language_is_manual: None,
         folder_id: Some(fixture.new_folder_id.clone()),
         tags: None,
+        expires_in: None,
     };
 
     let moved = TransactionOps::move_paste_between_folders(
@@ -166,6 +167,7 @@ fn move_within_same_folder_updates_paste_without_count_drift() {
         language_is_manual: None,
         folder_id: Some(fixture.old_folder_id.clone()),
         tags: None,
+        expires_in: None,
     };
 
     let moved = TransactionOps::move_paste_between_folders(
@@ -237,6 +239,7 @@ fn content_change_during_folder_moves_archives_middle_version_after_wait() {
         language_is_manual: None,
         folder_id: Some(fixture.new_folder_id.clone()),
         tags: None,
+        expires_in: None,
     };
     TransactionOps::move_paste_between_folders(
         db,
@@ -256,6 +259,7 @@ fn content_change_during_folder_moves_archives_middle_version_after_wait() {
         language_is_manual: None,
         folder_id: Some(fixture.old_folder_id.clone()),
         tags: None,
+        expires_in: None,
     };
     TransactionOps::move_paste_between_folders(
         db,
@@ -310,6 +314,7 @@ fn move_missing_paste_returns_none_without_count_drift() {
         language_is_manual: None,
         folder_id: Some(new_folder_id.clone()),
         tags: None,
+        expires_in: None,
     };
 
     let result = TransactionOps::move_paste_between_folders(
@@ -339,6 +344,7 @@ fn move_between_folders_rejects_conflicting_update_request_folder_id() {
         language_is_manual: None,
         folder_id: Some(fixture.old_folder_id.clone()),
         tags: None,
+        expires_in: None,
     };
 
     let result = TransactionOps::move_paste_between_folders(
@@ -376,6 +382,7 @@ fn delete_uses_folder_from_deleted_record_not_stale_context() {
         language_is_manual: None,
         folder_id: Some(fixture.new_folder_id.clone()),
         tags: None,
+        expires_in: None,
     };
     TransactionOps::move_paste_between_folders(
         db,
@@ -439,6 +446,7 @@ fn direct_folder_affecting_paste_ops_are_rejected() {
                 language_is_manual: None,
                 folder_id: Some(String::new()),
                 tags: None,
+                expires_in: None,
             },
         )
         .expect_err("direct folder update should be rejected");
@@ -459,3 +467,87 @@ fn direct_folder_affecting_paste_ops_are_rejected() {
     let folder_after = db.folders.get(&folder_id).expect("folder").expect("exists");
     assert_eq!(folder_after.paste_count, 1);
 }
+
+#[test]
+fn folder_stats_track_create_edit_move_and_delete() {
+    let fixture = setup_folder_move_fixture();
+    let db = &fixture.db;
+    let stats = |id: &str| {
+        let folder = db.folders.get(id).expect("get").expect("folder");
+        (folder.paste_count, folder.total_bytes, folder.last_updated)
+    };
+    let (count, bytes, created_at) = stats(&fixture.old_folder_id);
+    assert_eq!((count, bytes), (1, "content".len() as u64));
+    assert!(created_at.is_some());
+
+    let update = UpdatePasteRequest {
+        content: Some("longer content".to_string()),
+        name: None,
+        language: None,
+        language_is_manual: None,
+        folder_id: None,
+        tags: None,
+        expires_in: None,
+    };
+    db.pastes
+        .update(&fixture.paste_id, update.clone())
+        .expect("update")
+        .expect("paste");
+    let (count, bytes, edited_at) = stats(&fixture.old_folder_id);
+    assert_eq!((count, bytes), (1, "longer content".len() as u64));
+    assert!(edited_at >= created_at);
+
+    TransactionOps::move_paste_between_folders(
+        db,
+        &fixture.paste_id,
+        Some(fixture.new_folder_id.as_str()),
+        UpdatePasteRequest {
+            content: None,
+            folder_id: Some(fixture.new_folder_id.clone()),
+            ..update
+        },
+    )
+    .expect("move")
+    .expect("paste");
+    let (old_count, old_bytes, old_touched) = stats(&fixture.old_folder_id);
+    assert_eq!((old_count, old_bytes), (0, 0));
+    assert!(old_touched >= edited_at);
+    let (new_count, new_bytes, _) = stats(&fixture.new_folder_id);
+    assert_eq!((new_count, new_bytes), (1, "longer content".len() as u64));
+
+    assert!(TransactionOps::delete_paste_with_folder(db, &fixture.paste_id).expect("delete"));
+    let (new_count, new_bytes, deleted_at) = stats(&fixture.new_folder_id);
+    assert_eq!((new_count, new_bytes), (0, 0));
+    assert!(deleted_at.is_some());
+}
+
+#[test]
+fn create_pastes_inserts_all_rows_or_none() {
+    let (db, _temp) = setup_test_db();
+
+    let folder = Folder::new("batch-folder".to_string());
+    let folder_id = folder.id.clone();
+    db.folders.create(&folder).expect("create folder");
+
+    let loose = Paste::new("loose".to_string(), "loose".to_string());
+    let mut foldered = Paste::new("foldered".to_string(), "foldered".to_string());
+    foldered.folder_id = Some(folder_id.clone());
+    TransactionOps::create_pastes(&db, &[loose.clone(), foldered.clone()]).expect("batch");
+
+    assert!(db.pastes.get(&loose.id).expect("lookup").is_some());
+    assert!(db.pastes.get(&foldered.id).expect("lookup").is_some());
+    let stored_folder = db.folders.get(&folder_id).expect("get").expect("folder");
+    assert_eq!(stored_folder.paste_count, 1);
+
+    let fresh = Paste::new("fresh".to_string(), "fresh".to_string());
+    let mut orphan = Paste::new("orphan".to_string(), "orphan".to_string());
+    orphan.folder_id = Some("missing-folder".to_string());
+    let err = TransactionOps::create_pastes(&db, &[fresh.clone(), orphan])
+        .expect_err("missing folder must fail");
+    assert!(
+        matches!(&err, AppError::BadRequest(message) if message.contains("missing-folder")),
+        "{:?}",
+        err
+    );
+    assert!(db.pastes.get(&fresh.id).expect("lookup").is_none());
+}
@@ -12,6 +12,7 @@ use std::thread;
 
 mod basic_ops;
 mod concurrency;
+mod durability;
 mod folder_transactions;
 mod search_and_meta;
 mod startup_reconcile;
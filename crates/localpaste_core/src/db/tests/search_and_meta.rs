@@ -2,9 +2,9 @@
 
 use super::*;
 use crate::db::paste::{CURRENT_PASTES_META_SCHEMA_VERSION, META_SCHEMA_VERSION_KEY};
-use crate::db::tables::{PASTES_META, PASTES_META_STATE};
+use crate::db::tables::{PASTES_BY_TAG, PASTES_BY_TRIGRAM, PASTES_META, PASTES_META_STATE};
 use chrono::Duration;
-use redb::ReadableDatabase;
+use redb::{ReadableDatabase, ReadableTable};
 use serde::{Deserialize, Serialize};
 
 #[test]
@@ -54,7 +54,10 @@ fn paste_search_respects_exact_match_and_top_k_ranking() {
                 db.pastes.create(&paste2).expect("create");
                 db.pastes.create(&paste3).expect("create");
 
-                let results = db.pastes.search("rust", 10, None, None).expect("search");
+                let results = db
+                    .pastes
+                    .search("rust", 10, &SearchFilters::default())
+                    .expect("search");
                 assert_eq!(results.len(), 1);
                 assert_eq!(results[0].id, paste1.id);
             }
@@ -73,7 +76,10 @@ fn paste_search_respects_exact_match_and_top_k_ranking() {
                 db.pastes.create(&medium).expect("create");
                 db.pastes.create(&weak).expect("create");
 
-                let results = db.pastes.search("needle", 1, None, None).expect("search");
+                let results = db
+                    .pastes
+                    .search("needle", 1, &SearchFilters::default())
+                    .expect("search");
                 assert_eq!(results.len(), 1);
                 assert_eq!(results[0].id, strongest.id);
             }
@@ -103,7 +109,7 @@ fn paste_search_meta_uses_persisted_metadata_and_derived_terms() {
 
     let results = db
         .pastes
-        .search_meta("rust", 10, None, None)
+        .search_meta("rust", 10, &SearchFilters::default())
         .expect("search");
     let ids: Vec<String> = results.into_iter().map(|m| m.id).collect();
     assert!(ids.contains(&by_name.id));
@@ -111,6 +117,29 @@ fn paste_search_meta_uses_persisted_metadata_and_derived_terms() {
     assert!(ids.contains(&by_derived.id));
 }
 
+#[test]
+fn paste_search_meta_until_abandons_cancelled_scans() {
+    let (db, _temp) = setup_test_db();
+    for idx in 0..12 {
+        db.pastes
+            .create(&Paste::new("body".to_string(), format!("rust-{idx}")))
+            .expect("create");
+    }
+
+    let cancelled = db
+        .pastes
+        .search_meta_until("rust", 10, &SearchFilters::default(), &|| true)
+        .expect("search");
+    assert!(cancelled.is_none());
+
+    let completed = db
+        .pastes
+        .search_meta_until("rust", 10, &SearchFilters::default(), &|| false)
+        .expect("search")
+        .expect("not cancelled");
+    assert_eq!(completed.len(), 10);
+}
+
 #[test]
 fn paste_search_meta_multi_term_queries_rank_combined_metadata_hits() {
     let (db, _temp) = setup_test_db();
@@ -136,7 +165,7 @@ fn paste_search_meta_multi_term_queries_rank_combined_metadata_hits() {
 
     let results = db
         .pastes
-        .search_meta("docker postgres", 10, None, None)
+        .search_meta("docker postgres", 10, &SearchFilters::default())
         .expect("search");
     let ids: Vec<String> = results.into_iter().map(|meta| meta.id).collect();
 
@@ -166,7 +195,7 @@ fn paste_search_meta_matches_derived_handle_and_terms() {
 
     let handle_results = db
         .pastes
-        .search_meta("cargo test", 10, None, None)
+        .search_meta("cargo test", 10, &SearchFilters::default())
         .expect("search");
     assert_eq!(
         handle_results.first().map(|meta| meta.id.as_str()),
@@ -175,7 +204,7 @@ fn paste_search_meta_matches_derived_handle_and_terms() {
 
     let term_results = db
         .pastes
-        .search_meta("fsdp2 cublaslt", 10, None, None)
+        .search_meta("fsdp2 cublaslt", 10, &SearchFilters::default())
         .expect("search");
     let ids: Vec<String> = term_results.into_iter().map(|meta| meta.id).collect();
     assert_eq!(ids.first().map(String::as_str), Some(terms.id.as_str()));
@@ -205,7 +234,7 @@ fn paste_search_meta_keeps_name_above_tags_above_language() {
 
     let results = db
         .pastes
-        .search_meta("python", 10, None, None)
+        .search_meta("python", 10, &SearchFilters::default())
         .expect("search");
     let ids: Vec<String> = results.into_iter().map(|meta| meta.id).collect();
 
@@ -241,7 +270,14 @@ fn search_language_filters_are_case_insensitive_and_trimmed_for_full_and_meta_qu
 
                 let results = db
                     .pastes
-                    .search("run", 10, None, Some("  PyThOn  ".to_string()))
+                    .search(
+                        "run",
+                        10,
+                        &SearchFilters {
+                            language: Some("  PyThOn  ".to_string()),
+                            ..SearchFilters::default()
+                        },
+                    )
                     .expect("search");
                 assert_eq!(results.len(), 1);
                 assert_eq!(results[0].id, python.id);
@@ -262,7 +298,14 @@ fn search_language_filters_are_case_insensitive_and_trimmed_for_full_and_meta_qu
 
                 let results = db
                     .pastes
-                    .search_meta("tips", 10, None, Some(" PYTHON ".to_string()))
+                    .search_meta(
+                        "tips",
+                        10,
+                        &SearchFilters {
+                            language: Some(" PYTHON ".to_string()),
+                            ..SearchFilters::default()
+                        },
+                    )
                     .expect("search");
                 assert_eq!(results.len(), 1);
                 assert_eq!(results[0].id, python.id);
@@ -271,24 +314,101 @@ fn search_language_filters_are_case_insensitive_and_trimmed_for_full_and_meta_qu
     }
 }
 
+#[test]
+fn paste_search_combines_tag_and_updated_time_filters() {
+    let (db, _temp) = setup_test_db();
+    let now = chrono::Utc::now();
+
+    let mut old_work = Paste::new("deploy notes".to_string(), "old-work".to_string());
+    old_work.tags = vec!["Work".to_string()];
+    old_work.updated_at = now - Duration::days(10);
+    let mut new_work = Paste::new("deploy script".to_string(), "new-work".to_string());
+    new_work.tags = vec!["work".to_string()];
+    new_work.updated_at = now - Duration::hours(1);
+    let mut new_home = Paste::new("deploy garden".to_string(), "new-home".to_string());
+    new_home.tags = vec!["home".to_string()];
+    new_home.updated_at = now;
+    for paste in [&old_work, &new_work, &new_home] {
+        db.pastes.create(paste).expect("create");
+    }
+
+    let ids = |results: Vec<PasteMeta>| -> Vec<String> {
+        let mut ids: Vec<String> = results.into_iter().map(|meta| meta.id).collect();
+        ids.sort();
+        ids
+    };
+    let sorted = |mut expected: Vec<String>| {
+        expected.sort();
+        expected
+    };
+
+    let work = SearchFilters {
+        tag: Some(" WORK ".to_string()),
+        ..SearchFilters::default()
+    };
+    let recent_work = SearchFilters {
+        after: Some(now - Duration::days(1)),
+        ..work.clone()
+    };
+    let before_today = SearchFilters {
+        before: Some(now - Duration::minutes(1)),
+        ..SearchFilters::default()
+    };
+
+    assert_eq!(
+        ids(db.pastes.search("deploy", 10, &work).expect("search")),
+        sorted(vec![old_work.id.clone(), new_work.id.clone()])
+    );
+    assert_eq!(
+        ids(db
+            .pastes
+            .search("deploy", 10, &recent_work)
+            .expect("search")),
+        vec![new_work.id.clone()]
+    );
+    assert_eq!(
+        ids(db
+            .pastes
+            .search("deploy", 10, &before_today)
+            .expect("search")),
+        sorted(vec![old_work.id.clone(), new_work.id.clone()])
+    );
+    assert_eq!(
+        ids(db
+            .pastes
+            .search_meta("work", 10, &recent_work)
+            .expect("search")),
+        vec![new_work.id.clone()]
+    );
+}
+
 #[test]
 fn paste_search_ignores_empty_or_whitespace_queries() {
     let (db, _temp) = setup_test_db();
     let paste = Paste::new("hello world".to_string(), "note".to_string());
     db.pastes.create(&paste).expect("create");
 
-    let empty = db.pastes.search("", 10, None, None).expect("search");
+    let empty = db
+        .pastes
+        .search("", 10, &SearchFilters::default())
+        .expect("search");
     assert!(empty.is_empty());
 
-    let whitespace = db.pastes.search("   ", 10, None, None).expect("search");
+    let whitespace = db
+        .pastes
+        .search("   ", 10, &SearchFilters::default())
+        .expect("search");
     assert!(whitespace.is_empty());
 
-    let meta_empty = db.pastes.search_meta("", 10, None, None).expect("search");
+    let meta_empty = db
+        .pastes
+        .search_meta("", 10, &SearchFilters::default())
+        .expect("search");
     assert!(meta_empty.is_empty());
 
     let meta_whitespace = db
         .pastes
-        .search_meta("   ", 10, None, None)
+        .search_meta("   ", 10, &SearchFilters::default())
         .expect("search");
     assert!(meta_whitespace.is_empty());
 }
@@ -307,6 +427,7 @@ fn meta_indexes_stay_consistent_after_update_and_delete() {
         language_is_manual: None,
         folder_id: None,
         tags: Some(vec!["tag".to_string()]),
+        expires_in: None,
     };
     db.pastes
         .update(&paste_id, update)
@@ -324,6 +445,326 @@ fn meta_indexes_stay_consistent_after_update_and_delete() {
     assert!(!metas_after_delete.into_iter().any(|m| m.id == paste_id));
 }
 
+fn trigram_rows_for(db: &Database, paste_id: &str) -> usize {
+    let read_txn = db.db.begin_read().expect("begin read");
+    let index = read_txn
+        .open_table(PASTES_BY_TRIGRAM)
+        .expect("open trigram index");
+    index
+        .iter()
+        .expect("iter")
+        .filter(|item| item.as_ref().expect("row").0.value().1 == paste_id)
+        .count()
+}
+
+#[test]
+fn content_index_follows_create_update_delete_and_rebuild() {
+    let (db, _temp) = setup_test_db();
+    let target = Paste::new("fn parse_config() {}".to_string(), "a".to_string());
+    let target_id = target.id.clone();
+    let other = Paste::new("unrelated body".to_string(), "b".to_string());
+    db.pastes.create(&target).expect("create target");
+    db.pastes.create(&other).expect("create other");
+    let search_ids = |query: &str| -> Vec<String> {
+        db.pastes
+            .search(query, 10, &SearchFilters::default())
+            .expect("search")
+            .into_iter()
+            .map(|meta| meta.id)
+            .collect()
+    };
+
+    assert_eq!(search_ids("PARSE_conf"), vec![target_id.clone()]);
+    assert!(search_ids("missing text").is_empty());
+    let indexed = trigram_rows_for(&db, &target_id);
+    assert_eq!(indexed, "fn parse_config() {}".chars().count() - 2);
+
+    let retag = UpdatePasteRequest {
+        content: None,
+        name: None,
+        language: None,
+        language_is_manual: None,
+        folder_id: None,
+        tags: Some(vec!["config".to_string()]),
+        expires_in: None,
+    };
+    db.pastes.update(&target_id, retag).expect("retag");
+    assert_eq!(trigram_rows_for(&db, &target_id), indexed);
+
+    let rewrite = UpdatePasteRequest {
+        content: Some("fn load_settings() {}".to_string()),
+        name: None,
+        language: None,
+        language_is_manual: None,
+        folder_id: None,
+        tags: None,
+        expires_in: None,
+    };
+    db.pastes.update(&target_id, rewrite).expect("rewrite");
+    assert!(search_ids("parse_conf").is_empty());
+    assert_eq!(search_ids("Settings"), vec![target_id.clone()]);
+
+    let write_txn = db.db.begin_write().expect("begin write");
+    write_txn
+        .open_table(PASTES_BY_TRIGRAM)
+        .expect("open trigram index")
+        .retain(|_, _| false)
+        .expect("clear trigram index");
+    write_txn.commit().expect("commit");
+    assert!(search_ids("settings").is_empty());
+    db.pastes.rebuild_meta_index().expect("rebuild");
+    assert_eq!(search_ids("settings"), vec![target_id.clone()]);
+
+    db.pastes.delete(&target_id).expect("delete");
+    assert_eq!(trigram_rows_for(&db, &target_id), 0);
+    assert_eq!(search_ids("body"), vec![other.id.clone()]);
+}
+
+fn listed_ids(
+    db: &Database,
+    folder_id: Option<&str>,
+    tag: Option<&str>,
+    language: Option<&str>,
+) -> Vec<String> {
+    db.pastes
+        .list_meta_filtered(10, folder_id, tag, language)
+        .expect("list filtered")
+        .into_iter()
+        .map(|meta| meta.id)
+        .collect()
+}
+
+#[test]
+fn list_meta_filtered_follows_folder_tag_and_language_mutations() {
+    let (db, _temp) = setup_test_db();
+    let folder = Folder::new("filtered".to_string());
+    let folder_id = folder.id.clone();
+    db.folders.create(&folder).expect("create folder");
+
+    let now = chrono::Utc::now();
+    let mut older = Paste::new_with_language(
+        "fn main() {}\n".to_string(),
+        "older".to_string(),
+        Some("rust".to_string()),
+        true,
+    );
+    older.tags = vec!["Work".to_string()];
+    older.updated_at = now - Duration::minutes(5);
+    let older_id = older.id.clone();
+    let mut newer = Paste::new_with_language(
+        "echo hi\n".to_string(),
+        "newer".to_string(),
+        Some("bash".to_string()),
+        true,
+    );
+    newer.tags = vec!["work".to_string(), "ops".to_string()];
+    newer.updated_at = now;
+    let newer_id = newer.id.clone();
+    TransactionOps::create_paste_with_folder(&db, &older, &folder_id).expect("create older");
+    db.pastes.create(&newer).expect("create newer");
+
+    assert_eq!(
+        listed_ids(&db, Some(&folder_id), None, None),
+        vec![older_id.clone()]
+    );
+    assert_eq!(
+        listed_ids(&db, None, Some(" WORK "), None),
+        vec![newer_id.clone(), older_id.clone()]
+    );
+    assert_eq!(
+        listed_ids(&db, None, None, Some("sh")),
+        vec![newer_id.clone()]
+    );
+    assert_eq!(
+        listed_ids(&db, Some(&folder_id), Some("ops"), None),
+        Vec::<String>::new()
+    );
+    let count = |folder: Option<&str>, tag: Option<&str>, language: Option<&str>| {
+        db.pastes
+            .count_filtered(folder, tag, language)
+            .expect("count")
+    };
+    assert_eq!(count(None, None, None), 2);
+    assert_eq!(count(None, Some("work"), None), 2);
+    assert_eq!(count(Some(&folder_id), Some("work"), None), 1);
+    assert_eq!(count(None, Some("work"), Some("shell")), 1);
+    assert_eq!(
+        db.pastes.language_counts().expect("language counts"),
+        vec![("rust".to_string(), 1), ("shell".to_string(), 1)]
+    );
+
+    db.pastes
+        .update(
+            &newer_id,
+            UpdatePasteRequest {
+                content: None,
+                name: None,
+                language: Some("python".to_string()),
+                language_is_manual: None,
+                folder_id: None,
+                tags: Some(vec!["ops".to_string()]),
+                expires_in: None,
+            },
+        )
+        .expect("update")
+        .expect("row");
+    assert_eq!(
+        listed_ids(&db, None, Some("work"), None),
+        vec![older_id.clone()]
+    );
+    assert!(listed_ids(&db, None, None, Some("shell")).is_empty());
+    assert_eq!(
+        listed_ids(&db, None, None, Some("python")),
+        vec![newer_id.clone()]
+    );
+
+    TransactionOps::move_paste_between_folders(
+        &db,
+        &newer_id,
+        Some(folder_id.as_str()),
+        UpdatePasteRequest {
+            content: None,
+            name: None,
+            language: None,
+            language_is_manual: None,
+            folder_id: Some(folder_id.clone()),
+            tags: None,
+            expires_in: None,
+        },
+    )
+    .expect("move")
+    .expect("row");
+    assert_eq!(
+        listed_ids(&db, Some(&folder_id), None, None),
+        vec![newer_id.clone(), older_id.clone()]
+    );
+    let canonical: Vec<String> = db
+        .pastes
+        .list(10, Some(folder_id.clone()))
+        .expect("list canonical")
+        .into_iter()
+        .map(|paste| paste.id)
+        .collect();
+    assert_eq!(canonical, vec![newer_id.clone(), older_id.clone()]);
+
+    TransactionOps::delete_paste_with_folder(&db, &older_id).expect("delete older");
+    assert_eq!(
+        listed_ids(&db, Some(&folder_id), None, None),
+        vec![newer_id]
+    );
+    assert!(listed_ids(&db, None, Some("work"), None).is_empty());
+    assert!(listed_ids(&db, None, None, Some("rust")).is_empty());
+}
+
+#[test]
+fn list_meta_sorted_orders_by_each_field_in_both_directions() {
+    let (db, _temp) = setup_test_db();
+    let now = chrono::Utc::now();
+    for (name, content, created_ago, updated_ago, tag) in [
+        ("bravo", "12345", 30, 1, "work"),
+        ("Alpha", "123456789012", 20, 10, "work"),
+        ("charlie", "1", 10, 5, "workshop"),
+    ] {
+        let mut paste = Paste::new(content.to_string(), name.to_string());
+        paste.created_at = now - Duration::minutes(created_ago);
+        paste.updated_at = now - Duration::minutes(updated_ago);
+        paste.tags = vec![tag.to_string()];
+        db.pastes.create(&paste).expect("create");
+    }
+    let names = |limit: usize, tag: Option<&str>, sort: PasteSortKey, order: SortOrder| {
+        db.pastes
+            .list_meta_sorted(limit, None, tag, None, sort, order)
+            .expect("list sorted")
+            .into_iter()
+            .map(|meta| meta.name)
+            .collect::<Vec<_>>()
+    };
+    use SortOrder::{Asc, Desc};
+
+    assert_eq!(
+        names(10, None, PasteSortKey::Updated, Desc),
+        ["bravo", "charlie", "Alpha"]
+    );
+    assert_eq!(
+        names(10, None, PasteSortKey::Updated, Asc),
+        ["Alpha", "charlie", "bravo"]
+    );
+    assert_eq!(
+        names(10, None, PasteSortKey::Created, Desc),
+        ["charlie", "Alpha", "bravo"]
+    );
+    assert_eq!(
+        names(2, None, PasteSortKey::Created, Asc),
+        ["bravo", "Alpha"]
+    );
+    assert_eq!(
+        names(10, None, PasteSortKey::Name, Asc),
+        ["Alpha", "bravo", "charlie"]
+    );
+    assert_eq!(names(1, None, PasteSortKey::Name, Desc), ["charlie"]);
+    assert_eq!(
+        names(10, None, PasteSortKey::Size, Asc),
+        ["charlie", "bravo", "Alpha"]
+    );
+    // The reverse scan of one tag's range must not run into the next tag.
+    assert_eq!(
+        names(10, Some("work"), PasteSortKey::Updated, Asc),
+        ["Alpha", "bravo"]
+    );
+    assert_eq!(
+        names(10, Some("workshop"), PasteSortKey::Updated, Asc),
+        ["charlie"]
+    );
+    assert_eq!(
+        names(10, Some("work"), PasteSortKey::Created, Desc),
+        ["Alpha", "bravo"]
+    );
+
+    db.pastes.rebuild_meta_index().expect("rebuild");
+    assert_eq!(
+        names(10, None, PasteSortKey::Created, Asc),
+        ["bravo", "Alpha", "charlie"]
+    );
+    let mut canonical = db.pastes.list(10, None).expect("list");
+    crate::db::paste::sort_pastes(&mut canonical, PasteSortKey::Created, Asc);
+    let canonical: Vec<_> = canonical.into_iter().map(|paste| paste.name).collect();
+    assert_eq!(canonical, ["bravo", "Alpha", "charlie"]);
+}
+
+#[test]
+fn markerless_database_rebuilds_filter_indexes_on_open() {
+    let temp_dir = tempfile::TempDir::new().expect("temp dir");
+    let db_path = temp_dir.path().join("db");
+    let db_path_str = db_path.to_str().expect("db path").to_string();
+
+    let db = open_test_database(&db_path_str);
+    let mut paste = Paste::new("tagged".to_string(), "tagged".to_string());
+    paste.tags = vec!["keep".to_string()];
+    let paste_id = paste.id.clone();
+    db.pastes.create(&paste).expect("create");
+
+    let write_txn = db.db.begin_write().expect("begin write");
+    {
+        let mut tags = write_txn.open_table(PASTES_BY_TAG).expect("open tag index");
+        tags.retain(|_, _| false).expect("clear tag index");
+        let mut meta_state = write_txn
+            .open_table(PASTES_META_STATE)
+            .expect("open meta state");
+        let _ = meta_state
+            .remove(META_SCHEMA_VERSION_KEY)
+            .expect("remove schema marker");
+    }
+    write_txn.commit().expect("commit");
+    assert!(listed_ids(&db, None, Some("keep"), None).is_empty());
+    drop(db);
+
+    let reopened = open_test_database(&db_path_str);
+    assert_eq!(
+        listed_ids(&reopened, None, Some("keep"), None),
+        vec![paste_id]
+    );
+}
+
 #[derive(Serialize, Deserialize)]
 struct LegacyPasteMetaWire {
     id: String,
@@ -408,6 +849,7 @@ fn database_new_rebuilds_markerless_current_meta_rows() {
         kind: crate::semantic::PasteKind::Other,
         handle: Some("frozen-handle".to_string()),
         terms: vec!["frozen-term".to_string()],
+        indent: None,
     };
     let encoded = bincode::serialize(&current_meta).expect("serialize");
     let write_txn = db.db.begin_write().expect("begin write");
@@ -494,6 +936,7 @@ fn database_from_shared_rebuilds_markerless_current_meta_rows() {
         kind: crate::semantic::PasteKind::Other,
         handle: Some("stale-shared-handle".to_string()),
         terms: vec!["stale-shared-term".to_string()],
+        indent: None,
     };
     let encoded = bincode::serialize(&current_meta).expect("serialize");
     let write_txn = db.db.begin_write().expect("begin write");
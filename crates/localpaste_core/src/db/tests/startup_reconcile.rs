@@ -40,7 +40,9 @@ fn database_new_repairs_folder_count_drift_on_restart() {
     TransactionOps::create_paste_with_folder(&db, &paste_a, &folder_id).expect("create");
     TransactionOps::create_paste_with_folder(&db, &paste_b, &folder_id).expect("create");
 
-    db.folders.set_count(&folder_id, 99).expect("drift");
+    db.folders
+        .set_stats(&folder_id, 99, 1, None)
+        .expect("drift");
     drop(db);
 
     let reopened = open_test_database(&db_path_str);
@@ -55,6 +57,32 @@ fn database_new_repairs_folder_count_drift_on_restart() {
         .expect("list")
         .len();
     assert_eq!(folder_after.paste_count, canonical_count);
+    assert_eq!(folder_after.total_bytes, ("one".len() + "two".len()) as u64);
+    assert!(folder_after.last_updated.is_some());
+}
+
+#[test]
+fn folder_rows_written_before_stats_still_decode() {
+    #[derive(serde::Serialize)]
+    struct PreStatsFolder {
+        id: String,
+        name: String,
+        created_at: chrono::DateTime<chrono::Utc>,
+        paste_count: usize,
+        parent_id: Option<String>,
+    }
+    let old = PreStatsFolder {
+        id: "old".to_string(),
+        name: "Old".to_string(),
+        created_at: chrono::Utc::now(),
+        paste_count: 3,
+        parent_id: None,
+    };
+    let encoded = bincode::serialize(&old).expect("encode");
+    let folder = crate::db::folder::deserialize_folder(&encoded).expect("decode");
+    assert_eq!(folder.paste_count, 3);
+    assert_eq!(folder.total_bytes, 0);
+    assert!(folder.last_updated.is_none());
 }
 
 #[test]
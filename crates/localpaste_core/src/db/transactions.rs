@@ -1,18 +1,24 @@
 //! Atomic cross-table transaction helpers for folder-affecting mutations.
 
 use super::tables::{
-    FOLDERS, FOLDERS_DELETING, PASTES, PASTES_BY_UPDATED, PASTES_META, PASTE_VERSIONS_CONTENT,
-    PASTE_VERSIONS_META,
+    FOLDERS, FOLDERS_DELETING, PASTES, PASTES_BY_TAG, PASTES_BY_UPDATED, PASTES_META, PASTE_ACCESS,
+    PASTE_SLUGS, PASTE_VERSIONS_CONTENT, PASTE_VERSIONS_META,
 };
 use super::Database;
-use crate::db::paste::{apply_update_request, deserialize_paste, reverse_timestamp_key};
+use crate::db::folder::deserialize_folder;
+use crate::db::paste::{
+    apply_update_request, deserialize_paste, reverse_timestamp_key, FilterIndexKeys,
+    FilterIndexTables,
+};
 use crate::db::versioning::{
     decode_version_meta_list, encode_version_meta_list, next_version_meta_for_content,
     should_record_version,
 };
 use crate::error::AppError;
+use crate::folder_ops::map_missing_folder_for_request;
 use crate::models::folder::Folder;
-use crate::models::paste::{Paste, PasteMeta, UpdatePasteRequest};
+use crate::models::paste::{normalize_tag_filter, Paste, PasteMeta, UpdatePasteRequest};
+use chrono::{DateTime, Utc};
 use redb::ReadableTable;
 use std::sync::MutexGuard;
 
@@ -48,7 +54,7 @@ fn load_folder(
     let Some(guard) = folders.get(folder_id)? else {
         return Ok(None);
     };
-    Ok(Some(bincode::deserialize(guard.value())?))
+    Ok(Some(deserialize_folder(guard.value())?))
 }
 
 fn folder_disappeared_after_assignability_error(folder_id: &str) -> AppError {
@@ -58,27 +64,112 @@ fn folder_disappeared_after_assignability_error(folder_id: &str) -> AppError {
     ))
 }
 
-fn apply_folder_count_transition(
+/// Remove a paste row with its indexes, slug, versions, and folder stats.
+///
+/// Returns the removed paste, or `Ok(None)` without touching any table when
+/// the paste is missing.
+fn delete_paste_in_txn(
+    write_txn: &redb::WriteTransaction,
+    paste_id: &str,
+) -> Result<Option<Paste>, AppError> {
+    let mut pastes = write_txn.open_table(PASTES)?;
+    let mut metas = write_txn.open_table(PASTES_META)?;
+    let mut updated = write_txn.open_table(PASTES_BY_UPDATED)?;
+    let mut versions_meta = write_txn.open_table(PASTE_VERSIONS_META)?;
+    let mut versions_content = write_txn.open_table(PASTE_VERSIONS_CONTENT)?;
+    let mut folders = write_txn.open_table(FOLDERS)?;
+    let mut slugs = write_txn.open_table(PASTE_SLUGS)?;
+    let mut access = write_txn.open_table(PASTE_ACCESS)?;
+
+    let Some(old_guard) = pastes.get(paste_id)? else {
+        return Ok(None);
+    };
+    let paste = deserialize_paste(old_guard.value())?;
+    let old_recency_key = reverse_timestamp_key(paste.updated_at);
+    let old_filter_keys = FilterIndexKeys::from_paste(&paste);
+    drop(old_guard);
+
+    let _ = updated.remove((old_recency_key, paste_id))?;
+    FilterIndexTables::open(write_txn)?.remove(paste_id, &old_filter_keys)?;
+    let _ = pastes.remove(paste_id)?;
+    let _ = metas.remove(paste_id)?;
+    let _ = access.remove(paste_id)?;
+    if let Some(slug) = paste.slug.as_deref() {
+        let _ = slugs.remove(slug)?;
+    }
+    let version_items = decode_version_meta_list(
+        versions_meta
+            .get(paste_id)?
+            .as_ref()
+            .map(|value| value.value()),
+    )?;
+    for version in version_items {
+        let _ = versions_content.remove((paste_id, version.version_id_ms))?;
+    }
+    let _ = versions_meta.remove(paste_id)?;
+    super::comments::remove_paste_comments(write_txn, paste_id)?;
+    super::annotations::remove_paste_annotations(write_txn, paste_id)?;
+    super::attachments::remove_paste_attachments(write_txn, paste_id)?;
+    super::url_sources::remove_paste_url_source(write_txn, paste_id)?;
+
+    apply_folder_stats_transition(
+        &mut folders,
+        paste
+            .folder_id
+            .as_deref()
+            .map(|id| (id, paste.content.len())),
+        None,
+        Utc::now(),
+    )?;
+    Ok(Some(paste))
+}
+
+/// Apply a paste's move/edit/delete to folder `paste_count`, `total_bytes`,
+/// and `last_updated` inside the caller's write transaction.
+///
+/// `old`/`new` pair the paste's folder id with its stored content length
+/// before and after the write. A missing new folder is an error when the paste
+/// is entering it; stats for missing folders are otherwise skipped.
+pub(crate) fn apply_folder_stats_transition(
     folders: &mut redb::Table<&str, &[u8]>,
-    old_folder_id: Option<&str>,
-    new_folder_id: Option<&str>,
+    old: Option<(&str, usize)>,
+    new: Option<(&str, usize)>,
+    at: DateTime<Utc>,
 ) -> Result<(), AppError> {
-    if old_folder_id == new_folder_id {
-        return Ok(());
+    let touch = |folder: &mut Folder| {
+        folder.last_updated = Some(folder.last_updated.map_or(at, |prev| prev.max(at)));
+    };
+    if let (Some((old_id, old_len)), Some((new_id, new_len))) = (old, new) {
+        if old_id == new_id {
+            if let Some(mut folder) = load_folder(folders, old_id)? {
+                folder.total_bytes = folder
+                    .total_bytes
+                    .saturating_sub(old_len as u64)
+                    .saturating_add(new_len as u64);
+                touch(&mut folder);
+                let encoded = bincode::serialize(&folder)?;
+                folders.insert(old_id, encoded.as_slice())?;
+            }
+            return Ok(());
+        }
     }
 
-    if let Some(old_id) = old_folder_id {
+    if let Some((old_id, old_len)) = old {
         if let Some(mut old_folder) = load_folder(folders, old_id)? {
             old_folder.paste_count = old_folder.paste_count.saturating_sub(1);
+            old_folder.total_bytes = old_folder.total_bytes.saturating_sub(old_len as u64);
+            touch(&mut old_folder);
             let encoded_old = bincode::serialize(&old_folder)?;
             folders.insert(old_id, encoded_old.as_slice())?;
         }
     }
 
-    if let Some(new_id) = new_folder_id {
+    if let Some((new_id, new_len)) = new {
         let mut new_folder = load_folder(folders, new_id)?
             .ok_or_else(|| folder_disappeared_after_assignability_error(new_id))?;
         new_folder.paste_count = new_folder.paste_count.saturating_add(1);
+        new_folder.total_bytes = new_folder.total_bytes.saturating_add(new_len as u64);
+        touch(&mut new_folder);
         let encoded_new = bincode::serialize(&new_folder)?;
         folders.insert(new_id, encoded_new.as_slice())?;
     }
@@ -88,7 +179,10 @@ fn apply_folder_count_transition(
 
 struct PersistPasteIndexUpdate<'a> {
     old_recency_key: Option<u64>,
+    old_filter_keys: Option<&'a FilterIndexKeys>,
     old_folder_id: Option<&'a str>,
+    /// Stored content length before the write; ignored without `old_folder_id`.
+    old_content_len: usize,
     new_folder_id: Option<&'a str>,
 }
 
@@ -96,6 +190,7 @@ fn persist_paste_with_indexes_and_folder_counts(
     pastes: &mut redb::Table<&str, &[u8]>,
     metas: &mut redb::Table<&str, &[u8]>,
     updated: &mut redb::Table<(u64, &str), ()>,
+    filters: &mut FilterIndexTables<'_>,
     folders: &mut redb::Table<&str, &[u8]>,
     paste: &Paste,
     index_update: PersistPasteIndexUpdate<'_>,
@@ -107,12 +202,22 @@ fn persist_paste_with_indexes_and_folder_counts(
         let _ = updated.remove((old_key, paste_id))?;
     }
     updated.insert((reverse_timestamp_key(paste.updated_at), paste_id), ())?;
+    let new_filter_keys = FilterIndexKeys::from_paste(paste);
+    match index_update.old_filter_keys {
+        Some(old_keys) => filters.replace(paste_id, old_keys, &new_filter_keys)?,
+        None => filters.insert(paste_id, &new_filter_keys)?,
+    }
     pastes.insert(paste_id, encoded_paste.as_slice())?;
     metas.insert(paste_id, encoded_meta.as_slice())?;
-    apply_folder_count_transition(
+    apply_folder_stats_transition(
         folders,
-        index_update.old_folder_id,
-        index_update.new_folder_id,
+        index_update
+            .old_folder_id
+            .map(|id| (id, index_update.old_content_len)),
+        index_update
+            .new_folder_id
+            .map(|id| (id, paste.content.len())),
+        paste.updated_at,
     )?;
     Ok(())
 }
@@ -179,6 +284,7 @@ impl TransactionOps {
     /// # Errors
     /// Returns an error when folder assignment is invalid, id already exists,
     /// serialization fails, or storage operations fail.
+    #[tracing::instrument(level = "debug", name = "folder_txn.create_paste", skip_all, fields(id = %paste.id))]
     pub fn create_paste_with_folder_locked(
         db: &Database,
         _folder_guard: &FolderTxnGuard<'_>,
@@ -195,6 +301,7 @@ impl TransactionOps {
             let mut pastes = write_txn.open_table(PASTES)?;
             let mut metas = write_txn.open_table(PASTES_META)?;
             let mut updated = write_txn.open_table(PASTES_BY_UPDATED)?;
+            let mut filters = FilterIndexTables::open(&write_txn)?;
             let mut folders = write_txn.open_table(FOLDERS)?;
             let deleting = write_txn.open_table(FOLDERS_DELETING)?;
 
@@ -210,11 +317,14 @@ impl TransactionOps {
                 &mut pastes,
                 &mut metas,
                 &mut updated,
+                &mut filters,
                 &mut folders,
                 &paste,
                 PersistPasteIndexUpdate {
                     old_recency_key: None,
+                    old_filter_keys: None,
                     old_folder_id: None,
+                    old_content_len: 0,
                     new_folder_id: Some(folder_id),
                 },
             )?;
@@ -223,6 +333,137 @@ impl TransactionOps {
         Ok(())
     }
 
+    /// Atomically create several pastes in one write transaction.
+    ///
+    /// Each paste keeps its own `folder_id`; foldered rows update their folder
+    /// counts. Either every paste is inserted or none is.
+    ///
+    /// # Arguments
+    /// - `db`: Open database handle.
+    /// - `pastes`: Paste rows to insert.
+    ///
+    /// # Returns
+    /// `Ok(())` when the write commits.
+    ///
+    /// # Errors
+    /// Returns `BadRequest` naming the folder when any folder is missing or
+    /// being deleted, and an error when any id already exists, serialization fails, or storage operations fail.
+    #[tracing::instrument(level = "debug", name = "folder_txn.create_pastes", skip_all, fields(count = pastes.len()))]
+    pub fn create_pastes(db: &Database, pastes: &[Paste]) -> Result<(), AppError> {
+        let _guard = Self::acquire_folder_txn_guard(db)?;
+        let write_txn = db.db.begin_write()?;
+        {
+            let mut paste_rows = write_txn.open_table(PASTES)?;
+            let mut metas = write_txn.open_table(PASTES_META)?;
+            let mut updated = write_txn.open_table(PASTES_BY_UPDATED)?;
+            let mut filters = FilterIndexTables::open(&write_txn)?;
+            let mut folders = write_txn.open_table(FOLDERS)?;
+            let deleting = write_txn.open_table(FOLDERS_DELETING)?;
+
+            for paste in pastes {
+                if let Some(folder_id) = paste.folder_id.as_deref() {
+                    // A batch has no single request folder, so name the missing one here.
+                    ensure_folder_assignable_in_txn(&folders, &deleting, folder_id)
+                        .map_err(|err| map_missing_folder_for_request(err, folder_id, "Folder"))?;
+                }
+                if paste_rows.get(paste.id.as_str())?.is_some() {
+                    return Err(AppError::StorageMessage(format!(
+                        "Paste id '{}' already exists",
+                        paste.id
+                    )));
+                }
+
+                persist_paste_with_indexes_and_folder_counts(
+                    &mut paste_rows,
+                    &mut metas,
+                    &mut updated,
+                    &mut filters,
+                    &mut folders,
+                    paste,
+                    PersistPasteIndexUpdate {
+                        old_recency_key: None,
+                        old_filter_keys: None,
+                        old_folder_id: None,
+                        old_content_len: 0,
+                        new_folder_id: paste.folder_id.as_deref(),
+                    },
+                )?;
+            }
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// Atomically insert a merged paste and delete the pastes it replaces.
+    ///
+    /// Either the merged row is inserted and every listed paste is removed, or
+    /// nothing changes.
+    ///
+    /// # Arguments
+    /// - `db`: Open database handle.
+    /// - `_folder_guard`: Active folder transaction guard for this critical section.
+    /// - `merged`: Paste row to insert; its `folder_id` must be assignable.
+    /// - `delete_ids`: Paste ids to remove in the same write.
+    ///
+    /// # Returns
+    /// `Ok(())` when the write commits.
+    ///
+    /// # Errors
+    /// Returns `NotFound` when any id in `delete_ids` is missing, `BadRequest`
+    /// naming the folder when the destination folder is missing or being
+    /// deleted, and an error when the merged id already exists or storage
+    /// operations fail.
+    #[tracing::instrument(level = "debug", name = "folder_txn.merge_pastes", skip_all, fields(id = %merged.id, deleted = delete_ids.len()))]
+    pub fn merge_pastes_locked(
+        db: &Database,
+        _folder_guard: &FolderTxnGuard<'_>,
+        merged: &Paste,
+        delete_ids: &[String],
+    ) -> Result<(), AppError> {
+        let write_txn = db.db.begin_write()?;
+        {
+            let mut paste_rows = write_txn.open_table(PASTES)?;
+            let mut metas = write_txn.open_table(PASTES_META)?;
+            let mut updated = write_txn.open_table(PASTES_BY_UPDATED)?;
+            let mut filters = FilterIndexTables::open(&write_txn)?;
+            let mut folders = write_txn.open_table(FOLDERS)?;
+            let deleting = write_txn.open_table(FOLDERS_DELETING)?;
+
+            if let Some(folder_id) = merged.folder_id.as_deref() {
+                ensure_folder_assignable_in_txn(&folders, &deleting, folder_id)
+                    .map_err(|err| map_missing_folder_for_request(err, folder_id, "Folder"))?;
+            }
+            if paste_rows.get(merged.id.as_str())?.is_some() {
+                return Err(AppError::StorageMessage(format!(
+                    "Paste id '{}' already exists",
+                    merged.id
+                )));
+            }
+            persist_paste_with_indexes_and_folder_counts(
+                &mut paste_rows,
+                &mut metas,
+                &mut updated,
+                &mut filters,
+                &mut folders,
+                merged,
+                PersistPasteIndexUpdate {
+                    old_recency_key: None,
+                    old_filter_keys: None,
+                    old_folder_id: None,
+                    old_content_len: 0,
+                    new_folder_id: merged.folder_id.as_deref(),
+                },
+            )?;
+        }
+        for paste_id in delete_ids {
+            if delete_paste_in_txn(&write_txn, paste_id)?.is_none() {
+                return Err(AppError::NotFound);
+            }
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
     /// Atomically delete a paste and decrement folder count when applicable.
     ///
     /// # Arguments
@@ -243,7 +484,7 @@ impl TransactionOps {
     ///
     /// # Arguments
     /// - `db`: Open database handle.
-    /// - `_folder_guard`: Active folder transaction guard for this critical section.
+    /// - `folder_guard`: Active folder transaction guard for this critical section.
     /// - `paste_id`: Paste id to remove.
     ///
     /// # Returns
@@ -251,48 +492,40 @@ impl TransactionOps {
     ///
     /// # Errors
     /// Returns an error when storage access or deserialization fails.
+    #[tracing::instrument(level = "debug", name = "folder_txn.delete_paste", skip_all, fields(id = %paste_id))]
     pub fn delete_paste_with_folder_locked(
         db: &Database,
-        _folder_guard: &FolderTxnGuard<'_>,
+        folder_guard: &FolderTxnGuard<'_>,
         paste_id: &str,
     ) -> Result<bool, AppError> {
-        let write_txn = db.db.begin_write()?;
-        let deleted = {
-            let mut pastes = write_txn.open_table(PASTES)?;
-            let mut metas = write_txn.open_table(PASTES_META)?;
-            let mut updated = write_txn.open_table(PASTES_BY_UPDATED)?;
-            let mut versions_meta = write_txn.open_table(PASTE_VERSIONS_META)?;
-            let mut versions_content = write_txn.open_table(PASTE_VERSIONS_CONTENT)?;
-            let mut folders = write_txn.open_table(FOLDERS)?;
-
-            let Some(old_guard) = pastes.get(paste_id)? else {
-                return Ok(false);
-            };
-            let paste = deserialize_paste(old_guard.value())?;
-            let old_recency_key = reverse_timestamp_key(paste.updated_at);
-            let old_folder_id = paste.folder_id;
-            drop(old_guard);
-
-            let _ = updated.remove((old_recency_key, paste_id))?;
-            let _ = pastes.remove(paste_id)?;
-            let _ = metas.remove(paste_id)?;
-            let version_items = decode_version_meta_list(
-                versions_meta
-                    .get(paste_id)?
-                    .as_ref()
-                    .map(|value| value.value()),
-            )?;
-            for version in version_items {
-                let _ = versions_content.remove((paste_id, version.version_id_ms))?;
-            }
-            let _ = versions_meta.remove(paste_id)?;
-
-            apply_folder_count_transition(&mut folders, old_folder_id.as_deref(), None)?;
-            true
-        };
+        Ok(Self::take_paste_with_folder_locked(db, folder_guard, paste_id)?.is_some())
+    }
 
-        write_txn.commit()?;
-        Ok(deleted)
+    /// Read and delete a paste in one write transaction while holding a folder
+    /// transaction guard, so no other caller can observe it afterwards.
+    ///
+    /// # Arguments
+    /// - `db`: Open database handle.
+    /// - `_folder_guard`: Active folder transaction guard for this critical section.
+    /// - `paste_id`: Paste id to remove.
+    ///
+    /// # Returns
+    /// `Ok(Some(paste))` with the removed paste, `Ok(None)` when missing.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or deserialization fails.
+    #[tracing::instrument(level = "debug", name = "folder_txn.take_paste", skip_all, fields(id = %paste_id))]
+    pub fn take_paste_with_folder_locked(
+        db: &Database,
+        _folder_guard: &FolderTxnGuard<'_>,
+        paste_id: &str,
+    ) -> Result<Option<Paste>, AppError> {
+        let write_txn = db.db.begin_write()?;
+        let taken = delete_paste_in_txn(&write_txn, paste_id)?;
+        if taken.is_some() {
+            write_txn.commit()?;
+        }
+        Ok(taken)
     }
 
     /// Atomically move a paste between folders while applying additional updates.
@@ -347,6 +580,7 @@ impl TransactionOps {
     /// # Errors
     /// Returns an error when destination assignment is invalid, or when storage /
     /// serialization operations fail.
+    #[tracing::instrument(level = "debug", name = "folder_txn.move_paste", skip_all, fields(id = %paste_id))]
     pub fn move_paste_between_folders_locked(
         db: &Database,
         _folder_guard: &FolderTxnGuard<'_>,
@@ -362,6 +596,7 @@ impl TransactionOps {
             let mut updated = write_txn.open_table(PASTES_BY_UPDATED)?;
             let mut versions_meta = write_txn.open_table(PASTE_VERSIONS_META)?;
             let mut versions_content = write_txn.open_table(PASTE_VERSIONS_CONTENT)?;
+            let mut filters = FilterIndexTables::open(&write_txn)?;
             let mut folders = write_txn.open_table(FOLDERS)?;
             let deleting = write_txn.open_table(FOLDERS_DELETING)?;
 
@@ -372,6 +607,7 @@ impl TransactionOps {
             let old_folder_id = paste.folder_id.clone();
             let folder_changing = old_folder_id.as_deref() != new_folder_id;
             let old_recency_key = reverse_timestamp_key(paste.updated_at);
+            let old_filter_keys = FilterIndexKeys::from_paste(&paste);
             drop(old_guard);
 
             if folder_changing {
@@ -390,7 +626,7 @@ impl TransactionOps {
                     .as_ref()
                     .map(|value| value.value()),
             )?;
-            apply_update_request(&mut paste, &update_req);
+            apply_update_request(&mut paste, &update_req, db.pastes.normalization());
             paste.folder_id = new_folder_id.map(ToString::to_string);
             let content_changed = paste.content != old_content;
 
@@ -413,17 +649,26 @@ impl TransactionOps {
                     let encoded_versions = encode_version_meta_list(&version_items)?;
                     versions_meta.insert(paste_id, encoded_versions.as_slice())?;
                 }
+                super::annotations::reanchor_paste_annotations(
+                    &write_txn,
+                    paste_id,
+                    old_content.as_str(),
+                    paste.content.as_str(),
+                )?;
             }
 
             persist_paste_with_indexes_and_folder_counts(
                 &mut pastes,
                 &mut metas,
                 &mut updated,
+                &mut filters,
                 &mut folders,
                 &paste,
                 PersistPasteIndexUpdate {
                     old_recency_key: Some(old_recency_key),
+                    old_filter_keys: Some(&old_filter_keys),
                     old_folder_id: old_folder_id_ref,
+                    old_content_len: old_content.len(),
                     new_folder_id,
                 },
             )?;
@@ -434,4 +679,115 @@ impl TransactionOps {
         write_txn.commit()?;
         Ok(updated_paste)
     }
+
+    /// Rename or strip one tag on every paste carrying it, in one write.
+    ///
+    /// `tag` matches case-insensitively. Each touched paste gets a new
+    /// `updated_at`; a paste that ends up with the replacement twice keeps the
+    /// first copy.
+    ///
+    /// # Arguments
+    /// - `db`: Open database handle.
+    /// - `_folder_guard`: Active folder transaction guard for this critical section.
+    /// - `tag`: Tag to rewrite.
+    /// - `replacement`: New tag text, or `None` to remove the tag.
+    ///
+    /// # Returns
+    /// Ids of the pastes that changed.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or serialization fails.
+    #[tracing::instrument(level = "debug", name = "folder_txn.rewrite_tag", skip_all)]
+    pub fn rewrite_tag_locked(
+        db: &Database,
+        _folder_guard: &FolderTxnGuard<'_>,
+        tag: &str,
+        replacement: Option<&str>,
+    ) -> Result<Vec<String>, AppError> {
+        let Some(tag) = normalize_tag_filter(Some(tag)) else {
+            return Ok(Vec::new());
+        };
+        let replacement_key = normalize_tag_filter(replacement);
+        let now = Utc::now();
+        let write_txn = db.db.begin_write()?;
+        let mut changed_ids = Vec::new();
+        {
+            let tagged_ids: Vec<String> = {
+                let index = write_txn.open_table(PASTES_BY_TAG)?;
+                let mut ids = Vec::new();
+                for item in index.range((tag.as_str(), 0u64, "")..)? {
+                    let (key, _) = item?;
+                    let (indexed_tag, _, paste_id) = key.value();
+                    if indexed_tag != tag {
+                        break;
+                    }
+                    ids.push(paste_id.to_string());
+                }
+                ids
+            };
+            let mut paste_rows = write_txn.open_table(PASTES)?;
+            let mut metas = write_txn.open_table(PASTES_META)?;
+            let mut updated = write_txn.open_table(PASTES_BY_UPDATED)?;
+            let mut filters = FilterIndexTables::open(&write_txn)?;
+            let mut folders = write_txn.open_table(FOLDERS)?;
+
+            for paste_id in tagged_ids {
+                let Some(mut paste) = paste_rows
+                    .get(paste_id.as_str())?
+                    .map(|guard| deserialize_paste(guard.value()))
+                    .transpose()?
+                else {
+                    continue;
+                };
+                let mut seen_replacement = false;
+                let tags: Vec<String> = paste
+                    .tags
+                    .iter()
+                    .filter_map(|existing| {
+                        let key = normalize_tag_filter(Some(existing));
+                        let value = if key.as_deref() == Some(tag.as_str()) {
+                            replacement.map(|text| text.trim().to_string())?
+                        } else {
+                            existing.clone()
+                        };
+                        if replacement_key.is_some()
+                            && normalize_tag_filter(Some(&value)) == replacement_key
+                        {
+                            if seen_replacement {
+                                return None;
+                            }
+                            seen_replacement = true;
+                        }
+                        Some(value)
+                    })
+                    .collect();
+                if tags == paste.tags {
+                    continue;
+                }
+                let old_recency_key = reverse_timestamp_key(paste.updated_at);
+                let old_filter_keys = FilterIndexKeys::from_paste(&paste);
+                paste.tags = tags;
+                paste.updated_at = now;
+                let folder_id = paste.folder_id.clone();
+                persist_paste_with_indexes_and_folder_counts(
+                    &mut paste_rows,
+                    &mut metas,
+                    &mut updated,
+                    &mut filters,
+                    &mut folders,
+                    &paste,
+                    PersistPasteIndexUpdate {
+                        old_recency_key: Some(old_recency_key),
+                        old_filter_keys: Some(&old_filter_keys),
+                        old_folder_id: folder_id.as_deref(),
+                        old_content_len: paste.content.len(),
+                        new_folder_id: folder_id.as_deref(),
+                    },
+                )?;
+                changed_ids.push(paste_id);
+            }
+        }
+        write_txn.commit()?;
+        Ok(changed_ids)
+    }
 }
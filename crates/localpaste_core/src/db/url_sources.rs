@@ -0,0 +1,301 @@
+//! Source URLs that pastes mirror, with their refresh schedules.
+//!
+//! Kept beside the paste row rather than on it, so linking a URL never
+//! rewrites paste content or metadata, and deleting a paste drops its
+//! source in the same transaction. Fetching lives with the callers; this
+//! module only stores the link and the outcome of the last attempt.
+
+use crate::{
+    db::tables::{PASTES, PASTE_URL_SOURCES},
+    error::AppError,
+    models::url_source::{
+        UrlSource, MAX_REFRESH_INTERVAL_SECS, MAX_SOURCE_ERROR_CHARS, MAX_SOURCE_URL_CHARS,
+        MIN_REFRESH_INTERVAL_SECS,
+    },
+};
+use chrono::{DateTime, Utc};
+use redb::{ReadableDatabase, ReadableTable};
+use std::sync::Arc;
+
+/// Remove the source link of `paste_id` inside an open write transaction.
+///
+/// # Errors
+/// Returns an error when the source table cannot be written.
+pub(crate) fn remove_paste_url_source(
+    write_txn: &redb::WriteTransaction,
+    paste_id: &str,
+) -> Result<(), AppError> {
+    write_txn.open_table(PASTE_URL_SOURCES)?.remove(paste_id)?;
+    Ok(())
+}
+
+/// Trim a source URL and require an `http`/`https` scheme with a host.
+fn validated_url(url: &str) -> Result<String, AppError> {
+    let url = url.trim();
+    if url.chars().count() > MAX_SOURCE_URL_CHARS {
+        return Err(AppError::BadRequest(format!(
+            "Source URL exceeds {} characters",
+            MAX_SOURCE_URL_CHARS
+        )));
+    }
+    let lower = url.to_ascii_lowercase();
+    let rest = lower
+        .strip_prefix("https://")
+        .or_else(|| lower.strip_prefix("http://"));
+    let has_host = rest.is_some_and(|rest| !rest.starts_with(['/', '?', '#']) && !rest.is_empty());
+    if !has_host || url.chars().any(char::is_whitespace) {
+        return Err(AppError::BadRequest(
+            "Source URL must be an http or https URL".to_string(),
+        ));
+    }
+    Ok(url.to_string())
+}
+
+fn validated_interval(interval: Option<u64>) -> Result<Option<u64>, AppError> {
+    match interval {
+        Some(secs) if !(MIN_REFRESH_INTERVAL_SECS..=MAX_REFRESH_INTERVAL_SECS).contains(&secs) => {
+            Err(AppError::BadRequest(format!(
+                "refresh_interval_secs must be between {} and {}",
+                MIN_REFRESH_INTERVAL_SECS, MAX_REFRESH_INTERVAL_SECS
+            )))
+        }
+        other => Ok(other),
+    }
+}
+
+/// Accessor for the paste source URL table.
+pub struct UrlSourceDb {
+    db: Arc<redb::Database>,
+}
+
+impl UrlSourceDb {
+    /// Initialize the source URL table if it does not exist yet.
+    ///
+    /// # Returns
+    /// A new [`UrlSourceDb`] accessor bound to `db`.
+    ///
+    /// # Errors
+    /// Returns an error when redb transaction/table initialization fails.
+    pub fn new(db: Arc<redb::Database>) -> Result<Self, AppError> {
+        let write_txn = db.begin_write()?;
+        write_txn.open_table(PASTE_URL_SOURCES)?;
+        write_txn.commit()?;
+        Ok(Self { db })
+    }
+
+    /// Fetch the source link of one paste.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn get(&self, paste_id: &str) -> Result<Option<UrlSource>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(PASTE_URL_SOURCES)?;
+        table
+            .get(paste_id)?
+            .map(|row| bincode::deserialize::<UrlSource>(row.value()))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Sources whose scheduled refresh is due at `now`, most overdue first.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn due(&self, now: DateTime<Utc>) -> Result<Vec<UrlSource>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(PASTE_URL_SOURCES)?;
+        let mut due = Vec::new();
+        for entry in table.iter()? {
+            let (_, value) = entry?;
+            let source = bincode::deserialize::<UrlSource>(value.value())?;
+            if source.is_due(now) {
+                due.push(source);
+            }
+        }
+        due.sort_by_key(|source| source.next_refresh_at());
+        Ok(due)
+    }
+
+    /// Link a paste to a source URL, replacing any earlier link.
+    ///
+    /// Keeping the same URL only changes the schedule; a new URL starts over
+    /// with no fetch history.
+    ///
+    /// # Returns
+    /// The stored source link.
+    ///
+    /// # Errors
+    /// Returns `NotFound` when the paste is missing, `BadRequest` for a
+    /// non-http(s) URL or an interval outside
+    /// [`MIN_REFRESH_INTERVAL_SECS`]..=[`MAX_REFRESH_INTERVAL_SECS`], or an
+    /// error when storage fails.
+    pub fn set(
+        &self,
+        paste_id: &str,
+        url: &str,
+        refresh_interval_secs: Option<u64>,
+    ) -> Result<UrlSource, AppError> {
+        let url = validated_url(url)?;
+        let refresh_interval_secs = validated_interval(refresh_interval_secs)?;
+        let write_txn = self.db.begin_write()?;
+        let source = {
+            if write_txn.open_table(PASTES)?.get(paste_id)?.is_none() {
+                return Err(AppError::NotFound);
+            }
+            let mut table = write_txn.open_table(PASTE_URL_SOURCES)?;
+            let existing = table
+                .get(paste_id)?
+                .map(|row| bincode::deserialize::<UrlSource>(row.value()))
+                .transpose()?
+                .filter(|existing| existing.url == url);
+            let source = match existing {
+                Some(existing) => UrlSource {
+                    refresh_interval_secs,
+                    ..existing
+                },
+                None => UrlSource {
+                    paste_id: paste_id.to_string(),
+                    url,
+                    refresh_interval_secs,
+                    created_at: Utc::now(),
+                    last_checked_at: None,
+                    last_changed_at: None,
+                    last_error: None,
+                },
+            };
+            let encoded = bincode::serialize(&source)?;
+            table.insert(paste_id, encoded.as_slice())?;
+            source
+        };
+        write_txn.commit()?;
+        Ok(source)
+    }
+
+    /// Record the outcome of a fetch attempt made at `checked_at`.
+    ///
+    /// # Returns
+    /// The updated link, or `Ok(None)` when it was removed meanwhile.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn record_check(
+        &self,
+        paste_id: &str,
+        checked_at: DateTime<Utc>,
+        changed: bool,
+        error: Option<&str>,
+    ) -> Result<Option<UrlSource>, AppError> {
+        let write_txn = self.db.begin_write()?;
+        let updated = {
+            let mut table = write_txn.open_table(PASTE_URL_SOURCES)?;
+            let existing = table
+                .get(paste_id)?
+                .map(|row| bincode::deserialize::<UrlSource>(row.value()))
+                .transpose()?;
+            let Some(mut source) = existing else {
+                return Ok(None);
+            };
+            source.last_checked_at = Some(checked_at);
+            if changed {
+                source.last_changed_at = Some(checked_at);
+            }
+            source.last_error =
+                error.map(|error| error.chars().take(MAX_SOURCE_ERROR_CHARS).collect());
+            let encoded = bincode::serialize(&source)?;
+            table.insert(paste_id, encoded.as_slice())?;
+            source
+        };
+        write_txn.commit()?;
+        Ok(Some(updated))
+    }
+
+    /// Unlink a paste from its source URL.
+    ///
+    /// # Returns
+    /// `Ok(true)` when a link was removed, `Ok(false)` when none existed.
+    ///
+    /// # Errors
+    /// Returns an error when storage operations fail.
+    pub fn clear(&self, paste_id: &str) -> Result<bool, AppError> {
+        let write_txn = self.db.begin_write()?;
+        let removed = write_txn
+            .open_table(PASTE_URL_SOURCES)?
+            .remove(paste_id)?
+            .is_some();
+        write_txn.commit()?;
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::paste::Paste;
+    use crate::test_support::setup_temp_db;
+    use chrono::Duration;
+
+    #[test]
+    fn source_links_validate_schedule_and_follow_the_paste() {
+        let (db, _temp) = setup_temp_db();
+        let paste = Paste::new("a = 1".to_string(), "remote.toml".to_string());
+        db.pastes.create(&paste).expect("paste");
+
+        for bad in [
+            "ftp://example.com/a",
+            "https:///a",
+            "file:///etc/passwd",
+            "no scheme",
+        ] {
+            assert!(
+                matches!(
+                    db.url_sources.set(&paste.id, bad, None),
+                    Err(AppError::BadRequest(_))
+                ),
+                "{bad}"
+            );
+        }
+        assert!(matches!(
+            db.url_sources
+                .set(&paste.id, "https://example.com/a", Some(5)),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(matches!(
+            db.url_sources.set("missing", "https://example.com/a", None),
+            Err(AppError::NotFound)
+        ));
+
+        let source = db
+            .url_sources
+            .set(&paste.id, " https://example.com/a.toml ", Some(3600))
+            .expect("set");
+        assert_eq!(source.url, "https://example.com/a.toml");
+        let now = source.created_at;
+        assert!(db.url_sources.due(now).expect("due").is_empty());
+        let later = now + Duration::seconds(3600);
+        assert_eq!(db.url_sources.due(later).expect("due").len(), 1);
+
+        let checked = db
+            .url_sources
+            .record_check(&paste.id, later, false, Some("timed out"))
+            .expect("record")
+            .expect("source");
+        assert_eq!(checked.last_error.as_deref(), Some("timed out"));
+        assert!(checked.last_changed_at.is_none());
+        assert!(db.url_sources.due(later).expect("due").is_empty());
+
+        let rescheduled = db
+            .url_sources
+            .set(&paste.id, "https://example.com/a.toml", None)
+            .expect("set");
+        assert_eq!(rescheduled.last_checked_at, Some(later));
+        let relinked = db
+            .url_sources
+            .set(&paste.id, "https://example.com/b.toml", Some(60))
+            .expect("set");
+        assert!(relinked.last_checked_at.is_none());
+
+        assert!(db.pastes.delete(&paste.id).expect("delete paste"));
+        assert!(db.url_sources.get(&paste.id).expect("get").is_none());
+        assert!(!db.url_sources.clear(&paste.id).expect("clear"));
+    }
+}
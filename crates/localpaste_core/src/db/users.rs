@@ -0,0 +1,351 @@
+//! User accounts, token lookup, and paste ownership for multi-user mode.
+//!
+//! Ownership lives in its own table keyed by paste id so paste rows keep their
+//! single-user layout; pastes created outside multi-user mode have no row.
+
+use crate::{
+    config::{parse_bool_env, parse_bool_env_strict},
+    db::tables::{PASTE_ACCESS, USERS, USER_TOKENS},
+    error::AppError,
+    models::user::{CreatedUser, PasteAccess, User, UserRole},
+};
+use chrono::Utc;
+use redb::{ReadableDatabase, ReadableTable};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Prefix that makes LocalPaste tokens recognizable in config files and logs.
+const TOKEN_PREFIX: &str = "lp_";
+/// Env var that turns on multi-user mode.
+pub const MULTI_USER_ENV: &str = "LOCALPASTE_MULTI_USER";
+/// Env var holding the bootstrap admin token.
+pub const ADMIN_TOKEN_ENV: &str = "LOCALPASTE_ADMIN_TOKEN";
+/// Shortest bootstrap admin token accepted.
+const MIN_ADMIN_TOKEN_CHARS: usize = 16;
+/// Id reported for requests authenticated with the bootstrap admin token.
+pub const BOOTSTRAP_ADMIN_ID: &str = "admin";
+
+/// Multi-user settings loaded from the environment.
+///
+/// Disabled by default, which keeps every API route open to local callers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MultiUserOptions {
+    pub enabled: bool,
+    /// Hash of `LOCALPASTE_ADMIN_TOKEN`, which authenticates as an admin
+    /// without a stored account so the first users can be created.
+    admin_token_hash: Option<String>,
+}
+
+impl MultiUserOptions {
+    /// Load options from the environment, falling back to defaults on bad values.
+    ///
+    /// # Returns
+    /// Parsed options; a too-short admin token is logged and ignored.
+    pub fn from_env() -> Self {
+        Self {
+            enabled: parse_bool_env(MULTI_USER_ENV, false),
+            admin_token_hash: read_admin_token().unwrap_or_else(|message| {
+                tracing::warn!("{}; ignoring it", message);
+                None
+            }),
+        }
+    }
+
+    /// Load options from the environment, rejecting malformed values.
+    ///
+    /// # Errors
+    /// Returns a message when the flag is malformed or the admin token is too short.
+    pub fn from_env_strict() -> Result<Self, String> {
+        Ok(Self {
+            enabled: parse_bool_env_strict(MULTI_USER_ENV, false)?,
+            admin_token_hash: read_admin_token()?,
+        })
+    }
+}
+
+fn read_admin_token() -> Result<Option<String>, String> {
+    let Ok(raw) = std::env::var(ADMIN_TOKEN_ENV) else {
+        return Ok(None);
+    };
+    let token = raw.trim();
+    if token.is_empty() {
+        return Ok(None);
+    }
+    if token.chars().count() < MIN_ADMIN_TOKEN_CHARS {
+        return Err(format!(
+            "{} must be at least {} characters",
+            ADMIN_TOKEN_ENV, MIN_ADMIN_TOKEN_CHARS
+        ));
+    }
+    Ok(Some(token_hash(token)))
+}
+
+/// Hash stored in place of a bearer token.
+pub(crate) fn token_hash(token: &str) -> String {
+    blake3::hash(token.as_bytes()).to_hex().to_string()
+}
+
+fn generate_token() -> String {
+    let bytes: [u8; 32] = rand::random();
+    let mut token = String::with_capacity(TOKEN_PREFIX.len() + bytes.len() * 2);
+    token.push_str(TOKEN_PREFIX);
+    for byte in bytes {
+        token.push_str(&format!("{:02x}", byte));
+    }
+    token
+}
+
+/// Accessor for the user, token, and paste-access tables.
+pub struct UserDb {
+    db: Arc<redb::Database>,
+    options: MultiUserOptions,
+}
+
+impl UserDb {
+    /// Initialize the user tables if they do not exist yet.
+    ///
+    /// # Returns
+    /// A new [`UserDb`] accessor bound to `db`.
+    ///
+    /// # Errors
+    /// Returns an error when redb transaction/table initialization fails.
+    pub fn new(db: Arc<redb::Database>) -> Result<Self, AppError> {
+        let write_txn = db.begin_write()?;
+        write_txn.open_table(USERS)?;
+        write_txn.open_table(USER_TOKENS)?;
+        write_txn.open_table(PASTE_ACCESS)?;
+        write_txn.commit()?;
+        Ok(Self {
+            db,
+            options: MultiUserOptions::from_env(),
+        })
+    }
+
+    /// Multi-user settings loaded when the database was opened.
+    pub fn options(&self) -> &MultiUserOptions {
+        &self.options
+    }
+
+    /// Create a user with a freshly generated token.
+    ///
+    /// # Returns
+    /// The stored user plus its token; the token is not recoverable later.
+    ///
+    /// # Errors
+    /// Returns `BadRequest` for a blank name, `Conflict` when another user has
+    /// the same name (case-insensitive), or an error when storage fails.
+    pub fn create(&self, name: &str, role: UserRole) -> Result<CreatedUser, AppError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::BadRequest(
+                "User name cannot be empty".to_string(),
+            ));
+        }
+        if self
+            .list()?
+            .iter()
+            .any(|user| user.name.eq_ignore_ascii_case(name))
+        {
+            return Err(AppError::Conflict(format!(
+                "A user named '{}' already exists",
+                name
+            )));
+        }
+        let user = User {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            role,
+            created_at: Utc::now(),
+        };
+        let token = generate_token();
+        let encoded = bincode::serialize(&user)?;
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut users = write_txn.open_table(USERS)?;
+            let mut tokens = write_txn.open_table(USER_TOKENS)?;
+            users.insert(user.id.as_str(), encoded.as_slice())?;
+            tokens.insert(token_hash(&token).as_str(), user.id.as_str())?;
+        }
+        write_txn.commit()?;
+        Ok(CreatedUser { user, token })
+    }
+
+    /// List every user, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn list(&self) -> Result<Vec<User>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let users = read_txn.open_table(USERS)?;
+        let mut items = Vec::new();
+        for entry in users.iter()? {
+            let (_, value) = entry?;
+            items.push(bincode::deserialize::<User>(value.value())?);
+        }
+        items.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(items)
+    }
+
+    /// Delete a user and revoke its tokens.
+    ///
+    /// Pastes the user owned keep their owner id and stay reachable by admins.
+    ///
+    /// # Returns
+    /// `Ok(true)` when a user was removed, `Ok(false)` when none existed.
+    ///
+    /// # Errors
+    /// Returns an error when storage operations fail.
+    pub fn delete(&self, id: &str) -> Result<bool, AppError> {
+        let write_txn = self.db.begin_write()?;
+        let removed = {
+            let mut users = write_txn.open_table(USERS)?;
+            let mut tokens = write_txn.open_table(USER_TOKENS)?;
+            let removed = users.remove(id)?.is_some();
+            let revoked: Vec<String> = tokens
+                .iter()?
+                .filter_map(|entry| entry.ok())
+                .filter(|(_, user_id)| user_id.value() == id)
+                .map(|(hash, _)| hash.value().to_string())
+                .collect();
+            for hash in revoked {
+                tokens.remove(hash.as_str())?;
+            }
+            removed
+        };
+        write_txn.commit()?;
+        Ok(removed)
+    }
+
+    /// Resolve the user a bearer token belongs to.
+    ///
+    /// # Returns
+    /// `Ok(None)` when the token is unknown or its user was deleted.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn authenticate(&self, token: &str) -> Result<Option<User>, AppError> {
+        let hash = token_hash(token);
+        if self.options.admin_token_hash.as_deref() == Some(hash.as_str()) {
+            return Ok(Some(User {
+                id: BOOTSTRAP_ADMIN_ID.to_string(),
+                name: BOOTSTRAP_ADMIN_ID.to_string(),
+                role: UserRole::Admin,
+                created_at: Utc::now(),
+            }));
+        }
+        let read_txn = self.db.begin_read()?;
+        let tokens = read_txn.open_table(USER_TOKENS)?;
+        let Some(user_id) = tokens.get(hash.as_str())? else {
+            return Ok(None);
+        };
+        let users = read_txn.open_table(USERS)?;
+        let user = users.get(user_id.value())?;
+        user.map(|row| bincode::deserialize::<User>(row.value()))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Owner and visibility recorded for a paste.
+    ///
+    /// # Returns
+    /// `Ok(None)` for pastes created outside multi-user mode.
+    ///
+    /// # Errors
+    /// Returns an error when storage access or decoding fails.
+    pub fn paste_access(&self, paste_id: &str) -> Result<Option<PasteAccess>, AppError> {
+        let read_txn = self.db.begin_read()?;
+        let access = read_txn.open_table(PASTE_ACCESS)?;
+        access
+            .get(paste_id)?
+            .map(|row| bincode::deserialize::<PasteAccess>(row.value()))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Record `owner_id` as the owner of a new, unshared paste.
+    ///
+    /// # Errors
+    /// Returns an error when serialization or storage fails.
+    pub fn set_paste_owner(&self, paste_id: &str, owner_id: &str) -> Result<(), AppError> {
+        self.put_access(
+            paste_id,
+            &PasteAccess {
+                owner_id: owner_id.to_string(),
+                shared: false,
+            },
+        )
+    }
+
+    /// Change whether other users may read a paste.
+    ///
+    /// # Returns
+    /// The updated record, or `Ok(None)` when the paste has no owner.
+    ///
+    /// # Errors
+    /// Returns an error when serialization or storage fails.
+    pub fn set_paste_shared(
+        &self,
+        paste_id: &str,
+        shared: bool,
+    ) -> Result<Option<PasteAccess>, AppError> {
+        let Some(mut access) = self.paste_access(paste_id)? else {
+            return Ok(None);
+        };
+        access.shared = shared;
+        self.put_access(paste_id, &access)?;
+        Ok(Some(access))
+    }
+
+    fn put_access(&self, paste_id: &str, access: &PasteAccess) -> Result<(), AppError> {
+        let encoded = bincode::serialize(access)?;
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(PASTE_ACCESS)?;
+            table.insert(paste_id, encoded.as_slice())?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::paste::Paste;
+    use crate::test_support::setup_temp_db;
+
+    #[test]
+    fn tokens_authenticate_until_user_is_deleted_and_access_follows_ownership() {
+        let (db, _temp) = setup_temp_db();
+        let alice = db.users.create("alice", UserRole::Member).expect("create");
+        assert!(alice.token.starts_with(TOKEN_PREFIX));
+        assert!(matches!(
+            db.users.create("ALICE", UserRole::Member),
+            Err(AppError::Conflict(_))
+        ));
+        let bob = db.users.create("bob", UserRole::Member).expect("create");
+
+        let paste = Paste::new("notes".to_string(), "notes".to_string());
+        db.pastes.create(&paste).expect("paste");
+        db.users
+            .set_paste_owner(&paste.id, &alice.user.id)
+            .expect("owner");
+        let access = db.users.paste_access(&paste.id).expect("access");
+        assert!(alice.user.can_write(access.as_ref()));
+        assert!(!bob.user.can_read(access.as_ref()));
+
+        let access = db.users.set_paste_shared(&paste.id, true).expect("share");
+        assert!(bob.user.can_read(access.as_ref()));
+        assert!(!bob.user.can_write(access.as_ref()));
+
+        assert_eq!(
+            db.users.authenticate(&bob.token).expect("auth"),
+            Some(bob.user.clone())
+        );
+        assert!(db.users.delete(&bob.user.id).expect("delete"));
+        assert_eq!(db.users.authenticate(&bob.token).expect("auth"), None);
+
+        assert!(db.pastes.delete(&paste.id).expect("delete paste"));
+        assert_eq!(db.users.paste_access(&paste.id).expect("access"), None);
+    }
+}
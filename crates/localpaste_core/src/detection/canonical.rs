@@ -1,172 +1,367 @@
-//! Language canonicalization and shared manual selection options.
+//! Language canonicalization shared by detection, API input, the GUI selector,
+//! and export file names.
 
-/// Manual language option metadata for UI selectors.
+/// One known language: its canonical value, display label, and the aliases and
+/// file extensions that resolve to it.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct ManualLanguageOption {
+pub struct LanguageSpec {
     pub label: &'static str,
     pub value: &'static str,
+    pub aliases: &'static [&'static str],
+    /// File extensions without the leading dot; the first is used for exports.
+    pub extensions: &'static [&'static str],
+    /// Whether the language is offered in manual selection menus.
+    pub manual: bool,
 }
 
-/// Sorted language options for manual selection.
-pub const MANUAL_LANGUAGE_OPTIONS: &[ManualLanguageOption] = &[
-    ManualLanguageOption {
+/// Known languages, sorted by label.
+pub const LANGUAGES: &[LanguageSpec] = &[
+    LanguageSpec {
         label: "C",
         value: "c",
+        aliases: &[],
+        extensions: &["c", "h"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "C++",
         value: "cpp",
+        aliases: &["c++", "cxx"],
+        extensions: &["cpp", "cc", "hpp", "hh"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "C#",
         value: "cs",
+        aliases: &["csharp", "c#"],
+        extensions: &["cs"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "CSS",
         value: "css",
+        aliases: &[],
+        extensions: &["css"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "Dart",
         value: "dart",
+        aliases: &[],
+        extensions: &["dart"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
+        label: "Diff",
+        value: "diff",
+        aliases: &["patch"],
+        extensions: &["diff", "patch"],
+        manual: false,
+    },
+    LanguageSpec {
+        label: "Dockerfile",
+        value: "dockerfile",
+        aliases: &["docker"],
+        extensions: &["dockerfile"],
+        manual: false,
+    },
+    LanguageSpec {
         label: "Elixir",
         value: "elixir",
+        aliases: &[],
+        extensions: &["ex", "exs"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "Go",
         value: "go",
+        aliases: &["golang"],
+        extensions: &["go"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
+        label: "Haskell",
+        value: "haskell",
+        aliases: &[],
+        extensions: &["hs"],
+        manual: false,
+    },
+    LanguageSpec {
         label: "HTML",
         value: "html",
+        aliases: &["htm", "xhtml"],
+        extensions: &["html", "htm"],
+        manual: true,
+    },
+    LanguageSpec {
+        label: "INI",
+        value: "ini",
+        aliases: &["cfg"],
+        extensions: &["ini", "cfg"],
+        manual: false,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "Java",
         value: "java",
+        aliases: &[],
+        extensions: &["java"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "JavaScript",
         value: "javascript",
+        aliases: &["node", "nodejs", "jsx", "mjs", "cjs", "ecmascript"],
+        extensions: &["js", "mjs", "cjs", "jsx"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "JSON",
         value: "json",
+        aliases: &["jsonl", "json5"],
+        extensions: &["json", "jsonl"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "Kotlin",
         value: "kotlin",
+        aliases: &[],
+        extensions: &["kt", "kts"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "LaTeX",
         value: "latex",
+        aliases: &["tex"],
+        extensions: &["tex"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
+        label: "Log",
+        value: "log",
+        aliases: &[],
+        extensions: &["log"],
+        manual: false,
+    },
+    LanguageSpec {
         label: "Lua",
         value: "lua",
+        aliases: &[],
+        extensions: &["lua"],
+        manual: true,
+    },
+    LanguageSpec {
+        label: "Makefile",
+        value: "makefile",
+        aliases: &["make"],
+        extensions: &["makefile", "mk"],
+        manual: false,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "Markdown",
         value: "markdown",
+        aliases: &["mdown"],
+        extensions: &["md", "markdown"],
+        manual: true,
+    },
+    LanguageSpec {
+        label: "Objective-C",
+        value: "objectivec",
+        aliases: &["objc", "objective-c"],
+        extensions: &["m", "mm"],
+        manual: false,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "Perl",
         value: "perl",
+        aliases: &[],
+        extensions: &["pl", "pm"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
+        label: "PHP",
+        value: "php",
+        aliases: &[],
+        extensions: &["php"],
+        manual: false,
+    },
+    LanguageSpec {
         label: "Plain text",
         value: "text",
+        aliases: &["plaintext", "plain text", "plain"],
+        extensions: &["txt"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "PowerShell",
         value: "powershell",
+        aliases: &["pwsh"],
+        extensions: &["ps1", "psm1"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "Python",
         value: "python",
+        aliases: &["python3", "py3"],
+        extensions: &["py", "pyi"],
+        manual: true,
+    },
+    LanguageSpec {
+        label: "R",
+        value: "r",
+        aliases: &[],
+        extensions: &["r"],
+        manual: false,
     },
-    ManualLanguageOption {
+    LanguageSpec {
+        label: "Ruby",
+        value: "ruby",
+        aliases: &[],
+        extensions: &["rb"],
+        manual: false,
+    },
+    LanguageSpec {
         label: "Rust",
         value: "rust",
+        aliases: &[],
+        extensions: &["rs"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "Sass",
         value: "sass",
+        aliases: &[],
+        extensions: &["sass"],
+        manual: true,
+    },
+    LanguageSpec {
+        label: "Scala",
+        value: "scala",
+        aliases: &[],
+        extensions: &["scala"],
+        manual: false,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "SCSS",
         value: "scss",
+        aliases: &[],
+        extensions: &["scss"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "Shell",
         value: "shell",
+        aliases: &["bash", "zsh", "ksh", "fish"],
+        extensions: &["sh", "bash", "zsh"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "SQL",
         value: "sql",
+        aliases: &["postgresql", "mysql", "sqlite"],
+        extensions: &["sql"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "Swift",
         value: "swift",
+        aliases: &[],
+        extensions: &["swift"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "TOML",
         value: "toml",
+        aliases: &[],
+        extensions: &["toml"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "TypeScript",
         value: "typescript",
+        aliases: &["tsx"],
+        extensions: &["ts", "tsx"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "XML",
         value: "xml",
+        aliases: &[],
+        extensions: &["xml"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "YAML",
         value: "yaml",
+        aliases: &[],
+        extensions: &["yaml", "yml"],
+        manual: true,
     },
-    ManualLanguageOption {
+    LanguageSpec {
         label: "Zig",
         value: "zig",
+        aliases: &[],
+        extensions: &["zig"],
+        manual: true,
     },
 ];
 
+/// Find the table entry a language value, alias, or file extension names.
+///
+/// Matching is case-insensitive and ignores surrounding whitespace.
+///
+/// # Returns
+/// The matching [`LanguageSpec`], or `None` for unknown input.
+pub fn lookup(language: &str) -> Option<&'static LanguageSpec> {
+    let lowered = language.trim().to_ascii_lowercase();
+    if lowered.is_empty() {
+        return None;
+    }
+    let key = lowered.as_str();
+    LANGUAGES
+        .iter()
+        .find(|spec| spec.value == key)
+        .or_else(|| LANGUAGES.iter().find(|spec| spec.aliases.contains(&key)))
+        .or_else(|| LANGUAGES.iter().find(|spec| spec.extensions.contains(&key)))
+}
+
+/// Find the language for a file extension, with or without the leading dot.
+///
+/// # Returns
+/// The matching [`LanguageSpec`], or `None` when no language claims it.
+pub fn from_extension(extension: &str) -> Option<&'static LanguageSpec> {
+    let lowered = extension
+        .trim()
+        .trim_start_matches('.')
+        .to_ascii_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|spec| spec.extensions.contains(&lowered.as_str()))
+}
+
 /// Convert aliases/legacy names to canonical labels.
 ///
 /// # Returns
 /// Canonical, lowercase label (or empty string for empty/whitespace input).
+/// Labels outside [`LANGUAGES`] are returned lowercased so detector output
+/// for rarer languages is kept.
 pub fn canonicalize(language: &str) -> String {
-    let lowered = language.trim().to_ascii_lowercase();
-    match lowered.as_str() {
-        "csharp" | "c#" => "cs".to_string(),
-        "c++" => "cpp".to_string(),
-        "bash" | "sh" | "zsh" => "shell".to_string(),
-        "pwsh" | "ps1" => "powershell".to_string(),
-        "yml" => "yaml".to_string(),
-        "jsonl" => "json".to_string(),
-        "js" => "javascript".to_string(),
-        "ts" => "typescript".to_string(),
-        "md" => "markdown".to_string(),
-        "plaintext" | "plain text" | "plain" | "txt" => "text".to_string(),
-        "py" => "python".to_string(),
-        "rs" => "rust".to_string(),
-        "rb" => "ruby".to_string(),
-        "kt" => "kotlin".to_string(),
-        "m" | "mm" | "objc" | "objective-c" => "objectivec".to_string(),
-        "pl" => "perl".to_string(),
-        "ex" | "exs" => "elixir".to_string(),
-        _ => lowered,
+    match lookup(language) {
+        Some(spec) => spec.value.to_string(),
+        None => language.trim().to_ascii_lowercase(),
     }
 }
 
+/// Languages offered in manual selection menus, sorted by label.
+pub fn manual_language_options() -> impl Iterator<Item = &'static LanguageSpec> {
+    LANGUAGES.iter().filter(|spec| spec.manual)
+}
+
 /// Find the friendly label for a canonical/manual language value.
 ///
 /// # Returns
 /// The display label for a known manual option, otherwise `None`.
 pub fn manual_option_label(value: &str) -> Option<&'static str> {
-    let canonical = canonicalize(value);
-    MANUAL_LANGUAGE_OPTIONS
-        .iter()
-        .find(|option| option.value == canonical)
-        .map(|option| option.label)
+    lookup(value)
+        .filter(|spec| spec.manual)
+        .map(|spec| spec.label)
 }
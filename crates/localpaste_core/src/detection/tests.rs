@@ -1,6 +1,6 @@
 //! Detection module tests for canonicalization, fallback heuristics, and Magika integration.
 
-use super::canonical::canonicalize;
+use super::canonical::{canonicalize, from_extension, lookup, manual_option_label, LANGUAGES};
 use super::detect_language;
 use super::looks_like_yaml;
 use super::refine_magika_label;
@@ -180,12 +180,46 @@ fn canonicalization_matrix_handles_aliases() {
         ("scss", "scss"),
         ("sass", "sass"),
         ("rust", "rust"),
+        ("node", "javascript"),
+        ("NodeJS", "javascript"),
+        ("py", "python"),
+        ("golang", "go"),
+        ("vhdl", "vhdl"),
     ];
     for (input, expected) in cases {
         assert_eq!(canonicalize(input), expected, "input: {input}");
     }
 }
 
+#[test]
+fn language_table_keys_resolve_to_their_own_entry() {
+    for spec in LANGUAGES {
+        for key in std::iter::once(&spec.value)
+            .chain(spec.aliases)
+            .chain(spec.extensions)
+        {
+            assert_eq!(
+                lookup(key).map(|found| found.value),
+                Some(spec.value),
+                "key: {key}"
+            );
+        }
+        assert!(
+            !spec.extensions.is_empty(),
+            "{} has no extension",
+            spec.value
+        );
+    }
+    assert_eq!(
+        from_extension(".TSX").map(|spec| spec.value),
+        Some("typescript")
+    );
+    assert!(from_extension("exe").is_none());
+    assert!(lookup("  ").is_none());
+    assert_eq!(manual_option_label("js"), Some("JavaScript"));
+    assert_eq!(manual_option_label("ruby"), None);
+}
+
 #[cfg(feature = "magika")]
 #[test]
 fn magika_detects_high_signal_code_snippets() {
@@ -0,0 +1,262 @@
+//! Markdown activity digests stored as pastes in a `Digests` folder.
+//!
+//! A digest covers a rolling window ending now (the last day or week) and
+//! lists new pastes, edited pastes, and the most used languages among them.
+//! Earlier digests are left out of the activity they summarize.
+
+use crate::db::TransactionOps;
+use crate::models::folder::Folder;
+use crate::models::paste::Paste;
+use crate::{AppError, Database};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+/// Name of the top-level folder digests are stored in.
+pub const DIGEST_FOLDER_NAME: &str = "Digests";
+/// Tag added to every generated digest paste.
+pub const DIGEST_TAG: &str = "digest";
+/// Rows listed per section before the rest are summarized as a count.
+const MAX_LISTED_PASTES: usize = 50;
+/// Languages shown in the top-languages table.
+const MAX_TOP_LANGUAGES: usize = 5;
+const DIGEST_TIME_FORMAT: &str = "%Y-%m-%d %H:%M UTC";
+
+/// Window a digest summarizes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestPeriod {
+    /// The last 24 hours.
+    #[default]
+    Day,
+    /// The last 7 days.
+    Week,
+}
+
+impl DigestPeriod {
+    /// Length of the window.
+    pub fn duration(self) -> Duration {
+        match self {
+            Self::Day => Duration::days(1),
+            Self::Week => Duration::days(7),
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Day => "Daily digest",
+            Self::Week => "Weekly digest",
+        }
+    }
+}
+
+impl FromStr for DigestPeriod {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "day" | "daily" => Ok(Self::Day),
+            "week" | "weekly" => Ok(Self::Week),
+            other => Err(format!(
+                "unknown digest period '{}' (expected 'day' or 'week')",
+                other
+            )),
+        }
+    }
+}
+
+/// Request body for `POST /api/digest`.
+#[derive(Debug, Default, Deserialize)]
+pub struct DigestRequest {
+    #[serde(default)]
+    pub period: DigestPeriod,
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(
+            ch,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '#' | '|' | '<' | '>'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn language_label(paste: &Paste) -> &str {
+    paste.language.as_deref().unwrap_or("plain")
+}
+
+fn push_paste_section(out: &mut String, heading: &str, pastes: &[&Paste]) {
+    let _ = writeln!(out, "\n## {}\n", heading);
+    if pastes.is_empty() {
+        out.push_str("None.\n");
+        return;
+    }
+    for paste in pastes.iter().take(MAX_LISTED_PASTES) {
+        let _ = writeln!(
+            out,
+            "- {} ({}, {} bytes) `{}`",
+            escape_markdown(paste.name.as_str()),
+            escape_markdown(language_label(paste)),
+            paste.content.len(),
+            paste.id
+        );
+    }
+    if pastes.len() > MAX_LISTED_PASTES {
+        let _ = writeln!(out, "- ...and {} more", pastes.len() - MAX_LISTED_PASTES);
+    }
+}
+
+/// Compose the digest name and markdown body for a window ending at `now`.
+///
+/// # Arguments
+/// - `period`: Window length.
+/// - `now`: End of the window.
+/// - `pastes`: Pastes updated inside the window, newest first.
+///
+/// # Returns
+/// `(name, markdown)`. Pastes created inside the window count as new; older
+/// pastes count as edited.
+pub fn compose_digest(
+    period: DigestPeriod,
+    now: DateTime<Utc>,
+    pastes: &[Paste],
+) -> (String, String) {
+    let since = now - period.duration();
+    let (created, edited): (Vec<&Paste>, Vec<&Paste>) =
+        pastes.iter().partition(|paste| paste.created_at >= since);
+
+    let mut languages: HashMap<&str, usize> = HashMap::new();
+    for paste in pastes {
+        *languages.entry(language_label(paste)).or_default() += 1;
+    }
+    let mut languages: Vec<(&str, usize)> = languages.into_iter().collect();
+    languages.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let name = format!("{} {}", period.title(), now.format("%Y-%m-%d"));
+    let mut out = format!(
+        "# {}\n\nActivity from {} to {}.\n\n- New pastes: {}\n- Edited pastes: {}\n",
+        name,
+        since.format(DIGEST_TIME_FORMAT),
+        now.format(DIGEST_TIME_FORMAT),
+        created.len(),
+        edited.len()
+    );
+    out.push_str("\n## Top languages\n\n");
+    if languages.is_empty() {
+        out.push_str("None.\n");
+    } else {
+        out.push_str("| Language | Pastes |\n| --- | --- |\n");
+        for (language, count) in languages.iter().take(MAX_TOP_LANGUAGES) {
+            let _ = writeln!(out, "| {} | {} |", escape_markdown(language), count);
+        }
+    }
+    push_paste_section(&mut out, "New pastes", &created);
+    push_paste_section(&mut out, "Edited pastes", &edited);
+    (name, out)
+}
+
+/// Generate a digest for the window ending now and store it.
+///
+/// The top-level `Digests` folder is created on first use. Pastes already in
+/// that folder are excluded from the summary.
+///
+/// # Returns
+/// The stored digest paste.
+///
+/// # Errors
+/// Returns storage errors from listing, folder creation, or the paste insert.
+pub fn generate_digest(db: &Database, period: DigestPeriod) -> Result<Paste, AppError> {
+    let now = Utc::now();
+    let folder_guard = TransactionOps::acquire_folder_txn_guard(db)?;
+    let mut existing = None;
+    for folder in db.folders.list()? {
+        if folder.parent_id.is_none()
+            && folder.name == DIGEST_FOLDER_NAME
+            && !db.folders.is_delete_marked(folder.id.as_str())?
+        {
+            existing = Some(folder.id);
+            break;
+        }
+    }
+    let folder_id = match existing {
+        Some(id) => id,
+        None => {
+            let folder = Folder::new(DIGEST_FOLDER_NAME.to_string());
+            db.folders.create(&folder)?;
+            folder.id
+        }
+    };
+
+    let mut pastes = db.pastes.list_updated_since(now - period.duration())?;
+    pastes.retain(|paste| paste.folder_id.as_deref() != Some(folder_id.as_str()));
+    let (name, content) = compose_digest(period, now, &pastes);
+    let mut digest = Paste::new_with_language(content, name, Some("markdown".to_string()), true);
+    digest.tags = vec![DIGEST_TAG.to_string()];
+    digest.folder_id = Some(folder_id.clone());
+    TransactionOps::create_paste_with_folder_locked(db, &folder_guard, &digest, &folder_id)?;
+    Ok(digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::setup_temp_db;
+
+    #[test]
+    fn digest_splits_new_and_edited_and_ranks_languages() {
+        let now = Utc::now();
+        let mut old = Paste::new_with_language(
+            "fn main() {}".to_string(),
+            "old_*main*".to_string(),
+            Some("rust".to_string()),
+            true,
+        );
+        old.created_at = now - Duration::days(30);
+        let fresh = Paste::new_with_language(
+            "print(1)".to_string(),
+            "fresh".to_string(),
+            Some("python".to_string()),
+            true,
+        );
+        let other = Paste::new_with_language(
+            "fn x() {}".to_string(),
+            "other".to_string(),
+            Some("rust".to_string()),
+            true,
+        );
+        let (name, markdown) = compose_digest(DigestPeriod::Week, now, &[fresh, other, old]);
+        assert!(name.starts_with("Weekly digest "), "{name}");
+        assert!(markdown.contains("- New pastes: 2\n- Edited pastes: 1\n"));
+        assert!(markdown.contains("| rust | 2 |\n| python | 1 |"));
+        assert!(markdown.contains("## Edited pastes\n\n- old\\_\\*main\\* (rust"));
+    }
+
+    #[test]
+    fn generate_digest_reuses_folder_and_skips_earlier_digests() {
+        let (db, _temp) = setup_temp_db();
+        let paste = Paste::new("hello".to_string(), "hello".to_string());
+        db.pastes.create(&paste).expect("create paste");
+
+        let first = generate_digest(&db, DigestPeriod::Day).expect("first digest");
+        let second = generate_digest(&db, DigestPeriod::Day).expect("second digest");
+        assert_eq!(first.folder_id, second.folder_id);
+        assert_eq!(second.tags, vec![DIGEST_TAG.to_string()]);
+        assert!(second.content.contains("- New pastes: 1\n"));
+        assert!(!second.content.contains(first.id.as_str()));
+
+        let folders = db.folders.list().expect("folders");
+        let digests: Vec<_> = folders
+            .iter()
+            .filter(|folder| folder.name == DIGEST_FOLDER_NAME)
+            .collect();
+        assert_eq!(digests.len(), 1);
+        assert_eq!(digests[0].paste_count, 2);
+    }
+}
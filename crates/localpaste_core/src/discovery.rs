@@ -0,0 +1,216 @@
+//! API discovery files and the embedded server's port fallback policy.
+//!
+//! The embedded server writes its bound address to `.api-addr` inside the DB
+//! directory so CLI clients can find it. When the configured port is taken it
+//! walks a bounded run of successive ports before asking the OS for one, and
+//! the GUI can pin whichever port it ended up on via `.api-port`.
+
+use crate::config::{api_addr_file_path_for_db_path, parse_env_number};
+use crate::constants::{
+    API_PORT_PIN_FILE_NAME, DEFAULT_GALLERY_PORT, DEFAULT_PORT_FALLBACK_ATTEMPTS,
+};
+use std::fs;
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding how many successive ports are tried.
+pub const PORT_FALLBACK_ATTEMPTS_ENV: &str = "PORT_FALLBACK_ATTEMPTS";
+
+/// Resolve the successive-port budget from `PORT_FALLBACK_ATTEMPTS`.
+///
+/// # Returns
+/// The configured attempt count, or [`DEFAULT_PORT_FALLBACK_ATTEMPTS`] when
+/// unset or invalid. `0` skips straight to an OS-assigned port.
+pub fn port_fallback_attempts_from_env_or_default() -> u16 {
+    parse_env_number(PORT_FALLBACK_ATTEMPTS_ENV, DEFAULT_PORT_FALLBACK_ATTEMPTS)
+}
+
+/// Ordered addresses to try when binding the embedded API.
+///
+/// The requested address comes first, then up to `attempts` successive ports
+/// (stopping at port 65535), then port `0` so the OS picks a free port as a
+/// last resort. Fallbacks skip [`DEFAULT_GALLERY_PORT`] so a busy API port
+/// never pushes the API onto the gallery's default listener. A requested port of `0` is already OS-assigned and is the only
+/// candidate.
+///
+/// # Arguments
+/// - `requested`: Address resolved from `BIND`/`PORT`.
+/// - `attempts`: Number of successive ports to try after `requested`.
+///
+/// # Returns
+/// Candidate bind addresses in the order they should be tried.
+pub fn port_fallback_candidates(requested: SocketAddr, attempts: u16) -> Vec<SocketAddr> {
+    let mut candidates = vec![requested];
+    if requested.port() == 0 {
+        return candidates;
+    }
+    candidates.extend(
+        (1..=u16::MAX)
+            .map_while(|offset| requested.port().checked_add(offset))
+            .filter(|port| *port != DEFAULT_GALLERY_PORT)
+            .take(usize::from(attempts))
+            .map(|port| SocketAddr::new(requested.ip(), port)),
+    );
+    candidates.push(SocketAddr::new(requested.ip(), 0));
+    candidates
+}
+
+/// Replace `path` with `contents` so readers never observe a partial file.
+///
+/// Writes a sibling temp file, flushes it to disk, and renames it over
+/// `path`, creating the parent directory when needed. A crash leaves either
+/// the old file or the new one, never a truncated mix.
+///
+/// # Errors
+/// Returns an error when the directory, temp file, or rename fails.
+pub fn write_file_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+    let mut tmp_name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+    let written = fs::File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&tmp_path, path));
+    if let Err(err) = written {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Atomically publish the embedded API address for `db_path`.
+///
+/// # Arguments
+/// - `db_path`: Database path the server was started for.
+/// - `addr`: Address the server actually bound.
+///
+/// # Returns
+/// Path of the discovery file that was written.
+///
+/// # Errors
+/// Returns an error when the file cannot be written.
+pub fn write_api_addr(db_path: &str, addr: SocketAddr) -> io::Result<PathBuf> {
+    let path = api_addr_file_path_for_db_path(db_path);
+    write_file_atomic(&path, format!("http://{}", addr).as_bytes())?;
+    Ok(path)
+}
+
+/// Path of the pinned-port file for a database path.
+///
+/// # Returns
+/// Path to the `.api-port` file next to the `.api-addr` discovery file.
+pub fn pinned_port_file_path_for_db_path(db_path: &str) -> PathBuf {
+    api_addr_file_path_for_db_path(db_path).with_file_name(API_PORT_PIN_FILE_NAME)
+}
+
+/// Read the pinned API port for `db_path`, if any.
+///
+/// # Returns
+/// The pinned port, or `None` when no valid non-zero pin exists.
+pub fn read_pinned_port(db_path: &str) -> Option<u16> {
+    fs::read_to_string(pinned_port_file_path_for_db_path(db_path))
+        .ok()?
+        .trim()
+        .parse::<u16>()
+        .ok()
+        .filter(|port| *port != 0)
+}
+
+/// Pinned port to bind on GUI launch, unless `PORT` is set explicitly.
+///
+/// # Returns
+/// The pinned port when `PORT` is unset and a valid pin exists.
+pub fn pinned_port_for_launch(db_path: &str) -> Option<u16> {
+    if std::env::var_os("PORT").is_some() {
+        return None;
+    }
+    read_pinned_port(db_path)
+}
+
+/// Pin `port` as the API port for future GUI launches against `db_path`.
+///
+/// # Errors
+/// Returns an error when the pin file cannot be written.
+pub fn write_pinned_port(db_path: &str, port: u16) -> io::Result<()> {
+    write_file_atomic(
+        &pinned_port_file_path_for_db_path(db_path),
+        port.to_string().as_bytes(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::DEFAULT_PORT;
+    use tempfile::TempDir;
+
+    #[test]
+    fn port_fallback_candidates_walk_successive_ports_then_auto() {
+        let requested: SocketAddr = "127.0.0.1:4000".parse().expect("addr");
+        let ports: Vec<u16> = port_fallback_candidates(requested, 3)
+            .iter()
+            .map(SocketAddr::port)
+            .collect();
+        assert_eq!(ports, vec![4000, 4001, 4002, 4003, 0]);
+
+        let near_max: SocketAddr = "127.0.0.1:65534".parse().expect("addr");
+        let ports: Vec<u16> = port_fallback_candidates(near_max, 5)
+            .iter()
+            .map(SocketAddr::port)
+            .collect();
+        assert_eq!(ports, vec![65534, 65535, 0]);
+
+        let default_api = SocketAddr::from(([127, 0, 0, 1], DEFAULT_PORT));
+        let ports: Vec<u16> = port_fallback_candidates(default_api, 2)
+            .iter()
+            .map(SocketAddr::port)
+            .collect();
+        assert_eq!(
+            ports,
+            vec![DEFAULT_PORT, DEFAULT_PORT + 2, DEFAULT_PORT + 3, 0]
+        );
+        assert!(!ports.contains(&DEFAULT_GALLERY_PORT));
+
+        let auto: SocketAddr = "127.0.0.1:0".parse().expect("addr");
+        assert_eq!(port_fallback_candidates(auto, 5), vec![auto]);
+    }
+
+    #[test]
+    fn api_addr_and_pinned_port_round_trip_atomically() {
+        let dir = TempDir::new().expect("temp dir");
+        let db_path = dir.path().join("db");
+        let db_path = db_path.to_str().expect("db path");
+
+        assert_eq!(read_pinned_port(db_path), None);
+        let addr: SocketAddr = "127.0.0.1:4005".parse().expect("addr");
+        let written = write_api_addr(db_path, addr).expect("write addr");
+        assert_eq!(
+            fs::read_to_string(&written).expect("read addr"),
+            "http://127.0.0.1:4005"
+        );
+
+        write_pinned_port(db_path, 4005).expect("pin");
+        assert_eq!(read_pinned_port(db_path), Some(4005));
+        fs::write(pinned_port_file_path_for_db_path(db_path), "0").expect("zero pin");
+        assert_eq!(read_pinned_port(db_path), None);
+
+        let leftovers: Vec<_> = fs::read_dir(dir.path().join("db"))
+            .expect("read dir")
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+}
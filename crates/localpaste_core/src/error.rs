@@ -16,15 +16,39 @@ pub enum AppError {
     #[error("Not found")]
     NotFound,
 
+    #[error("Ambiguous id prefix '{prefix}': matches {}", candidates.join(", "))]
+    AmbiguousId {
+        prefix: String,
+        candidates: Vec<String>,
+    },
+
     #[error("Bad request: {0}")]
     BadRequest(String),
 
     #[error("Payload too large: {0}")]
     PayloadTooLarge(String),
 
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     #[error("Locked: {0}")]
     Locked(String),
 
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Upstream error: {0}")]
+    Upstream(String),
+
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
+
+    #[error("Unavailable: {0}")]
+    Unavailable(String),
+
     #[error("Internal server error")]
     Internal,
 }
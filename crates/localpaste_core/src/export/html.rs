@@ -0,0 +1,272 @@
+//! Standalone HTML export using syntect class-based markup and theme CSS,
+//! plus an inline-styled variant that survives copy/paste into rich text.
+
+use super::{
+    assets, find_theme, highlight_error, resolve_export_syntax, ExportDocument,
+    EXPORT_HIGHLIGHT_MAX_BYTES,
+};
+use crate::AppError;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::Color;
+use syntect::html::{
+    append_highlighted_html_for_styled_line, css_for_theme_with_class_style, ClassStyle,
+    ClassedHTMLGenerator, IncludeBackground,
+};
+use syntect::util::LinesWithEndings;
+
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "lp-" };
+const PAGE_CSS: &str = "body{margin:2rem;font-family:system-ui,sans-serif;}\
+h1{font-size:1.1rem;font-weight:600;}\
+pre{padding:1rem;border-radius:6px;overflow-x:auto;tab-size:4;\
+font:13px/1.45 ui-monospace,SFMono-Regular,Menlo,Consolas,monospace;}\
+pre.lp-code{position:relative;}\
+a.lp-ln{display:inline-block;margin-right:1.5ch;text-align:right;color:#8a8a8a;\
+text-decoration:none;user-select:none;}a.lp-ln::before{content:attr(data-line);}\
+a.lp-ln:target::after{content:\"\";position:absolute;left:0;right:0;height:1.45em;\
+pointer-events:none;background:rgba(255,200,60,.25);animation:lp-flash 1.5s ease-out;}\
+@keyframes lp-flash{from{background:rgba(255,200,60,.7);}}\
+ol.lp-notes{font-size:0.9rem;}ol.lp-notes li{white-space:pre-wrap;}\
+@media print{body{margin:0;}pre{white-space:pre-wrap;}}";
+
+/// Escapes text for HTML element and attribute content.
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Prefixes each highlighted content line with an `L<n>` anchor so
+/// `#L42` fragments scroll to and highlight that line.
+///
+/// Highlight spans may cross newlines, so anchors go at the start of each
+/// output line rather than wrapping it; the number itself comes from CSS and
+/// is left out of copied text.
+fn number_lines(code: &str, content: &str) -> String {
+    let line_count = content.lines().count();
+    let width = line_count.to_string().len();
+    let mut out = String::with_capacity(code.len() + line_count * 64);
+    for (idx, line) in code.split('\n').enumerate() {
+        if idx > 0 {
+            out.push('\n');
+        }
+        if idx < line_count {
+            let n = idx + 1;
+            out.push_str(&format!(
+                "<a class=\"lp-ln\" id=\"L{n}\" href=\"#L{n}\" data-line=\"{n}\" \
+style=\"width:{width}ch\"></a>"
+            ));
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+/// Renders annotations as an ordered footnote list, or nothing without any.
+fn render_notes(document: &ExportDocument<'_>) -> String {
+    if document.annotations.is_empty() {
+        return String::new();
+    }
+    let mut out = String::from("<h2>Notes</h2>\n<ol class=\"lp-notes\">\n");
+    for annotation in document.annotations {
+        out.push_str(
+            format!(
+                "<li><strong>{}</strong> ({}): {}</li>\n",
+                escape_html(annotation.range_label().as_str()),
+                escape_html(annotation.author.as_str()),
+                escape_html(annotation.body.as_str())
+            )
+            .as_str(),
+        );
+    }
+    out.push_str("</ol>\n");
+    out
+}
+
+/// Renders a self-contained HTML page with embedded theme CSS.
+///
+/// # Returns
+/// Complete HTML document text.
+///
+/// # Errors
+/// Returns [`AppError::Internal`] if syntax highlighting fails.
+pub(super) fn render_html(document: &ExportDocument<'_>) -> Result<String, AppError> {
+    render_html_with_back_link(document, None)
+}
+
+/// Renders the export page, optionally with a link above the title.
+///
+/// # Errors
+/// Returns [`AppError::Internal`] if syntax highlighting fails.
+pub(super) fn render_html_with_back_link(
+    document: &ExportDocument<'_>,
+    back_link: Option<&str>,
+) -> Result<String, AppError> {
+    let assets = assets();
+    let theme_css =
+        css_for_theme_with_class_style(&assets.theme, CLASS_STYLE).map_err(highlight_error)?;
+    let code = if document.content.len() > EXPORT_HIGHLIGHT_MAX_BYTES {
+        escape_html(document.content)
+    } else {
+        let syntax = resolve_export_syntax(&assets.syntaxes, document.language);
+        let mut generator =
+            ClassedHTMLGenerator::new_with_class_style(syntax, &assets.syntaxes, CLASS_STYLE);
+        for line in LinesWithEndings::from(document.content) {
+            generator
+                .parse_html_for_line_which_includes_newline(line)
+                .map_err(highlight_error)?;
+        }
+        generator.finalize()
+    };
+    let code = number_lines(code.as_str(), document.content);
+    let title = escape_html(document.name);
+    let notes = render_notes(document);
+    let nav = back_link
+        .map(|href| {
+            format!(
+                "<nav><a href=\"{}\">&larr; Back</a></nav>\n",
+                escape_html(href)
+            )
+        })
+        .unwrap_or_default();
+    Ok(format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+<meta name=\"generator\" content=\"LocalPaste\">\n<title>{title}</title>\n\
+<style>\n{PAGE_CSS}\n{theme_css}</style>\n</head>\n<body>\n{nav}<h1>{title}</h1>\n\
+<pre class=\"lp-code\"><code>{code}</code></pre>\n{notes}</body>\n</html>\n"
+    ))
+}
+
+fn css_hex(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+/// Renders a self-contained HTML page whose colors are inline `style`
+/// attributes rather than a stylesheet.
+///
+/// Mail clients and wiki editors drop `<style>` blocks and classes when
+/// HTML is pasted in, so every span carries its own color. Line anchors are
+/// left out because they depend on page CSS.
+///
+/// # Arguments
+/// - `document`: Paste name, language, content, and annotations.
+/// - `theme`: Bundled syntect theme name (case-insensitive); `None` uses the
+///   export default.
+///
+/// # Returns
+/// Complete HTML document text.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] for an unknown theme, or
+/// [`AppError::Internal`] if syntax highlighting fails.
+pub fn render_inline_html(
+    document: &ExportDocument<'_>,
+    theme: Option<&str>,
+) -> Result<String, AppError> {
+    let assets = assets();
+    let theme = find_theme(theme)?;
+    let background = theme.settings.background.unwrap_or(Color::WHITE);
+    let foreground = theme.settings.foreground.unwrap_or(Color::BLACK);
+    let code = if document.content.len() > EXPORT_HIGHLIGHT_MAX_BYTES {
+        escape_html(document.content)
+    } else {
+        let syntax = resolve_export_syntax(&assets.syntaxes, document.language);
+        let mut highlighter = HighlightLines::new(syntax, theme);
+        let mut code = String::with_capacity(document.content.len() * 4);
+        for line in LinesWithEndings::from(document.content) {
+            let regions = highlighter
+                .highlight_line(line, &assets.syntaxes)
+                .map_err(highlight_error)?;
+            append_highlighted_html_for_styled_line(
+                &regions,
+                IncludeBackground::IfDifferent(background),
+                &mut code,
+            )
+            .map_err(highlight_error)?;
+        }
+        code
+    };
+    let title = escape_html(document.name);
+    let notes = render_notes(document);
+    Ok(format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+<meta name=\"generator\" content=\"LocalPaste\">\n<title>{title}</title>\n</head>\n\
+<body style=\"margin:2rem;font-family:system-ui,sans-serif;\">\n\
+<h1 style=\"font-size:1.1rem;font-weight:600;\">{title}</h1>\n\
+<pre style=\"background-color:{bg};color:{fg};padding:1rem;border-radius:6px;\
+overflow-x:auto;tab-size:4;font:13px/1.45 ui-monospace,SFMono-Regular,Menlo,Consolas,monospace;\">\
+<code>{code}</code></pre>\n{notes}</body>\n</html>\n",
+        bg = css_hex(background),
+        fg = css_hex(foreground),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_html_escapes_title_and_embeds_theme_classes() {
+        let html = render_html(&ExportDocument {
+            name: "<notes & tips>",
+            language: Some("rust"),
+            content: "fn main() { let x = 1 < 2; }\n",
+            annotations: &[],
+        })
+        .expect("html");
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>&lt;notes &amp; tips&gt;</title>"));
+        assert!(html.contains("class=\"lp-"));
+        assert!(html.contains(".lp-code"));
+        assert!(html.contains("&lt;"));
+        assert!(!html.contains("1 < 2"));
+    }
+
+    #[test]
+    fn render_html_anchors_each_line_for_fragment_links() {
+        let html = render_html(&ExportDocument {
+            name: "notes.txt",
+            language: None,
+            content: "one\ntwo\n",
+            annotations: &[],
+        })
+        .expect("html");
+        assert!(html.contains("id=\"L1\" href=\"#L1\""));
+        assert!(html.contains("id=\"L2\" href=\"#L2\""));
+        assert!(!html.contains("id=\"L3\""));
+        assert!(html.contains("a.lp-ln:target"));
+    }
+
+    #[test]
+    fn render_inline_html_uses_style_attributes_and_named_themes() {
+        let document = ExportDocument {
+            name: "demo",
+            language: Some("rs"),
+            content: "fn main() { let x = 1 < 2; }\n",
+            annotations: &[],
+        };
+        let light = render_inline_html(&document, None).expect("default theme");
+        assert!(light.contains("<span style=\"color:#"));
+        assert!(!light.contains("class=\""));
+        assert!(!light.contains("<style>"));
+        assert!(light.contains("&lt;"));
+        assert!(!light.contains("1 < 2"));
+
+        let dark = render_inline_html(&document, Some("solarized (DARK)")).expect("dark theme");
+        assert!(dark.contains("background-color:#002b36"));
+        assert_ne!(light, dark);
+
+        match render_inline_html(&document, Some("neon")) {
+            Err(AppError::BadRequest(message)) => assert!(message.contains("InspiredGitHub")),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+}
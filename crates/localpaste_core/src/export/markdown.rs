@@ -0,0 +1,172 @@
+//! Markdown to sanitized HTML fragments for viewers without a renderer.
+//!
+//! Raw HTML in the source is shown as text rather than passed through, and
+//! link or image targets with a scheme other than `http`, `https`, or
+//! `mailto` are dropped, so the output is safe to insert into a page.
+
+use super::escape_html;
+use crate::detection::canonical::canonicalize;
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+
+/// How [`render_paste_html`] treated the content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderedAs {
+    /// Parsed as CommonMark with tables, strikethrough, task lists, and footnotes.
+    Markdown,
+    /// Escaped into a single `<pre><code>` block.
+    Code,
+}
+
+impl RenderedAs {
+    /// Lowercase label for response headers.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Markdown => "markdown",
+            Self::Code => "code",
+        }
+    }
+}
+
+/// Whether a link or image destination is safe to emit.
+///
+/// Relative paths and fragments have no scheme and are allowed. Control
+/// characters and whitespace are ignored while reading the scheme, as
+/// browsers do, so `java\tscript:` is still caught.
+fn is_safe_url(dest: &str) -> bool {
+    let compact: String = dest
+        .chars()
+        .filter(|ch| !ch.is_ascii_whitespace() && !ch.is_control())
+        .take_while(|ch| !matches!(ch, '/' | '?' | '#'))
+        .collect();
+    match compact.split_once(':') {
+        Some((scheme, _)) => matches!(
+            scheme.to_ascii_lowercase().as_str(),
+            "http" | "https" | "mailto"
+        ),
+        None => true,
+    }
+}
+
+fn sanitize_event(event: Event<'_>) -> Event<'_> {
+    match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) if !is_safe_url(&dest_url) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: CowStr::Borrowed(""),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) if !is_safe_url(&dest_url) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: CowStr::Borrowed(""),
+            title,
+            id,
+        }),
+        other => other,
+    }
+}
+
+/// Render markdown to a sanitized HTML fragment.
+pub fn render_markdown(content: &str) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+    let parser = Parser::new_ext(content, options).map(sanitize_event);
+    let mut out = String::with_capacity(content.len() + content.len() / 2);
+    html::push_html(&mut out, parser);
+    out
+}
+
+/// Render a paste body as an HTML fragment, honoring its language.
+///
+/// A `markdown` language renders as markdown and any other language as an
+/// escaped code block tagged `language-<name>`. Without a language, the
+/// stored `is_markdown` detection decides.
+pub fn render_paste_html(
+    language: Option<&str>,
+    is_markdown: bool,
+    content: &str,
+) -> (String, RenderedAs) {
+    let language = language.map(canonicalize);
+    let markdown = match language.as_deref() {
+        Some(language) => language == "markdown",
+        None => is_markdown,
+    };
+    if markdown {
+        return (render_markdown(content), RenderedAs::Markdown);
+    }
+    let class = language
+        .map(|language| format!(" class=\"language-{}\"", escape_html(language.as_str())))
+        .unwrap_or_default();
+    (
+        format!(
+            "<pre><code{}>{}</code></pre>\n",
+            class,
+            escape_html(content)
+        ),
+        RenderedAs::Code,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_renders_extensions_and_strips_raw_html() {
+        let html = render_markdown(
+            "# Notes\n\n- [x] done\n\n| a | b |\n|---|---|\n| 1 | 2 |\n\n\
+             <script>alert(1)</script>\n\nhi <b onclick=\"x\">there</b> ~~old~~\n",
+        );
+        assert!(html.contains("<h1>Notes</h1>"));
+        assert!(html.contains("type=\"checkbox\""));
+        assert!(html.contains("<table>"));
+        assert!(html.contains("<del>old</del>"));
+        assert!(!html.contains("<script"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<b "));
+    }
+
+    #[test]
+    fn unsafe_link_targets_are_dropped() {
+        let html = render_markdown(
+            "[ok](https://example.com) [rel](docs/a.md#x) [bad](JavaScript:alert(1)) \
+             ![img](data:image/svg+xml;base64,AAAA) [mail](mailto:a@b.c)",
+        );
+        assert!(html.contains("href=\"https://example.com\""));
+        assert!(html.contains("href=\"docs/a.md#x\""));
+        assert!(html.contains("href=\"mailto:a@b.c\""));
+        assert!(!html.to_ascii_lowercase().contains("javascript"));
+        assert!(!html.contains("data:image"));
+        assert!(!is_safe_url("java\tscript:alert(1)"));
+        assert!(is_safe_url("/p/abc?x=1:2"));
+    }
+
+    #[test]
+    fn paste_language_picks_markdown_or_code() {
+        let (html, kind) = render_paste_html(Some("md"), false, "*hi*");
+        assert_eq!(kind, RenderedAs::Markdown);
+        assert!(html.contains("<em>hi</em>"));
+
+        let (html, kind) = render_paste_html(Some("rust"), true, "# not a heading <b>");
+        assert_eq!(kind, RenderedAs::Code);
+        assert_eq!(
+            html,
+            "<pre><code class=\"language-rust\"># not a heading &lt;b&gt;</code></pre>\n"
+        );
+
+        assert_eq!(render_paste_html(None, true, "x").1, RenderedAs::Markdown);
+        assert_eq!(render_paste_html(None, false, "x").1, RenderedAs::Code);
+    }
+}
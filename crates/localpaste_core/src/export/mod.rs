@@ -0,0 +1,291 @@
+//! Standalone HTML and PDF renderings of paste content with syntax colors,
+//! plus sanitized markdown fragments.
+//!
+//! Both formats resolve the grammar the same way the GUI editor does and use a
+//! light theme so exports stay readable when printed; the inline-styled HTML
+//! variant can pick any of syntect's bundled themes instead.
+
+mod html;
+mod markdown;
+mod pdf;
+pub mod syntax;
+
+pub use html::{escape_html, render_inline_html};
+pub use markdown::{render_markdown, render_paste_html, RenderedAs};
+
+use crate::models::annotation::Annotation;
+use crate::models::paste::ExportFormat;
+use crate::AppError;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Color, Theme, ThemeSet};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+/// Theme used for exported documents.
+const EXPORT_THEME: &str = "InspiredGitHub";
+/// Content above this size is exported without highlighting.
+pub const EXPORT_HIGHLIGHT_MAX_BYTES: usize = 2 * 1024 * 1024;
+/// Tab stops used when laying out fixed-width output.
+const EXPORT_TAB_WIDTH: usize = 4;
+
+/// Paste fields needed to render an export.
+#[derive(Debug, Clone, Copy)]
+pub struct ExportDocument<'a> {
+    /// Display name used as the document title.
+    pub name: &'a str,
+    /// Language label, resolved to a grammar like the editor does.
+    pub language: Option<&'a str>,
+    /// Paste content.
+    pub content: &'a str,
+    /// Line annotations rendered as numbered footnotes after the content.
+    pub annotations: &'a [Annotation],
+}
+
+/// Page decorations for printable PDF output.
+#[derive(Debug, Clone, Default)]
+pub struct PrintOptions {
+    /// Text repeated at the top of every page, followed by the page number.
+    pub header: Option<String>,
+    /// Prefix each source line with its line number.
+    pub line_numbers: bool,
+}
+
+/// Run of text sharing one foreground color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Span {
+    color: [u8; 3],
+    text: String,
+}
+
+struct ExportAssets {
+    syntaxes: SyntaxSet,
+    theme: Theme,
+    /// syntect's bundled themes, selectable for inline-styled HTML.
+    themes: BTreeMap<String, Theme>,
+}
+
+fn assets() -> &'static ExportAssets {
+    static ASSETS: OnceLock<ExportAssets> = OnceLock::new();
+    ASSETS.get_or_init(|| {
+        let themes = ThemeSet::load_defaults().themes;
+        let theme = themes.get(EXPORT_THEME).cloned().unwrap_or_default();
+        ExportAssets {
+            syntaxes: SyntaxSet::load_defaults_newlines(),
+            theme,
+            themes,
+        }
+    })
+}
+
+/// Names accepted by [`render_inline_html`], in sorted order.
+pub fn highlight_theme_names() -> Vec<&'static str> {
+    assets().themes.keys().map(String::as_str).collect()
+}
+
+/// Looks up a bundled theme by name, ignoring ASCII case; `None` picks the
+/// export default.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] listing the known names when `name`
+/// matches none of them.
+fn find_theme(name: Option<&str>) -> Result<&'static Theme, AppError> {
+    let assets = assets();
+    let Some(name) = name.map(str::trim).filter(|name| !name.is_empty()) else {
+        return Ok(&assets.theme);
+    };
+    assets
+        .themes
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(name))
+        .map(|(_, theme)| theme)
+        .ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Unknown theme '{}'; expected one of: {}",
+                name,
+                highlight_theme_names().join(", ")
+            ))
+        })
+}
+
+fn resolve_export_syntax<'a>(
+    syntaxes: &'a SyntaxSet,
+    language: Option<&str>,
+) -> &'a SyntaxReference {
+    let hint = syntax::syntect_language_hint(language.unwrap_or("text"));
+    syntax::resolve_syntax(syntaxes, hint.as_str())
+}
+
+fn highlight_error(err: syntect::Error) -> AppError {
+    tracing::error!("Export highlighting failed: {}", err);
+    AppError::Internal
+}
+
+fn rgb(color: Color) -> [u8; 3] {
+    [color.r, color.g, color.b]
+}
+
+/// Expands tabs to spaces, tracking the display column across spans of a line.
+fn expand_tabs(text: &str, column: &mut usize) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if ch == '\t' {
+            let pad = EXPORT_TAB_WIDTH - *column % EXPORT_TAB_WIDTH;
+            out.extend(std::iter::repeat_n(' ', pad));
+            *column += pad;
+        } else {
+            out.push(ch);
+            *column += 1;
+        }
+    }
+    out
+}
+
+/// One-line footnote text for each annotation, numbered from 1.
+fn footnotes(annotations: &[Annotation]) -> Vec<String> {
+    (1..)
+        .zip(annotations)
+        .map(|(number, annotation)| {
+            let body: Vec<&str> = annotation.body.split_whitespace().collect();
+            format!(
+                "[{}] {} ({}): {}",
+                number,
+                annotation.range_label(),
+                annotation.author,
+                body.join(" ")
+            )
+        })
+        .collect()
+}
+
+/// Splits content into lines of colored spans with newlines and tabs removed.
+///
+/// Oversized content is returned as a single uncolored span per line.
+fn highlighted_lines(document: &ExportDocument<'_>) -> Result<Vec<Vec<Span>>, AppError> {
+    let assets = assets();
+    let foreground = rgb(assets.theme.settings.foreground.unwrap_or(Color::BLACK));
+    let plain = document.content.len() > EXPORT_HIGHLIGHT_MAX_BYTES;
+    let syntax = resolve_export_syntax(&assets.syntaxes, document.language);
+    let mut highlighter = HighlightLines::new(syntax, &assets.theme);
+    let mut lines = Vec::new();
+    for line in LinesWithEndings::from(document.content) {
+        let ranges = if plain {
+            vec![(foreground, line)]
+        } else {
+            highlighter
+                .highlight_line(line, &assets.syntaxes)
+                .map_err(highlight_error)?
+                .into_iter()
+                .map(|(style, text)| (rgb(style.foreground), text))
+                .collect()
+        };
+        let mut spans: Vec<Span> = Vec::with_capacity(ranges.len());
+        let mut column = 0usize;
+        for (color, text) in ranges {
+            let text = text.trim_end_matches(['\r', '\n']);
+            if text.is_empty() {
+                continue;
+            }
+            let text = expand_tabs(text, &mut column);
+            match spans.last_mut() {
+                Some(last) if last.color == color => last.text.push_str(text.as_str()),
+                _ => spans.push(Span { color, text }),
+            }
+        }
+        lines.push(spans);
+    }
+    Ok(lines)
+}
+
+/// Renders a paste as a standalone document.
+///
+/// # Arguments
+/// - `document`: Paste name, language, and content.
+/// - `format`: Output format.
+///
+/// # Returns
+/// Encoded document bytes (UTF-8 HTML or a PDF file).
+///
+/// # Errors
+/// Returns [`AppError::Internal`] if syntax highlighting fails.
+pub fn render_export(
+    document: &ExportDocument<'_>,
+    format: ExportFormat,
+) -> Result<Vec<u8>, AppError> {
+    match format {
+        ExportFormat::Html => html::render_html(document).map(String::into_bytes),
+        ExportFormat::Pdf => render_print_pdf(document, &PrintOptions::default()),
+    }
+}
+
+/// Renders a paste as a highlighted HTML page that links back to `back_link`.
+///
+/// # Returns
+/// Complete HTML document text.
+///
+/// # Errors
+/// Returns [`AppError::Internal`] if syntax highlighting fails.
+pub fn render_html_page(
+    document: &ExportDocument<'_>,
+    back_link: Option<&str>,
+) -> Result<String, AppError> {
+    html::render_html_with_back_link(document, back_link)
+}
+
+/// Renders a paste as a printable PDF with optional page headers and line numbers.
+///
+/// # Arguments
+/// - `document`: Paste name, language, and content.
+/// - `options`: Page header and line-number settings.
+///
+/// # Returns
+/// PDF file bytes.
+///
+/// # Errors
+/// Returns [`AppError::Internal`] if syntax highlighting fails.
+pub fn render_print_pdf(
+    document: &ExportDocument<'_>,
+    options: &PrintOptions,
+) -> Result<Vec<u8>, AppError> {
+    Ok(pdf::render_pdf(
+        document.name,
+        &highlighted_lines(document)?,
+        &footnotes(document.annotations),
+        options,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlighted_lines_expand_tabs_and_drop_newlines() {
+        let document = ExportDocument {
+            name: "demo",
+            language: Some("text"),
+            content: "a\tb\r\n\tc\n",
+            annotations: &[],
+        };
+        let lines = highlighted_lines(&document).expect("lines");
+        let text: Vec<String> = lines
+            .iter()
+            .map(|spans| spans.iter().map(|span| span.text.as_str()).collect())
+            .collect();
+        assert_eq!(text, vec!["a   b".to_string(), "    c".to_string()]);
+    }
+
+    #[test]
+    fn highlighted_lines_color_code_tokens() {
+        let document = ExportDocument {
+            name: "demo",
+            language: Some("rust"),
+            content: "fn main() {}\n",
+            annotations: &[],
+        };
+        let lines = highlighted_lines(&document).expect("lines");
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].len() > 1, "rust tokens should get distinct colors");
+    }
+}
@@ -0,0 +1,307 @@
+//! Minimal PDF writer for highlighted, paginated fixed-width text.
+//!
+//! Pages use the standard Courier font so no font data is embedded; characters
+//! outside WinAnsi (Latin-1) are replaced with `?`.
+
+use super::{PrintOptions, Span};
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 42.0;
+const FONT_SIZE: f32 = 9.0;
+const LEADING: f32 = 11.0;
+/// Courier advance width as a fraction of the font size.
+const CHAR_ADVANCE: f32 = 0.6;
+/// Color for page headers and the line-number gutter.
+const DECORATION_COLOR: [u8; 3] = [128, 128, 128];
+
+/// Encodes text as a PDF literal string body in WinAnsi encoding.
+fn pdf_string(text: &str, out: &mut Vec<u8>) {
+    out.push(b'(');
+    for ch in text.chars() {
+        let byte = match u32::from(ch) {
+            code @ 0x20..=0x7e => code as u8,
+            code @ 0xa0..=0xff => code as u8,
+            _ => b'?',
+        };
+        if matches!(byte, b'(' | b')' | b'\\') {
+            out.push(b'\\');
+        }
+        out.push(byte);
+    }
+    out.push(b')');
+}
+
+/// Wraps one line of spans into rows of at most `columns` characters.
+fn wrap_spans(spans: &[Span], columns: usize) -> Vec<Vec<Span>> {
+    let mut rows = vec![Vec::new()];
+    let mut used = 0usize;
+    for span in spans {
+        let mut rest = span.text.as_str();
+        while !rest.is_empty() {
+            if used == columns {
+                rows.push(Vec::new());
+                used = 0;
+            }
+            let take = rest
+                .char_indices()
+                .nth(columns - used)
+                .map_or(rest.len(), |(idx, _)| idx);
+            let (head, tail) = rest.split_at(take);
+            used += head.chars().count();
+            rows.last_mut().expect("row").push(Span {
+                color: span.color,
+                text: head.to_string(),
+            });
+            rest = tail;
+        }
+    }
+    rows
+}
+
+fn page_content(rows: &[Vec<Span>]) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(
+        format!(
+            "BT\n/F1 {} Tf\n{} TL\n{} {} Td\n",
+            FONT_SIZE,
+            LEADING,
+            MARGIN,
+            PAGE_HEIGHT - MARGIN - FONT_SIZE
+        )
+        .as_bytes(),
+    );
+    let mut current: Option<[u8; 3]> = None;
+    for row in rows {
+        for span in row {
+            if current != Some(span.color) {
+                let [r, g, b] = span.color;
+                out.extend_from_slice(
+                    format!(
+                        "{:.3} {:.3} {:.3} rg\n",
+                        f32::from(r) / 255.0,
+                        f32::from(g) / 255.0,
+                        f32::from(b) / 255.0
+                    )
+                    .as_bytes(),
+                );
+                current = Some(span.color);
+            }
+            pdf_string(span.text.as_str(), &mut out);
+            out.extend_from_slice(b" Tj\n");
+        }
+        out.extend_from_slice(b"T*\n");
+    }
+    out.extend_from_slice(b"ET\n");
+    out
+}
+
+fn decoration(text: String) -> Span {
+    Span {
+        color: DECORATION_COLOR,
+        text,
+    }
+}
+
+/// Wraps lines to the page width, prefixing a line-number gutter when enabled.
+fn layout_rows(lines: &[Vec<Span>], columns: usize, line_numbers: bool) -> Vec<Vec<Span>> {
+    let gutter = if line_numbers {
+        lines.len().max(1).to_string().len() + 2
+    } else {
+        0
+    };
+    let mut rows = Vec::with_capacity(lines.len());
+    for (idx, spans) in lines.iter().enumerate() {
+        for (row_idx, mut row) in wrap_spans(spans, columns.saturating_sub(gutter).max(1))
+            .into_iter()
+            .enumerate()
+        {
+            if line_numbers {
+                let label = if row_idx == 0 {
+                    format!("{:>width$}  ", idx + 1, width = gutter - 2)
+                } else {
+                    " ".repeat(gutter)
+                };
+                row.insert(0, decoration(label));
+            }
+            rows.push(row);
+        }
+    }
+    rows
+}
+
+/// Renders highlighted lines into a paginated A4 PDF.
+///
+/// # Arguments
+/// - `title`: Document title stored in the info dictionary.
+/// - `lines`: Highlighted source lines without trailing newlines.
+/// - `footnotes`: Annotation notes listed after the content.
+/// - `options`: Optional per-page header and line-number gutter.
+///
+/// # Returns
+/// Complete PDF file bytes.
+pub(super) fn render_pdf(
+    title: &str,
+    lines: &[Vec<Span>],
+    footnotes: &[String],
+    options: &PrintOptions,
+) -> Vec<u8> {
+    let columns = ((PAGE_WIDTH - 2.0 * MARGIN) / (FONT_SIZE * CHAR_ADVANCE)) as usize;
+    let mut rows_per_page = ((PAGE_HEIGHT - 2.0 * MARGIN) / LEADING) as usize;
+    if options.header.is_some() {
+        // Header row plus a blank separator row.
+        rows_per_page -= 2;
+    }
+    let mut rows = layout_rows(lines, columns, options.line_numbers);
+    if !footnotes.is_empty() {
+        rows.push(Vec::new());
+        rows.push(vec![decoration("Notes".to_string())]);
+        for note in footnotes {
+            rows.extend(wrap_spans(&[decoration(note.clone())], columns));
+        }
+    }
+    let pages: Vec<&[Vec<Span>]> = if rows.is_empty() {
+        vec![&[]]
+    } else {
+        rows.chunks(rows_per_page).collect()
+    };
+    let page_count = pages.len();
+
+    // Objects: 1 catalog, 2 page tree, 3 font, 4 info, then a page and its
+    // content stream per page.
+    let mut objects: Vec<Vec<u8>> = Vec::with_capacity(4 + pages.len() * 2);
+    let page_ids: Vec<usize> = (0..pages.len()).map(|idx| 5 + idx * 2).collect();
+    objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+    let kids: Vec<String> = page_ids.iter().map(|id| format!("{} 0 R", id)).collect();
+    objects.push(
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+    );
+    objects.push(
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>".to_vec(),
+    );
+    let mut info = b"<< /Producer (LocalPaste) /Title ".to_vec();
+    pdf_string(title, &mut info);
+    info.extend_from_slice(b" >>");
+    objects.push(info);
+    for (page_number, (page, page_id)) in (1..).zip(pages.iter().zip(&page_ids)) {
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+/Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                page_id + 1
+            )
+            .into_bytes(),
+        );
+        let header = options.header.as_ref().map(|header| {
+            let label = format!("{}  |  Page {} of {}", header, page_number, page_count);
+            vec![
+                vec![decoration(label.chars().take(columns).collect())],
+                Vec::new(),
+            ]
+        });
+        let content = match header {
+            Some(mut decorated) => {
+                decorated.extend_from_slice(page);
+                page_content(&decorated)
+            }
+            None => page_content(page),
+        };
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend_from_slice(&content);
+        stream.extend_from_slice(b"\nendstream");
+        objects.push(stream);
+    }
+
+    let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (idx, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n", idx + 1).as_bytes());
+        out.extend_from_slice(object);
+        out.extend_from_slice(b"\nendobj\n");
+    }
+    let xref_at = out.len();
+    out.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    out.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R /Info 4 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_at
+        )
+        .as_bytes(),
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(text: &str) -> Span {
+        Span {
+            color: [0, 0, 0],
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn wrap_spans_splits_long_rows_across_spans() {
+        let rows = wrap_spans(&[span("abcd"), span("efg")], 3);
+        let text: Vec<String> = rows
+            .iter()
+            .map(|row| row.iter().map(|span| span.text.as_str()).collect())
+            .collect();
+        assert_eq!(text, vec!["abc", "def", "g"]);
+        assert_eq!(wrap_spans(&[], 3).len(), 1);
+    }
+
+    #[test]
+    fn render_pdf_paginates_and_escapes_text() {
+        let lines: Vec<Vec<Span>> = (0..200)
+            .map(|i| vec![span(&format!("(line {})", i))])
+            .collect();
+        let pdf = render_pdf("demo", &lines, &[], &PrintOptions::default());
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.trim_end().ends_with("%%EOF"));
+        assert!(text.contains("/Count 3"));
+        assert!(text.contains("(\\(line 199\\)) Tj"));
+    }
+
+    #[test]
+    fn render_pdf_adds_page_headers_and_line_numbers() {
+        let lines: Vec<Vec<Span>> = (0..120).map(|_| vec![span("x")]).collect();
+        let options = PrintOptions {
+            header: Some("notes.md - 2026-01-02".to_string()),
+            line_numbers: true,
+        };
+        let pdf = render_pdf("notes.md", &lines, &[], &options);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Count 2"));
+        assert!(text.contains("(notes.md - 2026-01-02  |  Page 2 of 2) Tj"));
+        assert!(text.contains("(  1  ) Tj"));
+        assert!(text.contains("(120  ) Tj"));
+    }
+
+    #[test]
+    fn render_pdf_lists_footnotes_after_content() {
+        let lines = vec![vec![span("x")]];
+        let notes = ["[1] line 1 (ana): check this".to_string()];
+        let pdf = render_pdf("notes.md", &lines, &notes, &PrintOptions::default());
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("(Notes) Tj"));
+        assert!(text.contains("([1] line 1 \\(ana\\): check this) Tj"));
+    }
+}
@@ -1,4 +1,6 @@
 //! Syntax hint normalization and syntect grammar resolution helpers.
+//!
+//! Shared by the GUI highlighter and document exports so both pick the same grammar.
 
 use syntect::parsing::{SyntaxReference, SyntaxSet};
 
@@ -6,8 +8,8 @@ use syntect::parsing::{SyntaxReference, SyntaxSet};
 ///
 /// # Returns
 /// Canonical language hint or `"text"` when input is empty/unknown.
-pub(crate) fn syntect_language_hint(language: &str) -> String {
-    let canonical = localpaste_core::detection::canonical::canonicalize(language);
+pub fn syntect_language_hint(language: &str) -> String {
+    let canonical = crate::detection::canonical::canonicalize(language);
     if canonical.is_empty() {
         "text".to_string()
     } else {
@@ -87,11 +89,11 @@ fn syntax_fallback_candidates(hint_lower: &str) -> &'static [&'static str] {
 ///
 /// # Arguments
 /// - `ps`: Loaded syntect syntax set.
-/// - `hint`: Canonicalized language hint from app state.
+/// - `hint`: Canonicalized language hint.
 ///
 /// # Returns
 /// Best matching syntax definition, falling back to plain text.
-pub(crate) fn resolve_syntax<'a>(ps: &'a SyntaxSet, hint: &str) -> &'a SyntaxReference {
+pub fn resolve_syntax<'a>(ps: &'a SyntaxSet, hint: &str) -> &'a SyntaxReference {
     let hint_trimmed = hint.trim();
     if hint_trimmed.is_empty() {
         return ps.find_syntax_plain_text();
@@ -0,0 +1,412 @@
+//! Folder subtree and whole-store exports as ZIP archives with a JSON manifest.
+//!
+//! Each folder becomes a directory, each paste a file named after the paste
+//! with an extension derived from its language, and `manifest.json` records
+//! ids and metadata so the archive can be mapped back to pastes. Whole-store
+//! exports put top-level folders and unfiled pastes at the archive root.
+
+use crate::archive::ZipWriter;
+use crate::folder_ops::folder_delete_order;
+use crate::models::folder::Folder;
+use crate::naming::files::{language_extension, sanitize_filename};
+use crate::normalization::{restore_line_endings, LineEnding};
+use crate::{AppError, Database};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
+/// Manifest entry name at the archive root.
+pub const FOLDER_EXPORT_MANIFEST: &str = "manifest.json";
+/// `format` value identifying folder export manifests.
+pub const FOLDER_EXPORT_FORMAT: &str = "localpaste-folder-export";
+/// `format` value identifying whole-store export manifests.
+pub const STORE_EXPORT_FORMAT: &str = "localpaste-store-export";
+
+#[derive(Serialize)]
+struct ManifestFolder {
+    id: String,
+    name: String,
+    parent_id: Option<String>,
+    path: String,
+}
+
+#[derive(Serialize)]
+struct ManifestPaste {
+    id: String,
+    name: String,
+    path: String,
+    folder_id: Option<String>,
+    language: Option<String>,
+    language_is_manual: bool,
+    tags: Vec<String>,
+    line_ending: LineEnding,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+struct Manifest {
+    format: &'static str,
+    version: u32,
+    exported_at: DateTime<Utc>,
+    /// `None` for whole-store exports.
+    root_folder_id: Option<String>,
+    folders: Vec<ManifestFolder>,
+    pastes: Vec<ManifestPaste>,
+}
+
+/// Rendered folder or whole-store archive.
+#[derive(Debug)]
+pub struct FolderExport {
+    /// Suggested download name, e.g. `notes.zip`.
+    pub file_name: String,
+    /// ZIP archive bytes.
+    pub archive: Vec<u8>,
+    /// Number of pastes written.
+    pub paste_count: usize,
+}
+
+/// Returns `name` or the first `name-N` variant not yet in `used`.
+fn claim_unique(used: &mut HashSet<String>, dir: &str, stem: &str, extension: &str) -> String {
+    let join = |candidate: &str| {
+        let file = if extension.is_empty() {
+            candidate.to_string()
+        } else {
+            format!("{}.{}", candidate, extension)
+        };
+        if dir.is_empty() {
+            file
+        } else {
+            format!("{}/{}", dir, file)
+        }
+    };
+    let mut path = join(stem);
+    let mut suffix = 2;
+    while !used.insert(path.to_lowercase()) {
+        path = join(format!("{}-{}", stem, suffix).as_str());
+        suffix += 1;
+    }
+    path
+}
+
+/// Archive-safe path component: reserved characters and dot-only names replaced.
+fn path_component(name: &str) -> String {
+    let sanitized = sanitize_filename(name);
+    if sanitized.trim_matches('.').is_empty() {
+        "_".to_string()
+    } else {
+        sanitized
+    }
+}
+
+/// Assigns archive directory paths to every folder in the subtree.
+///
+/// `subtree` lists children before parents. The export root, or any folder
+/// whose parent is not being exported, is placed at the archive root.
+fn folder_paths(
+    folders: &[Folder],
+    subtree: &[String],
+    root_id: Option<&str>,
+) -> HashMap<String, String> {
+    let by_id: HashMap<&str, &Folder> = folders.iter().map(|f| (f.id.as_str(), f)).collect();
+    let mut paths: HashMap<String, String> = HashMap::new();
+    let mut used = HashSet::new();
+    // `folder_delete_order` lists children first; walk it reversed so parents resolve first.
+    for id in subtree.iter().rev() {
+        let Some(folder) = by_id.get(id.as_str()) else {
+            continue;
+        };
+        let parent_dir = if Some(id.as_str()) == root_id {
+            String::new()
+        } else {
+            folder
+                .parent_id
+                .as_deref()
+                .and_then(|parent| paths.get(parent))
+                .cloned()
+                .unwrap_or_default()
+        };
+        let path = claim_unique(
+            &mut used,
+            parent_dir.as_str(),
+            path_component(folder.name.as_str()).as_str(),
+            "",
+        );
+        paths.insert(id.clone(), path);
+    }
+    paths
+}
+
+/// Every folder, ordered so children come before their parents.
+///
+/// Folders whose parent is missing count as top-level, so an orphaned subtree
+/// is still exported.
+fn all_folders_order(folders: &[Folder]) -> Vec<String> {
+    let known: HashSet<&str> = folders.iter().map(|folder| folder.id.as_str()).collect();
+    folders
+        .iter()
+        .filter(|folder| {
+            folder
+                .parent_id
+                .as_deref()
+                .is_none_or(|parent| !known.contains(parent))
+        })
+        .flat_map(|top| folder_delete_order(folders, top.id.as_str()))
+        .collect()
+}
+
+/// Builds a ZIP of every paste under a folder, recursively.
+///
+/// # Arguments
+/// - `db`: Open database handle.
+/// - `folder_id`: Root folder of the export.
+///
+/// # Returns
+/// Archive bytes with one directory per folder and a `manifest.json`.
+///
+/// # Errors
+/// Returns [`AppError::NotFound`] for unknown folders or storage errors.
+pub fn export_folder_archive(db: &Database, folder_id: &str) -> Result<FolderExport, AppError> {
+    let folders = db.folders.list()?;
+    let root = folders
+        .iter()
+        .find(|folder| folder.id == folder_id)
+        .ok_or(AppError::NotFound)?;
+    let file_name = format!("{}.zip", path_component(root.name.as_str()));
+    let subtree = folder_delete_order(&folders, folder_id);
+    let (archive, paste_count) =
+        build_archive(db, &folders, &subtree, Some(folder_id), ZipWriter::new())?;
+    Ok(FolderExport {
+        file_name,
+        archive,
+        paste_count,
+    })
+}
+
+/// Builds a ZIP of every paste in the store.
+///
+/// Unfiled pastes and top-level folders sit at the archive root; the
+/// manifest has `format` [`STORE_EXPORT_FORMAT`] and a null
+/// `root_folder_id`.
+///
+/// # Returns
+/// Archive bytes named [`store_archive_file_name`].
+///
+/// # Errors
+/// Returns storage errors.
+pub fn export_store_archive(db: &Database) -> Result<FolderExport, AppError> {
+    let (archive, paste_count) = write_store_archive(db, Vec::new())?;
+    Ok(FolderExport {
+        file_name: store_archive_file_name(),
+        archive,
+        paste_count,
+    })
+}
+
+/// Download name for a whole-store export, `localpaste-export-<YYYYMMDD>.zip`.
+pub fn store_archive_file_name() -> String {
+    format!("localpaste-export-{}.zip", Utc::now().format("%Y%m%d"))
+}
+
+/// Streams the whole-store archive of [`export_store_archive`] into `out`.
+///
+/// Pastes are read and written one at a time, so memory stays bounded by the
+/// largest paste rather than the store.
+///
+/// # Returns
+/// The writer and the number of pastes written.
+///
+/// # Errors
+/// Returns storage errors, or [`AppError::StorageMessage`] when `out` fails.
+pub fn write_store_archive<W: Write>(db: &Database, out: W) -> Result<(W, usize), AppError> {
+    let folders = db.folders.list()?;
+    let order = all_folders_order(&folders);
+    build_archive(db, &folders, &order, None, ZipWriter::from_writer(out))
+}
+
+/// Writes the pastes filed in `subtree` (plus unfiled pastes when exporting
+/// the whole store) and the manifest.
+fn build_archive<W: Write>(
+    db: &Database,
+    folders: &[Folder],
+    subtree: &[String],
+    root_id: Option<&str>,
+    mut zip: ZipWriter<W>,
+) -> Result<(W, usize), AppError> {
+    let paths = folder_paths(folders, subtree, root_id);
+    // Select and order from metadata; content is loaded one paste at a time.
+    let mut metas = Vec::new();
+    db.pastes.scan_meta(|meta| {
+        let included = match meta.folder_id.as_deref() {
+            Some(id) => paths.contains_key(id),
+            None => root_id.is_none(),
+        };
+        if included {
+            metas.push(meta);
+        }
+        Ok(())
+    })?;
+    metas.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+
+    let zip_error = AppError::StorageMessage;
+    let mut used: HashSet<String> = paths.values().map(|path| path.to_lowercase()).collect();
+    used.insert(FOLDER_EXPORT_MANIFEST.to_string());
+    let mut manifest_pastes = Vec::with_capacity(metas.len());
+    for meta in &metas {
+        // Deleted since the scan; leave it out rather than fail the export.
+        let Some(paste) = db.pastes.get(meta.id.as_str())? else {
+            continue;
+        };
+        let dir = paste
+            .folder_id
+            .as_deref()
+            .and_then(|id| paths.get(id))
+            .map(String::as_str)
+            .unwrap_or_default();
+        let extension = language_extension(paste.language.as_deref());
+        let stem = path_component(paste.name.as_str());
+        // Names like `main.rs` already carry the extension.
+        let (stem, extension) = match stem.rsplit_once('.') {
+            Some((base, ext)) if ext.eq_ignore_ascii_case(extension) && !base.is_empty() => {
+                (base.to_string(), ext.to_string())
+            }
+            _ => (stem, extension.to_string()),
+        };
+        let path = claim_unique(&mut used, dir, stem.as_str(), extension.as_str());
+        let content = restore_line_endings(paste.content.as_str(), paste.line_ending);
+        zip.add_file(path.as_str(), content.as_bytes(), paste.updated_at)
+            .map_err(zip_error)?;
+        manifest_pastes.push(ManifestPaste {
+            id: paste.id,
+            name: paste.name,
+            path,
+            folder_id: paste.folder_id,
+            language: paste.language,
+            language_is_manual: paste.language_is_manual,
+            tags: paste.tags,
+            line_ending: paste.line_ending,
+            created_at: paste.created_at,
+            updated_at: paste.updated_at,
+        });
+    }
+    let paste_count = manifest_pastes.len();
+
+    let now = Utc::now();
+    let manifest = Manifest {
+        format: if root_id.is_some() {
+            FOLDER_EXPORT_FORMAT
+        } else {
+            STORE_EXPORT_FORMAT
+        },
+        version: 1,
+        exported_at: now,
+        root_folder_id: root_id.map(str::to_string),
+        folders: subtree
+            .iter()
+            .rev()
+            .filter_map(|id| folders.iter().find(|folder| &folder.id == id))
+            .map(|folder| ManifestFolder {
+                id: folder.id.clone(),
+                name: folder.name.clone(),
+                parent_id: folder.parent_id.clone(),
+                path: paths.get(&folder.id).cloned().unwrap_or_default(),
+            })
+            .collect(),
+        pastes: manifest_pastes,
+    };
+    let manifest = serde_json::to_vec_pretty(&manifest).map_err(|_| AppError::Internal)?;
+    zip.add_file(FOLDER_EXPORT_MANIFEST, &manifest, now)
+        .map_err(zip_error)?;
+
+    Ok((zip.finish().map_err(zip_error)?, paste_count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::TransactionOps;
+    use crate::models::paste::Paste;
+    use crate::test_support::setup_temp_db;
+
+    fn contains(haystack: &[u8], needle: &str) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle.as_bytes())
+    }
+
+    #[test]
+    fn export_folder_archive_walks_subfolders_and_skips_other_pastes() {
+        let (db, _temp) = setup_temp_db();
+        let root = Folder::new("Notes".to_string());
+        let child = Folder::with_parent("Sub".to_string(), Some(root.id.clone()));
+        let other = Folder::new("Other".to_string());
+        for folder in [&root, &child, &other] {
+            db.folders.create(folder).expect("create folder");
+        }
+        let mut filed = Vec::new();
+        for (name, language, folder) in [
+            ("main.rs", "rust", &root),
+            ("todo", "markdown", &child),
+            ("elsewhere", "text", &other),
+        ] {
+            let mut paste = Paste::new(format!("{} body", name), name.to_string());
+            paste.language = Some(language.to_string());
+            paste.folder_id = Some(folder.id.clone());
+            TransactionOps::create_paste_with_folder(&db, &paste, &folder.id).expect("create");
+            filed.push(paste.id);
+        }
+        db.pastes
+            .create(&Paste::new("loose".to_string(), "loose".to_string()))
+            .expect("create unfiled");
+
+        let export = export_folder_archive(&db, &root.id).expect("export");
+        assert_eq!(export.file_name, "Notes.zip");
+        assert_eq!(export.paste_count, 2);
+        assert!(contains(&export.archive, "Notes/main.rs"));
+        assert!(contains(&export.archive, "Notes/Sub/todo.md"));
+        assert!(contains(&export.archive, FOLDER_EXPORT_MANIFEST));
+        assert!(contains(&export.archive, filed[1].as_str()));
+        assert!(!contains(&export.archive, "elsewhere"));
+        assert!(!contains(&export.archive, "loose"));
+
+        assert!(matches!(
+            export_folder_archive(&db, "missing"),
+            Err(AppError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn export_store_archive_includes_every_folder_and_unfiled_paste() {
+        let (db, _temp) = setup_temp_db();
+        let root = Folder::new("Notes".to_string());
+        let child = Folder::with_parent("Sub".to_string(), Some(root.id.clone()));
+        for folder in [&root, &child] {
+            db.folders.create(folder).expect("create folder");
+        }
+        let mut nested = Paste::new("nested body".to_string(), "deep".to_string());
+        nested.folder_id = Some(child.id.clone());
+        TransactionOps::create_paste_with_folder(&db, &nested, &child.id).expect("create");
+        db.pastes
+            .create(&Paste::new("loose body".to_string(), "loose".to_string()))
+            .expect("create unfiled");
+
+        let export = export_store_archive(&db).expect("export");
+        assert!(export.file_name.starts_with("localpaste-export-"));
+        assert_eq!(export.paste_count, 2);
+        assert!(contains(&export.archive, "Notes/Sub/deep.txt"));
+        assert!(contains(&export.archive, "loose.txt"));
+        assert!(contains(&export.archive, STORE_EXPORT_FORMAT));
+        assert!(contains(&export.archive, "\"root_folder_id\": null"));
+    }
+
+    #[test]
+    fn claim_unique_suffixes_case_insensitive_collisions() {
+        let mut used = HashSet::new();
+        assert_eq!(claim_unique(&mut used, "a", "notes", "md"), "a/notes.md");
+        assert_eq!(claim_unique(&mut used, "a", "Notes", "md"), "a/Notes-2.md");
+        assert_eq!(claim_unique(&mut used, "", "notes", "md"), "notes.md");
+        assert_eq!(claim_unique(&mut used, "", "dir", ""), "dir");
+        assert_eq!(path_component(".."), "_");
+    }
+}
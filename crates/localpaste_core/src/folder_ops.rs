@@ -2,7 +2,12 @@
 
 use crate::{
     db::TransactionOps,
-    models::{folder::Folder, paste::UpdatePasteRequest},
+    models::{
+        folder::{
+            Folder, FolderListItem, FolderListQuery, FolderSortKey, FolderTreeNode, SortOrder,
+        },
+        paste::UpdatePasteRequest,
+    },
     AppError, Database,
 };
 use redb::ReadableTable;
@@ -104,6 +109,33 @@ pub fn create_folder_validated(
     Ok(folder)
 }
 
+/// Validate a parent change for `id`; an empty `parent_id` means top level.
+///
+/// Callers must hold the folder txn guard so the cycle check sees a stable
+/// tree.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] for self-parenting, a missing or
+/// deleting parent, or a parent inside the folder's own subtree.
+fn validate_parent_change(db: &Database, id: &str, parent_id: &str) -> Result<(), AppError> {
+    if parent_id == id {
+        return Err(AppError::BadRequest(
+            "Folder cannot be its own parent".to_string(),
+        ));
+    }
+    if !parent_id.is_empty() {
+        ensure_folder_assignable(db, parent_id)
+            .map_err(|err| map_missing_folder_for_request(err, parent_id, "Parent folder"))?;
+        let folders = db.folders.list()?;
+        if introduces_cycle(&folders, id, parent_id) {
+            return Err(AppError::BadRequest(
+                "Folder parent change would create cycle".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Update a folder with canonical parent validation under the folder txn lock.
 ///
 /// # Arguments
@@ -127,26 +159,163 @@ pub fn update_folder_validated(
     let parent_update = parent_id.map(|pid| pid.trim().to_string());
 
     if let Some(parent_id) = parent_update.as_deref() {
-        if parent_id == id {
-            return Err(AppError::BadRequest(
-                "Folder cannot be its own parent".to_string(),
-            ));
-        }
-        if !parent_id.is_empty() {
-            ensure_folder_assignable(db, parent_id)
-                .map_err(|err| map_missing_folder_for_request(err, parent_id, "Parent folder"))?;
-            let folders = db.folders.list()?;
-            if introduces_cycle(&folders, id, parent_id) {
-                return Err(AppError::BadRequest(
-                    "Updating folder would create cycle".to_string(),
-                ));
-            }
-        }
+        validate_parent_change(db, id, parent_id)?;
     }
 
     db.folders.update(id, name, parent_update)
 }
 
+/// Re-parent a folder, keeping its name, pastes, and subfolders.
+///
+/// # Arguments
+/// - `db`: Open database handle.
+/// - `id`: Folder id to move.
+/// - `parent_id`: New parent; `None` or empty moves it to the top level.
+///
+/// # Returns
+/// `Ok(Some(folder))` when moved, `Ok(None)` when the folder is missing.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] when the move would make the folder its
+/// own ancestor or the parent cannot take children, or storage errors.
+pub fn move_folder_validated(
+    db: &Database,
+    id: &str,
+    parent_id: Option<String>,
+) -> Result<Option<Folder>, AppError> {
+    let _folder_guard = TransactionOps::acquire_folder_txn_guard(db)?;
+    let Some(folder) = db.folders.get(id)? else {
+        return Ok(None);
+    };
+    let parent_id = parent_id
+        .map(|pid| pid.trim().to_string())
+        .unwrap_or_default();
+    validate_parent_change(db, id, parent_id.as_str())?;
+    db.folders.update(id, folder.name, Some(parent_id))
+}
+
+/// Split a slash-separated folder path such as `work/project/logs` into
+/// trimmed folder names, ignoring empty segments.
+///
+/// # Returns
+/// Folder names from the top level down.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] when the path names no folder.
+pub fn parse_folder_path(path: &str) -> Result<Vec<String>, AppError> {
+    let segments: Vec<String> = path
+        .split('/')
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+        .collect();
+    if segments.is_empty() {
+        return Err(AppError::BadRequest(
+            "Folder path must name at least one folder".to_string(),
+        ));
+    }
+    Ok(segments)
+}
+
+/// Child of `parent_id` named exactly `name`; the oldest wins when siblings
+/// share a name.
+fn child_named<'a>(
+    folders: &'a [Folder],
+    parent_id: Option<&str>,
+    name: &str,
+) -> Option<&'a Folder> {
+    folders
+        .iter()
+        .filter(|folder| folder.parent_id.as_deref() == parent_id && folder.name == name)
+        .min_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.id.cmp(&b.id))
+        })
+}
+
+/// Id of the existing folder at `segments`, without creating anything.
+///
+/// # Returns
+/// `Ok(None)` when any folder along the path is missing.
+///
+/// # Errors
+/// Returns storage errors when listing folders fails.
+pub fn find_folder_path(db: &Database, segments: &[String]) -> Result<Option<String>, AppError> {
+    let folders = db.folders.list()?;
+    let mut parent_id: Option<String> = None;
+    for name in segments {
+        match child_named(&folders, parent_id.as_deref(), name) {
+            Some(folder) => parent_id = Some(folder.id.clone()),
+            None => return Ok(None),
+        }
+    }
+    Ok(parent_id)
+}
+
+/// Resolve `segments` to a folder id under the folder txn lock, optionally
+/// creating missing folders.
+///
+/// # Arguments
+/// - `db`: Open database handle.
+/// - `segments`: Folder names from [`parse_folder_path`].
+/// - `create_missing`: Create folders that do not exist yet instead of failing.
+///
+/// # Returns
+/// The id of the last folder on the path and the folders created, parents
+/// first.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] when a folder is missing and
+/// `create_missing` is `false`, or when a folder on the path is being
+/// deleted, and storage errors when listing or creating fails.
+pub fn resolve_folder_path(
+    db: &Database,
+    segments: &[String],
+    create_missing: bool,
+) -> Result<(String, Vec<Folder>), AppError> {
+    let _folder_guard = TransactionOps::acquire_folder_txn_guard(db)?;
+    let folders = db.folders.list()?;
+    let mut parent_id: Option<String> = None;
+    let mut created = Vec::new();
+    for (depth, name) in segments.iter().enumerate() {
+        let existing = if created.is_empty() {
+            child_named(&folders, parent_id.as_deref(), name)
+        } else {
+            None
+        };
+        let id = match existing {
+            Some(folder) => {
+                if db.folders.is_delete_marked(&folder.id)? {
+                    return Err(AppError::BadRequest(format!(
+                        "Folder '{}' is being deleted",
+                        segments[..=depth].join("/")
+                    )));
+                }
+                folder.id.clone()
+            }
+            None if create_missing => {
+                let folder = Folder::with_parent(name.clone(), parent_id.clone());
+                db.folders.create(&folder)?;
+                let id = folder.id.clone();
+                created.push(folder);
+                id
+            }
+            None => {
+                return Err(AppError::BadRequest(format!(
+                    "Folder path '{}' does not exist",
+                    segments[..=depth].join("/")
+                )));
+            }
+        };
+        parent_id = Some(id);
+    }
+    let folder_id = parent_id.ok_or_else(|| {
+        AppError::BadRequest("Folder path must name at least one folder".to_string())
+    })?;
+    Ok((folder_id, created))
+}
+
 /// Returns `true` if assigning `folder_id` under `new_parent_id` introduces a cycle.
 ///
 /// # Arguments
@@ -204,6 +373,175 @@ pub fn folder_delete_order(folders: &[Folder], root_id: &str) -> Vec<String> {
     discovered
 }
 
+/// Sum each folder's direct paste count with those of all its descendants.
+///
+/// # Arguments
+/// - `folders`: Full folder list.
+///
+/// # Returns
+/// Recursive paste counts keyed by folder id.
+pub fn recursive_paste_counts(folders: &[Folder]) -> HashMap<String, usize> {
+    let parent_map: HashMap<&str, Option<&str>> = folders
+        .iter()
+        .map(|f| (f.id.as_str(), f.parent_id.as_deref()))
+        .collect();
+    let mut totals: HashMap<String, usize> = HashMap::with_capacity(folders.len());
+    for folder in folders {
+        // Credit the folder and every ancestor; `visited` guards corrupted cycles.
+        let mut current = Some(folder.id.as_str());
+        let mut visited = HashSet::new();
+        while let Some(curr) = current {
+            if !visited.insert(curr) {
+                break;
+            }
+            *totals.entry(curr.to_string()).or_default() += folder.paste_count;
+            current = parent_map.get(curr).copied().flatten();
+        }
+    }
+    totals
+}
+
+/// List folders filtered by parent, optionally with recursive counts and sorted.
+///
+/// # Arguments
+/// - `db`: Open database handle.
+/// - `query`: Parent filter, count, and sort options.
+///
+/// # Returns
+/// Matching folder rows; name order is kept when no sort key is given.
+///
+/// # Errors
+/// Returns [`AppError::NotFound`] when `parent_id` names a missing folder, or
+/// storage errors when listing fails.
+pub fn list_folders_filtered(
+    db: &Database,
+    query: &FolderListQuery,
+) -> Result<Vec<FolderListItem>, AppError> {
+    let folders = db.folders.list()?;
+    let counts = query
+        .recursive_counts
+        .unwrap_or(false)
+        .then(|| recursive_paste_counts(&folders));
+    // `Some(None)` selects top-level folders; `None` keeps every folder.
+    let parent_filter = query
+        .parent_id
+        .as_deref()
+        .map(|pid| Some(pid.trim()).filter(|pid| !pid.is_empty()));
+    if let Some(Some(parent_id)) = parent_filter {
+        if !folders.iter().any(|f| f.id == parent_id) {
+            return Err(AppError::NotFound);
+        }
+    }
+
+    let mut items: Vec<FolderListItem> = folders
+        .into_iter()
+        .filter(|f| parent_filter.is_none_or(|parent| f.parent_id.as_deref() == parent))
+        .map(|folder| FolderListItem {
+            recursive_paste_count: counts.as_ref().map(|c| c[folder.id.as_str()]),
+            folder,
+        })
+        .collect();
+    if let Some(key) = query.sort {
+        items.sort_by(|a, b| match key {
+            FolderSortKey::Name => a
+                .folder
+                .name
+                .to_lowercase()
+                .cmp(&b.folder.name.to_lowercase()),
+            FolderSortKey::CreatedAt => a.folder.created_at.cmp(&b.folder.created_at),
+            FolderSortKey::PasteCount => a
+                .recursive_paste_count
+                .unwrap_or(a.folder.paste_count)
+                .cmp(&b.recursive_paste_count.unwrap_or(b.folder.paste_count)),
+        });
+        if query.order.unwrap_or_default() == SortOrder::Desc {
+            items.reverse();
+        }
+    }
+    Ok(items)
+}
+
+fn attach_tree_node(
+    folder: Folder,
+    children: &mut HashMap<Option<String>, Vec<Folder>>,
+    counts: &HashMap<String, usize>,
+) -> FolderTreeNode {
+    let kids = children
+        .remove(&Some(folder.id.clone()))
+        .unwrap_or_default();
+    FolderTreeNode {
+        recursive_paste_count: counts
+            .get(&folder.id)
+            .copied()
+            .unwrap_or(folder.paste_count),
+        children: kids
+            .into_iter()
+            .map(|child| attach_tree_node(child, children, counts))
+            .collect(),
+        folder,
+    }
+}
+
+/// Arrange folders into nested tree nodes with recursive paste counts.
+///
+/// Siblings are ordered by case-insensitive name. Folders whose parent is
+/// missing become roots, and a corrupted parent cycle is broken at the
+/// first folder reached, so every folder appears exactly once.
+///
+/// # Arguments
+/// - `folders`: Flat folder list, as stored.
+///
+/// # Returns
+/// Top-level nodes with their descendants nested under `children`.
+pub fn build_folder_tree(folders: Vec<Folder>) -> Vec<FolderTreeNode> {
+    let counts = recursive_paste_counts(&folders);
+    let known: HashSet<String> = folders.iter().map(|f| f.id.clone()).collect();
+    let mut children: HashMap<Option<String>, Vec<Folder>> = HashMap::new();
+    for folder in folders {
+        let parent = folder
+            .parent_id
+            .clone()
+            .filter(|parent| known.contains(parent) && *parent != folder.id);
+        children.entry(parent).or_default().push(folder);
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_by(|a, b| {
+            a.name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then_with(|| a.id.cmp(&b.id))
+        });
+    }
+
+    let roots = children.remove(&None).unwrap_or_default();
+    let mut tree: Vec<FolderTreeNode> = roots
+        .into_iter()
+        .map(|root| attach_tree_node(root, &mut children, &counts))
+        .collect();
+    // Whatever is left only hangs off a cycle; surface each loop at its
+    // lowest-sorting member so no folder disappears from the tree.
+    while let Some(parent) = children.keys().min().cloned() {
+        let mut stranded = children.remove(&parent).unwrap_or_default();
+        let first = stranded.remove(0);
+        if !stranded.is_empty() {
+            children.insert(parent, stranded);
+        }
+        tree.push(attach_tree_node(first, &mut children, &counts));
+    }
+    tree
+}
+
+/// Load every folder and return it as a nested tree.
+///
+/// # Returns
+/// Top-level folder nodes; see [`build_folder_tree`].
+///
+/// # Errors
+/// Returns an error when folders cannot be listed.
+pub fn folder_tree(db: &Database) -> Result<Vec<FolderTreeNode>, AppError> {
+    Ok(build_folder_tree(db.folders.list()?))
+}
+
 /// Deletes a folder tree and migrates all affected pastes to unfiled.
 ///
 /// # Arguments
@@ -289,7 +627,10 @@ fn delete_folder_tree_and_migrate_with_order_locked(
     _folder_guard: &crate::db::FolderTxnGuard<'_>,
     delete_order: Vec<String>,
 ) -> Result<Vec<String>, AppError> {
-    use crate::db::paste::{apply_update_request, deserialize_paste, reverse_timestamp_key};
+    use crate::db::paste::{
+        apply_update_request, deserialize_paste, reverse_timestamp_key, FilterIndexKeys,
+        FilterIndexTables,
+    };
     use crate::db::tables::{FOLDERS, FOLDERS_DELETING, PASTES, PASTES_BY_UPDATED, PASTES_META};
     use crate::models::paste::PasteMeta;
 
@@ -301,6 +642,7 @@ fn delete_folder_tree_and_migrate_with_order_locked(
         language_is_manual: None,
         folder_id: Some(String::new()),
         tags: None,
+        expires_in: None,
     };
 
     let write_txn = db.db.begin_write()?;
@@ -308,6 +650,7 @@ fn delete_folder_tree_and_migrate_with_order_locked(
         let mut pastes = write_txn.open_table(PASTES)?;
         let mut metas = write_txn.open_table(PASTES_META)?;
         let mut updated = write_txn.open_table(PASTES_BY_UPDATED)?;
+        let mut filters = FilterIndexTables::open(&write_txn)?;
         let mut folders = write_txn.open_table(FOLDERS)?;
         let mut deleting = write_txn.open_table(FOLDERS_DELETING)?;
 
@@ -340,9 +683,10 @@ fn delete_folder_tree_and_migrate_with_order_locked(
             };
             let mut paste = deserialize_paste(paste_guard.value())?;
             let old_recency_key = reverse_timestamp_key(paste.updated_at);
+            let old_filter_keys = FilterIndexKeys::from_paste(&paste);
             drop(paste_guard);
 
-            apply_update_request(&mut paste, &clear_folder_update);
+            apply_update_request(&mut paste, &clear_folder_update, db.pastes.normalization());
             let encoded_paste = bincode::serialize(&paste)?;
             let encoded_meta = bincode::serialize(&PasteMeta::from(&paste))?;
             let new_recency_key = reverse_timestamp_key(paste.updated_at);
@@ -350,6 +694,11 @@ fn delete_folder_tree_and_migrate_with_order_locked(
             metas.insert(paste_id.as_str(), encoded_meta.as_slice())?;
             let _ = updated.remove((old_recency_key, paste_id.as_str()))?;
             updated.insert((new_recency_key, paste_id.as_str()), ())?;
+            filters.replace(
+                paste_id.as_str(),
+                &old_filter_keys,
+                &FilterIndexKeys::from_paste(&paste),
+            )?;
         }
 
         for folder_id in &delete_order {
@@ -397,11 +746,19 @@ fn reconcile_folder_parent_invariants_locked(
     Ok(())
 }
 
+/// Exact per-folder stats accumulated during reconciliation.
+#[derive(Default)]
+struct FolderStatsTally {
+    count: usize,
+    bytes: u64,
+    last_updated: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 /// Reconcile folder invariants from canonical paste rows.
 ///
 /// # Returns
-/// `Ok(())` when parent references, orphan folder references, and exact counts
-/// are repaired.
+/// `Ok(())` when parent references, orphan folder references, and exact
+/// counts, byte totals, and last-updated times are repaired.
 ///
 /// # Errors
 /// Returns storage and serialization errors when reconciliation cannot complete.
@@ -413,14 +770,21 @@ pub fn reconcile_folder_invariants(db: &Database) -> Result<(), AppError> {
     let folders = db.folders.list()?;
     let folder_id_set: HashSet<String> = folders.iter().map(|folder| folder.id.clone()).collect();
     let mut orphan_ids = Vec::new();
-    let mut exact_counts: HashMap<String, usize> = HashMap::new();
+    let mut exact_stats: HashMap<String, FolderStatsTally> = HashMap::new();
 
     db.pastes.scan_canonical_meta(|meta| {
         let Some(folder_id) = meta.folder_id.as_deref() else {
             return Ok(());
         };
         if folder_id_set.contains(folder_id) {
-            *exact_counts.entry(folder_id.to_string()).or_insert(0) += 1;
+            let tally = exact_stats.entry(folder_id.to_string()).or_default();
+            tally.count += 1;
+            tally.bytes = tally.bytes.saturating_add(meta.content_len as u64);
+            tally.last_updated = Some(
+                tally
+                    .last_updated
+                    .map_or(meta.updated_at, |prev| prev.max(meta.updated_at)),
+            );
         } else {
             orphan_ids.push(meta.id);
         }
@@ -437,6 +801,7 @@ pub fn reconcile_folder_invariants(db: &Database) -> Result<(), AppError> {
                 language_is_manual: None,
                 folder_id: Some(String::new()),
                 tags: None,
+                expires_in: None,
             };
             let _ = TransactionOps::move_paste_between_folders_locked(
                 db,
@@ -449,8 +814,11 @@ pub fn reconcile_folder_invariants(db: &Database) -> Result<(), AppError> {
     }
 
     for folder in db.folders.list()? {
-        let count = exact_counts.get(folder.id.as_str()).copied().unwrap_or(0);
-        db.folders.set_count(folder.id.as_str(), count)?;
+        let tally = exact_stats.remove(folder.id.as_str()).unwrap_or_default();
+        // Moves and deletes can leave a newer `last_updated` than any remaining paste.
+        let last_updated = tally.last_updated.max(folder.last_updated);
+        db.folders
+            .set_stats(folder.id.as_str(), tally.count, tally.bytes, last_updated)?;
     }
 
     Ok(())
@@ -475,6 +843,140 @@ mod tests {
         assert!(!introduces_cycle(&folders, &child.id, &root.id));
     }
 
+    #[test]
+    fn folder_tree_nests_sorted_children_and_keeps_stranded_folders() {
+        let mut root = Folder::with_parent("root".to_string(), None);
+        root.paste_count = 1;
+        let mut beta = Folder::with_parent("beta".to_string(), Some(root.id.clone()));
+        beta.paste_count = 2;
+        let alpha = Folder::with_parent("Alpha".to_string(), Some(root.id.clone()));
+        let orphan = Folder::with_parent("orphan".to_string(), Some("gone".to_string()));
+        let mut loop_a = Folder::with_parent("loop-a".to_string(), None);
+        let mut loop_b = Folder::with_parent("loop-b".to_string(), Some(loop_a.id.clone()));
+        loop_a.parent_id = Some(loop_b.id.clone());
+        loop_b.paste_count = 4;
+
+        let tree = build_folder_tree(vec![
+            beta.clone(),
+            loop_a.clone(),
+            orphan.clone(),
+            root.clone(),
+            alpha.clone(),
+            loop_b.clone(),
+        ]);
+        let top: Vec<&str> = tree.iter().map(|n| n.folder.name.as_str()).collect();
+        assert_eq!(top[..2], ["orphan", "root"]);
+        let root_node = &tree[1];
+        assert_eq!(root_node.recursive_paste_count, 3);
+        let kids: Vec<&str> = root_node
+            .children
+            .iter()
+            .map(|n| n.folder.name.as_str())
+            .collect();
+        assert_eq!(kids, ["Alpha", "beta"]);
+
+        // The two-folder loop is surfaced once, with the other member nested.
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree[2].children.len(), 1);
+        let mut loop_names = [
+            tree[2].folder.name.as_str(),
+            tree[2].children[0].folder.name.as_str(),
+        ];
+        loop_names.sort();
+        assert_eq!(loop_names, ["loop-a", "loop-b"]);
+    }
+
+    #[test]
+    fn move_folder_reparents_subtree_and_refuses_loops() {
+        let (db, _dir) = crate::test_support::setup_temp_db();
+        let root = create_folder_validated(&db, "root".to_string(), None).expect("root");
+        let child = create_folder_validated(&db, "child".to_string(), Some(root.id.clone()))
+            .expect("child");
+        let other = create_folder_validated(&db, "other".to_string(), None).expect("other");
+        let paste = Paste::new("kept".to_string(), "note".to_string());
+        TransactionOps::create_paste_with_folder(&db, &paste, &child.id).expect("paste");
+
+        for target in [root.id.as_str(), child.id.as_str()] {
+            assert!(matches!(
+                move_folder_validated(&db, &root.id, Some(target.to_string())),
+                Err(AppError::BadRequest(_))
+            ));
+        }
+        assert!(matches!(
+            move_folder_validated(&db, &root.id, Some("missing".to_string())),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(move_folder_validated(&db, "missing", None)
+            .expect("missing folder")
+            .is_none());
+
+        let moved = move_folder_validated(&db, &child.id, Some(other.id.clone()))
+            .expect("move")
+            .expect("folder");
+        assert_eq!(moved.name, "child");
+        assert_eq!(moved.parent_id.as_deref(), Some(other.id.as_str()));
+        assert_eq!(moved.paste_count, 1);
+        let stored = db.pastes.get(&paste.id).expect("get").expect("paste");
+        assert_eq!(stored.folder_id.as_deref(), Some(child.id.as_str()));
+
+        let top = move_folder_validated(&db, &child.id, Some(" ".to_string()))
+            .expect("move to top")
+            .expect("folder");
+        assert!(top.parent_id.is_none());
+    }
+
+    #[test]
+    fn recursive_counts_include_descendants() {
+        let mut root = Folder::with_parent("root".to_string(), None);
+        root.paste_count = 1;
+        let mut child = Folder::with_parent("child".to_string(), Some(root.id.clone()));
+        child.paste_count = 2;
+        let mut leaf = Folder::with_parent("leaf".to_string(), Some(child.id.clone()));
+        leaf.paste_count = 4;
+        let counts = recursive_paste_counts(&[root.clone(), child.clone(), leaf.clone()]);
+
+        assert_eq!(counts[&root.id], 7);
+        assert_eq!(counts[&child.id], 6);
+        assert_eq!(counts[&leaf.id], 4);
+    }
+
+    #[test]
+    fn folder_paths_resolve_existing_folders_and_create_missing_ones() {
+        let (db, _dir) = crate::test_support::setup_temp_db();
+        let work = Folder::with_parent("work".to_string(), None);
+        db.folders.create(&work).expect("create work");
+
+        assert!(matches!(
+            parse_folder_path(" / "),
+            Err(AppError::BadRequest(_))
+        ));
+        let segments = parse_folder_path("/work// project /logs").expect("path");
+        assert_eq!(segments, vec!["work", "project", "logs"]);
+        assert_eq!(find_folder_path(&db, &segments).expect("find"), None);
+        assert!(matches!(
+            resolve_folder_path(&db, &segments, false),
+            Err(AppError::BadRequest(message)) if message.contains("work/project")
+        ));
+
+        let (logs_id, created) = resolve_folder_path(&db, &segments, true).expect("create");
+        assert_eq!(created.len(), 2);
+        assert_eq!(created[0].parent_id.as_deref(), Some(work.id.as_str()));
+        assert_eq!(created[1].id, logs_id);
+        assert_eq!(
+            created[1].parent_id.as_deref(),
+            Some(created[0].id.as_str())
+        );
+
+        let (again, created) = resolve_folder_path(&db, &segments, false).expect("resolve");
+        assert_eq!(again, logs_id);
+        assert!(created.is_empty());
+        assert_eq!(
+            find_folder_path(&db, &segments).expect("find"),
+            Some(logs_id)
+        );
+        assert_eq!(db.folders.list().expect("list").len(), 3);
+    }
+
     #[test]
     fn delete_order_is_children_first() {
         let root = Folder::with_parent("root".to_string(), None);
@@ -692,6 +1194,7 @@ mod tests {
                 language_is_manual: None,
                 folder_id: Some(move_target.clone()),
                 tags: None,
+                expires_in: None,
             };
             TransactionOps::move_paste_between_folders(
                 &move_db,
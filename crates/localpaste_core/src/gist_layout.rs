@@ -0,0 +1,318 @@
+//! Gist-style directory layout: one directory per paste holding the content
+//! file and a `meta.json` sidecar.
+//!
+//! Directories are named after the paste id so renames never move them, which
+//! keeps the tree friendly to file-sync tools. Content files keep the paste's
+//! recorded line ending, so an export followed by an import reproduces the
+//! original bytes.
+
+use crate::import::ImportedSnippet;
+use crate::models::paste::Paste;
+use crate::naming::files::{language_extension, sanitize_filename};
+use crate::normalization::{restore_line_endings, LineEnding};
+use crate::{AppError, Database};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Sidecar file name inside each paste directory.
+pub const GIST_META_FILE: &str = "meta.json";
+/// `format` value identifying gist layout sidecars.
+pub const GIST_LAYOUT_FORMAT: &str = "localpaste-gist";
+/// Current sidecar schema version.
+pub const GIST_LAYOUT_VERSION: u32 = 1;
+
+/// Contents of a paste directory's `meta.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GistMeta {
+    /// Always [`GIST_LAYOUT_FORMAT`].
+    pub format: String,
+    /// Sidecar schema version.
+    pub version: u32,
+    /// Paste id; also the directory name.
+    pub id: String,
+    /// Paste name.
+    pub name: String,
+    /// Content file name within the paste directory.
+    pub file: String,
+    /// Stored language label.
+    pub language: Option<String>,
+    /// Whether the language is locked against re-detection.
+    #[serde(default)]
+    pub language_is_manual: bool,
+    /// Folder id at export time; informational on import.
+    #[serde(default)]
+    pub folder_id: Option<String>,
+    /// Paste tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Line ending the content file was written with.
+    #[serde(default)]
+    pub line_ending: LineEnding,
+    /// Creation time.
+    pub created_at: DateTime<Utc>,
+    /// Last update time.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One paste directory read back from disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GistEntry {
+    /// Parsed sidecar.
+    pub meta: GistMeta,
+    /// Content file text, exactly as stored on disk.
+    pub content: String,
+}
+
+impl GistEntry {
+    /// Converts the entry into an importer row that keeps the source paste id.
+    pub fn into_snippet(self) -> ImportedSnippet {
+        ImportedSnippet {
+            id: Some(self.meta.id),
+            name: self.meta.name,
+            content: self.content,
+            language: self.meta.language,
+            tags: self.meta.tags,
+        }
+    }
+}
+
+/// Content file name for a paste: sanitized name plus a language extension.
+///
+/// Names that already end in the extension (`main.rs`) are kept as-is.
+pub fn gist_content_file_name(name: &str, language: Option<&str>) -> String {
+    let extension = language_extension(language);
+    let stem = sanitize_filename(name);
+    let stem = if stem.trim_matches('.').is_empty() {
+        "paste".to_string()
+    } else {
+        stem
+    };
+    let file = match stem.rsplit_once('.') {
+        Some((base, ext)) if ext.eq_ignore_ascii_case(extension) && !base.is_empty() => stem,
+        _ => format!("{}.{}", stem, extension),
+    };
+    if file.eq_ignore_ascii_case(GIST_META_FILE) {
+        format!("_{}", file)
+    } else {
+        file
+    }
+}
+
+/// A content file name is a single plain component that is not the sidecar.
+fn is_plain_file_name(file: &str) -> bool {
+    !file.is_empty()
+        && !file.eq_ignore_ascii_case(GIST_META_FILE)
+        && !file.contains(['/', '\\'])
+        && !file.trim_matches('.').is_empty()
+}
+
+fn io_error(path: &Path, err: impl std::fmt::Display) -> AppError {
+    AppError::StorageMessage(format!("{}: {}", path.display(), err))
+}
+
+fn read_meta(path: &Path) -> Result<Option<GistMeta>, AppError> {
+    let raw = match std::fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(io_error(path, err)),
+    };
+    serde_json::from_str(raw.as_str())
+        .map(Some)
+        .map_err(|err| AppError::BadRequest(format!("Cannot parse {}: {}", path.display(), err)))
+}
+
+/// Writes one paste into `root/<id>/`, replacing any earlier export of it.
+///
+/// A content file left behind by an earlier name is removed, and the sidecar
+/// is written last so sync tools never see metadata for missing content.
+///
+/// # Arguments
+/// - `root`: Layout root directory; created when missing.
+/// - `paste`: Paste to write.
+///
+/// # Returns
+/// Path of the paste directory.
+///
+/// # Errors
+/// Returns [`AppError::StorageMessage`] when the id is not a plain directory
+/// name or files cannot be written.
+pub fn write_gist_paste(root: &Path, paste: &Paste) -> Result<PathBuf, AppError> {
+    if !is_plain_file_name(paste.id.as_str()) {
+        return Err(AppError::StorageMessage(format!(
+            "paste id '{}' is not a valid directory name",
+            paste.id
+        )));
+    }
+    let dir = root.join(paste.id.as_str());
+    std::fs::create_dir_all(&dir).map_err(|err| io_error(&dir, err))?;
+    let meta_path = dir.join(GIST_META_FILE);
+    let file = gist_content_file_name(paste.name.as_str(), paste.language.as_deref());
+    if let Ok(Some(previous)) = read_meta(&meta_path) {
+        if previous.file != file && is_plain_file_name(previous.file.as_str()) {
+            let _ = std::fs::remove_file(dir.join(previous.file.as_str()));
+        }
+    }
+
+    let content_path = dir.join(file.as_str());
+    let content = restore_line_endings(paste.content.as_str(), paste.line_ending);
+    std::fs::write(&content_path, content.as_bytes())
+        .map_err(|err| io_error(&content_path, err))?;
+    let meta = GistMeta {
+        format: GIST_LAYOUT_FORMAT.to_string(),
+        version: GIST_LAYOUT_VERSION,
+        id: paste.id.clone(),
+        name: paste.name.clone(),
+        file,
+        language: paste.language.clone(),
+        language_is_manual: paste.language_is_manual,
+        folder_id: paste.folder_id.clone(),
+        tags: paste.tags.clone(),
+        line_ending: paste.line_ending,
+        created_at: paste.created_at,
+        updated_at: paste.updated_at,
+    };
+    let mut encoded = serde_json::to_vec_pretty(&meta).map_err(|_| AppError::Internal)?;
+    encoded.push(b'\n');
+    std::fs::write(&meta_path, encoded).map_err(|err| io_error(&meta_path, err))?;
+    Ok(dir)
+}
+
+/// Writes every paste in the database into a gist layout.
+///
+/// # Returns
+/// Number of pastes written.
+///
+/// # Errors
+/// Returns storage errors from the database or filesystem.
+pub fn export_gist_layout(db: &Database, root: &Path) -> Result<usize, AppError> {
+    let pastes = db.pastes.list(usize::MAX, None)?;
+    for paste in &pastes {
+        write_gist_paste(root, paste)?;
+    }
+    Ok(pastes.len())
+}
+
+/// Reads every paste directory under `root`, sorted by directory name.
+///
+/// Subdirectories without a `meta.json` are ignored so the layout can share a
+/// directory with sync-tool bookkeeping folders.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] for unreadable directories, malformed
+/// sidecars, sidecars whose id does not match their directory, or content
+/// files that escape their paste directory.
+pub fn read_gist_layout(root: &Path) -> Result<Vec<GistEntry>, AppError> {
+    let bad =
+        |err: String| AppError::BadRequest(format!("Cannot read {}: {}", root.display(), err));
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(root)
+        .map_err(|err| bad(err.to_string()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+
+    let mut entries = Vec::with_capacity(dirs.len());
+    for dir in dirs {
+        let meta_path = dir.join(GIST_META_FILE);
+        let Some(meta) = read_meta(&meta_path)? else {
+            continue;
+        };
+        if meta.format != GIST_LAYOUT_FORMAT || meta.version > GIST_LAYOUT_VERSION {
+            return Err(bad(format!(
+                "{} is not a supported {} v{} sidecar",
+                meta_path.display(),
+                GIST_LAYOUT_FORMAT,
+                GIST_LAYOUT_VERSION
+            )));
+        }
+        let dir_name = dir.file_name().and_then(|name| name.to_str());
+        if dir_name != Some(meta.id.as_str()) || !is_plain_file_name(meta.file.as_str()) {
+            return Err(bad(format!(
+                "{} has an invalid id or file name",
+                meta_path.display()
+            )));
+        }
+        let content_path = dir.join(meta.file.as_str());
+        let content = std::fs::read_to_string(&content_path)
+            .map_err(|err| bad(format!("{}: {}", content_path.display(), err)))?;
+        entries.push(GistEntry { meta, content });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn paste(name: &str, content: &str) -> Paste {
+        let mut paste = Paste::new(content.to_string(), name.to_string());
+        paste.language = Some("rust".to_string());
+        paste.tags = vec!["cli".to_string()];
+        paste
+    }
+
+    #[test]
+    fn gist_content_file_name_adds_extension_and_avoids_sidecar() {
+        assert_eq!(gist_content_file_name("main.rs", Some("rust")), "main.rs");
+        assert_eq!(
+            gist_content_file_name("notes", Some("markdown")),
+            "notes.md"
+        );
+        assert_eq!(gist_content_file_name("a/b", None), "a_b.txt");
+        assert_eq!(gist_content_file_name("..", None), "paste.txt");
+        assert_eq!(gist_content_file_name("meta", Some("json")), "_meta.json");
+    }
+
+    #[test]
+    fn write_then_read_round_trips_content_and_metadata() {
+        let temp = TempDir::new().expect("temp dir");
+        let mut original = paste("main.rs", "fn main() {}\nlet x = 1;\n");
+        original.line_ending = LineEnding::Crlf;
+        let dir = write_gist_paste(temp.path(), &original).expect("write");
+        assert_eq!(dir, temp.path().join(original.id.as_str()));
+        assert_eq!(
+            std::fs::read(dir.join("main.rs")).expect("content"),
+            b"fn main() {}\r\nlet x = 1;\r\n"
+        );
+
+        // Renaming rewrites the content file and drops the stale one.
+        original.name = "entry".to_string();
+        write_gist_paste(temp.path(), &original).expect("rewrite");
+        assert!(!dir.join("main.rs").exists());
+        std::fs::create_dir(temp.path().join(".stfolder")).expect("sync dir");
+
+        let entries = read_gist_layout(temp.path()).expect("read");
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.meta.id, original.id);
+        assert_eq!(entry.meta.file, "entry.rs");
+        assert_eq!(entry.meta.line_ending, LineEnding::Crlf);
+        assert_eq!(entry.meta.tags, vec!["cli".to_string()]);
+        assert_eq!(entry.meta.created_at, original.created_at);
+        assert_eq!(entry.content, "fn main() {}\r\nlet x = 1;\r\n");
+
+        let snippet = entry.clone().into_snippet();
+        assert_eq!(snippet.id.as_deref(), Some(original.id.as_str()));
+        assert_eq!(snippet.language.as_deref(), Some("rust"));
+    }
+
+    #[test]
+    fn read_gist_layout_rejects_escaping_content_files() {
+        let temp = TempDir::new().expect("temp dir");
+        let original = paste("main.rs", "fn main() {}");
+        let dir = write_gist_paste(temp.path(), &original).expect("write");
+        let meta_path = dir.join(GIST_META_FILE);
+        let raw = std::fs::read_to_string(&meta_path).expect("meta");
+        std::fs::write(
+            &meta_path,
+            raw.replace("\"main.rs\"", "\"../../etc/passwd\""),
+        )
+        .expect("tamper");
+        assert!(matches!(
+            read_gist_layout(temp.path()),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+}
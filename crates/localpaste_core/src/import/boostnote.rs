@@ -0,0 +1,133 @@
+//! Boostnote.next importer.
+//!
+//! Local Boostnote.next storage keeps one JSON document per note. Each note
+//! becomes a markdown paste; trashed notes and non-note JSON files are skipped.
+
+use super::{dedupe_tags, parse_error, read_source, ImportedSnippet};
+use crate::AppError;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BoostnoteNote {
+    #[serde(default)]
+    title: String,
+    content: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    folder_pathname: Option<String>,
+    #[serde(default)]
+    trashed: bool,
+}
+
+/// Parses one Boostnote.next note document.
+///
+/// # Returns
+/// `None` for trashed notes.
+///
+/// # Errors
+/// Returns the JSON error when the document is not a note.
+fn parse_boostnote_note(json: &str) -> Result<Option<ImportedSnippet>, serde_json::Error> {
+    let note: BoostnoteNote = serde_json::from_str(json)?;
+    if note.trashed {
+        return Ok(None);
+    }
+    let folder = note
+        .folder_pathname
+        .as_deref()
+        .and_then(|path| path.rsplit('/').find(|segment| !segment.is_empty()))
+        .map(ToString::to_string);
+    Ok(Some(ImportedSnippet {
+        id: None,
+        name: match note.title.trim() {
+            "" => "Untitled note".to_string(),
+            title => title.to_string(),
+        },
+        content: note.content,
+        language: Some("markdown".to_string()),
+        tags: dedupe_tags(note.tags.into_iter().chain(folder)),
+    }))
+}
+
+/// Collects `*.json` files under `dir`, recursively, in path order.
+fn collect_json_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), AppError> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|err| AppError::BadRequest(format!("Cannot read {}: {}", dir.display(), err)))?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            collect_json_files(&path, out)?;
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Parses a single note file or every note under a storage directory.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] when the path cannot be read, or when a
+/// single file given directly is not a Boostnote note.
+pub(super) fn parse_boostnote_path(path: &Path) -> Result<Vec<ImportedSnippet>, AppError> {
+    if !path.is_dir() {
+        let note = parse_boostnote_note(read_source(path)?.as_str())
+            .map_err(|err| parse_error(path, err))?;
+        return Ok(note.into_iter().collect());
+    }
+    let mut files = Vec::new();
+    collect_json_files(path, &mut files)?;
+    let mut out = Vec::new();
+    for file in files {
+        // Storage directories also hold settings/metadata JSON; skip anything
+        // that is not shaped like a note.
+        if let Ok(Some(note)) = parse_boostnote_note(read_source(&file)?.as_str()) {
+            out.push(note);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_boostnote_note_maps_title_tags_and_folder() {
+        let note = parse_boostnote_note(
+            r#"{"_id": "note:1", "title": "Setup", "content": "Run `make`",
+                "tags": ["dev"], "folderPathname": "/work/tools", "trashed": false}"#,
+        )
+        .expect("parse")
+        .expect("note");
+        assert_eq!(note.name, "Setup");
+        assert_eq!(note.content, "Run `make`");
+        assert_eq!(note.language.as_deref(), Some("markdown"));
+        assert_eq!(note.tags, vec!["dev".to_string(), "tools".to_string()]);
+
+        let trashed = parse_boostnote_note(r#"{"title": "x", "content": "y", "trashed": true}"#);
+        assert_eq!(trashed.expect("parse"), None);
+        assert!(parse_boostnote_note(r#"{"storage": "settings"}"#).is_err());
+    }
+
+    #[test]
+    fn parse_boostnote_path_walks_directories_and_skips_non_notes() {
+        let dir = tempfile::TempDir::new().expect("temp dir");
+        let notes = dir.path().join("notes");
+        std::fs::create_dir(&notes).expect("mkdir");
+        std::fs::write(notes.join("b.json"), r#"{"title": "B", "content": "two"}"#).expect("write");
+        std::fs::write(notes.join("a.json"), r#"{"title": "A", "content": "one"}"#).expect("write");
+        std::fs::write(dir.path().join("boostnote.json"), r#"{"version": 1}"#).expect("write");
+        std::fs::write(notes.join("readme.txt"), "ignored").expect("write");
+
+        let snippets = parse_boostnote_path(dir.path()).expect("parse");
+        let names: Vec<&str> = snippets.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["A", "B"]);
+    }
+}
@@ -0,0 +1,142 @@
+//! Splits one markdown document into snippets, one per fenced code block.
+//!
+//! Blocks are named after the nearest preceding heading and take their
+//! language from the fence info string.
+
+use super::{import_language, ImportedSnippet};
+
+struct OpenFence {
+    marker: char,
+    width: usize,
+    language: Option<String>,
+    body: Vec<String>,
+}
+
+/// Returns `(marker, width, info)` when `line` opens or closes a fence.
+fn fence_marker(line: &str) -> Option<(char, usize, &str)> {
+    let trimmed = line.trim_start_matches(' ');
+    // Four or more spaces of indentation make an indented code block instead.
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let marker = trimmed
+        .chars()
+        .next()
+        .filter(|ch| matches!(ch, '`' | '~'))?;
+    let width = trimmed.chars().take_while(|ch| *ch == marker).count();
+    (width >= 3).then(|| (marker, width, trimmed[width..].trim()))
+}
+
+fn heading_text(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|ch| *ch == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    let text = rest.trim().trim_end_matches('#').trim();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Splits markdown text into one snippet per fenced code block.
+///
+/// Several blocks under the same heading get ` (2)`, ` (3)`, ... suffixes;
+/// blocks before any heading are named `Snippet N`. An unclosed fence runs to
+/// the end of the document.
+///
+/// # Returns
+/// Snippets in document order; empty blocks are skipped.
+pub(super) fn split_markdown(text: &str) -> Vec<ImportedSnippet> {
+    let mut out = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut under_heading = 0usize;
+    let mut open: Option<OpenFence> = None;
+
+    let mut finish = |fence: OpenFence, heading: &Option<String>, under_heading: &mut usize| {
+        let mut content = fence.body.join("\n");
+        if content.trim().is_empty() {
+            return;
+        }
+        content.push('\n');
+        *under_heading += 1;
+        let name = match heading {
+            Some(heading) if *under_heading == 1 => heading.clone(),
+            Some(heading) => format!("{} ({})", heading, under_heading),
+            None => format!("Snippet {}", out.len() + 1),
+        };
+        out.push(ImportedSnippet {
+            id: None,
+            name,
+            content,
+            language: fence.language,
+            tags: Vec::new(),
+        });
+    };
+
+    for line in text.lines() {
+        if let Some(fence) = open.as_mut() {
+            match fence_marker(line) {
+                Some((marker, width, info))
+                    if marker == fence.marker && width >= fence.width && info.is_empty() =>
+                {
+                    let closed = open.take().expect("open fence");
+                    finish(closed, &heading, &mut under_heading);
+                }
+                _ => fence.body.push(line.to_string()),
+            }
+            continue;
+        }
+        if let Some((marker, width, info)) = fence_marker(line) {
+            open = Some(OpenFence {
+                marker,
+                width,
+                language: import_language(info.split_whitespace().next()),
+                body: Vec::new(),
+            });
+        } else if let Some(text) = heading_text(line) {
+            heading = Some(text.to_string());
+            under_heading = 0;
+        }
+    }
+    if let Some(fence) = open {
+        finish(fence, &heading, &mut under_heading);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_markdown_names_blocks_after_headings() {
+        let text =
+            "intro\n```sh\necho hi\n```\n\n## Build ##\n\n```rust title\nfn main() {}\n```\n\
+~~~\nplain\n```\nstill inside\n~~~\n\n# Empty\n```\n\n```\n";
+        let snippets = split_markdown(text);
+        let summary: Vec<(&str, Option<&str>, &str)> = snippets
+            .iter()
+            .map(|s| (s.name.as_str(), s.language.as_deref(), s.content.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Snippet 1", Some("shell"), "echo hi\n"),
+                ("Build", Some("rust"), "fn main() {}\n"),
+                ("Build (2)", None, "plain\n```\nstill inside\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_markdown_keeps_unclosed_fence_and_ignores_indented_fences() {
+        let snippets = split_markdown("#Not a heading\n    ```\n```py\nx = 1\n");
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[0].name, "Snippet 1");
+        assert_eq!(snippets[0].content, "x = 1\n");
+        assert_eq!(snippets[0].language.as_deref(), Some("python"));
+    }
+}
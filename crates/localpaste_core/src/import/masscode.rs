@@ -0,0 +1,162 @@
+//! massCode `db.json` importer.
+//!
+//! Each snippet fragment becomes one paste; multi-fragment snippets append the
+//! fragment label to the snippet name.
+
+use super::{dedupe_tags, import_language, ImportedSnippet};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Database file name inside a massCode storage directory.
+pub(super) const MASSCODE_DB_FILE: &str = "db.json";
+
+#[derive(Deserialize)]
+struct MassCodeDb {
+    #[serde(default)]
+    folders: Vec<MassCodeNamed>,
+    #[serde(default)]
+    tags: Vec<MassCodeNamed>,
+    #[serde(default)]
+    snippets: Vec<MassCodeSnippet>,
+}
+
+#[derive(Deserialize)]
+struct MassCodeNamed {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MassCodeSnippet {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    folder_id: Option<String>,
+    #[serde(default)]
+    tags_ids: Vec<String>,
+    /// `0`/`1` in massCode 2+, a boolean in older versions.
+    #[serde(default)]
+    is_deleted: Value,
+    #[serde(default)]
+    content: Vec<MassCodeFragment>,
+}
+
+#[derive(Deserialize)]
+struct MassCodeFragment {
+    #[serde(default)]
+    label: String,
+    #[serde(default)]
+    value: String,
+    #[serde(default)]
+    language: Option<String>,
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Bool(flag) => *flag,
+        Value::Number(number) => number.as_f64().is_some_and(|n| n != 0.0),
+        _ => false,
+    }
+}
+
+/// Parses a massCode database document.
+///
+/// # Returns
+/// One snippet per non-empty fragment of every non-deleted snippet.
+///
+/// # Errors
+/// Returns the JSON error when the document does not match the massCode shape.
+pub(super) fn parse_masscode(json: &str) -> Result<Vec<ImportedSnippet>, serde_json::Error> {
+    let db: MassCodeDb = serde_json::from_str(json)?;
+    let names = |rows: &[MassCodeNamed]| -> HashMap<String, String> {
+        rows.iter()
+            .map(|row| (row.id.clone(), row.name.clone()))
+            .collect()
+    };
+    let folders = names(&db.folders);
+    let tags = names(&db.tags);
+
+    let mut out = Vec::new();
+    for snippet in db.snippets.iter().filter(|s| !truthy(&s.is_deleted)) {
+        let base_name = match snippet.name.trim() {
+            "" => "Untitled snippet",
+            name => name,
+        };
+        let snippet_tags = dedupe_tags(
+            snippet
+                .tags_ids
+                .iter()
+                .filter_map(|id| tags.get(id).cloned())
+                .chain(
+                    snippet
+                        .folder_id
+                        .as_ref()
+                        .and_then(|id| folders.get(id).cloned()),
+                ),
+        );
+        let fragments: Vec<&MassCodeFragment> = snippet
+            .content
+            .iter()
+            .filter(|fragment| !fragment.value.is_empty())
+            .collect();
+        for fragment in &fragments {
+            let name = if fragments.len() > 1 && !fragment.label.trim().is_empty() {
+                format!("{} - {}", base_name, fragment.label.trim())
+            } else {
+                base_name.to_string()
+            };
+            out.push(ImportedSnippet {
+                id: None,
+                name,
+                content: fragment.value.clone(),
+                language: import_language(fragment.language.as_deref()),
+                tags: snippet_tags.clone(),
+            });
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_masscode_expands_fragments_and_skips_deleted() {
+        let json = r#"{
+            "folders": [{"id": "f1", "name": "Shell"}],
+            "tags": [{"id": "t1", "name": "ops"}],
+            "snippets": [
+                {"name": "Deploy", "folderId": "f1", "tagsIds": ["t1"], "isDeleted": 0,
+                 "content": [
+                    {"label": "Fragment 1", "value": "make build", "language": "sh"},
+                    {"label": "Notes", "value": "run first", "language": "plain_text"}
+                 ]},
+                {"name": "Gone", "isDeleted": true,
+                 "content": [{"label": "x", "value": "old", "language": "js"}]},
+                {"name": "", "content": [{"label": "Fragment 1", "value": "x = 1", "language": "python"}]}
+            ]
+        }"#;
+        let snippets = parse_masscode(json).expect("parse");
+        let names: Vec<&str> = snippets.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Deploy - Fragment 1", "Deploy - Notes", "Untitled snippet"]
+        );
+        assert_eq!(snippets[0].language.as_deref(), Some("shell"));
+        assert_eq!(
+            snippets[0].tags,
+            vec!["ops".to_string(), "Shell".to_string()]
+        );
+        assert_eq!(snippets[1].language, None);
+        assert_eq!(snippets[2].language.as_deref(), Some("python"));
+        assert!(snippets[2].tags.is_empty());
+    }
+
+    #[test]
+    fn parse_masscode_rejects_non_object_documents() {
+        assert!(parse_masscode("[1, 2]").is_err());
+    }
+}
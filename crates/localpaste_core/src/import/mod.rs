@@ -0,0 +1,149 @@
+//! Importers for snippets exported by other local snippet tools.
+//!
+//! Importers only parse: they turn a source file or directory into
+//! [`ImportedSnippet`] rows and leave persistence to the caller, so the CLI can
+//! post them to the API and the GUI can preview them before committing.
+
+mod boostnote;
+mod markdown;
+mod masscode;
+
+use crate::detection::canonical::lookup;
+use crate::AppError;
+use std::path::Path;
+
+/// Supported import sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// massCode `db.json` (the file or the directory that contains it).
+    MassCode,
+    /// Boostnote.next local-space note files (`*.json`, file or directory).
+    Boostnote,
+    /// One markdown file where each fenced code block is a snippet.
+    Markdown,
+    /// LocalPaste gist layout: one directory per paste with a `meta.json`.
+    Gist,
+}
+
+impl ImportFormat {
+    /// All import formats in display order.
+    pub const ALL: [ImportFormat; 4] = [
+        ImportFormat::MassCode,
+        ImportFormat::Boostnote,
+        ImportFormat::Markdown,
+        ImportFormat::Gist,
+    ];
+
+    /// Returns the user-facing format name.
+    pub fn label(self) -> &'static str {
+        match self {
+            ImportFormat::MassCode => "massCode JSON",
+            ImportFormat::Boostnote => "Boostnote",
+            ImportFormat::Markdown => "Markdown fenced blocks",
+            ImportFormat::Gist => "LocalPaste gist directory",
+        }
+    }
+
+    /// Whether the source is usually a directory rather than a single file.
+    pub fn accepts_directory(self) -> bool {
+        matches!(
+            self,
+            ImportFormat::MassCode | ImportFormat::Boostnote | ImportFormat::Gist
+        )
+    }
+}
+
+/// One paste parsed from an import source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedSnippet {
+    /// Paste id recorded by the source; importers update that paste when it
+    /// still exists instead of creating a duplicate.
+    pub id: Option<String>,
+    /// Paste name.
+    pub name: String,
+    /// Paste content.
+    pub content: String,
+    /// Canonical language label, or `None` to let detection decide.
+    pub language: Option<String>,
+    /// Tags, including the source folder name when the tool had folders.
+    pub tags: Vec<String>,
+}
+
+/// Canonicalizes a source language label, dropping plain-text markers and
+/// languages the shared table does not know (detection decides those).
+fn import_language(raw: Option<&str>) -> Option<String> {
+    let spec = lookup(raw?.replace('_', " ").as_str())?;
+    (spec.value != "text").then(|| spec.value.to_string())
+}
+
+/// Trims tags and drops empty or case-insensitive duplicate entries.
+fn dedupe_tags(tags: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !out.iter().any(|seen| seen.eq_ignore_ascii_case(tag)) {
+            out.push(tag.to_string());
+        }
+    }
+    out
+}
+
+fn read_source(path: &Path) -> Result<String, AppError> {
+    std::fs::read_to_string(path)
+        .map_err(|err| AppError::BadRequest(format!("Cannot read {}: {}", path.display(), err)))
+}
+
+fn parse_error(path: &Path, err: impl std::fmt::Display) -> AppError {
+    AppError::BadRequest(format!("Cannot parse {}: {}", path.display(), err))
+}
+
+/// Parses snippets from an import source.
+///
+/// # Arguments
+/// - `format`: Source tool format.
+/// - `path`: Source file, or directory for formats that store one.
+///
+/// # Returns
+/// Snippets in source order; trashed or deleted entries are skipped.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] when the source cannot be read or parsed.
+pub fn parse_import(format: ImportFormat, path: &Path) -> Result<Vec<ImportedSnippet>, AppError> {
+    match format {
+        ImportFormat::MassCode => {
+            let file = if path.is_dir() {
+                path.join(masscode::MASSCODE_DB_FILE)
+            } else {
+                path.to_path_buf()
+            };
+            masscode::parse_masscode(read_source(&file)?.as_str())
+                .map_err(|err| parse_error(&file, err))
+        }
+        ImportFormat::Boostnote => boostnote::parse_boostnote_path(path),
+        ImportFormat::Markdown => Ok(markdown::split_markdown(read_source(path)?.as_str())),
+        ImportFormat::Gist => Ok(crate::gist_layout::read_gist_layout(path)?
+            .into_iter()
+            .map(|entry| entry.into_snippet())
+            .collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import_language_canonicalizes_and_drops_plain_text() {
+        assert_eq!(import_language(Some("js")), Some("javascript".to_string()));
+        assert_eq!(import_language(Some("plain_text")), None);
+        assert_eq!(import_language(Some("brainfuck")), None);
+        assert_eq!(import_language(Some("  ")), None);
+        assert_eq!(import_language(None), None);
+    }
+
+    #[test]
+    fn dedupe_tags_trims_and_ignores_case() {
+        let tags = dedupe_tags(["rust".into(), " Rust ".into(), String::new(), "cli".into()]);
+        assert_eq!(tags, vec!["rust".to_string(), "cli".to_string()]);
+    }
+}
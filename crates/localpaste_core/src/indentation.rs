@@ -0,0 +1,217 @@
+//! Indentation style detection and whole-document retabbing.
+
+use serde::{Deserialize, Serialize};
+
+/// Lines inspected when detecting indentation; later lines rarely change the verdict.
+const DETECT_MAX_LINES: usize = 4_000;
+/// Widest indent unit considered when inferring a spaces width.
+const MAX_INDENT_WIDTH: usize = 8;
+/// Width assumed when a document gives no usable signal.
+pub const DEFAULT_INDENT_WIDTH: u8 = 4;
+
+/// Indentation convention used by a paste.
+///
+/// `width` is the spaces-per-level for space indentation and the tab stop
+/// used when converting tab indentation to spaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndentStyle {
+    pub use_tabs: bool,
+    pub width: u8,
+}
+
+impl Default for IndentStyle {
+    fn default() -> Self {
+        Self {
+            use_tabs: false,
+            width: DEFAULT_INDENT_WIDTH,
+        }
+    }
+}
+
+impl IndentStyle {
+    /// Text inserted for one indentation level.
+    ///
+    /// # Returns
+    /// `"\t"` for tabs, otherwise `width` spaces.
+    pub fn unit(self) -> String {
+        if self.use_tabs {
+            "\t".to_string()
+        } else {
+            " ".repeat(usize::from(self.width.max(1)))
+        }
+    }
+
+    /// Short status label such as `Tabs` or `Spaces: 4`.
+    pub fn label(self) -> String {
+        if self.use_tabs {
+            "Tabs".to_string()
+        } else {
+            format!("Spaces: {}", self.width)
+        }
+    }
+
+    /// The same style with tabs and spaces swapped, keeping the width.
+    pub fn toggled(self) -> Self {
+        Self {
+            use_tabs: !self.use_tabs,
+            width: self.width,
+        }
+    }
+}
+
+/// Detect tabs-vs-spaces and indent width from leading whitespace.
+///
+/// Widths come from the most common positive indent step between
+/// consecutive space-indented lines, which tolerates alignment continuations.
+///
+/// # Returns
+/// `None` when no line is indented.
+pub fn detect_indentation(content: &str) -> Option<IndentStyle> {
+    let mut tab_lines = 0usize;
+    let mut space_lines = 0usize;
+    let mut step_counts = [0usize; MAX_INDENT_WIDTH + 1];
+    let mut previous_spaces = 0usize;
+
+    for line in content.lines().take(DETECT_MAX_LINES) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.starts_with('\t') {
+            tab_lines += 1;
+            previous_spaces = 0;
+            continue;
+        }
+        let spaces = line.len() - line.trim_start_matches(' ').len();
+        if spaces > 0 {
+            space_lines += 1;
+        }
+        if spaces > previous_spaces {
+            let step = spaces - previous_spaces;
+            if step <= MAX_INDENT_WIDTH {
+                step_counts[step] += 1;
+            }
+        }
+        previous_spaces = spaces;
+    }
+
+    if tab_lines == 0 && space_lines == 0 {
+        return None;
+    }
+    if tab_lines > space_lines {
+        return Some(IndentStyle {
+            use_tabs: true,
+            width: DEFAULT_INDENT_WIDTH,
+        });
+    }
+    // Prefer the smaller width on ties so 2-space code with nested 4-space
+    // jumps does not read as 4-space.
+    let width = (2..=MAX_INDENT_WIDTH)
+        .rev()
+        .max_by_key(|step| step_counts[*step])
+        .filter(|step| step_counts[*step] > 0)
+        .unwrap_or(usize::from(DEFAULT_INDENT_WIDTH));
+    Some(IndentStyle {
+        use_tabs: false,
+        width: width as u8,
+    })
+}
+
+/// Rewrite leading indentation of every line to `target`.
+///
+/// Existing indentation is measured in columns with tab stops every
+/// `target.width`; columns that do not fill a whole tab stay as spaces.
+///
+/// # Returns
+/// Converted content with line endings and non-leading whitespace untouched.
+pub fn retab(content: &str, target: IndentStyle) -> String {
+    let width = usize::from(target.width.max(1));
+    let mut out = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        let body = line.trim_start_matches([' ', '\t']);
+        let leading = &line[..line.len() - body.len()];
+        let columns = leading.chars().fold(0usize, |columns, ch| {
+            if ch == '\t' {
+                (columns / width + 1) * width
+            } else {
+                columns + 1
+            }
+        });
+        if body.is_empty() || body == "\n" || body == "\r\n" {
+            out.push_str(line);
+            continue;
+        }
+        if target.use_tabs {
+            out.extend(std::iter::repeat_n('\t', columns / width));
+            out.extend(std::iter::repeat_n(' ', columns % width));
+        } else {
+            out.extend(std::iter::repeat_n(' ', columns));
+        }
+        out.push_str(body);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spaces(width: u8) -> IndentStyle {
+        IndentStyle {
+            use_tabs: false,
+            width,
+        }
+    }
+
+    #[test]
+    fn detect_indentation_matrix() {
+        let tabs = IndentStyle {
+            use_tabs: true,
+            width: DEFAULT_INDENT_WIDTH,
+        };
+        let cases = [
+            ("no indent\nat all\n", None),
+            ("fn a() {\n\tx();\n\tif y {\n\t\tz();\n\t}\n}\n", Some(tabs)),
+            ("def a():\n  x()\n  if y:\n    z()\n", Some(spaces(2))),
+            (
+                "fn a() {\n    x();\n    if y {\n        z();\n    }\n}\n",
+                Some(spaces(4)),
+            ),
+            (
+                "{\n    \"a\": 1,\n    \"b\": [\n        2\n    ]\n}\n",
+                Some(spaces(4)),
+            ),
+        ];
+        for (content, expected) in cases {
+            assert_eq!(
+                detect_indentation(content),
+                expected,
+                "content: {:?}",
+                content
+            );
+        }
+    }
+
+    #[test]
+    fn retab_round_trips_between_tabs_and_spaces() {
+        let spaced = "fn a() {\n    x();\n        y(); // keep  inner\n\n  odd();\n}\n";
+        let tabbed = retab(spaced, spaces(4).toggled());
+        assert_eq!(
+            tabbed,
+            "fn a() {\n\tx();\n\t\ty(); // keep  inner\n\n  odd();\n}\n"
+        );
+        assert_eq!(retab(tabbed.as_str(), spaces(4)), spaced);
+    }
+
+    #[test]
+    fn retab_preserves_crlf_and_whitespace_only_lines() {
+        assert_eq!(retab("\tx\r\n \t\r\n", spaces(2)), "  x\r\n \t\r\n");
+    }
+
+    #[test]
+    fn indent_style_unit_and_label() {
+        assert_eq!(spaces(2).unit(), "  ");
+        assert_eq!(spaces(2).label(), "Spaces: 2");
+        assert_eq!(spaces(2).toggled().unit(), "\t");
+        assert_eq!(spaces(2).toggled().label(), "Tabs");
+    }
+}
@@ -1,5 +1,11 @@
 //! Core domain library for LocalPaste (config, storage, models).
 
+/// Store-only ZIP archive writer and reader.
+pub mod archive;
+/// Commit, build date, features, and default paths for `--build-info`.
+pub mod build_info;
+/// Patch-level merging of concurrent edits to one paste.
+pub mod collab;
 /// Configuration loading and defaults.
 pub mod config;
 /// Shared cross-crate constants.
@@ -10,31 +16,88 @@ pub mod db;
 pub mod detection;
 /// Line-based diff helpers and API payload types.
 pub mod diff;
+/// Markdown activity digests stored in the `Digests` folder.
+pub mod digest;
+/// API discovery files and embedded-server port fallback.
+pub mod discovery;
 /// Process-global environment mutation helpers.
 pub mod env;
 /// Application error types (storage/domain).
 pub mod error;
+/// Highlighted HTML and PDF document rendering.
+#[cfg(feature = "export")]
+pub mod export;
+/// Folder subtree ZIP export with a metadata manifest.
+pub mod folder_export;
 /// Shared folder tree operations.
 pub mod folder_ops;
+/// Gist-style one-directory-per-paste layout for export, clone, and import.
+pub mod gist_layout;
+/// Importers for other snippet tools' exports.
+pub mod import;
+/// Indentation style detection and retabbing.
+pub mod indentation;
+/// JSON, YAML, and TOML validity checks.
+pub mod lint;
+/// On-demand cleanup of orphaned rows and offline compaction.
+pub mod maintenance;
+/// Merging several pastes into one.
+pub mod merge;
 /// Data models for API requests and persistence.
 pub mod models;
 /// Paste naming helpers.
 pub mod naming;
+/// Save-time line-ending, BOM, and UTF-8 normalization.
+pub mod normalization;
+/// Heuristic detection of pasted credentials.
+pub mod secrets;
 /// Locally-derived retrieval metadata.
 pub mod semantic;
+/// Optional JSON settings file in the DB directory.
+pub mod settings;
+/// Per-source and per-folder paste size limits.
+pub mod size_limits;
+/// Splitting of pastes that concatenate several files.
+pub mod split;
+/// Paste totals, per-language and per-folder counts, and largest pastes.
+pub mod stats;
+/// Database and backup size alerts with their remediation actions.
+pub mod storage_alerts;
+/// Tag counts, renames, and removals across all pastes.
+pub mod tag_ops;
+/// `{{variable}}` template expansion for names and snippets.
+pub mod templates;
 /// Shared helpers used by `localpaste_core` tests.
 #[cfg(test)]
 pub(crate) mod test_support;
 /// Shared string and host normalization helpers.
 pub mod text;
+/// Timestamp display timezone, relative times, and API epoch fields.
+pub mod timestamps;
+/// Opt-in lookup of the latest LocalPaste release on GitHub.
+#[cfg(feature = "update-check")]
+pub mod update_check;
+/// Blocking fetch of paste content from a source URL.
+#[cfg(feature = "url-source")]
+pub mod url_fetch;
+/// Size, syntax, and secret checks for unsaved content.
+pub mod validation;
 
 pub use config::Config;
 pub use constants::{
-    API_ADDR_FILE_NAME, DB_OWNER_LOCK_FILE_NAME, DEFAULT_AUTO_SAVE_INTERVAL_MS,
-    DEFAULT_CLI_SERVER_URL, DEFAULT_LIST_PASTES_LIMIT, DEFAULT_MAX_PASTE_SIZE,
-    DEFAULT_PASTE_VERSION_INTERVAL_SECS, DEFAULT_PORT, DEFAULT_SEARCH_PASTES_LIMIT,
-    MAX_DIFF_INPUT_BYTES,
+    API_ADDR_FILE_NAME, API_PORT_PIN_FILE_NAME, DB_OWNER_LOCK_FILE_NAME,
+    DEFAULT_AUTO_SAVE_INTERVAL_MS, DEFAULT_BACKUP_SIZE_ALERT_MB, DEFAULT_CLI_SERVER_URL,
+    DEFAULT_DB_SIZE_ALERT_MB, DEFAULT_FLUSH_INTERVAL_MS, DEFAULT_GALLERY_PORT,
+    DEFAULT_LIST_PASTES_LIMIT, DEFAULT_MAX_PASTE_SIZE, DEFAULT_PASTE_VERSION_INTERVAL_SECS,
+    DEFAULT_PORT, DEFAULT_PORT_FALLBACK_ATTEMPTS, DEFAULT_SEARCH_PASTES_LIMIT,
+    DEFAULT_SHUTDOWN_DRAIN_SECS, DEFAULT_SLOW_QUERY_THRESHOLD_MS, DEFAULT_WRITE_RATE_LIMIT_BURST,
+    DEFAULT_WRITE_RATE_LIMIT_PER_SEC, MAX_DIFF_INPUT_BYTES, MAX_PASTE_EXPIRES_IN_SECS,
+    PASTE_LIST_SNIPPET_CHARS, PASTE_PREVIEW_DEFAULT_LINES, PASTE_PREVIEW_MAX_CHARS,
+    PASTE_PREVIEW_MAX_LINES, REMOTE_BACKUP_LEDGER_FILE_NAME, RESTORE_PENDING_FILE_NAME,
+    SEARCH_CONTENT_MAX_BYTES, SEARCH_MATCHES_MAX, SETTINGS_FILE_NAME,
 };
 pub use db::Database;
 pub use detection::detect_language;
 pub use error::AppError;
+pub use normalization::{LineEnding, NormalizationOptions};
+pub use size_limits::{PasteSizeLimits, PasteSource};
@@ -0,0 +1,190 @@
+//! Syntax validity checks for JSON, YAML, and TOML pastes.
+
+use crate::detection::canonical;
+use serde::{Deserialize, Serialize};
+
+/// Languages with a validity check, by canonical value.
+const LINTABLE_LANGUAGES: &[&str] = &["json", "yaml", "toml"];
+
+/// First parse error found in a paste.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintError {
+    /// 1-based line of the error.
+    pub line: usize,
+    /// 1-based column of the error.
+    pub column: usize,
+    pub message: String,
+}
+
+/// Outcome of checking a paste against its language's syntax.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintReport {
+    /// Canonical language the content was checked as.
+    pub language: String,
+    pub valid: bool,
+    pub error: Option<LintError>,
+}
+
+/// `GET /api/paste/:id/lint` response body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PasteLintResponse {
+    pub id: String,
+    /// `false` when the paste's language has no validity check.
+    pub checked: bool,
+    /// `true` unless a check ran and found an error.
+    pub valid: bool,
+    pub language: Option<String>,
+    pub error: Option<LintError>,
+}
+
+impl PasteLintResponse {
+    /// Response for paste `id` from an optional lint report.
+    pub fn new(id: String, report: Option<LintReport>) -> Self {
+        match report {
+            Some(report) => Self {
+                id,
+                checked: true,
+                valid: report.valid,
+                language: Some(report.language),
+                error: report.error,
+            },
+            None => Self {
+                id,
+                checked: false,
+                valid: true,
+                language: None,
+                error: None,
+            },
+        }
+    }
+}
+
+/// Canonical language value when `language` has a validity check.
+///
+/// # Returns
+/// `Some("json" | "yaml" | "toml")`, otherwise `None`.
+pub fn lint_language(language: Option<&str>) -> Option<&'static str> {
+    let spec = canonical::lookup(language?)?;
+    LINTABLE_LANGUAGES
+        .iter()
+        .copied()
+        .find(|value| *value == spec.value)
+}
+
+/// Check `content` against the syntax of `language`.
+///
+/// JSON accepts a stream of values, so JSON Lines content counts as valid.
+///
+/// # Returns
+/// `None` when the language has no validity check.
+pub fn lint_content(language: Option<&str>, content: &str) -> Option<LintReport> {
+    let language = lint_language(language)?;
+    let error = match language {
+        "json" => lint_json(content),
+        "yaml" => lint_yaml(content),
+        _ => lint_toml(content),
+    };
+    Some(LintReport {
+        language: language.to_string(),
+        valid: error.is_none(),
+        error,
+    })
+}
+
+fn lint_json(content: &str) -> Option<LintError> {
+    let stream = serde_json::Deserializer::from_str(content).into_iter::<serde::de::IgnoredAny>();
+    for value in stream {
+        if let Err(err) = value {
+            let message = err.to_string();
+            let message = message
+                .rsplit_once(" at line ")
+                .map_or(message.as_str(), |(head, _)| head)
+                .to_string();
+            return Some(LintError {
+                line: err.line().max(1),
+                column: err.column().max(1),
+                message,
+            });
+        }
+    }
+    None
+}
+
+fn lint_yaml(content: &str) -> Option<LintError> {
+    let err = yaml_rust::YamlLoader::load_from_str(content).err()?;
+    let marker = err.marker();
+    let message = err.to_string();
+    let message = message
+        .rsplit_once(" at line ")
+        .map_or(message.as_str(), |(head, _)| head)
+        .to_string();
+    Some(LintError {
+        line: marker.line().max(1),
+        column: marker.col() + 1,
+        message,
+    })
+}
+
+fn lint_toml(content: &str) -> Option<LintError> {
+    let err = toml::from_str::<toml::Table>(content).err()?;
+    let offset = err.span().map_or(0, |span| span.start);
+    let before = content.get(..offset).unwrap_or(content);
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    Some(LintError {
+        line,
+        column: before[line_start..].chars().count() + 1,
+        message: err.message().trim().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lint_language_recognizes_aliases_and_skips_other_languages() {
+        assert_eq!(lint_language(Some("JSON")), Some("json"));
+        assert_eq!(lint_language(Some("jsonl")), Some("json"));
+        assert_eq!(lint_language(Some("yml")), Some("yaml"));
+        assert_eq!(lint_language(Some("toml")), Some("toml"));
+        assert_eq!(lint_language(Some("rust")), None);
+        assert_eq!(lint_language(None), None);
+        assert!(lint_content(Some("rust"), "{").is_none());
+    }
+
+    #[test]
+    fn lint_content_accepts_valid_documents() {
+        for (language, content) in [
+            ("json", "{\"a\": [1, 2]}\n"),
+            ("json", "{\"a\": 1}\n{\"a\": 2}\n"),
+            ("yaml", "a:\n  - 1\n  - 2\n"),
+            ("toml", "[package]\nname = \"x\"\n"),
+        ] {
+            let report = lint_content(Some(language), content).expect("lintable");
+            assert!(report.valid, "{language}: {report:?}");
+            assert!(report.error.is_none());
+        }
+    }
+
+    #[test]
+    fn lint_content_reports_error_position() {
+        let report = lint_content(Some("json"), "{\n  \"a\": 1,\n  \"b\" 2\n}\n").expect("json");
+        assert!(!report.valid);
+        let error = report.error.expect("json error");
+        assert_eq!(error.line, 3);
+        assert!(!error.message.contains("at line"), "{}", error.message);
+
+        let error = lint_content(Some("yaml"), "a: 1\nb: [1, 2\n")
+            .and_then(|report| report.error)
+            .expect("yaml error");
+        assert!(error.line >= 2, "{error:?}");
+        assert!(!error.message.contains("at line"), "{}", error.message);
+
+        let error = lint_content(Some("toml"), "a = 1\nb = \n")
+            .and_then(|report| report.error)
+            .expect("toml error");
+        assert_eq!(error.line, 2);
+        assert!(!error.message.is_empty());
+    }
+}
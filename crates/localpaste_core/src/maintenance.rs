@@ -0,0 +1,130 @@
+//! On-demand storage cleanup shared by the API, the GUI, and `localpaste --cleanup`.
+//!
+//! Online cleanup only touches rows; compaction needs exclusive ownership of
+//! the redb file and therefore only runs from the offline maintenance mode.
+
+use crate::db::tables::REDB_FILE_NAME;
+use crate::folder_ops::reconcile_folder_invariants;
+use crate::{AppError, Database};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Outcome of a cleanup pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CleanupReport {
+    /// Crash-recovery drafts removed because their paste no longer exists.
+    pub orphan_drafts_removed: usize,
+    /// Folders whose parent links, counts, and stats were re-derived.
+    pub folders_reconciled: usize,
+    /// Idle chunked-upload sessions dropped (server only).
+    pub stale_uploads_dropped: usize,
+    /// Whether the database file was compacted.
+    pub compacted: bool,
+}
+
+/// Removes orphaned drafts and repairs folder invariants on an open database.
+///
+/// # Returns
+/// A report with `stale_uploads_dropped` and `compacted` left at their defaults.
+///
+/// # Errors
+/// Returns storage errors from draft or folder reconciliation.
+pub fn cleanup_database(db: &Database) -> Result<CleanupReport, AppError> {
+    let mut report = CleanupReport::default();
+    for draft in db.drafts.list()? {
+        let Some(paste_id) = draft.paste_id.as_deref() else {
+            continue;
+        };
+        if db.pastes.get(paste_id)?.is_none() && db.drafts.delete(draft.key.as_str())? {
+            report.orphan_drafts_removed += 1;
+        }
+    }
+    reconcile_folder_invariants(db)?;
+    report.folders_reconciled = db.folders.list()?.len();
+    Ok(report)
+}
+
+/// Compacts the redb file under `db_path` while no other handle has it open.
+///
+/// # Returns
+/// `Ok(false)` when the file does not exist or had nothing to reclaim.
+///
+/// # Errors
+/// Returns an error when the file is in use or compaction fails.
+pub fn compact_database_file(db_path: &Path) -> Result<bool, AppError> {
+    let file = db_path.join(REDB_FILE_NAME);
+    if !file.is_file() {
+        return Ok(false);
+    }
+    let mut db = redb::Database::open(&file)?;
+    let mut compacted = false;
+    // redb reclaims space incrementally; repeat until it reports no progress.
+    while db
+        .compact()
+        .map_err(|err| AppError::StorageMessage(format!("Compaction failed: {}", err)))?
+    {
+        compacted = true;
+    }
+    Ok(compacted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        draft::{Draft, SCRATCHPAD_DRAFT_KEY},
+        paste::Paste,
+    };
+    use crate::test_support::setup_temp_db;
+
+    fn draft(paste_id: Option<&str>, key: &str) -> Draft {
+        Draft {
+            key: key.to_string(),
+            paste_id: paste_id.map(str::to_string),
+            content: "draft".to_string(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn cleanup_removes_only_drafts_of_missing_pastes() {
+        let (db, _temp) = setup_temp_db();
+        let paste = Paste::new("kept".to_string(), "kept".to_string());
+        db.pastes.create(&paste).expect("create");
+        db.drafts
+            .put(&draft(
+                Some(paste.id.as_str()),
+                Draft::paste_key(&paste.id).as_str(),
+            ))
+            .expect("draft");
+        db.drafts
+            .put(&draft(Some("gone"), Draft::paste_key("gone").as_str()))
+            .expect("draft");
+        db.drafts
+            .put(&draft(None, SCRATCHPAD_DRAFT_KEY))
+            .expect("draft");
+
+        let report = cleanup_database(&db).expect("cleanup");
+        assert_eq!(report.orphan_drafts_removed, 1);
+        assert!(!report.compacted);
+        let keys: Vec<String> = db
+            .drafts
+            .list()
+            .expect("list")
+            .into_iter()
+            .map(|draft| draft.key)
+            .collect();
+        assert_eq!(keys.len(), 2);
+        assert!(!keys.contains(&Draft::paste_key("gone")));
+    }
+
+    #[test]
+    fn compact_database_file_requires_exclusive_access() {
+        let temp = tempfile::TempDir::new().expect("temp dir");
+        assert!(!compact_database_file(temp.path()).expect("missing file"));
+        let db = Database::new(temp.path().to_str().expect("utf8")).expect("open");
+        assert!(compact_database_file(temp.path()).is_err());
+        drop(db);
+        compact_database_file(temp.path()).expect("compact");
+    }
+}
@@ -0,0 +1,164 @@
+//! Joining several pastes into one, the inverse of [`crate::split`].
+
+use crate::models::paste::Paste;
+
+/// Text placed between merged pastes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeSeparator {
+    /// One blank line between pastes.
+    #[default]
+    BlankLine,
+    /// A `---` rule surrounded by blank lines.
+    Rule,
+    /// A `=== name ===` banner before each paste, which the split action can
+    /// undo when names contain no whitespace.
+    FileBanners,
+}
+
+impl MergeSeparator {
+    /// Every separator, in the order offered to users.
+    pub const ALL: [MergeSeparator; 3] = [
+        MergeSeparator::BlankLine,
+        MergeSeparator::Rule,
+        MergeSeparator::FileBanners,
+    ];
+
+    /// Human-readable label for pickers.
+    pub fn label(self) -> &'static str {
+        match self {
+            MergeSeparator::BlankLine => "Blank line",
+            MergeSeparator::Rule => "--- rule",
+            MergeSeparator::FileBanners => "=== name === banners",
+        }
+    }
+}
+
+/// Join paste contents in order with `separator` between them.
+///
+/// # Arguments
+/// - `parts`: `(name, content)` pairs in merge order.
+/// - `separator`: Text placed between (or, for banners, before) each part.
+///
+/// # Returns
+/// The merged text, ending in a newline when any part is non-empty.
+pub fn merge_contents(parts: &[(&str, &str)], separator: MergeSeparator) -> String {
+    let mut merged = String::new();
+    for (idx, (name, content)) in parts.iter().enumerate() {
+        if idx > 0 {
+            merged.push_str(match separator {
+                MergeSeparator::BlankLine | MergeSeparator::FileBanners => "\n",
+                MergeSeparator::Rule => "\n---\n\n",
+            });
+        }
+        if separator == MergeSeparator::FileBanners {
+            merged.push_str(&format!("=== {} ===\n", name.trim()));
+        }
+        let body = content.trim_end_matches(['\r', '\n']);
+        if !body.is_empty() {
+            merged.push_str(body);
+            merged.push('\n');
+        }
+    }
+    merged
+}
+
+/// Build the merged paste row for `sources`, taken in order.
+///
+/// The result lands in the first source's folder. A language shared by every
+/// source is kept (manual when any source set it manually); mixed languages
+/// fall back to auto-detection.
+///
+/// # Returns
+/// The unsaved merged paste.
+pub fn merged_paste(sources: &[Paste], name: String, separator: MergeSeparator) -> Paste {
+    let parts: Vec<(&str, &str)> = sources
+        .iter()
+        .map(|paste| (paste.name.as_str(), paste.content.as_str()))
+        .collect();
+    let content = merge_contents(&parts, separator);
+    let shared_language = sources
+        .first()
+        .and_then(|first| first.language.clone())
+        .filter(|language| {
+            sources
+                .iter()
+                .all(|paste| paste.language.as_deref() == Some(language.as_str()))
+        });
+    let mut paste = match shared_language {
+        Some(language) => {
+            let mut paste = Paste::new_with_language(content, name, Some(language), false);
+            paste.language_is_manual = sources.iter().any(|paste| paste.language_is_manual);
+            paste
+        }
+        None => Paste::new(content, name),
+    };
+    paste.folder_id = sources.first().and_then(|first| first.folder_id.clone());
+    paste
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::split::split_concatenated_files;
+
+    #[test]
+    fn separators_join_parts_and_banners_round_trip_through_split() {
+        let parts = [("a.rs", "fn a() {}\n\n"), ("b.py", "print(1)")];
+        assert_eq!(
+            merge_contents(&parts, MergeSeparator::BlankLine),
+            "fn a() {}\n\nprint(1)\n"
+        );
+        assert_eq!(
+            merge_contents(&parts, MergeSeparator::Rule),
+            "fn a() {}\n\n---\n\nprint(1)\n"
+        );
+
+        let banners = merge_contents(&parts, MergeSeparator::FileBanners);
+        assert_eq!(
+            banners,
+            "=== a.rs ===\nfn a() {}\n\n=== b.py ===\nprint(1)\n"
+        );
+        let files = split_concatenated_files(&banners);
+        let round_trip: Vec<(&str, &str)> = files
+            .iter()
+            .map(|file| (file.name.as_str(), file.content.as_str()))
+            .collect();
+        assert_eq!(
+            round_trip,
+            vec![("a.rs", "fn a() {}\n"), ("b.py", "print(1)\n")]
+        );
+    }
+
+    #[test]
+    fn merged_paste_keeps_shared_language_and_first_folder() {
+        let mut first = Paste::new_with_language(
+            "a".to_string(),
+            "a".to_string(),
+            Some("rust".to_string()),
+            true,
+        );
+        first.folder_id = Some("folder-1".to_string());
+        let second = Paste::new_with_language(
+            "b".to_string(),
+            "b".to_string(),
+            Some("rust".to_string()),
+            false,
+        );
+        let merged = merged_paste(
+            &[first.clone(), second],
+            "both".to_string(),
+            MergeSeparator::BlankLine,
+        );
+        assert_eq!(merged.content, "a\n\nb\n");
+        assert_eq!(merged.language.as_deref(), Some("rust"));
+        assert!(merged.language_is_manual);
+        assert_eq!(merged.folder_id.as_deref(), Some("folder-1"));
+
+        let mixed = merged_paste(
+            &[first, Paste::new("plain".to_string(), "p".to_string())],
+            "mixed".to_string(),
+            MergeSeparator::Rule,
+        );
+        assert_ne!(mixed.language.as_deref(), Some("rust"));
+    }
+}
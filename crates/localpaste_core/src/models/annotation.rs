@@ -0,0 +1,55 @@
+//! Notes anchored to a line range of a paste.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// One note attached to lines `start_line..=end_line` (1-based) of a paste.
+///
+/// The range follows the annotated lines when the paste content changes; see
+/// [`crate::db::annotations::reanchor_range`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub id: String,
+    pub paste_id: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Display label; the account name in multi-user mode.
+    pub author: String,
+    /// Account that wrote the note, set only in multi-user mode.
+    pub author_id: Option<String>,
+    pub body: String,
+    /// Set once every annotated line was deleted; the range then points at
+    /// the line where they used to be.
+    pub detached: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Annotation {
+    /// Short range label such as `line 4` or `lines 4-9`.
+    pub fn range_label(&self) -> String {
+        if self.start_line == self.end_line {
+            format!("line {}", self.start_line)
+        } else {
+            format!("lines {}-{}", self.start_line, self.end_line)
+        }
+    }
+}
+
+/// Request payload for annotating a line range.
+#[derive(Debug, Deserialize)]
+pub struct CreateAnnotationRequest {
+    /// First annotated line, 1-based.
+    pub start_line: usize,
+    /// Last annotated line, 1-based and inclusive; defaults to `start_line`.
+    pub end_line: Option<usize>,
+    /// Ignored in multi-user mode, where the caller's name is used.
+    pub author: Option<String>,
+    pub body: String,
+}
+
+/// Request payload for editing an annotation body.
+#[derive(Debug, Deserialize)]
+pub struct UpdateAnnotationRequest {
+    pub body: String,
+}
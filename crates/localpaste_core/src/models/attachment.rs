@@ -0,0 +1,25 @@
+//! Binary files attached to a paste.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Largest accepted attachment, in bytes.
+pub const MAX_ATTACHMENT_BYTES: usize = 10 * 1024 * 1024;
+/// Most attachments one paste may carry.
+pub const MAX_ATTACHMENTS_PER_PASTE: usize = 32;
+/// Longest accepted attachment file name, in characters.
+pub const MAX_ATTACHMENT_NAME_CHARS: usize = 255;
+/// Content type stored when the uploader does not send one.
+pub const DEFAULT_ATTACHMENT_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Metadata for one attachment; the bytes are stored separately.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: String,
+    pub paste_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    /// Size of the stored bytes.
+    pub size: u64,
+    pub created_at: DateTime<Utc>,
+}
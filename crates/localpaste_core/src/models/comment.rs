@@ -0,0 +1,39 @@
+//! Review comments attached to a paste.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Longest accepted comment body, in characters.
+pub const MAX_COMMENT_CHARS: usize = 10_000;
+/// Longest accepted author label, in characters.
+pub const MAX_COMMENT_AUTHOR_CHARS: usize = 64;
+/// Author label used when a comment is created without one.
+pub const DEFAULT_COMMENT_AUTHOR: &str = "anonymous";
+
+/// One comment in a paste's thread.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: String,
+    pub paste_id: String,
+    /// Display label; the account name in multi-user mode.
+    pub author: String,
+    /// Account that wrote the comment, set only in multi-user mode.
+    pub author_id: Option<String>,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request payload for adding a comment.
+#[derive(Debug, Deserialize)]
+pub struct CreateCommentRequest {
+    /// Ignored in multi-user mode, where the caller's name is used.
+    pub author: Option<String>,
+    pub body: String,
+}
+
+/// Request payload for editing a comment body.
+#[derive(Debug, Deserialize)]
+pub struct UpdateCommentRequest {
+    pub body: String,
+}
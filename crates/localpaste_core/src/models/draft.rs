@@ -0,0 +1,28 @@
+//! Crash-recovery draft models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Draft key used for the GUI scratchpad buffer.
+pub const SCRATCHPAD_DRAFT_KEY: &str = "scratchpad";
+
+/// Unsaved editor content persisted periodically for crash recovery.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Draft {
+    /// Stable slot id: [`SCRATCHPAD_DRAFT_KEY`] or [`Draft::paste_key`].
+    pub key: String,
+    /// Paste the draft was edited from, when it was not the scratchpad.
+    pub paste_id: Option<String>,
+    pub content: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Draft {
+    /// Draft key for unsaved edits to an existing paste.
+    ///
+    /// # Returns
+    /// `paste:<id>`.
+    pub fn paste_key(paste_id: &str) -> String {
+        format!("paste:{}", paste_id)
+    }
+}
@@ -13,6 +13,12 @@ pub struct Folder {
     pub paste_count: usize,
     #[serde(default)]
     pub parent_id: Option<String>,
+    /// Stored content bytes across the folder's direct pastes.
+    #[serde(default)]
+    pub total_bytes: u64,
+    /// Time of the most recent create, edit, move, or delete of a direct paste.
+    #[serde(default)]
+    pub last_updated: Option<DateTime<Utc>>,
 }
 
 /// Request payload for creating a folder.
@@ -31,6 +37,74 @@ pub struct UpdateFolderRequest {
     pub parent_id: Option<String>,
 }
 
+/// Request payload for moving a folder under a new parent.
+#[derive(Debug, Default, Deserialize)]
+pub struct MoveFolderRequest {
+    /// New parent folder; `null` or empty moves the folder to the top level.
+    #[serde(default)]
+    pub parent_id: Option<String>,
+}
+
+/// Sort keys accepted by `GET /api/folders?sort=`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FolderSortKey {
+    Name,
+    CreatedAt,
+    /// Direct paste count, or the recursive count when `recursive_counts` is set.
+    PasteCount,
+}
+
+/// Sort direction for folder and paste listings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Query parameters for listing folders.
+#[derive(Debug, Default, Deserialize)]
+pub struct FolderListQuery {
+    /// Restrict rows to direct children of this folder; empty selects top-level folders.
+    pub parent_id: Option<String>,
+    /// When `true`, rows include `recursive_paste_count` over the whole subtree.
+    pub recursive_counts: Option<bool>,
+    /// Sort key; folders stay in name order when omitted.
+    pub sort: Option<FolderSortKey>,
+    pub order: Option<SortOrder>,
+}
+
+/// Folder row returned by filtered folder listings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderListItem {
+    #[serde(flatten)]
+    pub folder: Folder,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recursive_paste_count: Option<usize>,
+}
+
+/// One folder in the nested tree returned by `GET /api/folders/tree`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderTreeNode {
+    #[serde(flatten)]
+    pub folder: Folder,
+    /// Pastes in the folder and all of its descendants.
+    pub recursive_paste_count: usize,
+    /// Subfolders in case-insensitive name order.
+    pub children: Vec<FolderTreeNode>,
+}
+
+/// Paste counts for one folder, returned by the folder count route.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FolderCountResponse {
+    /// Pastes filed directly in the folder.
+    pub paste_count: usize,
+    /// Pastes in the folder and all of its descendants.
+    pub recursive_paste_count: usize,
+}
+
 impl Folder {
     /// Create a new folder with no parent.
     ///
@@ -55,6 +129,8 @@ impl Folder {
             created_at: Utc::now(),
             paste_count: 0,
             parent_id,
+            total_bytes: 0,
+            last_updated: None,
         }
     }
 }
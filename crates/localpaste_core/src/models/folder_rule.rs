@@ -0,0 +1,126 @@
+//! Rules that file new pastes into folders automatically.
+
+use crate::models::paste::Paste;
+use crate::PasteSource;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Most rules stored at once.
+pub const MAX_FOLDER_RULES: usize = 200;
+/// Longest accepted rule pattern, in characters.
+pub const MAX_RULE_PATTERN_CHARS: usize = 256;
+
+/// Paste attribute a rule pattern is matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleField {
+    /// The paste name.
+    Name,
+    /// The stored (canonical) language.
+    Language,
+    /// Any one of the paste's tags.
+    Tag,
+    /// Where the paste came from: `api`, `gui`, `clipboard`, or `import`.
+    Source,
+}
+
+impl RuleField {
+    /// Every field, in display order.
+    pub const ALL: [Self; 4] = [Self::Name, Self::Language, Self::Tag, Self::Source];
+
+    /// Lowercase name used in JSON and the GUI.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Language => "language",
+            Self::Tag => "tag",
+            Self::Source => "source",
+        }
+    }
+}
+
+/// One "pattern on field -> folder" rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FolderRule {
+    pub id: String,
+    pub field: RuleField,
+    /// Case-insensitive glob: `*` matches any run of characters, `?` one.
+    pub pattern: String,
+    pub folder_id: String,
+    /// Evaluation order; lower runs first.
+    pub position: u32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FolderRule {
+    /// Whether this rule matches a new paste from `source`.
+    pub fn matches(&self, paste: &Paste, source: PasteSource) -> bool {
+        match self.field {
+            RuleField::Name => glob_matches(&self.pattern, &paste.name),
+            RuleField::Language => paste
+                .language
+                .as_deref()
+                .is_some_and(|language| glob_matches(&self.pattern, language)),
+            RuleField::Tag => paste
+                .tags
+                .iter()
+                .any(|tag| glob_matches(&self.pattern, tag)),
+            RuleField::Source => glob_matches(&self.pattern, source.label()),
+        }
+    }
+}
+
+/// Case-insensitive glob match of the whole `text`.
+///
+/// `*` matches any run of characters (including none) and `?` exactly one;
+/// every other character matches itself.
+pub fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position after the last `*` and the text index it is currently covering.
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p + 1, t));
+                p += 1;
+            }
+            Some(&ch) if ch == '?' || ch == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_t)) => {
+                    p = star_p;
+                    t = star_t + 1;
+                    backtrack = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&ch| ch == '*')
+}
+
+/// Request payload for adding a rule; it is appended after existing rules.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateFolderRuleRequest {
+    pub field: RuleField,
+    pub pattern: String,
+    pub folder_id: String,
+}
+
+/// Request payload for editing a rule; omitted fields keep their value.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UpdateFolderRuleRequest {
+    pub field: Option<RuleField>,
+    pub pattern: Option<String>,
+    pub folder_id: Option<String>,
+}
+
+/// Request payload listing every rule id in the new evaluation order.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReorderFolderRulesRequest {
+    pub ids: Vec<String>,
+}
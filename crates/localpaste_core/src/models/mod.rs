@@ -1,9 +1,25 @@
 //! Data models for API requests and persistence.
 
+/// Line-anchored paste annotation types.
+pub mod annotation;
+/// Binary paste attachment types.
+pub mod attachment;
+/// Paste comment thread types.
+pub mod comment;
+/// Crash-recovery draft types.
+pub mod draft;
 /// Folder data types.
 pub mod folder;
+/// Folder auto-organization rule types.
+pub mod folder_rule;
 /// Paste data types.
 pub mod paste;
+/// Recently opened paste history types.
+pub mod recent;
+/// URL sources and refresh schedules for mirrored pastes.
+pub mod url_source;
+/// Multi-user account types.
+pub mod user;
 
 #[cfg(test)]
 mod tests;
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::detect_language as detect_language_impl;
+use crate::models::folder::SortOrder;
+use crate::normalization::LineEnding;
 use crate::semantic::DerivedMeta;
 
 /// Paste metadata stored in the database and returned by the API.
@@ -26,6 +28,18 @@ pub struct Paste {
     pub updated_at: DateTime<Utc>,
     pub tags: Vec<String>,
     pub is_markdown: bool,
+    /// Line ending the content arrived with, restored by exports that ask for it.
+    #[serde(default)]
+    pub line_ending: LineEnding,
+    /// Optional unique human-chosen handle, e.g. `nginx-config`.
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// When set, the server's expiry sweep deletes the paste after this time.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// When `true`, the first `GET /api/paste/:id` deletes the paste.
+    #[serde(default)]
+    pub burn_after_read: bool,
 }
 
 /// Lightweight paste metadata used by GUI list/search paths.
@@ -50,8 +64,22 @@ pub struct CreatePasteRequest {
     pub language: Option<String>,
     pub language_is_manual: Option<bool>,
     pub folder_id: Option<String>,
+    /// Slash-separated folder names such as `work/project/logs`, resolved
+    /// from the top level; an alternative to `folder_id`.
+    #[serde(default)]
+    pub folder_path: Option<String>,
+    /// Whether missing folders on `folder_path` are created (default `true`).
+    #[serde(default)]
+    pub create_folders: Option<bool>,
     pub tags: Option<Vec<String>>,
     pub name: Option<String>,
+    /// When `true`, return the existing paste with identical stored content
+    /// instead of creating a duplicate.
+    pub dedupe: Option<bool>,
+    /// Seconds until the paste expires and is deleted; omit to keep it.
+    pub expires_in: Option<u64>,
+    /// When `true`, the first `GET /api/paste/:id` returns and deletes the paste.
+    pub burn_after_read: Option<bool>,
 }
 
 /// Request payload for updating a paste.
@@ -63,6 +91,8 @@ pub struct UpdatePasteRequest {
     pub language_is_manual: Option<bool>,
     pub folder_id: Option<String>,
     pub tags: Option<Vec<String>>,
+    /// Seconds from now until the paste expires; `0` removes any expiry.
+    pub expires_in: Option<u64>,
 }
 
 /// Query parameters for searching pastes.
@@ -70,8 +100,47 @@ pub struct UpdatePasteRequest {
 pub struct SearchQuery {
     pub q: String,
     pub folder_id: Option<String>,
+    /// Slash-separated folder path such as `work/logs`, resolved by name.
+    pub folder: Option<String>,
     pub language: Option<String>,
+    /// Restricts matches to pastes carrying this tag (case-insensitive).
+    pub tag: Option<String>,
+    /// Restricts matches to pastes updated before this RFC3339 time or date.
+    pub before: Option<String>,
+    /// Restricts matches to pastes updated at or after this RFC3339 time or date.
+    pub after: Option<String>,
     pub limit: Option<usize>,
+    /// When `true`, rows include byte ranges of content `matches`.
+    pub include_matches: Option<bool>,
+    /// When `true`, rows include full paste `content`, up to
+    /// [`crate::SEARCH_CONTENT_MAX_BYTES`] per response.
+    pub include_content: Option<bool>,
+}
+
+/// Sort keys accepted by `GET /api/pastes?sort=` and `GET /api/pastes/meta?sort=`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PasteSortKey {
+    /// Last update time.
+    #[default]
+    Updated,
+    /// Creation time.
+    Created,
+    /// Name, case-insensitive.
+    Name,
+    /// Content length in bytes.
+    Size,
+}
+
+impl PasteSortKey {
+    /// Direction used when a list request names no `order`: ascending for
+    /// names, newest or largest first otherwise.
+    pub fn default_order(self) -> SortOrder {
+        match self {
+            Self::Name => SortOrder::Asc,
+            Self::Updated | Self::Created | Self::Size => SortOrder::Desc,
+        }
+    }
 }
 
 /// Query parameters for listing pastes.
@@ -79,6 +148,72 @@ pub struct SearchQuery {
 pub struct ListQuery {
     pub limit: Option<usize>,
     pub folder_id: Option<String>,
+    /// Row order; defaults to [`PasteSortKey::Updated`].
+    pub sort: Option<PasteSortKey>,
+    /// Sort direction; defaults to [`PasteSortKey::default_order`].
+    pub order: Option<SortOrder>,
+    /// When `true`, list rows include a newline-collapsed content `snippet`.
+    pub include_snippet: Option<bool>,
+    /// BLAKE3 hex digest; restricts rows to pastes whose stored content matches.
+    pub content_hash: Option<String>,
+    /// Restricts rows to pastes carrying this tag (case-insensitive).
+    pub tag: Option<String>,
+    /// Restricts rows to pastes with this language (aliases are canonicalized).
+    pub language: Option<String>,
+}
+
+/// Query parameters for counting pastes.
+#[derive(Debug, Default, Deserialize)]
+pub struct PasteCountQuery {
+    pub folder_id: Option<String>,
+    /// Counts pastes carrying this tag (case-insensitive).
+    pub tag: Option<String>,
+    /// Counts pastes with this language (aliases are canonicalized).
+    pub language: Option<String>,
+}
+
+/// Response body for paste count queries.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PasteCountResponse {
+    pub count: usize,
+}
+
+/// Metadata row augmented with a short content snippet for list previews.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PasteMetaWithSnippet {
+    #[serde(flatten)]
+    pub meta: PasteMeta,
+    pub snippet: String,
+}
+
+/// Search row carrying the full paste content (`?include_content=true`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PasteMetaWithContent {
+    #[serde(flatten)]
+    pub meta: PasteMeta,
+    /// Present when `include_matches` was also requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matches: Option<Vec<ContentMatch>>,
+    /// `None` when the response size cap was reached or the paste is
+    /// `burn_after_read`.
+    pub content: Option<String>,
+}
+
+/// Byte range `start..end` of one search match in paste content.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ContentMatch {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Search row augmented with the positions of content matches.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PasteMetaWithMatches {
+    #[serde(flatten)]
+    pub meta: PasteMeta,
+    /// First matches in content order, capped at [`crate::SEARCH_MATCHES_MAX`];
+    /// empty when only the name or tags matched.
+    pub matches: Vec<ContentMatch>,
 }
 
 /// Metadata row for a persisted historical version of a paste.
@@ -113,12 +248,116 @@ pub struct VersionListQuery {
     pub limit: Option<usize>,
 }
 
+/// Query parameters for paste preview snippets.
+#[derive(Debug, Deserialize)]
+pub struct PreviewQuery {
+    pub lines: Option<usize>,
+}
+
+/// Truncated leading-lines preview of a paste for hover/list surfaces.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PastePreview {
+    pub id: String,
+    pub name: String,
+    pub language: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub tags: Vec<String>,
+    pub content_len: usize,
+    pub snippet: String,
+    /// `true` when `snippet` omits trailing content.
+    pub truncated: bool,
+}
+
+/// Query parameters for appending a chunk to an upload session.
+#[derive(Debug, Deserialize)]
+pub struct ChunkAppendQuery {
+    /// Expected bytes already received; omit to append at the current end.
+    pub offset: Option<usize>,
+}
+
+/// Progress of a chunked upload session.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChunkUploadStatus {
+    pub upload_id: String,
+    pub paste_id: String,
+    pub received_bytes: usize,
+}
+
+/// Line endings served by the raw content endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RawLineEndings {
+    /// Content exactly as stored.
+    #[default]
+    Stored,
+    /// Stored content with the paste's recorded line ending re-applied.
+    Original,
+}
+
+/// Query parameters for raw paste content.
+#[derive(Debug, Deserialize)]
+pub struct RawContentQuery {
+    #[serde(default)]
+    pub line_endings: RawLineEndings,
+}
+
+/// Rendered document formats served by the export endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Standalone HTML page with embedded syntax CSS.
+    #[default]
+    Html,
+    /// Paginated PDF using the built-in Courier font.
+    Pdf,
+}
+
+impl ExportFormat {
+    /// File extension for exported documents, without the leading dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Pdf => "pdf",
+        }
+    }
+
+    /// HTTP content type for exported documents.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Html => "text/html; charset=utf-8",
+            Self::Pdf => "application/pdf",
+        }
+    }
+}
+
+/// Query parameters for rendered paste exports.
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Query parameters for inline-styled HTML renders.
+#[derive(Debug, Deserialize)]
+pub struct HtmlQuery {
+    /// Bundled syntect theme name; omitted uses the export default.
+    pub theme: Option<String>,
+}
+
 /// Request payload for duplicating a paste from a historical version.
 #[derive(Debug, Deserialize)]
 pub struct DuplicateVersionRequest {
     pub name: Option<String>,
 }
 
+/// Request payload for setting or clearing a paste slug.
+#[derive(Debug, Deserialize)]
+pub struct SetSlugRequest {
+    /// New slug, or `null` to clear the current one.
+    pub slug: Option<String>,
+}
+
 impl Paste {
     /// Create a new paste with explicit language/manual-state values.
     ///
@@ -152,6 +391,10 @@ impl Paste {
             updated_at: now,
             tags: Vec::new(),
             is_markdown,
+            line_ending: LineEnding::default(),
+            slug: None,
+            expires_at: None,
+            burn_after_read: false,
         }
     }
 
@@ -172,6 +415,37 @@ impl Paste {
     }
 }
 
+impl PastePreview {
+    /// Build a preview from a full paste row.
+    ///
+    /// # Arguments
+    /// - `paste`: Canonical paste row.
+    /// - `max_lines`: Leading line budget, clamped to
+    ///   [`crate::PASTE_PREVIEW_MAX_LINES`].
+    ///
+    /// # Returns
+    /// A preview whose snippet is capped by lines and
+    /// [`crate::PASTE_PREVIEW_MAX_CHARS`].
+    pub fn from_paste(paste: &Paste, max_lines: usize) -> Self {
+        let (snippet, truncated) = crate::text::leading_lines_preview(
+            paste.content.as_str(),
+            max_lines.min(crate::PASTE_PREVIEW_MAX_LINES),
+            crate::PASTE_PREVIEW_MAX_CHARS,
+        );
+        Self {
+            id: paste.id.clone(),
+            name: paste.name.clone(),
+            language: paste.language.clone(),
+            created_at: paste.created_at,
+            updated_at: paste.updated_at,
+            tags: paste.tags.clone(),
+            content_len: paste.content.len(),
+            snippet,
+            truncated,
+        }
+    }
+}
+
 impl From<&Paste> for PasteMeta {
     fn from(value: &Paste) -> Self {
         Self {
@@ -188,6 +462,19 @@ impl From<&Paste> for PasteMeta {
     }
 }
 
+/// Expiry time `expires_in` seconds after `now`.
+///
+/// # Returns
+/// `None` for `0`, which means the paste never expires. Larger values are
+/// clamped to [`crate::MAX_PASTE_EXPIRES_IN_SECS`].
+pub fn expiry_after(now: DateTime<Utc>, expires_in: u64) -> Option<DateTime<Utc>> {
+    if expires_in == 0 {
+        return None;
+    }
+    let secs = expires_in.min(crate::MAX_PASTE_EXPIRES_IN_SECS) as i64;
+    Some(now + chrono::Duration::seconds(secs))
+}
+
 /// Normalize an optional language filter value.
 ///
 /// # Returns
@@ -198,6 +485,43 @@ pub fn normalize_language_filter(language: Option<&str>) -> Option<String> {
         .filter(|value| !value.is_empty())
 }
 
+/// Normalize an optional tag filter value.
+///
+/// # Returns
+/// Lowercased tag when non-empty after trimming, otherwise `None`.
+pub fn normalize_tag_filter(tag: Option<&str>) -> Option<String> {
+    tag.map(|value| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty())
+}
+
+/// Structured filters combined with a search query.
+///
+/// Every set filter must match. Time bounds compare against `updated_at`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchFilters {
+    /// Only pastes filed directly in this folder.
+    pub folder_id: Option<String>,
+    /// Only pastes with this language (aliases are canonicalized).
+    pub language: Option<String>,
+    /// Only pastes carrying this tag (case-insensitive).
+    pub tag: Option<String>,
+    /// Only pastes last updated strictly before this instant.
+    pub before: Option<DateTime<Utc>>,
+    /// Only pastes last updated at or after this instant.
+    pub after: Option<DateTime<Utc>>,
+}
+
+impl SearchFilters {
+    /// Copy with language and tag normalized like the filter indexes.
+    pub fn normalized(&self) -> Self {
+        Self {
+            language: normalize_language_filter(self.language.as_deref()),
+            tag: normalize_tag_filter(self.tag.as_deref()),
+            ..self.clone()
+        }
+    }
+}
+
 fn is_markdown_heading_line(line: &str) -> bool {
     let bytes = line.as_bytes();
     let mut hash_count = 0usize;
@@ -0,0 +1,38 @@
+//! Per-client history of recently opened pastes.
+
+use super::paste::PasteMeta;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Pastes remembered per client; older opens are dropped past this.
+pub const MAX_RECENT_PER_CLIENT: usize = 50;
+/// Longest accepted client label, in characters.
+pub const MAX_RECENT_CLIENT_CHARS: usize = 64;
+/// Client label recorded when a request does not name one.
+pub const DEFAULT_RECENT_CLIENT: &str = "api";
+
+/// One paste a client opened or edited, with the latest time it did.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentOpen {
+    /// Frontend label such as `gui`, `cli`, or `web`.
+    pub client: String,
+    pub paste_id: String,
+    pub opened_at: DateTime<Utc>,
+}
+
+/// `GET /api/recent` row: the paste's metadata with who opened it last.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentPaste {
+    #[serde(flatten)]
+    pub meta: PasteMeta,
+    pub client: String,
+    pub opened_at: DateTime<Utc>,
+}
+
+/// Query parameters for `GET /api/recent`.
+#[derive(Debug, Default, Deserialize)]
+pub struct RecentQuery {
+    /// Only this client's history; omit to merge every client.
+    pub client: Option<String>,
+    pub limit: Option<usize>,
+}
@@ -151,7 +151,12 @@ mod model_tests {
             language: Some("rust".to_string()),
             language_is_manual: Some(true),
             folder_id: None,
+            folder_path: None,
+            create_folders: None,
             tags: None,
+            dedupe: None,
+            expires_in: None,
+            burn_after_read: None,
         };
 
         assert!(!valid_req.content.is_empty());
@@ -215,4 +220,18 @@ mod model_tests {
         assert_eq!(req.name, "Test Folder");
         assert!(req.parent_id.is_none());
     }
+
+    #[test]
+    fn test_folder_rule_globs_are_case_insensitive_and_anchored() {
+        use folder_rule::glob_matches;
+
+        assert!(glob_matches("*.log", "Server.LOG"));
+        assert!(glob_matches("py*", "python"));
+        assert!(glob_matches("a*b*c", "aXXbYYbc"));
+        assert!(glob_matches("v?", "v2"));
+        assert!(glob_matches("*", ""));
+        assert!(!glob_matches("*.log", "server.log.bak"));
+        assert!(!glob_matches("log", "catalog"));
+        assert!(!glob_matches("v?", "v"));
+    }
 }
@@ -0,0 +1,61 @@
+//! Remote URLs that a paste mirrors, with an optional refresh schedule.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Longest accepted source URL, in characters.
+pub const MAX_SOURCE_URL_CHARS: usize = 2048;
+/// Shortest accepted refresh interval, in seconds.
+pub const MIN_REFRESH_INTERVAL_SECS: u64 = 60;
+/// Longest accepted refresh interval (30 days), in seconds.
+pub const MAX_REFRESH_INTERVAL_SECS: u64 = 30 * 24 * 60 * 60;
+/// Longest stored fetch error message, in characters.
+pub const MAX_SOURCE_ERROR_CHARS: usize = 500;
+
+/// Where a paste's content comes from and when it is next re-fetched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UrlSource {
+    pub paste_id: String,
+    /// `http` or `https` URL fetched on refresh.
+    pub url: String,
+    /// Seconds between scheduled refreshes; `None` refreshes only on request.
+    pub refresh_interval_secs: Option<u64>,
+    pub created_at: DateTime<Utc>,
+    /// Time of the last fetch attempt, successful or not.
+    pub last_checked_at: Option<DateTime<Utc>>,
+    /// Time the last fetch changed the paste content.
+    pub last_changed_at: Option<DateTime<Utc>>,
+    /// Why the last fetch failed; cleared by the next success.
+    pub last_error: Option<String>,
+}
+
+impl UrlSource {
+    /// When the next scheduled refresh is due, or `None` without a schedule.
+    pub fn next_refresh_at(&self) -> Option<DateTime<Utc>> {
+        let interval = self.refresh_interval_secs?;
+        let since = self.last_checked_at.unwrap_or(self.created_at);
+        let interval = interval.min(MAX_REFRESH_INTERVAL_SECS) as i64;
+        Some(since + Duration::seconds(interval))
+    }
+
+    /// Whether a scheduled refresh is due at `now`.
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_refresh_at().is_some_and(|due| due <= now)
+    }
+}
+
+/// Request payload for linking a paste to a source URL.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SetUrlSourceRequest {
+    pub url: String,
+    /// Seconds between scheduled refreshes; omit for manual refresh only.
+    pub refresh_interval_secs: Option<u64>,
+}
+
+/// Result of re-fetching a paste from its source URL.
+#[derive(Debug, Clone, Serialize)]
+pub struct UrlRefreshResponse {
+    /// Whether the fetched content differed and the paste was updated.
+    pub changed: bool,
+    pub source: UrlSource,
+}
@@ -0,0 +1,109 @@
+//! User accounts and per-paste ownership for the optional multi-user mode.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What a user may do beyond managing their own pastes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UserRole {
+    /// Reads and writes every paste and manages users.
+    Admin,
+    /// Reads and writes own pastes and reads pastes others shared.
+    #[default]
+    Member,
+}
+
+/// Account that authenticates with a bearer token.
+///
+/// Only a hash of the token is stored; the token itself is shown once when the
+/// account is created.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct User {
+    pub id: String,
+    pub name: String,
+    pub role: UserRole,
+    pub created_at: DateTime<Utc>,
+}
+
+impl User {
+    /// Whether this user may read and write every paste.
+    pub fn is_admin(&self) -> bool {
+        self.role == UserRole::Admin
+    }
+
+    /// Whether this user may read a paste with `access`.
+    ///
+    /// Pastes without an owner (created in single-user mode or by the GUI)
+    /// are visible to admins only.
+    pub fn can_read(&self, access: Option<&PasteAccess>) -> bool {
+        self.is_admin() || access.is_some_and(|access| access.shared || self.owns(access))
+    }
+
+    /// Whether this user may change or delete a paste with `access`.
+    pub fn can_write(&self, access: Option<&PasteAccess>) -> bool {
+        self.is_admin() || access.is_some_and(|access| self.owns(access))
+    }
+
+    fn owns(&self, access: &PasteAccess) -> bool {
+        access.owner_id == self.id
+    }
+}
+
+/// Owner and visibility recorded for a paste created in multi-user mode.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PasteAccess {
+    pub owner_id: String,
+    /// Other users may read (but not change) the paste.
+    pub shared: bool,
+}
+
+/// Which pastes list, search, and count routes return in multi-user mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListScope {
+    /// Pastes the caller owns.
+    #[default]
+    Own,
+    /// Owned pastes plus everything the caller may read.
+    All,
+}
+
+/// `?scope=` query parameter accepted next to list and search filters.
+#[derive(Debug, Default, Deserialize)]
+pub struct ScopeQuery {
+    #[serde(default)]
+    pub scope: ListScope,
+}
+
+impl ListScope {
+    /// Whether a paste with `access` belongs in `user`'s results.
+    pub fn includes(self, user: &User, access: Option<&PasteAccess>) -> bool {
+        match self {
+            ListScope::Own => access.is_some_and(|access| access.owner_id == user.id),
+            ListScope::All => user.can_read(access),
+        }
+    }
+}
+
+/// Request payload for creating a user.
+#[derive(Debug, Deserialize)]
+pub struct CreateUserRequest {
+    pub name: String,
+    #[serde(default)]
+    pub role: UserRole,
+}
+
+/// Response for a created user, carrying the only copy of its token.
+#[derive(Debug, Serialize)]
+pub struct CreatedUser {
+    #[serde(flatten)]
+    pub user: User,
+    pub token: String,
+}
+
+/// Request payload for changing a paste's visibility.
+#[derive(Debug, Deserialize)]
+pub struct PasteVisibilityRequest {
+    pub shared: bool,
+}
@@ -0,0 +1,54 @@
+//! File name helpers shared by exports across the API, CLI, and GUI.
+
+/// Maps a language to its preferred export file extension.
+///
+/// # Returns
+/// Extension without leading dot, defaulting to `"txt"`.
+pub fn language_extension(language: Option<&str>) -> &'static str {
+    language
+        .and_then(crate::detection::canonical::lookup)
+        .and_then(|spec| spec.extensions.first().copied())
+        .unwrap_or("txt")
+}
+
+/// Sanitizes a filename candidate for cross-platform export compatibility.
+///
+/// # Returns
+/// Safe filename with reserved characters replaced by `_`.
+pub fn sanitize_filename(value: &str) -> String {
+    let mut out: String = value
+        .chars()
+        .map(|ch| match ch {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            _ => ch,
+        })
+        .collect();
+    out = out.trim().to_string();
+    if out.is_empty() {
+        "localpaste-export".to_string()
+    } else {
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_extension_maps_known_and_unknown_languages() {
+        assert_eq!(language_extension(Some("rust")), "rs");
+        assert_eq!(language_extension(Some(" Python ")), "py");
+        assert_eq!(language_extension(Some("csharp")), "cs");
+        assert_eq!(language_extension(Some("bash")), "sh");
+        assert_eq!(language_extension(Some("scss")), "scss");
+        assert_eq!(language_extension(Some("unknown")), "txt");
+        assert_eq!(language_extension(None), "txt");
+    }
+
+    #[test]
+    fn sanitize_filename_replaces_reserved_chars_and_falls_back() {
+        assert_eq!(sanitize_filename("bad<>:\"/\\|?*name"), "bad_________name");
+        assert_eq!(sanitize_filename("   "), "localpaste-export");
+    }
+}
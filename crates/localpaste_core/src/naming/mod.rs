@@ -1,5 +1,12 @@
 //! Utilities for generating human-friendly paste names.
 
+/// Export file name and extension helpers.
+pub mod files;
+/// Paste slug validation.
+pub mod slug;
+/// Content-derived default titles.
+pub mod title;
+
 use rand::Rng;
 
 const ADJECTIVES: &[&str] = &[
@@ -0,0 +1,48 @@
+//! Validation for user-chosen paste slugs such as `nginx-config`.
+
+use crate::error::AppError;
+
+/// Longest slug accepted, in characters.
+pub const MAX_SLUG_LEN: usize = 64;
+
+/// Normalize and validate a user-supplied slug.
+///
+/// Slugs are trimmed and lowercased, must start with a letter or digit, and
+/// may otherwise contain only `a-z`, `0-9`, `-`, `_`, and `.`.
+///
+/// # Returns
+/// The normalized slug.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] when the slug is empty, too long, or
+/// contains other characters.
+pub fn normalize_slug(raw: &str) -> Result<String, AppError> {
+    let slug = raw.trim().to_ascii_lowercase();
+    let valid_char = |ch: char| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.');
+    if slug.is_empty()
+        || slug.len() > MAX_SLUG_LEN
+        || !slug.starts_with(|ch: char| ch.is_ascii_alphanumeric())
+        || !slug.chars().all(valid_char)
+    {
+        return Err(AppError::BadRequest(format!(
+            "Slug must be 1-{} characters of a-z, 0-9, '-', '_' or '.', starting with a letter or digit",
+            MAX_SLUG_LEN
+        )));
+    }
+    Ok(slug)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_slug_lowercases_and_rejects_invalid_input() {
+        assert_eq!(normalize_slug("  Nginx-Config ").unwrap(), "nginx-config");
+        assert_eq!(normalize_slug("v1.2_notes").unwrap(), "v1.2_notes");
+        for bad in ["", "-lead", ".hidden", "has space", "a/b", "ünï"] {
+            assert!(normalize_slug(bad).is_err(), "{bad}");
+        }
+        assert!(normalize_slug(&"a".repeat(MAX_SLUG_LEN + 1)).is_err());
+    }
+}
@@ -0,0 +1,333 @@
+//! Default paste titles derived from content when no name is supplied.
+//!
+//! The `content` strategy tries, in order, the first markdown heading, the
+//! first code comment, and the first non-empty line, and falls back to a
+//! random adjective-noun name. Strategies are chosen per write path through
+//! `LOCALPASTE_TITLE_STRATEGY` and `LOCALPASTE_CLIPBOARD_TITLE_STRATEGY`.
+
+use super::generate_name;
+use crate::size_limits::PasteSource;
+use std::fmt;
+use std::str::FromStr;
+use tracing::warn;
+
+const TITLE_STRATEGY_ENV_KEY: &str = "LOCALPASTE_TITLE_STRATEGY";
+const CLIPBOARD_TITLE_STRATEGY_ENV_KEY: &str = "LOCALPASTE_CLIPBOARD_TITLE_STRATEGY";
+/// Longest derived title, in characters, before truncation.
+pub const MAX_TITLE_CHARS: usize = 60;
+/// Lines inspected for a heading or comment before using the first line.
+const TITLE_SCAN_LINES: usize = 50;
+/// Comment openers, longest first so `///` wins over `//`.
+const COMMENT_MARKERS: &[&str] = &[
+    "<!--", "///", "//!", "/**", "/*!", "//", "/*", "\"\"\"", "'''", "--", "#", ";", "%",
+];
+/// Comment closers stripped from the end of a comment line.
+const COMMENT_CLOSERS: &[&str] = &["*/", "-->", "\"\"\"", "'''"];
+/// Comment bodies that are tooling noise rather than a description.
+const IGNORED_COMMENT_PREFIXES: &[&str] = &[
+    "spdx-license-identifier",
+    "copyright",
+    "-*-",
+    "eslint",
+    "prettier-ignore",
+    "@ts-",
+    "noqa",
+    "type:",
+    "pylint:",
+];
+
+/// How a default title is chosen for an unnamed paste.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TitleStrategy {
+    /// First markdown heading, then first comment, then first non-empty line.
+    #[default]
+    Content,
+    /// First non-empty line only.
+    FirstLine,
+    /// Random adjective-noun name, ignoring content.
+    Random,
+}
+
+impl TitleStrategy {
+    /// Lowercase name used by environment variables.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Content => "content",
+            Self::FirstLine => "first-line",
+            Self::Random => "random",
+        }
+    }
+
+    /// Choose a name for `content` with this strategy.
+    ///
+    /// # Returns
+    /// A derived title, or a random name when nothing usable is found.
+    pub fn name_for(self, content: &str) -> String {
+        title_from_content(content, self).unwrap_or_else(generate_name)
+    }
+}
+
+impl fmt::Display for TitleStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TitleStrategy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "content" => Ok(Self::Content),
+            "first-line" | "first_line" | "line" => Ok(Self::FirstLine),
+            "random" => Ok(Self::Random),
+            other => Err(format!(
+                "unknown title strategy '{}' (expected 'content', 'first-line', or 'random')",
+                other
+            )),
+        }
+    }
+}
+
+/// Title strategies for each write path.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TitleOptions {
+    /// Strategy for API, GUI, and import writes.
+    pub default: TitleStrategy,
+    /// Strategy for pastes captured from the clipboard.
+    pub clipboard: TitleStrategy,
+}
+
+impl TitleOptions {
+    /// Load strategies from the environment, falling back to defaults on bad values.
+    ///
+    /// The clipboard strategy inherits `LOCALPASTE_TITLE_STRATEGY` when its own
+    /// variable is unset.
+    ///
+    /// # Returns
+    /// Parsed options; invalid values are logged and replaced by defaults.
+    pub fn from_env() -> Self {
+        let default = read_strategy_env(TITLE_STRATEGY_ENV_KEY, TitleStrategy::default())
+            .unwrap_or_else(|message| {
+                warn!("{}; using default", message);
+                TitleStrategy::default()
+            });
+        let clipboard = read_strategy_env(CLIPBOARD_TITLE_STRATEGY_ENV_KEY, default)
+            .unwrap_or_else(|message| {
+                warn!("{}; using default", message);
+                default
+            });
+        Self { default, clipboard }
+    }
+
+    /// Load strategies from the environment, rejecting malformed values.
+    ///
+    /// # Returns
+    /// Parsed options when every provided value is valid.
+    ///
+    /// # Errors
+    /// Returns a descriptive message for the first invalid value.
+    pub fn from_env_strict() -> Result<Self, String> {
+        let default = read_strategy_env(TITLE_STRATEGY_ENV_KEY, TitleStrategy::default())?;
+        let clipboard = read_strategy_env(CLIPBOARD_TITLE_STRATEGY_ENV_KEY, default)?;
+        Ok(Self { default, clipboard })
+    }
+
+    /// Strategy applied to writes from `source`.
+    pub fn for_source(&self, source: PasteSource) -> TitleStrategy {
+        match source {
+            PasteSource::ClipboardCapture => self.clipboard,
+            PasteSource::Api | PasteSource::Gui | PasteSource::Import => self.default,
+        }
+    }
+}
+
+fn read_strategy_env(name: &str, unset: TitleStrategy) -> Result<TitleStrategy, String> {
+    let Ok(value) = std::env::var(name) else {
+        return Ok(unset);
+    };
+    if value.trim().is_empty() {
+        return Ok(unset);
+    }
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {}='{}'", name, value))
+}
+
+/// Derive a title from `content` without falling back to a random name.
+///
+/// Titles have whitespace collapsed and are cut at a word boundary to at most
+/// [`MAX_TITLE_CHARS`] characters, ending in `…` when shortened. Lines without
+/// any letter or digit are skipped.
+///
+/// # Returns
+/// `None` for [`TitleStrategy::Random`] or when no usable line is found.
+pub fn title_from_content(content: &str, strategy: TitleStrategy) -> Option<String> {
+    let raw = match strategy {
+        TitleStrategy::Random => None,
+        TitleStrategy::FirstLine => first_line(content),
+        TitleStrategy::Content => first_heading(content)
+            .or_else(|| first_comment(content))
+            .or_else(|| first_line(content)),
+    }?;
+    Some(truncate_title(raw.as_str()))
+}
+
+fn first_heading(content: &str) -> Option<String> {
+    let mut open_fence: Option<char> = None;
+    for line in content.lines().take(TITLE_SCAN_LINES) {
+        let trimmed = line.trim_start();
+        if let Some(fence) = ['`', '~']
+            .into_iter()
+            .find(|fence| trimmed.chars().take_while(|ch| ch == fence).count() >= 3)
+        {
+            match open_fence {
+                None => open_fence = Some(fence),
+                Some(open) if open == fence => open_fence = None,
+                Some(_) => {}
+            }
+            continue;
+        }
+        if open_fence.is_some() {
+            continue;
+        }
+        let level = trimmed.chars().take_while(|ch| *ch == '#').count();
+        if !(1..=6).contains(&level) {
+            continue;
+        }
+        let rest = &trimmed[level..];
+        if !rest.starts_with([' ', '\t']) {
+            continue;
+        }
+        let text = rest.trim().trim_end_matches('#').trim_end();
+        if is_meaningful(text) {
+            return Some(text.to_string());
+        }
+    }
+    None
+}
+
+fn comment_body(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    let marker = COMMENT_MARKERS
+        .iter()
+        .find(|marker| trimmed.starts_with(**marker))?;
+    let rest = &trimmed[marker.len()..];
+    // Single-character markers double as syntax (`#[derive]`, `#!`, `;;`), so
+    // they only count as comments when followed by whitespace.
+    if marker.len() == 1 && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    let mut body = rest
+        .trim_start_matches(['/', '*', '!', '#', ';', '-'])
+        .trim();
+    for closer in COMMENT_CLOSERS {
+        body = body.strip_suffix(closer).unwrap_or(body).trim_end();
+    }
+    let lowered = body.to_ascii_lowercase();
+    if IGNORED_COMMENT_PREFIXES
+        .iter()
+        .any(|prefix| lowered.starts_with(prefix))
+    {
+        return None;
+    }
+    Some(body)
+}
+
+fn first_comment(content: &str) -> Option<String> {
+    content
+        .lines()
+        .take(TITLE_SCAN_LINES)
+        .filter_map(comment_body)
+        .find(|body| is_meaningful(body))
+        .map(ToString::to_string)
+}
+
+fn first_line(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .find(|line| is_meaningful(line))
+        .map(ToString::to_string)
+}
+
+fn is_meaningful(text: &str) -> bool {
+    text.chars().any(char::is_alphanumeric)
+}
+
+fn truncate_title(raw: &str) -> String {
+    let collapsed = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= MAX_TITLE_CHARS {
+        return collapsed;
+    }
+    let cut: String = collapsed.chars().take(MAX_TITLE_CHARS - 1).collect();
+    let cut = match cut.rfind(' ') {
+        Some(space) if space >= MAX_TITLE_CHARS / 2 => &cut[..space],
+        _ => cut.as_str(),
+    };
+    format!("{}…", cut.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_strategy_prefers_heading_then_comment_then_first_line() {
+        let cases = [
+            (
+                "intro text\n```sh\n# not a heading\n```\n## Deploy steps ##\n",
+                "Deploy steps",
+            ),
+            (
+                "#!/usr/bin/env bash\n#[derive]\n# Rotate the nginx logs\nlogrotate -f\n",
+                "Rotate the nginx logs",
+            ),
+            (
+                "// SPDX-License-Identifier: MIT\n/** Parse a config file. */\nfn parse() {}\n",
+                "Parse a config file.",
+            ),
+            ("<!-- page footer -->\n<footer/>", "page footer"),
+            ("\n\n  SELECT *   FROM users;\n", "SELECT * FROM users;"),
+        ];
+        for (content, expected) in cases {
+            assert_eq!(
+                title_from_content(content, TitleStrategy::Content).as_deref(),
+                Some(expected),
+                "{content:?}"
+            );
+        }
+        assert_eq!(title_from_content("---\n\n", TitleStrategy::Content), None);
+    }
+
+    #[test]
+    fn other_strategies_and_truncation() {
+        let content = "use std::io;\n// Read stdin\n";
+        assert_eq!(
+            title_from_content(content, TitleStrategy::FirstLine).as_deref(),
+            Some("use std::io;")
+        );
+        assert_eq!(title_from_content(content, TitleStrategy::Random), None);
+        assert!(!TitleStrategy::Random.name_for(content).is_empty());
+
+        let long = "word ".repeat(40);
+        let title = title_from_content(long.as_str(), TitleStrategy::Content).unwrap();
+        assert!(title.chars().count() <= MAX_TITLE_CHARS, "{title}");
+        assert!(title.ends_with("word…"), "{title}");
+
+        assert_eq!(
+            "First_Line".parse::<TitleStrategy>().unwrap(),
+            TitleStrategy::FirstLine
+        );
+        assert!("clever".parse::<TitleStrategy>().is_err());
+        let options = TitleOptions {
+            default: TitleStrategy::Content,
+            clipboard: TitleStrategy::Random,
+        };
+        assert_eq!(
+            options.for_source(PasteSource::ClipboardCapture),
+            TitleStrategy::Random
+        );
+        assert_eq!(options.for_source(PasteSource::Api), TitleStrategy::Content);
+    }
+}
@@ -0,0 +1,331 @@
+//! Save-time content normalization: line endings, byte-order marks, and UTF-8.
+//!
+//! Defaults preserve content exactly as submitted. Normalization is opt-in via
+//! environment variables so existing databases keep byte-for-byte behavior.
+
+use crate::config::{parse_bool_env, parse_bool_env_strict};
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::env;
+use tracing::warn;
+
+const LINE_ENDINGS_ENV_KEY: &str = "LOCALPASTE_LINE_ENDINGS";
+const STRIP_BOM_ENV_KEY: &str = "LOCALPASTE_STRIP_BOM";
+const INVALID_UTF8_ENV_KEY: &str = "LOCALPASTE_INVALID_UTF8";
+const UTF8_BOM: char = '\u{FEFF}';
+
+/// Line-ending convention recorded for a paste.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    /// Unix `\n`.
+    #[default]
+    Lf,
+    /// Windows `\r\n`.
+    Crlf,
+}
+
+/// How line endings are handled when content is saved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEndingPolicy {
+    /// Store line endings exactly as submitted.
+    #[default]
+    Preserve,
+    /// Convert `\r\n` to `\n` and remember the original convention.
+    Lf,
+}
+
+/// How byte uploads that are not valid UTF-8 are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InvalidUtf8Policy {
+    /// Reject the upload with a `400`.
+    #[default]
+    Reject,
+    /// Replace invalid sequences with U+FFFD.
+    Replace,
+}
+
+/// Normalization applied to paste content on create and update.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NormalizationOptions {
+    pub line_endings: LineEndingPolicy,
+    pub strip_bom: bool,
+    pub invalid_utf8: InvalidUtf8Policy,
+}
+
+impl NormalizationOptions {
+    /// Load options from the environment, falling back to defaults on bad values.
+    ///
+    /// # Returns
+    /// Parsed options; invalid values are logged and replaced by defaults.
+    pub fn from_env() -> Self {
+        Self {
+            line_endings: read_policy_env(LINE_ENDINGS_ENV_KEY, parse_line_ending_policy)
+                .unwrap_or_else(|message| {
+                    warn!("{}; using default", message);
+                    LineEndingPolicy::default()
+                }),
+            strip_bom: parse_bool_env(STRIP_BOM_ENV_KEY, false),
+            invalid_utf8: read_policy_env(INVALID_UTF8_ENV_KEY, parse_invalid_utf8_policy)
+                .unwrap_or_else(|message| {
+                    warn!("{}; using default", message);
+                    InvalidUtf8Policy::default()
+                }),
+        }
+    }
+
+    /// Load options from the environment, rejecting malformed values.
+    ///
+    /// # Returns
+    /// Parsed options when every provided value is valid.
+    ///
+    /// # Errors
+    /// Returns a descriptive message for the first invalid value.
+    pub fn from_env_strict() -> Result<Self, String> {
+        Ok(Self {
+            line_endings: read_policy_env(LINE_ENDINGS_ENV_KEY, parse_line_ending_policy)?,
+            strip_bom: parse_bool_env_strict(STRIP_BOM_ENV_KEY, false)?,
+            invalid_utf8: read_policy_env(INVALID_UTF8_ENV_KEY, parse_invalid_utf8_policy)?,
+        })
+    }
+}
+
+fn parse_line_ending_policy(value: &str) -> Option<LineEndingPolicy> {
+    match value {
+        "preserve" => Some(LineEndingPolicy::Preserve),
+        "lf" => Some(LineEndingPolicy::Lf),
+        _ => None,
+    }
+}
+
+fn parse_invalid_utf8_policy(value: &str) -> Option<InvalidUtf8Policy> {
+    match value {
+        "reject" => Some(InvalidUtf8Policy::Reject),
+        "replace" => Some(InvalidUtf8Policy::Replace),
+        _ => None,
+    }
+}
+
+fn read_policy_env<T: Default>(
+    name: &str,
+    parse: impl FnOnce(&str) -> Option<T>,
+) -> Result<T, String> {
+    let Ok(value) = env::var(name) else {
+        return Ok(T::default());
+    };
+    let normalized = value.trim().to_ascii_lowercase();
+    if normalized.is_empty() {
+        return Ok(T::default());
+    }
+    parse(normalized.as_str()).ok_or_else(|| format!("Invalid value for {}='{}'", name, value))
+}
+
+/// Detect the dominant line ending in `content`.
+///
+/// # Returns
+/// `None` when the content has no line breaks; ties resolve to [`LineEnding::Lf`].
+pub fn detect_line_ending(content: &str) -> Option<LineEnding> {
+    let total = content.matches('\n').count();
+    if total == 0 {
+        return None;
+    }
+    let crlf = content.matches("\r\n").count();
+    if crlf * 2 > total {
+        Some(LineEnding::Crlf)
+    } else {
+        Some(LineEnding::Lf)
+    }
+}
+
+/// Apply save-time normalization to incoming content.
+///
+/// # Arguments
+/// - `content`: Content as submitted.
+/// - `options`: Active normalization options.
+///
+/// # Returns
+/// The content to store, plus the line ending detected before conversion.
+pub fn normalize_content(
+    content: String,
+    options: &NormalizationOptions,
+) -> (String, Option<LineEnding>) {
+    let content = match content.strip_prefix(UTF8_BOM) {
+        Some(stripped) if options.strip_bom => stripped.to_string(),
+        _ => content,
+    };
+    let detected = detect_line_ending(content.as_str());
+    let content = if options.line_endings == LineEndingPolicy::Lf && content.contains("\r\n") {
+        content.replace("\r\n", "\n")
+    } else {
+        content
+    };
+    (content, detected)
+}
+
+/// Choose the line ending to record after a save.
+///
+/// Under [`LineEndingPolicy::Lf`] stored content always looks LF-terminated,
+/// so an LF save keeps the previously recorded convention instead of
+/// forgetting that the paste originally used CRLF.
+///
+/// # Arguments
+/// - `previous`: Convention recorded before this save.
+/// - `detected`: Convention detected in the incoming content.
+/// - `policy`: Active line-ending policy.
+///
+/// # Returns
+/// The convention exports should restore.
+pub fn record_line_ending(
+    previous: LineEnding,
+    detected: Option<LineEnding>,
+    policy: LineEndingPolicy,
+) -> LineEnding {
+    match (detected, policy) {
+        (Some(LineEnding::Crlf), _) => LineEnding::Crlf,
+        (Some(LineEnding::Lf), LineEndingPolicy::Preserve) => LineEnding::Lf,
+        _ => previous,
+    }
+}
+
+/// Re-apply a recorded line ending to stored content for export.
+///
+/// # Returns
+/// Borrowed content for LF, or content with bare `\n` expanded to `\r\n`.
+pub fn restore_line_endings(content: &str, ending: LineEnding) -> Cow<'_, str> {
+    if ending == LineEnding::Lf || !content.contains('\n') {
+        return Cow::Borrowed(content);
+    }
+    let mut restored = String::with_capacity(content.len() + content.len() / 16);
+    let mut previous = None;
+    for ch in content.chars() {
+        if ch == '\n' && previous != Some('\r') {
+            restored.push('\r');
+        }
+        restored.push(ch);
+        previous = Some(ch);
+    }
+    Cow::Owned(restored)
+}
+
+/// Compare editor content against its stored form after save-time normalization.
+///
+/// Clients holding unnormalized text use this to recognize a save ack as
+/// current instead of re-saving content the server already normalized.
+///
+/// # Returns
+/// `true` when `stored` equals `local`, optionally with BOM stripped and CRLF converted.
+pub fn content_matches_stored(local: &str, stored: &str) -> bool {
+    if local == stored || local.strip_prefix(UTF8_BOM) == Some(stored) {
+        return true;
+    }
+    if !local.contains("\r\n") {
+        return false;
+    }
+    let lf = local.replace("\r\n", "\n");
+    lf == stored || lf.strip_prefix(UTF8_BOM) == Some(stored)
+}
+
+/// Decode uploaded bytes according to the invalid UTF-8 policy.
+///
+/// # Returns
+/// Decoded content.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] when bytes are invalid and the policy rejects them.
+pub fn decode_content_bytes(
+    bytes: Vec<u8>,
+    options: &NormalizationOptions,
+) -> Result<String, AppError> {
+    match String::from_utf8(bytes) {
+        Ok(content) => Ok(content),
+        Err(err) if options.invalid_utf8 == InvalidUtf8Policy::Replace => {
+            Ok(String::from_utf8_lossy(err.as_bytes()).into_owned())
+        }
+        Err(_) => Err(AppError::BadRequest(
+            "Uploaded content is not valid UTF-8".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lf_options() -> NormalizationOptions {
+        NormalizationOptions {
+            line_endings: LineEndingPolicy::Lf,
+            strip_bom: true,
+            invalid_utf8: InvalidUtf8Policy::Replace,
+        }
+    }
+
+    #[test]
+    fn detect_line_ending_uses_dominant_convention() {
+        assert_eq!(detect_line_ending("one line"), None);
+        assert_eq!(detect_line_ending("a\nb\n"), Some(LineEnding::Lf));
+        assert_eq!(detect_line_ending("a\r\nb\r\n"), Some(LineEnding::Crlf));
+        assert_eq!(detect_line_ending("a\r\nb\nc\r\n"), Some(LineEnding::Crlf));
+        assert_eq!(detect_line_ending("a\r\nb\n"), Some(LineEnding::Lf));
+    }
+
+    #[test]
+    fn default_options_preserve_content_exactly() {
+        let input = "\u{FEFF}a\r\nb\n".to_string();
+        let (content, detected) = normalize_content(input.clone(), &Default::default());
+        assert_eq!(content, input);
+        assert_eq!(detected, Some(LineEnding::Lf));
+    }
+
+    #[test]
+    fn lf_policy_converts_crlf_and_strips_bom() {
+        let (content, detected) =
+            normalize_content("\u{FEFF}a\r\nb\r\n".to_string(), &lf_options());
+        assert_eq!(content, "a\nb\n");
+        assert_eq!(detected, Some(LineEnding::Crlf));
+    }
+
+    #[test]
+    fn record_line_ending_keeps_crlf_across_normalized_saves() {
+        let lf = LineEndingPolicy::Lf;
+        let recorded = record_line_ending(LineEnding::Lf, Some(LineEnding::Crlf), lf);
+        assert_eq!(recorded, LineEnding::Crlf);
+        assert_eq!(
+            record_line_ending(recorded, Some(LineEnding::Lf), lf),
+            LineEnding::Crlf
+        );
+        assert_eq!(
+            record_line_ending(recorded, Some(LineEnding::Lf), LineEndingPolicy::Preserve),
+            LineEnding::Lf
+        );
+        assert_eq!(record_line_ending(recorded, None, lf), LineEnding::Crlf);
+    }
+
+    #[test]
+    fn restore_line_endings_round_trips_mixed_content() {
+        assert_eq!(restore_line_endings("a\nb", LineEnding::Lf), "a\nb");
+        assert_eq!(
+            restore_line_endings("a\nb\r\nc\n", LineEnding::Crlf),
+            "a\r\nb\r\nc\r\n"
+        );
+    }
+
+    #[test]
+    fn content_matches_stored_accepts_normalized_forms_only() {
+        assert!(content_matches_stored("a\r\nb", "a\r\nb"));
+        assert!(content_matches_stored("\u{FEFF}a\r\nb", "a\nb"));
+        assert!(content_matches_stored("\u{FEFF}a\r\nb", "\u{FEFF}a\nb"));
+        assert!(content_matches_stored("\u{FEFF}a\nb", "a\nb"));
+        assert!(!content_matches_stored("a\nb", "a\nc"));
+    }
+
+    #[test]
+    fn decode_content_bytes_follows_invalid_utf8_policy() {
+        let bytes = vec![b'o', b'k', 0xFF];
+        let err = decode_content_bytes(bytes.clone(), &Default::default())
+            .expect_err("default policy rejects");
+        assert!(matches!(err, AppError::BadRequest(_)));
+        let decoded = decode_content_bytes(bytes, &lf_options()).expect("replace policy");
+        assert_eq!(decoded, "ok\u{FFFD}");
+    }
+}
@@ -1,6 +1,7 @@
 //! Lightweight locally-derived semantic metadata for retrieval.
 
 use crate::detection::canonical::canonicalize;
+use crate::indentation::{detect_indentation, IndentStyle};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -43,6 +44,9 @@ pub struct DerivedMeta {
     pub handle: Option<String>,
     #[serde(default)]
     pub terms: Vec<String>,
+    /// Detected indentation convention, when any line is indented.
+    #[serde(default)]
+    pub indent: Option<IndentStyle>,
 }
 
 /// Derive cheap structural retrieval hints from paste content.
@@ -72,6 +76,7 @@ pub fn derive(content: &str, language: Option<&str>) -> DerivedMeta {
         kind,
         handle,
         terms,
+        indent: detect_indentation(content),
     }
 }
 
@@ -0,0 +1,328 @@
+//! Optional JSON settings file stored inside the DB directory.
+//!
+//! `settings.json` holds configuration that does not fit an environment
+//! variable, such as the list of remote backup targets. A missing file means
+//! every section uses its defaults. Files carry a schema `version` and are
+//! migrated on load. [`load_settings`] reports a malformed file as an error;
+//! the GUI uses [`load_settings_or_quarantine`], which moves it aside and
+//! starts from defaults instead.
+
+use crate::config::api_addr_file_path_for_db_path;
+use crate::constants::SETTINGS_FILE_NAME;
+use crate::db::backup_remote::RemoteBackupTarget;
+use crate::discovery::write_file_atomic;
+use crate::error::AppError;
+use crate::timestamps::DisplayTimezone;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Contents of the settings file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub backup: BackupSettings,
+    #[serde(default)]
+    pub display: DisplaySettings,
+    #[serde(default)]
+    pub updates: UpdateSettings,
+}
+
+/// Backup section of the settings file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupSettings {
+    /// Destinations each new backup is pushed to after it is created locally.
+    #[serde(default)]
+    pub remote_targets: Vec<RemoteBackupTarget>,
+    /// Number of backups kept on each remote target; `None` keeps all of them.
+    #[serde(default)]
+    pub remote_keep_last: Option<usize>,
+    /// Hours between scheduled backups while the GUI runs; `None` disables them.
+    #[serde(default)]
+    pub interval_hours: Option<u64>,
+}
+
+/// Display section of the settings file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplaySettings {
+    /// Timezone the GUI and CLI use for absolute timestamps.
+    #[serde(default)]
+    pub timezone: DisplayTimezone,
+}
+
+/// Update check section of the settings file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateSettings {
+    /// Whether the GUI looks up the latest GitHub release once a day.
+    #[serde(default)]
+    pub check_enabled: bool,
+    /// Release version whose notice the user dismissed.
+    #[serde(default)]
+    pub dismissed_version: Option<String>,
+}
+
+/// Schema version written to the `version` key of new settings files.
+///
+/// Bump it together with a new entry in [`SETTINGS_MIGRATIONS`] whenever a
+/// section is renamed or reshaped; purely additive fields with
+/// `#[serde(default)]` do not need a bump.
+pub const SETTINGS_SCHEMA_VERSION: u64 = 1;
+
+/// Upgrades, indexed by the version they start from. Entry `n` rewrites a
+/// version `n` document in place into version `n + 1`.
+const SETTINGS_MIGRATIONS: [fn(&mut Map<String, Value>); SETTINGS_SCHEMA_VERSION as usize] = [
+    // Version 0 files predate the `version` key; their sections are read as is.
+    |_| {},
+];
+
+/// Settings read by [`load_settings_or_quarantine`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadedSettings {
+    pub settings: Settings,
+    /// Where an unreadable settings file was moved before defaults were used.
+    pub quarantined: Option<PathBuf>,
+}
+
+/// Path of the settings file for a database path.
+///
+/// # Returns
+/// Path to `settings.json` next to the `.api-addr` discovery file.
+pub fn settings_file_path_for_db_path(db_path: &str) -> PathBuf {
+    api_addr_file_path_for_db_path(db_path).with_file_name(SETTINGS_FILE_NAME)
+}
+
+fn document_version(document: &Map<String, Value>) -> u64 {
+    document.get("version").and_then(Value::as_u64).unwrap_or(0)
+}
+
+/// Decode a settings document, migrating older schema versions first.
+///
+/// Files from a newer release load with the sections this build knows;
+/// [`save_settings`] refuses to overwrite them.
+fn parse_settings(raw: &[u8]) -> Result<Settings, serde_json::Error> {
+    let mut document: Map<String, Value> = serde_json::from_slice(raw)?;
+    let from = document_version(&document);
+    for migrate in SETTINGS_MIGRATIONS.iter().skip(from as usize) {
+        migrate(&mut document);
+    }
+    document.remove("version");
+    serde_json::from_value(Value::Object(document))
+}
+
+fn read_settings_file(path: &Path) -> Result<Option<Vec<u8>>, AppError> {
+    match fs::read(path) {
+        Ok(raw) => Ok(Some(raw)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(AppError::StorageMessage(format!(
+            "Failed to read settings '{}': {}",
+            path.display(),
+            err
+        ))),
+    }
+}
+
+/// Load the settings for `db_path`.
+///
+/// # Returns
+/// Parsed settings migrated to the current schema, or defaults when the
+/// file does not exist.
+///
+/// # Errors
+/// Returns an error when the file exists but cannot be read or parsed.
+pub fn load_settings(db_path: &str) -> Result<Settings, AppError> {
+    let path = settings_file_path_for_db_path(db_path);
+    let Some(raw) = read_settings_file(&path)? else {
+        return Ok(Settings::default());
+    };
+    parse_settings(&raw).map_err(|err| {
+        AppError::StorageMessage(format!(
+            "Settings file '{}' is malformed: {}",
+            path.display(),
+            err
+        ))
+    })
+}
+
+/// Load the settings for `db_path`, setting aside a file that cannot be parsed.
+///
+/// A malformed file (for example one truncated by a crash on a filesystem
+/// without atomic rename) is renamed to `settings.json.corrupt-<UTC time>`
+/// and defaults are returned, so the GUI still starts and the next save
+/// writes a fresh file.
+///
+/// # Errors
+/// Returns an error when the file cannot be read, or cannot be moved aside.
+pub fn load_settings_or_quarantine(db_path: &str) -> Result<LoadedSettings, AppError> {
+    let path = settings_file_path_for_db_path(db_path);
+    let Some(raw) = read_settings_file(&path)? else {
+        return Ok(LoadedSettings::default());
+    };
+    let err = match parse_settings(&raw) {
+        Ok(settings) => {
+            return Ok(LoadedSettings {
+                settings,
+                quarantined: None,
+            })
+        }
+        Err(err) => err,
+    };
+    let mut quarantine_name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    quarantine_name.push(format!(
+        ".corrupt-{}",
+        chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ")
+    ));
+    let quarantine = path.with_file_name(quarantine_name);
+    fs::rename(&path, &quarantine).map_err(|rename_err| {
+        AppError::StorageMessage(format!(
+            "Settings file '{}' is malformed ({}) and could not be moved aside: {}",
+            path.display(),
+            err,
+            rename_err
+        ))
+    })?;
+    tracing::warn!(
+        "settings file {} is malformed ({}); moved to {} and using defaults",
+        path.display(),
+        err,
+        quarantine.display()
+    );
+    Ok(LoadedSettings {
+        settings: Settings::default(),
+        quarantined: Some(quarantine),
+    })
+}
+
+/// Atomically write `settings` for `db_path`, stamped with
+/// [`SETTINGS_SCHEMA_VERSION`].
+///
+/// # Errors
+/// Returns an error when the existing file was written by a newer schema
+/// version (saving would drop what this build does not understand), or when
+/// the settings cannot be encoded or written.
+pub fn save_settings(db_path: &str, settings: &Settings) -> Result<(), AppError> {
+    let path = settings_file_path_for_db_path(db_path);
+    let existing_version = fs::read(&path)
+        .ok()
+        .and_then(|raw| serde_json::from_slice::<Map<String, Value>>(&raw).ok())
+        .map(|document| document_version(&document));
+    if let Some(version) = existing_version.filter(|&v| v > SETTINGS_SCHEMA_VERSION) {
+        return Err(AppError::StorageMessage(format!(
+            "Settings file '{}' uses schema version {}, newer than this build's {}; not overwriting it",
+            path.display(),
+            version,
+            SETTINGS_SCHEMA_VERSION
+        )));
+    }
+    let encode_err = |err: serde_json::Error| {
+        AppError::StorageMessage(format!("Failed to encode settings: {}", err))
+    };
+    let mut document = serde_json::to_value(settings).map_err(encode_err)?;
+    if let Value::Object(sections) = &mut document {
+        sections.insert("version".to_string(), Value::from(SETTINGS_SCHEMA_VERSION));
+    }
+    let bytes = serde_json::to_vec_pretty(&document).map_err(encode_err)?;
+    write_file_atomic(&path, &bytes).map_err(|err| {
+        AppError::StorageMessage(format!(
+            "Failed to write settings '{}': {}",
+            path.display(),
+            err
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn settings_default_when_missing_and_round_trip() {
+        let dir = TempDir::new().expect("temp dir");
+        let db_path = dir.path().join("db");
+        let db_path = db_path.to_str().expect("db path");
+        assert_eq!(load_settings(db_path).expect("load"), Settings::default());
+
+        let mut settings = Settings::default();
+        settings.backup.remote_keep_last = Some(3);
+        settings.display.timezone = DisplayTimezone::Utc;
+        settings.updates.check_enabled = true;
+        settings.updates.dismissed_version = Some("0.5.0".to_string());
+        settings
+            .backup
+            .remote_targets
+            .push(RemoteBackupTarget::Command {
+                name: "rclone".to_string(),
+                upload: vec!["rclone".into(), "copyto".into(), "{path}".into()],
+                delete: None,
+            });
+        save_settings(db_path, &settings).expect("save");
+        assert_eq!(load_settings(db_path).expect("reload"), settings);
+
+        fs::write(settings_file_path_for_db_path(db_path), "{ not json").expect("corrupt");
+        let err = load_settings(db_path).expect_err("malformed settings must fail");
+        assert!(err.to_string().contains("malformed"), "{}", err);
+    }
+
+    #[test]
+    fn malformed_settings_are_quarantined_and_defaults_used() {
+        let dir = TempDir::new().expect("temp dir");
+        let db_path = dir.path().join("db");
+        let db_path = db_path.to_str().expect("db path");
+        let path = settings_file_path_for_db_path(db_path);
+        fs::create_dir_all(path.parent().expect("parent")).expect("dir");
+        fs::write(&path, r#"{"display": {"timezone": "ut"#).expect("truncated");
+
+        let loaded = load_settings_or_quarantine(db_path).expect("load");
+        assert_eq!(loaded.settings, Settings::default());
+        let quarantined = loaded.quarantined.expect("quarantined path");
+        assert!(!path.exists());
+        assert!(quarantined
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("name")
+            .starts_with("settings.json.corrupt-"));
+        assert_eq!(
+            fs::read_to_string(&quarantined).expect("kept"),
+            r#"{"display": {"timezone": "ut"#
+        );
+
+        let again = load_settings_or_quarantine(db_path).expect("reload");
+        assert_eq!(again, LoadedSettings::default());
+    }
+
+    #[test]
+    fn settings_carry_a_schema_version_and_migrate_older_files() {
+        let dir = TempDir::new().expect("temp dir");
+        let db_path = dir.path().join("db");
+        let db_path = db_path.to_str().expect("db path");
+        let path = settings_file_path_for_db_path(db_path);
+
+        save_settings(db_path, &Settings::default()).expect("save");
+        let written: Value = serde_json::from_slice(&fs::read(&path).expect("read")).expect("json");
+        assert_eq!(written["version"], Value::from(SETTINGS_SCHEMA_VERSION));
+
+        // Files written before the version key existed still load.
+        fs::write(&path, r#"{"display": {"timezone": "utc"}}"#).expect("v0 file");
+        let settings = load_settings(db_path).expect("load v0");
+        assert_eq!(settings.display.timezone, DisplayTimezone::Utc);
+
+        // A newer release's file loads what this build knows but is not overwritten.
+        let newer = format!(
+            r#"{{"version": {}, "display": {{"timezone": "utc"}}, "sync": {{}}}}"#,
+            SETTINGS_SCHEMA_VERSION + 1
+        );
+        fs::write(&path, &newer).expect("newer file");
+        assert_eq!(
+            load_settings(db_path).expect("load newer").display.timezone,
+            DisplayTimezone::Utc
+        );
+        let err = save_settings(db_path, &Settings::default()).expect_err("must not downgrade");
+        assert!(err.to_string().contains("newer"), "{}", err);
+        assert_eq!(fs::read_to_string(&path).expect("read"), newer);
+    }
+}
@@ -0,0 +1,279 @@
+//! Per-source and per-folder paste size limits layered over `MAX_PASTE_SIZE`.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use tracing::warn;
+
+const FOLDER_LIMITS_ENV_KEY: &str = "MAX_PASTE_SIZE_FOLDERS";
+
+/// Write path that produced paste content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteSource {
+    /// HTTP API writes (`POST /api/paste`, `PUT /api/paste/:id`).
+    Api,
+    /// Native GUI create/save paths.
+    Gui,
+    /// GUI paste-as-new captured from the system clipboard.
+    ClipboardCapture,
+    /// Bulk imports into an existing database.
+    Import,
+}
+
+impl PasteSource {
+    /// Every source, in the order limits are documented.
+    pub const ALL: [Self; 4] = [Self::Api, Self::Gui, Self::ClipboardCapture, Self::Import];
+
+    /// Environment variable holding this source's override.
+    ///
+    /// # Returns
+    /// The `MAX_PASTE_SIZE_*` key for this source.
+    pub fn env_key(self) -> &'static str {
+        match self {
+            Self::Api => "MAX_PASTE_SIZE_API",
+            Self::Gui => "MAX_PASTE_SIZE_GUI",
+            Self::ClipboardCapture => "MAX_PASTE_SIZE_CLIPBOARD",
+            Self::Import => "MAX_PASTE_SIZE_IMPORT",
+        }
+    }
+
+    /// Short lowercase name, matching the `x-localpaste-source` header values.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Api => "api",
+            Self::Gui => "gui",
+            Self::ClipboardCapture => "clipboard",
+            Self::Import => "import",
+        }
+    }
+}
+
+/// Optional size-limit overrides applied on top of the global `max_paste_size`.
+///
+/// A source override or folder override may raise or lower the global
+/// default. When both apply to the same write, the smaller one wins so a
+/// strict source (for example clipboard capture) stays strict inside a folder
+/// that allows large pastes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct PasteSizeLimits {
+    pub api: Option<usize>,
+    pub gui: Option<usize>,
+    pub clipboard_capture: Option<usize>,
+    pub import: Option<usize>,
+    pub folders: BTreeMap<String, usize>,
+}
+
+impl PasteSizeLimits {
+    /// Returns the configured override for `source`, if any.
+    pub fn source_limit(&self, source: PasteSource) -> Option<usize> {
+        match source {
+            PasteSource::Api => self.api,
+            PasteSource::Gui => self.gui,
+            PasteSource::ClipboardCapture => self.clipboard_capture,
+            PasteSource::Import => self.import,
+        }
+    }
+
+    /// Resolve the effective limit for a write.
+    ///
+    /// # Arguments
+    /// - `default_max`: Global `max_paste_size` used when no override applies.
+    /// - `source`: Write path producing the content.
+    /// - `folder_id`: Destination folder, when the paste is filed.
+    ///
+    /// # Returns
+    /// Maximum allowed content size in bytes.
+    pub fn resolve(
+        &self,
+        default_max: usize,
+        source: PasteSource,
+        folder_id: Option<&str>,
+    ) -> usize {
+        let source_limit = self.source_limit(source);
+        let folder_limit = folder_id.and_then(|id| self.folders.get(id).copied());
+        match (source_limit, folder_limit) {
+            (Some(source_limit), Some(folder_limit)) => source_limit.min(folder_limit),
+            (Some(limit), None) | (None, Some(limit)) => limit,
+            (None, None) => default_max,
+        }
+    }
+
+    /// Largest limit any write could be granted.
+    ///
+    /// Transport layers use this to size request-body caps so per-folder or
+    /// per-source raises are not rejected before handlers can validate them.
+    ///
+    /// # Returns
+    /// The maximum of `default_max` and every configured override.
+    pub fn largest(&self, default_max: usize) -> usize {
+        PasteSource::ALL
+            .iter()
+            .filter_map(|source| self.source_limit(*source))
+            .chain(self.folders.values().copied())
+            .fold(default_max, usize::max)
+    }
+
+    /// Load overrides from the environment, skipping malformed values.
+    ///
+    /// # Returns
+    /// Parsed overrides; invalid entries are logged and ignored.
+    pub fn from_env() -> Self {
+        let mut limits = Self::default();
+        for source in PasteSource::ALL {
+            let value = match read_limit_env(source.env_key()) {
+                Ok(value) => value,
+                Err(message) => {
+                    warn!("{}; ignoring override", message);
+                    None
+                }
+            };
+            limits.set_source_limit(source, value);
+        }
+        if let Ok(raw) = env::var(FOLDER_LIMITS_ENV_KEY) {
+            for entry in split_folder_entries(raw.as_str()) {
+                match parse_folder_entry(entry) {
+                    Ok((folder_id, limit)) => {
+                        limits.folders.insert(folder_id, limit);
+                    }
+                    Err(message) => warn!("{}; ignoring entry", message),
+                }
+            }
+        }
+        limits
+    }
+
+    /// Load overrides from the environment, rejecting malformed values.
+    ///
+    /// # Returns
+    /// Parsed overrides when every provided value is valid.
+    ///
+    /// # Errors
+    /// Returns a descriptive message for the first invalid override.
+    pub fn from_env_strict() -> Result<Self, String> {
+        let mut limits = Self::default();
+        for source in PasteSource::ALL {
+            let value = read_limit_env(source.env_key())?;
+            limits.set_source_limit(source, value);
+        }
+        if let Ok(raw) = env::var(FOLDER_LIMITS_ENV_KEY) {
+            for entry in split_folder_entries(raw.as_str()) {
+                let (folder_id, limit) = parse_folder_entry(entry)?;
+                limits.folders.insert(folder_id, limit);
+            }
+        }
+        Ok(limits)
+    }
+
+    fn set_source_limit(&mut self, source: PasteSource, value: Option<usize>) {
+        let slot = match source {
+            PasteSource::Api => &mut self.api,
+            PasteSource::Gui => &mut self.gui,
+            PasteSource::ClipboardCapture => &mut self.clipboard_capture,
+            PasteSource::Import => &mut self.import,
+        };
+        *slot = value;
+    }
+}
+
+fn parse_limit_value(name: &str, raw: &str) -> Result<usize, String> {
+    match raw.trim().parse::<usize>() {
+        Ok(value) if value >= 1 => Ok(value),
+        Ok(_) => Err(format!(
+            "Invalid value for {}='{}': expected integer >= 1",
+            name, raw
+        )),
+        Err(err) => Err(format!("Invalid value for {}='{}': {}", name, raw, err)),
+    }
+}
+
+fn read_limit_env(name: &str) -> Result<Option<usize>, String> {
+    let Ok(value) = env::var(name) else {
+        return Ok(None);
+    };
+    if value.trim().is_empty() {
+        return Ok(None);
+    }
+    parse_limit_value(name, value.as_str()).map(Some)
+}
+
+fn split_folder_entries(raw: &str) -> impl Iterator<Item = &str> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+}
+
+fn parse_folder_entry(entry: &str) -> Result<(String, usize), String> {
+    let Some((folder_id, limit)) = entry.split_once('=') else {
+        return Err(format!(
+            "Invalid {} entry '{}': expected <folder_id>=<bytes>",
+            FOLDER_LIMITS_ENV_KEY, entry
+        ));
+    };
+    let folder_id = folder_id.trim();
+    if folder_id.is_empty() {
+        return Err(format!(
+            "Invalid {} entry '{}': folder id is empty",
+            FOLDER_LIMITS_ENV_KEY, entry
+        ));
+    }
+    let name = format!("{}[{}]", FOLDER_LIMITS_ENV_KEY, folder_id);
+    let limit = parse_limit_value(name.as_str(), limit)?;
+    Ok((folder_id.to_string(), limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PasteSizeLimits, PasteSource};
+    use crate::env::{env_lock, EnvGuard};
+
+    #[test]
+    fn resolve_prefers_overrides_and_takes_the_stricter_of_source_and_folder() {
+        let mut limits = PasteSizeLimits {
+            clipboard_capture: Some(64),
+            ..PasteSizeLimits::default()
+        };
+        limits.folders.insert("logs".to_string(), 4096);
+
+        assert_eq!(limits.resolve(1024, PasteSource::Api, None), 1024);
+        assert_eq!(limits.resolve(1024, PasteSource::Api, Some("logs")), 4096);
+        assert_eq!(limits.resolve(1024, PasteSource::Api, Some("other")), 1024);
+        assert_eq!(
+            limits.resolve(1024, PasteSource::ClipboardCapture, None),
+            64
+        );
+        assert_eq!(
+            limits.resolve(1024, PasteSource::ClipboardCapture, Some("logs")),
+            64
+        );
+        assert_eq!(limits.largest(1024), 4096);
+    }
+
+    #[test]
+    fn env_loading_is_permissive_by_default_and_fails_fast_in_strict_mode() {
+        let _lock = env_lock().lock().expect("env lock");
+        let _api = EnvGuard::set("MAX_PASTE_SIZE_API", "2048");
+        let _gui = EnvGuard::remove("MAX_PASTE_SIZE_GUI");
+        let _clipboard = EnvGuard::set("MAX_PASTE_SIZE_CLIPBOARD", "0");
+        let _import = EnvGuard::remove("MAX_PASTE_SIZE_IMPORT");
+        let _folders = EnvGuard::set("MAX_PASTE_SIZE_FOLDERS", "logs=8192, bad, =5");
+
+        let limits = PasteSizeLimits::from_env();
+        assert_eq!(limits.api, Some(2048));
+        assert_eq!(limits.clipboard_capture, None);
+        assert_eq!(limits.folders.get("logs"), Some(&8192));
+        assert_eq!(limits.folders.len(), 1);
+
+        let err = PasteSizeLimits::from_env_strict().expect_err("zero limit should fail");
+        assert!(err.contains("MAX_PASTE_SIZE_CLIPBOARD"));
+
+        let _clipboard = EnvGuard::set("MAX_PASTE_SIZE_CLIPBOARD", "512");
+        let err = PasteSizeLimits::from_env_strict().expect_err("bad folder entry should fail");
+        assert!(err.contains("MAX_PASTE_SIZE_FOLDERS"));
+
+        let _folders = EnvGuard::set("MAX_PASTE_SIZE_FOLDERS", "logs=8192");
+        let limits = PasteSizeLimits::from_env_strict().expect("valid overrides");
+        assert_eq!(limits.clipboard_capture, Some(512));
+        assert_eq!(limits.folders.get("logs"), Some(&8192));
+    }
+}
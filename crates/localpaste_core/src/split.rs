@@ -0,0 +1,194 @@
+//! Detect pastes holding several concatenated files and split them apart.
+//!
+//! Two layouts are recognized: banner lines naming each file (`=== main.rs ===`,
+//! `==> main.rs <==` as printed by `head`/`tail`, or `--- main.rs ---`) and
+//! git patches with one `diff --git` header per file. Text before the first
+//! marker is not part of any file and is left out.
+
+use crate::detection::canonical::canonicalize;
+use crate::naming::files::language_extension;
+
+/// Fewest files a paste must contain before it is offered for splitting.
+const MIN_SPLIT_FILES: usize = 2;
+const DIFF_HEADER: &str = "diff --git ";
+
+/// One file recovered from a concatenated paste.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitFile {
+    /// File name or path taken from the marker.
+    pub name: String,
+    /// File body without its marker line (diff sections keep their header).
+    pub content: String,
+    /// Canonical language implied by the file extension, when recognized.
+    pub language: Option<String>,
+}
+
+fn banner_name(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    let inner = if let Some(rest) = trimmed.strip_prefix("==>") {
+        rest.strip_suffix("<==")?
+    } else {
+        ["===", "---"].iter().find_map(|fence| {
+            let rest = trimmed.strip_prefix(fence)?.strip_suffix(fence)?;
+            Some(rest.trim_matches(fence.chars().next().unwrap_or_default()))
+        })?
+    };
+    let name = inner.trim();
+    // File names in banners never contain whitespace; this keeps prose rules
+    // such as `--- see below ---` from being read as file markers.
+    (!name.is_empty() && !name.contains(char::is_whitespace)).then_some(name)
+}
+
+fn diff_name(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix(DIFF_HEADER)?;
+    let name = match rest.rfind(" b/") {
+        Some(idx) => &rest[idx + 3..],
+        None => rest,
+    };
+    let name = name.trim();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Canonical language for a file name, from its extension or well-known name.
+///
+/// # Returns
+/// `None` when the extension is unknown, leaving detection to decide.
+pub fn language_for_file_name(name: &str) -> Option<String> {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or(name);
+    let candidate = match base.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => ext,
+        _ => base,
+    };
+    let language = canonicalize(candidate);
+    (language_extension(Some(language.as_str())) != "txt").then_some(language)
+}
+
+fn trim_blank_lines(body: &str) -> String {
+    let start = body
+        .char_indices()
+        .find(|(_, ch)| !ch.is_whitespace())
+        .map_or(body.len(), |(idx, _)| {
+            body[..idx].rfind('\n').map_or(0, |nl| nl + 1)
+        });
+    let trimmed = body[start..].trim_end();
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", trimmed)
+    }
+}
+
+/// Split `content` into files when it looks like several concatenated files.
+///
+/// Diff headers take precedence over banners so patches that mention banner
+/// text in their hunks still split per file.
+///
+/// # Returns
+/// The detected files in order, or an empty list when fewer than two markers
+/// are found.
+pub fn split_concatenated_files(content: &str) -> Vec<SplitFile> {
+    let mut diff_starts = Vec::new();
+    let mut banner_starts = Vec::new();
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let bare = line.trim_end_matches(['\r', '\n']);
+        if let Some(name) = diff_name(bare) {
+            diff_starts.push((offset, offset, name));
+        } else if let Some(name) = banner_name(bare) {
+            banner_starts.push((offset, offset + line.len(), name));
+        }
+        offset += line.len();
+    }
+    let (starts, is_diff) = if diff_starts.len() >= MIN_SPLIT_FILES {
+        (diff_starts, true)
+    } else if banner_starts.len() >= MIN_SPLIT_FILES {
+        (banner_starts, false)
+    } else {
+        return Vec::new();
+    };
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(idx, (_, body_start, name))| {
+            let body_end = starts
+                .get(idx + 1)
+                .map_or(content.len(), |(next_marker, _, _)| *next_marker);
+            SplitFile {
+                name: (*name).to_string(),
+                content: trim_blank_lines(&content[*body_start..body_end]),
+                language: if is_diff {
+                    None
+                } else {
+                    language_for_file_name(name)
+                },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn banners_split_into_named_files_with_languages() {
+        let content = concat!(
+            "Here are the files:\n",
+            "=== src/main.rs ===\n",
+            "\n",
+            "fn main() {}\n",
+            "\n",
+            "==> Dockerfile <==\n",
+            "FROM alpine\n",
+            "--- notes.txt ---\n",
+            "--- see below ---\n",
+            "done\n",
+        );
+        let files = split_concatenated_files(content);
+        let summary: Vec<(&str, &str, Option<&str>)> = files
+            .iter()
+            .map(|file| {
+                (
+                    file.name.as_str(),
+                    file.content.as_str(),
+                    file.language.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/main.rs", "fn main() {}\n", Some("rust")),
+                ("Dockerfile", "FROM alpine\n", Some("dockerfile")),
+                ("notes.txt", "--- see below ---\ndone\n", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn git_diffs_split_per_file_and_single_markers_do_not() {
+        let patch = concat!(
+            "Subject: [PATCH] tweak\n",
+            "diff --git a/a.py b/a.py\n",
+            "--- a/a.py\n",
+            "+++ b/a.py\n",
+            "@@ -1 +1 @@\n",
+            "-x = 1\n",
+            "+x = 2\n",
+            "diff --git a/docs/b.md b/docs/b.md\n",
+            "+=== not a banner ===\n",
+        );
+        let files = split_concatenated_files(patch);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].name, "a.py");
+        assert!(files[0]
+            .content
+            .starts_with("diff --git a/a.py b/a.py\n--- a/a.py\n"));
+        assert_eq!(files[1].name, "docs/b.md");
+        assert_eq!(files[1].language, None);
+
+        assert!(split_concatenated_files("=== only.rs ===\nfn x() {}\n").is_empty());
+        assert!(split_concatenated_files("plain text\n").is_empty());
+    }
+}
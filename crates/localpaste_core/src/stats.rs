@@ -0,0 +1,296 @@
+//! Instance-wide paste statistics served by `GET /api/stats`.
+//!
+//! Built from stored metadata rows, so paste content is never loaded.
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::models::paste::PasteMeta;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Largest pastes listed when the request does not say.
+pub const DEFAULT_STATS_LARGEST: usize = 10;
+/// Cap on `?largest=`.
+pub const MAX_STATS_LARGEST: usize = 100;
+
+/// `GET /api/stats` query parameters.
+#[derive(Debug, Default, Deserialize)]
+pub struct StatsQuery {
+    /// Largest pastes to list; defaults to [`DEFAULT_STATS_LARGEST`] and is
+    /// capped at [`MAX_STATS_LARGEST`].
+    pub largest: Option<usize>,
+}
+
+impl StatsQuery {
+    /// Number of largest pastes to report.
+    pub fn largest(&self) -> usize {
+        self.largest
+            .unwrap_or(DEFAULT_STATS_LARGEST)
+            .min(MAX_STATS_LARGEST)
+    }
+}
+
+/// Pastes and content bytes for one language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageStats {
+    /// `None` for pastes without a detected or chosen language.
+    pub language: Option<String>,
+    pub pastes: usize,
+    pub bytes: u64,
+}
+
+/// Pastes and content bytes filed directly in one folder.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FolderStats {
+    /// `None` for unfiled pastes.
+    pub folder_id: Option<String>,
+    /// Folder name; `None` for unfiled pastes or a folder that no longer
+    /// exists.
+    pub name: Option<String>,
+    pub pastes: usize,
+    pub bytes: u64,
+}
+
+/// One entry in the largest-pastes list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LargestPaste {
+    pub id: String,
+    pub name: String,
+    pub language: Option<String>,
+    pub folder_id: Option<String>,
+    pub bytes: u64,
+}
+
+/// `GET /api/stats` response body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PasteStats {
+    pub total_pastes: usize,
+    pub total_bytes: u64,
+    /// Sorted by paste count, most first, then language.
+    pub languages: Vec<LanguageStats>,
+    /// Sorted by paste count, most first, then folder id.
+    pub folders: Vec<FolderStats>,
+    /// Sorted by size, largest first.
+    pub largest: Vec<LargestPaste>,
+}
+
+#[derive(Default)]
+struct Totals {
+    pastes: usize,
+    bytes: u64,
+}
+
+impl Totals {
+    fn add(&mut self, bytes: u64) {
+        self.pastes += 1;
+        self.bytes += bytes;
+    }
+}
+
+/// Accumulates [`PasteStats`] one metadata row at a time.
+struct StatsBuilder {
+    limit: usize,
+    total: Totals,
+    languages: HashMap<Option<String>, Totals>,
+    folders: HashMap<Option<String>, Totals>,
+    // Min-heap on size so the smallest kept paste is evicted first.
+    largest: BinaryHeap<Reverse<(u64, String)>>,
+    rows: HashMap<String, PasteMeta>,
+}
+
+impl StatsBuilder {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            total: Totals::default(),
+            languages: HashMap::new(),
+            folders: HashMap::new(),
+            largest: BinaryHeap::with_capacity(limit + 1),
+            rows: HashMap::with_capacity(limit + 1),
+        }
+    }
+
+    fn add(&mut self, meta: PasteMeta) {
+        let bytes = meta.content_len as u64;
+        self.total.add(bytes);
+        self.languages
+            .entry(meta.language.clone())
+            .or_default()
+            .add(bytes);
+        self.folders
+            .entry(meta.folder_id.clone())
+            .or_default()
+            .add(bytes);
+        if self.limit == 0 {
+            return;
+        }
+        self.largest.push(Reverse((bytes, meta.id.clone())));
+        self.rows.insert(meta.id.clone(), meta);
+        if self.largest.len() > self.limit {
+            if let Some(Reverse((_, id))) = self.largest.pop() {
+                self.rows.remove(&id);
+            }
+        }
+    }
+
+    fn finish(mut self, folder_names: &HashMap<String, String>) -> PasteStats {
+        let mut languages: Vec<_> = self
+            .languages
+            .into_iter()
+            .map(|(language, totals)| LanguageStats {
+                language,
+                pastes: totals.pastes,
+                bytes: totals.bytes,
+            })
+            .collect();
+        languages.sort_by(|a, b| {
+            b.pastes
+                .cmp(&a.pastes)
+                .then_with(|| a.language.cmp(&b.language))
+        });
+        let mut folders: Vec<_> = self
+            .folders
+            .into_iter()
+            .map(|(folder_id, totals)| FolderStats {
+                name: folder_id
+                    .as_ref()
+                    .and_then(|id| folder_names.get(id).cloned()),
+                folder_id,
+                pastes: totals.pastes,
+                bytes: totals.bytes,
+            })
+            .collect();
+        folders.sort_by(|a, b| {
+            b.pastes
+                .cmp(&a.pastes)
+                .then_with(|| a.folder_id.cmp(&b.folder_id))
+        });
+        let mut largest: Vec<_> = self
+            .largest
+            .into_iter()
+            .filter_map(|Reverse((bytes, id))| {
+                let meta = self.rows.remove(&id)?;
+                Some(LargestPaste {
+                    id: meta.id,
+                    name: meta.name,
+                    language: meta.language,
+                    folder_id: meta.folder_id,
+                    bytes,
+                })
+            })
+            .collect();
+        largest.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.id.cmp(&b.id)));
+        PasteStats {
+            total_pastes: self.total.pastes,
+            total_bytes: self.total.bytes,
+            languages,
+            folders,
+            largest,
+        }
+    }
+}
+
+/// Aggregate statistics over every paste `keep` accepts.
+///
+/// # Arguments
+/// - `db`: Open database handle.
+/// - `largest`: How many of the largest pastes to list.
+/// - `keep`: Row filter, e.g. a multi-user visibility check.
+///
+/// # Returns
+/// Totals, per-language and per-folder counts, and the largest pastes.
+///
+/// # Errors
+/// Returns an error when storage access fails or `keep` fails.
+pub fn collect_stats(
+    db: &Database,
+    largest: usize,
+    mut keep: impl FnMut(&PasteMeta) -> Result<bool, AppError>,
+) -> Result<PasteStats, AppError> {
+    let mut builder = StatsBuilder::new(largest);
+    db.pastes.scan_meta(|meta| {
+        if keep(&meta)? {
+            builder.add(meta);
+        }
+        Ok(())
+    })?;
+    let folder_names = db
+        .folders
+        .list()?
+        .into_iter()
+        .map(|folder| (folder.id, folder.name))
+        .collect();
+    Ok(builder.finish(&folder_names))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::TransactionOps;
+    use crate::models::folder::Folder;
+    use crate::models::paste::Paste;
+    use crate::test_support::setup_temp_db;
+
+    fn paste(content: &str, language: Option<&str>) -> Paste {
+        let mut paste = Paste::new(content.to_string(), format!("p{}", content.len()));
+        paste.language = language.map(ToString::to_string);
+        paste.language_is_manual = language.is_some();
+        paste
+    }
+
+    #[test]
+    fn collect_stats_groups_by_language_and_folder_and_ranks_largest() {
+        let (db, _temp) = setup_temp_db();
+        let folder = Folder::new("work".to_string());
+        db.folders.create(&folder).expect("folder");
+        let big = paste(&"x".repeat(40), Some("rust"));
+        TransactionOps::create_paste_with_folder(&db, &big, &folder.id).expect("big");
+        let medium = paste(&"y".repeat(20), Some("rust"));
+        db.pastes.create(&medium).expect("medium");
+        let small = paste("z", None);
+        db.pastes.create(&small).expect("small");
+
+        let stats = collect_stats(&db, 2, |_| Ok(true)).expect("stats");
+        assert_eq!(stats.total_pastes, 3);
+        assert_eq!(stats.total_bytes, 61);
+        assert_eq!(
+            stats.languages,
+            vec![
+                LanguageStats {
+                    language: Some("rust".to_string()),
+                    pastes: 2,
+                    bytes: 60,
+                },
+                LanguageStats {
+                    language: None,
+                    pastes: 1,
+                    bytes: 1,
+                },
+            ]
+        );
+        assert_eq!(
+            stats.folders,
+            vec![
+                FolderStats {
+                    folder_id: None,
+                    name: None,
+                    pastes: 2,
+                    bytes: 21,
+                },
+                FolderStats {
+                    folder_id: Some(folder.id.clone()),
+                    name: Some("work".to_string()),
+                    pastes: 1,
+                    bytes: 40,
+                },
+            ]
+        );
+        let largest: Vec<_> = stats.largest.iter().map(|row| row.id.as_str()).collect();
+        assert_eq!(largest, vec![big.id.as_str(), medium.id.as_str()]);
+
+        let filtered = collect_stats(&db, 0, |meta| Ok(meta.folder_id.is_none())).expect("stats");
+        assert_eq!(filtered.total_pastes, 2);
+        assert!(filtered.largest.is_empty());
+    }
+}
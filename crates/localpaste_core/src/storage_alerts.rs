@@ -0,0 +1,217 @@
+//! Size checks for the database file and the backups next to it.
+//!
+//! Each alert names the remediation that shrinks it: `cleanup` removes
+//! orphaned rows (the file itself only shrinks after offline compaction with
+//! `localpaste --cleanup`), and `prune_backups` deletes all but the newest
+//! [`DEFAULT_BACKUP_PRUNE_KEEP_LAST`] backups.
+
+use crate::config::StorageAlertLimits;
+use crate::db::backup::BackupManager;
+use crate::db::tables::REDB_FILE_NAME;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// How often the server and GUI re-check storage sizes.
+pub const STORAGE_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+/// Backups kept by the one-click prune remediation.
+pub const DEFAULT_BACKUP_PRUNE_KEEP_LAST: usize = 5;
+
+const MIB: u64 = 1024 * 1024;
+
+/// What grew past its limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageAlertKind {
+    DatabaseSize,
+    BackupsSize,
+}
+
+/// Action that frees space for an alert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageRemediation {
+    /// `POST /api/admin/cleanup`.
+    Cleanup,
+    /// `POST /api/admin/backups/prune`.
+    PruneBackups,
+}
+
+/// One size limit that was exceeded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageAlert {
+    pub kind: StorageAlertKind,
+    pub size_bytes: u64,
+    pub limit_bytes: u64,
+    pub message: String,
+    pub remediation: StorageRemediation,
+}
+
+impl StorageAlert {
+    fn new(kind: StorageAlertKind, size_bytes: u64, limit_bytes: u64) -> Self {
+        let (subject, remediation) = match kind {
+            StorageAlertKind::DatabaseSize => ("Database", StorageRemediation::Cleanup),
+            StorageAlertKind::BackupsSize => ("Backups", StorageRemediation::PruneBackups),
+        };
+        Self {
+            kind,
+            size_bytes,
+            limit_bytes,
+            message: format!(
+                "{} use {} MiB, above the {} MiB alert limit",
+                subject,
+                size_bytes.div_ceil(MIB),
+                limit_bytes.div_ceil(MIB)
+            ),
+            remediation,
+        }
+    }
+}
+
+/// Overall state reported by `GET /api/health`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Ok,
+    Warning,
+    /// The database or lock manager cannot serve requests.
+    Unavailable,
+}
+
+/// Storage part of the `GET /api/health` response body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageHealth {
+    pub status: HealthStatus,
+    pub alerts: Vec<StorageAlert>,
+}
+
+impl StorageHealth {
+    /// Wrap `alerts`, reporting a warning when there are any.
+    pub fn from_alerts(alerts: Vec<StorageAlert>) -> Self {
+        let status = if alerts.is_empty() {
+            HealthStatus::Ok
+        } else {
+            HealthStatus::Warning
+        };
+        Self { status, alerts }
+    }
+}
+
+/// `POST /api/admin/backups/prune` request body.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PruneBackupsRequest {
+    /// Newest backups to keep; defaults to [`DEFAULT_BACKUP_PRUNE_KEEP_LAST`].
+    pub keep_last: Option<usize>,
+}
+
+/// `POST /api/admin/backups/prune` response body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PruneBackupsResponse {
+    /// Paths of the deleted backups, newest first.
+    pub deleted: Vec<String>,
+}
+
+fn file_size(path: &Path) -> Result<u64, AppError> {
+    match std::fs::metadata(path) {
+        Ok(metadata) => Ok(metadata.len()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(0),
+        Err(err) => Err(AppError::StorageMessage(format!(
+            "Failed to stat '{}': {}",
+            path.display(),
+            err
+        ))),
+    }
+}
+
+/// Compare the database file and backup sizes under `db_path` to `limits`.
+///
+/// # Returns
+/// One alert per exceeded limit, database first; empty when within limits.
+///
+/// # Errors
+/// Returns an error when the database file or backup directory cannot be read.
+pub fn check_storage(
+    db_path: &str,
+    limits: StorageAlertLimits,
+) -> Result<Vec<StorageAlert>, AppError> {
+    let mut alerts = Vec::new();
+    if let Some(limit) = limits.database_bytes {
+        let size = file_size(&Path::new(db_path).join(REDB_FILE_NAME))?;
+        if size > limit {
+            alerts.push(StorageAlert::new(
+                StorageAlertKind::DatabaseSize,
+                size,
+                limit,
+            ));
+        }
+    }
+    if let Some(limit) = limits.backups_bytes {
+        let size = BackupManager::new(db_path)
+            .list_backups()?
+            .iter()
+            .map(|entry| entry.size)
+            .sum();
+        if size > limit {
+            alerts.push(StorageAlert::new(
+                StorageAlertKind::BackupsSize,
+                size,
+                limit,
+            ));
+        }
+    }
+    Ok(alerts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::open_test_database;
+    use tempfile::TempDir;
+
+    #[test]
+    fn check_storage_reports_each_exceeded_limit() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let db_path = temp_dir.path().join("db");
+        let db_path_str = db_path.to_str().expect("db path");
+        let db = open_test_database(db_path_str);
+        BackupManager::new(db_path_str)
+            .with_passphrase(None)
+            .create_backup(db.db.as_ref())
+            .expect("backup");
+
+        let unlimited = StorageAlertLimits {
+            database_bytes: None,
+            backups_bytes: None,
+        };
+        assert!(check_storage(db_path_str, unlimited)
+            .expect("check")
+            .is_empty());
+
+        let tiny = StorageAlertLimits {
+            database_bytes: Some(1),
+            backups_bytes: Some(1),
+        };
+        let alerts = check_storage(db_path_str, tiny).expect("check");
+        let kinds: Vec<_> = alerts
+            .iter()
+            .map(|alert| (alert.kind, alert.remediation))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (StorageAlertKind::DatabaseSize, StorageRemediation::Cleanup),
+                (
+                    StorageAlertKind::BackupsSize,
+                    StorageRemediation::PruneBackups
+                ),
+            ]
+        );
+        assert!(alerts.iter().all(|alert| alert.size_bytes > 1));
+        assert_eq!(
+            StorageHealth::from_alerts(alerts).status,
+            HealthStatus::Warning
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! Tag listing, renaming, and removal across all pastes.
+//!
+//! Tags are free text on each paste and match case-insensitively, the same
+//! way the `?tag=` list filter does. Renames and removals rewrite every
+//! affected paste in one transaction.
+
+use crate::{
+    db::TransactionOps,
+    models::paste::{normalize_tag_filter, PasteMeta},
+    AppError, Database,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// One tag and how many pastes carry it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TagCount {
+    /// Normalized (trimmed, lowercase) tag.
+    pub tag: String,
+    pub count: usize,
+}
+
+/// Every tag in use with its paste count.
+///
+/// # Returns
+/// Tags sorted by descending count, then name.
+///
+/// # Errors
+/// Returns an error when the tag index cannot be read.
+pub fn tag_counts(db: &Database) -> Result<Vec<TagCount>, AppError> {
+    Ok(db
+        .pastes
+        .tag_counts()?
+        .into_iter()
+        .map(|(tag, count)| TagCount { tag, count })
+        .collect())
+}
+
+/// Tag counts over the pastes `keep` accepts, for callers that scope rows.
+///
+/// Scans metadata rather than the tag index, so prefer [`tag_counts`] when
+/// every paste counts.
+///
+/// # Returns
+/// Tags sorted by descending count, then name.
+///
+/// # Errors
+/// Returns an error when metadata cannot be read or `keep` fails.
+pub fn tag_counts_where<F>(db: &Database, mut keep: F) -> Result<Vec<TagCount>, AppError>
+where
+    F: FnMut(&PasteMeta) -> Result<bool, AppError>,
+{
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    db.pastes.scan_meta(|meta| {
+        if meta.tags.is_empty() || !keep(&meta)? {
+            return Ok(());
+        }
+        let tags: BTreeSet<String> = meta
+            .tags
+            .iter()
+            .filter_map(|tag| normalize_tag_filter(Some(tag)))
+            .collect();
+        for tag in tags {
+            *counts.entry(tag).or_default() += 1;
+        }
+        Ok(())
+    })?;
+    let mut counts: Vec<TagCount> = counts
+        .into_iter()
+        .map(|(tag, count)| TagCount { tag, count })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+    Ok(counts)
+}
+
+/// Rename `from` to `to` on every paste carrying it.
+///
+/// # Returns
+/// Ids of the pastes that changed.
+///
+/// # Errors
+/// Returns `BadRequest` when either name is blank, `NotFound` when no paste
+/// carries `from`, or any error from `acquire_guard` / storage.
+pub fn rename_tag_guarded<G, F>(
+    db: &Database,
+    from: &str,
+    to: &str,
+    acquire_guard: F,
+) -> Result<Vec<String>, AppError>
+where
+    F: FnOnce(&[String]) -> Result<G, AppError>,
+{
+    if normalize_tag_filter(Some(to)).is_none() {
+        return Err(AppError::BadRequest("Tag name cannot be empty".to_string()));
+    }
+    rewrite_tag_guarded(db, from, Some(to), acquire_guard)
+}
+
+/// Remove `tag` from every paste carrying it.
+///
+/// # Returns
+/// Ids of the pastes that changed.
+///
+/// # Errors
+/// Returns `BadRequest` when `tag` is blank, `NotFound` when no paste
+/// carries it, or any error from `acquire_guard` / storage.
+pub fn delete_tag_guarded<G, F>(
+    db: &Database,
+    tag: &str,
+    acquire_guard: F,
+) -> Result<Vec<String>, AppError>
+where
+    F: FnOnce(&[String]) -> Result<G, AppError>,
+{
+    rewrite_tag_guarded(db, tag, None, acquire_guard)
+}
+
+fn rewrite_tag_guarded<G, F>(
+    db: &Database,
+    tag: &str,
+    replacement: Option<&str>,
+    acquire_guard: F,
+) -> Result<Vec<String>, AppError>
+where
+    F: FnOnce(&[String]) -> Result<G, AppError>,
+{
+    if normalize_tag_filter(Some(tag)).is_none() {
+        return Err(AppError::BadRequest("Tag name cannot be empty".to_string()));
+    }
+    let folder_guard = TransactionOps::acquire_folder_txn_guard(db)?;
+    let affected_paste_ids = db.pastes.ids_with_tag(tag)?;
+    if affected_paste_ids.is_empty() {
+        return Err(AppError::NotFound);
+    }
+    let _external_guard = acquire_guard(&affected_paste_ids)?;
+    TransactionOps::rewrite_tag_locked(db, &folder_guard, tag, replacement)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::paste::Paste;
+    use crate::test_support::setup_temp_db;
+
+    fn tagged(db: &Database, name: &str, tags: &[&str]) -> String {
+        let mut paste = Paste::new(format!("{} body", name), name.to_string());
+        paste.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        db.pastes.create(&paste).expect("create");
+        paste.id
+    }
+
+    fn tags_of(db: &Database, id: &str) -> Vec<String> {
+        db.pastes.get(id).expect("get").expect("paste").tags
+    }
+
+    #[test]
+    fn tags_are_counted_renamed_and_removed_case_insensitively() {
+        let (db, _temp) = setup_temp_db();
+        let first = tagged(&db, "first", &["Rust", "cli"]);
+        let second = tagged(&db, "second", &["rust", "Tooling"]);
+        let untouched = tagged(&db, "third", &["notes"]);
+        let before = db
+            .pastes
+            .get(&untouched)
+            .expect("get")
+            .expect("paste")
+            .updated_at;
+
+        let counts = tag_counts(&db).expect("counts");
+        assert_eq!(
+            counts[0],
+            TagCount {
+                tag: "rust".to_string(),
+                count: 2
+            }
+        );
+        assert_eq!(counts.len(), 4);
+        assert_eq!(tag_counts_where(&db, |_| Ok(true)).expect("scan"), counts);
+        assert_eq!(
+            tag_counts_where(&db, |meta| Ok(meta.id == untouched)).expect("scan"),
+            vec![TagCount {
+                tag: "notes".to_string(),
+                count: 1
+            }]
+        );
+
+        let mut renamed = rename_tag_guarded(&db, "RUST", "Tooling", |_| Ok(())).expect("rename");
+        renamed.sort();
+        let mut expected = vec![first.clone(), second.clone()];
+        expected.sort();
+        assert_eq!(renamed, expected);
+        assert_eq!(tags_of(&db, &first), vec!["Tooling", "cli"]);
+        assert_eq!(tags_of(&db, &second), vec!["Tooling"]);
+        assert_eq!(
+            db.pastes.ids_with_tag("rust").expect("ids"),
+            Vec::<String>::new()
+        );
+        assert_eq!(db.pastes.ids_with_tag("tooling").expect("ids").len(), 2);
+
+        let removed = delete_tag_guarded(&db, " cli ", |_| Ok(())).expect("delete");
+        assert_eq!(removed, vec![first.clone()]);
+        assert_eq!(tags_of(&db, &first), vec!["Tooling"]);
+        let after = db.pastes.get(&untouched).expect("get").expect("paste");
+        assert_eq!(after.updated_at, before);
+
+        assert!(matches!(
+            delete_tag_guarded(&db, "cli", |_| Ok(())),
+            Err(AppError::NotFound)
+        ));
+        assert!(matches!(
+            rename_tag_guarded(&db, "tooling", "  ", |_| Ok(())),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(matches!(
+            rename_tag_guarded(&db, "tooling", "x", |_| Err::<(), _>(AppError::Locked(
+                "busy".to_string()
+            ))),
+            Err(AppError::Locked(_))
+        ));
+        assert_eq!(db.pastes.ids_with_tag("tooling").expect("ids").len(), 2);
+    }
+}
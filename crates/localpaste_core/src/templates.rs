@@ -0,0 +1,165 @@
+//! `{{variable}}` template expansion shared by paste names and snippet bodies.
+//!
+//! Templates substitute `{{name}}` placeholders from a [`TemplateVars`] set.
+//! Whitespace inside the braces is ignored, `{{name|fallback}}` supplies a
+//! literal used when the variable is missing or empty, and `{{env.NAME}}`
+//! reads the process environment. Unknown variables without a fallback are
+//! an error rather than being left in the output.
+
+use crate::error::AppError;
+use chrono::{DateTime, Local};
+use std::collections::BTreeMap;
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+const ENV_PREFIX: &str = "env.";
+
+/// Named values available to a template.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateVars {
+    values: BTreeMap<String, String>,
+}
+
+impl TemplateVars {
+    /// Variables for the clock reading `now`.
+    ///
+    /// Provides `date` (`2026-03-10`), `time` (`14-05`), `datetime`
+    /// (`2026-03-10_14-05`), `year`, `month`, `day`, and `timestamp` (Unix
+    /// seconds). Times avoid `:` so expansions stay valid in file names.
+    pub fn with_clock(now: DateTime<Local>) -> Self {
+        let mut vars = Self::default();
+        for (name, pattern) in [
+            ("date", "%Y-%m-%d"),
+            ("time", "%H-%M"),
+            ("datetime", "%Y-%m-%d_%H-%M"),
+            ("year", "%Y"),
+            ("month", "%m"),
+            ("day", "%d"),
+        ] {
+            vars.insert(name, now.format(pattern).to_string());
+        }
+        vars.insert("timestamp", now.timestamp().to_string());
+        vars
+    }
+
+    /// Clock variables for the current local time; see [`Self::with_clock`].
+    pub fn now() -> Self {
+        Self::with_clock(Local::now())
+    }
+
+    /// Set `name` to `value`, replacing any earlier value.
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.values.insert(name.into(), value.into());
+    }
+
+    /// Look up `name`, reading `env.NAME` from the process environment.
+    pub fn get(&self, name: &str) -> Option<String> {
+        if let Some(key) = name.strip_prefix(ENV_PREFIX) {
+            return std::env::var(key).ok();
+        }
+        self.values.get(name).cloned()
+    }
+
+    /// Names of the stored (non-environment) variables, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+}
+
+/// Return `true` when `template` contains a `{{` placeholder opener.
+pub fn has_placeholders(template: &str) -> bool {
+    template.contains(OPEN)
+}
+
+/// Expand every placeholder in `template`.
+///
+/// # Returns
+/// The expanded string; text without placeholders is returned unchanged.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] for an unterminated `{{`, an empty
+/// placeholder, or an unknown variable without a fallback.
+pub fn render_template(template: &str, vars: &TemplateVars) -> Result<String, AppError> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find(OPEN) {
+        out.push_str(&rest[..start]);
+        let after_open = &rest[start + OPEN.len()..];
+        let end = after_open.find(CLOSE).ok_or_else(|| {
+            AppError::BadRequest(format!("Unterminated '{{{{' in template '{}'", template))
+        })?;
+        let inner = &after_open[..end];
+        let (name, fallback) = match inner.split_once('|') {
+            Some((name, fallback)) => (name.trim(), Some(fallback.trim())),
+            None => (inner.trim(), None),
+        };
+        if name.is_empty() {
+            return Err(AppError::BadRequest(format!(
+                "Empty placeholder in template '{}'",
+                template
+            )));
+        }
+        let value = vars.get(name).filter(|value| !value.is_empty());
+        match (value, fallback) {
+            (Some(value), _) => out.push_str(value.as_str()),
+            (None, Some(fallback)) => out.push_str(fallback),
+            (None, None) => {
+                let known: Vec<&str> = vars.names().collect();
+                return Err(AppError::BadRequest(format!(
+                    "Unknown template variable '{}' (available: {}, env.NAME)",
+                    name,
+                    known.join(", ")
+                )));
+            }
+        }
+        rest = &after_open[end + CLOSE.len()..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn vars() -> TemplateVars {
+        let now = Local.with_ymd_and_hms(2026, 3, 10, 14, 5, 0).unwrap();
+        let mut vars = TemplateVars::with_clock(now);
+        vars.insert("git_branch", "main");
+        vars.insert("hostname", "");
+        vars
+    }
+
+    #[test]
+    fn render_expands_variables_fallbacks_and_env() {
+        let vars = vars();
+        assert_eq!(
+            render_template("{{git_branch}}-{{ date }}", &vars).unwrap(),
+            "main-2026-03-10"
+        );
+        assert_eq!(
+            render_template("{{datetime}} on {{hostname|unknown}}", &vars).unwrap(),
+            "2026-03-10_14-05 on unknown"
+        );
+        assert_eq!(
+            render_template("{{env.LOCALPASTE_TEMPLATE_TEST_UNSET|none}}", &vars).unwrap(),
+            "none"
+        );
+        assert_eq!(render_template("plain } {", &vars).unwrap(), "plain } {");
+        assert!(!has_placeholders("plain"));
+    }
+
+    #[test]
+    fn render_rejects_unknown_empty_and_unterminated_placeholders() {
+        let vars = vars();
+        let err = render_template("{{branch}}", &vars)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'branch'"), "{}", err);
+        assert!(err.contains("git_branch"), "{}", err);
+        assert!(render_template("{{ }}", &vars).is_err());
+        assert!(render_template("{{date", &vars).is_err());
+        assert!(render_template("{{hostname}}", &vars).is_err());
+    }
+}
@@ -0,0 +1,461 @@
+//! Cleanup transforms for text pasted from the clipboard or the web.
+//!
+//! Each transform is off by default and toggled by name: `urls` strips
+//! tracking query parameters, `quotes` converts typographic quotes to ASCII,
+//! `whitespace` replaces invisible and non-breaking spaces, trims line ends,
+//! and collapses blank-line runs, and `unwrap` joins hard-wrapped email
+//! paragraphs. Clipboard captures use `LOCALPASTE_CLIPBOARD_CLEANUP`; API
+//! clients can request transforms per call.
+
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use tracing::warn;
+
+const CLIPBOARD_CLEANUP_ENV_KEY: &str = "LOCALPASTE_CLIPBOARD_CLEANUP";
+/// Query parameters treated as tracking noise, matched case-insensitively.
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid",
+    "gclid",
+    "dclid",
+    "gbraid",
+    "wbraid",
+    "msclkid",
+    "yclid",
+    "igshid",
+    "mc_cid",
+    "mc_eid",
+    "_hsenc",
+    "_hsmi",
+    "mkt_tok",
+    "vero_id",
+    "oly_anon_id",
+    "oly_enc_id",
+];
+/// Query parameter prefix used by campaign tracking (`utm_source`, ...).
+const TRACKING_PARAM_PREFIX: &str = "utm_";
+/// Lines at least this long are assumed to be hard-wrapped when unwrapping.
+const WRAP_MIN_WIDTH: usize = 50;
+
+/// Transforms applied to pasted text before it is stored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CleanupOptions {
+    /// Remove `utm_*`, `fbclid`, and similar tracking parameters from URLs.
+    pub strip_tracking_params: bool,
+    /// Convert curly single and double quotes to `'` and `"`.
+    pub straighten_quotes: bool,
+    /// Normalize invisible spaces, trailing whitespace, and blank-line runs.
+    pub normalize_whitespace: bool,
+    /// Join hard-wrapped paragraphs, including `>` quoted email replies.
+    pub unwrap_lines: bool,
+}
+
+impl CleanupOptions {
+    /// Every transform enabled.
+    pub const ALL: Self = Self {
+        strip_tracking_params: true,
+        straighten_quotes: true,
+        normalize_whitespace: true,
+        unwrap_lines: true,
+    };
+
+    /// Return `true` when no transform is enabled.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Load the clipboard-capture transforms, falling back to none on bad values.
+    ///
+    /// # Returns
+    /// Parsed options; an invalid list is logged and ignored.
+    pub fn clipboard_from_env() -> Self {
+        Self::clipboard_from_env_strict().unwrap_or_else(|message| {
+            warn!("{}; clipboard cleanup disabled", message);
+            Self::default()
+        })
+    }
+
+    /// Load the clipboard-capture transforms, rejecting unknown names.
+    ///
+    /// # Errors
+    /// Returns a descriptive message when the list contains an unknown name.
+    pub fn clipboard_from_env_strict() -> Result<Self, String> {
+        let Ok(value) = env::var(CLIPBOARD_CLEANUP_ENV_KEY) else {
+            return Ok(Self::default());
+        };
+        value
+            .parse()
+            .map_err(|err| format!("Invalid value for {}: {}", CLIPBOARD_CLEANUP_ENV_KEY, err))
+    }
+
+    /// Apply the enabled transforms to `content`.
+    ///
+    /// Transforms run in a fixed order: quotes, unwrapping, whitespace, URLs.
+    ///
+    /// # Returns
+    /// The cleaned text; `content` is returned untouched when nothing is enabled.
+    pub fn apply(&self, content: String) -> String {
+        if self.is_empty() {
+            return content;
+        }
+        let mut text = content;
+        if self.straighten_quotes {
+            text = straighten_quotes(text.as_str());
+        }
+        if self.unwrap_lines {
+            text = unwrap_hard_wrapped(text.as_str());
+        }
+        if self.normalize_whitespace {
+            text = normalize_whitespace(text.as_str());
+        }
+        if self.strip_tracking_params {
+            text = strip_tracking_params(text.as_str());
+        }
+        text
+    }
+}
+
+impl FromStr for CleanupOptions {
+    type Err = String;
+
+    /// Parse a comma-separated list such as `urls,quotes`.
+    ///
+    /// `all` enables every transform; `none` and an empty list enable none.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut options = Self::default();
+        for name in value
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            match name.to_ascii_lowercase().as_str() {
+                "urls" => options.strip_tracking_params = true,
+                "quotes" => options.straighten_quotes = true,
+                "whitespace" => options.normalize_whitespace = true,
+                "unwrap" => options.unwrap_lines = true,
+                "all" => options = Self::ALL,
+                "none" => {}
+                other => {
+                    return Err(format!(
+                        "unknown cleanup '{}' (expected urls, quotes, whitespace, unwrap, all, or none)",
+                        other
+                    ))
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+impl fmt::Display for CleanupOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = [
+            (self.strip_tracking_params, "urls"),
+            (self.straighten_quotes, "quotes"),
+            (self.normalize_whitespace, "whitespace"),
+            (self.unwrap_lines, "unwrap"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, name)| enabled.then_some(name))
+        .collect();
+        if names.is_empty() {
+            f.write_str("none")
+        } else {
+            f.write_str(names.join(",").as_str())
+        }
+    }
+}
+
+/// Convert typographic quotes and primes to their ASCII forms.
+pub fn straighten_quotes(text: &str) -> String {
+    text.chars()
+        .map(|ch| match ch {
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => '\'',
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' => '"',
+            other => other,
+        })
+        .collect()
+}
+
+/// Normalize whitespace left behind by rich-text sources.
+///
+/// Non-breaking and other Unicode spaces become plain spaces, zero-width
+/// characters are removed, trailing whitespace is trimmed from every line,
+/// runs of blank lines collapse to one, and leading and trailing blank lines
+/// are dropped. `\r\n` line endings are preserved.
+pub fn normalize_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut blank_run = 0usize;
+    // Line terminator of the last written line, reused for the separator.
+    let mut newline: Option<&str> = None;
+    for raw_line in text.split('\n') {
+        let (line, crlf) = match raw_line.strip_suffix('\r') {
+            Some(line) => (line, true),
+            None => (raw_line, false),
+        };
+        let cleaned: String = line
+            .chars()
+            .filter(|ch| {
+                !matches!(
+                    ch,
+                    '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}'
+                )
+            })
+            .map(|ch| match ch {
+                '\u{00A0}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => ' ',
+                other => other,
+            })
+            .collect();
+        let cleaned = cleaned.trim_end();
+        if cleaned.is_empty() {
+            blank_run += 1;
+            continue;
+        }
+        if let Some(newline) = newline {
+            out.push_str(newline);
+            if blank_run > 0 {
+                out.push_str(newline);
+            }
+        }
+        blank_run = 0;
+        out.push_str(cleaned);
+        newline = Some(if crlf { "\r\n" } else { "\n" });
+    }
+    if let Some(newline) = newline {
+        if text.ends_with('\n') {
+            out.push_str(newline);
+        }
+    }
+    out
+}
+
+fn quote_prefix(line: &str) -> &str {
+    let body_start = line
+        .char_indices()
+        .find(|(_, ch)| !matches!(ch, '>' | ' '))
+        .map_or(line.len(), |(idx, _)| idx);
+    let prefix = &line[..body_start];
+    if prefix.contains('>') {
+        prefix
+    } else {
+        ""
+    }
+}
+
+fn starts_structural_line(body: &str) -> bool {
+    let trimmed = body.trim_start();
+    if body.starts_with([' ', '\t']) && body.len() - trimmed.len() >= 4 {
+        return true;
+    }
+    if trimmed.starts_with(['-', '*', '+', '#', '|', '`']) {
+        return true;
+    }
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    digits > 0 && trimmed[digits..].starts_with(['.', ')'])
+}
+
+/// Join hard-wrapped lines back into paragraphs.
+///
+/// A line is joined with the next when both carry the same `>` quote prefix,
+/// the next line does not start a list, heading, table, or indented block, and
+/// the line is either long enough to have been wrapped or ends with a space
+/// (the `format=flowed` soft-break marker).
+pub fn unwrap_hard_wrapped(text: &str) -> String {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let mut out = String::with_capacity(text.len());
+    let mut idx = 0;
+    while idx < lines.len() {
+        let (first, mut crlf) = match lines[idx].strip_suffix('\r') {
+            Some(line) => (line, true),
+            None => (lines[idx], false),
+        };
+        let prefix = quote_prefix(first);
+        let mut current = first.to_string();
+        while idx + 1 < lines.len() {
+            let next = lines[idx + 1].trim_end_matches('\r');
+            let next_body = &next[quote_prefix(next).len()..];
+            let current_body = &current[prefix.len()..];
+            let soft_break = current_body.ends_with(' ');
+            let wrapped = current_body.trim_end().chars().count() >= WRAP_MIN_WIDTH;
+            if quote_prefix(next) != prefix
+                || next_body.trim().is_empty()
+                || current_body.trim().is_empty()
+                || starts_structural_line(next_body)
+                || !(soft_break || wrapped)
+            {
+                break;
+            }
+            let trimmed_len = current.trim_end().len();
+            current.truncate(trimmed_len);
+            current.push(' ');
+            current.push_str(next_body.trim_start());
+            crlf = lines[idx + 1].ends_with('\r');
+            idx += 1;
+        }
+        out.push_str(current.as_str());
+        idx += 1;
+        if idx < lines.len() {
+            out.push_str(if crlf { "\r\n" } else { "\n" });
+        }
+    }
+    out
+}
+
+fn is_tracking_param(pair: &str) -> bool {
+    let key = pair.split_once('=').map_or(pair, |(key, _)| key);
+    let key = key.to_ascii_lowercase();
+    key.starts_with(TRACKING_PARAM_PREFIX) || TRACKING_PARAMS.contains(&key.as_str())
+}
+
+fn clean_url(url: &str) -> String {
+    let (before_fragment, fragment) = match url.split_once('#') {
+        Some((head, fragment)) => (head, Some(fragment)),
+        None => (url, None),
+    };
+    let Some((base, query)) = before_fragment.split_once('?') else {
+        return url.to_string();
+    };
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty() && !is_tracking_param(pair))
+        .collect();
+    let mut cleaned = base.to_string();
+    if !kept.is_empty() {
+        cleaned.push('?');
+        cleaned.push_str(kept.join("&").as_str());
+    }
+    if let Some(fragment) = fragment {
+        cleaned.push('#');
+        cleaned.push_str(fragment);
+    }
+    cleaned
+}
+
+fn url_end(rest: &str) -> usize {
+    let mut end = rest
+        .find(|ch: char| ch.is_whitespace() || matches!(ch, '<' | '>' | '"' | '\'' | '`'))
+        .unwrap_or(rest.len());
+    // Sentence punctuation and unbalanced closing brackets after a URL belong
+    // to the surrounding text.
+    loop {
+        let candidate = &rest[..end];
+        let Some(last) = candidate.chars().last() else {
+            break;
+        };
+        let unbalanced = match last {
+            ')' => candidate.matches('(').count() < candidate.matches(')').count(),
+            ']' => candidate.matches('[').count() < candidate.matches(']').count(),
+            '.' | ',' | ';' | ':' | '!' | '?' => true,
+            _ => false,
+        };
+        if !unbalanced {
+            break;
+        }
+        end -= last.len_utf8();
+    }
+    end
+}
+
+/// Remove tracking query parameters from every `http(s)://` URL in `text`.
+///
+/// Other parameters and fragments are kept in order; the `?` is dropped when
+/// no parameters remain.
+pub fn strip_tracking_params(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = ["https://", "http://"]
+        .iter()
+        .filter_map(|scheme| rest.find(scheme))
+        .min()
+    {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let end = url_end(tail);
+        out.push_str(clean_url(&tail[..end]).as_str());
+        rest = &tail[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_parse_display_and_apply_nothing_by_default() {
+        let options: CleanupOptions = " URLs , quotes ".parse().unwrap();
+        assert!(options.strip_tracking_params && options.straighten_quotes);
+        assert!(!options.normalize_whitespace && !options.unwrap_lines);
+        assert_eq!(options.to_string(), "urls,quotes");
+        assert_eq!(
+            "all".parse::<CleanupOptions>().unwrap(),
+            CleanupOptions::ALL
+        );
+        assert!("none".parse::<CleanupOptions>().unwrap().is_empty());
+        assert!("urls,emoji".parse::<CleanupOptions>().is_err());
+
+        let text = "“quoted”\u{00A0}https://x.test/?utm_source=a".to_string();
+        assert_eq!(CleanupOptions::default().apply(text.clone()), text);
+        assert_eq!(
+            CleanupOptions::ALL.apply(text),
+            "\"quoted\" https://x.test/"
+        );
+    }
+
+    #[test]
+    fn tracking_params_are_removed_and_surrounding_text_kept() {
+        assert_eq!(
+            strip_tracking_params(
+                "see https://example.com/a?id=7&utm_source=news&FBCLID=x#top, and (http://b.test/?gclid=1)."
+            ),
+            "see https://example.com/a?id=7#top, and (http://b.test/)."
+        );
+        assert_eq!(
+            strip_tracking_params("https://en.wikipedia.org/wiki/Rust_(language)?utm_medium=x"),
+            "https://en.wikipedia.org/wiki/Rust_(language)"
+        );
+        assert_eq!(strip_tracking_params("no links here"), "no links here");
+    }
+
+    #[test]
+    fn quotes_and_whitespace_are_normalized() {
+        assert_eq!(straighten_quotes("‘it’s’ “done” 5′"), "'it's' \"done\" 5'");
+        assert_eq!(
+            normalize_whitespace(
+                "\n\nfirst\u{00A0}line  \r\nzero\u{200B}width\r\n\r\n\r\n\r\nlast\t\n\n"
+            ),
+            "first line\r\nzerowidth\r\n\r\nlast\n"
+        );
+    }
+
+    #[test]
+    fn unwrap_joins_wrapped_paragraphs_but_keeps_structure() {
+        let email = concat!(
+            "Hi team, the deploy is scheduled for Thursday afternoon once the\n",
+            "migration has finished.\n",
+            "\n",
+            "> We should double check the rollback plan before we go ahead and\n",
+            "> flip the switch.\n",
+            "Steps:\n",
+            "- drain traffic\n",
+            "- migrate\n",
+            "Thanks,\n",
+            "Sam"
+        );
+        assert_eq!(
+            unwrap_hard_wrapped(email),
+            concat!(
+                "Hi team, the deploy is scheduled for Thursday afternoon once the migration has finished.\n",
+                "\n",
+                "> We should double check the rollback plan before we go ahead and flip the switch.\n",
+                "Steps:\n",
+                "- drain traffic\n",
+                "- migrate\n",
+                "Thanks,\n",
+                "Sam"
+            )
+        );
+        assert_eq!(unwrap_hard_wrapped("soft \nbreak\r\n"), "soft break\r\n");
+    }
+}
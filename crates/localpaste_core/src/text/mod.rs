@@ -0,0 +1,230 @@
+//! Shared text and host normalization helpers.
+
+/// Optional cleanup transforms for pasted clipboard and web text.
+pub mod cleanup;
+
+pub use cleanup::CleanupOptions;
+
+use std::net::IpAddr;
+use std::ops::Range;
+
+/// Trim an optional string and drop empty values.
+///
+/// # Returns
+/// `None` when the input is missing or whitespace-only; otherwise the trimmed
+/// string.
+pub fn normalize_optional_nonempty(value: Option<String>) -> Option<String> {
+    value.and_then(|raw| {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            None
+        } else {
+            Some(trimmed.to_string())
+        }
+    })
+}
+
+/// Return `true` when `host` is localhost or a loopback IP literal.
+///
+/// Supports bracketed IPv6 hosts (for example `[::1]`).
+///
+/// # Returns
+/// `true` when `host` resolves to loopback identity (`localhost`, `127.0.0.0/8`,
+/// or `::1`), otherwise `false`.
+pub fn is_loopback_host(host: &str) -> bool {
+    if host.eq_ignore_ascii_case("localhost") {
+        return true;
+    }
+    let normalized_host = host
+        .strip_prefix('[')
+        .and_then(|value| value.strip_suffix(']'))
+        .unwrap_or(host);
+    normalized_host
+        .parse::<IpAddr>()
+        .map(|ip| ip.is_loopback())
+        .unwrap_or(false)
+}
+
+/// Return the leading lines of `content`, capped by line and character count.
+///
+/// # Arguments
+/// - `content`: Full text to preview.
+/// - `max_lines`: Maximum number of lines to keep.
+/// - `max_chars`: Maximum number of characters to keep after line truncation.
+///
+/// # Returns
+/// `(snippet, truncated)` where `truncated` is `true` when any content was
+/// dropped. Line endings inside the snippet are preserved as-is.
+pub fn leading_lines_preview(content: &str, max_lines: usize, max_chars: usize) -> (String, bool) {
+    let mut end = content.len();
+    let mut lines_seen = 0usize;
+    for (idx, _) in content.match_indices('\n') {
+        lines_seen += 1;
+        if lines_seen >= max_lines {
+            end = idx;
+            break;
+        }
+    }
+    if max_lines == 0 {
+        end = 0;
+    }
+    let by_lines = &content[..end];
+    let snippet = match by_lines.char_indices().nth(max_chars) {
+        Some((byte_idx, _)) => &by_lines[..byte_idx],
+        None => by_lines,
+    };
+    let snippet = snippet.trim_end_matches(['\r', '\n']);
+    let truncated = snippet.len() < content.trim_end_matches(['\r', '\n']).len();
+    (snippet.to_string(), truncated)
+}
+
+/// Collapse all whitespace runs (including newlines) to single spaces and cap length.
+///
+/// # Arguments
+/// - `content`: Full text to summarize.
+/// - `max_chars`: Maximum number of characters to keep.
+///
+/// # Returns
+/// Single-line snippet with leading/trailing whitespace removed.
+pub fn collapsed_snippet(content: &str, max_chars: usize) -> String {
+    let mut out = String::new();
+    let mut chars = 0usize;
+    for word in content.split_whitespace() {
+        if chars >= max_chars {
+            break;
+        }
+        if !out.is_empty() {
+            out.push(' ');
+            chars += 1;
+        }
+        for ch in word.chars() {
+            if chars >= max_chars {
+                break;
+            }
+            out.push(ch);
+            chars += 1;
+        }
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Find case-insensitive occurrences of `query` in `content`.
+///
+/// Matching follows search scoring: the trimmed query is compared against
+/// lowercased text. Matches do not overlap.
+///
+/// # Arguments
+/// - `content`: Text to scan.
+/// - `query`: Search text; surrounding whitespace is ignored.
+/// - `max_matches`: Maximum number of ranges to return.
+///
+/// # Returns
+/// Byte ranges into `content` in ascending order; empty for a blank query.
+pub fn find_match_ranges(content: &str, query: &str, max_matches: usize) -> Vec<Range<usize>> {
+    let needle: Vec<char> = query.trim().chars().flat_map(char::to_lowercase).collect();
+    let mut ranges = Vec::new();
+    if needle.is_empty() {
+        return ranges;
+    }
+    let mut start = 0;
+    while start < content.len() && ranges.len() < max_matches {
+        let rest = &content[start..];
+        match match_len_at(rest, needle.as_slice()) {
+            Some(len) => {
+                ranges.push(start..start + len);
+                start += len;
+            }
+            None => start += rest.chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    ranges
+}
+
+/// Byte length of the lowercased `needle` match at the start of `text`, if any.
+fn match_len_at(text: &str, needle: &[char]) -> Option<usize> {
+    let mut matched = 0;
+    for (offset, ch) in text.char_indices() {
+        for lower in ch.to_lowercase() {
+            if needle.get(matched) != Some(&lower) {
+                return None;
+            }
+            matched += 1;
+        }
+        if matched == needle.len() {
+            return Some(offset + ch.len_utf8());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        collapsed_snippet, find_match_ranges, is_loopback_host, leading_lines_preview,
+        normalize_optional_nonempty,
+    };
+
+    #[test]
+    fn find_match_ranges_is_case_insensitive_and_capped() {
+        let content = "Foo bar\nfoo FOO é É";
+        assert_eq!(
+            find_match_ranges(content, " foo ", 10),
+            vec![0..3, 8..11, 12..15]
+        );
+        assert_eq!(find_match_ranges(content, "foo", 2), vec![0..3, 8..11]);
+        assert_eq!(find_match_ranges(content, "é", 10), vec![16..18, 19..21]);
+        assert!(find_match_ranges(content, "  ", 10).is_empty());
+        assert!(find_match_ranges(content, "missing", 10).is_empty());
+    }
+
+    #[test]
+    fn normalize_optional_nonempty_trims_and_drops_blank() {
+        assert_eq!(
+            normalize_optional_nonempty(Some("  value  ".to_string())),
+            Some("value".to_string())
+        );
+        assert_eq!(normalize_optional_nonempty(Some("   ".to_string())), None);
+        assert_eq!(normalize_optional_nonempty(None), None);
+    }
+
+    #[test]
+    fn is_loopback_host_accepts_localhost_and_loopback_ips() {
+        assert!(is_loopback_host("localhost"));
+        assert!(is_loopback_host("127.0.0.1"));
+        assert!(is_loopback_host("::1"));
+        assert!(is_loopback_host("[::1]"));
+        assert!(!is_loopback_host("example.com"));
+        assert!(!is_loopback_host("192.168.1.20"));
+    }
+
+    #[test]
+    fn leading_lines_preview_caps_lines_and_chars() {
+        let content = "one\ntwo\r\nthree\nfour\n";
+        assert_eq!(
+            leading_lines_preview(content, 2, 100),
+            ("one\ntwo".to_string(), true)
+        );
+        assert_eq!(
+            leading_lines_preview(content, 10, 100),
+            ("one\ntwo\r\nthree\nfour".to_string(), false)
+        );
+        assert_eq!(
+            leading_lines_preview("h\u{e9}llo world", 10, 4),
+            ("h\u{e9}ll".to_string(), true)
+        );
+        assert_eq!(leading_lines_preview("", 10, 100), (String::new(), false));
+        assert_eq!(leading_lines_preview("abc", 0, 100), (String::new(), true));
+    }
+
+    #[test]
+    fn collapsed_snippet_joins_lines_and_caps_chars() {
+        assert_eq!(
+            collapsed_snippet("  fn main() {\n\n    println!(1);\r\n}\n", 100),
+            "fn main() { println!(1); }"
+        );
+        assert_eq!(collapsed_snippet("alpha beta gamma", 8), "alpha be");
+        assert_eq!(collapsed_snippet("alpha\nbeta", 6), "alpha");
+        assert_eq!(collapsed_snippet(" \n\t ", 10), "");
+    }
+}
@@ -0,0 +1,505 @@
+//! Timestamp presentation shared by the API, CLI, and GUI.
+//!
+//! Timestamps are stored and transmitted in UTC. API responses carry each one
+//! twice: the RFC3339 string under its usual key and epoch milliseconds under
+//! `<key>_ms`. Clients choose whether to display them in local time or UTC.
+
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, SecondsFormat, Utc};
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// Suffix appended to a timestamp key for its epoch-milliseconds twin.
+pub const EPOCH_MS_SUFFIX: &str = "_ms";
+
+/// Timezone used when showing timestamps to a person.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DisplayTimezone {
+    /// The machine's local timezone.
+    #[default]
+    Local,
+    /// Coordinated Universal Time.
+    Utc,
+}
+
+impl DisplayTimezone {
+    /// Lowercase name used by settings, flags, and environment variables.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Local => "local",
+            Self::Utc => "utc",
+        }
+    }
+
+    /// Format `at` with a `chrono` strftime pattern in this timezone.
+    ///
+    /// UTC output gets a trailing ` UTC` so it is not mistaken for local time.
+    pub fn format(self, at: DateTime<Utc>, pattern: &str) -> String {
+        match self {
+            Self::Local => at.with_timezone(&Local).format(pattern).to_string(),
+            Self::Utc => format!("{} UTC", at.format(pattern)),
+        }
+    }
+
+    /// Format an RFC3339 string from an API response in this timezone.
+    ///
+    /// # Returns
+    /// `None` when `raw` is not a valid RFC3339 timestamp.
+    pub fn format_rfc3339(self, raw: &str, pattern: &str) -> Option<String> {
+        DateTime::parse_from_rfc3339(raw)
+            .ok()
+            .map(|parsed| self.format(parsed.with_timezone(&Utc), pattern))
+    }
+}
+
+impl fmt::Display for DisplayTimezone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DisplayTimezone {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "local" => Ok(Self::Local),
+            "utc" => Ok(Self::Utc),
+            other => Err(format!(
+                "unknown timezone '{}' (expected 'local' or 'utc')",
+                other
+            )),
+        }
+    }
+}
+
+/// Describe how long ago `at` was, relative to `now`.
+///
+/// # Returns
+/// Strings like `just now`, `5 minutes ago`, or `2 years ago`. Times in the
+/// future (clock skew) read as `just now`.
+pub fn relative_time(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - at).num_seconds();
+    if seconds < 45 {
+        return "just now".to_string();
+    }
+    let (count, unit) = match seconds {
+        s if s < 3_600 => ((s + 30) / 60, "minute"),
+        s if s < 86_400 => (s / 3_600, "hour"),
+        s if s < 7 * 86_400 => (s / 86_400, "day"),
+        s if s < 30 * 86_400 => (s / (7 * 86_400), "week"),
+        s if s < 365 * 86_400 => (s / (30 * 86_400), "month"),
+        s => (s / (365 * 86_400), "year"),
+    };
+    let count = count.max(1);
+    if count == 1 {
+        format!("1 {} ago", unit)
+    } else {
+        format!("{} {}s ago", count, unit)
+    }
+}
+
+/// Parse a time filter written as RFC3339 or as a `YYYY-MM-DD` date.
+///
+/// # Returns
+/// The instant in UTC, where a bare date means midnight UTC at the start of
+/// that day, or `None` when `raw` is neither form.
+pub fn parse_time_filter(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(raw) {
+        return Some(parsed.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .ok()
+        .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+}
+
+fn is_timestamp_key(key: &str) -> bool {
+    key.ends_with("_at") || key == "last_updated"
+}
+
+/// The canonical UTC string and epoch milliseconds of an RFC3339 timestamp.
+fn parse_timestamp<T: Serialize + ?Sized>(value: &T) -> Option<(String, i64)> {
+    let Ok(Value::String(raw)) = serde_json::to_value(value) else {
+        return None;
+    };
+    let utc = DateTime::parse_from_rfc3339(&raw).ok()?.with_timezone(&Utc);
+    Some((
+        utc.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        utc.timestamp_millis(),
+    ))
+}
+
+/// Serializes `T` with an epoch-milliseconds twin for every RFC3339 timestamp.
+///
+/// Fields and map keys ending in `_at`, plus `last_updated`, whose value is an
+/// RFC3339 string are written in canonical UTC form (`Z` suffix) and followed
+/// by `<key>_ms`, at any depth. `null` and unparsable values are written
+/// unchanged without a twin. Structs are written as maps, so this is meant
+/// for self-describing formats such as JSON, never for stored models.
+pub struct WithEpochFields<'a, T: ?Sized>(pub &'a T);
+
+impl<T: Serialize + ?Sized> Serialize for WithEpochFields<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(EpochSerializer(serializer))
+    }
+}
+
+/// Forwards to `S`, rewriting struct fields and map entries as described on
+/// [`WithEpochFields`].
+struct EpochSerializer<S>(S);
+
+/// Sequence-like compound whose elements are serialized through
+/// [`WithEpochFields`].
+struct EpochCompound<C>(C);
+
+/// Map or struct compound that appends `<key>_ms` after timestamp entries.
+struct EpochMap<M> {
+    inner: M,
+    key: Option<String>,
+}
+
+macro_rules! forward_scalars {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(fn $method(self, value: $ty) -> Result<S::Ok, S::Error> {
+            self.0.$method(value)
+        })*
+    };
+}
+
+impl<S: Serializer> Serializer for EpochSerializer<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = EpochCompound<S::SerializeSeq>;
+    type SerializeTuple = EpochCompound<S::SerializeTuple>;
+    type SerializeTupleStruct = EpochCompound<S::SerializeTupleStruct>;
+    type SerializeTupleVariant = EpochCompound<S::SerializeTupleVariant>;
+    type SerializeMap = EpochMap<S::SerializeMap>;
+    type SerializeStruct = EpochMap<S::SerializeMap>;
+    type SerializeStructVariant = EpochCompound<S::SerializeStructVariant>;
+
+    forward_scalars!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_unit_struct(&'static str),
+    );
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        self.0.serialize_none()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        self.0.serialize_some(&WithEpochFields(value))
+    }
+
+    fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+        self.0.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        self.0.serialize_unit_variant(name, index, variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.0
+            .serialize_newtype_struct(name, &WithEpochFields(value))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.0
+            .serialize_newtype_variant(name, index, variant, &WithEpochFields(value))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        self.0.serialize_seq(len).map(EpochCompound)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        self.0.serialize_tuple(len).map(EpochCompound)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        self.0.serialize_tuple_struct(name, len).map(EpochCompound)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        self.0
+            .serialize_tuple_variant(name, index, variant, len)
+            .map(EpochCompound)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        // Twins make the entry count unknown up front.
+        self.0
+            .serialize_map(None)
+            .map(|inner| EpochMap { inner, key: None })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        self.0
+            .serialize_map(None)
+            .map(|inner| EpochMap { inner, key: None })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        self.0
+            .serialize_struct_variant(name, index, variant, len)
+            .map(EpochCompound)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+macro_rules! epoch_compound {
+    ($($trait:ident::$method:ident),* $(,)?) => {
+        $(impl<C: ser::$trait> ser::$trait for EpochCompound<C> {
+            type Ok = C::Ok;
+            type Error = C::Error;
+
+            fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+                self.0.$method(&WithEpochFields(value))
+            }
+
+            fn end(self) -> Result<C::Ok, C::Error> {
+                self.0.end()
+            }
+        })*
+    };
+}
+
+epoch_compound!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field,
+);
+
+impl<C: ser::SerializeStructVariant> ser::SerializeStructVariant for EpochCompound<C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), C::Error> {
+        self.0.serialize_field(key, &WithEpochFields(value))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.0.end()
+    }
+}
+
+impl<M: ser::SerializeMap> ser::SerializeMap for EpochMap<M> {
+    type Ok = M::Ok;
+    type Error = M::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), M::Error> {
+        self.key = match serde_json::to_value(key) {
+            Ok(Value::String(key)) if is_timestamp_key(&key) => Some(key),
+            _ => None,
+        };
+        self.inner.serialize_key(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), M::Error> {
+        let key = self.key.take();
+        let Some((key, (canonical, millis))) =
+            key.and_then(|key| parse_timestamp(value).map(|parsed| (key, parsed)))
+        else {
+            return self.inner.serialize_value(&WithEpochFields(value));
+        };
+        self.inner.serialize_value(&canonical)?;
+        self.inner
+            .serialize_entry(&format!("{}{}", key, EPOCH_MS_SUFFIX), &millis)
+    }
+
+    fn end(self) -> Result<M::Ok, M::Error> {
+        self.inner.end()
+    }
+}
+
+impl<M: ser::SerializeMap> ser::SerializeStruct for EpochMap<M> {
+    type Ok = M::Ok;
+    type Error = M::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), M::Error> {
+        self.key = is_timestamp_key(key).then(|| key.to_string());
+        self.inner.serialize_key(key)?;
+        ser::SerializeMap::serialize_value(self, value)
+    }
+
+    fn end(self) -> Result<M::Ok, M::Error> {
+        self.inner.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use serde_json::json;
+
+    #[test]
+    fn relative_time_buckets() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let cases = [
+            (Duration::seconds(-30), "just now"),
+            (Duration::seconds(10), "just now"),
+            (Duration::seconds(50), "1 minute ago"),
+            (Duration::minutes(5), "5 minutes ago"),
+            (Duration::hours(1), "1 hour ago"),
+            (Duration::hours(23), "23 hours ago"),
+            (Duration::days(3), "3 days ago"),
+            (Duration::days(15), "2 weeks ago"),
+            (Duration::days(95), "3 months ago"),
+            (Duration::days(800), "2 years ago"),
+        ];
+        for (ago, expected) in cases {
+            assert_eq!(relative_time(now - ago, now), expected, "{:?}", ago);
+        }
+    }
+
+    #[test]
+    fn display_timezone_parses_and_formats() {
+        assert_eq!(
+            "UTC".parse::<DisplayTimezone>().unwrap(),
+            DisplayTimezone::Utc
+        );
+        assert_eq!(
+            " local ".parse::<DisplayTimezone>().unwrap(),
+            DisplayTimezone::Local
+        );
+        assert!("mars".parse::<DisplayTimezone>().is_err());
+        let at = Utc.with_ymd_and_hms(2026, 3, 10, 7, 5, 0).unwrap();
+        assert_eq!(
+            DisplayTimezone::Utc.format(at, "%Y-%m-%d %H:%M"),
+            "2026-03-10 07:05 UTC"
+        );
+        assert_eq!(
+            DisplayTimezone::Utc
+                .format_rfc3339("2026-03-10T09:05:00+02:00", "%H:%M")
+                .as_deref(),
+            Some("07:05 UTC")
+        );
+        assert!(DisplayTimezone::Utc
+            .format_rfc3339("yesterday", "%H:%M")
+            .is_none());
+        assert_eq!(
+            serde_json::to_string(&DisplayTimezone::Utc).unwrap(),
+            "\"utc\""
+        );
+    }
+
+    #[test]
+    fn time_filters_accept_rfc3339_and_dates() {
+        assert_eq!(
+            parse_time_filter("2026-03-10T09:05:00+02:00").map(|at| at.timestamp_millis()),
+            Some(1_773_126_300_000)
+        );
+        assert_eq!(
+            parse_time_filter(" 2026-03-10 ").map(|at| at.to_rfc3339()),
+            Some("2026-03-10T00:00:00+00:00".to_string())
+        );
+        assert_eq!(parse_time_filter("yesterday"), None);
+        assert_eq!(parse_time_filter("2026-13-01"), None);
+    }
+
+    #[test]
+    fn epoch_fields_are_added_at_any_depth() {
+        #[derive(Serialize)]
+        struct Item {
+            name: &'static str,
+            created_at: &'static str,
+            updated_at: DateTime<Utc>,
+            last_updated: Option<DateTime<Utc>>,
+        }
+
+        let updated_at = DateTime::parse_from_rfc3339("2026-03-10T07:05:00.250Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let value = json!({
+            "meta": { "last_updated": "2026-03-10T07:05:00Z" },
+            "expires_at": "not a time",
+            "started": "2026-03-10T07:05:00Z",
+        });
+        let items = vec![Item {
+            name: "a",
+            created_at: "2026-03-10T09:05:00+02:00",
+            updated_at,
+            last_updated: None,
+        }];
+        let items = serde_json::to_value(WithEpochFields(&items)).unwrap();
+        let value = serde_json::to_value(WithEpochFields(&value)).unwrap();
+
+        let item = &items[0];
+        assert_eq!(item["name"], "a");
+        assert_eq!(item["created_at"], "2026-03-10T07:05:00Z");
+        assert_eq!(item["created_at_ms"], 1_773_126_300_000i64);
+        assert_eq!(item["updated_at_ms"], 1_773_126_300_250i64);
+        assert!(item["last_updated"].is_null());
+        assert!(item.get("last_updated_ms").is_none());
+        assert_eq!(value["meta"]["last_updated_ms"], 1_773_126_300_000i64);
+        assert!(value.get("expires_at_ms").is_none());
+        assert!(value.get("started_ms").is_none());
+    }
+}
@@ -0,0 +1,195 @@
+//! Opt-in check for newer LocalPaste releases on GitHub.
+//!
+//! Only the latest published release is considered; drafts and pre-releases
+//! are skipped by the GitHub endpoint itself. Installing stays manual: callers
+//! show the release page, which lists the per-platform downloads.
+
+use crate::AppError;
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// GitHub API endpoint for the newest published release.
+pub const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/pszemraj/localpaste.rs/releases/latest";
+/// Minimum time between automatic checks while the GUI runs.
+pub const UPDATE_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Upper bound on one release lookup.
+pub const UPDATE_CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// A published release newer than the running build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseInfo {
+    /// Release version without a leading `v`, e.g. `0.5.0`.
+    pub version: String,
+    /// Release page with the changelog and downloads.
+    pub url: String,
+    /// Release notes as written on GitHub (Markdown); may be empty.
+    pub notes: String,
+}
+
+#[derive(Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    html_url: String,
+    #[serde(default)]
+    body: Option<String>,
+}
+
+/// Parse `major.minor.patch` from a tag such as `v0.4.7` or `0.5.0-rc.1`.
+///
+/// Missing minor/patch parts count as zero; anything after `-` or `+` is
+/// ignored.
+pub fn parse_version(tag: &str) -> Option<(u64, u64, u64)> {
+    let core = tag
+        .trim()
+        .trim_start_matches(['v', 'V'])
+        .split(['-', '+'])
+        .next()?;
+    let mut parts = core.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |part| part.parse().ok())?;
+    let patch = parts.next().map_or(Some(0), |part| part.parse().ok())?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+/// Whether `latest` is a strictly higher version than `current`.
+///
+/// Unparseable versions never count as newer.
+pub fn is_newer_version(latest: &str, current: &str) -> bool {
+    match (parse_version(latest), parse_version(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+/// Parse a GitHub "latest release" response body.
+///
+/// # Errors
+/// Returns `Upstream` when the body is not a release object.
+pub fn parse_latest_release(body: &str) -> Result<ReleaseInfo, AppError> {
+    let release: GithubRelease = serde_json::from_str(body)
+        .map_err(|err| AppError::Upstream(format!("unexpected release response: {}", err)))?;
+    Ok(ReleaseInfo {
+        version: release
+            .tag_name
+            .trim()
+            .trim_start_matches(['v', 'V'])
+            .to_string(),
+        url: release.html_url,
+        notes: release.body.unwrap_or_default().trim().to_string(),
+    })
+}
+
+/// Look up the latest release at `url` and keep it only if it is newer.
+///
+/// # Arguments
+/// - `url`: Release endpoint, normally [`LATEST_RELEASE_URL`].
+/// - `current_version`: Version of the running build.
+///
+/// # Returns
+/// `Some` release when it is newer than `current_version`, else `None`.
+///
+/// # Errors
+/// Returns `Upstream` when the request fails, the server answers with a
+/// non-success status, or the response cannot be parsed.
+pub fn check_for_update(url: &str, current_version: &str) -> Result<Option<ReleaseInfo>, AppError> {
+    let client = Client::builder()
+        .timeout(UPDATE_CHECK_TIMEOUT)
+        .user_agent(concat!("localpaste/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|err| AppError::Upstream(format!("http client: {}", err)))?;
+    let response = client
+        .get(url)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .map_err(|err| AppError::Upstream(format!("checking {} failed: {}", url, err)))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(AppError::Upstream(format!(
+            "checking {} returned HTTP {}",
+            url, status
+        )));
+    }
+    let body = response
+        .text()
+        .map_err(|err| AppError::Upstream(format!("reading {} failed: {}", url, err)))?;
+    let release = parse_latest_release(&body)?;
+    Ok(is_newer_version(&release.version, current_version).then_some(release))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_compare_numerically_and_ignore_suffixes() {
+        assert_eq!(parse_version("v0.4.7"), Some((0, 4, 7)));
+        assert_eq!(parse_version("1.2"), Some((1, 2, 0)));
+        assert_eq!(parse_version("0.5.0-rc.1"), Some((0, 5, 0)));
+        assert_eq!(parse_version("nightly"), None);
+        assert_eq!(parse_version("1.2.3.4"), None);
+
+        assert!(is_newer_version("v0.4.10", "0.4.7"));
+        assert!(is_newer_version("1.0.0", "0.9.9"));
+        assert!(!is_newer_version("v0.4.7", "0.4.7"));
+        assert!(!is_newer_version("0.4.6", "0.4.7"));
+        assert!(!is_newer_version("nightly", "0.4.7"));
+    }
+
+    #[test]
+    fn latest_release_response_is_parsed() {
+        let body = r###"{
+            "tag_name": "v0.5.0",
+            "html_url": "https://github.com/pszemraj/localpaste.rs/releases/tag/v0.5.0",
+            "body": "## Changes\n- faster search\n",
+            "prerelease": false
+        }"###;
+        let release = parse_latest_release(body).expect("parse");
+        assert_eq!(release.version, "0.5.0");
+        assert!(release.url.ends_with("/v0.5.0"));
+        assert_eq!(release.notes, "## Changes\n- faster search");
+
+        let without_notes = parse_latest_release(
+            r#"{"tag_name": "0.5.1", "html_url": "https://example.invalid", "body": null}"#,
+        )
+        .expect("parse");
+        assert!(without_notes.notes.is_empty());
+        assert!(matches!(
+            parse_latest_release(r#"{"message": "Not Found"}"#),
+            Err(AppError::Upstream(_))
+        ));
+    }
+
+    #[test]
+    fn check_for_update_keeps_only_newer_releases() {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let url = format!("http://{}/latest", listener.local_addr().expect("addr"));
+        std::thread::spawn(move || {
+            let body = r#"{"tag_name": "v0.5.0", "html_url": "https://example.invalid/v0.5.0"}"#;
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().expect("accept");
+                let mut request = [0u8; 1024];
+                let _ = stream.read(&mut request);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        let newer = check_for_update(&url, "0.4.7").expect("check");
+        assert_eq!(
+            newer.map(|release| release.version).as_deref(),
+            Some("0.5.0")
+        );
+        assert_eq!(check_for_update(&url, "0.5.0").expect("check"), None);
+    }
+}
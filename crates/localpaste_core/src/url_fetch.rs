@@ -0,0 +1,115 @@
+//! Blocking fetch of paste content from a source URL.
+//!
+//! Fetching and storing are separate steps so callers can download without
+//! holding a paste lock, then take the lock only for the short write.
+
+use crate::models::paste::{Paste, UpdatePasteRequest};
+use crate::models::url_source::UrlRefreshResponse;
+use crate::normalization::{content_matches_stored, decode_content_bytes};
+use crate::{AppError, Database};
+use chrono::{DateTime, Utc};
+use reqwest::blocking::Client;
+use std::io::Read;
+use std::time::Duration;
+
+/// Upper bound on one source fetch, including redirects and the body.
+pub const URL_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Download `url`, reading at most `max_bytes` of the body.
+///
+/// # Errors
+/// Returns `PayloadTooLarge` when the body exceeds `max_bytes`, and
+/// `Upstream` when the request fails or the server answers with a
+/// non-success status.
+pub fn fetch_source_bytes(url: &str, max_bytes: usize) -> Result<Vec<u8>, AppError> {
+    let client = Client::builder()
+        .timeout(URL_FETCH_TIMEOUT)
+        .user_agent(concat!("localpaste/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|err| AppError::Upstream(format!("http client: {}", err)))?;
+    let response = client
+        .get(url)
+        .send()
+        .map_err(|err| AppError::Upstream(format!("fetching {} failed: {}", url, err)))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(AppError::Upstream(format!(
+            "fetching {} returned HTTP {}",
+            url, status
+        )));
+    }
+    if response
+        .content_length()
+        .is_some_and(|len| len > max_bytes as u64)
+    {
+        return Err(too_large(max_bytes));
+    }
+    let mut bytes = Vec::new();
+    response
+        .take(max_bytes as u64 + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|err| AppError::Upstream(format!("reading {} failed: {}", url, err)))?;
+    if bytes.len() > max_bytes {
+        return Err(too_large(max_bytes));
+    }
+    Ok(bytes)
+}
+
+fn too_large(max_bytes: usize) -> AppError {
+    AppError::PayloadTooLarge(format!(
+        "Source content exceeds maximum of {} bytes",
+        max_bytes
+    ))
+}
+
+/// Store fetched bytes on a linked paste and record the attempt.
+///
+/// The paste is only rewritten when the decoded content differs from what
+/// is stored, so unchanged sources do not bump `updated_at` or add
+/// revisions. Callers that guard paste writes must hold that guard.
+///
+/// # Returns
+/// The paste as stored after the refresh, and the refresh outcome.
+///
+/// # Errors
+/// Returns `NotFound` when the paste or its source link is gone,
+/// `BadRequest` when the bytes are not valid UTF-8 under the configured
+/// policy, or an error when storage fails.
+pub fn apply_source_bytes(
+    db: &Database,
+    paste_id: &str,
+    bytes: Vec<u8>,
+    checked_at: DateTime<Utc>,
+) -> Result<(Paste, UrlRefreshResponse), AppError> {
+    let content = match decode_content_bytes(bytes, db.pastes.normalization()) {
+        Ok(content) => content,
+        Err(err) => {
+            db.url_sources
+                .record_check(paste_id, checked_at, false, Some(&err.to_string()))?;
+            return Err(err);
+        }
+    };
+    let current = db.pastes.get(paste_id)?.ok_or(AppError::NotFound)?;
+    let changed = !content_matches_stored(&content, &current.content);
+    let paste = if changed {
+        let update = UpdatePasteRequest {
+            content: Some(content),
+            name: None,
+            language: None,
+            language_is_manual: None,
+            folder_id: None,
+            tags: None,
+            expires_in: None,
+        };
+        db.pastes
+            .update(paste_id, update)?
+            .ok_or(AppError::NotFound)?
+    } else {
+        current
+    };
+    let source = db
+        .url_sources
+        .record_check(paste_id, checked_at, changed, None)?
+        .ok_or(AppError::NotFound)?;
+    Ok((paste, UrlRefreshResponse { changed, source }))
+}
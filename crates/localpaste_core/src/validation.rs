@@ -0,0 +1,156 @@
+//! Pre-flight checks for content that has not been saved yet.
+
+use crate::lint::lint_content;
+use crate::secrets::scan_secrets;
+use serde::{Deserialize, Serialize};
+
+/// `POST /api/validate` request body.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ValidateRequest {
+    pub content: String,
+    /// Language to lint as; detected from `content` when omitted.
+    pub language: Option<String>,
+    /// Destination folder, so folder size overrides apply.
+    pub folder_id: Option<String>,
+}
+
+/// Check that produced a [`ValidationFinding`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationCheck {
+    Size,
+    Lint,
+    Secret,
+}
+
+/// Whether a finding would block saving (`error`) or is advisory (`warning`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationSeverity {
+    Error,
+    Warning,
+}
+
+/// One problem found in the content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationFinding {
+    pub check: ValidationCheck,
+    pub severity: ValidationSeverity,
+    pub message: String,
+    /// 1-based line, when the finding points at one.
+    pub line: Option<usize>,
+    /// 1-based column, when the finding points at one.
+    pub column: Option<usize>,
+}
+
+/// `POST /api/validate` response body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidateResponse {
+    /// `false` when any finding is an error.
+    pub valid: bool,
+    /// Canonical language the content was checked as, when known.
+    pub language: Option<String>,
+    /// `true` when `language` has a syntax check and it ran.
+    pub linted: bool,
+    pub findings: Vec<ValidationFinding>,
+}
+
+/// Run size, syntax, and secret checks over `content`.
+///
+/// # Arguments
+/// - `content`: Text to check.
+/// - `language`: Canonical language, already resolved by the caller.
+/// - `max_size`: Size limit in bytes that a save would enforce.
+///
+/// # Returns
+/// Every finding, errors and warnings alike, in check order.
+pub fn validate_content(
+    content: &str,
+    language: Option<&str>,
+    max_size: usize,
+) -> ValidateResponse {
+    let mut findings = Vec::new();
+    if content.len() > max_size {
+        findings.push(ValidationFinding {
+            check: ValidationCheck::Size,
+            severity: ValidationSeverity::Error,
+            message: format!(
+                "Paste size {} bytes exceeds maximum of {} bytes",
+                content.len(),
+                max_size
+            ),
+            line: None,
+            column: None,
+        });
+    }
+    let report = lint_content(language, content);
+    let linted = report.is_some();
+    if let Some(error) = report.and_then(|report| report.error) {
+        findings.push(ValidationFinding {
+            check: ValidationCheck::Lint,
+            severity: ValidationSeverity::Error,
+            message: error.message,
+            line: Some(error.line),
+            column: Some(error.column),
+        });
+    }
+    findings.extend(
+        scan_secrets(content)
+            .into_iter()
+            .map(|secret| ValidationFinding {
+                check: ValidationCheck::Secret,
+                severity: ValidationSeverity::Warning,
+                message: format!("Possible {}", secret.kind.label()),
+                line: Some(secret.line),
+                column: Some(secret.column),
+            }),
+    );
+    ValidateResponse {
+        valid: !findings
+            .iter()
+            .any(|finding| finding.severity == ValidationSeverity::Error),
+        language: language.map(str::to_string),
+        linted,
+        findings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_content_reports_size_lint_and_secret_findings() {
+        let content = format!("{{\"key\": \"AKIA{}\",}}", "ABCDEFGHIJKLMNOP");
+        let response = validate_content(&content, Some("json"), 8);
+        assert!(!response.valid);
+        assert!(response.linted);
+        let checks: Vec<_> = response
+            .findings
+            .iter()
+            .map(|finding| (finding.check, finding.severity))
+            .collect();
+        assert_eq!(
+            checks,
+            vec![
+                (ValidationCheck::Size, ValidationSeverity::Error),
+                (ValidationCheck::Lint, ValidationSeverity::Error),
+                (ValidationCheck::Secret, ValidationSeverity::Warning),
+            ]
+        );
+        assert!(!response.findings[2].message.contains("AKIA"));
+    }
+
+    #[test]
+    fn validate_content_warnings_alone_stay_valid() {
+        let content = format!("token = \"ghp_{}\"\n", "b".repeat(36));
+        let response = validate_content(&content, Some("toml"), 1024);
+        assert!(!response.findings.is_empty());
+        assert!(response.valid);
+
+        let clean = validate_content("fn main() {}", Some("rust"), 1024);
+        assert!(clean.valid);
+        assert!(!clean.linted);
+        assert!(clean.findings.is_empty());
+    }
+}
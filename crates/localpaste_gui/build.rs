@@ -1,20 +1,71 @@
-//! Embeds the Windows icon resource into the GUI executable.
+//! Embeds the Windows icon resource into the GUI executable and splits the
+//! bundled syntect grammars into per-syntax dumps.
 //!
-//! This ensures Explorer, taskbar, and Start-menu surfaces use the packaged
-//! icon instead of the default PE placeholder.
+//! The icon ensures Explorer, taskbar, and Start-menu surfaces use the packaged
+//! icon instead of the default PE placeholder. The grammar dumps let
+//! `LOCALPASTE_SYNTAX_PRUNE` deserialize only the grammars it keeps instead of
+//! the whole bundled set.
 
-use std::{env, path::PathBuf};
+use std::{
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+use syntect::dumps::dump_binary;
+use syntect::parsing::syntax_definition::Context;
+use syntect::parsing::SyntaxSet;
 
-// On non-Windows *targets* this script does nothing.
+/// Writes one compressed dump per bundled grammar, a dump of every grammar as
+/// an empty-context stub, and `syntax_dumps.rs` embedding them in set order.
+fn write_syntax_dumps(out_dir: &Path) {
+    let dump_dir = out_dir.join("syntaxes");
+    fs::create_dir_all(&dump_dir).expect("create syntax dump directory");
+    let builder = SyntaxSet::load_defaults_newlines().into_builder();
+    let mut stubs = Vec::new();
+    let mut table = String::from("/// Full grammar dumps, in bundled set order.\n");
+    table.push_str("pub(super) static SYNTAX_DUMPS: &[&[u8]] = &[\n");
+    for (index, definition) in builder.syntaxes().iter().enumerate() {
+        let path = dump_dir.join(format!("{index}.packdump"));
+        fs::write(&path, dump_binary(definition)).expect("write syntax dump");
+        writeln!(
+            table,
+            "    include_bytes!({:?}),",
+            path.display().to_string()
+        )
+        .expect("format syntax table");
+        let mut stub = definition.clone();
+        for context in stub.contexts.values_mut() {
+            *context = Context::new(false);
+        }
+        stubs.push(stub);
+    }
+    table.push_str("];\n");
+    let stubs_path = dump_dir.join("stubs.packdump");
+    fs::write(&stubs_path, dump_binary(&stubs)).expect("write syntax stubs");
+    writeln!(
+        table,
+        "/// Every grammar with empty contexts, in bundled set order.\n\
+         pub(super) static SYNTAX_STUBS: &[u8] = include_bytes!({:?});",
+        stubs_path.display().to_string()
+    )
+    .expect("format syntax table");
+    fs::write(out_dir.join("syntax_dumps.rs"), table).expect("write syntax table");
+}
+
+// On non-Windows *targets* only the grammar dumps are written.
 fn main() {
     let manifest_dir = PathBuf::from(
         env::var("CARGO_MANIFEST_DIR").expect("missing CARGO_MANIFEST_DIR for build script"),
     );
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("missing OUT_DIR for build script"));
     let icon_path = manifest_dir.join("../../packaging/windows/localpaste.ico");
 
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed={}", icon_path.display());
 
+    write_syntax_dumps(&out_dir);
+
     if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("windows") {
         let mut res = winresource::WindowsResource::new();
         // Path is relative to this build.rs (i.e. the crate root).
@@ -0,0 +1,198 @@
+//! Live merging of edits other editors make to the selected paste.
+//!
+//! Once the backend reports the collaboration revision of the loaded content,
+//! saves go through the shared merge hub instead of overwriting, and the
+//! editor polls for remote edits. Remote changes are folded into the buffer
+//! with unsaved local edits rebased on top, so neither side is reverted.
+
+use super::virtual_editor::{EditIntent, VirtualEditorHistory};
+use super::LocalPasteApp;
+use crate::backend::CoreCmd;
+use localpaste_core::collab::TextEdit;
+use localpaste_core::models::paste::Paste;
+use ropey::Rope;
+use std::time::{Duration, Instant};
+
+/// How often the selected paste is checked for remote edits.
+pub(super) const COLLAB_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Collaboration state for the selected paste.
+#[derive(Debug, Default)]
+pub(super) struct CollabSession {
+    /// Revision of `base`; `None` until the backend reports one.
+    pub(super) rev: Option<u64>,
+    /// Stored content at `rev`.
+    base: String,
+    /// Content sent with the merge request in flight.
+    sent: Option<Rope>,
+    polled_at: Option<Instant>,
+}
+
+impl LocalPasteApp {
+    /// Forgets collaboration state after the selection changes; the first
+    /// poll waits one interval.
+    pub(super) fn reset_collab(&mut self) {
+        self.collab = CollabSession {
+            polled_at: Some(Instant::now()),
+            ..CollabSession::default()
+        };
+    }
+
+    /// Builds a merge command when the selected paste has a known revision.
+    pub(super) fn collab_merge_command(&mut self, id: &str) -> Option<CoreCmd> {
+        let base_rev = self.collab.rev?;
+        if !self.is_virtual_editor_mode() {
+            return None;
+        }
+        let content = self.virtual_editor_buffer.rope().clone();
+        self.collab.sent = Some(content.clone());
+        Some(CoreCmd::MergePaste {
+            id: id.to_string(),
+            base_rev,
+            content,
+        })
+    }
+
+    /// Drops the in-flight merge snapshot after a failed save.
+    pub(super) fn clear_collab_sent(&mut self) {
+        self.collab.sent = None;
+    }
+
+    /// Asks the backend for remote edits once per [`COLLAB_POLL_INTERVAL`].
+    pub(super) fn maybe_poll_collab_edits(&mut self) {
+        if self.save_in_flight || !self.is_virtual_editor_mode() {
+            return;
+        }
+        let Some(id) = self.selected_id.clone() else {
+            return;
+        };
+        if self
+            .collab
+            .polled_at
+            .is_some_and(|at| at.elapsed() < COLLAB_POLL_INTERVAL)
+        {
+            return;
+        }
+        self.collab.polled_at = Some(Instant::now());
+        let _ = self.backend.cmd_tx.send(CoreCmd::PollPasteEdits {
+            id,
+            since_rev: self.collab.rev,
+        });
+    }
+
+    /// Folds the result of this editor's merge into the buffer.
+    ///
+    /// Called before the save acknowledgement is applied, so edits typed
+    /// while the merge was in flight stay dirty and nothing else does.
+    pub(super) fn absorb_merged_save(&mut self, paste: &Paste) {
+        if self.selected_id.as_deref() != Some(paste.id.as_str()) {
+            return;
+        }
+        let Some(sent) = self.collab.sent.take() else {
+            return;
+        };
+        let sent = sent.to_string();
+        let unchanged_since_send = self.active_snapshot() == sent;
+        if self.absorb_stored_content(&sent, paste.content.as_str()) && unchanged_since_send {
+            self.save_request_revision = Some(self.active_revision());
+        }
+    }
+
+    /// Records that the selected paste's stored content is at `rev`.
+    pub(super) fn apply_collab_revision(&mut self, id: &str, rev: u64) {
+        if self.selected_id.as_deref() != Some(id) {
+            return;
+        }
+        let Some(paste) = self.selected_paste.as_ref() else {
+            return;
+        };
+        self.collab.base = paste.content.clone();
+        self.collab.rev = Some(rev);
+    }
+
+    /// Applies another editor's changes to the selected paste.
+    ///
+    /// Ignored while a save is in flight; its merge result carries the same
+    /// changes.
+    pub(super) fn apply_remote_edit(&mut self, paste: Paste, rev: u64) {
+        if self.selected_id.as_deref() != Some(paste.id.as_str()) || self.save_in_flight {
+            return;
+        }
+        let base = match self.collab.rev {
+            Some(_) => std::mem::take(&mut self.collab.base),
+            None => self
+                .selected_paste
+                .as_ref()
+                .map(|selected| selected.content.clone())
+                .unwrap_or_default(),
+        };
+        self.absorb_stored_content(&base, paste.content.as_str());
+        self.upsert_cached_paste_summary(&paste);
+        if !self.metadata_dirty && !self.metadata_save_in_flight {
+            self.sync_editor_metadata(&paste);
+        }
+        self.collab.base = paste.content.clone();
+        self.collab.rev = Some(rev);
+        self.selected_paste = Some(paste);
+    }
+
+    /// Moves the buffer from `base` to `stored`, keeping local edits made
+    /// since `base`. Where both changed the same region, the local edit wins.
+    ///
+    /// # Returns
+    /// `true` when the buffer changed.
+    fn absorb_stored_content(&mut self, base: &str, stored: &str) -> bool {
+        let Some(remote) = TextEdit::between(base, stored) else {
+            return false;
+        };
+        let current = self.active_snapshot();
+        let target = TextEdit::between(base, &current)
+            .and_then(|local| local.rebase(&remote).0.apply(stored).ok())
+            .unwrap_or_else(|| stored.to_string());
+        let Some(edit) = TextEdit::between(&current, &target) else {
+            return false;
+        };
+        if !self.is_virtual_editor_mode() {
+            self.selected_content.reset(target);
+            self.editor_lines.reset();
+            return true;
+        }
+        let inserted = edit.text.chars().count();
+        let map = |offset: usize| {
+            if offset <= edit.start {
+                offset
+            } else if offset >= edit.end {
+                offset - (edit.end - edit.start) + inserted
+            } else {
+                edit.start + inserted
+            }
+        };
+        let cursor = self.virtual_editor_state.cursor();
+        let anchor = self.virtual_editor_state.selection_range().map(|range| {
+            if range.end == cursor {
+                range.start
+            } else {
+                range.end
+            }
+        });
+        self.replace_virtual_range(
+            edit.start..edit.end,
+            edit.text.as_str(),
+            EditIntent::Other,
+            false,
+            Instant::now(),
+        );
+        // Recorded offsets no longer line up with the merged text.
+        self.virtual_editor_history = VirtualEditorHistory::default();
+        let len = self.virtual_editor_buffer.len_chars();
+        match anchor {
+            Some(anchor) => {
+                self.virtual_editor_state.set_cursor(map(anchor), len);
+                self.virtual_editor_state
+                    .move_cursor(map(cursor), len, true);
+            }
+            None => self.virtual_editor_state.set_cursor(map(cursor), len),
+        }
+        true
+    }
+}
@@ -0,0 +1,256 @@
+//! Headless driver for scripted end-to-end UI tests.
+//!
+//! [`GuiHarness`] runs full [`LocalPasteApp`] frames against an offscreen egui
+//! context, with the real backend worker and embedded server behind it. Tests
+//! feed synthetic input (paste selection, typed text, key shortcuts) as a
+//! [`Step`] script and then assert on app state through the accessors here.
+//! Only built with the `test-harness` feature.
+
+pub use eframe::egui;
+
+use super::{EditorMode, LocalPasteApp, SaveStatus, DEFAULT_WINDOW_SIZE};
+use eframe::App as _;
+use localpaste_core::constants::{DEFAULT_AUTO_SAVE_INTERVAL_MS, DEFAULT_MAX_PASTE_SIZE};
+use localpaste_core::{AppError, Config};
+use std::time::{Duration, Instant};
+
+/// How long [`GuiHarness`] waits for backend round trips before failing a step.
+pub const DEFAULT_STEP_TIMEOUT: Duration = Duration::from_secs(5);
+
+const IDLE_FRAME_SLEEP: Duration = Duration::from_millis(2);
+
+/// One scripted input step for [`GuiHarness::run_script`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+    /// Select a paste by id and wait until its content is loaded.
+    Select(String),
+    /// Give the editor keyboard focus.
+    FocusEditor,
+    /// Type text into the focused widget, as one text event.
+    Type(String),
+    /// Press a key with explicit modifiers.
+    Key(egui::Key, egui::Modifiers),
+    /// Press a key with the platform command modifier (`Cmd` or `Ctrl`).
+    Shortcut(egui::Key),
+    /// Save the selected paste now and wait for the backend acknowledgement.
+    Save,
+    /// Run idle frames.
+    Frames(usize),
+}
+
+/// Offscreen [`LocalPasteApp`] driven one frame at a time.
+pub struct GuiHarness {
+    ctx: egui::Context,
+    frame: eframe::Frame,
+    app: LocalPasteApp,
+    timeout: Duration,
+}
+
+impl GuiHarness {
+    /// Opens the database at `db_path` with the embedded server on an
+    /// ephemeral port and runs one frame so the initial list request is sent.
+    ///
+    /// Seed data by writing to the database before opening the harness; the
+    /// app holds the database open until the harness is dropped.
+    ///
+    /// # Returns
+    /// A harness whose app has loaded the paste list.
+    ///
+    /// # Errors
+    /// Returns an error if the database or embedded server cannot be opened.
+    pub fn open(db_path: &str) -> Result<Self, AppError> {
+        let config = Config {
+            db_path: db_path.to_string(),
+            port: 0,
+            max_paste_size: DEFAULT_MAX_PASTE_SIZE,
+            auto_save_interval: DEFAULT_AUTO_SAVE_INTERVAL_MS,
+            auto_backup: false,
+            size_limits: Default::default(),
+            flush_policy: Default::default(),
+            recent_history: false,
+        };
+        let mut app = LocalPasteApp::with_config(config, None, false)?;
+        app.editor_mode = EditorMode::VirtualEditor;
+        let mut harness = Self {
+            ctx: egui::Context::default(),
+            frame: eframe::Frame::_new_kittest(),
+            app,
+            timeout: DEFAULT_STEP_TIMEOUT,
+        };
+        harness.run_frame(Vec::new());
+        Ok(harness)
+    }
+
+    /// Overrides how long steps wait for backend round trips.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Runs one full app update with `events` as that frame's input.
+    pub fn run_frame(&mut self, events: Vec<egui::Event>) {
+        let input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::Pos2::ZERO,
+                egui::vec2(DEFAULT_WINDOW_SIZE[0], DEFAULT_WINDOW_SIZE[1]),
+            )),
+            events,
+            ..Default::default()
+        };
+        let app = &mut self.app;
+        let frame = &mut self.frame;
+        let _ = self.ctx.run(input, |ctx| app.update(ctx, frame));
+    }
+
+    /// Runs idle frames until `done` holds or the step timeout passes.
+    ///
+    /// # Returns
+    /// `true` when `done` held before the timeout.
+    pub fn run_until(&mut self, mut done: impl FnMut(&Self) -> bool) -> bool {
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            self.run_frame(Vec::new());
+            if done(self) {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(IDLE_FRAME_SLEEP);
+        }
+    }
+
+    /// Runs `steps` in order.
+    ///
+    /// # Errors
+    /// Returns a message naming the first step that was rejected or timed out.
+    pub fn run_script(&mut self, steps: &[Step]) -> Result<(), String> {
+        for (index, step) in steps.iter().enumerate() {
+            self.run_step(step)
+                .map_err(|message| format!("step {} ({:?}): {}", index + 1, step, message))?;
+        }
+        Ok(())
+    }
+
+    fn run_step(&mut self, step: &Step) -> Result<(), String> {
+        match step {
+            Step::Select(id) => {
+                if !self.run_until(|harness| harness.paste_ids().contains(&id.as_str())) {
+                    return Err("paste is not listed".to_string());
+                }
+                if !self.app.select_paste(id.clone()) {
+                    return Err("selection was blocked".to_string());
+                }
+                if !self.run_until(|harness| {
+                    harness
+                        .app
+                        .selected_paste
+                        .as_ref()
+                        .is_some_and(|paste| paste.id == *id)
+                }) {
+                    return Err("paste did not load".to_string());
+                }
+            }
+            Step::FocusEditor => {
+                self.app.focus_editor_next = true;
+                if !self.run_until(|harness| harness.editor_focused()) {
+                    return Err("editor did not take focus".to_string());
+                }
+            }
+            Step::Type(text) => self.run_frame(vec![egui::Event::Text(text.clone())]),
+            Step::Key(key, modifiers) => self.run_frame(vec![key_event(*key, *modifiers)]),
+            Step::Shortcut(key) => self.run_frame(vec![key_event(*key, command_modifiers())]),
+            Step::Save => {
+                self.app.save_now();
+                if !self.run_until(|harness| !harness.is_dirty() && !harness.app.save_in_flight) {
+                    return Err("save was not acknowledged".to_string());
+                }
+            }
+            Step::Frames(count) => {
+                for _ in 0..*count {
+                    self.run_frame(Vec::new());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Ids of the pastes shown in the sidebar, in display order.
+    pub fn paste_ids(&self) -> Vec<&str> {
+        self.app
+            .pastes
+            .iter()
+            .map(|paste| paste.id.as_str())
+            .collect()
+    }
+
+    /// Id of the selected paste, if any.
+    pub fn selected_id(&self) -> Option<&str> {
+        self.app.selected_id.as_deref()
+    }
+
+    /// Current editor buffer text.
+    pub fn editor_text(&self) -> String {
+        self.app.active_snapshot()
+    }
+
+    /// Editor cursor position in characters.
+    pub fn cursor(&self) -> usize {
+        self.app.virtual_editor_state.cursor()
+    }
+
+    /// Selected character range in the editor, if any.
+    pub fn selection(&self) -> Option<std::ops::Range<usize>> {
+        self.app.virtual_editor_state.selection_range()
+    }
+
+    /// Whether the editor holds keyboard focus.
+    pub fn editor_focused(&self) -> bool {
+        self.app.virtual_editor_state.has_focus
+    }
+
+    /// Whether the editor has edits not yet sent to the backend.
+    pub fn is_dirty(&self) -> bool {
+        self.app.save_status != SaveStatus::Saved
+    }
+
+    /// Text of the status bar message, if one is showing.
+    pub fn status_text(&self) -> Option<&str> {
+        self.app.status.as_ref().map(|status| status.text.as_str())
+    }
+
+    /// The egui context frames run against, for inspecting memory or output.
+    pub fn ctx(&self) -> &egui::Context {
+        &self.ctx
+    }
+}
+
+/// Modifier state egui reports for `Cmd`/`Ctrl` shortcuts on this platform.
+pub fn command_modifiers() -> egui::Modifiers {
+    #[cfg(target_os = "macos")]
+    {
+        egui::Modifiers {
+            command: true,
+            ..Default::default()
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        egui::Modifiers {
+            ctrl: true,
+            command: true,
+            ..Default::default()
+        }
+    }
+}
+
+fn key_event(key: egui::Key, modifiers: egui::Modifiers) -> egui::Event {
+    egui::Event::Key {
+        key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers,
+    }
+}
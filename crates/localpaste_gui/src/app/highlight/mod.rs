@@ -1,7 +1,8 @@
 //! Syntax highlighting caches and worker support for the native GUI editor.
 
+mod outline;
+mod pruning;
 mod reuse;
-mod syntax;
 #[cfg(test)]
 mod tests;
 mod worker;
@@ -12,6 +13,7 @@ use eframe::egui::{
     Color32, FontId, Stroke,
 };
 use egui_extras::syntax_highlighting::CodeTheme;
+use localpaste_core::lint::LintReport;
 use ropey::Rope;
 use std::ops::Range;
 #[cfg(test)]
@@ -28,10 +30,13 @@ use syntect::parsing::SyntaxSet;
 #[cfg(test)]
 use syntect::util::LinesWithEndings;
 
+pub(super) use localpaste_core::export::syntax::{resolve_syntax, syntect_language_hint};
+#[cfg(test)]
+pub(super) use outline::OutlineSymbolKind;
+pub(super) use outline::{HighlightOutline, OutlineSymbol};
 pub(super) use reuse::{
     align_old_lines_by_hash, hash_bytes, line_hash_matches, line_start_state_matches,
 };
-pub(super) use syntax::{resolve_syntax, syntect_language_hint};
 pub(super) use worker::{spawn_highlight_worker, HighlightWorker};
 
 /// Cached layout state for highlighted editor content.
@@ -548,6 +553,8 @@ where
 pub(super) struct SyntectSettings {
     pub(super) ps: SyntaxSet,
     pub(super) ts: ThemeSet,
+    /// Loaded grammars when `ps` is pruned; `None` means every grammar is loaded.
+    pruned: Option<pruning::PrunedSyntaxes>,
 }
 
 impl Default for SyntectSettings {
@@ -555,6 +562,7 @@ impl Default for SyntectSettings {
         Self {
             ps: SyntaxSet::load_defaults_newlines(),
             ts: ThemeSet::load_defaults(),
+            pruned: None,
         }
     }
 }
@@ -789,6 +797,33 @@ pub(super) struct HighlightRenderLine {
 }
 
 impl HighlightRenderLine {
+    /// Returns byte ranges of highlight tokens on this line.
+    ///
+    /// Includes each span and each run of adjacent spans sharing a style, so a
+    /// string split into quote and body spans still yields one range.
+    ///
+    /// # Returns
+    /// Token byte ranges relative to the line start.
+    pub(super) fn token_byte_ranges(&self) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = Vec::with_capacity(self.spans.len() * 2);
+        let mut run: Option<(Range<usize>, HighlightStyle)> = None;
+        for span in &self.spans {
+            ranges.push(span.range.clone());
+            run = match run {
+                Some((range, style)) if style == span.style && range.end == span.range.start => {
+                    Some((range.start..span.range.end, style))
+                }
+                Some((range, _)) => {
+                    ranges.push(range);
+                    Some((span.range.clone(), span.style))
+                }
+                None => Some((span.range.clone(), span.style)),
+            };
+        }
+        ranges.extend(run.map(|(range, _)| range));
+        ranges
+    }
+
     #[cfg(test)]
     /// Creates a plain (unhighlighted) render line for tests.
     ///
@@ -843,11 +878,23 @@ pub(super) struct HighlightPatch {
     pub(super) lines: Vec<HighlightRenderLine>,
 }
 
-/// Worker output event carrying either full-highlight render or range patch.
+/// Syntax check of a JSON/YAML/TOML buffer snapshot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HighlightLint {
+    pub(crate) paste_id: String,
+    pub(crate) revision: u64,
+    /// `None` when the snapshot's language has no validity check.
+    pub(crate) report: Option<LintReport>,
+}
+
+/// Worker output event carrying a full-highlight render, a range patch, the
+/// symbol outline, or the syntax check for the latest snapshot.
 #[derive(Clone)]
 pub(super) enum HighlightWorkerResult {
     Render(HighlightRender),
     Patch(HighlightPatch),
+    Outline(HighlightOutline),
+    Lint(HighlightLint),
 }
 
 impl HighlightRender {
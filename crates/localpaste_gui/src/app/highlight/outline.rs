@@ -0,0 +1,240 @@
+//! Symbol outline derived from syntect scopes on the highlight worker.
+//!
+//! Each highlighted line records the named symbols its scopes expose
+//! (functions, types, markdown headings, mapping keys). The worker caches
+//! those per line next to the spans, so reused lines never re-scan and the
+//! outline is rebuilt from the cache after every pass.
+
+use std::sync::OnceLock;
+use syntect::easy::ScopeRangeIterator;
+use syntect::parsing::{Scope, ScopeStack, ScopeStackOp};
+
+/// Upper bound on outline entries sent to the UI per snapshot.
+const MAX_OUTLINE_SYMBOLS: usize = 5_000;
+/// Mapping keys nested deeper than this are left out of the outline.
+const MAX_KEY_DEPTH: usize = 2;
+/// Upper bound on symbols recorded for one line (minified JSON, etc.).
+const MAX_SYMBOLS_PER_LINE: usize = 16;
+
+/// Category of an outline entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum OutlineSymbolKind {
+    Function,
+    Type,
+    Heading,
+    Key,
+}
+
+impl OutlineSymbolKind {
+    /// Short label rendered next to the symbol name.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Function => "fn",
+            Self::Type => "type",
+            Self::Heading => "#",
+            Self::Key => "key",
+        }
+    }
+}
+
+/// Symbol found on a single line, cached with the worker line state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct LineSymbol {
+    kind: OutlineSymbolKind,
+    name: String,
+    /// Heading level for headings; leading indentation columns otherwise.
+    level: usize,
+}
+
+/// Outline entry with its zero-based line and nesting depth.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct OutlineSymbol {
+    pub(crate) kind: OutlineSymbolKind,
+    pub(crate) name: String,
+    pub(crate) line: usize,
+    pub(crate) depth: usize,
+}
+
+/// Outline for one buffer snapshot, matched to renders by revision.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct HighlightOutline {
+    pub(crate) paste_id: String,
+    pub(crate) revision: u64,
+    pub(crate) text_len: usize,
+    pub(crate) symbols: Vec<OutlineSymbol>,
+}
+
+struct OutlineScopes {
+    function: Scope,
+    types: [Scope; 8],
+    heading: Scope,
+    keys: [Scope; 3],
+}
+
+fn outline_scopes() -> &'static OutlineScopes {
+    static SCOPES: OnceLock<OutlineScopes> = OnceLock::new();
+    SCOPES.get_or_init(|| {
+        let scope = |name: &str| Scope::new(name).expect("static outline scope");
+        OutlineScopes {
+            function: scope("entity.name.function"),
+            types: [
+                scope("entity.name.class"),
+                scope("entity.name.struct"),
+                scope("entity.name.enum"),
+                scope("entity.name.union"),
+                scope("entity.name.trait"),
+                scope("entity.name.interface"),
+                scope("entity.name.impl"),
+                scope("entity.name.type"),
+            ],
+            heading: scope("entity.name.section"),
+            keys: [
+                scope("meta.mapping.key"),
+                scope("meta.structure.dictionary.key"),
+                scope("entity.name.tag.yaml"),
+            ],
+        }
+    })
+}
+
+fn classify(stack: &ScopeStack) -> Option<OutlineSymbolKind> {
+    let scopes = outline_scopes();
+    // Innermost scope wins so a function name inside a key is still a function.
+    stack.as_slice().iter().rev().find_map(|scope| {
+        if scopes.function.is_prefix_of(*scope) {
+            Some(OutlineSymbolKind::Function)
+        } else if scopes.types.iter().any(|ty| ty.is_prefix_of(*scope)) {
+            Some(OutlineSymbolKind::Type)
+        } else if scopes.heading.is_prefix_of(*scope) {
+            Some(OutlineSymbolKind::Heading)
+        } else if scopes.keys.iter().any(|key| key.is_prefix_of(*scope)) {
+            Some(OutlineSymbolKind::Key)
+        } else {
+            None
+        }
+    })
+}
+
+fn indent_columns(line: &str) -> usize {
+    line.chars()
+        .take_while(|ch| *ch == ' ' || *ch == '\t')
+        .map(|ch| if ch == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+fn heading_level(line: &str) -> usize {
+    line.trim_start()
+        .chars()
+        .take_while(|ch| *ch == '#')
+        .count()
+        .max(1)
+}
+
+fn push_symbol(out: &mut Vec<LineSymbol>, kind: OutlineSymbolKind, raw: &str, line: &str) {
+    let name = raw.trim().trim_matches(|ch| ch == '"' || ch == '\'').trim();
+    if name.is_empty() || out.len() >= MAX_SYMBOLS_PER_LINE {
+        return;
+    }
+    let level = if kind == OutlineSymbolKind::Heading {
+        heading_level(line)
+    } else {
+        indent_columns(line)
+    };
+    out.push(LineSymbol {
+        kind,
+        name: name.to_string(),
+        level,
+    });
+}
+
+/// Collects the named symbols on one parsed line.
+///
+/// # Arguments
+/// - `ops`: Parser scope operations for `line`.
+/// - `line`: Line text, including any trailing newline.
+/// - `start_stack`: Scope stack in effect at the start of the line.
+///
+/// # Returns
+/// Symbols in line order; adjacent tokens of the same kind are joined.
+pub(super) fn line_symbols(
+    ops: &[(usize, ScopeStackOp)],
+    line: &str,
+    start_stack: &ScopeStack,
+) -> Vec<LineSymbol> {
+    let mut stack = start_stack.clone();
+    let mut symbols = Vec::new();
+    let mut current: Option<(OutlineSymbolKind, std::ops::Range<usize>)> = None;
+    for (range, op) in ScopeRangeIterator::new(ops, line) {
+        if stack.apply(op).is_err() {
+            break;
+        }
+        if range.is_empty() {
+            continue;
+        }
+        let kind = classify(&stack);
+        current = match (current, kind) {
+            (Some((open, span)), Some(kind)) if open == kind && span.end == range.start => {
+                Some((kind, span.start..range.end))
+            }
+            (previous, kind) => {
+                if let Some((open, span)) = previous {
+                    push_symbol(&mut symbols, open, &line[span], line);
+                }
+                kind.map(|kind| (kind, range))
+            }
+        };
+    }
+    if let Some((open, span)) = current {
+        push_symbol(&mut symbols, open, &line[span], line);
+    }
+    symbols
+}
+
+/// Builds the snapshot outline from cached per-line symbols.
+///
+/// Headings nest by level. Other symbols nest by indentation relative to the
+/// enclosing symbols, and deeply nested mapping keys are dropped.
+///
+/// # Returns
+/// Outline capped at [`MAX_OUTLINE_SYMBOLS`] entries.
+pub(super) fn assemble_outline<'a>(
+    paste_id: String,
+    revision: u64,
+    text_len: usize,
+    lines: impl Iterator<Item = &'a [LineSymbol]>,
+) -> HighlightOutline {
+    let mut symbols = Vec::new();
+    let mut indents: Vec<usize> = Vec::new();
+    'lines: for (line, line_symbols) in lines.enumerate() {
+        for symbol in line_symbols {
+            let depth = if symbol.kind == OutlineSymbolKind::Heading {
+                symbol.level.saturating_sub(1)
+            } else {
+                while indents.last().is_some_and(|indent| *indent >= symbol.level) {
+                    indents.pop();
+                }
+                let depth = indents.len();
+                indents.push(symbol.level);
+                depth
+            };
+            if symbol.kind == OutlineSymbolKind::Key && depth > MAX_KEY_DEPTH {
+                continue;
+            }
+            if symbols.len() >= MAX_OUTLINE_SYMBOLS {
+                break 'lines;
+            }
+            symbols.push(OutlineSymbol {
+                kind: symbol.kind,
+                name: symbol.name.clone(),
+                line,
+                depth,
+            });
+        }
+    }
+    HighlightOutline {
+        paste_id,
+        revision,
+        text_len,
+        symbols,
+    }
+}
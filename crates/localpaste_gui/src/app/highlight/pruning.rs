@@ -0,0 +1,126 @@
+//! Optional grammar pruning that keeps only the languages stored in the DB.
+//!
+//! A pruned set still lists every bundled grammar, but grammars outside the
+//! kept set are stubs with empty contexts. Stubs keep their names, extensions,
+//! and context names, so resolution works unchanged and linked context ids in
+//! the kept grammars stay valid. When a request resolves to a stub, the set is
+//! rebuilt with that grammar loaded.
+//!
+//! `build.rs` dumps each bundled grammar separately, plus every grammar as a
+//! stub, so building a pruned set only deserializes the kept grammars and
+//! never materializes the full bundled set.
+
+use super::{resolve_syntax, syntect_language_hint, SyntectSettings};
+use std::collections::BTreeSet;
+use syntect::dumps::from_binary;
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::syntax_definition::SyntaxDefinition;
+use syntect::parsing::{SyntaxSet, SyntaxSetBuilder};
+
+mod dumps {
+    include!(concat!(env!("OUT_DIR"), "/syntax_dumps.rs"));
+}
+
+/// Grammars loaded with `name` because it commonly embeds them.
+fn companion_syntaxes(name: &str) -> &'static [&'static str] {
+    match name {
+        "HTML" | "PHP" => &["JavaScript", "CSS"],
+        "Markdown" => &["HTML", "JavaScript", "CSS"],
+        _ => &[],
+    }
+}
+
+/// Grammar bookkeeping for a pruned syntax set.
+#[derive(Debug, Default)]
+pub(super) struct PrunedSyntaxes {
+    loaded: BTreeSet<String>,
+}
+
+impl PrunedSyntaxes {
+    fn insert_with_companions(&mut self, name: &str) -> bool {
+        let inserted = self.loaded.insert(name.to_string());
+        for companion in companion_syntaxes(name) {
+            self.loaded.insert((*companion).to_string());
+        }
+        inserted
+    }
+}
+
+/// Rebuilds the bundled set with grammars outside `keep` replaced by stubs.
+fn build_pruned_set(keep: &BTreeSet<String>) -> SyntaxSet {
+    let stubs: Vec<SyntaxDefinition> = from_binary(dumps::SYNTAX_STUBS);
+    let mut builder = SyntaxSetBuilder::new();
+    for (stub, dump) in stubs.into_iter().zip(dumps::SYNTAX_DUMPS) {
+        if stub.name == "Plain Text" || keep.contains(stub.name.as_str()) {
+            builder.add(from_binary(dump));
+        } else {
+            builder.add(stub);
+        }
+    }
+    builder.build()
+}
+
+impl SyntectSettings {
+    /// Loads only the grammars for `languages` (plus common embeds).
+    ///
+    /// # Arguments
+    /// - `languages`: Stored paste languages, e.g. from `PasteDb::language_counts`.
+    ///
+    /// # Returns
+    /// Settings whose other grammars load on demand through [`Self::ensure_loaded`].
+    pub(super) fn pruned_for_languages<'a>(languages: impl IntoIterator<Item = &'a str>) -> Self {
+        // Stubs keep names and extensions, so they resolve like the full set.
+        let stubs = build_pruned_set(&BTreeSet::new());
+        let mut pruned = PrunedSyntaxes::default();
+        for language in languages {
+            let syntax = resolve_syntax(&stubs, syntect_language_hint(language).as_str());
+            pruned.insert_with_companions(syntax.name.as_str());
+        }
+        drop(stubs);
+        Self {
+            ps: build_pruned_set(&pruned.loaded),
+            ts: ThemeSet::load_defaults(),
+            pruned: Some(pruned),
+        }
+    }
+
+    /// Makes sure the grammar for `hint` is loaded, rebuilding a pruned set if needed.
+    ///
+    /// # Returns
+    /// `true` when the set was rebuilt, so cached parse states are stale.
+    pub(super) fn ensure_loaded(&mut self, hint: &str) -> bool {
+        let Some(pruned) = self.pruned.as_mut() else {
+            return false;
+        };
+        let name = resolve_syntax(&self.ps, hint).name.clone();
+        if name == "Plain Text" || !pruned.insert_with_companions(name.as_str()) {
+            return false;
+        }
+        self.ps = build_pruned_set(&pruned.loaded);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_pruned_set, dumps};
+    use std::collections::BTreeSet;
+    use syntect::parsing::SyntaxSet;
+
+    #[test]
+    fn per_syntax_dumps_match_the_bundled_set() {
+        let full = SyntaxSet::load_defaults_newlines();
+        assert_eq!(dumps::SYNTAX_DUMPS.len(), full.syntaxes().len());
+
+        let keep = BTreeSet::from(["Rust".to_string()]);
+        let pruned = build_pruned_set(&keep);
+        let names = |set: &SyntaxSet| -> Vec<String> {
+            set.syntaxes()
+                .iter()
+                .map(|syntax| syntax.name.clone())
+                .collect()
+        };
+        assert_eq!(names(&pruned), names(&full));
+        assert!(pruned.find_syntax_by_extension("py").is_some());
+    }
+}
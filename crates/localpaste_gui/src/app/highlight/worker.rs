@@ -1,12 +1,15 @@
 //! Background syntect worker lifecycle and tests.
 
 use super::super::util::env_flag_enabled;
+use super::outline::{assemble_outline, line_symbols, HighlightOutline, LineSymbol};
 use super::{
     align_old_lines_by_hash, hash_bytes, line_hash_matches, line_start_state_matches,
-    resolve_syntax, HighlightPatch, HighlightRender, HighlightRenderLine, HighlightRequest,
-    HighlightSpan, HighlightStateSnapshot, HighlightStyle, HighlightWorkerResult, SyntectSettings,
+    resolve_syntax, HighlightLint, HighlightPatch, HighlightRender, HighlightRenderLine,
+    HighlightRequest, HighlightSpan, HighlightStateSnapshot, HighlightStyle, HighlightWorkerResult,
+    SyntectSettings,
 };
 use crossbeam_channel::{Receiver, Sender};
+use localpaste_core::lint::{lint_content, lint_language};
 use std::ops::Range;
 use std::thread;
 use std::time::Instant;
@@ -35,17 +38,22 @@ struct HighlightWorkerLine {
     hash: u64,
     len: usize,
     spans: Vec<HighlightSpan>,
+    symbols: Vec<LineSymbol>,
     end_state: HighlightStateSnapshot,
 }
 
 /// Spawns the syntect worker thread and returns its channel endpoints.
 ///
+/// # Arguments
+/// - `pruned_languages`: Languages to preload when grammar pruning is enabled;
+///   `None` loads every bundled grammar.
+///
 /// # Returns
 /// Worker handle containing request/response channels.
 ///
 /// # Panics
 /// Panics if the highlight thread cannot be spawned.
-pub(crate) fn spawn_highlight_worker() -> HighlightWorker {
+pub(crate) fn spawn_highlight_worker(pruned_languages: Option<Vec<String>>) -> HighlightWorker {
     let (tx, rx_cmd) = crossbeam_channel::unbounded();
     let (tx_evt, rx_evt) = crossbeam_channel::unbounded();
     let trace_enabled = env_flag_enabled("LOCALPASTE_HIGHLIGHT_TRACE");
@@ -53,20 +61,54 @@ pub(crate) fn spawn_highlight_worker() -> HighlightWorker {
     thread::Builder::new()
         .name("localpaste-gui-highlight".to_string())
         .spawn(move || {
-            let settings = SyntectSettings::default();
+            let mut settings = match pruned_languages {
+                Some(languages) => {
+                    SyntectSettings::pruned_for_languages(languages.iter().map(String::as_str))
+                }
+                None => SyntectSettings::default(),
+            };
             let mut cache = HighlightWorkerCache::default();
+            let mut last_outline: Option<HighlightOutline> = None;
+            let mut last_lint: Option<HighlightLint> = None;
             for req in rx_cmd.iter() {
                 let mut latest: HighlightRequest = req;
                 // Coalesce backlog bursts so stale highlight work is skipped.
                 while let Ok(next) = rx_cmd.try_recv() {
                     latest = next;
                 }
+                if settings.ensure_loaded(latest.language_hint.as_str()) {
+                    // Cached parse states may reference a stub grammar.
+                    cache = HighlightWorkerCache::default();
+                }
                 let started = Instant::now();
                 let trace_paste_id = latest.paste_id.clone();
                 let trace_revision = latest.revision;
                 let trace_len = latest.text.len_bytes();
+                let lint_input = lint_language(Some(latest.language_hint.as_str()))
+                    .map(|language| (language, latest.text.clone()));
                 let render = highlight_in_worker(&settings, &mut cache, latest);
                 let _ = tx_evt.send(render);
+                let outline = outline_from_cache(&cache, trace_paste_id.clone());
+                if last_outline.as_ref() != Some(&outline) {
+                    let _ = tx_evt.send(HighlightWorkerResult::Outline(outline.clone()));
+                    last_outline = Some(outline);
+                }
+                let lint = HighlightLint {
+                    paste_id: trace_paste_id.clone(),
+                    revision: trace_revision,
+                    report: lint_input.and_then(|(language, text)| {
+                        lint_content(Some(language), text.into_string().as_str())
+                    }),
+                };
+                // Unlintable snapshots only need one clearing event.
+                let unchanged_unlintable = lint.report.is_none()
+                    && last_lint.as_ref().is_some_and(|last| {
+                        last.report.is_none() && last.paste_id == lint.paste_id
+                    });
+                if !unchanged_unlintable {
+                    let _ = tx_evt.send(HighlightWorkerResult::Lint(lint.clone()));
+                    last_lint = Some(lint);
+                }
                 if trace_enabled {
                     let elapsed_ms = started.elapsed().as_secs_f32() * 1000.0;
                     info!(
@@ -97,15 +139,27 @@ fn worker_lines_to_render_lines(lines: &[HighlightWorkerLine]) -> Vec<HighlightR
     lines.iter().map(worker_line_to_render_line).collect()
 }
 
+/// Builds the outline for the snapshot currently held in `cache`.
+fn outline_from_cache(cache: &HighlightWorkerCache, paste_id: String) -> HighlightOutline {
+    assemble_outline(
+        paste_id,
+        cache.last_revision.unwrap_or_default(),
+        cache.last_text_len.unwrap_or_default(),
+        cache.lines.iter().map(|line| line.symbols.as_slice()),
+    )
+}
+
 fn highlight_line_spans(
     settings: &SyntectSettings,
     highlighter: &Highlighter<'_>,
     parse_state: &mut ParseState,
     highlight_state: &mut HighlightState,
     line: &str,
-) -> Vec<HighlightSpan> {
+) -> (Vec<HighlightSpan>, Vec<LineSymbol>) {
     let mut spans = Vec::new();
+    let mut symbols = Vec::new();
     if let Ok(ops) = parse_state.parse_line(line, &settings.ps) {
+        symbols = line_symbols(&ops, line, &highlight_state.path);
         let iter = syntect::highlighting::RangedHighlightIterator::new(
             highlight_state,
             &ops[..],
@@ -135,7 +189,7 @@ fn highlight_line_spans(
             });
         }
     }
-    spans
+    (spans, symbols)
 }
 
 fn changed_line_range_for_render(
@@ -295,7 +349,7 @@ fn highlight_in_worker(
                 changed_start = Some(idx);
             }
             changed_end = idx;
-            let spans = highlight_line_spans(
+            let (spans, symbols) = highlight_line_spans(
                 settings,
                 &highlighter,
                 &mut parse_state,
@@ -310,6 +364,7 @@ fn highlight_in_worker(
                 hash: line_hash,
                 len: line.len(),
                 spans,
+                symbols,
                 end_state,
             });
             idx = idx.saturating_add(1);
@@ -347,7 +402,7 @@ fn highlight_in_worker(
                 changed_start = Some(idx);
             }
             changed_end = idx;
-            let spans = highlight_line_spans(
+            let (spans, symbols) = highlight_line_spans(
                 settings,
                 &highlighter,
                 &mut parse_state,
@@ -362,6 +417,7 @@ fn highlight_in_worker(
                 hash: line_hash,
                 len: line.len(),
                 spans,
+                symbols,
                 end_state,
             });
             prev_line_reused = false;
@@ -421,10 +477,11 @@ fn highlight_in_worker(
 
 #[cfg(test)]
 mod resolver_tests {
+    use super::super::outline::OutlineSymbolKind;
     use super::super::{resolve_syntax, syntect_language_hint, HighlightRequestText};
     use super::{
-        highlight_in_worker, HighlightRender, HighlightRequest, HighlightWorkerResult,
-        SyntectSettings,
+        highlight_in_worker, outline_from_cache, HighlightRender, HighlightRequest,
+        HighlightWorkerResult, SyntectSettings,
     };
     use crate::app::highlight::worker::HighlightWorkerCache;
 
@@ -442,7 +499,7 @@ mod resolver_tests {
         };
         match highlight_in_worker(settings, &mut cache, req) {
             HighlightWorkerResult::Render(render) => render,
-            HighlightWorkerResult::Patch(_) => panic!("expected full render for cold worker cache"),
+            _ => panic!("expected full render for cold worker cache"),
         }
     }
 
@@ -628,4 +685,85 @@ mod resolver_tests {
         let out = highlight_in_worker(&settings, &mut cache, req);
         assert!(matches!(out, HighlightWorkerResult::Render(_)));
     }
+
+    #[test]
+    fn pruned_settings_stub_unused_grammars_and_load_them_on_demand() {
+        let mut settings = SyntectSettings::pruned_for_languages(["rust"]);
+        let rust = render_for_label(&settings, "rust", "fn main() { let x = 1; }\n");
+        assert!(has_non_default_coloring(&settings, &rust));
+
+        let python_text = "def main():\n    return 'x'\n";
+        let stubbed = render_for_label(&settings, "python", python_text);
+        assert!(!has_non_default_coloring(&settings, &stubbed));
+
+        assert!(settings.ensure_loaded("python"));
+        assert!(!settings.ensure_loaded("python"));
+        let loaded = render_for_label(&settings, "python", python_text);
+        assert!(has_non_default_coloring(&settings, &loaded));
+        let rust = render_for_label(&settings, "rust", "fn main() { let x = 1; }\n");
+        assert!(has_non_default_coloring(&settings, &rust));
+    }
+
+    fn outline_for_label(
+        label: &str,
+        text: &str,
+    ) -> Vec<(OutlineSymbolKind, String, usize, usize)> {
+        let settings = SyntectSettings::default();
+        let mut cache = HighlightWorkerCache::default();
+        let mut req = rust_request(1, text, None, None);
+        req.language_hint = syntect_language_hint(label);
+        highlight_in_worker(&settings, &mut cache, req);
+        outline_from_cache(&cache, "test".to_string())
+            .symbols
+            .into_iter()
+            .map(|symbol| (symbol.kind, symbol.name, symbol.line, symbol.depth))
+            .collect()
+    }
+
+    #[test]
+    fn outline_lists_code_symbols_headings_and_keys_with_depth() {
+        use OutlineSymbolKind::{Function, Heading, Key, Type};
+        let rust = "struct A;\nimpl A {\n    fn new() -> Self { A }\n}\nfn main() {}\n";
+        assert_eq!(
+            outline_for_label("rust", rust),
+            vec![
+                (Type, "A".to_string(), 0, 0),
+                (Type, "A".to_string(), 1, 0),
+                (Function, "new".to_string(), 2, 1),
+                (Function, "main".to_string(), 4, 0),
+            ]
+        );
+        let python = "class A:\n    def run(self):\n        pass\n\ndef main():\n    pass\n";
+        assert_eq!(
+            outline_for_label("python", python),
+            vec![
+                (Type, "A".to_string(), 0, 0),
+                (Function, "run".to_string(), 1, 1),
+                (Function, "main".to_string(), 4, 0),
+            ]
+        );
+        let markdown = "# Title\n\ntext\n\n## Part one\n\n### Deep\n";
+        assert_eq!(
+            outline_for_label("markdown", markdown),
+            vec![
+                (Heading, "Title".to_string(), 0, 0),
+                (Heading, "Part one".to_string(), 4, 1),
+                (Heading, "Deep".to_string(), 6, 2),
+            ]
+        );
+        let json = "{\n  \"name\": \"x\",\n  \"nested\": {\n    \"inner\": 1\n  }\n}\n";
+        let yaml = "name: x\nnested:\n  inner: 1\n";
+        for (label, text, first_line) in [("json", json, 1), ("yaml", yaml, 0)] {
+            assert_eq!(
+                outline_for_label(label, text),
+                vec![
+                    (Key, "name".to_string(), first_line, 0),
+                    (Key, "nested".to_string(), first_line + 1, 0),
+                    (Key, "inner".to_string(), first_line + 2, 1),
+                ],
+                "label: {label}"
+            );
+        }
+        assert!(outline_for_label("text", "fn main() {}\n").is_empty());
+    }
 }
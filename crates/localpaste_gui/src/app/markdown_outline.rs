@@ -0,0 +1,226 @@
+//! Markdown heading outline kept in step with virtual-editor edits.
+//!
+//! Only structural lines (ATX headings and code fences) are stored, keyed by
+//! line. Edits splice the touched line span using the rope edit delta and
+//! shift later entries, so typing never rescans the whole buffer. Fence
+//! pairing is resolved when headings are listed, which keeps `#` comments in
+//! fenced code out of the outline. Setext (`===` underline) headings are not
+//! recognized.
+
+use super::virtual_editor::{RopeBuffer, VirtualEditDelta};
+use super::LocalPasteApp;
+
+/// Heading shown in the properties drawer outline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct MarkdownHeading {
+    pub(super) line: usize,
+    pub(super) level: usize,
+    pub(super) text: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum StructuralLine {
+    Fence(char),
+    Heading { level: usize, text: String },
+}
+
+/// Sparse per-line index of markdown headings and code fences.
+#[derive(Debug, Default)]
+pub(super) struct MarkdownOutlineIndex {
+    /// Buffer revision and char length the entries describe.
+    synced: Option<(u64, usize)>,
+    entries: Vec<(usize, StructuralLine)>,
+    scratch: String,
+}
+
+fn classify_line(line: &str) -> Option<StructuralLine> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let trimmed = &line[indent..];
+    for fence in ['`', '~'] {
+        if trimmed.chars().take_while(|ch| *ch == fence).count() >= 3 {
+            return Some(StructuralLine::Fence(fence));
+        }
+    }
+    let level = trimmed.chars().take_while(|ch| *ch == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    let text = rest.trim().trim_end_matches('#').trim_end();
+    Some(StructuralLine::Heading {
+        level,
+        text: text.to_string(),
+    })
+}
+
+impl MarkdownOutlineIndex {
+    /// Drops all entries so the next [`Self::ensure_synced`] rebuilds.
+    pub(super) fn reset(&mut self) {
+        self.synced = None;
+        self.entries.clear();
+    }
+
+    /// Returns whether the index describes the buffer's current revision.
+    pub(super) fn is_synced_to(&self, buffer: &RopeBuffer) -> bool {
+        self.synced == Some((buffer.revision(), buffer.len_chars()))
+    }
+
+    fn scan_lines(
+        &mut self,
+        buffer: &RopeBuffer,
+        lines: std::ops::Range<usize>,
+    ) -> Vec<(usize, StructuralLine)> {
+        let mut found = Vec::new();
+        for line in lines {
+            buffer.line_without_newline_into(line, &mut self.scratch);
+            if let Some(kind) = classify_line(self.scratch.as_str()) {
+                found.push((line, kind));
+            }
+        }
+        found
+    }
+
+    /// Rebuilds the index from scratch when it is not synced to `buffer`.
+    pub(super) fn ensure_synced(&mut self, buffer: &RopeBuffer) {
+        if self.is_synced_to(buffer) {
+            return;
+        }
+        self.entries = self.scan_lines(buffer, 0..buffer.line_count());
+        self.synced = Some((buffer.revision(), buffer.len_chars()));
+    }
+
+    /// Splices one edit into an index that was synced before the edit.
+    ///
+    /// # Arguments
+    /// - `delta`: Line span reported by the rope mutation.
+    /// - `buffer`: Post-edit buffer.
+    pub(super) fn apply_delta(&mut self, delta: VirtualEditDelta, buffer: &RopeBuffer) {
+        let first_kept = self
+            .entries
+            .partition_point(|(line, _)| *line < delta.start_line);
+        let tail_start = self
+            .entries
+            .partition_point(|(line, _)| *line <= delta.old_end_line);
+        let shift = delta.new_end_line as isize - delta.old_end_line as isize;
+        let mut tail = self.entries.split_off(tail_start);
+        for (line, _) in &mut tail {
+            *line = (*line as isize + shift).max(0) as usize;
+        }
+        self.entries.truncate(first_kept);
+        let rescan_end = delta
+            .new_end_line
+            .saturating_add(1)
+            .min(buffer.line_count());
+        let rescanned = self.scan_lines(buffer, delta.start_line..rescan_end);
+        self.entries.extend(rescanned);
+        self.entries.extend(tail);
+        self.synced = Some((buffer.revision(), buffer.len_chars()));
+    }
+
+    /// Lists headings outside fenced code blocks, in line order.
+    pub(super) fn headings(&self) -> Vec<MarkdownHeading> {
+        let mut open_fence: Option<char> = None;
+        let mut headings = Vec::new();
+        for (line, kind) in &self.entries {
+            match (kind, open_fence) {
+                (StructuralLine::Fence(fence), None) => open_fence = Some(*fence),
+                (StructuralLine::Fence(fence), Some(open)) if *fence == open => open_fence = None,
+                (StructuralLine::Heading { level, text }, None) => headings.push(MarkdownHeading {
+                    line: *line,
+                    level: *level,
+                    text: text.clone(),
+                }),
+                _ => {}
+            }
+        }
+        headings
+    }
+}
+
+impl LocalPasteApp {
+    /// Returns whether the selected paste is edited as markdown.
+    pub(super) fn markdown_outline_eligible(&self) -> bool {
+        self.is_virtual_editor_mode()
+            && self
+                .edit_language
+                .as_deref()
+                .map(localpaste_core::detection::canonical::canonicalize)
+                .is_some_and(|language| language == "markdown")
+    }
+
+    /// Returns the current markdown headings, rebuilding the index if stale.
+    pub(super) fn markdown_headings(&mut self) -> Vec<MarkdownHeading> {
+        self.markdown_outline
+            .ensure_synced(&self.virtual_editor_buffer);
+        self.markdown_outline.headings()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heading_lines(index: &MarkdownOutlineIndex) -> Vec<(usize, usize, String)> {
+        index
+            .headings()
+            .into_iter()
+            .map(|heading| (heading.line, heading.level, heading.text))
+            .collect()
+    }
+
+    #[test]
+    fn headings_skip_fenced_code_and_non_heading_hashes() {
+        let buffer = RopeBuffer::new(
+            "# Title\n#hashtag\n```sh\n# comment\n```\n  ## Setup ##\n~~~\n# also code\n",
+        );
+        let mut index = MarkdownOutlineIndex::default();
+        index.ensure_synced(&buffer);
+        assert_eq!(
+            heading_lines(&index),
+            vec![(0, 1, "Title".to_string()), (5, 2, "Setup".to_string())]
+        );
+    }
+
+    #[test]
+    fn incremental_edits_match_a_full_rebuild() {
+        let mut buffer = RopeBuffer::new("# One\ntext\n## Two\nmore\n### Three\n");
+        let mut index = MarkdownOutlineIndex::default();
+        index.ensure_synced(&buffer);
+
+        let edits: [(std::ops::Range<usize>, &str); 4] = [
+            (6..6, "## Inserted\n\n"),
+            (0..6, ""),
+            (0..0, "```\n"),
+            (0..4, "# Back\n"),
+        ];
+        for (range, text) in edits {
+            let delta = buffer
+                .replace_char_range(range, text)
+                .expect("edit applies");
+            index.apply_delta(delta, &buffer);
+            assert!(index.is_synced_to(&buffer));
+            let mut rebuilt = MarkdownOutlineIndex::default();
+            rebuilt.ensure_synced(&buffer);
+            assert_eq!(
+                heading_lines(&index),
+                heading_lines(&rebuilt),
+                "after {text:?}"
+            );
+        }
+        assert_eq!(
+            heading_lines(&index),
+            vec![
+                (0, 1, "Back".to_string()),
+                (1, 2, "Inserted".to_string()),
+                (4, 2, "Two".to_string()),
+                (6, 3, "Three".to_string()),
+            ]
+        );
+    }
+}
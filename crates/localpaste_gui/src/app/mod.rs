@@ -1,18 +1,28 @@
 //! Native egui app skeleton for the LocalPaste rewrite.
 
+mod collab;
 mod editor;
+#[cfg(feature = "test-harness")]
+pub mod harness;
 mod highlight;
 mod highlight_flow;
 mod interaction_helpers;
+mod markdown_outline;
 mod paste_intent;
 mod perf_trace;
+mod presence;
+mod selection_expand;
+mod selection_stats;
 mod shutdown;
+mod sidebar_groups;
+mod sidebar_preview;
 mod state_accessors;
 mod state_cache;
 mod state_feedback;
 mod state_ops;
 mod style;
 mod text_coords;
+mod text_transforms;
 mod ui;
 mod util;
 mod version_ui;
@@ -37,10 +47,16 @@ pub(super) use interaction_helpers::{
     next_virtual_click_count, non_focusable_click_sense, paint_virtual_selection_overlay,
     should_consume_virtual_editor_focus_keys, should_route_sidebar_arrows, VirtualCommandBucket,
 };
+use localpaste_core::indentation::IndentStyle;
 use localpaste_core::models::paste::Paste;
+use localpaste_core::timestamps::DisplayTimezone;
 use localpaste_core::{Config, Database};
+use localpaste_server::presence::PresenceTracker;
 use localpaste_server::{AppState, EmbeddedServer, LockOwnerId, PasteLockManager};
 use perf_trace::VirtualInputPerfStats;
+use selection_stats::SelectionStatsCache;
+use sidebar_groups::SidebarGroupState;
+use sidebar_preview::SidebarPreviewCache;
 use std::collections::VecDeque;
 use std::net::SocketAddr;
 use std::ops::Range;
@@ -82,6 +98,8 @@ pub(crate) struct LocalPasteApp {
     search_focus_requested: bool,
     active_collection: SidebarCollection,
     active_language_filter: Option<String>,
+    sidebar_group_state: SidebarGroupState,
+    sidebar_previews: SidebarPreviewCache,
     properties_drawer_open: bool,
     command_palette_open: bool,
     command_palette_query: String,
@@ -100,6 +118,10 @@ pub(crate) struct LocalPasteApp {
     virtual_editor_buffer: RopeBuffer,
     virtual_editor_state: VirtualEditorState,
     virtual_editor_history: VirtualEditorHistory,
+    indent_style: IndentStyle,
+    selection_stats: SelectionStatsCache,
+    /// Selections visited by `Alt+Up`, ending with the current one, for `Alt+Down`.
+    selection_expand_history: Vec<std::ops::Range<usize>>,
     virtual_layout: WrapLayoutCache,
     virtual_galley_cache: VirtualGalleyCache,
     virtual_line_scratch: String,
@@ -128,9 +150,14 @@ pub(crate) struct LocalPasteApp {
     db_path: String,
     locks: Arc<PasteLockManager>,
     lock_owner_id: LockOwnerId,
+    /// Viewers announced through the embedded API.
+    presence_tracker: Arc<PresenceTracker>,
+    presence: presence::PresenceSnapshot,
     _server: EmbeddedServer,
     server_addr: SocketAddr,
-    server_used_fallback: bool,
+    /// Requested address that was in use when the embedded API fell back.
+    server_fallback_from: Option<SocketAddr>,
+    server_port_pinned: bool,
     status: Option<StatusMessage>,
     toasts: VecDeque<ToastMessage>,
     export_result_rx: Option<mpsc::Receiver<ExportCompletion>>,
@@ -138,8 +165,29 @@ pub(crate) struct LocalPasteApp {
     last_edit_at: Option<Instant>,
     save_in_flight: bool,
     save_request_revision: Option<u64>,
+    collab: collab::CollabSession,
     autosave_delay: Duration,
     shortcut_help_open: bool,
+    scratchpad: ui::scratchpad::ScratchpadState,
+    drafts: ui::drafts::DraftState,
+    backups: ui::backups::BackupsState,
+    storage_alerts: ui::storage_alerts::StorageAlertsState,
+    /// Timezone for absolute timestamps, from `display.timezone` in settings.
+    display_timezone: DisplayTimezone,
+    updates: ui::update_notice::UpdateNoticeState,
+    import_wizard: ui::import_wizard::ImportWizardState,
+    split_dialog: ui::split_dialog::SplitDialogState,
+    merge_dialog: ui::merge_dialog::MergeDialogState,
+    comments: ui::comments::CommentsState,
+    folder_rules: ui::folder_rules::FolderRulesState,
+    content_lint: ui::content_lint::ContentLintState,
+    annotations: ui::annotations::AnnotationsState,
+    line_links: ui::line_links::LineLinkState,
+    search_matches: ui::search_matches::SearchMatchState,
+    close_prompt: shutdown::ClosePromptState,
+    writing_mode: ui::writing_mode::WritingModeState,
+    symbol_outline: ui::symbol_outline::SymbolOutlineState,
+    markdown_outline: markdown_outline::MarkdownOutlineIndex,
     focus_editor_next: bool,
     style_applied: bool,
     window_checked: bool,
@@ -231,6 +279,7 @@ struct ToastMessage {
 struct ExportCompletion {
     paste_id: String,
     path: String,
+    printed: bool,
     result: Result<(), String>,
 }
 
@@ -292,22 +341,68 @@ impl LocalPasteApp {
     /// Returns an error if the database path is invalid or the underlying store
     /// cannot be opened.
     pub(crate) fn new() -> Result<Self, localpaste_core::AppError> {
-        let config = Config::from_env();
+        let mut config = Config::from_env();
+        let pinned_port = localpaste_core::discovery::pinned_port_for_launch(&config.db_path);
+        config.port = pinned_port.unwrap_or(config.port);
+        let allow_public = localpaste_core::config::env_flag_enabled("ALLOW_PUBLIC_ACCESS");
+        Self::with_config(config, pinned_port, allow_public)
+    }
+
+    /// Construct an app instance from an explicit config.
+    ///
+    /// # Arguments
+    /// - `config`: Resolved config; `config.port` is the port to bind.
+    /// - `pinned_port`: Port pinned for this database by discovery, if any.
+    /// - `allow_public`: Whether the embedded server accepts any origin.
+    ///
+    /// # Returns
+    /// The initialized [`LocalPasteApp`].
+    ///
+    /// # Errors
+    /// Returns an error if the database or embedded server cannot be opened.
+    pub(crate) fn with_config(
+        config: Config,
+        pinned_port: Option<u16>,
+        allow_public: bool,
+    ) -> Result<Self, localpaste_core::AppError> {
         let db_path = config.db_path.clone();
         let autosave_delay = Duration::from_millis(config.auto_save_interval);
+        let restored_backup = localpaste_core::db::backup::BackupManager::new(&config.db_path)
+            .apply_pending_restore();
+        let loaded_settings = localpaste_core::settings::load_settings_or_quarantine(
+            &config.db_path,
+        )
+        .unwrap_or_else(|err| {
+            warn!("ignoring settings file: {}", err);
+            Default::default()
+        });
+        let settings = loaded_settings.settings;
         let db = Database::new(&config.db_path)?;
         info!("native GUI opened database at {}", config.db_path);
+        let pruned_languages = if env_flag_enabled("LOCALPASTE_SYNTAX_PRUNE") {
+            match db.pastes.language_counts() {
+                Ok(counts) => Some(counts.into_iter().map(|(language, _)| language).collect()),
+                Err(err) => {
+                    warn!("loading every syntax grammar: {}", err);
+                    None
+                }
+            }
+        } else {
+            None
+        };
 
         let locks = Arc::new(PasteLockManager::default());
         let server_db = db.share()?;
         let state = AppState::with_locks(config.clone(), server_db, locks.clone());
-        let allow_public = localpaste_core::config::env_flag_enabled("ALLOW_PUBLIC_ACCESS");
+        let collab = state.collab.clone();
+        let presence_tracker = state.presence.clone();
         if allow_public {
             warn!("Public access enabled - server will accept requests from any origin");
         }
         let server = EmbeddedServer::start(state, allow_public)?;
         let server_addr = server.addr();
-        let server_used_fallback = server.used_fallback();
+        let server_fallback_from = server.used_fallback().then(|| server.requested_addr());
+        let server_port_pinned = pinned_port == Some(server_addr.port());
 
         let lock_owner_id = crate::lock_owner::next_lock_owner_id("gui");
         let backend = spawn_backend_with_locks_and_owner(
@@ -315,8 +410,9 @@ impl LocalPasteApp {
             config.max_paste_size,
             locks.clone(),
             lock_owner_id.clone(),
+            collab,
         );
-        let highlight_worker = spawn_highlight_worker();
+        let highlight_worker = spawn_highlight_worker(pruned_languages);
 
         let mut app = Self {
             backend,
@@ -337,6 +433,8 @@ impl LocalPasteApp {
             search_focus_requested: false,
             active_collection: SidebarCollection::All,
             active_language_filter: None,
+            sidebar_group_state: SidebarGroupState::default(),
+            sidebar_previews: SidebarPreviewCache::default(),
             properties_drawer_open: false,
             command_palette_open: false,
             command_palette_query: String::new(),
@@ -355,6 +453,9 @@ impl LocalPasteApp {
             virtual_editor_buffer: RopeBuffer::new(""),
             virtual_editor_state: VirtualEditorState::default(),
             virtual_editor_history: VirtualEditorHistory::default(),
+            indent_style: IndentStyle::default(),
+            selection_stats: SelectionStatsCache::default(),
+            selection_expand_history: Vec::new(),
             virtual_layout: WrapLayoutCache::default(),
             virtual_galley_cache: VirtualGalleyCache::default(),
             virtual_line_scratch: String::new(),
@@ -377,9 +478,12 @@ impl LocalPasteApp {
             db_path,
             locks,
             lock_owner_id,
+            presence_tracker,
+            presence: Default::default(),
             _server: server,
             server_addr,
-            server_used_fallback,
+            server_fallback_from,
+            server_port_pinned,
             status: None,
             toasts: VecDeque::with_capacity(TOAST_LIMIT),
             export_result_rx: None,
@@ -387,8 +491,28 @@ impl LocalPasteApp {
             last_edit_at: None,
             save_in_flight: false,
             save_request_revision: None,
+            collab: Default::default(),
             autosave_delay,
             shortcut_help_open: false,
+            scratchpad: Default::default(),
+            drafts: Default::default(),
+            backups: ui::backups::BackupsState::with_interval(settings.backup.interval_hours),
+            storage_alerts: Default::default(),
+            display_timezone: settings.display.timezone,
+            updates: ui::update_notice::UpdateNoticeState::with_settings(settings.updates),
+            import_wizard: Default::default(),
+            split_dialog: Default::default(),
+            merge_dialog: Default::default(),
+            comments: Default::default(),
+            folder_rules: Default::default(),
+            content_lint: Default::default(),
+            annotations: Default::default(),
+            line_links: Default::default(),
+            search_matches: Default::default(),
+            close_prompt: Default::default(),
+            writing_mode: Default::default(),
+            symbol_outline: Default::default(),
+            markdown_outline: Default::default(),
             focus_editor_next: false,
             style_applied: false,
             window_checked: false,
@@ -407,13 +531,34 @@ impl LocalPasteApp {
             editor_input_trace_enabled: env_flag_enabled("LOCALPASTE_EDITOR_INPUT_TRACE"),
             highlight_trace_enabled: env_flag_enabled("LOCALPASTE_HIGHLIGHT_TRACE"),
         };
+        app.announce_port_fallback();
+        if let Some(path) = loaded_settings.quarantined {
+            app.set_status(format!(
+                "Settings file was unreadable; moved to {} and using defaults.",
+                path.display()
+            ));
+        }
+        match restored_backup {
+            Ok(Some(path)) => {
+                info!("restored scheduled backup {}", path.display());
+                app.set_status(format!("Restored backup {}", path.display()));
+            }
+            Ok(None) => {}
+            Err(err) => {
+                warn!("scheduled backup restore failed: {}", err);
+                app.set_status(format!("Scheduled restore failed: {}", err));
+            }
+        }
         app.request_refresh();
         Ok(app)
     }
 
     fn acquire_paste_lock(&mut self, id: &str) -> bool {
         match self.locks.acquire(id, &self.lock_owner_id) {
-            Ok(()) => true,
+            Ok(()) => {
+                self.publish_lock_presence(id);
+                true
+            }
             Err(err) => {
                 warn!(
                     "failed to acquire paste lock '{}' for GUI owner: {}",
@@ -432,7 +577,9 @@ impl LocalPasteApp {
                 id, err
             );
             self.set_status("Lock release failed; restart app if edits remain blocked.");
+            return;
         }
+        self.publish_lock_presence(id);
     }
 
     fn track_frame_metrics(&mut self) {
@@ -530,6 +677,7 @@ impl eframe::App for LocalPasteApp {
             self.apply_event(event);
         }
         self.poll_export_result();
+        self.poll_update_check();
 
         if let Some(text) = self.clipboard_outgoing.take() {
             ctx.send_cmd(egui::OutputCommand::CopyText(text));
@@ -539,6 +687,8 @@ impl eframe::App for LocalPasteApp {
             match result {
                 HighlightWorkerResult::Render(render) => self.queue_highlight_render(render),
                 HighlightWorkerResult::Patch(patch) => self.queue_highlight_patch(patch),
+                HighlightWorkerResult::Outline(outline) => self.apply_highlight_outline(outline),
+                HighlightWorkerResult::Lint(lint) => self.apply_highlight_lint(lint),
             }
         }
 
@@ -634,7 +784,7 @@ impl eframe::App for LocalPasteApp {
             should_consume_virtual_editor_focus_keys(
                 virtual_editor_keyboard_claim_pre,
                 self.command_palette_open,
-                version_overlay_open,
+                version_overlay_open || self.symbol_outline.jump_open,
                 self.shortcut_help_open,
             ),
         );
@@ -664,6 +814,9 @@ impl eframe::App for LocalPasteApp {
                     self.create_new_paste();
                 }
             }
+            if command_shift && input.key_pressed(egui::Key::N) {
+                self.toggle_scratchpad();
+            }
             if plain_command
                 && input.key_pressed(egui::Key::Delete)
                 && self.should_route_delete_selected_shortcut(Self::delete_shortcut_focus_state(
@@ -696,7 +849,10 @@ impl eframe::App for LocalPasteApp {
                 self.palette_search_last_input_at = None;
             }
             if plain_command && input.key_pressed(egui::Key::I) {
-                self.properties_drawer_open = !self.properties_drawer_open;
+                self.toggle_properties_drawer();
+            }
+            if command_shift && input.key_pressed(egui::Key::O) {
+                self.toggle_symbol_jump();
             }
             if command_shift && input.key_pressed(egui::Key::V) {
                 if mutation_shortcut_blocked.is_some() {
@@ -830,20 +986,29 @@ impl eframe::App for LocalPasteApp {
         }
 
         if sidebar_direction != 0 {
-            let current = self.selected_index().unwrap_or(0) as i32;
-            let max_index = (self.pastes.len().saturating_sub(1)) as i32;
-            let next = (current + sidebar_direction).clamp(0, max_index) as usize;
-            if self.selected_index() != Some(next) {
-                let next_id = self.pastes[next].id.clone();
+            if let Some(next_id) = self.sidebar_navigation_target(sidebar_direction) {
                 self.select_paste(next_id);
             }
         }
-        self.render_top_bar(ctx);
-        self.render_sidebar(ctx);
-        self.render_properties_drawer(ctx);
+        if !self.writing_mode_active() {
+            self.render_top_bar(ctx);
+            self.render_sidebar(ctx);
+            self.render_properties_drawer(ctx);
+            self.render_symbol_outline_panel(ctx);
+        }
         self.render_editor_panel(ctx);
         self.render_command_palette(ctx);
+        self.render_symbol_jump(ctx);
         self.render_shortcut_help(ctx);
+        self.render_scratchpad(ctx);
+        self.render_draft_recovery(ctx);
+        self.render_import_wizard(ctx);
+        self.render_split_dialog(ctx);
+        self.render_merge_dialog(ctx);
+        self.render_backups(ctx);
+        self.render_folder_rules(ctx);
+        self.render_update_notes(ctx);
+        self.update_close_prompt(ctx);
 
         let mut deferred_focus_apply_result = VirtualApplyResult::default();
         let mut deferred_copy_apply_result = VirtualApplyResult::default();
@@ -970,6 +1135,12 @@ impl eframe::App for LocalPasteApp {
         self.maybe_dispatch_palette_search();
         self.maybe_dispatch_search();
         self.maybe_autosave();
+        self.maybe_poll_collab_edits();
+        self.maybe_refresh_presence();
+        self.maybe_persist_drafts();
+        self.maybe_run_scheduled_backup();
+        self.maybe_check_storage();
+        self.maybe_check_for_update();
         if self.last_refresh_at.elapsed() >= AUTO_REFRESH_INTERVAL {
             self.request_refresh();
         }
@@ -978,6 +1149,9 @@ impl eframe::App for LocalPasteApp {
         } else {
             AUTO_REFRESH_INTERVAL
         };
+        if self.selected_id.is_some() {
+            repaint_after = repaint_after.min(collab::COLLAB_POLL_INTERVAL);
+        }
         if let Some(status) = &self.status {
             let until = status.expires_at.saturating_duration_since(Instant::now());
             repaint_after = repaint_after.min(until);
@@ -1008,8 +1182,9 @@ impl eframe::App for LocalPasteApp {
 impl Drop for LocalPasteApp {
     fn drop(&mut self) {
         if let Some(id) = self.selected_id.take() {
-            if let Err(err) = self.locks.release(&id, &self.lock_owner_id) {
-                warn!("failed to release paste lock '{}' on drop: {}", id, err);
+            match self.locks.release(&id, &self.lock_owner_id) {
+                Ok(()) => self.publish_lock_presence(&id),
+                Err(err) => warn!("failed to release paste lock '{}' on drop: {}", id, err),
             }
         }
     }
@@ -155,7 +155,7 @@ impl LocalPasteApp {
     /// # Arguments
     /// - `pasted_text`: Optional clipboard text captured from current-frame egui events.
     /// # Returns
-    /// `true` when clipboard text was consumed and routed into `CreatePaste`.
+    /// `true` when clipboard text was consumed and routed into `CreateClipboardPaste`.
     pub(super) fn maybe_consume_explicit_paste_as_new(
         &mut self,
         pasted_text: &mut Option<String>,
@@ -176,7 +176,7 @@ impl LocalPasteApp {
                 text.as_str(),
                 ClipboardCreatePolicy::ExplicitPasteAsNew,
             ) {
-                self.create_new_paste_with_content(text);
+                self.create_new_paste_from_clipboard(text);
                 return true;
             }
             self.set_status("Clipboard was empty.");
@@ -243,7 +243,7 @@ impl LocalPasteApp {
             text.as_str(),
             ClipboardCreatePolicy::ImplicitGlobalShortcut,
         ) {
-            self.create_new_paste_with_content(text);
+            self.create_new_paste_from_clipboard(text);
             return true;
         }
         false
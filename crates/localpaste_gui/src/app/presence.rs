@@ -0,0 +1,88 @@
+//! Which other clients are editing or viewing each paste.
+//!
+//! Lock holders come from the lock manager shared with the embedded server,
+//! and viewers from the server's presence tracker, so the GUI reads both in
+//! process instead of polling the API. The snapshot backs the sidebar markers
+//! and the status-bar "Also open" label.
+
+use super::LocalPasteApp;
+use localpaste_server::presence::{PresenceEntry, PresenceState};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// How often the presence snapshot is rebuilt.
+pub(super) const PRESENCE_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Other clients per paste, excluding this window's own lock.
+#[derive(Debug, Default)]
+pub(super) struct PresenceSnapshot {
+    others: HashMap<String, Vec<PresenceEntry>>,
+    refreshed_at: Option<Instant>,
+}
+
+impl LocalPasteApp {
+    /// Rebuilds the presence snapshot once [`PRESENCE_REFRESH_INTERVAL`] has
+    /// passed since the last rebuild.
+    pub(super) fn maybe_refresh_presence(&mut self) {
+        if self
+            .presence
+            .refreshed_at
+            .is_some_and(|at| at.elapsed() < PRESENCE_REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.presence.refreshed_at = Some(Instant::now());
+        match self.presence_tracker.all(&self.locks) {
+            Ok(mut others) => {
+                let own = self.lock_owner_id.as_str();
+                others.retain(|_, entries| {
+                    entries.retain(|entry| entry.owner != own);
+                    !entries.is_empty()
+                });
+                self.presence.others = others;
+            }
+            Err(err) => warn!("presence snapshot failed: {}", err),
+        }
+    }
+
+    /// Other clients with `paste_id` open, editors first.
+    pub(super) fn paste_presence(&self, paste_id: &str) -> &[PresenceEntry] {
+        self.presence
+            .others
+            .get(paste_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Tells API subscribers this window took or dropped the lock on
+    /// `paste_id`.
+    pub(super) fn publish_lock_presence(&self, paste_id: &str) {
+        self.presence_tracker.lock_changed(paste_id);
+    }
+}
+
+/// Sidebar marker for a paste: filled when someone else holds the edit lock,
+/// hollow when others are only viewing.
+pub(super) fn presence_marker(entries: &[PresenceEntry]) -> Option<&'static str> {
+    let first = entries.first()?;
+    Some(match first.state {
+        PresenceState::Editing => "\u{25CF}",
+        PresenceState::Viewing => "\u{25CB}",
+    })
+}
+
+/// Owner labels with their state, such as `"gui-2 (editing), cli (viewing)"`.
+pub(super) fn presence_summary(entries: &[PresenceEntry]) -> String {
+    entries
+        .iter()
+        .map(|entry| {
+            let state = match entry.state {
+                PresenceState::Editing => "editing",
+                PresenceState::Viewing => "viewing",
+            };
+            format!("{} ({})", entry.owner, state)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,390 @@
+//! Syntactic selection expansion (`Alt+Up`) and shrinking (`Alt+Down`).
+//!
+//! Each expansion picks the smallest candidate range that strictly contains
+//! the current selection: word, highlight token, quoted string, bracket
+//! contents, line, indentation block, and finally the whole document.
+//! Highlight tokens come from the current worker render when it matches the
+//! buffer; the other candidates are text heuristics.
+
+use super::interaction_helpers::is_editor_word_char;
+use super::LocalPasteApp;
+use std::ops::Range;
+
+/// Lines scanned on each side of the selection when building candidates.
+const EXPAND_WINDOW_LINES: usize = 1000;
+
+const QUOTES: [char; 3] = ['"', '\'', '`'];
+
+fn closer_for(open: char) -> Option<char> {
+    match open {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        _ => None,
+    }
+}
+
+fn opener_for(close: char) -> Option<char> {
+    match close {
+        ')' => Some('('),
+        ']' => Some('['),
+        '}' => Some('{'),
+        _ => None,
+    }
+}
+
+fn line_bounds(chars: &[char], index: usize) -> Range<usize> {
+    let index = index.min(chars.len());
+    let start = chars[..index]
+        .iter()
+        .rposition(|ch| *ch == '\n')
+        .map_or(0, |pos| pos + 1);
+    let end = chars[index..]
+        .iter()
+        .position(|ch| *ch == '\n')
+        .map_or(chars.len(), |pos| index + pos);
+    start..end
+}
+
+fn trimmed(chars: &[char], range: Range<usize>) -> Range<usize> {
+    let mut start = range.start;
+    let mut end = range.end;
+    while start < end && chars[start].is_whitespace() {
+        start += 1;
+    }
+    while end > start && chars[end - 1].is_whitespace() {
+        end -= 1;
+    }
+    start..end
+}
+
+fn word_candidate(chars: &[char], selection: &Range<usize>) -> Option<Range<usize>> {
+    if !chars[selection.clone()]
+        .iter()
+        .all(|ch| is_editor_word_char(*ch))
+    {
+        return None;
+    }
+    let mut start = selection.start;
+    while start > 0 && is_editor_word_char(chars[start - 1]) {
+        start -= 1;
+    }
+    let mut end = selection.end;
+    while end < chars.len() && is_editor_word_char(chars[end]) {
+        end += 1;
+    }
+    (start < end).then_some(start..end)
+}
+
+fn quote_candidates(chars: &[char], selection: &Range<usize>, out: &mut Vec<Range<usize>>) {
+    let line = line_bounds(chars, selection.start);
+    if selection.end > line.end {
+        return;
+    }
+    for quote in QUOTES {
+        let is_quote =
+            |idx: usize| chars[idx] == quote && (idx == 0 || chars[idx.saturating_sub(1)] != '\\');
+        let Some(open) = (line.start..selection.start)
+            .rev()
+            .find(|idx| is_quote(*idx))
+        else {
+            continue;
+        };
+        let Some(close) = (selection.end..line.end).find(|idx| is_quote(*idx)) else {
+            continue;
+        };
+        out.push(open + 1..close);
+        out.push(open..close + 1);
+    }
+}
+
+fn bracket_candidates(chars: &[char], selection: &Range<usize>, out: &mut Vec<Range<usize>>) {
+    let mut pending_closers = Vec::new();
+    let mut open = None;
+    for idx in (0..selection.start).rev() {
+        let ch = chars[idx];
+        if opener_for(ch).is_some() {
+            pending_closers.push(ch);
+        } else if let Some(close) = closer_for(ch) {
+            if pending_closers.last() == Some(&close) {
+                pending_closers.pop();
+            } else if pending_closers.is_empty() {
+                open = Some((idx, close));
+                break;
+            }
+        }
+    }
+    let Some((open, close)) = open else {
+        return;
+    };
+    let opener = chars[open];
+    let mut depth = 0usize;
+    for idx in selection.end..chars.len() {
+        let ch = chars[idx];
+        if ch == opener {
+            depth += 1;
+        } else if ch == close {
+            if depth == 0 {
+                out.push(open + 1..idx);
+                out.push(trimmed(chars, open + 1..idx));
+                out.push(open..idx + 1);
+                return;
+            }
+            depth -= 1;
+        }
+    }
+}
+
+fn indent_of(chars: &[char], line: &Range<usize>) -> Option<usize> {
+    let content = chars[line.clone()]
+        .iter()
+        .position(|ch| !ch.is_whitespace())?;
+    Some(content)
+}
+
+fn is_block_closer_line(chars: &[char], line: &Range<usize>) -> bool {
+    let line = trimmed(chars, line.clone());
+    let content = &chars[line];
+    content.first().is_some_and(|ch| opener_for(*ch).is_some())
+        || content.starts_with(&['e', 'n', 'd'])
+}
+
+fn block_candidates(chars: &[char], selection: &Range<usize>, out: &mut Vec<Range<usize>>) {
+    let first = line_bounds(chars, selection.start);
+    let last = line_bounds(chars, selection.end);
+    out.push(trimmed(chars, first.start..last.end));
+
+    let mut base_indent = None;
+    let mut line = first.clone();
+    loop {
+        if let Some(indent) = indent_of(chars, &line) {
+            base_indent = Some(base_indent.map_or(indent, |base: usize| base.min(indent)));
+        }
+        if line.end >= last.end || line.end >= chars.len() {
+            break;
+        }
+        line = line_bounds(chars, line.end + 1);
+    }
+    let Some(base_indent) = base_indent else {
+        return;
+    };
+    let belongs = |line: &Range<usize>| indent_of(chars, line).is_none_or(|i| i >= base_indent);
+
+    let mut block_start = first.clone();
+    let mut header = None;
+    while block_start.start > 0 {
+        let previous = line_bounds(chars, block_start.start - 1);
+        if !belongs(&previous) {
+            header = Some(previous);
+            break;
+        }
+        block_start = previous;
+    }
+    let mut block_end = last.clone();
+    let mut footer = None;
+    while block_end.end < chars.len() {
+        let next = line_bounds(chars, block_end.end + 1);
+        if !belongs(&next) {
+            footer = Some(next);
+            break;
+        }
+        block_end = next;
+    }
+    let block = trimmed(chars, block_start.start..block_end.end);
+    out.push(block.clone());
+    if let Some(header) = header {
+        let mut end = block.end;
+        // Keep a closing line at the header's indent (`}`, `end`, `)`) with the block.
+        if let Some(footer) = footer {
+            if indent_of(chars, &footer) == indent_of(chars, &header)
+                && is_block_closer_line(chars, &footer)
+            {
+                end = footer.end;
+            }
+        }
+        out.push(trimmed(chars, header.start..end));
+    }
+}
+
+/// Returns the smallest candidate range that strictly contains `selection`.
+///
+/// # Arguments
+/// - `chars`: Text window the ranges index into.
+/// - `selection`: Current selection (empty for a caret).
+/// - `tokens`: Highlight token ranges near the selection, if available.
+///
+/// # Returns
+/// The next larger range, or `None` when nothing in the window contains it.
+pub(super) fn expand_range(
+    chars: &[char],
+    selection: Range<usize>,
+    tokens: &[Range<usize>],
+) -> Option<Range<usize>> {
+    let selection = selection.start.min(chars.len())..selection.end.min(chars.len());
+    let mut candidates = Vec::new();
+    candidates.extend(word_candidate(chars, &selection));
+    candidates.extend(tokens.iter().cloned());
+    quote_candidates(chars, &selection, &mut candidates);
+    bracket_candidates(chars, &selection, &mut candidates);
+    block_candidates(chars, &selection, &mut candidates);
+    candidates.push(0..chars.len());
+    candidates
+        .into_iter()
+        .filter(|range| {
+            range.start <= selection.start
+                && range.end >= selection.end
+                && range.len() > selection.len()
+        })
+        .min_by_key(|range| (range.len(), range.start))
+}
+
+impl LocalPasteApp {
+    fn set_virtual_selection(&mut self, range: Range<usize>) {
+        let len = self.virtual_editor_buffer.len_chars();
+        self.virtual_editor_state.set_cursor(range.start, len);
+        if !range.is_empty() {
+            self.virtual_editor_state.move_cursor(range.end, len, true);
+        }
+    }
+
+    fn virtual_selection_or_caret(&self) -> Range<usize> {
+        let cursor = self.virtual_editor_state.cursor();
+        self.virtual_editor_state
+            .selection_range()
+            .unwrap_or(cursor..cursor)
+    }
+
+    /// Highlight token ranges on `line`, in global char coordinates.
+    fn highlight_token_ranges(&self, line: usize) -> Vec<Range<usize>> {
+        let Some(render) = self.highlight_render.as_ref() else {
+            return Vec::new();
+        };
+        let current = self.selected_id.as_deref() == Some(render.paste_id.as_str())
+            && render.revision == self.virtual_editor_buffer.revision()
+            && render.text_len == self.virtual_editor_buffer.len_bytes();
+        let Some(render_line) = render.lines.get(line).filter(|_| current) else {
+            return Vec::new();
+        };
+        let mut text = String::new();
+        self.virtual_editor_buffer
+            .line_without_newline_into(line, &mut text);
+        let line_start = self.virtual_editor_buffer.line_col_to_char(line, 0);
+        render_line
+            .token_byte_ranges()
+            .into_iter()
+            .filter(|range| range.end <= text.len())
+            .filter(|range| text.is_char_boundary(range.start) && text.is_char_boundary(range.end))
+            .map(|range| {
+                let start = text[..range.start].chars().count();
+                let end = start + text[range].chars().count();
+                line_start + start..line_start + end
+            })
+            .collect()
+    }
+
+    /// Grows the selection by one syntactic unit.
+    ///
+    /// # Returns
+    /// `true` when the selection changed.
+    pub(super) fn expand_virtual_selection(&mut self) -> bool {
+        let selection = self.virtual_selection_or_caret();
+        let buffer = &self.virtual_editor_buffer;
+        let (first_line, _) = buffer.char_to_line_col(selection.start);
+        let (last_line, _) = buffer.char_to_line_col(selection.end);
+        let window_start =
+            buffer.line_col_to_char(first_line.saturating_sub(EXPAND_WINDOW_LINES), 0);
+        let window_end_line = last_line.saturating_add(EXPAND_WINDOW_LINES + 1);
+        let window_end = if window_end_line >= buffer.line_count() {
+            buffer.len_chars()
+        } else {
+            buffer.line_col_to_char(window_end_line, 0)
+        };
+        let chars: Vec<char> = buffer
+            .slice_chars(window_start..window_end)
+            .chars()
+            .collect();
+        let tokens: Vec<Range<usize>> = self
+            .highlight_token_ranges(first_line)
+            .into_iter()
+            .map(|range| range.start - window_start..range.end - window_start)
+            .collect();
+        let local = selection.start - window_start..selection.end - window_start;
+        let next = match expand_range(&chars, local, &tokens) {
+            Some(range) if range != (0..chars.len()) => {
+                range.start + window_start..range.end + window_start
+            }
+            _ => 0..self.virtual_editor_buffer.len_chars(),
+        };
+        if next == selection {
+            return false;
+        }
+        if self.selection_expand_history.last() != Some(&selection) {
+            self.selection_expand_history.clear();
+            self.selection_expand_history.push(selection);
+        }
+        self.selection_expand_history.push(next.clone());
+        self.set_virtual_selection(next);
+        true
+    }
+
+    /// Returns to the selection before the last expansion.
+    ///
+    /// # Returns
+    /// `true` when the selection changed.
+    pub(super) fn shrink_virtual_selection(&mut self) -> bool {
+        let selection = self.virtual_selection_or_caret();
+        if self.selection_expand_history.len() < 2
+            || self.selection_expand_history.last() != Some(&selection)
+        {
+            self.selection_expand_history.clear();
+            return false;
+        }
+        self.selection_expand_history.pop();
+        let Some(previous) = self.selection_expand_history.last().cloned() else {
+            return false;
+        };
+        self.set_virtual_selection(previous);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand_range;
+
+    fn expand(text: &str, selection: std::ops::Range<usize>) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let range = expand_range(&chars, selection, &[]).expect("expansion");
+        chars[range].iter().collect()
+    }
+
+    #[test]
+    fn expansion_walks_word_string_brackets_line_and_block() {
+        let text = "fn main() {\n    call(\"hello world\", 2);\n    other();\n}\n";
+        let hello = text.find("hello").unwrap();
+        assert_eq!(expand(text, hello + 1..hello + 1), "hello");
+        assert_eq!(expand(text, hello..hello + 5), "hello world");
+        assert_eq!(expand(text, hello..hello + 11), "\"hello world\"");
+        assert_eq!(expand(text, hello - 1..hello + 12), "\"hello world\", 2");
+        let args = text.find("\"hello").unwrap();
+        assert_eq!(expand(text, args..args + 16), "(\"hello world\", 2)");
+        let call = text.find("call").unwrap();
+        let line_end = text.find(";\n").unwrap() + 1;
+        assert_eq!(
+            expand(text, call..line_end - 1),
+            "call(\"hello world\", 2);"
+        );
+        assert_eq!(
+            expand(text, call..line_end),
+            "call(\"hello world\", 2);\n    other();"
+        );
+    }
+
+    #[test]
+    fn indentation_block_expands_to_header_for_bracketless_code() {
+        let text = "def f():\n    a = 1\n    b = 2\nprint(f())\n";
+        let a = text.find("a = 1").unwrap();
+        assert_eq!(expand(text, a..a + 5), "a = 1\n    b = 2");
+        assert_eq!(expand(text, a..a + 15), "def f():\n    a = 1\n    b = 2");
+    }
+}
@@ -0,0 +1,181 @@
+//! Status-bar statistics for the active virtual editor selection.
+
+use super::LocalPasteApp;
+use std::ops::Range;
+
+/// Selections above this many chars skip statistics to keep frames cheap.
+const SELECTION_STATS_MAX_CHARS: usize = 4 * 1024 * 1024;
+/// Minimum numeric tokens before sum/avg/min/max are shown.
+const MIN_NUMERIC_TOKENS: usize = 2;
+
+/// Aggregates over numeric tokens in a selection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct NumericSummary {
+    pub(super) count: usize,
+    pub(super) sum: f64,
+    pub(super) min: f64,
+    pub(super) max: f64,
+}
+
+impl NumericSummary {
+    pub(super) fn avg(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+/// Counts describing a selection.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct SelectionStats {
+    pub(super) chars: usize,
+    pub(super) lines: usize,
+    pub(super) words: usize,
+    /// Present when at least half of the whitespace-separated tokens are numbers.
+    pub(super) numbers: Option<NumericSummary>,
+}
+
+impl SelectionStats {
+    /// Computes counts and numeric aggregates for `text`.
+    ///
+    /// # Returns
+    /// Statistics for the selected text.
+    pub(super) fn from_text(text: &str) -> Self {
+        let mut tokens = 0usize;
+        let mut numbers: Option<NumericSummary> = None;
+        for token in text.split(|ch: char| ch.is_whitespace() || ch == ',' || ch == ';') {
+            if token.is_empty() {
+                continue;
+            }
+            tokens += 1;
+            let Some(value) = parse_number(token) else {
+                continue;
+            };
+            numbers = Some(match numbers {
+                None => NumericSummary {
+                    count: 1,
+                    sum: value,
+                    min: value,
+                    max: value,
+                },
+                Some(summary) => NumericSummary {
+                    count: summary.count + 1,
+                    sum: summary.sum + value,
+                    min: summary.min.min(value),
+                    max: summary.max.max(value),
+                },
+            });
+        }
+        let numbers = numbers
+            .filter(|summary| summary.count >= MIN_NUMERIC_TOKENS && summary.count * 2 >= tokens);
+        Self {
+            chars: text.chars().count(),
+            lines: text.lines().count().max(1),
+            words: text.split_whitespace().count(),
+            numbers,
+        }
+    }
+
+    /// Formats the status-bar label.
+    ///
+    /// # Returns
+    /// Text such as `12 chars, 2 lines, 3 words | sum 6 avg 2 min 1 max 3`.
+    pub(super) fn label(&self) -> String {
+        let mut label = format!(
+            "{} chars, {} {}, {} {}",
+            self.chars,
+            self.lines,
+            if self.lines == 1 { "line" } else { "lines" },
+            self.words,
+            if self.words == 1 { "word" } else { "words" },
+        );
+        if let Some(numbers) = self.numbers {
+            label.push_str(&format!(
+                " | sum {} avg {} min {} max {}",
+                format_number(numbers.sum),
+                format_number(numbers.avg()),
+                format_number(numbers.min),
+                format_number(numbers.max),
+            ));
+        }
+        label
+    }
+}
+
+fn parse_number(token: &str) -> Option<f64> {
+    let token = token.trim_matches(|ch: char| matches!(ch, '(' | ')' | '[' | ']' | '"' | '\''));
+    let token = token.strip_suffix('%').unwrap_or(token);
+    let first = token.chars().next()?;
+    if !(first.is_ascii_digit() || matches!(first, '-' | '+' | '.')) {
+        return None;
+    }
+    token.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+fn format_number(value: f64) -> String {
+    let formatted = format!("{:.4}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Memoized stats for the last selection so idle frames skip recounting.
+#[derive(Debug, Default)]
+pub(super) struct SelectionStatsCache {
+    key: Option<(u64, Range<usize>)>,
+    label: Option<String>,
+}
+
+impl LocalPasteApp {
+    /// Returns the status-bar label for the active virtual editor selection.
+    ///
+    /// # Returns
+    /// `None` without a selection or when the selection is too large to scan.
+    pub(super) fn selection_stats_label(&mut self) -> Option<String> {
+        if !self.is_virtual_editor_mode() {
+            return None;
+        }
+        let range = self.virtual_editor_state.selection_range()?;
+        if range.len() > SELECTION_STATS_MAX_CHARS {
+            return None;
+        }
+        let key = (self.virtual_editor_buffer.revision(), range.clone());
+        if self.selection_stats.key.as_ref() != Some(&key) {
+            let text = self.virtual_editor_buffer.slice_chars(range);
+            self.selection_stats.label = Some(SelectionStats::from_text(text.as_str()).label());
+            self.selection_stats.key = Some(key);
+        }
+        self.selection_stats.label.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SelectionStats;
+
+    #[test]
+    fn selection_stats_counts_text_and_skips_mostly_text_numbers() {
+        let stats = SelectionStats::from_text("error code 42\nretry later\n");
+        assert_eq!(stats.chars, 26);
+        assert_eq!(stats.lines, 2);
+        assert_eq!(stats.words, 5);
+        assert_eq!(stats.numbers, None);
+        assert_eq!(stats.label(), "26 chars, 2 lines, 5 words");
+    }
+
+    #[test]
+    fn selection_stats_summarizes_mostly_numeric_selections() {
+        let stats = SelectionStats::from_text("latency_ms\n12\n-3.5\n40%\n(1.5)\n");
+        let numbers = stats.numbers.expect("numeric summary");
+        assert_eq!(numbers.count, 4);
+        assert_eq!(numbers.sum, 50.0);
+        assert_eq!(numbers.min, -3.5);
+        assert_eq!(numbers.max, 40.0);
+        assert_eq!(
+            stats.label(),
+            "29 chars, 5 lines, 5 words | sum 50 avg 12.5 min -3.5 max 40"
+        );
+        assert_eq!(SelectionStats::from_text("7").numbers, None);
+    }
+}
@@ -1,12 +1,104 @@
 //! Shutdown save-flush logic for `LocalPasteApp`.
 
 use super::{LocalPasteApp, SaveStatus, SHUTDOWN_SAVE_FLUSH_TIMEOUT};
+use eframe::egui;
 use std::time::{Duration, Instant};
 use tracing::warn;
 
 const BACKEND_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+const WINDOW_TITLE: &str = "LocalPaste.rs";
+
+/// Close-request interception state for the unsaved-changes prompt.
+#[derive(Debug, Default)]
+pub(super) struct ClosePromptState {
+    pub(super) open: bool,
+    /// Set once the user chose to close; later close requests pass through.
+    pub(super) confirmed: bool,
+    /// Close as soon as dispatched saves settle.
+    pub(super) close_after_save: bool,
+    /// Dirty flag last reflected in the window title.
+    title_dirty: Option<bool>,
+}
+
+/// Window title reflecting unsaved state.
+///
+/// # Returns
+/// `● LocalPaste.rs` while dirty, otherwise `LocalPaste.rs`.
+pub(super) fn window_title(dirty: bool) -> String {
+    if dirty {
+        format!("\u{25CF} {}", WINDOW_TITLE)
+    } else {
+        WINDOW_TITLE.to_string()
+    }
+}
 
 impl LocalPasteApp {
+    /// Returns whether content or metadata edits have not been persisted yet.
+    pub(super) fn has_unsaved_changes(&self) -> bool {
+        self.save_status != SaveStatus::Saved
+            || self.metadata_dirty
+            || self.save_in_flight
+            || self.metadata_save_in_flight
+    }
+
+    /// Syncs the dirty window title and intercepts close requests with unsaved edits.
+    ///
+    /// Unlike the bounded shutdown flush, a close routed through the prompt
+    /// waits for saves to land (or an explicit discard) before the window closes.
+    ///
+    /// # Arguments
+    /// - `ctx`: Egui context used for viewport title/close commands.
+    pub(super) fn update_close_prompt(&mut self, ctx: &egui::Context) {
+        let dirty = self.has_unsaved_changes();
+        if self.close_prompt.title_dirty != Some(dirty) {
+            self.close_prompt.title_dirty = Some(dirty);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(window_title(dirty)));
+        }
+        if dirty
+            && !self.close_prompt.confirmed
+            && ctx.input(|input| input.viewport().close_requested())
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.close_prompt.open = true;
+        }
+        if self.close_prompt.close_after_save {
+            if !dirty {
+                self.close_prompt.close_after_save = false;
+                self.close_prompt.confirmed = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            } else if !self.save_in_flight && !self.metadata_save_in_flight {
+                // A failed save leaves the editor dirty with nothing in flight.
+                self.close_prompt.close_after_save = false;
+                self.set_status("Save failed; retry or discard changes before closing.");
+            }
+        }
+        self.render_close_prompt(ctx);
+    }
+
+    /// Dispatches pending saves and closes once they are acknowledged.
+    pub(super) fn confirm_close_with_save(&mut self) {
+        if self.save_status == SaveStatus::Dirty {
+            self.save_now();
+        }
+        if self.metadata_dirty {
+            self.save_metadata_now();
+        }
+        self.close_prompt.close_after_save = true;
+    }
+
+    /// Drops unsaved edits and closes without flushing them.
+    ///
+    /// # Arguments
+    /// - `ctx`: Egui context used to issue the close command.
+    pub(super) fn confirm_close_with_discard(&mut self, ctx: &egui::Context) {
+        self.save_status = SaveStatus::Saved;
+        self.metadata_dirty = false;
+        self.discard_pending_paste_draft();
+        self.close_prompt.open = false;
+        self.close_prompt.confirmed = true;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+    }
+
     /// Drains pending content/metadata saves before backend shutdown.
     ///
     /// This best-effort flush keeps dispatching and consuming backend events
@@ -0,0 +1,456 @@
+//! Sidebar group-by projection computed locally from paste summaries.
+//!
+//! Grouping never issues backend requests: it reorders the already-filtered
+//! visible projection into collapsible buckets keyed by language, folder, or
+//! update-date recency.
+
+use super::LocalPasteApp;
+use crate::backend::{CoreCmd, PasteSummary};
+use chrono::{DateTime, Duration as ChronoDuration, Local, NaiveDate, Utc};
+use localpaste_core::models::folder::Folder;
+use localpaste_core::timestamps::DisplayTimezone;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+const NO_LANGUAGE_GROUP_LABEL: &str = "No language";
+const UNFILED_GROUP_LABEL: &str = "Unfiled";
+
+/// Grouping mode applied to the visible sidebar list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) enum SidebarGroupBy {
+    /// Flat recency-ordered list (no headers).
+    #[default]
+    None,
+    /// Group by canonical language label.
+    Language,
+    /// Group by folder id, with unfiled pastes last.
+    Folder,
+    /// Group into Today / This week / Older buckets.
+    Date,
+}
+
+impl SidebarGroupBy {
+    /// All selectable grouping modes in display order.
+    pub(super) const ALL: [SidebarGroupBy; 4] = [
+        SidebarGroupBy::None,
+        SidebarGroupBy::Language,
+        SidebarGroupBy::Folder,
+        SidebarGroupBy::Date,
+    ];
+
+    /// Returns the user-facing label for the grouping toggle.
+    ///
+    /// # Returns
+    /// Short label used by the sidebar combo box.
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            SidebarGroupBy::None => "No grouping",
+            SidebarGroupBy::Language => "Language",
+            SidebarGroupBy::Folder => "Folder",
+            SidebarGroupBy::Date => "Date",
+        }
+    }
+}
+
+/// Persistent sidebar grouping state (mode + collapsed headers).
+#[derive(Debug, Clone, Default)]
+pub(super) struct SidebarGroupState {
+    pub(super) group_by: SidebarGroupBy,
+    pub(super) collapsed: BTreeSet<String>,
+    /// Last folder rows from the backend, keyed by id, for names and stats.
+    pub(super) folders: HashMap<String, Folder>,
+}
+
+/// One collapsible sidebar group over the visible projection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct SidebarGroup {
+    pub(super) key: String,
+    pub(super) label: String,
+    /// Indices into the visible `pastes` projection, preserving its order.
+    pub(super) indices: Vec<usize>,
+}
+
+/// A single renderable sidebar row in grouped mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SidebarRow {
+    /// Group header referencing an index into the group list.
+    Header(usize),
+    /// Paste row referencing an index into the visible `pastes` projection.
+    Paste(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum DateBucket {
+    Today,
+    Week,
+    Older,
+}
+
+impl DateBucket {
+    fn key(self) -> &'static str {
+        match self {
+            DateBucket::Today => "date:today",
+            DateBucket::Week => "date:week",
+            DateBucket::Older => "date:older",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            DateBucket::Today => "Today",
+            DateBucket::Week => "This week",
+            DateBucket::Older => "Older",
+        }
+    }
+}
+
+fn date_bucket(
+    updated_at: DateTime<Utc>,
+    today_local: NaiveDate,
+    week_cutoff: DateTime<Utc>,
+) -> DateBucket {
+    if updated_at.with_timezone(&Local).date_naive() == today_local {
+        DateBucket::Today
+    } else if updated_at >= week_cutoff {
+        DateBucket::Week
+    } else {
+        DateBucket::Older
+    }
+}
+
+fn language_group_label(language: Option<&str>) -> Option<String> {
+    let trimmed = language.map(str::trim).filter(|value| !value.is_empty())?;
+    Some(localpaste_core::detection::canonical::canonicalize(trimmed))
+}
+
+/// Groups visible summaries without touching the backend.
+///
+/// # Arguments
+/// - `items`: Visible sidebar projection in display order.
+/// - `group_by`: Active grouping mode.
+/// - `today_local`: Local calendar date used for the `Today` bucket.
+/// - `week_cutoff`: Lower bound for the `This week` bucket.
+///
+/// # Returns
+/// Groups in display order. Empty when grouping is disabled. Language/folder
+/// groups sort alphabetically with the "missing" bucket last; date buckets
+/// keep their chronological order. Items keep their input order per group.
+pub(super) fn build_sidebar_groups(
+    items: &[PasteSummary],
+    group_by: SidebarGroupBy,
+    today_local: NaiveDate,
+    week_cutoff: DateTime<Utc>,
+) -> Vec<SidebarGroup> {
+    match group_by {
+        SidebarGroupBy::None => Vec::new(),
+        SidebarGroupBy::Date => {
+            let mut buckets: BTreeMap<DateBucket, Vec<usize>> = BTreeMap::new();
+            for (idx, item) in items.iter().enumerate() {
+                buckets
+                    .entry(date_bucket(item.updated_at, today_local, week_cutoff))
+                    .or_default()
+                    .push(idx);
+            }
+            buckets
+                .into_iter()
+                .map(|(bucket, indices)| SidebarGroup {
+                    key: bucket.key().to_string(),
+                    label: bucket.label().to_string(),
+                    indices,
+                })
+                .collect()
+        }
+        SidebarGroupBy::Language | SidebarGroupBy::Folder => {
+            let (prefix, missing_label) = if group_by == SidebarGroupBy::Language {
+                ("lang", NO_LANGUAGE_GROUP_LABEL)
+            } else {
+                ("folder", UNFILED_GROUP_LABEL)
+            };
+            let mut named: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+            let mut missing: Vec<usize> = Vec::new();
+            for (idx, item) in items.iter().enumerate() {
+                let value = if group_by == SidebarGroupBy::Language {
+                    language_group_label(item.language.as_deref())
+                } else {
+                    item.folder_id
+                        .as_deref()
+                        .map(str::trim)
+                        .filter(|value| !value.is_empty())
+                        .map(ToString::to_string)
+                };
+                match value {
+                    Some(value) => named.entry(value).or_default().push(idx),
+                    None => missing.push(idx),
+                }
+            }
+            let mut groups: Vec<SidebarGroup> = named
+                .into_iter()
+                .map(|(value, indices)| SidebarGroup {
+                    key: format!("{}:{}", prefix, value),
+                    label: value,
+                    indices,
+                })
+                .collect();
+            if !missing.is_empty() {
+                groups.push(SidebarGroup {
+                    key: format!("{}:", prefix),
+                    label: missing_label.to_string(),
+                    indices: missing,
+                });
+            }
+            groups
+        }
+    }
+}
+
+/// Header text for a folder group: the folder name (its id until folders
+/// load), the visible paste count, and the folder's stored size.
+pub(super) fn folder_group_label(group: &SidebarGroup, folder: Option<&Folder>) -> String {
+    match folder {
+        Some(folder) => format!(
+            "{} ({}) - {} bytes",
+            folder.name,
+            group.indices.len(),
+            folder.total_bytes
+        ),
+        None => format!("{} ({})", group.label, group.indices.len()),
+    }
+}
+
+/// Folder statistics lines shared by the group header hover and the properties drawer.
+pub(super) fn folder_stats_lines(folder: &Folder, timezone: DisplayTimezone) -> Vec<String> {
+    let updated = folder.last_updated.map_or_else(
+        || "Never".to_string(),
+        |at| timezone.format(at, "%Y-%m-%d %H:%M"),
+    );
+    vec![
+        format!("Pastes: {}", folder.paste_count),
+        format!("Size: {} bytes", folder.total_bytes),
+        format!("Last updated: {}", updated),
+    ]
+}
+
+/// Folder id behind a folder-mode group key, or `None` for other groups and Unfiled.
+pub(super) fn sidebar_group_folder_id(key: &str) -> Option<&str> {
+    key.strip_prefix("folder:").filter(|id| !id.is_empty())
+}
+
+/// Flattens groups into renderable rows, hiding members of collapsed groups.
+///
+/// # Returns
+/// Header rows for every group followed by member rows for expanded groups.
+pub(super) fn sidebar_rows(
+    groups: &[SidebarGroup],
+    collapsed: &BTreeSet<String>,
+) -> Vec<SidebarRow> {
+    let mut rows = Vec::new();
+    for (group_idx, group) in groups.iter().enumerate() {
+        rows.push(SidebarRow::Header(group_idx));
+        if !collapsed.contains(&group.key) {
+            rows.extend(group.indices.iter().copied().map(SidebarRow::Paste));
+        }
+    }
+    rows
+}
+
+impl LocalPasteApp {
+    /// Computes sidebar groups for the current visible projection.
+    ///
+    /// # Returns
+    /// Display-ordered groups, or an empty list when grouping is disabled.
+    pub(super) fn sidebar_groups(&self) -> Vec<SidebarGroup> {
+        let now = Utc::now();
+        build_sidebar_groups(
+            &self.pastes,
+            self.sidebar_group_state.group_by,
+            Local::now().date_naive(),
+            now - ChronoDuration::days(7),
+        )
+    }
+
+    /// Switches the sidebar grouping mode, resetting collapsed headers.
+    pub(super) fn set_sidebar_group_by(&mut self, group_by: SidebarGroupBy) {
+        if self.sidebar_group_state.group_by == group_by {
+            return;
+        }
+        self.sidebar_group_state.group_by = group_by;
+        self.sidebar_group_state.collapsed.clear();
+        if group_by == SidebarGroupBy::Folder {
+            self.request_folder_stats();
+        }
+    }
+
+    /// Whether folder rows are shown anywhere: folder headers or the properties drawer.
+    pub(super) fn folder_stats_visible(&self) -> bool {
+        self.sidebar_group_state.group_by == SidebarGroupBy::Folder || self.properties_drawer_open
+    }
+
+    /// Asks the backend for fresh folder rows; the reply replaces the folder map.
+    pub(super) fn request_folder_stats(&self) {
+        let _ = self.backend.cmd_tx.send(CoreCmd::ListFolders);
+    }
+
+    /// Toggles whether a sidebar group header is collapsed.
+    pub(super) fn toggle_sidebar_group(&mut self, key: &str) {
+        if !self.sidebar_group_state.collapsed.remove(key) {
+            self.sidebar_group_state.collapsed.insert(key.to_string());
+        }
+    }
+
+    /// Resolves the visible paste id reached by arrow navigation.
+    ///
+    /// In grouped mode this walks the rendered row order (skipping headers and
+    /// collapsed members) so arrows follow what the user sees.
+    ///
+    /// # Arguments
+    /// - `direction`: `1` for down, `-1` for up.
+    ///
+    /// # Returns
+    /// The next paste id, or `None` when the selection would not change.
+    pub(super) fn sidebar_navigation_target(&self, direction: i32) -> Option<String> {
+        let order: Vec<usize> = if self.sidebar_group_state.group_by == SidebarGroupBy::None {
+            (0..self.pastes.len()).collect()
+        } else {
+            sidebar_rows(&self.sidebar_groups(), &self.sidebar_group_state.collapsed)
+                .into_iter()
+                .filter_map(|row| match row {
+                    SidebarRow::Paste(idx) => Some(idx),
+                    SidebarRow::Header(_) => None,
+                })
+                .collect()
+        };
+        if order.is_empty() {
+            return None;
+        }
+        let current = self
+            .selected_index()
+            .and_then(|selected| order.iter().position(|idx| *idx == selected))
+            .unwrap_or(0) as i32;
+        let max_index = (order.len() - 1) as i32;
+        let next = order[(current + direction).clamp(0, max_index) as usize];
+        if self.selected_index() == Some(next) {
+            return None;
+        }
+        self.pastes.get(next).map(|paste| paste.id.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(
+        id: &str,
+        language: Option<&str>,
+        folder_id: Option<&str>,
+        updated_at: DateTime<Utc>,
+    ) -> PasteSummary {
+        PasteSummary {
+            id: id.to_string(),
+            name: id.to_string(),
+            language: language.map(ToString::to_string),
+            content_len: 1,
+            updated_at,
+            folder_id: folder_id.map(ToString::to_string),
+            tags: Vec::new(),
+            derived: Default::default(),
+        }
+    }
+
+    #[test]
+    fn language_and_folder_groups_sort_named_first_and_missing_last() {
+        let now = Utc::now();
+        let items = vec![
+            summary("a", Some("Rust"), Some("work"), now),
+            summary("b", None, None, now),
+            summary("c", Some("python"), Some("home"), now),
+            summary("d", Some("rust"), None, now),
+        ];
+        let today = Local::now().date_naive();
+        let cutoff = now - ChronoDuration::days(7);
+
+        let by_lang = build_sidebar_groups(&items, SidebarGroupBy::Language, today, cutoff);
+        let labels: Vec<&str> = by_lang.iter().map(|g| g.label.as_str()).collect();
+        assert_eq!(labels, vec!["python", "rust", NO_LANGUAGE_GROUP_LABEL]);
+        assert_eq!(by_lang[1].indices, vec![0, 3]);
+
+        let by_folder = build_sidebar_groups(&items, SidebarGroupBy::Folder, today, cutoff);
+        let labels: Vec<&str> = by_folder.iter().map(|g| g.label.as_str()).collect();
+        assert_eq!(labels, vec!["home", "work", UNFILED_GROUP_LABEL]);
+        assert_eq!(by_folder[2].indices, vec![1, 3]);
+
+        assert!(build_sidebar_groups(&items, SidebarGroupBy::None, today, cutoff).is_empty());
+    }
+
+    #[test]
+    fn folder_group_label_prefers_folder_name_and_size() {
+        let group = SidebarGroup {
+            key: "folder:f1".to_string(),
+            label: "f1".to_string(),
+            indices: vec![0, 2],
+        };
+        assert_eq!(folder_group_label(&group, None), "f1 (2)");
+        let mut folder = Folder::new("Work".to_string());
+        folder.paste_count = 5;
+        folder.total_bytes = 1234;
+        assert_eq!(
+            folder_group_label(&group, Some(&folder)),
+            "Work (2) - 1234 bytes"
+        );
+        let lines = folder_stats_lines(&folder, DisplayTimezone::Utc);
+        assert_eq!(lines[0], "Pastes: 5");
+        assert_eq!(lines[2], "Last updated: Never");
+    }
+
+    #[test]
+    fn folder_group_keys_expose_folder_ids_except_unfiled() {
+        assert_eq!(sidebar_group_folder_id("folder:abc"), Some("abc"));
+        assert_eq!(sidebar_group_folder_id("folder:"), None);
+        assert_eq!(sidebar_group_folder_id("lang:rust"), None);
+    }
+
+    #[test]
+    fn date_groups_bucket_today_week_and_older_in_order() {
+        let now = Utc::now();
+        let items = vec![
+            summary("old", None, None, now - ChronoDuration::days(30)),
+            summary("today", None, None, now),
+            summary("week", None, None, now - ChronoDuration::days(3)),
+        ];
+        let groups = build_sidebar_groups(
+            &items,
+            SidebarGroupBy::Date,
+            now.with_timezone(&Local).date_naive(),
+            now - ChronoDuration::days(7),
+        );
+        let labels: Vec<&str> = groups.iter().map(|g| g.label.as_str()).collect();
+        assert_eq!(labels, vec!["Today", "This week", "Older"]);
+        assert_eq!(groups[0].indices, vec![1]);
+        assert_eq!(groups[2].indices, vec![0]);
+    }
+
+    #[test]
+    fn collapsed_groups_keep_headers_but_hide_members() {
+        let groups = vec![
+            SidebarGroup {
+                key: "lang:rust".to_string(),
+                label: "rust".to_string(),
+                indices: vec![0, 2],
+            },
+            SidebarGroup {
+                key: "lang:".to_string(),
+                label: NO_LANGUAGE_GROUP_LABEL.to_string(),
+                indices: vec![1],
+            },
+        ];
+        let mut collapsed = BTreeSet::new();
+        collapsed.insert("lang:rust".to_string());
+        assert_eq!(
+            sidebar_rows(&groups, &collapsed),
+            vec![
+                SidebarRow::Header(0),
+                SidebarRow::Header(1),
+                SidebarRow::Paste(1)
+            ]
+        );
+    }
+}
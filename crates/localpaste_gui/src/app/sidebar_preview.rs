@@ -0,0 +1,69 @@
+//! Lazily fetched sidebar hover previews cached per paste revision.
+
+use super::LocalPasteApp;
+use crate::backend::{CoreCmd, PasteSummary};
+use localpaste_core::models::paste::PastePreview;
+use std::collections::{HashMap, HashSet};
+
+const SIDEBAR_PREVIEW_CACHE_CAP: usize = 256;
+
+/// Cache of hover previews keyed by paste id.
+///
+/// Entries are only served while their `updated_at` matches the summary being
+/// hovered, so edits made locally or through the API trigger a refetch.
+#[derive(Debug, Default)]
+pub(super) struct SidebarPreviewCache {
+    entries: HashMap<String, PastePreview>,
+    pending: HashSet<String>,
+}
+
+impl SidebarPreviewCache {
+    /// Returns a cached preview that still matches the summary revision.
+    ///
+    /// # Returns
+    /// `Some(preview)` when cached and fresh, otherwise `None`.
+    pub(super) fn fresh(&self, summary: &PasteSummary) -> Option<&PastePreview> {
+        self.entries
+            .get(summary.id.as_str())
+            .filter(|preview| preview.updated_at == summary.updated_at)
+    }
+
+    /// Returns whether a preview request is already in flight for `id`.
+    pub(super) fn is_pending(&self, id: &str) -> bool {
+        self.pending.contains(id)
+    }
+}
+
+impl LocalPasteApp {
+    /// Requests a hover preview for `summary` unless a fresh one is cached or in flight.
+    pub(super) fn request_sidebar_preview(&mut self, summary: &PasteSummary) {
+        if self.sidebar_previews.fresh(summary).is_some()
+            || self.sidebar_previews.is_pending(summary.id.as_str())
+        {
+            return;
+        }
+        let id = summary.id.clone();
+        if self
+            .backend
+            .cmd_tx
+            .send(CoreCmd::GetPastePreview { id: id.clone() })
+            .is_ok()
+        {
+            self.sidebar_previews.pending.insert(id);
+        }
+    }
+
+    /// Stores a backend preview response and clears its in-flight marker.
+    pub(super) fn store_sidebar_preview(&mut self, id: String, preview: Option<PastePreview>) {
+        let cache = &mut self.sidebar_previews;
+        cache.pending.remove(id.as_str());
+        let Some(preview) = preview else {
+            cache.entries.remove(id.as_str());
+            return;
+        };
+        if cache.entries.len() >= SIDEBAR_PREVIEW_CACHE_CAP && !cache.entries.contains_key(&id) {
+            cache.entries.clear();
+        }
+        cache.entries.insert(id, preview);
+    }
+}
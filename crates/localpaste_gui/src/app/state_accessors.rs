@@ -23,7 +23,10 @@ impl LocalPasteApp {
     /// # Returns
     /// `true` when a modal keyboard-owning surface is open.
     pub(super) fn keyboard_overlay_open(&self) -> bool {
-        self.command_palette_open || self.shortcut_help_open || self.version_overlay_open()
+        self.command_palette_open
+            || self.shortcut_help_open
+            || self.symbol_outline.jump_open
+            || self.version_overlay_open()
     }
 
     /// Returns whether the app is currently in interactive virtual-editor mode.
@@ -2,6 +2,7 @@
 
 use super::LocalPasteApp;
 use crate::backend::PasteSummary;
+use chrono::{Duration as ChronoDuration, Local, Utc};
 use localpaste_core::models::paste::Paste;
 
 impl LocalPasteApp {
@@ -42,4 +43,49 @@ impl LocalPasteApp {
         Self::sort_paste_summaries_by_recency(&mut self.all_pastes);
         self.pastes = self.filter_by_collection(&self.all_pastes);
     }
+
+    /// Filters sidebar summaries through the active collection/language state.
+    /// # Returns
+    /// Visible sidebar rows preserving the input ordering of `items`.
+    pub(super) fn filter_by_collection(&self, items: &[PasteSummary]) -> Vec<PasteSummary> {
+        let now = Utc::now();
+        let today_local = Local::now().date_naive();
+        let week_cutoff = now - ChronoDuration::days(7);
+        let recent_cutoff = now - ChronoDuration::days(30);
+        let active_language_filter = self.active_language_filter.as_deref();
+        items
+            .iter()
+            .filter(|item| {
+                Self::matches_active_filters(
+                    item,
+                    &self.active_collection,
+                    active_language_filter,
+                    today_local,
+                    week_cutoff,
+                    recent_cutoff,
+                )
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Drops visible search rows that no longer match active collection/language filters.
+    pub(super) fn retain_search_results_for_active_filters(&mut self) {
+        let now = Utc::now();
+        let today_local = Local::now().date_naive();
+        let week_cutoff = now - ChronoDuration::days(7);
+        let recent_cutoff = now - ChronoDuration::days(30);
+        let active_collection = self.active_collection.clone();
+        let active_language_filter = self.active_language_filter.clone();
+        self.pastes.retain(|item| {
+            Self::matches_active_filters(
+                item,
+                &active_collection,
+                active_language_filter.as_deref(),
+                today_local,
+                week_cutoff,
+                recent_cutoff,
+            )
+        });
+    }
 }
@@ -52,6 +52,9 @@ impl LocalPasteApp {
         };
         self.export_result_rx = None;
         match completion.result {
+            Ok(()) if completion.printed => {
+                self.set_status("Opened print preview; print from the viewer.");
+            }
             Ok(()) => {
                 self.set_status(format!(
                     "Exported {} to {}",
@@ -59,7 +62,12 @@ impl LocalPasteApp {
                 ));
             }
             Err(err) => {
-                self.set_status(format!("Export failed: {}", err));
+                let action = if completion.printed {
+                    "Print"
+                } else {
+                    "Export"
+                };
+                self.set_status(format!("{} failed: {}", action, err));
             }
         }
     }
@@ -1,25 +1,25 @@
 //! State transitions for backend events, selection, and autosave flow.
 
+mod export;
 mod filters;
+mod url_source;
 
 use super::util::format_fenced_code_block;
 use super::{
-    ExportCompletion, LocalPasteApp, MetadataDraftSnapshot, PaletteCopyAction, SaveStatus,
-    SidebarCollection, PALETTE_SEARCH_LIMIT, SEARCH_DEBOUNCE,
+    LocalPasteApp, MetadataDraftSnapshot, PaletteCopyAction, SaveStatus, SidebarCollection,
+    PALETTE_SEARCH_LIMIT, SEARCH_DEBOUNCE,
 };
-use crate::backend::{CoreCmd, CoreErrorSource, CoreEvent, PasteSummary};
-use chrono::{Duration as ChronoDuration, Local, Utc};
+use crate::backend::{CoreCmd, CoreErrorSource, CoreEvent, PasteSummary, QueryKind};
+use chrono::{Local, Utc};
 use localpaste_core::{
-    models::paste::Paste, DEFAULT_LIST_PASTES_LIMIT, DEFAULT_SEARCH_PASTES_LIMIT,
+    models::paste::Paste, normalization::content_matches_stored, DEFAULT_LIST_PASTES_LIMIT,
+    DEFAULT_SEARCH_PASTES_LIMIT,
 };
 use std::collections::BTreeSet;
 use std::time::Instant;
 use tracing::warn;
 
-use self::filters::{
-    language_extension, matches_semantic_collection, normalize_language_filter_value,
-    parse_tags_csv, sanitize_filename,
-};
+use self::filters::{matches_semantic_collection, normalize_language_filter_value, parse_tags_csv};
 
 impl LocalPasteApp {
     fn send_backend_cmd_or_status(&mut self, command: CoreCmd, error_message: &str) -> bool {
@@ -47,7 +47,9 @@ impl LocalPasteApp {
         self.save_in_flight = true;
         self.save_status = SaveStatus::Saving;
 
-        let command = if self.is_virtual_editor_mode() {
+        let command = if let Some(command) = self.collab_merge_command(id.as_str()) {
+            command
+        } else if self.is_virtual_editor_mode() {
             CoreCmd::UpdatePasteVirtual {
                 id,
                 content: self.virtual_editor_buffer.rope().clone(),
@@ -88,8 +90,13 @@ impl LocalPasteApp {
             CoreEvent::PasteLoaded { paste } => {
                 if self.selected_id.as_deref() == Some(paste.id.as_str()) {
                     self.select_loaded_paste(paste);
+                    self.apply_pending_line_jump();
+                    self.apply_pending_search_reveal();
                 }
             }
+            CoreEvent::PastePreviewLoaded { id, preview } => {
+                self.store_sidebar_preview(id, preview);
+            }
             CoreEvent::DiffPreviewComputed { request_id, diff } => {
                 self.apply_diff_preview_response(request_id, diff);
             }
@@ -139,6 +146,7 @@ impl LocalPasteApp {
                 self.set_status(status);
             }
             CoreEvent::PasteSaved { paste } => {
+                self.absorb_merged_save(&paste);
                 let paste_id = paste.id.clone();
                 let requested_revision = self.save_request_revision.take();
                 self.upsert_cached_paste_summary(&paste);
@@ -155,15 +163,18 @@ impl LocalPasteApp {
                         // Use snapshot comparison as a safe fallback for late save acks.
                         requested_revision
                             .map(|revision| self.active_revision() != revision)
-                            .unwrap_or_else(|| self.active_snapshot() != paste.content)
+                            .unwrap_or_else(|| {
+                                !content_matches_stored(&self.active_snapshot(), &paste.content)
+                            })
                     } else {
-                        self.selected_content.as_str() != paste.content
+                        !content_matches_stored(self.selected_content.as_str(), &paste.content)
                     };
                     if !self.metadata_dirty && !self.metadata_save_in_flight {
                         self.sync_editor_metadata(&paste);
                     }
                     self.selected_paste = Some(paste);
                     self.save_in_flight = false;
+                    self.reload_annotations_after_save(paste_id.as_str());
                     if has_newer_local_edits {
                         // Keep autosave armed when this ack corresponds to an older snapshot.
                         self.save_status = SaveStatus::Dirty;
@@ -183,6 +194,12 @@ impl LocalPasteApp {
                     self.ensure_selection_after_list_update();
                 }
             }
+            CoreEvent::CollabRevision { id, rev } => {
+                self.apply_collab_revision(id.as_str(), rev);
+            }
+            CoreEvent::PasteRemoteEdited { paste, rev } => {
+                self.apply_remote_edit(paste, rev);
+            }
             CoreEvent::PasteMetaSaved { paste } => {
                 let requested_metadata = self.metadata_save_request.take();
                 self.metadata_save_in_flight = false;
@@ -306,17 +323,114 @@ impl LocalPasteApp {
                 }
                 self.set_status(message);
             }
+            CoreEvent::PasteRefreshedFromSource { paste, changed } => {
+                self.apply_paste_refreshed_from_source(paste, changed);
+            }
             CoreEvent::PasteVersionsLoaded { .. }
             | CoreEvent::PasteVersionLoaded { .. }
             | CoreEvent::PasteVersionLoadFailed { .. }
             | CoreEvent::PasteResetToVersion { .. }
             | CoreEvent::DiffTargetLoaded { .. }
             | CoreEvent::DiffTargetLoadFailed { .. }
-            | CoreEvent::FoldersLoaded { items: _ }
             | CoreEvent::ShutdownComplete { flush_result: _ } => {}
+            CoreEvent::FoldersLoaded { items } => {
+                self.sidebar_group_state.folders = items
+                    .into_iter()
+                    .map(|folder| (folder.id.clone(), folder))
+                    .collect();
+            }
+            CoreEvent::DraftsLoaded { drafts } => self.offer_draft_recovery(drafts),
+            CoreEvent::CommentsLoaded { paste_id, comments } => {
+                self.apply_comments_loaded(paste_id, comments)
+            }
+            CoreEvent::AnnotationsLoaded {
+                paste_id,
+                annotations,
+            } => self.apply_annotations_loaded(paste_id, annotations),
+            CoreEvent::SnippetsImported { created, failed } => {
+                self.request_refresh();
+                if failed == 0 {
+                    self.set_status(format!("Imported {} snippet(s).", created));
+                } else {
+                    self.set_status(format!(
+                        "Imported {} snippet(s); {} failed (too large or not saved).",
+                        created, failed
+                    ));
+                }
+            }
+            CoreEvent::PasteSplit { created, failed } => {
+                self.request_refresh();
+                if failed == 0 {
+                    self.set_status(format!("Split into {} paste(s).", created));
+                } else {
+                    self.set_status(format!(
+                        "Split into {} paste(s); {} failed (too large or not saved).",
+                        created, failed
+                    ));
+                }
+            }
+            CoreEvent::PastesMerged { paste, deleted } => {
+                for id in &deleted {
+                    self.all_pastes.retain(|item| &item.id != id);
+                    self.pastes.retain(|item| &item.id != id);
+                    self.clear_pending_copy_for(id.as_str());
+                    if self.selected_id.as_deref() == Some(id.as_str()) {
+                        self.clear_selection();
+                    }
+                }
+                let name = paste.name.clone();
+                self.apply_event(CoreEvent::PasteCreated { paste });
+                self.request_refresh();
+                if deleted.is_empty() {
+                    self.set_status(format!("Merged into \"{}\".", name));
+                } else {
+                    self.set_status(format!(
+                        "Merged into \"{}\"; deleted {} original(s).",
+                        name,
+                        deleted.len()
+                    ));
+                }
+            }
+            CoreEvent::FolderRulesLoaded { rules } => self.apply_folder_rules_loaded(rules),
+            CoreEvent::CleanupFinished { report } => {
+                self.request_refresh();
+                self.set_status(format!(
+                    "Cleanup removed {} orphan draft(s) and checked {} folder(s).",
+                    report.orphan_drafts_removed, report.folders_reconciled
+                ));
+            }
+            CoreEvent::BackupsLoaded {
+                items,
+                pending_restore,
+                interval_hours,
+            } => self.apply_backups_loaded(items, pending_restore, interval_hours),
+            CoreEvent::BackupCreated {
+                path,
+                remote_failures,
+            } => self.apply_backup_created(&path, &remote_failures),
+            CoreEvent::BackupVerified { path, result } => self.apply_backup_verified(path, result),
+            CoreEvent::BackupDeleted { path } => self.apply_backup_deleted(&path),
+            CoreEvent::BackupsPruned { deleted } => self.apply_backups_pruned(deleted),
+            CoreEvent::StorageChecked { alerts } => self.apply_storage_checked(alerts),
+            CoreEvent::RestoreScheduled { path } => self.apply_restore_scheduled(path),
+            CoreEvent::BackupScheduleSaved { interval_hours } => {
+                self.apply_backup_schedule_saved(interval_hours)
+            }
+            CoreEvent::DisplayTimezoneSaved { timezone } => {
+                self.apply_display_timezone_saved(timezone)
+            }
+            CoreEvent::UpdateSettingsSaved { updates } => self.apply_update_settings_saved(updates),
             CoreEvent::FolderSaved { folder: _ } | CoreEvent::FolderDeleted { id: _ } => {
                 self.request_refresh();
             }
+            CoreEvent::FolderExported {
+                path, paste_count, ..
+            }
+            | CoreEvent::GistLayoutExported { path, paste_count } => self.set_status(format!(
+                "Exported {} paste(s) to {}",
+                paste_count,
+                path.display()
+            )),
             CoreEvent::Error { source, message } => {
                 warn!("backend error ({:?}): {}", source, message);
                 // Only mutate save-in-flight state for the matching request class.
@@ -342,6 +456,7 @@ impl LocalPasteApp {
                         }
                         self.save_in_flight = false;
                         self.save_request_revision = None;
+                        self.clear_collab_sent();
                         if let Some(pending) = self.pending_selection_id.take() {
                             self.clear_pending_copy_for(pending.as_str());
                         }
@@ -362,6 +477,7 @@ impl LocalPasteApp {
             .send(CoreCmd::ListPastes {
                 limit: DEFAULT_LIST_PASTES_LIMIT,
                 folder_id: None,
+                token: self.backend.query_tokens.next(QueryKind::List),
             })
             .is_err()
         {
@@ -371,6 +487,9 @@ impl LocalPasteApp {
         self.query_perf.list_requests_sent = self.query_perf.list_requests_sent.saturating_add(1);
         self.query_perf.list_last_sent_at = Some(sent_at);
         self.last_refresh_at = sent_at;
+        if self.folder_stats_visible() {
+            self.request_folder_stats();
+        }
     }
 
     /// Updates the sidebar search query and starts debounce timing.
@@ -474,6 +593,7 @@ impl LocalPasteApp {
                 limit: DEFAULT_SEARCH_PASTES_LIMIT,
                 folder_id,
                 language,
+                token: self.backend.query_tokens.next(QueryKind::Search),
             })
             .is_err()
         {
@@ -524,6 +644,7 @@ impl LocalPasteApp {
             .send(CoreCmd::SearchPalette {
                 query: query.clone(),
                 limit: PALETTE_SEARCH_LIMIT,
+                token: self.backend.query_tokens.next(QueryKind::Palette),
             })
             .is_err()
         {
@@ -636,6 +757,7 @@ impl LocalPasteApp {
         self.virtual_selection.clear();
         self.clear_highlight_state();
         self.selected_paste = Some(paste);
+        self.search_matches.source = None;
         self.try_complete_pending_copy();
         self.save_status = SaveStatus::Saved;
         self.last_edit_at = None;
@@ -643,6 +765,7 @@ impl LocalPasteApp {
         self.save_request_revision = None;
         self.metadata_save_in_flight = false;
         self.metadata_save_request = None;
+        self.reset_collab();
         self.clear_version_view_state();
     }
 
@@ -665,6 +788,7 @@ impl LocalPasteApp {
         self.last_edit_at = None;
         self.save_in_flight = false;
         self.save_request_revision = None;
+        self.reset_collab();
         self.clear_version_view_state();
     }
 
@@ -722,14 +846,20 @@ impl LocalPasteApp {
 
     /// Creates a new paste pre-populated with `content`.
     pub(super) fn create_new_paste_with_content(&mut self, content: String) {
+        self.dispatch_create_paste(CoreCmd::CreatePaste { content });
+    }
+
+    /// Creates a new paste from clipboard text under the clipboard-capture size limit.
+    pub(super) fn create_new_paste_from_clipboard(&mut self, content: String) {
+        self.dispatch_create_paste(CoreCmd::CreateClipboardPaste { content });
+    }
+
+    fn dispatch_create_paste(&mut self, command: CoreCmd) {
         if self.mutation_shortcut_block_reason().is_some() {
             self.set_mutation_shortcut_blocked_status();
             return;
         }
-        let _sent = self.send_backend_cmd_or_status(
-            CoreCmd::CreatePaste { content },
-            "Create failed: backend unavailable.",
-        );
+        let _sent = self.send_backend_cmd_or_status(command, "Create failed: backend unavailable.");
     }
 
     /// Sends a delete command for `id` and reports whether dispatch succeeded.
@@ -848,43 +978,6 @@ impl LocalPasteApp {
         self.metadata_save_request = Some(request);
     }
 
-    /// Starts asynchronous export of the selected paste to a user-chosen file path.
-    pub(super) fn export_selected_paste(&mut self) {
-        let Some(paste_id) = self.selected_paste.as_ref().map(|paste| paste.id.clone()) else {
-            self.set_status("Nothing selected to export.");
-            return;
-        };
-        if self.export_result_rx.is_some() {
-            self.set_status("Export already in progress.");
-            return;
-        }
-        let extension = language_extension(self.edit_language.as_deref());
-        let default_name = format!("{}.{}", sanitize_filename(&self.edit_name), extension);
-        let dialog = rfd::FileDialog::new()
-            .set_file_name(default_name.as_str())
-            .add_filter("Text", &[extension]);
-        let Some(path) = dialog.save_file() else {
-            return;
-        };
-
-        let content = self.active_snapshot();
-        let path_for_write = path.clone();
-        let completion = ExportCompletion {
-            paste_id,
-            path: path.to_string_lossy().to_string(),
-            result: Ok(()),
-        };
-        let (tx, rx) = std::sync::mpsc::channel();
-        self.export_result_rx = Some(rx);
-        std::thread::spawn(move || {
-            let mut completion = completion;
-            completion.result =
-                std::fs::write(&path_for_write, content).map_err(|err| err.to_string());
-            let _ = tx.send(completion);
-        });
-        self.set_status("Export started...");
-    }
-
     /// Returns the visible-list index of the selected paste, if visible.
     /// # Returns
     /// `Some(index)` when the selected paste is visible, otherwise `None`.
@@ -897,7 +990,11 @@ impl LocalPasteApp {
         (None, self.active_language_filter.clone())
     }
 
-    fn matches_active_filters(
+    /// Returns whether a summary passes the active collection and language filters.
+    ///
+    /// # Returns
+    /// `true` when `item` belongs in the visible sidebar projection.
+    pub(super) fn matches_active_filters(
         item: &PasteSummary,
         active_collection: &SidebarCollection,
         active_language_filter: Option<&str>,
@@ -936,50 +1033,6 @@ impl LocalPasteApp {
         }
     }
 
-    /// Filters sidebar summaries through the active collection/language state.
-    /// # Returns
-    /// Visible sidebar rows preserving the input ordering of `items`.
-    pub(super) fn filter_by_collection(&self, items: &[PasteSummary]) -> Vec<PasteSummary> {
-        let now = Utc::now();
-        let today_local = Local::now().date_naive();
-        let week_cutoff = now - ChronoDuration::days(7);
-        let recent_cutoff = now - ChronoDuration::days(30);
-        let active_language_filter = self.active_language_filter.as_deref();
-        items
-            .iter()
-            .filter(|item| {
-                Self::matches_active_filters(
-                    item,
-                    &self.active_collection,
-                    active_language_filter,
-                    today_local,
-                    week_cutoff,
-                    recent_cutoff,
-                )
-            })
-            .cloned()
-            .collect()
-    }
-
-    fn retain_search_results_for_active_filters(&mut self) {
-        let now = Utc::now();
-        let today_local = Local::now().date_naive();
-        let week_cutoff = now - ChronoDuration::days(7);
-        let recent_cutoff = now - ChronoDuration::days(30);
-        let active_collection = self.active_collection.clone();
-        let active_language_filter = self.active_language_filter.clone();
-        self.pastes.retain(|item| {
-            Self::matches_active_filters(
-                item,
-                &active_collection,
-                active_language_filter.as_deref(),
-                today_local,
-                week_cutoff,
-                recent_cutoff,
-            )
-        });
-    }
-
     /// Ensures the current selection still exists in the visible sidebar list.
     ///
     /// When the active item no longer matches the current filters, this selects
@@ -0,0 +1,241 @@
+//! Exports of the selected paste as raw text or rendered HTML/PDF, printing,
+//! and folder ZIP archives.
+
+use crate::app::{ExportCompletion, LocalPasteApp};
+use crate::backend::CoreCmd;
+use chrono::Local;
+use localpaste_core::export::{render_export, render_print_pdf, ExportDocument, PrintOptions};
+use localpaste_core::models::paste::ExportFormat;
+use localpaste_core::naming::files::{language_extension, sanitize_filename};
+use std::path::Path;
+#[cfg(not(target_os = "windows"))]
+use std::process::Command;
+
+impl LocalPasteApp {
+    /// Starts asynchronous export of the selected paste to a user-chosen file path.
+    ///
+    /// # Arguments
+    /// - `format`: `None` writes raw text; `Some` renders highlighted HTML/PDF.
+    pub(crate) fn export_selected_paste(&mut self, format: Option<ExportFormat>) {
+        let Some(paste_id) = self.selected_paste.as_ref().map(|paste| paste.id.clone()) else {
+            self.set_status("Nothing selected to export.");
+            return;
+        };
+        if self.export_result_rx.is_some() {
+            self.set_status("Export already in progress.");
+            return;
+        }
+        let extension = format.map_or_else(
+            || language_extension(self.edit_language.as_deref()),
+            ExportFormat::extension,
+        );
+        let default_name = format!("{}.{}", sanitize_filename(&self.edit_name), extension);
+        let filter = if format.is_some() { "Document" } else { "Text" };
+        let dialog = rfd::FileDialog::new()
+            .set_file_name(default_name.as_str())
+            .add_filter(filter, &[extension]);
+        let Some(path) = dialog.save_file() else {
+            return;
+        };
+
+        let content = self.active_snapshot();
+        let name = self.edit_name.clone();
+        let language = self.edit_language.clone();
+        let annotations = self.annotations.for_paste(paste_id.as_str());
+        let path_for_write = path.clone();
+        let completion = ExportCompletion {
+            paste_id,
+            path: path.to_string_lossy().to_string(),
+            printed: false,
+            result: Ok(()),
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.export_result_rx = Some(rx);
+        std::thread::spawn(move || {
+            let mut completion = completion;
+            let bytes = match format {
+                None => Ok(content.into_bytes()),
+                Some(format) => render_export(
+                    &ExportDocument {
+                        name: name.as_str(),
+                        language: language.as_deref(),
+                        content: content.as_str(),
+                        annotations: annotations.as_slice(),
+                    },
+                    format,
+                )
+                .map_err(|err| err.to_string()),
+            };
+            completion.result = bytes.and_then(|bytes| {
+                std::fs::write(&path_for_write, bytes).map_err(|err| err.to_string())
+            });
+            let _ = tx.send(completion);
+        });
+        self.set_status("Export started...");
+    }
+
+    /// Renders the selected paste as a printable PDF and opens it for printing.
+    ///
+    /// The PDF carries a name/date header on every page and line numbers. It is
+    /// written to the temp directory and handed to the system viewer, whose
+    /// print dialog does the actual printing.
+    pub(crate) fn print_selected_paste(&mut self) {
+        let Some(paste_id) = self.selected_paste.as_ref().map(|paste| paste.id.clone()) else {
+            self.set_status("Nothing selected to print.");
+            return;
+        };
+        if self.export_result_rx.is_some() {
+            self.set_status("Export already in progress.");
+            return;
+        }
+        let content = self.active_snapshot();
+        let name = self.edit_name.clone();
+        let language = self.edit_language.clone();
+        let annotations = self.annotations.for_paste(paste_id.as_str());
+        let options = PrintOptions {
+            header: Some(format!(
+                "{}  |  {}",
+                name,
+                Local::now().format("%Y-%m-%d %H:%M")
+            )),
+            line_numbers: true,
+        };
+        // Only the id goes into the name: the viewer launcher must never see
+        // user-controlled text.
+        let path = std::env::temp_dir().join(format!("localpaste-print-{}.pdf", paste_id));
+        let completion = ExportCompletion {
+            paste_id,
+            path: path.to_string_lossy().to_string(),
+            printed: true,
+            result: Ok(()),
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.export_result_rx = Some(rx);
+        std::thread::spawn(move || {
+            let mut completion = completion;
+            completion.result = render_print_pdf(
+                &ExportDocument {
+                    name: name.as_str(),
+                    language: language.as_deref(),
+                    content: content.as_str(),
+                    annotations: annotations.as_slice(),
+                },
+                &options,
+            )
+            .map_err(|err| err.to_string())
+            .and_then(|bytes| std::fs::write(&path, bytes).map_err(|err| err.to_string()))
+            .and_then(|()| open_with_system_viewer(&path));
+            let _ = tx.send(completion);
+        });
+        self.set_status("Preparing print preview...");
+    }
+
+    /// Asks for a destination and exports a folder subtree as a ZIP archive.
+    ///
+    /// The backend worker reads the folder's pastes and writes the archive;
+    /// completion arrives as a `FolderExported` event.
+    ///
+    /// # Arguments
+    /// - `folder_id`: Root folder of the export.
+    pub(crate) fn export_folder(&mut self, folder_id: String) {
+        let default_name = format!("{}.zip", sanitize_filename(folder_id.as_str()));
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(default_name.as_str())
+            .add_filter("ZIP archive", &["zip"])
+            .save_file()
+        else {
+            return;
+        };
+        if self
+            .backend
+            .cmd_tx
+            .send(CoreCmd::ExportFolder {
+                id: folder_id,
+                path,
+            })
+            .is_err()
+        {
+            self.set_status("Export folder failed: backend unavailable.");
+            return;
+        }
+        self.set_status("Exporting folder...");
+    }
+
+    /// Prompts for a directory and writes every paste into the gist layout there.
+    pub(crate) fn export_gist_layout(&mut self) {
+        let Some(path) = rfd::FileDialog::new().pick_folder() else {
+            return;
+        };
+        if self
+            .backend
+            .cmd_tx
+            .send(CoreCmd::ExportGistLayout { path })
+            .is_err()
+        {
+            self.set_status("Gist export failed: backend unavailable.");
+            return;
+        }
+        self.set_status("Exporting pastes to gist directory...");
+    }
+}
+
+/// Opens a file with the platform's default application.
+///
+/// Windows goes through `ShellExecuteW` rather than `cmd /C start`, so the
+/// path is never parsed by a shell.
+#[cfg(target_os = "windows")]
+fn open_with_system_viewer(path: &Path) -> Result<(), String> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::UI::Shell::ShellExecuteW;
+    use windows_sys::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+    let wide = |value: &std::ffi::OsStr| -> Vec<u16> {
+        value.encode_wide().chain(std::iter::once(0)).collect()
+    };
+    let operation = wide(std::ffi::OsStr::new("open"));
+    let file = wide(path.as_os_str());
+    // SAFETY: both strings are NUL-terminated UTF-16 buffers that outlive the
+    // call; null window, parameter, and directory pointers are allowed.
+    let result = unsafe {
+        ShellExecuteW(
+            std::ptr::null_mut(),
+            operation.as_ptr(),
+            file.as_ptr(),
+            std::ptr::null(),
+            std::ptr::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+    // Values above 32 mean success; anything else is a SE_ERR_* code.
+    if result as isize > 32 {
+        Ok(())
+    } else {
+        Err(format!(
+            "viewer failed to open (code {}); the PDF is at {}",
+            result as isize,
+            path.display()
+        ))
+    }
+}
+
+/// Opens a file with the platform's default application and waits for the launcher.
+#[cfg(not(target_os = "windows"))]
+fn open_with_system_viewer(path: &Path) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let mut command = Command::new("xdg-open");
+    let status = command
+        .arg(path)
+        .status()
+        .map_err(|err| format!("could not launch a viewer: {}", err))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "viewer exited with {}; the PDF is at {}",
+            status,
+            path.display()
+        ))
+    }
+}
@@ -286,73 +286,6 @@ pub(super) fn matches_semantic_collection(
     }
 }
 
-/// Maps canonical language labels to preferred export file extensions.
-///
-/// # Returns
-/// Extension without leading dot, defaulting to `"txt"`.
-pub(super) fn language_extension(language: Option<&str>) -> &'static str {
-    let canonical =
-        localpaste_core::detection::canonical::canonicalize(language.unwrap_or_default().trim());
-    match canonical.as_str() {
-        "rust" => "rs",
-        "python" => "py",
-        "javascript" => "js",
-        "typescript" => "ts",
-        "json" => "json",
-        "yaml" => "yaml",
-        "toml" => "toml",
-        "markdown" => "md",
-        "html" => "html",
-        "css" => "css",
-        "scss" => "scss",
-        "sass" => "sass",
-        "sql" => "sql",
-        "shell" => "sh",
-        "cs" => "cs",
-        "cpp" => "cpp",
-        "c" => "c",
-        "go" => "go",
-        "java" => "java",
-        "kotlin" => "kt",
-        "swift" => "swift",
-        "ruby" => "rb",
-        "php" => "php",
-        "perl" => "pl",
-        "lua" => "lua",
-        "r" => "r",
-        "scala" => "scala",
-        "dart" => "dart",
-        "elixir" => "ex",
-        "haskell" => "hs",
-        "zig" => "zig",
-        "xml" => "xml",
-        "dockerfile" => "dockerfile",
-        "makefile" => "makefile",
-        "powershell" => "ps1",
-        _ => "txt",
-    }
-}
-
-/// Sanitizes a filename candidate for cross-platform export compatibility.
-///
-/// # Returns
-/// Safe filename with reserved characters replaced by `_`.
-pub(super) fn sanitize_filename(value: &str) -> String {
-    let mut out: String = value
-        .chars()
-        .map(|ch| match ch {
-            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
-            _ => ch,
-        })
-        .collect();
-    out = out.trim().to_string();
-    if out.is_empty() {
-        "localpaste-export".to_string()
-    } else {
-        out
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -363,23 +296,6 @@ mod tests {
         assert_eq!(parsed, vec!["rust".to_string(), "CLI".to_string()]);
     }
 
-    #[test]
-    fn language_extension_maps_known_and_unknown_languages() {
-        assert_eq!(language_extension(Some("rust")), "rs");
-        assert_eq!(language_extension(Some(" Python ")), "py");
-        assert_eq!(language_extension(Some("csharp")), "cs");
-        assert_eq!(language_extension(Some("bash")), "sh");
-        assert_eq!(language_extension(Some("scss")), "scss");
-        assert_eq!(language_extension(Some("unknown")), "txt");
-        assert_eq!(language_extension(None), "txt");
-    }
-
-    #[test]
-    fn sanitize_filename_replaces_reserved_chars_and_falls_back() {
-        assert_eq!(sanitize_filename("bad<>:\"/\\|?*name"), "bad_________name");
-        assert_eq!(sanitize_filename("   "), "localpaste-export");
-    }
-
     #[test]
     fn smart_summary_heuristics_cover_suffixes_commands_and_urls() {
         let base = PasteSummary {
@@ -437,6 +353,7 @@ mod tests {
                 kind: PasteKind::Code,
                 handle: Some("fn handle_request".to_string()),
                 terms: vec!["handle_request".to_string()],
+                indent: None,
             },
             ..base.clone()
         };
@@ -445,6 +362,7 @@ mod tests {
                 kind: PasteKind::Config,
                 handle: Some("model gpt-4".to_string()),
                 terms: vec!["gpt-4".to_string()],
+                indent: None,
             },
             ..base.clone()
         };
@@ -453,6 +371,7 @@ mod tests {
                 kind: PasteKind::Log,
                 handle: Some("panic failed".to_string()),
                 terms: vec!["panic".to_string()],
+                indent: None,
             },
             ..base.clone()
         };
@@ -461,6 +380,7 @@ mod tests {
                 kind: PasteKind::Link,
                 handle: Some("example.com".to_string()),
                 terms: vec!["example".to_string()],
+                indent: None,
             },
             ..base
         };
@@ -0,0 +1,61 @@
+//! "Refresh from source" for pastes linked to a source URL.
+
+use crate::app::{LocalPasteApp, SEARCH_DEBOUNCE};
+use crate::backend::CoreCmd;
+use localpaste_core::models::paste::Paste;
+use std::time::Instant;
+
+impl LocalPasteApp {
+    /// Asks the backend to re-fetch the selected paste from its source URL.
+    ///
+    /// Refused while the paste has unsaved edits, since the fetched content
+    /// replaces the editor buffer.
+    pub(crate) fn refresh_selected_from_source(&mut self) {
+        let Some(id) = self.selected_id.clone() else {
+            self.set_status("Nothing selected to refresh.");
+            return;
+        };
+        if self.has_unsaved_changes() {
+            self.set_status("Save or discard changes before refreshing from source.");
+            return;
+        }
+        if self
+            .backend
+            .cmd_tx
+            .send(CoreCmd::RefreshPasteFromSource { id })
+            .is_err()
+        {
+            self.set_status("Refresh from source failed: backend unavailable.");
+            return;
+        }
+        self.set_status("Refreshing from source...");
+    }
+
+    /// Applies a finished source refresh to the list and the open editor.
+    pub(crate) fn apply_paste_refreshed_from_source(&mut self, paste: Paste, changed: bool) {
+        let selected = self.selected_id.as_deref() == Some(paste.id.as_str());
+        if !changed {
+            if selected {
+                self.set_status("Source unchanged.");
+            }
+            return;
+        }
+        self.upsert_cached_paste_summary(&paste);
+        if self.search_query.trim().is_empty() {
+            self.recompute_visible_pastes();
+        } else {
+            self.search_last_sent.clear();
+            self.search_last_input_at = Some(Instant::now() - SEARCH_DEBOUNCE);
+        }
+        if selected {
+            if self.has_unsaved_changes() {
+                // Edits made while the fetch ran win; the fetched content is
+                // still in version history.
+                self.set_status("Refreshed from source; kept unsaved local edits.");
+                return;
+            }
+            self.select_loaded_paste(paste);
+            self.set_status("Refreshed from source.");
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Line annotation loading, gutter lookup, and selection-based submission.
+
+use super::*;
+use chrono::Utc;
+use localpaste_core::models::annotation::Annotation;
+
+fn annotation(paste_id: &str, start_line: usize, end_line: usize) -> Annotation {
+    Annotation {
+        id: format!("{}-{}", paste_id, start_line),
+        paste_id: paste_id.to_string(),
+        start_line,
+        end_line,
+        author: "ana".to_string(),
+        author_id: None,
+        body: "note".to_string(),
+        detached: false,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[test]
+fn annotations_reload_after_save_and_map_to_editor_lines() {
+    let mut harness = make_app();
+    harness.app.selected_id = Some("alpha".to_string());
+    harness.app.sync_annotations();
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::ListAnnotations { paste_id } => assert_eq!(paste_id, "alpha"),
+        other => panic!("unexpected command: {:?}", other),
+    }
+    harness.app.apply_event(CoreEvent::AnnotationsLoaded {
+        paste_id: "beta".to_string(),
+        annotations: vec![annotation("beta", 1, 1)],
+    });
+    assert!(!harness.app.annotations.loaded);
+    harness.app.apply_event(CoreEvent::AnnotationsLoaded {
+        paste_id: "alpha".to_string(),
+        annotations: vec![annotation("alpha", 2, 3)],
+    });
+    assert!(harness.app.annotations.on_line(0).is_empty());
+    assert_eq!(harness.app.annotations.on_line(2).len(), 1);
+
+    harness.app.reload_annotations_after_save("beta");
+    assert!(harness.cmd_rx.try_recv().is_err());
+    harness.app.reload_annotations_after_save("alpha");
+    assert!(matches!(
+        recv_cmd(&harness.cmd_rx),
+        CoreCmd::ListAnnotations { .. }
+    ));
+}
+
+#[test]
+fn submit_annotation_covers_selected_lines() {
+    let mut harness = make_app();
+    harness.app.editor_mode = EditorMode::VirtualEditor;
+    harness.app.reset_virtual_editor("a\nb\nc\n");
+    harness.app.selected_id = Some("alpha".to_string());
+    harness.app.sync_annotations();
+    let _ = recv_cmd(&harness.cmd_rx);
+
+    // A selection ending at the start of line 4 stops at line 3.
+    let len = harness.app.virtual_editor_buffer.len_chars();
+    harness.app.virtual_editor_state.set_cursor(2, len);
+    harness.app.virtual_editor_state.move_cursor(6, len, true);
+    assert_eq!(harness.app.annotation_target_lines(), Some((2, 3)));
+
+    harness.app.comments.author = "ana".to_string();
+    harness.app.annotations.draft = " rename these \n".to_string();
+    harness.app.submit_annotation();
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::AddAnnotation {
+            paste_id,
+            start_line,
+            end_line,
+            author,
+            body,
+        } => {
+            assert_eq!(paste_id, "alpha");
+            assert_eq!((start_line, end_line), (2, 3));
+            assert_eq!(author, "ana");
+            assert_eq!(body, "rename these");
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(harness.app.annotations.draft.is_empty());
+}
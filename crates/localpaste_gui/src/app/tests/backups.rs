@@ -0,0 +1,108 @@
+//! Backups window commands and scheduled-backup behavior.
+
+use super::*;
+use localpaste_core::db::backup::BackupEntry;
+use std::path::PathBuf;
+
+fn backup_entry(name: &str, created_at_unix: u64) -> BackupEntry {
+    BackupEntry {
+        path: PathBuf::from(name),
+        created_at_unix,
+        size: 4096,
+        encrypted: false,
+        has_manifest: true,
+    }
+}
+
+#[test]
+fn scheduled_backup_lists_first_then_creates_when_due() {
+    let mut harness = make_app();
+    harness.app.maybe_run_scheduled_backup();
+    assert!(matches!(
+        harness.cmd_rx.try_recv(),
+        Err(TryRecvError::Empty)
+    ));
+
+    harness.app.backups = ui::backups::BackupsState::with_interval(Some(1));
+    harness.app.maybe_run_scheduled_backup();
+    assert!(matches!(
+        recv_cmd(&harness.cmd_rx),
+        CoreCmd::ListBackups { .. }
+    ));
+
+    let fresh = Utc::now().timestamp() as u64;
+    harness.app.apply_event(CoreEvent::BackupsLoaded {
+        items: vec![backup_entry("db.backup.1.redb", fresh)],
+        pending_restore: None,
+        interval_hours: Some(1),
+    });
+    harness.app.backups.schedule_checked_at = None;
+    harness.app.maybe_run_scheduled_backup();
+    assert!(matches!(
+        harness.cmd_rx.try_recv(),
+        Err(TryRecvError::Empty)
+    ));
+
+    harness.app.backups.items = vec![backup_entry("db.backup.1.redb", fresh - 7200)];
+    harness.app.backups.schedule_checked_at = None;
+    harness.app.maybe_run_scheduled_backup();
+    assert!(matches!(
+        recv_cmd(&harness.cmd_rx),
+        CoreCmd::CreateBackup { .. }
+    ));
+
+    // A failed attempt is not retried until another interval has passed.
+    harness.app.backups.schedule_checked_at = None;
+    harness.app.maybe_run_scheduled_backup();
+    assert!(matches!(
+        harness.cmd_rx.try_recv(),
+        Err(TryRecvError::Empty)
+    ));
+}
+
+#[test]
+fn backup_events_update_listing_and_pending_restore() {
+    let mut harness = make_app();
+    harness.app.open_backups();
+    assert!(matches!(
+        recv_cmd(&harness.cmd_rx),
+        CoreCmd::ListBackups { .. }
+    ));
+    harness.app.apply_event(CoreEvent::BackupsLoaded {
+        items: vec![
+            backup_entry("db.backup.2.redb", 2),
+            backup_entry("db.backup.1.redb", 1),
+        ],
+        pending_restore: None,
+        interval_hours: None,
+    });
+    harness.app.apply_event(CoreEvent::BackupVerified {
+        path: PathBuf::from("db.backup.1.redb"),
+        result: Err("checksum mismatch".to_string()),
+    });
+    assert!(harness
+        .app
+        .backups
+        .verified
+        .contains_key(&PathBuf::from("db.backup.1.redb")));
+
+    harness.app.apply_event(CoreEvent::BackupDeleted {
+        path: PathBuf::from("db.backup.1.redb"),
+    });
+    assert_eq!(harness.app.backups.items.len(), 1);
+    assert!(harness.app.backups.verified.is_empty());
+
+    harness.app.apply_event(CoreEvent::RestoreScheduled {
+        path: Some(PathBuf::from("db.backup.2.redb")),
+    });
+    assert_eq!(
+        harness.app.backups.pending_restore,
+        Some(PathBuf::from("db.backup.2.redb"))
+    );
+    harness.app.apply_event(CoreEvent::BackupScheduleSaved {
+        interval_hours: Some(12),
+    });
+    assert_eq!(harness.app.backups.interval_hours, Some(12));
+    assert!(harness.app.backups.schedule_enabled);
+    assert_eq!(harness.app.backups.interval_input, "12");
+}
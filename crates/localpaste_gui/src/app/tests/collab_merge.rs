@@ -0,0 +1,144 @@
+//! Collaborative save, poll, and remote-edit merge tests.
+
+use super::*;
+use crate::app::collab::CollabSession;
+use crate::app::virtual_editor::EditIntent;
+
+fn alpha(content: &str) -> Paste {
+    let mut paste = Paste::new(content.to_string(), "Alpha".to_string());
+    paste.id = "alpha".to_string();
+    paste
+}
+
+fn type_at(harness: &mut TestHarness, at: usize, text: &str) {
+    harness
+        .app
+        .replace_virtual_range(at..at, text, EditIntent::Other, true, Instant::now());
+    harness.app.save_status = SaveStatus::Dirty;
+}
+
+fn load_collab_paste(content: &str, rev: u64) -> TestHarness {
+    let mut harness = make_app();
+    harness.app.editor_mode = EditorMode::VirtualEditor;
+    harness.app.apply_event(CoreEvent::PasteLoaded {
+        paste: alpha(content),
+    });
+    harness.app.apply_event(CoreEvent::CollabRevision {
+        id: "alpha".to_string(),
+        rev,
+    });
+    harness
+}
+
+#[test]
+fn save_merges_from_known_revision_and_keeps_edits_typed_meanwhile() {
+    let mut harness = load_collab_paste("alpha beta gamma", 3);
+    type_at(&mut harness, 16, "!");
+
+    harness.app.save_now();
+    let sent = harness
+        .cmd_rx
+        .try_iter()
+        .find_map(|cmd| match cmd {
+            CoreCmd::MergePaste {
+                id,
+                base_rev,
+                content,
+            } => Some((id, base_rev, content.to_string())),
+            _ => None,
+        })
+        .expect("merge command");
+    assert_eq!(
+        sent,
+        ("alpha".to_string(), 3, "alpha beta gamma!".to_string())
+    );
+
+    // Typed while the merge was in flight; another editor changed "beta".
+    type_at(&mut harness, 0, ">");
+    harness.app.apply_event(CoreEvent::PasteSaved {
+        paste: alpha("alpha BETA gamma!"),
+    });
+    harness.app.apply_event(CoreEvent::CollabRevision {
+        id: "alpha".to_string(),
+        rev: 5,
+    });
+
+    assert_eq!(
+        harness.app.virtual_editor_buffer.to_string(),
+        ">alpha BETA gamma!"
+    );
+    assert_eq!(harness.app.save_status, SaveStatus::Dirty);
+    assert_eq!(harness.app.collab.rev, Some(5));
+}
+
+#[test]
+fn merged_save_without_newer_typing_is_saved() {
+    let mut harness = load_collab_paste("one two", 0);
+    type_at(&mut harness, 7, " three");
+    harness.app.save_now();
+
+    harness.app.apply_event(CoreEvent::PasteSaved {
+        paste: alpha("ONE two three"),
+    });
+
+    assert_eq!(
+        harness.app.virtual_editor_buffer.to_string(),
+        "ONE two three"
+    );
+    assert_eq!(harness.app.save_status, SaveStatus::Saved);
+}
+
+#[test]
+fn remote_edits_rebase_unsaved_typing_and_keep_the_caret() {
+    let mut harness = load_collab_paste("alpha beta gamma", 1);
+    type_at(&mut harness, 0, ">");
+    assert_eq!(harness.app.virtual_editor_state.cursor(), 1);
+
+    harness.app.apply_event(CoreEvent::PasteRemoteEdited {
+        paste: alpha("alpha beta GAMMA"),
+        rev: 2,
+    });
+
+    assert_eq!(
+        harness.app.virtual_editor_buffer.to_string(),
+        ">alpha beta GAMMA"
+    );
+    assert_eq!(harness.app.virtual_editor_state.cursor(), 1);
+    assert_eq!(harness.app.save_status, SaveStatus::Dirty);
+    assert_eq!(harness.app.collab.rev, Some(2));
+    assert_eq!(
+        harness
+            .app
+            .selected_paste
+            .as_ref()
+            .map(|paste| paste.content.as_str()),
+        Some("alpha beta GAMMA")
+    );
+}
+
+#[test]
+fn polling_waits_for_the_interval_and_sends_the_known_revision() {
+    let mut harness = load_collab_paste("text", 4);
+    let _ = harness.cmd_rx.try_iter().count();
+
+    harness.app.maybe_poll_collab_edits();
+    assert!(
+        harness
+            .cmd_rx
+            .try_iter()
+            .all(|cmd| !matches!(cmd, CoreCmd::PollPasteEdits { .. })),
+        "first poll waits one interval after loading"
+    );
+
+    harness.app.collab = CollabSession::default();
+    harness.app.apply_event(CoreEvent::CollabRevision {
+        id: "alpha".to_string(),
+        rev: 4,
+    });
+    harness.app.maybe_poll_collab_edits();
+    let polled = harness.cmd_rx.try_iter().find_map(|cmd| match cmd {
+        CoreCmd::PollPasteEdits { id, since_rev } => Some((id, since_rev)),
+        _ => None,
+    });
+    assert_eq!(polled, Some(("alpha".to_string(), Some(4))));
+}
@@ -185,6 +185,7 @@ fn selected_paste_summary_prefers_visible_search_result_over_stale_cache() {
             kind: localpaste_core::semantic::PasteKind::Code,
             handle: Some("cargo test".to_string()),
             terms: vec!["cargo".to_string(), "test".to_string()],
+            indent: None,
         },
     };
     harness.app.apply_event(CoreEvent::SearchResults {
@@ -508,7 +509,7 @@ fn maybe_dispatch_search_flows_require_debounce_and_dedupe_matrix() {
                     Some(Instant::now() - SEARCH_DEBOUNCE - Duration::from_millis(10));
                 harness.app.maybe_dispatch_palette_search();
                 match recv_cmd(&harness.cmd_rx) {
-                    CoreCmd::SearchPalette { query, limit } => {
+                    CoreCmd::SearchPalette { query, limit, .. } => {
                         assert_eq!(query, "alpha");
                         assert_eq!(limit, PALETTE_SEARCH_LIMIT);
                     }
@@ -540,11 +541,13 @@ fn maybe_dispatch_search_flows_require_debounce_and_dedupe_matrix() {
                         limit,
                         folder_id,
                         language,
+                        token,
                     } => {
                         assert_eq!(query, "rust");
                         assert_eq!(limit, localpaste_core::DEFAULT_SEARCH_PASTES_LIMIT);
                         assert!(folder_id.is_none());
                         assert!(language.is_none());
+                        assert!(!token.is_cancelled());
                     }
                     other => panic!("unexpected command: {:?}", other),
                 }
@@ -1002,6 +1005,7 @@ fn smart_collections_use_derived_kind_without_name_or_language_hints() {
             kind: localpaste_core::semantic::PasteKind::Code,
             handle: Some("fn handle_request".to_string()),
             terms: vec!["handle_request".to_string()],
+            indent: None,
         },
         ..test_summary("code", "untamed-tundra", None, 20)
     };
@@ -1010,6 +1014,7 @@ fn smart_collections_use_derived_kind_without_name_or_language_hints() {
             kind: localpaste_core::semantic::PasteKind::Config,
             handle: Some("model gpt-4".to_string()),
             terms: vec!["gpt-4".to_string()],
+            indent: None,
         },
         ..test_summary("config", "silent-forest", None, 20)
     };
@@ -1018,6 +1023,7 @@ fn smart_collections_use_derived_kind_without_name_or_language_hints() {
             kind: localpaste_core::semantic::PasteKind::Log,
             handle: Some("panic failed".to_string()),
             terms: vec!["panic".to_string()],
+            indent: None,
         },
         ..test_summary("log", "hidden-river", None, 20)
     };
@@ -1026,6 +1032,7 @@ fn smart_collections_use_derived_kind_without_name_or_language_hints() {
             kind: localpaste_core::semantic::PasteKind::Link,
             handle: Some("example.com".to_string()),
             terms: vec!["example".to_string()],
+            indent: None,
         },
         ..test_summary("link", "quiet-valley", None, 20)
     };
@@ -0,0 +1,74 @@
+//! Comment thread loading, stale-response handling, and submission.
+
+use super::*;
+use chrono::Utc;
+use localpaste_core::models::comment::Comment;
+
+fn comment(paste_id: &str, body: &str) -> Comment {
+    Comment {
+        id: format!("{}-{}", paste_id, body),
+        paste_id: paste_id.to_string(),
+        author: "ana".to_string(),
+        author_id: None,
+        body: body.to_string(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[test]
+fn comments_load_once_per_selection_and_ignore_stale_threads() {
+    let mut harness = make_app();
+    harness.app.selected_id = Some("alpha".to_string());
+    harness.app.sync_comments_thread();
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::ListComments { paste_id } => assert_eq!(paste_id, "alpha"),
+        other => panic!("unexpected command: {:?}", other),
+    }
+    harness.app.sync_comments_thread();
+    assert!(harness.cmd_rx.try_recv().is_err());
+
+    harness.app.selected_id = Some("beta".to_string());
+    harness.app.sync_comments_thread();
+    let _ = recv_cmd(&harness.cmd_rx);
+    harness.app.apply_event(CoreEvent::CommentsLoaded {
+        paste_id: "alpha".to_string(),
+        comments: vec![comment("alpha", "old")],
+    });
+    assert!(!harness.app.comments.loaded);
+    harness.app.apply_event(CoreEvent::CommentsLoaded {
+        paste_id: "beta".to_string(),
+        comments: vec![comment("beta", "fresh")],
+    });
+    assert!(harness.app.comments.loaded);
+    assert_eq!(harness.app.comments.items[0].body, "fresh");
+}
+
+#[test]
+fn submit_comment_sends_trimmed_body_and_clears_draft() {
+    let mut harness = make_app();
+    harness.app.selected_id = Some("alpha".to_string());
+    harness.app.sync_comments_thread();
+    let _ = recv_cmd(&harness.cmd_rx);
+
+    harness.app.comments.draft = "   ".to_string();
+    harness.app.submit_comment();
+    assert!(harness.cmd_rx.try_recv().is_err());
+
+    harness.app.comments.author = " ana ".to_string();
+    harness.app.comments.draft = "  looks good \n".to_string();
+    harness.app.submit_comment();
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::AddComment {
+            paste_id,
+            author,
+            body,
+        } => {
+            assert_eq!(paste_id, "alpha");
+            assert_eq!(author, "ana");
+            assert_eq!(body, "looks good");
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(harness.app.comments.draft.is_empty());
+}
@@ -0,0 +1,41 @@
+//! JSON/YAML/TOML syntax check storage and per-line error lookup.
+
+use super::super::highlight::HighlightLint;
+use super::*;
+use localpaste_core::lint::lint_content;
+
+fn lint_for(paste_id: &str, language: &str, content: &str) -> HighlightLint {
+    HighlightLint {
+        paste_id: paste_id.to_string(),
+        revision: 0,
+        report: lint_content(Some(language), content),
+    }
+}
+
+#[test]
+fn lint_is_kept_only_for_selected_paste_and_marks_error_line() {
+    let mut harness = make_app();
+    harness
+        .app
+        .apply_highlight_lint(lint_for("other", "json", "{\"a\" 1}"));
+    assert!(harness.app.current_lint_report().is_none());
+
+    harness
+        .app
+        .apply_highlight_lint(lint_for("alpha", "json", "{\n  \"a\": 1,\n  \"b\" 2\n}\n"));
+    let report = harness.app.current_lint_report().expect("report");
+    assert!(!report.valid);
+    assert!(harness.app.lint_error_on_line(1).is_none());
+    assert_eq!(harness.app.lint_error_on_line(2).expect("error").line, 3);
+
+    harness
+        .app
+        .apply_highlight_lint(lint_for("alpha", "json", "{\"a\": 1}"));
+    assert!(harness.app.current_lint_report().expect("report").valid);
+    assert!(harness.app.lint_error_on_line(0).is_none());
+
+    harness
+        .app
+        .apply_highlight_lint(lint_for("alpha", "rust", "fn main() {"));
+    assert!(harness.app.current_lint_report().is_none());
+}
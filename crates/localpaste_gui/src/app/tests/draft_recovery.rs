@@ -0,0 +1,78 @@
+//! Crash-recovery draft persistence and recovery prompt behavior.
+
+use super::*;
+use localpaste_core::models::draft::{Draft, SCRATCHPAD_DRAFT_KEY};
+
+#[test]
+fn dirty_edits_persist_drafts_until_saved() {
+    let mut harness = make_app();
+    harness.app.maybe_persist_drafts();
+    assert!(matches!(recv_cmd(&harness.cmd_rx), CoreCmd::ListDrafts));
+    assert!(matches!(
+        harness.cmd_rx.try_recv(),
+        Err(TryRecvError::Empty)
+    ));
+
+    harness.app.selected_id = Some("alpha".to_string());
+    harness.app.save_status = SaveStatus::Dirty;
+    harness.app.scratchpad.text = "scratch".to_string();
+    harness.app.drafts.last_persist_at = None;
+    harness.app.maybe_persist_drafts();
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::SaveDraft { draft } => {
+            assert_eq!(draft.key, Draft::paste_key("alpha"));
+            assert_eq!(draft.content, harness.app.active_snapshot());
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::SaveDraft { draft } => assert_eq!(draft.key, SCRATCHPAD_DRAFT_KEY),
+        other => panic!("unexpected command: {:?}", other),
+    }
+
+    // Unchanged content within the interval is not rewritten.
+    harness.app.maybe_persist_drafts();
+    assert!(matches!(
+        harness.cmd_rx.try_recv(),
+        Err(TryRecvError::Empty)
+    ));
+
+    harness.app.save_status = SaveStatus::Saved;
+    harness.app.scratchpad.text.clear();
+    harness.app.drafts.last_persist_at = None;
+    harness.app.maybe_persist_drafts();
+    for expected in [Draft::paste_key("alpha"), SCRATCHPAD_DRAFT_KEY.to_string()] {
+        match recv_cmd(&harness.cmd_rx) {
+            CoreCmd::DiscardDraft { key } => assert_eq!(key, expected),
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn recovered_paste_draft_restores_as_new_paste() {
+    let mut harness = make_app();
+    let draft = Draft {
+        key: Draft::paste_key("alpha"),
+        paste_id: Some("alpha".to_string()),
+        content: "lost edit".to_string(),
+        updated_at: Utc::now(),
+    };
+    harness.app.apply_event(CoreEvent::DraftsLoaded {
+        drafts: vec![draft],
+    });
+    assert_eq!(harness.app.drafts.recoverable.len(), 1);
+
+    harness
+        .app
+        .restore_draft(Draft::paste_key("alpha").as_str());
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::CreatePaste { content } => assert_eq!(content, "lost edit"),
+        other => panic!("unexpected command: {:?}", other),
+    }
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::DiscardDraft { key } => assert_eq!(key, Draft::paste_key("alpha")),
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(harness.app.drafts.recoverable.is_empty());
+}
@@ -249,7 +249,7 @@ fn explicit_paste_as_new_pending_ttl_and_consumption_matrix() {
     assert!(clipboard.is_none());
     assert_eq!(harness.app.paste_as_new_pending_frames, 0);
     match recv_cmd(&harness.cmd_rx) {
-        CoreCmd::CreatePaste { content } => assert_eq!(content, "from clipboard"),
+        CoreCmd::CreateClipboardPaste { content } => assert_eq!(content, "from clipboard"),
         other => panic!("unexpected command: {:?}", other),
     }
 }
@@ -268,7 +268,7 @@ fn explicit_paste_as_new_payload_matrix_preserves_exact_content() {
             .maybe_consume_explicit_paste_as_new(&mut clipboard));
         assert!(clipboard.is_none());
         match recv_cmd(&harness.cmd_rx) {
-            CoreCmd::CreatePaste { content } => assert_eq!(content, payload),
+            CoreCmd::CreateClipboardPaste { content } => assert_eq!(content, payload),
             other => panic!("unexpected command: {:?}", other),
         }
     }
@@ -328,7 +328,7 @@ fn explicit_paste_as_new_waits_for_delayed_clipboard_payload() {
     assert!(harness.app.paste_as_new_clipboard_requested_at.is_none());
     assert!(clipboard.is_none());
     match recv_cmd(&harness.cmd_rx) {
-        CoreCmd::CreatePaste { content } => assert_eq!(content, "from delayed clipboard"),
+        CoreCmd::CreateClipboardPaste { content } => assert_eq!(content, "from delayed clipboard"),
         other => panic!("unexpected command: {:?}", other),
     }
 }
@@ -0,0 +1,70 @@
+//! Folder rules window: opening, rule submission, and reordering.
+
+use super::*;
+use chrono::Utc;
+use localpaste_core::models::folder_rule::{FolderRule, RuleField};
+
+fn rule(id: &str, position: u32) -> FolderRule {
+    FolderRule {
+        id: id.to_string(),
+        field: RuleField::Name,
+        pattern: format!("{}*", id),
+        folder_id: "logs".to_string(),
+        position,
+        created_at: Utc::now(),
+    }
+}
+
+#[test]
+fn opening_folder_rules_requests_rules_and_folders() {
+    let mut harness = make_app();
+    harness.app.open_folder_rules();
+    assert!(harness.app.folder_rules.open);
+    assert!(matches!(
+        recv_cmd(&harness.cmd_rx),
+        CoreCmd::ListFolderRules
+    ));
+    assert!(matches!(recv_cmd(&harness.cmd_rx), CoreCmd::ListFolders));
+
+    harness.app.apply_event(CoreEvent::FolderRulesLoaded {
+        rules: vec![rule("a", 0)],
+    });
+    assert!(harness.app.folder_rules.loaded);
+    assert_eq!(harness.app.folder_rules.items.len(), 1);
+}
+
+#[test]
+fn submit_requires_pattern_and_folder_and_reorder_sends_every_id() {
+    let mut harness = make_app();
+    harness.app.folder_rules.pattern = "  *.log ".to_string();
+    harness.app.submit_folder_rule();
+    assert!(harness.cmd_rx.try_recv().is_err());
+
+    harness.app.folder_rules.field = RuleField::Source;
+    harness.app.folder_rules.folder_id = Some("logs".to_string());
+    harness.app.submit_folder_rule();
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::AddFolderRule {
+            field,
+            pattern,
+            folder_id,
+        } => {
+            assert_eq!(field, RuleField::Source);
+            assert_eq!(pattern, "*.log");
+            assert_eq!(folder_id, "logs");
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(harness.app.folder_rules.pattern.is_empty());
+
+    harness.app.apply_event(CoreEvent::FolderRulesLoaded {
+        rules: vec![rule("a", 0), rule("b", 1), rule("c", 2)],
+    });
+    harness.app.move_folder_rule(0, true);
+    assert!(harness.cmd_rx.try_recv().is_err());
+    harness.app.move_folder_rule(2, true);
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::ReorderFolderRules { ids } => assert_eq!(ids, vec!["a", "c", "b"]),
+        other => panic!("unexpected command: {:?}", other),
+    }
+}
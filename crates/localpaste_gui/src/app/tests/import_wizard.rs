@@ -0,0 +1,67 @@
+//! Import wizard preview and commit behavior.
+
+use super::*;
+use localpaste_core::import::ImportFormat;
+
+#[test]
+fn import_preview_commits_only_checked_snippets() {
+    let mut harness = make_app();
+    let source = harness._dir.path().join("snippets.md");
+    std::fs::write(
+        &source,
+        "# Build\n```sh\nmake\n```\n# Query\n```sql\nselect 1;\n```\n",
+    )
+    .expect("write source");
+
+    harness.app.open_import_wizard();
+    harness.app.import_wizard.format = ImportFormat::Markdown;
+    harness.app.load_import_preview(source.as_path());
+    assert!(harness.app.import_wizard.error.is_none());
+    let names: Vec<&str> = harness
+        .app
+        .import_wizard
+        .preview
+        .iter()
+        .map(|(snippet, checked)| {
+            assert!(*checked);
+            snippet.name.as_str()
+        })
+        .collect();
+    assert_eq!(names, vec!["Build", "Query"]);
+
+    harness.app.import_wizard.preview[0].1 = false;
+    harness.app.commit_import();
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::ImportSnippets { snippets } => {
+            assert_eq!(snippets.len(), 1);
+            assert_eq!(snippets[0].name, "Query");
+            assert_eq!(snippets[0].language.as_deref(), Some("sql"));
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(!harness.app.import_wizard.open);
+}
+
+#[test]
+fn import_preview_reports_parse_errors_inline() {
+    let mut harness = make_app();
+    let source = harness._dir.path().join("db.json");
+    std::fs::write(&source, "not json").expect("write source");
+
+    harness.app.open_import_wizard();
+    harness.app.load_import_preview(source.as_path());
+    assert!(harness.app.import_wizard.preview.is_empty());
+    assert!(harness
+        .app
+        .import_wizard
+        .error
+        .as_deref()
+        .is_some_and(|error| error.contains("Cannot parse")));
+
+    harness.app.commit_import();
+    assert!(harness.app.import_wizard.open);
+    assert!(matches!(
+        harness.cmd_rx.try_recv(),
+        Err(TryRecvError::Empty)
+    ));
+}
@@ -0,0 +1,40 @@
+//! Indentation detection, Tab insertion, and status-bar retab coverage.
+
+use super::*;
+
+#[test]
+fn tab_key_honors_detected_indentation_and_retab_is_one_undo_step() {
+    let mut harness = make_app();
+    harness.app.editor_mode = EditorMode::VirtualEditor;
+    let original = "fn main() {\n\tlet x = 1;\n}\n";
+    harness.app.reset_virtual_editor(original);
+    assert_eq!(harness.app.indent_style.label(), "Tabs");
+
+    let ctx = egui::Context::default();
+    let len = harness.app.virtual_editor_buffer.len_chars();
+    harness.app.virtual_editor_state.set_cursor(len, len);
+    let result = harness
+        .app
+        .apply_virtual_commands(&ctx, &[VirtualInputCommand::InsertTab]);
+    assert!(result.changed);
+    assert_eq!(
+        harness.app.virtual_editor_buffer.to_string(),
+        "fn main() {\n\tlet x = 1;\n}\n\t"
+    );
+    let _ = harness
+        .app
+        .apply_virtual_commands(&ctx, &[VirtualInputCommand::Undo]);
+
+    assert!(harness.app.toggle_virtual_indentation());
+    assert_eq!(harness.app.indent_style.label(), "Spaces: 4");
+    assert_eq!(harness.app.save_status, SaveStatus::Dirty);
+    assert_eq!(
+        harness.app.virtual_editor_buffer.to_string(),
+        "fn main() {\n    let x = 1;\n}\n"
+    );
+
+    let _ = harness
+        .app
+        .apply_virtual_commands(&ctx, &[VirtualInputCommand::Undo]);
+    assert_eq!(harness.app.virtual_editor_buffer.to_string(), original);
+}
@@ -655,3 +655,38 @@ fn delete_to_line_boundaries_obey_selection_and_cursor_cases() {
         .apply_virtual_commands(&ctx, &[VirtualInputCommand::DeleteToLineStart]);
     assert_eq!(selection_case.app.virtual_editor_buffer.to_string(), "aef");
 }
+
+#[test]
+fn expand_selection_grows_by_units_and_shrink_retraces_steps() {
+    let ctx = egui::Context::default();
+    let mut harness = make_app();
+    let text = "call(alpha, beta)";
+    configure_virtual_editor_with_wrap(&mut harness.app, text, 400.0);
+    let len = harness.app.virtual_editor_buffer.len_chars();
+    harness.app.virtual_editor_state.set_cursor(7, len);
+
+    let mut selected = Vec::new();
+    for _ in 0..3 {
+        let _ = harness
+            .app
+            .apply_virtual_commands(&ctx, &[VirtualInputCommand::ExpandSelection]);
+        selected.push(harness.app.virtual_selected_text().unwrap_or_default());
+    }
+    assert_eq!(selected, vec!["alpha", "alpha, beta", "(alpha, beta)"]);
+
+    let _ = harness
+        .app
+        .apply_virtual_commands(&ctx, &[VirtualInputCommand::ShrinkSelection]);
+    assert_eq!(
+        harness.app.virtual_selected_text().as_deref(),
+        Some("alpha, beta")
+    );
+    let _ = harness
+        .app
+        .apply_virtual_commands(&ctx, &[VirtualInputCommand::ShrinkSelection]);
+    let _ = harness
+        .app
+        .apply_virtual_commands(&ctx, &[VirtualInputCommand::ShrinkSelection]);
+    assert!(harness.app.virtual_editor_state.selection_range().is_none());
+    assert_eq!(harness.app.virtual_editor_state.cursor(), 7);
+}
@@ -0,0 +1,66 @@
+//! Copy with line numbers, line permalinks, and permalink resolution.
+
+use super::*;
+
+#[test]
+fn copy_actions_number_lines_and_link_the_caret_line() {
+    let mut harness = make_app();
+    harness.app.editor_mode = EditorMode::VirtualEditor;
+    harness.app.reset_virtual_editor("a\nb\nc\n");
+
+    harness.app.copy_with_line_numbers();
+    assert_eq!(
+        harness.app.clipboard_outgoing.take().as_deref(),
+        Some("1 | a\n2 | b\n3 | c\n")
+    );
+
+    let len = harness.app.virtual_editor_buffer.len_chars();
+    harness.app.virtual_editor_state.set_cursor(2, len);
+    harness.app.virtual_editor_state.move_cursor(5, len, true);
+    harness.app.copy_with_line_numbers();
+    assert_eq!(
+        harness.app.clipboard_outgoing.take().as_deref(),
+        Some("2 | b\n3 | c\n")
+    );
+
+    harness.app.virtual_editor_state.set_cursor(4, len);
+    harness.app.copy_link_to_line();
+    assert_eq!(
+        harness.app.clipboard_outgoing.take().as_deref(),
+        Some("localpaste://paste/alpha#L3")
+    );
+}
+
+#[test]
+fn palette_link_opens_paste_and_flashes_target_line_after_load() {
+    let mut harness = make_app();
+    harness.app.editor_mode = EditorMode::VirtualEditor;
+    harness.app.command_palette_open = true;
+    harness
+        .app
+        .set_command_palette_query("localpaste://paste/beta#L3".to_string());
+    assert_eq!(harness.app.command_palette_action_count(), 1);
+
+    assert!(harness.app.open_paste_link("beta".to_string(), Some(3)));
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::GetPaste { id } => assert_eq!(id, "beta"),
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(harness.app.line_links.flash.is_none());
+
+    let mut loaded = Paste::new("one\ntwo\nthree\nfour\n".to_string(), "Beta".to_string());
+    loaded.id = "beta".to_string();
+    harness
+        .app
+        .apply_event(CoreEvent::PasteLoaded { paste: loaded });
+
+    assert!(harness.app.line_links.pending_jump.is_none());
+    let cursor = harness.app.virtual_editor_state.cursor();
+    assert_eq!(
+        harness.app.virtual_editor_buffer.char_to_line_col(cursor),
+        (2, 0)
+    );
+    let now = Instant::now();
+    assert!(harness.app.line_links.flash_strength(2, now).is_some());
+    assert!(harness.app.line_links.flash_strength(1, now).is_none());
+}
@@ -0,0 +1,76 @@
+//! Merge dialog picking, ordering, and commit behavior.
+
+use super::*;
+use localpaste_core::merge::MergeSeparator;
+
+fn status_text(harness: &TestHarness) -> &str {
+    harness
+        .app
+        .status
+        .as_ref()
+        .map(|status| status.text.as_str())
+        .unwrap_or_default()
+}
+
+#[test]
+fn merge_dialog_sends_picked_pastes_in_dragged_order() {
+    let mut harness = make_app();
+    let mut first = Paste::new("one\n".to_string(), "first".to_string());
+    first.id = "first-id".to_string();
+    harness
+        .app
+        .apply_event(CoreEvent::PasteCreated { paste: first });
+
+    harness.app.open_merge_dialog();
+    assert_eq!(
+        harness.app.merge_dialog.order,
+        vec![("first-id".to_string(), "first".to_string())]
+    );
+    harness.app.commit_merge();
+    assert_eq!(status_text(&harness), "Pick at least two pastes to merge.");
+    assert!(harness.app.merge_dialog.open);
+
+    harness.app.merge_dialog.toggle("second-id", "second");
+    harness.app.merge_dialog.toggle("third-id", "third");
+    harness.app.merge_dialog.toggle("second-id", "second");
+    harness.app.merge_dialog.toggle("second-id", "second");
+    harness.app.merge_dialog.move_item(2, 0);
+    harness.app.merge_dialog.separator = MergeSeparator::Rule;
+    harness.app.merge_dialog.delete_originals = true;
+    harness.app.commit_merge();
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::MergePastes {
+            ids,
+            name,
+            separator,
+            delete_originals,
+        } => {
+            assert_eq!(ids, vec!["second-id", "first-id", "third-id"]);
+            assert_eq!(name, "second (merged)");
+            assert_eq!(separator, MergeSeparator::Rule);
+            assert!(delete_originals);
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(!harness.app.merge_dialog.open);
+
+    let mut merged = Paste::new(
+        "one\n\n---\n\ntwo\n".to_string(),
+        "second (merged)".to_string(),
+    );
+    merged.id = "merged-id".to_string();
+    harness.app.apply_event(CoreEvent::PastesMerged {
+        paste: merged,
+        deleted: vec!["first-id".to_string()],
+    });
+    assert!(harness
+        .app
+        .all_pastes
+        .iter()
+        .all(|item| item.id != "first-id"));
+    assert_eq!(harness.app.selected_id.as_deref(), Some("merged-id"));
+    assert_eq!(
+        status_text(&harness),
+        "Merged into \"second (merged)\"; deleted 1 original(s)."
+    );
+}
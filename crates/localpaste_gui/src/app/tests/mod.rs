@@ -201,11 +201,14 @@ fn make_app() -> TestHarness {
         max_paste_size: 10 * 1024 * 1024,
         auto_save_interval: 2000,
         auto_backup: false,
+        size_limits: Default::default(),
+        flush_policy: Default::default(),
+        recent_history: false,
     };
     let state = AppState::with_locks(config, server_db, locks.clone());
+    let presence_tracker = state.presence.clone();
     let server = EmbeddedServer::start(state, false).expect("server");
     let server_addr = server.addr();
-    let server_used_fallback = server.used_fallback();
 
     let app = LocalPasteApp {
         backend: BackendHandle::from_test_channels(cmd_tx, evt_rx),
@@ -226,6 +229,8 @@ fn make_app() -> TestHarness {
         search_focus_requested: false,
         active_collection: SidebarCollection::All,
         active_language_filter: None,
+        sidebar_group_state: Default::default(),
+        sidebar_previews: Default::default(),
         properties_drawer_open: false,
         command_palette_open: false,
         command_palette_query: String::new(),
@@ -244,6 +249,9 @@ fn make_app() -> TestHarness {
         virtual_editor_buffer: RopeBuffer::new("content"),
         virtual_editor_state: VirtualEditorState::default(),
         virtual_editor_history: VirtualEditorHistory::default(),
+        indent_style: Default::default(),
+        selection_stats: Default::default(),
+        selection_expand_history: Vec::new(),
         virtual_layout: WrapLayoutCache::default(),
         virtual_galley_cache: VirtualGalleyCache::default(),
         virtual_line_scratch: String::new(),
@@ -256,7 +264,7 @@ fn make_app() -> TestHarness {
         virtual_pending_scroll_offset_y: None,
         virtual_follow_cursor_next_frame: false,
         version_ui: super::version_ui::VersionUiState::default(),
-        highlight_worker: spawn_highlight_worker(),
+        highlight_worker: spawn_highlight_worker(None),
         highlight_pending: None,
         highlight_render: None,
         highlight_staged: None,
@@ -266,9 +274,12 @@ fn make_app() -> TestHarness {
         db_path: db_path_str,
         locks,
         lock_owner_id: LockOwnerId::new("test-owner".to_string()),
+        presence_tracker,
+        presence: Default::default(),
         _server: server,
         server_addr,
-        server_used_fallback,
+        server_fallback_from: None,
+        server_port_pinned: false,
         status: None,
         toasts: VecDeque::with_capacity(TOAST_LIMIT),
         export_result_rx: None,
@@ -276,8 +287,28 @@ fn make_app() -> TestHarness {
         last_edit_at: None,
         save_in_flight: false,
         save_request_revision: None,
+        collab: Default::default(),
         autosave_delay: Duration::from_millis(2000),
         shortcut_help_open: false,
+        scratchpad: Default::default(),
+        drafts: Default::default(),
+        backups: Default::default(),
+        storage_alerts: Default::default(),
+        display_timezone: Default::default(),
+        updates: Default::default(),
+        import_wizard: Default::default(),
+        split_dialog: Default::default(),
+        merge_dialog: Default::default(),
+        comments: Default::default(),
+        folder_rules: Default::default(),
+        content_lint: Default::default(),
+        annotations: Default::default(),
+        line_links: Default::default(),
+        search_matches: Default::default(),
+        close_prompt: Default::default(),
+        writing_mode: Default::default(),
+        symbol_outline: Default::default(),
+        markdown_outline: Default::default(),
         focus_editor_next: false,
         style_applied: false,
         window_checked: false,
@@ -325,15 +356,37 @@ fn recv_cmd(rx: &Receiver<CoreCmd>) -> CoreCmd {
     }
 }
 
+mod annotations_panel;
+mod backups;
+mod collab_merge;
 mod collections_and_search;
+mod comments_panel;
+mod content_lint;
 mod creation_and_projection;
+mod draft_recovery;
 mod focus_and_paste_routing;
+mod folder_rules;
 mod highlight_behaviors;
+mod import_wizard;
+mod indentation_style;
 mod keyboard_navigation_audit;
+mod line_links;
+mod merge_dialog;
+mod presence_markers;
 mod save_and_metadata;
+mod scratchpad;
+mod search_reveal;
 mod shutdown_behavior;
+mod sidebar_views;
+mod split_dialog;
 mod state_basics;
+mod storage_alerts;
+mod symbol_outline;
+mod text_transform_commands;
+mod update_notice;
+mod url_source_refresh;
 mod version_async_status;
 mod version_modal_caching;
 mod version_overlay_exclusivity;
 mod virtual_editor_behaviors;
+mod writing_mode;
@@ -0,0 +1,32 @@
+//! Sidebar and status-bar presence for pastes other clients have open.
+
+use super::super::presence::{presence_marker, presence_summary};
+use super::*;
+
+#[test]
+fn presence_snapshot_lists_other_clients_but_not_this_window() {
+    let mut harness = make_app();
+    let own = harness.app.lock_owner_id.clone();
+    harness.app.locks.acquire("alpha", &own).expect("own lock");
+    harness
+        .app
+        .locks
+        .acquire("alpha", &LockOwnerId::new("gui-2".to_string()))
+        .expect("foreign lock");
+    harness.app.presence_tracker.touch("alpha", "cli");
+    harness.app.presence_tracker.touch("beta", "web");
+
+    harness.app.maybe_refresh_presence();
+    let alpha = harness.app.paste_presence("alpha");
+    assert_eq!(presence_marker(alpha), Some("\u{25CF}"));
+    assert_eq!(presence_summary(alpha), "gui-2 (editing), cli (viewing)");
+    let beta = harness.app.paste_presence("beta");
+    assert_eq!(presence_marker(beta), Some("\u{25CB}"));
+    assert_eq!(presence_summary(beta), "web (viewing)");
+    assert!(harness.app.paste_presence("gamma").is_empty());
+
+    // Rebuilds wait for the refresh interval.
+    harness.app.presence_tracker.leave("beta", "web");
+    harness.app.maybe_refresh_presence();
+    assert_eq!(harness.app.paste_presence("beta").len(), 1);
+}
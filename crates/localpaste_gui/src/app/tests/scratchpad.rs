@@ -0,0 +1,30 @@
+//! Scratchpad buffer stays out of the database until promoted.
+
+use super::*;
+
+#[test]
+fn scratchpad_edits_do_not_dispatch_until_promoted() {
+    let mut harness = make_app();
+    harness.app.toggle_scratchpad();
+    assert!(harness.app.scratchpad.open);
+
+    assert!(!harness.app.promote_scratchpad());
+    assert!(matches!(
+        harness.cmd_rx.try_recv(),
+        Err(TryRecvError::Empty)
+    ));
+
+    harness.app.scratchpad.text = "throwaway notes".to_string();
+    assert!(matches!(
+        harness.cmd_rx.try_recv(),
+        Err(TryRecvError::Empty)
+    ));
+
+    assert!(harness.app.promote_scratchpad());
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::CreatePaste { content } => assert_eq!(content, "throwaway notes"),
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(harness.app.scratchpad.text.is_empty());
+    assert!(!harness.app.scratchpad.open);
+}
@@ -0,0 +1,46 @@
+//! Opening search hits jumps to and highlights content matches.
+
+use super::*;
+
+#[test]
+fn palette_search_hit_selects_first_match_and_highlights_until_edit() {
+    let mut harness = make_app();
+    harness.app.editor_mode = EditorMode::VirtualEditor;
+    harness.app.command_palette_open = true;
+    harness.app.set_command_palette_query("needle".to_string());
+
+    harness.app.open_palette_selection("beta".to_string());
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::GetPaste { id } => assert_eq!(id, "beta"),
+        other => panic!("unexpected command: {:?}", other),
+    }
+
+    let mut loaded = Paste::new(
+        "intro\nfind the Needle\nand NEEDLE again\n".to_string(),
+        "Beta".to_string(),
+    );
+    loaded.id = "beta".to_string();
+    harness
+        .app
+        .apply_event(CoreEvent::PasteLoaded { paste: loaded });
+
+    assert_eq!(
+        harness.app.virtual_editor_state.selection_range(),
+        Some(15..21)
+    );
+    let matches = harness
+        .app
+        .current_search_matches()
+        .expect("matches highlighted");
+    assert_eq!(matches.ranges, vec![15..21, 26..32]);
+    assert_eq!(matches.ranges_in_row(22, 17), vec![4..10]);
+    assert!(matches.ranges_in_row(0, 6).is_empty());
+
+    let ctx = egui::Context::default();
+    let len = harness.app.virtual_editor_buffer.len_chars();
+    harness.app.virtual_editor_state.set_cursor(0, len);
+    harness
+        .app
+        .apply_virtual_commands(&ctx, &[VirtualInputCommand::InsertText("x".to_string())]);
+    assert!(harness.app.current_search_matches().is_none());
+}
@@ -1,5 +1,6 @@
 //! Shutdown-flush command emission tests.
 
+use super::super::shutdown::window_title;
 use super::*;
 
 #[test]
@@ -275,3 +276,50 @@ fn on_exit_forces_final_dirty_snapshots_even_when_acks_never_arrive() {
         "expected shutdown to force a final metadata save without waiting for ack"
     );
 }
+
+#[test]
+fn close_prompt_save_waits_for_ack_and_discard_skips_flush() {
+    let mut harness = make_app();
+    let ctx = egui::Context::default();
+    assert_eq!(window_title(false), "LocalPaste.rs");
+    assert_eq!(window_title(true), "\u{25CF} LocalPaste.rs");
+
+    harness.app.selected_content.reset("pending".to_string());
+    harness.app.save_status = SaveStatus::Dirty;
+    assert!(harness.app.has_unsaved_changes());
+    harness.app.confirm_close_with_save();
+    assert!(matches!(
+        recv_cmd(&harness.cmd_rx),
+        CoreCmd::UpdatePaste { .. }
+    ));
+    harness.app.update_close_prompt(&ctx);
+    assert!(
+        !harness.app.close_prompt.confirmed,
+        "close must wait for the save ack"
+    );
+
+    harness.app.save_in_flight = false;
+    harness.app.save_status = SaveStatus::Saved;
+    harness.app.update_close_prompt(&ctx);
+    assert!(harness.app.close_prompt.confirmed);
+
+    let mut harness = make_app();
+    harness.app.save_status = SaveStatus::Dirty;
+    harness.app.metadata_dirty = true;
+    harness.app.confirm_close_with_discard(&ctx);
+    assert!(!harness.app.has_unsaved_changes());
+    assert!(harness.app.close_prompt.confirmed);
+    eframe::App::on_exit(&mut harness.app, None);
+    while let Ok(cmd) = harness.cmd_rx.try_recv() {
+        assert!(
+            !matches!(
+                cmd,
+                CoreCmd::UpdatePaste { .. }
+                    | CoreCmd::UpdatePasteVirtual { .. }
+                    | CoreCmd::UpdatePasteMeta { .. }
+            ),
+            "discarded edits must not be flushed: {:?}",
+            cmd
+        );
+    }
+}
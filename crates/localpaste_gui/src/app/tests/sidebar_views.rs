@@ -0,0 +1,71 @@
+//! Sidebar hover-preview cache tests.
+
+use super::*;
+use localpaste_core::models::paste::PastePreview;
+
+fn preview_for(summary: &PasteSummary, snippet: &str) -> PastePreview {
+    let mut paste = Paste::new(snippet.to_string(), summary.name.clone());
+    paste.id = summary.id.clone();
+    paste.updated_at = summary.updated_at;
+    PastePreview::from_paste(&paste, 10)
+}
+
+#[test]
+fn sidebar_preview_requests_once_and_serves_fresh_cache() {
+    let mut harness = make_app();
+    let summary = harness.app.pastes[0].clone();
+
+    harness.app.request_sidebar_preview(&summary);
+    harness.app.request_sidebar_preview(&summary);
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::GetPastePreview { id } => assert_eq!(id, summary.id),
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(matches!(
+        harness.cmd_rx.try_recv(),
+        Err(TryRecvError::Empty)
+    ));
+
+    harness.app.apply_event(CoreEvent::PastePreviewLoaded {
+        id: summary.id.clone(),
+        preview: Some(preview_for(&summary, "first\nsecond")),
+    });
+    let cached = harness
+        .app
+        .sidebar_previews
+        .fresh(&summary)
+        .expect("fresh preview");
+    assert_eq!(cached.snippet, "first\nsecond");
+    harness.app.request_sidebar_preview(&summary);
+    assert!(matches!(
+        harness.cmd_rx.try_recv(),
+        Err(TryRecvError::Empty)
+    ));
+}
+
+#[test]
+fn sidebar_preview_refetches_after_summary_revision_changes() {
+    let mut harness = make_app();
+    let summary = harness.app.pastes[0].clone();
+    harness.app.apply_event(CoreEvent::PastePreviewLoaded {
+        id: summary.id.clone(),
+        preview: Some(preview_for(&summary, "old")),
+    });
+
+    let newer = PasteSummary {
+        updated_at: summary.updated_at + chrono::Duration::seconds(5),
+        ..summary
+    };
+    assert!(harness.app.sidebar_previews.fresh(&newer).is_none());
+    harness.app.request_sidebar_preview(&newer);
+    assert!(matches!(
+        recv_cmd(&harness.cmd_rx),
+        CoreCmd::GetPastePreview { .. }
+    ));
+
+    harness.app.apply_event(CoreEvent::PastePreviewLoaded {
+        id: newer.id.clone(),
+        preview: None,
+    });
+    assert!(!harness.app.sidebar_previews.is_pending(newer.id.as_str()));
+}
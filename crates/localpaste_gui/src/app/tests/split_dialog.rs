@@ -0,0 +1,81 @@
+//! Split dialog detection, preview editing, and commit behavior.
+
+use super::*;
+
+fn status_text(harness: &TestHarness) -> &str {
+    harness
+        .app
+        .status
+        .as_ref()
+        .map(|status| status.text.as_str())
+        .unwrap_or_default()
+}
+
+#[test]
+fn split_dialog_commits_checked_files_into_chosen_folder() {
+    let mut harness = make_app();
+    let mut created = Paste::new(
+        "=== a.rs ===\nfn a() {}\n=== b.py ===\nprint(1)\n=== c.md ===\n# C\n".to_string(),
+        "bundle".to_string(),
+    );
+    created.id = "bundle-id".to_string();
+    harness
+        .app
+        .apply_event(CoreEvent::PasteCreated { paste: created });
+
+    harness.app.open_split_dialog();
+    assert!(harness.app.split_dialog.open);
+    assert_eq!(harness.app.split_dialog.source_name, "bundle");
+    let names: Vec<&str> = harness
+        .app
+        .split_dialog
+        .files
+        .iter()
+        .map(|(file, _)| file.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["a.rs", "b.py", "c.md"]);
+    assert!(matches!(recv_cmd(&harness.cmd_rx), CoreCmd::ListFolders));
+
+    harness.app.split_dialog.files[1].1 = false;
+    harness.app.split_dialog.files[2].0.name = "  notes.md ".to_string();
+    harness.app.split_dialog.folder_id = Some("folder-1".to_string());
+    harness.app.commit_split();
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::SplitPaste { files, folder_id } => {
+            assert_eq!(folder_id.as_deref(), Some("folder-1"));
+            let summary: Vec<(&str, Option<&str>)> = files
+                .iter()
+                .map(|file| (file.name.as_str(), file.language.as_deref()))
+                .collect();
+            assert_eq!(
+                summary,
+                vec![("a.rs", Some("rust")), ("notes.md", Some("markdown"))]
+            );
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    assert!(!harness.app.split_dialog.open);
+
+    harness.app.apply_event(CoreEvent::PasteSplit {
+        created: 2,
+        failed: 0,
+    });
+    assert_eq!(status_text(&harness), "Split into 2 paste(s).");
+}
+
+#[test]
+fn split_dialog_stays_closed_without_concatenated_files() {
+    let mut harness = make_app();
+    let mut created = Paste::new("just one file\n".to_string(), "single".to_string());
+    created.id = "single-id".to_string();
+    harness
+        .app
+        .apply_event(CoreEvent::PasteCreated { paste: created });
+
+    harness.app.open_split_dialog();
+    assert!(!harness.app.split_dialog.open);
+    assert_eq!(
+        status_text(&harness),
+        "No concatenated files detected in this paste."
+    );
+}
@@ -151,6 +151,34 @@ fn set_status_pushes_toast_feedback() {
     );
 }
 
+#[test]
+fn pin_server_port_persists_bound_port_for_next_launch() {
+    let mut harness = make_app();
+    harness.app.server_fallback_from = Some("127.0.0.1:1".parse().expect("addr"));
+    harness.app.announce_port_fallback();
+    assert!(harness
+        .app
+        .status
+        .as_ref()
+        .is_some_and(|status| status.text.starts_with("Port 1 was in use")));
+    harness.app.pin_server_port();
+
+    let port = harness.app.server_addr.port();
+    assert!(harness.app.server_port_pinned);
+    assert_eq!(
+        localpaste_core::discovery::read_pinned_port(&harness.app.db_path),
+        Some(port)
+    );
+    assert_eq!(
+        harness
+            .app
+            .status
+            .as_ref()
+            .map(|status| status.text.clone()),
+        Some(format!("API port {} pinned for future launches.", port))
+    );
+}
+
 #[test]
 fn toast_queue_dedupes_tail_and_caps_length() {
     let mut harness = make_app();
@@ -0,0 +1,85 @@
+//! Storage alert checks, toasts, and remediation commands.
+
+use super::*;
+use localpaste_core::storage_alerts::{StorageAlert, StorageAlertKind, StorageRemediation};
+
+fn backups_alert() -> StorageAlert {
+    StorageAlert {
+        kind: StorageAlertKind::BackupsSize,
+        size_bytes: 8 * 1024 * 1024,
+        limit_bytes: 1024 * 1024,
+        message: "Backups use 8 MiB, above the 1 MiB alert limit".to_string(),
+        remediation: StorageRemediation::PruneBackups,
+    }
+}
+
+#[test]
+fn storage_check_is_throttled_and_new_alerts_toast_once() {
+    let mut harness = make_app();
+    harness.app.maybe_check_storage();
+    assert!(matches!(
+        recv_cmd(&harness.cmd_rx),
+        CoreCmd::CheckStorage { .. }
+    ));
+    harness.app.maybe_check_storage();
+    assert!(matches!(
+        harness.cmd_rx.try_recv(),
+        Err(TryRecvError::Empty)
+    ));
+
+    harness.app.apply_event(CoreEvent::StorageChecked {
+        alerts: vec![backups_alert()],
+    });
+    assert_eq!(harness.app.storage_alerts.alerts.len(), 1);
+    assert_eq!(harness.app.toasts.len(), 1);
+    assert!(harness.app.toasts[0].text.contains("Backups use 8 MiB"));
+
+    harness.app.toasts.clear();
+    harness.app.apply_event(CoreEvent::StorageChecked {
+        alerts: vec![backups_alert()],
+    });
+    assert!(harness.app.toasts.is_empty());
+
+    harness
+        .app
+        .apply_event(CoreEvent::StorageChecked { alerts: Vec::new() });
+    assert!(harness.app.storage_alerts.alerts.is_empty());
+}
+
+#[test]
+fn remediation_sends_prune_and_rechecks_after_it() {
+    let mut harness = make_app();
+    harness.app.storage_alerts.checked_at = Some(Instant::now());
+    harness
+        .app
+        .run_storage_remediation(StorageRemediation::PruneBackups);
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::PruneBackups { keep_last, .. } => {
+            assert_eq!(
+                keep_last,
+                localpaste_core::storage_alerts::DEFAULT_BACKUP_PRUNE_KEEP_LAST
+            )
+        }
+        other => panic!("unexpected command: {:?}", other),
+    }
+    harness.app.maybe_check_storage();
+    assert!(matches!(
+        recv_cmd(&harness.cmd_rx),
+        CoreCmd::CheckStorage { .. }
+    ));
+
+    harness
+        .app
+        .run_storage_remediation(StorageRemediation::Cleanup);
+    assert!(matches!(recv_cmd(&harness.cmd_rx), CoreCmd::Cleanup));
+
+    harness.app.apply_event(CoreEvent::BackupsPruned {
+        deleted: vec![std::path::PathBuf::from("db.backup.1.redb")],
+    });
+    assert!(harness.app.storage_alerts.checked_at.is_none());
+    assert!(harness
+        .app
+        .toasts
+        .iter()
+        .any(|toast| toast.text == "Pruned 1 old backup(s)."));
+}
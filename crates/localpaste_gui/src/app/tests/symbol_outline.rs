@@ -0,0 +1,133 @@
+//! Symbol outline storage, fuzzy ranking, jump-to-line, and markdown heading sync.
+
+use super::super::highlight::{HighlightOutline, OutlineSymbol, OutlineSymbolKind};
+use super::super::ui::symbol_outline::fuzzy_score;
+use super::*;
+
+fn symbol(kind: OutlineSymbolKind, name: &str, line: usize) -> OutlineSymbol {
+    OutlineSymbol {
+        kind,
+        name: name.to_string(),
+        line,
+        depth: 0,
+    }
+}
+
+fn outline_for(paste_id: &str, symbols: Vec<OutlineSymbol>) -> HighlightOutline {
+    HighlightOutline {
+        paste_id: paste_id.to_string(),
+        revision: 0,
+        text_len: 0,
+        symbols,
+    }
+}
+
+#[test]
+fn fuzzy_score_prefers_contiguous_word_start_matches() {
+    assert!(fuzzy_score("xyz", "parse_config").is_none());
+    let exact = fuzzy_score("parse", "parse_config").expect("prefix match");
+    let scattered = fuzzy_score("parse", "prepare_session").expect("subsequence match");
+    assert!(exact > scattered);
+    let initials = fuzzy_score("pc", "parse_config").expect("word starts");
+    let inner = fuzzy_score("pc", "spec").expect("inner letters");
+    assert!(initials > inner);
+    assert!(fuzzy_score("PC", "parseConfig").is_some());
+}
+
+#[test]
+fn outline_is_kept_only_for_selected_paste_and_ranked_by_query() {
+    let mut harness = make_app();
+    harness.app.apply_highlight_outline(outline_for(
+        "other",
+        vec![symbol(OutlineSymbolKind::Function, "main", 0)],
+    ));
+    assert!(harness.app.current_outline_symbols().is_empty());
+
+    harness.app.apply_highlight_outline(outline_for(
+        "alpha",
+        vec![
+            symbol(OutlineSymbolKind::Type, "Config", 0),
+            symbol(OutlineSymbolKind::Function, "load_config", 4),
+            symbol(OutlineSymbolKind::Function, "main", 9),
+        ],
+    ));
+    assert_eq!(harness.app.current_outline_symbols().len(), 3);
+
+    harness.app.symbol_outline.jump_query = "conf".to_string();
+    let names: Vec<String> = harness
+        .app
+        .symbol_jump_results()
+        .into_iter()
+        .map(|symbol| symbol.name)
+        .collect();
+    assert_eq!(names, vec!["Config".to_string(), "load_config".to_string()]);
+
+    harness.app.selected_id = Some("beta".to_string());
+    assert!(harness.app.current_outline_symbols().is_empty());
+}
+
+#[test]
+fn symbol_jump_opens_as_keyboard_overlay_and_moves_caret_to_line_start() {
+    let mut harness = make_app();
+    harness.app.toggle_symbol_jump();
+    assert!(
+        !harness.app.symbol_outline.jump_open,
+        "jump needs the editable virtual editor"
+    );
+
+    harness.app.editor_mode = EditorMode::VirtualEditor;
+    harness.app.reset_virtual_editor("fn a() {}\n\nfn b() {}\n");
+    harness.app.toggle_symbol_jump();
+    assert!(harness.app.symbol_outline.jump_open);
+    assert!(harness.app.keyboard_overlay_open());
+
+    harness.app.jump_to_outline_line(2);
+    assert_eq!(harness.app.virtual_editor_state.cursor(), 11);
+    assert!(harness.app.focus_editor_next);
+
+    harness.app.jump_to_outline_line(99);
+    assert_eq!(
+        harness.app.virtual_editor_state.cursor(),
+        harness.app.virtual_editor_buffer.len_chars()
+    );
+}
+
+#[test]
+fn markdown_outline_follows_typing_without_full_rebuilds() {
+    let ctx = egui::Context::default();
+    let mut harness = make_app();
+    harness.app.editor_mode = EditorMode::VirtualEditor;
+    harness.app.edit_language = Some("rust".to_string());
+    assert!(!harness.app.markdown_outline_eligible());
+    harness.app.edit_language = Some("markdown".to_string());
+    assert!(harness.app.markdown_outline_eligible());
+
+    harness.app.reset_virtual_editor("# Intro\nbody\n");
+    let titles = |headings: Vec<super::super::markdown_outline::MarkdownHeading>| {
+        headings
+            .into_iter()
+            .map(|heading| (heading.line, heading.text))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        titles(harness.app.markdown_headings()),
+        vec![(0, "Intro".to_string())]
+    );
+
+    harness.app.virtual_editor_state.set_cursor(0, 13);
+    let _ = harness.app.apply_virtual_commands(
+        &ctx,
+        &[VirtualInputCommand::InsertText("## Setup\n".to_string())],
+    );
+    assert!(
+        harness
+            .app
+            .markdown_outline
+            .is_synced_to(&harness.app.virtual_editor_buffer),
+        "edits splice into the synced index"
+    );
+    assert_eq!(
+        titles(harness.app.markdown_headings()),
+        vec![(0, "Setup".to_string()), (1, "Intro".to_string())]
+    );
+}
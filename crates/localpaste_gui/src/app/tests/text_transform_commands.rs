@@ -0,0 +1,65 @@
+//! Editor text transforms applied through the rope buffer.
+
+use super::super::text_transforms::TextTransform;
+use super::*;
+
+#[test]
+fn transform_applies_to_selection_as_one_undo_step() {
+    let mut harness = make_app();
+    harness.app.editor_mode = EditorMode::VirtualEditor;
+    let original = "header\npear\napple\nfig\nfooter\n";
+    harness.app.reset_virtual_editor(original);
+
+    // Select "pear\napple\nfig\n" (chars 7..22).
+    let len = harness.app.virtual_editor_buffer.len_chars();
+    harness.app.virtual_editor_state.set_cursor(7, len);
+    harness.app.virtual_editor_state.move_cursor(22, len, true);
+    assert!(harness
+        .app
+        .apply_text_transform(TextTransform::SortAscending));
+    assert_eq!(
+        harness.app.virtual_editor_buffer.to_string(),
+        "header\napple\nfig\npear\nfooter\n"
+    );
+    assert_eq!(
+        harness.app.virtual_editor_state.selection_range(),
+        Some(7..22)
+    );
+    assert_eq!(harness.app.save_status, SaveStatus::Dirty);
+
+    assert!(harness.app.apply_text_transform(TextTransform::Uppercase));
+    assert_eq!(
+        harness.app.virtual_editor_buffer.to_string(),
+        "header\nAPPLE\nFIG\nPEAR\nfooter\n"
+    );
+
+    let ctx = egui::Context::default();
+    let _ = harness
+        .app
+        .apply_virtual_commands(&ctx, &[VirtualInputCommand::Undo]);
+    assert_eq!(
+        harness.app.virtual_editor_buffer.to_string(),
+        "header\napple\nfig\npear\nfooter\n"
+    );
+    let _ = harness
+        .app
+        .apply_virtual_commands(&ctx, &[VirtualInputCommand::Undo]);
+    assert_eq!(harness.app.virtual_editor_buffer.to_string(), original);
+}
+
+#[test]
+fn transform_without_selection_targets_whole_paste_and_reports_decode_errors() {
+    let mut harness = make_app();
+    harness.app.editor_mode = EditorMode::VirtualEditor;
+    harness.app.reset_virtual_editor("b?\na\nb?\n");
+
+    assert!(harness.app.apply_text_transform(TextTransform::UniqueLines));
+    assert_eq!(harness.app.virtual_editor_buffer.to_string(), "b?\na\n");
+
+    assert!(!harness
+        .app
+        .apply_text_transform(TextTransform::Base64Decode));
+    assert_eq!(harness.app.virtual_editor_buffer.to_string(), "b?\na\n");
+    let status = harness.app.status.as_ref().expect("status").text.clone();
+    assert!(status.starts_with("Base64 decode failed"), "{}", status);
+}
@@ -0,0 +1,136 @@
+//! Opt-in update checks, the one-time notice, and per-version dismissal.
+
+use super::*;
+use localpaste_core::settings::UpdateSettings;
+use localpaste_core::update_check::ReleaseInfo;
+use ui::update_notice::UpdateCheckResult;
+
+fn release(version: &str) -> ReleaseInfo {
+    ReleaseInfo {
+        version: version.to_string(),
+        url: format!(
+            "https://github.com/pszemraj/localpaste.rs/releases/tag/v{}",
+            version
+        ),
+        notes: "- faster search".to_string(),
+    }
+}
+
+fn found(version: &str, manual: bool) -> UpdateCheckResult {
+    UpdateCheckResult {
+        manual,
+        result: Ok(Some(release(version))),
+    }
+}
+
+#[test]
+fn update_checks_stay_off_until_enabled() {
+    let mut harness = make_app();
+    // Nothing listens here, so a started check fails fast without network.
+    harness.app.updates.release_url = "http://127.0.0.1:9/latest".to_string();
+    harness.app.maybe_check_for_update();
+    assert!(harness.app.updates.result_rx.is_none());
+
+    harness.app.toggle_update_checks();
+    let updates = match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::SaveUpdateSettings { updates, .. } => updates,
+        other => panic!("unexpected command: {:?}", other),
+    };
+    assert!(updates.check_enabled);
+    harness
+        .app
+        .apply_event(CoreEvent::UpdateSettingsSaved { updates });
+    assert!(harness.app.toasts[0].text.contains("Update checks enabled"));
+
+    harness.app.maybe_check_for_update();
+    assert!(harness.app.updates.result_rx.is_some());
+    assert!(harness.app.updates.checked_at.is_some());
+    harness.app.updates.result_rx = None;
+    harness.app.maybe_check_for_update();
+    assert!(
+        harness.app.updates.result_rx.is_none(),
+        "checks are throttled to once per interval"
+    );
+}
+
+#[test]
+fn newer_release_is_announced_once_and_dismissal_sticks() {
+    let mut harness = make_app();
+    harness.app.apply_update_checked(found("0.5.0", false));
+    assert_eq!(
+        harness
+            .app
+            .updates
+            .available
+            .as_ref()
+            .map(|r| r.version.as_str()),
+        Some("0.5.0")
+    );
+    assert_eq!(harness.app.toasts.len(), 1);
+    assert!(harness.app.toasts[0]
+        .text
+        .contains("LocalPaste 0.5.0 is available"));
+
+    harness.app.toasts.clear();
+    harness.app.apply_update_checked(found("0.5.0", false));
+    assert!(harness.app.toasts.is_empty());
+
+    harness.app.dismiss_update();
+    assert!(harness.app.updates.available.is_none());
+    let updates = match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::SaveUpdateSettings { updates, .. } => updates,
+        other => panic!("unexpected command: {:?}", other),
+    };
+    assert_eq!(updates.dismissed_version.as_deref(), Some("0.5.0"));
+    harness
+        .app
+        .apply_event(CoreEvent::UpdateSettingsSaved { updates });
+
+    harness.app.apply_update_checked(found("0.5.0", false));
+    assert!(harness.app.updates.available.is_none());
+    assert!(harness.app.toasts.is_empty());
+
+    harness.app.apply_update_checked(found("0.5.1", false));
+    assert!(harness.app.updates.available.is_some());
+
+    harness.app.updates.available = None;
+    harness.app.apply_update_checked(found("0.5.0", true));
+    assert!(
+        harness.app.updates.available.is_some(),
+        "a manual check shows dismissed versions again"
+    );
+}
+
+#[test]
+fn background_failures_are_quiet_but_manual_ones_report() {
+    let mut harness = make_app();
+    harness.app.updates.settings = UpdateSettings {
+        check_enabled: true,
+        dismissed_version: None,
+    };
+    harness.app.apply_update_checked(UpdateCheckResult {
+        manual: false,
+        result: Err("offline".to_string()),
+    });
+    assert!(harness.app.toasts.is_empty());
+
+    harness.app.apply_update_checked(UpdateCheckResult {
+        manual: true,
+        result: Err("offline".to_string()),
+    });
+    assert!(harness.app.toasts[0]
+        .text
+        .contains("Update check failed: offline"));
+
+    harness.app.apply_update_checked(UpdateCheckResult {
+        manual: true,
+        result: Ok(None),
+    });
+    assert!(harness
+        .app
+        .toasts
+        .back()
+        .expect("toast")
+        .text
+        .contains("up to date"));
+}
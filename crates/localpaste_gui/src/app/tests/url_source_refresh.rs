@@ -0,0 +1,60 @@
+//! "Refresh from source": request gating and applying refreshed content.
+
+use super::*;
+
+fn refreshed(content: &str) -> Paste {
+    let mut paste = Paste::new(content.to_string(), "Alpha".to_string());
+    paste.id = "alpha".to_string();
+    paste
+}
+
+#[test]
+fn refresh_from_source_waits_for_a_clean_editor() {
+    let mut harness = make_app();
+    harness.app.save_status = SaveStatus::Dirty;
+    harness.app.refresh_selected_from_source();
+    assert!(matches!(
+        harness.cmd_rx.try_recv(),
+        Err(TryRecvError::Empty)
+    ));
+
+    harness.app.save_status = SaveStatus::Saved;
+    harness.app.refresh_selected_from_source();
+    match recv_cmd(&harness.cmd_rx) {
+        CoreCmd::RefreshPasteFromSource { id } => assert_eq!(id, "alpha"),
+        other => panic!("unexpected command: {:?}", other),
+    }
+}
+
+#[test]
+fn refreshed_content_replaces_a_clean_editor_but_not_local_edits() {
+    let mut harness = make_app();
+    harness
+        .app
+        .apply_event(CoreEvent::PasteRefreshedFromSource {
+            paste: refreshed("unchanged"),
+            changed: false,
+        });
+    assert_eq!(harness.app.selected_content.as_str(), "content");
+
+    harness
+        .app
+        .apply_event(CoreEvent::PasteRefreshedFromSource {
+            paste: refreshed("port = 2\n"),
+            changed: true,
+        });
+    assert_eq!(harness.app.selected_content.as_str(), "port = 2\n");
+
+    harness
+        .app
+        .selected_content
+        .reset("typed meanwhile".to_string());
+    harness.app.save_status = SaveStatus::Dirty;
+    harness
+        .app
+        .apply_event(CoreEvent::PasteRefreshedFromSource {
+            paste: refreshed("port = 3\n"),
+            changed: true,
+        });
+    assert_eq!(harness.app.selected_content.as_str(), "typed meanwhile");
+}
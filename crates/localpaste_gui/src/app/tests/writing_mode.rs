@@ -0,0 +1,35 @@
+//! Writing mode is limited to prose pastes and tracks word counts.
+
+use super::*;
+
+#[test]
+fn writing_mode_toggles_only_for_markdown_and_plain_pastes() {
+    let mut harness = make_app();
+    harness.app.edit_language = Some("rust".to_string());
+    harness.app.toggle_writing_mode();
+    assert!(!harness.app.writing_mode.active);
+
+    harness.app.edit_language = Some("markdown".to_string());
+    harness.app.toggle_writing_mode();
+    assert!(harness.app.writing_mode_active());
+
+    // Switching to a code language suspends the layout without losing the toggle.
+    harness.app.edit_language = Some("python".to_string());
+    assert!(!harness.app.writing_mode_active());
+
+    harness.app.edit_language = None;
+    assert!(harness.app.writing_mode_active());
+    harness.app.toggle_writing_mode();
+    assert!(!harness.app.writing_mode.active);
+}
+
+#[test]
+fn writing_mode_word_count_follows_buffer_edits() {
+    let mut harness = make_app();
+    harness.app.editor_mode = EditorMode::VirtualEditor;
+    harness.app.reset_virtual_editor("one two\nthree\n");
+    assert_eq!(harness.app.active_word_count(), 3);
+
+    harness.app.reset_virtual_editor("one two three four");
+    assert_eq!(harness.app.active_word_count(), 4);
+}
@@ -0,0 +1,453 @@
+//! Line and text transforms applied to the editor selection or whole paste.
+
+use super::virtual_editor::EditIntent;
+use super::LocalPasteApp;
+use std::cmp::Ordering;
+use std::time::Instant;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Editor transform exposed through the command palette and toolbar menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TextTransform {
+    SortAscending,
+    SortDescending,
+    SortNumeric,
+    UniqueLines,
+    ReverseLines,
+    JoinLines,
+    Uppercase,
+    Lowercase,
+    TitleCase,
+    UrlEncode,
+    UrlDecode,
+    Base64Encode,
+    Base64Decode,
+    JsonEscape,
+    JsonUnescape,
+}
+
+impl TextTransform {
+    /// Every transform in menu order.
+    pub(crate) const ALL: [TextTransform; 15] = [
+        TextTransform::SortAscending,
+        TextTransform::SortDescending,
+        TextTransform::SortNumeric,
+        TextTransform::UniqueLines,
+        TextTransform::ReverseLines,
+        TextTransform::JoinLines,
+        TextTransform::Uppercase,
+        TextTransform::Lowercase,
+        TextTransform::TitleCase,
+        TextTransform::UrlEncode,
+        TextTransform::UrlDecode,
+        TextTransform::Base64Encode,
+        TextTransform::Base64Decode,
+        TextTransform::JsonEscape,
+        TextTransform::JsonUnescape,
+    ];
+
+    /// Human-readable label used in menus and status messages.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            TextTransform::SortAscending => "Sort lines (A-Z)",
+            TextTransform::SortDescending => "Sort lines (Z-A)",
+            TextTransform::SortNumeric => "Sort lines (numeric)",
+            TextTransform::UniqueLines => "Unique lines",
+            TextTransform::ReverseLines => "Reverse lines",
+            TextTransform::JoinLines => "Join lines",
+            TextTransform::Uppercase => "UPPERCASE",
+            TextTransform::Lowercase => "lowercase",
+            TextTransform::TitleCase => "Title Case",
+            TextTransform::UrlEncode => "URL-encode",
+            TextTransform::UrlDecode => "URL-decode",
+            TextTransform::Base64Encode => "Base64 encode",
+            TextTransform::Base64Decode => "Base64 decode",
+            TextTransform::JsonEscape => "JSON escape",
+            TextTransform::JsonUnescape => "JSON unescape",
+        }
+    }
+
+    /// Transform `text`.
+    ///
+    /// Line operations keep a trailing newline when the input had one.
+    ///
+    /// # Returns
+    /// Transformed text.
+    ///
+    /// # Errors
+    /// Returns a short reason when decode-style transforms reject the input.
+    pub(crate) fn apply(self, text: &str) -> Result<String, String> {
+        match self {
+            TextTransform::SortAscending => Ok(map_lines(text, |lines| lines.sort())),
+            TextTransform::SortDescending => Ok(map_lines(text, |lines| {
+                lines.sort_by(|a, b| b.cmp(a));
+            })),
+            TextTransform::SortNumeric => Ok(map_lines(text, |lines| {
+                lines.sort_by(|a, b| compare_numeric(a, b));
+            })),
+            TextTransform::UniqueLines => Ok(map_lines(text, |lines| {
+                let mut seen = std::collections::HashSet::new();
+                lines.retain(|line| seen.insert(*line));
+            })),
+            TextTransform::ReverseLines => Ok(map_lines(text, |lines| lines.reverse())),
+            TextTransform::JoinLines => Ok(join_lines(text)),
+            TextTransform::Uppercase => Ok(text.to_uppercase()),
+            TextTransform::Lowercase => Ok(text.to_lowercase()),
+            TextTransform::TitleCase => Ok(title_case(text)),
+            TextTransform::UrlEncode => Ok(url_encode(text)),
+            TextTransform::UrlDecode => url_decode(text),
+            TextTransform::Base64Encode => Ok(base64_encode(text.as_bytes())),
+            TextTransform::Base64Decode => base64_decode(text),
+            TextTransform::JsonEscape => Ok(json_escape(text)),
+            TextTransform::JsonUnescape => json_unescape(text),
+        }
+    }
+}
+
+fn map_lines(text: &str, op: impl FnOnce(&mut Vec<&str>)) -> String {
+    let (body, trailing) = match text.strip_suffix('\n') {
+        Some(body) => (body, "\n"),
+        None => (text, ""),
+    };
+    let mut lines: Vec<&str> = body.split('\n').collect();
+    op(&mut lines);
+    let mut out = lines.join("\n");
+    out.push_str(trailing);
+    out
+}
+
+fn leading_number(line: &str) -> Option<f64> {
+    let trimmed = line.trim_start();
+    let end = trimmed
+        .char_indices()
+        .find(|(idx, ch)| {
+            !(ch.is_ascii_digit() || *ch == '.' || (*idx == 0 && matches!(ch, '-' | '+')))
+        })
+        .map(|(idx, _)| idx)
+        .unwrap_or(trimmed.len());
+    trimmed[..end].parse::<f64>().ok()
+}
+
+/// Numbers sort first by value; remaining lines keep lexical order after them.
+fn compare_numeric(a: &str, b: &str) -> Ordering {
+    match (leading_number(a), leading_number(b)) {
+        (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal).then(a.cmp(b)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.cmp(b),
+    }
+}
+
+fn join_lines(text: &str) -> String {
+    let (body, trailing) = match text.strip_suffix('\n') {
+        Some(body) => (body, "\n"),
+        None => (text, ""),
+    };
+    let mut out = body
+        .split('\n')
+        .map(|line| line.trim_end_matches('\r').trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    out.push_str(trailing);
+    out
+}
+
+fn title_case(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut at_word_start = true;
+    for ch in text.chars() {
+        if ch.is_alphanumeric() || ch == '\'' {
+            if at_word_start {
+                out.extend(ch.to_uppercase());
+            } else {
+                out.extend(ch.to_lowercase());
+            }
+            at_word_start = false;
+        } else {
+            out.push(ch);
+            at_word_start = true;
+        }
+    }
+    out
+}
+
+fn url_encode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+fn url_decode(text: &str) -> Result<String, String> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'%' => {
+                let hex = text
+                    .get(idx + 1..idx + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| format!("invalid percent escape at byte {}", idx))?;
+                out.push(hex);
+                idx += 3;
+            }
+            b'+' => {
+                out.push(b' ');
+                idx += 1;
+            }
+            byte => {
+                out.push(byte);
+                idx += 1;
+            }
+        }
+    }
+    String::from_utf8(out).map_err(|_| "decoded bytes are not valid UTF-8".to_string())
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for (pos, shift) in [18u32, 12, 6, 0].into_iter().enumerate() {
+            if pos <= chunk.len() {
+                out.push(BASE64_ALPHABET[((n >> shift) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn base64_decode(text: &str) -> Result<String, String> {
+    let mut out = Vec::with_capacity(text.len() / 4 * 3);
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    for ch in text.chars().filter(|ch| !ch.is_whitespace()) {
+        if ch == '=' {
+            break;
+        }
+        let value = match ch {
+            'A'..='Z' => ch as u32 - 'A' as u32,
+            'a'..='z' => ch as u32 - 'a' as u32 + 26,
+            '0'..='9' => ch as u32 - '0' as u32 + 52,
+            '+' | '-' => 62,
+            '/' | '_' => 63,
+            _ => return Err(format!("invalid base64 character '{}'", ch)),
+        };
+        acc = (acc << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    String::from_utf8(out).map_err(|_| "decoded bytes are not valid UTF-8".to_string())
+}
+
+fn json_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if u32::from(ch) < 0x20 => out.push_str(&format!("\\u{:04x}", u32::from(ch))),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+fn json_unescape(text: &str) -> Result<String, String> {
+    // Accept either a bare escaped body or a full JSON string literal.
+    let body = text
+        .trim()
+        .strip_prefix('"')
+        .and_then(|inner| inner.strip_suffix('"'))
+        .unwrap_or(text);
+    let mut out = String::with_capacity(body.len());
+    let mut chars = body.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            Some('/') => out.push('/'),
+            Some('b') => out.push('\u{8}'),
+            Some('f') => out.push('\u{c}'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some('u') => {
+                let high = read_hex4(&mut chars)?;
+                let code = if (0xD800..0xDC00).contains(&high) {
+                    if chars.next() != Some('\\') || chars.next() != Some('u') {
+                        return Err("unpaired surrogate escape".to_string());
+                    }
+                    let low = read_hex4(&mut chars)?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err("unpaired surrogate escape".to_string());
+                    }
+                    0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                } else {
+                    high
+                };
+                out.push(char::from_u32(code).ok_or("invalid unicode escape")?);
+            }
+            Some(other) => return Err(format!("invalid escape '\\{}'", other)),
+            None => return Err("trailing backslash".to_string()),
+        }
+    }
+    Ok(out)
+}
+
+fn read_hex4(chars: &mut std::str::Chars<'_>) -> Result<u32, String> {
+    let hex: String = chars.by_ref().take(4).collect();
+    if hex.len() != 4 {
+        return Err("truncated unicode escape".to_string());
+    }
+    u32::from_str_radix(hex.as_str(), 16).map_err(|_| "invalid unicode escape".to_string())
+}
+
+impl LocalPasteApp {
+    /// Applies a text transform to the selection, or the whole paste without one.
+    ///
+    /// The replacement is recorded as a single undo step and stays selected.
+    ///
+    /// # Arguments
+    /// - `transform`: Transform to run.
+    ///
+    /// # Returns
+    /// `true` when the buffer changed.
+    pub(crate) fn apply_text_transform(&mut self, transform: TextTransform) -> bool {
+        if !self.is_virtual_editor_mode() || self.reset_transition_active() {
+            self.set_status("Transforms need the editable virtual editor.");
+            return false;
+        }
+        let range = self
+            .virtual_editor_state
+            .selection_range()
+            .unwrap_or(0..self.virtual_editor_buffer.len_chars());
+        let source = self.virtual_editor_buffer.slice_chars(range.clone());
+        let transformed = match transform.apply(source.as_str()) {
+            Ok(transformed) => transformed,
+            Err(reason) => {
+                self.set_status(format!("{} failed: {}", transform.label(), reason));
+                return false;
+            }
+        };
+        if transformed == source {
+            self.set_status(format!("{}: no changes.", transform.label()));
+            return false;
+        }
+        let start = range.start;
+        let changed = self.replace_virtual_range(
+            range,
+            transformed.as_str(),
+            EditIntent::Other,
+            true,
+            Instant::now(),
+        );
+        if changed {
+            let len = self.virtual_editor_buffer.len_chars();
+            let end = (start + transformed.chars().count()).min(len);
+            self.virtual_editor_state.set_cursor(start, len);
+            self.virtual_editor_state.move_cursor(end, len, true);
+            self.mark_dirty();
+            self.set_status(format!("Applied {}.", transform.label()));
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextTransform;
+
+    fn apply(transform: TextTransform, text: &str) -> String {
+        transform.apply(text).expect("transform")
+    }
+
+    #[test]
+    fn line_transforms_keep_trailing_newline() {
+        let text = "pear\napple\n10\n9\napple\n";
+        assert_eq!(
+            apply(TextTransform::SortAscending, text),
+            "10\n9\napple\napple\npear\n"
+        );
+        assert_eq!(
+            apply(TextTransform::SortDescending, text),
+            "pear\napple\napple\n9\n10\n"
+        );
+        assert_eq!(
+            apply(TextTransform::SortNumeric, text),
+            "9\n10\napple\napple\npear\n"
+        );
+        assert_eq!(
+            apply(TextTransform::UniqueLines, text),
+            "pear\napple\n10\n9\n"
+        );
+        assert_eq!(apply(TextTransform::ReverseLines, "a\nb\nc"), "c\nb\na");
+        assert_eq!(
+            apply(TextTransform::JoinLines, "  a\n\nb  \nc\n"),
+            "a b c\n"
+        );
+    }
+
+    #[test]
+    fn case_transforms() {
+        assert_eq!(apply(TextTransform::Uppercase, "straße"), "STRASSE");
+        assert_eq!(apply(TextTransform::Lowercase, "MiXeD"), "mixed");
+        assert_eq!(
+            apply(TextTransform::TitleCase, "hello wORLD, it's-fine"),
+            "Hello World, It's-Fine"
+        );
+    }
+
+    #[test]
+    fn encoding_transforms_round_trip() {
+        let text = "a b/é?&=\"q\"\n\ttab\u{1}";
+        for (encode, decode) in [
+            (TextTransform::UrlEncode, TextTransform::UrlDecode),
+            (TextTransform::Base64Encode, TextTransform::Base64Decode),
+            (TextTransform::JsonEscape, TextTransform::JsonUnescape),
+        ] {
+            let encoded = apply(encode, text);
+            assert_eq!(apply(decode, encoded.as_str()), text, "{:?}", encode);
+        }
+        assert_eq!(apply(TextTransform::UrlEncode, "a b"), "a%20b");
+        assert_eq!(apply(TextTransform::Base64Encode, "hi"), "aGk=");
+        assert_eq!(
+            apply(TextTransform::JsonUnescape, "\"\\ud83d\\ude00\""),
+            "\u{1F600}"
+        );
+    }
+
+    #[test]
+    fn decode_transforms_reject_malformed_input() {
+        assert!(TextTransform::UrlDecode.apply("%zz").is_err());
+        assert!(TextTransform::Base64Decode.apply("@@").is_err());
+        assert!(TextTransform::JsonUnescape.apply("bad\\q").is_err());
+    }
+}
@@ -0,0 +1,267 @@
+//! Line annotations: gutter markers with hover popovers in the virtual editor
+//! and an add/remove list in the properties drawer.
+//!
+//! Annotations anchor to the stored content. The backend re-anchors them when a
+//! save lands, so the list reloads after every save acknowledgement for the
+//! selected paste.
+
+use super::super::*;
+use super::comments::default_comment_author;
+use crate::backend::CoreCmd;
+use eframe::egui;
+use localpaste_core::models::annotation::Annotation;
+
+/// Width of the colored bar painted at the left edge of annotated rows.
+const ANNOTATION_MARKER_WIDTH: f32 = 3.0;
+
+/// Annotation state for the selected paste.
+#[derive(Debug, Default)]
+pub(crate) struct AnnotationsState {
+    /// Paste whose annotations were requested; `items` belong to it once loaded.
+    pub(crate) paste_id: Option<String>,
+    pub(crate) items: Vec<Annotation>,
+    pub(crate) loaded: bool,
+    pub(crate) draft: String,
+}
+
+impl AnnotationsState {
+    /// Annotations covering the 0-based editor line `line_idx`.
+    pub(crate) fn on_line(&self, line_idx: usize) -> Vec<&Annotation> {
+        let line = line_idx.saturating_add(1);
+        self.items
+            .iter()
+            .filter(|annotation| annotation.start_line <= line && line <= annotation.end_line)
+            .collect()
+    }
+
+    /// Loaded annotations when they belong to `paste_id`, for exports.
+    pub(crate) fn for_paste(&self, paste_id: &str) -> Vec<Annotation> {
+        if self.paste_id.as_deref() == Some(paste_id) {
+            self.items.clone()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Paints the gutter marker for one editor row and shows the covering
+/// annotations in a popover while the pointer is over the gutter.
+///
+/// # Arguments
+/// - `gutter_rect`: Line-number gutter area of the row.
+/// - `annotations`: Annotations covering the row's line; nothing is drawn when empty.
+/// - `timezone`: Zone used for the popover timestamps.
+pub(super) fn paint_annotation_marker(
+    ui: &egui::Ui,
+    gutter_rect: egui::Rect,
+    annotations: &[&Annotation],
+    timezone: DisplayTimezone,
+) {
+    if annotations.is_empty() {
+        return;
+    }
+    let marker = egui::Rect::from_min_size(
+        gutter_rect.min,
+        egui::vec2(ANNOTATION_MARKER_WIDTH, gutter_rect.height()),
+    );
+    ui.painter().rect_filled(marker, 1.0, COLOR_ACCENT_TEXT);
+    if !ui.rect_contains_pointer(gutter_rect) {
+        return;
+    }
+    let id = egui::Id::new(("annotation_popover", annotations[0].id.as_str()));
+    egui::Tooltip::always_open(ui.ctx().clone(), ui.layer_id(), id, gutter_rect).show(|ui| {
+        ui.set_max_width(320.0);
+        for annotation in annotations {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(annotation.author.as_str()).small().strong());
+                ui.label(
+                    RichText::new(format!(
+                        "{} · {}",
+                        annotation.range_label(),
+                        timezone.format(annotation.created_at, "%Y-%m-%d %H:%M")
+                    ))
+                    .small()
+                    .color(COLOR_TEXT_MUTED),
+                );
+            });
+            ui.label(annotation.body.as_str());
+        }
+    });
+}
+
+impl LocalPasteApp {
+    /// Requests the selected paste's annotations when they have not been loaded yet.
+    pub(crate) fn sync_annotations(&mut self) {
+        let Some(selected) = self.selected_id.clone() else {
+            return;
+        };
+        if self.annotations.paste_id.as_deref() == Some(selected.as_str()) {
+            return;
+        }
+        self.annotations.paste_id = Some(selected.clone());
+        self.annotations.items.clear();
+        self.annotations.loaded = false;
+        self.annotations.draft.clear();
+        let _ = self
+            .backend
+            .cmd_tx
+            .send(CoreCmd::ListAnnotations { paste_id: selected });
+    }
+
+    /// Reloads annotations after a save of `paste_id` may have moved them.
+    pub(crate) fn reload_annotations_after_save(&mut self, paste_id: &str) {
+        if self.annotations.paste_id.as_deref() != Some(paste_id) {
+            return;
+        }
+        let _ = self.backend.cmd_tx.send(CoreCmd::ListAnnotations {
+            paste_id: paste_id.to_string(),
+        });
+    }
+
+    /// Stores loaded annotations when they still belong to the selected paste.
+    pub(crate) fn apply_annotations_loaded(
+        &mut self,
+        paste_id: String,
+        annotations: Vec<Annotation>,
+    ) {
+        if self.annotations.paste_id.as_deref() != Some(paste_id.as_str()) {
+            return;
+        }
+        self.annotations.items = annotations;
+        self.annotations.loaded = true;
+    }
+
+    /// 1-based inclusive line range covered by the editor selection or caret.
+    ///
+    /// A selection ending at the start of a line does not include that line.
+    ///
+    /// # Returns
+    /// `None` outside the virtual editor.
+    pub(crate) fn annotation_target_lines(&self) -> Option<(usize, usize)> {
+        if !self.is_virtual_editor_mode() {
+            return None;
+        }
+        let cursor = self.virtual_editor_state.cursor();
+        let range = self
+            .virtual_editor_state
+            .selection_range()
+            .unwrap_or(cursor..cursor);
+        let (start, _) = self.virtual_editor_buffer.char_to_line_col(range.start);
+        let (mut end, end_col) = self.virtual_editor_buffer.char_to_line_col(range.end);
+        if range.end > range.start && end_col == 0 && end > start {
+            end -= 1;
+        }
+        Some((start + 1, end + 1))
+    }
+
+    /// Sends the draft annotation for the selected lines.
+    pub(crate) fn submit_annotation(&mut self) {
+        let Some(paste_id) = self.annotations.paste_id.clone() else {
+            return;
+        };
+        let Some((start_line, end_line)) = self.annotation_target_lines() else {
+            return;
+        };
+        let body = self.annotations.draft.trim().to_string();
+        if body.is_empty() {
+            return;
+        }
+        let author = match self.comments.author.trim() {
+            "" => default_comment_author(),
+            author => author.to_string(),
+        };
+        let cmd = CoreCmd::AddAnnotation {
+            paste_id,
+            start_line,
+            end_line,
+            author,
+            body,
+        };
+        if self.backend.cmd_tx.send(cmd).is_err() {
+            self.set_status("Add annotation failed: backend unavailable.");
+            return;
+        }
+        self.annotations.draft.clear();
+    }
+
+    /// Renders the collapsible annotations section inside the properties drawer.
+    ///
+    /// New annotations cover the editor selection and need saved content, since
+    /// line numbers refer to what the backend has stored.
+    pub(crate) fn render_annotations_section(&mut self, ui: &mut egui::Ui) {
+        self.sync_annotations();
+        let title = if self.annotations.loaded {
+            format!("Annotations ({})", self.annotations.items.len())
+        } else {
+            "Annotations".to_string()
+        };
+        let target = self.annotation_target_lines();
+        let saved = self.save_status == SaveStatus::Saved;
+        let mut delete = None;
+        let mut submit = false;
+        let annotations = &mut self.annotations;
+        egui::CollapsingHeader::new(RichText::new(title).small().color(COLOR_TEXT_MUTED))
+            .id_salt("drawer_annotations")
+            .default_open(false)
+            .show(ui, |ui| {
+                if annotations.loaded && annotations.items.is_empty() {
+                    ui.label(
+                        RichText::new("Select lines in the editor to annotate them.")
+                            .small()
+                            .color(COLOR_TEXT_MUTED),
+                    );
+                }
+                for annotation in &annotations.items {
+                    ui.horizontal(|ui| {
+                        let mut label = annotation.range_label();
+                        if annotation.detached {
+                            label.push_str(" (lines removed)");
+                        }
+                        ui.label(RichText::new(label).small().strong());
+                        ui.label(
+                            RichText::new(annotation.author.as_str())
+                                .small()
+                                .color(COLOR_TEXT_MUTED),
+                        );
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("Delete").clicked() {
+                                delete = Some(annotation.id.clone());
+                            }
+                        });
+                    });
+                    ui.label(annotation.body.as_str());
+                    ui.add_space(4.0);
+                }
+                ui.separator();
+                ui.add(
+                    egui::TextEdit::multiline(&mut annotations.draft)
+                        .id_salt("drawer_annotation_draft")
+                        .hint_text("Note for the selected lines")
+                        .desired_rows(2)
+                        .desired_width(f32::INFINITY),
+                );
+                let button_label = match target {
+                    Some((start, end)) if start == end => format!("Annotate line {}", start),
+                    Some((start, end)) => format!("Annotate lines {}-{}", start, end),
+                    None => "Annotate".to_string(),
+                };
+                let enabled = saved && target.is_some() && !annotations.draft.trim().is_empty();
+                let mut response = ui.add_enabled(enabled, egui::Button::new(button_label));
+                if !saved {
+                    response = response.on_disabled_hover_text("Save the paste before annotating.");
+                }
+                if response.clicked() {
+                    submit = true;
+                }
+            });
+        if submit {
+            self.submit_annotation();
+        }
+        if let (Some(annotation_id), Some(paste_id)) = (delete, self.annotations.paste_id.clone()) {
+            let _ = self.backend.cmd_tx.send(CoreCmd::DeleteAnnotation {
+                paste_id,
+                annotation_id,
+            });
+        }
+    }
+}
@@ -0,0 +1,387 @@
+//! Backups window: list, create, verify, restore, and delete backups, and
+//! configure the scheduled-backup interval.
+//!
+//! All filesystem work runs on the backend worker. Restores cannot replace
+//! the open database, so they are scheduled and applied on the next launch.
+
+use super::super::*;
+use crate::backend::CoreCmd;
+use eframe::egui;
+use localpaste_core::db::backup::BackupEntry;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Minimum spacing between scheduled-backup checks.
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Destructive backup action awaiting confirmation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BackupConfirm {
+    Restore(PathBuf),
+    Delete(PathBuf),
+}
+
+/// Backups window state and scheduled-backup bookkeeping.
+#[derive(Debug, Default)]
+pub(crate) struct BackupsState {
+    pub(crate) open: bool,
+    /// Whether `items` reflects a `BackupsLoaded` response.
+    pub(crate) loaded: bool,
+    pub(crate) items: Vec<BackupEntry>,
+    /// Verification outcome per backup path; missing means not checked yet.
+    pub(crate) verified: HashMap<PathBuf, Result<(), String>>,
+    pub(crate) pending_restore: Option<PathBuf>,
+    /// Saved scheduled-backup interval from the settings file.
+    pub(crate) interval_hours: Option<u64>,
+    pub(crate) schedule_enabled: bool,
+    pub(crate) interval_input: String,
+    pub(crate) confirm: Option<BackupConfirm>,
+    pub(crate) schedule_checked_at: Option<Instant>,
+    pub(crate) scheduled_attempt_at: Option<Instant>,
+}
+
+impl BackupsState {
+    /// Builds the initial state from the saved scheduled-backup interval.
+    pub(crate) fn with_interval(interval_hours: Option<u64>) -> Self {
+        let mut state = Self::default();
+        state.set_interval(interval_hours);
+        state
+    }
+
+    fn set_interval(&mut self, interval_hours: Option<u64>) {
+        self.interval_hours = interval_hours;
+        self.schedule_enabled = interval_hours.is_some();
+        self.interval_input = interval_hours.unwrap_or(24).to_string();
+    }
+}
+
+fn file_label(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+impl LocalPasteApp {
+    /// Opens the backups window and requests a fresh listing.
+    pub(crate) fn open_backups(&mut self) {
+        self.backups.open = true;
+        self.backups.confirm = None;
+        self.send_backup_command(CoreCmd::ListBackups {
+            db_path: self.db_path.clone(),
+        });
+    }
+
+    fn send_backup_command(&mut self, cmd: CoreCmd) -> bool {
+        if self.backend.cmd_tx.send(cmd).is_err() {
+            self.set_status("Backup action failed: backend unavailable.");
+            return false;
+        }
+        true
+    }
+
+    /// Starts a backup of the open database.
+    pub(crate) fn create_backup_now(&mut self) {
+        if self.send_backup_command(CoreCmd::CreateBackup {
+            db_path: self.db_path.clone(),
+        }) {
+            self.set_status("Creating backup...");
+        }
+    }
+
+    /// Creates a backup when the saved interval has elapsed since the newest one.
+    ///
+    /// Checks at most once a minute. Backups are listed first so the newest
+    /// timestamp is known, and a failed attempt is not retried until another
+    /// interval has passed.
+    pub(crate) fn maybe_run_scheduled_backup(&mut self) {
+        let Some(hours) = self.backups.interval_hours else {
+            return;
+        };
+        if self
+            .backups
+            .schedule_checked_at
+            .is_some_and(|at| at.elapsed() < SCHEDULE_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.backups.schedule_checked_at = Some(Instant::now());
+        if !self.backups.loaded {
+            let db_path = self.db_path.clone();
+            self.send_backup_command(CoreCmd::ListBackups { db_path });
+            return;
+        }
+        let interval = Duration::from_secs(hours.saturating_mul(3600));
+        if self
+            .backups
+            .scheduled_attempt_at
+            .is_some_and(|at| at.elapsed() < interval)
+        {
+            return;
+        }
+        let newest = self.backups.items.first().map(|item| item.created_at_unix);
+        let due = newest.is_none_or(|at| unix_now().saturating_sub(at) >= interval.as_secs());
+        if due {
+            self.backups.scheduled_attempt_at = Some(Instant::now());
+            let db_path = self.db_path.clone();
+            self.send_backup_command(CoreCmd::CreateBackup { db_path });
+        }
+    }
+
+    /// Applies a backup listing from the backend.
+    pub(crate) fn apply_backups_loaded(
+        &mut self,
+        items: Vec<BackupEntry>,
+        pending_restore: Option<PathBuf>,
+        interval_hours: Option<u64>,
+    ) {
+        let backups = &mut self.backups;
+        backups
+            .verified
+            .retain(|path, _| items.iter().any(|item| &item.path == path));
+        backups.items = items;
+        backups.loaded = true;
+        backups.pending_restore = pending_restore;
+        if backups.interval_hours != interval_hours {
+            backups.set_interval(interval_hours);
+        }
+    }
+
+    /// Reports a finished backup, including remote targets that failed.
+    pub(crate) fn apply_backup_created(&mut self, path: &Path, remote_failures: &[String]) {
+        if remote_failures.is_empty() {
+            self.set_status(format!("Backup created: {}", file_label(path)));
+        } else {
+            self.set_status(format!(
+                "Backup created: {}; remote target(s) failed: {}",
+                file_label(path),
+                remote_failures.join(", ")
+            ));
+        }
+    }
+
+    /// Records a verification outcome for one backup.
+    pub(crate) fn apply_backup_verified(&mut self, path: PathBuf, result: Result<(), String>) {
+        match &result {
+            Ok(()) => self.set_status(format!("Backup verified: {}", file_label(&path))),
+            Err(err) => self.set_status(format!("Backup verification failed: {}", err)),
+        }
+        self.backups.verified.insert(path, result);
+    }
+
+    /// Drops a deleted backup from the listing.
+    pub(crate) fn apply_backup_deleted(&mut self, path: &Path) {
+        self.backups.items.retain(|item| item.path != path);
+        self.backups.verified.remove(path);
+        self.set_status(format!("Deleted backup {}", file_label(path)));
+    }
+
+    /// Updates the scheduled restore shown in the window.
+    pub(crate) fn apply_restore_scheduled(&mut self, path: Option<PathBuf>) {
+        match path.as_deref() {
+            Some(path) => self.set_status(format!(
+                "Restore of {} scheduled; restart LocalPaste to apply it.",
+                file_label(path)
+            )),
+            None => self.set_status("Scheduled restore cancelled."),
+        }
+        self.backups.pending_restore = path;
+    }
+
+    /// Applies a saved scheduled-backup interval.
+    pub(crate) fn apply_backup_schedule_saved(&mut self, interval_hours: Option<u64>) {
+        self.backups.set_interval(interval_hours);
+        self.backups.scheduled_attempt_at = None;
+        self.backups.schedule_checked_at = None;
+        match interval_hours {
+            Some(hours) => self.set_status(format!("Backups scheduled every {} hour(s).", hours)),
+            None => self.set_status("Scheduled backups turned off."),
+        }
+    }
+
+    fn save_backup_schedule(&mut self) {
+        let interval_hours = if self.backups.schedule_enabled {
+            match self.backups.interval_input.trim().parse::<u64>() {
+                Ok(hours) if hours > 0 => Some(hours),
+                _ => {
+                    self.set_status("Backup interval must be a whole number of hours above 0.");
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+        self.send_backup_command(CoreCmd::SaveBackupSchedule {
+            db_path: self.db_path.clone(),
+            interval_hours,
+        });
+    }
+
+    fn confirm_backup_action(&mut self, confirm: BackupConfirm) {
+        let db_path = self.db_path.clone();
+        let cmd = match confirm {
+            BackupConfirm::Restore(path) => CoreCmd::ScheduleRestore { db_path, path },
+            BackupConfirm::Delete(path) => CoreCmd::DeleteBackup { db_path, path },
+        };
+        self.send_backup_command(cmd);
+    }
+
+    /// Renders the backups window while it is open.
+    pub(crate) fn render_backups(&mut self, ctx: &egui::Context) {
+        if !self.backups.open {
+            return;
+        }
+        let mut open = true;
+        let mut create = false;
+        let mut refresh = false;
+        let mut cancel_restore = false;
+        let mut save_schedule = false;
+        let mut verify: Option<PathBuf> = None;
+        let mut confirmed: Option<BackupConfirm> = None;
+        let backups = &mut self.backups;
+        egui::Window::new("Backups")
+            .open(&mut open)
+            .default_width(620.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    create = ui.button("Create backup now").clicked();
+                    refresh = ui.button("Refresh").clicked();
+                });
+                if let Some(pending) = backups.pending_restore.as_deref() {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(
+                            ui.visuals().warn_fg_color,
+                            format!("{} will be restored on next launch.", file_label(pending)),
+                        );
+                        cancel_restore = ui.small_button("Cancel restore").clicked();
+                    });
+                }
+                ui.separator();
+                if backups.items.is_empty() {
+                    ui.label(
+                        egui::RichText::new(if backups.loaded {
+                            "No backups yet."
+                        } else {
+                            "Loading backups..."
+                        })
+                        .color(COLOR_TEXT_MUTED),
+                    );
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        for item in &backups.items {
+                            ui.horizontal(|ui| {
+                                let created = chrono::DateTime::from_timestamp(
+                                    item.created_at_unix as i64,
+                                    0,
+                                )
+                                .map(|at| self.display_timezone.format(at, "%Y-%m-%d %H:%M:%S"))
+                                .unwrap_or_else(|| item.created_at_unix.to_string());
+                                ui.label(created)
+                                    .on_hover_text(item.path.display().to_string());
+                                let mut detail = format!("{} KB", item.size.div_ceil(1024));
+                                if item.encrypted {
+                                    detail.push_str(", encrypted");
+                                }
+                                let status = match backups.verified.get(&item.path) {
+                                    _ if !item.has_manifest => "no manifest",
+                                    Some(Ok(())) => "verified",
+                                    Some(Err(_)) => "FAILED verification",
+                                    None => "not verified",
+                                };
+                                detail.push_str(", ");
+                                detail.push_str(status);
+                                let detail_label = ui.label(
+                                    egui::RichText::new(detail).small().color(COLOR_TEXT_MUTED),
+                                );
+                                if let Some(Err(err)) = backups.verified.get(&item.path) {
+                                    detail_label.on_hover_text(err.as_str());
+                                }
+                                if ui.small_button("Verify").clicked() {
+                                    verify = Some(item.path.clone());
+                                }
+                                if ui.small_button("Restore...").clicked() {
+                                    backups.confirm =
+                                        Some(BackupConfirm::Restore(item.path.clone()));
+                                }
+                                if ui.small_button("Delete...").clicked() {
+                                    backups.confirm =
+                                        Some(BackupConfirm::Delete(item.path.clone()));
+                                }
+                            });
+                        }
+                    });
+                if let Some(confirm) = backups.confirm.clone() {
+                    ui.separator();
+                    let prompt = match &confirm {
+                        BackupConfirm::Restore(path) => format!(
+                            "Restore {} on next launch? Changes made after it are lost.",
+                            file_label(path)
+                        ),
+                        BackupConfirm::Delete(path) => {
+                            format!("Delete {} and its manifest?", file_label(path))
+                        }
+                    };
+                    ui.label(prompt);
+                    ui.horizontal(|ui| {
+                        if ui.button("Confirm").clicked() {
+                            confirmed = Some(confirm);
+                        }
+                        if ui.button("Cancel").clicked() {
+                            backups.confirm = None;
+                        }
+                    });
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut backups.schedule_enabled, "Back up every");
+                    ui.add_enabled(
+                        backups.schedule_enabled,
+                        egui::TextEdit::singleline(&mut backups.interval_input).desired_width(40.0),
+                    );
+                    ui.label("hours while LocalPaste is open");
+                    save_schedule = ui.button("Save").clicked();
+                });
+                ui.label(
+                    egui::RichText::new(
+                        "Remote targets and retention are configured in settings.json.",
+                    )
+                    .small()
+                    .color(COLOR_TEXT_MUTED),
+                );
+            });
+        if !open {
+            self.backups.open = false;
+            self.backups.confirm = None;
+            return;
+        }
+        if create {
+            self.create_backup_now();
+        }
+        if refresh {
+            let db_path = self.db_path.clone();
+            self.send_backup_command(CoreCmd::ListBackups { db_path });
+        }
+        if cancel_restore {
+            let db_path = self.db_path.clone();
+            self.send_backup_command(CoreCmd::CancelRestore { db_path });
+        }
+        if let Some(path) = verify {
+            self.send_backup_command(CoreCmd::VerifyBackup { path });
+        }
+        if let Some(confirm) = confirmed {
+            self.backups.confirm = None;
+            self.confirm_backup_action(confirm);
+        }
+        if save_schedule {
+            self.save_backup_schedule();
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Unsaved-changes prompt shown when the window close is intercepted.
+
+use super::super::*;
+use eframe::egui;
+
+impl LocalPasteApp {
+    /// Renders the Save/Discard/Cancel prompt for a pending close.
+    pub(crate) fn render_close_prompt(&mut self, ctx: &egui::Context) {
+        if !self.close_prompt.open {
+            return;
+        }
+        let mut save = false;
+        let mut discard = false;
+        let mut cancel = ctx.input(|input| input.key_pressed(egui::Key::Escape));
+        let name = if self.edit_name.trim().is_empty() {
+            self.selected_id.clone().unwrap_or_default()
+        } else {
+            self.edit_name.clone()
+        };
+        let mut pending = Vec::new();
+        if self.save_status != SaveStatus::Saved || self.save_in_flight {
+            pending.push("content");
+        }
+        if self.metadata_dirty || self.metadata_save_in_flight {
+            pending.push("metadata");
+        }
+
+        with_muted_modal_chrome(ctx, || {
+            egui::Window::new("Unsaved Changes")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+                .show(ctx, |ui| {
+                    ui.label("Changes not saved yet:");
+                    ui.add_space(4.0);
+                    ui.label(
+                        RichText::new(format!("\u{25CF} {} ({})", name, pending.join(", ")))
+                            .monospace(),
+                    );
+                    ui.add_space(8.0);
+                    if self.close_prompt.close_after_save {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(RichText::new("Saving...").color(COLOR_TEXT_MUTED));
+                        });
+                    }
+                    ui.horizontal(|ui| {
+                        let idle = !self.close_prompt.close_after_save;
+                        save = ui.add_enabled(idle, egui::Button::new("Save")).clicked();
+                        discard = ui.add_enabled(idle, egui::Button::new("Discard")).clicked();
+                        cancel |= ui.button("Cancel").clicked();
+                    });
+                });
+        });
+
+        if save {
+            self.confirm_close_with_save();
+        } else if discard {
+            self.confirm_close_with_discard(ctx);
+        } else if cancel {
+            self.close_prompt.open = false;
+            self.close_prompt.close_after_save = false;
+        }
+    }
+}
@@ -1,5 +1,7 @@
 //! Command palette rendering and quick actions.
 
+use super::super::text_transforms::TextTransform;
+use super::super::util::parse_paste_link;
 use super::super::*;
 use crate::backend::CoreCmd;
 use eframe::egui::{self, RichText};
@@ -16,11 +18,30 @@ pub(crate) enum CommandPaletteAction {
     OpenHistoryModal,
     FocusSearch,
     ToggleProperties,
+    ToggleOutline,
+    JumpToSymbol,
     RefreshList,
     OpenPaste(String),
     DeletePaste(String),
     CopyPasteRaw(String),
     CopyPasteFenced(String),
+    Transform(TextTransform),
+    ToggleWritingMode,
+    PrintSelected,
+    ImportSnippets,
+    SplitSelected,
+    MergePastes,
+    ExportGistLayout,
+    CleanUpNow,
+    OpenBackups,
+    OpenFolderRules,
+    RefreshFromSource,
+    ToggleDisplayTimezone,
+    ToggleUpdateChecks,
+    CheckForUpdate,
+    CopyWithLineNumbers,
+    CopyLinkToLine,
+    OpenLink { id: String, line: Option<usize> },
 }
 
 /// Display row for command actions in the palette command section.
@@ -178,6 +199,7 @@ impl LocalPasteApp {
                     | CommandPaletteAction::PasteAsNew
                     | CommandPaletteAction::DeleteSelected
                     | CommandPaletteAction::DeletePaste(_)
+                    | CommandPaletteAction::RefreshFromSource
             )
         {
             self.set_mutation_shortcut_blocked_status();
@@ -218,9 +240,17 @@ impl LocalPasteApp {
                 self.command_palette_open = false;
             }
             CommandPaletteAction::ToggleProperties => {
-                self.properties_drawer_open = !self.properties_drawer_open;
+                self.toggle_properties_drawer();
                 self.command_palette_open = false;
             }
+            CommandPaletteAction::ToggleOutline => {
+                self.symbol_outline.panel_open = !self.symbol_outline.panel_open;
+                self.command_palette_open = false;
+            }
+            CommandPaletteAction::JumpToSymbol => {
+                self.command_palette_open = false;
+                self.toggle_symbol_jump();
+            }
             CommandPaletteAction::RefreshList => {
                 self.request_refresh();
                 self.command_palette_open = false;
@@ -237,6 +267,79 @@ impl LocalPasteApp {
             CommandPaletteAction::CopyPasteFenced(id) => {
                 self.queue_palette_copy(id, true);
             }
+            CommandPaletteAction::ImportSnippets => {
+                self.open_import_wizard();
+                self.command_palette_open = false;
+            }
+            CommandPaletteAction::SplitSelected => {
+                self.command_palette_open = false;
+                self.open_split_dialog();
+            }
+            CommandPaletteAction::MergePastes => {
+                self.command_palette_open = false;
+                self.open_merge_dialog();
+            }
+            CommandPaletteAction::ExportGistLayout => {
+                self.command_palette_open = false;
+                self.export_gist_layout();
+            }
+            CommandPaletteAction::CleanUpNow => {
+                self.command_palette_open = false;
+                if self.backend.cmd_tx.send(CoreCmd::Cleanup).is_err() {
+                    self.set_status("Cleanup failed: backend unavailable.");
+                } else {
+                    self.set_status("Cleaning up...");
+                }
+            }
+            CommandPaletteAction::OpenBackups => {
+                self.command_palette_open = false;
+                self.open_backups();
+            }
+            CommandPaletteAction::OpenFolderRules => {
+                self.command_palette_open = false;
+                self.open_folder_rules();
+            }
+            CommandPaletteAction::RefreshFromSource => {
+                self.command_palette_open = false;
+                self.refresh_selected_from_source();
+            }
+            CommandPaletteAction::ToggleDisplayTimezone => {
+                self.command_palette_open = false;
+                self.toggle_display_timezone();
+            }
+            CommandPaletteAction::ToggleUpdateChecks => {
+                self.command_palette_open = false;
+                self.toggle_update_checks();
+            }
+            CommandPaletteAction::CheckForUpdate => {
+                self.command_palette_open = false;
+                self.check_for_update_now();
+            }
+            CommandPaletteAction::PrintSelected => {
+                self.print_selected_paste();
+                self.command_palette_open = false;
+            }
+            CommandPaletteAction::ToggleWritingMode => {
+                self.toggle_writing_mode();
+                self.command_palette_open = false;
+            }
+            CommandPaletteAction::Transform(transform) => {
+                self.apply_text_transform(transform);
+                self.command_palette_open = false;
+            }
+            CommandPaletteAction::CopyWithLineNumbers => {
+                self.copy_with_line_numbers();
+                self.command_palette_open = false;
+            }
+            CommandPaletteAction::CopyLinkToLine => {
+                self.copy_link_to_line();
+                self.command_palette_open = false;
+            }
+            CommandPaletteAction::OpenLink { id, line } => {
+                if self.open_paste_link(id, line) {
+                    self.command_palette_open = false;
+                }
+            }
         }
     }
 
@@ -267,18 +370,70 @@ impl LocalPasteApp {
 
     fn command_palette_actions(&self) -> Vec<CommandPaletteItem> {
         let query = self.command_palette_query.trim().to_ascii_lowercase();
-        let mut items = Vec::new();
-
-        items.push(CommandPaletteItem {
-            label: "New paste".to_string(),
-            hint: "(Ctrl/Cmd+N)".to_string(),
-            action: CommandPaletteAction::NewPaste,
-        });
-        items.push(CommandPaletteItem {
-            label: "Paste as new paste".to_string(),
-            hint: "(Ctrl/Cmd+Shift+V)".to_string(),
-            action: CommandPaletteAction::PasteAsNew,
-        });
+        let mut items = vec![
+            CommandPaletteItem {
+                label: "New paste".to_string(),
+                hint: "(Ctrl/Cmd+N)".to_string(),
+                action: CommandPaletteAction::NewPaste,
+            },
+            CommandPaletteItem {
+                label: "Paste as new paste".to_string(),
+                hint: "(Ctrl/Cmd+Shift+V)".to_string(),
+                action: CommandPaletteAction::PasteAsNew,
+            },
+            CommandPaletteItem {
+                label: "Import snippets".to_string(),
+                hint: "massCode, Boostnote, markdown, gist".to_string(),
+                action: CommandPaletteAction::ImportSnippets,
+            },
+            CommandPaletteItem {
+                label: "Merge pastes".to_string(),
+                hint: "join several into one, in chosen order".to_string(),
+                action: CommandPaletteAction::MergePastes,
+            },
+            CommandPaletteItem {
+                label: "Export all to gist directory".to_string(),
+                hint: "one folder per paste".to_string(),
+                action: CommandPaletteAction::ExportGistLayout,
+            },
+            CommandPaletteItem {
+                label: "Clean up now".to_string(),
+                hint: "orphan drafts, folder stats".to_string(),
+                action: CommandPaletteAction::CleanUpNow,
+            },
+            CommandPaletteItem {
+                label: "Backups".to_string(),
+                hint: "create, verify, restore, schedule".to_string(),
+                action: CommandPaletteAction::OpenBackups,
+            },
+            CommandPaletteItem {
+                label: "Folder rules".to_string(),
+                hint: "auto-file new pastes by name, language, tag, source".to_string(),
+                action: CommandPaletteAction::OpenFolderRules,
+            },
+            CommandPaletteItem {
+                label: match self.display_timezone {
+                    DisplayTimezone::Local => "Show timestamps in UTC".to_string(),
+                    DisplayTimezone::Utc => "Show timestamps in local time".to_string(),
+                },
+                hint: "timezone setting".to_string(),
+                action: CommandPaletteAction::ToggleDisplayTimezone,
+            },
+            CommandPaletteItem {
+                label: if self.updates.settings.check_enabled {
+                    "Disable update checks".to_string()
+                } else {
+                    "Enable update checks".to_string()
+                },
+                hint: "daily GitHub release lookup".to_string(),
+                action: CommandPaletteAction::ToggleUpdateChecks,
+            },
+            CommandPaletteItem {
+                label: "Check for updates".to_string(),
+                hint: format!("running {}", env!("CARGO_PKG_VERSION")),
+                action: CommandPaletteAction::CheckForUpdate,
+            },
+        ];
         if self.selected_id.is_some() {
             items.push(CommandPaletteItem {
                 label: "Delete selected".to_string(),
@@ -295,6 +450,11 @@ impl LocalPasteApp {
                 hint: "persist title/type/tags".to_string(),
                 action: CommandPaletteAction::SaveMetadata,
             });
+            items.push(CommandPaletteItem {
+                label: "Split into multiple pastes".to_string(),
+                hint: "file banners or multi-file patches".to_string(),
+                action: CommandPaletteAction::SplitSelected,
+            });
             items.push(CommandPaletteItem {
                 label: "Open diff modal".to_string(),
                 hint: "compare current paste".to_string(),
@@ -305,6 +465,66 @@ impl LocalPasteApp {
                 hint: "browse snapshots".to_string(),
                 action: CommandPaletteAction::OpenHistoryModal,
             });
+            items.push(CommandPaletteItem {
+                label: "Refresh from source".to_string(),
+                hint: "re-fetch linked URL".to_string(),
+                action: CommandPaletteAction::RefreshFromSource,
+            });
+            items.push(CommandPaletteItem {
+                label: "Print paste".to_string(),
+                hint: "PDF with header and line numbers".to_string(),
+                action: CommandPaletteAction::PrintSelected,
+            });
+            items.push(CommandPaletteItem {
+                label: "Toggle outline panel".to_string(),
+                hint: "functions, headings, keys".to_string(),
+                action: CommandPaletteAction::ToggleOutline,
+            });
+            if self.is_virtual_editor_mode() {
+                items.push(CommandPaletteItem {
+                    label: "Go to symbol".to_string(),
+                    hint: "(Ctrl/Cmd+Shift+O)".to_string(),
+                    action: CommandPaletteAction::JumpToSymbol,
+                });
+            }
+            if self.writing_mode.active || self.writing_mode_eligible() {
+                items.push(CommandPaletteItem {
+                    label: "Toggle writing mode".to_string(),
+                    hint: "distraction-free, word goal".to_string(),
+                    action: CommandPaletteAction::ToggleWritingMode,
+                });
+            }
+            if self.is_virtual_editor_mode() {
+                let scope = if self.virtual_editor_state.selection_range().is_some() {
+                    "selected lines"
+                } else {
+                    "whole paste"
+                };
+                items.push(CommandPaletteItem {
+                    label: "Copy with line numbers".to_string(),
+                    hint: scope.to_string(),
+                    action: CommandPaletteAction::CopyWithLineNumbers,
+                });
+                items.push(CommandPaletteItem {
+                    label: "Copy link to line".to_string(),
+                    hint: "localpaste://paste/<id>#L<line>".to_string(),
+                    action: CommandPaletteAction::CopyLinkToLine,
+                });
+            }
+            if self.is_virtual_editor_mode() {
+                let hint = if self.virtual_editor_state.selection_range().is_some() {
+                    "selection"
+                } else {
+                    "whole paste"
+                };
+                for transform in TextTransform::ALL {
+                    items.push(CommandPaletteItem {
+                        label: format!("Transform: {}", transform.label()),
+                        hint: hint.to_string(),
+                        action: CommandPaletteAction::Transform(transform),
+                    });
+                }
+            }
         }
         items.push(CommandPaletteItem {
             label: "Focus sidebar search".to_string(),
@@ -325,6 +545,18 @@ impl LocalPasteApp {
         if query.is_empty() {
             return items;
         }
+        // A pasted permalink is an action of its own; it never matches a label.
+        if let Some((id, line)) = parse_paste_link(self.command_palette_query.as_str()) {
+            let label = match line {
+                Some(line) => format!("Open link to line {}", line),
+                None => "Open link".to_string(),
+            };
+            return vec![CommandPaletteItem {
+                label,
+                hint: id.clone(),
+                action: CommandPaletteAction::OpenLink { id, line },
+            }];
+        }
         items
             .into_iter()
             .filter(|item| {
@@ -389,7 +621,8 @@ impl LocalPasteApp {
 
     /// Opens the selected palette result in the main editor view.
     pub(crate) fn open_palette_selection(&mut self, id: String) {
-        if self.select_paste(id) {
+        let query = self.command_palette_query.clone();
+        if self.open_search_hit(id, query.as_str()) {
             self.command_palette_open = false;
         }
     }
@@ -0,0 +1,162 @@
+//! Collapsible comment thread shown in the properties drawer.
+//!
+//! The thread loads lazily the first time the drawer renders for a paste and
+//! reloads from the backend after every add or delete.
+
+use super::super::*;
+use crate::backend::CoreCmd;
+use eframe::egui;
+use localpaste_core::models::comment::Comment;
+
+/// Comment thread state for the selected paste.
+#[derive(Debug, Default)]
+pub(crate) struct CommentsState {
+    /// Paste whose thread was requested; `items` belong to it once loaded.
+    pub(crate) paste_id: Option<String>,
+    pub(crate) items: Vec<Comment>,
+    pub(crate) loaded: bool,
+    /// Author label for new comments; blank falls back to the OS user name.
+    pub(crate) author: String,
+    pub(crate) draft: String,
+}
+
+/// Author label used when the author field is left blank.
+pub(crate) fn default_comment_author() -> String {
+    ["USER", "USERNAME"]
+        .iter()
+        .find_map(|key| {
+            std::env::var(key)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+        })
+        .unwrap_or_else(|| "local".to_string())
+}
+
+impl LocalPasteApp {
+    /// Requests the selected paste's thread when it has not been loaded yet.
+    pub(crate) fn sync_comments_thread(&mut self) {
+        let Some(selected) = self.selected_id.clone() else {
+            return;
+        };
+        if self.comments.paste_id.as_deref() == Some(selected.as_str()) {
+            return;
+        }
+        self.comments.paste_id = Some(selected.clone());
+        self.comments.items.clear();
+        self.comments.loaded = false;
+        self.comments.draft.clear();
+        let _ = self
+            .backend
+            .cmd_tx
+            .send(CoreCmd::ListComments { paste_id: selected });
+    }
+
+    /// Stores a loaded thread when it still belongs to the selected paste.
+    pub(crate) fn apply_comments_loaded(&mut self, paste_id: String, comments: Vec<Comment>) {
+        if self.comments.paste_id.as_deref() != Some(paste_id.as_str()) {
+            return;
+        }
+        self.comments.items = comments;
+        self.comments.loaded = true;
+    }
+
+    /// Sends the draft comment for the selected paste.
+    pub(crate) fn submit_comment(&mut self) {
+        let Some(paste_id) = self.comments.paste_id.clone() else {
+            return;
+        };
+        let body = self.comments.draft.trim().to_string();
+        if body.is_empty() {
+            return;
+        }
+        let author = match self.comments.author.trim() {
+            "" => default_comment_author(),
+            author => author.to_string(),
+        };
+        let cmd = CoreCmd::AddComment {
+            paste_id,
+            author,
+            body,
+        };
+        if self.backend.cmd_tx.send(cmd).is_err() {
+            self.set_status("Add comment failed: backend unavailable.");
+            return;
+        }
+        self.comments.draft.clear();
+    }
+
+    /// Renders the collapsible comments section inside the properties drawer.
+    pub(crate) fn render_comments_section(&mut self, ui: &mut egui::Ui) {
+        self.sync_comments_thread();
+        let title = if self.comments.loaded {
+            format!("Comments ({})", self.comments.items.len())
+        } else {
+            "Comments".to_string()
+        };
+        let mut delete = None;
+        let mut submit = false;
+        let timezone = self.display_timezone;
+        let comments = &mut self.comments;
+        egui::CollapsingHeader::new(RichText::new(title).small().color(COLOR_TEXT_MUTED))
+            .id_salt("drawer_comments")
+            .default_open(false)
+            .show(ui, |ui| {
+                if comments.loaded && comments.items.is_empty() {
+                    ui.label(
+                        RichText::new("No comments yet.")
+                            .small()
+                            .color(COLOR_TEXT_MUTED),
+                    );
+                }
+                for comment in &comments.items {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(comment.author.as_str()).small().strong());
+                        ui.label(
+                            RichText::new(timezone.format(comment.created_at, "%Y-%m-%d %H:%M"))
+                                .small()
+                                .color(COLOR_TEXT_MUTED),
+                        );
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("Delete").clicked() {
+                                delete = Some(comment.id.clone());
+                            }
+                        });
+                    });
+                    ui.label(comment.body.as_str());
+                    ui.add_space(4.0);
+                }
+                ui.separator();
+                ui.add(
+                    egui::TextEdit::singleline(&mut comments.author)
+                        .id_salt("drawer_comment_author")
+                        .hint_text(default_comment_author())
+                        .desired_width(f32::INFINITY),
+                );
+                ui.add(
+                    egui::TextEdit::multiline(&mut comments.draft)
+                        .id_salt("drawer_comment_draft")
+                        .hint_text("Add a comment")
+                        .desired_rows(2)
+                        .desired_width(f32::INFINITY),
+                );
+                if ui
+                    .add_enabled(
+                        !comments.draft.trim().is_empty(),
+                        egui::Button::new("Comment"),
+                    )
+                    .clicked()
+                {
+                    submit = true;
+                }
+            });
+        if submit {
+            self.submit_comment();
+        }
+        if let (Some(comment_id), Some(paste_id)) = (delete, self.comments.paste_id.clone()) {
+            let _ = self.backend.cmd_tx.send(CoreCmd::DeleteComment {
+                paste_id,
+                comment_id,
+            });
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Syntax check results for JSON, YAML, and TOML pastes: a gutter marker with
+//! the parse error at the offending line and an "invalid" drawer badge.
+//!
+//! Checks run on the highlight worker, so buffers that render as plain text
+//! (past the highlight threshold) are not checked.
+
+use super::super::highlight::HighlightLint;
+use super::super::*;
+use eframe::egui;
+use localpaste_core::lint::{LintError, LintReport};
+
+/// Width of the bar painted at the left edge of the erroring row.
+const LINT_MARKER_WIDTH: f32 = 3.0;
+/// Marker and badge color for parse errors.
+const COLOR_LINT_ERROR: egui::Color32 = egui::Color32::from_rgb(0xF8, 0x51, 0x49);
+
+/// Latest worker syntax check for the selected paste.
+#[derive(Debug, Default)]
+pub(crate) struct ContentLintState {
+    pub(crate) lint: Option<HighlightLint>,
+}
+
+/// Paints the parse-error marker and its hover message for one editor row.
+///
+/// # Arguments
+/// - `gutter_rect`: Line-number gutter area of the row.
+/// - `error`: Parse error on the row's line.
+pub(super) fn paint_lint_marker(ui: &egui::Ui, gutter_rect: egui::Rect, error: &LintError) {
+    let marker = egui::Rect::from_min_size(
+        gutter_rect.min,
+        egui::vec2(LINT_MARKER_WIDTH, gutter_rect.height()),
+    );
+    ui.painter().rect_filled(marker, 1.0, COLOR_LINT_ERROR);
+    if !ui.rect_contains_pointer(gutter_rect) {
+        return;
+    }
+    let id = egui::Id::new(("lint_error_popover", error.line));
+    egui::Tooltip::always_open(ui.ctx().clone(), ui.layer_id(), id, gutter_rect).show(|ui| {
+        ui.set_max_width(320.0);
+        ui.label(format!(
+            "{}:{}: {}",
+            error.line, error.column, error.message
+        ));
+    });
+}
+
+/// Renders the drawer badge for a paste that failed its syntax check.
+pub(super) fn render_lint_badge(ui: &mut egui::Ui, report: &LintReport) {
+    let Some(error) = report.error.as_ref() else {
+        return;
+    };
+    let label = localpaste_core::detection::canonical::lookup(report.language.as_str())
+        .map_or(report.language.as_str(), |spec| spec.label);
+    ui.label(
+        RichText::new(format!("Invalid {}", label))
+            .small()
+            .strong()
+            .color(COLOR_LINT_ERROR),
+    )
+    .on_hover_text(format!(
+        "Line {}, column {}: {}",
+        error.line, error.column, error.message
+    ));
+}
+
+impl LocalPasteApp {
+    /// Stores a worker syntax check when it belongs to the selected paste.
+    pub(crate) fn apply_highlight_lint(&mut self, lint: HighlightLint) {
+        if self.selected_id.as_deref() != Some(lint.paste_id.as_str()) {
+            return;
+        }
+        self.content_lint.lint = Some(lint);
+    }
+
+    /// Syntax check for the selected paste, when its language has one.
+    pub(crate) fn current_lint_report(&self) -> Option<&LintReport> {
+        self.content_lint
+            .lint
+            .as_ref()
+            .filter(|lint| self.selected_id.as_deref() == Some(lint.paste_id.as_str()))?
+            .report
+            .as_ref()
+    }
+
+    /// Parse error on the 0-based `line_idx`, if the selected paste has one there.
+    pub(crate) fn lint_error_on_line(&self, line_idx: usize) -> Option<&LintError> {
+        self.current_lint_report()?
+            .error
+            .as_ref()
+            .filter(|error| error.line == line_idx.saturating_add(1))
+    }
+}
@@ -0,0 +1,212 @@
+//! Crash-recovery drafts: periodic persistence and the recovery prompt.
+//!
+//! Unsaved paste edits and the scratchpad are written to the database drafts
+//! table every few seconds. Drafts left behind by a crash are offered for
+//! recovery on the next launch; normal saves discard them.
+
+use super::super::*;
+use crate::backend::CoreCmd;
+use chrono::Utc;
+use eframe::egui;
+use localpaste_core::models::draft::{Draft, SCRATCHPAD_DRAFT_KEY};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Minimum spacing between draft writes.
+const DRAFT_SAVE_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Draft persistence bookkeeping and drafts awaiting a recovery decision.
+#[derive(Debug, Default)]
+pub(crate) struct DraftState {
+    listed: bool,
+    pub(crate) last_persist_at: Option<Instant>,
+    /// Paste id and buffer revision of the last paste draft written.
+    paste_draft: Option<(String, u64)>,
+    /// Hash of the scratchpad text last written, `None` when no draft is stored.
+    scratch_hash: Option<u64>,
+    pub(crate) recoverable: Vec<Draft>,
+}
+
+fn text_hash(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl LocalPasteApp {
+    /// Requests leftover drafts once, then periodically persists unsaved content.
+    ///
+    /// Paste drafts are written while the editor is dirty and discarded once
+    /// the content save lands; the scratchpad draft tracks its text.
+    pub(crate) fn maybe_persist_drafts(&mut self) {
+        if !self.drafts.listed {
+            self.drafts.listed = true;
+            let _ = self.backend.cmd_tx.send(CoreCmd::ListDrafts);
+        }
+        if self
+            .drafts
+            .last_persist_at
+            .is_some_and(|at| at.elapsed() < DRAFT_SAVE_INTERVAL)
+        {
+            return;
+        }
+        self.drafts.last_persist_at = Some(Instant::now());
+
+        let dirty_paste = self
+            .selected_id
+            .clone()
+            .filter(|_| self.save_status != SaveStatus::Saved);
+        if let Some(id) = dirty_paste {
+            let revision = self.active_revision();
+            if self.drafts.paste_draft.as_ref() != Some(&(id.clone(), revision)) {
+                let draft = Draft {
+                    key: Draft::paste_key(id.as_str()),
+                    paste_id: Some(id.clone()),
+                    content: self.active_snapshot(),
+                    updated_at: Utc::now(),
+                };
+                let _ = self.backend.cmd_tx.send(CoreCmd::SaveDraft { draft });
+                self.drafts.paste_draft = Some((id, revision));
+            }
+        } else {
+            self.discard_pending_paste_draft();
+        }
+
+        let scratch_hash =
+            (!self.scratchpad.text.is_empty()).then(|| text_hash(self.scratchpad.text.as_str()));
+        if scratch_hash != self.drafts.scratch_hash {
+            let command = if scratch_hash.is_some() {
+                CoreCmd::SaveDraft {
+                    draft: Draft {
+                        key: SCRATCHPAD_DRAFT_KEY.to_string(),
+                        paste_id: None,
+                        content: self.scratchpad.text.clone(),
+                        updated_at: Utc::now(),
+                    },
+                }
+            } else {
+                CoreCmd::DiscardDraft {
+                    key: SCRATCHPAD_DRAFT_KEY.to_string(),
+                }
+            };
+            let _ = self.backend.cmd_tx.send(command);
+            self.drafts.scratch_hash = scratch_hash;
+        }
+    }
+
+    /// Drops the stored draft for the current paste, e.g. when edits are discarded.
+    pub(crate) fn discard_pending_paste_draft(&mut self) {
+        if let Some((id, _)) = self.drafts.paste_draft.take() {
+            let key = Draft::paste_key(id.as_str());
+            let _ = self.backend.cmd_tx.send(CoreCmd::DiscardDraft { key });
+        }
+    }
+
+    /// Queues drafts from a previous session for the recovery prompt.
+    ///
+    /// A scratchpad draft matching the restored scratchpad text is skipped.
+    pub(crate) fn offer_draft_recovery(&mut self, drafts: Vec<Draft>) {
+        self.drafts.recoverable = drafts
+            .into_iter()
+            .filter(|draft| {
+                draft.key != SCRATCHPAD_DRAFT_KEY || draft.content != self.scratchpad.text
+            })
+            .collect();
+    }
+
+    /// Restores a recovered draft and removes it from storage.
+    ///
+    /// Paste drafts become a new paste so the stored original is never
+    /// overwritten; the scratchpad draft is appended to the scratchpad.
+    pub(crate) fn restore_draft(&mut self, key: &str) {
+        let Some(idx) = self.drafts.recoverable.iter().position(|d| d.key == key) else {
+            return;
+        };
+        let draft = self.drafts.recoverable.remove(idx);
+        if draft.key == SCRATCHPAD_DRAFT_KEY {
+            if !self.scratchpad.text.is_empty() && !self.scratchpad.text.ends_with('\n') {
+                self.scratchpad.text.push('\n');
+            }
+            self.scratchpad.text.push_str(draft.content.as_str());
+            self.scratchpad.open = true;
+            self.set_status("Recovered scratchpad draft.");
+            return;
+        }
+        self.create_new_paste_with_content(draft.content);
+        let _ = self
+            .backend
+            .cmd_tx
+            .send(CoreCmd::DiscardDraft { key: draft.key });
+        self.set_status("Recovered draft as a new paste.");
+    }
+
+    /// Discards a recovered draft without restoring it.
+    pub(crate) fn discard_recovered_draft(&mut self, key: &str) {
+        self.drafts.recoverable.retain(|draft| draft.key != key);
+        let _ = self.backend.cmd_tx.send(CoreCmd::DiscardDraft {
+            key: key.to_string(),
+        });
+    }
+
+    /// Renders the draft recovery window while recovered drafts are pending.
+    pub(crate) fn render_draft_recovery(&mut self, ctx: &egui::Context) {
+        if self.drafts.recoverable.is_empty() {
+            return;
+        }
+        let mut open = true;
+        let mut restore: Option<String> = None;
+        let mut discard: Option<String> = None;
+        egui::Window::new("Recover Unsaved Drafts")
+            .open(&mut open)
+            .resizable(false)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                ui.label(
+                    egui::RichText::new("These edits were not saved before LocalPaste closed.")
+                        .small()
+                        .color(COLOR_TEXT_MUTED),
+                );
+                ui.add_space(4.0);
+                for draft in &self.drafts.recoverable {
+                    let title = match draft.paste_id.as_deref() {
+                        None => "Scratchpad".to_string(),
+                        Some(id) => self
+                            .all_pastes
+                            .iter()
+                            .find(|item| item.id == id)
+                            .map(|item| item.name.clone())
+                            .unwrap_or_else(|| id.to_string()),
+                    };
+                    ui.horizontal(|ui| {
+                        ui.label(title);
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{} chars, {}",
+                                draft.content.chars().count(),
+                                self.display_timezone
+                                    .format(draft.updated_at, "%Y-%m-%d %H:%M:%S")
+                            ))
+                            .small()
+                            .color(COLOR_TEXT_MUTED),
+                        );
+                        if ui.small_button("Restore").clicked() {
+                            restore = Some(draft.key.clone());
+                        }
+                        if ui.small_button("Discard").clicked() {
+                            discard = Some(draft.key.clone());
+                        }
+                    });
+                }
+            });
+        if let Some(key) = restore {
+            self.restore_draft(key.as_str());
+        }
+        if let Some(key) = discard {
+            self.discard_recovered_draft(key.as_str());
+        }
+        if !open {
+            // Closing keeps drafts stored so they are offered again next launch.
+            self.drafts.recoverable.clear();
+        }
+    }
+}
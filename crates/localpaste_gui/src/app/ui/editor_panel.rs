@@ -1,11 +1,14 @@
 //! Central editor panel rendering for virtual preview and virtual editor modes.
 
+use super::super::text_transforms::TextTransform;
 use super::super::*;
 use super::editor_panel_virtual::VirtualEditorRenderOptions;
 use super::properties_drawer::{
     apply_language_choice, auto_language_choice_key, render_language_choice_combo,
 };
+use super::writing_mode::{WRITING_COLUMN_WIDTH, WRITING_FONT_SCALE};
 use eframe::egui;
+use localpaste_core::models::paste::ExportFormat;
 
 impl LocalPasteApp {
     /// Renders the primary editor panel, including metadata toolbar and mode UI.
@@ -33,9 +36,11 @@ impl LocalPasteApp {
                 let mut copy_requested = false;
                 let mut copy_link_requested = false;
                 let mut duplicate_requested = false;
-                let mut export_requested = false;
+                let mut export_requested: Option<Option<ExportFormat>> = None;
+                let mut print_requested = false;
                 let mut open_properties = false;
                 let mut delete_requested = false;
+                let mut pending_transform: Option<TextTransform> = None;
                 ui.scope(|ui| {
                     apply_compact_meta_row_style(ui);
                     ui.horizontal_wrapped(|ui| {
@@ -129,9 +134,33 @@ impl LocalPasteApp {
                             duplicate_requested = true;
                             preserve_virtual_editor_focus |= editor_had_virtual_focus;
                         }
-                        if non_focusable_small_toolbar_button(ui, "Export").clicked() {
-                            export_requested = true;
-                            preserve_virtual_editor_focus |= editor_had_virtual_focus;
+                        ui.menu_button("Export", |ui| {
+                            let choices = [
+                                ("Plain text...", None),
+                                ("HTML (highlighted)...", Some(ExportFormat::Html)),
+                                ("PDF (highlighted)...", Some(ExportFormat::Pdf)),
+                            ];
+                            for (label, format) in choices {
+                                if ui.button(label).clicked() {
+                                    export_requested = Some(format);
+                                    ui.close();
+                                }
+                            }
+                            ui.separator();
+                            if ui.button("Print...").clicked() {
+                                print_requested = true;
+                                ui.close();
+                            }
+                        });
+                        if self.editor_mode == EditorMode::VirtualEditor {
+                            ui.menu_button("Transform", |ui| {
+                                for transform in TextTransform::ALL {
+                                    if ui.button(transform.label()).clicked() {
+                                        pending_transform = Some(transform);
+                                        ui.close();
+                                    }
+                                }
+                            });
                         }
                         if non_focusable_small_toolbar_button(ui, "Properties").clicked() {
                             open_properties = true;
@@ -165,8 +194,15 @@ impl LocalPasteApp {
                     self.create_new_paste_with_content(self.active_snapshot());
                     self.set_status("Duplicated paste into a new draft.");
                 }
-                if export_requested {
-                    self.export_selected_paste();
+                if let Some(format) = export_requested {
+                    self.export_selected_paste(format);
+                }
+                if print_requested {
+                    self.print_selected_paste();
+                }
+                if let Some(transform) = pending_transform {
+                    self.apply_text_transform(transform);
+                    preserve_virtual_editor_focus |= editor_had_virtual_focus;
                 }
                 if open_properties {
                     self.properties_drawer_open = true;
@@ -188,14 +224,32 @@ impl LocalPasteApp {
                     );
                 }
                 ui.add_space(6.0);
+                let writing_mode = self.writing_mode_active();
+                let mut writing_column = writing_mode.then(|| {
+                    let available = ui.available_rect_before_wrap();
+                    let inset = ((available.width() - WRITING_COLUMN_WIDTH) / 2.0).max(0.0);
+                    ui.new_child(
+                        egui::UiBuilder::new().max_rect(available.shrink2(egui::vec2(inset, 0.0))),
+                    )
+                });
+                let ui = match writing_column.as_mut() {
+                    Some(column) => {
+                        self.render_writing_header(column);
+                        column
+                    }
+                    None => ui,
+                };
                 let editor_height = ui.available_height();
                 let editor_style = TextStyle::Name(EDITOR_TEXT_STYLE.into());
-                let editor_font = ui
+                let mut editor_font = ui
                     .style()
                     .text_styles
                     .get(&editor_style)
                     .cloned()
                     .unwrap_or_else(|| TextStyle::Monospace.resolve(ui.style()));
+                if writing_mode {
+                    editor_font.size *= WRITING_FONT_SCALE;
+                }
                 let language_hint = syntect_language_hint(language.as_deref().unwrap_or("text"));
                 let theme = (!is_large).then(|| CodeTheme::from_memory(ui.ctx(), ui.style()));
                 let theme_key = theme
@@ -328,7 +382,11 @@ impl LocalPasteApp {
                             None
                         }
                     });
-                let row_height = ui.text_style_height(&editor_style);
+                let row_height = if writing_mode {
+                    ui.fonts_mut(|fonts| fonts.row_height(&editor_font))
+                } else {
+                    ui.text_style_height(&editor_style)
+                };
                 if preserve_virtual_editor_focus {
                     self.focus_editor_next = true;
                 }
@@ -1,6 +1,10 @@
 //! Virtual preview/editor rendering extracted from the main editor panel.
 
 use super::super::*;
+use super::annotations::paint_annotation_marker;
+use super::content_lint::paint_lint_marker;
+use super::line_links::paint_line_flash;
+use super::search_matches::paint_search_matches;
 use crate::app::text_coords::prefix_by_chars;
 use eframe::egui;
 use tracing::info;
@@ -457,6 +461,7 @@ impl LocalPasteApp {
             .x
             .max(1.0)
         });
+        self.sync_annotations();
         let line_count = self.virtual_editor_buffer.line_count();
         let line_number_char_width = ui.fonts_mut(|f| {
             f.layout_no_wrap(
@@ -833,6 +838,17 @@ impl LocalPasteApp {
                 let paint_started = perf_enabled.then(Instant::now);
                 for row in rows {
                     let galley = row.galley;
+                    if let Some(strength) = self.line_links.flash_strength(row.line_idx, now) {
+                        paint_line_flash(ui, row.rect, strength);
+                    }
+                    if let Some(matches) = self.current_search_matches() {
+                        paint_search_matches(
+                            ui.painter(),
+                            row.text_rect,
+                            galley.as_ref(),
+                            matches.ranges_in_row(row.segment_start, row.segment_chars),
+                        );
+                    }
                     if let Some(selection) =
                         self.virtual_selection_for_line(row.segment_start, row.segment_chars)
                     {
@@ -856,6 +872,25 @@ impl LocalPasteApp {
                             COLOR_TEXT_MUTED,
                         );
                     }
+                    if !self.annotations.items.is_empty() {
+                        let gutter_rect = egui::Rect::from_min_max(
+                            row.rect.min,
+                            egui::pos2(row.text_rect.min.x, row.rect.max.y),
+                        );
+                        paint_annotation_marker(
+                            ui,
+                            gutter_rect,
+                            &self.annotations.on_line(row.line_idx),
+                            self.display_timezone,
+                        );
+                    }
+                    if let Some(error) = self.lint_error_on_line(row.line_idx) {
+                        let gutter_rect = egui::Rect::from_min_max(
+                            row.rect.min,
+                            egui::pos2(row.text_rect.min.x, row.rect.max.y),
+                        );
+                        paint_lint_marker(ui, gutter_rect, error);
+                    }
                     ui.painter()
                         .galley(row.text_origin, galley.clone(), ui.visuals().text_color());
 
@@ -0,0 +1,224 @@
+//! Folder rules window: list, add, reorder, and delete the rules that file
+//! new pastes into folders.
+//!
+//! Rules are stored in the database and shared with the API, so the window
+//! always reloads them from the backend after a change.
+
+use super::super::*;
+use crate::backend::CoreCmd;
+use eframe::egui;
+use localpaste_core::models::folder_rule::{FolderRule, RuleField};
+
+/// Folder rules window state and the rule being drafted.
+#[derive(Debug)]
+pub(crate) struct FolderRulesState {
+    pub(crate) open: bool,
+    /// Whether `items` reflects a `FolderRulesLoaded` response.
+    pub(crate) loaded: bool,
+    pub(crate) items: Vec<FolderRule>,
+    pub(crate) field: RuleField,
+    pub(crate) pattern: String,
+    pub(crate) folder_id: Option<String>,
+}
+
+impl Default for FolderRulesState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            loaded: false,
+            items: Vec::new(),
+            field: RuleField::Name,
+            pattern: String::new(),
+            folder_id: None,
+        }
+    }
+}
+
+impl LocalPasteApp {
+    /// Opens the folder rules window and requests rules and folder names.
+    pub(crate) fn open_folder_rules(&mut self) {
+        self.folder_rules.open = true;
+        let _ = self.backend.cmd_tx.send(CoreCmd::ListFolderRules);
+        self.request_folder_stats();
+    }
+
+    /// Replaces the listed rules with a backend response.
+    pub(crate) fn apply_folder_rules_loaded(&mut self, rules: Vec<FolderRule>) {
+        self.folder_rules.items = rules;
+        self.folder_rules.loaded = true;
+    }
+
+    /// Sends the drafted rule once it has a pattern and a folder.
+    pub(crate) fn submit_folder_rule(&mut self) {
+        let pattern = self.folder_rules.pattern.trim().to_string();
+        let Some(folder_id) = self.folder_rules.folder_id.clone() else {
+            return;
+        };
+        if pattern.is_empty() {
+            return;
+        }
+        let cmd = CoreCmd::AddFolderRule {
+            field: self.folder_rules.field,
+            pattern,
+            folder_id,
+        };
+        if self.backend.cmd_tx.send(cmd).is_err() {
+            self.set_status("Add folder rule failed: backend unavailable.");
+            return;
+        }
+        self.folder_rules.pattern.clear();
+    }
+
+    /// Moves the rule at `index` one step earlier (`up`) or later.
+    pub(crate) fn move_folder_rule(&mut self, index: usize, up: bool) {
+        let target = if up {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1).filter(|next| *next < self.folder_rules.items.len())
+        };
+        let Some(target) = target else {
+            return;
+        };
+        self.folder_rules.items.swap(index, target);
+        let ids = self
+            .folder_rules
+            .items
+            .iter()
+            .map(|rule| rule.id.clone())
+            .collect();
+        let _ = self
+            .backend
+            .cmd_tx
+            .send(CoreCmd::ReorderFolderRules { ids });
+    }
+
+    fn folder_label(&self, folder_id: &str) -> String {
+        self.sidebar_group_state
+            .folders
+            .get(folder_id)
+            .map(|folder| folder.name.clone())
+            .unwrap_or_else(|| format!("(missing folder {})", folder_id))
+    }
+
+    /// Renders the folder rules window while it is open.
+    pub(crate) fn render_folder_rules(&mut self, ctx: &egui::Context) {
+        if !self.folder_rules.open {
+            return;
+        }
+        let mut open = true;
+        let mut delete: Option<String> = None;
+        let mut move_rule: Option<(usize, bool)> = None;
+        let mut submit = false;
+        let rows: Vec<(String, String)> = self
+            .folder_rules
+            .items
+            .iter()
+            .map(|rule| {
+                (
+                    format!("{} matches \"{}\"", rule.field.label(), rule.pattern),
+                    self.folder_label(rule.folder_id.as_str()),
+                )
+            })
+            .collect();
+        let mut folders: Vec<(String, String)> = self
+            .sidebar_group_state
+            .folders
+            .values()
+            .map(|folder| (folder.id.clone(), folder.name.clone()))
+            .collect();
+        folders.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        let selected_folder = self
+            .folder_rules
+            .folder_id
+            .as_deref()
+            .map(|id| self.folder_label(id))
+            .unwrap_or_else(|| "Choose folder".to_string());
+        let state = &mut self.folder_rules;
+        egui::Window::new("Folder Rules")
+            .open(&mut open)
+            .resizable(false)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(
+                        "New pastes without a folder go to the first matching rule's folder. \
+                         Patterns are case-insensitive; * and ? are wildcards.",
+                    )
+                    .small()
+                    .color(COLOR_TEXT_MUTED),
+                );
+                ui.add_space(4.0);
+                if state.loaded && rows.is_empty() {
+                    ui.label(
+                        RichText::new("No rules yet.")
+                            .small()
+                            .color(COLOR_TEXT_MUTED),
+                    );
+                }
+                let last = rows.len().saturating_sub(1);
+                for (index, (rule, folder)) in rows.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}. {} -> {}", index + 1, rule, folder));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("Delete").clicked() {
+                                delete = Some(state.items[index].id.clone());
+                            }
+                            if ui
+                                .add_enabled(index < last, egui::Button::new("Down").small())
+                                .clicked()
+                            {
+                                move_rule = Some((index, false));
+                            }
+                            if ui
+                                .add_enabled(index > 0, egui::Button::new("Up").small())
+                                .clicked()
+                            {
+                                move_rule = Some((index, true));
+                            }
+                        });
+                    });
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("folder_rule_field")
+                        .selected_text(state.field.label())
+                        .width(90.0)
+                        .show_ui(ui, |ui| {
+                            for field in RuleField::ALL {
+                                ui.selectable_value(&mut state.field, field, field.label());
+                            }
+                        });
+                    ui.add(
+                        egui::TextEdit::singleline(&mut state.pattern)
+                            .id_salt("folder_rule_pattern")
+                            .hint_text("*.log")
+                            .desired_width(150.0),
+                    );
+                    egui::ComboBox::from_id_salt("folder_rule_folder")
+                        .selected_text(selected_folder)
+                        .width(140.0)
+                        .show_ui(ui, |ui| {
+                            for (id, name) in &folders {
+                                ui.selectable_value(&mut state.folder_id, Some(id.clone()), name);
+                            }
+                        });
+                    let ready = !state.pattern.trim().is_empty() && state.folder_id.is_some();
+                    if ui.add_enabled(ready, egui::Button::new("Add")).clicked() {
+                        submit = true;
+                    }
+                });
+            });
+        if submit {
+            self.submit_folder_rule();
+        }
+        if let Some((index, up)) = move_rule {
+            self.move_folder_rule(index, up);
+        }
+        if let Some(id) = delete {
+            let _ = self.backend.cmd_tx.send(CoreCmd::DeleteFolderRule { id });
+        }
+        if !open {
+            self.folder_rules.open = false;
+        }
+    }
+}
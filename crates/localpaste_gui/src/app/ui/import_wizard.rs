@@ -0,0 +1,203 @@
+//! Import wizard: pick a snippet-tool export, preview the parsed snippets, then commit.
+//!
+//! Parsing runs locally on the UI thread when a source is chosen; only the
+//! snippets left checked in the preview are sent to the backend worker.
+
+use super::super::*;
+use crate::backend::CoreCmd;
+use eframe::egui;
+use localpaste_core::import::{parse_import, ImportFormat, ImportedSnippet};
+use std::path::{Path, PathBuf};
+
+/// Import wizard window state.
+#[derive(Debug)]
+pub(crate) struct ImportWizardState {
+    pub(crate) open: bool,
+    pub(crate) format: ImportFormat,
+    pub(crate) source: Option<PathBuf>,
+    /// Parsed snippets paired with whether they are checked for import.
+    pub(crate) preview: Vec<(ImportedSnippet, bool)>,
+    pub(crate) error: Option<String>,
+}
+
+impl Default for ImportWizardState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            format: ImportFormat::MassCode,
+            source: None,
+            preview: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+impl LocalPasteApp {
+    /// Opens the import wizard with an empty preview.
+    pub(crate) fn open_import_wizard(&mut self) {
+        self.import_wizard = ImportWizardState {
+            open: true,
+            format: self.import_wizard.format,
+            ..Default::default()
+        };
+    }
+
+    /// Parses `path` with the selected format and replaces the preview.
+    ///
+    /// Every parsed snippet starts checked; parse failures are shown inline.
+    pub(crate) fn load_import_preview(&mut self, path: &Path) {
+        let wizard = &mut self.import_wizard;
+        wizard.source = Some(path.to_path_buf());
+        match parse_import(wizard.format, path) {
+            Ok(snippets) => {
+                wizard.error = snippets
+                    .is_empty()
+                    .then(|| "No snippets found in this source.".to_string());
+                wizard.preview = snippets.into_iter().map(|s| (s, true)).collect();
+            }
+            Err(err) => {
+                wizard.preview.clear();
+                wizard.error = Some(err.to_string());
+            }
+        }
+    }
+
+    /// Sends the checked preview snippets to the backend and closes the wizard.
+    pub(crate) fn commit_import(&mut self) {
+        let snippets: Vec<ImportedSnippet> = std::mem::take(&mut self.import_wizard.preview)
+            .into_iter()
+            .filter_map(|(snippet, checked)| checked.then_some(snippet))
+            .collect();
+        if snippets.is_empty() {
+            self.set_status("Nothing selected to import.");
+            return;
+        }
+        let count = snippets.len();
+        if self
+            .backend
+            .cmd_tx
+            .send(CoreCmd::ImportSnippets { snippets })
+            .is_err()
+        {
+            self.set_status("Import failed: backend unavailable.");
+            return;
+        }
+        self.import_wizard.open = false;
+        self.set_status(format!("Importing {} snippet(s)...", count));
+    }
+
+    /// Renders the import wizard while it is open.
+    pub(crate) fn render_import_wizard(&mut self, ctx: &egui::Context) {
+        if !self.import_wizard.open {
+            return;
+        }
+        let mut open = true;
+        let mut pick: Option<bool> = None;
+        let mut commit = false;
+        let wizard = &mut self.import_wizard;
+        egui::Window::new("Import Snippets")
+            .open(&mut open)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Format");
+                    let before = wizard.format;
+                    egui::ComboBox::from_id_salt("import_wizard_format")
+                        .selected_text(wizard.format.label())
+                        .show_ui(ui, |ui| {
+                            for format in ImportFormat::ALL {
+                                ui.selectable_value(&mut wizard.format, format, format.label());
+                            }
+                        });
+                    if wizard.format != before {
+                        wizard.source = None;
+                        wizard.preview.clear();
+                        wizard.error = None;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("Choose file...").clicked() {
+                        pick = Some(false);
+                    }
+                    if wizard.format.accepts_directory() && ui.button("Choose folder...").clicked()
+                    {
+                        pick = Some(true);
+                    }
+                    if let Some(source) = wizard.source.as_ref() {
+                        ui.label(
+                            egui::RichText::new(source.display().to_string())
+                                .small()
+                                .color(COLOR_TEXT_MUTED),
+                        );
+                    }
+                });
+                if let Some(error) = wizard.error.as_deref() {
+                    ui.colored_label(ui.visuals().warn_fg_color, error);
+                }
+                if wizard.preview.is_empty() {
+                    return;
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.small_button("All").clicked() {
+                        wizard.preview.iter_mut().for_each(|(_, on)| *on = true);
+                    }
+                    if ui.small_button("None").clicked() {
+                        wizard.preview.iter_mut().for_each(|(_, on)| *on = false);
+                    }
+                });
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        for (snippet, checked) in wizard.preview.iter_mut() {
+                            ui.horizontal(|ui| {
+                                ui.checkbox(checked, snippet.name.as_str());
+                                let mut detail = format!(
+                                    "{} - {} bytes",
+                                    snippet.language.as_deref().unwrap_or("auto"),
+                                    snippet.content.len()
+                                );
+                                if !snippet.tags.is_empty() {
+                                    detail.push_str(" - ");
+                                    detail.push_str(snippet.tags.join(", ").as_str());
+                                }
+                                ui.label(
+                                    egui::RichText::new(detail).small().color(COLOR_TEXT_MUTED),
+                                );
+                            })
+                            .response
+                            .on_hover_text(snippet.content.chars().take(400).collect::<String>());
+                        }
+                    });
+                ui.separator();
+                let checked = wizard.preview.iter().filter(|(_, on)| *on).count();
+                if ui
+                    .add_enabled(
+                        checked > 0,
+                        egui::Button::new(format!("Import {}", checked)),
+                    )
+                    .clicked()
+                {
+                    commit = true;
+                }
+            });
+        if !open {
+            self.import_wizard.open = false;
+            return;
+        }
+        if let Some(directory) = pick {
+            let dialog = rfd::FileDialog::new();
+            let picked = if directory {
+                dialog.pick_folder()
+            } else {
+                dialog.pick_file()
+            };
+            if let Some(path) = picked {
+                self.load_import_preview(path.as_path());
+            }
+        }
+        if commit {
+            self.commit_import();
+        }
+    }
+}
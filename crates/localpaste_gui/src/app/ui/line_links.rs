@@ -0,0 +1,120 @@
+//! Review helpers: copy with line numbers, `#L<line>` permalinks, and the
+//! brief highlight shown on the line a permalink points at.
+
+use super::super::util::{format_with_line_numbers, paste_line_link_for_copy};
+use super::super::*;
+use eframe::egui;
+
+/// How long a permalink target line stays highlighted.
+const LINE_FLASH_DURATION: Duration = Duration::from_millis(1500);
+
+/// Pending permalink jump and the currently flashing line.
+#[derive(Debug, Default)]
+pub(crate) struct LineLinkState {
+    /// Paste id and 0-based line to reveal once that paste finishes loading.
+    pub(crate) pending_jump: Option<(String, usize)>,
+    /// 0-based line highlighted after a jump, with the time the flash started.
+    pub(crate) flash: Option<(usize, Instant)>,
+}
+
+impl LineLinkState {
+    /// Highlight opacity for `line_idx`, fading from 1 to 0 over the flash.
+    ///
+    /// # Returns
+    /// `None` when the line is not flashing or the flash has ended.
+    pub(crate) fn flash_strength(&self, line_idx: usize, now: Instant) -> Option<f32> {
+        let (line, started) = self.flash?;
+        if line != line_idx {
+            return None;
+        }
+        let elapsed = now.saturating_duration_since(started);
+        if elapsed >= LINE_FLASH_DURATION {
+            return None;
+        }
+        Some(1.0 - elapsed.as_secs_f32() / LINE_FLASH_DURATION.as_secs_f32())
+    }
+}
+
+/// Paints the fading permalink highlight over one editor row.
+pub(super) fn paint_line_flash(ui: &egui::Ui, row_rect: egui::Rect, strength: f32) {
+    let alpha = (strength * 90.0).round() as u8;
+    ui.painter().rect_filled(
+        row_rect,
+        0.0,
+        egui::Color32::from_rgba_unmultiplied(255, 200, 60, alpha),
+    );
+    ui.ctx().request_repaint();
+}
+
+impl LocalPasteApp {
+    /// Copies the selected lines, or the whole paste, prefixed with line numbers.
+    pub(crate) fn copy_with_line_numbers(&mut self) {
+        let Some((start, end)) = self.annotation_target_lines() else {
+            return;
+        };
+        let whole = self.virtual_editor_state.selection_range().is_none();
+        let text = if whole {
+            format_with_line_numbers(self.active_snapshot().as_str(), 1)
+        } else {
+            let buffer = &self.virtual_editor_buffer;
+            let from = buffer.line_col_to_char(start - 1, 0);
+            let to = buffer.line_col_to_char(end - 1, buffer.line_len_chars(end - 1));
+            format_with_line_numbers(buffer.slice_chars(from..to).as_str(), start)
+        };
+        self.clipboard_outgoing = Some(text);
+        self.set_status("Copied with line numbers.");
+    }
+
+    /// Copies a `localpaste://paste/<id>#L<line>` link to the caret line.
+    pub(crate) fn copy_link_to_line(&mut self) {
+        let Some(id) = self.selected_id.clone() else {
+            return;
+        };
+        let Some((line, _)) = self.annotation_target_lines() else {
+            return;
+        };
+        self.clipboard_outgoing = Some(paste_line_link_for_copy(id.as_str(), line));
+        self.set_status(format!("Copied link to line {}.", line));
+    }
+
+    /// Opens the paste behind a permalink and reveals its line once loaded.
+    ///
+    /// # Arguments
+    /// - `id`: Paste id from the link.
+    /// - `line`: 1-based target line, when the link has a `#L<line>` fragment.
+    ///
+    /// # Returns
+    /// `true` when the paste was selected or its selection was queued.
+    pub(crate) fn open_paste_link(&mut self, id: String, line: Option<usize>) -> bool {
+        self.line_links.pending_jump = None;
+        let already_loaded = self.selected_id.as_deref() == Some(id.as_str())
+            && self
+                .selected_paste
+                .as_ref()
+                .is_some_and(|paste| paste.id == id);
+        if !self.select_paste(id.clone()) {
+            return false;
+        }
+        if let Some(line) = line {
+            self.line_links.pending_jump = Some((id, line.saturating_sub(1)));
+            if already_loaded {
+                self.apply_pending_line_jump();
+            }
+        }
+        true
+    }
+
+    /// Reveals and flashes the pending permalink line when its paste is loaded.
+    pub(crate) fn apply_pending_line_jump(&mut self) {
+        let Some((id, line)) = self.line_links.pending_jump.clone() else {
+            return;
+        };
+        if self.selected_id.as_deref() != Some(id.as_str()) || self.selected_paste.is_none() {
+            return;
+        }
+        self.line_links.pending_jump = None;
+        let line = line.min(self.virtual_editor_buffer.line_count().saturating_sub(1));
+        self.jump_to_outline_line(line);
+        self.line_links.flash = Some((line, Instant::now()));
+    }
+}
@@ -0,0 +1,224 @@
+//! Merge dialog: pick pastes from the list, drag them into order, then join
+//! them into one new paste, optionally deleting the originals.
+//!
+//! The backend commits the new paste and any deletions in one transaction.
+
+use super::super::*;
+use crate::backend::CoreCmd;
+use eframe::egui;
+use localpaste_core::merge::MergeSeparator;
+
+/// Merge dialog window state.
+#[derive(Debug, Default)]
+pub(crate) struct MergeDialogState {
+    pub(crate) open: bool,
+    /// Picked pastes as `(id, name)`, in merge order.
+    pub(crate) order: Vec<(String, String)>,
+    /// Name for the merged paste; blank derives one from the first paste.
+    pub(crate) name: String,
+    pub(crate) separator: MergeSeparator,
+    pub(crate) delete_originals: bool,
+    /// Case-insensitive name filter for the candidate list.
+    pub(crate) filter: String,
+}
+
+impl MergeDialogState {
+    /// Adds `id` to the end of the merge order, or removes it when present.
+    pub(crate) fn toggle(&mut self, id: &str, name: &str) {
+        if let Some(idx) = self.order.iter().position(|(picked, _)| picked == id) {
+            self.order.remove(idx);
+        } else {
+            self.order.push((id.to_string(), name.to_string()));
+        }
+    }
+
+    /// Moves the picked paste at `from` so it lands at `to`.
+    pub(crate) fn move_item(&mut self, from: usize, to: usize) {
+        if from >= self.order.len() || from == to {
+            return;
+        }
+        let item = self.order.remove(from);
+        self.order.insert(to.min(self.order.len()), item);
+    }
+}
+
+impl LocalPasteApp {
+    /// Opens the merge dialog with the selected paste (if any) already picked.
+    pub(crate) fn open_merge_dialog(&mut self) {
+        let order = self
+            .selected_paste
+            .as_ref()
+            .map(|paste| vec![(paste.id.clone(), paste.name.clone())])
+            .unwrap_or_default();
+        self.merge_dialog = MergeDialogState {
+            open: true,
+            order,
+            ..Default::default()
+        };
+    }
+
+    /// Sends the picked pastes to the backend and closes the dialog.
+    ///
+    /// Refuses to delete a selected paste that has unsaved edits.
+    pub(crate) fn commit_merge(&mut self) {
+        let dialog = &self.merge_dialog;
+        if dialog.order.len() < 2 {
+            self.set_status("Pick at least two pastes to merge.");
+            return;
+        }
+        let selected_dirty = self.save_status == SaveStatus::Dirty || self.metadata_dirty;
+        if dialog.delete_originals
+            && selected_dirty
+            && dialog
+                .order
+                .iter()
+                .any(|(id, _)| self.selected_id.as_deref() == Some(id.as_str()))
+        {
+            self.set_status("Save the selected paste before merging it away.");
+            return;
+        }
+        let name = match dialog.name.trim() {
+            "" => format!("{} (merged)", dialog.order[0].1),
+            name => name.to_string(),
+        };
+        let count = dialog.order.len();
+        let cmd = CoreCmd::MergePastes {
+            ids: dialog.order.iter().map(|(id, _)| id.clone()).collect(),
+            name,
+            separator: dialog.separator,
+            delete_originals: dialog.delete_originals,
+        };
+        if self.backend.cmd_tx.send(cmd).is_err() {
+            self.set_status("Merge failed: backend unavailable.");
+            return;
+        }
+        self.merge_dialog.open = false;
+        self.set_status(format!("Merging {} pastes...", count));
+    }
+
+    /// Renders the merge dialog while it is open.
+    pub(crate) fn render_merge_dialog(&mut self, ctx: &egui::Context) {
+        if !self.merge_dialog.open {
+            return;
+        }
+        let filter = self.merge_dialog.filter.trim().to_lowercase();
+        let candidates: Vec<(String, String)> = self
+            .pastes
+            .iter()
+            .filter(|paste| filter.is_empty() || paste.name.to_lowercase().contains(&filter))
+            .map(|paste| (paste.id.clone(), paste.name.clone()))
+            .collect();
+        let mut open = true;
+        let mut commit = false;
+        let dialog = &mut self.merge_dialog;
+        egui::Window::new("Merge Pastes")
+            .open(&mut open)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Filter");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut dialog.filter)
+                            .id_salt("merge_dialog_filter")
+                            .desired_width(240.0),
+                    );
+                });
+                egui::ScrollArea::vertical()
+                    .id_salt("merge_dialog_candidates")
+                    .max_height(180.0)
+                    .show(ui, |ui| {
+                        for (id, name) in &candidates {
+                            let mut picked = dialog.order.iter().any(|(item, _)| item == id);
+                            if ui.checkbox(&mut picked, name.as_str()).changed() {
+                                dialog.toggle(id, name);
+                            }
+                        }
+                    });
+                ui.separator();
+                ui.label(
+                    egui::RichText::new("Merge order (drag :: to reorder)")
+                        .small()
+                        .color(COLOR_TEXT_MUTED),
+                );
+                let mut reorder = None;
+                let last = dialog.order.len().saturating_sub(1);
+                for (idx, (_, name)) in dialog.order.iter().enumerate() {
+                    let row = ui
+                        .horizontal(|ui| {
+                            ui.dnd_drag_source(
+                                egui::Id::new(("merge_dialog_row", idx)),
+                                idx,
+                                |ui| {
+                                    ui.label("::");
+                                },
+                            );
+                            ui.label(format!("{}. {}", idx + 1, name));
+                            if ui
+                                .add_enabled(idx > 0, egui::Button::new("Up").small())
+                                .clicked()
+                            {
+                                reorder = Some((idx, idx - 1));
+                            }
+                            if ui
+                                .add_enabled(idx < last, egui::Button::new("Down").small())
+                                .clicked()
+                            {
+                                reorder = Some((idx, idx + 1));
+                            }
+                        })
+                        .response;
+                    if let Some(from) = row.dnd_release_payload::<usize>() {
+                        reorder = Some((*from, idx));
+                    }
+                }
+                if let Some((from, to)) = reorder {
+                    dialog.move_item(from, to);
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Name");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut dialog.name)
+                            .id_salt("merge_dialog_name")
+                            .hint_text("<first paste> (merged)")
+                            .desired_width(260.0),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Separator");
+                    egui::ComboBox::from_id_salt("merge_dialog_separator")
+                        .selected_text(dialog.separator.label())
+                        .show_ui(ui, |ui| {
+                            for separator in MergeSeparator::ALL {
+                                ui.selectable_value(
+                                    &mut dialog.separator,
+                                    separator,
+                                    separator.label(),
+                                );
+                            }
+                        });
+                });
+                ui.checkbox(
+                    &mut dialog.delete_originals,
+                    "Delete originals after merging",
+                );
+                ui.separator();
+                if ui
+                    .add_enabled(
+                        dialog.order.len() >= 2,
+                        egui::Button::new(format!("Merge {} pastes", dialog.order.len())),
+                    )
+                    .clicked()
+                {
+                    commit = true;
+                }
+            });
+        if !open {
+            self.merge_dialog.open = false;
+            return;
+        }
+        if commit {
+            self.commit_merge();
+        }
+    }
+}
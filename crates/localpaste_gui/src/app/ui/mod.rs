@@ -1,22 +1,56 @@
 //! UI panel modules extracted from the main app update loop.
 
+/// Line annotation gutter markers and the drawer annotation list.
+pub(super) mod annotations;
+/// Backup listing, restore scheduling, and backup schedule settings.
+pub(super) mod backups;
+/// Unsaved-changes prompt for intercepted window close.
+pub(super) mod close_prompt;
 /// Command palette modal and quick-action behavior.
 pub(super) mod command_palette;
+/// Collapsible paste comment thread in the properties drawer.
+pub(super) mod comments;
+/// JSON/YAML/TOML parse error gutter marker and drawer badge.
+pub(super) mod content_lint;
 /// Detached diff modal for side-by-side compare operations.
 pub(super) mod diff_modal;
+/// Crash-recovery draft persistence and recovery prompt.
+pub(super) mod drafts;
 /// Standard text editor panel and header controls.
 pub(super) mod editor_panel;
 /// Virtual preview/editor panel rendering.
 pub(super) mod editor_panel_virtual;
+/// Folder auto-organization rules editor window.
+pub(super) mod folder_rules;
 /// Detached version-history modal for historical snapshots/reset.
 pub(super) mod history_modal;
+/// Snippet import wizard with preview before commit.
+pub(super) mod import_wizard;
+/// Copy with line numbers, line permalinks, and the permalink line flash.
+pub(super) mod line_links;
+/// Merge dialog for joining several pastes into one.
+pub(super) mod merge_dialog;
 /// Right-side properties drawer.
 pub(super) mod properties_drawer;
+/// Never-autosaved scratch buffer window.
+pub(super) mod scratchpad;
+/// Jump to and highlight query matches when opening a search result.
+pub(super) mod search_matches;
 /// Keyboard shortcut help window.
 pub(super) mod shortcut_help;
 /// Top bar and left sidebar surfaces.
 pub(super) mod sidebar;
+/// Split dialog for pastes holding several concatenated files.
+pub(super) mod split_dialog;
 /// Bottom status bar content.
 pub(super) mod status_bar;
+/// Database and backup size alerts with one-click remediation.
+pub(super) mod storage_alerts;
+/// Symbol outline panel and fuzzy symbol jump.
+pub(super) mod symbol_outline;
 /// Transient toast notifications.
 pub(super) mod toasts;
+/// Opt-in release check and the available-update notice.
+pub(super) mod update_notice;
+/// Distraction-free writing mode and word goals.
+pub(super) mod writing_mode;
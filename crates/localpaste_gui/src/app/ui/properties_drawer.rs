@@ -1,8 +1,13 @@
 //! Right-side metadata drawer for infrequent property edits.
 
+use super::super::markdown_outline::MarkdownHeading;
+use super::super::sidebar_groups::folder_stats_lines;
 use super::super::*;
+use super::content_lint::render_lint_badge;
 use eframe::egui;
+use localpaste_core::models::folder::Folder;
 use localpaste_core::semantic::DerivedMeta;
+use localpaste_core::timestamps::DisplayTimezone;
 
 const AUTO_LANGUAGE: &str = "__auto__";
 
@@ -99,6 +104,92 @@ fn render_derived_meta_section(ui: &mut egui::Ui, derived: &DerivedMeta) {
     ui.label(format!("Terms: {}", format_derived_terms(&derived.terms)));
 }
 
+/// Renders the markdown heading outline.
+///
+/// # Returns
+/// Line of the clicked heading, if any.
+fn render_markdown_outline_section(
+    ui: &mut egui::Ui,
+    headings: &[MarkdownHeading],
+) -> Option<usize> {
+    ui.add_space(10.0);
+    ui.separator();
+    ui.add_space(8.0);
+    let mut clicked = None;
+    egui::CollapsingHeader::new(RichText::new("Outline").small().color(COLOR_TEXT_MUTED))
+        .id_salt("properties_markdown_outline")
+        .default_open(true)
+        .show(ui, |ui| {
+            if headings.is_empty() {
+                ui.label(RichText::new("No headings").color(COLOR_TEXT_MUTED));
+                return;
+            }
+            egui::ScrollArea::vertical()
+                .id_salt("properties_markdown_outline_scroll")
+                .max_height(240.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for heading in headings {
+                        ui.horizontal(|ui| {
+                            ui.add_space(heading.level.saturating_sub(1) as f32 * 12.0);
+                            if ui.selectable_label(false, heading.text.as_str()).clicked() {
+                                clicked = Some(heading.line);
+                            }
+                        });
+                    }
+                });
+        });
+    clicked
+}
+
+fn render_folder_stats_section(ui: &mut egui::Ui, folder: &Folder, timezone: DisplayTimezone) {
+    ui.add_space(10.0);
+    ui.separator();
+    ui.add_space(8.0);
+    ui.label(
+        RichText::new(format!("Folder: {}", folder.name))
+            .small()
+            .color(COLOR_TEXT_MUTED),
+    );
+    for line in folder_stats_lines(folder, timezone) {
+        ui.label(line);
+    }
+}
+
+/// Copyable ways to reach a paste outside the GUI, as `(label, text)` pairs.
+fn share_snippets(server_addr: std::net::SocketAddr, id: &str) -> [(&'static str, String); 3] {
+    [
+        (
+            "API URL",
+            super::super::util::api_paste_link_for_copy(server_addr, id),
+        ),
+        (
+            "CLI command",
+            super::super::util::cli_get_command_for_copy(id),
+        ),
+        (
+            "Deep link",
+            super::super::util::paste_deep_link_for_copy(id),
+        ),
+    ]
+}
+
+/// Renders one labelled share snippet with a copy button.
+///
+/// # Returns
+/// The snippet text when its copy button was clicked.
+fn render_share_snippet(ui: &mut egui::Ui, label: &str, text: &str) -> Option<String> {
+    ui.label(RichText::new(label).small().color(COLOR_TEXT_MUTED));
+    let mut copied = None;
+    ui.horizontal(|ui| {
+        if ui.small_button("Copy").clicked() {
+            copied = Some(text.to_string());
+        }
+        ui.add(egui::Label::new(RichText::new(text).small().monospace()).truncate());
+    });
+    copied
+}
+
 fn language_typeahead_target(letter: char) -> Option<&'static str> {
     let mut normalized = letter.to_lowercase();
     let letter = normalized.next()?;
@@ -109,8 +200,7 @@ fn language_typeahead_target(letter: char) -> Option<&'static str> {
         return Some(AUTO_LANGUAGE);
     }
     let prefix = letter.to_string();
-    localpaste_core::detection::canonical::MANUAL_LANGUAGE_OPTIONS
-        .iter()
+    localpaste_core::detection::canonical::manual_language_options()
         .find(|option| {
             option
                 .label
@@ -176,13 +266,21 @@ pub(super) fn render_language_choice_combo(
             auto_language_choice_key().to_string(),
             "Auto",
         );
-        for option in localpaste_core::detection::canonical::MANUAL_LANGUAGE_OPTIONS {
+        for option in localpaste_core::detection::canonical::manual_language_options() {
             ui.selectable_value(language_choice, option.value.to_string(), option.label);
         }
     });
 }
 
 impl LocalPasteApp {
+    /// Opens or closes the properties drawer, fetching folder stats on open.
+    pub(crate) fn toggle_properties_drawer(&mut self) {
+        self.properties_drawer_open = !self.properties_drawer_open;
+        if self.properties_drawer_open {
+            self.request_folder_stats();
+        }
+    }
+
     /// Renders the side drawer used for less-frequent metadata edits.
     pub(crate) fn render_properties_drawer(&mut self, ctx: &egui::Context) {
         if !self.properties_drawer_open || self.selected_id.is_none() {
@@ -257,6 +355,9 @@ impl LocalPasteApp {
                             current_manual_value.as_str(),
                         );
                     }
+                    if let Some(report) = self.current_lint_report() {
+                        render_lint_badge(ui, report);
+                    }
 
                     ui.add_space(6.0);
                     ui.label(RichText::new("Tags").small().color(COLOR_TEXT_MUTED));
@@ -287,12 +388,39 @@ impl LocalPasteApp {
                     ui.add_space(6.0);
                     ui.label(RichText::new(reason).small().color(COLOR_TEXT_MUTED));
                 }
+                if self.markdown_outline_eligible() {
+                    let headings = self.markdown_headings();
+                    if let Some(line) = render_markdown_outline_section(ui, &headings) {
+                        self.jump_to_outline_line(line);
+                    }
+                }
                 if let Some(summary) = self.selected_paste_summary() {
                     render_derived_meta_section(ui, &summary.derived);
+                    let folder = summary
+                        .folder_id
+                        .as_deref()
+                        .and_then(|id| self.sidebar_group_state.folders.get(id));
+                    if let Some(folder) = folder {
+                        render_folder_stats_section(ui, folder, self.display_timezone);
+                    }
+                }
+                ui.add_space(6.0);
+                self.render_comments_section(ui);
+                self.render_annotations_section(ui);
+                if let Some(id) = self.selected_id.clone() {
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(8.0);
+                    for (label, text) in share_snippets(self.server_addr, id.as_str()) {
+                        if let Some(copied) = render_share_snippet(ui, label, text.as_str()) {
+                            self.clipboard_outgoing = Some(copied);
+                            self.set_status(format!("{} copied.", label));
+                        }
+                    }
                 }
                 ui.add_space(10.0);
                 if ui.button("Export").clicked() {
-                    self.export_selected_paste();
+                    self.export_selected_paste(None);
                 }
             });
 
@@ -306,7 +434,8 @@ impl LocalPasteApp {
 mod tests {
     use super::{
         apply_language_choice, apply_language_selector_typeahead, auto_language_status_label,
-        format_derived_terms, language_typeahead_target, typed_letter_from_events, AUTO_LANGUAGE,
+        format_derived_terms, language_typeahead_target, share_snippets, typed_letter_from_events,
+        AUTO_LANGUAGE,
     };
     use eframe::egui;
 
@@ -419,6 +548,23 @@ mod tests {
         assert_eq!(typed_letter_from_events(&events), Some('m'));
     }
 
+    #[test]
+    fn share_snippets_cover_api_cli_and_deep_link() {
+        let addr = std::net::SocketAddr::from(([127, 0, 0, 1], 38411));
+        let labels: Vec<(&str, String)> = share_snippets(addr, "abc").into_iter().collect();
+        assert_eq!(
+            labels,
+            vec![
+                (
+                    "API URL",
+                    "http://127.0.0.1:38411/api/paste/abc".to_string()
+                ),
+                ("CLI command", "lpaste get abc".to_string()),
+                ("Deep link", "localpaste://paste/abc".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn format_derived_terms_handles_empty_and_joined_lists() {
         assert_eq!(format_derived_terms(&[]), "None");
@@ -0,0 +1,100 @@
+//! In-memory scratch buffer that never autosaves to the database.
+//!
+//! Scratch text lives on the app and is mirrored into egui's persisted memory,
+//! so it survives restarts through the window settings file without ever
+//! creating a paste until the user promotes it.
+
+use super::super::*;
+use eframe::egui;
+
+const SCRATCHPAD_MEMORY_ID: &str = "localpaste_scratchpad_text";
+
+/// Open state and contents of the scratch buffer.
+#[derive(Debug, Default)]
+pub(crate) struct ScratchpadState {
+    pub(crate) open: bool,
+    pub(crate) text: String,
+    restored: bool,
+}
+
+impl LocalPasteApp {
+    /// Toggles the scratchpad window.
+    pub(crate) fn toggle_scratchpad(&mut self) {
+        self.scratchpad.open = !self.scratchpad.open;
+    }
+
+    /// Creates a paste from the scratch buffer and clears it.
+    ///
+    /// # Returns
+    /// `true` when a create command was dispatched.
+    pub(crate) fn promote_scratchpad(&mut self) -> bool {
+        if self.scratchpad.text.trim().is_empty() {
+            self.set_status("Scratchpad is empty.");
+            return false;
+        }
+        if self.mutation_shortcut_block_reason().is_some() {
+            self.set_mutation_shortcut_blocked_status();
+            return false;
+        }
+        let content = std::mem::take(&mut self.scratchpad.text);
+        self.create_new_paste_with_content(content);
+        self.scratchpad.open = false;
+        self.set_status("Promoted scratchpad to a new paste.");
+        true
+    }
+
+    /// Renders the scratchpad window and mirrors edits into persisted memory.
+    pub(crate) fn render_scratchpad(&mut self, ctx: &egui::Context) {
+        let memory_id = egui::Id::new(SCRATCHPAD_MEMORY_ID);
+        if !self.scratchpad.restored {
+            self.scratchpad.restored = true;
+            if self.scratchpad.text.is_empty() {
+                if let Some(text) = ctx.data_mut(|data| data.get_persisted::<String>(memory_id)) {
+                    self.scratchpad.text = text;
+                }
+            }
+        }
+        if !self.scratchpad.open {
+            return;
+        }
+        let mut open = self.scratchpad.open;
+        let mut promote = false;
+        let mut changed = false;
+        egui::Window::new("Scratchpad")
+            .open(&mut open)
+            .default_width(520.0)
+            .default_height(320.0)
+            .show(ctx, |ui| {
+                ui.label(
+                    egui::RichText::new("Not saved to the database until promoted.")
+                        .small()
+                        .color(COLOR_TEXT_MUTED),
+                );
+                ui.horizontal(|ui| {
+                    promote = ui.button("Promote to paste").clicked();
+                    if ui.button("Clear").clicked() {
+                        self.scratchpad.text.clear();
+                        changed = true;
+                    }
+                });
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::multiline(&mut self.scratchpad.text)
+                                .code_editor()
+                                .desired_width(f32::INFINITY)
+                                .desired_rows(14),
+                        )
+                        .changed();
+                });
+            });
+        self.scratchpad.open &= open;
+        if promote {
+            changed |= self.promote_scratchpad();
+        }
+        if changed {
+            let text = self.scratchpad.text.clone();
+            ctx.data_mut(|data| data.insert_persisted(memory_id, text));
+        }
+    }
+}
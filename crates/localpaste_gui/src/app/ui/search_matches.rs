@@ -0,0 +1,132 @@
+//! Search-hit reveal: opening a paste from a search result jumps to the first
+//! content match and highlights every match until the buffer is edited.
+
+use super::super::*;
+use eframe::egui;
+use localpaste_core::text::find_match_ranges;
+use localpaste_core::SEARCH_MATCHES_MAX;
+
+/// Fill painted behind highlighted search matches.
+const SEARCH_MATCH_FILL: egui::Color32 = egui::Color32::from_rgba_premultiplied(90, 75, 20, 90);
+
+/// Query waiting for its paste to load, and the matches shown in the editor.
+#[derive(Debug, Default)]
+pub(crate) struct SearchMatchState {
+    /// Paste id and query to reveal once that paste finishes loading.
+    pub(crate) pending: Option<(String, String)>,
+    /// Paste and buffer revision the highlighted ranges were computed against.
+    pub(crate) source: Option<(String, u64)>,
+    /// Highlighted matches as char ranges into the editor buffer.
+    pub(crate) ranges: Vec<Range<usize>>,
+}
+
+impl SearchMatchState {
+    /// Matches clipped to one editor row, as row-local char ranges.
+    ///
+    /// # Arguments
+    /// - `segment_start`: Buffer char index where the row starts.
+    /// - `segment_chars`: Number of chars on the row.
+    pub(crate) fn ranges_in_row(
+        &self,
+        segment_start: usize,
+        segment_chars: usize,
+    ) -> Vec<Range<usize>> {
+        let segment_end = segment_start.saturating_add(segment_chars);
+        let first = self
+            .ranges
+            .partition_point(|range| range.end <= segment_start);
+        self.ranges[first..]
+            .iter()
+            .take_while(|range| range.start < segment_end)
+            .map(|range| {
+                range.start.saturating_sub(segment_start)
+                    ..range.end.min(segment_end).saturating_sub(segment_start)
+            })
+            .filter(|range| range.start < range.end)
+            .collect()
+    }
+}
+
+/// Paints search match highlights for one editor row.
+pub(super) fn paint_search_matches(
+    painter: &egui::Painter,
+    row_rect: egui::Rect,
+    galley: &egui::Galley,
+    ranges: Vec<Range<usize>>,
+) {
+    for range in ranges {
+        paint_virtual_selection_overlay(painter, row_rect, galley, range, SEARCH_MATCH_FILL);
+    }
+}
+
+impl LocalPasteApp {
+    /// Opens a paste picked from search results and reveals `query` in it.
+    ///
+    /// # Returns
+    /// `true` when the paste was selected or its selection was queued.
+    pub(crate) fn open_search_hit(&mut self, id: String, query: &str) -> bool {
+        self.search_matches.pending = None;
+        let query = query.trim().to_string();
+        let already_loaded = self.selected_id.as_deref() == Some(id.as_str())
+            && self
+                .selected_paste
+                .as_ref()
+                .is_some_and(|paste| paste.id == id);
+        if !self.select_paste(id.clone()) {
+            return false;
+        }
+        if !query.is_empty() {
+            self.search_matches.pending = Some((id, query));
+            if already_loaded {
+                self.apply_pending_search_reveal();
+            }
+        }
+        true
+    }
+
+    /// Highlights matches of the pending query and selects the first one once
+    /// its paste is loaded. A paste matched only by name or tags stays at the top.
+    pub(crate) fn apply_pending_search_reveal(&mut self) {
+        let Some((id, query)) = self.search_matches.pending.clone() else {
+            return;
+        };
+        if self.selected_id.as_deref() != Some(id.as_str()) || self.selected_paste.is_none() {
+            return;
+        }
+        self.search_matches.pending = None;
+        let content = self.active_snapshot();
+        let rope = self.virtual_editor_buffer.rope();
+        let ranges: Vec<Range<usize>> =
+            find_match_ranges(content.as_str(), query.as_str(), SEARCH_MATCHES_MAX)
+                .into_iter()
+                .map(|range| rope.byte_to_char(range.start)..rope.byte_to_char(range.end))
+                .collect();
+        self.search_matches.source = Some((id, self.virtual_editor_buffer.revision()));
+        self.search_matches.ranges = ranges;
+        let Some(first) = self.search_matches.ranges.first().cloned() else {
+            return;
+        };
+        let len = self.virtual_editor_buffer.len_chars();
+        self.virtual_editor_state.set_cursor(first.start, len);
+        self.virtual_editor_state.move_cursor(first.end, len, true);
+        let row = self.virtual_cursor_row_index(first.start);
+        self.virtual_pending_scroll_offset_y =
+            Some(row.saturating_sub(2) as f32 * self.virtual_line_height);
+        self.focus_editor_next = true;
+        let count = self.search_matches.ranges.len();
+        let suffix = if count == SEARCH_MATCHES_MAX { "+" } else { "" };
+        let noun = if count == 1 { "match" } else { "matches" };
+        self.set_status(format!("{}{} {} for \"{}\".", count, suffix, noun, query));
+    }
+
+    /// Search match highlights for the buffer as currently displayed.
+    ///
+    /// # Returns
+    /// `None` once the paste changed or its buffer was edited.
+    pub(crate) fn current_search_matches(&self) -> Option<&SearchMatchState> {
+        let (id, revision) = self.search_matches.source.as_ref()?;
+        let current = self.selected_id.as_deref() == Some(id.as_str())
+            && *revision == self.virtual_editor_buffer.revision();
+        (current && !self.search_matches.ranges.is_empty()).then_some(&self.search_matches)
+    }
+}
@@ -24,6 +24,11 @@ impl LocalPasteApp {
                             .color(COLOR_TEXT_MUTED),
                     );
                     shortcut_row(ui, "Ctrl/Cmd+N", "Create new paste");
+                    shortcut_row(
+                        ui,
+                        "Ctrl/Cmd+Shift+N",
+                        "Toggle scratchpad (never autosaved)",
+                    );
                     shortcut_row(ui, "Ctrl/Cmd+S", "Save content and metadata");
                     shortcut_row(
                         ui,
@@ -34,6 +39,7 @@ impl LocalPasteApp {
                     shortcut_row(ui, "Ctrl/Cmd+Shift+P", "Toggle command palette");
                     shortcut_row(ui, "Ctrl/Cmd+K", "Toggle command palette (legacy)");
                     shortcut_row(ui, "Ctrl/Cmd+I", "Toggle properties drawer");
+                    shortcut_row(ui, "Ctrl/Cmd+Shift+O", "Go to symbol in paste");
                     shortcut_row(ui, "F1", "Toggle this help");
 
                     ui.add_space(6.0);
@@ -81,6 +87,11 @@ impl LocalPasteApp {
                         "Palette query: history",
                         "Open history modal for selected paste",
                     );
+                    shortcut_row(
+                        ui,
+                        "Alt+Up / Alt+Down",
+                        "Expand / shrink selection by syntax unit",
+                    );
                     shortcut_row(ui, "Enter", "Open selected command palette result");
                     shortcut_row(ui, "Esc", "Close command palette/window");
                     shortcut_row(ui, "Ctrl/Cmd+C", "Copy selected text");
@@ -1,12 +1,28 @@
 //! Top bar and sidebar rendering for paste navigation and quick actions.
 
+use super::super::presence::{presence_marker, presence_summary};
+use super::super::sidebar_groups::{
+    folder_group_label, folder_stats_lines, sidebar_group_folder_id, sidebar_rows, SidebarGroup,
+    SidebarGroupBy, SidebarRow,
+};
 use super::super::*;
+use crate::backend::CoreCmd;
 use eframe::egui::{self, RichText};
+use localpaste_core::models::paste::PastePreview;
+use localpaste_core::timestamps::{relative_time, DisplayTimezone};
 
 const APP_VERSION_LABEL: &str = concat!("- v", env!("CARGO_PKG_VERSION"));
 const SIDEBAR_LANGUAGE_COLUMN_WIDTH: f32 = 84.0;
+const SIDEBAR_TIME_COLUMN_WIDTH: f32 = 76.0;
+/// Rows narrower than this drop the relative-time column to keep titles legible.
+const SIDEBAR_TIME_COLUMN_MIN_ROW_WIDTH: f32 = 260.0;
+const SIDEBAR_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M";
 
-fn sidebar_hover_text(paste: &PasteSummary) -> String {
+fn sidebar_hover_text(
+    paste: &PasteSummary,
+    preview: Option<&PastePreview>,
+    timezone: DisplayTimezone,
+) -> String {
     let mut lines = vec![paste.name.clone()];
     let derived = &paste.derived;
     if derived.handle.is_some() || !derived.terms.is_empty() {
@@ -18,27 +34,76 @@ fn sidebar_hover_text(paste: &PasteSummary) -> String {
             lines.push(format!("Terms: {}", derived.terms.join(", ")));
         }
     }
+    lines.push(format!(
+        "Updated: {}",
+        timezone.format(paste.updated_at, SIDEBAR_TIMESTAMP_FORMAT)
+    ));
+    if let Some(preview) = preview {
+        lines.push(format!(
+            "Created: {}",
+            timezone.format(preview.created_at, SIDEBAR_TIMESTAMP_FORMAT)
+        ));
+    }
+    lines.push(format!("Size: {} bytes", paste.content_len));
+    if !paste.tags.is_empty() {
+        lines.push(format!("Tags: {}", paste.tags.join(", ")));
+    }
     lines.join("\n")
 }
 
+/// Splits a sidebar row into title, relative-time, and language rects.
+///
+/// The time rect is `None` when the row is too narrow to spare the column.
 fn sidebar_row_text_rects(
     row_rect: egui::Rect,
     padding_x: f32,
     spacing_x: f32,
-) -> (egui::Rect, egui::Rect) {
+) -> (egui::Rect, Option<egui::Rect>, egui::Rect) {
     let content_rect = row_rect.shrink2(egui::vec2(padding_x, 0.0));
     let lang_left = (content_rect.right() - SIDEBAR_LANGUAGE_COLUMN_WIDTH).max(content_rect.left());
-    let title_right = (lang_left - spacing_x).max(content_rect.left());
+    let time_rect = (row_rect.width() >= SIDEBAR_TIME_COLUMN_MIN_ROW_WIDTH).then(|| {
+        let time_right = lang_left - spacing_x;
+        egui::Rect::from_min_max(
+            egui::pos2(time_right - SIDEBAR_TIME_COLUMN_WIDTH, content_rect.min.y),
+            egui::pos2(time_right, content_rect.max.y),
+        )
+    });
+    let title_right =
+        (time_rect.map_or(lang_left, |rect| rect.left()) - spacing_x).max(content_rect.left());
     let title_rect = egui::Rect::from_min_max(
         content_rect.min,
         egui::pos2(title_right, content_rect.max.y),
     );
     let lang_rect =
         egui::Rect::from_min_max(egui::pos2(lang_left, content_rect.min.y), content_rect.max);
-    (title_rect, lang_rect)
+    (title_rect, time_rect, lang_rect)
 }
 
 impl LocalPasteApp {
+    /// Switches absolute timestamps between local time and UTC and persists it.
+    pub(crate) fn toggle_display_timezone(&mut self) {
+        let timezone = match self.display_timezone {
+            DisplayTimezone::Local => DisplayTimezone::Utc,
+            DisplayTimezone::Utc => DisplayTimezone::Local,
+        };
+        let cmd = CoreCmd::SaveDisplayTimezone {
+            db_path: self.db_path.clone(),
+            timezone,
+        };
+        if self.backend.cmd_tx.send(cmd).is_err() {
+            self.set_status("Timezone change failed: backend unavailable.");
+        }
+    }
+
+    /// Applies a display timezone the backend has saved.
+    pub(crate) fn apply_display_timezone_saved(&mut self, timezone: DisplayTimezone) {
+        self.display_timezone = timezone;
+        self.set_status(match timezone {
+            DisplayTimezone::Local => "Timestamps shown in local time.",
+            DisplayTimezone::Utc => "Timestamps shown in UTC.",
+        });
+    }
+
     /// Renders the top title/status bar.
     pub(crate) fn render_top_bar(&mut self, ctx: &egui::Context) {
         egui::TopBottomPanel::top("top")
@@ -129,78 +194,248 @@ impl LocalPasteApp {
                 ui.add_space(10.0);
                 self.render_collection_filters(ui);
                 self.render_language_filters(ui);
+                self.render_group_by_toggle(ui);
 
                 ui.separator();
                 ui.add_space(4.0);
                 let mut pending_select: Option<String> = None;
+                let mut pending_toggle: Option<String> = None;
+                let mut pending_folder_export: Option<String> = None;
                 let selection_blocked = self.selection_transition_block_reason().is_some();
                 let row_height = ui.spacing().interact_size.y;
+                let groups = self.sidebar_groups();
+                let rows = if self.sidebar_group_state.group_by == SidebarGroupBy::None {
+                    (0..self.pastes.len()).map(SidebarRow::Paste).collect()
+                } else {
+                    sidebar_rows(&groups, &self.sidebar_group_state.collapsed)
+                };
                 egui::ScrollArea::vertical()
                     .auto_shrink([false; 2])
-                    .show_rows(ui, row_height, self.pastes.len(), |ui, range| {
-                        for idx in range {
-                            if let Some(paste) = self.pastes.get(idx) {
-                                let selected =
-                                    self.selected_id.as_deref() == Some(paste.id.as_str());
-                                let lang_label = display_language_label(
-                                    paste.language.as_deref(),
-                                    false,
-                                    paste.content_len >= HIGHLIGHT_PLAIN_THRESHOLD,
-                                );
-                                let row_width = ui.available_width().max(1.0);
-                                let (row_rect, row_response) = ui.allocate_exact_size(
-                                    egui::vec2(row_width, row_height),
-                                    egui::Sense::click(),
-                                );
-                                let row_visuals =
-                                    ui.style().interact_selectable(&row_response, selected);
-                                ui.painter().rect(
-                                    row_rect.expand(row_visuals.expansion),
-                                    row_visuals.corner_radius,
-                                    row_visuals.bg_fill,
-                                    row_visuals.bg_stroke,
-                                    egui::StrokeKind::Middle,
-                                );
-
-                                let (title_rect, lang_rect) = sidebar_row_text_rects(
-                                    row_rect,
-                                    ui.spacing().button_padding.x,
-                                    ui.spacing().item_spacing.x,
-                                );
-                                ui.painter().with_clip_rect(title_rect).text(
-                                    egui::pos2(title_rect.left(), title_rect.center().y),
-                                    egui::Align2::LEFT_CENTER,
-                                    paste.name.as_str(),
-                                    egui::TextStyle::Button.resolve(ui.style()),
-                                    row_visuals.text_color(),
-                                );
-                                ui.painter().with_clip_rect(lang_rect).text(
-                                    egui::pos2(lang_rect.right(), lang_rect.center().y),
-                                    egui::Align2::RIGHT_CENTER,
-                                    lang_label.as_str(),
-                                    egui::TextStyle::Small.resolve(ui.style()),
-                                    COLOR_TEXT_MUTED,
-                                );
-
-                                if row_response
-                                    .on_hover_text(sidebar_hover_text(paste))
-                                    .clicked()
-                                {
-                                    if selection_blocked {
-                                        self.set_selection_transition_blocked_status();
-                                    } else {
-                                        pending_select = Some(paste.id.clone());
+                    .show_rows(ui, row_height, rows.len(), |ui, range| {
+                        for row in &rows[range] {
+                            match *row {
+                                SidebarRow::Header(group_idx) => {
+                                    let group = &groups[group_idx];
+                                    let collapsed =
+                                        self.sidebar_group_state.collapsed.contains(&group.key);
+                                    if self.render_sidebar_group_header(
+                                        ui,
+                                        group,
+                                        collapsed,
+                                        row_height,
+                                        &mut pending_folder_export,
+                                    ) {
+                                        pending_toggle = Some(group.key.clone());
+                                    }
+                                }
+                                SidebarRow::Paste(idx) => {
+                                    if let Some(id) = self.render_sidebar_paste_row(
+                                        ui,
+                                        idx,
+                                        row_height,
+                                        selection_blocked,
+                                    ) {
+                                        pending_select = Some(id);
                                     }
                                 }
                             }
                         }
                     });
+                if let Some(key) = pending_toggle {
+                    self.toggle_sidebar_group(key.as_str());
+                }
+                if let Some(folder_id) = pending_folder_export {
+                    self.export_folder(folder_id);
+                }
                 if let Some(id) = pending_select {
-                    self.select_paste(id);
+                    let query = self.search_query.clone();
+                    self.open_search_hit(id, query.as_str());
                 }
             });
     }
 
+    fn render_sidebar_group_header(
+        &self,
+        ui: &mut egui::Ui,
+        group: &SidebarGroup,
+        collapsed: bool,
+        row_height: f32,
+        pending_folder_export: &mut Option<String>,
+    ) -> bool {
+        let row_width = ui.available_width().max(1.0);
+        let (row_rect, row_response) = ui.allocate_exact_size(
+            egui::vec2(row_width, row_height),
+            non_focusable_click_sense(),
+        );
+        let marker = if collapsed { "\u{25B6}" } else { "\u{25BC}" };
+        let folder = sidebar_group_folder_id(group.key.as_str())
+            .and_then(|id| self.sidebar_group_state.folders.get(id));
+        let label = folder_group_label(group, folder);
+        let row_response = match folder {
+            Some(folder) => row_response
+                .on_hover_text(folder_stats_lines(folder, self.display_timezone).join("\n")),
+            None => row_response,
+        };
+        let content_rect = row_rect.shrink2(egui::vec2(ui.spacing().button_padding.x, 0.0));
+        ui.painter().with_clip_rect(content_rect).text(
+            egui::pos2(content_rect.left(), content_rect.center().y),
+            egui::Align2::LEFT_CENTER,
+            format!("{} {}", marker, label),
+            egui::TextStyle::Small.resolve(ui.style()),
+            if row_response.hovered() {
+                COLOR_TEXT_PRIMARY
+            } else {
+                COLOR_TEXT_SECONDARY
+            },
+        );
+        if let Some(folder_id) = sidebar_group_folder_id(group.key.as_str()) {
+            row_response.context_menu(|ui| {
+                if ui.button("Export folder...").clicked() {
+                    *pending_folder_export = Some(folder_id.to_string());
+                    ui.close();
+                }
+            });
+        }
+        row_response.clicked()
+    }
+
+    fn render_sidebar_paste_row(
+        &mut self,
+        ui: &mut egui::Ui,
+        idx: usize,
+        row_height: f32,
+        selection_blocked: bool,
+    ) -> Option<String> {
+        let paste = self.pastes.get(idx)?.clone();
+        let selected = self.selected_id.as_deref() == Some(paste.id.as_str());
+        let lang_label = display_language_label(
+            paste.language.as_deref(),
+            false,
+            paste.content_len >= HIGHLIGHT_PLAIN_THRESHOLD,
+        );
+        let row_width = ui.available_width().max(1.0);
+        let (row_rect, row_response) =
+            ui.allocate_exact_size(egui::vec2(row_width, row_height), egui::Sense::click());
+        let row_visuals = ui.style().interact_selectable(&row_response, selected);
+        ui.painter().rect(
+            row_rect.expand(row_visuals.expansion),
+            row_visuals.corner_radius,
+            row_visuals.bg_fill,
+            row_visuals.bg_stroke,
+            egui::StrokeKind::Middle,
+        );
+
+        let (mut title_rect, time_rect, lang_rect) = sidebar_row_text_rects(
+            row_rect,
+            ui.spacing().button_padding.x,
+            ui.spacing().item_spacing.x,
+        );
+        let presence = self.paste_presence(paste.id.as_str()).to_vec();
+        if let Some(marker) = presence_marker(&presence) {
+            let marker_rect = ui.painter().with_clip_rect(title_rect).text(
+                egui::pos2(title_rect.left(), title_rect.center().y),
+                egui::Align2::LEFT_CENTER,
+                marker,
+                egui::TextStyle::Small.resolve(ui.style()),
+                COLOR_ACCENT_TEXT,
+            );
+            title_rect.min.x =
+                (marker_rect.right() + ui.spacing().item_spacing.x / 2.0).min(title_rect.max.x);
+        }
+        ui.painter().with_clip_rect(title_rect).text(
+            egui::pos2(title_rect.left(), title_rect.center().y),
+            egui::Align2::LEFT_CENTER,
+            paste.name.as_str(),
+            egui::TextStyle::Button.resolve(ui.style()),
+            row_visuals.text_color(),
+        );
+        if let Some(time_rect) = time_rect {
+            ui.painter().with_clip_rect(time_rect).text(
+                egui::pos2(time_rect.right(), time_rect.center().y),
+                egui::Align2::RIGHT_CENTER,
+                relative_time(paste.updated_at, chrono::Utc::now()),
+                egui::TextStyle::Small.resolve(ui.style()),
+                COLOR_TEXT_MUTED,
+            );
+            // Keep "N minutes ago" current while the window is otherwise idle.
+            ui.ctx()
+                .request_repaint_after(std::time::Duration::from_secs(30));
+        }
+        ui.painter().with_clip_rect(lang_rect).text(
+            egui::pos2(lang_rect.right(), lang_rect.center().y),
+            egui::Align2::RIGHT_CENTER,
+            lang_label.as_str(),
+            egui::TextStyle::Small.resolve(ui.style()),
+            COLOR_TEXT_MUTED,
+        );
+
+        if row_response.hovered() {
+            self.request_sidebar_preview(&paste);
+        }
+        let preview = self.sidebar_previews.fresh(&paste);
+        let preview_pending = self.sidebar_previews.is_pending(paste.id.as_str());
+        let timezone = self.display_timezone;
+        let clicked = row_response
+            .on_hover_ui(|ui| {
+                ui.label(sidebar_hover_text(&paste, preview, timezone));
+                if !presence.is_empty() {
+                    ui.label(
+                        RichText::new(format!("Open in: {}", presence_summary(&presence)))
+                            .color(COLOR_ACCENT_TEXT),
+                    );
+                }
+                ui.separator();
+                match preview {
+                    Some(preview) if !preview.snippet.is_empty() => {
+                        let mut snippet = preview.snippet.clone();
+                        if preview.truncated {
+                            snippet.push_str("\n...");
+                        }
+                        ui.label(RichText::new(snippet).monospace().small());
+                    }
+                    Some(_) => {
+                        ui.label(RichText::new("(empty)").small().color(COLOR_TEXT_MUTED));
+                    }
+                    None => {
+                        if preview_pending {
+                            ui.ctx()
+                                .request_repaint_after(std::time::Duration::from_millis(50));
+                        }
+                        ui.label(
+                            RichText::new("Loading preview...")
+                                .small()
+                                .color(COLOR_TEXT_MUTED),
+                        );
+                    }
+                }
+            })
+            .clicked();
+        if !clicked {
+            return None;
+        }
+        if selection_blocked {
+            self.set_selection_transition_blocked_status();
+            None
+        } else {
+            Some(paste.id)
+        }
+    }
+
+    fn render_group_by_toggle(&mut self, ui: &mut egui::Ui) {
+        ui.add_space(8.0);
+        ui.label(RichText::new("Group by").small().color(COLOR_TEXT_MUTED));
+        let mut group_by = self.sidebar_group_state.group_by;
+        egui::ComboBox::from_id_salt("sidebar_group_by")
+            .selected_text(group_by.label())
+            .width(180.0)
+            .show_ui(ui, |ui| {
+                for option in SidebarGroupBy::ALL {
+                    ui.selectable_value(&mut group_by, option, option.label());
+                }
+            });
+        self.set_sidebar_group_by(group_by);
+    }
+
     fn render_collection_filters(&mut self, ui: &mut egui::Ui) {
         ui.label(
             RichText::new("Smart filters")
@@ -334,6 +569,7 @@ impl LocalPasteApp {
 mod tests {
     use super::{sidebar_hover_text, sidebar_row_text_rects};
     use eframe::egui;
+    use localpaste_core::timestamps::DisplayTimezone;
 
     #[test]
     fn sidebar_row_text_layout_matrix() {
@@ -348,10 +584,15 @@ mod tests {
                 8.0,
                 Some(72.0),
             ),
+            (
+                egui::Rect::from_min_size(egui::pos2(0.0, 0.0), egui::vec2(200.0, 28.0)),
+                8.0,
+                Some(192.0),
+            ),
         ];
 
         for (row_rect, expected_title_left, expected_lang_right) in cases {
-            let (title_rect, lang_rect) = sidebar_row_text_rects(row_rect, 8.0, 6.0);
+            let (title_rect, time_rect, lang_rect) = sidebar_row_text_rects(row_rect, 8.0, 6.0);
 
             assert!((title_rect.left() - expected_title_left).abs() < f32::EPSILON);
             assert!(title_rect.width() >= 0.0);
@@ -361,6 +602,14 @@ mod tests {
             if let Some(expected_lang_right) = expected_lang_right {
                 assert!((lang_rect.right() - expected_lang_right).abs() < f32::EPSILON);
             }
+            match time_rect {
+                Some(time_rect) => {
+                    assert!(title_rect.right() <= time_rect.left());
+                    assert!(time_rect.right() <= lang_rect.left());
+                    assert!(time_rect.width() > 0.0);
+                }
+                None => assert!(row_rect.width() < 260.0),
+            }
         }
     }
 
@@ -378,12 +627,15 @@ mod tests {
                 kind: localpaste_core::semantic::PasteKind::Code,
                 handle: Some("fn handle_request".to_string()),
                 terms: vec!["fsdp2".to_string(), "cublaslt".to_string()],
+                indent: None,
             },
         };
-        let tooltip = sidebar_hover_text(&summary);
+        let tooltip = sidebar_hover_text(&summary, None, DisplayTimezone::Utc);
         assert!(tooltip.contains("untamed-tundra"));
         assert!(tooltip.contains("Kind: Code"));
         assert!(tooltip.contains("Handle: fn handle_request"));
         assert!(tooltip.contains("Terms: fsdp2, cublaslt"));
+        assert!(tooltip.contains("Updated: "));
+        assert!(tooltip.contains(" UTC"));
     }
 }
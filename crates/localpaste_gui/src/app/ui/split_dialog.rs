@@ -0,0 +1,185 @@
+//! Split dialog: preview the files detected in a concatenated paste, then
+//! create one paste per checked file in the chosen folder.
+//!
+//! Detection runs on the UI thread when the dialog opens; the source paste is
+//! never modified.
+
+use super::super::*;
+use crate::backend::CoreCmd;
+use eframe::egui;
+use localpaste_core::split::{split_concatenated_files, SplitFile};
+
+/// Split dialog window state.
+#[derive(Debug, Default)]
+pub(crate) struct SplitDialogState {
+    pub(crate) open: bool,
+    /// Name of the paste the files were detected in.
+    pub(crate) source_name: String,
+    /// Detected files paired with whether they are checked for creation.
+    pub(crate) files: Vec<(SplitFile, bool)>,
+    /// Destination folder; `None` creates unfiled pastes.
+    pub(crate) folder_id: Option<String>,
+}
+
+impl LocalPasteApp {
+    /// Detects concatenated files in the selected paste and opens the dialog.
+    ///
+    /// Reports a status message instead when fewer than two files are found.
+    pub(crate) fn open_split_dialog(&mut self) {
+        let Some(paste) = self.selected_paste.as_ref() else {
+            self.set_status("Select a paste to split.");
+            return;
+        };
+        let source_name = paste.name.clone();
+        let folder_id = paste.folder_id.clone();
+        let files = split_concatenated_files(self.active_snapshot().as_str());
+        if files.is_empty() {
+            self.set_status("No concatenated files detected in this paste.");
+            return;
+        }
+        self.split_dialog = SplitDialogState {
+            open: true,
+            source_name,
+            files: files.into_iter().map(|file| (file, true)).collect(),
+            folder_id,
+        };
+        self.request_folder_stats();
+    }
+
+    /// Sends the checked files to the backend and closes the dialog.
+    ///
+    /// Names are trimmed; files whose name was cleared are skipped.
+    pub(crate) fn commit_split(&mut self) {
+        let files: Vec<SplitFile> = std::mem::take(&mut self.split_dialog.files)
+            .into_iter()
+            .filter_map(|(mut file, checked)| {
+                file.name = file.name.trim().to_string();
+                (checked && !file.name.is_empty()).then_some(file)
+            })
+            .collect();
+        if files.is_empty() {
+            self.set_status("Nothing selected to split.");
+            return;
+        }
+        let count = files.len();
+        let folder_id = self.split_dialog.folder_id.clone();
+        if self
+            .backend
+            .cmd_tx
+            .send(CoreCmd::SplitPaste { files, folder_id })
+            .is_err()
+        {
+            self.set_status("Split failed: backend unavailable.");
+            return;
+        }
+        self.split_dialog.open = false;
+        self.set_status(format!("Splitting into {} paste(s)...", count));
+    }
+
+    /// Renders the split dialog while it is open.
+    pub(crate) fn render_split_dialog(&mut self, ctx: &egui::Context) {
+        if !self.split_dialog.open {
+            return;
+        }
+        let mut folders: Vec<(String, String)> = self
+            .sidebar_group_state
+            .folders
+            .values()
+            .map(|folder| (folder.id.clone(), folder.name.clone()))
+            .collect();
+        folders.sort_by(|a, b| a.1.to_lowercase().cmp(&b.1.to_lowercase()));
+        let mut open = true;
+        let mut commit = false;
+        let dialog = &mut self.split_dialog;
+        egui::Window::new("Split into Multiple Pastes")
+            .open(&mut open)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} files detected in \"{}\".",
+                    dialog.files.len(),
+                    dialog.source_name
+                ));
+                ui.horizontal(|ui| {
+                    ui.label("Folder");
+                    let selected = dialog
+                        .folder_id
+                        .as_deref()
+                        .map(|id| {
+                            folders
+                                .iter()
+                                .find(|(folder_id, _)| folder_id == id)
+                                .map_or(id, |(_, name)| name.as_str())
+                        })
+                        .unwrap_or("Unfiled");
+                    egui::ComboBox::from_id_salt("split_dialog_folder")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut dialog.folder_id, None, "Unfiled");
+                            for (id, name) in &folders {
+                                ui.selectable_value(
+                                    &mut dialog.folder_id,
+                                    Some(id.clone()),
+                                    name.as_str(),
+                                );
+                            }
+                        });
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.small_button("All").clicked() {
+                        dialog.files.iter_mut().for_each(|(_, on)| *on = true);
+                    }
+                    if ui.small_button("None").clicked() {
+                        dialog.files.iter_mut().for_each(|(_, on)| *on = false);
+                    }
+                });
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        for (idx, (file, checked)) in dialog.files.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.checkbox(checked, "");
+                                ui.add(
+                                    egui::TextEdit::singleline(&mut file.name)
+                                        .id_salt(("split_dialog_name", idx))
+                                        .desired_width(260.0),
+                                );
+                                let detail = format!(
+                                    "{} - {} lines",
+                                    file.language.as_deref().unwrap_or("auto"),
+                                    file.content.lines().count()
+                                );
+                                ui.label(
+                                    egui::RichText::new(detail).small().color(COLOR_TEXT_MUTED),
+                                );
+                            })
+                            .response
+                            .on_hover_text(file.content.chars().take(400).collect::<String>());
+                        }
+                    });
+                ui.separator();
+                let checked = dialog
+                    .files
+                    .iter()
+                    .filter(|(file, on)| *on && !file.name.trim().is_empty())
+                    .count();
+                if ui
+                    .add_enabled(
+                        checked > 0,
+                        egui::Button::new(format!("Create {} paste(s)", checked)),
+                    )
+                    .clicked()
+                {
+                    commit = true;
+                }
+            });
+        if !open {
+            self.split_dialog.open = false;
+            return;
+        }
+        if commit {
+            self.commit_split();
+        }
+    }
+}
@@ -1,5 +1,6 @@
-//! Bottom status bar rendering for save state and server metadata.
+//! Bottom status bar rendering for save state, editor stats, and server metadata.
 
+use super::super::presence::presence_summary;
 use super::super::*;
 use eframe::egui;
 
@@ -20,6 +21,19 @@ impl LocalPasteApp {
                         ui.label(egui::RichText::new(label).color(color));
                         has_primary_item = true;
                     }
+                    if let Some(id) = self.selected_id.as_deref() {
+                        let presence = self.paste_presence(id);
+                        if !presence.is_empty() {
+                            ui.separator();
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "Also open: {}",
+                                    presence_summary(presence)
+                                ))
+                                .color(COLOR_ACCENT_TEXT),
+                            );
+                        }
+                    }
                     if let Some(status) = &self.status {
                         if has_primary_item {
                             ui.separator();
@@ -30,6 +44,8 @@ impl LocalPasteApp {
                     if has_primary_item {
                         ui.separator();
                     }
+                    self.render_storage_alerts(ui);
+                    self.render_update_notice(ui);
                     ui.label(egui::RichText::new("DB:").small().color(COLOR_TEXT_MUTED));
                     ui.add(
                         egui::Label::new(
@@ -44,16 +60,42 @@ impl LocalPasteApp {
                 // Keep API metadata on its own row so long DB paths and status text
                 // cannot starve/right-clip the endpoint label at narrow widths.
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    let api_label = if self.server_used_fallback {
-                        format!("API: http://{} (auto)", self.server_addr)
+                    if self.server_fallback_from.is_some() && !self.server_port_pinned {
+                        let pin = ui
+                            .add(
+                                egui::Label::new(
+                                    egui::RichText::new("Pin port")
+                                        .small()
+                                        .underline()
+                                        .color(COLOR_TEXT_SECONDARY),
+                                )
+                                .sense(egui::Sense::click()),
+                            )
+                            .on_hover_text(format!(
+                                "Use port {} on future launches",
+                                self.server_addr.port()
+                            ));
+                        if pin.clicked() {
+                            self.pin_server_port();
+                        }
+                        ui.separator();
+                    }
+                    let api_label = if self.server_fallback_from.is_some() {
+                        format!("API: http://{} (fallback)", self.server_addr)
                     } else {
                         format!("API: http://{}", self.server_addr)
                     };
-                    ui.label(
+                    let api_response = ui.label(
                         egui::RichText::new(api_label)
                             .small()
                             .color(COLOR_TEXT_SECONDARY),
                     );
+                    if let Some(requested) = self.server_fallback_from {
+                        api_response.on_hover_text(format!(
+                            "{} was in use when the app started",
+                            requested
+                        ));
+                    }
                     if self.selected_id.is_some() {
                         ui.separator();
                         ui.label(
@@ -61,8 +103,57 @@ impl LocalPasteApp {
                                 .small()
                                 .color(COLOR_TEXT_MUTED),
                         );
+                        ui.separator();
+                        let indent = egui::RichText::new(self.indent_style.label())
+                            .small()
+                            .color(COLOR_TEXT_MUTED);
+                        if self.is_virtual_editor_mode() {
+                            let target = self.indent_style.toggled().label();
+                            let response = ui
+                                .add(egui::Label::new(indent).sense(egui::Sense::click()))
+                                .on_hover_text(format!("Convert indentation to {}", target));
+                            if response.clicked() {
+                                self.toggle_virtual_indentation();
+                            }
+                        } else {
+                            ui.label(indent);
+                        }
+                        if let Some(stats) = self.selection_stats_label() {
+                            ui.separator();
+                            ui.label(
+                                egui::RichText::new(stats)
+                                    .small()
+                                    .color(COLOR_TEXT_SECONDARY),
+                            );
+                        }
                     }
                 });
             });
     }
+
+    /// Tells the user which port the API fell back to, if it did.
+    pub(crate) fn announce_port_fallback(&mut self) {
+        if let Some(requested) = self.server_fallback_from {
+            self.set_status(format!(
+                "Port {} was in use; API is on port {}. Pin it from the status bar.",
+                requested.port(),
+                self.server_addr.port()
+            ));
+        }
+    }
+
+    /// Pins the current API port so later launches bind it first.
+    pub(crate) fn pin_server_port(&mut self) {
+        let port = self.server_addr.port();
+        match localpaste_core::discovery::write_pinned_port(&self.db_path, port) {
+            Ok(()) => {
+                self.server_port_pinned = true;
+                self.set_status(format!("API port {} pinned for future launches.", port));
+            }
+            Err(err) => {
+                warn!("failed to pin API port {}: {}", port, err);
+                self.set_status("Pinning the API port failed; see logs.");
+            }
+        }
+    }
 }
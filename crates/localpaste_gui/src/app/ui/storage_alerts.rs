@@ -0,0 +1,122 @@
+//! Storage size alerts: periodic checks, a toast when an alert first
+//! appears, and status bar links that run the matching remediation.
+
+use super::super::*;
+use crate::backend::CoreCmd;
+use eframe::egui;
+use localpaste_core::storage_alerts::{
+    StorageAlert, StorageRemediation, DEFAULT_BACKUP_PRUNE_KEEP_LAST, STORAGE_CHECK_INTERVAL,
+};
+use std::path::PathBuf;
+
+const COLOR_STORAGE_ALERT: egui::Color32 = egui::Color32::from_rgb(0xD2, 0x99, 0x22);
+
+/// Latest storage alerts and when they were requested.
+#[derive(Debug, Default)]
+pub(crate) struct StorageAlertsState {
+    pub(crate) alerts: Vec<StorageAlert>,
+    pub(crate) checked_at: Option<Instant>,
+}
+
+fn remediation_label(remediation: StorageRemediation) -> (&'static str, &'static str) {
+    match remediation {
+        StorageRemediation::Cleanup => (
+            "Clean up",
+            "Remove orphaned drafts and repair folders. The file shrinks after \
+             `localpaste --cleanup` runs with the app closed.",
+        ),
+        StorageRemediation::PruneBackups => (
+            "Prune backups",
+            "Delete all but the newest backups; a scheduled restore is kept.",
+        ),
+    }
+}
+
+impl LocalPasteApp {
+    /// Requests a storage check when [`STORAGE_CHECK_INTERVAL`] has passed.
+    pub(crate) fn maybe_check_storage(&mut self) {
+        if self
+            .storage_alerts
+            .checked_at
+            .is_some_and(|at| at.elapsed() < STORAGE_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.storage_alerts.checked_at = Some(Instant::now());
+        let _ = self.backend.cmd_tx.send(CoreCmd::CheckStorage {
+            db_path: self.db_path.clone(),
+        });
+    }
+
+    /// Stores the latest alerts and toasts the first one not seen last check.
+    pub(crate) fn apply_storage_checked(&mut self, alerts: Vec<StorageAlert>) {
+        let raised = alerts
+            .iter()
+            .find(|alert| {
+                !self
+                    .storage_alerts
+                    .alerts
+                    .iter()
+                    .any(|known| known.kind == alert.kind)
+            })
+            .map(|alert| alert.message.clone());
+        self.storage_alerts.alerts = alerts;
+        if let Some(message) = raised {
+            self.set_status(format!("{}.", message));
+        }
+    }
+
+    /// Reports a finished prune and re-checks storage.
+    pub(crate) fn apply_backups_pruned(&mut self, deleted: Vec<PathBuf>) {
+        self.storage_alerts.checked_at = None;
+        self.set_status(format!("Pruned {} old backup(s).", deleted.len()));
+    }
+
+    /// Sends the remediation for an alert; storage is re-checked afterwards.
+    pub(crate) fn run_storage_remediation(&mut self, remediation: StorageRemediation) {
+        let cmd = match remediation {
+            StorageRemediation::Cleanup => CoreCmd::Cleanup,
+            StorageRemediation::PruneBackups => CoreCmd::PruneBackups {
+                db_path: self.db_path.clone(),
+                keep_last: DEFAULT_BACKUP_PRUNE_KEEP_LAST,
+            },
+        };
+        if self.backend.cmd_tx.send(cmd).is_err() {
+            self.set_status("Storage action failed: backend unavailable.");
+            return;
+        }
+        // The worker runs commands in order, so the next check sees the result.
+        self.storage_alerts.checked_at = None;
+    }
+
+    /// Renders active alerts with their remediation links in the status bar.
+    pub(crate) fn render_storage_alerts(&mut self, ui: &mut egui::Ui) {
+        let mut clicked = None;
+        for alert in &self.storage_alerts.alerts {
+            ui.label(
+                egui::RichText::new(&alert.message)
+                    .small()
+                    .color(COLOR_STORAGE_ALERT),
+            );
+            let (label, hint) = remediation_label(alert.remediation);
+            let action = ui
+                .add(
+                    egui::Label::new(
+                        egui::RichText::new(label)
+                            .small()
+                            .underline()
+                            .color(COLOR_TEXT_SECONDARY),
+                    )
+                    .sense(egui::Sense::click()),
+                )
+                .on_hover_text(hint);
+            if action.clicked() {
+                clicked = Some(alert.remediation);
+            }
+            ui.separator();
+        }
+        if let Some(remediation) = clicked {
+            self.run_storage_remediation(remediation);
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! Symbol outline side panel and the `Ctrl/Cmd+Shift+O` fuzzy symbol jump.
+//!
+//! Symbols come from the highlight worker, so pastes that render as plain text
+//! (unknown languages or buffers past the highlight threshold) have no outline.
+
+use super::super::highlight::{HighlightOutline, OutlineSymbol};
+use super::super::*;
+use eframe::egui::{self, RichText};
+
+/// Rows shown in the jump modal before the list scrolls.
+const JUMP_MAX_HEIGHT: f32 = 360.0;
+
+/// Latest worker outline plus panel and jump-modal state.
+#[derive(Debug, Default)]
+pub(crate) struct SymbolOutlineState {
+    pub(crate) outline: Option<HighlightOutline>,
+    pub(crate) panel_open: bool,
+    pub(crate) jump_open: bool,
+    pub(crate) jump_query: String,
+    pub(crate) jump_selected: usize,
+}
+
+/// Scores `candidate` against a subsequence `query`, ignoring case.
+///
+/// Consecutive matches and matches at word starts score higher; gaps and a
+/// late first match cost a little.
+///
+/// # Returns
+/// `None` when `query` is not a subsequence of `candidate`.
+pub(crate) fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let mut query_chars = query.chars().flat_map(char::to_lowercase).peekable();
+    let mut score = 0i64;
+    let mut previous: Option<char> = None;
+    let mut last_match: Option<usize> = None;
+    let mut first_match: Option<usize> = None;
+    for (idx, ch) in candidate.chars().enumerate() {
+        let Some(wanted) = query_chars.peek().copied() else {
+            break;
+        };
+        let lowered = ch.to_lowercase().next().unwrap_or(ch);
+        if lowered == wanted {
+            query_chars.next();
+            score += 10;
+            let word_start = previous.is_none_or(|prev| {
+                !prev.is_alphanumeric() || (prev.is_lowercase() && ch.is_uppercase())
+            });
+            if word_start {
+                score += 8;
+            }
+            match last_match {
+                Some(last) if last + 1 == idx => score += 6,
+                Some(last) => score -= (idx - last - 1).min(6) as i64,
+                None => {}
+            }
+            first_match.get_or_insert(idx);
+            last_match = Some(idx);
+        }
+        previous = Some(ch);
+    }
+    if query_chars.peek().is_some() {
+        return None;
+    }
+    Some(score - first_match.unwrap_or(0).min(10) as i64)
+}
+
+impl LocalPasteApp {
+    /// Stores a worker outline when it belongs to the selected paste.
+    pub(crate) fn apply_highlight_outline(&mut self, outline: HighlightOutline) {
+        if self.selected_id.as_deref() != Some(outline.paste_id.as_str()) {
+            return;
+        }
+        self.symbol_outline.outline = Some(outline);
+    }
+
+    /// Returns outline symbols for the selected paste.
+    pub(crate) fn current_outline_symbols(&self) -> &[OutlineSymbol] {
+        self.symbol_outline
+            .outline
+            .as_ref()
+            .filter(|outline| self.selected_id.as_deref() == Some(outline.paste_id.as_str()))
+            .map(|outline| outline.symbols.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns outline symbols matching the jump query, best match first.
+    pub(crate) fn symbol_jump_results(&self) -> Vec<OutlineSymbol> {
+        let query = self.symbol_outline.jump_query.trim();
+        let symbols = self.current_outline_symbols();
+        if query.is_empty() {
+            return symbols.to_vec();
+        }
+        let mut scored: Vec<(i64, &OutlineSymbol)> = symbols
+            .iter()
+            .filter_map(|symbol| fuzzy_score(query, symbol.name.as_str()).map(|s| (s, symbol)))
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.line.cmp(&b.1.line)));
+        scored
+            .into_iter()
+            .map(|(_, symbol)| symbol.clone())
+            .collect()
+    }
+
+    /// Opens or closes the fuzzy symbol jump modal.
+    pub(crate) fn toggle_symbol_jump(&mut self) {
+        if self.symbol_outline.jump_open {
+            self.symbol_outline.jump_open = false;
+            return;
+        }
+        if self.selected_id.is_none() || !self.is_virtual_editor_mode() {
+            return;
+        }
+        self.command_palette_open = false;
+        self.symbol_outline.jump_open = true;
+        self.symbol_outline.jump_query.clear();
+        self.symbol_outline.jump_selected = 0;
+    }
+
+    /// Moves the caret to the start of `line` and scrolls it near the top.
+    pub(crate) fn jump_to_outline_line(&mut self, line: usize) {
+        if !self.is_virtual_editor_mode() {
+            return;
+        }
+        let buffer = &self.virtual_editor_buffer;
+        let line = line.min(buffer.line_count().saturating_sub(1));
+        let target = buffer.line_col_to_char(line, 0);
+        self.virtual_editor_state
+            .set_cursor(target, buffer.len_chars());
+        let row = self.virtual_cursor_row_index(target);
+        self.virtual_pending_scroll_offset_y =
+            Some(row.saturating_sub(2) as f32 * self.virtual_line_height);
+        self.focus_editor_next = true;
+    }
+
+    /// Renders the collapsible outline side panel.
+    pub(crate) fn render_symbol_outline_panel(&mut self, ctx: &egui::Context) {
+        if !self.symbol_outline.panel_open || self.selected_id.is_none() {
+            return;
+        }
+        let mut keep_open = true;
+        let mut jump_line: Option<usize> = None;
+        egui::SidePanel::right("symbol_outline_panel")
+            .default_width(240.0)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading("Outline");
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button("Close").clicked() {
+                            keep_open = false;
+                        }
+                    });
+                });
+                ui.separator();
+                let symbols = self.current_outline_symbols();
+                if symbols.is_empty() {
+                    ui.label(RichText::new("No symbols").color(COLOR_TEXT_MUTED));
+                    return;
+                }
+                let row_height = ui.spacing().interact_size.y;
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false; 2])
+                    .show_rows(ui, row_height, symbols.len(), |ui, range| {
+                        for symbol in &symbols[range] {
+                            ui.horizontal(|ui| {
+                                ui.add_space(symbol.depth as f32 * 12.0);
+                                ui.label(
+                                    RichText::new(symbol.kind.label())
+                                        .small()
+                                        .monospace()
+                                        .color(COLOR_TEXT_MUTED),
+                                );
+                                if ui.selectable_label(false, symbol.name.as_str()).clicked() {
+                                    jump_line = Some(symbol.line);
+                                }
+                            });
+                        }
+                    });
+            });
+        if let Some(line) = jump_line {
+            self.jump_to_outline_line(line);
+        }
+        self.symbol_outline.panel_open = keep_open;
+    }
+
+    /// Renders the fuzzy symbol jump modal.
+    pub(crate) fn render_symbol_jump(&mut self, ctx: &egui::Context) {
+        if !self.symbol_outline.jump_open {
+            return;
+        }
+        let mut jump_line: Option<usize> = None;
+        let mut close = false;
+        egui::Window::new("Go to Symbol")
+            .id(egui::Id::new("symbol_jump_modal"))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(520.0)
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 60.0))
+            .show(ctx, |ui| {
+                let query_resp = ui.add(
+                    egui::TextEdit::singleline(&mut self.symbol_outline.jump_query)
+                        .hint_text("Jump to symbol..."),
+                );
+                query_resp.request_focus();
+                if query_resp.changed() {
+                    self.symbol_outline.jump_selected = 0;
+                }
+                if ui.input(|input| input.key_pressed(egui::Key::Escape)) {
+                    close = true;
+                    return;
+                }
+
+                let results = self.symbol_jump_results();
+                if results.is_empty() {
+                    ui.add_space(8.0);
+                    ui.label(RichText::new("No matching symbols").color(COLOR_TEXT_MUTED));
+                    return;
+                }
+                let last = results.len() - 1;
+                let selected = &mut self.symbol_outline.jump_selected;
+                *selected = (*selected).min(last);
+                if ui.input(|input| input.key_pressed(egui::Key::ArrowDown)) {
+                    *selected = (*selected + 1).min(last);
+                }
+                if ui.input(|input| input.key_pressed(egui::Key::ArrowUp)) {
+                    *selected = selected.saturating_sub(1);
+                }
+                if ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                    jump_line = Some(results[*selected].line);
+                }
+
+                ui.add_space(8.0);
+                let selected = *selected;
+                egui::ScrollArea::vertical()
+                    .max_height(JUMP_MAX_HEIGHT)
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        for (idx, symbol) in results.iter().enumerate() {
+                            let label = format!(
+                                "{}  {}  :{}",
+                                symbol.kind.label(),
+                                symbol.name,
+                                symbol.line + 1
+                            );
+                            let response = ui.selectable_label(idx == selected, label);
+                            if idx == selected {
+                                response.scroll_to_me(None);
+                            }
+                            if response.clicked() {
+                                jump_line = Some(symbol.line);
+                            }
+                        }
+                    });
+            });
+        if let Some(line) = jump_line {
+            self.jump_to_outline_line(line);
+            close = true;
+        }
+        if close {
+            self.symbol_outline.jump_open = false;
+        }
+    }
+}
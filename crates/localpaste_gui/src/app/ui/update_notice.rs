@@ -0,0 +1,267 @@
+//! Opt-in update check: a daily background lookup of the latest GitHub
+//! release and a status bar notice with the release notes and page link.
+//!
+//! The notice never reopens for a version the user dismissed, and failed
+//! background checks are only logged. Downloads stay manual.
+
+use super::super::*;
+use crate::backend::CoreCmd;
+use eframe::egui;
+use localpaste_core::settings::UpdateSettings;
+use localpaste_core::update_check::{
+    check_for_update, ReleaseInfo, LATEST_RELEASE_URL, UPDATE_CHECK_INTERVAL,
+};
+
+/// Outcome of one release lookup, sent back from the check thread.
+#[derive(Debug)]
+pub(crate) struct UpdateCheckResult {
+    /// Whether the user asked for this check from the command palette.
+    pub(crate) manual: bool,
+    pub(crate) result: Result<Option<ReleaseInfo>, String>,
+}
+
+/// Update check settings, the running check, and the release being shown.
+#[derive(Debug)]
+pub(crate) struct UpdateNoticeState {
+    /// Saved `updates` section of the settings file.
+    pub(crate) settings: UpdateSettings,
+    pub(crate) release_url: String,
+    pub(crate) checked_at: Option<Instant>,
+    pub(crate) result_rx: Option<mpsc::Receiver<UpdateCheckResult>>,
+    /// Newer release that has not been dismissed.
+    pub(crate) available: Option<ReleaseInfo>,
+    pub(crate) notes_open: bool,
+}
+
+impl UpdateNoticeState {
+    /// Builds the initial state from the saved update settings.
+    pub(crate) fn with_settings(settings: UpdateSettings) -> Self {
+        Self {
+            settings,
+            release_url: LATEST_RELEASE_URL.to_string(),
+            checked_at: None,
+            result_rx: None,
+            available: None,
+            notes_open: false,
+        }
+    }
+}
+
+impl Default for UpdateNoticeState {
+    fn default() -> Self {
+        Self::with_settings(UpdateSettings::default())
+    }
+}
+
+impl LocalPasteApp {
+    /// Starts a background check when enabled and [`UPDATE_CHECK_INTERVAL`]
+    /// has passed since the last one.
+    pub(crate) fn maybe_check_for_update(&mut self) {
+        if !self.updates.settings.check_enabled
+            || self
+                .updates
+                .checked_at
+                .is_some_and(|at| at.elapsed() < UPDATE_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.start_update_check(false);
+    }
+
+    /// Checks for a newer release now, even when automatic checks are off.
+    pub(crate) fn check_for_update_now(&mut self) {
+        if self.updates.result_rx.is_some() {
+            self.set_status("Update check already in progress.");
+            return;
+        }
+        self.start_update_check(true);
+        self.set_status("Checking for updates...");
+    }
+
+    fn start_update_check(&mut self, manual: bool) {
+        if self.updates.result_rx.is_some() {
+            return;
+        }
+        self.updates.checked_at = Some(Instant::now());
+        let url = self.updates.release_url.clone();
+        let (tx, rx) = mpsc::channel();
+        self.updates.result_rx = Some(rx);
+        std::thread::spawn(move || {
+            let result = check_for_update(url.as_str(), env!("CARGO_PKG_VERSION"))
+                .map_err(|err| err.to_string());
+            let _ = tx.send(UpdateCheckResult { manual, result });
+        });
+    }
+
+    /// Polls the running update check and applies its result.
+    pub(crate) fn poll_update_check(&mut self) {
+        let Some(rx) = self.updates.result_rx.as_ref() else {
+            return;
+        };
+        let outcome = match rx.try_recv() {
+            Ok(outcome) => outcome,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => UpdateCheckResult {
+                manual: false,
+                result: Err("check thread stopped".to_string()),
+            },
+        };
+        self.updates.result_rx = None;
+        self.apply_update_checked(outcome);
+    }
+
+    /// Shows a newer release once; dismissed versions stay quiet unless the
+    /// check was manual.
+    pub(crate) fn apply_update_checked(&mut self, outcome: UpdateCheckResult) {
+        match outcome.result {
+            Ok(Some(release)) => {
+                let dismissed = self.updates.settings.dismissed_version.as_deref()
+                    == Some(release.version.as_str());
+                if dismissed && !outcome.manual {
+                    return;
+                }
+                let already_shown = self
+                    .updates
+                    .available
+                    .as_ref()
+                    .is_some_and(|shown| shown.version == release.version);
+                if !already_shown || outcome.manual {
+                    self.set_status(format!(
+                        "LocalPaste {} is available (running {}).",
+                        release.version,
+                        env!("CARGO_PKG_VERSION")
+                    ));
+                }
+                self.updates.available = Some(release);
+            }
+            Ok(None) => {
+                self.updates.available = None;
+                if outcome.manual {
+                    self.set_status(format!(
+                        "LocalPaste {} is up to date.",
+                        env!("CARGO_PKG_VERSION")
+                    ));
+                }
+            }
+            Err(err) => {
+                warn!("update check failed: {}", err);
+                if outcome.manual {
+                    self.set_status(format!("Update check failed: {}", err));
+                }
+            }
+        }
+    }
+
+    /// Turns automatic update checks on or off in the settings file.
+    pub(crate) fn toggle_update_checks(&mut self) {
+        let mut updates = self.updates.settings.clone();
+        updates.check_enabled = !updates.check_enabled;
+        self.send_update_settings(updates);
+    }
+
+    /// Hides the current notice and stops showing it for that version.
+    pub(crate) fn dismiss_update(&mut self) {
+        let Some(release) = self.updates.available.take() else {
+            return;
+        };
+        self.updates.notes_open = false;
+        let mut updates = self.updates.settings.clone();
+        updates.dismissed_version = Some(release.version);
+        self.send_update_settings(updates);
+    }
+
+    fn send_update_settings(&mut self, updates: UpdateSettings) {
+        let cmd = CoreCmd::SaveUpdateSettings {
+            db_path: self.db_path.clone(),
+            updates,
+        };
+        if self.backend.cmd_tx.send(cmd).is_err() {
+            self.set_status("Saving update settings failed: backend unavailable.");
+        }
+    }
+
+    /// Applies saved update settings; enabling checks schedules one right away.
+    pub(crate) fn apply_update_settings_saved(&mut self, updates: UpdateSettings) {
+        let toggled = updates.check_enabled != self.updates.settings.check_enabled;
+        self.updates.settings = updates;
+        if toggled {
+            if self.updates.settings.check_enabled {
+                self.updates.checked_at = None;
+                self.set_status("Update checks enabled; checking daily.");
+            } else {
+                self.set_status("Update checks disabled.");
+            }
+        }
+    }
+
+    /// Renders the available-update notice in the status bar.
+    pub(crate) fn render_update_notice(&mut self, ui: &mut egui::Ui) {
+        let Some(release) = self.updates.available.as_ref() else {
+            return;
+        };
+        ui.label(
+            egui::RichText::new(format!("Update {} available", release.version))
+                .small()
+                .color(COLOR_ACCENT_TEXT),
+        );
+        let link = |ui: &mut egui::Ui, text: &str, hint: &str| {
+            ui.add(
+                egui::Label::new(
+                    egui::RichText::new(text)
+                        .small()
+                        .underline()
+                        .color(COLOR_TEXT_SECONDARY),
+                )
+                .sense(egui::Sense::click()),
+            )
+            .on_hover_text(hint)
+            .clicked()
+        };
+        let show_notes = link(ui, "What's new", "Show the release notes");
+        if link(ui, "Download", release.url.as_str()) {
+            ui.ctx()
+                .open_url(egui::OpenUrl::new_tab(release.url.as_str()));
+        }
+        let dismiss = link(ui, "Dismiss", "Hide this notice until the next release");
+        if show_notes {
+            self.updates.notes_open = true;
+        }
+        if dismiss {
+            self.dismiss_update();
+        }
+        ui.separator();
+    }
+
+    /// Renders the release notes window for the available update.
+    pub(crate) fn render_update_notes(&mut self, ctx: &egui::Context) {
+        if !self.updates.notes_open {
+            return;
+        }
+        let Some(release) = self.updates.available.as_ref() else {
+            self.updates.notes_open = false;
+            return;
+        };
+        let mut open = true;
+        egui::Window::new(format!("LocalPaste {}", release.version))
+            .id(egui::Id::new("update_notes"))
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                ui.label(format!("You are running {}.", env!("CARGO_PKG_VERSION")));
+                ui.hyperlink_to("Open the release page", release.url.as_str());
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .max_height(360.0)
+                    .show(ui, |ui| {
+                        if release.notes.is_empty() {
+                            ui.label(
+                                egui::RichText::new("No release notes.").color(COLOR_TEXT_MUTED),
+                            );
+                        } else {
+                            ui.label(egui::RichText::new(&release.notes).monospace());
+                        }
+                    });
+            });
+        self.updates.notes_open = open;
+    }
+}
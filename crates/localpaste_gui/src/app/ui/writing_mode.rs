@@ -0,0 +1,175 @@
+//! Distraction-free writing mode with per-paste word-count goals.
+//!
+//! Writing mode hides the sidebar, top bar, and properties drawer, centers the
+//! editor in a narrower column, and enlarges the editor font. Goals are kept in
+//! egui's persisted memory keyed by paste id, so they never touch paste rows.
+
+use super::super::*;
+use eframe::egui;
+use std::collections::HashMap;
+
+const WORD_GOALS_MEMORY_ID: &str = "localpaste_word_goals";
+/// Editor column width while writing mode is active.
+pub(crate) const WRITING_COLUMN_WIDTH: f32 = 760.0;
+/// Editor font scale while writing mode is active.
+pub(crate) const WRITING_FONT_SCALE: f32 = 1.25;
+/// Canonical languages treated as prose.
+const WRITING_LANGUAGES: &[&str] = &["markdown", "text"];
+
+/// Writing mode toggle, persisted word goals, and a cached word count.
+#[derive(Debug, Default)]
+pub(crate) struct WritingModeState {
+    pub(crate) active: bool,
+    pub(crate) goals: HashMap<String, u32>,
+    restored: bool,
+    /// Paste id, buffer revision, and word count of the last count.
+    pub(crate) word_count: Option<(String, u64, usize)>,
+}
+
+/// Counts whitespace-separated words across text chunks.
+///
+/// # Returns
+/// Number of words, treating chunk boundaries as contiguous text.
+pub(crate) fn count_words<'a>(chunks: impl Iterator<Item = &'a str>) -> usize {
+    let mut words = 0usize;
+    let mut in_word = false;
+    for chunk in chunks {
+        for ch in chunk.chars() {
+            if ch.is_whitespace() {
+                in_word = false;
+            } else if !in_word {
+                in_word = true;
+                words += 1;
+            }
+        }
+    }
+    words
+}
+
+impl LocalPasteApp {
+    /// Returns whether the selected paste is prose that writing mode supports.
+    pub(crate) fn writing_mode_eligible(&self) -> bool {
+        if self.selected_id.is_none() {
+            return false;
+        }
+        let language = self
+            .edit_language
+            .as_deref()
+            .map(localpaste_core::detection::canonical::canonicalize)
+            .filter(|value| !value.is_empty());
+        language.is_none_or(|value| WRITING_LANGUAGES.contains(&value.as_str()))
+    }
+
+    /// Returns whether writing-mode layout should be applied this frame.
+    pub(crate) fn writing_mode_active(&self) -> bool {
+        self.writing_mode.active && self.writing_mode_eligible()
+    }
+
+    /// Toggles writing mode for the selected markdown/plain paste.
+    pub(crate) fn toggle_writing_mode(&mut self) {
+        if self.writing_mode.active {
+            self.writing_mode.active = false;
+            return;
+        }
+        if !self.writing_mode_eligible() {
+            self.set_status("Writing mode is available for markdown and plain-text pastes.");
+            return;
+        }
+        self.writing_mode.active = true;
+    }
+
+    /// Word count of the active buffer, recounted only when the revision changes.
+    pub(crate) fn active_word_count(&mut self) -> usize {
+        let id = self.selected_id.clone().unwrap_or_default();
+        let revision = self.active_revision();
+        if let Some((cached_id, cached_revision, words)) = &self.writing_mode.word_count {
+            if *cached_id == id && *cached_revision == revision {
+                return *words;
+            }
+        }
+        let words = match self.editor_mode {
+            EditorMode::VirtualEditor => count_words(self.virtual_editor_buffer.rope().chunks()),
+            EditorMode::VirtualPreview => {
+                let text = self.selected_content.to_string();
+                count_words(std::iter::once(text.as_str()))
+            }
+        };
+        self.writing_mode.word_count = Some((id, revision, words));
+        words
+    }
+
+    /// Renders the writing header: goal input, progress, and exit control.
+    ///
+    /// # Arguments
+    /// - `ui`: Column UI the editor renders into.
+    pub(crate) fn render_writing_header(&mut self, ui: &mut egui::Ui) {
+        let memory_id = egui::Id::new(WORD_GOALS_MEMORY_ID);
+        if !self.writing_mode.restored {
+            self.writing_mode.restored = true;
+            if let Some(goals) = ui
+                .ctx()
+                .data_mut(|data| data.get_persisted::<HashMap<String, u32>>(memory_id))
+            {
+                self.writing_mode.goals = goals;
+            }
+        }
+        let Some(id) = self.selected_id.clone() else {
+            return;
+        };
+        let words = self.active_word_count();
+        let mut goal = self.writing_mode.goals.get(&id).copied().unwrap_or(0);
+        let mut exit = false;
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Goal").small().color(COLOR_TEXT_MUTED));
+            let changed = ui
+                .add(
+                    egui::DragValue::new(&mut goal)
+                        .range(0..=1_000_000)
+                        .speed(10.0)
+                        .suffix(" words"),
+                )
+                .changed();
+            if changed {
+                if goal == 0 {
+                    self.writing_mode.goals.remove(&id);
+                } else {
+                    self.writing_mode.goals.insert(id.clone(), goal);
+                }
+                let goals = self.writing_mode.goals.clone();
+                ui.ctx()
+                    .data_mut(|data| data.insert_persisted(memory_id, goals));
+            }
+            if goal > 0 {
+                let progress = (words as f32 / goal as f32).min(1.0);
+                ui.add(
+                    egui::ProgressBar::new(progress)
+                        .desired_width(220.0)
+                        .text(format!("{} / {} words", words, goal)),
+                );
+            } else {
+                ui.label(
+                    RichText::new(format!("{} words", words))
+                        .small()
+                        .color(COLOR_TEXT_MUTED),
+                );
+            }
+            exit = ui.small_button("Exit writing mode").clicked();
+        });
+        ui.add_space(6.0);
+        if exit {
+            self.writing_mode.active = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::count_words;
+
+    #[test]
+    fn count_words_spans_chunk_boundaries() {
+        assert_eq!(count_words(["hello wor", "ld  again\n"].into_iter()), 3);
+        assert_eq!(count_words(["", "  \n\t"].into_iter()), 0);
+        assert_eq!(count_words(["one ", "two"].into_iter()), 2);
+    }
+}
@@ -86,6 +86,59 @@ pub(super) fn api_paste_link_for_copy(addr: std::net::SocketAddr, id: &str) -> S
     format!("http://{}/api/paste/{}", routed, id)
 }
 
+/// Builds the `lpaste` command that prints the paste in a terminal.
+pub(super) fn cli_get_command_for_copy(id: &str) -> String {
+    format!("lpaste get {}", id)
+}
+
+/// Builds the `localpaste://paste/<id>` deep link for the paste.
+pub(super) fn paste_deep_link_for_copy(id: &str) -> String {
+    format!("localpaste://paste/{}", id)
+}
+
+/// Builds the `localpaste://paste/<id>#L<line>` link to a 1-based line.
+pub(super) fn paste_line_link_for_copy(id: &str, line: usize) -> String {
+    format!("{}#L{}", paste_deep_link_for_copy(id), line)
+}
+
+/// Parses a `localpaste://paste/<id>` link with an optional `#L<line>` fragment.
+///
+/// # Returns
+/// The paste id and 1-based line, or `None` when `text` is not a paste link.
+pub(super) fn parse_paste_link(text: &str) -> Option<(String, Option<usize>)> {
+    let rest = text.trim().strip_prefix("localpaste://paste/")?;
+    let (id, fragment) = match rest.split_once('#') {
+        Some((id, fragment)) => (id, Some(fragment)),
+        None => (rest, None),
+    };
+    if id.is_empty() || id.contains(['/', '?']) || id.chars().any(char::is_whitespace) {
+        return None;
+    }
+    let line = match fragment {
+        None | Some("") => None,
+        Some(fragment) => {
+            let line = fragment.strip_prefix('L')?.parse::<usize>().ok()?;
+            if line == 0 {
+                return None;
+            }
+            Some(line)
+        }
+    };
+    Some((id.to_string(), line))
+}
+
+/// Prefixes each line with its right-aligned 1-based number, starting at `first_line`.
+pub(super) fn format_with_line_numbers(text: &str, first_line: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let last = first_line.saturating_add(lines.len().saturating_sub(1));
+    let width = last.to_string().len();
+    let mut out = String::with_capacity(text.len() + lines.len() * (width + 3));
+    for (offset, line) in lines.iter().enumerate() {
+        out.push_str(&format!("{:>width$} | {}\n", first_line + offset, line));
+    }
+    out
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum WordSelectionClass {
     Word,
@@ -161,7 +214,9 @@ pub(super) fn word_range_at(text: &str, char_index: usize) -> Option<(usize, usi
 #[cfg(test)]
 mod tests {
     use super::{
-        api_paste_link_for_copy, display_language_label, format_fenced_code_block, word_range_at,
+        api_paste_link_for_copy, cli_get_command_for_copy, display_language_label,
+        format_fenced_code_block, format_with_line_numbers, parse_paste_link,
+        paste_deep_link_for_copy, paste_line_link_for_copy, word_range_at,
     };
 
     #[test]
@@ -191,6 +246,38 @@ mod tests {
         assert_eq!(v6, "http://[::1]:38411/api/paste/abc");
     }
 
+    #[test]
+    fn share_snippets_embed_paste_id() {
+        assert_eq!(cli_get_command_for_copy("abc"), "lpaste get abc");
+        assert_eq!(paste_deep_link_for_copy("abc"), "localpaste://paste/abc");
+        assert_eq!(
+            paste_line_link_for_copy("abc", 42),
+            "localpaste://paste/abc#L42"
+        );
+    }
+
+    #[test]
+    fn parse_paste_link_reads_id_and_optional_line() {
+        assert_eq!(
+            parse_paste_link(" localpaste://paste/abc#L42 "),
+            Some(("abc".to_string(), Some(42)))
+        );
+        assert_eq!(
+            parse_paste_link("localpaste://paste/abc"),
+            Some(("abc".to_string(), None))
+        );
+        assert_eq!(parse_paste_link("localpaste://paste/abc#L0"), None);
+        assert_eq!(parse_paste_link("localpaste://paste/abc#top"), None);
+        assert_eq!(parse_paste_link("localpaste://paste/"), None);
+        assert_eq!(parse_paste_link("https://example.com/abc#L1"), None);
+    }
+
+    #[test]
+    fn format_with_line_numbers_pads_to_widest_number() {
+        assert_eq!(format_with_line_numbers("a\nb\n", 9), " 9 | a\n10 | b\n");
+        assert_eq!(format_with_line_numbers("", 1), "");
+    }
+
     #[test]
     fn display_language_label_distinguishes_auto_and_manual_plain() {
         assert_eq!(display_language_label(None, false, false), "auto");
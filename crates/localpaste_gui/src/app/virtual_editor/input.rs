@@ -34,6 +34,10 @@ pub(crate) enum VirtualInputCommand {
     InsertNewline,
     InsertTab,
 
+    // Syntactic selection
+    ExpandSelection,
+    ShrinkSelection,
+
     // Clipboard + history
     SelectAll,
     Copy,
@@ -197,6 +201,15 @@ fn map_navigation_key(
 ) -> Option<VirtualInputCommand> {
     let select = modifiers.shift;
 
+    // Alt+Up/Down grow and shrink the selection by syntactic units.
+    if modifiers.alt && !modifiers.shift && !modifiers.command && !modifiers.ctrl {
+        match key {
+            egui::Key::ArrowUp => return Some(VirtualInputCommand::ExpandSelection),
+            egui::Key::ArrowDown => return Some(VirtualInputCommand::ShrinkSelection),
+            _ => {}
+        }
+    }
+
     match key {
         // --- Horizontal arrows ---
         egui::Key::ArrowLeft => match platform {
@@ -682,3 +682,24 @@ fn frame_focus_retention_command_detection_ignores_tab_only_frames() {
         selection.as_slice()
     ));
 }
+
+#[test]
+fn alt_vertical_arrows_map_to_selection_expansion_on_all_platforms() {
+    let alt = egui::Modifiers {
+        alt: true,
+        ..Default::default()
+    };
+    let events = vec![
+        key_event(egui::Key::ArrowUp, alt),
+        key_event(egui::Key::ArrowDown, alt),
+    ];
+    for platform in [PlatformFlavor::Mac, PlatformFlavor::Other] {
+        assert_eq!(
+            commands_from_events_for_platform(&events, true, platform),
+            vec![
+                VirtualInputCommand::ExpandSelection,
+                VirtualInputCommand::ShrinkSelection,
+            ]
+        );
+    }
+}
@@ -6,6 +6,7 @@ use super::virtual_editor::{
 };
 use super::{is_editor_word_char, next_virtual_click_count, LocalPasteApp};
 use eframe::egui;
+use localpaste_core::indentation::detect_indentation;
 use std::ops::Range;
 use std::time::Instant;
 
@@ -229,6 +230,7 @@ impl LocalPasteApp {
         self.virtual_editor_buffer.reset(text);
         self.virtual_editor_state = VirtualEditorState::default();
         self.virtual_editor_history = VirtualEditorHistory::default();
+        self.indent_style = detect_indentation(text).unwrap_or_default();
         self.virtual_layout = WrapLayoutCache::default();
         self.virtual_galley_cache = VirtualGalleyCache::default();
         self.virtual_line_scratch.clear();
@@ -238,6 +240,8 @@ impl LocalPasteApp {
         self.virtual_pending_scroll_offset_y = None;
         self.virtual_follow_cursor_next_frame = false;
         self.reset_virtual_click_streak();
+        self.writing_mode.word_count = None;
+        self.markdown_outline.reset();
     }
 
     /// Restarts the caret blink timer from the current instant.
@@ -6,6 +6,7 @@ use super::virtual_editor::{
 };
 use super::{LocalPasteApp, VirtualApplyResult};
 use eframe::egui;
+use localpaste_core::indentation::retab;
 use std::ops::Range;
 use std::time::Instant;
 use tracing::info;
@@ -90,6 +91,9 @@ impl LocalPasteApp {
         let touched_lines = inserted_newlines.max(deleted_newlines).saturating_add(1);
         let before_cursor =
             self.clamp_virtual_cursor_for_render(self.virtual_editor_state.cursor());
+        let outline_synced = self
+            .markdown_outline
+            .is_synced_to(&self.virtual_editor_buffer);
         let perf_enabled = self.perf_log_enabled;
         let rope_started = perf_enabled.then(Instant::now);
         let delta = self
@@ -98,6 +102,10 @@ impl LocalPasteApp {
         let rope_apply_ms =
             rope_started.map_or(0.0, |started| started.elapsed().as_secs_f32() * 1000.0);
         if let Some(delta) = delta {
+            if outline_synced {
+                self.markdown_outline
+                    .apply_delta(delta, &self.virtual_editor_buffer);
+            }
             let mut galley_apply_ms = 0.0f32;
             let layout_started = perf_enabled.then(Instant::now);
             let layout_recovered = self.apply_virtual_layout_delta_with_recovery(
@@ -147,6 +155,36 @@ impl LocalPasteApp {
         true
     }
 
+    /// Converts document indentation to the other style as a single undo step.
+    ///
+    /// The caret keeps its char offset (clamped to the new length) instead of
+    /// jumping to the end of the whole-document replacement.
+    ///
+    /// # Returns
+    /// `true` when the buffer changed.
+    pub(super) fn toggle_virtual_indentation(&mut self) -> bool {
+        if !self.is_virtual_editor_mode() || self.reset_transition_active() {
+            return false;
+        }
+        let target = self.indent_style.toggled();
+        self.indent_style = target;
+        let current = self.virtual_editor_buffer.to_string();
+        let converted = retab(current.as_str(), target);
+        if converted == current {
+            return false;
+        }
+        let cursor = self.virtual_editor_state.cursor();
+        let len = self.virtual_editor_buffer.len_chars();
+        let changed =
+            self.replace_virtual_range(0..len, &converted, EditIntent::Other, true, Instant::now());
+        if changed {
+            let len = self.virtual_editor_buffer.len_chars();
+            self.virtual_editor_state.set_cursor(cursor.min(len), len);
+            self.mark_dirty();
+        }
+        changed
+    }
+
     /// Applies normalized input commands to virtual editor state and buffer.
     ///
     /// # Arguments
@@ -179,6 +217,12 @@ impl LocalPasteApp {
                     self.virtual_editor_state
                         .select_all(self.virtual_editor_buffer.len_chars());
                 }
+                VirtualInputCommand::ExpandSelection => {
+                    self.expand_virtual_selection();
+                }
+                VirtualInputCommand::ShrinkSelection => {
+                    self.shrink_virtual_selection();
+                }
                 VirtualInputCommand::Copy => {
                     if let Some(selection) = self.virtual_selected_text() {
                         ctx.send_cmd(egui::OutputCommand::CopyText(selection));
@@ -246,8 +290,9 @@ impl LocalPasteApp {
                         .virtual_editor_state
                         .selection_range()
                         .unwrap_or(cursor..cursor);
+                    let unit = self.indent_style.unit();
                     result.changed |=
-                        self.replace_virtual_range(range, "    ", EditIntent::Insert, true, now);
+                        self.replace_virtual_range(range, &unit, EditIntent::Insert, true, now);
                     self.virtual_editor_state.clear_preferred_column();
                 }
                 VirtualInputCommand::Backspace { word } => {
@@ -7,7 +7,9 @@ mod protocol;
 mod worker;
 
 pub(crate) use protocol::VERSION_WORKFLOW_LIST_LIMIT;
-pub use protocol::{CoreCmd, CoreErrorSource, CoreEvent, PasteSummary};
+pub use protocol::{
+    CoreCmd, CoreErrorSource, CoreEvent, PasteSummary, QueryKind, QueryToken, QueryTokens,
+};
 pub use worker::{
     spawn_backend, spawn_backend_with_locks, spawn_backend_with_locks_and_owner, BackendHandle,
 };
@@ -17,8 +19,10 @@ mod tests {
     use super::*;
     use chrono::{Duration as ChronoDuration, Utc};
     use localpaste_core::db::tables::{PASTE_VERSIONS_CONTENT, PASTE_VERSIONS_META};
+    use localpaste_core::merge::MergeSeparator;
     use localpaste_core::models::folder::Folder;
     use localpaste_core::models::paste::{Paste, VersionMeta};
+    use localpaste_core::timestamps::DisplayTimezone;
     use localpaste_core::Database;
     use ropey::Rope;
     use std::thread;
@@ -144,6 +148,7 @@ mod tests {
             .send(CoreCmd::ListPastes {
                 limit: 10,
                 folder_id: None,
+                token: QueryToken::default(),
             })
             .expect("send list");
 
@@ -172,6 +177,7 @@ mod tests {
             .send(CoreCmd::ListPastes {
                 limit: 10,
                 folder_id: None,
+                token: QueryToken::default(),
             })
             .expect("send initial list");
         match recv_event(&backend.evt_rx) {
@@ -195,6 +201,7 @@ mod tests {
             .send(CoreCmd::ListPastes {
                 limit: 10,
                 folder_id: None,
+                token: QueryToken::default(),
             })
             .expect("send refreshed list");
         match recv_event(&backend.evt_rx) {
@@ -364,22 +371,263 @@ mod tests {
     }
 
     #[test]
-    fn backend_create_uses_random_name_instead_of_content_first_line() {
+    fn backend_gist_import_updates_known_ids_and_export_round_trips() {
+        let TestDb { _dir: guard, db } = setup_db();
+        let existing = Paste::new("old".to_string(), "old".to_string());
+        db.pastes.create(&existing).expect("create paste");
+        let backend = spawn_backend(db, 10 * 1024 * 1024);
+
+        let snippet = |id: &str, text: &str| localpaste_core::import::ImportedSnippet {
+            id: Some(id.to_string()),
+            name: text.to_string(),
+            content: text.to_string(),
+            language: None,
+            tags: vec!["synced".to_string()],
+        };
+        let snippets = vec![
+            snippet(existing.id.as_str(), "new"),
+            snippet("kept-id", "fresh"),
+        ];
+        backend
+            .cmd_tx
+            .send(CoreCmd::ImportSnippets { snippets })
+            .expect("send import");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::SnippetsImported { created, failed } => {
+                assert_eq!((created, failed), (2, 0))
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        let path = guard.path().join("gist");
+        backend
+            .cmd_tx
+            .send(CoreCmd::ExportGistLayout { path: path.clone() })
+            .expect("send export");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::GistLayoutExported { paste_count, .. } => assert_eq!(paste_count, 2),
+            other => panic!("unexpected event: {:?}", other),
+        }
+        let mut entries = localpaste_core::gist_layout::read_gist_layout(&path).expect("read");
+        entries.sort_by(|a, b| a.content.cmp(&b.content));
+        assert_eq!(entries[0].meta.id, "kept-id");
+        assert_eq!(entries[1].meta.id, existing.id);
+        assert_eq!(entries[1].content, "new");
+        assert_eq!(entries[1].meta.tags, vec!["synced".to_string()]);
+    }
+
+    #[test]
+    fn backend_split_creates_files_in_folder() {
         let TestDb { _dir: _guard, db } = setup_db();
+        let folder = Folder::new("Split".to_string());
+        db.folders.create(&folder).expect("create folder");
+        let files = localpaste_core::split::split_concatenated_files(
+            "==> a.rs <==\nfn a() {}\n==> b.txt <==\nnotes\n",
+        );
         let backend = spawn_backend(db, 10 * 1024 * 1024);
-        let content = "this line should not become title\nprint('hello')";
 
+        backend
+            .cmd_tx
+            .send(CoreCmd::SplitPaste {
+                files,
+                folder_id: Some(folder.id.clone()),
+            })
+            .expect("send split");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::PasteSplit { created, failed } => assert_eq!((created, failed), (2, 0)),
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::ListFolders)
+            .expect("send list folders");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::FoldersLoaded { items } => {
+                let split = items
+                    .iter()
+                    .find(|item| item.id == folder.id)
+                    .expect("split folder");
+                assert_eq!(split.paste_count, 2);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn backend_files_clipboard_captures_by_folder_rule() {
+        let TestDb { _dir: _guard, db } = setup_db();
+        let folder = Folder::new("Clips".to_string());
+        db.folders.create(&folder).expect("create folder");
+        let backend = spawn_backend(db, 10 * 1024 * 1024);
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::AddFolderRule {
+                field: localpaste_core::models::folder_rule::RuleField::Source,
+                pattern: "clipboard".to_string(),
+                folder_id: folder.id.clone(),
+            })
+            .expect("send add rule");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::FolderRulesLoaded { rules } => assert_eq!(rules.len(), 1),
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::CreateClipboardPaste {
+                content: "copied".to_string(),
+            })
+            .expect("send clipboard create");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::PasteCreated { paste } => {
+                assert_eq!(paste.folder_id.as_deref(), Some(folder.id.as_str()))
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
         backend
             .cmd_tx
             .send(CoreCmd::CreatePaste {
-                content: content.to_string(),
+                content: "typed".to_string(),
             })
             .expect("send create");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::PasteCreated { paste } => assert!(paste.folder_id.is_none()),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn backend_refreshes_linked_paste_from_source() {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind origin");
+        let url = format!("http://{}/conf.toml", listener.local_addr().expect("addr"));
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept");
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            let _ = stream.write_all(
+                b"HTTP/1.1 200 OK\r\ncontent-length: 9\r\nconnection: close\r\n\r\nport = 2\n",
+            );
+        });
+
+        let TestDb { _dir: _guard, db } = setup_db();
+        let linked = Paste::new("port = 1\n".to_string(), "conf".to_string());
+        let unlinked = Paste::new("x".to_string(), "plain".to_string());
+        db.pastes.create(&linked).expect("create linked");
+        db.pastes.create(&unlinked).expect("create unlinked");
+        db.url_sources
+            .set(&linked.id, url.as_str(), None)
+            .expect("link source");
+        let backend = spawn_backend(db, 10 * 1024 * 1024);
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::RefreshPasteFromSource {
+                id: unlinked.id.clone(),
+            })
+            .expect("send refresh");
+        expect_error_contains(&backend.evt_rx, "no source URL");
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::RefreshPasteFromSource {
+                id: linked.id.clone(),
+            })
+            .expect("send refresh");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::PasteRefreshedFromSource { paste, changed } => {
+                assert!(changed);
+                assert_eq!(paste.content, "port = 2\n");
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn backend_merge_replaces_originals_in_one_transaction() {
+        let TestDb { _dir: _guard, db } = setup_db();
+        let first = Paste::new("one\n".to_string(), "first".to_string());
+        let second = Paste::new("two\n".to_string(), "second".to_string());
+        db.pastes.create(&first).expect("create first");
+        db.pastes.create(&second).expect("create second");
+        let backend = spawn_backend(db, 10 * 1024 * 1024);
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::MergePastes {
+                ids: vec![second.id.clone(), "missing-id".to_string()],
+                name: "broken".to_string(),
+                separator: MergeSeparator::BlankLine,
+                delete_originals: true,
+            })
+            .expect("send failing merge");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::Error { message, .. } => assert!(message.starts_with("Merge failed")),
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::MergePastes {
+                ids: vec![second.id.clone(), first.id.clone()],
+                name: "both".to_string(),
+                separator: MergeSeparator::BlankLine,
+                delete_originals: true,
+            })
+            .expect("send merge");
+        let merged = match recv_event(&backend.evt_rx) {
+            CoreEvent::PastesMerged { paste, deleted } => {
+                assert_eq!(paste.content, "two\n\none\n");
+                assert_eq!(deleted, vec![second.id.clone(), first.id.clone()]);
+                paste
+            }
+            other => panic!("unexpected event: {:?}", other),
+        };
+
+        for (id, expected_missing) in [(first.id.clone(), true), (merged.id.clone(), false)] {
+            backend
+                .cmd_tx
+                .send(CoreCmd::GetPaste { id: id.clone() })
+                .expect("send get");
+            match recv_event(&backend.evt_rx) {
+                CoreEvent::PasteMissing { id: missing } if expected_missing => {
+                    assert_eq!(missing, id)
+                }
+                CoreEvent::PasteLoaded { paste } if !expected_missing => {
+                    assert_eq!(paste.name, "both")
+                }
+                other => panic!("unexpected event: {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn backend_create_derives_title_from_content() {
+        let TestDb { _dir: _guard, db } = setup_db();
+        let backend = spawn_backend(db, 10 * 1024 * 1024);
+        let content = "#!/usr/bin/env python\n# Greet the user\nprint('hello')";
+
+        for content in [content, ""] {
+            backend
+                .cmd_tx
+                .send(CoreCmd::CreatePaste {
+                    content: content.to_string(),
+                })
+                .expect("send create");
+        }
 
         match recv_event(&backend.evt_rx) {
             CoreEvent::PasteCreated { paste } => {
                 assert_eq!(paste.content, content);
-                assert_ne!(paste.name, "this line should not become title");
+                assert_eq!(paste.name, "Greet the user");
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::PasteCreated { paste } => {
                 assert!(paste.name.contains('-'));
                 assert!(!paste.name.contains(' '));
             }
@@ -497,6 +745,93 @@ mod tests {
         }
     }
 
+    #[test]
+    fn backend_merge_paste_keeps_edits_from_other_editors() {
+        use localpaste_core::collab::TextEdit;
+        use localpaste_server::collab::CollabHub;
+        use localpaste_server::{LockOwnerId, PasteLockManager};
+        use std::sync::Arc;
+
+        let TestDb { _dir: _guard, db } = setup_db();
+        let other_editor = db.share().expect("share db");
+        let paste = Paste::new("alpha beta gamma".to_string(), "collab".to_string());
+        let id = paste.id.clone();
+        db.pastes.create(&paste).expect("create paste");
+        let locks = Arc::new(PasteLockManager::default());
+        let hub = Arc::new(CollabHub::default());
+        let backend = spawn_backend_with_locks_and_owner(
+            db,
+            10 * 1024 * 1024,
+            locks.clone(),
+            LockOwnerId::new("gui".to_string()),
+            hub.clone(),
+        );
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::GetPaste { id: id.clone() })
+            .expect("send get");
+        assert!(matches!(
+            recv_event(&backend.evt_rx),
+            CoreEvent::PasteLoaded { .. }
+        ));
+        backend
+            .cmd_tx
+            .send(CoreCmd::PollPasteEdits {
+                id: id.clone(),
+                since_rev: None,
+            })
+            .expect("send poll");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::CollabRevision { id: polled, rev } => {
+                assert_eq!(polled, id);
+                assert_eq!(rev, 0);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        let edit = TextEdit {
+            start: 0,
+            end: 5,
+            text: "ALPHA".to_string(),
+        };
+        hub.merge(&other_editor, &locks, &id, 0, edit, |_, _| Ok(()))
+            .expect("other editor merges")
+            .expect("paste exists");
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::MergePaste {
+                id: id.clone(),
+                base_rev: 0,
+                content: Rope::from_str("alpha beta GAMMA"),
+            })
+            .expect("send merge");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::PasteSaved { paste } => assert_eq!(paste.content, "ALPHA beta GAMMA"),
+            other => panic!("unexpected event: {:?}", other),
+        }
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::CollabRevision { rev, .. } => assert_eq!(rev, 2),
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::PollPasteEdits {
+                id: id.clone(),
+                since_rev: Some(1),
+            })
+            .expect("send poll");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::PasteRemoteEdited { paste, rev } => {
+                assert_eq!(paste.content, "ALPHA beta GAMMA");
+                assert_eq!(rev, 2);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
     #[test]
     fn backend_delete_paste_updates_folder_count() {
         let TestDb { _dir: _guard, db } = setup_db();
@@ -603,6 +938,7 @@ mod tests {
                 limit: 10,
                 folder_id: None,
                 language: None,
+                token: QueryToken::default(),
             })
             .expect("send search");
 
@@ -650,6 +986,7 @@ mod tests {
             .send(CoreCmd::SearchPalette {
                 query: "alpha".to_string(),
                 limit: 10,
+                token: QueryToken::default(),
             })
             .expect("send palette search");
 
@@ -1087,4 +1424,191 @@ mod tests {
             other => panic!("unexpected event: {:?}", other),
         }
     }
+
+    #[test]
+    fn backend_settings_save_replaces_a_corrupt_settings_file() {
+        let dir = TempDir::new().expect("temp dir");
+        let db_path = dir.path().join("db").to_string_lossy().to_string();
+        let db = Database::new(&db_path).expect("db");
+        let settings_path = localpaste_core::settings::settings_file_path_for_db_path(&db_path);
+        std::fs::write(&settings_path, "{\"backup\": {").expect("corrupt settings");
+        let backend = spawn_backend(db, 10 * 1024 * 1024);
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::SaveDisplayTimezone {
+                db_path: db_path.clone(),
+                timezone: DisplayTimezone::Utc,
+            })
+            .expect("send timezone save");
+        assert!(matches!(
+            recv_event(&backend.evt_rx),
+            CoreEvent::DisplayTimezoneSaved {
+                timezone: DisplayTimezone::Utc
+            }
+        ));
+        let settings = localpaste_core::settings::load_settings(&db_path).expect("settings");
+        assert_eq!(settings.display.timezone, DisplayTimezone::Utc);
+        let quarantined = std::fs::read_dir(settings_path.parent().expect("parent"))
+            .expect("list")
+            .filter_map(Result::ok)
+            .any(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with("settings.json.corrupt-")
+            });
+        assert!(quarantined, "corrupt file should be kept aside");
+    }
+
+    #[test]
+    fn backend_backup_commands_create_verify_schedule_and_delete() {
+        let dir = TempDir::new().expect("temp dir");
+        let db_path = dir.path().join("db").to_string_lossy().to_string();
+        let db = Database::new(&db_path).expect("db");
+        db.pastes
+            .create(&Paste::new("backed up".to_string(), "b".to_string()))
+            .expect("create paste");
+        let backend = spawn_backend(db, 10 * 1024 * 1024);
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::CreateBackup {
+                db_path: db_path.clone(),
+            })
+            .expect("send create");
+        let path = match recv_event(&backend.evt_rx) {
+            CoreEvent::BackupCreated {
+                path,
+                remote_failures,
+            } => {
+                assert!(remote_failures.is_empty());
+                path
+            }
+            other => panic!("unexpected event: {:?}", other),
+        };
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::BackupsLoaded {
+                items,
+                pending_restore,
+                interval_hours,
+            } => {
+                assert_eq!(items.len(), 1);
+                assert_eq!(items[0].path, path);
+                assert!(pending_restore.is_none());
+                assert!(interval_hours.is_none());
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::VerifyBackup { path: path.clone() })
+            .expect("send verify");
+        assert!(matches!(
+            recv_event(&backend.evt_rx),
+            CoreEvent::BackupVerified { result: Ok(()), .. }
+        ));
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::ScheduleRestore {
+                db_path: db_path.clone(),
+                path: path.clone(),
+            })
+            .expect("send schedule");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::RestoreScheduled { path: scheduled } => {
+                assert_eq!(scheduled.as_ref(), Some(&path))
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::SaveBackupSchedule {
+                db_path: db_path.clone(),
+                interval_hours: Some(6),
+            })
+            .expect("send schedule save");
+        assert!(matches!(
+            recv_event(&backend.evt_rx),
+            CoreEvent::BackupScheduleSaved {
+                interval_hours: Some(6)
+            }
+        ));
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::SaveDisplayTimezone {
+                db_path: db_path.clone(),
+                timezone: DisplayTimezone::Utc,
+            })
+            .expect("send timezone save");
+        assert!(matches!(
+            recv_event(&backend.evt_rx),
+            CoreEvent::DisplayTimezoneSaved {
+                timezone: DisplayTimezone::Utc
+            }
+        ));
+        let updates = localpaste_core::settings::UpdateSettings {
+            check_enabled: true,
+            dismissed_version: Some("9.9.9".to_string()),
+        };
+        backend
+            .cmd_tx
+            .send(CoreCmd::SaveUpdateSettings {
+                db_path: db_path.clone(),
+                updates: updates.clone(),
+            })
+            .expect("send update settings save");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::UpdateSettingsSaved { updates: saved } => assert_eq!(saved, updates),
+            other => panic!("unexpected event: {:?}", other),
+        }
+        let settings = localpaste_core::settings::load_settings(&db_path).expect("settings");
+        assert_eq!(settings.backup.interval_hours, Some(6));
+        assert_eq!(settings.display.timezone, DisplayTimezone::Utc);
+        assert_eq!(settings.updates, updates);
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::DeleteBackup {
+                db_path: db_path.clone(),
+                path: path.clone(),
+            })
+            .expect("send delete");
+        assert!(matches!(
+            recv_event(&backend.evt_rx),
+            CoreEvent::BackupDeleted { .. }
+        ));
+        assert!(!path.exists());
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::PruneBackups {
+                db_path: db_path.clone(),
+                keep_last: 0,
+            })
+            .expect("send prune");
+        match recv_event(&backend.evt_rx) {
+            CoreEvent::BackupsPruned { deleted } => assert!(deleted.is_empty()),
+            other => panic!("unexpected event: {:?}", other),
+        }
+        assert!(matches!(
+            recv_event(&backend.evt_rx),
+            CoreEvent::BackupsLoaded { .. }
+        ));
+
+        backend
+            .cmd_tx
+            .send(CoreCmd::CheckStorage {
+                db_path: db_path.clone(),
+            })
+            .expect("send storage check");
+        assert!(matches!(
+            recv_event(&backend.evt_rx),
+            CoreEvent::StorageChecked { .. }
+        ));
+    }
 }
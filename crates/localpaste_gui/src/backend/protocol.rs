@@ -1,17 +1,87 @@
 //! Protocol types for the native GUI backend worker.
 
 use chrono::{DateTime, Utc};
+use localpaste_core::db::backup::BackupEntry;
 use localpaste_core::diff::DiffResponse;
+use localpaste_core::import::ImportedSnippet;
+use localpaste_core::maintenance::CleanupReport;
+use localpaste_core::merge::MergeSeparator;
 use localpaste_core::models::{
+    annotation::Annotation,
+    comment::Comment,
+    draft::Draft,
     folder::Folder,
-    paste::{Paste, PasteMeta, VersionMeta, VersionSnapshot},
+    folder_rule::{FolderRule, RuleField},
+    paste::{Paste, PasteMeta, PastePreview, VersionMeta, VersionSnapshot},
 };
 use localpaste_core::semantic::DerivedMeta;
+use localpaste_core::settings::UpdateSettings;
+use localpaste_core::split::SplitFile;
+use localpaste_core::storage_alerts::StorageAlert;
+use localpaste_core::timestamps::DisplayTimezone;
 use ropey::Rope;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 /// Version row count requested by detached history workflows.
 pub(crate) const VERSION_WORKFLOW_LIST_LIMIT: usize = 200;
 
+/// List/search command families that supersede their own earlier requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryKind {
+    List,
+    Search,
+    Palette,
+}
+
+/// Cancellation token carried by list/search commands.
+///
+/// A token is cancelled once a newer token of the same [`QueryKind`] has been
+/// issued, letting the worker skip queued work and abandon long scans whose
+/// results the UI would drop anyway. The default token is never cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct QueryToken {
+    generation: u64,
+    latest: Option<Arc<AtomicU64>>,
+}
+
+impl QueryToken {
+    /// Returns `true` when a newer request of the same kind has been issued.
+    pub fn is_cancelled(&self) -> bool {
+        self.latest
+            .as_ref()
+            .is_some_and(|latest| latest.load(Ordering::Acquire) != self.generation)
+    }
+}
+
+/// Per-kind generation counters shared by the UI thread and backend worker.
+#[derive(Debug, Default)]
+pub struct QueryTokens {
+    list: Arc<AtomicU64>,
+    search: Arc<AtomicU64>,
+    palette: Arc<AtomicU64>,
+}
+
+impl QueryTokens {
+    /// Issue a token for a new request, cancelling earlier tokens of `kind`.
+    ///
+    /// # Returns
+    /// A token that stays live until the next call for the same kind.
+    pub fn next(&self, kind: QueryKind) -> QueryToken {
+        let latest = match kind {
+            QueryKind::List => &self.list,
+            QueryKind::Search => &self.search,
+            QueryKind::Palette => &self.palette,
+        };
+        let generation = latest.fetch_add(1, Ordering::AcqRel) + 1;
+        QueryToken {
+            generation,
+            latest: Some(Arc::clone(latest)),
+        }
+    }
+}
+
 /// Commands issued by the UI thread for the backend worker to execute.
 #[derive(Debug)]
 pub enum CoreCmd {
@@ -19,6 +89,7 @@ pub enum CoreCmd {
     ListPastes {
         limit: usize,
         folder_id: Option<String>,
+        token: QueryToken,
     },
     /// Search pastes with optional folder/language filters.
     SearchPastes {
@@ -26,21 +97,63 @@ pub enum CoreCmd {
         limit: usize,
         folder_id: Option<String>,
         language: Option<String>,
+        token: QueryToken,
     },
     /// Search metadata globally for command palette discovery.
-    SearchPalette { query: String, limit: usize },
+    SearchPalette {
+        query: String,
+        limit: usize,
+        token: QueryToken,
+    },
     /// Load a single paste by id for display in the editor pane.
     GetPaste { id: String },
     /// Load a comparison target for the detached diff modal.
     GetDiffTargetPaste { id: String },
+    /// Load a truncated leading-lines preview for sidebar hover tooltips.
+    GetPastePreview { id: String },
     /// Create a new paste with the provided content.
     CreatePaste { content: String },
+    /// Create one paste per snippet confirmed in the import wizard.
+    ///
+    /// Snippets carrying an id update that paste when it exists.
+    ImportSnippets { snippets: Vec<ImportedSnippet> },
+    /// Create one paste per file confirmed in the split dialog, filed into
+    /// `folder_id` when set. The source paste is left unchanged.
+    SplitPaste {
+        files: Vec<SplitFile>,
+        folder_id: Option<String>,
+    },
+    /// Join `ids` in order into one new paste, deleting the originals in the
+    /// same transaction when `delete_originals` is set.
+    MergePastes {
+        ids: Vec<String>,
+        name: String,
+        separator: MergeSeparator,
+        delete_originals: bool,
+    },
+    /// Create a new paste from captured clipboard text.
+    ///
+    /// Validated against the clipboard-capture size limit instead of the GUI limit.
+    CreateClipboardPaste { content: String },
     /// Persist updated content for an existing paste.
     UpdatePaste { id: String, content: String },
     /// Persist updated content for an existing paste using a rope snapshot.
     ///
     /// This keeps rope->string materialization off the UI thread.
     UpdatePasteVirtual { id: String, content: Rope },
+    /// Merge the edits made since collaboration revision `base_rev` into the
+    /// stored paste, keeping edits other editors committed in the meantime.
+    ///
+    /// Falls back to a full content update when `base_rev` can no longer be
+    /// merged.
+    MergePaste {
+        id: String,
+        base_rev: u64,
+        content: Rope,
+    },
+    /// Report edits other editors committed after `since_rev`, or after the
+    /// revision recorded when the paste was loaded when `since_rev` is `None`.
+    PollPasteEdits { id: String, since_rev: Option<u64> },
     /// Persist metadata changes for an existing paste.
     UpdatePasteMeta {
         id: String,
@@ -58,6 +171,8 @@ pub enum CoreCmd {
     GetPasteVersion { id: String, version_id_ms: u64 },
     /// Reset current paste content to a historical version.
     ResetPasteHardToVersion { id: String, version_id_ms: u64 },
+    /// Re-fetch a paste from its linked source URL.
+    RefreshPasteFromSource { id: String },
     /// Duplicate a paste from a historical version snapshot.
     DuplicatePasteVersion {
         id: String,
@@ -74,6 +189,86 @@ pub enum CoreCmd {
         left_text: String,
         right_text: String,
     },
+    /// Persist unsaved editor content for crash recovery.
+    SaveDraft { draft: Draft },
+    /// Drop a crash-recovery draft once its content is saved or dismissed.
+    DiscardDraft { key: String },
+    /// Load crash-recovery drafts left by a previous session.
+    ListDrafts,
+    /// Load the comment thread for `paste_id`.
+    ListComments { paste_id: String },
+    /// Add a comment to `paste_id`, then reload its thread.
+    AddComment {
+        paste_id: String,
+        author: String,
+        body: String,
+    },
+    /// Delete one comment, then reload the thread.
+    DeleteComment {
+        paste_id: String,
+        comment_id: String,
+    },
+    /// Load the line annotations for `paste_id`.
+    ListAnnotations { paste_id: String },
+    /// Annotate lines `start_line..=end_line` (1-based) of the stored content,
+    /// then reload the annotations.
+    AddAnnotation {
+        paste_id: String,
+        start_line: usize,
+        end_line: usize,
+        author: String,
+        body: String,
+    },
+    /// Delete one annotation, then reload the annotations.
+    DeleteAnnotation {
+        paste_id: String,
+        annotation_id: String,
+    },
+    /// Load the folder auto-organization rules.
+    ListFolderRules,
+    /// Append a rule, then reload the rules.
+    AddFolderRule {
+        field: RuleField,
+        pattern: String,
+        folder_id: String,
+    },
+    /// Delete one rule, then reload the rules.
+    DeleteFolderRule { id: String },
+    /// Store `ids` as the new evaluation order, then reload the rules.
+    ReorderFolderRules { ids: Vec<String> },
+    /// Remove orphaned drafts and repair folder invariants.
+    Cleanup,
+    /// List backups of `db_path` with the pending restore and schedule.
+    ListBackups { db_path: String },
+    /// Snapshot the open database, then push to configured remote targets.
+    CreateBackup { db_path: String },
+    /// Check a backup against its manifest.
+    VerifyBackup { path: PathBuf },
+    /// Delete a backup and its manifest.
+    DeleteBackup { db_path: String, path: PathBuf },
+    /// Delete all but the newest `keep_last` backups, then re-list backups.
+    PruneBackups { db_path: String, keep_last: usize },
+    /// Compare database and backup sizes to the configured alert limits.
+    CheckStorage { db_path: String },
+    /// Verify a backup and restore it on the next launch.
+    ScheduleRestore { db_path: String, path: PathBuf },
+    /// Drop a scheduled restore.
+    CancelRestore { db_path: String },
+    /// Persist the scheduled-backup interval in the settings file.
+    SaveBackupSchedule {
+        db_path: String,
+        interval_hours: Option<u64>,
+    },
+    /// Persist the timestamp display timezone in the settings file.
+    SaveDisplayTimezone {
+        db_path: String,
+        timezone: DisplayTimezone,
+    },
+    /// Persist the update check opt-in and dismissed version in the settings file.
+    SaveUpdateSettings {
+        db_path: String,
+        updates: UpdateSettings,
+    },
     /// Gracefully stop the backend worker.
     ///
     /// When `flush` is true, the worker flushes pending database writes before
@@ -97,6 +292,10 @@ pub enum CoreCmd {
     },
     /// Delete a folder tree and migrate contained pastes to unfiled.
     DeleteFolder { id: String },
+    /// Write a ZIP of every paste under a folder, recursively, to `path`.
+    ExportFolder { id: String, path: PathBuf },
+    /// Write every paste into a gist-style directory layout rooted at `path`.
+    ExportGistLayout { path: PathBuf },
 }
 
 /// Events produced by the backend worker and polled by the UI thread.
@@ -120,6 +319,13 @@ pub enum CoreEvent {
     PasteLoaded { paste: Paste },
     /// Loading a specific paste failed due to backend/storage error.
     PasteLoadFailed { id: String, message: String },
+    /// Response containing a sidebar hover preview.
+    ///
+    /// `preview` is `None` when the paste is missing or the lookup failed.
+    PastePreviewLoaded {
+        id: String,
+        preview: Option<PastePreview>,
+    },
     /// Response containing the detached diff target payload requested by id.
     DiffTargetLoaded { paste: Paste },
     /// The requested detached diff target id no longer exists in the database.
@@ -130,8 +336,17 @@ pub enum CoreEvent {
     PasteCreated { paste: Paste },
     /// Response confirming a paste was updated.
     PasteSaved { paste: Paste },
+    /// Collaboration revision matching the selected paste's last loaded or
+    /// saved content.
+    CollabRevision { id: String, rev: u64 },
+    /// Another editor changed the selected paste; `paste` is the stored result
+    /// at collaboration revision `rev`.
+    PasteRemoteEdited { paste: Paste, rev: u64 },
     /// Response confirming a paste was reset to a historical version.
     PasteResetToVersion { paste: Paste },
+    /// Response to a source refresh; `changed` is false when the remote
+    /// content already matched.
+    PasteRefreshedFromSource { paste: Paste, changed: bool },
     /// Response confirming a paste's metadata was updated.
     PasteMetaSaved { paste: Paste },
     /// Response confirming a paste was deleted.
@@ -150,12 +365,74 @@ pub enum CoreEvent {
     DiffPreviewComputed { request_id: u64, diff: DiffResponse },
     /// The requested paste id no longer exists in the database.
     PasteMissing { id: String },
+    /// Import finished; `created` includes pastes updated from a source id and
+    /// `failed` counts snippets that were too large or not stored.
+    SnippetsImported { created: usize, failed: usize },
+    /// Split finished; `failed` counts files that were too large or not stored.
+    PasteSplit { created: usize, failed: usize },
+    /// Merge committed; `deleted` lists originals removed in the same write.
+    PastesMerged { paste: Paste, deleted: Vec<String> },
+    /// Response containing crash-recovery drafts, newest first.
+    DraftsLoaded { drafts: Vec<Draft> },
+    /// Response containing a paste's comments, oldest first.
+    CommentsLoaded {
+        paste_id: String,
+        comments: Vec<Comment>,
+    },
+    /// Response containing a paste's line annotations in line order.
+    AnnotationsLoaded {
+        paste_id: String,
+        annotations: Vec<Annotation>,
+    },
+    /// Response containing every folder rule in evaluation order.
+    FolderRulesLoaded { rules: Vec<FolderRule> },
+    /// Cleanup finished; `compacted` is always false for the open GUI database.
+    CleanupFinished { report: CleanupReport },
+    /// Response containing backups (newest first), the scheduled restore, and
+    /// the scheduled-backup interval.
+    BackupsLoaded {
+        items: Vec<BackupEntry>,
+        pending_restore: Option<PathBuf>,
+        interval_hours: Option<u64>,
+    },
+    /// A backup was written; `remote_failures` lists remote targets that failed.
+    BackupCreated {
+        path: PathBuf,
+        remote_failures: Vec<String>,
+    },
+    /// Verification finished for a backup.
+    BackupVerified {
+        path: PathBuf,
+        result: Result<(), String>,
+    },
+    /// Response confirming a backup was deleted.
+    BackupDeleted { path: PathBuf },
+    /// Backups removed by a prune, newest first.
+    BackupsPruned { deleted: Vec<PathBuf> },
+    /// Storage size alerts; empty when everything is within limits.
+    StorageChecked { alerts: Vec<StorageAlert> },
+    /// The scheduled restore changed; `None` means it was cancelled.
+    RestoreScheduled { path: Option<PathBuf> },
+    /// Response confirming the scheduled-backup interval was saved.
+    BackupScheduleSaved { interval_hours: Option<u64> },
+    /// Response confirming the display timezone was saved.
+    DisplayTimezoneSaved { timezone: DisplayTimezone },
+    /// Response confirming the update check settings were saved.
+    UpdateSettingsSaved { updates: UpdateSettings },
     /// Response containing current folder list.
     FoldersLoaded { items: Vec<Folder> },
     /// Response confirming a folder was created/updated.
     FolderSaved { folder: Folder },
     /// Response confirming a folder tree was deleted.
     FolderDeleted { id: String },
+    /// Response confirming a folder archive was written.
+    FolderExported {
+        id: String,
+        path: PathBuf,
+        paste_count: usize,
+    },
+    /// Gist layout export finished writing `paste_count` pastes under `path`.
+    GistLayoutExported { path: PathBuf, paste_count: usize },
     /// Backend worker has finished shutdown processing.
     ShutdownComplete {
         /// Result of optional database flush requested by shutdown command.
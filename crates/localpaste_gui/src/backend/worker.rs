@@ -1,13 +1,24 @@
 //! Background worker thread for database access.
 
+mod annotation;
+mod backup;
+mod collab;
+mod comment;
+mod draft;
 mod folder;
+mod folder_rule;
 mod paste;
 mod query;
+mod settings;
+mod url_source;
 
-use crate::backend::{CoreCmd, CoreErrorSource, CoreEvent};
+use crate::backend::{CoreCmd, CoreErrorSource, CoreEvent, QueryTokens};
 use crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Sender};
-use localpaste_core::{config::env_flag_enabled, Database};
+use localpaste_core::config::{env_flag_enabled, RECENT_HISTORY_ENV};
+use localpaste_core::{Database, PasteSizeLimits, PasteSource};
+use localpaste_server::collab::CollabHub;
 use localpaste_server::{LockOwnerId, PasteLockManager};
+use std::collections::VecDeque;
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
@@ -16,6 +27,8 @@ use std::time::{Duration, Instant};
 pub struct BackendHandle {
     pub cmd_tx: Sender<CoreCmd>,
     pub evt_rx: Receiver<CoreEvent>,
+    /// Issues list/search tokens; each new token cancels older ones of its kind.
+    pub query_tokens: QueryTokens,
     worker_join: Option<thread::JoinHandle<()>>,
 }
 
@@ -115,6 +128,7 @@ impl BackendHandle {
         Self {
             cmd_tx,
             evt_rx,
+            query_tokens: QueryTokens::default(),
             worker_join: None,
         }
     }
@@ -124,12 +138,37 @@ struct WorkerState {
     db: Database,
     evt_tx: Sender<CoreEvent>,
     max_paste_size: usize,
+    size_limits: PasteSizeLimits,
     locks: Arc<PasteLockManager>,
     lock_owner_id: LockOwnerId,
+    collab: Arc<CollabHub>,
+    collab_snapshots: VecDeque<collab::CollabSnapshot>,
     perf_log_enabled: bool,
+    /// Record opened pastes as `gui` in the shared recent history.
+    recent_history: bool,
     query_cache: query::QueryCache,
 }
 
+impl WorkerState {
+    fn max_paste_size_for(&self, source: PasteSource, folder_id: Option<&str>) -> usize {
+        self.size_limits
+            .resolve(self.max_paste_size, source, folder_id)
+    }
+
+    /// Folder of an existing paste, read only when folder overrides exist.
+    fn folder_for_size_limit(&self, id: &str) -> Option<String> {
+        if self.size_limits.folders.is_empty() {
+            return None;
+        }
+        self.db
+            .pastes
+            .get(id)
+            .ok()
+            .flatten()
+            .and_then(|paste| paste.folder_id)
+    }
+}
+
 fn send_error(evt_tx: &Sender<CoreEvent>, source: CoreErrorSource, message: String) {
     let _ = evt_tx.send(CoreEvent::Error { source, message });
 }
@@ -151,8 +190,12 @@ fn validate_paste_size(content: &str, max_paste_size: usize) -> Result<(), Strin
 
 fn dispatch_command(state: &mut WorkerState, cmd: CoreCmd) -> bool {
     match cmd {
-        CoreCmd::ListPastes { limit, folder_id } => {
-            query::handle_list_pastes(state, limit, folder_id);
+        CoreCmd::ListPastes {
+            limit,
+            folder_id,
+            token,
+        } => {
+            query::handle_list_pastes(state, limit, folder_id, token);
             true
         }
         CoreCmd::SearchPastes {
@@ -160,6 +203,7 @@ fn dispatch_command(state: &mut WorkerState, cmd: CoreCmd) -> bool {
             limit,
             folder_id,
             language,
+            token,
         } => {
             query::handle_search(
                 state,
@@ -169,11 +213,16 @@ fn dispatch_command(state: &mut WorkerState, cmd: CoreCmd) -> bool {
                 },
                 query,
                 limit,
+                token,
             );
             true
         }
-        CoreCmd::SearchPalette { query, limit } => {
-            query::handle_search(state, query::SearchRoute::Palette, query, limit);
+        CoreCmd::SearchPalette {
+            query,
+            limit,
+            token,
+        } => {
+            query::handle_search(state, query::SearchRoute::Palette, query, limit, token);
             true
         }
         CoreCmd::GetPaste { id } => {
@@ -184,8 +233,33 @@ fn dispatch_command(state: &mut WorkerState, cmd: CoreCmd) -> bool {
             paste::handle_get_diff_target_paste(state, id);
             true
         }
+        CoreCmd::GetPastePreview { id } => {
+            paste::handle_get_paste_preview(state, id);
+            true
+        }
         CoreCmd::CreatePaste { content } => {
-            paste::handle_create_paste(state, content);
+            paste::handle_create_paste(state, content, PasteSource::Gui);
+            true
+        }
+        CoreCmd::CreateClipboardPaste { content } => {
+            paste::handle_create_paste(state, content, PasteSource::ClipboardCapture);
+            true
+        }
+        CoreCmd::ImportSnippets { snippets } => {
+            paste::handle_import_snippets(state, snippets);
+            true
+        }
+        CoreCmd::SplitPaste { files, folder_id } => {
+            paste::handle_split_paste(state, files, folder_id);
+            true
+        }
+        CoreCmd::MergePastes {
+            ids,
+            name,
+            separator,
+            delete_originals,
+        } => {
+            paste::handle_merge_pastes(state, ids, name, separator, delete_originals);
             true
         }
         CoreCmd::UpdatePaste { id, content } => {
@@ -196,6 +270,18 @@ fn dispatch_command(state: &mut WorkerState, cmd: CoreCmd) -> bool {
             paste::handle_update_paste_virtual(state, id, content);
             true
         }
+        CoreCmd::MergePaste {
+            id,
+            base_rev,
+            content,
+        } => {
+            collab::handle_merge_paste(state, id, base_rev, content);
+            true
+        }
+        CoreCmd::PollPasteEdits { id, since_rev } => {
+            collab::handle_poll_paste_edits(state, id, since_rev);
+            true
+        }
         CoreCmd::UpdatePasteMeta {
             id,
             name,
@@ -231,6 +317,10 @@ fn dispatch_command(state: &mut WorkerState, cmd: CoreCmd) -> bool {
             paste::handle_reset_paste_hard_to_version(state, id, version_id_ms);
             true
         }
+        CoreCmd::RefreshPasteFromSource { id } => {
+            url_source::handle_refresh_from_source(state, id);
+            true
+        }
         CoreCmd::DuplicatePasteVersion {
             id,
             version_id_ms,
@@ -247,6 +337,135 @@ fn dispatch_command(state: &mut WorkerState, cmd: CoreCmd) -> bool {
             paste::handle_compute_diff_preview(state, request_id, left_text, right_text);
             true
         }
+        CoreCmd::SaveDraft { draft } => {
+            draft::handle_save_draft(state, draft);
+            true
+        }
+        CoreCmd::DiscardDraft { key } => {
+            draft::handle_discard_draft(state, key);
+            true
+        }
+        CoreCmd::ListDrafts => {
+            draft::handle_list_drafts(state);
+            true
+        }
+        CoreCmd::ListComments { paste_id } => {
+            comment::handle_list_comments(state, paste_id);
+            true
+        }
+        CoreCmd::AddComment {
+            paste_id,
+            author,
+            body,
+        } => {
+            comment::handle_add_comment(state, paste_id, author, body);
+            true
+        }
+        CoreCmd::DeleteComment {
+            paste_id,
+            comment_id,
+        } => {
+            comment::handle_delete_comment(state, paste_id, comment_id);
+            true
+        }
+        CoreCmd::ListAnnotations { paste_id } => {
+            annotation::handle_list_annotations(state, paste_id);
+            true
+        }
+        CoreCmd::AddAnnotation {
+            paste_id,
+            start_line,
+            end_line,
+            author,
+            body,
+        } => {
+            annotation::handle_add_annotation(
+                state,
+                paste_id,
+                (start_line, end_line),
+                author,
+                body,
+            );
+            true
+        }
+        CoreCmd::DeleteAnnotation {
+            paste_id,
+            annotation_id,
+        } => {
+            annotation::handle_delete_annotation(state, paste_id, annotation_id);
+            true
+        }
+        CoreCmd::ListFolderRules => {
+            folder_rule::handle_list_folder_rules(state);
+            true
+        }
+        CoreCmd::AddFolderRule {
+            field,
+            pattern,
+            folder_id,
+        } => {
+            folder_rule::handle_add_folder_rule(state, field, pattern, folder_id);
+            true
+        }
+        CoreCmd::DeleteFolderRule { id } => {
+            folder_rule::handle_delete_folder_rule(state, id);
+            true
+        }
+        CoreCmd::ReorderFolderRules { ids } => {
+            folder_rule::handle_reorder_folder_rules(state, ids);
+            true
+        }
+        CoreCmd::Cleanup => {
+            draft::handle_cleanup(state);
+            true
+        }
+        CoreCmd::ListBackups { db_path } => {
+            backup::handle_list_backups(state, db_path);
+            true
+        }
+        CoreCmd::CreateBackup { db_path } => {
+            backup::handle_create_backup(state, db_path);
+            true
+        }
+        CoreCmd::VerifyBackup { path } => {
+            backup::handle_verify_backup(state, path);
+            true
+        }
+        CoreCmd::DeleteBackup { db_path, path } => {
+            backup::handle_delete_backup(state, db_path, path);
+            true
+        }
+        CoreCmd::PruneBackups { db_path, keep_last } => {
+            backup::handle_prune_backups(state, db_path, keep_last);
+            true
+        }
+        CoreCmd::CheckStorage { db_path } => {
+            backup::handle_check_storage(state, db_path);
+            true
+        }
+        CoreCmd::ScheduleRestore { db_path, path } => {
+            backup::handle_schedule_restore(state, db_path, path);
+            true
+        }
+        CoreCmd::CancelRestore { db_path } => {
+            backup::handle_cancel_restore(state, db_path);
+            true
+        }
+        CoreCmd::SaveBackupSchedule {
+            db_path,
+            interval_hours,
+        } => {
+            backup::handle_save_backup_schedule(state, db_path, interval_hours);
+            true
+        }
+        CoreCmd::SaveDisplayTimezone { db_path, timezone } => {
+            settings::handle_save_display_timezone(state, db_path, timezone);
+            true
+        }
+        CoreCmd::SaveUpdateSettings { db_path, updates } => {
+            settings::handle_save_update_settings(state, db_path, updates);
+            true
+        }
         CoreCmd::ListFolders => {
             folder::handle_list_folders(state);
             true
@@ -267,6 +486,14 @@ fn dispatch_command(state: &mut WorkerState, cmd: CoreCmd) -> bool {
             folder::handle_delete_folder(state, id);
             true
         }
+        CoreCmd::ExportFolder { id, path } => {
+            folder::handle_export_folder(state, id, path);
+            true
+        }
+        CoreCmd::ExportGistLayout { path } => {
+            paste::handle_export_gist_layout(state, path);
+            true
+        }
         CoreCmd::Shutdown { flush } => {
             let flush_result = if flush {
                 state.db.flush().map_err(|err| err.to_string())
@@ -321,6 +548,7 @@ pub fn spawn_backend_with_locks(
         max_paste_size,
         locks,
         crate::lock_owner::next_lock_owner_id("gui-backend-worker"),
+        Arc::default(),
     )
 }
 
@@ -331,6 +559,8 @@ pub fn spawn_backend_with_locks(
 /// - `max_paste_size`: Maximum allowed paste content size in bytes.
 /// - `locks`: Shared paste lock manager used for lock-aware operations.
 /// - `lock_owner_id`: Owner id representing this backend's in-process GUI owner.
+/// - `collab`: Edit history shared with the embedded server's collaborative
+///   edit endpoints.
 ///
 /// # Returns
 /// A [`BackendHandle`] containing the command sender and event receiver.
@@ -342,6 +572,7 @@ pub fn spawn_backend_with_locks_and_owner(
     max_paste_size: usize,
     locks: Arc<PasteLockManager>,
     lock_owner_id: LockOwnerId,
+    collab: Arc<CollabHub>,
 ) -> BackendHandle {
     let (cmd_tx, cmd_rx) = unbounded();
     let (evt_tx, evt_rx) = unbounded();
@@ -354,9 +585,13 @@ pub fn spawn_backend_with_locks_and_owner(
                 db,
                 evt_tx,
                 max_paste_size,
+                size_limits: PasteSizeLimits::from_env(),
                 locks,
                 lock_owner_id,
+                collab,
+                collab_snapshots: VecDeque::new(),
                 perf_log_enabled: env_flag_enabled("LOCALPASTE_BACKEND_PERF_LOG"),
+                recent_history: env_flag_enabled(RECENT_HISTORY_ENV),
                 query_cache: query::QueryCache::default(),
             };
             for cmd in cmd_rx.iter() {
@@ -370,6 +605,7 @@ pub fn spawn_backend_with_locks_and_owner(
     BackendHandle {
         cmd_tx,
         evt_rx,
+        query_tokens: QueryTokens::default(),
         worker_join: Some(worker_join),
     }
 }
@@ -0,0 +1,72 @@
+//! Line annotation handlers for the GUI backend worker.
+
+use super::{send_error, WorkerState};
+use crate::backend::{CoreErrorSource, CoreEvent};
+use tracing::error;
+
+/// Loads a paste's annotations and emits `AnnotationsLoaded` or an error event.
+pub(super) fn handle_list_annotations(state: &mut WorkerState, paste_id: String) {
+    match state.db.annotations.list(paste_id.as_str()) {
+        Ok(annotations) => {
+            let _ = state.evt_tx.send(CoreEvent::AnnotationsLoaded {
+                paste_id,
+                annotations,
+            });
+        }
+        Err(err) => {
+            error!("backend list annotations failed: {}", err);
+            send_error(
+                &state.evt_tx,
+                CoreErrorSource::Other,
+                format!("Load annotations failed: {}", err),
+            );
+        }
+    }
+}
+
+/// Stores a new annotation, then reloads the paste's annotations.
+pub(super) fn handle_add_annotation(
+    state: &mut WorkerState,
+    paste_id: String,
+    lines: (usize, usize),
+    author: String,
+    body: String,
+) {
+    if let Err(err) = state.db.annotations.create(
+        paste_id.as_str(),
+        lines,
+        author.as_str(),
+        None,
+        body.as_str(),
+    ) {
+        send_error(
+            &state.evt_tx,
+            CoreErrorSource::Other,
+            format!("Add annotation failed: {}", err),
+        );
+        return;
+    }
+    handle_list_annotations(state, paste_id);
+}
+
+/// Deletes one annotation, then reloads the paste's annotations.
+pub(super) fn handle_delete_annotation(
+    state: &mut WorkerState,
+    paste_id: String,
+    annotation_id: String,
+) {
+    if let Err(err) = state
+        .db
+        .annotations
+        .delete(paste_id.as_str(), annotation_id.as_str())
+    {
+        error!("backend delete annotation failed: {}", err);
+        send_error(
+            &state.evt_tx,
+            CoreErrorSource::Other,
+            format!("Delete annotation failed: {}", err),
+        );
+        return;
+    }
+    handle_list_annotations(state, paste_id);
+}
@@ -0,0 +1,155 @@
+//! Backup management and storage size handlers for the GUI backend worker.
+
+use super::{send_error, WorkerState};
+use crate::backend::{CoreErrorSource, CoreEvent};
+use localpaste_core::db::backup::{verify_backup, BackupManager};
+use localpaste_core::db::backup_remote::push_backup_to_remote_targets;
+use localpaste_core::settings::load_settings;
+use localpaste_core::storage_alerts::check_storage;
+use std::path::PathBuf;
+use tracing::error;
+
+fn report_error(state: &WorkerState, action: &str, err: impl std::fmt::Display) {
+    error!("backend {} failed: {}", action.to_ascii_lowercase(), err);
+    send_error(
+        &state.evt_tx,
+        CoreErrorSource::Other,
+        format!("{} failed: {}", action, err),
+    );
+}
+
+/// Lists backups and emits `BackupsLoaded` or an error event.
+///
+/// A malformed settings file is reported instead of hiding the backup list.
+pub(super) fn handle_list_backups(state: &mut WorkerState, db_path: String) {
+    let manager = BackupManager::new(&db_path);
+    let items = match manager.list_backups() {
+        Ok(items) => items,
+        Err(err) => return report_error(state, "List backups", err),
+    };
+    let interval_hours = match load_settings(&db_path) {
+        Ok(settings) => settings.backup.interval_hours,
+        Err(err) => {
+            report_error(state, "Load settings", err);
+            None
+        }
+    };
+    let _ = state.evt_tx.send(CoreEvent::BackupsLoaded {
+        items,
+        pending_restore: manager.pending_restore(),
+        interval_hours,
+    });
+}
+
+/// Creates a backup of the open database, pushes it to remote targets, and
+/// re-lists backups.
+pub(super) fn handle_create_backup(state: &mut WorkerState, db_path: String) {
+    let created = BackupManager::new(&db_path).create_backup(state.db.db.as_ref());
+    let path = match created {
+        Ok(path) if !path.is_empty() => PathBuf::from(path),
+        Ok(_) => return report_error(state, "Backup", "no database file to back up"),
+        Err(err) => return report_error(state, "Backup", err),
+    };
+    let remote_failures = match push_backup_to_remote_targets(&db_path, &path) {
+        Ok(reports) => reports
+            .into_iter()
+            .filter_map(|report| report.outcome.err().map(|_| report.target))
+            .collect(),
+        Err(err) => {
+            report_error(state, "Remote backup push", err);
+            Vec::new()
+        }
+    };
+    let _ = state.evt_tx.send(CoreEvent::BackupCreated {
+        path,
+        remote_failures,
+    });
+    handle_list_backups(state, db_path);
+}
+
+/// Checks a backup against its manifest and emits `BackupVerified`.
+pub(super) fn handle_verify_backup(state: &mut WorkerState, path: PathBuf) {
+    let result = verify_backup(&path)
+        .map(|_| ())
+        .map_err(|err| err.to_string());
+    let _ = state
+        .evt_tx
+        .send(CoreEvent::BackupVerified { path, result });
+}
+
+/// Deletes a backup and emits `BackupDeleted` or an error event.
+pub(super) fn handle_delete_backup(state: &mut WorkerState, db_path: String, path: PathBuf) {
+    match BackupManager::new(&db_path).delete_backup(&path) {
+        Ok(()) => {
+            let _ = state.evt_tx.send(CoreEvent::BackupDeleted { path });
+        }
+        Err(err) => report_error(state, "Delete backup", err),
+    }
+}
+
+/// Deletes all but the newest `keep_last` backups, emits `BackupsPruned`, and
+/// re-lists backups.
+pub(super) fn handle_prune_backups(state: &mut WorkerState, db_path: String, keep_last: usize) {
+    match BackupManager::new(&db_path).prune_backups(keep_last) {
+        Ok(deleted) => {
+            let _ = state.evt_tx.send(CoreEvent::BackupsPruned { deleted });
+            handle_list_backups(state, db_path);
+        }
+        Err(err) => report_error(state, "Prune backups", err),
+    }
+}
+
+/// Checks database and backup sizes against the env-configured alert limits
+/// and emits `StorageChecked`.
+pub(super) fn handle_check_storage(state: &mut WorkerState, db_path: String) {
+    let limits = localpaste_core::config::storage_alert_limits_from_env_or_default();
+    match check_storage(&db_path, limits) {
+        Ok(alerts) => {
+            let _ = state.evt_tx.send(CoreEvent::StorageChecked { alerts });
+        }
+        Err(err) => report_error(state, "Storage check", err),
+    }
+}
+
+/// Verifies a backup and schedules it to be restored on the next launch.
+pub(super) fn handle_schedule_restore(state: &mut WorkerState, db_path: String, path: PathBuf) {
+    match BackupManager::new(&db_path).schedule_restore(&path) {
+        Ok(()) => {
+            let _ = state
+                .evt_tx
+                .send(CoreEvent::RestoreScheduled { path: Some(path) });
+        }
+        Err(err) => report_error(state, "Schedule restore", err),
+    }
+}
+
+/// Drops a scheduled restore.
+pub(super) fn handle_cancel_restore(state: &mut WorkerState, db_path: String) {
+    match BackupManager::new(&db_path).cancel_pending_restore() {
+        Ok(()) => {
+            let _ = state
+                .evt_tx
+                .send(CoreEvent::RestoreScheduled { path: None });
+        }
+        Err(err) => report_error(state, "Cancel restore", err),
+    }
+}
+
+/// Saves the scheduled-backup interval, keeping the rest of the settings file.
+pub(super) fn handle_save_backup_schedule(
+    state: &mut WorkerState,
+    db_path: String,
+    interval_hours: Option<u64>,
+) {
+    let saved = super::settings::update_settings(&db_path, |settings| {
+        settings.backup.interval_hours = interval_hours;
+    });
+    match saved {
+        Ok(()) => {
+            let _ = state
+                .evt_tx
+                .send(CoreEvent::BackupScheduleSaved { interval_hours });
+        }
+        Err(err) => report_error(state, "Save backup schedule", err),
+    }
+}
@@ -0,0 +1,196 @@
+//! Collaborative merge and poll handlers for the selected paste.
+//!
+//! The worker keeps the stored content for the last few collaboration
+//! revisions it reported, so a save can be sent to the hub as one edit
+//! against the revision the UI last absorbed.
+
+use super::{paste::apply_content_update, send_error, validate_paste_size, WorkerState};
+use crate::backend::{CoreErrorSource, CoreEvent};
+use localpaste_core::{collab::TextEdit, models::paste::Paste, AppError, PasteSource};
+use ropey::Rope;
+use tracing::{error, warn};
+
+/// Revisions kept per worker; the UI only ever bases a save on the latest.
+const COLLAB_SNAPSHOT_LIMIT: usize = 2;
+
+/// Stored content at one collaboration revision.
+#[derive(Debug)]
+pub(super) struct CollabSnapshot {
+    id: String,
+    rev: u64,
+    content: String,
+}
+
+/// Revision of `paste`'s stored content, or `None` when a newer write has
+/// already replaced it.
+fn revision_of(state: &WorkerState, paste: &Paste) -> Option<u64> {
+    let rev = match state.collab.revision(&state.db, paste.id.as_str()) {
+        Ok(rev) => rev?,
+        Err(err) => {
+            warn!("backend collab revision failed: {}", err);
+            return None;
+        }
+    };
+    let meta = state.db.pastes.get_meta(paste.id.as_str()).ok()??;
+    (meta.updated_at == paste.updated_at).then_some(rev)
+}
+
+fn remember(state: &mut WorkerState, rev: u64, paste: &Paste) {
+    state
+        .collab_snapshots
+        .retain(|snapshot| snapshot.id != paste.id || snapshot.rev != rev);
+    if state.collab_snapshots.len() == COLLAB_SNAPSHOT_LIMIT {
+        state.collab_snapshots.pop_front();
+    }
+    state.collab_snapshots.push_back(CollabSnapshot {
+        id: paste.id.clone(),
+        rev,
+        content: paste.content.clone(),
+    });
+}
+
+/// Record the revision of a paste just loaded for editing.
+pub(super) fn remember_loaded(state: &mut WorkerState, paste: &Paste) {
+    if let Some(rev) = revision_of(state, paste) {
+        remember(state, rev, paste);
+    }
+}
+
+fn remember_and_report(state: &mut WorkerState, paste: &Paste) {
+    if let Some(rev) = revision_of(state, paste) {
+        remember(state, rev, paste);
+        let _ = state.evt_tx.send(CoreEvent::CollabRevision {
+            id: paste.id.clone(),
+            rev,
+        });
+    }
+}
+
+/// Merges the edits made since `base_rev` into the stored paste.
+///
+/// Emits `PasteSaved` then `CollabRevision` on success. When `base_rev` is no
+/// longer known or mergeable, the content is saved as a full update instead.
+///
+/// # Arguments
+/// - `state`: Worker state containing db, locks, and event channel handles.
+/// - `id`: Target paste id.
+/// - `base_rev`: Collaboration revision the UI's edits were made against.
+/// - `content`: Current editor content.
+pub(super) fn handle_merge_paste(
+    state: &mut WorkerState,
+    id: String,
+    base_rev: u64,
+    content: Rope,
+) {
+    let content = content.to_string();
+    let base = state
+        .collab_snapshots
+        .iter()
+        .find(|snapshot| snapshot.id == id && snapshot.rev == base_rev);
+    let Some(base) = base else {
+        fall_back_to_update(state, id, content);
+        return;
+    };
+    let edit = TextEdit::between(&base.content, &content).unwrap_or(TextEdit {
+        start: 0,
+        end: 0,
+        text: String::new(),
+    });
+    let merged = state.collab.merge(
+        &state.db,
+        &state.locks,
+        id.as_str(),
+        base_rev,
+        edit,
+        |paste, merged| {
+            let max_paste_size =
+                state.max_paste_size_for(PasteSource::Gui, paste.folder_id.as_deref());
+            validate_paste_size(merged, max_paste_size).map_err(AppError::BadRequest)
+        },
+    );
+    match merged {
+        Ok(Some(outcome)) => {
+            state.query_cache.invalidate();
+            remember(state, outcome.rev, &outcome.paste);
+            let _ = state.evt_tx.send(CoreEvent::PasteSaved {
+                paste: outcome.paste,
+            });
+            let _ = state.evt_tx.send(CoreEvent::CollabRevision {
+                id,
+                rev: outcome.rev,
+            });
+        }
+        Ok(None) => {
+            state.query_cache.invalidate();
+            let _ = state.evt_tx.send(CoreEvent::PasteMissing { id });
+        }
+        Err(AppError::Conflict(_)) => fall_back_to_update(state, id, content),
+        Err(err) => {
+            error!("backend merge failed: {}", err);
+            send_error(
+                &state.evt_tx,
+                CoreErrorSource::SaveContent,
+                format!("Update failed: {}", err),
+            );
+        }
+    }
+}
+
+fn fall_back_to_update(state: &mut WorkerState, id: String, content: String) {
+    if let Some(paste) = apply_content_update(state, id, content, "backend merge fallback failed") {
+        remember_and_report(state, &paste);
+    }
+}
+
+/// Reports edits other editors committed to a paste.
+///
+/// Emits `PasteRemoteEdited` when the stored content moved past `since_rev`,
+/// and `CollabRevision` when a first poll finds nothing new.
+///
+/// # Arguments
+/// - `state`: Worker state containing db and event channel handles.
+/// - `id`: Selected paste id.
+/// - `since_rev`: Revision the UI has absorbed, or `None` for the revision
+///   recorded when the paste was loaded.
+pub(super) fn handle_poll_paste_edits(state: &mut WorkerState, id: String, since_rev: Option<u64>) {
+    let loaded_rev = state
+        .collab_snapshots
+        .iter()
+        .rev()
+        .find(|snapshot| snapshot.id == id)
+        .map(|snapshot| snapshot.rev);
+    let Some(base_rev) = since_rev.or(loaded_rev) else {
+        return;
+    };
+    let current = match state.collab.revision(&state.db, id.as_str()) {
+        Ok(current) => current,
+        Err(err) => {
+            warn!("backend collab poll failed: {}", err);
+            return;
+        }
+    };
+    match current {
+        Some(rev) if rev == base_rev => {
+            if since_rev.is_none() {
+                let _ = state.evt_tx.send(CoreEvent::CollabRevision { id, rev });
+            }
+        }
+        Some(_) => match state.db.pastes.get(id.as_str()) {
+            Ok(Some(paste)) => {
+                if let Some(rev) = revision_of(state, &paste) {
+                    remember(state, rev, &paste);
+                    let _ = state
+                        .evt_tx
+                        .send(CoreEvent::PasteRemoteEdited { paste, rev });
+                }
+            }
+            Ok(None) => {
+                let _ = state.evt_tx.send(CoreEvent::PasteMissing { id });
+            }
+            Err(err) => warn!("backend collab poll failed: {}", err),
+        },
+        None => {
+            let _ = state.evt_tx.send(CoreEvent::PasteMissing { id });
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Paste comment thread handlers for the GUI backend worker.
+
+use super::{send_error, WorkerState};
+use crate::backend::{CoreErrorSource, CoreEvent};
+use tracing::error;
+
+/// Loads a paste's comments and emits `CommentsLoaded` or an error event.
+pub(super) fn handle_list_comments(state: &mut WorkerState, paste_id: String) {
+    match state.db.comments.list(paste_id.as_str()) {
+        Ok(comments) => {
+            let _ = state
+                .evt_tx
+                .send(CoreEvent::CommentsLoaded { paste_id, comments });
+        }
+        Err(err) => {
+            error!("backend list comments failed: {}", err);
+            send_error(
+                &state.evt_tx,
+                CoreErrorSource::Other,
+                format!("Load comments failed: {}", err),
+            );
+        }
+    }
+}
+
+/// Stores a new comment, then reloads the thread.
+pub(super) fn handle_add_comment(
+    state: &mut WorkerState,
+    paste_id: String,
+    author: String,
+    body: String,
+) {
+    if let Err(err) =
+        state
+            .db
+            .comments
+            .create(paste_id.as_str(), author.as_str(), None, body.as_str())
+    {
+        send_error(
+            &state.evt_tx,
+            CoreErrorSource::Other,
+            format!("Add comment failed: {}", err),
+        );
+        return;
+    }
+    handle_list_comments(state, paste_id);
+}
+
+/// Deletes one comment, then reloads the thread.
+pub(super) fn handle_delete_comment(state: &mut WorkerState, paste_id: String, comment_id: String) {
+    if let Err(err) = state
+        .db
+        .comments
+        .delete(paste_id.as_str(), comment_id.as_str())
+    {
+        error!("backend delete comment failed: {}", err);
+        send_error(
+            &state.evt_tx,
+            CoreErrorSource::Other,
+            format!("Delete comment failed: {}", err),
+        );
+        return;
+    }
+    handle_list_comments(state, paste_id);
+}
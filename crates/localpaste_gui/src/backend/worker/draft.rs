@@ -0,0 +1,58 @@
+//! Crash-recovery draft and cleanup handlers for the GUI backend worker.
+
+use super::{send_error, WorkerState};
+use crate::backend::{CoreErrorSource, CoreEvent};
+use localpaste_core::maintenance::cleanup_database;
+use localpaste_core::models::draft::Draft;
+use tracing::{error, warn};
+
+/// Persists a draft snapshot; failures are logged because drafts are best-effort.
+pub(super) fn handle_save_draft(state: &mut WorkerState, draft: Draft) {
+    if let Err(err) = state.db.drafts.put(&draft) {
+        warn!("backend save draft '{}' failed: {}", draft.key, err);
+    }
+}
+
+/// Removes the draft stored under `key`.
+pub(super) fn handle_discard_draft(state: &mut WorkerState, key: String) {
+    if let Err(err) = state.db.drafts.delete(key.as_str()) {
+        warn!("backend discard draft '{}' failed: {}", key, err);
+    }
+}
+
+/// Loads stored drafts and emits `DraftsLoaded` or an error event.
+pub(super) fn handle_list_drafts(state: &mut WorkerState) {
+    match state.db.drafts.list() {
+        Ok(drafts) => {
+            let _ = state.evt_tx.send(CoreEvent::DraftsLoaded { drafts });
+        }
+        Err(err) => {
+            error!("backend list drafts failed: {}", err);
+            send_error(
+                &state.evt_tx,
+                CoreErrorSource::Other,
+                format!("List drafts failed: {}", err),
+            );
+        }
+    }
+}
+
+/// Runs an online cleanup pass and emits `CleanupFinished` or an error event.
+///
+/// Compaction is skipped because the GUI keeps the database open.
+pub(super) fn handle_cleanup(state: &mut WorkerState) {
+    match cleanup_database(&state.db) {
+        Ok(report) => {
+            state.query_cache.invalidate();
+            let _ = state.evt_tx.send(CoreEvent::CleanupFinished { report });
+        }
+        Err(err) => {
+            error!("backend cleanup failed: {}", err);
+            send_error(
+                &state.evt_tx,
+                CoreErrorSource::Other,
+                format!("Cleanup failed: {}", err),
+            );
+        }
+    }
+}
@@ -2,9 +2,11 @@
 
 use super::{send_error, WorkerState};
 use crate::backend::{CoreErrorSource, CoreEvent};
+use localpaste_core::folder_export::export_folder_archive;
 use localpaste_core::folder_ops::{
     create_folder_validated, delete_folder_tree_and_migrate_guarded, update_folder_validated,
 };
+use std::path::PathBuf;
 use tracing::error;
 
 /// Loads all folders and emits a `FoldersLoaded` or error event.
@@ -116,3 +118,36 @@ pub(super) fn handle_delete_folder(state: &mut WorkerState, id: String) {
         }
     }
 }
+
+/// Writes a folder subtree ZIP to `path` and emits `FolderExported` on success.
+///
+/// # Arguments
+/// - `state`: Worker state containing db and event channel handles.
+/// - `id`: Root folder id to export.
+/// - `path`: Destination archive path.
+pub(super) fn handle_export_folder(state: &mut WorkerState, id: String, path: PathBuf) {
+    let result = export_folder_archive(&state.db, &id)
+        .map_err(|err| err.to_string())
+        .and_then(|export| {
+            std::fs::write(&path, &export.archive)
+                .map(|_| export.paste_count)
+                .map_err(|err| err.to_string())
+        });
+    match result {
+        Ok(paste_count) => {
+            let _ = state.evt_tx.send(CoreEvent::FolderExported {
+                id,
+                path,
+                paste_count,
+            });
+        }
+        Err(err) => {
+            error!("backend export folder failed: {}", err);
+            send_error(
+                &state.evt_tx,
+                CoreErrorSource::Other,
+                format!("Export folder failed: {}", err),
+            );
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Folder auto-organization rule handlers for the GUI backend worker.
+
+use super::{send_error, WorkerState};
+use crate::backend::{CoreErrorSource, CoreEvent};
+use localpaste_core::models::folder_rule::RuleField;
+use tracing::error;
+
+/// Loads every rule and emits `FolderRulesLoaded` or an error event.
+pub(super) fn handle_list_folder_rules(state: &mut WorkerState) {
+    match state.db.folder_rules.list() {
+        Ok(rules) => {
+            let _ = state.evt_tx.send(CoreEvent::FolderRulesLoaded { rules });
+        }
+        Err(err) => {
+            error!("backend list folder rules failed: {}", err);
+            send_error(
+                &state.evt_tx,
+                CoreErrorSource::Other,
+                format!("Load folder rules failed: {}", err),
+            );
+        }
+    }
+}
+
+/// Appends a rule, then reloads the rules.
+pub(super) fn handle_add_folder_rule(
+    state: &mut WorkerState,
+    field: RuleField,
+    pattern: String,
+    folder_id: String,
+) {
+    if let Err(err) = state
+        .db
+        .folder_rules
+        .create(field, pattern.as_str(), folder_id.as_str())
+    {
+        send_error(
+            &state.evt_tx,
+            CoreErrorSource::Other,
+            format!("Add folder rule failed: {}", err),
+        );
+        return;
+    }
+    handle_list_folder_rules(state);
+}
+
+/// Deletes one rule, then reloads the rules.
+pub(super) fn handle_delete_folder_rule(state: &mut WorkerState, id: String) {
+    if let Err(err) = state.db.folder_rules.delete(id.as_str()) {
+        error!("backend delete folder rule failed: {}", err);
+        send_error(
+            &state.evt_tx,
+            CoreErrorSource::Other,
+            format!("Delete folder rule failed: {}", err),
+        );
+        return;
+    }
+    handle_list_folder_rules(state);
+}
+
+/// Stores a new evaluation order, then reloads the rules.
+pub(super) fn handle_reorder_folder_rules(state: &mut WorkerState, ids: Vec<String>) {
+    if let Err(err) = state.db.folder_rules.reorder(&ids) {
+        send_error(
+            &state.evt_tx,
+            CoreErrorSource::Other,
+            format!("Reorder folder rules failed: {}", err),
+        );
+    }
+    // Reload either way so a stale order in the editor is replaced.
+    handle_list_folder_rules(state);
+}
@@ -6,11 +6,17 @@ use localpaste_core::{
     db::TransactionOps,
     diff::{unified_diff_lines, DiffResponse},
     folder_ops::map_missing_folder_for_optional_request,
+    gist_layout::export_gist_layout,
+    import::ImportedSnippet,
+    merge::{merged_paste, MergeSeparator},
     models::paste::{self, UpdatePasteRequest},
-    naming,
+    normalization::{normalize_content, record_line_ending, NormalizationOptions},
+    split::SplitFile,
+    PasteSource,
 };
 use ropey::Rope;
-use tracing::error;
+use std::path::PathBuf;
+use tracing::{error, warn};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum PasteLoadRoute {
@@ -36,9 +42,25 @@ pub(super) fn handle_get_diff_target_paste(state: &mut WorkerState, id: String)
     handle_get_paste_for_route(state, id, PasteLoadRoute::DiffTarget);
 }
 
+/// Client label for GUI opens in the shared recent history.
+const RECENT_CLIENT: &str = "gui";
+
+fn record_recent_open(state: &WorkerState, id: &str) {
+    if !state.recent_history {
+        return;
+    }
+    if let Err(err) = state.db.recent.record(RECENT_CLIENT, id) {
+        warn!("failed to record recent open of {}: {}", id, err);
+    }
+}
+
 fn handle_get_paste_for_route(state: &mut WorkerState, id: String, route: PasteLoadRoute) {
     match state.db.pastes.get(&id) {
         Ok(Some(paste)) => {
+            if route == PasteLoadRoute::Selection {
+                super::collab::remember_loaded(state, &paste);
+                record_recent_open(state, paste.id.as_str());
+            }
             let event = match route {
                 PasteLoadRoute::Selection => CoreEvent::PasteLoaded { paste },
                 PasteLoadRoute::DiffTarget => CoreEvent::DiffTargetLoaded { paste },
@@ -75,21 +97,80 @@ fn handle_get_paste_for_route(state: &mut WorkerState, id: String, route: PasteL
     }
 }
 
+/// Fetches a sidebar hover preview and emits `PastePreviewLoaded`.
+///
+/// # Arguments
+/// - `state`: Worker state containing db and event channel handles.
+/// - `id`: Paste id to preview.
+pub(super) fn handle_get_paste_preview(state: &mut WorkerState, id: String) {
+    let preview = match state.db.pastes.get(&id) {
+        Ok(paste) => paste.map(|paste| {
+            paste::PastePreview::from_paste(&paste, localpaste_core::PASTE_PREVIEW_DEFAULT_LINES)
+        }),
+        Err(err) => {
+            error!("backend preview get failed: {}", err);
+            None
+        }
+    };
+    let _ = state
+        .evt_tx
+        .send(CoreEvent::PastePreviewLoaded { id, preview });
+}
+
 /// Creates a new paste from raw content and emits `PasteCreated` on success.
 ///
 /// # Arguments
 /// - `state`: Worker state containing db and event channel handles.
 /// - `content`: Paste body content.
-pub(super) fn handle_create_paste(state: &mut WorkerState, content: String) {
-    if let Err(message) = validate_paste_size(content.as_str(), state.max_paste_size) {
+/// - `source`: Write path used to pick the size limit, title strategy, and
+///   clipboard cleanup.
+pub(super) fn handle_create_paste(state: &mut WorkerState, content: String, source: PasteSource) {
+    let max_paste_size = state.max_paste_size_for(source, None);
+    if let Err(message) = validate_paste_size(content.as_str(), max_paste_size) {
         send_error(&state.evt_tx, CoreErrorSource::Other, message);
         return;
     }
+    let content = match source {
+        PasteSource::ClipboardCapture => state.db.pastes.clipboard_cleanup().apply(content),
+        _ => content,
+    };
+    let normalization = *state.db.pastes.normalization();
+    let (content, detected_line_ending) = normalize_content(content, &normalization);
     let inferred = paste::detect_language(&content);
     let inferred_is_locked = inferred.is_some();
-    let name = naming::generate_name();
-    let paste = paste::Paste::new_with_language(content, name, inferred, inferred_is_locked);
-    match state.db.pastes.create(&paste) {
+    let name = state
+        .db
+        .pastes
+        .title_options()
+        .for_source(source)
+        .name_for(content.as_str());
+    let mut paste = paste::Paste::new_with_language(content, name, inferred, inferred_is_locked);
+    paste.line_ending = record_line_ending(
+        paste.line_ending,
+        detected_line_ending,
+        normalization.line_endings,
+    );
+    // A rule lookup failure files the paste unsorted rather than losing it.
+    let rule_folder = match state.db.folder_rules.folder_for(&paste, source) {
+        Ok(rule) => rule.map(|rule| rule.folder_id),
+        Err(err) => {
+            warn!("backend folder rule lookup failed: {}", err);
+            None
+        }
+    };
+    let result = match rule_folder {
+        Some(folder_id) => {
+            let max_paste_size = state.max_paste_size_for(source, Some(folder_id.as_str()));
+            if let Err(message) = validate_paste_size(paste.content.as_str(), max_paste_size) {
+                send_error(&state.evt_tx, CoreErrorSource::Other, message);
+                return;
+            }
+            paste.folder_id = Some(folder_id.clone());
+            TransactionOps::create_paste_with_folder(&state.db, &paste, folder_id.as_str())
+        }
+        None => state.db.pastes.create(&paste),
+    };
+    match result {
         Ok(()) => {
             state.query_cache.invalidate();
             let _ = state.evt_tx.send(CoreEvent::PasteCreated { paste });
@@ -105,11 +186,284 @@ pub(super) fn handle_create_paste(state: &mut WorkerState, content: String) {
     }
 }
 
-fn apply_content_update(state: &mut WorkerState, id: String, content: String, log_label: &str) {
-    if let Err(message) = validate_paste_size(content.as_str(), state.max_paste_size) {
-        send_error(&state.evt_tx, CoreErrorSource::SaveContent, message);
+/// Creates one paste per imported snippet and emits `SnippetsImported`.
+///
+/// Snippets carrying a source id (gist layouts) update that paste when it
+/// exists and otherwise keep the id on the new paste, so layouts round-trip.
+/// Snippets over the import size limit or failing to persist are counted as
+/// failed; the remaining snippets are still imported.
+///
+/// # Arguments
+/// - `state`: Worker state containing db and event channel handles.
+/// - `snippets`: Parsed snippets confirmed in the import wizard.
+pub(super) fn handle_import_snippets(state: &mut WorkerState, snippets: Vec<ImportedSnippet>) {
+    let max_paste_size = state.max_paste_size_for(PasteSource::Import, None);
+    let normalization = *state.db.pastes.normalization();
+    let mut created = 0usize;
+    let mut failed = 0usize;
+    for snippet in snippets {
+        if let Err(message) = validate_paste_size(snippet.content.as_str(), max_paste_size) {
+            error!("backend import of '{}' skipped: {}", snippet.name, message);
+            failed += 1;
+            continue;
+        }
+        let existing = match snippet.id.as_deref() {
+            Some(id) => state.db.pastes.get(id).map(|paste| paste.is_some()),
+            None => Ok(false),
+        };
+        let result = match existing {
+            Ok(true) => update_imported_snippet(state, snippet),
+            Ok(false) => create_imported_snippet(state, snippet, &normalization),
+            Err(err) => Err(err.to_string()),
+        };
+        match result {
+            Ok(()) => created += 1,
+            Err(err) => {
+                error!("backend import failed: {}", err);
+                failed += 1;
+            }
+        }
+    }
+    if created > 0 {
+        state.query_cache.invalidate();
+    }
+    let _ = state
+        .evt_tx
+        .send(CoreEvent::SnippetsImported { created, failed });
+}
+
+fn create_imported_snippet(
+    state: &WorkerState,
+    snippet: ImportedSnippet,
+    normalization: &NormalizationOptions,
+) -> Result<(), String> {
+    let (content, detected_line_ending) = normalize_content(snippet.content, normalization);
+    let mut paste = match snippet.language {
+        Some(language) => {
+            paste::Paste::new_with_language(content, snippet.name, Some(language), true)
+        }
+        None => paste::Paste::new(content, snippet.name),
+    };
+    if let Some(id) = snippet.id {
+        paste.id = id;
+    }
+    paste.line_ending = record_line_ending(
+        paste.line_ending,
+        detected_line_ending,
+        normalization.line_endings,
+    );
+    paste.tags = snippet.tags;
+    state
+        .db
+        .pastes
+        .create(&paste)
+        .map_err(|err| err.to_string())
+}
+
+fn update_imported_snippet(state: &WorkerState, snippet: ImportedSnippet) -> Result<(), String> {
+    let id = snippet.id.unwrap_or_default();
+    let _mutation_guard = localpaste_server::locks::acquire_paste_mutation_guard(
+        state.locks.as_ref(),
+        id.as_str(),
+        "Paste is currently open for editing.",
+        Some(&state.lock_owner_id),
+    )
+    .map_err(|err| err.to_string())?;
+    let update = UpdatePasteRequest {
+        content: Some(snippet.content),
+        name: Some(snippet.name),
+        language_is_manual: snippet.language.as_ref().map(|_| true),
+        language: snippet.language,
+        folder_id: None,
+        tags: Some(snippet.tags),
+        expires_in: None,
+    };
+    match state.db.pastes.update(id.as_str(), update) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(format!("paste '{}' disappeared during import", id)),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Creates one paste per split file and emits `PasteSplit`.
+///
+/// # Arguments
+/// - `state`: Worker state containing db and event channel handles.
+/// - `files`: Files confirmed in the split dialog.
+/// - `folder_id`: Destination folder, or `None` for unfiled pastes.
+pub(super) fn handle_split_paste(
+    state: &mut WorkerState,
+    files: Vec<SplitFile>,
+    folder_id: Option<String>,
+) {
+    let max_paste_size = state.max_paste_size_for(PasteSource::Gui, folder_id.as_deref());
+    let normalization = *state.db.pastes.normalization();
+    let mut created = 0usize;
+    let mut failed = 0usize;
+    for file in files {
+        if let Err(message) = validate_paste_size(file.content.as_str(), max_paste_size) {
+            error!("backend split of '{}' skipped: {}", file.name, message);
+            failed += 1;
+            continue;
+        }
+        let (content, detected_line_ending) = normalize_content(file.content, &normalization);
+        let mut paste = match file.language {
+            Some(language) => {
+                paste::Paste::new_with_language(content, file.name, Some(language), true)
+            }
+            None => paste::Paste::new(content, file.name),
+        };
+        paste.line_ending = record_line_ending(
+            paste.line_ending,
+            detected_line_ending,
+            normalization.line_endings,
+        );
+        let result = match folder_id.as_deref() {
+            Some(folder_id) => {
+                paste.folder_id = Some(folder_id.to_string());
+                TransactionOps::create_paste_with_folder(&state.db, &paste, folder_id).map_err(
+                    |err| map_missing_folder_for_optional_request(err, Some(folder_id), "Folder"),
+                )
+            }
+            None => state.db.pastes.create(&paste),
+        };
+        match result {
+            Ok(()) => created += 1,
+            Err(err) => {
+                error!("backend split create failed: {}", err);
+                failed += 1;
+            }
+        }
+    }
+    if created > 0 {
+        state.query_cache.invalidate();
+    }
+    let _ = state.evt_tx.send(CoreEvent::PasteSplit { created, failed });
+}
+
+/// Joins pastes into one new paste and emits `PastesMerged`.
+///
+/// Creation and the optional deletion of the originals commit in one
+/// transaction, so a failure leaves every paste untouched.
+///
+/// # Arguments
+/// - `state`: Worker state containing db, locks, and event channel handles.
+/// - `ids`: Paste ids in merge order.
+/// - `name`: Name for the merged paste.
+/// - `separator`: Text placed between merged pastes.
+/// - `delete_originals`: Whether to delete the merged pastes.
+pub(super) fn handle_merge_pastes(
+    state: &mut WorkerState,
+    ids: Vec<String>,
+    name: String,
+    separator: MergeSeparator,
+    delete_originals: bool,
+) {
+    let fail = |state: &WorkerState, message: String| {
+        send_error(
+            &state.evt_tx,
+            CoreErrorSource::Other,
+            format!("Merge failed: {}", message),
+        );
+    };
+    if ids.len() < 2 {
+        fail(state, "select at least two pastes.".to_string());
+        return;
+    }
+    let mut sources = Vec::with_capacity(ids.len());
+    for id in &ids {
+        match state.db.pastes.get(id) {
+            Ok(Some(paste)) => sources.push(paste),
+            Ok(None) => {
+                fail(state, format!("paste '{}' no longer exists.", id));
+                return;
+            }
+            Err(err) => {
+                error!("backend merge load failed: {}", err);
+                fail(state, err.to_string());
+                return;
+            }
+        }
+    }
+    let merged = merged_paste(&sources, name, separator);
+    let max_paste_size = state.max_paste_size_for(PasteSource::Gui, merged.folder_id.as_deref());
+    if let Err(message) = validate_paste_size(merged.content.as_str(), max_paste_size) {
+        fail(state, message);
         return;
     }
+
+    let result = TransactionOps::acquire_folder_txn_guard(&state.db).and_then(|folder_guard| {
+        let deleted = if delete_originals { ids } else { Vec::new() };
+        let _mutation_guard = state
+            .locks
+            .begin_batch_mutation_ignoring_owner(deleted.iter(), &state.lock_owner_id)
+            .map_err(|err| {
+                localpaste_server::locks::map_paste_mutation_lock_error(
+                    err,
+                    "Paste is currently open for editing.",
+                )
+            })?;
+        TransactionOps::merge_pastes_locked(&state.db, &folder_guard, &merged, &deleted)?;
+        Ok(deleted)
+    });
+    match result {
+        Ok(deleted) => {
+            state.query_cache.invalidate();
+            let _ = state.evt_tx.send(CoreEvent::PastesMerged {
+                paste: merged,
+                deleted,
+            });
+        }
+        Err(err) => {
+            error!("backend merge failed: {}", err);
+            fail(state, err.to_string());
+        }
+    }
+}
+
+/// Writes every paste into a gist layout and emits `GistLayoutExported`.
+///
+/// # Arguments
+/// - `state`: Worker state containing db and event channel handles.
+/// - `path`: Layout root directory.
+pub(super) fn handle_export_gist_layout(state: &mut WorkerState, path: PathBuf) {
+    match export_gist_layout(&state.db, &path) {
+        Ok(paste_count) => {
+            let _ = state
+                .evt_tx
+                .send(CoreEvent::GistLayoutExported { path, paste_count });
+        }
+        Err(err) => {
+            error!("backend gist export failed: {}", err);
+            send_error(
+                &state.evt_tx,
+                CoreErrorSource::Other,
+                format!("Gist export failed: {}", err),
+            );
+        }
+    }
+}
+
+pub(super) fn gui_update_size_limit(state: &WorkerState, id: &str) -> usize {
+    let folder_id = state.folder_for_size_limit(id);
+    state.max_paste_size_for(PasteSource::Gui, folder_id.as_deref())
+}
+
+/// Saves full replacement content and emits save/missing/error events.
+///
+/// # Returns
+/// The saved paste, or `None` when nothing was written.
+pub(super) fn apply_content_update(
+    state: &mut WorkerState,
+    id: String,
+    content: String,
+    log_label: &str,
+) -> Option<paste::Paste> {
+    let max_paste_size = gui_update_size_limit(state, id.as_str());
+    if let Err(message) = validate_paste_size(content.as_str(), max_paste_size) {
+        send_error(&state.evt_tx, CoreErrorSource::SaveContent, message);
+        return None;
+    }
     let update = UpdatePasteRequest {
         content: Some(content),
         name: None,
@@ -117,6 +471,7 @@ fn apply_content_update(state: &mut WorkerState, id: String, content: String, lo
         language_is_manual: None,
         folder_id: None,
         tags: None,
+        expires_in: None,
     };
     let _mutation_guard = match localpaste_server::locks::acquire_paste_mutation_guard(
         state.locks.as_ref(),
@@ -131,17 +486,21 @@ fn apply_content_update(state: &mut WorkerState, id: String, content: String, lo
                 CoreErrorSource::SaveContent,
                 format!("Update failed: {}", err),
             );
-            return;
+            return None;
         }
     };
     match state.db.pastes.update(&id, update) {
         Ok(Some(paste)) => {
             state.query_cache.invalidate();
-            let _ = state.evt_tx.send(CoreEvent::PasteSaved { paste });
+            let _ = state.evt_tx.send(CoreEvent::PasteSaved {
+                paste: paste.clone(),
+            });
+            Some(paste)
         }
         Ok(None) => {
             state.query_cache.invalidate();
             let _ = state.evt_tx.send(CoreEvent::PasteMissing { id });
+            None
         }
         Err(err) => {
             error!("{}: {}", log_label, err);
@@ -150,6 +509,7 @@ fn apply_content_update(state: &mut WorkerState, id: String, content: String, lo
                 CoreErrorSource::SaveContent,
                 format!("Update failed: {}", err),
             );
+            None
         }
     }
 }
@@ -171,7 +531,8 @@ pub(super) fn handle_update_paste(state: &mut WorkerState, id: String, content:
 /// - `id`: Target paste id.
 /// - `content`: Replacement content stored as a rope buffer.
 pub(super) fn handle_update_paste_virtual(state: &mut WorkerState, id: String, content: Rope) {
-    if let Err(message) = validate_paste_size_bytes(content.len_bytes(), state.max_paste_size) {
+    let max_paste_size = gui_update_size_limit(state, id.as_str());
+    if let Err(message) = validate_paste_size_bytes(content.len_bytes(), max_paste_size) {
         send_error(&state.evt_tx, CoreErrorSource::SaveContent, message);
         return;
     }
@@ -236,6 +597,7 @@ pub(super) fn handle_update_paste_meta(
         language_is_manual,
         folder_id: normalized_folder_id.clone(),
         tags,
+        expires_in: None,
     };
 
     let result = if normalized_folder_id.is_some() {
@@ -456,10 +818,11 @@ pub(super) fn handle_reset_paste_hard_to_version(
                 return;
             }
         };
+        let max_paste_size = gui_update_size_limit(state, id.as_str());
         state
             .db
             .pastes
-            .reset_hard_to_version(id.as_str(), version_id_ms, state.max_paste_size)
+            .reset_hard_to_version(id.as_str(), version_id_ms, max_paste_size)
     };
 
     match reset_result {
@@ -512,7 +875,7 @@ pub(super) fn handle_duplicate_paste_version(
     match state.db.pastes.duplicate_from_version(
         id.as_str(),
         version_id_ms,
-        state.max_paste_size,
+        state.max_paste_size_for(PasteSource::Gui, None),
         name,
     ) {
         Ok(Some(paste)) => {
@@ -1,7 +1,8 @@
 //! Metadata list/search command handlers and short-lived cache for the GUI backend worker.
 
 use super::{send_error, WorkerState};
-use crate::backend::{CoreErrorSource, CoreEvent, PasteSummary};
+use crate::backend::{CoreErrorSource, CoreEvent, PasteSummary, QueryToken};
+use localpaste_core::models::paste::SearchFilters;
 use std::time::{Duration, Instant};
 use tracing::{error, info};
 
@@ -33,6 +34,7 @@ pub(super) struct QueryCache {
     search_hits: u64,
     search_misses: u64,
     invalidations: u64,
+    cancelled: u64,
 }
 
 impl QueryCache {
@@ -54,6 +56,11 @@ impl QueryCache {
             self.invalidations = self.invalidations.saturating_add(1);
         }
     }
+
+    /// Counts a list/search request abandoned because a newer one superseded it.
+    fn record_cancelled(&mut self) {
+        self.cancelled = self.cancelled.saturating_add(1);
+    }
 }
 
 // List/search cache entries intentionally expire quickly so out-of-band
@@ -82,6 +89,7 @@ fn log_query_perf(
         search_hits = cache.search_hits,
         search_misses = cache.search_misses,
         cache_invalidations = cache.invalidations,
+        cancelled = cache.cancelled,
         "backend list/search perf"
     );
 }
@@ -145,7 +153,7 @@ fn run_cached_search<F, E>(
     fetch_items: F,
     to_event: E,
 ) where
-    F: FnOnce(&WorkerState) -> Result<Vec<PasteSummary>, String>,
+    F: FnOnce(&WorkerState) -> Result<Option<Vec<PasteSummary>>, String>,
     E: Fn(Vec<PasteSummary>) -> CoreEvent,
 {
     let started = Instant::now();
@@ -156,10 +164,12 @@ fn run_cached_search<F, E>(
 
     state.query_cache.search_misses = state.query_cache.search_misses.saturating_add(1);
     match fetch_items(state) {
-        Ok(items) => {
+        Ok(Some(items)) => {
             let items = store_search_items_in_cache(state, key, op, started, items);
             let _ = state.evt_tx.send(to_event(items));
         }
+        // Superseded mid-scan: the newer request in the queue will answer.
+        Ok(None) => state.query_cache.record_cancelled(),
         Err(err) => {
             error!("backend {} failed: {}", op, err);
             send_error(
@@ -183,10 +193,15 @@ fn handle_search_variant<E>(
     query: String,
     limit: usize,
     variant: SearchVariant,
+    token: QueryToken,
     to_event: E,
 ) where
     E: Fn(String, Option<String>, Option<String>, Vec<PasteSummary>) -> CoreEvent,
 {
+    if token.is_cancelled() {
+        state.query_cache.record_cancelled();
+        return;
+    }
     let SearchVariant {
         folder_id,
         language,
@@ -200,8 +215,11 @@ fn handle_search_variant<E>(
         language: language.clone(),
     };
     let query_for_fetch = query.clone();
-    let folder_for_fetch = folder_id.clone();
-    let language_for_fetch = language.clone();
+    let filters = SearchFilters {
+        folder_id: folder_id.clone(),
+        language: language.clone(),
+        ..SearchFilters::default()
+    };
     run_cached_search(
         state,
         key,
@@ -211,13 +229,8 @@ fn handle_search_variant<E>(
             worker
                 .db
                 .pastes
-                .search_meta(
-                    &query_for_fetch,
-                    limit,
-                    folder_for_fetch,
-                    language_for_fetch,
-                )
-                .map(|metas| metas.iter().map(PasteSummary::from_meta).collect())
+                .search_meta_until(&query_for_fetch, limit, &filters, &|| token.is_cancelled())
+                .map(|metas| metas.map(|metas| metas.iter().map(PasteSummary::from_meta).collect()))
                 .map_err(|err| err.to_string())
         },
         move |items| to_event(query.clone(), folder_id.clone(), language.clone(), items),
@@ -239,7 +252,17 @@ pub(super) enum SearchRoute {
 /// - `state`: Worker state containing db/cache/event handles.
 /// - `limit`: Maximum number of rows to return.
 /// - `folder_id`: Optional folder filter.
-pub(super) fn handle_list_pastes(state: &mut WorkerState, limit: usize, folder_id: Option<String>) {
+/// - `token`: Skips the request when a newer list request has been issued.
+pub(super) fn handle_list_pastes(
+    state: &mut WorkerState,
+    limit: usize,
+    folder_id: Option<String>,
+    token: QueryToken,
+) {
+    if token.is_cancelled() {
+        state.query_cache.record_cancelled();
+        return;
+    }
     let started = Instant::now();
     let key = ListCacheKey {
         limit,
@@ -301,11 +324,13 @@ pub(super) fn handle_list_pastes(state: &mut WorkerState, limit: usize, folder_i
 /// - `route`: Search route selecting standard or command-palette behavior.
 /// - `query`: Raw search text.
 /// - `limit`: Maximum number of rows to return.
+/// - `token`: Skips or abandons the search once a newer one of its kind is issued.
 pub(super) fn handle_search(
     state: &mut WorkerState,
     route: SearchRoute,
     query: String,
     limit: usize,
+    token: QueryToken,
 ) {
     match route {
         SearchRoute::Standard {
@@ -321,6 +346,7 @@ pub(super) fn handle_search(
                 op: "search",
                 error_prefix: "Search",
             },
+            token,
             |query, folder_id, language, items| CoreEvent::SearchResults {
                 query,
                 folder_id,
@@ -338,7 +364,74 @@ pub(super) fn handle_search(
                 op: "palette_search",
                 error_prefix: "Palette search",
             },
+            token,
             |query, _folder_id, _language, items| CoreEvent::PaletteSearchResults { query, items },
         ),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::{spawn_backend, CoreCmd, CoreEvent, QueryKind};
+    use localpaste_core::{models::paste::Paste, Database};
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn superseded_list_and_search_requests_emit_no_events() {
+        let dir = TempDir::new().expect("temp dir");
+        let db_path = dir.path().join("db");
+        let db = Database::new(db_path.to_str().expect("db path")).expect("db");
+        db.pastes
+            .create(&Paste::new("body".to_string(), "rust-alpha".to_string()))
+            .expect("create paste");
+
+        let backend = spawn_backend(db, 10 * 1024 * 1024);
+        let stale_search = backend.query_tokens.next(QueryKind::Search);
+        let fresh_search = backend.query_tokens.next(QueryKind::Search);
+        let stale_list = backend.query_tokens.next(QueryKind::List);
+        let fresh_list = backend.query_tokens.next(QueryKind::List);
+        assert!(stale_search.is_cancelled());
+        assert!(!fresh_search.is_cancelled());
+
+        for (query, token) in [("rust", stale_search), ("alpha", fresh_search)] {
+            backend
+                .cmd_tx
+                .send(CoreCmd::SearchPastes {
+                    query: query.to_string(),
+                    limit: 10,
+                    folder_id: None,
+                    language: None,
+                    token,
+                })
+                .expect("send search");
+        }
+        for token in [stale_list, fresh_list] {
+            backend
+                .cmd_tx
+                .send(CoreCmd::ListPastes {
+                    limit: 10,
+                    folder_id: None,
+                    token,
+                })
+                .expect("send list");
+        }
+
+        let recv = || backend.evt_rx.recv_timeout(Duration::from_secs(2));
+        match recv().expect("search event") {
+            CoreEvent::SearchResults { query, items, .. } => {
+                assert_eq!(query, "alpha");
+                assert_eq!(items.len(), 1);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        match recv().expect("list event") {
+            CoreEvent::PasteList { items } => assert_eq!(items.len(), 1),
+            other => panic!("unexpected event: {:?}", other),
+        }
+        assert!(backend
+            .evt_rx
+            .recv_timeout(Duration::from_millis(100))
+            .is_err());
+    }
+}
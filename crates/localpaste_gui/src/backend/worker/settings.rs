@@ -0,0 +1,71 @@
+//! Display and update settings handlers for the GUI backend worker.
+
+use super::{send_error, WorkerState};
+use crate::backend::{CoreErrorSource, CoreEvent};
+use localpaste_core::settings::{
+    load_settings_or_quarantine, save_settings, Settings, UpdateSettings,
+};
+use localpaste_core::timestamps::DisplayTimezone;
+use localpaste_core::AppError;
+use tracing::error;
+
+/// Rewrites one part of the settings file, keeping the rest.
+///
+/// A malformed file is moved aside first (see
+/// [`load_settings_or_quarantine`]), so a corrupt file cannot block saves.
+pub(super) fn update_settings(
+    db_path: &str,
+    change: impl FnOnce(&mut Settings),
+) -> Result<(), AppError> {
+    let mut settings = load_settings_or_quarantine(db_path)?.settings;
+    change(&mut settings);
+    save_settings(db_path, &settings)
+}
+
+/// Saves the display timezone, keeping the rest of the settings file.
+pub(super) fn handle_save_display_timezone(
+    state: &mut WorkerState,
+    db_path: String,
+    timezone: DisplayTimezone,
+) {
+    let saved = update_settings(&db_path, |settings| settings.display.timezone = timezone);
+    match saved {
+        Ok(()) => {
+            let _ = state
+                .evt_tx
+                .send(CoreEvent::DisplayTimezoneSaved { timezone });
+        }
+        Err(err) => {
+            error!("backend save display timezone failed: {}", err);
+            send_error(
+                &state.evt_tx,
+                CoreErrorSource::Other,
+                format!("Save display timezone failed: {}", err),
+            );
+        }
+    }
+}
+
+/// Saves the update check settings, keeping the rest of the settings file.
+pub(super) fn handle_save_update_settings(
+    state: &mut WorkerState,
+    db_path: String,
+    updates: UpdateSettings,
+) {
+    let saved = update_settings(&db_path, |settings| settings.updates = updates.clone());
+    match saved {
+        Ok(()) => {
+            let _ = state
+                .evt_tx
+                .send(CoreEvent::UpdateSettingsSaved { updates });
+        }
+        Err(err) => {
+            error!("backend save update settings failed: {}", err);
+            send_error(
+                &state.evt_tx,
+                CoreErrorSource::Other,
+                format!("Save update settings failed: {}", err),
+            );
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Source URL refresh handler for the GUI backend worker.
+
+use super::{paste::gui_update_size_limit, send_error, WorkerState};
+use crate::backend::{CoreErrorSource, CoreEvent};
+use chrono::Utc;
+use localpaste_core::url_fetch::{apply_source_bytes, fetch_source_bytes};
+use tracing::warn;
+
+fn send_refresh_error(state: &WorkerState, message: impl std::fmt::Display) {
+    send_error(
+        &state.evt_tx,
+        CoreErrorSource::SaveContent,
+        format!("Refresh from source failed: {}", message),
+    );
+}
+
+/// Re-fetches a paste from its source URL and emits `PasteRefreshedFromSource`.
+///
+/// The download runs on the worker thread, so other commands wait for it
+/// (at most [`localpaste_core::url_fetch::URL_FETCH_TIMEOUT`]).
+pub(super) fn handle_refresh_from_source(state: &mut WorkerState, id: String) {
+    let source = match state.db.url_sources.get(id.as_str()) {
+        Ok(Some(source)) => source,
+        Ok(None) => {
+            send_refresh_error(state, "paste has no source URL.");
+            return;
+        }
+        Err(err) => {
+            send_refresh_error(state, err);
+            return;
+        }
+    };
+    let now = Utc::now();
+    let max_paste_size = gui_update_size_limit(state, id.as_str());
+    let bytes = match fetch_source_bytes(source.url.as_str(), max_paste_size) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            warn!(id = %id, url = %source.url, "backend source refresh failed: {}", err);
+            if let Err(record_err) =
+                state
+                    .db
+                    .url_sources
+                    .record_check(id.as_str(), now, false, Some(&err.to_string()))
+            {
+                warn!("recording source refresh failure failed: {}", record_err);
+            }
+            send_refresh_error(state, err);
+            return;
+        }
+    };
+    let result = {
+        let _mutation_guard = match localpaste_server::locks::acquire_paste_mutation_guard(
+            state.locks.as_ref(),
+            id.as_str(),
+            "Paste is currently open for editing.",
+            Some(&state.lock_owner_id),
+        ) {
+            Ok(guard) => guard,
+            Err(err) => {
+                send_refresh_error(state, err);
+                return;
+            }
+        };
+        apply_source_bytes(&state.db, id.as_str(), bytes, now)
+    };
+    match result {
+        Ok((paste, response)) => {
+            if response.changed {
+                state.query_cache.invalidate();
+            }
+            let _ = state.evt_tx.send(CoreEvent::PasteRefreshedFromSource {
+                paste,
+                changed: response.changed,
+            });
+        }
+        Err(err) => send_refresh_error(state, err),
+    }
+}
@@ -4,6 +4,9 @@
 //! without duplicating initialization logic.
 
 mod app;
+/// Headless app driver for scripted UI tests.
+#[cfg(feature = "test-harness")]
+pub use app::harness;
 /// Backend worker + protocol types used by the GUI and headless tests.
 pub mod backend;
 mod lock_owner;
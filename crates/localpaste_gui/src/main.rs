@@ -7,6 +7,18 @@
 )]
 
 fn main() {
+    if std::env::args_os().skip(1).any(|arg| arg == "--build-info") {
+        // Release Windows builds have no console, so this prints nothing there.
+        print!(
+            "{}",
+            localpaste_core::build_info::render_build_info(
+                "localpaste-gui",
+                env!("CARGO_PKG_VERSION"),
+                &[]
+            )
+        );
+        return;
+    }
     let exit_code = run_and_report(localpaste_gui::run);
     if exit_code != 0 {
         std::process::exit(exit_code);
@@ -6,6 +6,7 @@ use localpaste_core::{
 };
 use localpaste_gui::backend::{
     spawn_backend, spawn_backend_with_locks, BackendHandle, CoreCmd, CoreErrorSource, CoreEvent,
+    QueryToken,
 };
 use localpaste_server::{AppState, EmbeddedServer, LockOwnerId, PasteLockManager};
 use ropey::Rope;
@@ -29,6 +30,9 @@ fn test_config(db_path: &str) -> Config {
         max_paste_size: TEST_MAX_PASTE_SIZE,
         auto_save_interval: 2000,
         auto_backup: false,
+        size_limits: Default::default(),
+        flush_policy: Default::default(),
+        recent_history: false,
     }
 }
 
@@ -95,6 +99,7 @@ fn api_updates_are_visible_to_backend_list() {
         .send(CoreCmd::ListPastes {
             limit: 10,
             folder_id: None,
+            token: QueryToken::default(),
         })
         .expect("send list");
 
@@ -499,6 +504,7 @@ fn metadata_update_persists_and_manual_auto_language_transitions_work() {
             limit: 10,
             folder_id: Some(folder_id),
             language: None,
+            token: QueryToken::default(),
         })
         .expect("search");
     match recv_event(&backend.evt_rx) {
@@ -550,6 +556,7 @@ fn backend_metadata_search_surfaces_persisted_derived_retrieval_hints() {
             limit: 10,
             folder_id: None,
             language: None,
+            token: QueryToken::default(),
         })
         .expect("send search");
 
@@ -662,6 +669,7 @@ fn backend_virtual_update_and_api_delete_race_keeps_consistent_visibility() {
         .send(CoreCmd::ListPastes {
             limit: 20,
             folder_id: None,
+            token: QueryToken::default(),
         })
         .expect("list after race");
     match recv_event(&backend.evt_rx) {
@@ -816,6 +824,7 @@ fn api_folder_changes_are_visible_to_backend_state() {
         .send(CoreCmd::ListPastes {
             limit: 10,
             folder_id: Some(folder_id.clone()),
+            token: QueryToken::default(),
         })
         .expect("list folder");
     match recv_event(&backend.evt_rx) {
@@ -957,6 +966,7 @@ fn list_and_search_latency_stay_within_reasonable_headless_budget() {
         .send(CoreCmd::ListPastes {
             limit: 512,
             folder_id: None,
+            token: QueryToken::default(),
         })
         .expect("send list");
     let list_elapsed = list_start.elapsed();
@@ -973,6 +983,7 @@ fn list_and_search_latency_stay_within_reasonable_headless_budget() {
             limit: 32,
             folder_id: None,
             language: None,
+            token: QueryToken::default(),
         })
         .expect("send search");
     let search_elapsed = search_start.elapsed();
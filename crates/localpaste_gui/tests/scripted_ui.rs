@@ -0,0 +1,93 @@
+//! End-to-end editor scripts driven through the headless GUI harness.
+
+use localpaste_core::{models::paste::Paste, Database};
+use localpaste_gui::harness::{egui, GuiHarness, Step};
+use tempfile::TempDir;
+
+fn seeded_harness(pastes: &[(&str, &str)]) -> (TempDir, GuiHarness, Vec<String>) {
+    let dir = TempDir::new().expect("temp dir");
+    let db_path = dir.path().join("db").to_string_lossy().to_string();
+    let ids = {
+        let db = Database::new(&db_path).expect("db");
+        pastes
+            .iter()
+            .map(|(name, content)| {
+                let paste = Paste::new(content.to_string(), name.to_string());
+                db.pastes.create(&paste).expect("create");
+                paste.id
+            })
+            .collect()
+    };
+    let harness = GuiHarness::open(&db_path).expect("harness");
+    (dir, harness, ids)
+}
+
+#[test]
+fn typed_edits_and_undo_round_trip_through_save() {
+    let (dir, mut harness, ids) = seeded_harness(&[("notes", "hello")]);
+    let id = ids[0].clone();
+
+    harness
+        .run_script(&[
+            Step::Select(id.clone()),
+            Step::FocusEditor,
+            Step::Key(egui::Key::End, egui::Modifiers::default()),
+            Step::Type(" world".to_string()),
+        ])
+        .expect("type");
+    assert_eq!(harness.selected_id(), Some(id.as_str()));
+    assert_eq!(harness.editor_text(), "hello world");
+    assert!(harness.is_dirty());
+
+    harness
+        .run_script(&[Step::Shortcut(egui::Key::Z)])
+        .expect("undo");
+    assert_eq!(harness.editor_text(), "hello");
+
+    harness
+        .run_script(&[
+            Step::Shortcut(egui::Key::A),
+            Step::Type("replaced".to_string()),
+            Step::Save,
+        ])
+        .expect("replace and save");
+    assert_eq!(harness.editor_text(), "replaced");
+    assert!(!harness.is_dirty());
+
+    drop(harness);
+    let db = Database::new(&dir.path().join("db").to_string_lossy()).expect("reopen");
+    let saved = db.pastes.get(&id).expect("get").expect("paste");
+    assert_eq!(saved.content, "replaced");
+}
+
+#[test]
+fn switching_pastes_saves_pending_edits_first() {
+    let (_dir, mut harness, ids) = seeded_harness(&[("first", "one"), ("second", "two")]);
+
+    harness
+        .run_script(&[
+            Step::Select(ids[0].clone()),
+            Step::FocusEditor,
+            Step::Shortcut(egui::Key::A),
+            Step::Type("edited".to_string()),
+            Step::Select(ids[1].clone()),
+        ])
+        .expect("switch");
+    assert_eq!(harness.editor_text(), "two");
+
+    harness
+        .run_script(&[Step::Select(ids[0].clone())])
+        .expect("switch back");
+    assert_eq!(harness.editor_text(), "edited");
+}
+
+#[test]
+fn script_errors_name_the_failing_step() {
+    let (_dir, harness, _ids) = seeded_harness(&[]);
+    let mut harness = harness.with_timeout(std::time::Duration::from_millis(50));
+    let err = harness
+        .run_script(&[Step::Frames(1), Step::Select("missing".to_string())])
+        .unwrap_err();
+    assert!(err.starts_with("step 2"), "{}", err);
+    assert!(err.contains("not listed"), "{}", err);
+}
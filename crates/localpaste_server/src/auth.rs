@@ -0,0 +1,198 @@
+//! Bearer-token authentication and per-paste access checks for multi-user mode.
+//!
+//! Everything here is a no-op unless `LOCALPASTE_MULTI_USER` is on. When it is,
+//! every `/api/` and `/raw/` request needs `Authorization: Bearer <token>` and
+//! the resolved [`User`] rides along as a request extension. Routes addressing one paste are
+//! checked in [`enforce_access`] so their handlers stay unaware of ownership;
+//! list, search, and create handlers use the helpers below. Pastes a caller may
+//! not read answer `404`, the same as missing ones.
+
+use crate::{error::HttpError, AppError, AppState, Database};
+use axum::{
+    extract::{Request, State},
+    http::{header, Method},
+    middleware::Next,
+    response::Response,
+    Extension,
+};
+use localpaste_core::models::user::{ListScope, User};
+
+/// Rows scanned before scope filtering so owned pastes are not crowded out of
+/// a limited list by other users' newer ones.
+pub(crate) const SCOPED_SCAN_LIMIT: usize = 5_000;
+
+/// Request extension carrying the authenticated user, absent in single-user mode.
+pub type CurrentUser = Option<Extension<User>>;
+
+fn bearer_token(request: &Request) -> Option<&str> {
+    let value = request
+        .headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?;
+    let (scheme, token) = value.trim().split_once(' ')?;
+    scheme
+        .eq_ignore_ascii_case("bearer")
+        .then(|| token.trim())
+        .filter(|token| !token.is_empty())
+}
+
+fn is_admin_route(method: &Method, segments: &[&str]) -> bool {
+    match segments {
+        ["admin", ..]
+        | ["digest"]
+        | ["export"]
+        | ["pastes", "export"]
+        | ["folder", _, "export"] => true,
+        // Deleting a folder removes every paste in it, whoever owns them.
+        ["folder", _] => method == Method::DELETE,
+        // Rules file everyone's new pastes, so only admins may change them.
+        ["folder-rules", ..] => method != Method::GET,
+        // The server fetches linked URLs itself, so linking one is privileged.
+        ["paste", _, "source"] => method == Method::PUT,
+        // Renaming or removing a tag rewrites every user's pastes.
+        ["tags", _] => method != Method::GET,
+        _ => false,
+    }
+}
+
+/// Authenticate `/api/` and `/raw/` requests and enforce per-paste access.
+///
+/// # Returns
+/// The downstream response, with the caller's [`User`] attached as a request
+/// extension when multi-user mode is on.
+///
+/// # Errors
+/// Returns `401` without a valid bearer token, `403` for admin-only routes,
+/// and `404` for pastes the caller may not read or change.
+pub async fn enforce_access(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, HttpError> {
+    if !state.db.users.options().enabled {
+        return Ok(next.run(request).await);
+    }
+    let path = request.uri().path();
+    let segments: Vec<&str> = if let Some(rest) = path.strip_prefix("/api/") {
+        rest.split('/').filter(|part| !part.is_empty()).collect()
+    } else if let Some(id) = path.strip_prefix("/raw/") {
+        // Same access rules as `/api/paste/:id/raw`.
+        vec!["paste", id, "raw"]
+    } else {
+        return Ok(next.run(request).await);
+    };
+    let token = bearer_token(&request)
+        .ok_or_else(|| AppError::Unauthorized("Missing bearer token".to_string()))?;
+    let user = state
+        .db
+        .users
+        .authenticate(token)?
+        .ok_or_else(|| AppError::Unauthorized("Unknown or revoked token".to_string()))?;
+
+    let method = request.method().clone();
+    if is_admin_route(&method, &segments) && !user.is_admin() {
+        return Err(AppError::Forbidden("Admin access required".to_string()).into());
+    }
+    let paste_id = match segments.as_slice() {
+        ["paste", "by-slug", slug] => state.db.pastes.get_by_slug(slug)?.map(|paste| paste.id),
+        // Ids that do not resolve fall through so the handler reports them.
+        ["paste", id, ..] => state.db.pastes.resolve_id(id).ok(),
+        _ => None,
+    };
+    if let Some(paste_id) = paste_id {
+        let access = state.db.users.paste_access(&paste_id)?;
+        // Readers may comment, annotate, and announce they are viewing; the
+        // comment and annotation handlers check edits against authorship.
+        let read_only = method == Method::GET
+            || method == Method::HEAD
+            || matches!(
+                segments.as_slice(),
+                ["paste", _, "comments" | "annotations" | "presence", ..]
+            );
+        let allowed = if read_only {
+            user.can_read(access.as_ref())
+        } else {
+            user.can_write(access.as_ref())
+        };
+        if !allowed {
+            return Err(AppError::NotFound.into());
+        }
+    }
+
+    request.extensions_mut().insert(user);
+    Ok(next.run(request).await)
+}
+
+/// Record `user` as the owner of a newly created paste.
+///
+/// # Errors
+/// Returns an error when the access row cannot be written.
+pub(crate) fn record_owner(
+    state: &AppState,
+    user: Option<&User>,
+    paste_id: &str,
+) -> Result<(), AppError> {
+    match user {
+        Some(user) => state.db.users.set_paste_owner(paste_id, user.id.as_str()),
+        None => Ok(()),
+    }
+}
+
+/// Fail with `NotFound` unless `user` may read `paste_id`.
+///
+/// # Errors
+/// Returns [`AppError::NotFound`] when access is denied, or a storage error.
+pub(crate) fn ensure_readable(
+    state: &AppState,
+    user: Option<&User>,
+    paste_id: &str,
+) -> Result<(), AppError> {
+    let Some(user) = user else {
+        return Ok(());
+    };
+    let access = state.db.users.paste_access(paste_id)?;
+    if user.can_read(access.as_ref()) {
+        Ok(())
+    } else {
+        Err(AppError::NotFound)
+    }
+}
+
+/// Keep the rows `user` should see under `scope`, then cap them at `limit`.
+///
+/// # Errors
+/// Returns an error when access rows cannot be read.
+pub(crate) fn retain_scoped<T>(
+    db: &Database,
+    user: Option<&User>,
+    scope: ListScope,
+    rows: Vec<T>,
+    limit: usize,
+    id_of: impl Fn(&T) -> &str,
+) -> Result<Vec<T>, AppError> {
+    let Some(user) = user else {
+        return Ok(rows);
+    };
+    let mut kept = Vec::with_capacity(rows.len().min(limit));
+    for row in rows {
+        if kept.len() == limit {
+            break;
+        }
+        let access = db.users.paste_access(id_of(&row))?;
+        if scope.includes(user, access.as_ref()) {
+            kept.push(row);
+        }
+    }
+    Ok(kept)
+}
+
+/// Store scan limit for a list call: the caller's limit in single-user mode,
+/// [`SCOPED_SCAN_LIMIT`] when rows are filtered afterwards.
+pub(crate) fn scan_limit(user: Option<&User>, limit: usize) -> usize {
+    if user.is_some() {
+        SCOPED_SCAN_LIMIT.max(limit)
+    } else {
+        limit
+    }
+}
@@ -0,0 +1,318 @@
+//! Live co-editing of one paste from several editors on this machine.
+//!
+//! The edit lock makes a second editor wait until the first closes the paste.
+//! Collaborative edits skip that wait: each editor sends a [`TextEdit`]
+//! against the revision it last saw, and the hub rebases it over the edits
+//! committed since then (see [`localpaste_core::collab`]). Revisions are
+//! per paste, kept in memory, and restart at 0 with the process.
+//!
+//! A write that bypasses the hub (a plain `PUT`, a version restore) resets the
+//! paste's history, so editors holding an older revision must re-fetch.
+//! Every merge is published to the change feed, whichever editor sent it.
+
+use crate::events::{ChangeEvent, ChangeFeed, ChangeKind, ChangeTarget};
+use crate::{locks::PasteLockManager, AppError, Database, PasteLockError};
+use chrono::{DateTime, Utc};
+use localpaste_core::collab::TextEdit;
+use localpaste_core::db::paste::paste_content_hash;
+use localpaste_core::models::paste::{Paste, PasteMeta, UpdatePasteRequest};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Edits kept per paste for rebasing late writers.
+pub const COLLAB_HISTORY_LIMIT: usize = 128;
+
+/// An edit with the revision it produced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevisedEdit {
+    pub rev: u64,
+    #[serde(flatten)]
+    pub edit: TextEdit,
+}
+
+/// Edits committed after a revision.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditsSince {
+    /// Current revision.
+    pub rev: u64,
+    /// Edits after the requested revision, oldest first.
+    pub edits: Vec<RevisedEdit>,
+    /// `true` when some of those edits are no longer kept, or the paste was
+    /// rewritten outside the hub; re-fetch the paste instead.
+    pub gap: bool,
+}
+
+/// Result of [`CollabHub::merge`].
+#[derive(Debug, Clone)]
+pub struct MergeOutcome {
+    /// The paste after the merge.
+    pub paste: Paste,
+    /// Revision produced by the merge; unchanged when the edit was a no-op.
+    pub rev: u64,
+    /// `true` when the edit overlapped one committed since its base revision
+    /// and replaced it.
+    pub conflicted: bool,
+}
+
+/// What the hub last saw stored for a paste. Metadata edits also move
+/// `updated_at`, so the content hash decides whether history is still valid.
+#[derive(Debug)]
+struct ContentStamp {
+    updated_at: DateTime<Utc>,
+    len: usize,
+    hash: String,
+}
+
+impl ContentStamp {
+    fn of(paste: &Paste) -> Self {
+        Self {
+            updated_at: paste.updated_at,
+            len: paste.content.len(),
+            hash: paste_content_hash(&paste.content),
+        }
+    }
+
+    fn matches_meta(&self, meta: &PasteMeta) -> bool {
+        self.updated_at == meta.updated_at && self.len == meta.content_len
+    }
+}
+
+#[derive(Debug)]
+struct EditLog {
+    rev: u64,
+    /// Oldest revision an editor can still rebase from.
+    floor: u64,
+    recent: VecDeque<RevisedEdit>,
+    stamp: ContentStamp,
+}
+
+impl EditLog {
+    fn new(paste: &Paste) -> Self {
+        Self {
+            rev: 0,
+            floor: 0,
+            recent: VecDeque::new(),
+            stamp: ContentStamp::of(paste),
+        }
+    }
+
+    /// Start over when the stored content no longer matches the last merge.
+    fn sync(&mut self, paste: &Paste) {
+        if self.stamp.updated_at == paste.updated_at && self.stamp.len == paste.content.len() {
+            return;
+        }
+        let stamp = ContentStamp::of(paste);
+        if stamp.hash != self.stamp.hash {
+            self.rev += 1;
+            self.floor = self.rev;
+            self.recent.clear();
+        }
+        self.stamp = stamp;
+    }
+
+    fn since(&self, base_rev: u64) -> EditsSince {
+        EditsSince {
+            rev: self.rev,
+            edits: self
+                .recent
+                .iter()
+                .filter(|edit| edit.rev > base_rev)
+                .cloned()
+                .collect(),
+            gap: base_rev < self.floor || base_rev > self.rev,
+        }
+    }
+
+    fn push(&mut self, edit: TextEdit, paste: &Paste) -> u64 {
+        self.rev += 1;
+        if self.recent.len() == COLLAB_HISTORY_LIMIT {
+            if let Some(dropped) = self.recent.pop_front() {
+                self.floor = dropped.rev;
+            }
+        }
+        self.recent.push_back(RevisedEdit {
+            rev: self.rev,
+            edit,
+        });
+        self.stamp = ContentStamp::of(paste);
+        self.rev
+    }
+}
+
+/// The log for `id`, reset first if the paste changed outside the hub.
+///
+/// Only the metadata row is read unless `updated_at` or the length moved.
+fn synced_log<'a>(
+    logs: &'a mut HashMap<String, EditLog>,
+    db: &Database,
+    id: &str,
+) -> Result<Option<&'a mut EditLog>, AppError> {
+    let Some(meta) = db.pastes.get_meta(id)? else {
+        logs.remove(id);
+        return Ok(None);
+    };
+    if logs
+        .get(id)
+        .is_some_and(|log| log.stamp.matches_meta(&meta))
+    {
+        return Ok(logs.get_mut(id));
+    }
+    let Some(paste) = db.pastes.get(id)? else {
+        logs.remove(id);
+        return Ok(None);
+    };
+    let log = logs
+        .entry(id.to_string())
+        .or_insert_with(|| EditLog::new(&paste));
+    log.sync(&paste);
+    Ok(Some(log))
+}
+
+/// Per-paste edit history shared by the API and the GUI backend.
+#[derive(Debug, Default)]
+pub struct CollabHub {
+    logs: Mutex<HashMap<String, EditLog>>,
+    changes: Arc<ChangeFeed>,
+}
+
+impl CollabHub {
+    /// Create a hub that announces merges on `changes`.
+    pub fn new(changes: Arc<ChangeFeed>) -> Self {
+        Self {
+            logs: Mutex::default(),
+            changes,
+        }
+    }
+
+    fn logs(&self) -> Result<MutexGuard<'_, HashMap<String, EditLog>>, AppError> {
+        self.logs.lock().map_err(|_| {
+            AppError::StorageMessage("Collaboration state is unavailable.".to_string())
+        })
+    }
+
+    /// Edits committed to `id` after `base_rev`.
+    ///
+    /// # Returns
+    /// `Ok(None)` when the paste does not exist.
+    ///
+    /// # Errors
+    /// Returns an error when storage access fails.
+    pub fn edits_since(
+        &self,
+        db: &Database,
+        id: &str,
+        base_rev: u64,
+    ) -> Result<Option<EditsSince>, AppError> {
+        let mut logs = self.logs()?;
+        Ok(synced_log(&mut logs, db, id)?.map(|log| log.since(base_rev)))
+    }
+
+    /// Current revision of `id`.
+    ///
+    /// # Returns
+    /// `Ok(None)` when the paste does not exist.
+    ///
+    /// # Errors
+    /// Returns an error when storage access fails.
+    pub fn revision(&self, db: &Database, id: &str) -> Result<Option<u64>, AppError> {
+        let mut logs = self.logs()?;
+        Ok(synced_log(&mut logs, db, id)?.map(|log| log.rev))
+    }
+
+    /// Rebase `edit` from `base_rev` onto the current content and save it.
+    ///
+    /// Edit locks held by other editors are ignored; only an in-flight
+    /// mutation of the same paste blocks the merge. `validate` sees the
+    /// stored paste and the merged content before anything is written.
+    ///
+    /// # Returns
+    /// `Ok(None)` when the paste does not exist.
+    ///
+    /// # Errors
+    /// Returns [`AppError::Conflict`] when `base_rev` can no longer be
+    /// rebased, [`AppError::BadRequest`] when the edit does not fit the text
+    /// it was made against, [`AppError::Locked`] when another mutation is
+    /// in flight, or the error from `validate`.
+    pub fn merge(
+        &self,
+        db: &Database,
+        locks: &PasteLockManager,
+        id: &str,
+        base_rev: u64,
+        edit: TextEdit,
+        validate: impl FnOnce(&Paste, &str) -> Result<(), AppError>,
+    ) -> Result<Option<MergeOutcome>, AppError> {
+        let mut logs = self.logs()?;
+        let _guard = locks.begin_shared_edit(id).map_err(|err| match err {
+            PasteLockError::Poisoned => {
+                AppError::StorageMessage("Paste lock manager is unavailable.".to_string())
+            }
+            other => AppError::Locked(format!("{}; retry shortly.", other)),
+        })?;
+        let Some(current) = db.pastes.get(id)? else {
+            logs.remove(id);
+            return Ok(None);
+        };
+        let log = logs
+            .entry(id.to_string())
+            .or_insert_with(|| EditLog::new(&current));
+        log.sync(&current);
+        let since = log.since(base_rev);
+        if since.gap {
+            return Err(AppError::Conflict(format!(
+                "Revision {} of paste '{}' can no longer be merged (current revision {}); re-fetch it.",
+                base_rev, id, since.rev
+            )));
+        }
+        let mut conflicted = false;
+        let mut rebased = edit;
+        for committed in &since.edits {
+            let (next, overlapped) = rebased.rebase(&committed.edit);
+            rebased = next;
+            conflicted |= overlapped;
+        }
+        let content = rebased.apply(&current.content)?;
+        validate(&current, &content)?;
+        if content == current.content {
+            return Ok(Some(MergeOutcome {
+                paste: current,
+                rev: log.rev,
+                conflicted,
+            }));
+        }
+        let update = UpdatePasteRequest {
+            content: Some(content),
+            name: None,
+            language: None,
+            language_is_manual: None,
+            folder_id: None,
+            tags: None,
+            expires_in: None,
+        };
+        let Some(paste) = db.pastes.update(id, update)? else {
+            logs.remove(id);
+            return Ok(None);
+        };
+        // Record what was stored, which save-time normalization may have
+        // changed from the rebased edit.
+        let rev = match TextEdit::between(&current.content, &paste.content) {
+            Some(stored) => log.push(stored, &paste),
+            None => {
+                log.stamp = ContentStamp::of(&paste);
+                log.rev
+            }
+        };
+        self.changes.publish(ChangeEvent {
+            target: ChangeTarget::Paste,
+            id: id.to_string(),
+            change: ChangeKind::Updated,
+            access: db.users.paste_access(id).unwrap_or_default(),
+        });
+        Ok(Some(MergeOutcome {
+            paste,
+            rev,
+            conflicted,
+        }))
+    }
+}
@@ -1,6 +1,9 @@
 //! Embedded server helper for running the API inside another process (e.g. GUI).
 
 use crate::{resolve_bind_address, serve_router, AppError, AppState};
+use localpaste_core::discovery::{
+    port_fallback_attempts_from_env_or_default, port_fallback_candidates, write_api_addr,
+};
 use std::{
     fs,
     net::SocketAddr,
@@ -16,7 +19,7 @@ pub struct EmbeddedServer {
     shutdown: Option<oneshot::Sender<()>>,
     thread: Option<JoinHandle<()>>,
     addr: SocketAddr,
-    used_fallback: bool,
+    requested_addr: SocketAddr,
     api_addr_path: Option<PathBuf>,
 }
 
@@ -24,7 +27,9 @@ impl EmbeddedServer {
     /// Start the API server on a background thread.
     ///
     /// The server binds to `BIND` or `127.0.0.1:PORT` from `Config`. If the
-    /// requested address is in use, it will fall back to an auto-assigned port.
+    /// requested address is in use it tries the next `PORT_FALLBACK_ATTEMPTS`
+    /// ports in order, then an auto-assigned port. The bound address is written
+    /// atomically to the `.api-addr` discovery file.
     ///
     /// # Arguments
     /// - `state`: Shared application state (config, db, locks).
@@ -57,27 +62,9 @@ impl EmbeddedServer {
                 };
 
                 let bind_addr = resolve_bind_address(&state.config, allow_public);
-                let mut used_fallback = false;
-                let listener = match rt.block_on(tokio::net::TcpListener::bind(bind_addr)) {
+                let attempts = port_fallback_attempts_from_env_or_default();
+                let listener = match rt.block_on(bind_with_fallback(bind_addr, attempts)) {
                     Ok(listener) => listener,
-                    Err(err) if err.kind() == std::io::ErrorKind::AddrInUse => {
-                        warn!(
-                            "API bind address {} is in use; falling back to an auto port",
-                            bind_addr
-                        );
-                        used_fallback = true;
-                        let fallback_addr = SocketAddr::new(bind_addr.ip(), 0);
-                        match rt.block_on(tokio::net::TcpListener::bind(fallback_addr)) {
-                            Ok(listener) => listener,
-                            Err(fallback_err) => {
-                                let _ = ready_tx.send(Err(format!(
-                                    "failed to bind server socket: {}",
-                                    fallback_err
-                                )));
-                                return;
-                            }
-                        }
-                    }
                     Err(err) => {
                         let _ =
                             ready_tx.send(Err(format!("failed to bind server socket: {}", err)));
@@ -86,32 +73,22 @@ impl EmbeddedServer {
                 };
 
                 let actual_addr = listener.local_addr().unwrap_or(bind_addr);
-                let api_addr = format!("http://{}", actual_addr);
-                if let Some(parent) = api_addr_path_for_thread.parent() {
-                    if let Err(err) = fs::create_dir_all(parent) {
-                        warn!(
-                            "failed to ensure API discovery directory '{}': {}",
-                            parent.display(),
-                            err
-                        );
-                    }
-                }
-                if let Err(err) = fs::write(&api_addr_path_for_thread, api_addr.as_bytes()) {
+                if let Err(err) = write_api_addr(&state.config.db_path, actual_addr) {
                     warn!(
                         "failed to write API discovery file '{}': {}",
                         api_addr_path_for_thread.display(),
                         err
                     );
                 }
-                if used_fallback {
+                if actual_addr != bind_addr {
                     warn!(
-                        "API listening on http://{} (auto port; {} was in use)",
+                        "API listening on http://{} (fallback; {} was in use)",
                         actual_addr, bind_addr
                     );
                 } else {
                     info!("API listening on http://{}", actual_addr);
                 }
-                let _ = ready_tx.send(Ok((actual_addr, used_fallback)));
+                let _ = ready_tx.send(Ok((actual_addr, bind_addr)));
 
                 let shutdown = async {
                     let _ = shutdown_rx.await;
@@ -131,7 +108,7 @@ impl EmbeddedServer {
         let mut thread_handle = Some(thread);
 
         match ready_rx.recv() {
-            Ok(Ok((addr, used_fallback))) => {
+            Ok(Ok((addr, requested_addr))) => {
                 if !addr.ip().is_loopback() {
                     warn!("binding to non-localhost address {}", addr);
                 }
@@ -139,7 +116,7 @@ impl EmbeddedServer {
                     shutdown: Some(shutdown_tx),
                     thread: thread_handle.take(),
                     addr,
-                    used_fallback,
+                    requested_addr,
                     api_addr_path: Some(api_addr_path),
                 })
             }
@@ -168,13 +145,43 @@ impl EmbeddedServer {
         self.addr
     }
 
-    /// Whether the server had to fall back to an auto-assigned port.
+    /// Address the server was asked to bind before any fallback.
     ///
     /// # Returns
-    /// `true` if the requested bind address was in use and an auto port was used.
+    /// The address resolved from `BIND`/`PORT`.
+    pub fn requested_addr(&self) -> SocketAddr {
+        self.requested_addr
+    }
+
+    /// Whether the server had to fall back from the requested address.
+    ///
+    /// # Returns
+    /// `true` if the requested bind address was in use and another port was used.
     pub fn used_fallback(&self) -> bool {
-        self.used_fallback
+        self.addr != self.requested_addr && self.requested_addr.port() != 0
+    }
+}
+
+/// Bind the first free address from [`port_fallback_candidates`].
+///
+/// Only `AddrInUse` moves on to the next candidate; any other bind error is
+/// returned immediately.
+async fn bind_with_fallback(
+    requested: SocketAddr,
+    attempts: u16,
+) -> std::io::Result<tokio::net::TcpListener> {
+    let mut last_err = None;
+    for candidate in port_fallback_candidates(requested, attempts) {
+        match tokio::net::TcpListener::bind(candidate).await {
+            Ok(listener) => return Ok(listener),
+            Err(err) if err.kind() == std::io::ErrorKind::AddrInUse => {
+                warn!("API bind address {} is in use", candidate);
+                last_err = Some(err);
+            }
+            Err(err) => return Err(err),
+        }
     }
+    Err(last_err.unwrap_or_else(|| std::io::ErrorKind::AddrInUse.into()))
 }
 
 impl Drop for EmbeddedServer {
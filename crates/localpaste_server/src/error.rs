@@ -20,11 +20,21 @@ impl From<AppError> for HttpError {
 
 impl IntoResponse for HttpError {
     fn into_response(self) -> Response {
+        if let AppError::AmbiguousId { candidates, .. } = &self.0 {
+            let body = Json(json!({ "error": self.0.to_string(), "candidates": candidates }));
+            return (StatusCode::NOT_FOUND, body).into_response();
+        }
         let (status, error_message) = match &self.0 {
             AppError::NotFound => (StatusCode::NOT_FOUND, "Not found"),
             AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg.as_str()),
             AppError::PayloadTooLarge(msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg.as_str()),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, msg.as_str()),
             AppError::Locked(msg) => (StatusCode::LOCKED, msg.as_str()),
+            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg.as_str()),
+            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg.as_str()),
+            AppError::Upstream(msg) => (StatusCode::BAD_GATEWAY, msg.as_str()),
+            AppError::TooManyRequests(msg) => (StatusCode::TOO_MANY_REQUESTS, msg.as_str()),
+            AppError::Unavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg.as_str()),
             AppError::Database(err) => {
                 tracing::error!("Database error: {}", err);
                 (StatusCode::INTERNAL_SERVER_ERROR, "Database error")
@@ -0,0 +1,245 @@
+//! Change notifications for API writes and collaborative merges, streamed to
+//! `/api/ws` and `/api/events` subscribers.
+//!
+//! Every event gets an increasing id, and the most recent ones are kept so an
+//! SSE client reconnecting with `Last-Event-ID` can replay what it missed.
+//! Ids restart at 1 when the server restarts.
+
+use crate::AppState;
+use localpaste_core::models::user::{PasteAccess, User};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+
+/// Events buffered per subscriber, and kept for resume, before slow or
+/// disconnected clients start missing some.
+pub const CHANGE_CHANNEL_CAPACITY: usize = 256;
+
+/// Kind of record an event refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeTarget {
+    Paste,
+    Folder,
+}
+
+/// What happened to the record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    Deleted,
+    /// Someone started or stopped editing or viewing the paste; re-fetch
+    /// `/api/paste/:id/presence`.
+    Presence,
+}
+
+/// One create, update, delete, or presence change, sent as
+/// `{"target", "id", "change"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    pub target: ChangeTarget,
+    pub id: String,
+    pub change: ChangeKind,
+    /// Paste owner and sharing as of publishing, so a later delete cannot
+    /// hide the event. `None` when not recorded; [`visible_to`] then reads
+    /// the stored access row.
+    #[serde(skip)]
+    pub access: Option<PasteAccess>,
+}
+
+/// A [`ChangeEvent`] with its position in the feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequencedChange {
+    pub seq: u64,
+    pub event: ChangeEvent,
+}
+
+/// Events published after a resume point.
+#[derive(Debug)]
+pub struct ChangeReplay {
+    /// Retained events after the resume point, oldest first.
+    pub missed: Vec<SequencedChange>,
+    /// `true` when events after the resume point were already dropped, so
+    /// `missed` is incomplete and the client should re-fetch.
+    pub gap: bool,
+    /// Live events published after `missed`.
+    pub receiver: broadcast::Receiver<SequencedChange>,
+}
+
+#[derive(Debug)]
+struct ChangeLog {
+    last_seq: u64,
+    recent: VecDeque<SequencedChange>,
+}
+
+/// Live change broadcast plus a short replay buffer.
+#[derive(Debug)]
+pub struct ChangeFeed {
+    sender: broadcast::Sender<SequencedChange>,
+    log: Mutex<ChangeLog>,
+}
+
+impl Default for ChangeFeed {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(CHANGE_CHANNEL_CAPACITY).0,
+            log: Mutex::new(ChangeLog {
+                last_seq: 0,
+                recent: VecDeque::with_capacity(CHANGE_CHANNEL_CAPACITY),
+            }),
+        }
+    }
+}
+
+impl ChangeFeed {
+    fn lock_log(&self) -> std::sync::MutexGuard<'_, ChangeLog> {
+        self.log
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Receive events published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<SequencedChange> {
+        self.sender.subscribe()
+    }
+
+    /// Receive events published after `last_seq`, replaying retained ones.
+    ///
+    /// The replay and the live receiver are taken under one lock, so no event
+    /// is missed or repeated between them.
+    pub fn subscribe_after(&self, last_seq: u64) -> ChangeReplay {
+        let log = self.lock_log();
+        let missed: Vec<_> = log
+            .recent
+            .iter()
+            .filter(|change| change.seq > last_seq)
+            .cloned()
+            .collect();
+        let oldest_needed = missed.first().map_or(log.last_seq + 1, |change| change.seq);
+        ChangeReplay {
+            gap: last_seq > log.last_seq || oldest_needed > last_seq + 1,
+            missed,
+            receiver: self.sender.subscribe(),
+        }
+    }
+
+    /// Assign the next id to `event`, retain it, and broadcast it.
+    pub fn publish(&self, event: ChangeEvent) {
+        let mut log = self.lock_log();
+        log.last_seq += 1;
+        let change = SequencedChange {
+            seq: log.last_seq,
+            event,
+        };
+        if log.recent.len() == CHANGE_CHANNEL_CAPACITY {
+            log.recent.pop_front();
+        }
+        log.recent.push_back(change.clone());
+        // `send` only fails when there are no receivers.
+        let _ = self.sender.send(change);
+    }
+}
+
+/// Broadcast a paste change, recording the paste's current access row.
+pub(crate) fn paste_changed(state: &AppState, id: &str, change: ChangeKind) {
+    let access = match state.db.users.paste_access(id) {
+        Ok(access) => access,
+        Err(err) => {
+            tracing::warn!("change event access lookup failed: {}", err);
+            None
+        }
+    };
+    publish(state, ChangeTarget::Paste, id, change, access);
+}
+
+/// Broadcast a paste deletion.
+///
+/// `access` must be read before the delete, which removes the access row.
+pub(crate) fn paste_deleted(state: &AppState, id: &str, access: Option<PasteAccess>) {
+    publish(state, ChangeTarget::Paste, id, ChangeKind::Deleted, access);
+}
+
+/// Broadcast a folder change.
+pub(crate) fn folder_changed(state: &AppState, id: &str, change: ChangeKind) {
+    publish(state, ChangeTarget::Folder, id, change, None);
+}
+
+/// Whether `user` may see `event`.
+///
+/// Without multi-user mode everything is visible. Paste events are limited
+/// to pastes the user can read, judged by the access recorded on the event
+/// when there is one; folder events are always visible.
+pub(crate) fn visible_to(state: &AppState, user: Option<&User>, event: &ChangeEvent) -> bool {
+    let Some(user) = user else {
+        return true;
+    };
+    if event.target != ChangeTarget::Paste || user.is_admin() {
+        return true;
+    }
+    if let Some(access) = event.access.as_ref() {
+        return user.can_read(Some(access));
+    }
+    match state.db.users.paste_access(event.id.as_str()) {
+        Ok(access) => user.can_read(access.as_ref()),
+        Err(err) => {
+            tracing::warn!("change event access lookup failed: {}", err);
+            false
+        }
+    }
+}
+
+fn publish(
+    state: &AppState,
+    target: ChangeTarget,
+    id: &str,
+    change: ChangeKind,
+    access: Option<PasteAccess>,
+) {
+    state.changes.publish(ChangeEvent {
+        target,
+        id: id.to_string(),
+        change,
+        access,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: &str) -> ChangeEvent {
+        ChangeEvent {
+            target: ChangeTarget::Paste,
+            id: id.to_string(),
+            change: ChangeKind::Updated,
+            access: None,
+        }
+    }
+
+    #[test]
+    fn subscribe_after_replays_retained_events_and_flags_gaps() {
+        let feed = ChangeFeed::default();
+        feed.publish(event("a"));
+        feed.publish(event("b"));
+
+        let replay = feed.subscribe_after(1);
+        assert!(!replay.gap);
+        assert_eq!(replay.missed.len(), 1);
+        assert_eq!(replay.missed[0].seq, 2);
+        assert_eq!(replay.missed[0].event.id, "b");
+        assert!(!feed.subscribe_after(2).gap);
+        // Ids from before a restart are ahead of the feed.
+        assert!(feed.subscribe_after(9).gap);
+
+        for n in 0..CHANGE_CHANNEL_CAPACITY {
+            feed.publish(event(&n.to_string()));
+        }
+        let replay = feed.subscribe_after(1);
+        assert!(replay.gap);
+        assert_eq!(replay.missed.len(), CHANGE_CHANNEL_CAPACITY);
+        assert!(!feed.subscribe_after(2).gap);
+    }
+}
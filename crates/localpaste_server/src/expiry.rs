@@ -0,0 +1,81 @@
+//! Background sweep that deletes pastes past their `expires_at`.
+
+use crate::events::paste_deleted;
+use crate::{AppError, AppState};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// How often the server looks for expired pastes.
+pub const EXPIRY_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Delete every paste that expired at or before `now`.
+///
+/// Pastes open for editing in the GUI are skipped and retried on the next
+/// sweep. Each paste is re-read under the delete guards, so an update that
+/// pushed the expiry back after the scan keeps the paste.
+///
+/// # Returns
+/// Number of pastes deleted.
+///
+/// # Errors
+/// Returns an error when storage access fails.
+pub fn sweep_expired_pastes(state: &AppState, now: DateTime<Utc>) -> Result<usize, AppError> {
+    let mut deleted = 0;
+    for id in state.db.pastes.expired_ids(now)? {
+        let guards = crate::locks::acquire_folder_scoped_mutation_guards(
+            state.db.as_ref(),
+            state.locks.as_ref(),
+            &id,
+            "Paste is currently open for editing.",
+            None,
+        );
+        let (folder_guard, _mutation_guard) = match guards {
+            Ok(guards) => guards,
+            Err(AppError::Locked(_)) => {
+                tracing::debug!(id = %id, "expired paste is open for editing; retrying later");
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        let still_expired = state
+            .db
+            .pastes
+            .get(&id)?
+            .and_then(|paste| paste.expires_at)
+            .is_some_and(|expires_at| expires_at <= now);
+        if !still_expired {
+            continue;
+        }
+        // The delete drops the access row the event needs for visibility.
+        let access = state.db.users.paste_access(&id)?;
+        if crate::db::TransactionOps::delete_paste_with_folder_locked(
+            &state.db,
+            &folder_guard,
+            &id,
+        )? {
+            paste_deleted(state, id.as_str(), access);
+            deleted += 1;
+        }
+    }
+    Ok(deleted)
+}
+
+/// Run [`sweep_expired_pastes`] every [`EXPIRY_SWEEP_INTERVAL`] until aborted.
+pub(crate) fn spawn_expiry_sweep(state: AppState) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(EXPIRY_SWEEP_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let state = state.clone();
+            let result =
+                tokio::task::spawn_blocking(move || sweep_expired_pastes(&state, Utc::now())).await;
+            match result {
+                Ok(Ok(0)) => {}
+                Ok(Ok(count)) => tracing::info!("Deleted {} expired paste(s)", count),
+                Ok(Err(err)) => tracing::warn!("Expired paste sweep failed: {}", err),
+                Err(err) => tracing::warn!("Expired paste sweep task failed: {}", err),
+            }
+        }
+    })
+}
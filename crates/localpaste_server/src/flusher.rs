@@ -0,0 +1,33 @@
+//! Background flushes for the relaxed database flush policies.
+
+use crate::Database;
+use localpaste_core::db::durability::FlushPolicy;
+use std::sync::Arc;
+
+/// Apply `policy` to `db` and, for a relaxed policy, flush whenever it is
+/// due until aborted.
+///
+/// # Returns
+/// The flush task, or `None` for [`FlushPolicy::EveryCommit`], where every
+/// commit is already durable.
+pub(crate) fn spawn_flusher(
+    db: Arc<Database>,
+    policy: FlushPolicy,
+) -> Option<tokio::task::JoinHandle<()>> {
+    db.set_flush_policy(policy);
+    let poll = policy.poll_interval()?;
+    tracing::info!(?policy, "database commits are flushed in the background");
+    Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(poll);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let db = db.clone();
+            match tokio::task::spawn_blocking(move || db.flush_if_due()).await {
+                Ok(Ok(_)) => {}
+                Ok(Err(err)) => tracing::warn!("Background database flush failed: {}", err),
+                Err(err) => tracing::warn!("Background database flush task failed: {}", err),
+            }
+        }
+    }))
+}
@@ -0,0 +1,260 @@
+//! Read-only gallery that publishes one folder to other devices on the LAN.
+//!
+//! The gallery runs on its own listener with its own router: a rendered list at
+//! `/`, highlighted views at `/p/:id`, and plain text at `/p/:id/raw`. Only
+//! `GET` routes exist, so nothing reachable through it can change the store,
+//! and pastes outside the published folder answer `404`. In multi-user mode
+//! visitors are anonymous, so only shared pastes are listed or served.
+
+use crate::{auth, error::HttpError, AppError, Database};
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderName, HeaderValue},
+    response::{Html, IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use localpaste_core::export::{escape_html, render_html_page, ExportDocument};
+use localpaste_core::models::paste::Paste;
+use localpaste_core::models::user::{ListScope, User, UserRole};
+use localpaste_core::normalization::restore_line_endings;
+use localpaste_core::DEFAULT_GALLERY_PORT;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tower_http::{set_header::SetResponseHeaderLayer, trace::TraceLayer};
+
+/// Env var naming the folder id to publish; the gallery is off when unset.
+pub const GALLERY_FOLDER_ENV: &str = "LOCALPASTE_GALLERY_FOLDER";
+/// Env var overriding the gallery listener address.
+pub const GALLERY_BIND_ENV: &str = "LOCALPASTE_GALLERY_BIND";
+/// Most pastes listed on the gallery index.
+pub const GALLERY_LIST_LIMIT: usize = 500;
+
+const RAW_CONTENT_TYPE: &str = "text/plain; charset=utf-8";
+const INDEX_CSS: &str = "body{margin:2rem;font-family:system-ui,sans-serif;}\
+h1{font-size:1.2rem;font-weight:600;}table{border-collapse:collapse;}\
+td{padding:.25rem 1rem .25rem 0;}td.meta{color:#666;font-size:.85rem;}";
+
+/// Gallery settings resolved from the environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GalleryConfig {
+    /// Folder whose pastes are published.
+    pub folder_id: String,
+    /// Listener address; defaults to all interfaces on the port after the API.
+    pub bind: SocketAddr,
+}
+
+impl GalleryConfig {
+    /// Default gallery listener: every interface, one port above the API.
+    pub fn default_bind() -> SocketAddr {
+        SocketAddr::from(([0, 0, 0, 0], DEFAULT_GALLERY_PORT))
+    }
+
+    /// Load gallery settings in strict mode.
+    ///
+    /// # Returns
+    /// `Ok(None)` when `LOCALPASTE_GALLERY_FOLDER` is unset or blank.
+    ///
+    /// # Errors
+    /// Returns a message when `LOCALPASTE_GALLERY_BIND` is not a socket address.
+    pub fn from_env_strict() -> Result<Option<Self>, String> {
+        let folder_id = std::env::var(GALLERY_FOLDER_ENV).unwrap_or_default();
+        let folder_id = folder_id.trim();
+        if folder_id.is_empty() {
+            return Ok(None);
+        }
+        let bind = match std::env::var(GALLERY_BIND_ENV) {
+            Ok(raw) if !raw.trim().is_empty() => raw
+                .trim()
+                .parse::<SocketAddr>()
+                .map_err(|err| format!("Invalid {}='{}': {}", GALLERY_BIND_ENV, raw, err))?,
+            _ => Self::default_bind(),
+        };
+        Ok(Some(Self {
+            folder_id: folder_id.to_string(),
+            bind,
+        }))
+    }
+}
+
+/// Stand-in account for gallery visitors in multi-user mode: a member who
+/// owns nothing, so only shared pastes are visible.
+fn gallery_visitor() -> User {
+    User {
+        id: "gallery".to_string(),
+        name: "gallery".to_string(),
+        role: UserRole::Member,
+        created_at: chrono::Utc::now(),
+    }
+}
+
+#[derive(Clone)]
+struct GalleryState {
+    db: Arc<Database>,
+    folder_id: Arc<str>,
+    /// Set in multi-user mode; gallery reads are scoped to what it may see.
+    visitor: Option<Arc<User>>,
+}
+
+impl GalleryState {
+    fn published_paste(&self, id: &str) -> Result<Paste, AppError> {
+        let paste = self
+            .db
+            .pastes
+            .get(id)?
+            // The gallery is read-only, so it cannot burn one-time pastes;
+            // it refuses them rather than serving them repeatedly.
+            .filter(|paste| {
+                paste.folder_id.as_deref() == Some(&*self.folder_id) && !paste.burn_after_read
+            })
+            .ok_or(AppError::NotFound)?;
+        if let Some(visitor) = self.visitor.as_deref() {
+            let access = self.db.users.paste_access(id)?;
+            if !visitor.can_read(access.as_ref()) {
+                return Err(AppError::NotFound);
+            }
+        }
+        Ok(paste)
+    }
+}
+
+/// Create the read-only gallery router for `folder_id`.
+///
+/// # Arguments
+/// - `db`: Shared database handle (the same one the API uses).
+/// - `folder_id`: Folder to publish; its subfolders are not included.
+///
+/// # Returns
+/// A router with only `GET` routes and the API's security headers.
+pub fn create_gallery_app(db: Arc<Database>, folder_id: &str) -> Router {
+    let visitor = db
+        .users
+        .options()
+        .enabled
+        .then(|| Arc::new(gallery_visitor()));
+    Router::new()
+        .route("/", get(gallery_index))
+        .route("/p/:id", get(gallery_view))
+        .route("/p/:id/raw", get(gallery_raw))
+        .with_state(GalleryState {
+            db,
+            folder_id: Arc::from(folder_id),
+            visitor,
+        })
+        .layer(
+            tower::ServiceBuilder::new()
+                .layer(TraceLayer::new_for_http())
+                .layer(SetResponseHeaderLayer::overriding(
+                    header::CONTENT_SECURITY_POLICY,
+                    HeaderValue::from_static(super::CSP_HEADER_VALUE),
+                ))
+                .layer(SetResponseHeaderLayer::overriding(
+                    header::X_CONTENT_TYPE_OPTIONS,
+                    HeaderValue::from_static(super::X_CONTENT_TYPE_OPTIONS_NOSNIFF),
+                ))
+                .layer(SetResponseHeaderLayer::overriding(
+                    header::X_FRAME_OPTIONS,
+                    HeaderValue::from_static(super::X_FRAME_OPTIONS_DENY),
+                ))
+                .layer(SetResponseHeaderLayer::overriding(
+                    HeaderName::from_static(super::X_LOCALPASTE_SERVER_HEADER),
+                    HeaderValue::from_static("gallery"),
+                )),
+        )
+}
+
+async fn gallery_index(State(state): State<GalleryState>) -> Result<Html<String>, HttpError> {
+    let folder = state
+        .db
+        .folders
+        .get(&state.folder_id)?
+        .ok_or(AppError::NotFound)?;
+    let visitor = state.visitor.as_deref();
+    let rows = state.db.pastes.list_meta(
+        auth::scan_limit(visitor, GALLERY_LIST_LIMIT),
+        Some(state.folder_id.to_string()),
+    )?;
+    let rows = auth::retain_scoped(
+        &state.db,
+        visitor,
+        ListScope::All,
+        rows,
+        usize::MAX,
+        |meta| meta.id.as_str(),
+    )?;
+    // Metadata rows do not carry the one-time flag, and their view pages 404.
+    let mut pastes = Vec::with_capacity(rows.len().min(GALLERY_LIST_LIMIT));
+    for meta in rows {
+        if pastes.len() == GALLERY_LIST_LIMIT {
+            break;
+        }
+        if state
+            .db
+            .pastes
+            .get(&meta.id)?
+            .is_some_and(|paste| !paste.burn_after_read)
+        {
+            pastes.push(meta);
+        }
+    }
+    let title = escape_html(folder.name.as_str());
+    let mut rows = String::new();
+    for paste in &pastes {
+        let id = escape_html(paste.id.as_str());
+        rows.push_str(&format!(
+            "<tr><td><a href=\"/p/{id}\">{}</a></td><td class=\"meta\">{}</td>\
+<td class=\"meta\">{}</td><td class=\"meta\"><a href=\"/p/{id}/raw\">raw</a></td></tr>\n",
+            escape_html(paste.name.as_str()),
+            escape_html(paste.language.as_deref().unwrap_or("text")),
+            paste.updated_at.format("%Y-%m-%d"),
+        ));
+    }
+    if pastes.is_empty() {
+        rows.push_str("<tr><td>Nothing published yet.</td></tr>\n");
+    }
+    Ok(Html(format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+<meta name=\"generator\" content=\"LocalPaste\">\n<title>{title}</title>\n\
+<style>{INDEX_CSS}</style>\n</head>\n<body>\n<h1>{title}</h1>\n<table>\n{rows}</table>\n\
+</body>\n</html>\n"
+    )))
+}
+
+async fn gallery_view(
+    State(state): State<GalleryState>,
+    Path(id): Path<String>,
+) -> Result<Html<String>, HttpError> {
+    let paste = state.published_paste(&id)?;
+    // Highlighting large pastes is CPU-bound; keep it off the async workers.
+    let page = tokio::task::spawn_blocking(move || {
+        render_html_page(
+            &ExportDocument {
+                name: paste.name.as_str(),
+                language: paste.language.as_deref(),
+                content: paste.content.as_str(),
+                // Annotations are review notes, not part of the published text.
+                annotations: &[],
+            },
+            Some("/"),
+        )
+    })
+    .await
+    .map_err(|_| AppError::Internal)??;
+    Ok(Html(page))
+}
+
+async fn gallery_raw(
+    State(state): State<GalleryState>,
+    Path(id): Path<String>,
+) -> Result<Response, HttpError> {
+    let paste = state.published_paste(&id)?;
+    let body = restore_line_endings(paste.content.as_str(), paste.line_ending).into_owned();
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(RAW_CONTENT_TYPE),
+        )],
+        body,
+    )
+        .into_response())
+}
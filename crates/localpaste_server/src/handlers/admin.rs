@@ -0,0 +1,63 @@
+//! Administrative maintenance endpoints.
+
+use super::json::Json;
+use crate::{error::HttpError, AppState};
+use axum::extract::State;
+use localpaste_core::db::backup::BackupManager;
+use localpaste_core::maintenance::{cleanup_database, CleanupReport};
+use localpaste_core::storage_alerts::{
+    PruneBackupsRequest, PruneBackupsResponse, DEFAULT_BACKUP_PRUNE_KEEP_LAST,
+};
+
+/// Remove orphaned drafts, repair folder invariants, and drop idle uploads.
+///
+/// The database stays open while serving, so the report's `compacted` is
+/// always `false`; run `localpaste --cleanup` with the server stopped to
+/// compact the file.
+///
+/// # Returns
+/// A [`CleanupReport`] describing what was removed.
+///
+/// # Errors
+/// Returns an error if storage access fails.
+pub async fn cleanup(State(state): State<AppState>) -> Result<Json<CleanupReport>, HttpError> {
+    let mut report = cleanup_database(&state.db)?;
+    report.stale_uploads_dropped = state.uploads.prune_idle()?;
+    tracing::info!(
+        "Cleanup removed {} orphan draft(s) and {} idle upload(s)",
+        report.orphan_drafts_removed,
+        report.stale_uploads_dropped
+    );
+    Ok(Json(report))
+}
+
+/// Delete all but the newest backups next to the database directory.
+///
+/// The backup scheduled for restore is always kept. An empty body keeps
+/// [`DEFAULT_BACKUP_PRUNE_KEEP_LAST`] backups.
+///
+/// # Returns
+/// The deleted backup paths, newest first.
+///
+/// # Errors
+/// Returns an error if listing or deleting a backup fails.
+pub async fn prune_backups(
+    State(state): State<AppState>,
+    req: Option<Json<PruneBackupsRequest>>,
+) -> Result<Json<PruneBackupsResponse>, HttpError> {
+    let keep_last = req
+        .and_then(|Json(req)| req.keep_last)
+        .unwrap_or(DEFAULT_BACKUP_PRUNE_KEEP_LAST);
+    let deleted = BackupManager::new(state.config.db_path.as_str()).prune_backups(keep_last)?;
+    tracing::info!(
+        "Pruned {} backup(s), keeping the newest {}",
+        deleted.len(),
+        keep_last
+    );
+    Ok(Json(PruneBackupsResponse {
+        deleted: deleted
+            .into_iter()
+            .map(|path| path.display().to_string())
+            .collect(),
+    }))
+}
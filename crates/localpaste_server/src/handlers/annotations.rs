@@ -0,0 +1,111 @@
+//! Line annotation endpoints under `/api/paste/:id/annotations`.
+//!
+//! Permissions match comments: readers may annotate, and only the author or
+//! someone who can change the paste may edit or delete an annotation.
+
+use super::comments::ensure_note_editable;
+use super::json::Json;
+use crate::{auth::CurrentUser, error::HttpError, AppError, AppState};
+use axum::extract::{Path, State};
+use localpaste_core::models::annotation::{
+    Annotation, CreateAnnotationRequest, UpdateAnnotationRequest,
+};
+
+/// List a paste's annotations in line order.
+///
+/// # Returns
+/// Annotations as JSON.
+///
+/// # Errors
+/// Returns `404` when the paste is missing.
+pub async fn list_annotations(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<Annotation>>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    Ok(Json(state.db.annotations.list(&id)?))
+}
+
+/// Annotate a line range of a paste.
+///
+/// # Returns
+/// The stored annotation as JSON.
+///
+/// # Errors
+/// Returns `400` for a range outside the content or an empty or oversized
+/// body, and `404` when the paste is missing.
+pub async fn create_annotation(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    user: CurrentUser,
+    Json(req): Json<CreateAnnotationRequest>,
+) -> Result<Json<Annotation>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let (author, author_id) = match user.as_deref() {
+        Some(user) => (user.name.clone(), Some(user.id.as_str())),
+        None => (req.author.unwrap_or_default(), None),
+    };
+    let lines = (req.start_line, req.end_line.unwrap_or(req.start_line));
+    let annotation =
+        state
+            .db
+            .annotations
+            .create(&id, lines, author.as_str(), author_id, req.body.as_str())?;
+    Ok(Json(annotation))
+}
+
+/// Edit an annotation body.
+///
+/// # Returns
+/// The updated annotation as JSON.
+///
+/// # Errors
+/// Returns `400` for an empty or oversized body, `403` when the caller may not
+/// change the annotation, and `404` when it is missing.
+pub async fn update_annotation(
+    State(state): State<AppState>,
+    Path((id, annotation_id)): Path<(String, String)>,
+    user: CurrentUser,
+    Json(req): Json<UpdateAnnotationRequest>,
+) -> Result<Json<Annotation>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let existing = state
+        .db
+        .annotations
+        .get(&id, &annotation_id)?
+        .ok_or(AppError::NotFound)?;
+    ensure_note_editable(&state, user.as_deref(), &id, existing.author_id.as_deref())?;
+    let annotation = state
+        .db
+        .annotations
+        .update(&id, &annotation_id, req.body.as_str())?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(annotation))
+}
+
+/// Delete an annotation.
+///
+/// # Returns
+/// Success marker as JSON.
+///
+/// # Errors
+/// Returns `403` when the caller may not change the annotation and `404` when
+/// it is missing.
+pub async fn delete_annotation(
+    State(state): State<AppState>,
+    Path((id, annotation_id)): Path<(String, String)>,
+    user: CurrentUser,
+) -> Result<Json<serde_json::Value>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let existing = state
+        .db
+        .annotations
+        .get(&id, &annotation_id)?
+        .ok_or(AppError::NotFound)?;
+    ensure_note_editable(&state, user.as_deref(), &id, existing.author_id.as_deref())?;
+    if state.db.annotations.delete(&id, &annotation_id)? {
+        Ok(Json(serde_json::json!({ "success": true })))
+    } else {
+        Err(AppError::NotFound.into())
+    }
+}
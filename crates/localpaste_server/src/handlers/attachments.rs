@@ -0,0 +1,164 @@
+//! Binary attachment endpoints under `/api/paste/:id/attachments`.
+//!
+//! Uploads are `multipart/form-data` with one `file` part, so images and logs
+//! travel as raw bytes. Downloads always carry an attachment
+//! `Content-Disposition`; together with `nosniff` this keeps uploaded HTML or
+//! SVG from rendering inline on the server's origin.
+
+use super::export::{attachment_disposition, download_filename};
+use super::json::Json;
+use super::upload::{multipart_error, upload_file_name, UPLOAD_FILE_FIELD};
+use crate::{error::HttpError, AppError, AppState};
+use axum::{
+    extract::{Multipart, Path, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use localpaste_core::models::attachment::{Attachment, DEFAULT_ATTACHMENT_CONTENT_TYPE};
+
+/// ASCII download filename that keeps the attachment's extension.
+fn attachment_filename(file_name: &str) -> String {
+    match file_name.rsplit_once('.') {
+        Some((stem, extension))
+            if !stem.is_empty()
+                && !extension.is_empty()
+                && extension.chars().all(|ch| ch.is_ascii_alphanumeric()) =>
+        {
+            download_filename(stem, "attachment", extension)
+        }
+        _ => download_filename(file_name, "attachment", "bin"),
+    }
+}
+
+/// List a paste's attachments, oldest first.
+///
+/// # Returns
+/// Attachment metadata as JSON; use the download route for the bytes.
+///
+/// # Errors
+/// Returns `404` when the paste is missing.
+pub async fn list_attachments(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<Attachment>>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    Ok(Json(state.db.attachments.list(&id)?))
+}
+
+/// Attach a file to a paste from a `multipart/form-data` upload.
+///
+/// The `file` part is required; its filename and content type are stored
+/// with the bytes. Other parts are ignored.
+///
+/// # Returns
+/// The stored attachment metadata as JSON.
+///
+/// # Errors
+/// Returns `400` for a missing, repeated, or unnamed `file` part or when the
+/// paste is at its attachment limit, `404` when the paste is missing, and
+/// `413` when the file is too large.
+pub async fn upload_attachment(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    mut multipart: Multipart,
+) -> Result<Json<Attachment>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let mut file: Option<(Option<String>, Option<String>, Vec<u8>)> = None;
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        if field.name() != Some(UPLOAD_FILE_FIELD) {
+            continue;
+        }
+        if file.is_some() {
+            return Err(AppError::BadRequest(
+                "Attachment upload accepts exactly one 'file' field".to_string(),
+            )
+            .into());
+        }
+        let file_name = field.file_name().and_then(upload_file_name);
+        let content_type = field.content_type().map(str::to_string);
+        let bytes = field.bytes().await.map_err(multipart_error)?;
+        file = Some((file_name, content_type, bytes.to_vec()));
+    }
+    let Some((file_name, content_type, bytes)) = file else {
+        return Err(AppError::BadRequest(
+            "Attachment upload must include a 'file' field".to_string(),
+        )
+        .into());
+    };
+    let file_name = file_name.ok_or_else(|| {
+        AppError::BadRequest("Attachment 'file' part must have a filename".to_string())
+    })?;
+    let attachment = state.db.attachments.create(
+        &id,
+        file_name.as_str(),
+        content_type.as_deref(),
+        bytes.as_slice(),
+    )?;
+    Ok(Json(attachment))
+}
+
+/// Download an attachment's bytes.
+///
+/// # Returns
+/// The file with its stored content type (or `application/octet-stream` if
+/// that is not a valid header value) and an attachment
+/// `Content-Disposition` named after the file.
+///
+/// # Errors
+/// Returns `404` when the paste or attachment is missing.
+pub async fn download_attachment(
+    State(state): State<AppState>,
+    Path((id, attachment_id)): Path<(String, String)>,
+) -> Result<Response, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let (attachment, bytes) = state
+        .db
+        .attachments
+        .read(&id, &attachment_id)?
+        .ok_or(AppError::NotFound)?;
+    let content_type = HeaderValue::from_str(attachment.content_type.as_str())
+        .unwrap_or_else(|_| HeaderValue::from_static(DEFAULT_ATTACHMENT_CONTENT_TYPE));
+    let disposition = attachment_disposition(&attachment_filename(&attachment.file_name))?;
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+/// Delete an attachment.
+///
+/// # Returns
+/// Success marker as JSON.
+///
+/// # Errors
+/// Returns `404` when the paste or attachment is missing.
+pub async fn delete_attachment(
+    State(state): State<AppState>,
+    Path((id, attachment_id)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    if state.db.attachments.delete(&id, &attachment_id)? {
+        Ok(Json(serde_json::json!({ "success": true })))
+    } else {
+        Err(AppError::NotFound.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attachment_filenames_keep_safe_extensions() {
+        assert_eq!(attachment_filename("screen shot.png"), "screen_shot.png");
+        assert_eq!(attachment_filename("archive.tar.gz"), "archive.tar.gz");
+        assert_eq!(attachment_filename("README"), "README.bin");
+        assert_eq!(attachment_filename("日本.txt"), "attachment.txt");
+        assert_eq!(attachment_filename(".env"), ".env.bin");
+    }
+}
@@ -0,0 +1,184 @@
+//! Chunked upload HTTP handlers for content larger than one request body.
+//!
+//! Clients begin a session against an existing paste, append raw byte chunks
+//! at explicit offsets (resuming from `received_bytes` after failures), and
+//! commit to replace the paste content in one write.
+
+use super::json::Json;
+use super::paste::{
+    current_folder_for_size_limit, ensure_within_size_limit, paste_source_from_headers,
+};
+use crate::events::{paste_changed, ChangeKind};
+use crate::{error::HttpError, models::paste::*, AppError, AppState};
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::HeaderMap,
+};
+use localpaste_core::normalization::decode_content_bytes;
+
+/// Begin a chunked upload session for an existing paste.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Target paste identifier from the path.
+///
+/// # Returns
+/// New session status with `received_bytes` of `0`.
+///
+/// # Errors
+/// Returns `404` when the paste does not exist, `429` when the paste already
+/// has the maximum open sessions, and `503` when the server-wide session cap
+/// is reached.
+pub async fn begin_chunked_upload(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ChunkUploadStatus>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    state
+        .db
+        .pastes
+        .get(id.as_str())?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(state.uploads.begin(id.as_str())?))
+}
+
+/// Report progress for a chunked upload session.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Target paste identifier.
+/// - `upload_id`: Session identifier from `begin`.
+///
+/// # Returns
+/// Session status; clients resume appending at `received_bytes`.
+///
+/// # Errors
+/// Returns `404` when the session is unknown or targets another paste.
+pub async fn chunked_upload_status(
+    State(state): State<AppState>,
+    Path((id, upload_id)): Path<(String, String)>,
+) -> Result<Json<ChunkUploadStatus>, HttpError> {
+    Ok(Json(state.uploads.status(id.as_str(), upload_id.as_str())?))
+}
+
+/// Append a raw chunk to a session.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Target paste identifier.
+/// - `upload_id`: Session identifier from `begin`.
+/// - `query`: Optional expected `offset` for resumable appends.
+/// - `headers`: Request headers (`x-localpaste-source` selects the size-limit source).
+/// - `body`: Raw chunk bytes.
+///
+/// # Returns
+/// Updated session status.
+///
+/// # Errors
+/// Returns `400` on offset mismatch or when the assembled content would exceed
+/// the applicable size limit, `404` for unknown sessions, and `503` when open
+/// sessions already buffer the server-wide byte cap.
+pub async fn append_chunk(
+    State(state): State<AppState>,
+    Path((id, upload_id)): Path<(String, String)>,
+    Query(query): Query<ChunkAppendQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<ChunkUploadStatus>, HttpError> {
+    let folder_id = current_folder_for_size_limit(&state, id.as_str())?;
+    let max_bytes = state
+        .config
+        .max_paste_size_for(paste_source_from_headers(&headers), folder_id.as_deref());
+    let status = state.uploads.append(
+        id.as_str(),
+        upload_id.as_str(),
+        query.offset,
+        body.as_ref(),
+        max_bytes,
+    )?;
+    Ok(Json(status))
+}
+
+/// Commit a session, replacing the paste content with the assembled bytes.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Target paste identifier.
+/// - `upload_id`: Session identifier from `begin`.
+/// - `headers`: Request headers (`x-localpaste-source` selects the size-limit source).
+///
+/// # Returns
+/// Updated paste as JSON.
+///
+/// # Errors
+/// Returns `400` when the content is not UTF-8 (unless the invalid UTF-8
+/// policy replaces bad sequences) or exceeds the size limit,
+/// `404` for unknown sessions or pastes, and `423` when the paste is open for
+/// editing (the session is kept so the commit can be retried).
+pub async fn commit_chunked_upload(
+    State(state): State<AppState>,
+    Path((id, upload_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Json<Paste>, HttpError> {
+    let bytes = state.uploads.take(id.as_str(), upload_id.as_str())?;
+    let content = decode_content_bytes(bytes, state.db.pastes.normalization())?;
+    let folder_id = current_folder_for_size_limit(&state, id.as_str())?;
+    ensure_within_size_limit(
+        &state,
+        content.len(),
+        paste_source_from_headers(&headers),
+        folder_id.as_deref(),
+    )?;
+
+    let _mutation_guard = match crate::locks::acquire_paste_mutation_guard(
+        state.locks.as_ref(),
+        id.as_str(),
+        "Paste is currently open for editing.",
+        None,
+    ) {
+        Ok(guard) => guard,
+        Err(err) => {
+            state
+                .uploads
+                .restore(id.as_str(), upload_id.as_str(), content.into_bytes())?;
+            return Err(err.into());
+        }
+    };
+    let update = UpdatePasteRequest {
+        content: Some(content),
+        name: None,
+        language: None,
+        language_is_manual: None,
+        folder_id: None,
+        tags: None,
+        expires_in: None,
+    };
+    let paste = state
+        .db
+        .pastes
+        .update(id.as_str(), update)?
+        .ok_or(AppError::NotFound)?;
+    paste_changed(&state, paste.id.as_str(), ChangeKind::Updated);
+    Ok(Json(paste))
+}
+
+/// Abort a session and discard its received bytes.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Target paste identifier.
+/// - `upload_id`: Session identifier from `begin`.
+///
+/// # Returns
+/// Success marker as JSON.
+///
+/// # Errors
+/// Returns `404` when the session is unknown or targets another paste.
+pub async fn abort_chunked_upload(
+    State(state): State<AppState>,
+    Path((id, upload_id)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, HttpError> {
+    state.uploads.take(id.as_str(), upload_id.as_str())?;
+    Ok(Json(serde_json::json!({ "success": true })))
+}
@@ -0,0 +1,101 @@
+//! Collaborative edit endpoints under `/api/paste/:id/edits`.
+//!
+//! Unlike `PUT /api/paste/:id`, these do not fail while another editor holds
+//! the paste open; see [`crate::collab`] for how edits are merged.
+
+use super::json::Json;
+use super::paste::ensure_within_size_limit;
+use crate::collab::EditsSince;
+use crate::{error::HttpError, AppError, AppState};
+use axum::extract::{Path, Query, State};
+use localpaste_core::collab::TextEdit;
+use localpaste_core::models::paste::Paste;
+use localpaste_core::PasteSource;
+use serde::{Deserialize, Serialize};
+
+/// `GET /api/paste/:id/edits` query parameters.
+#[derive(Debug, Default, Deserialize)]
+pub struct EditsQuery {
+    /// Revision the caller already has; defaults to 0.
+    #[serde(default)]
+    pub since: u64,
+}
+
+/// `POST /api/paste/:id/edits` request body.
+#[derive(Debug, Deserialize)]
+pub struct EditRequest {
+    /// Revision the edit was made against.
+    pub base_rev: u64,
+    #[serde(flatten)]
+    pub edit: TextEdit,
+}
+
+/// `POST /api/paste/:id/edits` response body.
+#[derive(Debug, Serialize)]
+pub struct EditResponse {
+    pub rev: u64,
+    /// `true` when the edit overlapped a newer one and replaced it.
+    pub conflicted: bool,
+    pub paste: Paste,
+}
+
+/// List edits committed after `?since=`.
+///
+/// # Returns
+/// The current revision and newer edits; `gap` is set when the caller must
+/// re-fetch the paste instead.
+///
+/// # Errors
+/// Returns `404` when the paste is missing.
+pub async fn list_edits(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<EditsQuery>,
+) -> Result<Json<EditsSince>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let edits = state
+        .collab
+        .edits_since(&state.db, &id, query.since)?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(edits))
+}
+
+/// Merge one edit into a paste, even while another editor has it open.
+///
+/// # Returns
+/// The new revision and merged paste as JSON.
+///
+/// # Errors
+/// Returns `400` for a range outside the text or an oversized result, `404`
+/// when the paste is missing, `409` when `base_rev` is too old to merge, and
+/// `423` while another mutation of the paste is in flight.
+pub async fn post_edit(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<EditRequest>,
+) -> Result<Json<EditResponse>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let outcome = state
+        .collab
+        .merge(
+            &state.db,
+            &state.locks,
+            &id,
+            req.base_rev,
+            req.edit,
+            |paste, content| {
+                ensure_within_size_limit(
+                    &state,
+                    content.len(),
+                    PasteSource::Api,
+                    paste.folder_id.as_deref(),
+                )
+            },
+        )?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(EditResponse {
+        rev: outcome.rev,
+        conflicted: outcome.conflicted,
+        paste: outcome.paste,
+    }))
+}
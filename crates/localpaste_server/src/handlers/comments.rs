@@ -0,0 +1,132 @@
+//! Comment thread endpoints under `/api/paste/:id/comments`.
+//!
+//! In multi-user mode anyone who can read a paste may comment on it; the
+//! author label is the caller's account name, and only the comment's author or
+//! someone who can change the paste may edit or delete a comment.
+
+use super::json::Json;
+use crate::{auth::CurrentUser, error::HttpError, AppError, AppState};
+use axum::extract::{Path, State};
+use localpaste_core::models::comment::{Comment, CreateCommentRequest, UpdateCommentRequest};
+use localpaste_core::models::user::User;
+
+/// Allow changes to a comment or annotation by its author or by anyone who
+/// can change the paste; always allowed outside multi-user mode.
+pub(super) fn ensure_note_editable(
+    state: &AppState,
+    user: Option<&User>,
+    paste_id: &str,
+    author_id: Option<&str>,
+) -> Result<(), AppError> {
+    let Some(user) = user else {
+        return Ok(());
+    };
+    if author_id == Some(user.id.as_str()) {
+        return Ok(());
+    }
+    let access = state.db.users.paste_access(paste_id)?;
+    if user.can_write(access.as_ref()) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "Only the author or the paste owner can change this note".to_string(),
+        ))
+    }
+}
+
+/// List a paste's comments, oldest first.
+///
+/// # Returns
+/// Comments as JSON.
+///
+/// # Errors
+/// Returns `404` when the paste is missing.
+pub async fn list_comments(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<Comment>>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    Ok(Json(state.db.comments.list(&id)?))
+}
+
+/// Add a comment to a paste.
+///
+/// # Returns
+/// The stored comment as JSON.
+///
+/// # Errors
+/// Returns `400` for an empty or oversized body and `404` when the paste is
+/// missing.
+pub async fn create_comment(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    user: CurrentUser,
+    Json(req): Json<CreateCommentRequest>,
+) -> Result<Json<Comment>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let (author, author_id) = match user.as_deref() {
+        Some(user) => (user.name.clone(), Some(user.id.as_str())),
+        None => (req.author.unwrap_or_default(), None),
+    };
+    let comment = state
+        .db
+        .comments
+        .create(&id, author.as_str(), author_id, req.body.as_str())?;
+    Ok(Json(comment))
+}
+
+/// Edit a comment body.
+///
+/// # Returns
+/// The updated comment as JSON.
+///
+/// # Errors
+/// Returns `400` for an empty or oversized body, `403` when the caller may not
+/// change the comment, and `404` when it is missing.
+pub async fn update_comment(
+    State(state): State<AppState>,
+    Path((id, comment_id)): Path<(String, String)>,
+    user: CurrentUser,
+    Json(req): Json<UpdateCommentRequest>,
+) -> Result<Json<Comment>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let existing = state
+        .db
+        .comments
+        .get(&id, &comment_id)?
+        .ok_or(AppError::NotFound)?;
+    ensure_note_editable(&state, user.as_deref(), &id, existing.author_id.as_deref())?;
+    let comment = state
+        .db
+        .comments
+        .update(&id, &comment_id, req.body.as_str())?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(comment))
+}
+
+/// Delete a comment.
+///
+/// # Returns
+/// Success marker as JSON.
+///
+/// # Errors
+/// Returns `403` when the caller may not change the comment and `404` when it
+/// is missing.
+pub async fn delete_comment(
+    State(state): State<AppState>,
+    Path((id, comment_id)): Path<(String, String)>,
+    user: CurrentUser,
+) -> Result<Json<serde_json::Value>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let existing = state
+        .db
+        .comments
+        .get(&id, &comment_id)?
+        .ok_or(AppError::NotFound)?;
+    ensure_note_editable(&state, user.as_deref(), &id, existing.author_id.as_deref())?;
+    if state.db.comments.delete(&id, &comment_id)? {
+        Ok(Json(serde_json::json!({ "success": true })))
+    } else {
+        Err(AppError::NotFound.into())
+    }
+}
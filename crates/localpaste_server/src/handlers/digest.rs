@@ -0,0 +1,29 @@
+//! Activity digest generation endpoint.
+
+use super::json::Json;
+use crate::events::{paste_changed, ChangeKind};
+use crate::{error::HttpError, AppState};
+use axum::extract::State;
+use localpaste_core::digest::{generate_digest, DigestRequest};
+use localpaste_core::models::paste::Paste;
+
+/// Compose a markdown digest of recent activity and store it as a paste.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `req`: Digest window (`day` or `week`).
+///
+/// # Returns
+/// The created digest paste, filed in the `Digests` folder.
+///
+/// # Errors
+/// Returns an error if listing or storing fails.
+pub async fn create_digest(
+    State(state): State<AppState>,
+    Json(req): Json<DigestRequest>,
+) -> Result<Json<Paste>, HttpError> {
+    let digest = generate_digest(&state.db, req.period)?;
+    tracing::info!("Created {:?} digest {}", req.period, digest.id);
+    paste_changed(&state, digest.id.as_str(), ChangeKind::Created);
+    Ok(Json(digest))
+}
@@ -0,0 +1,325 @@
+//! Rendered paste exports (highlighted HTML and PDF) and markdown rendering.
+
+use super::json::Json;
+use crate::{error::HttpError, models::paste::*, AppError, AppState};
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use localpaste_core::export::{
+    render_export, render_inline_html, render_paste_html, ExportDocument,
+};
+use localpaste_core::folder_export::{store_archive_file_name, write_store_archive};
+use std::io::Write;
+use tokio::sync::mpsc;
+
+/// Response header naming how `/render` treated the paste: `markdown` or `code`.
+pub const RENDERED_AS_HEADER: &str = "x-localpaste-rendered";
+
+/// Builds an ASCII-only download filename from a display name.
+///
+/// # Arguments
+/// - `name`: Paste or folder name.
+/// - `fallback`: Stem used when nothing printable survives sanitizing.
+/// - `extension`: File extension without the leading dot.
+pub(super) fn download_filename(name: &str, fallback: &str, extension: &str) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let stem = if stem.trim_matches(['_', '.']).is_empty() {
+        fallback
+    } else {
+        stem.as_str()
+    };
+    format!("{}.{}", stem, extension)
+}
+
+/// `Content-Disposition` value marking a response as a named download.
+///
+/// # Errors
+/// Returns [`AppError::Internal`] if the filename is not a valid header value.
+pub(super) fn attachment_disposition(filename: &str) -> Result<HeaderValue, AppError> {
+    HeaderValue::from_str(format!("attachment; filename=\"{}\"", filename).as_str())
+        .map_err(|_| AppError::Internal)
+}
+
+/// Render a paste as a standalone, syntax-highlighted document.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Paste identifier from the path.
+/// - `query`: `format=html` (default) or `format=pdf`.
+///
+/// # Returns
+/// The rendered document with a matching content type and an attachment
+/// `Content-Disposition` named after the paste. Line annotations are listed
+/// as footnotes.
+///
+/// # Errors
+/// Returns an error if the paste does not exist, storage fails, or rendering
+/// fails.
+pub async fn get_paste_export(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let paste = super::paste::load_paste_for_read(&state, &id)?;
+    let annotations = state.db.annotations.list(&id)?;
+    let format = query.format;
+    let filename = download_filename(paste.name.as_str(), "paste", format.extension());
+    // Highlighting large pastes is CPU-bound; keep it off the async workers.
+    let body = tokio::task::spawn_blocking(move || {
+        render_export(
+            &ExportDocument {
+                name: paste.name.as_str(),
+                language: paste.language.as_deref(),
+                content: paste.content.as_str(),
+                annotations: annotations.as_slice(),
+            },
+            format,
+        )
+    })
+    .await
+    .map_err(|_| AppError::Internal)??;
+    let disposition = attachment_disposition(filename.as_str())?;
+    Ok((
+        StatusCode::OK,
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(format.content_type()),
+            ),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Render a paste as a standalone HTML page with inline-styled colors.
+///
+/// Unlike the `format=html` export, colors sit on each span rather than in a
+/// stylesheet, so the markup keeps its highlighting when pasted into email
+/// or wiki editors. Served inline rather than as a download.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Paste identifier from the path.
+/// - `query`: Optional `theme` naming one of syntect's bundled themes.
+///
+/// # Returns
+/// The page as `text/html`, with line annotations as footnotes.
+///
+/// # Errors
+/// Returns `400` for an unknown theme, or an error if the paste does not
+/// exist, storage fails, or rendering fails.
+pub async fn get_paste_html(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<HtmlQuery>,
+) -> Result<Response, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let paste = super::paste::load_paste_for_read(&state, &id)?;
+    let annotations = state.db.annotations.list(&id)?;
+    // Highlighting large pastes is CPU-bound; keep it off the async workers.
+    let body = tokio::task::spawn_blocking(move || {
+        render_inline_html(
+            &ExportDocument {
+                name: paste.name.as_str(),
+                language: paste.language.as_deref(),
+                content: paste.content.as_str(),
+                annotations: annotations.as_slice(),
+            },
+            query.theme.as_deref(),
+        )
+    })
+    .await
+    .map_err(|_| AppError::Internal)??;
+    Ok((
+        StatusCode::OK,
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/html; charset=utf-8"),
+        )],
+        body,
+    )
+        .into_response())
+}
+
+/// Render a paste as a sanitized HTML fragment for embedding.
+///
+/// Markdown pastes (by language, or by content detection when no language
+/// is set) are converted with raw HTML escaped and unsafe link targets
+/// dropped; other pastes become one escaped `<pre><code>` block.
+///
+/// # Returns
+/// The fragment as `text/html` with [`RENDERED_AS_HEADER`] set.
+///
+/// # Errors
+/// Returns an error if the paste does not exist or storage fails.
+pub async fn get_paste_render(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Response, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let paste = super::paste::load_paste_for_read(&state, &id)?;
+    // Large documents take a while to parse; keep it off the async workers.
+    let (body, rendered_as) = tokio::task::spawn_blocking(move || {
+        render_paste_html(
+            paste.language.as_deref(),
+            paste.is_markdown,
+            paste.content.as_str(),
+        )
+    })
+    .await
+    .map_err(|_| AppError::Internal)?;
+    Ok((
+        StatusCode::OK,
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("text/html; charset=utf-8"),
+            ),
+            (
+                header::HeaderName::from_static(RENDERED_AS_HEADER),
+                HeaderValue::from_static(rendered_as.label()),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Return every paste with full content for whole-store clones.
+///
+/// `lpaste clone` writes this list into the gist directory layout.
+///
+/// # Returns
+/// All paste rows, newest first, including recorded line endings.
+///
+/// # Errors
+/// Returns an error if storage access fails.
+pub async fn export_all_pastes(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Paste>>, HttpError> {
+    let db = state.db.clone();
+    // Loading every row is blocking storage work.
+    let pastes = tokio::task::spawn_blocking(move || db.pastes.list(usize::MAX, None))
+        .await
+        .map_err(|_| AppError::Internal)??;
+    Ok(Json(pastes))
+}
+
+/// Bytes buffered before a chunk is handed to the response body.
+const ARCHIVE_CHUNK_BYTES: usize = 256 * 1024;
+/// Chunks in flight between the archive writer and the client.
+const ARCHIVE_CHANNEL_CHUNKS: usize = 8;
+
+/// Blocking [`Write`] that forwards archive bytes to a streaming body.
+///
+/// A full channel blocks the writer, so a slow client throttles the export
+/// instead of letting it buffer.
+struct BodyChannelWriter {
+    tx: mpsc::Sender<std::io::Result<Bytes>>,
+    buf: Vec<u8>,
+}
+
+impl BodyChannelWriter {
+    fn send_buffered(&mut self) -> std::io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        let chunk = Bytes::from(std::mem::replace(
+            &mut self.buf,
+            Vec::with_capacity(ARCHIVE_CHUNK_BYTES),
+        ));
+        self.tx
+            .blocking_send(Ok(chunk))
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "client went away"))
+    }
+}
+
+impl Write for BodyChannelWriter {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        self.buf.extend_from_slice(data);
+        if self.buf.len() >= ARCHIVE_CHUNK_BYTES {
+            self.send_buffered()?;
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.send_buffered()
+    }
+}
+
+/// Export the whole store as one ZIP archive for moving to another machine.
+///
+/// The archive is streamed as it is written, one paste at a time, and
+/// switches to ZIP64 records past 4 GiB or 65535 entries.
+///
+/// # Returns
+/// `application/zip` bytes laid out as `folder-path/name.ext`, unfiled pastes
+/// at the root, plus a `manifest.json` with ids and metadata, served as a
+/// dated attachment.
+///
+/// # Errors
+/// Returns an error if the attachment header cannot be built. Storage
+/// failures after streaming starts abort the body, so the client sees a
+/// truncated download rather than an error status.
+pub async fn export_archive(State(state): State<AppState>) -> Result<Response, HttpError> {
+    let disposition = attachment_disposition(store_archive_file_name().as_str())?;
+    let (tx, rx) = mpsc::channel(ARCHIVE_CHANNEL_CHUNKS);
+    let db = state.db.clone();
+    // Reading every paste is blocking storage work.
+    tokio::task::spawn_blocking(move || {
+        let writer = BodyChannelWriter {
+            tx: tx.clone(),
+            buf: Vec::with_capacity(ARCHIVE_CHUNK_BYTES),
+        };
+        if let Err(err) = write_store_archive(&db, writer) {
+            tracing::warn!("Store archive export failed: {}", err);
+            let _ = tx.blocking_send(Err(std::io::Error::other(err.to_string())));
+        }
+    });
+    let body = Body::from_stream(futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|chunk| (chunk, rx))
+    }));
+    Ok((
+        StatusCode::OK,
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/zip"),
+            ),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn download_filename_replaces_unsafe_characters() {
+        assert_eq!(
+            download_filename("my notes/v2.rs", "paste", ExportFormat::Html.extension()),
+            "my_notes_v2.rs.html"
+        );
+        assert_eq!(download_filename("日本", "paste", "pdf"), "paste.pdf");
+    }
+}
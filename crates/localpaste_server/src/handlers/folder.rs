@@ -1,14 +1,19 @@
 //! Folder HTTP handlers.
 
 use super::deprecation::{warn_folder_deprecation, with_folder_deprecation_headers};
+use super::export::{attachment_disposition, download_filename};
+use super::json::Json;
+use crate::events::{folder_changed, paste_changed, ChangeKind};
 use crate::{error::HttpError, models::folder::*, AppError, AppState};
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
+    http::{header, HeaderValue, StatusCode},
     response::Response,
-    Json,
 };
+use localpaste_core::folder_export::export_folder_archive;
 use localpaste_core::folder_ops::{
-    create_folder_validated, delete_folder_tree_and_migrate_guarded, update_folder_validated,
+    create_folder_validated, delete_folder_tree_and_migrate_guarded, folder_tree,
+    list_folders_filtered, move_folder_validated, recursive_paste_counts, update_folder_validated,
 };
 
 /// Create a new folder.
@@ -28,23 +33,101 @@ pub async fn create_folder(
 ) -> Result<Response, HttpError> {
     warn_folder_deprecation("POST /api/folder");
     let folder = create_folder_validated(&state.db, req.name, req.parent_id)?;
+    folder_changed(&state, folder.id.as_str(), ChangeKind::Created);
     Ok(with_folder_deprecation_headers(Json(folder)))
 }
 
-/// List all folders.
+/// List folders, optionally filtered by parent and sorted.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `query`: `parent_id`, `recursive_counts`, `sort`, and `order` parameters.
 ///
 /// # Returns
-/// All folders as JSON.
+/// Folder rows as JSON; all folders in name order when no parameters are set.
 ///
 /// # Errors
-/// Returns an error if listing fails.
-pub async fn list_folders(State(state): State<AppState>) -> Result<Response, HttpError> {
+/// Returns an error if `parent_id` names a missing folder or listing fails.
+pub async fn list_folders(
+    State(state): State<AppState>,
+    Query(query): Query<FolderListQuery>,
+) -> Result<Response, HttpError> {
     warn_folder_deprecation("GET /api/folders");
 
-    let folders = state.db.folders.list()?;
+    let folders = list_folders_filtered(&state.db, &query)?;
     Ok(with_folder_deprecation_headers(Json(folders)))
 }
 
+/// Return the whole folder hierarchy as nested JSON.
+///
+/// # Arguments
+/// - `state`: Application state.
+///
+/// # Returns
+/// Top-level folders, each with `recursive_paste_count` and nested `children`.
+///
+/// # Errors
+/// Returns an error if listing folders fails.
+pub async fn get_folder_tree(State(state): State<AppState>) -> Result<Response, HttpError> {
+    warn_folder_deprecation("GET /api/folders/tree");
+    let tree = folder_tree(&state.db)?;
+    Ok(with_folder_deprecation_headers(Json(tree)))
+}
+
+/// Fetch a folder by id, including its paste statistics.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Folder identifier from the path.
+///
+/// # Returns
+/// The folder as JSON.
+///
+/// # Errors
+/// Returns an error if the folder does not exist or lookup fails.
+pub async fn get_folder(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Response, HttpError> {
+    warn_folder_deprecation("GET /api/folder/:id");
+    let folder = state.db.folders.get(&id)?.ok_or(AppError::NotFound)?;
+    Ok(with_folder_deprecation_headers(Json(folder)))
+}
+
+/// Return direct and recursive paste counts for a folder.
+///
+/// Counts come from the stored folder stats, so no paste rows are loaded.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Folder identifier from the path.
+///
+/// # Returns
+/// The folder's counts as JSON.
+///
+/// # Errors
+/// Returns an error if the folder does not exist or lookup fails.
+pub async fn count_folder_pastes(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Response, HttpError> {
+    warn_folder_deprecation("GET /api/folder/:id/count");
+    let folders = state.db.folders.list()?;
+    let folder = folders
+        .iter()
+        .find(|folder| folder.id == id)
+        .ok_or(AppError::NotFound)?;
+    let recursive = recursive_paste_counts(&folders);
+    let counts = FolderCountResponse {
+        paste_count: folder.paste_count,
+        recursive_paste_count: recursive
+            .get(id.as_str())
+            .copied()
+            .unwrap_or(folder.paste_count),
+    };
+    Ok(with_folder_deprecation_headers(Json(counts)))
+}
+
 /// Update a folder's name or parent.
 ///
 /// # Arguments
@@ -68,6 +151,31 @@ pub async fn update_folder(
     warn_folder_deprecation("PUT /api/folder/:id");
     let folder = update_folder_validated(&state.db, &id, req.name, req.parent_id)?
         .ok_or(AppError::NotFound)?;
+    folder_changed(&state, folder.id.as_str(), ChangeKind::Updated);
+    Ok(with_folder_deprecation_headers(Json(folder)))
+}
+
+/// Move a folder under a new parent, keeping its name, pastes, and subfolders.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Folder identifier from the path.
+/// - `req`: New `parent_id`; `null` or empty moves to the top level.
+///
+/// # Returns
+/// The moved folder as JSON.
+///
+/// # Errors
+/// Returns `404` for an unknown folder, `400` when the parent is missing or
+/// would make the folder its own ancestor, or storage errors.
+pub async fn move_folder(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<MoveFolderRequest>,
+) -> Result<Response, HttpError> {
+    warn_folder_deprecation("POST /api/folder/:id/move");
+    let folder = move_folder_validated(&state.db, &id, req.parent_id)?.ok_or(AppError::NotFound)?;
+    folder_changed(&state, folder.id.as_str(), ChangeKind::Updated);
     Ok(with_folder_deprecation_headers(Json(folder)))
 }
 
@@ -88,14 +196,63 @@ pub async fn delete_folder(
 ) -> Result<Response, HttpError> {
     warn_folder_deprecation("DELETE /api/folder/:id");
 
-    let _ = delete_folder_tree_and_migrate_guarded(&state.db, &id, |affected_paste_ids| {
-        state
-            .locks
-            .begin_batch_mutation(affected_paste_ids.iter())
-            .map_err(crate::locks::map_folder_delete_lock_error)
-    })?;
+    let mut moved_paste_ids = Vec::new();
+    let deleted_folder_ids =
+        delete_folder_tree_and_migrate_guarded(&state.db, &id, |affected_paste_ids| {
+            moved_paste_ids = affected_paste_ids.to_vec();
+            state
+                .locks
+                .begin_batch_mutation(affected_paste_ids.iter())
+                .map_err(crate::locks::map_folder_delete_lock_error)
+        })?;
+    for folder_id in &deleted_folder_ids {
+        folder_changed(&state, folder_id, ChangeKind::Deleted);
+    }
+    for paste_id in &moved_paste_ids {
+        paste_changed(&state, paste_id, ChangeKind::Updated);
+    }
 
     Ok(with_folder_deprecation_headers(Json(
         serde_json::json!({ "success": true }),
     )))
 }
+
+/// Export every paste under a folder, recursively, as a ZIP archive.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Root folder identifier from the path.
+///
+/// # Returns
+/// `application/zip` bytes with one directory per folder and a
+/// `manifest.json`, served as an attachment named after the folder.
+///
+/// # Errors
+/// Returns an error if the folder does not exist or storage fails.
+pub async fn export_folder(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Response, HttpError> {
+    warn_folder_deprecation("GET /api/folder/:id/export");
+    let db = state.db.clone();
+    // Reading every paste in the subtree is blocking storage work.
+    let export = tokio::task::spawn_blocking(move || export_folder_archive(&db, &id))
+        .await
+        .map_err(|_| AppError::Internal)??;
+    let stem = export
+        .file_name
+        .strip_suffix(".zip")
+        .unwrap_or(export.file_name.as_str());
+    let disposition = attachment_disposition(download_filename(stem, "folder", "zip").as_str())?;
+    Ok(with_folder_deprecation_headers((
+        StatusCode::OK,
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/zip"),
+            ),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        export.archive,
+    )))
+}
@@ -0,0 +1,100 @@
+//! Folder auto-organization rules at `/api/folder-rules`.
+//!
+//! Rules file pastes created without `folder_id` or `folder_path`: the first
+//! rule (in list order) whose pattern matches the new paste's name, language,
+//! a tag, or its source picks the folder. In multi-user mode anyone may list
+//! rules, and only admins may change them.
+
+use super::json::Json;
+use crate::{error::HttpError, AppError, AppState};
+use axum::extract::{Path, State};
+use localpaste_core::models::folder_rule::{
+    CreateFolderRuleRequest, FolderRule, ReorderFolderRulesRequest, UpdateFolderRuleRequest,
+};
+
+/// List every rule in evaluation order.
+///
+/// # Returns
+/// Rules as JSON.
+///
+/// # Errors
+/// Returns an error if storage access fails.
+pub async fn list_folder_rules(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<FolderRule>>, HttpError> {
+    Ok(Json(state.db.folder_rules.list()?))
+}
+
+/// Add a rule after the existing ones.
+///
+/// # Returns
+/// The stored rule as JSON.
+///
+/// # Errors
+/// Returns `400` for a blank or oversized pattern, an unknown folder, or when
+/// the rule limit is reached.
+pub async fn create_folder_rule(
+    State(state): State<AppState>,
+    Json(req): Json<CreateFolderRuleRequest>,
+) -> Result<Json<FolderRule>, HttpError> {
+    let rule =
+        state
+            .db
+            .folder_rules
+            .create(req.field, req.pattern.as_str(), req.folder_id.trim())?;
+    Ok(Json(rule))
+}
+
+/// Change a rule's field, pattern, or folder.
+///
+/// # Returns
+/// The updated rule as JSON.
+///
+/// # Errors
+/// Returns `400` for a blank or oversized pattern or an unknown folder, and
+/// `404` when the rule is missing.
+pub async fn update_folder_rule(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(mut req): Json<UpdateFolderRuleRequest>,
+) -> Result<Json<FolderRule>, HttpError> {
+    req.folder_id = req.folder_id.map(|folder_id| folder_id.trim().to_string());
+    let rule = state
+        .db
+        .folder_rules
+        .update(&id, req)?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(rule))
+}
+
+/// Replace the evaluation order.
+///
+/// # Returns
+/// Every rule in the new order as JSON.
+///
+/// # Errors
+/// Returns `400` unless `ids` lists every rule exactly once.
+pub async fn reorder_folder_rules(
+    State(state): State<AppState>,
+    Json(req): Json<ReorderFolderRulesRequest>,
+) -> Result<Json<Vec<FolderRule>>, HttpError> {
+    Ok(Json(state.db.folder_rules.reorder(&req.ids)?))
+}
+
+/// Delete a rule.
+///
+/// # Returns
+/// Success marker as JSON.
+///
+/// # Errors
+/// Returns `404` when the rule is missing.
+pub async fn delete_folder_rule(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, HttpError> {
+    if state.db.folder_rules.delete(&id)? {
+        Ok(Json(serde_json::json!({ "success": true })))
+    } else {
+        Err(AppError::NotFound.into())
+    }
+}
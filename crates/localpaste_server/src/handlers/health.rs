@@ -0,0 +1,99 @@
+//! Health and readiness endpoint.
+
+use super::json::Json;
+use crate::{error::HttpError, locks::LockStats, AppState};
+use axum::{extract::State, http::StatusCode};
+use localpaste_core::storage_alerts::{check_storage, HealthStatus, StorageHealth};
+use serde::Serialize;
+
+/// Database state in the health response.
+#[derive(Debug, Serialize)]
+pub struct DatabaseHealth {
+    pub path: String,
+    /// Whether a read transaction succeeded.
+    pub open: bool,
+    /// Total pastes; `None` when the database could not be read.
+    pub paste_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Lock manager state in the health response.
+#[derive(Debug, Serialize)]
+pub struct LocksHealth {
+    /// `false` when the lock state is poisoned and every edit would fail.
+    pub healthy: bool,
+    #[serde(flatten)]
+    pub stats: Option<LockStats>,
+}
+
+/// `GET /api/health` response body.
+#[derive(Debug, Serialize)]
+pub struct HealthResponse {
+    #[serde(flatten)]
+    pub storage: StorageHealth,
+    pub version: &'static str,
+    pub database: DatabaseHealth,
+    pub locks: LocksHealth,
+}
+
+/// Report liveness, readiness, and storage alerts.
+///
+/// The paste count reads one table length, so this stays cheap enough to
+/// poll instead of listing pastes.
+///
+/// # Returns
+/// `200` with `status` `ok` or `warning` (one alert per exceeded size limit),
+/// or `503` with `status` `unavailable` when the database cannot be read or
+/// the lock manager is poisoned.
+///
+/// # Errors
+/// Returns an error if the database file or backup directory cannot be read.
+pub async fn get_health(
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<HealthResponse>), HttpError> {
+    let alerts = check_storage(state.config.db_path.as_str(), state.storage_limits)?;
+    let mut storage = StorageHealth::from_alerts(alerts);
+
+    let database = match state.db.pastes.count_filtered(None, None, None) {
+        Ok(count) => DatabaseHealth {
+            path: state.config.db_path.clone(),
+            open: true,
+            paste_count: Some(count),
+            error: None,
+        },
+        Err(err) => {
+            tracing::warn!("health check could not read the database: {}", err);
+            DatabaseHealth {
+                path: state.config.db_path.clone(),
+                open: false,
+                paste_count: None,
+                error: Some(err.to_string()),
+            }
+        }
+    };
+    let stats = state.locks.stats().ok();
+    let locks = LocksHealth {
+        healthy: stats.is_some(),
+        stats,
+    };
+
+    let ready = database.open && locks.healthy;
+    if !ready {
+        storage.status = HealthStatus::Unavailable;
+    }
+    let code = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    Ok((
+        code,
+        Json(HealthResponse {
+            storage,
+            version: env!("CARGO_PKG_VERSION"),
+            database,
+            locks,
+        }),
+    ))
+}
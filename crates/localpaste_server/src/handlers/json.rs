@@ -0,0 +1,38 @@
+//! JSON extractor and response that pairs every timestamp with epoch milliseconds.
+
+use axum::{
+    extract::{rejection::JsonRejection, FromRequest, Request},
+    response::{IntoResponse, Response},
+};
+use localpaste_core::timestamps::WithEpochFields;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Drop-in for [`axum::Json`] used by every API handler.
+///
+/// Request bodies are extracted exactly as [`axum::Json`] would. Responses are
+/// serialized through [`WithEpochFields`], so each `*_at` and `last_updated`
+/// timestamp is written in UTC with an epoch-milliseconds `<key>_ms` twin in
+/// the same pass, without re-reading the body.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json<T>(pub T);
+
+#[axum::async_trait]
+impl<T, S> FromRequest<S> for Json<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        axum::Json::<T>::from_request(req, state)
+            .await
+            .map(|axum::Json(value)| Self(value))
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(WithEpochFields(&self.0)).into_response()
+    }
+}
@@ -1,10 +1,52 @@
 //! HTTP request handlers.
 
+/// Maintenance endpoints such as on-demand cleanup.
+pub mod admin;
+/// Line annotations attached to pastes.
+pub mod annotations;
+/// Binary file attachments on pastes.
+pub mod attachments;
+/// Chunked upload endpoints for content larger than one request body.
+pub mod chunks;
+/// Collaborative patch edits that merge instead of waiting on the edit lock.
+pub mod collab;
+/// Comment threads attached to pastes.
+pub mod comments;
 /// Deprecation warning helpers for legacy request pathways.
 pub(crate) mod deprecation;
+/// Markdown activity digest generation.
+pub mod digest;
+/// Rendered HTML and PDF paste exports and sanitized markdown fragments.
+pub mod export;
 /// Folder-related endpoints.
 pub mod folder;
+/// Rules that file new pastes into folders.
+pub mod folder_rules;
+/// Health and readiness: version, database and lock state, and storage alerts.
+pub mod health;
+/// JSON extractor and response that adds epoch-millisecond timestamp fields.
+pub mod json;
 /// Request normalization helpers shared across handlers.
 pub(crate) mod normalize;
 /// Paste-related endpoints.
 pub mod paste;
+/// Who is editing or viewing a paste.
+pub mod presence;
+/// Raw paste content with HTTP range support.
+pub mod raw;
+/// Per-client recently opened pastes.
+pub mod recent;
+/// Server-Sent Events stream of paste and folder changes with resume.
+pub mod sse;
+/// Paste totals and per-language, per-folder, and largest-paste breakdowns.
+pub mod stats;
+/// Tag listing, renaming, and removal across all pastes.
+pub mod tags;
+/// Multipart file uploads that create a paste.
+pub mod upload;
+/// Source URL links and on-demand refresh for mirrored pastes.
+pub mod url_source;
+/// User management and paste visibility endpoints for multi-user mode.
+pub mod users;
+/// WebSocket stream of paste and folder change events.
+pub mod ws;
@@ -27,3 +27,26 @@ pub(super) fn normalize_optional_for_update(value: Option<String>) -> Option<Str
         }
     })
 }
+
+/// Canonicalize a client-supplied language through the shared language table.
+///
+/// Aliases and file extensions (`js`, `node`, `py`) resolve to their canonical
+/// value. Blank values pass through unchanged so update clear semantics hold.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] for languages the table does not know.
+pub(super) fn canonical_language_input(
+    value: Option<String>,
+) -> Result<Option<String>, localpaste_core::AppError> {
+    let Some(raw) = value else {
+        return Ok(None);
+    };
+    if raw.trim().is_empty() {
+        return Ok(Some(raw));
+    }
+    localpaste_core::detection::canonical::lookup(raw.as_str())
+        .map(|spec| Some(spec.value.to_string()))
+        .ok_or_else(|| {
+            localpaste_core::AppError::BadRequest(format!("Unknown language '{}'", raw.trim()))
+        })
+}
@@ -1,19 +1,109 @@
 //! Paste HTTP handlers.
 
 use super::deprecation::maybe_with_folder_deprecation_headers;
-use super::normalize::{normalize_optional_for_create, normalize_optional_for_update};
-use crate::{error::HttpError, models::paste::*, naming, AppError, AppState};
+use super::json::Json;
+use super::normalize::{
+    canonical_language_input, normalize_optional_for_create, normalize_optional_for_update,
+};
+use crate::auth::{self, CurrentUser};
+use crate::events::{folder_changed, paste_changed, paste_deleted, ChangeKind};
+use crate::{error::HttpError, models::paste::*, AppError, AppState};
 use axum::{
     extract::{Path, Query, State},
-    http::HeaderValue,
+    http::{HeaderMap, HeaderValue, StatusCode},
     response::Response,
-    Json,
 };
+use chrono::{DateTime, Utc};
+use localpaste_core::db::paste::{parse_content_hash, sort_pastes};
 use localpaste_core::diff::{DiffRequest, DiffResponse, EqualResponse};
-use localpaste_core::folder_ops::map_missing_folder_for_optional_request;
+use localpaste_core::folder_ops::{
+    find_folder_path, map_missing_folder_for_optional_request, parse_folder_path,
+    resolve_folder_path,
+};
+use localpaste_core::lint::{lint_content, PasteLintResponse};
+use localpaste_core::models::user::{ListScope, ScopeQuery, User};
+use localpaste_core::normalization::{normalize_content, record_line_ending};
+use localpaste_core::text::CleanupOptions;
+use localpaste_core::timestamps::parse_time_filter;
+use localpaste_core::validation::{validate_content, ValidateRequest, ValidateResponse};
+use localpaste_core::PasteSource;
 
 const RESPONSE_SHAPE_HEADER: &str = "x-localpaste-response-shape";
 const META_RESPONSE_SHAPE: &str = "meta-only";
+/// Request header selecting the write source: `import` for bulk importers,
+/// `clipboard` for clipboard watchers (size limit and default-title strategy).
+pub const PASTE_SOURCE_HEADER: &str = "x-localpaste-source";
+/// Response header set to `true` when `dedupe` returned an existing paste.
+pub const DEDUPLICATED_HEADER: &str = "x-localpaste-deduplicated";
+/// Request header listing cleanup transforms for created content (`urls,quotes`).
+pub const CLEANUP_HEADER: &str = "x-localpaste-cleanup";
+/// Response header counting search rows whose `content` was left out.
+pub const CONTENT_OMITTED_HEADER: &str = "x-localpaste-content-omitted";
+
+pub(super) fn paste_source_from_headers(headers: &HeaderMap) -> PasteSource {
+    match headers
+        .get(PASTE_SOURCE_HEADER)
+        .and_then(|value| value.to_str().ok())
+    {
+        Some(value) if value.trim().eq_ignore_ascii_case("import") => PasteSource::Import,
+        Some(value) if value.trim().eq_ignore_ascii_case("clipboard") => {
+            PasteSource::ClipboardCapture
+        }
+        _ => PasteSource::Api,
+    }
+}
+
+/// Cleanup transforms requested for a create call.
+///
+/// An explicit `x-localpaste-cleanup` list wins (`none` disables cleanup);
+/// otherwise clipboard captures use `LOCALPASTE_CLIPBOARD_CLEANUP` and other
+/// sources are stored as submitted.
+///
+/// # Errors
+/// Returns [`AppError::BadRequest`] when the header names an unknown transform.
+fn cleanup_from_headers(
+    state: &AppState,
+    headers: &HeaderMap,
+    source: PasteSource,
+) -> Result<CleanupOptions, AppError> {
+    if let Some(value) = headers.get(CLEANUP_HEADER) {
+        let value = value
+            .to_str()
+            .map_err(|_| AppError::BadRequest(format!("Invalid {} header", CLEANUP_HEADER)))?;
+        return value.parse().map_err(AppError::BadRequest);
+    }
+    Ok(match source {
+        PasteSource::ClipboardCapture => *state.db.pastes.clipboard_cleanup(),
+        _ => CleanupOptions::default(),
+    })
+}
+
+pub(super) fn ensure_within_size_limit(
+    state: &AppState,
+    content_len: usize,
+    source: PasteSource,
+    folder_id: Option<&str>,
+) -> Result<(), AppError> {
+    let max_paste_size = state.config.max_paste_size_for(source, folder_id);
+    if content_len > max_paste_size {
+        return Err(AppError::BadRequest(format!(
+            "Paste size exceeds maximum of {} bytes",
+            max_paste_size
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn current_folder_for_size_limit(
+    state: &AppState,
+    id: &str,
+) -> Result<Option<String>, AppError> {
+    // Folder overrides are rare; skip the extra read when none are configured.
+    if state.config.size_limits.folders.is_empty() {
+        return Ok(None);
+    }
+    Ok(state.db.pastes.get(id)?.and_then(|paste| paste.folder_id))
+}
 
 fn with_meta_only_response_shape(mut response: Response) -> Response {
     response.headers_mut().insert(
@@ -33,19 +123,57 @@ fn normalize_folder_filter_for_query(folder_id: Option<String>) -> (Option<Strin
     (normalized, used)
 }
 
-fn normalize_search_filters_for_query(
+/// Parses a `before`/`after` search bound.
+fn parse_search_time(param: &str, raw: Option<&str>) -> Result<Option<DateTime<Utc>>, AppError> {
+    let Some(raw) = raw.map(str::trim).filter(|raw| !raw.is_empty()) else {
+        return Ok(None);
+    };
+    parse_time_filter(raw).map(Some).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "{} must be an RFC3339 timestamp or a YYYY-MM-DD date",
+            param
+        ))
+    })
+}
+
+/// Builds search filters from query parameters.
+///
+/// `folder` is a slash-separated path resolved by folder name; it cannot be
+/// combined with `folder_id`.
+///
+/// # Returns
+/// The row limit, the filters, and whether the legacy `folder_id` was used.
+///
+/// # Errors
+/// Returns `400` for conflicting folder parameters or unparsable time bounds
+/// and `404` when `folder` names no existing folder.
+fn search_filters_for_query(
+    state: &AppState,
     query: &SearchQuery,
-) -> (usize, Option<String>, Option<String>, bool) {
+) -> Result<(usize, SearchFilters, bool), AppError> {
     let limit = normalized_limit(query.limit);
-    let normalized_language = normalize_optional_for_create(query.language.clone());
-    let (normalized_folder_id, folder_filter_used) =
+    let (folder_id, folder_filter_used) =
         normalize_folder_filter_for_query(query.folder_id.clone());
-    (
-        limit,
-        normalized_folder_id,
-        normalized_language,
-        folder_filter_used,
-    )
+    let folder_id = match normalize_optional_for_create(query.folder.clone()) {
+        Some(_) if folder_id.is_some() => {
+            return Err(AppError::BadRequest(
+                "Set either folder_id or folder, not both".to_string(),
+            ));
+        }
+        Some(path) => Some(
+            find_folder_path(&state.db, &parse_folder_path(path.as_str())?)?
+                .ok_or(AppError::NotFound)?,
+        ),
+        None => folder_id,
+    };
+    let filters = SearchFilters {
+        folder_id,
+        language: normalize_optional_for_create(query.language.clone()),
+        tag: normalize_optional_for_create(query.tag.clone()),
+        before: parse_search_time("before", query.before.as_deref())?,
+        after: parse_search_time("after", query.after.as_deref())?,
+    };
+    Ok((limit, filters, folder_filter_used))
 }
 
 fn with_folder_metadata_response(response: Response, include_meta_shape_header: bool) -> Response {
@@ -114,18 +242,160 @@ where
     inferred
 }
 
+fn with_list_snippets(
+    state: &AppState,
+    items: Vec<PasteMeta>,
+) -> Result<Vec<PasteMetaWithSnippet>, AppError> {
+    // Rows deleted between the meta scan and the content read are dropped
+    // instead of surfacing an empty snippet for a paste that no longer exists.
+    let mut rows = Vec::with_capacity(items.len());
+    for meta in items {
+        let Some(paste) = state.db.pastes.get(meta.id.as_str())? else {
+            continue;
+        };
+        // A snippet would leak one-time content without burning it.
+        let snippet = if paste.burn_after_read {
+            String::new()
+        } else {
+            localpaste_core::text::collapsed_snippet(
+                paste.content.as_str(),
+                localpaste_core::PASTE_LIST_SNIPPET_CHARS,
+            )
+        };
+        rows.push(PasteMetaWithSnippet { meta, snippet });
+    }
+    Ok(rows)
+}
+
+fn with_search_matches(
+    state: &AppState,
+    items: Vec<PasteMeta>,
+    query: &str,
+) -> Result<Vec<PasteMetaWithMatches>, AppError> {
+    // Same policy as list snippets: rows deleted since the scan are dropped.
+    let mut rows = Vec::with_capacity(items.len());
+    for meta in items {
+        let Some(paste) = state.db.pastes.get(meta.id.as_str())? else {
+            continue;
+        };
+        let matches = content_matches(paste.content.as_str(), query);
+        rows.push(PasteMetaWithMatches { meta, matches });
+    }
+    Ok(rows)
+}
+
+fn content_matches(content: &str, query: &str) -> Vec<ContentMatch> {
+    localpaste_core::text::find_match_ranges(content, query, localpaste_core::SEARCH_MATCHES_MAX)
+        .into_iter()
+        .map(|range| ContentMatch {
+            start: range.start,
+            end: range.end,
+        })
+        .collect()
+}
+
+/// Attach full content to search rows until `budget` bytes are spent. Rows that
+/// do not fit, and `burn_after_read` pastes, keep `content: None`.
+///
+/// # Returns
+/// The rows and how many of them had content left out.
+fn with_search_content(
+    state: &AppState,
+    items: Vec<PasteMeta>,
+    matches_for: Option<&str>,
+    mut budget: usize,
+) -> Result<(Vec<PasteMetaWithContent>, usize), AppError> {
+    let mut omitted = 0;
+    let mut rows = Vec::with_capacity(items.len());
+    for meta in items {
+        let Some(paste) = state.db.pastes.get(meta.id.as_str())? else {
+            continue;
+        };
+        let matches = matches_for.map(|query| content_matches(paste.content.as_str(), query));
+        let content = if paste.burn_after_read || paste.content.len() > budget {
+            omitted += 1;
+            None
+        } else {
+            budget -= paste.content.len();
+            Some(paste.content)
+        };
+        rows.push(PasteMetaWithContent {
+            meta,
+            matches,
+            content,
+        });
+    }
+    Ok((rows, omitted))
+}
+
+fn meta_matches_list_filters(meta: &PasteMeta, tag: Option<&str>, language: Option<&str>) -> bool {
+    let tag_matches = tag.is_none_or(|tag| {
+        meta.tags
+            .iter()
+            .any(|candidate| normalize_tag_filter(Some(candidate)).as_deref() == Some(tag))
+    });
+    tag_matches
+        && language.is_none_or(|language| {
+            normalize_language_filter(meta.language.as_deref()).as_deref() == Some(language)
+        })
+}
+
 fn list_meta_response(
     state: &AppState,
+    user: Option<&User>,
+    scope: ListScope,
     query: ListQuery,
     route_hint: &'static str,
     include_meta_shape_header: bool,
 ) -> Result<Response, HttpError> {
     let limit = normalized_limit(query.limit);
+    let scan_limit = auth::scan_limit(user, limit);
     let (normalized_folder_id, folder_filter_used) =
         normalize_folder_filter_for_query(query.folder_id);
-    let items = state.db.pastes.list_meta(limit, normalized_folder_id)?;
-    let response =
-        maybe_with_folder_deprecation_headers(Json(items), folder_filter_used, route_hint);
+    let tag_filter = normalize_tag_filter(query.tag.as_deref());
+    let language_filter = normalize_language_filter(query.language.as_deref());
+    let sort = query.sort.unwrap_or_default();
+    let order = query.order.unwrap_or_else(|| sort.default_order());
+    let items = match query.content_hash.as_deref() {
+        Some(raw) => {
+            let hash = parse_content_hash(raw)?;
+            // Hash lookups return few rows, so filters and sorting apply afterwards.
+            let mut pastes = state.db.pastes.find_by_content_hash(
+                hash.as_str(),
+                scan_limit,
+                normalized_folder_id.as_deref(),
+            )?;
+            sort_pastes(&mut pastes, sort, order);
+            pastes
+                .iter()
+                .map(PasteMeta::from)
+                .filter(|meta| {
+                    meta_matches_list_filters(
+                        meta,
+                        tag_filter.as_deref(),
+                        language_filter.as_deref(),
+                    )
+                })
+                .collect()
+        }
+        None => state.db.pastes.list_meta_sorted(
+            scan_limit,
+            normalized_folder_id.as_deref(),
+            tag_filter.as_deref(),
+            language_filter.as_deref(),
+            sort,
+            order,
+        )?,
+    };
+    let items = auth::retain_scoped(&state.db, user, scope, items, limit, |meta| {
+        meta.id.as_str()
+    })?;
+    let response = if query.include_snippet.unwrap_or(false) {
+        let rows = with_list_snippets(state, items)?;
+        maybe_with_folder_deprecation_headers(Json(rows), folder_filter_used, route_hint)
+    } else {
+        maybe_with_folder_deprecation_headers(Json(items), folder_filter_used, route_hint)
+    };
     Ok(with_folder_metadata_response(
         response,
         include_meta_shape_header,
@@ -140,94 +410,280 @@ enum SearchMode {
 
 fn search_meta_response(
     state: &AppState,
+    user: Option<&User>,
+    scope: ListScope,
     query: SearchQuery,
     mode: SearchMode,
     route_hint: &'static str,
     include_meta_shape_header: bool,
 ) -> Result<Response, HttpError> {
-    let (limit, normalized_folder_id, normalized_language, folder_filter_used) =
-        normalize_search_filters_for_query(&query);
+    let (limit, filters, folder_filter_used) = search_filters_for_query(state, &query)?;
+    let scan_limit = auth::scan_limit(user, limit);
     let items = match mode {
         SearchMode::Canonical => {
             // Preserve content-match semantics from canonical search while returning
             // metadata rows to avoid large full-content responses.
-            state
-                .db
-                .pastes
-                .search(&query.q, limit, normalized_folder_id, normalized_language)?
+            state.db.pastes.search(&query.q, scan_limit, &filters)?
         }
-        SearchMode::MetaOnly => state.db.pastes.search_meta(
-            &query.q,
-            limit,
-            normalized_folder_id,
-            normalized_language,
-        )?,
+        SearchMode::MetaOnly => state
+            .db
+            .pastes
+            .search_meta(&query.q, scan_limit, &filters)?,
+    };
+    let items = auth::retain_scoped(&state.db, user, scope, items, limit, |meta| {
+        meta.id.as_str()
+    })?;
+    // Metadata search never reads content, so it has no match positions to report.
+    let include_matches =
+        matches!(mode, SearchMode::Canonical) && query.include_matches.unwrap_or(false);
+    let include_content = query.include_content.unwrap_or(false);
+    let response = if include_content {
+        let matches_for = include_matches.then_some(query.q.as_str());
+        let (rows, omitted) = with_search_content(
+            state,
+            items,
+            matches_for,
+            localpaste_core::SEARCH_CONTENT_MAX_BYTES,
+        )?;
+        let mut response =
+            maybe_with_folder_deprecation_headers(Json(rows), folder_filter_used, route_hint);
+        if omitted > 0 {
+            response
+                .headers_mut()
+                .insert(CONTENT_OMITTED_HEADER, HeaderValue::from(omitted));
+        }
+        response
+    } else if include_matches {
+        let rows = with_search_matches(state, items, query.q.as_str())?;
+        maybe_with_folder_deprecation_headers(Json(rows), folder_filter_used, route_hint)
+    } else {
+        maybe_with_folder_deprecation_headers(Json(items), folder_filter_used, route_hint)
     };
-    let response =
-        maybe_with_folder_deprecation_headers(Json(items), folder_filter_used, route_hint);
+    // Rows carrying full content are no longer the meta-only shape.
     Ok(with_folder_metadata_response(
         response,
-        include_meta_shape_header,
+        include_meta_shape_header && !include_content,
     ))
 }
 
 fn compare_pastes_json<T, F>(
     state: &AppState,
-    req: DiffRequest,
+    user: Option<&User>,
+    mut req: DiffRequest,
     compare: F,
 ) -> Result<Json<T>, HttpError>
 where
     F: FnOnce(&localpaste_core::Database, &DiffRequest) -> Result<Option<T>, AppError>,
 {
+    for side in [&mut req.left, &mut req.right] {
+        side.paste_id = state.db.pastes.resolve_id(side.paste_id.as_str())?;
+        auth::ensure_readable(state, user, side.paste_id.as_str())?;
+    }
     let response = compare(state.db.as_ref(), &req)?.ok_or(AppError::NotFound)?;
     Ok(Json(response))
 }
 
-/// Create a new paste.
-///
-/// # Arguments
-/// - `state`: Application state.
-/// - `req`: Paste creation payload.
-///
-/// # Returns
-/// The created paste as JSON.
+/// Maximum number of pastes accepted by one batch create request.
+pub const MAX_BATCH_CREATE_PASTES: usize = 500;
+
+/// Rejects an `expires_in` beyond [`localpaste_core::MAX_PASTE_EXPIRES_IN_SECS`].
+fn validate_expires_in(expires_in: Option<u64>) -> Result<(), AppError> {
+    match expires_in {
+        Some(secs) if secs > localpaste_core::MAX_PASTE_EXPIRES_IN_SECS => {
+            Err(AppError::BadRequest(format!(
+                "expires_in must be at most {} seconds",
+                localpaste_core::MAX_PASTE_EXPIRES_IN_SECS
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// A validated create payload that has not touched storage yet.
+struct PendingPaste {
+    paste: Paste,
+    /// Whether the caller asked for `dedupe`.
+    dedupe: bool,
+    /// `folder_path` segments and `create_folders`, applied by
+    /// [`PendingPaste::into_paste`].
+    folder_path: Option<(Vec<String>, bool)>,
+}
+
+impl PendingPaste {
+    /// Resolve `folder_path`, creating missing folders, and file the paste
+    /// there. Call only once a new row will be inserted.
+    fn into_paste(self, state: &AppState) -> Result<Paste, AppError> {
+        let mut paste = self.paste;
+        if let Some((segments, create_folders)) = self.folder_path {
+            let (folder_id, created) = resolve_folder_path(&state.db, &segments, create_folders)?;
+            for folder in &created {
+                folder_changed(state, folder.id.as_str(), ChangeKind::Created);
+            }
+            paste.folder_id = Some(folder_id);
+        }
+        Ok(paste)
+    }
+}
+
+/// Validates and normalizes a create payload into a paste row.
 ///
-/// # Errors
-/// Returns an error if validation or persistence fails.
-pub async fn create_paste(
-    State(state): State<AppState>,
-    Json(req): Json<CreatePasteRequest>,
-) -> Result<Response, HttpError> {
-    let folder_field_used = req.folder_id.is_some();
+/// Folders named by `folder_path` are only created by
+/// [`PendingPaste::into_paste`], so rejected and deduplicated creates leave
+/// none behind.
+fn paste_for_create(
+    state: &AppState,
+    source: PasteSource,
+    cleanup: CleanupOptions,
+    req: CreatePasteRequest,
+) -> Result<PendingPaste, AppError> {
     let CreatePasteRequest {
         content,
         language,
         language_is_manual,
         folder_id,
+        folder_path,
+        create_folders,
         tags,
         name,
+        dedupe,
+        expires_in,
+        burn_after_read,
     } = req;
+    validate_expires_in(expires_in)?;
+    let language = canonical_language_input(language)?;
     let normalized_folder_id = normalize_optional_for_create(folder_id);
-
-    // Check paste size limit
-    if content.len() > state.config.max_paste_size {
-        return Err(AppError::BadRequest(format!(
-            "Paste size exceeds maximum of {} bytes",
-            state.config.max_paste_size
-        ))
-        .into());
+    let folder_segments = normalize_optional_for_create(folder_path)
+        .map(|path| parse_folder_path(path.as_str()))
+        .transpose()?;
+    if folder_segments.is_some() && normalized_folder_id.is_some() {
+        return Err(AppError::BadRequest(
+            "Set either folder_id or folder_path, not both".to_string(),
+        ));
     }
+    let size_limit_folder_id = match folder_segments.as_deref() {
+        Some(segments) => find_folder_path(&state.db, segments)?,
+        None => normalized_folder_id.clone(),
+    };
 
-    let name = name.unwrap_or_else(naming::generate_name);
+    ensure_within_size_limit(
+        state,
+        content.len(),
+        source,
+        size_limit_folder_id.as_deref(),
+    )?;
+
+    let content = cleanup.apply(content);
+    let normalization = state.db.pastes.normalization();
+    let (content, detected_line_ending) = normalize_content(content, normalization);
+    let name = name.unwrap_or_else(|| {
+        state
+            .db
+            .pastes
+            .title_options()
+            .for_source(source)
+            .name_for(content.as_str())
+    });
     let mut paste = build_paste_for_create(content, name, language, language_is_manual);
+    paste.line_ending = record_line_ending(
+        paste.line_ending,
+        detected_line_ending,
+        normalization.line_endings,
+    );
 
-    if let Some(ref folder_id) = normalized_folder_id {
-        paste.folder_id = Some(folder_id.clone());
+    if let Some(folder_id) = normalized_folder_id {
+        paste.folder_id = Some(folder_id);
     }
 
     if let Some(tags) = tags {
         paste.tags = tags;
     }
+    if paste.folder_id.is_none() && folder_segments.is_none() {
+        if let Some(rule) = state.db.folder_rules.folder_for(&paste, source)? {
+            // The rule's folder may carry a stricter size limit.
+            ensure_within_size_limit(
+                state,
+                paste.content.len(),
+                source,
+                Some(rule.folder_id.as_str()),
+            )?;
+            paste.folder_id = Some(rule.folder_id);
+        }
+    }
+    paste.expires_at = expires_in.and_then(|secs| expiry_after(paste.created_at, secs));
+    paste.burn_after_read = burn_after_read.unwrap_or(false);
+    // A one-time paste must be a fresh row, never a shared existing one.
+    let dedupe = dedupe.unwrap_or(false) && !paste.burn_after_read;
+    Ok(PendingPaste {
+        paste,
+        dedupe,
+        folder_path: folder_segments.map(|segments| (segments, create_folders.unwrap_or(true))),
+    })
+}
+
+/// Create a new paste.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `headers`: Request headers (`x-localpaste-source` selects the size-limit source;
+///   `x-localpaste-cleanup` selects cleanup transforms).
+/// - `user`: Caller in multi-user mode; recorded as the owner.
+/// - `req`: Paste creation payload; `dedupe: true` reuses identical content,
+///   and `folder_path` files the paste by folder names, creating missing
+///   folders unless `create_folders` is `false`.
+///
+/// # Returns
+/// The created paste as JSON, or the existing duplicate with
+/// `x-localpaste-deduplicated: true` when `dedupe` matched. In multi-user mode
+/// only the caller's own pastes are dedupe candidates.
+///
+/// # Errors
+/// Returns an error if validation or persistence fails.
+pub async fn create_paste(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    user: CurrentUser,
+    Json(req): Json<CreatePasteRequest>,
+) -> Result<Response, HttpError> {
+    Ok(create_paste_response(
+        &state,
+        &headers,
+        user.as_deref(),
+        req,
+        "POST /api/paste with folder_id",
+    )?)
+}
+
+/// Shared create path for JSON and multipart uploads.
+///
+/// `folder_pathway` labels the deprecation warning logged when the request
+/// sets `folder_id` or `folder_path`.
+pub(super) fn create_paste_response(
+    state: &AppState,
+    headers: &HeaderMap,
+    user: Option<&User>,
+    req: CreatePasteRequest,
+    folder_pathway: &str,
+) -> Result<Response, AppError> {
+    let folder_field_used = req.folder_id.is_some() || req.folder_path.is_some();
+    let source = paste_source_from_headers(headers);
+    let cleanup = cleanup_from_headers(state, headers, source)?;
+    let pending = paste_for_create(state, source, cleanup, req)?;
+    if pending.dedupe {
+        if let Some(existing) = find_own_duplicate(state, user, pending.paste.content.as_str())?
+            .filter(|existing| !existing.burn_after_read)
+        {
+            let mut response = maybe_with_folder_deprecation_headers(
+                Json(existing),
+                folder_field_used,
+                folder_pathway,
+            );
+            response
+                .headers_mut()
+                .insert(DEDUPLICATED_HEADER, HeaderValue::from_static("true"));
+            return Ok(response);
+        }
+    }
+    let paste = pending.into_paste(state)?;
 
     // Use transaction-like operation for atomic folder count update
     if let Some(ref folder_id) = paste.folder_id {
@@ -237,11 +693,132 @@ pub async fn create_paste(
     } else {
         state.db.pastes.create(&paste)?;
     }
+    auth::record_owner(state, user, paste.id.as_str())?;
+    paste_changed(state, paste.id.as_str(), ChangeKind::Created);
 
     Ok(maybe_with_folder_deprecation_headers(
         Json(paste),
         folder_field_used,
-        "POST /api/paste with folder_id",
+        folder_pathway,
+    ))
+}
+
+/// Check content for size, syntax, and secret problems without saving it.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `headers`: Request headers (`x-localpaste-source` selects the size-limit source).
+/// - `req`: Content, optional language (detected when omitted), and optional folder.
+///
+/// # Returns
+/// Structured findings as JSON; `valid` is `false` when a save would be
+/// rejected or the content does not parse as its language.
+///
+/// # Errors
+/// Returns `400` for an unknown language.
+pub async fn validate_paste_content(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<ValidateRequest>,
+) -> Result<Json<ValidateResponse>, HttpError> {
+    let source = paste_source_from_headers(&headers);
+    let folder_id = normalize_optional_for_create(req.folder_id);
+    let max_size = state
+        .config
+        .max_paste_size_for(source, folder_id.as_deref());
+    let language = canonical_language_input(req.language)?
+        .filter(|language| !language.trim().is_empty())
+        .or_else(|| localpaste_core::models::paste::detect_language(req.content.as_str()));
+    Ok(Json(validate_content(
+        req.content.as_str(),
+        language.as_deref(),
+        max_size,
+    )))
+}
+
+fn find_own_duplicate(
+    state: &AppState,
+    user: Option<&User>,
+    content: &str,
+) -> Result<Option<Paste>, AppError> {
+    let Some(user) = user else {
+        return state.db.pastes.find_duplicate(content);
+    };
+    let hash = localpaste_core::db::paste::paste_content_hash(content);
+    for candidate in
+        state
+            .db
+            .pastes
+            .find_by_content_hash(hash.as_str(), auth::SCOPED_SCAN_LIMIT, None)?
+    {
+        let access = state.db.users.paste_access(candidate.id.as_str())?;
+        if access.is_some_and(|access| access.owner_id == user.id) {
+            return Ok(Some(candidate));
+        }
+    }
+    Ok(None)
+}
+
+/// Create several pastes in one transaction.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `headers`: Request headers (`x-localpaste-source` selects the size-limit source;
+///   `x-localpaste-cleanup` selects cleanup transforms).
+/// - `user`: Caller in multi-user mode; recorded as the owner of every paste.
+/// - `reqs`: Paste creation payloads, in order; `dedupe` is not supported.
+///
+/// # Returns
+/// The created pastes as JSON, in request order.
+///
+/// # Errors
+/// Returns an error if the batch is empty or too large, any item fails
+/// validation, or persistence fails; nothing is created in that case.
+pub async fn create_pastes_batch(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    user: CurrentUser,
+    Json(reqs): Json<Vec<CreatePasteRequest>>,
+) -> Result<Response, HttpError> {
+    if reqs.is_empty() {
+        return Err(
+            AppError::BadRequest("Batch must contain at least one paste".to_string()).into(),
+        );
+    }
+    if reqs.len() > MAX_BATCH_CREATE_PASTES {
+        return Err(AppError::BadRequest(format!(
+            "Batch exceeds maximum of {} pastes",
+            MAX_BATCH_CREATE_PASTES
+        ))
+        .into());
+    }
+    let folder_field_used = reqs
+        .iter()
+        .any(|req| req.folder_id.is_some() || req.folder_path.is_some());
+    let source = paste_source_from_headers(&headers);
+    let cleanup = cleanup_from_headers(&state, &headers, source)?;
+    let mut pastes = Vec::with_capacity(reqs.len());
+    for req in reqs {
+        let pending = paste_for_create(&state, source, cleanup, req)?;
+        if pending.dedupe {
+            return Err(AppError::BadRequest(
+                "dedupe is not supported for batch create".to_string(),
+            )
+            .into());
+        }
+        pastes.push(pending.into_paste(&state)?);
+    }
+
+    crate::db::TransactionOps::create_pastes(&state.db, &pastes)?;
+    for paste in &pastes {
+        auth::record_owner(&state, user.as_deref(), paste.id.as_str())?;
+        paste_changed(&state, paste.id.as_str(), ChangeKind::Created);
+    }
+
+    Ok(maybe_with_folder_deprecation_headers(
+        Json(pastes),
+        folder_field_used,
+        "POST /api/pastes/batch with folder_id",
     ))
 }
 
@@ -250,22 +827,212 @@ pub async fn create_paste(
 /// # Arguments
 /// - `state`: Application state.
 /// - `id`: Paste identifier from the path.
+/// - `headers`: Request headers (`x-localpaste-client` names the client in
+///   recent history).
 ///
 /// # Returns
-/// The paste as JSON.
+/// The paste as JSON. A `burn_after_read` paste is deleted in the same
+/// transaction that reads it, so only the first request succeeds.
 ///
 /// # Errors
-/// Returns an error if the paste does not exist or lookup fails.
+/// Returns an error if the paste does not exist or lookup fails, and `423`
+/// when a `burn_after_read` paste is open for editing.
 pub async fn get_paste(
     State(state): State<AppState>,
     Path(id): Path<String>,
+    headers: HeaderMap,
 ) -> Result<Json<Paste>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let paste = state.db.pastes.get(&id)?.ok_or(AppError::NotFound)?;
+    if !paste.burn_after_read {
+        super::recent::record_open(&state, &headers, &paste.id);
+    }
+    Ok(Json(burn_if_requested(&state, paste)?))
+}
+
+/// Load a paste for a route that serves its content.
+///
+/// Every content route goes through here so a `burn_after_read` paste is
+/// deleted by whichever route reads it first, not only by [`get_paste`].
+///
+/// # Errors
+/// Returns `404` when the paste is missing or a concurrent read burned it,
+/// and `423` while a one-time paste is open for editing.
+pub(crate) fn load_paste_for_read(state: &AppState, id: &str) -> Result<Paste, AppError> {
+    let paste = state.db.pastes.get(id)?.ok_or(AppError::NotFound)?;
+    burn_if_requested(state, paste)
+}
+
+/// Load a paste for a route that derives output from its content.
+///
+/// Previews, lint reports, and version history would reveal a
+/// `burn_after_read` paste without consuming it, so those pastes read as
+/// missing here.
+///
+/// # Errors
+/// Returns `404` when the paste is missing or is `burn_after_read`.
+pub(crate) fn load_paste_unburned(state: &AppState, id: &str) -> Result<Paste, AppError> {
     state
         .db
         .pastes
-        .get(&id)?
-        .map(Json)
-        .ok_or_else(|| AppError::NotFound.into())
+        .get(id)?
+        .filter(|paste| !paste.burn_after_read)
+        .ok_or(AppError::NotFound)
+}
+
+/// Delete a `burn_after_read` paste as it is read.
+///
+/// # Returns
+/// `paste` unchanged when it is not one-time, otherwise the row removed under
+/// the delete guards.
+///
+/// # Errors
+/// Returns `404` when a concurrent read burned it first and `423` while it is
+/// open for editing.
+fn burn_if_requested(state: &AppState, paste: Paste) -> Result<Paste, AppError> {
+    if !paste.burn_after_read {
+        return Ok(paste);
+    }
+    let (folder_guard, _mutation_guard) = crate::locks::acquire_folder_scoped_mutation_guards(
+        state.db.as_ref(),
+        state.locks.as_ref(),
+        &paste.id,
+        "Paste is currently open for editing.",
+        None,
+    )?;
+    let access = state.db.users.paste_access(&paste.id)?;
+    let burned = crate::db::TransactionOps::take_paste_with_folder_locked(
+        &state.db,
+        &folder_guard,
+        &paste.id,
+    )?
+    .ok_or(AppError::NotFound)?;
+    paste_deleted(state, burned.id.as_str(), access);
+    Ok(burned)
+}
+
+/// Check whether a paste exists without loading its content.
+///
+/// Served for `HEAD /api/paste/:id`; ids resolve like [`get_paste`].
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Paste id, slug, or unique id prefix from the path.
+///
+/// # Returns
+/// `200 OK` with an empty body when the paste exists.
+///
+/// # Errors
+/// Returns `404` when no paste matches or the prefix is ambiguous.
+pub async fn paste_exists(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, HttpError> {
+    state.db.pastes.resolve_id(&id)?;
+    Ok(StatusCode::OK)
+}
+
+/// Fetch a paste by its slug.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `slug`: Slug from the path; matched case-insensitively.
+///
+/// # Returns
+/// The paste as JSON; `burn_after_read` pastes are deleted like in [`get_paste`].
+///
+/// # Errors
+/// Returns an error if no paste owns the slug or lookup fails.
+pub async fn get_paste_by_slug(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<Json<Paste>, HttpError> {
+    let paste = state
+        .db
+        .pastes
+        .get_by_slug(&slug)?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(burn_if_requested(&state, paste)?))
+}
+
+/// Set, rename, or clear a paste slug.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Paste identifier from the path.
+/// - `req`: New slug, or `null` to clear it.
+///
+/// # Returns
+/// Updated paste as JSON.
+///
+/// # Errors
+/// Returns `400` for invalid slugs, `409` when another paste owns the slug,
+/// `423` while the paste is locked for editing, and `404` when it is missing.
+pub async fn set_paste_slug(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<SetSlugRequest>,
+) -> Result<Json<Paste>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let _mutation_guard = crate::locks::acquire_paste_mutation_guard(
+        state.locks.as_ref(),
+        &id,
+        "Paste is currently open for editing.",
+        None,
+    )?;
+    let paste = state
+        .db
+        .pastes
+        .set_slug(&id, req.slug.as_deref())?
+        .ok_or(AppError::NotFound)?;
+    paste_changed(&state, paste.id.as_str(), ChangeKind::Updated);
+    Ok(Json(paste))
+}
+
+/// Fetch a truncated leading-lines preview of a paste.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Paste identifier from the path.
+/// - `query`: Optional `lines` budget (default 10, capped at 50).
+///
+/// # Returns
+/// Preview snippet plus display metadata as JSON.
+///
+/// # Errors
+/// Returns an error if the paste does not exist or lookup fails.
+pub async fn get_paste_preview(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<PreviewQuery>,
+) -> Result<Json<PastePreview>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let paste = load_paste_unburned(&state, &id)?;
+    let lines = query
+        .lines
+        .unwrap_or(localpaste_core::PASTE_PREVIEW_DEFAULT_LINES);
+    Ok(Json(PastePreview::from_paste(&paste, lines)))
+}
+
+/// Check a JSON, YAML, or TOML paste for syntax errors.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Paste identifier from the path.
+///
+/// # Returns
+/// The first parse error, or `checked: false` for languages without a check.
+///
+/// # Errors
+/// Returns an error if the paste does not exist or lookup fails.
+pub async fn get_paste_lint(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<PasteLintResponse>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let paste = load_paste_unburned(&state, &id)?;
+    let report = lint_content(paste.language.as_deref(), &paste.content);
+    Ok(Json(PasteLintResponse::new(paste.id, report)))
 }
 
 /// List historical versions for a paste.
@@ -285,6 +1052,8 @@ pub async fn list_paste_versions(
     Path(id): Path<String>,
     Query(query): Query<VersionListQuery>,
 ) -> Result<Json<Vec<VersionMeta>>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    load_paste_unburned(&state, &id)?;
     let items = state
         .db
         .pastes
@@ -309,6 +1078,8 @@ pub async fn get_paste_version(
     State(state): State<AppState>,
     Path((id, version_id_ms)): Path<(String, u64)>,
 ) -> Result<Json<VersionSnapshot>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    load_paste_unburned(&state, &id)?;
     let snapshot = state
         .db
         .pastes
@@ -333,17 +1104,24 @@ pub async fn reset_hard_paste_version(
     State(state): State<AppState>,
     Path((id, version_id_ms)): Path<(String, u64)>,
 ) -> Result<Json<Paste>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
     let _mutation_guard = crate::locks::acquire_paste_mutation_guard(
         state.locks.as_ref(),
         id.as_str(),
         "Paste is currently open for editing.",
         None,
     )?;
+    load_paste_unburned(&state, &id)?;
+    let folder_id = current_folder_for_size_limit(&state, id.as_str())?;
+    let max_paste_size = state
+        .config
+        .max_paste_size_for(PasteSource::Api, folder_id.as_deref());
     let paste = state
         .db
         .pastes
-        .reset_hard_to_version(id.as_str(), version_id_ms, state.config.max_paste_size)?
+        .reset_hard_to_version(id.as_str(), version_id_ms, max_paste_size)?
         .ok_or(AppError::NotFound)?;
+    paste_changed(&state, paste.id.as_str(), ChangeKind::Updated);
     Ok(Json(paste))
 }
 
@@ -363,8 +1141,11 @@ pub async fn reset_hard_paste_version(
 pub async fn duplicate_paste_version(
     State(state): State<AppState>,
     Path((id, version_id_ms)): Path<(String, u64)>,
+    user: CurrentUser,
     payload: Option<Json<DuplicateVersionRequest>>,
 ) -> Result<Json<Paste>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    load_paste_unburned(&state, &id)?;
     let req = payload
         .map(|Json(req)| req)
         .unwrap_or(DuplicateVersionRequest { name: None });
@@ -374,10 +1155,12 @@ pub async fn duplicate_paste_version(
         .duplicate_from_version(
             id.as_str(),
             version_id_ms,
-            state.config.max_paste_size,
+            state.config.max_paste_size_for(PasteSource::Api, None),
             req.name,
         )?
         .ok_or(AppError::NotFound)?;
+    auth::record_owner(&state, user.as_deref(), paste.id.as_str())?;
+    paste_changed(&state, paste.id.as_str(), ChangeKind::Created);
     Ok(Json(paste))
 }
 
@@ -394,9 +1177,12 @@ pub async fn duplicate_paste_version(
 /// Returns an error when either reference cannot be resolved or diffing fails.
 pub async fn diff_pastes(
     State(state): State<AppState>,
+    user: CurrentUser,
     Json(req): Json<DiffRequest>,
 ) -> Result<Json<DiffResponse>, HttpError> {
-    compare_pastes_json(&state, req, |db, request| db.pastes.diff(request))
+    compare_pastes_json(&state, user.as_deref(), req, |db, request| {
+        db.pastes.diff(request)
+    })
 }
 
 /// Compare two paste references for equality without building a diff payload.
@@ -412,9 +1198,12 @@ pub async fn diff_pastes(
 /// Returns an error when either reference cannot be resolved or comparison fails.
 pub async fn equal_pastes(
     State(state): State<AppState>,
+    user: CurrentUser,
     Json(req): Json<DiffRequest>,
 ) -> Result<Json<EqualResponse>, HttpError> {
-    compare_pastes_json(&state, req, |db, request| db.pastes.equal(request))
+    compare_pastes_json(&state, user.as_deref(), req, |db, request| {
+        db.pastes.equal(request)
+    })
 }
 
 /// Update an existing paste.
@@ -422,6 +1211,8 @@ pub async fn equal_pastes(
 /// # Arguments
 /// - `state`: Application state.
 /// - `id`: Paste identifier from the path.
+/// - `headers`: Request headers (`x-localpaste-source` selects the size-limit source;
+///   `x-localpaste-client` names the client in recent history).
 /// - `req`: Paste update payload.
 ///
 /// # Returns
@@ -432,20 +1223,26 @@ pub async fn equal_pastes(
 pub async fn update_paste(
     State(state): State<AppState>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     Json(mut req): Json<UpdatePasteRequest>,
 ) -> Result<Response, HttpError> {
     let folder_field_used = req.folder_id.is_some();
+    validate_expires_in(req.expires_in)?;
+    req.language = canonical_language_input(req.language)?;
     req.folder_id = normalize_optional_for_update(req.folder_id);
 
-    // Check size limit if content is being updated
+    // Check size limit against the destination folder if content is being updated
     if let Some(ref content) = req.content {
-        if content.len() > state.config.max_paste_size {
-            return Err(AppError::BadRequest(format!(
-                "Paste size exceeds maximum of {} bytes",
-                state.config.max_paste_size
-            ))
-            .into());
-        }
+        let folder_id = match req.folder_id.as_deref() {
+            Some(folder_id) => (!folder_id.is_empty()).then(|| folder_id.to_string()),
+            None => current_folder_for_size_limit(&state, id.as_str())?,
+        };
+        ensure_within_size_limit(
+            &state,
+            content.len(),
+            paste_source_from_headers(&headers),
+            folder_id.as_deref(),
+        )?;
     }
 
     let updated = if req.folder_id.is_some() {
@@ -485,6 +1282,8 @@ pub async fn update_paste(
             .update(&id, req)?
             .ok_or(AppError::NotFound)?
     };
+    paste_changed(&state, updated.id.as_str(), ChangeKind::Updated);
+    super::recent::record_open(&state, &headers, updated.id.as_str());
 
     Ok(maybe_with_folder_deprecation_headers(
         Json(updated),
@@ -508,6 +1307,7 @@ pub async fn delete_paste(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
     let (folder_guard, _mutation_guard) = crate::locks::acquire_folder_scoped_mutation_guards(
         state.db.as_ref(),
         state.locks.as_ref(),
@@ -515,10 +1315,12 @@ pub async fn delete_paste(
         "Paste is currently open for editing.",
         None,
     )?;
+    let access = state.db.users.paste_access(&id)?;
     let deleted =
         crate::db::TransactionOps::delete_paste_with_folder_locked(&state.db, &folder_guard, &id)?;
 
     if deleted {
+        paste_deleted(&state, id.as_str(), access);
         Ok(Json(serde_json::json!({ "success": true })))
     } else {
         Err(AppError::NotFound.into())
@@ -529,6 +1331,8 @@ pub async fn delete_paste(
 ///
 /// # Arguments
 /// - `state`: Application state.
+/// - `user`: Caller in multi-user mode.
+/// - `scope`: `?scope=all` adds pastes shared by other users (multi-user mode only).
 /// - `query`: List query parameters.
 ///
 /// # Returns
@@ -538,16 +1342,27 @@ pub async fn delete_paste(
 /// Returns an error if listing fails.
 pub async fn list_pastes(
     State(state): State<AppState>,
+    user: CurrentUser,
+    Query(scope): Query<ScopeQuery>,
     Query(query): Query<ListQuery>,
 ) -> Result<Response, HttpError> {
     // This route intentionally returns metadata only to cap payload size.
-    list_meta_response(&state, query, "GET /api/pastes?folder_id=...", true)
+    list_meta_response(
+        &state,
+        user.as_deref(),
+        scope.scope,
+        query,
+        "GET /api/pastes?folder_id=...",
+        true,
+    )
 }
 
 /// List paste metadata with optional filters.
 ///
 /// # Arguments
 /// - `state`: Application state.
+/// - `user`: Caller in multi-user mode.
+/// - `scope`: `?scope=all` adds pastes shared by other users (multi-user mode only).
 /// - `query`: List query parameters.
 ///
 /// # Returns
@@ -557,15 +1372,74 @@ pub async fn list_pastes(
 /// Returns an error if listing fails.
 pub async fn list_pastes_meta(
     State(state): State<AppState>,
+    user: CurrentUser,
+    Query(scope): Query<ScopeQuery>,
     Query(query): Query<ListQuery>,
 ) -> Result<Response, HttpError> {
-    list_meta_response(&state, query, "GET /api/pastes/meta?folder_id=...", false)
+    list_meta_response(
+        &state,
+        user.as_deref(),
+        scope.scope,
+        query,
+        "GET /api/pastes/meta?folder_id=...",
+        false,
+    )
+}
+
+/// Count pastes with optional folder, tag, and language filters.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `user`: Caller in multi-user mode.
+/// - `scope`: `?scope=all` adds pastes shared by other users (multi-user mode only).
+/// - `query`: Count filters.
+///
+/// # Returns
+/// `{"count": n}` as JSON.
+///
+/// # Errors
+/// Returns an error if counting fails.
+pub async fn count_pastes(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(scope): Query<ScopeQuery>,
+    Query(query): Query<PasteCountQuery>,
+) -> Result<Json<PasteCountResponse>, HttpError> {
+    let folder_id = normalize_optional_for_create(query.folder_id);
+    let count = match user.as_deref() {
+        // Scoped counts check access rows one by one, so they walk metadata.
+        Some(user) => {
+            let rows = state.db.pastes.list_meta_filtered(
+                auth::SCOPED_SCAN_LIMIT,
+                folder_id.as_deref(),
+                query.tag.as_deref(),
+                query.language.as_deref(),
+            )?;
+            auth::retain_scoped(
+                &state.db,
+                Some(user),
+                scope.scope,
+                rows,
+                usize::MAX,
+                |meta| meta.id.as_str(),
+            )?
+            .len()
+        }
+        None => state.db.pastes.count_filtered(
+            folder_id.as_deref(),
+            query.tag.as_deref(),
+            query.language.as_deref(),
+        )?,
+    };
+    Ok(Json(PasteCountResponse { count }))
 }
 
 /// Search pastes by query.
 ///
 /// # Arguments
 /// - `state`: Application state.
+/// - `user`: Caller in multi-user mode.
+/// - `scope`: `?scope=all` adds pastes shared by other users (multi-user mode only).
 /// - `query`: Search query parameters.
 ///
 /// # Returns
@@ -575,10 +1449,14 @@ pub async fn list_pastes_meta(
 /// Returns an error if search fails.
 pub async fn search_pastes(
     State(state): State<AppState>,
+    user: CurrentUser,
+    Query(scope): Query<ScopeQuery>,
     Query(query): Query<SearchQuery>,
 ) -> Result<Response, HttpError> {
     search_meta_response(
         &state,
+        user.as_deref(),
+        scope.scope,
         query,
         SearchMode::Canonical,
         "GET /api/search?folder_id=...",
@@ -592,6 +1470,8 @@ pub async fn search_pastes(
 ///
 /// # Arguments
 /// - `state`: Application state.
+/// - `user`: Caller in multi-user mode.
+/// - `scope`: `?scope=all` adds pastes shared by other users (multi-user mode only).
 /// - `query`: Search query parameters.
 ///
 /// # Returns
@@ -601,10 +1481,14 @@ pub async fn search_pastes(
 /// Returns an error if search fails.
 pub async fn search_pastes_meta(
     State(state): State<AppState>,
+    user: CurrentUser,
+    Query(scope): Query<ScopeQuery>,
     Query(query): Query<SearchQuery>,
 ) -> Result<Response, HttpError> {
     search_meta_response(
         &state,
+        user.as_deref(),
+        scope.scope,
         query,
         SearchMode::MetaOnly,
         "GET /api/search/meta?folder_id=...",
@@ -614,9 +1498,12 @@ pub async fn search_pastes_meta(
 
 #[cfg(test)]
 mod tests {
-    use super::build_paste_for_create_with_detector;
+    use super::{build_paste_for_create_with_detector, with_search_content};
     use crate::{db::TransactionOps, AppState, Config, Database};
-    use localpaste_core::models::{folder::Folder, paste::Paste};
+    use localpaste_core::models::{
+        folder::Folder,
+        paste::{Paste, PasteMeta},
+    };
     use std::sync::mpsc;
     use std::thread;
     use std::time::{Duration, Instant};
@@ -643,6 +1530,9 @@ mod tests {
                 max_paste_size: 1024 * 1024,
                 auto_save_interval: 500,
                 auto_backup: false,
+                size_limits: Default::default(),
+                flush_policy: Default::default(),
+                recent_history: false,
             },
             db,
         );
@@ -709,4 +1599,33 @@ mod tests {
         assert!(paste.language.is_none());
         assert!(!paste.language_is_manual);
     }
+
+    #[test]
+    fn search_content_skips_rows_over_budget_and_keeps_filling_smaller_ones() {
+        let (_temp_dir, state, seeded_id) = setup_state_with_foldered_paste();
+        let big = Paste::new("x".repeat(10), "big".to_string());
+        let mut burn = Paste::new("one-time".to_string(), "burn".to_string());
+        burn.burn_after_read = true;
+        for paste in [&big, &burn] {
+            state.db.pastes.create(paste).expect("create paste");
+        }
+        let seeded = state
+            .db
+            .pastes
+            .get(&seeded_id)
+            .expect("lookup")
+            .expect("seeded paste");
+        let metas = [&big, &burn, &seeded]
+            .into_iter()
+            .map(PasteMeta::from)
+            .collect();
+
+        let (rows, omitted) =
+            with_search_content(&state, metas, Some("content"), 8).expect("content rows");
+
+        let contents: Vec<Option<&str>> = rows.iter().map(|row| row.content.as_deref()).collect();
+        assert_eq!(contents, vec![None, None, Some("content")]);
+        assert_eq!(omitted, 2);
+        assert_eq!(rows[2].matches.as_ref().map(Vec::len), Some(1));
+    }
 }
@@ -0,0 +1,127 @@
+//! Editor presence endpoints under `/api/paste/:id/presence` and
+//! `/api/presence`.
+
+use super::json::Json;
+use crate::presence::{PresenceEntry, MAX_OWNER_LABEL_CHARS};
+use crate::{
+    auth::{ensure_readable, CurrentUser},
+    error::HttpError,
+    AppError, AppState,
+};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// `PUT` body and `DELETE` query naming the viewing client.
+#[derive(Debug, Deserialize)]
+pub struct PresenceOwner {
+    /// Short client label such as `"cli"` or `"web"`.
+    pub owner: String,
+}
+
+fn owner_label(owner: &str) -> Result<&str, AppError> {
+    let owner = owner.trim();
+    if owner.is_empty() || owner.chars().count() > MAX_OWNER_LABEL_CHARS {
+        return Err(AppError::BadRequest(format!(
+            "Presence owner must be 1-{} characters",
+            MAX_OWNER_LABEL_CHARS
+        )));
+    }
+    Ok(owner)
+}
+
+fn lock_manager_unavailable() -> AppError {
+    AppError::StorageMessage("Paste lock manager is unavailable.".to_string())
+}
+
+fn existing_paste_id(state: &AppState, id: &str) -> Result<String, AppError> {
+    let id = state.db.pastes.resolve_id(id)?;
+    state.db.pastes.get_meta(&id)?.ok_or(AppError::NotFound)?;
+    Ok(id)
+}
+
+/// List clients holding the edit lock on, or viewing, a paste.
+///
+/// # Returns
+/// Presence entries as JSON, editors first.
+///
+/// # Errors
+/// Returns `404` when the paste is missing.
+pub async fn list_presence(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<PresenceEntry>>, HttpError> {
+    let id = existing_paste_id(&state, &id)?;
+    let entries = state
+        .presence
+        .list(&state.locks, &id)
+        .map_err(|_| lock_manager_unavailable())?;
+    Ok(Json(entries))
+}
+
+/// List clients holding the edit lock on, or viewing, any paste.
+///
+/// In multi-user mode only pastes the caller can read are included.
+///
+/// # Returns
+/// Presence entries keyed by paste id, editors first; pastes nobody has open
+/// are omitted.
+pub async fn list_all_presence(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> Result<Json<HashMap<String, Vec<PresenceEntry>>>, HttpError> {
+    let mut all = state
+        .presence
+        .all(&state.locks)
+        .map_err(|_| lock_manager_unavailable())?;
+    if user.is_some() {
+        all.retain(|paste_id, _| ensure_readable(&state, user.as_deref(), paste_id).is_ok());
+    }
+    Ok(Json(all))
+}
+
+/// Announce that a client is viewing a paste.
+///
+/// Clients renew within [`crate::presence::PRESENCE_TTL`] to stay listed.
+///
+/// # Returns
+/// The paste's presence entries as JSON.
+///
+/// # Errors
+/// Returns `400` for an empty or overlong owner label and `404` when the
+/// paste is missing.
+pub async fn touch_presence(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<PresenceOwner>,
+) -> Result<Json<Vec<PresenceEntry>>, HttpError> {
+    let owner = owner_label(&req.owner)?;
+    let id = existing_paste_id(&state, &id)?;
+    state.presence.touch(&id, owner);
+    let entries = state
+        .presence
+        .list(&state.locks, &id)
+        .map_err(|_| lock_manager_unavailable())?;
+    Ok(Json(entries))
+}
+
+/// Stop listing a client as viewing a paste.
+///
+/// # Returns
+/// `204 No Content`, whether or not the client was listed.
+///
+/// # Errors
+/// Returns `400` for an empty or overlong owner label.
+pub async fn leave_presence(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<PresenceOwner>,
+) -> Result<StatusCode, HttpError> {
+    let owner = owner_label(&query.owner)?;
+    let id = state.db.pastes.resolve_id(&id)?;
+    state.presence.leave(&id, owner);
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,259 @@
+//! Raw paste content endpoint with HTTP range and ETag support.
+//!
+//! Served at `/api/paste/:id/raw` and at the shorter `/raw/:id` for piping
+//! into `curl`/`wget`.
+
+use crate::{error::HttpError, models::paste::*, AppError, AppState};
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use localpaste_core::naming::files::language_extension;
+use localpaste_core::normalization::{restore_line_endings, LineEnding};
+
+const RAW_CONTENT_TYPE: &str = "text/plain; charset=utf-8";
+
+/// Parsed outcome of a `Range` header against a known body length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ByteRange {
+    /// No usable range; serve the full body.
+    Full,
+    /// Inclusive byte span to serve with `206 Partial Content`.
+    Partial { start: usize, end: usize },
+    /// Syntactically valid but outside the body; reply `416`.
+    Unsatisfiable,
+}
+
+/// Resolve a single `bytes=` range against `len`.
+///
+/// Multi-range and malformed headers fall back to [`ByteRange::Full`], which
+/// RFC 9110 permits for ranges a server chooses not to honor.
+fn parse_byte_range(value: &str, len: usize) -> ByteRange {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return ByteRange::Full;
+    };
+    if spec.contains(',') {
+        return ByteRange::Full;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return ByteRange::Full;
+    };
+    let (start, end) = (start.trim(), end.trim());
+    if start.is_empty() {
+        let Ok(suffix) = end.parse::<usize>() else {
+            return ByteRange::Full;
+        };
+        if suffix == 0 || len == 0 {
+            return ByteRange::Unsatisfiable;
+        }
+        return ByteRange::Partial {
+            start: len.saturating_sub(suffix),
+            end: len - 1,
+        };
+    }
+    let Ok(start) = start.parse::<usize>() else {
+        return ByteRange::Full;
+    };
+    let end = if end.is_empty() {
+        usize::MAX
+    } else {
+        match end.parse::<usize>() {
+            Ok(end) if end >= start => end,
+            _ => return ByteRange::Full,
+        }
+    };
+    if start >= len {
+        return ByteRange::Unsatisfiable;
+    }
+    ByteRange::Partial {
+        start,
+        end: end.min(len - 1),
+    }
+}
+
+/// Filename hint for a paste: its name with the language's extension added
+/// unless the name already ends with it.
+fn raw_filename(name: &str, language: Option<&str>) -> String {
+    let extension = language_extension(language);
+    let suffix = format!(".{}", extension);
+    let stem = name.trim();
+    let stem = if stem.len() > suffix.len() && stem.to_ascii_lowercase().ends_with(&suffix) {
+        &stem[..stem.len() - suffix.len()]
+    } else {
+        stem
+    };
+    super::export::download_filename(stem, "paste", extension)
+}
+
+fn header_str(headers: &HeaderMap, name: header::HeaderName) -> Option<&str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+fn etag_matches(candidates: &str, etag: &str) -> bool {
+    candidates.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+/// Return paste content as `text/plain`, honoring `Range` requests.
+///
+/// Responses carry an inline `Content-Disposition` whose filename ends in the
+/// extension for the paste language (`main.rs`, `notes.md`), so `curl -OJ`
+/// and browsers save it under a useful name. They also carry an `ETag`
+/// derived from the paste revision. `If-None-Match`
+/// yields `304`, and `If-Range` only applies the range while the tag matches.
+/// `?line_endings=original` re-applies the line ending recorded when the paste
+/// was saved, so CRLF content normalized on save exports as CRLF.
+///
+/// A `burn_after_read` paste is deleted by this read, so it ignores the
+/// conditional and range headers and always sends the full body; a `304` or
+/// `206` would otherwise consume it without delivering it.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `id`: Paste identifier from the path.
+/// - `query`: Optional `line_endings` export mode.
+/// - `headers`: Request headers (`Range`, `If-Range`, `If-None-Match`).
+///
+/// # Returns
+/// `200` with the full body, `206` with the requested slice, `304` when the
+/// client copy is current, or `416` when the range starts past the end.
+///
+/// # Errors
+/// Returns an error if the paste does not exist or storage fails.
+pub async fn get_paste_raw(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<RawContentQuery>,
+    headers: HeaderMap,
+) -> Result<Response, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let paste = super::paste::load_paste_for_read(&state, &id)?;
+    let one_time = paste.burn_after_read;
+    let restore_crlf =
+        query.line_endings == RawLineEndings::Original && paste.line_ending == LineEnding::Crlf;
+    let body = if restore_crlf {
+        restore_line_endings(paste.content.as_str(), LineEnding::Crlf)
+            .into_owned()
+            .into_bytes()
+    } else {
+        paste.content.into_bytes()
+    };
+    let len = body.len();
+    // Length differs between stored and restored bodies, so the tag tracks both.
+    let etag = format!(
+        "\"{}-{}-{}\"",
+        paste.id,
+        paste.updated_at.timestamp_micros(),
+        len
+    );
+    let etag_value = HeaderValue::from_str(etag.as_str()).map_err(|_| AppError::Internal)?;
+    let filename = raw_filename(paste.name.as_str(), paste.language.as_deref());
+    let disposition = HeaderValue::from_str(format!("inline; filename=\"{}\"", filename).as_str())
+        .map_err(|_| AppError::Internal)?;
+
+    if !one_time
+        && header_str(&headers, header::IF_NONE_MATCH)
+            .is_some_and(|candidates| etag_matches(candidates, etag.as_str()))
+    {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag_value)]).into_response());
+    }
+
+    let range_is_current = header_str(&headers, header::IF_RANGE)
+        .is_none_or(|if_range| if_range.trim() == etag.as_str());
+    let range = match header_str(&headers, header::RANGE) {
+        Some(value) if range_is_current && !one_time => parse_byte_range(value, len),
+        _ => ByteRange::Full,
+    };
+
+    let accept_ranges = if one_time { "none" } else { "bytes" };
+    let common = [
+        (
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(RAW_CONTENT_TYPE),
+        ),
+        (
+            header::ACCEPT_RANGES,
+            HeaderValue::from_static(accept_ranges),
+        ),
+        (header::ETAG, etag_value),
+        (header::CONTENT_DISPOSITION, disposition),
+    ];
+    let response = match range {
+        ByteRange::Full => (StatusCode::OK, common, body).into_response(),
+        ByteRange::Partial { start, end } => {
+            let content_range = format!("bytes {}-{}/{}", start, end, len);
+            (
+                StatusCode::PARTIAL_CONTENT,
+                common,
+                [(
+                    header::CONTENT_RANGE,
+                    HeaderValue::from_str(content_range.as_str())
+                        .map_err(|_| AppError::Internal)?,
+                )],
+                body[start..=end].to_vec(),
+            )
+                .into_response()
+        }
+        ByteRange::Unsatisfiable => {
+            let content_range = format!("bytes */{}", len);
+            (
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                common,
+                [(
+                    header::CONTENT_RANGE,
+                    HeaderValue::from_str(content_range.as_str())
+                        .map_err(|_| AppError::Internal)?,
+                )],
+            )
+                .into_response()
+        }
+    };
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{etag_matches, parse_byte_range, raw_filename, ByteRange};
+
+    #[test]
+    fn parse_byte_range_matrix() {
+        let cases = [
+            ("bytes=0-3", ByteRange::Partial { start: 0, end: 3 }),
+            ("bytes=4-", ByteRange::Partial { start: 4, end: 9 }),
+            ("bytes=5-100", ByteRange::Partial { start: 5, end: 9 }),
+            ("bytes=-3", ByteRange::Partial { start: 7, end: 9 }),
+            ("bytes=-50", ByteRange::Partial { start: 0, end: 9 }),
+            ("bytes=10-", ByteRange::Unsatisfiable),
+            ("bytes=-0", ByteRange::Unsatisfiable),
+            ("bytes=3-1", ByteRange::Full),
+            ("bytes=0-1,4-5", ByteRange::Full),
+            ("items=0-3", ByteRange::Full),
+            ("bytes=abc", ByteRange::Full),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(parse_byte_range(value, 10), expected, "range: {}", value);
+        }
+        assert_eq!(parse_byte_range("bytes=0-", 0), ByteRange::Unsatisfiable);
+    }
+
+    #[test]
+    fn etag_matches_lists_wildcards_and_weak_tags() {
+        assert!(etag_matches("\"a\", \"b\"", "\"b\""));
+        assert!(etag_matches("*", "\"b\""));
+        assert!(etag_matches("W/\"b\"", "\"b\""));
+        assert!(!etag_matches("\"c\"", "\"b\""));
+    }
+
+    #[test]
+    fn raw_filename_adds_language_extension_once() {
+        assert_eq!(raw_filename("main.rs", Some("rust")), "main.rs");
+        assert_eq!(raw_filename("Main.RS", Some("rust")), "Main.rs");
+        assert_eq!(
+            raw_filename("build notes", Some("markdown")),
+            "build_notes.md"
+        );
+        assert_eq!(raw_filename("scratch", None), "scratch.txt");
+    }
+}
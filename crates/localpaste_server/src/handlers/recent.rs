@@ -0,0 +1,101 @@
+//! Per-client recently opened pastes at `GET /api/recent`.
+//!
+//! History is opt-in through [`Config::recent_history`]. When it is on,
+//! fetching or updating a paste records the client named by
+//! [`RECENT_CLIENT_HEADER`], so the CLI picker, web UI, and GUI can show the
+//! same "Recent" collection.
+//!
+//! [`Config::recent_history`]: crate::Config::recent_history
+
+use super::json::Json;
+use crate::auth::{retain_scoped, scan_limit, CurrentUser};
+use crate::{error::HttpError, AppError, AppState};
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+};
+use localpaste_core::config::RECENT_HISTORY_ENV;
+use localpaste_core::models::recent::{RecentPaste, RecentQuery, DEFAULT_RECENT_CLIENT};
+use localpaste_core::models::user::ScopeQuery;
+
+/// Request header naming the frontend, such as `cli` or `web`.
+pub const RECENT_CLIENT_HEADER: &str = "x-localpaste-client";
+
+fn normalized_limit(limit: Option<usize>) -> usize {
+    limit.unwrap_or(20).min(100)
+}
+
+/// Record that the client named in `headers` opened `paste_id`.
+///
+/// Does nothing unless recent history is enabled. Failures are logged rather
+/// than returned so history never fails the read or write that triggered it.
+pub(crate) fn record_open(state: &AppState, headers: &HeaderMap, paste_id: &str) {
+    if !state.config.recent_history {
+        return;
+    }
+    let client = headers
+        .get(RECENT_CLIENT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or(DEFAULT_RECENT_CLIENT);
+    if let Err(err) = state.db.recent.record(client, paste_id) {
+        tracing::warn!("Failed to record recent open of '{}': {}", paste_id, err);
+    }
+}
+
+/// List recently opened pastes, newest first.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `user`: Caller in multi-user mode; only pastes in their scope are listed.
+/// - `scope`: `?scope=all` adds pastes shared by other users (multi-user mode only).
+/// - `query`: `?client=` restricts the list to one client; `?limit=` caps rows.
+///
+/// # Returns
+/// Paste metadata with the client that opened each paste last and when.
+///
+/// # Errors
+/// Returns `400` when recent history is disabled, or a storage error.
+pub async fn list_recent(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(scope): Query<ScopeQuery>,
+    Query(query): Query<RecentQuery>,
+) -> Result<Json<Vec<RecentPaste>>, HttpError> {
+    if !state.config.recent_history {
+        return Err(AppError::BadRequest(format!(
+            "Recent history is not enabled; set {}=true",
+            RECENT_HISTORY_ENV
+        ))
+        .into());
+    }
+    let limit = normalized_limit(query.limit);
+    let client = query
+        .client
+        .as_deref()
+        .map(str::trim)
+        .filter(|client| !client.is_empty());
+    let opens = state
+        .db
+        .recent
+        .list(client, scan_limit(user.as_deref(), limit))?;
+    let mut rows = Vec::with_capacity(opens.len());
+    for open in opens {
+        // A paste deleted since the history read is simply left out.
+        if let Some(meta) = state.db.pastes.get_meta(&open.paste_id)? {
+            rows.push(RecentPaste {
+                meta,
+                client: open.client,
+                opened_at: open.opened_at,
+            });
+        }
+    }
+    let rows = retain_scoped(
+        &state.db,
+        user.as_deref(),
+        scope.scope,
+        rows,
+        limit,
+        |row| row.meta.id.as_str(),
+    )?;
+    Ok(Json(rows))
+}
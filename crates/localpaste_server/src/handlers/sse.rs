@@ -0,0 +1,104 @@
+//! Server-Sent Events stream of paste and folder changes.
+
+use crate::auth::CurrentUser;
+use crate::events::{visible_to, SequencedChange};
+use crate::AppState;
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+    Extension,
+};
+use futures_util::stream::{self, Stream};
+use localpaste_core::models::user::User;
+use serde_json::json;
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Subscriber state threaded through the event stream.
+struct Subscription {
+    state: AppState,
+    user: Option<User>,
+    /// Resume point was older than the replay buffer; report it first.
+    gap: bool,
+    backlog: VecDeque<SequencedChange>,
+    receiver: broadcast::Receiver<SequencedChange>,
+}
+
+fn change_event(change: &SequencedChange) -> Event {
+    Event::default()
+        .id(change.seq.to_string())
+        .data(serde_json::to_string(&change.event).unwrap_or_default())
+}
+
+fn lagged_event(skipped: Option<u64>) -> Event {
+    let payload = match skipped {
+        Some(skipped) => json!({ "change": "lagged", "skipped": skipped }),
+        None => json!({ "change": "lagged" }),
+    };
+    Event::default().data(payload.to_string())
+}
+
+async fn next_event(mut sub: Subscription) -> Option<(Result<Event, Infallible>, Subscription)> {
+    if std::mem::take(&mut sub.gap) {
+        return Some((Ok(lagged_event(None)), sub));
+    }
+    loop {
+        let change = match sub.backlog.pop_front() {
+            Some(change) => change,
+            None => match sub.receiver.recv().await {
+                Ok(change) => change,
+                Err(RecvError::Lagged(skipped)) => {
+                    return Some((Ok(lagged_event(Some(skipped))), sub));
+                }
+                Err(RecvError::Closed) => return None,
+            },
+        };
+        if visible_to(&sub.state, sub.user.as_ref(), &change.event) {
+            return Some((Ok(change_event(&change)), sub));
+        }
+    }
+}
+
+/// Stream change events as `text/event-stream`.
+///
+/// Each message's `data` is the same JSON as an `/api/ws` frame and its `id`
+/// is the event's sequence number. A client reconnecting with
+/// `Last-Event-ID` first receives the retained events after that id; when
+/// some were already dropped (or the server restarted) it receives
+/// `{"change": "lagged"}` and should re-fetch. In multi-user mode, paste
+/// events are limited to pastes the caller can read.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `user`: Caller in multi-user mode.
+/// - `headers`: Request headers (`Last-Event-ID` selects the resume point).
+///
+/// # Returns
+/// An SSE response with periodic keep-alive comments.
+pub async fn change_events_sse(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let last_seq = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok());
+    let (gap, backlog, receiver) = match last_seq {
+        Some(last_seq) => {
+            let replay = state.changes.subscribe_after(last_seq);
+            (replay.gap, replay.missed.into(), replay.receiver)
+        }
+        None => (false, VecDeque::new(), state.changes.subscribe()),
+    };
+    let subscription = Subscription {
+        state,
+        user: user.map(|Extension(user)| user),
+        gap,
+        backlog,
+        receiver,
+    };
+    Sse::new(stream::unfold(subscription, next_event)).keep_alive(KeepAlive::default())
+}
@@ -0,0 +1,38 @@
+//! Instance statistics endpoint.
+
+use super::json::Json;
+use crate::auth::CurrentUser;
+use crate::{error::HttpError, AppState};
+use axum::extract::{Query, State};
+use localpaste_core::models::user::ScopeQuery;
+use localpaste_core::stats::{collect_stats, PasteStats, StatsQuery};
+
+/// Aggregate paste totals, per-language and per-folder counts, and the
+/// largest pastes.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `user`: Caller in multi-user mode; only pastes in their scope count.
+/// - `scope`: `?scope=all` adds pastes shared by other users (multi-user mode only).
+/// - `query`: `?largest=` sets how many of the largest pastes to list.
+///
+/// # Returns
+/// [`PasteStats`] as JSON.
+///
+/// # Errors
+/// Returns an error if metadata or access rows cannot be read.
+pub async fn get_stats(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(scope): Query<ScopeQuery>,
+    Query(query): Query<StatsQuery>,
+) -> Result<Json<PasteStats>, HttpError> {
+    let stats = collect_stats(&state.db, query.largest(), |meta| match user.as_deref() {
+        Some(user) => {
+            let access = state.db.users.paste_access(&meta.id)?;
+            Ok(scope.scope.includes(user, access.as_ref()))
+        }
+        None => Ok(true),
+    })?;
+    Ok(Json(stats))
+}
@@ -0,0 +1,118 @@
+//! Tag management across all pastes.
+
+use super::json::Json;
+use crate::auth::CurrentUser;
+use crate::events::{paste_changed, ChangeKind};
+use crate::{error::HttpError, AppState};
+use axum::extract::{Path, Query, State};
+use localpaste_core::models::user::ScopeQuery;
+use localpaste_core::tag_ops::{
+    delete_tag_guarded, rename_tag_guarded, tag_counts, tag_counts_where, TagCount,
+};
+use serde::{Deserialize, Serialize};
+
+/// Body of `PUT /api/tags/:name`.
+#[derive(Debug, Deserialize)]
+pub struct RenameTagRequest {
+    /// New tag text, stored as given after trimming.
+    pub name: String,
+}
+
+/// Result of a tag rename or removal.
+#[derive(Debug, Serialize)]
+pub struct TagChangeResponse {
+    pub tag: String,
+    /// Number of pastes rewritten.
+    pub updated: usize,
+}
+
+/// List every tag in use with its paste count.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `user`: Caller in multi-user mode; only pastes in their scope count.
+/// - `scope`: `?scope=all` adds pastes shared by other users (multi-user mode only).
+///
+/// # Returns
+/// Normalized tags with counts, most used first.
+///
+/// # Errors
+/// Returns an error if the tag index or access rows cannot be read.
+pub async fn list_tags(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(scope): Query<ScopeQuery>,
+) -> Result<Json<Vec<TagCount>>, HttpError> {
+    let tags = match user.as_deref() {
+        Some(user) => tag_counts_where(&state.db, |meta| {
+            let access = state.db.users.paste_access(&meta.id)?;
+            Ok(scope.scope.includes(user, access.as_ref()))
+        })?,
+        None => tag_counts(&state.db)?,
+    };
+    Ok(Json(tags))
+}
+
+/// Rename a tag on every paste that carries it, in one transaction.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `name`: Existing tag; matched case-insensitively.
+/// - `req`: New tag name.
+///
+/// # Returns
+/// The new name and how many pastes changed.
+///
+/// # Errors
+/// Returns `400` for a blank name, `404` when no paste has the tag, `423`
+/// when an affected paste is open for editing, or storage errors.
+pub async fn rename_tag(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(req): Json<RenameTagRequest>,
+) -> Result<Json<TagChangeResponse>, HttpError> {
+    let changed = rename_tag_guarded(&state.db, &name, &req.name, |ids| {
+        state
+            .locks
+            .begin_batch_mutation(ids.iter())
+            .map_err(crate::locks::map_tag_change_lock_error)
+    })?;
+    for paste_id in &changed {
+        paste_changed(&state, paste_id, ChangeKind::Updated);
+    }
+    Ok(Json(TagChangeResponse {
+        tag: req.name.trim().to_string(),
+        updated: changed.len(),
+    }))
+}
+
+/// Remove a tag from every paste that carries it, in one transaction.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `name`: Tag to remove; matched case-insensitively.
+///
+/// # Returns
+/// The removed tag and how many pastes changed.
+///
+/// # Errors
+/// Returns `404` when no paste has the tag, `423` when an affected paste is
+/// open for editing, or storage errors.
+pub async fn delete_tag(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<TagChangeResponse>, HttpError> {
+    let changed = delete_tag_guarded(&state.db, &name, |ids| {
+        state
+            .locks
+            .begin_batch_mutation(ids.iter())
+            .map_err(crate::locks::map_tag_change_lock_error)
+    })?;
+    for paste_id in &changed {
+        paste_changed(&state, paste_id, ChangeKind::Updated);
+    }
+    Ok(Json(TagChangeResponse {
+        tag: name.trim().to_lowercase(),
+        updated: changed.len(),
+    }))
+}
@@ -0,0 +1,191 @@
+//! Multipart file uploads at `POST /api/paste/upload`.
+//!
+//! Browser forms and `curl -F file=@path` send the file as raw bytes, so the
+//! route's body limit only needs room for the content instead of the JSON
+//! escaping budget. The filename supplies the paste name and language unless
+//! the form sets them, and the rest goes through the same create path as
+//! `POST /api/paste`.
+
+use super::paste::create_paste_response;
+use crate::auth::CurrentUser;
+use crate::{error::HttpError, models::paste::CreatePasteRequest, AppError, AppState};
+use axum::{
+    extract::{multipart::MultipartError, Multipart, State},
+    http::{HeaderMap, StatusCode},
+    response::Response,
+};
+use localpaste_core::detection::canonical::from_extension;
+use localpaste_core::normalization::decode_content_bytes;
+
+/// Form field carrying the file contents.
+pub const UPLOAD_FILE_FIELD: &str = "file";
+
+pub(super) fn multipart_error(err: MultipartError) -> AppError {
+    if err.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        AppError::PayloadTooLarge(err.body_text())
+    } else {
+        AppError::BadRequest(format!("Invalid multipart body: {}", err.body_text()))
+    }
+}
+
+fn parse_bool_field(field: &str, value: &str) -> Result<bool, AppError> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "1" | "on" => Ok(true),
+        "false" | "0" | "off" => Ok(false),
+        _ => Err(AppError::BadRequest(format!(
+            "Field '{}' must be true or false",
+            field
+        ))),
+    }
+}
+
+/// Final path component of a client-supplied filename, or `None` when blank.
+///
+/// Some clients send the full local path, so both separators are stripped.
+pub(super) fn upload_file_name(raw: &str) -> Option<String> {
+    let name = raw.rsplit(['/', '\\']).next().unwrap_or(raw).trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Canonical language for a filename's extension, if the shared table has one.
+fn language_for_file_name(file_name: &str) -> Option<String> {
+    let (stem, extension) = file_name.rsplit_once('.')?;
+    if stem.is_empty() {
+        return None;
+    }
+    from_extension(extension).map(|spec| spec.value.to_string())
+}
+
+/// Create a paste from a `multipart/form-data` upload.
+///
+/// The `file` field is required. Optional text fields mirror the JSON create
+/// body: `name`, `language`, `language_is_manual`, `folder_id`,
+/// `folder_path`, `create_folders`, `tags` (comma-separated), `dedupe`,
+/// `expires_in`, and `burn_after_read`. Empty and unknown fields are ignored.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `headers`: Request headers, read as in [`super::paste::create_paste`].
+/// - `user`: Caller in multi-user mode; recorded as the owner.
+/// - `multipart`: Form body.
+///
+/// # Returns
+/// The created paste as JSON, named after the file and with its language
+/// taken from the file extension unless the form overrides them.
+///
+/// # Errors
+/// Returns `400` for a missing or repeated `file` field, malformed fields, or
+/// content that is not valid UTF-8, and `413` when the upload is too large.
+pub async fn upload_paste(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    user: CurrentUser,
+    mut multipart: Multipart,
+) -> Result<Response, HttpError> {
+    let mut file: Option<(Option<String>, Vec<u8>)> = None;
+    let mut req = CreatePasteRequest {
+        content: String::new(),
+        language: None,
+        language_is_manual: None,
+        folder_id: None,
+        folder_path: None,
+        create_folders: None,
+        tags: None,
+        name: None,
+        dedupe: None,
+        expires_in: None,
+        burn_after_read: None,
+    };
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        let Some(field_name) = field.name().map(str::to_string) else {
+            continue;
+        };
+        if field_name == UPLOAD_FILE_FIELD {
+            if file.is_some() {
+                return Err(AppError::BadRequest(
+                    "Upload accepts exactly one 'file' field".to_string(),
+                )
+                .into());
+            }
+            let file_name = field.file_name().and_then(upload_file_name);
+            let bytes = field.bytes().await.map_err(multipart_error)?;
+            file = Some((file_name, bytes.to_vec()));
+            continue;
+        }
+        let value = field.text().await.map_err(multipart_error)?;
+        // Browser forms send empty inputs; treat them as unset.
+        if value.trim().is_empty() {
+            continue;
+        }
+        match field_name.as_str() {
+            "name" => req.name = Some(value),
+            "language" => req.language = Some(value),
+            "language_is_manual" => {
+                req.language_is_manual = Some(parse_bool_field("language_is_manual", &value)?)
+            }
+            "folder_id" => req.folder_id = Some(value),
+            "folder_path" => req.folder_path = Some(value),
+            "create_folders" => {
+                req.create_folders = Some(parse_bool_field("create_folders", &value)?)
+            }
+            "tags" => {
+                req.tags = Some(
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|tag| !tag.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                )
+            }
+            "dedupe" => req.dedupe = Some(parse_bool_field("dedupe", &value)?),
+            "expires_in" => {
+                req.expires_in = Some(value.trim().parse().map_err(|_| {
+                    AppError::BadRequest("Field 'expires_in' must be a whole number".to_string())
+                })?)
+            }
+            "burn_after_read" => {
+                req.burn_after_read = Some(parse_bool_field("burn_after_read", &value)?)
+            }
+            _ => {}
+        }
+    }
+
+    let Some((file_name, bytes)) = file else {
+        return Err(AppError::BadRequest("Upload must include a 'file' field".to_string()).into());
+    };
+    req.content = decode_content_bytes(bytes, state.db.pastes.normalization())?;
+    if let Some(file_name) = file_name {
+        if req.language.is_none() {
+            req.language = language_for_file_name(file_name.as_str());
+        }
+        req.name.get_or_insert(file_name);
+    }
+    Ok(create_paste_response(
+        &state,
+        &headers,
+        user.as_deref(),
+        req,
+        "POST /api/paste/upload with folder_id",
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_names_drop_client_paths_and_map_extensions() {
+        assert_eq!(
+            upload_file_name("C:\\logs\\server.log").as_deref(),
+            Some("server.log")
+        );
+        assert_eq!(upload_file_name("src/main.rs").as_deref(), Some("main.rs"));
+        assert_eq!(upload_file_name("  "), None);
+
+        assert_eq!(language_for_file_name("main.RS").as_deref(), Some("rust"));
+        assert_eq!(language_for_file_name("notes.unknownext"), None);
+        assert_eq!(language_for_file_name(".bashrc"), None);
+        assert_eq!(language_for_file_name("Makefile"), None);
+    }
+}
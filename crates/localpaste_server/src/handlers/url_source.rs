@@ -0,0 +1,94 @@
+//! Source URL links under `/api/paste/:id/source`.
+//!
+//! A linked paste mirrors a remote file: `POST .../source/refresh` re-fetches
+//! it on demand, and a background sweep re-fetches pastes whose
+//! `refresh_interval_secs` has elapsed. Changed content is saved like any
+//! other update, so earlier content stays in the paste's version history.
+//! In multi-user mode only admins may link a URL, since the server fetches
+//! it on their behalf.
+
+use super::json::Json;
+use crate::{error::HttpError, AppError, AppState};
+use axum::extract::{Path, State};
+use chrono::Utc;
+use localpaste_core::models::url_source::{SetUrlSourceRequest, UrlRefreshResponse, UrlSource};
+
+/// Show a paste's source link and its last fetch outcome.
+///
+/// # Returns
+/// The source link as JSON.
+///
+/// # Errors
+/// Returns `404` when the paste is missing or has no source link.
+pub async fn get_url_source(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<UrlSource>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let source = state.db.url_sources.get(&id)?.ok_or(AppError::NotFound)?;
+    Ok(Json(source))
+}
+
+/// Link a paste to a source URL, or change its refresh schedule.
+///
+/// Linking does not fetch; call the refresh route to pull content now.
+///
+/// # Returns
+/// The stored source link as JSON.
+///
+/// # Errors
+/// Returns `400` for a non-http(s) URL or an out-of-range
+/// `refresh_interval_secs`, and `404` when the paste is missing.
+pub async fn set_url_source(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<SetUrlSourceRequest>,
+) -> Result<Json<UrlSource>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let source = state
+        .db
+        .url_sources
+        .set(&id, req.url.as_str(), req.refresh_interval_secs)?;
+    Ok(Json(source))
+}
+
+/// Unlink a paste from its source URL; the content is kept.
+///
+/// # Returns
+/// Success marker as JSON.
+///
+/// # Errors
+/// Returns `404` when the paste is missing or has no source link.
+pub async fn delete_url_source(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    if state.db.url_sources.clear(&id)? {
+        Ok(Json(serde_json::json!({ "success": true })))
+    } else {
+        Err(AppError::NotFound.into())
+    }
+}
+
+/// Re-fetch a paste from its source URL now.
+///
+/// # Returns
+/// Whether the content changed, and the updated source link, as JSON.
+///
+/// # Errors
+/// Returns `404` without a source link, `413` when the remote file exceeds
+/// the paste size limit, `423` while the paste is open for editing, and
+/// `502` when the fetch fails.
+pub async fn refresh_url_source(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<UrlRefreshResponse>, HttpError> {
+    let id = state.db.pastes.resolve_id(&id)?;
+    let response = tokio::task::spawn_blocking(move || {
+        crate::url_refresh::refresh_from_source(&state, &id, Utc::now())
+    })
+    .await
+    .map_err(|_| AppError::Internal)??;
+    Ok(Json(response))
+}
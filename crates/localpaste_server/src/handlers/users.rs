@@ -0,0 +1,119 @@
+//! User management and paste visibility endpoints for multi-user mode.
+
+use super::json::Json;
+use crate::{auth::CurrentUser, error::HttpError, AppError, AppState};
+use axum::{
+    extract::{Path, State},
+    Extension,
+};
+use localpaste_core::models::user::{
+    CreateUserRequest, CreatedUser, PasteAccess, PasteVisibilityRequest, User,
+};
+
+fn require_user(user: CurrentUser) -> Result<User, AppError> {
+    user.map(|Extension(user)| user)
+        .ok_or_else(|| AppError::BadRequest("Multi-user mode is not enabled".to_string()))
+}
+
+/// List user accounts (admin only).
+///
+/// # Returns
+/// Users as JSON, oldest first; tokens are never included.
+///
+/// # Errors
+/// Returns `400` outside multi-user mode, or an error if storage access fails.
+pub async fn list_users(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> Result<Json<Vec<User>>, HttpError> {
+    require_user(user)?;
+    Ok(Json(state.db.users.list()?))
+}
+
+/// Create a user account (admin only).
+///
+/// # Returns
+/// The new user plus its bearer token, which is shown only this once.
+///
+/// # Errors
+/// Returns `400` for a blank name or outside multi-user mode, and `409` when
+/// the name is taken.
+pub async fn create_user(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(req): Json<CreateUserRequest>,
+) -> Result<Json<CreatedUser>, HttpError> {
+    require_user(user)?;
+    let created = state.db.users.create(req.name.as_str(), req.role)?;
+    tracing::info!(
+        "Created {:?} user '{}'",
+        created.user.role,
+        created.user.name
+    );
+    Ok(Json(created))
+}
+
+/// Delete a user account and revoke its tokens (admin only).
+///
+/// # Returns
+/// Success marker as JSON.
+///
+/// # Errors
+/// Returns `404` when no user has `id`.
+pub async fn delete_user(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, HttpError> {
+    require_user(user)?;
+    if state.db.users.delete(&id)? {
+        Ok(Json(serde_json::json!({ "success": true })))
+    } else {
+        Err(AppError::NotFound.into())
+    }
+}
+
+/// Show a paste's owner and visibility.
+///
+/// # Returns
+/// The access record, or `null` for pastes created outside multi-user mode.
+///
+/// # Errors
+/// Returns `400` outside multi-user mode and `404` when the paste is missing.
+pub async fn get_paste_visibility(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<Option<PasteAccess>>, HttpError> {
+    require_user(user)?;
+    let id = state.db.pastes.resolve_id(&id)?;
+    Ok(Json(state.db.users.paste_access(&id)?))
+}
+
+/// Share a paste with other users or make it private again.
+///
+/// Admins changing an unowned paste take ownership of it first.
+///
+/// # Returns
+/// The updated access record.
+///
+/// # Errors
+/// Returns `400` outside multi-user mode and `404` when the paste is missing.
+pub async fn set_paste_visibility(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<String>,
+    Json(req): Json<PasteVisibilityRequest>,
+) -> Result<Json<PasteAccess>, HttpError> {
+    let user = require_user(user)?;
+    let id = state.db.pastes.resolve_id(&id)?;
+    if state.db.users.paste_access(&id)?.is_none() {
+        state.db.users.set_paste_owner(&id, user.id.as_str())?;
+    }
+    let access = state
+        .db
+        .users
+        .set_paste_shared(&id, req.shared)?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(access))
+}
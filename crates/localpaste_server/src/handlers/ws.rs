@@ -0,0 +1,73 @@
+//! WebSocket stream of paste and folder change events.
+
+use crate::auth::CurrentUser;
+use crate::events::{visible_to, SequencedChange};
+use crate::AppState;
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+    Extension,
+};
+use localpaste_core::models::user::User;
+use serde_json::json;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Upgrade to a WebSocket that streams change events as JSON text frames.
+///
+/// Each frame is `{"target": "paste"|"folder", "id", "change":
+/// "created"|"updated"|"deleted"}`. A subscriber that falls behind receives
+/// `{"change": "lagged", "skipped": n}` and should re-fetch what it shows.
+/// In multi-user mode, paste events are limited to pastes the caller can read.
+///
+/// # Arguments
+/// - `state`: Application state.
+/// - `user`: Caller in multi-user mode.
+/// - `ws`: Upgrade request.
+///
+/// # Returns
+/// The `101 Switching Protocols` response.
+pub async fn changes_ws(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let user = user.map(|Extension(user)| user);
+    // Subscribe before upgrading so no event between the two is missed.
+    let events = state.changes.subscribe();
+    ws.on_upgrade(move |socket| forward_changes(socket, events, state, user))
+}
+
+async fn forward_changes(
+    mut socket: WebSocket,
+    mut events: broadcast::Receiver<SequencedChange>,
+    state: AppState,
+    user: Option<User>,
+) {
+    loop {
+        tokio::select! {
+            change = events.recv() => {
+                let frame = match change {
+                    Ok(change) if visible_to(&state, user.as_ref(), &change.event) => {
+                        serde_json::to_string(&change.event).unwrap_or_default()
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(skipped)) => {
+                        json!({ "change": "lagged", "skipped": skipped }).to_string()
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                if socket.send(Message::Text(frame)).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                // Pings are answered by the WebSocket layer; other frames are ignored.
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
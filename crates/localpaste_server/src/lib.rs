@@ -1,17 +1,43 @@
 //! HTTP server wiring for LocalPaste (API, handlers, and shared state).
 
+/// Bearer-token authentication and paste access checks for multi-user mode.
+pub mod auth;
+/// Live co-editing with patch-level merges.
+pub mod collab;
 /// Embedded server helper for GUI integration.
 pub mod embedded;
 /// HTTP error mapping for API handlers.
 pub mod error;
+/// Change notifications broadcast to `/api/ws` and `/api/events` subscribers.
+pub mod events;
+/// Background deletion of pastes past their expiry time.
+pub mod expiry;
+/// Background flushes for relaxed database flush policies.
+pub mod flusher;
+/// Read-only LAN gallery for one published folder.
+pub mod gallery;
 /// HTTP handlers for paste and folder endpoints.
 pub mod handlers;
 /// In-memory paste locks shared between GUI and API handlers.
 pub mod locks;
+/// Who is editing or viewing each paste.
+pub mod presence;
+/// Per-client rate limiting of API writes.
+pub mod rate_limit;
+/// Periodic database and backup size checks that log alerts.
+pub mod storage_monitor;
+/// Tracing subscriber setup with optional OTLP span export.
+pub mod telemetry;
+/// In-memory chunked upload sessions.
+pub mod uploads;
+/// Scheduled and on-demand re-fetching of pastes from source URLs.
+pub mod url_refresh;
 
 pub use embedded::EmbeddedServer;
+pub use gallery::{create_gallery_app, GalleryConfig};
 pub use localpaste_core::{config, db, models, naming, AppError, Config, Database, DEFAULT_PORT};
-pub use locks::{LockOwnerId, PasteLockError, PasteLockManager, PasteMutationGuard};
+pub use locks::{LockOwnerId, LockStats, PasteLockError, PasteLockManager, PasteMutationGuard};
+pub use uploads::ChunkUploadManager;
 
 use axum::{
     extract::DefaultBodyLimit,
@@ -19,12 +45,14 @@ use axum::{
     routing::{delete, get, post, put},
     Router,
 };
-use std::future::Future;
+use std::future::{Future, IntoFuture};
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tower_http::{
     compression::CompressionLayer,
     cors::{AllowOrigin, CorsLayer},
+    decompression::RequestDecompressionLayer,
     set_header::SetResponseHeaderLayer,
     trace::TraceLayer,
 };
@@ -32,6 +60,8 @@ use tower_http::{
 const JSON_BODY_OVERHEAD_BYTES: usize = 16 * 1024;
 const JSON_STRING_ESCAPE_EXPANSION_FACTOR: usize = 6;
 const MAX_JSON_REQUEST_BODY_BYTES: usize = 256 * 1024 * 1024;
+/// Room for multipart boundaries, part headers, and small form fields.
+const MULTIPART_BODY_OVERHEAD_BYTES: usize = 64 * 1024;
 const CSP_HEADER_VALUE: &str = "default-src 'self'; script-src 'self' 'unsafe-inline'; style-src 'self' 'unsafe-inline'; img-src 'self' data:; font-src 'self'; connect-src 'self'; frame-ancestors 'none'; base-uri 'self'; form-action 'self'";
 const X_CONTENT_TYPE_OPTIONS_NOSNIFF: &str = "nosniff";
 const X_FRAME_OPTIONS_DENY: &str = "DENY";
@@ -49,6 +79,13 @@ fn request_body_limit(max_paste_size: usize) -> usize {
     uncapped_request_body_limit(max_paste_size).min(MAX_JSON_REQUEST_BODY_BYTES)
 }
 
+fn upload_request_body_limit(max_paste_size: usize) -> usize {
+    // Multipart file parts carry raw bytes, so no escape expansion applies.
+    max_paste_size
+        .saturating_add(MULTIPART_BODY_OVERHEAD_BYTES)
+        .min(MAX_JSON_REQUEST_BODY_BYTES)
+}
+
 fn parse_http_origin_uri(origin: &HeaderValue) -> Option<axum::http::Uri> {
     let origin = match origin.to_str() {
         Ok(value) => value,
@@ -105,6 +142,16 @@ pub struct AppState {
     pub db: Arc<Database>,
     pub config: Arc<Config>,
     pub locks: Arc<PasteLockManager>,
+    pub uploads: Arc<ChunkUploadManager>,
+    pub rate_limiter: Arc<rate_limit::WriteRateLimiter>,
+    /// Paste and folder changes made through the API.
+    pub changes: Arc<events::ChangeFeed>,
+    /// Edit history for collaborative edits, shared with the GUI backend.
+    pub collab: Arc<collab::CollabHub>,
+    /// Announced viewers per paste, shared with the GUI sidebar.
+    pub presence: Arc<presence::PresenceTracker>,
+    /// Sizes above which `/api/health` and the storage monitor alert.
+    pub storage_limits: localpaste_core::config::StorageAlertLimits,
 }
 
 impl AppState {
@@ -131,10 +178,17 @@ impl AppState {
     /// # Returns
     /// A new [`AppState`] wired to the provided lock manager.
     pub fn with_locks(config: Config, db: Database, locks: Arc<PasteLockManager>) -> Self {
+        let changes: Arc<events::ChangeFeed> = Arc::default();
         Self {
             db: Arc::new(db),
             config: Arc::new(config),
             locks,
+            uploads: Arc::new(ChunkUploadManager::default()),
+            rate_limiter: Arc::new(rate_limit::WriteRateLimiter::from_env()),
+            collab: Arc::new(collab::CollabHub::new(changes.clone())),
+            presence: Arc::new(presence::PresenceTracker::new(changes.clone())),
+            changes,
+            storage_limits: localpaste_core::config::storage_alert_limits_from_env_or_default(),
         }
     }
 }
@@ -190,11 +244,14 @@ pub fn resolve_bind_address(config: &Config, allow_public_access: bool) -> Socke
 }
 
 fn create_app_with_cors(state: AppState, allow_public_access: bool, listener_port: u16) -> Router {
-    let uncapped_body_limit = uncapped_request_body_limit(state.config.max_paste_size);
-    let body_limit = request_body_limit(state.config.max_paste_size);
+    // Size transport limits for the largest per-source/per-folder override;
+    // handlers enforce the exact limit for each write.
+    let largest_max_paste_size = state.config.largest_max_paste_size();
+    let uncapped_body_limit = uncapped_request_body_limit(largest_max_paste_size);
+    let body_limit = request_body_limit(largest_max_paste_size);
     if body_limit < uncapped_body_limit {
         tracing::warn!(
-            configured_max_paste_size = state.config.max_paste_size,
+            configured_max_paste_size = largest_max_paste_size,
             body_limit_bytes = body_limit,
             uncapped_limit_bytes = uncapped_body_limit,
             hard_limit_bytes = MAX_JSON_REQUEST_BODY_BYTES,
@@ -224,15 +281,129 @@ fn create_app_with_cors(state: AppState, allow_public_access: bool, listener_por
                 axum::http::Method::PUT,
                 axum::http::Method::DELETE,
             ])
-            .allow_headers([header::CONTENT_TYPE, header::ACCEPT])
+            .allow_headers([
+                header::CONTENT_TYPE,
+                header::CONTENT_ENCODING,
+                header::ACCEPT,
+                header::AUTHORIZATION,
+                HeaderName::from_static(handlers::paste::PASTE_SOURCE_HEADER),
+            ])
     };
 
     Router::new()
         // API routes
         .route("/api/paste", post(handlers::paste::create_paste))
-        .route("/api/paste/:id", get(handlers::paste::get_paste))
+        .route(
+            "/api/paste/upload",
+            post(handlers::upload::upload_paste).layer(DefaultBodyLimit::max(
+                upload_request_body_limit(largest_max_paste_size),
+            )),
+        )
+        .route(
+            "/api/paste/:id",
+            get(handlers::paste::get_paste).head(handlers::paste::paste_exists),
+        )
         .route("/api/paste/:id", put(handlers::paste::update_paste))
         .route("/api/paste/:id", delete(handlers::paste::delete_paste))
+        .route(
+            "/api/paste/:id/preview",
+            get(handlers::paste::get_paste_preview),
+        )
+        .route("/api/paste/:id/lint", get(handlers::paste::get_paste_lint))
+        .route(
+            "/api/paste/:id/render",
+            get(handlers::export::get_paste_render),
+        )
+        .route(
+            "/api/paste/:id/edits",
+            get(handlers::collab::list_edits).post(handlers::collab::post_edit),
+        )
+        .route(
+            "/api/validate",
+            post(handlers::paste::validate_paste_content),
+        )
+        .route("/api/paste/:id/slug", put(handlers::paste::set_paste_slug))
+        .route(
+            "/api/paste/:id/comments",
+            get(handlers::comments::list_comments).post(handlers::comments::create_comment),
+        )
+        .route(
+            "/api/paste/:id/presence",
+            get(handlers::presence::list_presence)
+                .put(handlers::presence::touch_presence)
+                .delete(handlers::presence::leave_presence),
+        )
+        .route("/api/presence", get(handlers::presence::list_all_presence))
+        .route(
+            "/api/paste/:id/comments/:comment_id",
+            put(handlers::comments::update_comment).delete(handlers::comments::delete_comment),
+        )
+        .route(
+            "/api/paste/:id/annotations",
+            get(handlers::annotations::list_annotations)
+                .post(handlers::annotations::create_annotation),
+        )
+        .route(
+            "/api/paste/:id/annotations/:annotation_id",
+            put(handlers::annotations::update_annotation)
+                .delete(handlers::annotations::delete_annotation),
+        )
+        .route(
+            "/api/paste/:id/attachments",
+            get(handlers::attachments::list_attachments),
+        )
+        .route(
+            "/api/paste/:id/attachments",
+            post(handlers::attachments::upload_attachment).layer(DefaultBodyLimit::max(
+                upload_request_body_limit(
+                    localpaste_core::models::attachment::MAX_ATTACHMENT_BYTES,
+                ),
+            )),
+        )
+        .route(
+            "/api/paste/:id/attachments/:attachment_id",
+            get(handlers::attachments::download_attachment)
+                .delete(handlers::attachments::delete_attachment),
+        )
+        .route(
+            "/api/paste/:id/source",
+            get(handlers::url_source::get_url_source)
+                .put(handlers::url_source::set_url_source)
+                .delete(handlers::url_source::delete_url_source),
+        )
+        .route(
+            "/api/paste/:id/source/refresh",
+            post(handlers::url_source::refresh_url_source),
+        )
+        .route(
+            "/api/paste/:id/visibility",
+            get(handlers::users::get_paste_visibility).put(handlers::users::set_paste_visibility),
+        )
+        .route(
+            "/api/paste/by-slug/:slug",
+            get(handlers::paste::get_paste_by_slug),
+        )
+        .route("/api/paste/:id/raw", get(handlers::raw::get_paste_raw))
+        .route("/raw/:id", get(handlers::raw::get_paste_raw))
+        .route(
+            "/api/paste/:id/export",
+            get(handlers::export::get_paste_export),
+        )
+        .route("/api/paste/:id/html", get(handlers::export::get_paste_html))
+        .route(
+            "/api/paste/:id/chunks",
+            post(handlers::chunks::begin_chunked_upload),
+        )
+        .route(
+            "/api/paste/:id/chunks/:upload_id",
+            get(handlers::chunks::chunked_upload_status)
+                .post(handlers::chunks::append_chunk)
+                .delete(handlers::chunks::abort_chunked_upload),
+        )
+        .route(
+            "/api/paste/:id/chunks/:upload_id/commit",
+            post(handlers::chunks::commit_chunked_upload),
+        )
         .route(
             "/api/paste/:id/versions",
             get(handlers::paste::list_paste_versions),
@@ -250,18 +421,85 @@ fn create_app_with_cors(state: AppState, allow_public_access: bool, listener_por
             post(handlers::paste::duplicate_paste_version),
         )
         .route("/api/pastes", get(handlers::paste::list_pastes))
+        .route(
+            "/api/pastes/batch",
+            post(handlers::paste::create_pastes_batch),
+        )
         .route("/api/pastes/meta", get(handlers::paste::list_pastes_meta))
+        .route("/api/pastes/count", get(handlers::paste::count_pastes))
+        .route(
+            "/api/pastes/export",
+            get(handlers::export::export_all_pastes),
+        )
+        .route("/api/export", get(handlers::export::export_archive))
         .route("/api/search", get(handlers::paste::search_pastes))
         .route("/api/search/meta", get(handlers::paste::search_pastes_meta))
         .route("/api/diff", post(handlers::paste::diff_pastes))
         .route("/api/equal", post(handlers::paste::equal_pastes))
         .route("/api/folder", post(handlers::folder::create_folder))
+        .route("/api/folder/:id", get(handlers::folder::get_folder))
         .route("/api/folder/:id", put(handlers::folder::update_folder))
         .route("/api/folder/:id", delete(handlers::folder::delete_folder))
+        .route("/api/folder/:id/move", post(handlers::folder::move_folder))
+        .route(
+            "/api/folder/:id/count",
+            get(handlers::folder::count_folder_pastes),
+        )
+        .route(
+            "/api/folder/:id/export",
+            get(handlers::folder::export_folder),
+        )
         .route("/api/folders", get(handlers::folder::list_folders))
+        .route("/api/folders/tree", get(handlers::folder::get_folder_tree))
+        .route(
+            "/api/folder-rules",
+            get(handlers::folder_rules::list_folder_rules)
+                .post(handlers::folder_rules::create_folder_rule),
+        )
+        .route(
+            "/api/folder-rules/order",
+            put(handlers::folder_rules::reorder_folder_rules),
+        )
+        .route(
+            "/api/folder-rules/:id",
+            put(handlers::folder_rules::update_folder_rule)
+                .delete(handlers::folder_rules::delete_folder_rule),
+        )
+        .route("/api/admin/cleanup", post(handlers::admin::cleanup))
+        .route(
+            "/api/admin/backups/prune",
+            post(handlers::admin::prune_backups),
+        )
+        .route(
+            "/api/admin/users",
+            get(handlers::users::list_users).post(handlers::users::create_user),
+        )
+        .route("/api/admin/users/:id", delete(handlers::users::delete_user))
+        .route("/api/digest", post(handlers::digest::create_digest))
+        .route("/api/ws", get(handlers::ws::changes_ws))
+        .route("/api/events", get(handlers::sse::change_events_sse))
+        .route("/api/health", get(handlers::health::get_health))
+        .route("/api/stats", get(handlers::stats::get_stats))
+        .route("/api/tags", get(handlers::tags::list_tags))
+        .route(
+            "/api/tags/:name",
+            put(handlers::tags::rename_tag).delete(handlers::tags::delete_tag),
+        )
+        .route("/api/recent", get(handlers::recent::list_recent))
         // Note: Static files are not included in the library version
         // Main.rs handles static files with RustEmbed
         // Apply state
+        // No-op unless multi-user mode is on; runs inside CORS so preflight
+        // requests never need a token.
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            auth::enforce_access,
+        ))
+        // Throttles before auth so a runaway client cannot hammer token checks.
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::limit_writes,
+        ))
         .with_state(state.clone())
         // Apply middleware
         .layer(
@@ -271,6 +509,10 @@ fn create_app_with_cors(state: AppState, allow_public_access: bool, listener_por
                 .layer(DefaultBodyLimit::max(body_limit))
                 .layer(TraceLayer::new_for_http())
                 .layer(CompressionLayer::new())
+                // Every route accepts `Content-Encoding: gzip` or `zstd`; the
+                // body limit above applies to the decompressed stream, and
+                // other encodings get `415`.
+                .layer(RequestDecompressionLayer::new())
                 .layer(cors)
                 .layer(SetResponseHeaderLayer::overriding(
                     header::CONTENT_SECURITY_POLICY,
@@ -293,6 +535,9 @@ fn create_app_with_cors(state: AppState, allow_public_access: bool, listener_por
 
 /// Run the Axum server with graceful shutdown support.
 ///
+/// Uses the drain timeout from `LOCALPASTE_SHUTDOWN_DRAIN_SECS`; see
+/// [`serve_router_with_drain`].
+///
 /// # Arguments
 /// - `listener`: Bound TCP listener for the server.
 /// - `state`: Shared application state.
@@ -309,15 +554,109 @@ pub async fn serve_router(
     state: AppState,
     allow_public_access: bool,
     shutdown_signal: impl Future<Output = ()> + Send + 'static,
+) -> Result<(), std::io::Error> {
+    let drain_timeout = config::shutdown_drain_timeout_from_env_or_default();
+    serve_router_with_drain(
+        listener,
+        state,
+        allow_public_access,
+        shutdown_signal,
+        drain_timeout,
+    )
+    .await
+}
+
+/// Run the Axum server and drain in-flight requests on shutdown.
+///
+/// Once `shutdown_signal` resolves the listener stops accepting connections,
+/// and requests already in progress (such as upload chunks still streaming)
+/// get up to `drain_timeout` to finish. After that the server stops waiting and
+/// returns; connections still open are closed when the runtime shuts down.
+/// The database is flushed before returning either way.
+///
+/// # Arguments
+/// - `listener`: Bound TCP listener for the server.
+/// - `state`: Shared application state.
+/// - `allow_public_access`: Whether to allow cross-origin requests from any origin.
+/// - `shutdown_signal`: Future that resolves when shutdown should start.
+/// - `drain_timeout`: How long to wait for in-flight requests after the signal.
+///
+/// # Returns
+/// `Ok(())` when the server exits, whether or not draining finished in time.
+///
+/// # Errors
+/// Returns any I/O error produced by `axum::serve`.
+pub async fn serve_router_with_drain(
+    listener: tokio::net::TcpListener,
+    state: AppState,
+    allow_public_access: bool,
+    shutdown_signal: impl Future<Output = ()> + Send + 'static,
+    drain_timeout: Duration,
 ) -> Result<(), std::io::Error> {
     let listener_port = listener
         .local_addr()
         .map(|addr| addr.port())
         .unwrap_or(state.config.port);
+    let db = state.db.clone();
+    let uploads = state.uploads.clone();
+    let expiry_sweep = expiry::spawn_expiry_sweep(state.clone());
+    let url_refresh = url_refresh::spawn_url_refresh(state.clone());
+    let storage_monitor = storage_monitor::spawn_storage_monitor(state.clone());
+    let flusher = flusher::spawn_flusher(db.clone(), state.config.flush_policy);
     let app = create_app_with_cors(state, allow_public_access, listener_port);
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal)
-        .await
+
+    let (signalled_tx, signalled_rx) = tokio::sync::oneshot::channel();
+    let shutdown_signal = async move {
+        shutdown_signal.await;
+        let _ = signalled_tx.send(());
+    };
+    let serve = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal)
+    .into_future();
+    tokio::pin!(serve);
+
+    let result = tokio::select! {
+        result = &mut serve => result,
+        Ok(()) = signalled_rx => {
+            tracing::info!(
+                "shutdown requested; draining in-flight requests for up to {:?}",
+                drain_timeout
+            );
+            match tokio::time::timeout(drain_timeout, &mut serve).await {
+                Ok(result) => result,
+                Err(_) => {
+                    tracing::warn!(
+                        "drain timeout of {:?} elapsed; abandoning remaining connections",
+                        drain_timeout
+                    );
+                    Ok(())
+                }
+            }
+        }
+    };
+
+    expiry_sweep.abort();
+    url_refresh.abort();
+    storage_monitor.abort();
+    if let Some(flusher) = flusher {
+        flusher.abort();
+        // Nothing flushes relaxed commits once the task is gone.
+        db.set_flush_policy(localpaste_core::db::durability::FlushPolicy::EveryCommit);
+    }
+    let open_uploads = uploads.open_sessions();
+    if open_uploads > 0 {
+        tracing::warn!(
+            "{} chunked upload(s) were not finished and are discarded",
+            open_uploads
+        );
+    }
+    if let Err(err) = db.flush() {
+        tracing::warn!("failed to flush database on shutdown: {}", err);
+    }
+    result
 }
 
 #[cfg(test)]
@@ -326,9 +665,11 @@ mod tests {
     use super::is_loopback_origin_for_listener_port;
     use super::request_body_limit;
     use super::resolve_bind_address;
+    use super::upload_request_body_limit;
     use super::JSON_BODY_OVERHEAD_BYTES;
     use super::JSON_STRING_ESCAPE_EXPANSION_FACTOR;
     use super::MAX_JSON_REQUEST_BODY_BYTES;
+    use super::MULTIPART_BODY_OVERHEAD_BYTES;
     use axum::http::HeaderValue;
     use localpaste_core::env::{env_lock, EnvGuard};
     use localpaste_core::Config;
@@ -348,6 +689,18 @@ mod tests {
         assert_eq!(request_body_limit(usize::MAX), MAX_JSON_REQUEST_BODY_BYTES);
     }
 
+    #[test]
+    fn upload_body_limit_skips_json_escape_expansion() {
+        assert_eq!(
+            upload_request_body_limit(1024),
+            1024 + MULTIPART_BODY_OVERHEAD_BYTES
+        );
+        assert_eq!(
+            upload_request_body_limit(usize::MAX),
+            MAX_JSON_REQUEST_BODY_BYTES
+        );
+    }
+
     #[test]
     fn loopback_origin_detection_matrix_covers_valid_loopback_and_rejections() {
         let cases = [
@@ -419,6 +772,9 @@ mod tests {
             max_paste_size: 1024,
             auto_save_interval: 2000,
             auto_backup: false,
+            size_limits: Default::default(),
+            flush_policy: Default::default(),
+            recent_history: false,
         };
         let _bind = EnvGuard::set("BIND", "0.0.0.0:4040");
         let resolved = resolve_bind_address(&config, false);
@@ -435,6 +791,9 @@ mod tests {
             max_paste_size: 1024,
             auto_save_interval: 2000,
             auto_backup: false,
+            size_limits: Default::default(),
+            flush_policy: Default::default(),
+            recent_history: false,
         };
         let loopback = resolve_bind_address(&config, false);
         assert_eq!(loopback, SocketAddr::from(([127, 0, 0, 1], 4041)));
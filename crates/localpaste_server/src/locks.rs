@@ -1,6 +1,7 @@
 //! In-memory paste edit locks shared between GUI and API handlers.
 
 use crate::AppError;
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::sync::{Mutex, MutexGuard};
@@ -92,6 +93,32 @@ pub fn map_paste_mutation_lock_error(
     }
 }
 
+/// Map tag rename/removal lock errors into a consistent lock-rejection contract.
+///
+/// # Arguments
+/// - `err`: Lock manager error from batch mutation guard acquisition.
+///
+/// # Returns
+/// API-facing error for a tag change that would rewrite a locked paste.
+pub fn map_tag_change_lock_error(err: PasteLockError) -> AppError {
+    match err {
+        PasteLockError::Held { paste_id } => AppError::Locked(format!(
+            "Tag change would rewrite locked paste '{}'; close it first.",
+            paste_id
+        )),
+        PasteLockError::Mutating { paste_id } => AppError::Locked(format!(
+            "Tag change would rewrite paste '{}' during an in-flight mutation; retry shortly.",
+            paste_id
+        )),
+        PasteLockError::Poisoned => {
+            AppError::StorageMessage("Paste lock manager is unavailable.".to_string())
+        }
+        PasteLockError::NotHeld { .. } => {
+            AppError::StorageMessage(format!("Unexpected paste lock state: {}", err))
+        }
+    }
+}
+
 /// Map folder-delete lock errors into a consistent lock-rejection contract.
 ///
 /// # Arguments
@@ -174,12 +201,29 @@ fn lock_conflict_message(err: &PasteLockError, locked_message: &'static str) ->
     }
 }
 
+/// Which edit-lock holders a new mutation guard tolerates.
+#[derive(Clone, Copy)]
+enum HolderCheck<'a> {
+    RejectAll,
+    AllowOwner(&'a LockOwnerId),
+    AllowAll,
+}
+
 #[derive(Default)]
 struct LockState {
     holders_by_paste: HashMap<String, HashSet<LockOwnerId>>,
     mutating_pastes: HashSet<String>,
 }
 
+/// Point-in-time lock counts reported by `GET /api/health`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LockStats {
+    /// Pastes held open by at least one owner.
+    pub locked_pastes: usize,
+    /// Pastes with an in-flight mutation guard.
+    pub mutating_pastes: usize,
+}
+
 /// Tracks lock holders and in-flight mutation guards for paste ids.
 #[derive(Default)]
 pub struct PasteLockManager {
@@ -295,6 +339,40 @@ impl PasteLockManager {
             .unwrap_or(false))
     }
 
+    /// Count held and mutating pastes.
+    ///
+    /// # Errors
+    /// Returns [`PasteLockError::Poisoned`] when lock state is poisoned.
+    pub fn stats(&self) -> Result<LockStats, PasteLockError> {
+        let state = self.state()?;
+        Ok(LockStats {
+            locked_pastes: state
+                .holders_by_paste
+                .values()
+                .filter(|holders| !holders.is_empty())
+                .count(),
+            mutating_pastes: state.mutating_pastes.len(),
+        })
+    }
+
+    /// Current edit-lock holders per paste, each list sorted by owner id.
+    ///
+    /// # Errors
+    /// Returns [`PasteLockError::Poisoned`] when lock state is poisoned.
+    pub fn holders_snapshot(&self) -> Result<HashMap<String, Vec<LockOwnerId>>, PasteLockError> {
+        let state = self.state()?;
+        Ok(state
+            .holders_by_paste
+            .iter()
+            .filter(|(_, holders)| !holders.is_empty())
+            .map(|(paste_id, holders)| {
+                let mut holders: Vec<_> = holders.iter().cloned().collect();
+                holders.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+                (paste_id.clone(), holders)
+            })
+            .collect())
+    }
+
     /// Begin a mutation guard for one paste id.
     ///
     /// # Returns
@@ -327,29 +405,45 @@ impl PasteLockManager {
         paste_id: &str,
         owner_id: &LockOwnerId,
     ) -> Result<PasteMutationGuard<'a>, PasteLockError> {
-        let mut state = self.state()?;
-        if state.mutating_pastes.contains(paste_id) {
-            return Err(PasteLockError::Mutating {
-                paste_id: paste_id.to_string(),
-            });
-        }
-        if let Some(holders) = state.holders_by_paste.get(paste_id) {
-            let held_by_other_owner = match holders.len() {
-                0 => false,
-                1 => !holders.contains(owner_id),
-                _ => true,
-            };
-            if held_by_other_owner {
-                return Err(PasteLockError::Held {
-                    paste_id: paste_id.to_string(),
-                });
-            }
-        }
-        state.mutating_pastes.insert(paste_id.to_string());
-        Ok(PasteMutationGuard {
-            manager: self,
-            paste_ids: vec![paste_id.to_string()],
-        })
+        self.begin_batch_mutation_ignoring_owner([paste_id], owner_id)
+    }
+
+    /// Begin a mutation guard for multiple paste ids, allowing `owner_id` to
+    /// already hold any of them.
+    ///
+    /// # Returns
+    /// A guard that blocks competing mutations on all provided paste ids until
+    /// dropped.
+    ///
+    /// # Errors
+    /// Returns an error when any target id is already mutating, held by any
+    /// owner other than `owner_id`, or lock state is poisoned.
+    pub fn begin_batch_mutation_ignoring_owner<'a, I>(
+        &'a self,
+        paste_ids: I,
+        owner_id: &LockOwnerId,
+    ) -> Result<PasteMutationGuard<'a>, PasteLockError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.begin_batch_mutation_inner(paste_ids, HolderCheck::AllowOwner(owner_id))
+    }
+
+    /// Begin a mutation guard for a collaborative edit, which merges with
+    /// other editors instead of waiting for them to close the paste.
+    ///
+    /// # Returns
+    /// A guard that blocks competing mutations on `paste_id` until dropped.
+    ///
+    /// # Errors
+    /// Returns an error when `paste_id` is already mutating or lock state is
+    /// poisoned.
+    pub fn begin_shared_edit(
+        &self,
+        paste_id: &str,
+    ) -> Result<PasteMutationGuard<'_>, PasteLockError> {
+        self.begin_batch_mutation_inner([paste_id], HolderCheck::AllowAll)
     }
 
     /// Begin a mutation guard for multiple paste ids.
@@ -367,6 +461,18 @@ impl PasteLockManager {
         &'a self,
         paste_ids: I,
     ) -> Result<PasteMutationGuard<'a>, PasteLockError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.begin_batch_mutation_inner(paste_ids, HolderCheck::RejectAll)
+    }
+
+    fn begin_batch_mutation_inner<'a, I>(
+        &'a self,
+        paste_ids: I,
+        holder_check: HolderCheck<'_>,
+    ) -> Result<PasteMutationGuard<'a>, PasteLockError>
     where
         I: IntoIterator,
         I::Item: AsRef<str>,
@@ -387,12 +493,14 @@ impl PasteLockManager {
                     paste_id: paste_id.clone(),
                 });
             }
-            if state
-                .holders_by_paste
-                .get(paste_id)
-                .map(|holders| !holders.is_empty())
-                .unwrap_or(false)
-            {
+            let held_by_other_owner = state.holders_by_paste.get(paste_id).is_some_and(|holders| {
+                match (holders.len(), holder_check) {
+                    (0, _) | (_, HolderCheck::AllowAll) => false,
+                    (1, HolderCheck::AllowOwner(owner_id)) => !holders.contains(owner_id),
+                    _ => true,
+                }
+            });
+            if held_by_other_owner {
                 return Err(PasteLockError::Held {
                     paste_id: paste_id.clone(),
                 });
@@ -411,8 +519,8 @@ impl PasteLockManager {
 #[cfg(test)]
 mod tests {
     use super::{
-        map_folder_delete_lock_error, map_paste_mutation_lock_error, LockOwnerId, PasteLockError,
-        PasteLockManager,
+        map_folder_delete_lock_error, map_paste_mutation_lock_error, LockOwnerId, LockStats,
+        PasteLockError, PasteLockManager,
     };
     use crate::AppError;
     use std::sync::Arc;
@@ -567,6 +675,48 @@ mod tests {
         assert!(matches!(shared_err, PasteLockError::Held { .. }));
     }
 
+    #[test]
+    fn begin_shared_edit_ignores_holders_but_not_in_flight_mutations() {
+        let locks = PasteLockManager::default();
+        locks
+            .acquire("alpha", &owner("owner-a"))
+            .expect("owner-a acquires");
+        locks
+            .acquire("alpha", &owner("owner-b"))
+            .expect("owner-b acquires");
+
+        let guard = locks
+            .begin_shared_edit("alpha")
+            .expect("shared holders should not block a collaborative edit");
+        let blocked = match locks.begin_shared_edit("alpha") {
+            Ok(_) => panic!("second concurrent edit should wait for the first"),
+            Err(err) => err,
+        };
+        assert!(matches!(blocked, PasteLockError::Mutating { .. }));
+        drop(guard);
+
+        assert!(locks.is_locked("alpha").expect("is_locked"));
+    }
+
+    #[test]
+    fn stats_count_held_and_mutating_pastes() {
+        let locks = PasteLockManager::default();
+        let owner_a = owner("owner-a");
+        locks.acquire("alpha", &owner_a).expect("acquire alpha");
+        locks.acquire("beta", &owner_a).expect("acquire beta");
+        locks.release("beta", &owner_a).expect("release beta");
+        let guard = locks.begin_mutation("gamma").expect("mutate gamma");
+        assert_eq!(
+            locks.stats().expect("stats"),
+            LockStats {
+                locked_pastes: 1,
+                mutating_pastes: 1,
+            }
+        );
+        drop(guard);
+        assert_eq!(locks.stats().expect("stats").mutating_pastes, 0);
+    }
+
     #[test]
     fn methods_return_poisoned_error_instead_of_panicking() {
         let locks = Arc::new(PasteLockManager::default());
@@ -594,6 +744,7 @@ mod tests {
             locks.begin_mutation("alpha"),
             Err(PasteLockError::Poisoned)
         ));
+        assert!(matches!(locks.stats(), Err(PasteLockError::Poisoned)));
     }
 
     #[test]
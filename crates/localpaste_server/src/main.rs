@@ -1,23 +1,37 @@
 //! Headless API server entrypoint.
 
-use localpaste_core::DEFAULT_PORT;
-use localpaste_server::{config::Config, db::Database, serve_router, AppState};
+use localpaste_core::{DEFAULT_GALLERY_PORT, DEFAULT_PORT};
+use localpaste_server::{config::Config, db::Database, serve_router, AppState, GalleryConfig};
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 struct CliFlags {
     help: bool,
+    build_info: bool,
     backup: bool,
+    cleanup: bool,
+    verify_backup: Option<PathBuf>,
+    restore_backup: Option<PathBuf>,
 }
 
 fn parse_cli_flags(args: &[String]) -> anyhow::Result<CliFlags> {
     let mut flags = CliFlags::default();
-    for arg in args.iter().skip(1) {
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        let mut path_value = |option: &str| -> anyhow::Result<PathBuf> {
+            match args.next() {
+                Some(value) if !value.starts_with('-') => Ok(PathBuf::from(value)),
+                _ => anyhow::bail!("Option '{}' requires a backup file path.", option),
+            }
+        };
         match arg.as_str() {
             "--help" => flags.help = true,
+            "--build-info" => flags.build_info = true,
             "--backup" => flags.backup = true,
+            "--cleanup" => flags.cleanup = true,
+            "--verify-backup" => flags.verify_backup = Some(path_value(arg)?),
+            "--restore-backup" => flags.restore_backup = Some(path_value(arg)?),
             value if value.starts_with('-') => {
                 anyhow::bail!(
                     "Unknown option: '{}'. Use --help to see supported options.",
@@ -35,8 +49,8 @@ fn parse_cli_flags(args: &[String]) -> anyhow::Result<CliFlags> {
     Ok(flags)
 }
 
-fn runs_maintenance_mode(flags: CliFlags) -> bool {
-    flags.backup
+fn runs_maintenance_mode(flags: &CliFlags) -> bool {
+    flags.backup || flags.cleanup || flags.verify_backup.is_some() || flags.restore_backup.is_some()
 }
 
 fn validate_bind_override(allow_public_access: bool) -> anyhow::Result<()> {
@@ -65,13 +79,10 @@ fn database_file_path(config: &Config) -> PathBuf {
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "localpaste=info,tower_http=warn".into()),
-        )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+    let telemetry = localpaste_server::telemetry::init_tracing("localpaste=info,tower_http=warn");
+    if telemetry.exporting() {
+        tracing::info!("Exporting trace spans over OTLP");
+    }
 
     let args: Vec<String> = std::env::args().collect();
     let cli_flags = parse_cli_flags(&args)?;
@@ -80,29 +91,73 @@ async fn main() -> anyhow::Result<()> {
         print_help();
         return Ok(());
     }
+    if cli_flags.build_info {
+        print!(
+            "{}",
+            localpaste_core::build_info::render_build_info(
+                "localpaste",
+                env!("CARGO_PKG_VERSION"),
+                &[("otlp", cfg!(feature = "otlp"))],
+            )
+        );
+        return Ok(());
+    }
 
     let config = Config::from_env_strict().map_err(anyhow::Error::msg)?;
     let db_exists_before_open = database_file_path(&config).is_file();
 
+    if let Some(path) = cli_flags.verify_backup.as_deref() {
+        let manifest = localpaste_server::db::backup::verify_backup(path)?;
+        println!(
+            "Backup verified: {} file(s) match the manifest",
+            manifest.files.len()
+        );
+    }
+
     if cli_flags.backup {
         run_backup(&config)?;
     }
 
-    if runs_maintenance_mode(cli_flags) {
+    if let Some(path) = cli_flags.restore_backup.as_deref() {
+        localpaste_server::db::backup::BackupManager::new(&config.db_path).restore_backup(path)?;
+        println!("Database restored from: {}", path.display());
+    }
+
+    if cli_flags.cleanup {
+        run_cleanup(&config)?;
+    }
+
+    if runs_maintenance_mode(&cli_flags) {
         return Ok(());
     }
 
+    match localpaste_server::db::backup::BackupManager::new(&config.db_path).apply_pending_restore()
+    {
+        Ok(Some(path)) => tracing::info!("Restored scheduled backup: {}", path.display()),
+        Ok(None) => {}
+        Err(err) => tracing::error!("Scheduled backup restore failed: {}", err),
+    }
     let database = Database::new(&config.db_path)?;
     localpaste_core::detection::prewarm();
 
     if config.auto_backup && db_exists_before_open {
         let backup_manager = localpaste_server::db::backup::BackupManager::new(&config.db_path);
-        if let Err(err) = backup_manager.create_backup(database.db.as_ref()) {
-            tracing::warn!("Failed to create auto-backup: {}", err);
+        match backup_manager.create_backup(database.db.as_ref()) {
+            Ok(backup_path) if !backup_path.is_empty() => {
+                let config = config.clone();
+                tokio::task::spawn_blocking(move || {
+                    if let Err(err) = push_remote_backup(&config, Path::new(&backup_path)) {
+                        tracing::warn!("Failed to push auto-backup: {}", err);
+                    }
+                });
+            }
+            Ok(_) => {}
+            Err(err) => tracing::warn!("Failed to create auto-backup: {}", err),
         }
     }
 
     let state = AppState::new(config.clone(), database);
+    let gallery = GalleryConfig::from_env_strict().map_err(anyhow::Error::msg)?;
 
     let allow_public =
         localpaste_server::config::parse_bool_env_strict("ALLOW_PUBLIC_ACCESS", false)
@@ -124,6 +179,10 @@ async fn main() -> anyhow::Result<()> {
     let actual_addr = listener.local_addr().unwrap_or(bind_addr);
     tracing::info!("LocalPaste running at http://{}", actual_addr);
 
+    if let Some(gallery) = gallery {
+        spawn_gallery(&state, gallery).await?;
+    }
+
     let serve_result = serve_router(listener, state, allow_public, shutdown_signal()).await;
 
     serve_result?;
@@ -131,11 +190,44 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Bind the read-only gallery listener and serve it in the background.
+///
+/// The gallery stops with the process; it has no writes to drain.
+async fn spawn_gallery(state: &AppState, gallery: GalleryConfig) -> anyhow::Result<()> {
+    if state.db.folders.get(&gallery.folder_id)?.is_none() {
+        anyhow::bail!(
+            "{}='{}' does not name an existing folder",
+            localpaste_server::gallery::GALLERY_FOLDER_ENV,
+            gallery.folder_id
+        );
+    }
+    let listener = tokio::net::TcpListener::bind(gallery.bind).await?;
+    let addr = listener.local_addr().unwrap_or(gallery.bind);
+    tracing::warn!(
+        "Read-only gallery publishing folder '{}' at http://{}",
+        gallery.folder_id,
+        addr
+    );
+    let app = localpaste_server::create_gallery_app(state.db.clone(), &gallery.folder_id);
+    tokio::spawn(async move {
+        if let Err(err) = axum::serve(listener, app).await {
+            tracing::error!("Gallery server stopped: {}", err);
+        }
+    });
+    Ok(())
+}
+
 fn print_help() {
     println!("LocalPaste Server\n");
     println!("Usage: localpaste [OPTIONS]\n");
     println!("Options:");
     println!("  --backup          Create a backup of the database");
+    println!("  --cleanup         Remove orphaned rows and compact the database, then exit");
+    println!("  --verify-backup <path>   Check a backup against its checksum manifest, then exit");
+    println!(
+        "  --restore-backup <path>  Verify a backup and replace the database with it, then exit"
+    );
+    println!("  --build-info      Print version, commit, build date, features, and default paths");
     println!("  --help            Show this help message");
     println!("\nEnvironment variables:");
     println!(
@@ -146,23 +238,47 @@ fn print_help() {
         DEFAULT_PORT
     );
     println!("  MAX_PASTE_SIZE    Maximum paste size in bytes (default: 10MB)");
+    println!("  MAX_PASTE_SIZE_API|_GUI|_CLIPBOARD|_IMPORT  Per-source size overrides in bytes");
+    println!("  MAX_PASTE_SIZE_FOLDERS  Per-folder size overrides (<folder_id>=<bytes>,...)");
     println!(
         "  LOCALPASTE_VERSION_INTERVAL_SECS  Min seconds between saved historical snapshots (default: 300, must be >= 1)"
     );
     println!(
         "  LOCALPASTE_PASTE_VERSION_INTERVAL_SECS  Legacy fallback for LOCALPASTE_VERSION_INTERVAL_SECS"
     );
+    println!("  LOCALPASTE_LINE_ENDINGS  preserve (default) or lf; lf records the original ending");
+    println!("  LOCALPASTE_STRIP_BOM     Strip a leading UTF-8 BOM on save (1/0/true/false)");
+    println!(
+        "  LOCALPASTE_INVALID_UTF8  reject (default) or replace invalid bytes in chunked uploads"
+    );
     println!(
         "  AUTO_BACKUP       Create backup at startup when DB already exists (1/0/true/false)"
     );
+    println!(
+        "  LOCALPASTE_BACKUP_PASSPHRASE  Encrypt backups (age, scrypt); needed to restore them"
+    );
     println!("  ALLOW_PUBLIC_ACCESS  Allow CORS from any origin");
+    println!(
+        "  LOCALPASTE_SHUTDOWN_DRAIN_SECS  Seconds to let in-flight requests finish on shutdown (default: 30)"
+    );
     println!(
         "  BIND              Override bind address (e.g. 0.0.0.0:{})",
         DEFAULT_PORT
     );
+    println!(
+        "  LOCALPASTE_GALLERY_FOLDER  Publish this folder id read-only on a separate listener"
+    );
+    println!(
+        "  LOCALPASTE_GALLERY_BIND    Gallery listener address (default: 0.0.0.0:{})",
+        DEFAULT_GALLERY_PORT
+    );
     println!("  (malformed env values fail startup instead of silently defaulting)");
     println!("\nSide effects:");
     println!("  --backup          Writes a consistent backup copy of data.redb");
+    println!("  --cleanup         Rewrites data.redb in place; the server must be stopped");
+    println!("  --backup          Also writes <backup>.manifest.json with BLAKE3 checksums");
+    println!("  --restore-backup  Replaces data.redb; the server must be stopped");
+    println!("  --backup          Pushes to remote targets listed in DB_PATH/settings.json");
 }
 
 fn run_backup(config: &Config) -> anyhow::Result<()> {
@@ -175,6 +291,7 @@ fn run_backup(config: &Config) -> anyhow::Result<()> {
         let backup_manager = localpaste_server::db::backup::BackupManager::new(&config.db_path);
         let backup_path = backup_manager.create_backup(temp_db.db.as_ref())?;
         println!("Database backed up to: {}", backup_path);
+        push_remote_backup(config, Path::new(&backup_path))?;
     } else if db_dir.is_dir() && localpaste_server::db::looks_like_legacy_sled_layout(db_dir)? {
         anyhow::bail!(
             "Detected legacy sled database files in '{}' but '{}' is missing.\n\
@@ -189,6 +306,50 @@ fn run_backup(config: &Config) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn push_remote_backup(config: &Config, backup_path: &Path) -> anyhow::Result<()> {
+    let reports = localpaste_server::db::backup_remote::push_backup_to_remote_targets(
+        &config.db_path,
+        backup_path,
+    )?;
+    let mut failed = 0usize;
+    for report in reports {
+        match report.outcome {
+            Ok(pruned) => println!(
+                "Pushed backup to '{}' (pruned {} old backup(s))",
+                report.target,
+                pruned.len()
+            ),
+            Err(err) => {
+                failed += 1;
+                eprintln!("Remote backup target '{}' failed: {}", report.target, err);
+            }
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!("{} remote backup target(s) failed", failed);
+    }
+    Ok(())
+}
+
+fn run_cleanup(config: &Config) -> anyhow::Result<()> {
+    if !database_file_path(config).is_file() {
+        println!("No existing database to clean up");
+        return Ok(());
+    }
+    let database = Database::new(&config.db_path)?;
+    let mut report = localpaste_core::maintenance::cleanup_database(&database)?;
+    drop(database);
+    report.compacted =
+        localpaste_core::maintenance::compact_database_file(Path::new(&config.db_path))?;
+    println!(
+        "Removed {} orphan draft(s), reconciled {} folder(s), compacted: {}",
+        report.orphan_drafts_removed,
+        report.folders_reconciled,
+        if report.compacted { "yes" } else { "no" }
+    );
+    Ok(())
+}
+
 async fn shutdown_signal() {
     let ctrl_c = async {
         tokio::signal::ctrl_c()
@@ -220,6 +381,7 @@ mod tests {
         validate_bind_override, CliFlags,
     };
     use localpaste_core::env::{env_lock, EnvGuard};
+    use std::path::PathBuf;
     use tempfile::TempDir;
 
     #[test]
@@ -249,9 +411,44 @@ mod tests {
             flags,
             CliFlags {
                 help: false,
+                build_info: false,
                 backup: true,
+                cleanup: false,
+                verify_backup: None,
+                restore_backup: None,
             }
         );
+
+        let args = vec!["localpaste".to_string(), "--build-info".to_string()];
+        let flags = parse_cli_flags(&args).expect("--build-info should parse");
+        assert!(flags.build_info);
+        assert!(!runs_maintenance_mode(&flags));
+    }
+
+    #[test]
+    fn parse_cli_flags_reads_backup_paths_and_requires_values() {
+        let args: Vec<String> = [
+            "localpaste",
+            "--verify-backup",
+            "a.redb",
+            "--restore-backup",
+            "b.redb",
+        ]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+        let flags = parse_cli_flags(&args).expect("backup paths should parse");
+        assert_eq!(flags.verify_backup, Some(PathBuf::from("a.redb")));
+        assert_eq!(flags.restore_backup, Some(PathBuf::from("b.redb")));
+
+        for args in [
+            vec!["localpaste", "--verify-backup"],
+            vec!["localpaste", "--restore-backup", "--backup"],
+        ] {
+            let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+            let err = parse_cli_flags(&args).expect_err("missing path should be rejected");
+            assert!(err.to_string().contains("requires a backup file path"));
+        }
     }
 
     #[test]
@@ -260,9 +457,19 @@ mod tests {
             backup: true,
             ..CliFlags::default()
         };
+        let cleanup_only = CliFlags {
+            cleanup: true,
+            ..CliFlags::default()
+        };
         let none = CliFlags::default();
-        assert!(runs_maintenance_mode(backup_only));
-        assert!(!runs_maintenance_mode(none));
+        let verify_only = CliFlags {
+            verify_backup: Some(PathBuf::from("db.backup.1.redb")),
+            ..CliFlags::default()
+        };
+        assert!(runs_maintenance_mode(&backup_only));
+        assert!(runs_maintenance_mode(&cleanup_only));
+        assert!(runs_maintenance_mode(&verify_only));
+        assert!(!runs_maintenance_mode(&none));
     }
 
     #[test]
@@ -276,6 +483,9 @@ mod tests {
             max_paste_size: 1024 * 1024,
             auto_save_interval: 500,
             auto_backup: false,
+            size_limits: Default::default(),
+            flush_policy: Default::default(),
+            recent_history: false,
         };
 
         run_backup(&config).expect("backup mode should succeed when db file is missing");
@@ -308,6 +518,9 @@ mod tests {
             max_paste_size: 1024 * 1024,
             auto_save_interval: 500,
             auto_backup: false,
+            size_limits: Default::default(),
+            flush_policy: Default::default(),
+            recent_history: false,
         };
 
         let err = run_backup(&config).expect_err("legacy layout should fail in backup mode");
@@ -0,0 +1,215 @@
+//! Who has a paste open: edit-lock holders plus clients that announced they
+//! are viewing it.
+//!
+//! Viewers renew their entry with `PUT /api/paste/:id/presence`; entries not
+//! renewed within [`PRESENCE_TTL`] are dropped the next time presence is read.
+//! Joins, leaves, and expiries are published to the change feed as
+//! `"presence"` changes so subscribers know to re-fetch.
+
+use crate::events::{ChangeEvent, ChangeFeed, ChangeKind, ChangeTarget};
+use crate::locks::{PasteLockError, PasteLockManager};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// How long a viewer stays listed without renewing.
+pub const PRESENCE_TTL: Duration = Duration::from_secs(30);
+
+/// Longest owner label accepted from a client.
+pub const MAX_OWNER_LABEL_CHARS: usize = 64;
+
+/// Whether an owner holds the edit lock or only announced it is viewing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceState {
+    Editing,
+    Viewing,
+}
+
+/// One client with a paste open, sent as `{"owner", "state"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresenceEntry {
+    /// Lock owner id, or the label a viewer announced (`"cli"`, `"web"`).
+    pub owner: String,
+    pub state: PresenceState,
+}
+
+/// Announced viewers per paste, merged with lock holders on read.
+#[derive(Debug, Default)]
+pub struct PresenceTracker {
+    viewers: Mutex<HashMap<String, HashMap<String, Instant>>>,
+    changes: Arc<ChangeFeed>,
+}
+
+impl PresenceTracker {
+    /// Create a tracker that publishes presence changes to `changes`.
+    pub fn new(changes: Arc<ChangeFeed>) -> Self {
+        Self {
+            viewers: Mutex::default(),
+            changes,
+        }
+    }
+
+    fn publish(&self, paste_id: &str) {
+        self.changes.publish(ChangeEvent {
+            target: ChangeTarget::Paste,
+            id: paste_id.to_string(),
+            change: ChangeKind::Presence,
+            access: None,
+        });
+    }
+
+    /// Viewer map with expired entries removed; expiries are published.
+    fn live_viewers(&self) -> MutexGuard<'_, HashMap<String, HashMap<String, Instant>>> {
+        let mut viewers = self
+            .viewers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut expired = Vec::new();
+        viewers.retain(|paste_id, owners| {
+            let before = owners.len();
+            owners.retain(|_, seen| seen.elapsed() < PRESENCE_TTL);
+            if owners.len() != before {
+                expired.push(paste_id.clone());
+            }
+            !owners.is_empty()
+        });
+        for paste_id in &expired {
+            self.publish(paste_id);
+        }
+        viewers
+    }
+
+    /// Mark `owner` as viewing `paste_id` for another [`PRESENCE_TTL`].
+    pub fn touch(&self, paste_id: &str, owner: &str) {
+        let joined = self
+            .live_viewers()
+            .entry(paste_id.to_string())
+            .or_default()
+            .insert(owner.to_string(), Instant::now())
+            .is_none();
+        if joined {
+            self.publish(paste_id);
+        }
+    }
+
+    /// Publish a presence change after an edit lock on `paste_id` was
+    /// acquired or released outside the API.
+    pub fn lock_changed(&self, paste_id: &str) {
+        self.publish(paste_id);
+    }
+
+    /// Remove `owner` from the viewers of `paste_id`.
+    ///
+    /// # Returns
+    /// `true` when `owner` was listed.
+    pub fn leave(&self, paste_id: &str, owner: &str) -> bool {
+        let left = {
+            let mut viewers = self.live_viewers();
+            let left = viewers
+                .get_mut(paste_id)
+                .is_some_and(|owners| owners.remove(owner).is_some());
+            if viewers.get(paste_id).is_some_and(HashMap::is_empty) {
+                viewers.remove(paste_id);
+            }
+            left
+        };
+        if left {
+            self.publish(paste_id);
+        }
+        left
+    }
+
+    /// Everyone with `paste_id` open, editors first.
+    ///
+    /// # Errors
+    /// Returns [`PasteLockError::Poisoned`] when lock state is poisoned.
+    pub fn list(
+        &self,
+        locks: &PasteLockManager,
+        paste_id: &str,
+    ) -> Result<Vec<PresenceEntry>, PasteLockError> {
+        Ok(self.all(locks)?.remove(paste_id).unwrap_or_default())
+    }
+
+    /// Everyone with any paste open, keyed by paste id, editors first.
+    ///
+    /// An owner that both holds the lock and announced itself as a viewer is
+    /// listed once, as editing.
+    ///
+    /// # Errors
+    /// Returns [`PasteLockError::Poisoned`] when lock state is poisoned.
+    pub fn all(
+        &self,
+        locks: &PasteLockManager,
+    ) -> Result<HashMap<String, Vec<PresenceEntry>>, PasteLockError> {
+        let mut owners: HashMap<String, BTreeMap<String, PresenceState>> = HashMap::new();
+        for (paste_id, viewers) in self.live_viewers().iter() {
+            let entry = owners.entry(paste_id.clone()).or_default();
+            for owner in viewers.keys() {
+                entry.insert(owner.clone(), PresenceState::Viewing);
+            }
+        }
+        for (paste_id, holders) in locks.holders_snapshot()? {
+            let entry = owners.entry(paste_id).or_default();
+            for holder in holders {
+                entry.insert(holder.as_str().to_string(), PresenceState::Editing);
+            }
+        }
+        Ok(owners
+            .into_iter()
+            .map(|(paste_id, owners)| {
+                let mut entries: Vec<_> = owners
+                    .into_iter()
+                    .map(|(owner, state)| PresenceEntry { owner, state })
+                    .collect();
+                entries.sort_by_key(|entry| entry.state != PresenceState::Editing);
+                (paste_id, entries)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locks::LockOwnerId;
+
+    #[test]
+    fn holders_and_viewers_are_merged_with_editors_first() {
+        let changes = Arc::new(ChangeFeed::default());
+        let presence = PresenceTracker::new(changes.clone());
+        let locks = PasteLockManager::default();
+        let mut feed = changes.subscribe();
+
+        presence.touch("alpha", "web");
+        presence.touch("alpha", "web");
+        presence.touch("alpha", "gui-2");
+        locks
+            .acquire("alpha", &LockOwnerId::new("gui-2".to_string()))
+            .expect("acquire");
+
+        assert_eq!(
+            presence.list(&locks, "alpha").expect("list"),
+            vec![
+                PresenceEntry {
+                    owner: "gui-2".to_string(),
+                    state: PresenceState::Editing,
+                },
+                PresenceEntry {
+                    owner: "web".to_string(),
+                    state: PresenceState::Viewing,
+                },
+            ]
+        );
+        assert!(presence.leave("alpha", "web"));
+        assert!(!presence.leave("alpha", "web"));
+
+        // Renewing an existing viewer publishes nothing.
+        let published: Vec<_> = std::iter::from_fn(|| feed.try_recv().ok())
+            .map(|change| change.event.change)
+            .collect();
+        assert_eq!(published, vec![ChangeKind::Presence; 3]);
+    }
+}
@@ -0,0 +1,172 @@
+//! Per-client token-bucket limiting for API write requests.
+
+use crate::AppState;
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use localpaste_core::config::WriteRateLimit;
+use serde_json::json;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Tracked clients above which full (idle) buckets are dropped.
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// `POST` routes that only compute a response and store nothing.
+const READ_ONLY_POST_ROUTES: &[&str] = &["/api/validate", "/api/diff", "/api/equal"];
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Token buckets keyed by remote IP.
+///
+/// Requests without connection info (in-process test servers) share one
+/// bucket.
+#[derive(Debug)]
+pub struct WriteRateLimiter {
+    limit: Option<WriteRateLimit>,
+    buckets: Mutex<HashMap<Option<IpAddr>, Bucket>>,
+}
+
+impl WriteRateLimiter {
+    /// Construct a limiter; `None` lets every request through.
+    pub fn new(limit: Option<WriteRateLimit>) -> Self {
+        Self {
+            limit,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Construct a limiter from `LOCALPASTE_RATE_LIMIT_BURST` and
+    /// `LOCALPASTE_RATE_LIMIT_PER_SEC`.
+    pub fn from_env() -> Self {
+        Self::new(localpaste_core::config::write_rate_limit_from_env_or_default())
+    }
+
+    /// Take one token for `client`.
+    ///
+    /// # Returns
+    /// `Ok(())` when the write may proceed, otherwise how long until a token
+    /// is available.
+    pub fn check(&self, client: Option<IpAddr>, now: Instant) -> Result<(), Duration> {
+        let Some(limit) = self.limit else {
+            return Ok(());
+        };
+        let burst = f64::from(limit.burst);
+        let rate = f64::from(limit.per_second);
+        let mut buckets = self
+            .buckets
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if buckets.len() >= PRUNE_THRESHOLD && !buckets.contains_key(&client) {
+            buckets.retain(|_, bucket| {
+                let idle = now.saturating_duration_since(bucket.refilled_at);
+                bucket.tokens + idle.as_secs_f64() * rate < burst
+            });
+        }
+        let bucket = buckets.entry(client).or_insert(Bucket {
+            tokens: burst,
+            refilled_at: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(burst);
+        bucket.refilled_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+        Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+    }
+}
+
+fn is_write(method: &Method, path: &str) -> bool {
+    match *method {
+        Method::POST => !READ_ONLY_POST_ROUTES.contains(&path),
+        Method::PUT | Method::PATCH | Method::DELETE => true,
+        _ => false,
+    }
+}
+
+/// Reject API writes from clients that exceeded their rate limit.
+///
+/// Reads, including the read-only `POST` routes such as `/api/validate`,
+/// never spend tokens.
+///
+/// # Returns
+/// The downstream response, or `429 Too Many Requests` with a `Retry-After`
+/// header in whole seconds.
+pub async fn limit_writes(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    if !is_write(request.method(), request.uri().path()) {
+        return next.run(request).await;
+    }
+    let client = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    match state.rate_limiter.check(client, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => {
+            let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+            tracing::debug!(client = ?client, retry_after, "write rate limit exceeded");
+            let body = Json(json!({ "error": "Too many write requests; retry later" }));
+            let mut response = (StatusCode::TOO_MANY_REQUESTS, body).into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_allows_burst_then_refills_at_steady_rate() {
+        let limiter = WriteRateLimiter::new(Some(WriteRateLimit {
+            burst: 2,
+            per_second: 4,
+        }));
+        let client = Some(IpAddr::from([10, 0, 0, 1]));
+        let start = Instant::now();
+        assert!(limiter.check(client, start).is_ok());
+        assert!(limiter.check(client, start).is_ok());
+        let wait = limiter.check(client, start).expect_err("bucket empty");
+        assert_eq!(wait, Duration::from_millis(250));
+        assert!(limiter
+            .check(Some(IpAddr::from([10, 0, 0, 2])), start)
+            .is_ok());
+        assert!(limiter
+            .check(client, start + Duration::from_millis(250))
+            .is_ok());
+    }
+
+    #[test]
+    fn is_write_skips_reads_and_read_only_posts() {
+        assert!(is_write(&Method::POST, "/api/paste"));
+        assert!(is_write(&Method::DELETE, "/api/paste/abc"));
+        assert!(!is_write(&Method::GET, "/api/pastes"));
+        for path in READ_ONLY_POST_ROUTES {
+            assert!(!is_write(&Method::POST, path), "{path}");
+        }
+    }
+
+    #[test]
+    fn check_without_limit_always_allows() {
+        let limiter = WriteRateLimiter::new(None);
+        let now = Instant::now();
+        for _ in 0..1000 {
+            assert!(limiter.check(None, now).is_ok());
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Background check that warns when the database or its backups grow too large.
+
+use crate::AppState;
+use localpaste_core::storage_alerts::{
+    check_storage, StorageAlert, StorageAlertKind, STORAGE_CHECK_INTERVAL,
+};
+
+/// Log alerts that appeared since the last check and ones that cleared.
+///
+/// Persisting alerts are not repeated, so an oversized database warns once
+/// rather than every interval.
+///
+/// # Returns
+/// The kinds alerting now, to pass as `previous` next time.
+fn log_alert_changes(
+    previous: &[StorageAlertKind],
+    alerts: &[StorageAlert],
+) -> Vec<StorageAlertKind> {
+    for alert in alerts {
+        if !previous.contains(&alert.kind) {
+            tracing::warn!(
+                kind = ?alert.kind,
+                remediation = ?alert.remediation,
+                "{}",
+                alert.message
+            );
+        }
+    }
+    let current: Vec<_> = alerts.iter().map(|alert| alert.kind).collect();
+    for kind in previous {
+        if !current.contains(kind) {
+            tracing::info!(kind = ?kind, "storage alert cleared");
+        }
+    }
+    current
+}
+
+/// Run [`check_storage`] every [`STORAGE_CHECK_INTERVAL`] until aborted.
+pub(crate) fn spawn_storage_monitor(state: AppState) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(STORAGE_CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut alerting = Vec::new();
+        loop {
+            interval.tick().await;
+            let db_path = state.config.db_path.clone();
+            let limits = state.storage_limits;
+            let result = tokio::task::spawn_blocking(move || check_storage(&db_path, limits)).await;
+            match result {
+                Ok(Ok(alerts)) => alerting = log_alert_changes(&alerting, &alerts),
+                Ok(Err(err)) => tracing::warn!("Storage check failed: {}", err),
+                Err(err) => tracing::warn!("Storage check task failed: {}", err),
+            }
+        }
+    })
+}
@@ -0,0 +1,163 @@
+//! Tracing subscriber setup for the standalone server, with optional OTLP export.
+//!
+//! Console logs always use `RUST_LOG` (or the caller's default filter). When the
+//! crate is built with the `otlp` feature and `OTEL_EXPORTER_OTLP_ENDPOINT` or
+//! `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT` is set, spans are also exported over
+//! OTLP/HTTP (protobuf) with their own filter, so request and database spans
+//! reach a collector without making the console noisier.
+
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+/// Environment variables that enable OTLP export when set to a non-empty value.
+pub const OTLP_ENDPOINT_ENV_VARS: &[&str] = &[
+    "OTEL_EXPORTER_OTLP_ENDPOINT",
+    "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+];
+/// Overrides which spans are exported over OTLP.
+pub const OTLP_FILTER_ENV: &str = "LOCALPASTE_OTLP_FILTER";
+/// Default exported spans: HTTP request spans plus server and storage spans.
+pub const DEFAULT_OTLP_FILTER: &str =
+    "localpaste_server=debug,localpaste_core=debug,tower_http=debug";
+/// Service name reported when `OTEL_SERVICE_NAME` is unset.
+pub const DEFAULT_SERVICE_NAME: &str = "localpaste";
+
+/// Flushes exported spans when dropped; keep it alive for the life of the process.
+#[must_use = "dropping the guard stops span export"]
+#[derive(Default)]
+pub struct TelemetryGuard {
+    #[cfg(feature = "otlp")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl TelemetryGuard {
+    /// Whether spans are being exported over OTLP.
+    pub fn exporting(&self) -> bool {
+        #[cfg(feature = "otlp")]
+        {
+            self.provider.is_some()
+        }
+        #[cfg(not(feature = "otlp"))]
+        {
+            false
+        }
+    }
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otlp")]
+        if let Some(provider) = self.provider.take() {
+            if let Err(err) = provider.shutdown() {
+                eprintln!("failed to flush OTLP spans: {}", err);
+            }
+        }
+    }
+}
+
+/// Returns whether any OTLP endpoint environment variable is set.
+pub fn otlp_endpoint_configured() -> bool {
+    OTLP_ENDPOINT_ENV_VARS.iter().any(|name| {
+        std::env::var(name)
+            .map(|value| !value.trim().is_empty())
+            .unwrap_or(false)
+    })
+}
+
+#[cfg(feature = "otlp")]
+fn otlp_filter() -> EnvFilter {
+    std::env::var(OTLP_FILTER_ENV)
+        .ok()
+        .and_then(|raw| EnvFilter::try_new(raw).ok())
+        .unwrap_or_else(|| EnvFilter::new(DEFAULT_OTLP_FILTER))
+}
+
+#[cfg(feature = "otlp")]
+fn build_tracer_provider() -> Result<opentelemetry_sdk::trace::SdkTracerProvider, String> {
+    use opentelemetry_otlp::WithExportConfig;
+
+    // The exporter reads endpoint, headers, and timeout from the standard
+    // `OTEL_EXPORTER_OTLP_*` variables.
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_protocol(opentelemetry_otlp::Protocol::HttpBinary)
+        .build()
+        .map_err(|err| err.to_string())?;
+    let mut resource = opentelemetry_sdk::Resource::builder();
+    if std::env::var_os("OTEL_SERVICE_NAME").is_none() {
+        resource = resource.with_service_name(DEFAULT_SERVICE_NAME);
+    }
+    Ok(opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource.build())
+        .build())
+}
+
+/// Installs the global tracing subscriber.
+///
+/// # Arguments
+/// - `default_console_filter`: Console filter used when `RUST_LOG` is unset.
+///
+/// # Returns
+/// A guard that flushes exported spans on drop.
+///
+/// # Panics
+/// Panics if a global subscriber was already installed.
+pub fn init_tracing(default_console_filter: &str) -> TelemetryGuard {
+    let console_filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(default_console_filter));
+    let console = tracing_subscriber::fmt::layer().with_filter(console_filter);
+
+    #[cfg(feature = "otlp")]
+    {
+        use opentelemetry::trace::TracerProvider as _;
+
+        let provider = if otlp_endpoint_configured() {
+            match build_tracer_provider() {
+                Ok(provider) => Some(provider),
+                Err(err) => {
+                    eprintln!("OTLP export disabled: {}", err);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let otlp = provider.as_ref().map(|provider| {
+            tracing_opentelemetry::layer()
+                .with_tracer(provider.tracer("localpaste"))
+                .with_filter(otlp_filter())
+        });
+        tracing_subscriber::registry()
+            .with(console)
+            .with(otlp)
+            .init();
+        TelemetryGuard { provider }
+    }
+
+    #[cfg(not(feature = "otlp"))]
+    {
+        tracing_subscriber::registry().with(console).init();
+        if otlp_endpoint_configured() {
+            tracing::warn!(
+                "OTLP endpoint is set but this build lacks the `otlp` feature; spans are not exported"
+            );
+        }
+        TelemetryGuard::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use localpaste_core::env::{env_lock, EnvGuard};
+
+    #[test]
+    fn otlp_endpoint_configured_ignores_blank_values() {
+        let _lock = env_lock().lock().expect("env lock");
+        let _endpoint = EnvGuard::remove(OTLP_ENDPOINT_ENV_VARS[0]);
+        let _traces = EnvGuard::set(OTLP_ENDPOINT_ENV_VARS[1], " ");
+        assert!(!otlp_endpoint_configured());
+        let _endpoint = EnvGuard::set(OTLP_ENDPOINT_ENV_VARS[0], "http://localhost:4318");
+        assert!(otlp_endpoint_configured());
+    }
+}
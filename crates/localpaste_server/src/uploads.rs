@@ -0,0 +1,331 @@
+//! In-memory chunked upload sessions for content larger than one request body.
+
+use crate::models::paste::ChunkUploadStatus;
+use crate::AppError;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Idle sessions older than this are dropped when an upload begins or on cleanup.
+const CHUNK_UPLOAD_IDLE_TTL: Duration = Duration::from_secs(60 * 60);
+/// Open sessions allowed against one paste.
+const MAX_SESSIONS_PER_PASTE: usize = 4;
+/// Open sessions allowed across the whole server.
+const MAX_SESSIONS: usize = 64;
+/// Bytes all open sessions may buffer in memory together.
+const MAX_BUFFERED_BYTES: usize = 512 * 1024 * 1024;
+
+/// Caps on in-memory upload state, so clients cannot exhaust server memory by
+/// opening sessions and never committing them.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ChunkUploadLimits {
+    pub(crate) sessions_per_paste: usize,
+    pub(crate) sessions: usize,
+    pub(crate) buffered_bytes: usize,
+}
+
+impl Default for ChunkUploadLimits {
+    fn default() -> Self {
+        Self {
+            sessions_per_paste: MAX_SESSIONS_PER_PASTE,
+            sessions: MAX_SESSIONS,
+            buffered_bytes: MAX_BUFFERED_BYTES,
+        }
+    }
+}
+
+struct ChunkUploadSession {
+    paste_id: String,
+    buffer: Vec<u8>,
+    last_touched: Instant,
+}
+
+impl ChunkUploadSession {
+    fn status(&self, upload_id: &str) -> ChunkUploadStatus {
+        ChunkUploadStatus {
+            upload_id: upload_id.to_string(),
+            paste_id: self.paste_id.clone(),
+            received_bytes: self.buffer.len(),
+        }
+    }
+}
+
+/// Removes idle sessions and returns how many were dropped.
+fn retain_live_sessions(sessions: &mut HashMap<String, ChunkUploadSession>) -> usize {
+    let before = sessions.len();
+    sessions.retain(|_, session| session.last_touched.elapsed() < CHUNK_UPLOAD_IDLE_TTL);
+    before - sessions.len()
+}
+
+/// Tracks partially received chunked uploads keyed by upload id.
+///
+/// Sessions live only in memory; a server restart discards them and clients
+/// must begin a new upload. Session counts and buffered bytes are capped by
+/// [`ChunkUploadLimits`].
+#[derive(Default)]
+pub struct ChunkUploadManager {
+    sessions: Mutex<HashMap<String, ChunkUploadSession>>,
+    limits: ChunkUploadLimits,
+}
+
+impl ChunkUploadManager {
+    #[cfg(test)]
+    pub(crate) fn with_limits(limits: ChunkUploadLimits) -> Self {
+        Self {
+            sessions: Mutex::default(),
+            limits,
+        }
+    }
+
+    fn sessions(&self) -> Result<MutexGuard<'_, HashMap<String, ChunkUploadSession>>, AppError> {
+        self.sessions.lock().map_err(|_| AppError::Internal)
+    }
+
+    /// Number of uploads that have begun but not been finished or pruned.
+    ///
+    /// # Returns
+    /// Open session count, or `0` if session state is poisoned.
+    pub fn open_sessions(&self) -> usize {
+        self.sessions().map(|sessions| sessions.len()).unwrap_or(0)
+    }
+
+    /// Drop sessions that have been idle longer than the upload TTL.
+    ///
+    /// # Returns
+    /// Number of sessions dropped.
+    ///
+    /// # Errors
+    /// Returns [`AppError::Internal`] if session state is poisoned.
+    pub fn prune_idle(&self) -> Result<usize, AppError> {
+        let mut sessions = self.sessions()?;
+        Ok(retain_live_sessions(&mut sessions))
+    }
+
+    /// Start a new upload session targeting `paste_id`.
+    ///
+    /// # Returns
+    /// Status for the new, empty session.
+    ///
+    /// # Errors
+    /// Returns [`AppError::TooManyRequests`] when `paste_id` already has the
+    /// maximum open sessions, [`AppError::Unavailable`] when the server-wide
+    /// session cap is reached, and [`AppError::Internal`] if session state is
+    /// poisoned.
+    pub fn begin(&self, paste_id: &str) -> Result<ChunkUploadStatus, AppError> {
+        let mut sessions = self.sessions()?;
+        retain_live_sessions(&mut sessions);
+        let for_paste = sessions
+            .values()
+            .filter(|session| session.paste_id == paste_id)
+            .count();
+        if for_paste >= self.limits.sessions_per_paste {
+            return Err(AppError::TooManyRequests(format!(
+                "Paste already has {} open uploads; commit or wait for them to expire",
+                for_paste
+            )));
+        }
+        if sessions.len() >= self.limits.sessions {
+            return Err(AppError::Unavailable(
+                "Too many chunked uploads in progress; retry later".to_string(),
+            ));
+        }
+        let upload_id = uuid::Uuid::new_v4().to_string();
+        let session = ChunkUploadSession {
+            paste_id: paste_id.to_string(),
+            buffer: Vec::new(),
+            last_touched: Instant::now(),
+        };
+        let status = session.status(upload_id.as_str());
+        sessions.insert(upload_id, session);
+        Ok(status)
+    }
+
+    /// Report progress for an existing session.
+    ///
+    /// # Returns
+    /// Session status so clients can resume from `received_bytes`.
+    ///
+    /// # Errors
+    /// Returns [`AppError::NotFound`] when the session does not exist for `paste_id`.
+    pub fn status(&self, paste_id: &str, upload_id: &str) -> Result<ChunkUploadStatus, AppError> {
+        let sessions = self.sessions()?;
+        match sessions.get(upload_id) {
+            Some(session) if session.paste_id == paste_id => Ok(session.status(upload_id)),
+            _ => Err(AppError::NotFound),
+        }
+    }
+
+    /// Append `bytes` at `offset` to an existing session.
+    ///
+    /// # Arguments
+    /// - `paste_id`: Paste the session targets.
+    /// - `upload_id`: Session identifier returned by [`Self::begin`].
+    /// - `offset`: Expected current length; `None` appends at the end.
+    /// - `bytes`: Raw chunk payload.
+    /// - `max_bytes`: Size limit the assembled content must stay within.
+    ///
+    /// # Returns
+    /// Updated session status.
+    ///
+    /// # Errors
+    /// Returns [`AppError::NotFound`] for unknown sessions, [`AppError::BadRequest`]
+    /// when `offset` does not match the received length, and
+    /// [`AppError::BadRequest`] when the chunk would exceed `max_bytes`, and
+    /// [`AppError::Unavailable`] when open sessions would buffer more than the
+    /// server-wide byte cap.
+    pub fn append(
+        &self,
+        paste_id: &str,
+        upload_id: &str,
+        offset: Option<usize>,
+        bytes: &[u8],
+        max_bytes: usize,
+    ) -> Result<ChunkUploadStatus, AppError> {
+        let mut sessions = self.sessions()?;
+        let buffered: usize = sessions.values().map(|session| session.buffer.len()).sum();
+        let session = match sessions.get_mut(upload_id) {
+            Some(session) if session.paste_id == paste_id => session,
+            _ => return Err(AppError::NotFound),
+        };
+        let received = session.buffer.len();
+        if let Some(offset) = offset {
+            if offset != received {
+                return Err(AppError::BadRequest(format!(
+                    "Chunk offset {} does not match received bytes {}; resume from {}",
+                    offset, received, received
+                )));
+            }
+        }
+        if received.saturating_add(bytes.len()) > max_bytes {
+            return Err(AppError::BadRequest(format!(
+                "Paste size exceeds maximum of {} bytes",
+                max_bytes
+            )));
+        }
+        if buffered.saturating_add(bytes.len()) > self.limits.buffered_bytes {
+            return Err(AppError::Unavailable(
+                "Chunked uploads are buffering too much data; retry later".to_string(),
+            ));
+        }
+        session.buffer.extend_from_slice(bytes);
+        session.last_touched = Instant::now();
+        Ok(session.status(upload_id))
+    }
+
+    /// Remove a session and return its assembled bytes for commit.
+    ///
+    /// # Errors
+    /// Returns [`AppError::NotFound`] when the session does not exist for `paste_id`.
+    pub fn take(&self, paste_id: &str, upload_id: &str) -> Result<Vec<u8>, AppError> {
+        let mut sessions = self.sessions()?;
+        match sessions.get(upload_id) {
+            Some(session) if session.paste_id == paste_id => {}
+            _ => return Err(AppError::NotFound),
+        }
+        Ok(sessions
+            .remove(upload_id)
+            .map(|session| session.buffer)
+            .unwrap_or_default())
+    }
+
+    /// Reinstate a taken session after a retryable commit failure.
+    ///
+    /// # Errors
+    /// Returns [`AppError::Internal`] if session state is poisoned.
+    pub fn restore(
+        &self,
+        paste_id: &str,
+        upload_id: &str,
+        buffer: Vec<u8>,
+    ) -> Result<(), AppError> {
+        self.sessions()?.insert(
+            upload_id.to_string(),
+            ChunkUploadSession {
+                paste_id: paste_id.to_string(),
+                buffer,
+                last_touched: Instant::now(),
+            },
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChunkUploadLimits, ChunkUploadManager};
+    use crate::AppError;
+
+    #[test]
+    fn append_enforces_offsets_limits_and_paste_scope() {
+        let uploads = ChunkUploadManager::default();
+        let status = uploads.begin("paste-a").expect("begin");
+        let upload_id = status.upload_id.as_str();
+
+        let status = uploads
+            .append("paste-a", upload_id, Some(0), b"hello ", 16)
+            .expect("first chunk");
+        assert_eq!(status.received_bytes, 6);
+
+        let err = uploads
+            .append("paste-a", upload_id, Some(0), b"hello ", 16)
+            .expect_err("replayed chunk should be rejected");
+        assert!(matches!(err, AppError::BadRequest(message) if message.contains("resume from 6")));
+
+        let err = uploads
+            .append("paste-b", upload_id, None, b"x", 16)
+            .expect_err("other paste cannot use the session");
+        assert!(matches!(err, AppError::NotFound));
+
+        let err = uploads
+            .append("paste-a", upload_id, None, b"world, too long", 16)
+            .expect_err("limit should apply to assembled content");
+        assert!(matches!(err, AppError::BadRequest(_)));
+
+        uploads
+            .append("paste-a", upload_id, Some(6), b"world", 16)
+            .expect("resumed chunk");
+        let bytes = uploads.take("paste-a", upload_id).expect("take");
+        assert_eq!(bytes, b"hello world");
+        assert!(matches!(
+            uploads.status("paste-a", upload_id),
+            Err(AppError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn begin_and_append_enforce_session_and_buffer_caps() {
+        let uploads = ChunkUploadManager::with_limits(ChunkUploadLimits {
+            sessions_per_paste: 2,
+            sessions: 3,
+            buffered_bytes: 8,
+        });
+        let first = uploads.begin("paste-a").expect("first session");
+        uploads.begin("paste-a").expect("second session");
+        assert!(matches!(
+            uploads.begin("paste-a"),
+            Err(AppError::TooManyRequests(_))
+        ));
+
+        let other = uploads.begin("paste-b").expect("other paste");
+        assert!(matches!(
+            uploads.begin("paste-c"),
+            Err(AppError::Unavailable(_))
+        ));
+
+        uploads
+            .append("paste-a", first.upload_id.as_str(), None, b"12345", 16)
+            .expect("within buffer cap");
+        let err = uploads
+            .append("paste-b", other.upload_id.as_str(), None, b"6789", 16)
+            .expect_err("buffer cap spans sessions");
+        assert!(matches!(err, AppError::Unavailable(_)));
+
+        uploads
+            .take("paste-a", first.upload_id.as_str())
+            .expect("take frees a slot");
+        uploads.begin("paste-c").expect("slot reused after take");
+        uploads
+            .append("paste-b", other.upload_id.as_str(), None, b"6789", 16)
+            .expect("taken bytes no longer count");
+    }
+}
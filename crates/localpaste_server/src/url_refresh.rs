@@ -0,0 +1,109 @@
+//! Re-fetching pastes from their source URLs, on request and on schedule.
+
+use crate::events::{paste_changed, ChangeKind};
+use crate::{AppError, AppState};
+use chrono::{DateTime, Utc};
+use localpaste_core::models::url_source::UrlRefreshResponse;
+use localpaste_core::url_fetch::{apply_source_bytes, fetch_source_bytes};
+use localpaste_core::PasteSource;
+use std::time::Duration;
+
+/// How often the server looks for sources due a scheduled refresh.
+pub const URL_REFRESH_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Fetch a linked paste's source URL and store the result.
+///
+/// The download runs without the paste's edit lock; the lock is taken only
+/// to write the new content. Failed fetches are recorded on the source so
+/// the schedule moves on instead of retrying every sweep.
+///
+/// # Returns
+/// Whether the content changed, and the updated source link.
+///
+/// # Errors
+/// Returns `NotFound` when the paste has no source link, `Locked` while the
+/// paste is open for editing, `PayloadTooLarge` above the paste size limit,
+/// and `Upstream` when the fetch fails.
+pub fn refresh_from_source(
+    state: &AppState,
+    paste_id: &str,
+    now: DateTime<Utc>,
+) -> Result<UrlRefreshResponse, AppError> {
+    let source = state
+        .db
+        .url_sources
+        .get(paste_id)?
+        .ok_or(AppError::NotFound)?;
+    let folder_id = crate::handlers::paste::current_folder_for_size_limit(state, paste_id)?;
+    let max_bytes = state
+        .config
+        .max_paste_size_for(PasteSource::Api, folder_id.as_deref());
+    let bytes = match fetch_source_bytes(source.url.as_str(), max_bytes) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            state
+                .db
+                .url_sources
+                .record_check(paste_id, now, false, Some(&err.to_string()))?;
+            return Err(err);
+        }
+    };
+    let _mutation_guard = crate::locks::acquire_paste_mutation_guard(
+        state.locks.as_ref(),
+        paste_id,
+        "Paste is currently open for editing.",
+        None,
+    )?;
+    let (_, response) = apply_source_bytes(&state.db, paste_id, bytes, now)?;
+    if response.changed {
+        paste_changed(state, paste_id, ChangeKind::Updated);
+    }
+    Ok(response)
+}
+
+/// Refresh every source whose schedule is due at `now`.
+///
+/// Pastes open for editing are skipped and retried on the next sweep;
+/// fetch failures are logged and recorded on the source.
+///
+/// # Returns
+/// Number of pastes whose content changed.
+///
+/// # Errors
+/// Returns an error when listing due sources fails.
+pub fn sweep_due_sources(state: &AppState, now: DateTime<Utc>) -> Result<usize, AppError> {
+    let mut changed = 0;
+    for source in state.db.url_sources.due(now)? {
+        let id = source.paste_id.as_str();
+        match refresh_from_source(state, id, now) {
+            Ok(response) => changed += usize::from(response.changed),
+            Err(AppError::Locked(_)) => {
+                tracing::debug!(id = %id, "source refresh skipped while paste is open for editing");
+            }
+            Err(err) => {
+                tracing::warn!(id = %id, url = %source.url, "Source refresh failed: {}", err)
+            }
+        }
+    }
+    Ok(changed)
+}
+
+/// Run [`sweep_due_sources`] every [`URL_REFRESH_SWEEP_INTERVAL`] until aborted.
+pub(crate) fn spawn_url_refresh(state: AppState) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(URL_REFRESH_SWEEP_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let state = state.clone();
+            let result =
+                tokio::task::spawn_blocking(move || sweep_due_sources(&state, Utc::now())).await;
+            match result {
+                Ok(Ok(0)) => {}
+                Ok(Ok(count)) => tracing::info!("Refreshed {} paste(s) from source URLs", count),
+                Ok(Err(err)) => tracing::warn!("Source refresh sweep failed: {}", err),
+                Err(err) => tracing::warn!("Source refresh sweep task failed: {}", err),
+            }
+        }
+    })
+}
@@ -0,0 +1,48 @@
+//! Integration tests for the on-demand cleanup endpoint.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_admin_cleanup_reports_without_touching_live_data() {
+    let (server, _temp, _locks) = setup_test_server();
+    let folder = server
+        .post("/api/folder")
+        .json(&json!({ "name": "work" }))
+        .await
+        .json::<Value>();
+    let folder_id = folder["id"].as_str().unwrap();
+    let paste = server
+        .post("/api/paste")
+        .json(&json!({ "content": "kept", "folder_id": folder_id }))
+        .await
+        .json::<Value>();
+    let paste_id = paste["id"].as_str().unwrap();
+    let upload = server
+        .post(&format!("/api/paste/{}/chunks", paste_id))
+        .await
+        .json::<Value>();
+    let upload_id = upload["upload_id"].as_str().unwrap();
+
+    let response = server.post("/api/admin/cleanup").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let report: Value = response.json();
+    assert_eq!(report["orphan_drafts_removed"], 0);
+    assert_eq!(report["folders_reconciled"], 1);
+    // Fresh upload sessions are not idle yet, and compaction is offline-only.
+    assert_eq!(report["stale_uploads_dropped"], 0);
+    assert_eq!(report["compacted"], false);
+
+    let folder = server
+        .get(&format!("/api/folder/{}", folder_id))
+        .await
+        .json::<Value>();
+    assert_eq!(folder["paste_count"], 1);
+    server
+        .get(&format!("/api/paste/{}/chunks/{}", paste_id, upload_id))
+        .await
+        .assert_status_ok();
+}
@@ -0,0 +1,56 @@
+//! Integration tests for line-anchored paste annotations.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_annotations_follow_edits_and_appear_in_exports() {
+    let (server, _temp, _locks) = setup_test_server();
+    let paste: serde_json::Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "a\nb\nc\n", "name": "notes.txt" }))
+        .await
+        .json();
+    let paste_path = format!("/api/paste/{}", paste["id"].as_str().unwrap());
+    let annotations_path = format!("{}/annotations", paste_path);
+
+    server
+        .post(&annotations_path)
+        .json(&json!({ "start_line": 3, "end_line": 9, "body": "past the end" }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    let created = server
+        .post(&annotations_path)
+        .json(&json!({ "start_line": 2, "author": "ana", "body": "Why b?" }))
+        .await;
+    assert_eq!(created.status_code(), StatusCode::OK);
+    let created: serde_json::Value = created.json();
+    assert_eq!(created["end_line"], 2);
+
+    server
+        .put(&paste_path)
+        .json(&json!({ "content": "intro\na\nb\nc\n" }))
+        .await
+        .assert_status_ok();
+    let listed: Vec<serde_json::Value> = server.get(&annotations_path).await.json();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0]["start_line"], 3);
+    assert_eq!(listed[0]["detached"], false);
+
+    let html = server
+        .get(&format!("{}/export?format=html", paste_path))
+        .await
+        .text();
+    assert!(html.contains("<ol class=\"lp-notes\">"));
+    assert!(html.contains("<strong>line 3</strong> (ana): Why b?"));
+
+    let annotation_path = format!("{}/{}", annotations_path, created["id"].as_str().unwrap());
+    server.delete(&annotation_path).await.assert_status_ok();
+    server
+        .delete(&annotation_path)
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
@@ -499,6 +499,9 @@ async fn test_max_paste_size_allows_exact_content_limit_with_json_overhead() {
         max_paste_size: 20_000,
         auto_save_interval: 2000,
         auto_backup: false,
+        size_limits: Default::default(),
+        flush_policy: Default::default(),
+        recent_history: false,
     };
     let (server, _locks) = test_server_for_config(config);
 
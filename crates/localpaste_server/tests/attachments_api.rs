@@ -0,0 +1,112 @@
+//! Integration tests for binary attachments under `/api/paste/:id/attachments`.
+
+mod support;
+
+use axum::http::StatusCode;
+use axum_test::multipart::{MultipartForm, Part};
+use localpaste_core::models::attachment::MAX_ATTACHMENT_BYTES;
+use serde_json::{json, Value};
+use support::setup_test_server;
+
+const PNG_BYTES: &[u8] = &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0xff];
+
+#[tokio::test]
+async fn test_attachments_upload_list_download_and_delete() {
+    let (server, _temp, _locks) = setup_test_server();
+    let paste: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "crash report" }))
+        .await
+        .json();
+    let paste_id = paste["id"].as_str().unwrap();
+    let base = format!("/api/paste/{}/attachments", paste_id);
+
+    let response = server
+        .post(&base)
+        .multipart(
+            MultipartForm::new().add_part(
+                "file",
+                Part::bytes(PNG_BYTES.to_vec())
+                    .file_name("C:\\shots\\screen shot.png")
+                    .mime_type("image/png"),
+            ),
+        )
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let attachment: Value = response.json();
+    assert_eq!(attachment["file_name"], "screen shot.png");
+    assert_eq!(attachment["content_type"], "image/png");
+    assert_eq!(attachment["size"], PNG_BYTES.len());
+    let attachment_id = attachment["id"].as_str().unwrap();
+
+    let listed: Vec<Value> = server.get(&base).await.json();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0]["id"], attachment_id);
+
+    let download = server.get(&format!("{}/{}", base, attachment_id)).await;
+    assert_eq!(download.status_code(), StatusCode::OK);
+    assert_eq!(download.header("content-type"), "image/png");
+    assert_eq!(
+        download.header("content-disposition"),
+        "attachment; filename=\"screen_shot.png\""
+    );
+    assert_eq!(download.as_bytes().as_ref(), PNG_BYTES);
+
+    server
+        .delete(&format!("{}/{}", base, attachment_id))
+        .await
+        .assert_status_ok();
+    let missing = server.get(&format!("{}/{}", base, attachment_id)).await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_attachments_reject_bad_uploads_and_follow_the_paste() {
+    let (server, _temp, _locks) = setup_test_server();
+    let paste: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "logs" }))
+        .await
+        .json();
+    let paste_id = paste["id"].as_str().unwrap();
+    let base = format!("/api/paste/{}/attachments", paste_id);
+
+    let no_file = server
+        .post(&base)
+        .multipart(MultipartForm::new().add_text("note", "hi"))
+        .await;
+    assert_eq!(no_file.status_code(), StatusCode::BAD_REQUEST);
+
+    let too_large = server
+        .post(&base)
+        .multipart(MultipartForm::new().add_part(
+            "file",
+            Part::bytes(vec![0u8; MAX_ATTACHMENT_BYTES + 1]).file_name("big.bin"),
+        ))
+        .await;
+    assert_eq!(too_large.status_code(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let unknown_paste = server
+        .post("/api/paste/missing/attachments")
+        .multipart(MultipartForm::new().add_part("file", Part::bytes(vec![1]).file_name("a.bin")))
+        .await;
+    assert_eq!(unknown_paste.status_code(), StatusCode::NOT_FOUND);
+
+    let stored: Value = server
+        .post(&base)
+        .multipart(
+            MultipartForm::new().add_part("file", Part::bytes(vec![1, 2, 3]).file_name("a.bin")),
+        )
+        .await
+        .json();
+    assert_eq!(stored["content_type"], "application/octet-stream");
+
+    server
+        .delete(&format!("/api/paste/{}", paste_id))
+        .await
+        .assert_status_ok();
+    let gone = server
+        .get(&format!("{}/{}", base, stored["id"].as_str().unwrap()))
+        .await;
+    assert_eq!(gone.status_code(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,53 @@
+//! Integration tests for batch paste creation.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_batch_create_returns_pastes_in_order() {
+    let (server, _temp, _locks) = setup_test_server();
+    let response = server
+        .post("/api/pastes/batch")
+        .json(&json!([
+            { "content": "fn main() {}", "name": "first" },
+            { "content": "second body", "name": "second", "tags": ["snippet"] },
+        ]))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let created: Vec<serde_json::Value> = response.json();
+    assert_eq!(created.len(), 2);
+    assert_eq!(created[0]["name"], "first");
+    assert_eq!(created[1]["name"], "second");
+    assert_eq!(created[1]["tags"], json!(["snippet"]));
+
+    let count: serde_json::Value = server.get("/api/pastes/count").await.json();
+    assert_eq!(count["count"], 2);
+}
+
+#[tokio::test]
+async fn test_batch_create_rejects_whole_batch_on_invalid_item() {
+    let (server, _temp, _locks) = setup_test_server();
+    let response = server
+        .post("/api/pastes/batch")
+        .json(&json!([
+            { "content": "fine" },
+            { "content": "orphan", "folder_id": "missing-folder" },
+        ]))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    let dedupe = server
+        .post("/api/pastes/batch")
+        .json(&json!([{ "content": "fine", "dedupe": true }]))
+        .await;
+    assert_eq!(dedupe.status_code(), StatusCode::BAD_REQUEST);
+
+    let empty = server.post("/api/pastes/batch").json(&json!([])).await;
+    assert_eq!(empty.status_code(), StatusCode::BAD_REQUEST);
+
+    let count: serde_json::Value = server.get("/api/pastes/count").await.json();
+    assert_eq!(count["count"], 0);
+}
@@ -0,0 +1,157 @@
+//! Integration tests for one-time `burn_after_read` pastes.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_first_get_returns_and_deletes_burn_after_read_paste() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let created: serde_json::Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "s3cr3t", "burn_after_read": true }))
+        .await
+        .json();
+    assert_eq!(created["burn_after_read"], true);
+    let id = created["id"].as_str().expect("id").to_string();
+
+    assert_eq!(
+        server
+            .method(axum::http::Method::HEAD, &format!("/api/paste/{}", id))
+            .await
+            .status_code(),
+        StatusCode::OK
+    );
+
+    let first = server.get(&format!("/api/paste/{}", id)).await;
+    assert_eq!(first.status_code(), StatusCode::OK);
+    let first: serde_json::Value = first.json();
+    assert_eq!(first["content"], "s3cr3t");
+
+    let second = server.get(&format!("/api/paste/{}", id)).await;
+    assert_eq!(second.status_code(), StatusCode::NOT_FOUND);
+    let listed: Vec<serde_json::Value> = server.get("/api/pastes").await.json();
+    assert!(listed.iter().all(|row| row["id"] != id));
+}
+
+#[tokio::test]
+async fn test_regular_pastes_survive_reads_and_never_dedupe_to_burn_pastes() {
+    let (server, _temp, locks) = setup_test_server();
+
+    let kept: serde_json::Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "shared body" }))
+        .await
+        .json();
+    assert_eq!(kept["burn_after_read"], false);
+    let kept_id = kept["id"].as_str().expect("id").to_string();
+    for _ in 0..2 {
+        assert_eq!(
+            server
+                .get(&format!("/api/paste/{}", kept_id))
+                .await
+                .status_code(),
+            StatusCode::OK
+        );
+    }
+
+    let burn = server
+        .post("/api/paste")
+        .json(&json!({ "content": "shared body", "dedupe": true, "burn_after_read": true }))
+        .await;
+    assert!(burn.maybe_header("x-localpaste-deduplicated").is_none());
+    let burn: serde_json::Value = burn.json();
+    let burn_id = burn["id"].as_str().expect("id").to_string();
+    assert_ne!(burn_id, kept_id);
+
+    let owner = localpaste_server::LockOwnerId::new("gui");
+    locks.acquire(&burn_id, &owner).expect("lock");
+    assert_eq!(
+        server
+            .get(&format!("/api/paste/{}", burn_id))
+            .await
+            .status_code(),
+        StatusCode::LOCKED
+    );
+    locks.release(&burn_id, &owner).expect("unlock");
+    assert_eq!(
+        server
+            .get(&format!("/api/paste/{}", burn_id))
+            .await
+            .status_code(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        server
+            .get(&format!("/api/paste/{}", burn_id))
+            .await
+            .status_code(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn test_raw_read_burns_and_derived_routes_never_reveal_burn_pastes() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let created: serde_json::Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "raw s3cr3t", "burn_after_read": true }))
+        .await
+        .json();
+    let id = created["id"].as_str().expect("id").to_string();
+
+    for route in ["preview", "lint", "versions"] {
+        assert_eq!(
+            server
+                .get(&format!("/api/paste/{}/{}", id, route))
+                .await
+                .status_code(),
+            StatusCode::NOT_FOUND,
+            "{route} must not reveal a one-time paste"
+        );
+    }
+
+    let raw = server.get(&format!("/raw/{}", id)).await;
+    assert_eq!(raw.status_code(), StatusCode::OK);
+    assert_eq!(raw.text(), "raw s3cr3t");
+
+    for path in [
+        format!("/raw/{}", id),
+        format!("/api/paste/{}", id),
+        format!("/api/paste/{}/raw", id),
+        format!("/api/paste/{}/html", id),
+    ] {
+        assert_eq!(
+            server.get(&path).await.status_code(),
+            StatusCode::NOT_FOUND,
+            "{path} after burn"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_raw_conditional_and_range_reads_send_whole_burn_paste() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    for (name, value) in [("if-none-match", "*"), ("range", "bytes=0-0")] {
+        let created: serde_json::Value = server
+            .post("/api/paste")
+            .json(&json!({ "content": "whole s3cr3t", "burn_after_read": true }))
+            .await
+            .json();
+        let id = created["id"].as_str().expect("id").to_string();
+
+        let raw = server
+            .get(&format!("/raw/{}", id))
+            .add_header(name, value)
+            .await;
+        assert_eq!(raw.status_code(), StatusCode::OK, "{name}");
+        assert_eq!(raw.text(), "whole s3cr3t", "{name}");
+        assert_eq!(raw.header("accept-ranges"), "none");
+        assert!(raw.maybe_header("content-range").is_none());
+    }
+}
@@ -0,0 +1,67 @@
+//! Integration tests for chunked paste uploads.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_chunked_upload_appends_resumes_and_commits() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let created = server
+        .post("/api/paste")
+        .json(&json!({ "content": "", "name": "chunked" }))
+        .await;
+    assert_eq!(created.status_code(), StatusCode::OK);
+    let created: serde_json::Value = created.json();
+    let paste_id = created["id"].as_str().unwrap();
+
+    let begin = server
+        .post(&format!("/api/paste/{}/chunks", paste_id))
+        .await;
+    assert_eq!(begin.status_code(), StatusCode::OK);
+    let begin: serde_json::Value = begin.json();
+    assert_eq!(begin["received_bytes"], 0);
+    let session_path = format!(
+        "/api/paste/{}/chunks/{}",
+        paste_id,
+        begin["upload_id"].as_str().unwrap()
+    );
+
+    let first = server
+        .post(&format!("{}?offset=0", session_path))
+        .text("fn main() {\n")
+        .await;
+    assert_eq!(first.status_code(), StatusCode::OK);
+    let first: serde_json::Value = first.json();
+    assert_eq!(first["received_bytes"], 12);
+
+    // A replayed chunk is rejected; the client resumes from the reported offset.
+    let replay = server
+        .post(&format!("{}?offset=0", session_path))
+        .text("fn main() {\n")
+        .await;
+    assert_eq!(replay.status_code(), StatusCode::BAD_REQUEST);
+    let status: serde_json::Value = server.get(&session_path).await.json();
+    assert_eq!(status["received_bytes"], 12);
+
+    let second = server
+        .post(&format!("{}?offset=12", session_path))
+        .text("}\n")
+        .await;
+    assert_eq!(second.status_code(), StatusCode::OK);
+
+    let commit = server.post(&format!("{}/commit", session_path)).await;
+    assert_eq!(commit.status_code(), StatusCode::OK);
+    let committed: serde_json::Value = commit.json();
+    assert_eq!(committed["id"], paste_id);
+    assert_eq!(committed["content"], "fn main() {\n}\n");
+
+    let stale = server.get(&session_path).await;
+    assert_eq!(stale.status_code(), StatusCode::NOT_FOUND);
+
+    let missing = server.post("/api/paste/does-not-exist/chunks").await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
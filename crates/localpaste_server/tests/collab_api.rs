@@ -0,0 +1,156 @@
+//! Integration tests for collaborative edits under `/api/paste/:id/edits`.
+
+mod support;
+
+use axum::http::StatusCode;
+use localpaste_server::LockOwnerId;
+use serde_json::json;
+use support::setup_test_server;
+
+async fn create(server: &axum_test::TestServer, content: &str) -> String {
+    let response = server
+        .post("/api/paste")
+        .json(&json!({ "content": content }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let created: serde_json::Value = response.json();
+    created["id"].as_str().expect("id").to_string()
+}
+
+async fn post_edit(
+    server: &axum_test::TestServer,
+    id: &str,
+    edit: serde_json::Value,
+) -> axum_test::TestResponse {
+    server
+        .post(&format!("/api/paste/{}/edits", id))
+        .json(&edit)
+        .await
+}
+
+#[tokio::test]
+async fn test_concurrent_edits_merge_while_another_editor_holds_the_lock() {
+    let (server, _temp, locks) = setup_test_server();
+    let id = create(&server, "alpha beta gamma").await;
+    let gui = LockOwnerId::new("gui-editor".to_string());
+    locks.acquire(&id, &gui).expect("gui opens paste");
+
+    // A plain PUT still waits on the edit lock.
+    let blocked = server
+        .put(&format!("/api/paste/{}", id))
+        .json(&json!({ "content": "overwrite" }))
+        .await;
+    assert_eq!(blocked.status_code(), StatusCode::LOCKED);
+
+    let body: serde_json::Value = server.get(&format!("/api/paste/{}/edits", id)).await.json();
+    assert_eq!(body["rev"], 0);
+    assert_eq!(body["gap"], false);
+
+    // Two editors both start from revision 0 and touch different words.
+    let first = post_edit(
+        &server,
+        &id,
+        json!({ "base_rev": 0, "start": 0, "end": 5, "text": "ALPHA!" }),
+    )
+    .await;
+    assert_eq!(first.status_code(), StatusCode::OK);
+    let first: serde_json::Value = first.json();
+    assert_eq!(first["rev"], 1);
+    assert_eq!(first["conflicted"], false);
+
+    let second = post_edit(
+        &server,
+        &id,
+        json!({ "base_rev": 0, "start": 11, "end": 16, "text": "GAMMA" }),
+    )
+    .await;
+    assert_eq!(second.status_code(), StatusCode::OK);
+    let second: serde_json::Value = second.json();
+    assert_eq!(second["rev"], 2);
+    assert_eq!(second["conflicted"], false);
+    assert_eq!(second["paste"]["content"], "ALPHA! beta GAMMA");
+
+    let since: serde_json::Value = server
+        .get(&format!("/api/paste/{}/edits?since=1", id))
+        .await
+        .json();
+    assert_eq!(since["rev"], 2);
+    assert_eq!(since["edits"].as_array().expect("edits").len(), 1);
+    assert_eq!(since["edits"][0]["rev"], 2);
+    assert_eq!(since["edits"][0]["text"], "GAMMA");
+
+    locks.release(&id, &gui).expect("gui closes paste");
+}
+
+#[tokio::test]
+async fn test_overlapping_edit_wins_and_reports_conflict() {
+    let (server, _temp, _locks) = setup_test_server();
+    let id = create(&server, "alpha beta gamma").await;
+
+    let first = post_edit(
+        &server,
+        &id,
+        json!({ "base_rev": 0, "start": 6, "end": 10, "text": "BETA" }),
+    )
+    .await;
+    assert_eq!(first.status_code(), StatusCode::OK);
+
+    let second = post_edit(
+        &server,
+        &id,
+        json!({ "base_rev": 0, "start": 8, "end": 13, "text": "x" }),
+    )
+    .await;
+    assert_eq!(second.status_code(), StatusCode::OK);
+    let second: serde_json::Value = second.json();
+    assert_eq!(second["conflicted"], true);
+    assert_eq!(second["paste"]["content"], "alpha xmma");
+}
+
+#[tokio::test]
+async fn test_edits_are_rejected_after_a_direct_write_or_out_of_range() {
+    let (server, _temp, _locks) = setup_test_server();
+    let id = create(&server, "one two").await;
+
+    let out_of_range = post_edit(
+        &server,
+        &id,
+        json!({ "base_rev": 0, "start": 5, "end": 50, "text": "" }),
+    )
+    .await;
+    assert_eq!(out_of_range.status_code(), StatusCode::BAD_REQUEST);
+
+    let put = server
+        .put(&format!("/api/paste/{}", id))
+        .json(&json!({ "content": "rewritten" }))
+        .await;
+    assert_eq!(put.status_code(), StatusCode::OK);
+
+    let stale = post_edit(
+        &server,
+        &id,
+        json!({ "base_rev": 0, "start": 0, "end": 0, "text": ">" }),
+    )
+    .await;
+    assert_eq!(stale.status_code(), StatusCode::CONFLICT);
+
+    let body: serde_json::Value = server
+        .get(&format!("/api/paste/{}/edits?since=0", id))
+        .await
+        .json();
+    assert_eq!(body["gap"], true);
+    let rev = body["rev"].as_u64().expect("rev");
+
+    let fresh = post_edit(
+        &server,
+        &id,
+        json!({ "base_rev": rev, "start": 0, "end": 0, "text": ">" }),
+    )
+    .await;
+    assert_eq!(fresh.status_code(), StatusCode::OK);
+    let fresh: serde_json::Value = fresh.json();
+    assert_eq!(fresh["paste"]["content"], ">rewritten");
+
+    let missing = server.get("/api/paste/does-not-exist/edits").await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,75 @@
+//! Integration tests for paste comment threads.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_comment_thread_crud_and_cleanup_on_paste_delete() {
+    let (server, _temp, _locks) = setup_test_server();
+    let paste: serde_json::Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "fn main() {}", "name": "main.rs" }))
+        .await
+        .json();
+    let paste_id = paste["id"].as_str().unwrap();
+    let comments_path = format!("/api/paste/{}/comments", paste_id);
+
+    let created = server
+        .post(&comments_path)
+        .json(&json!({ "author": "ana", "body": "Can this return a Result?" }))
+        .await;
+    assert_eq!(created.status_code(), StatusCode::OK);
+    let created: serde_json::Value = created.json();
+    assert_eq!(created["author"], "ana");
+    assert_eq!(created["paste_id"], paste_id);
+    let comment_path = format!("{}/{}", comments_path, created["id"].as_str().unwrap());
+
+    server
+        .post(&comments_path)
+        .json(&json!({ "body": "  " }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    server
+        .post("/api/paste/missing/comments")
+        .json(&json!({ "body": "hello" }))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    let updated: serde_json::Value = server
+        .put(&comment_path)
+        .json(&json!({ "body": "Should this return a Result?" }))
+        .await
+        .json();
+    assert_eq!(updated["body"], "Should this return a Result?");
+    server
+        .post(&comments_path)
+        .json(&json!({ "body": "Yes, done." }))
+        .await
+        .assert_status_ok();
+
+    let thread: Vec<serde_json::Value> = server.get(&comments_path).await.json();
+    let bodies: Vec<&str> = thread
+        .iter()
+        .map(|comment| comment["body"].as_str().unwrap())
+        .collect();
+    assert_eq!(bodies, vec!["Should this return a Result?", "Yes, done."]);
+    assert_eq!(thread[1]["author"], "anonymous");
+
+    server.delete(&comment_path).await.assert_status_ok();
+    server
+        .delete(&comment_path)
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    server
+        .delete(&format!("/api/paste/{}", paste_id))
+        .await
+        .assert_status_ok();
+    server
+        .get(&comments_path)
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
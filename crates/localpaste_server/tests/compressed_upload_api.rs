@@ -0,0 +1,195 @@
+//! Integration tests for gzip- and zstd-encoded request bodies.
+
+mod support;
+
+use axum::http::{header, HeaderValue, StatusCode};
+use flate2::{write::GzEncoder, Compression};
+use serde_json::json;
+use std::io::Write;
+use support::{setup_test_server, test_config_for_db_path, test_server_for_config};
+use tempfile::TempDir;
+
+fn gzip_bytes(bytes: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+    encoder.write_all(bytes).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn gzip_json(value: &serde_json::Value) -> Vec<u8> {
+    gzip_bytes(serde_json::to_vec(value).unwrap().as_slice())
+}
+
+fn zstd_json(value: &serde_json::Value) -> Vec<u8> {
+    zstd::encode_all(serde_json::to_vec(value).unwrap().as_slice(), 0).unwrap()
+}
+
+#[tokio::test]
+async fn test_gzip_bodies_are_accepted_on_create_and_update() {
+    let (server, _temp, _locks) = setup_test_server();
+    let content = "line of text\n".repeat(1000);
+
+    let created = server
+        .post("/api/paste")
+        .add_header(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        )
+        .add_header(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"))
+        .bytes(gzip_json(&json!({ "content": content, "name": "zipped" })).into())
+        .await;
+    assert_eq!(created.status_code(), StatusCode::OK);
+    let created: serde_json::Value = created.json();
+    assert_eq!(created["content"].as_str(), Some(content.as_str()));
+
+    let path = format!("/api/paste/{}", created["id"].as_str().unwrap());
+    let updated = server
+        .put(&path)
+        .add_header(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        )
+        .add_header(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"))
+        .bytes(gzip_json(&json!({ "content": "replaced" })).into())
+        .await;
+    assert_eq!(updated.status_code(), StatusCode::OK);
+    let updated: serde_json::Value = updated.json();
+    assert_eq!(updated["content"], "replaced");
+
+    let brotli = server
+        .post("/api/paste")
+        .add_header(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        )
+        .add_header(header::CONTENT_ENCODING, HeaderValue::from_static("br"))
+        .bytes(b"not brotli".to_vec().into())
+        .await;
+    assert_eq!(brotli.status_code(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[tokio::test]
+async fn test_zstd_bodies_are_accepted_on_create_update_and_chunks() {
+    let (server, _temp, _locks) = setup_test_server();
+    let content = "2024-01-01 INFO request handled\n".repeat(1000);
+
+    let created = server
+        .post("/api/paste")
+        .add_header(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        )
+        .add_header(header::CONTENT_ENCODING, HeaderValue::from_static("zstd"))
+        .bytes(zstd_json(&json!({ "content": content, "name": "server.log" })).into())
+        .await;
+    assert_eq!(created.status_code(), StatusCode::OK);
+    let created: serde_json::Value = created.json();
+    assert_eq!(created["content"].as_str(), Some(content.as_str()));
+
+    let id = created["id"].as_str().unwrap();
+    let updated = server
+        .put(&format!("/api/paste/{}", id))
+        .add_header(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        )
+        .add_header(header::CONTENT_ENCODING, HeaderValue::from_static("zstd"))
+        .bytes(zstd_json(&json!({ "content": "replaced" })).into())
+        .await;
+    assert_eq!(updated.status_code(), StatusCode::OK);
+    let updated: serde_json::Value = updated.json();
+    assert_eq!(updated["content"], "replaced");
+
+    let begin: serde_json::Value = server
+        .post(&format!("/api/paste/{}/chunks", id))
+        .await
+        .json();
+    let session_path = format!(
+        "/api/paste/{}/chunks/{}",
+        id,
+        begin["upload_id"].as_str().unwrap()
+    );
+    let appended = server
+        .post(&format!("{}?offset=0", session_path))
+        .add_header(header::CONTENT_ENCODING, HeaderValue::from_static("zstd"))
+        .bytes(zstd::encode_all(content.as_bytes(), 0).unwrap().into())
+        .await;
+    assert_eq!(appended.status_code(), StatusCode::OK);
+    let appended: serde_json::Value = appended.json();
+    assert_eq!(appended["received_bytes"], content.len());
+}
+
+#[tokio::test]
+async fn test_gzip_bodies_are_accepted_beyond_paste_writes() {
+    let (server, _temp, _locks) = setup_test_server();
+    let created: serde_json::Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "alpha" }))
+        .await
+        .json();
+    let id = created["id"].as_str().unwrap();
+
+    let edit = server
+        .post(&format!("/api/paste/{}/edits", id))
+        .add_header(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        )
+        .add_header(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"))
+        .bytes(gzip_json(&json!({ "base_rev": 0, "start": 5, "end": 5, "text": "\n" })).into())
+        .await;
+    assert_eq!(edit.status_code(), StatusCode::OK);
+
+    // Chunk offsets count decompressed bytes.
+    let begin: serde_json::Value = server
+        .post(&format!("/api/paste/{}/chunks", id))
+        .await
+        .json();
+    let session_path = format!(
+        "/api/paste/{}/chunks/{}",
+        id,
+        begin["upload_id"].as_str().unwrap()
+    );
+    let chunk = "log line\n".repeat(1000);
+    let appended = server
+        .post(&format!("{}?offset=0", session_path))
+        .add_header(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"))
+        .bytes(gzip_bytes(chunk.as_bytes()).into())
+        .await;
+    assert_eq!(appended.status_code(), StatusCode::OK);
+    let appended: serde_json::Value = appended.json();
+    assert_eq!(appended["received_bytes"], chunk.len());
+
+    let committed: serde_json::Value = server
+        .post(&format!("{}/commit", session_path))
+        .await
+        .json();
+    assert_eq!(committed["content"].as_str(), Some(chunk.as_str()));
+}
+
+#[tokio::test]
+async fn test_body_limit_applies_to_decompressed_size() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = test_config_for_db_path(&temp_dir.path().join("bomb.db"));
+    config.max_paste_size = 1024;
+    let (server, _locks) = test_server_for_config(config);
+
+    // A few KiB on the wire that expands far past the transport body limit.
+    let body = json!({ "content": "a".repeat(4 * 1024 * 1024) });
+    for (encoding, bomb) in [("gzip", gzip_json(&body)), ("zstd", zstd_json(&body))] {
+        assert!(bomb.len() < 64 * 1024, "{encoding} bomb too large");
+        let response = server
+            .post("/api/paste")
+            .add_header(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            )
+            .add_header(header::CONTENT_ENCODING, HeaderValue::from_static(encoding))
+            .bytes(bomb.into())
+            .await;
+        assert_eq!(
+            response.status_code(),
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "{encoding}"
+        );
+    }
+}
@@ -0,0 +1,69 @@
+//! Integration tests for content-hash lookup and dedupe-on-create.
+
+mod support;
+
+use axum::http::StatusCode;
+use localpaste_core::db::paste::paste_content_hash;
+use serde_json::json;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_dedupe_create_returns_existing_paste() {
+    let (server, _temp, _locks) = setup_test_server();
+    let created = server
+        .post("/api/paste")
+        .json(&json!({ "content": "same body\n", "name": "original" }))
+        .await;
+    assert_eq!(created.status_code(), StatusCode::OK);
+    let created: serde_json::Value = created.json();
+
+    let deduped = server
+        .post("/api/paste")
+        .json(&json!({ "content": "same body\n", "name": "copy", "dedupe": true }))
+        .await;
+    assert_eq!(deduped.status_code(), StatusCode::OK);
+    deduped.assert_header("x-localpaste-deduplicated", "true");
+    let deduped: serde_json::Value = deduped.json();
+    assert_eq!(deduped["id"], created["id"]);
+    assert_eq!(deduped["name"], "original");
+
+    let fresh = server
+        .post("/api/paste")
+        .json(&json!({ "content": "other body", "dedupe": true }))
+        .await;
+    assert_eq!(fresh.status_code(), StatusCode::OK);
+    assert!(fresh.maybe_header("x-localpaste-deduplicated").is_none());
+    let fresh: serde_json::Value = fresh.json();
+    assert_ne!(fresh["id"], created["id"]);
+
+    let without_flag = server
+        .post("/api/paste")
+        .json(&json!({ "content": "same body\n" }))
+        .await;
+    let without_flag: serde_json::Value = without_flag.json();
+    assert_ne!(without_flag["id"], created["id"]);
+}
+
+#[tokio::test]
+async fn test_meta_list_filters_by_content_hash() {
+    let (server, _temp, _locks) = setup_test_server();
+    for (content, name) in [("alpha", "a"), ("beta", "b")] {
+        server
+            .post("/api/paste")
+            .json(&json!({ "content": content, "name": name }))
+            .await
+            .assert_status_ok();
+    }
+
+    let hash = paste_content_hash("alpha").to_uppercase();
+    let response = server
+        .get(&format!("/api/pastes/meta?content_hash={}", hash))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let rows: Vec<serde_json::Value> = response.json();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["name"], "a");
+
+    let bad = server.get("/api/pastes/meta?content_hash=xyz").await;
+    assert_eq!(bad.status_code(), StatusCode::BAD_REQUEST);
+}
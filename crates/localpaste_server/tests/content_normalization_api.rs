@@ -0,0 +1,70 @@
+//! Integration tests for save-time line-ending, BOM, and UTF-8 normalization.
+
+mod support;
+
+use axum::http::StatusCode;
+use localpaste_core::env::{env_lock, EnvGuard};
+use serde_json::json;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_normalization_converts_on_save_and_restores_on_export() {
+    // Options are read when the database opens, so the env only needs to be
+    // pinned while the server boots.
+    let (server, _temp, _locks) = {
+        let _lock = env_lock().lock().expect("env lock");
+        let _line_endings = EnvGuard::set("LOCALPASTE_LINE_ENDINGS", "lf");
+        let _strip_bom = EnvGuard::set("LOCALPASTE_STRIP_BOM", "1");
+        let _invalid_utf8 = EnvGuard::set("LOCALPASTE_INVALID_UTF8", "replace");
+        setup_test_server()
+    };
+
+    let created = server
+        .post("/api/paste")
+        .json(&json!({ "content": "\u{FEFF}one\r\ntwo\r\n", "name": "windows" }))
+        .await;
+    assert_eq!(created.status_code(), StatusCode::OK);
+    let created: serde_json::Value = created.json();
+    assert_eq!(created["content"], "one\ntwo\n");
+    assert_eq!(created["line_ending"], "crlf");
+    let paste_id = created["id"].as_str().unwrap();
+
+    // A mixed-ending GUI edit is stored as LF and keeps the recorded original.
+    let updated = server
+        .put(&format!("/api/paste/{}", paste_id))
+        .json(&json!({ "content": "one\ntwo\r\nthree\n" }))
+        .await;
+    assert_eq!(updated.status_code(), StatusCode::OK);
+    let updated: serde_json::Value = updated.json();
+    assert_eq!(updated["content"], "one\ntwo\nthree\n");
+    assert_eq!(updated["line_ending"], "crlf");
+
+    let stored = server.get(&format!("/api/paste/{}/raw", paste_id)).await;
+    assert_eq!(stored.text(), "one\ntwo\nthree\n");
+    let original = server
+        .get(&format!(
+            "/api/paste/{}/raw?line_endings=original",
+            paste_id
+        ))
+        .await;
+    assert_eq!(original.text(), "one\r\ntwo\r\nthree\r\n");
+
+    let begin: serde_json::Value = server
+        .post(&format!("/api/paste/{}/chunks", paste_id))
+        .await
+        .json();
+    let session_path = format!(
+        "/api/paste/{}/chunks/{}",
+        paste_id,
+        begin["upload_id"].as_str().unwrap()
+    );
+    server
+        .post(&session_path)
+        .bytes(vec![b'o', b'k', 0xFF].into())
+        .await
+        .assert_status_ok();
+    let committed = server.post(&format!("{}/commit", session_path)).await;
+    assert_eq!(committed.status_code(), StatusCode::OK);
+    let committed: serde_json::Value = committed.json();
+    assert_eq!(committed["content"], "ok\u{FFFD}");
+}
@@ -0,0 +1,84 @@
+//! Integration tests for count routes and HEAD existence checks.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_paste_and_folder_counts() {
+    let (server, _temp, _locks) = setup_test_server();
+    let parent: serde_json::Value = server
+        .post("/api/folder")
+        .json(&json!({ "name": "parent" }))
+        .await
+        .json();
+    let parent_id = parent["id"].as_str().expect("parent id").to_string();
+    let child: serde_json::Value = server
+        .post("/api/folder")
+        .json(&json!({ "name": "child", "parent_id": parent_id }))
+        .await
+        .json();
+    let child_id = child["id"].as_str().expect("child id").to_string();
+
+    for (content, folder_id, tags) in [
+        ("one", Some(parent_id.as_str()), vec!["keep"]),
+        ("two", Some(child_id.as_str()), vec!["keep"]),
+        ("three", Some(child_id.as_str()), vec![]),
+        ("four", None, vec!["Keep"]),
+    ] {
+        server
+            .post("/api/paste")
+            .json(&json!({ "content": content, "folder_id": folder_id, "tags": tags }))
+            .await
+            .assert_status_ok();
+    }
+
+    let total: serde_json::Value = server.get("/api/pastes/count").await.json();
+    assert_eq!(total, json!({ "count": 4 }));
+    let tagged: serde_json::Value = server.get("/api/pastes/count?tag=keep").await.json();
+    assert_eq!(tagged["count"], 3);
+    let in_child: serde_json::Value = server
+        .get(&format!(
+            "/api/pastes/count?folder_id={}&tag=keep",
+            child_id
+        ))
+        .await
+        .json();
+    assert_eq!(in_child["count"], 1);
+
+    let folder_counts = server
+        .get(&format!("/api/folder/{}/count", parent_id))
+        .await;
+    assert_eq!(folder_counts.status_code(), StatusCode::OK);
+    let folder_counts: serde_json::Value = folder_counts.json();
+    assert_eq!(
+        folder_counts,
+        json!({ "paste_count": 1, "recursive_paste_count": 3 })
+    );
+    let missing = server.get("/api/folder/missing/count").await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_head_paste_reports_existence_without_body() {
+    let (server, _temp, _locks) = setup_test_server();
+    let created: serde_json::Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "body" }))
+        .await
+        .json();
+    let id = created["id"].as_str().expect("id");
+
+    let found = server
+        .method(axum::http::Method::HEAD, &format!("/api/paste/{}", id))
+        .await;
+    assert_eq!(found.status_code(), StatusCode::OK);
+    assert!(found.as_bytes().is_empty());
+
+    let missing = server
+        .method(axum::http::Method::HEAD, "/api/paste/does-not-exist")
+        .await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
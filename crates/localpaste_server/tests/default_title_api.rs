@@ -0,0 +1,40 @@
+//! Integration tests for content-derived default paste names.
+
+mod support;
+
+use serde_json::{json, Value};
+use support::setup_test_server;
+
+#[tokio::test]
+async fn unnamed_pastes_are_titled_from_content() {
+    let (server, _temp, _locks) = setup_test_server();
+    let cases = [
+        ("intro\n\n## Release checklist\n- tag", "Release checklist"),
+        ("// Retry with backoff\nfn retry() {}", "Retry with backoff"),
+        ("\n  docker compose up -d\n", "docker compose up -d"),
+    ];
+    for (content, expected) in cases {
+        let paste: Value = server
+            .post("/api/paste")
+            .add_header("x-localpaste-source", "clipboard")
+            .json(&json!({ "content": content }))
+            .await
+            .json();
+        assert_eq!(paste["name"], expected, "{content:?}");
+    }
+
+    let named: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "# Heading", "name": "kept" }))
+        .await
+        .json();
+    assert_eq!(named["name"], "kept");
+
+    let blank: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "   \n" }))
+        .await
+        .json();
+    let name = blank["name"].as_str().expect("generated name");
+    assert!(name.contains('-') && !name.contains(' '), "{name}");
+}
@@ -0,0 +1,46 @@
+//! Integration tests for activity digest generation.
+
+mod support;
+
+use serde_json::{json, Value};
+use support::setup_test_server;
+
+#[tokio::test]
+async fn digest_is_stored_in_digests_folder() {
+    let (server, _temp, _locks) = setup_test_server();
+    for content in ["fn main() {}", "# notes"] {
+        server
+            .post("/api/paste")
+            .json(&json!({ "content": content }))
+            .await
+            .assert_status_ok();
+    }
+
+    let digest: Value = server
+        .post("/api/digest")
+        .json(&json!({ "period": "week" }))
+        .await
+        .json();
+    let name = digest["name"].as_str().expect("digest name");
+    assert!(name.starts_with("Weekly digest "), "{name}");
+    assert_eq!(digest["language"], "markdown");
+    assert_eq!(digest["tags"], json!(["digest"]));
+    let content = digest["content"].as_str().expect("digest content");
+    assert!(content.contains("- New pastes: 2\n"), "{content}");
+
+    let folders: Value = server.get("/api/folders").await.json();
+    let folder = folders
+        .as_array()
+        .expect("folders")
+        .iter()
+        .find(|folder| folder["name"] == "Digests")
+        .expect("Digests folder");
+    assert_eq!(digest["folder_id"], folder["id"]);
+    assert_eq!(folder["paste_count"], 1);
+
+    server
+        .post("/api/digest")
+        .json(&json!({ "period": "fortnight" }))
+        .await
+        .assert_status(axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+}
@@ -0,0 +1,41 @@
+//! Integration tests for embedded server port fallback and discovery output.
+
+use localpaste_server::{config::api_addr_file_path_for_db_path, AppState, Config, Database};
+use localpaste_server::{EmbeddedServer, PasteLockManager};
+use std::net::TcpListener;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+#[test]
+fn test_embedded_server_falls_back_to_next_port_and_publishes_it() {
+    let temp_dir = TempDir::new().expect("temp dir");
+    let db_path = temp_dir.path().join("test.db");
+    let db_path = db_path.to_str().expect("db path").to_string();
+    let occupied = TcpListener::bind("127.0.0.1:0").expect("occupy port");
+    let taken_port = occupied.local_addr().expect("occupied addr").port();
+
+    let config = Config {
+        port: taken_port,
+        db_path: db_path.clone(),
+        max_paste_size: 10_000_000,
+        auto_save_interval: 2000,
+        auto_backup: false,
+        size_limits: Default::default(),
+        flush_policy: Default::default(),
+        recent_history: false,
+    };
+    let db = Database::new(&db_path).expect("open db");
+    let state = AppState::with_locks(config, db, Arc::new(PasteLockManager::default()));
+    let server = EmbeddedServer::start(state, false).expect("server");
+
+    assert!(server.used_fallback());
+    assert_eq!(server.requested_addr().port(), taken_port);
+    assert_ne!(server.addr().port(), taken_port);
+
+    let discovery_path = api_addr_file_path_for_db_path(&db_path);
+    let published = std::fs::read_to_string(&discovery_path).expect("discovery file");
+    assert_eq!(published, format!("http://{}", server.addr()));
+
+    drop(server);
+    assert!(!discovery_path.exists());
+}
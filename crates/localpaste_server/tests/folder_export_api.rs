@@ -0,0 +1,64 @@
+//! Integration tests for folder ZIP exports.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use support::setup_test_server;
+
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle.as_bytes())
+}
+
+#[tokio::test]
+async fn test_folder_export_zips_subtree_with_manifest() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let root: serde_json::Value = server
+        .post("/api/folder")
+        .json(&json!({ "name": "Project Notes" }))
+        .await
+        .json();
+    let root_id = root["id"].as_str().unwrap();
+    let child: serde_json::Value = server
+        .post("/api/folder")
+        .json(&json!({ "name": "scripts", "parent_id": root_id }))
+        .await
+        .json();
+    let child_id = child["id"].as_str().unwrap();
+
+    for (name, language, folder_id) in [
+        ("readme", "markdown", root_id),
+        ("build", "shell", child_id),
+    ] {
+        let created = server
+            .post("/api/paste")
+            .json(&json!({
+                "content": format!("{} body\n", name),
+                "name": name,
+                "language": language,
+                "folder_id": folder_id
+            }))
+            .await;
+        assert_eq!(created.status_code(), StatusCode::OK);
+    }
+
+    let response = server.get(&format!("/api/folder/{}/export", root_id)).await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    response.assert_header("content-type", "application/zip");
+    response.assert_header(
+        "content-disposition",
+        "attachment; filename=\"Project_Notes.zip\"",
+    );
+    let archive = response.as_bytes();
+    assert!(archive.starts_with(b"PK\x03\x04"));
+    assert!(contains(archive, "Project Notes/readme.md"));
+    assert!(contains(archive, "Project Notes/scripts/build.sh"));
+    assert!(contains(archive, "manifest.json"));
+    assert!(contains(archive, "build body"));
+
+    let missing = server.get("/api/folder/missing/export").await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
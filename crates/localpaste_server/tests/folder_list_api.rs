@@ -0,0 +1,117 @@
+//! Integration tests for filtered and sorted folder listings and the folder tree.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use support::setup_test_server;
+
+async fn create_folder(server: &axum_test::TestServer, name: &str, parent: Option<&str>) -> String {
+    let response = server
+        .post("/api/folder")
+        .json(&json!({ "name": name, "parent_id": parent }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    response.json::<Value>()["id"].as_str().unwrap().to_string()
+}
+
+fn names(rows: &[Value]) -> Vec<&str> {
+    rows.iter()
+        .map(|row| row["name"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_folder_list_filters_by_parent_and_sorts() {
+    let (server, _temp, _locks) = setup_test_server();
+    let work = create_folder(&server, "work", None).await;
+    let archive = create_folder(&server, "Archive", None).await;
+    let nested = create_folder(&server, "nested", Some(work.as_str())).await;
+    let _leaf = create_folder(&server, "leaf", Some(nested.as_str())).await;
+    for (folder, count) in [(&work, 1), (&nested, 2), (&archive, 1)] {
+        for idx in 0..count {
+            server
+                .post("/api/paste")
+                .json(&json!({ "content": format!("body {}", idx), "folder_id": folder }))
+                .await
+                .assert_status_ok();
+        }
+    }
+
+    let all: Vec<Value> = server.get("/api/folders").await.json();
+    assert_eq!(all.len(), 4);
+    assert!(all
+        .iter()
+        .all(|row| row.get("recursive_paste_count").is_none()));
+
+    let top: Vec<Value> = server.get("/api/folders?parent_id=&sort=name").await.json();
+    assert_eq!(names(&top), vec!["Archive", "work"]);
+
+    let by_count: Vec<Value> = server
+        .get("/api/folders?parent_id=&recursive_counts=true&sort=paste_count&order=desc")
+        .await
+        .json();
+    assert_eq!(names(&by_count), vec!["work", "Archive"]);
+    assert_eq!(by_count[0]["recursive_paste_count"], 3);
+    assert_eq!(by_count[0]["paste_count"], 1);
+
+    let children: Vec<Value> = server
+        .get(&format!("/api/folders?parent_id={}", work))
+        .await
+        .json();
+    assert_eq!(names(&children), vec!["nested"]);
+
+    let detail = server.get(&format!("/api/folder/{}", nested)).await;
+    assert_eq!(detail.status_code(), StatusCode::OK);
+    let detail: Value = detail.json();
+    assert_eq!(detail["paste_count"], 2);
+    assert_eq!(
+        detail["total_bytes"],
+        ("body 0".len() + "body 1".len()) as u64
+    );
+    assert!(detail["last_updated"].is_string());
+    let missing_folder = server.get("/api/folder/missing").await;
+    assert_eq!(missing_folder.status_code(), StatusCode::NOT_FOUND);
+
+    let missing = server.get("/api/folders?parent_id=missing").await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+    let bad_sort = server.get("/api/folders?sort=size").await;
+    assert_eq!(bad_sort.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_folder_tree_nests_children_with_recursive_counts() {
+    let (server, _temp, _locks) = setup_test_server();
+    let work = create_folder(&server, "work", None).await;
+    let _archive = create_folder(&server, "Archive", None).await;
+    let nested = create_folder(&server, "nested", Some(work.as_str())).await;
+    let _leaf = create_folder(&server, "leaf", Some(nested.as_str())).await;
+    let _docs = create_folder(&server, "Docs", Some(work.as_str())).await;
+    for folder in [&work, &nested, &nested] {
+        server
+            .post("/api/paste")
+            .json(&json!({ "content": "body", "folder_id": folder }))
+            .await
+            .assert_status_ok();
+    }
+
+    let response = server.get("/api/folders/tree").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert!(response.headers().get("deprecation").is_some());
+    let tree: Vec<Value> = response.json();
+    assert_eq!(names(&tree), vec!["Archive", "work"]);
+    assert_eq!(tree[0]["children"], json!([]));
+    assert_eq!(tree[0]["recursive_paste_count"], 0);
+
+    let work_node = &tree[1];
+    assert_eq!(work_node["id"], work.as_str());
+    assert_eq!(work_node["paste_count"], 1);
+    assert_eq!(work_node["recursive_paste_count"], 3);
+    let children = work_node["children"].as_array().unwrap();
+    assert_eq!(names(children), vec!["Docs", "nested"]);
+    assert_eq!(children[1]["paste_count"], 2);
+    assert_eq!(children[1]["parent_id"], work.as_str());
+    let leaves = children[1]["children"].as_array().unwrap();
+    assert_eq!(names(leaves), vec!["leaf"]);
+    assert_eq!(leaves[0]["children"], json!([]));
+}
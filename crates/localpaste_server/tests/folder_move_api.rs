@@ -0,0 +1,66 @@
+//! Integration tests for re-parenting folders.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use support::setup_test_server;
+
+async fn create_folder(server: &axum_test::TestServer, name: &str, parent: Option<&str>) -> String {
+    let response = server
+        .post("/api/folder")
+        .json(&json!({ "name": name, "parent_id": parent }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    response.json::<Value>()["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_folder_move_reparents_and_rejects_cycles() {
+    let (server, _temp, _locks) = setup_test_server();
+    let work = create_folder(&server, "work", None).await;
+    let project = create_folder(&server, "project", Some(work.as_str())).await;
+    let logs = create_folder(&server, "logs", Some(project.as_str())).await;
+    let archive = create_folder(&server, "archive", None).await;
+    let paste: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "kept", "folder_id": project }))
+        .await
+        .json();
+
+    let moved = server
+        .post(&format!("/api/folder/{}/move", project))
+        .json(&json!({ "parent_id": archive }))
+        .await;
+    assert_eq!(moved.status_code(), StatusCode::OK);
+    let moved: Value = moved.json();
+    assert_eq!(moved["name"], "project");
+    assert_eq!(moved["parent_id"], archive.as_str());
+    assert_eq!(moved["paste_count"], 1);
+    let leaf: Value = server.get(&format!("/api/folder/{}", logs)).await.json();
+    assert_eq!(leaf["parent_id"], project.as_str());
+    let paste_id = paste["id"].as_str().unwrap();
+    let stored: Value = server.get(&format!("/api/paste/{}", paste_id)).await.json();
+    assert_eq!(stored["folder_id"], project.as_str());
+
+    for target in [project.as_str(), logs.as_str(), "missing"] {
+        let refused = server
+            .post(&format!("/api/folder/{}/move", project))
+            .json(&json!({ "parent_id": target }))
+            .await;
+        assert_eq!(refused.status_code(), StatusCode::BAD_REQUEST, "{target}");
+    }
+
+    let top = server
+        .post(&format!("/api/folder/{}/move", project))
+        .json(&json!({ "parent_id": null }))
+        .await;
+    assert_eq!(top.status_code(), StatusCode::OK);
+    assert!(top.json::<Value>()["parent_id"].is_null());
+
+    let missing = server
+        .post("/api/folder/missing/move")
+        .json(&json!({ "parent_id": work }))
+        .await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,112 @@
+//! Integration tests for filing new pastes by `folder_path`.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_create_by_folder_path_reuses_and_creates_nested_folders() {
+    let (server, _temp, _locks) = setup_test_server();
+    let work: Value = server
+        .post("/api/folder")
+        .json(&json!({ "name": "work" }))
+        .await
+        .json();
+
+    let response = server
+        .post("/api/paste")
+        .json(&json!({ "content": "first", "folder_path": "work/project/logs" }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert!(response.headers().contains_key("deprecation"));
+    let first: Value = response.json();
+    let logs_id = first["folder_id"].as_str().unwrap().to_string();
+
+    let folders: Vec<Value> = server.get("/api/folders").await.json();
+    assert_eq!(folders.len(), 3);
+    let project = folders.iter().find(|row| row["name"] == "project").unwrap();
+    assert_eq!(project["parent_id"], work["id"]);
+    let logs = folders
+        .iter()
+        .find(|row| row["id"] == logs_id.as_str())
+        .unwrap();
+    assert_eq!(logs["parent_id"], project["id"]);
+    assert_eq!(logs["paste_count"], 1);
+
+    let second: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "second", "folder_path": " /work/ project/logs/ " }))
+        .await
+        .json();
+    assert_eq!(second["folder_id"], logs_id.as_str());
+    let folders: Vec<Value> = server.get("/api/folders").await.json();
+    assert_eq!(folders.len(), 3);
+
+    let batch: Vec<Value> = server
+        .post("/api/pastes/batch")
+        .json(&json!([{ "content": "third", "folder_path": "work/project/logs" }]))
+        .await
+        .json();
+    assert_eq!(batch[0]["folder_id"], logs_id.as_str());
+}
+
+#[tokio::test]
+async fn test_create_by_folder_path_rejects_conflicts_and_missing_folders() {
+    let (server, _temp, _locks) = setup_test_server();
+    let work: Value = server
+        .post("/api/folder")
+        .json(&json!({ "name": "work" }))
+        .await
+        .json();
+
+    let both = server
+        .post("/api/paste")
+        .json(&json!({ "content": "x", "folder_id": work["id"], "folder_path": "work" }))
+        .await;
+    assert_eq!(both.status_code(), StatusCode::BAD_REQUEST);
+
+    let blank = server
+        .post("/api/paste")
+        .json(&json!({ "content": "x", "folder_path": "//" }))
+        .await;
+    assert_eq!(blank.status_code(), StatusCode::BAD_REQUEST);
+
+    let missing = server
+        .post("/api/paste")
+        .json(&json!({ "content": "x", "folder_path": "work/new", "create_folders": false }))
+        .await;
+    assert_eq!(missing.status_code(), StatusCode::BAD_REQUEST);
+    let folders: Vec<Value> = server.get("/api/folders").await.json();
+    assert_eq!(folders.len(), 1);
+
+    let existing: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "x", "folder_path": "work", "create_folders": false }))
+        .await
+        .json();
+    assert_eq!(existing["folder_id"], work["id"]);
+}
+
+#[tokio::test]
+async fn test_deduplicated_create_does_not_create_folder_path() {
+    let (server, _temp, _locks) = setup_test_server();
+    let original: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "same body" }))
+        .await
+        .json();
+
+    let response = server
+        .post("/api/paste")
+        .json(&json!({ "content": "same body", "dedupe": true, "folder_path": "unused/nested" }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert_eq!(response.header("x-localpaste-deduplicated"), "true");
+    let deduplicated: Value = response.json();
+    assert_eq!(deduplicated["id"], original["id"]);
+
+    let folders: Vec<Value> = server.get("/api/folders").await.json();
+    assert!(folders.is_empty(), "{:?}", folders);
+}
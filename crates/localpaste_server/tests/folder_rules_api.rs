@@ -0,0 +1,150 @@
+//! Integration tests for folder auto-organization rules at `/api/folder-rules`.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use support::setup_test_server;
+
+async fn create_folder(server: &axum_test::TestServer, name: &str) -> String {
+    let folder: Value = server
+        .post("/api/folder")
+        .json(&json!({ "name": name }))
+        .await
+        .json();
+    folder["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_rules_file_new_pastes_without_an_explicit_folder() {
+    let (server, _temp, _locks) = setup_test_server();
+    let logs = create_folder(&server, "logs").await;
+    let python = create_folder(&server, "python").await;
+    let clips = create_folder(&server, "clips").await;
+
+    for (field, pattern, folder_id) in [
+        ("name", "*.log", &logs),
+        ("language", "py*", &python),
+        ("source", "clipboard", &clips),
+    ] {
+        let response = server
+            .post("/api/folder-rules")
+            .json(&json!({ "field": field, "pattern": pattern, "folder_id": folder_id }))
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+    }
+    let rules: Vec<Value> = server.get("/api/folder-rules").await.json();
+    assert_eq!(rules.len(), 3);
+    assert_eq!(rules[0]["field"], "name");
+    assert_eq!(rules[2]["position"], 2);
+
+    let by_name: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "boot ok", "name": "Server.LOG" }))
+        .await
+        .json();
+    assert_eq!(by_name["folder_id"], logs.as_str());
+
+    let by_language: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "print(1)", "language": "python" }))
+        .await
+        .json();
+    assert_eq!(by_language["folder_id"], python.as_str());
+
+    let by_source: Value = server
+        .post("/api/paste")
+        .add_header("x-localpaste-source", "clipboard")
+        .json(&json!({ "content": "copied text" }))
+        .await
+        .json();
+    assert_eq!(by_source["folder_id"], clips.as_str());
+
+    let explicit: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "boot ok", "name": "a.log", "folder_id": python }))
+        .await
+        .json();
+    assert_eq!(explicit["folder_id"], python.as_str());
+
+    let unmatched: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "plain notes", "name": "notes" }))
+        .await
+        .json();
+    assert!(unmatched["folder_id"].is_null());
+
+    let folders: Vec<Value> = server.get("/api/folders").await.json();
+    let logs_row = folders
+        .iter()
+        .find(|row| row["id"] == logs.as_str())
+        .unwrap();
+    assert_eq!(logs_row["paste_count"], 1);
+}
+
+#[tokio::test]
+async fn test_rules_validate_reorder_and_delete() {
+    let (server, _temp, _locks) = setup_test_server();
+    let first = create_folder(&server, "first").await;
+    let second = create_folder(&server, "second").await;
+
+    let missing_folder = server
+        .post("/api/folder-rules")
+        .json(&json!({ "field": "tag", "pattern": "x", "folder_id": "nope" }))
+        .await;
+    assert_eq!(missing_folder.status_code(), StatusCode::BAD_REQUEST);
+    let blank = server
+        .post("/api/folder-rules")
+        .json(&json!({ "field": "tag", "pattern": "  ", "folder_id": first }))
+        .await;
+    assert_eq!(blank.status_code(), StatusCode::BAD_REQUEST);
+
+    let a: Value = server
+        .post("/api/folder-rules")
+        .json(&json!({ "field": "tag", "pattern": "work", "folder_id": first }))
+        .await
+        .json();
+    let b: Value = server
+        .post("/api/folder-rules")
+        .json(&json!({ "field": "tag", "pattern": "w*", "folder_id": second }))
+        .await
+        .json();
+
+    let reordered: Vec<Value> = server
+        .put("/api/folder-rules/order")
+        .json(&json!({ "ids": [b["id"], a["id"]] }))
+        .await
+        .json();
+    assert_eq!(reordered[0]["id"], b["id"]);
+    let tagged: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "x", "tags": ["work"] }))
+        .await
+        .json();
+    assert_eq!(tagged["folder_id"], second.as_str());
+
+    let partial = server
+        .put("/api/folder-rules/order")
+        .json(&json!({ "ids": [a["id"]] }))
+        .await;
+    assert_eq!(partial.status_code(), StatusCode::BAD_REQUEST);
+
+    let updated: Value = server
+        .put(&format!("/api/folder-rules/{}", b["id"].as_str().unwrap()))
+        .json(&json!({ "pattern": "personal" }))
+        .await
+        .json();
+    assert_eq!(updated["pattern"], "personal");
+    assert_eq!(updated["folder_id"], second.as_str());
+
+    server
+        .delete(&format!("/api/folder-rules/{}", a["id"].as_str().unwrap()))
+        .await
+        .assert_status_ok();
+    let gone = server
+        .delete(&format!("/api/folder-rules/{}", a["id"].as_str().unwrap()))
+        .await;
+    assert_eq!(gone.status_code(), StatusCode::NOT_FOUND);
+    let rules: Vec<Value> = server.get("/api/folder-rules").await.json();
+    assert_eq!(rules.len(), 1);
+}
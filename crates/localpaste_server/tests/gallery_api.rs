@@ -0,0 +1,118 @@
+//! Integration tests for the read-only LAN gallery router.
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use localpaste_core::env::{env_lock, EnvGuard};
+use localpaste_core::models::{folder::Folder, paste::Paste};
+use localpaste_server::{create_gallery_app, db::TransactionOps, Database};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn gallery_serves_only_the_published_folder_read_only() {
+    let temp = TempDir::new().expect("temp dir");
+    let db =
+        Database::new(temp.path().join("gallery.db").to_str().expect("path")).expect("open db");
+    let folder = Folder::new("Team <snippets>".to_string());
+    db.folders.create(&folder).expect("create folder");
+    let mut published = Paste::new_with_language(
+        "fn main() {}\n".to_string(),
+        "hello.rs".to_string(),
+        Some("rust".to_string()),
+        true,
+    );
+    published.folder_id = Some(folder.id.clone());
+    TransactionOps::create_paste_with_folder(&db, &published, &folder.id)
+        .expect("create published");
+    let private = Paste::new("secret\n".to_string(), "private".to_string());
+    db.pastes.create(&private).expect("create private");
+
+    let server = TestServer::new(create_gallery_app(Arc::new(db), &folder.id)).expect("server");
+
+    let index = server.get("/").await;
+    index.assert_status_ok();
+    let html = index.text();
+    assert!(html.contains("<h1>Team &lt;snippets&gt;</h1>"), "{}", html);
+    assert!(html.contains(&format!("href=\"/p/{}\"", published.id)));
+    assert!(!html.contains("private"));
+
+    let view = server.get(&format!("/p/{}", published.id)).await;
+    view.assert_status_ok();
+    let page = view.text();
+    assert!(page.contains("<a href=\"/\">"));
+    assert!(page.contains("class=\"lp-"));
+    assert!(page.contains("id=\"L1\" href=\"#L1\""));
+
+    let raw = server.get(&format!("/p/{}/raw", published.id)).await;
+    assert_eq!(raw.text(), "fn main() {}\n");
+
+    for path in [
+        format!("/p/{}", private.id),
+        format!("/p/{}/raw", private.id),
+        "/api/pastes".to_string(),
+        format!("/api/paste/{}", published.id),
+    ] {
+        server
+            .get(&path)
+            .expect_failure()
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+    server
+        .post("/")
+        .expect_failure()
+        .await
+        .assert_status(StatusCode::METHOD_NOT_ALLOWED);
+    server
+        .delete(&format!("/p/{}", published.id))
+        .expect_failure()
+        .await
+        .assert_status(StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[tokio::test]
+async fn gallery_hides_one_time_and_unshared_pastes() {
+    let temp = TempDir::new().expect("temp dir");
+    let path = temp.path().join("gallery.db");
+    let db = {
+        let _lock = env_lock().lock().expect("env lock");
+        let _enabled = EnvGuard::set("LOCALPASTE_MULTI_USER", "1");
+        Database::new(path.to_str().expect("path")).expect("open db")
+    };
+    let folder = Folder::new("Shared".to_string());
+    db.folders.create(&folder).expect("create folder");
+    let mut ids = Vec::new();
+    for (name, shared, burn) in [
+        ("shared", true, false),
+        ("private", false, false),
+        ("onetime", true, true),
+    ] {
+        let mut paste = Paste::new(format!("{} body\n", name), name.to_string());
+        paste.folder_id = Some(folder.id.clone());
+        paste.burn_after_read = burn;
+        TransactionOps::create_paste_with_folder(&db, &paste, &folder.id).expect("create");
+        db.users
+            .set_paste_owner(&paste.id, "owner")
+            .expect("set owner");
+        db.users
+            .set_paste_shared(&paste.id, shared)
+            .expect("set shared");
+        ids.push(paste.id);
+    }
+
+    let server = TestServer::new(create_gallery_app(Arc::new(db), &folder.id)).expect("server");
+    let html = server.get("/").await.text();
+    assert!(
+        html.contains(&format!("href=\"/p/{}\"", ids[0])),
+        "{}",
+        html
+    );
+    for id in &ids[1..] {
+        assert!(!html.contains(id.as_str()), "{}", html);
+        server
+            .get(&format!("/p/{}", id))
+            .expect_failure()
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+}
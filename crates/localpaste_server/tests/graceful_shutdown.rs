@@ -0,0 +1,112 @@
+//! Integration tests for draining in-flight requests on shutdown.
+
+use localpaste_server::{serve_router_with_drain, AppState, Config, Database};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+struct RunningServer {
+    addr: SocketAddr,
+    shutdown: oneshot::Sender<()>,
+    handle: JoinHandle<std::io::Result<()>>,
+    _temp: TempDir,
+}
+
+async fn start_server(drain_timeout: Duration) -> RunningServer {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("test.db");
+    let config = Config {
+        port: 0,
+        db_path: db_path.to_str().expect("db path").to_string(),
+        max_paste_size: 10_000_000,
+        auto_save_interval: 2000,
+        auto_backup: false,
+        size_limits: Default::default(),
+        flush_policy: Default::default(),
+        recent_history: false,
+    };
+    let db = Database::new(&config.db_path).expect("open db");
+    let state = AppState::new(config, db);
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+    let handle = tokio::spawn(serve_router_with_drain(
+        listener,
+        state,
+        false,
+        async move {
+            let _ = shutdown_rx.await;
+        },
+        drain_timeout,
+    ));
+    RunningServer {
+        addr,
+        shutdown,
+        handle,
+        _temp: temp,
+    }
+}
+
+/// Opens a create-paste request and sends only the first half of its body.
+async fn begin_partial_create(addr: SocketAddr, body: &str) -> (TcpStream, String) {
+    let mut stream = TcpStream::connect(addr).await.expect("connect");
+    let head = format!(
+        "POST /api/paste HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        addr,
+        body.len()
+    );
+    let (first, rest) = body.split_at(body.len() / 2);
+    stream
+        .write_all(format!("{}{}", head, first).as_bytes())
+        .await
+        .expect("write partial request");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    (stream, rest.to_string())
+}
+
+#[tokio::test]
+async fn test_shutdown_lets_in_flight_request_finish() {
+    let server = start_server(Duration::from_secs(5)).await;
+    let (mut stream, rest) = begin_partial_create(server.addr, r#"{"content":"draining"}"#).await;
+
+    server.shutdown.send(()).expect("signal shutdown");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(
+        TcpStream::connect(server.addr).await.is_err(),
+        "new connections must be refused while draining"
+    );
+
+    stream
+        .write_all(rest.as_bytes())
+        .await
+        .expect("finish body");
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .await
+        .expect("read response");
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+
+    tokio::time::timeout(Duration::from_secs(5), server.handle)
+        .await
+        .expect("server stops after drain")
+        .expect("join")
+        .expect("serve result");
+}
+
+#[tokio::test]
+async fn test_shutdown_stops_waiting_for_stalled_request_after_drain_timeout() {
+    let server = start_server(Duration::from_millis(200)).await;
+    let (_stream, _rest) = begin_partial_create(server.addr, r#"{"content":"stalled"}"#).await;
+
+    server.shutdown.send(()).expect("signal shutdown");
+    tokio::time::timeout(Duration::from_secs(5), server.handle)
+        .await
+        .expect("server stops once the drain timeout elapses")
+        .expect("join")
+        .expect("serve result");
+}
@@ -0,0 +1,118 @@
+//! Integration tests for tag and language list filters and list sorting.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_meta_list_filters_by_tag_and_language() {
+    let (server, _temp, _locks) = setup_test_server();
+    for (content, name, language, tags) in [
+        ("fn main() {}", "rust-work", "rust", vec!["Work"]),
+        ("print(1)", "py-work", "python", vec!["work", "scratch"]),
+        ("echo hi", "shell-home", "bash", vec!["home"]),
+    ] {
+        server
+            .post("/api/paste")
+            .json(&json!({
+                "content": content,
+                "name": name,
+                "language": language,
+                "tags": tags,
+            }))
+            .await
+            .assert_status_ok();
+    }
+
+    let names = |rows: Vec<serde_json::Value>| -> Vec<String> {
+        let mut names: Vec<String> = rows
+            .iter()
+            .map(|row| row["name"].as_str().expect("name").to_string())
+            .collect();
+        names.sort();
+        names
+    };
+
+    let by_tag = server.get("/api/pastes/meta?tag=WORK").await;
+    assert_eq!(by_tag.status_code(), StatusCode::OK);
+    assert_eq!(names(by_tag.json()), vec!["py-work", "rust-work"]);
+
+    let by_language = server.get("/api/pastes?language=sh").await;
+    assert_eq!(by_language.status_code(), StatusCode::OK);
+    assert_eq!(names(by_language.json()), vec!["shell-home"]);
+
+    let combined = server
+        .get("/api/pastes/meta?tag=work&language=python")
+        .await;
+    assert_eq!(combined.status_code(), StatusCode::OK);
+    assert_eq!(names(combined.json()), vec!["py-work"]);
+
+    let blank = server.get("/api/pastes/meta?tag=%20&language=").await;
+    assert_eq!(blank.status_code(), StatusCode::OK);
+    assert_eq!(names(blank.json()).len(), 3);
+}
+
+#[tokio::test]
+async fn test_list_sort_and_order_params() {
+    let (server, _temp, _locks) = setup_test_server();
+    let mut ids = Vec::new();
+    for (content, name) in [
+        ("12345", "bravo"),
+        ("123456789012", "Alpha"),
+        ("1", "charlie"),
+    ] {
+        let created: serde_json::Value = server
+            .post("/api/paste")
+            .json(&json!({ "content": content, "name": name, "tags": ["sorted"] }))
+            .await
+            .json();
+        ids.push(created["id"].as_str().expect("id").to_string());
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+    server
+        .put(&format!("/api/paste/{}", ids[0]))
+        .json(&json!({ "content": "54321" }))
+        .await
+        .assert_status_ok();
+
+    let names = |rows: Vec<serde_json::Value>| -> Vec<String> {
+        rows.iter()
+            .map(|row| row["name"].as_str().expect("name").to_string())
+            .collect()
+    };
+    for (query, expected) in [
+        ("", ["bravo", "charlie", "Alpha"]),
+        ("sort=updated&order=asc", ["Alpha", "charlie", "bravo"]),
+        ("sort=created", ["charlie", "Alpha", "bravo"]),
+        ("sort=created&order=asc", ["bravo", "Alpha", "charlie"]),
+        ("sort=name", ["Alpha", "bravo", "charlie"]),
+        ("sort=name&order=desc", ["charlie", "bravo", "Alpha"]),
+        ("sort=size", ["Alpha", "bravo", "charlie"]),
+        (
+            "sort=size&order=asc&tag=sorted",
+            ["charlie", "bravo", "Alpha"],
+        ),
+    ] {
+        for route in ["/api/pastes", "/api/pastes/meta"] {
+            let response = server.get(&format!("{}?{}", route, query)).await;
+            assert_eq!(
+                response.status_code(),
+                StatusCode::OK,
+                "{}?{}",
+                route,
+                query
+            );
+            assert_eq!(names(response.json()), expected, "{}?{}", route, query);
+        }
+    }
+
+    let limited = server.get("/api/pastes/meta?sort=name&limit=1").await;
+    assert_eq!(names(limited.json()), ["Alpha"]);
+
+    let bad_sort = server.get("/api/pastes?sort=popularity").await;
+    assert_eq!(bad_sort.status_code(), StatusCode::BAD_REQUEST);
+    let bad_order = server.get("/api/pastes/meta?order=sideways").await;
+    assert_eq!(bad_order.status_code(), StatusCode::BAD_REQUEST);
+}
@@ -119,3 +119,35 @@ async fn test_metadata_save_does_not_clear_legacy_auto_resolved_language() {
     assert_eq!(updated_json["language"], "rust");
     assert_eq!(updated_json["language_is_manual"], false);
 }
+
+#[tokio::test]
+async fn test_language_aliases_canonicalize_and_unknown_languages_are_rejected() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let created: serde_json::Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "console.log(1)", "language": "Node" }))
+        .await
+        .json();
+    assert_eq!(created["language"], "javascript");
+    let id = created["id"].as_str().expect("id").to_string();
+
+    let unknown = server
+        .post("/api/paste")
+        .json(&json!({ "content": "x", "language": "klingon" }))
+        .await;
+    assert_eq!(unknown.status_code(), StatusCode::BAD_REQUEST);
+
+    let updated: serde_json::Value = server
+        .put(&format!("/api/paste/{}", id))
+        .json(&json!({ "language": "py" }))
+        .await
+        .json();
+    assert_eq!(updated["language"], "python");
+
+    let unknown = server
+        .put(&format!("/api/paste/{}", id))
+        .json(&json!({ "language": "klingon" }))
+        .await;
+    assert_eq!(unknown.status_code(), StatusCode::BAD_REQUEST);
+}
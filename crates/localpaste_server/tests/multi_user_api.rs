@@ -0,0 +1,163 @@
+//! Integration tests for multi-user mode: tokens, ownership, and sharing.
+
+mod support;
+
+use axum::http::StatusCode;
+use localpaste_core::env::{env_lock, EnvGuard};
+use serde_json::json;
+use support::setup_test_server;
+
+const ADMIN_TOKEN: &str = "household-admin-token";
+
+#[tokio::test]
+async fn test_multi_user_scopes_pastes_to_owners_and_shares() {
+    let (server, _temp, _locks) = {
+        let _lock = env_lock().lock().expect("env lock");
+        let _enabled = EnvGuard::set("LOCALPASTE_MULTI_USER", "1");
+        let _admin = EnvGuard::set("LOCALPASTE_ADMIN_TOKEN", ADMIN_TOKEN);
+        setup_test_server()
+    };
+
+    server
+        .get("/api/pastes")
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    server
+        .get("/api/pastes")
+        .authorization_bearer("lp_not-a-token")
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    let mut tokens = Vec::new();
+    for name in ["alice", "bob"] {
+        let created = server
+            .post("/api/admin/users")
+            .authorization_bearer(ADMIN_TOKEN)
+            .json(&json!({ "name": name }))
+            .await;
+        assert_eq!(created.status_code(), StatusCode::OK);
+        let created: serde_json::Value = created.json();
+        assert_eq!(created["role"], "member");
+        tokens.push(created["token"].as_str().unwrap().to_string());
+    }
+    let (alice, bob) = (tokens[0].as_str(), tokens[1].as_str());
+    server
+        .get("/api/admin/users")
+        .authorization_bearer(alice)
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+
+    let paste: serde_json::Value = server
+        .post("/api/paste")
+        .authorization_bearer(alice)
+        .json(&json!({ "content": "alice notes", "name": "notes" }))
+        .await
+        .json();
+    let paste_path = format!("/api/paste/{}", paste["id"].as_str().unwrap());
+
+    // Private pastes look missing to other members, in lists and by id.
+    let bob_list: Vec<serde_json::Value> = server
+        .get("/api/pastes?scope=all")
+        .authorization_bearer(bob)
+        .await
+        .json();
+    assert!(bob_list.is_empty());
+    server
+        .get(&paste_path)
+        .authorization_bearer(bob)
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    let short_raw = format!("/raw/{}", paste["id"].as_str().unwrap());
+    server
+        .get(&short_raw)
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    server
+        .get(&short_raw)
+        .authorization_bearer(bob)
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    let alice_list: Vec<serde_json::Value> = server
+        .get("/api/pastes")
+        .authorization_bearer(alice)
+        .await
+        .json();
+    assert_eq!(alice_list.len(), 1);
+
+    let shared = server
+        .put(&format!("{}/visibility", paste_path))
+        .authorization_bearer(alice)
+        .json(&json!({ "shared": true }))
+        .await;
+    assert_eq!(shared.status_code(), StatusCode::OK);
+    assert_eq!(shared.json::<serde_json::Value>()["shared"], true);
+
+    // Shared pastes are readable, listed under `scope=all`, and still read-only.
+    server
+        .get(&paste_path)
+        .authorization_bearer(bob)
+        .await
+        .assert_status_ok();
+    let own: Vec<serde_json::Value> = server
+        .get("/api/pastes")
+        .authorization_bearer(bob)
+        .await
+        .json();
+    assert!(own.is_empty());
+    let all: Vec<serde_json::Value> = server
+        .get("/api/search?q=alice&scope=all")
+        .authorization_bearer(bob)
+        .await
+        .json();
+    assert_eq!(all.len(), 1);
+    server
+        .delete(&paste_path)
+        .authorization_bearer(bob)
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    // Admins reach everything; deleted users lose access immediately.
+    let count: serde_json::Value = server
+        .get("/api/pastes/count?scope=all")
+        .authorization_bearer(ADMIN_TOKEN)
+        .await
+        .json();
+    assert_eq!(count["count"], 1);
+    let users: Vec<serde_json::Value> = server
+        .get("/api/admin/users")
+        .authorization_bearer(ADMIN_TOKEN)
+        .await
+        .json();
+    assert!(users.iter().all(|user| user.get("token").is_none()));
+    let bob_id = users[1]["id"].as_str().unwrap();
+    server
+        .delete(&format!("/api/admin/users/{}", bob_id))
+        .authorization_bearer(ADMIN_TOKEN)
+        .await
+        .assert_status_ok();
+    server
+        .get(&paste_path)
+        .authorization_bearer(bob)
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_single_user_mode_needs_no_token() {
+    let (server, _temp, _locks) = {
+        let _lock = env_lock().lock().expect("env lock");
+        let _enabled = EnvGuard::remove("LOCALPASTE_MULTI_USER");
+        setup_test_server()
+    };
+
+    server
+        .post("/api/paste")
+        .json(&json!({ "content": "open", "name": "open" }))
+        .await
+        .assert_status_ok();
+    server.get("/api/pastes").await.assert_status_ok();
+    server
+        .get("/api/admin/users")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
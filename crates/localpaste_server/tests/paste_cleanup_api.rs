@@ -0,0 +1,59 @@
+//! Integration tests for clipboard and per-request paste cleanup transforms.
+
+mod support;
+
+use axum::http::StatusCode;
+use localpaste_core::env::{env_lock, EnvGuard};
+use serde_json::{json, Value};
+use support::setup_test_server;
+
+const DIRTY: &str = "“Read” https://example.com/post?id=3&utm_source=mail\u{00A0}  \n\n\n\nbye\n";
+
+#[tokio::test]
+async fn cleanup_applies_to_clipboard_source_and_explicit_header() {
+    let (server, _temp, _locks) = {
+        let _lock = env_lock().lock().expect("env lock");
+        let _cleanup = EnvGuard::set("LOCALPASTE_CLIPBOARD_CLEANUP", "urls,whitespace");
+        setup_test_server()
+    };
+
+    let plain: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": DIRTY, "name": "api" }))
+        .await
+        .json();
+    assert_eq!(plain["content"], DIRTY);
+
+    let clipboard: Value = server
+        .post("/api/paste")
+        .add_header("x-localpaste-source", "clipboard")
+        .json(&json!({ "content": DIRTY, "name": "clip" }))
+        .await
+        .json();
+    assert_eq!(
+        clipboard["content"],
+        "“Read” https://example.com/post?id=3\n\nbye\n"
+    );
+
+    let explicit: Value = server
+        .post("/api/paste")
+        .add_header("x-localpaste-cleanup", "quotes")
+        .json(&json!({ "content": DIRTY, "name": "web" }))
+        .await
+        .json();
+    assert!(
+        explicit["content"]
+            .as_str()
+            .expect("content")
+            .starts_with("\"Read\" https://example.com/post?id=3&utm_source=mail"),
+        "{}",
+        explicit["content"]
+    );
+
+    let rejected = server
+        .post("/api/paste")
+        .add_header("x-localpaste-cleanup", "emoji")
+        .json(&json!({ "content": DIRTY }))
+        .await;
+    assert_eq!(rejected.status_code(), StatusCode::BAD_REQUEST);
+}
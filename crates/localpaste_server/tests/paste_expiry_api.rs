@@ -0,0 +1,129 @@
+//! Integration tests for paste expiry fields and the expired-paste sweep.
+
+mod support;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use chrono::{DateTime, Duration, Utc};
+use localpaste_core::MAX_PASTE_EXPIRES_IN_SECS;
+use localpaste_server::{
+    create_app, expiry::sweep_expired_pastes, AppState, Database, LockOwnerId, PasteLockManager,
+};
+use serde_json::json;
+use std::sync::Arc;
+use support::{setup_test_server, test_config_for_db_path};
+use tempfile::TempDir;
+
+fn expires_at(paste: &serde_json::Value) -> Option<DateTime<Utc>> {
+    paste["expires_at"]
+        .as_str()
+        .map(|raw| raw.parse().expect("expires_at timestamp"))
+}
+
+#[tokio::test]
+async fn test_expires_in_sets_and_clears_expiry() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let created: serde_json::Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "short-lived", "expires_in": 3600 }))
+        .await
+        .json();
+    let created_at: DateTime<Utc> = created["created_at"].as_str().unwrap().parse().unwrap();
+    assert_eq!(
+        expires_at(&created),
+        Some(created_at + Duration::seconds(3600))
+    );
+    let id = created["id"].as_str().expect("id").to_string();
+
+    let kept: serde_json::Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "forever" }))
+        .await
+        .json();
+    assert!(expires_at(&kept).is_none());
+
+    let renamed: serde_json::Value = server
+        .put(&format!("/api/paste/{}", id))
+        .json(&json!({ "name": "renamed" }))
+        .await
+        .json();
+    assert_eq!(expires_at(&renamed), expires_at(&created));
+
+    let cleared: serde_json::Value = server
+        .put(&format!("/api/paste/{}", id))
+        .json(&json!({ "expires_in": 0 }))
+        .await
+        .json();
+    assert!(expires_at(&cleared).is_none());
+
+    let too_long = server
+        .post("/api/paste")
+        .json(&json!({ "content": "x", "expires_in": MAX_PASTE_EXPIRES_IN_SECS + 1 }))
+        .await;
+    assert_eq!(too_long.status_code(), StatusCode::BAD_REQUEST);
+    let too_long = server
+        .put(&format!("/api/paste/{}", id))
+        .json(&json!({ "expires_in": MAX_PASTE_EXPIRES_IN_SECS + 1 }))
+        .await;
+    assert_eq!(too_long.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_sweep_deletes_only_expired_unlocked_pastes() {
+    let temp = TempDir::new().expect("temp dir");
+    let config = test_config_for_db_path(&temp.path().join("test.db"));
+    let db = Database::new(config.db_path.as_str()).expect("open db");
+    let locks = Arc::new(PasteLockManager::default());
+    let state = AppState::with_locks(config, db, locks.clone());
+    let server = TestServer::new(create_app(state.clone(), false)).expect("server");
+
+    let mut ids = Vec::new();
+    for body in [
+        json!({ "content": "expires", "expires_in": 60 }),
+        json!({ "content": "locked", "expires_in": 60 }),
+        json!({ "content": "later", "expires_in": 86_400 }),
+        json!({ "content": "forever" }),
+    ] {
+        let created: serde_json::Value = server.post("/api/paste").json(&body).await.json();
+        ids.push(created["id"].as_str().expect("id").to_string());
+    }
+    let owner = LockOwnerId::new("gui");
+    locks.acquire(&ids[1], &owner).expect("lock");
+
+    assert_eq!(sweep_expired_pastes(&state, Utc::now()).expect("sweep"), 0);
+
+    let later = Utc::now() + Duration::seconds(120);
+    assert_eq!(sweep_expired_pastes(&state, later).expect("sweep"), 1);
+    let statuses: Vec<StatusCode> = {
+        let mut statuses = Vec::new();
+        for id in &ids {
+            statuses.push(
+                server
+                    .get(&format!("/api/paste/{}", id))
+                    .await
+                    .status_code(),
+            );
+        }
+        statuses
+    };
+    assert_eq!(
+        statuses,
+        vec![
+            StatusCode::NOT_FOUND,
+            StatusCode::OK,
+            StatusCode::OK,
+            StatusCode::OK
+        ]
+    );
+
+    locks.release(&ids[1], &owner).expect("unlock");
+    assert_eq!(sweep_expired_pastes(&state, later).expect("sweep"), 1);
+    assert_eq!(
+        server
+            .get(&format!("/api/paste/{}", ids[1]))
+            .await
+            .status_code(),
+        StatusCode::NOT_FOUND
+    );
+}
@@ -0,0 +1,156 @@
+//! Integration tests for rendered HTML/PDF paste exports and whole-store exports.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_export_renders_highlighted_html_and_pdf() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let created = server
+        .post("/api/paste")
+        .json(&json!({
+            "content": "fn main() {\n    println!(\"<hi>\");\n}\n",
+            "name": "demo script",
+            "language": "rust"
+        }))
+        .await;
+    assert_eq!(created.status_code(), StatusCode::OK);
+    let created: serde_json::Value = created.json();
+    let export_path = format!("/api/paste/{}/export", created["id"].as_str().unwrap());
+
+    let html = server.get(&export_path).await;
+    assert_eq!(html.status_code(), StatusCode::OK);
+    html.assert_header("content-type", "text/html; charset=utf-8");
+    html.assert_header(
+        "content-disposition",
+        "attachment; filename=\"demo_script.html\"",
+    );
+    let body = html.text();
+    assert!(body.contains("<title>demo script</title>"));
+    assert!(body.contains("&lt;hi&gt;"));
+    assert!(body.contains("class=\"lp-"));
+
+    let pdf = server.get(&format!("{}?format=pdf", export_path)).await;
+    assert_eq!(pdf.status_code(), StatusCode::OK);
+    pdf.assert_header("content-type", "application/pdf");
+    assert!(pdf.as_bytes().starts_with(b"%PDF-"));
+
+    let bad = server.get(&format!("{}?format=docx", export_path)).await;
+    assert_eq!(bad.status_code(), StatusCode::BAD_REQUEST);
+
+    let missing = server.get("/api/paste/missing/export").await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_html_route_inlines_colors_for_the_requested_theme() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let created = server
+        .post("/api/paste")
+        .json(&json!({
+            "content": "def greet():\n    return \"<hi>\"\n",
+            "name": "greet",
+            "language": "py"
+        }))
+        .await;
+    assert_eq!(created.status_code(), StatusCode::OK);
+    let created: serde_json::Value = created.json();
+    let html_path = format!("/api/paste/{}/html", created["id"].as_str().unwrap());
+
+    let light = server.get(&html_path).await;
+    assert_eq!(light.status_code(), StatusCode::OK);
+    light.assert_header("content-type", "text/html; charset=utf-8");
+    assert!(light.maybe_header("content-disposition").is_none());
+    let light = light.text();
+    assert!(light.contains("<title>greet</title>"));
+    assert!(light.contains("<span style=\"color:#"));
+    assert!(!light.contains("class=\"lp-"));
+    assert!(light.contains("&lt;"));
+    assert!(!light.contains("<hi>"));
+
+    let dark = server
+        .get(&format!("{}?theme=base16-ocean.dark", html_path))
+        .await;
+    assert_eq!(dark.status_code(), StatusCode::OK);
+    assert!(dark.text().contains("background-color:#2b303b"));
+
+    let unknown = server.get(&format!("{}?theme=neon", html_path)).await;
+    assert_eq!(unknown.status_code(), StatusCode::BAD_REQUEST);
+    assert!(unknown.text().contains("InspiredGitHub"));
+
+    let missing = server.get("/api/paste/missing/html").await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_export_all_returns_full_rows_beyond_list_limit() {
+    let (server, _temp, _locks) = setup_test_server();
+    for idx in 0..105 {
+        server
+            .post("/api/paste")
+            .json(&json!({ "content": format!("body {}\r\n", idx), "name": format!("p{}", idx) }))
+            .await
+            .assert_status_ok();
+    }
+
+    let response = server.get("/api/pastes/export").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let pastes: Vec<serde_json::Value> = response.json();
+    assert_eq!(pastes.len(), 105);
+    let first = pastes
+        .iter()
+        .find(|paste| paste["name"] == "p0")
+        .expect("p0 exported");
+    assert!(first["content"].as_str().unwrap().starts_with("body 0"));
+    assert!(first["line_ending"].is_string());
+    assert!(first["created_at"].is_string());
+}
+
+#[tokio::test]
+async fn test_export_archive_zips_every_paste_with_manifest() {
+    let (server, _temp, _locks) = setup_test_server();
+    let folder: serde_json::Value = server
+        .post("/api/folder")
+        .json(&json!({ "name": "Work" }))
+        .await
+        .json();
+    for (name, folder_id) in [("plan", folder["id"].clone()), ("scratch", json!(null))] {
+        server
+            .post("/api/paste")
+            .json(&json!({
+                "content": format!("{} body\n", name),
+                "name": name,
+                "language": "markdown",
+                "folder_id": folder_id
+            }))
+            .await
+            .assert_status_ok();
+    }
+
+    let response = server.get("/api/export").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    response.assert_header("content-type", "application/zip");
+    let disposition = response.header("content-disposition");
+    let disposition = disposition.to_str().unwrap();
+    assert!(disposition.starts_with("attachment; filename=\"localpaste-export-"));
+    let archive = response.as_bytes();
+    assert!(archive.starts_with(b"PK\x03\x04"));
+    for needle in [
+        "Work/plan.md",
+        "scratch.md",
+        "manifest.json",
+        "localpaste-store-export",
+    ] {
+        assert!(
+            archive
+                .windows(needle.len())
+                .any(|window| window == needle.as_bytes()),
+            "{needle}"
+        );
+    }
+}
@@ -0,0 +1,56 @@
+//! Integration tests for JSON/YAML/TOML paste lint responses.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use support::setup_test_server;
+
+async fn create(server: &axum_test::TestServer, content: &str, language: &str) -> String {
+    let response = server
+        .post("/api/paste")
+        .json(&json!({ "content": content, "language": language }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let created: serde_json::Value = response.json();
+    created["id"].as_str().expect("id").to_string()
+}
+
+#[tokio::test]
+async fn test_paste_lint_reports_first_parse_error_line() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let invalid = create(&server, "{\n  \"a\": 1,\n  \"b\" 2\n}\n", "json").await;
+    let response = server.get(&format!("/api/paste/{}/lint", invalid)).await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["checked"], true);
+    assert_eq!(body["valid"], false);
+    assert_eq!(body["language"], "json");
+    assert_eq!(body["error"]["line"], 3);
+    assert!(body["error"]["message"]
+        .as_str()
+        .is_some_and(|m| !m.is_empty()));
+
+    let valid = create(&server, "a = 1\n", "toml").await;
+    let body: serde_json::Value = server
+        .get(&format!("/api/paste/{}/lint", valid))
+        .await
+        .json();
+    assert_eq!(body["checked"], true);
+    assert_eq!(body["valid"], true);
+    assert!(body["error"].is_null());
+}
+
+#[tokio::test]
+async fn test_paste_lint_skips_languages_without_a_check() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let id = create(&server, "fn main() {", "rust").await;
+    let body: serde_json::Value = server.get(&format!("/api/paste/{}/lint", id)).await.json();
+    assert_eq!(body["checked"], false);
+    assert_eq!(body["valid"], true);
+
+    let missing = server.get("/api/paste/does-not-exist/lint").await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
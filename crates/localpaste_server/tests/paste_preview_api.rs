@@ -0,0 +1,129 @@
+//! Integration tests for paste preview and list snippet responses.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_paste_preview_truncates_lines_and_reports_metadata() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let content = (1..=25)
+        .map(|idx| format!("line {}", idx))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let create_response = server
+        .post("/api/paste")
+        .json(&json!({
+            "content": content,
+            "name": "preview-me",
+            "tags": ["ops"]
+        }))
+        .await;
+    assert_eq!(create_response.status_code(), StatusCode::OK);
+    let created: serde_json::Value = create_response.json();
+    let paste_id = created["id"].as_str().expect("id").to_string();
+
+    let response = server
+        .get(&format!("/api/paste/{}/preview", paste_id))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let preview: serde_json::Value = response.json();
+    let snippet = preview["snippet"].as_str().expect("snippet");
+    assert_eq!(snippet.lines().count(), 10);
+    assert!(snippet.starts_with("line 1\n"));
+    assert!(snippet.ends_with("line 10"));
+    assert_eq!(preview["truncated"], true);
+    assert_eq!(preview["name"], "preview-me");
+    assert_eq!(preview["tags"], json!(["ops"]));
+    assert_eq!(
+        preview["content_len"],
+        created["content"].as_str().unwrap().len()
+    );
+    assert_eq!(preview["created_at"], created["created_at"]);
+    assert!(preview.get("content").is_none());
+
+    let short = server
+        .get(&format!("/api/paste/{}/preview?lines=3", paste_id))
+        .await;
+    let short: serde_json::Value = short.json();
+    assert_eq!(short["snippet"], "line 1\nline 2\nline 3");
+
+    let capped = server
+        .get(&format!("/api/paste/{}/preview?lines=1000", paste_id))
+        .await;
+    let capped: serde_json::Value = capped.json();
+    assert_eq!(capped["snippet"].as_str().unwrap().lines().count(), 25);
+    assert_eq!(capped["truncated"], false);
+
+    let missing = server.get("/api/paste/does-not-exist/preview").await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_list_meta_include_snippet_is_opt_in_and_collapses_newlines() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let create_response = server
+        .post("/api/paste")
+        .json(&json!({
+            "content": "fn main() {\n    println!(\"hi\");\n}\n",
+            "name": "snippet-me"
+        }))
+        .await;
+    assert_eq!(create_response.status_code(), StatusCode::OK);
+
+    let plain = server.get("/api/pastes/meta").await;
+    assert_eq!(plain.status_code(), StatusCode::OK);
+    let plain: Vec<serde_json::Value> = plain.json();
+    assert_eq!(plain.len(), 1);
+    assert!(plain[0].get("snippet").is_none());
+
+    let with_snippet = server.get("/api/pastes/meta?include_snippet=true").await;
+    assert_eq!(with_snippet.status_code(), StatusCode::OK);
+    let with_snippet: Vec<serde_json::Value> = with_snippet.json();
+    assert_eq!(with_snippet.len(), 1);
+    assert_eq!(with_snippet[0]["name"], "snippet-me");
+    assert_eq!(
+        with_snippet[0]["snippet"],
+        "fn main() { println!(\"hi\"); }"
+    );
+    assert!(with_snippet[0].get("content").is_none());
+}
+
+#[tokio::test]
+async fn test_search_include_matches_reports_content_byte_ranges() {
+    let (server, _temp, _locks) = setup_test_server();
+    server
+        .post("/api/paste")
+        .json(&json!({ "content": "alpha\nBeta beta\n", "name": "greek" }))
+        .await
+        .assert_status_ok();
+
+    let plain: Vec<serde_json::Value> = server.get("/api/search?q=beta").await.json();
+    assert_eq!(plain.len(), 1);
+    assert!(plain[0].get("matches").is_none());
+
+    let with_matches: Vec<serde_json::Value> = server
+        .get("/api/search?q=beta&include_matches=true")
+        .await
+        .json();
+    assert_eq!(
+        with_matches[0]["matches"],
+        json!([{ "start": 6, "end": 10 }, { "start": 11, "end": 15 }])
+    );
+
+    let by_name: Vec<serde_json::Value> = server
+        .get("/api/search?q=greek&include_matches=true")
+        .await
+        .json();
+    assert_eq!(by_name[0]["matches"], json!([]));
+
+    let meta: Vec<serde_json::Value> = server
+        .get("/api/search/meta?q=greek&include_matches=true")
+        .await
+        .json();
+    assert!(meta[0].get("matches").is_none());
+}
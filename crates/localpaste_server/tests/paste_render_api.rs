@@ -0,0 +1,59 @@
+//! Integration tests for sanitized markdown rendering at `/api/paste/:id/render`.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use support::setup_test_server;
+
+async fn render(server: &axum_test::TestServer, body: Value) -> axum_test::TestResponse {
+    let created: Value = server.post("/api/paste").json(&body).await.json();
+    server
+        .get(&format!(
+            "/api/paste/{}/render",
+            created["id"].as_str().unwrap()
+        ))
+        .await
+}
+
+#[tokio::test]
+async fn test_render_converts_markdown_and_strips_unsafe_html() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let response = render(
+        &server,
+        json!({
+            "content": "# Plan\n\n- [ ] ship\n\n<img src=x onerror=alert(1)>\n\n[x](javascript:alert(1))\n",
+            "language": "markdown"
+        }),
+    )
+    .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    response.assert_header("content-type", "text/html; charset=utf-8");
+    response.assert_header("x-localpaste-rendered", "markdown");
+    let body = response.text();
+    assert!(body.contains("<h1>Plan</h1>"));
+    assert!(body.contains("type=\"checkbox\""));
+    assert!(!body.contains("<img"));
+    assert!(!body.contains("javascript:"));
+
+    let missing = server.get("/api/paste/missing/render").await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_render_honors_non_markdown_language() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let response = render(
+        &server,
+        json!({ "content": "# comment <b>\nprint(1)\n", "language": "python" }),
+    )
+    .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    response.assert_header("x-localpaste-rendered", "code");
+    assert_eq!(
+        response.text(),
+        "<pre><code class=\"language-python\"># comment &lt;b&gt;\nprint(1)\n</code></pre>\n"
+    );
+}
@@ -0,0 +1,87 @@
+//! Integration tests for per-source and per-folder paste size limits.
+
+mod support;
+
+use axum::http::StatusCode;
+use localpaste_server::{models::folder::Folder, Database};
+use serde_json::json;
+use support::{setup_test_server, test_config_for_db_path, test_server_for_config};
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_folder_and_import_overrides_replace_the_global_limit() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("size-limits.db");
+    let folder = Folder::new("Logs".to_string());
+    let folder_id = folder.id.clone();
+
+    let mut config = test_config_for_db_path(&db_path);
+    config.max_paste_size = 16;
+    config.size_limits.import = Some(8);
+    config.size_limits.folders.insert(folder_id.clone(), 64);
+
+    {
+        let db = Database::new(&config.db_path).unwrap();
+        db.folders.create(&folder).unwrap();
+    }
+    let (server, _locks) = test_server_for_config(config);
+
+    let body = "x".repeat(32);
+    let unfiled = server
+        .post("/api/paste")
+        .json(&json!({ "content": body, "name": "unfiled" }))
+        .await;
+    assert_eq!(unfiled.status_code(), StatusCode::BAD_REQUEST);
+
+    let filed = server
+        .post("/api/paste")
+        .json(&json!({ "content": body, "name": "filed", "folder_id": folder_id }))
+        .await;
+    assert_eq!(filed.status_code(), StatusCode::OK);
+    let filed: serde_json::Value = filed.json();
+    let filed_id = filed["id"].as_str().unwrap();
+
+    // Updates without a folder field are checked against the paste's current folder.
+    let grown = server
+        .put(&format!("/api/paste/{}", filed_id))
+        .json(&json!({ "content": "y".repeat(48) }))
+        .await;
+    assert_eq!(grown.status_code(), StatusCode::OK);
+
+    let moved_out = server
+        .put(&format!("/api/paste/{}", filed_id))
+        .json(&json!({ "content": "z".repeat(48), "folder_id": "" }))
+        .await;
+    assert_eq!(moved_out.status_code(), StatusCode::BAD_REQUEST);
+
+    let imported = server
+        .post("/api/paste")
+        .add_header("x-localpaste-source", "import")
+        .json(&json!({ "content": "x".repeat(12), "name": "imported" }))
+        .await;
+    assert_eq!(imported.status_code(), StatusCode::BAD_REQUEST);
+    let error: serde_json::Value = imported.json();
+    assert_eq!(
+        error["error"].as_str(),
+        Some("Paste size exceeds maximum of 8 bytes")
+    );
+
+    let imported_into_folder = server
+        .post("/api/paste")
+        .add_header("x-localpaste-source", "import")
+        .json(&json!({ "content": "x".repeat(12), "name": "imported", "folder_id": folder_id }))
+        .await;
+    assert_eq!(imported_into_folder.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_import_source_falls_back_to_the_global_limit() {
+    let (server, _temp_dir, _locks) = setup_test_server();
+
+    let imported = server
+        .post("/api/paste")
+        .add_header("x-localpaste-source", "import")
+        .json(&json!({ "content": "x".repeat(64), "name": "imported" }))
+        .await;
+    assert_eq!(imported.status_code(), StatusCode::OK);
+}
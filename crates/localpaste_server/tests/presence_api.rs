@@ -0,0 +1,53 @@
+//! Integration tests for editor presence under `/api/paste/:id/presence`.
+
+mod support;
+
+use axum::http::StatusCode;
+use localpaste_server::LockOwnerId;
+use serde_json::json;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_presence_lists_lock_holders_and_announced_viewers() {
+    let (server, _temp, locks) = setup_test_server();
+    let created: serde_json::Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "alpha" }))
+        .await
+        .json();
+    let id = created["id"].as_str().unwrap();
+    let path = format!("/api/paste/{}/presence", id);
+
+    let empty: serde_json::Value = server.get(&path).await.json();
+    assert_eq!(empty, json!([]));
+
+    locks
+        .acquire(id, &LockOwnerId::new("gui-2".to_string()))
+        .expect("gui opens paste");
+    let touched = server.put(&path).json(&json!({ "owner": "cli" })).await;
+    assert_eq!(touched.status_code(), StatusCode::OK);
+    let touched: serde_json::Value = touched.json();
+    assert_eq!(
+        touched,
+        json!([
+            { "owner": "gui-2", "state": "editing" },
+            { "owner": "cli", "state": "viewing" },
+        ])
+    );
+
+    let all: serde_json::Value = server.get("/api/presence").await.json();
+    assert_eq!(all[id], touched);
+
+    let left = server.delete(&format!("{}?owner=cli", path)).await;
+    assert_eq!(left.status_code(), StatusCode::NO_CONTENT);
+    let remaining: serde_json::Value = server.get(&path).await.json();
+    assert_eq!(remaining, json!([{ "owner": "gui-2", "state": "editing" }]));
+
+    let blank = server.put(&path).json(&json!({ "owner": "  " })).await;
+    assert_eq!(blank.status_code(), StatusCode::BAD_REQUEST);
+    let missing = server
+        .put("/api/paste/missing-paste/presence")
+        .json(&json!({ "owner": "web" }))
+        .await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
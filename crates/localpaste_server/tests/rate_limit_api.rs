@@ -0,0 +1,62 @@
+//! Integration tests for per-client API write rate limiting.
+
+mod support;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use localpaste_core::config::WriteRateLimit;
+use localpaste_server::rate_limit::WriteRateLimiter;
+use localpaste_server::{create_app, AppState, Database, PasteLockManager};
+use serde_json::json;
+use std::sync::Arc;
+use support::test_config_for_db_path;
+use tempfile::TempDir;
+
+fn limited_server(burst: u32, per_second: u32) -> (TestServer, TempDir) {
+    let temp = TempDir::new().expect("temp dir");
+    let config = test_config_for_db_path(&temp.path().join("test.db"));
+    let db = Database::new(config.db_path.as_str()).expect("open db");
+    let mut state = AppState::with_locks(config, db, Arc::new(PasteLockManager::default()));
+    state.rate_limiter = Arc::new(WriteRateLimiter::new(Some(WriteRateLimit {
+        burst,
+        per_second,
+    })));
+    let server = TestServer::new(create_app(state, false)).expect("server");
+    (server, temp)
+}
+
+#[tokio::test]
+async fn test_writes_past_burst_get_429_with_retry_after() {
+    let (server, _temp) = limited_server(2, 1);
+
+    for _ in 0..2 {
+        let response = server
+            .post("/api/paste")
+            .json(&json!({ "content": "ok" }))
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+    }
+    let limited = server
+        .post("/api/paste")
+        .json(&json!({ "content": "too many" }))
+        .await;
+    assert_eq!(limited.status_code(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(limited.header("retry-after"), "1");
+    let body: serde_json::Value = limited.json();
+    assert!(body["error"].as_str().is_some());
+
+    let listed = server.get("/api/pastes/meta").await;
+    assert_eq!(listed.status_code(), StatusCode::OK);
+    let listed: Vec<serde_json::Value> = listed.json();
+    assert_eq!(listed.len(), 2);
+}
+
+#[tokio::test]
+async fn test_reads_are_not_rate_limited() {
+    let (server, _temp, _locks) = support::setup_test_server();
+
+    for _ in 0..(localpaste_core::DEFAULT_WRITE_RATE_LIMIT_BURST + 10) {
+        let response = server.get("/api/pastes/meta").await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+    }
+}
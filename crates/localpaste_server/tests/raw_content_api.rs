@@ -0,0 +1,95 @@
+//! Integration tests for raw paste content range requests.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_raw_content_serves_ranges_and_validates_etags() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let created = server
+        .post("/api/paste")
+        .json(&json!({ "content": "0123456789", "name": "raw" }))
+        .await;
+    assert_eq!(created.status_code(), StatusCode::OK);
+    let created: serde_json::Value = created.json();
+    let raw_path = format!("/api/paste/{}/raw", created["id"].as_str().unwrap());
+
+    let full = server.get(&raw_path).await;
+    assert_eq!(full.status_code(), StatusCode::OK);
+    assert_eq!(full.text(), "0123456789");
+    full.assert_header("accept-ranges", "bytes");
+    let etag = full
+        .headers()
+        .get("etag")
+        .and_then(|value| value.to_str().ok())
+        .expect("etag")
+        .to_string();
+
+    let partial = server.get(&raw_path).add_header("range", "bytes=2-5").await;
+    assert_eq!(partial.status_code(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(partial.text(), "2345");
+    partial.assert_header("content-range", "bytes 2-5/10");
+
+    let suffix = server.get(&raw_path).add_header("range", "bytes=-3").await;
+    assert_eq!(suffix.status_code(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(suffix.text(), "789");
+
+    let past_end = server.get(&raw_path).add_header("range", "bytes=20-").await;
+    assert_eq!(past_end.status_code(), StatusCode::RANGE_NOT_SATISFIABLE);
+    past_end.assert_header("content-range", "bytes */10");
+
+    let not_modified = server
+        .get(&raw_path)
+        .add_header("if-none-match", etag.as_str())
+        .await;
+    assert_eq!(not_modified.status_code(), StatusCode::NOT_MODIFIED);
+
+    // A stale If-Range validator ignores the range and returns the full body.
+    let stale_if_range = server
+        .get(&raw_path)
+        .add_header("range", "bytes=0-1")
+        .add_header("if-range", "\"stale\"")
+        .await;
+    assert_eq!(stale_if_range.status_code(), StatusCode::OK);
+    assert_eq!(stale_if_range.text(), "0123456789");
+
+    let missing = server.get("/api/paste/does-not-exist/raw").await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_short_raw_route_serves_plain_text_with_filename_hint() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let created: serde_json::Value = server
+        .post("/api/paste")
+        .json(&json!({
+            "content": "fn main() {}\n",
+            "name": "main",
+            "language": "rust"
+        }))
+        .await
+        .json();
+    let id = created["id"].as_str().unwrap();
+
+    let raw = server.get(&format!("/raw/{}", id)).await;
+    assert_eq!(raw.status_code(), StatusCode::OK);
+    assert_eq!(raw.text(), "fn main() {}\n");
+    raw.assert_header("content-type", "text/plain; charset=utf-8");
+    raw.assert_header("content-disposition", "inline; filename=\"main.rs\"");
+
+    // Short ids and ranges work the same as on the API route.
+    let partial = server
+        .get(&format!("/raw/{}", &id[..8]))
+        .add_header("range", "bytes=0-1")
+        .await;
+    assert_eq!(partial.status_code(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(partial.text(), "fn");
+
+    let missing = server.get("/raw/does-not-exist").await;
+    assert_eq!(missing.status_code(), StatusCode::NOT_FOUND);
+}
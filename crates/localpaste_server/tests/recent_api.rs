@@ -0,0 +1,83 @@
+//! Integration tests for per-client recent history at `GET /api/recent`.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use support::{setup_test_server, test_config_for_db_path, test_server_for_config};
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_recent_is_rejected_unless_enabled() {
+    let (server, _temp, _locks) = setup_test_server();
+    let created: serde_json::Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "alpha" }))
+        .await
+        .json();
+    server
+        .get(&format!("/api/paste/{}", created["id"].as_str().unwrap()))
+        .await
+        .assert_status_ok();
+
+    let response = server.get("/api/recent").await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_recent_tracks_opens_per_client_and_merges_by_default() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = test_config_for_db_path(&temp_dir.path().join("recent.db"));
+    config.recent_history = true;
+    let (server, _locks) = test_server_for_config(config);
+
+    let mut ids = Vec::new();
+    for content in ["alpha", "beta", "gamma"] {
+        let created: serde_json::Value = server
+            .post("/api/paste")
+            .json(&json!({ "content": content }))
+            .await
+            .json();
+        ids.push(created["id"].as_str().unwrap().to_string());
+    }
+    let (alpha, beta, gamma) = (&ids[0], &ids[1], &ids[2]);
+
+    server
+        .get(&format!("/api/paste/{}", alpha))
+        .add_header("x-localpaste-client", "cli")
+        .await
+        .assert_status_ok();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    server
+        .put(&format!("/api/paste/{}", beta))
+        .add_header("x-localpaste-client", "web")
+        .json(&json!({ "content": "beta v2" }))
+        .await
+        .assert_status_ok();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    server
+        .get(&format!("/api/paste/{}", gamma))
+        .await
+        .assert_status_ok();
+    server
+        .delete(&format!("/api/paste/{}", gamma))
+        .await
+        .assert_status_ok();
+
+    let merged: serde_json::Value = server.get("/api/recent").await.json();
+    let merged = merged.as_array().unwrap();
+    assert_eq!(merged.len(), 2);
+    assert_eq!(merged[0]["id"], beta.as_str());
+    assert_eq!(merged[0]["client"], "web");
+    assert!(merged[0]["opened_at_ms"].is_u64());
+    assert_eq!(merged[1]["id"], alpha.as_str());
+    assert_eq!(merged[1]["client"], "cli");
+
+    let cli: serde_json::Value = server.get("/api/recent?client=cli").await.json();
+    assert_eq!(cli.as_array().unwrap().len(), 1);
+    assert_eq!(cli[0]["id"], alpha.as_str());
+
+    let limited: serde_json::Value = server.get("/api/recent?limit=1").await.json();
+    assert_eq!(limited.as_array().unwrap().len(), 1);
+    assert_eq!(limited[0]["id"], beta.as_str());
+}
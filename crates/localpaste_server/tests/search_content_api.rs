@@ -0,0 +1,57 @@
+//! Integration tests for search responses that inline full paste content.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_search_include_content_returns_full_pastes() {
+    let (server, _temp, _locks) = setup_test_server();
+    for body in [
+        json!({ "content": "needle in line one\nline two", "name": "kept" }),
+        json!({ "content": "needle secret", "name": "one-time", "burn_after_read": true }),
+    ] {
+        server
+            .post("/api/paste")
+            .json(&body)
+            .await
+            .assert_status_ok();
+    }
+
+    let plain = server.get("/api/search?q=needle").await;
+    assert_eq!(plain.header("x-localpaste-response-shape"), "meta-only");
+    let plain: Vec<serde_json::Value> = plain.json();
+    assert!(plain.iter().all(|row| row.get("content").is_none()));
+
+    let response = server
+        .get("/api/search?q=needle&include_content=true&include_matches=true")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert!(response
+        .maybe_header("x-localpaste-response-shape")
+        .is_none());
+    assert_eq!(response.header("x-localpaste-content-omitted"), "1");
+    let rows: Vec<serde_json::Value> = response.json();
+    assert_eq!(rows.len(), 2);
+    let kept = rows.iter().find(|row| row["name"] == "kept").expect("kept");
+    assert_eq!(kept["content"], "needle in line one\nline two");
+    assert_eq!(kept["matches"], json!([{ "start": 0, "end": 6 }]));
+    let burn = rows
+        .iter()
+        .find(|row| row["name"] == "one-time")
+        .expect("one-time");
+    assert!(burn["content"].is_null());
+
+    let without_matches: Vec<serde_json::Value> = server
+        .get("/api/search?q=line&include_content=true")
+        .await
+        .json();
+    assert_eq!(without_matches.len(), 1);
+    assert!(without_matches[0].get("matches").is_none());
+    assert_eq!(
+        without_matches[0]["content"],
+        "needle in line one\nline two"
+    );
+}
@@ -0,0 +1,87 @@
+//! Integration tests for structured search filters.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use std::time::Duration;
+use support::setup_test_server;
+
+#[tokio::test]
+async fn test_search_combines_query_with_structured_filters() {
+    let (server, _temp, _locks) = setup_test_server();
+    let mut created = Vec::new();
+    for (name, language, tags, folder_path) in [
+        ("deploy-rust", "rust", vec!["work"], Some("work/logs")),
+        ("deploy-python", "python", vec!["Work"], None),
+        ("deploy-shell", "bash", vec!["home"], None),
+    ] {
+        let paste: serde_json::Value = server
+            .post("/api/paste")
+            .json(&json!({
+                "content": format!("{} steps", name),
+                "name": name,
+                "language": language,
+                "tags": tags,
+                "folder_path": folder_path,
+            }))
+            .await
+            .json();
+        created.push(paste);
+        // Distinct updated_at values so time bounds can split the pastes.
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+
+    let names = |rows: Vec<serde_json::Value>| -> Vec<String> {
+        let mut names: Vec<String> = rows
+            .iter()
+            .map(|row| row["name"].as_str().expect("name").to_string())
+            .collect();
+        names.sort();
+        names
+    };
+
+    let by_tag = server.get("/api/search?q=deploy&tag=WORK").await;
+    assert_eq!(by_tag.status_code(), StatusCode::OK);
+    assert_eq!(names(by_tag.json()), vec!["deploy-python", "deploy-rust"]);
+
+    let by_tag_and_language = server
+        .get("/api/search/meta?q=deploy&tag=work&language=py")
+        .await;
+    assert_eq!(names(by_tag_and_language.json()), vec!["deploy-python"]);
+
+    let by_folder = server.get("/api/search?q=deploy&folder=work/logs").await;
+    assert_eq!(by_folder.status_code(), StatusCode::OK);
+    assert_eq!(names(by_folder.json()), vec!["deploy-rust"]);
+
+    let second = created[1]["updated_at"].as_str().expect("updated_at");
+    let after = server
+        .get("/api/search?q=deploy")
+        .add_query_param("after", second)
+        .await;
+    assert_eq!(names(after.json()), vec!["deploy-python", "deploy-shell"]);
+    let before = server
+        .get("/api/search?q=deploy&tag=work")
+        .add_query_param("before", second)
+        .await;
+    assert_eq!(names(before.json()), vec!["deploy-rust"]);
+    let by_date = server.get("/api/search?q=deploy&before=2000-01-01").await;
+    assert_eq!(by_date.status_code(), StatusCode::OK);
+    assert!(names(by_date.json()).is_empty());
+}
+
+#[tokio::test]
+async fn test_search_rejects_invalid_structured_filters() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let bad_time = server.get("/api/search?q=x&after=last-week").await;
+    assert_eq!(bad_time.status_code(), StatusCode::BAD_REQUEST);
+
+    let missing_folder = server.get("/api/search?q=x&folder=nowhere").await;
+    assert_eq!(missing_folder.status_code(), StatusCode::NOT_FOUND);
+
+    let both_folders = server
+        .get("/api/search/meta?q=x&folder=work&folder_id=abc")
+        .await;
+    assert_eq!(both_folders.status_code(), StatusCode::BAD_REQUEST);
+}
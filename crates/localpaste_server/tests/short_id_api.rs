@@ -0,0 +1,80 @@
+//! Integration tests for resolving unique id prefixes in paste routes.
+
+mod support;
+
+use axum::http::StatusCode;
+use localpaste_server::{models::paste::Paste, Database};
+use serde_json::{json, Value};
+use support::{setup_test_server, test_config_for_db_path, test_server_for_config};
+use tempfile::TempDir;
+
+fn seed(db_path: &std::path::Path, ids: &[&str]) {
+    let db = Database::new(db_path.to_str().unwrap()).expect("open db");
+    for id in ids {
+        let mut paste = Paste::new(format!("content of {}", id), id.to_string());
+        paste.id = id.to_string();
+        db.pastes.create(&paste).expect("seed paste");
+    }
+}
+
+#[tokio::test]
+async fn test_paste_routes_accept_unique_id_prefixes() {
+    let temp = TempDir::new().expect("temp dir");
+    let db_path = temp.path().join("test.db");
+    seed(&db_path, &["3f2a0001", "3f2b0002", "3f2b0003"]);
+    let (server, _locks) = test_server_for_config(test_config_for_db_path(&db_path));
+
+    let paste = server.get("/api/paste/3f2a").await.json::<Value>();
+    assert_eq!(paste["id"], "3f2a0001");
+    server
+        .get("/api/paste/3F2A/raw")
+        .await
+        .assert_text("content of 3f2a0001");
+    server
+        .get("/api/paste/3f2b0002/versions")
+        .await
+        .assert_status_ok();
+
+    let ambiguous = server.get("/api/paste/3f2b").await;
+    assert_eq!(ambiguous.status_code(), StatusCode::NOT_FOUND);
+    let body = ambiguous.json::<Value>();
+    assert_eq!(body["candidates"], json!(["3f2b0002", "3f2b0003"]));
+    assert!(body["error"].as_str().unwrap().contains("Ambiguous"));
+
+    // Prefixes below the minimum length never match.
+    let short = server.get("/api/paste/3f2").await;
+    assert_eq!(short.status_code(), StatusCode::NOT_FOUND);
+    assert!(short.json::<Value>().get("candidates").is_none());
+
+    let diff = server
+        .post("/api/diff")
+        .json(&json!({
+            "left": { "paste_id": "3f2a" },
+            "right": { "paste_id": "3f2b0003" }
+        }))
+        .await;
+    assert_eq!(diff.status_code(), StatusCode::OK);
+
+    server.delete("/api/paste/3f2a").await.assert_status_ok();
+    server
+        .get("/api/paste/3f2a0001")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_generated_ids_resolve_from_short_prefix() {
+    let (server, _temp, _locks) = setup_test_server();
+    let created = server
+        .post("/api/paste")
+        .json(&json!({ "content": "short id" }))
+        .await
+        .json::<Value>();
+    let id = created["id"].as_str().unwrap();
+
+    let fetched = server
+        .get(&format!("/api/paste/{}/preview", &id[..8]))
+        .await
+        .json::<Value>();
+    assert_eq!(fetched["id"], id);
+}
@@ -0,0 +1,101 @@
+//! Integration tests for paste slugs and `/api/paste/by-slug/:slug`.
+
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use support::setup_test_server;
+
+async fn create(server: &axum_test::TestServer, content: &str) -> String {
+    let created = server
+        .post("/api/paste")
+        .json(&json!({ "content": content }))
+        .await
+        .json::<Value>();
+    created["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_slug_assignment_lookup_and_rename() {
+    let (server, _temp, _locks) = setup_test_server();
+    let id = create(&server, "server { listen 80; }").await;
+
+    let updated = server
+        .put(&format!("/api/paste/{}/slug", id))
+        .json(&json!({ "slug": "Nginx-Config" }))
+        .await
+        .json::<Value>();
+    assert_eq!(updated["slug"], "nginx-config");
+
+    let by_slug = server
+        .get("/api/paste/by-slug/nginx-config")
+        .await
+        .json::<Value>();
+    assert_eq!(by_slug["id"], id.as_str());
+    // Slugs also work wherever a paste id is accepted.
+    server
+        .get("/api/paste/nginx-config/raw")
+        .await
+        .assert_text("server { listen 80; }");
+
+    server
+        .put("/api/paste/nginx-config/slug")
+        .json(&json!({ "slug": "nginx" }))
+        .await
+        .assert_status_ok();
+    server
+        .get("/api/paste/by-slug/nginx-config")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    let cleared = server
+        .put(&format!("/api/paste/{}/slug", id))
+        .json(&json!({ "slug": null }))
+        .await
+        .json::<Value>();
+    assert!(cleared["slug"].is_null());
+    server
+        .get("/api/paste/by-slug/nginx")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_slug_conflicts_and_validation() {
+    let (server, _temp, _locks) = setup_test_server();
+    let first = create(&server, "first").await;
+    let second = create(&server, "second").await;
+
+    server
+        .put(&format!("/api/paste/{}/slug", first))
+        .json(&json!({ "slug": "shared" }))
+        .await
+        .assert_status_ok();
+    let conflict = server
+        .put(&format!("/api/paste/{}/slug", second))
+        .json(&json!({ "slug": "SHARED" }))
+        .await;
+    assert_eq!(conflict.status_code(), StatusCode::CONFLICT);
+
+    server
+        .put(&format!("/api/paste/{}/slug", second))
+        .json(&json!({ "slug": "bad slug" }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    server
+        .put("/api/paste/missing-paste/slug")
+        .json(&json!({ "slug": "free" }))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    // Deleting the owner releases the slug.
+    server
+        .delete(&format!("/api/paste/{}", first))
+        .await
+        .assert_status_ok();
+    server
+        .put(&format!("/api/paste/{}/slug", second))
+        .json(&json!({ "slug": "shared" }))
+        .await
+        .assert_status_ok();
+}
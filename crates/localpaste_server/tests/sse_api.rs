@@ -0,0 +1,193 @@
+//! Integration tests for the `/api/events` Server-Sent Events stream.
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
+use axum_test::TestServer;
+use futures_util::StreamExt;
+use localpaste_core::env::{env_lock, EnvGuard};
+use localpaste_server::{create_app, AppState, Config, Database, PasteLockManager};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+fn app() -> (Router, TestServer, TempDir) {
+    let temp = TempDir::new().expect("temp dir");
+    let config = Config {
+        port: 0,
+        db_path: temp
+            .path()
+            .join("test.db")
+            .to_str()
+            .expect("db path")
+            .to_string(),
+        max_paste_size: 10_000_000,
+        auto_save_interval: 2000,
+        auto_backup: false,
+        size_limits: Default::default(),
+        flush_policy: Default::default(),
+        recent_history: false,
+    };
+    let db = Database::new(&config.db_path).expect("open db");
+    let state = AppState::with_locks(config, db, Arc::new(PasteLockManager::default()));
+    let app = create_app(state, false);
+    let server = TestServer::new(app.clone()).expect("server");
+    (app, server, temp)
+}
+
+/// Read SSE messages until `count` have arrived, as `(id, data)` pairs.
+async fn read_messages(body: Body, count: usize) -> Vec<(Option<String>, Value)> {
+    let mut stream = body.into_data_stream();
+    let mut buffer = String::new();
+    let mut messages = Vec::new();
+    while messages.len() < count {
+        let chunk = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("event before timeout")
+            .expect("stream open")
+            .expect("chunk");
+        buffer.push_str(std::str::from_utf8(&chunk).expect("utf8"));
+        while let Some(end) = buffer.find("\n\n") {
+            let block: String = buffer.drain(..end + 2).collect();
+            let mut id = None;
+            let mut data = None;
+            for line in block.lines() {
+                if let Some(value) = line.strip_prefix("id:") {
+                    id = Some(value.trim().to_string());
+                } else if let Some(value) = line.strip_prefix("data:") {
+                    data = Some(serde_json::from_str(value.trim()).expect("json data"));
+                }
+            }
+            if let Some(data) = data {
+                messages.push((id, data));
+            }
+        }
+    }
+    messages
+}
+
+async fn create_paste(server: &TestServer, content: &str) -> String {
+    let paste: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": content }))
+        .await
+        .json();
+    paste["id"].as_str().expect("paste id").to_string()
+}
+
+#[tokio::test]
+async fn test_events_resume_after_last_event_id() {
+    let (app, server, _temp) = app();
+    let _first = create_paste(&server, "one").await;
+    let second = create_paste(&server, "two").await;
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::get("/api/events")
+                .header("last-event-id", "1")
+                .body(Body::empty())
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/event-stream"
+    );
+    let body = response.into_body();
+    let third = create_paste(&server, "three").await;
+
+    let messages = read_messages(body, 2).await;
+    assert_eq!(
+        messages,
+        vec![
+            (
+                Some("2".to_string()),
+                json!({ "target": "paste", "id": second, "change": "created" })
+            ),
+            (
+                Some("3".to_string()),
+                json!({ "target": "paste", "id": third, "change": "created" })
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_events_report_lagged_when_resume_point_is_unknown() {
+    let (app, _server, _temp) = app();
+    let response = app
+        .oneshot(
+            Request::get("/api/events")
+                .header("last-event-id", "42")
+                .body(Body::empty())
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+
+    let messages = read_messages(response.into_body(), 1).await;
+    assert_eq!(messages, vec![(None, json!({ "change": "lagged" }))]);
+}
+
+#[tokio::test]
+async fn test_events_deliver_owner_deletions_in_multi_user_mode() {
+    const ADMIN_TOKEN: &str = "events-admin-token";
+    let (app, server, _temp) = {
+        let _lock = env_lock().lock().expect("env lock");
+        let _enabled = EnvGuard::set("LOCALPASTE_MULTI_USER", "1");
+        let _admin = EnvGuard::set("LOCALPASTE_ADMIN_TOKEN", ADMIN_TOKEN);
+        app()
+    };
+    let mut tokens = Vec::new();
+    for name in ["alice", "bob"] {
+        let user: Value = server
+            .post("/api/admin/users")
+            .authorization_bearer(ADMIN_TOKEN)
+            .json(&json!({ "name": name }))
+            .await
+            .json();
+        tokens.push(user["token"].as_str().expect("token").to_string());
+    }
+    let (alice, bob) = (tokens[0].as_str(), tokens[1].as_str());
+    let mut ids = Vec::new();
+    for token in [alice, bob] {
+        let paste: Value = server
+            .post("/api/paste")
+            .authorization_bearer(token)
+            .json(&json!({ "content": "private" }))
+            .await
+            .json();
+        ids.push(paste["id"].as_str().expect("paste id").to_string());
+    }
+
+    let response = app
+        .oneshot(
+            Request::get("/api/events")
+                .header(header::AUTHORIZATION, format!("Bearer {}", alice))
+                .body(Body::empty())
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body();
+    // Bob's private deletion stays hidden; Alice's own arrives.
+    for (token, id) in [(bob, &ids[1]), (alice, &ids[0])] {
+        server
+            .delete(&format!("/api/paste/{}", id))
+            .authorization_bearer(token)
+            .await
+            .assert_status_ok();
+    }
+
+    let messages = read_messages(body, 1).await;
+    assert_eq!(
+        messages[0].1,
+        json!({ "target": "paste", "id": ids[0], "change": "deleted" })
+    );
+}
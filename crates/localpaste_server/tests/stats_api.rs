@@ -0,0 +1,80 @@
+//! Integration tests for the `/api/stats` endpoint.
+
+use axum_test::TestServer;
+use localpaste_server::{create_app, AppState, Config, Database, PasteLockManager};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn server() -> (TestServer, TempDir) {
+    let temp = TempDir::new().expect("temp dir");
+    let config = Config {
+        port: 0,
+        db_path: temp
+            .path()
+            .join("test.db")
+            .to_str()
+            .expect("db path")
+            .to_string(),
+        max_paste_size: 10_000_000,
+        auto_save_interval: 2000,
+        auto_backup: false,
+        size_limits: Default::default(),
+        flush_policy: Default::default(),
+        recent_history: false,
+    };
+    let db = Database::new(&config.db_path).expect("open db");
+    let state = AppState::with_locks(config, db, Arc::new(PasteLockManager::default()));
+    let server = TestServer::new(create_app(state, false)).expect("server");
+    (server, temp)
+}
+
+async fn create_paste(server: &TestServer, body: Value) -> String {
+    let paste: Value = server.post("/api/paste").json(&body).await.json();
+    paste["id"].as_str().expect("paste id").to_string()
+}
+
+#[tokio::test]
+async fn test_stats_aggregate_languages_folders_and_largest() {
+    let (server, _temp) = server();
+    let folder: Value = server
+        .post("/api/folder")
+        .json(&json!({ "name": "work" }))
+        .await
+        .json();
+    let folder_id = folder["id"].as_str().expect("folder id");
+    let big = create_paste(
+        &server,
+        json!({ "content": "fn main() {}\n".repeat(10), "language": "rust", "folder_id": folder_id }),
+    )
+    .await;
+    create_paste(
+        &server,
+        json!({ "content": "print(1)", "language": "python" }),
+    )
+    .await;
+
+    let stats: Value = server.get("/api/stats").await.json();
+    assert_eq!(stats["total_pastes"], 2);
+    assert_eq!(stats["total_bytes"], 130 + 8);
+    assert_eq!(
+        stats["languages"],
+        json!([
+            { "language": "python", "pastes": 1, "bytes": 8 },
+            { "language": "rust", "pastes": 1, "bytes": 130 },
+        ])
+    );
+    assert_eq!(
+        stats["folders"],
+        json!([
+            { "folder_id": null, "name": null, "pastes": 1, "bytes": 8 },
+            { "folder_id": folder_id, "name": "work", "pastes": 1, "bytes": 130 },
+        ])
+    );
+    assert_eq!(stats["largest"].as_array().expect("largest").len(), 2);
+    assert_eq!(stats["largest"][0]["id"], big);
+
+    let limited: Value = server.get("/api/stats?largest=1").await.json();
+    assert_eq!(limited["largest"].as_array().expect("largest").len(), 1);
+    assert_eq!(limited["total_pastes"], 2);
+}
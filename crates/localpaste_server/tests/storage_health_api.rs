@@ -0,0 +1,104 @@
+//! Integration tests for `/api/health`, storage alerts, and backup pruning.
+
+mod support;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use localpaste_core::config::StorageAlertLimits;
+use localpaste_core::db::backup::BackupManager;
+use localpaste_server::{create_app, AppState, Database, LockOwnerId, PasteLockManager};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use support::test_config_for_db_path;
+use tempfile::TempDir;
+
+fn server_with_backups(
+    limits: StorageAlertLimits,
+    backups: usize,
+) -> (TestServer, TempDir, BackupManager) {
+    let temp = TempDir::new().expect("temp dir");
+    let config = test_config_for_db_path(&temp.path().join("test.db"));
+    let db_path = config.db_path.clone();
+    let db = Database::new(db_path.as_str()).expect("open db");
+    let mut state = AppState::with_locks(config, db, Arc::new(PasteLockManager::default()));
+    state.storage_limits = limits;
+    let manager = BackupManager::new(db_path.as_str()).with_passphrase(None);
+    for _ in 0..backups {
+        manager.create_backup(state.db.db.as_ref()).expect("backup");
+    }
+    let server = TestServer::new(create_app(state, false)).expect("server");
+    (server, temp, manager)
+}
+
+#[tokio::test]
+async fn test_health_reports_version_database_and_locks() {
+    let (server, _temp, locks) = support::setup_test_server();
+    server
+        .post("/api/paste")
+        .json(&json!({ "content": "one" }))
+        .await
+        .assert_status_ok();
+    locks
+        .acquire("held", &LockOwnerId::new("gui"))
+        .expect("acquire");
+
+    let response = server.get("/api/health").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body: Value = response.json();
+    assert_eq!(body["status"], "ok");
+    assert_eq!(body["alerts"], json!([]));
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(body["database"]["open"], true);
+    assert_eq!(body["database"]["paste_count"], 1);
+    assert!(body["database"]["path"]
+        .as_str()
+        .expect("path")
+        .ends_with("test.db"));
+    assert_eq!(
+        body["locks"],
+        json!({ "healthy": true, "locked_pastes": 1, "mutating_pastes": 0 })
+    );
+}
+
+#[tokio::test]
+async fn test_health_reports_alerts_with_remediation() {
+    let (server, _temp, _manager) = server_with_backups(
+        StorageAlertLimits {
+            database_bytes: Some(1),
+            backups_bytes: Some(1),
+        },
+        1,
+    );
+    let response = server.get("/api/health").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body: Value = response.json();
+    assert_eq!(body["status"], "warning");
+    let alerts = body["alerts"].as_array().expect("alerts");
+    assert_eq!(alerts.len(), 2);
+    assert_eq!(alerts[0]["kind"], "database_size");
+    assert_eq!(alerts[0]["remediation"], "cleanup");
+    assert_eq!(alerts[1]["kind"], "backups_size");
+    assert_eq!(alerts[1]["remediation"], "prune_backups");
+    assert!(alerts[1]["size_bytes"].as_u64().expect("size") > 1);
+}
+
+#[tokio::test]
+async fn test_prune_backups_keeps_newest() {
+    let limits = StorageAlertLimits {
+        database_bytes: None,
+        backups_bytes: Some(1),
+    };
+    let (server, _temp, manager) = server_with_backups(limits, 3);
+
+    let response = server
+        .post("/api/admin/backups/prune")
+        .json(&json!({ "keep_last": 1 }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body: Value = response.json();
+    assert_eq!(body["deleted"].as_array().expect("deleted").len(), 2);
+    assert_eq!(manager.list_backups().expect("list").len(), 1);
+
+    let defaulted: Value = server.post("/api/admin/backups/prune").await.json();
+    assert_eq!(defaulted["deleted"], json!([]));
+}
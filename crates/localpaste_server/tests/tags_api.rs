@@ -0,0 +1,96 @@
+//! Integration tests for listing, renaming, and removing tags across pastes.
+
+mod support;
+
+use axum::http::StatusCode;
+use localpaste_server::LockOwnerId;
+use serde_json::{json, Value};
+use support::setup_test_server;
+
+async fn create_tagged(server: &axum_test::TestServer, name: &str, tags: &[&str]) -> String {
+    let response = server
+        .post("/api/paste")
+        .json(&json!({ "content": format!("{} body", name), "name": name, "tags": tags }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    response.json::<Value>()["id"].as_str().unwrap().to_string()
+}
+
+async fn tags_of(server: &axum_test::TestServer, id: &str) -> Value {
+    server
+        .get(&format!("/api/paste/{}", id))
+        .await
+        .json::<Value>()["tags"]
+        .clone()
+}
+
+#[tokio::test]
+async fn test_tags_list_rename_and_delete_across_pastes() {
+    let (server, _temp, _locks) = setup_test_server();
+    let first = create_tagged(&server, "first", &["Rust", "cli"]).await;
+    let second = create_tagged(&server, "second", &["rust", "tooling"]).await;
+    let _third = create_tagged(&server, "third", &["notes"]).await;
+
+    let tags: Value = server.get("/api/tags").await.json();
+    assert_eq!(tags[0], json!({ "tag": "rust", "count": 2 }));
+    assert_eq!(tags.as_array().unwrap().len(), 4);
+
+    let renamed = server
+        .put("/api/tags/RUST")
+        .json(&json!({ "name": " Tooling " }))
+        .await;
+    assert_eq!(renamed.status_code(), StatusCode::OK);
+    assert_eq!(
+        renamed.json::<Value>(),
+        json!({ "tag": "Tooling", "updated": 2 })
+    );
+    assert_eq!(tags_of(&server, &first).await, json!(["Tooling", "cli"]));
+    assert_eq!(tags_of(&server, &second).await, json!(["Tooling"]));
+    let filtered: Vec<Value> = server.get("/api/pastes/meta?tag=tooling").await.json();
+    assert_eq!(filtered.len(), 2);
+
+    let deleted = server.delete("/api/tags/cli").await;
+    assert_eq!(deleted.status_code(), StatusCode::OK);
+    assert_eq!(
+        deleted.json::<Value>(),
+        json!({ "tag": "cli", "updated": 1 })
+    );
+    assert_eq!(tags_of(&server, &first).await, json!(["Tooling"]));
+
+    let tags: Value = server.get("/api/tags").await.json();
+    assert_eq!(
+        tags,
+        json!([{ "tag": "tooling", "count": 2 }, { "tag": "notes", "count": 1 }])
+    );
+
+    assert_eq!(
+        server.delete("/api/tags/cli").await.status_code(),
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        server
+            .put("/api/tags/tooling")
+            .json(&json!({ "name": "  " }))
+            .await
+            .status_code(),
+        StatusCode::BAD_REQUEST
+    );
+}
+
+#[tokio::test]
+async fn test_tag_changes_reject_locked_pastes_without_partial_writes() {
+    let (server, _temp, locks) = setup_test_server();
+    let open = create_tagged(&server, "open", &["draft"]).await;
+    let other = create_tagged(&server, "other", &["draft"]).await;
+    let owner = LockOwnerId::new("tag-editor".to_string());
+    locks.acquire(&open, &owner).expect("acquire");
+
+    let response = server.delete("/api/tags/draft").await;
+    assert_eq!(response.status_code(), StatusCode::LOCKED);
+    assert_eq!(tags_of(&server, &open).await, json!(["draft"]));
+    assert_eq!(tags_of(&server, &other).await, json!(["draft"]));
+
+    locks.release(&open, &owner).expect("release");
+    let response = server.delete("/api/tags/draft").await;
+    assert_eq!(response.json::<Value>()["updated"], 2);
+}
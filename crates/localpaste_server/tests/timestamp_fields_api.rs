@@ -0,0 +1,78 @@
+//! Integration tests for the epoch-millisecond twins of JSON timestamps.
+
+mod support;
+
+use serde_json::{json, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
+use support::setup_test_server;
+
+fn assert_timestamp_pair(value: &Value, key: &str) {
+    let raw = value[key]
+        .as_str()
+        .unwrap_or_else(|| panic!("{key} string"));
+    assert!(raw.ends_with('Z'), "{key} should be UTC RFC3339: {raw}");
+    let millis = value[format!("{key}_ms")]
+        .as_i64()
+        .unwrap_or_else(|| panic!("{key}_ms in {value}"));
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock after epoch")
+        .as_millis() as i64;
+    assert!(
+        (now - 60_000..=now).contains(&millis),
+        "{key}_ms={millis} should be within the last minute of {now}"
+    );
+}
+
+#[tokio::test]
+async fn json_responses_pair_timestamps_with_epoch_millis() {
+    let (server, _temp, _locks) = setup_test_server();
+    let folder: Value = server
+        .post("/api/folder")
+        .json(&json!({ "name": "docs" }))
+        .await
+        .json();
+    assert_timestamp_pair(&folder, "created_at");
+    let folder_id = folder["id"].as_str().expect("folder id");
+
+    let paste: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "hello", "folder_id": folder_id }))
+        .await
+        .json();
+    assert_timestamp_pair(&paste, "created_at");
+    assert_timestamp_pair(&paste, "updated_at");
+    let paste_id = paste["id"].as_str().expect("paste id");
+
+    let list: Value = server.get("/api/pastes/meta").await.json();
+    assert_timestamp_pair(&list[0], "updated_at");
+
+    let folders: Value = server.get("/api/folders").await.json();
+    assert_timestamp_pair(&folders[0], "created_at");
+    assert_timestamp_pair(&folders[0], "last_updated");
+
+    let preview: Value = server
+        .get(&format!("/api/paste/{paste_id}/preview"))
+        .await
+        .json();
+    assert_timestamp_pair(&preview, "created_at");
+    assert_timestamp_pair(&preview, "updated_at");
+}
+
+#[tokio::test]
+async fn non_json_responses_are_left_untouched() {
+    let (server, _temp, _locks) = setup_test_server();
+    let paste: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "{\"created_at\": \"2026-01-01T00:00:00Z\"}" }))
+        .await
+        .json();
+    let id = paste["id"].as_str().expect("paste id");
+    let raw = server.get(&format!("/api/paste/{id}/raw")).await;
+    raw.assert_status_ok();
+    assert_eq!(raw.text(), "{\"created_at\": \"2026-01-01T00:00:00Z\"}");
+    assert_eq!(
+        paste["content"],
+        "{\"created_at\": \"2026-01-01T00:00:00Z\"}"
+    );
+}
@@ -0,0 +1,114 @@
+//! Integration tests for multipart uploads at `POST /api/paste/upload`.
+
+mod support;
+
+use axum::http::StatusCode;
+use axum_test::multipart::{MultipartForm, Part};
+use support::{setup_test_server, test_config_for_db_path, test_server_for_config};
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_upload_infers_name_and_language_from_filename() {
+    let (server, _temp, _locks) = setup_test_server();
+    let content = "fn main() {\n    println!(\"hi\");\n}\n";
+
+    let response = server
+        .post("/api/paste/upload")
+        .multipart(MultipartForm::new().add_part(
+            "file",
+            Part::bytes(content.as_bytes().to_vec()).file_name("src/main.rs"),
+        ))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let created: serde_json::Value = response.json();
+    assert_eq!(created["name"], "main.rs");
+    assert_eq!(created["language"], "rust");
+    assert_eq!(created["content"].as_str(), Some(content));
+
+    let fetched: serde_json::Value = server
+        .get(&format!("/api/paste/{}", created["id"].as_str().unwrap()))
+        .await
+        .json();
+    assert_eq!(fetched["content"].as_str(), Some(content));
+}
+
+#[tokio::test]
+async fn test_upload_form_fields_override_filename_inference() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let response = server
+        .post("/api/paste/upload")
+        .multipart(
+            MultipartForm::new()
+                .add_part(
+                    "file",
+                    Part::bytes(b"key: value\n".to_vec()).file_name("config.txt"),
+                )
+                .add_text("name", "deploy config")
+                .add_text("language", "yml")
+                .add_text("tags", "ops, deploy,")
+                .add_text("folder_id", ""),
+        )
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let created: serde_json::Value = response.json();
+    assert_eq!(created["name"], "deploy config");
+    assert_eq!(created["language"], "yaml");
+    assert_eq!(created["tags"], serde_json::json!(["ops", "deploy"]));
+    assert!(created["folder_id"].is_null());
+}
+
+#[tokio::test]
+async fn test_upload_rejects_missing_file_and_bad_fields() {
+    let (server, _temp, _locks) = setup_test_server();
+
+    let missing = server
+        .post("/api/paste/upload")
+        .multipart(MultipartForm::new().add_text("name", "no file"))
+        .await;
+    assert_eq!(missing.status_code(), StatusCode::BAD_REQUEST);
+
+    let bad_flag = server
+        .post("/api/paste/upload")
+        .multipart(
+            MultipartForm::new()
+                .add_part("file", Part::bytes(b"x".to_vec()).file_name("x.txt"))
+                .add_text("burn_after_read", "maybe"),
+        )
+        .await;
+    assert_eq!(bad_flag.status_code(), StatusCode::BAD_REQUEST);
+
+    let not_utf8 = server
+        .post("/api/paste/upload")
+        .multipart(MultipartForm::new().add_part(
+            "file",
+            Part::bytes(vec![0xff, 0xfe, 0x00]).file_name("blob.bin"),
+        ))
+        .await;
+    assert_eq!(not_utf8.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_upload_body_limit_does_not_include_json_escape_budget() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = test_config_for_db_path(&temp_dir.path().join("upload.db"));
+    config.max_paste_size = 1024 * 1024;
+    let (server, _locks) = test_server_for_config(config);
+
+    // Fits the 6x JSON budget but not the multipart route's limit.
+    let oversized = vec![b'a'; 2 * 1024 * 1024];
+    let response = server
+        .post("/api/paste/upload")
+        .multipart(
+            MultipartForm::new().add_part("file", Part::bytes(oversized).file_name("big.log")),
+        )
+        .await;
+    assert_eq!(response.status_code(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let fits = vec![b'a'; 1024 * 1024];
+    let response = server
+        .post("/api/paste/upload")
+        .multipart(MultipartForm::new().add_part("file", Part::bytes(fits).file_name("ok.log")))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+}
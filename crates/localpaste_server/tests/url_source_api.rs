@@ -0,0 +1,147 @@
+//! Integration tests for source URL links and refreshes at `/api/paste/:id/source`.
+
+mod support;
+
+use axum::{extract::State, http::StatusCode, routing::get, Router};
+use axum_test::TestServer;
+use chrono::{Duration, Utc};
+use localpaste_server::{
+    create_app, url_refresh::sweep_due_sources, AppState, Database, PasteLockManager,
+};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use support::{setup_test_server, test_config_for_db_path};
+use tempfile::TempDir;
+
+/// Serve `/conf.toml` from shared text on a local port; other paths are 404.
+async fn spawn_origin(body: Arc<Mutex<String>>) -> String {
+    let app = Router::new()
+        .route(
+            "/conf.toml",
+            get(|State(body): State<Arc<Mutex<String>>>| async move {
+                body.lock().expect("origin body").clone()
+            }),
+        )
+        .with_state(body);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind origin");
+    let addr = listener.local_addr().expect("origin addr");
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    format!("http://{}", addr)
+}
+
+async fn create_paste(server: &TestServer, content: &str) -> String {
+    let created: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": content, "name": "conf.toml" }))
+        .await
+        .json();
+    created["id"].as_str().expect("id").to_string()
+}
+
+#[tokio::test]
+async fn test_refresh_pulls_changes_and_records_failures() {
+    let (server, _temp, _locks) = setup_test_server();
+    let body = Arc::new(Mutex::new("port = 1\n".to_string()));
+    let origin = spawn_origin(body.clone()).await;
+    let id = create_paste(&server, "stale").await;
+    let source_path = format!("/api/paste/{}/source", id);
+    let refresh_path = format!("{}/refresh", source_path);
+
+    let unlinked = server.post(&refresh_path).await;
+    assert_eq!(unlinked.status_code(), StatusCode::NOT_FOUND);
+    let bad = server
+        .put(&source_path)
+        .json(&json!({ "url": "file:///etc/hosts" }))
+        .await;
+    assert_eq!(bad.status_code(), StatusCode::BAD_REQUEST);
+    let too_often = server
+        .put(&source_path)
+        .json(&json!({ "url": format!("{}/conf.toml", origin), "refresh_interval_secs": 1 }))
+        .await;
+    assert_eq!(too_often.status_code(), StatusCode::BAD_REQUEST);
+
+    let linked: Value = server
+        .put(&source_path)
+        .json(&json!({ "url": format!("{}/conf.toml", origin), "refresh_interval_secs": 3600 }))
+        .await
+        .json();
+    assert_eq!(linked["refresh_interval_secs"], 3600);
+    assert!(linked["last_checked_at"].is_null());
+
+    let first: Value = server.post(&refresh_path).await.json();
+    assert_eq!(first["changed"], true);
+    let paste: Value = server.get(&format!("/api/paste/{}", id)).await.json();
+    assert_eq!(paste["content"], "port = 1\n");
+    let versions: Vec<Value> = server
+        .get(&format!("/api/paste/{}/versions", id))
+        .await
+        .json();
+    assert!(!versions.is_empty());
+
+    let unchanged: Value = server.post(&refresh_path).await.json();
+    assert_eq!(unchanged["changed"], false);
+    *body.lock().expect("origin body") = "port = 2\n".to_string();
+    let second: Value = server.post(&refresh_path).await.json();
+    assert_eq!(second["changed"], true);
+    assert!(second["source"]["last_error"].is_null());
+
+    server
+        .put(&source_path)
+        .json(&json!({ "url": format!("{}/gone.toml", origin) }))
+        .await
+        .assert_status_ok();
+    let failed = server.post(&refresh_path).await;
+    assert_eq!(failed.status_code(), StatusCode::BAD_GATEWAY);
+    let source: Value = server.get(&source_path).await.json();
+    assert!(source["last_error"].as_str().unwrap().contains("404"));
+    let paste: Value = server.get(&format!("/api/paste/{}", id)).await.json();
+    assert_eq!(paste["content"], "port = 2\n");
+
+    server.delete(&source_path).await.assert_status_ok();
+    assert_eq!(
+        server.get(&source_path).await.status_code(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn test_sweep_refreshes_only_due_sources() {
+    let temp = TempDir::new().expect("temp dir");
+    let config = test_config_for_db_path(&temp.path().join("test.db"));
+    let db = Database::new(config.db_path.as_str()).expect("open db");
+    let state = AppState::with_locks(config, db, Arc::new(PasteLockManager::default()));
+    let server = TestServer::new(create_app(state.clone(), false)).expect("server");
+    let origin = spawn_origin(Arc::new(Mutex::new("fresh".to_string()))).await;
+
+    let scheduled = create_paste(&server, "old").await;
+    let manual = create_paste(&server, "old").await;
+    for (id, interval) in [(&scheduled, json!(600)), (&manual, Value::Null)] {
+        server
+            .put(&format!("/api/paste/{}/source", id))
+            .json(&json!({ "url": format!("{}/conf.toml", origin), "refresh_interval_secs": interval }))
+            .await
+            .assert_status_ok();
+    }
+
+    let sweep = |offset_secs: i64| {
+        let state = state.clone();
+        tokio::task::spawn_blocking(move || {
+            sweep_due_sources(&state, Utc::now() + Duration::seconds(offset_secs))
+        })
+    };
+    assert_eq!(sweep(0).await.expect("join").expect("sweep"), 0);
+    assert_eq!(sweep(601).await.expect("join").expect("sweep"), 1);
+    assert_eq!(sweep(601).await.expect("join").expect("sweep"), 0);
+
+    let contents: Vec<Value> = {
+        let mut contents = Vec::new();
+        for id in [&scheduled, &manual] {
+            let paste: Value = server.get(&format!("/api/paste/{}", id)).await.json();
+            contents.push(paste["content"].clone());
+        }
+        contents
+    };
+    assert_eq!(contents, vec![json!("fresh"), json!("old")]);
+}
@@ -0,0 +1,71 @@
+//! Integration tests for the `/api/ws` change notification stream.
+
+mod support;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use localpaste_server::{create_app, AppState, Database, PasteLockManager};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use support::test_config_for_db_path;
+use tempfile::TempDir;
+
+fn http_server() -> (TestServer, TempDir) {
+    let temp = TempDir::new().expect("temp dir");
+    let config = test_config_for_db_path(&temp.path().join("test.db"));
+    let db = Database::new(config.db_path.as_str()).expect("open db");
+    let state = AppState::with_locks(config, db, Arc::new(PasteLockManager::default()));
+    let server = TestServer::builder()
+        .http_transport()
+        .build(create_app(state, false))
+        .expect("server");
+    (server, temp)
+}
+
+#[tokio::test]
+async fn test_ws_streams_paste_and_folder_changes() {
+    let (server, _temp) = http_server();
+    let mut socket = server.get_websocket("/api/ws").await.into_websocket().await;
+
+    let folder: Value = server
+        .post("/api/folder")
+        .json(&json!({ "name": "Notes" }))
+        .await
+        .json();
+    let folder_id = folder["id"].as_str().expect("folder id");
+    let paste: Value = server
+        .post("/api/paste")
+        .json(&json!({ "content": "hello", "folder_id": folder_id }))
+        .await
+        .json();
+    let paste_id = paste["id"].as_str().expect("paste id");
+    let updated = server
+        .put(&format!("/api/paste/{}", paste_id))
+        .json(&json!({ "content": "hello again" }))
+        .await;
+    assert_eq!(updated.status_code(), StatusCode::OK);
+    let deleted = server.delete(&format!("/api/paste/{}", paste_id)).await;
+    assert_eq!(deleted.status_code(), StatusCode::OK);
+
+    let expected = [
+        ("folder", folder_id, "created"),
+        ("paste", paste_id, "created"),
+        ("paste", paste_id, "updated"),
+        ("paste", paste_id, "deleted"),
+    ];
+    for (target, id, change) in expected {
+        let event: Value = socket.receive_json().await;
+        assert_eq!(
+            event,
+            json!({ "target": target, "id": id, "change": change })
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_ws_requires_upgrade_headers() {
+    let (server, _temp, _locks) = support::setup_test_server();
+
+    let response = server.get("/api/ws").await;
+    assert!(response.status_code().is_client_error());
+}
@@ -0,0 +1,230 @@
+//! Write generated data through a running server's HTTP API.
+//!
+//! Unlike direct database writes, every paste goes through the create
+//! handler, so size limits, rate limits, and folder validation apply. Rate
+//! limited writes are retried after the server's `Retry-After`. The
+//! server assigns timestamps itself; spec timestamp spreads only affect
+//! direct database writes.
+
+use localpaste_core::models::paste::Paste;
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, RETRY_AFTER};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Result of one create request the server answered.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum ApiOutcome {
+    Created,
+    /// The server refused the paste, e.g. `413` over the size limit or `429`
+    /// still rate limited after retries; generation continues.
+    Rejected {
+        status: u16,
+        message: String,
+    },
+}
+
+/// Blocking client bound to one server.
+pub(crate) struct ApiTarget {
+    client: Client,
+    base_url: String,
+}
+
+/// Attempts per request, including the first, before a `429` is reported.
+const RATE_LIMIT_ATTEMPTS: usize = 6;
+/// Upper bound on a single `Retry-After` wait.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+fn retry_after(response: &Response) -> Duration {
+    response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map_or(Duration::from_secs(1), Duration::from_secs)
+        .min(MAX_RETRY_AFTER)
+}
+
+fn error_message(response: Response) -> String {
+    let status = response.status();
+    let body = response.text().unwrap_or_default();
+    serde_json::from_str::<Value>(&body)
+        .ok()
+        .and_then(|value| value["error"].as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("HTTP {}", status))
+}
+
+impl ApiTarget {
+    /// Build a client for `base_url`, sending `token` as a bearer token.
+    ///
+    /// # Errors
+    /// Returns a message when the token is not a valid header value or the
+    /// client cannot be built.
+    pub(crate) fn new(base_url: &str, token: Option<&str>) -> Result<Self, String> {
+        let mut headers = HeaderMap::new();
+        if let Some(token) = token.filter(|token| !token.is_empty()) {
+            let value = HeaderValue::from_str(&format!("Bearer {}", token))
+                .map_err(|err| format!("invalid token: {}", err))?;
+            headers.insert(AUTHORIZATION, value);
+        }
+        headers.insert(
+            "x-localpaste-client",
+            HeaderValue::from_static("generate-test-data"),
+        );
+        let client = Client::builder()
+            .default_headers(headers)
+            .build()
+            .map_err(|err| format!("http client: {}", err))?;
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// POST `body`, waiting out `429` responses as `Retry-After` asks.
+    fn post(&self, path: &str, body: &Value) -> Result<Response, String> {
+        let url = format!("{}{}", self.base_url, path);
+        for attempt in 1.. {
+            let response = self
+                .client
+                .post(&url)
+                .json(body)
+                .send()
+                .map_err(|err| format!("POST {} failed: {}", url, err))?;
+            if response.status() != StatusCode::TOO_MANY_REQUESTS || attempt >= RATE_LIMIT_ATTEMPTS
+            {
+                return Ok(response);
+            }
+            std::thread::sleep(retry_after(&response));
+        }
+        unreachable!("attempt loop returns")
+    }
+
+    /// Create a folder and return its id.
+    ///
+    /// # Errors
+    /// Returns a message when the request fails or is refused.
+    pub(crate) fn create_folder(
+        &self,
+        name: &str,
+        parent_id: Option<&str>,
+    ) -> Result<String, String> {
+        let response = self.post(
+            "/api/folder",
+            &json!({ "name": name, "parent_id": parent_id }),
+        )?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "creating folder '{}' failed: {}",
+                name,
+                error_message(response)
+            ));
+        }
+        let folder: Value = response
+            .json()
+            .map_err(|err| format!("unexpected folder response: {}", err))?;
+        folder["id"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| "folder response has no id".to_string())
+    }
+
+    /// Create `paste` with its content, name, language, folder, and tags.
+    ///
+    /// # Errors
+    /// Returns a message when the request fails, credentials are refused, or
+    /// the server answers with a `5xx`; other refusals are
+    /// [`ApiOutcome::Rejected`].
+    pub(crate) fn create_paste(&self, paste: &Paste) -> Result<ApiOutcome, String> {
+        let response = self.post(
+            "/api/paste",
+            &json!({
+                "content": paste.content,
+                "name": paste.name,
+                "language": paste.language,
+                "language_is_manual": paste.language_is_manual,
+                "folder_id": paste.folder_id,
+                "tags": paste.tags,
+            }),
+        )?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(ApiOutcome::Created);
+        }
+        let message = error_message(response);
+        if status.is_server_error()
+            || status == StatusCode::UNAUTHORIZED
+            || status == StatusCode::FORBIDDEN
+        {
+            return Err(format!("creating paste failed: {}", message));
+        }
+        Ok(ApiOutcome::Rejected {
+            status: status.as_u16(),
+            message,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    fn respond_in_order(responses: Vec<(&'static str, &'static str)>) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let url = format!("http://{}", listener.local_addr().expect("addr"));
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().expect("accept");
+                let mut request = [0u8; 4096];
+                let _ = stream.read(&mut request);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn api_target_retries_429_reports_rejections_and_fails_on_server_errors() {
+        let url = respond_in_order(vec![
+            ("200 OK", r#"{"id": "folder-1", "name": "docs"}"#),
+            (
+                "429 Too Many Requests\r\nretry-after: 0",
+                r#"{"error": "Too many write requests; retry later"}"#,
+            ),
+            ("200 OK", r#"{"id": "paste-1"}"#),
+            (
+                "413 Payload Too Large",
+                r#"{"error": "Paste size exceeds maximum"}"#,
+            ),
+            ("500 Internal Server Error", r#"{"error": "disk full"}"#),
+        ]);
+        let target = ApiTarget::new(&url, Some("secret")).expect("client");
+        assert_eq!(
+            target.create_folder("docs", None).expect("folder"),
+            "folder-1"
+        );
+
+        let paste = Paste::new("body".to_string(), "name".to_string());
+        assert_eq!(
+            target.create_paste(&paste).expect("create"),
+            ApiOutcome::Created
+        );
+        assert_eq!(
+            target.create_paste(&paste).expect("rejected"),
+            ApiOutcome::Rejected {
+                status: 413,
+                message: "Paste size exceeds maximum".to_string()
+            }
+        );
+        let err = target.create_paste(&paste).expect_err("5xx aborts");
+        assert!(err.contains("disk full"));
+    }
+}
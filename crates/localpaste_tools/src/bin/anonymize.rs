@@ -0,0 +1,164 @@
+//! Write an anonymized copy of a LocalPaste database for sharing.
+//!
+//! The source is copied into a scratch directory and scrubbed there with
+//! `localpaste_core::db::anonymize`, then snapshotted table by table into a
+//! fresh file. Freed pages of the scratch copy can still hold the original
+//! bytes, so only the snapshot is written to `--out`.
+
+use clap::Parser;
+use localpaste_core::db::anonymize::{anonymize_database, AnonymizeReport};
+use localpaste_core::db::backup::{is_encrypted_backup, BackupManager};
+use localpaste_core::db::tables::REDB_FILE_NAME;
+use localpaste_core::Database;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "anonymize",
+    about = "Copy a database with paste text, names, and tags replaced by placeholders"
+)]
+struct Args {
+    /// Source database directory or plaintext redb backup file; never modified.
+    #[arg(long)]
+    db_path: PathBuf,
+
+    /// Directory for the anonymized database; must not exist or be empty.
+    #[arg(long)]
+    out: PathBuf,
+}
+
+fn main() {
+    match run(&Args::parse()) {
+        Ok(report) => {
+            println!("Anonymized database written.");
+            println!("  Pastes:   {}", report.pastes);
+            println!("  Versions: {}", report.versions);
+            println!("  Folders:  {}", report.folders);
+            println!("  Tags:     {}", report.tags);
+            println!("  Users:    {}", report.users);
+            println!("  Dropped:  {} side-table rows", report.cleared_rows);
+        }
+        Err(message) => {
+            eprintln!("error: {}", message);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn source_file(db_path: &Path) -> Result<PathBuf, String> {
+    if db_path.is_dir() {
+        let file = db_path.join(REDB_FILE_NAME);
+        if !file.is_file() {
+            return Err(format!("no database at '{}'", file.display()));
+        }
+        return Ok(file);
+    }
+    if !db_path.is_file() {
+        return Err(format!("'{}' does not exist", db_path.display()));
+    }
+    if is_encrypted_backup(db_path) {
+        return Err(format!(
+            "'{}' is an encrypted backup; restore it to a directory first",
+            db_path.display()
+        ));
+    }
+    Ok(db_path.to_path_buf())
+}
+
+fn check_output_dir(out: &Path) -> Result<(), String> {
+    if !out.exists() {
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(out).map_err(|err| {
+        format!(
+            "--out '{}' is not a usable directory: {}",
+            out.display(),
+            err
+        )
+    })?;
+    if entries.next().is_some() {
+        return Err(format!("--out '{}' must be empty", out.display()));
+    }
+    Ok(())
+}
+
+fn run(args: &Args) -> Result<AnonymizeReport, String> {
+    let source = source_file(&args.db_path)?;
+    check_output_dir(&args.out)?;
+
+    let scratch = tempfile::tempdir().map_err(|err| err.to_string())?;
+    let scratch_db = scratch.path().join("db");
+    fs::create_dir(&scratch_db).map_err(|err| err.to_string())?;
+    fs::copy(&source, scratch_db.join(REDB_FILE_NAME))
+        .map_err(|err| format!("copying '{}' failed: {}", source.display(), err))?;
+    let scratch_path = scratch_db
+        .to_str()
+        .ok_or_else(|| "scratch path is not UTF-8".to_string())?;
+
+    let db = Database::new(scratch_path).map_err(|err| err.to_string())?;
+    let report = anonymize_database(&db).map_err(|err| err.to_string())?;
+    let snapshot = BackupManager::new(scratch_path)
+        .with_passphrase(None)
+        .create_backup(&db.db)
+        .map_err(|err| err.to_string())?;
+    drop(db);
+
+    fs::create_dir_all(&args.out)
+        .map_err(|err| format!("creating '{}' failed: {}", args.out.display(), err))?;
+    let target = args.out.join(REDB_FILE_NAME);
+    fs::copy(&snapshot, &target)
+        .map_err(|err| format!("writing '{}' failed: {}", target.display(), err))?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use localpaste_core::models::paste::Paste;
+    use tempfile::TempDir;
+
+    #[test]
+    fn output_keeps_no_original_bytes_and_source_is_untouched() {
+        let dir = TempDir::new().expect("temp dir");
+        let source = dir.path().join("source");
+        let secret = "token=sk-live-5f3a9c";
+        {
+            let db = Database::new(source.to_str().expect("db path")).expect("db");
+            let mut paste = Paste::new(format!("{}\n", secret), "prod.env".to_string());
+            paste.tags = vec!["customer-x".to_string()];
+            db.pastes.create(&paste).expect("create");
+        }
+        let out = dir.path().join("out");
+        let args = Args {
+            db_path: source.clone(),
+            out: out.clone(),
+        };
+
+        let report = run(&args).expect("anonymize");
+        assert_eq!(report.pastes, 1);
+
+        let written = fs::read(out.join(REDB_FILE_NAME)).expect("read output");
+        for needle in [secret, "customer-x", "prod.env"] {
+            assert!(
+                !written
+                    .windows(needle.len())
+                    .any(|window| window == needle.as_bytes()),
+                "output still contains {}",
+                needle
+            );
+        }
+        let original = fs::read(source.join(REDB_FILE_NAME)).expect("read source");
+        assert!(original
+            .windows(secret.len())
+            .any(|window| window == secret.as_bytes()));
+
+        let reopened = Database::new(out.to_str().expect("out path")).expect("open output");
+        let pastes = reopened.pastes.list(10, None).expect("list");
+        assert_eq!(pastes.len(), 1);
+        assert_eq!(pastes[0].content.len(), secret.len() + 1);
+        drop(reopened);
+
+        assert!(run(&args).unwrap_err().contains("must be empty"));
+    }
+}
@@ -0,0 +1,491 @@
+//! Compare two LocalPaste databases or exports and report what changed.
+//!
+//! Each side is a database directory, a redb backup file, or a folder/store
+//! export ZIP. Databases are copied into a scratch directory before opening,
+//! so the compared store is never locked or repaired in place.
+
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use localpaste_core::archive::read_stored_zip;
+use localpaste_core::db::backup::is_encrypted_backup;
+use localpaste_core::db::paste::paste_content_hash;
+use localpaste_core::db::tables::REDB_FILE_NAME;
+use localpaste_core::folder_export::{
+    FOLDER_EXPORT_FORMAT, FOLDER_EXPORT_MANIFEST, STORE_EXPORT_FORMAT,
+};
+use localpaste_core::normalization::restore_line_endings;
+use localpaste_core::Database;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "db-diff",
+    about = "Report pastes and folders added, removed, or changed between two databases or exports"
+)]
+struct Args {
+    /// Baseline: database directory, redb backup file, or export `.zip`.
+    left: PathBuf,
+
+    /// Compared side, in any of the same forms.
+    right: PathBuf,
+
+    /// Print the report as JSON instead of text.
+    #[arg(long)]
+    json: bool,
+}
+
+/// Fields compared for one paste.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PasteEntry {
+    name: String,
+    folder_id: Option<String>,
+    language: Option<String>,
+    tags: Vec<String>,
+    updated_at: DateTime<Utc>,
+    /// Hash of the content as exported, with original line endings restored.
+    content_hash: String,
+}
+
+/// Fields compared for one folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FolderEntry {
+    name: String,
+    parent_id: Option<String>,
+}
+
+/// Comparable view of one side, keyed by id.
+#[derive(Debug, Default)]
+struct Snapshot {
+    pastes: BTreeMap<String, PasteEntry>,
+    folders: BTreeMap<String, FolderEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestFolder {
+    id: String,
+    name: String,
+    parent_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestPaste {
+    id: String,
+    name: String,
+    path: String,
+    folder_id: Option<String>,
+    language: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    format: String,
+    #[serde(default)]
+    folders: Vec<ManifestFolder>,
+    #[serde(default)]
+    pastes: Vec<ManifestPaste>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+struct Item {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+struct ChangedItem {
+    id: String,
+    name: String,
+    fields: Vec<&'static str>,
+}
+
+#[derive(Debug, Default, Serialize, PartialEq, Eq)]
+struct Changes {
+    added: Vec<Item>,
+    removed: Vec<Item>,
+    changed: Vec<ChangedItem>,
+}
+
+impl Changes {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[derive(Debug, Default, Serialize, PartialEq, Eq)]
+struct Report {
+    pastes: Changes,
+    folders: Changes,
+}
+
+fn main() {
+    match run(Args::parse()) {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(message) => {
+            eprintln!("error: {}", message);
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Returns whether both sides match.
+fn run(args: Args) -> Result<bool, String> {
+    let left = load_snapshot(&args.left)?;
+    let right = load_snapshot(&args.right)?;
+    let report = diff_snapshots(&left, &right);
+    if args.json {
+        let rendered = serde_json::to_string_pretty(&report).map_err(|err| err.to_string())?;
+        println!("{}", rendered);
+    } else {
+        print_report(&report);
+    }
+    Ok(report.pastes.is_empty() && report.folders.is_empty())
+}
+
+fn load_snapshot(path: &Path) -> Result<Snapshot, String> {
+    if path.is_dir() {
+        return load_database_copy(&path.join(REDB_FILE_NAME));
+    }
+    if !path.is_file() {
+        return Err(format!("'{}' does not exist", path.display()));
+    }
+    let is_zip = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"));
+    if is_zip {
+        let bytes = fs::read(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        return load_export(&bytes).map_err(|err| format!("{}: {}", path.display(), err));
+    }
+    if is_encrypted_backup(path) {
+        return Err(format!(
+            "'{}' is an encrypted backup; restore it to a directory first",
+            path.display()
+        ));
+    }
+    load_database_copy(path)
+}
+
+/// Opens a scratch copy of a redb file and reads it.
+fn load_database_copy(redb_file: &Path) -> Result<Snapshot, String> {
+    if !redb_file.is_file() {
+        return Err(format!("no database at '{}'", redb_file.display()));
+    }
+    let scratch = tempfile::tempdir().map_err(|err| err.to_string())?;
+    fs::copy(redb_file, scratch.path().join(REDB_FILE_NAME))
+        .map_err(|err| format!("copying '{}' failed: {}", redb_file.display(), err))?;
+    let scratch_path = scratch
+        .path()
+        .to_str()
+        .ok_or_else(|| "scratch path is not UTF-8".to_string())?;
+    let db = Database::new(scratch_path)
+        .map_err(|err| format!("opening '{}' failed: {}", redb_file.display(), err))?;
+    load_database(&db).map_err(|err| format!("reading '{}' failed: {}", redb_file.display(), err))
+}
+
+fn load_database(db: &Database) -> Result<Snapshot, localpaste_core::AppError> {
+    let mut snapshot = Snapshot::default();
+    for folder in db.folders.list()? {
+        snapshot.folders.insert(
+            folder.id,
+            FolderEntry {
+                name: folder.name,
+                parent_id: folder.parent_id,
+            },
+        );
+    }
+    let mut ids = Vec::new();
+    db.pastes.scan_meta(|meta| {
+        ids.push(meta.id);
+        Ok(())
+    })?;
+    for id in ids {
+        let Some(paste) = db.pastes.get(id.as_str())? else {
+            continue;
+        };
+        let content = restore_line_endings(paste.content.as_str(), paste.line_ending);
+        snapshot.pastes.insert(
+            paste.id,
+            PasteEntry {
+                name: paste.name,
+                folder_id: paste.folder_id,
+                language: paste.language,
+                tags: paste.tags,
+                updated_at: paste.updated_at,
+                content_hash: paste_content_hash(&content),
+            },
+        );
+    }
+    Ok(snapshot)
+}
+
+/// Reads a folder or store export from its manifest and file contents.
+fn load_export(bytes: &[u8]) -> Result<Snapshot, String> {
+    let mut files: HashMap<String, Vec<u8>> = read_stored_zip(bytes)?
+        .into_iter()
+        .map(|entry| (entry.name, entry.data))
+        .collect();
+    let manifest = files
+        .remove(FOLDER_EXPORT_MANIFEST)
+        .ok_or_else(|| format!("missing {}", FOLDER_EXPORT_MANIFEST))?;
+    let manifest: Manifest = serde_json::from_slice(&manifest)
+        .map_err(|err| format!("invalid {}: {}", FOLDER_EXPORT_MANIFEST, err))?;
+    if manifest.format != FOLDER_EXPORT_FORMAT && manifest.format != STORE_EXPORT_FORMAT {
+        return Err(format!("unsupported export format '{}'", manifest.format));
+    }
+
+    let mut snapshot = Snapshot::default();
+    for folder in manifest.folders {
+        snapshot.folders.insert(
+            folder.id,
+            FolderEntry {
+                name: folder.name,
+                parent_id: folder.parent_id,
+            },
+        );
+    }
+    for paste in manifest.pastes {
+        let data = files
+            .get(paste.path.as_str())
+            .ok_or_else(|| format!("missing file '{}' for paste {}", paste.path, paste.id))?;
+        let content =
+            std::str::from_utf8(data).map_err(|_| format!("file '{}' is not UTF-8", paste.path))?;
+        snapshot.pastes.insert(
+            paste.id,
+            PasteEntry {
+                name: paste.name,
+                folder_id: paste.folder_id,
+                language: paste.language,
+                tags: paste.tags,
+                updated_at: paste.updated_at,
+                content_hash: paste_content_hash(content),
+            },
+        );
+    }
+    Ok(snapshot)
+}
+
+fn paste_changes(left: &PasteEntry, right: &PasteEntry) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if left.name != right.name {
+        fields.push("name");
+    }
+    if left.folder_id != right.folder_id {
+        fields.push("folder");
+    }
+    if left.language != right.language {
+        fields.push("language");
+    }
+    if left.tags != right.tags {
+        fields.push("tags");
+    }
+    if left.content_hash != right.content_hash {
+        fields.push("content");
+    }
+    if left.updated_at != right.updated_at {
+        fields.push("updated_at");
+    }
+    fields
+}
+
+fn folder_changes(left: &FolderEntry, right: &FolderEntry) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if left.name != right.name {
+        fields.push("name");
+    }
+    if left.parent_id != right.parent_id {
+        fields.push("parent");
+    }
+    fields
+}
+
+/// Compares keyed entries; changed items are named by their right-hand value.
+fn diff_maps<T>(
+    left: &BTreeMap<String, T>,
+    right: &BTreeMap<String, T>,
+    name: fn(&T) -> &str,
+    changes: fn(&T, &T) -> Vec<&'static str>,
+) -> Changes {
+    let item = |id: &String, entry: &T| Item {
+        id: id.clone(),
+        name: name(entry).to_string(),
+    };
+    let mut out = Changes::default();
+    for (id, before) in left {
+        match right.get(id) {
+            None => out.removed.push(item(id, before)),
+            Some(after) => {
+                let fields = changes(before, after);
+                if !fields.is_empty() {
+                    out.changed.push(ChangedItem {
+                        id: id.clone(),
+                        name: name(after).to_string(),
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+    out.added = right
+        .iter()
+        .filter(|(id, _)| !left.contains_key(*id))
+        .map(|(id, entry)| item(id, entry))
+        .collect();
+    out
+}
+
+fn diff_snapshots(left: &Snapshot, right: &Snapshot) -> Report {
+    Report {
+        pastes: diff_maps(
+            &left.pastes,
+            &right.pastes,
+            |entry| entry.name.as_str(),
+            paste_changes,
+        ),
+        folders: diff_maps(
+            &left.folders,
+            &right.folders,
+            |entry| entry.name.as_str(),
+            folder_changes,
+        ),
+    }
+}
+
+fn print_report(report: &Report) {
+    for (kind, changes) in [("paste", &report.pastes), ("folder", &report.folders)] {
+        for item in &changes.added {
+            println!("+ {} {} \"{}\"", kind, item.id, item.name);
+        }
+        for item in &changes.removed {
+            println!("- {} {} \"{}\"", kind, item.id, item.name);
+        }
+        for item in &changes.changed {
+            println!(
+                "~ {} {} \"{}\" ({})",
+                kind,
+                item.id,
+                item.name,
+                item.fields.join(", ")
+            );
+        }
+    }
+    for (label, changes) in [("pastes", &report.pastes), ("folders", &report.folders)] {
+        println!(
+            "{}: {} added, {} removed, {} changed",
+            label,
+            changes.added.len(),
+            changes.removed.len(),
+            changes.changed.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use localpaste_core::db::TransactionOps;
+    use localpaste_core::folder_export::write_store_archive;
+    use localpaste_core::models::{folder::Folder, paste::Paste};
+    use tempfile::TempDir;
+
+    fn paste_entry(name: &str, content: &str) -> PasteEntry {
+        PasteEntry {
+            name: name.to_string(),
+            folder_id: None,
+            language: None,
+            tags: Vec::new(),
+            updated_at: DateTime::<Utc>::UNIX_EPOCH,
+            content_hash: paste_content_hash(content),
+        }
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_fields() {
+        let mut left = Snapshot::default();
+        left.pastes.insert("a".into(), paste_entry("kept", "same"));
+        left.pastes.insert("b".into(), paste_entry("edited", "old"));
+        left.pastes.insert("c".into(), paste_entry("gone", "x"));
+        left.folders.insert(
+            "f".into(),
+            FolderEntry {
+                name: "docs".into(),
+                parent_id: None,
+            },
+        );
+
+        let mut right = Snapshot::default();
+        right.pastes.insert("a".into(), paste_entry("kept", "same"));
+        let mut edited = paste_entry("renamed", "new");
+        edited.tags = vec!["rust".into()];
+        right.pastes.insert("b".into(), edited);
+        right.pastes.insert("d".into(), paste_entry("fresh", "y"));
+        right.folders.insert(
+            "f".into(),
+            FolderEntry {
+                name: "docs".into(),
+                parent_id: Some("root".into()),
+            },
+        );
+
+        let report = diff_snapshots(&left, &right);
+        assert_eq!(
+            report.pastes.added,
+            vec![Item {
+                id: "d".into(),
+                name: "fresh".into()
+            }]
+        );
+        assert_eq!(
+            report.pastes.removed,
+            vec![Item {
+                id: "c".into(),
+                name: "gone".into()
+            }]
+        );
+        assert_eq!(
+            report.pastes.changed,
+            vec![ChangedItem {
+                id: "b".into(),
+                name: "renamed".into(),
+                fields: vec!["name", "tags", "content"],
+            }]
+        );
+        assert_eq!(report.folders.changed[0].fields, vec!["parent"]);
+        assert!(diff_snapshots(&right, &right).pastes.is_empty());
+    }
+
+    #[test]
+    fn store_export_matches_its_database_until_it_changes() {
+        let dir = TempDir::new().expect("temp dir");
+        let db_path = dir.path().join("db");
+        let db = Database::new(db_path.to_str().expect("db path")).expect("db");
+        let folder = Folder::new("notes".to_string());
+        let folder_id = folder.id.clone();
+        db.folders.create(&folder).expect("create folder");
+        let filed = Paste::new("line one\r\nline two\r\n".to_string(), "crlf".to_string());
+        TransactionOps::create_paste_with_folder(&db, &filed, &folder_id).expect("filed paste");
+        let unfiled = Paste::new("fn main() {}".to_string(), "main.rs".to_string());
+        db.pastes.create(&unfiled).expect("unfiled paste");
+
+        let (archive, _) = write_store_archive(&db, Vec::new()).expect("export");
+        let export_path = dir.path().join("export.zip");
+        fs::write(&export_path, archive).expect("write export");
+
+        let exported = load_snapshot(&export_path).expect("load export");
+        assert_eq!(exported.pastes.len(), 2);
+        let live = load_snapshot(&db_path).expect("load db");
+        assert_eq!(diff_snapshots(&exported, &live), Report::default());
+
+        db.pastes.delete(unfiled.id.as_str()).expect("delete");
+        let live = load_snapshot(&db_path).expect("reload db");
+        let report = diff_snapshots(&exported, &live);
+        assert_eq!(report.pastes.removed.len(), 1);
+        assert_eq!(report.pastes.removed[0].id, unfiled.id);
+        assert!(report.folders.is_empty());
+    }
+}
@@ -17,8 +17,20 @@
 //!
 //! # Use custom database path
 //! DB_PATH=/tmp/test-db cargo run -p localpaste_tools --bin generate-test-data -- --count 100
+//!
+//! # Shape languages, sizes, folders, timestamps, and tags with a spec file
+//! cargo run -p localpaste_tools --bin generate-test-data -- --db-path /tmp/localpaste-fixtures --spec fixtures.toml
+//!
+//! # Create through a running server so handlers and limits apply
+//! cargo run -p localpaste_tools --bin generate-test-data -- --api-url http://127.0.0.1:38411 --count 500
 //! ```
+//!
+//! See [`spec`] for the spec file format.
+
+mod api_target;
+mod spec;
 
+use api_target::{ApiOutcome, ApiTarget};
 use clap::Parser;
 use localpaste_core::{
     config::resolve_db_path_with_explicit_or_env,
@@ -29,8 +41,10 @@ use localpaste_core::{
     AppError,
 };
 use rand::prelude::*;
-use std::collections::HashMap;
+use spec::{load_spec, GeneratorSpec, Sampler, SizeClass};
+use std::collections::{BTreeMap, HashMap};
 use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::time::Instant;
 
 /// Test data generator for LocalPaste performance testing.
@@ -41,26 +55,39 @@ use std::time::Instant;
 )]
 struct Args {
     /// Database path to mutate. When omitted, DB_PATH may be used instead.
-    #[arg(long)]
+    #[arg(long, conflicts_with = "api_url")]
     db_path: Option<String>,
 
+    /// Create folders and pastes through the server at this URL instead of
+    /// writing the database directly.
+    #[arg(long)]
+    api_url: Option<String>,
+
+    /// Bearer token for `--api-url` servers running in multi-user mode.
+    #[arg(long, env = "LP_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+
+    /// TOML spec with language, size, folder, timestamp, and tag distributions.
+    #[arg(long)]
+    spec: Option<PathBuf>,
+
     /// Allow writing to the platform-default LocalPaste database path.
     ///
     /// By default this tool requires `--db-path` or `DB_PATH` so test-data
     /// generation does not silently mutate the main app database.
-    #[arg(long, default_value_t = false)]
+    #[arg(long, default_value_t = false, conflicts_with = "api_url")]
     allow_default_db: bool,
 
-    /// Number of pastes to generate
-    #[arg(short, long, default_value = "1000")]
-    count: usize,
+    /// Number of pastes to generate [default: spec `count`, else 1000]
+    #[arg(short, long)]
+    count: Option<usize>,
 
-    /// Number of folders to create
-    #[arg(short, long, default_value = "20")]
-    folders: usize,
+    /// Number of folders to create [default: spec `folders.count`, else 20]
+    #[arg(short, long)]
+    folders: Option<usize>,
 
     /// Clear existing data before generating
-    #[arg(long)]
+    #[arg(long, conflicts_with = "api_url")]
     clear: bool,
 
     /// Confirm destructive operations such as `--clear`
@@ -519,17 +546,10 @@ fn generate_folder_name(rng: &mut impl Rng) -> String {
     }
 }
 
-/// Generate content of a specific size category.
-fn generate_content(rng: &mut impl Rng, size_category: &str) -> (String, &'static str) {
-    let lang = LANGUAGES.choose(rng).unwrap();
+/// Generate content in `lang` within the byte range of `size`.
+fn generate_content(rng: &mut impl Rng, lang: &LanguageTemplate, size: SizeClass) -> String {
     let base_sample = *lang.samples.choose(rng).unwrap();
-
-    let target_size = match size_category {
-        "medium" => rng.gen_range(1024..10 * 1024),     // 1-10KB
-        "large" => rng.gen_range(10 * 1024..50 * 1024), // 10-50KB
-        "very_large" => rng.gen_range(50 * 1024..256 * 1024), // 50-256KB
-        _ => rng.gen_range(100..1024),                  // small (<1KB) and fallback
-    };
+    let target_size = rng.gen_range(size.byte_range());
 
     let mut content = String::with_capacity(target_size);
 
@@ -554,7 +574,35 @@ fn generate_content(rng: &mut impl Rng, size_category: &str) -> (String, &'stati
         }
     }
 
-    (content, lang.id)
+    content
+}
+
+/// Where generated rows are written.
+enum Target {
+    Db(Database),
+    Api(ApiTarget),
+}
+
+impl Target {
+    fn create_folder(&self, name: String, parent_id: Option<String>) -> Result<String, String> {
+        match self {
+            Self::Db(db) => {
+                let folder = Folder::with_parent(name, parent_id);
+                db.folders.create(&folder).map_err(|err| err.to_string())?;
+                Ok(folder.id)
+            }
+            Self::Api(api) => api.create_folder(&name, parent_id.as_deref()),
+        }
+    }
+
+    fn create_paste(&self, paste: &Paste) -> Result<ApiOutcome, String> {
+        match self {
+            Self::Db(db) => persist_generated_paste(db, paste)
+                .map(|()| ApiOutcome::Created)
+                .map_err(|err| err.to_string()),
+            Self::Api(api) => api.create_paste(paste),
+        }
+    }
 }
 
 fn persist_generated_paste(db: &Database, paste: &Paste) -> Result<(), AppError> {
@@ -645,119 +693,113 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .with_target(false)
         .init();
 
-    let db_path =
-        resolve_db_path_with_explicit_or_env(args.db_path.clone(), args.allow_default_db)?;
-    println!("Using database at: {}", db_path);
-
-    let db = Database::new(&db_path)?;
-
-    if args.clear {
-        println!("Clearing existing data...");
-        let (deleted_pastes, deleted_folders) = clear_existing_data(&db)?;
-        assert_folder_invariants(&db)?;
-        println!(
-            "Cleared {} pastes and {} folders",
-            deleted_pastes, deleted_folders
-        );
-    }
+    let spec = match args.spec.as_deref() {
+        Some(path) => load_spec(path)?,
+        None => GeneratorSpec::default(),
+    };
+    let count = args.count.unwrap_or(spec.count);
+    let folder_count = args.folders.unwrap_or(spec.folders.count);
+    let spread_months = spec.timestamps.spread_months;
+    let language_ids: Vec<&str> = LANGUAGES.iter().map(|lang| lang.id).collect();
+    let sampler = Sampler::new(spec, &language_ids)?;
+
+    let target = match args.api_url.as_deref() {
+        Some(api_url) => {
+            println!("Using API at: {}", api_url);
+            if spread_months > 0 {
+                println!("Note: the server sets timestamps; timestamps.spread_months is ignored");
+            }
+            Target::Api(ApiTarget::new(api_url, args.token.as_deref())?)
+        }
+        None => {
+            let db_path =
+                resolve_db_path_with_explicit_or_env(args.db_path.clone(), args.allow_default_db)?;
+            println!("Using database at: {}", db_path);
+            let db = Database::new(&db_path)?;
+            if args.clear {
+                println!("Clearing existing data...");
+                let (deleted_pastes, deleted_folders) = clear_existing_data(&db)?;
+                assert_folder_invariants(&db)?;
+                println!(
+                    "Cleared {} pastes and {} folders",
+                    deleted_pastes, deleted_folders
+                );
+            }
+            Target::Db(db)
+        }
+    };
 
     let mut rng = rand::thread_rng();
 
-    // Generate folders first
-    println!("Generating {} folders...", args.folders);
-    let mut folder_ids: Vec<String> = Vec::with_capacity(args.folders);
-
-    for i in 0..args.folders {
+    // Generate folders first; parents always precede their children.
+    println!("Generating {} folders...", folder_count);
+    let mut folder_ids: Vec<String> = Vec::with_capacity(folder_count);
+    for (i, parent) in sampler
+        .folder_parents(&mut rng, folder_count)
+        .into_iter()
+        .enumerate()
+    {
         let name = format!("{}_{}", generate_folder_name(&mut rng), i);
-
-        // Some folders can have parents (but not too deep)
-        let parent_id = if !folder_ids.is_empty() && rng.gen_bool(0.3) {
-            // Only pick from first half to avoid deep nesting
-            let max_parent_idx = (folder_ids.len() / 2).max(1);
-            Some(folder_ids[rng.gen_range(0..max_parent_idx)].clone())
-        } else {
-            None
-        };
-
-        let folder = Folder::with_parent(name, parent_id);
-        db.folders.create(&folder)?;
-        folder_ids.push(folder.id);
+        let parent_id = parent.map(|idx| folder_ids[idx].clone());
+        folder_ids.push(target.create_folder(name, parent_id)?);
     }
 
-    println!("Generating {} pastes...", args.count);
+    println!("Generating {} pastes...", count);
     let start = Instant::now();
-
-    // Size distribution: 10% small (<1KB), 70% medium (1-10KB),
-    // 15% large (10-50KB), 5% very large (50-256KB)
-    for i in 0..args.count {
-        // Pick size category based on weights
-        let roll: u32 = rng.gen_range(0..100);
-        let size_category = if roll < 10 {
-            "small"
-        } else if roll < 80 {
-            "medium"
-        } else if roll < 95 {
-            "large"
-        } else {
-            "very_large"
-        };
-
-        let (content, language) = generate_content(&mut rng, size_category);
+    let now = chrono::Utc::now();
+    let mut created = 0usize;
+    let mut rejected: BTreeMap<u16, usize> = BTreeMap::new();
+
+    for i in 0..count {
+        let language = &LANGUAGES[sampler.language_index(&mut rng)];
+        let size = sampler.size_class(&mut rng);
+        let content = generate_content(&mut rng, language, size);
         let name = localpaste_core::naming::generate_name();
 
         let mut paste = Paste::new(content, name);
-        paste.language = Some(language.to_string());
+        paste.language = Some(language.id.to_string());
         paste.language_is_manual = rng.gen_bool(0.3); // 30% have manual language set
 
-        // 70% of pastes go into folders
-        if !folder_ids.is_empty() && rng.gen_bool(0.7) {
+        if !folder_ids.is_empty() && sampler.filed(&mut rng) {
             paste.folder_id = Some(folder_ids.choose(&mut rng).unwrap().clone());
         }
-
-        // Some pastes have tags
-        if rng.gen_bool(0.4) {
-            let num_tags = rng.gen_range(1..=3);
-            let tags = [
-                "todo",
-                "important",
-                "review",
-                "wip",
-                "done",
-                "bug",
-                "feature",
-            ];
-            paste.tags = tags
-                .choose_multiple(&mut rng, num_tags)
-                .map(|s| s.to_string())
-                .collect();
+        paste.tags = sampler.tags(&mut rng);
+        (paste.created_at, paste.updated_at) = sampler.timestamps(&mut rng, now);
+
+        match target.create_paste(&paste)? {
+            ApiOutcome::Created => created += 1,
+            ApiOutcome::Rejected { status, message } => {
+                let seen = rejected.entry(status).or_default();
+                if *seen == 0 {
+                    tracing::warn!("server rejected a paste with HTTP {}: {}", status, message);
+                }
+                *seen += 1;
+            }
         }
 
-        persist_generated_paste(&db, &paste)?;
-
         // Progress reporting
         if (i + 1) % args.progress_interval.get() == 0 {
             let elapsed = start.elapsed().as_secs_f64();
             let rate = (i + 1) as f64 / elapsed;
-            println!(
-                "  Created {}/{} pastes ({:.1}/sec)",
-                i + 1,
-                args.count,
-                rate
-            );
+            println!("  Created {}/{} pastes ({:.1}/sec)", i + 1, count, rate);
         }
     }
 
-    assert_folder_invariants(&db)?;
+    if let Target::Db(db) = &target {
+        assert_folder_invariants(db)?;
+    }
 
     let elapsed = start.elapsed();
-    let rate = args.count as f64 / elapsed.as_secs_f64();
+    let rate = count as f64 / elapsed.as_secs_f64();
 
     println!("\nGeneration complete:");
-    println!("  Folders: {}", args.folders);
-    println!("  Pastes:  {}", args.count);
+    println!("  Folders: {}", folder_count);
+    println!("  Pastes:  {}", created);
+    for (status, rejections) in &rejected {
+        println!("  Rejected (HTTP {}): {}", status, rejections);
+    }
     println!("  Time:    {:.2}s", elapsed.as_secs_f64());
     println!("  Rate:    {:.1} pastes/sec", rate);
-    println!("\nDatabase path: {}", db_path);
 
     Ok(())
 }
@@ -795,6 +837,30 @@ mod tests {
         assert!(yes_without_clear.is_err());
     }
 
+    #[test]
+    fn api_url_excludes_direct_database_flags() {
+        let api = Args::try_parse_from([
+            "generate-test-data",
+            "--api-url",
+            "http://127.0.0.1:38411",
+            "--token",
+            "secret",
+        ])
+        .expect("api target parse");
+        assert_eq!(api.count, None, "count falls back to the spec");
+        assert_eq!(api.token.as_deref(), Some("secret"));
+
+        for flag in ["--db-path=scratch", "--clear", "--allow-default-db"] {
+            let parsed = Args::try_parse_from([
+                "generate-test-data",
+                "--api-url",
+                "http://127.0.0.1:38411",
+                flag,
+            ]);
+            assert!(parsed.is_err(), "{} must conflict with --api-url", flag);
+        }
+    }
+
     #[test]
     fn validate_args_requires_explicit_database_intent() {
         let args = Args::try_parse_from(["generate-test-data", "--count", "1"])
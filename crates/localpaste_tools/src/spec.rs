@@ -0,0 +1,419 @@
+//! TOML spec describing the distributions `generate-test-data` samples from.
+//!
+//! Every section is optional; omitted fields keep the built-in mix the tool
+//! uses without a spec. Weights are relative, so `rust = 3, python = 1`
+//! yields roughly three Rust pastes per Python one.
+//!
+//! ```toml
+//! count = 5000
+//!
+//! [languages]          # ids of the built-in templates; omitted = uniform
+//! rust = 4
+//! python = 2
+//! markdown = 1
+//!
+//! [sizes]              # small <1KB, medium 1-10KB, large 10-50KB, very_large 50-256KB
+//! small = 20
+//! medium = 60
+//! large = 15
+//! very_large = 5
+//!
+//! [folders]
+//! count = 40
+//! max_depth = 4            # 1 keeps every folder at the top level
+//! nest_probability = 0.5   # chance a folder gets a parent
+//! filed_probability = 0.8  # chance a paste goes into a folder
+//!
+//! [timestamps]
+//! spread_months = 12         # created_at spread over the last year; 0 = now
+//! edited_probability = 0.3   # chance updated_at lands after created_at
+//!
+//! [tags]
+//! vocabulary = ["todo", "infra", "snippet"]
+//! probability = 0.6
+//! max_per_paste = 2
+//! ```
+
+use chrono::{DateTime, Duration, Utc};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::prelude::*;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const SECONDS_PER_MONTH: i64 = 30 * 24 * 60 * 60;
+
+/// Distributions for one generation run.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct GeneratorSpec {
+    /// Number of pastes; `--count` overrides it.
+    pub(crate) count: usize,
+    /// Language template id to relative weight; empty means uniform.
+    pub(crate) languages: BTreeMap<String, u32>,
+    pub(crate) sizes: SizeWeights,
+    pub(crate) folders: FolderSpec,
+    pub(crate) timestamps: TimestampSpec,
+    pub(crate) tags: TagSpec,
+}
+
+impl Default for GeneratorSpec {
+    fn default() -> Self {
+        Self {
+            count: 1000,
+            languages: BTreeMap::new(),
+            sizes: SizeWeights::default(),
+            folders: FolderSpec::default(),
+            timestamps: TimestampSpec::default(),
+            tags: TagSpec::default(),
+        }
+    }
+}
+
+/// Relative weights of the four size classes.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct SizeWeights {
+    pub(crate) small: u32,
+    pub(crate) medium: u32,
+    pub(crate) large: u32,
+    pub(crate) very_large: u32,
+}
+
+impl Default for SizeWeights {
+    fn default() -> Self {
+        Self {
+            small: 10,
+            medium: 70,
+            large: 15,
+            very_large: 5,
+        }
+    }
+}
+
+/// Folder count, nesting, and how many pastes are filed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct FolderSpec {
+    /// Number of folders; `--folders` overrides it.
+    pub(crate) count: usize,
+    pub(crate) max_depth: usize,
+    pub(crate) nest_probability: f64,
+    pub(crate) filed_probability: f64,
+}
+
+impl Default for FolderSpec {
+    fn default() -> Self {
+        Self {
+            count: 20,
+            max_depth: 3,
+            nest_probability: 0.3,
+            filed_probability: 0.7,
+        }
+    }
+}
+
+/// Spread of `created_at` and `updated_at` into the past.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct TimestampSpec {
+    pub(crate) spread_months: u32,
+    pub(crate) edited_probability: f64,
+}
+
+impl Default for TimestampSpec {
+    fn default() -> Self {
+        Self {
+            spread_months: 0,
+            edited_probability: 0.3,
+        }
+    }
+}
+
+/// Tag vocabulary and how often pastes are tagged.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct TagSpec {
+    pub(crate) vocabulary: Vec<String>,
+    pub(crate) probability: f64,
+    pub(crate) max_per_paste: usize,
+}
+
+impl Default for TagSpec {
+    fn default() -> Self {
+        Self {
+            vocabulary: [
+                "todo",
+                "important",
+                "review",
+                "wip",
+                "done",
+                "bug",
+                "feature",
+            ]
+            .map(str::to_string)
+            .to_vec(),
+            probability: 0.4,
+            max_per_paste: 3,
+        }
+    }
+}
+
+/// Content size class with its byte range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SizeClass {
+    Small,
+    Medium,
+    Large,
+    VeryLarge,
+}
+
+impl SizeClass {
+    const ALL: [Self; 4] = [Self::Small, Self::Medium, Self::Large, Self::VeryLarge];
+
+    /// Target content length range in bytes.
+    pub(crate) fn byte_range(self) -> std::ops::Range<usize> {
+        match self {
+            Self::Small => 100..1024,
+            Self::Medium => 1024..10 * 1024,
+            Self::Large => 10 * 1024..50 * 1024,
+            Self::VeryLarge => 50 * 1024..256 * 1024,
+        }
+    }
+}
+
+/// Read and parse a spec file.
+///
+/// # Errors
+/// Returns a message naming the file when it cannot be read or parsed.
+pub(crate) fn load_spec(path: &Path) -> Result<GeneratorSpec, String> {
+    let raw = std::fs::read_to_string(path)
+        .map_err(|err| format!("failed to read spec '{}': {}", path.display(), err))?;
+    toml::from_str(&raw).map_err(|err| format!("invalid spec '{}': {}", path.display(), err))
+}
+
+fn check_probability(name: &str, value: f64) -> Result<(), String> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(())
+    } else {
+        Err(format!("{} must be between 0 and 1, got {}", name, value))
+    }
+}
+
+/// Validated spec with its weighted distributions prepared.
+pub(crate) struct Sampler {
+    spec: GeneratorSpec,
+    /// Template indexes matching `language_dist` weights.
+    languages: Vec<usize>,
+    language_dist: WeightedIndex<u32>,
+    size_dist: WeightedIndex<u32>,
+}
+
+impl Sampler {
+    /// Validate `spec` against the available language template ids.
+    ///
+    /// # Errors
+    /// Returns a message for unknown languages, all-zero weights,
+    /// probabilities outside `[0, 1]`, or a `max_depth` of zero.
+    pub(crate) fn new(spec: GeneratorSpec, language_ids: &[&str]) -> Result<Self, String> {
+        let (languages, language_weights): (Vec<usize>, Vec<u32>) = if spec.languages.is_empty() {
+            (0..language_ids.len()).map(|idx| (idx, 1)).unzip()
+        } else {
+            spec.languages
+                .iter()
+                .map(|(id, weight)| {
+                    language_ids
+                        .iter()
+                        .position(|known| known == id)
+                        .map(|idx| (idx, *weight))
+                        .ok_or_else(|| {
+                            format!(
+                                "unknown language '{}' (expected one of: {})",
+                                id,
+                                language_ids.join(", ")
+                            )
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .unzip()
+        };
+        let language_dist = WeightedIndex::new(&language_weights)
+            .map_err(|_| "[languages] needs at least one non-zero weight".to_string())?;
+        let sizes = &spec.sizes;
+        let size_dist =
+            WeightedIndex::new([sizes.small, sizes.medium, sizes.large, sizes.very_large])
+                .map_err(|_| "[sizes] needs at least one non-zero weight".to_string())?;
+        if spec.folders.max_depth == 0 {
+            return Err("folders.max_depth must be at least 1".to_string());
+        }
+        check_probability("folders.nest_probability", spec.folders.nest_probability)?;
+        check_probability("folders.filed_probability", spec.folders.filed_probability)?;
+        check_probability(
+            "timestamps.edited_probability",
+            spec.timestamps.edited_probability,
+        )?;
+        check_probability("tags.probability", spec.tags.probability)?;
+        Ok(Self {
+            spec,
+            languages,
+            language_dist,
+            size_dist,
+        })
+    }
+
+    /// Index into the language templates passed to [`Sampler::new`].
+    pub(crate) fn language_index(&self, rng: &mut impl Rng) -> usize {
+        self.languages[self.language_dist.sample(rng)]
+    }
+
+    pub(crate) fn size_class(&self, rng: &mut impl Rng) -> SizeClass {
+        SizeClass::ALL[self.size_dist.sample(rng)]
+    }
+
+    /// Whether a paste goes into a folder.
+    pub(crate) fn filed(&self, rng: &mut impl Rng) -> bool {
+        rng.gen_bool(self.spec.folders.filed_probability)
+    }
+
+    /// Distinct tags drawn from the vocabulary; often empty.
+    pub(crate) fn tags(&self, rng: &mut impl Rng) -> Vec<String> {
+        let tags = &self.spec.tags;
+        if tags.vocabulary.is_empty() || tags.max_per_paste == 0 || !rng.gen_bool(tags.probability)
+        {
+            return Vec::new();
+        }
+        let count = rng.gen_range(1..=tags.max_per_paste.min(tags.vocabulary.len()));
+        tags.vocabulary
+            .choose_multiple(rng, count)
+            .cloned()
+            .collect()
+    }
+
+    /// `(created_at, updated_at)` no later than `now`.
+    pub(crate) fn timestamps(
+        &self,
+        rng: &mut impl Rng,
+        now: DateTime<Utc>,
+    ) -> (DateTime<Utc>, DateTime<Utc>) {
+        let spread = i64::from(self.spec.timestamps.spread_months) * SECONDS_PER_MONTH;
+        if spread == 0 {
+            return (now, now);
+        }
+        let created = now - Duration::seconds(rng.gen_range(0..spread));
+        if !rng.gen_bool(self.spec.timestamps.edited_probability) {
+            return (created, created);
+        }
+        let age = (now - created).num_seconds();
+        (created, created + Duration::seconds(rng.gen_range(0..=age)))
+    }
+
+    /// Parent index for each of `count` folders, created in order.
+    ///
+    /// A parent always precedes its children and no folder is deeper than
+    /// `max_depth` (top-level folders have depth 1).
+    pub(crate) fn folder_parents(&self, rng: &mut impl Rng, count: usize) -> Vec<Option<usize>> {
+        let folders = &self.spec.folders;
+        let mut depths: Vec<usize> = Vec::with_capacity(count);
+        let mut parents = Vec::with_capacity(count);
+        for _ in 0..count {
+            let candidates: Vec<usize> = (0..depths.len())
+                .filter(|&idx| depths[idx] < folders.max_depth)
+                .collect();
+            let parent = if !candidates.is_empty() && rng.gen_bool(folders.nest_probability) {
+                candidates.choose(rng).copied()
+            } else {
+                None
+            };
+            depths.push(parent.map_or(1, |idx| depths[idx] + 1));
+            parents.push(parent);
+        }
+        parents
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDS: &[&str] = &["rust", "python", "markdown"];
+
+    #[test]
+    fn spec_sections_shape_every_sample() {
+        let spec: GeneratorSpec = toml::from_str(
+            r#"
+            count = 50
+
+            [languages]
+            python = 1
+
+            [sizes]
+            small = 0
+            medium = 0
+            large = 1
+            very_large = 0
+
+            [folders]
+            max_depth = 2
+            nest_probability = 1.0
+
+            [timestamps]
+            spread_months = 6
+            edited_probability = 1.0
+
+            [tags]
+            vocabulary = ["infra", "snippet"]
+            probability = 1.0
+            max_per_paste = 5
+            "#,
+        )
+        .expect("parse spec");
+        assert_eq!(spec.count, 50);
+        assert_eq!(spec.folders.count, 20, "omitted fields keep defaults");
+
+        let sampler = Sampler::new(spec, IDS).expect("valid spec");
+        let mut rng = StdRng::seed_from_u64(7);
+        let now = Utc::now();
+        for _ in 0..200 {
+            assert_eq!(sampler.language_index(&mut rng), 1);
+            assert_eq!(sampler.size_class(&mut rng), SizeClass::Large);
+            let tags = sampler.tags(&mut rng);
+            assert!((1..=2).contains(&tags.len()));
+            assert!(tags.iter().all(|tag| tag == "infra" || tag == "snippet"));
+            let (created, updated) = sampler.timestamps(&mut rng, now);
+            assert!(created <= updated && updated <= now);
+            assert!(now - created <= Duration::seconds(6 * SECONDS_PER_MONTH));
+        }
+
+        let parents = sampler.folder_parents(&mut rng, 30);
+        assert_eq!(parents[0], None);
+        for (idx, parent) in parents.iter().enumerate() {
+            if let Some(parent) = parent {
+                assert!(*parent < idx);
+                assert_eq!(parents[*parent], None, "max_depth 2 allows one level");
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_specs_are_rejected_with_the_field_name() {
+        let sampler = |raw: &str| {
+            let spec: GeneratorSpec = toml::from_str(raw).expect("parse spec");
+            Sampler::new(spec, IDS).err().unwrap_or_default()
+        };
+        assert!(sampler("[languages]\ncobol = 1").contains("unknown language 'cobol'"));
+        assert!(sampler("[languages]\nrust = 0").contains("[languages]"));
+        assert!(
+            sampler("[sizes]\nsmall = 0\nmedium = 0\nlarge = 0\nvery_large = 0")
+                .contains("[sizes]")
+        );
+        assert!(sampler("[tags]\nprobability = 1.5").contains("tags.probability"));
+        assert!(sampler("[folders]\nmax_depth = 0").contains("max_depth"));
+        assert!(toml::from_str::<GeneratorSpec>("[folders]\ndepth = 2").is_err());
+
+        let default = Sampler::new(GeneratorSpec::default(), IDS).expect("defaults are valid");
+        let mut rng = StdRng::seed_from_u64(1);
+        let now = Utc::now();
+        assert_eq!(default.timestamps(&mut rng, now), (now, now));
+    }
+}